@@ -0,0 +1,136 @@
+use criterion::{BenchmarkId, Criterion};
+use pricelevel::{
+    Hash32, Id, OrderType, OrderUpdate, Price, PriceLevel, PriceLevelConfig, Quantity, Side,
+    TakerKind, TimeInForce, TimestampMs, UuidGenerator,
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Orders resting at the level before a measurement starts.
+const DEPTH: u64 = 200_000;
+
+/// Threads admitting and cancelling their own orders alongside the matcher.
+const CHURN_THREADS: u64 = 4;
+
+/// Register benchmarks comparing an unsharded queue with sharded ones on a
+/// very deep level under concurrent admission, cancellation and matching.
+pub fn register_deep_level_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PriceLevel - Deep Level Sharding");
+    group.sample_size(10);
+
+    for shards in [1usize, 4, 16].iter() {
+        // One deep level per configuration: the workload keeps its depth
+        // steady, so rebuilding it for every sample would only measure the
+        // pre-fill.
+        let price_level = Arc::new(deep_level(*shards));
+        let next_id = AtomicU64::new(DEPTH);
+        group.bench_with_input(
+            BenchmarkId::new("churn_and_match", shards),
+            shards,
+            |b, _| {
+                b.iter_custom(|iters| measure_churn_and_match(&price_level, &next_id, iters));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn deep_level(shards: usize) -> PriceLevel {
+    let price_level =
+        PriceLevel::with_config(10000, PriceLevelConfig::new().with_queue_shards(shards));
+    for i in 0..DEPTH {
+        price_level
+            .add_order(create_standard_order(i, 10000, 10))
+            .expect("add_order should succeed");
+    }
+    price_level
+}
+
+/// Runs `iterations` add-and-cancel rounds on each churn thread while one
+/// matcher takes one lot and rests a replacement `iterations` times, and
+/// returns the wall time until every thread is done.
+///
+/// The level outlives the measurement, so every order id is drawn from a
+/// block of `next_id` no earlier measurement used.
+fn measure_churn_and_match(
+    price_level: &Arc<PriceLevel>,
+    next_id: &AtomicU64,
+    iterations: u64,
+) -> Duration {
+    let ids_per_thread = 2 * iterations;
+    let first_id = next_id.fetch_add((CHURN_THREADS + 1) * ids_per_thread, Ordering::Relaxed);
+    let barrier = Arc::new(Barrier::new(CHURN_THREADS as usize + 2));
+    let mut handles = Vec::with_capacity(CHURN_THREADS as usize + 1);
+
+    for thread_id in 0..CHURN_THREADS {
+        let thread_price_level = Arc::clone(price_level);
+        let thread_barrier = Arc::clone(&barrier);
+        handles.push(thread::spawn(move || {
+            let base = first_id + thread_id * ids_per_thread;
+            thread_barrier.wait();
+            for i in 0..iterations {
+                let order_id = base + i;
+                thread_price_level
+                    .add_order(create_standard_order(order_id, 10000, 10))
+                    .expect("add_order should succeed");
+                let _ = thread_price_level.update_order(OrderUpdate::Cancel {
+                    order_id: Id::from_u64(order_id),
+                });
+            }
+            thread_barrier.wait();
+        }));
+    }
+
+    {
+        let thread_price_level = Arc::clone(price_level);
+        let thread_barrier = Arc::clone(&barrier);
+        handles.push(thread::spawn(move || {
+            let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+            let transaction_id_gen = UuidGenerator::new(namespace);
+            let base = first_id + CHURN_THREADS * ids_per_thread;
+            thread_barrier.wait();
+            for i in 0..iterations {
+                thread_price_level.match_order(
+                    1,
+                    Id::from_u64(base + 2 * i),
+                    TimeInForce::Ioc,
+                    TakerKind::Standard,
+                    TimestampMs::new(1_716_000_000_000),
+                    &transaction_id_gen,
+                );
+                thread_price_level
+                    .add_order(create_standard_order(base + 2 * i + 1, 10000, 1))
+                    .expect("add_order should succeed");
+            }
+            thread_barrier.wait();
+        }));
+    }
+
+    barrier.wait();
+    let start = Instant::now();
+    barrier.wait();
+    let duration = start.elapsed();
+
+    for handle in handles {
+        let _ = handle.join();
+    }
+
+    duration
+}
+
+fn create_standard_order(id: u64, price: u128, quantity: u64) -> OrderType<()> {
+    OrderType::Standard {
+        id: Id::from_u64(id),
+        price: Price::new(price),
+        quantity: Quantity::new(quantity),
+        side: Side::Buy,
+        user_id: Hash32::zero(),
+        timestamp: TimestampMs::new(1616823000000),
+        time_in_force: TimeInForce::Gtc,
+        extra_fields: (),
+    }
+}
@@ -1,14 +1,20 @@
 use criterion::criterion_group;
 
 mod contention;
+mod deep_level;
 mod register;
+mod snapshot_interference;
 
 pub use contention::register_contention_benchmarks;
+pub use deep_level::register_deep_level_benchmarks;
 pub use register::register_benchmarks;
+pub use snapshot_interference::register_snapshot_interference_benchmarks;
 
 // Import and re-export our main concurrent benchmarks
 criterion_group!(
     concurrent_benches,
     register_benchmarks,
-    register_contention_benchmarks
+    register_contention_benchmarks,
+    register_deep_level_benchmarks,
+    register_snapshot_interference_benchmarks
 );
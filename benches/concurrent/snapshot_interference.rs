@@ -0,0 +1,265 @@
+use criterion::{BenchmarkId, Criterion};
+use pricelevel::{
+    Hash32, Id, OrderType, OrderUpdate, Price, PriceLevel, Quantity, Side, TakerKind, TimeInForce,
+    TimestampMs, UuidGenerator,
+};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Adder / canceller threads churning the level in every scenario. Matching
+/// stays on a single thread: `match_order` requires one logical matcher per
+/// level.
+const CHURN_THREADS: usize = 2;
+
+/// Each thread draws its order ids from its own `1 << 40`-wide range, so a
+/// long measurement never re-uses an id another thread still has resting.
+const ID_RANGE: u64 = 1 << 40;
+
+/// Which snapshot a reader takes.
+#[derive(Clone, Copy)]
+enum SnapshotKind {
+    /// `snapshot`, under the shared side of the fill-or-kill guard.
+    Guarded,
+    /// `snapshot_consistent`, validated against the mutation sequence.
+    Consistent,
+}
+
+impl SnapshotKind {
+    fn take(self, price_level: &PriceLevel) {
+        match self {
+            Self::Guarded => {
+                black_box(price_level.snapshot());
+            }
+            Self::Consistent => {
+                black_box(price_level.snapshot_consistent());
+            }
+        }
+    }
+}
+
+/// Register benchmarks quantifying what a snapshot costs while the level is
+/// being matched, and how much a continuous snapshot reader slows matching.
+pub fn register_snapshot_interference_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PriceLevel - Snapshot Under Matching");
+
+    for depth in [100u64, 1_000, 10_000].iter() {
+        // Snapshot latency on a quiescent level vs. one under active churn.
+        for (name, kind) in [
+            ("snapshot", SnapshotKind::Guarded),
+            ("snapshot_consistent", SnapshotKind::Consistent),
+        ] {
+            for churn in [false, true] {
+                let label = if churn { "under_churn" } else { "quiescent" };
+                group.bench_with_input(
+                    BenchmarkId::new(format!("{name}_{label}"), depth),
+                    depth,
+                    |b, &depth| {
+                        b.iter_custom(|iters| measure_snapshot_cost(depth, iters, churn, kind));
+                    },
+                );
+            }
+        }
+
+        // Matching latency with and without a reader snapshotting in a loop.
+        for (label, reader) in [
+            ("without_snapshot_reader", None),
+            ("with_snapshot_reader", Some(SnapshotKind::Guarded)),
+            ("with_consistent_reader", Some(SnapshotKind::Consistent)),
+        ] {
+            group.bench_with_input(
+                BenchmarkId::new(format!("match_{label}"), depth),
+                depth,
+                |b, &depth| {
+                    b.iter_custom(|iters| measure_match_interference(depth, iters, reader));
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+/// Times `iterations` snapshots of a level holding `depth` orders, optionally
+/// while the churn threads and a matcher mutate it.
+fn measure_snapshot_cost(depth: u64, iterations: u64, churn: bool, kind: SnapshotKind) -> Duration {
+    let price_level = Arc::new(setup_level(depth));
+    let background = if churn {
+        Background::start(&price_level, true, None)
+    } else {
+        Background::idle()
+    };
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        kind.take(&price_level);
+    }
+    let duration = start.elapsed();
+
+    background.stop();
+    duration
+}
+
+/// Times `iterations` matches on a level holding `depth` orders while the
+/// churn threads mutate it, optionally with a reader snapshotting in a loop.
+fn measure_match_interference(
+    depth: u64,
+    iterations: u64,
+    reader: Option<SnapshotKind>,
+) -> Duration {
+    let price_level = Arc::new(setup_level(depth));
+    let background = Background::start(&price_level, false, reader);
+    let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    let transaction_id_generator = UuidGenerator::new(namespace);
+
+    let start = Instant::now();
+    for i in 0..iterations {
+        match_and_replenish(&price_level, 8 * ID_RANGE, i, &transaction_id_generator);
+    }
+    let duration = start.elapsed();
+
+    background.stop();
+    duration
+}
+
+/// Threads mutating (and optionally snapshotting) a level until stopped.
+struct Background {
+    stop: Arc<AtomicBool>,
+    handles: Vec<thread::JoinHandle<()>>,
+}
+
+impl Background {
+    fn idle() -> Self {
+        Self {
+            stop: Arc::new(AtomicBool::new(false)),
+            handles: Vec::new(),
+        }
+    }
+
+    /// Starts the churn threads, plus a matcher and / or a snapshot reader,
+    /// and returns once all of them are running.
+    fn start(price_level: &Arc<PriceLevel>, matcher: bool, reader: Option<SnapshotKind>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_count = CHURN_THREADS + usize::from(matcher) + usize::from(reader.is_some());
+        let barrier = Arc::new(Barrier::new(thread_count + 1)); // +1 for main thread
+        let mut handles = Vec::with_capacity(thread_count);
+
+        for thread_id in 0..CHURN_THREADS {
+            let level = Arc::clone(price_level);
+            let stop = Arc::clone(&stop);
+            let barrier = Arc::clone(&barrier);
+            handles.push(thread::spawn(move || {
+                barrier.wait();
+                let mut i = 0u64;
+                while !stop.load(Ordering::Relaxed) {
+                    // Add a fresh order, then cancel one added two rounds ago,
+                    // keeping the depth roughly constant.
+                    let base_id = (thread_id as u64 + 1) * ID_RANGE + i;
+                    level
+                        .add_order(create_standard_order(base_id, 10000, 10))
+                        .expect("add_order should succeed");
+                    if i >= 2 {
+                        let _ = level.update_order(OrderUpdate::Cancel {
+                            order_id: Id::from_u64(base_id - 2),
+                        });
+                    }
+                    i += 1;
+                }
+            }));
+        }
+
+        if matcher {
+            let level = Arc::clone(price_level);
+            let stop = Arc::clone(&stop);
+            let barrier = Arc::clone(&barrier);
+            handles.push(thread::spawn(move || {
+                let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+                let transaction_id_generator = UuidGenerator::new(namespace);
+                barrier.wait();
+                let mut i = 0u64;
+                while !stop.load(Ordering::Relaxed) {
+                    match_and_replenish(&level, 9 * ID_RANGE, i, &transaction_id_generator);
+                    i += 1;
+                }
+            }));
+        }
+
+        if let Some(kind) = reader {
+            let level = Arc::clone(price_level);
+            let stop = Arc::clone(&stop);
+            let barrier = Arc::clone(&barrier);
+            handles.push(thread::spawn(move || {
+                barrier.wait();
+                while !stop.load(Ordering::Relaxed) {
+                    kind.take(&level);
+                }
+            }));
+        }
+
+        barrier.wait();
+        Self { stop, handles }
+    }
+
+    fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        for handle in self.handles {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Matches one unit and, every tenth match, re-adds the order's worth of
+/// liquidity consumed, so the level keeps its depth however long the
+/// benchmark runs. Both sides of every comparison pay the same re-add.
+fn match_and_replenish(
+    price_level: &PriceLevel,
+    id_base: u64,
+    iteration: u64,
+    transaction_id_generator: &UuidGenerator,
+) {
+    black_box(price_level.match_order(
+        1,
+        Id::from_u64(id_base + iteration),
+        TimeInForce::Gtc,
+        TakerKind::Standard,
+        TimestampMs::new(1_716_000_000_000),
+        transaction_id_generator,
+    ));
+    if iteration % 10 == 9 {
+        price_level
+            .add_order(create_standard_order(
+                id_base + ID_RANGE / 2 + iteration,
+                10000,
+                10,
+            ))
+            .expect("add_order should succeed");
+    }
+}
+
+/// Set up a price level with `depth` standard orders
+fn setup_level(depth: u64) -> PriceLevel {
+    let price_level = PriceLevel::new(10000);
+    for i in 0..depth {
+        price_level
+            .add_order(create_standard_order(i, 10000, 10))
+            .expect("add_order should succeed");
+    }
+    price_level
+}
+
+/// Create a standard limit order for testing
+fn create_standard_order(id: u64, price: u128, quantity: u64) -> OrderType<()> {
+    OrderType::Standard {
+        id: Id::from_u64(id),
+        price: Price::new(price),
+        quantity: Quantity::new(quantity),
+        side: Side::Buy,
+        user_id: Hash32::zero(),
+        timestamp: TimestampMs::new(1616823000000),
+        time_in_force: TimeInForce::Gtc,
+        extra_fields: (),
+    }
+}
@@ -6,6 +6,8 @@ mod simple;
 mod concurrent;
 
 use concurrent::register_benchmarks as register_concurrent_benchmarks;
+use concurrent::register_deep_level_benchmarks;
+use concurrent::register_snapshot_interference_benchmarks;
 use price_level::register_benchmarks as register_price_level_benchmarks;
 use simple::first::benchmark_data;
 
@@ -15,6 +17,8 @@ criterion_group!(
     benchmark_data,
     register_price_level_benchmarks,
     register_concurrent_benchmarks,
+    register_snapshot_interference_benchmarks,
+    register_deep_level_benchmarks,
 );
 
 criterion_main!(benches);
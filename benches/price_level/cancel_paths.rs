@@ -0,0 +1,248 @@
+use criterion::{BenchmarkId, Criterion, Throughput};
+use pricelevel::{
+    Hash32, Id, MatchingPolicy, OrderQueue, OrderType, OrderUpdate, Price, PriceLevel,
+    PriceLevelConfig, Quantity, Side, TimeInForce, TimestampMs,
+};
+use std::cell::{Cell, RefCell};
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Level depths the cancel and update paths are compared at.
+const DEPTHS: [u64; 3] = [100, 10_000, 250_000];
+
+/// Starting quantity of every resting order: large enough that the update
+/// benchmark can shrink each order by one lot per update for as many rounds
+/// as criterion asks for without reaching zero.
+const INITIAL_QUANTITY: u64 = 1_000_000_000;
+
+/// Register benchmarks comparing the per-message cost of cancelling and
+/// resizing a resting order at increasing depths, between the indexed queue
+/// (`update_order` on a FIFO level), its tombstone mode (a strict FIFO
+/// level) and the drain-and-rebuild approach of the original queue, which
+/// popped every order and pushed back all but the target.
+///
+/// Every benchmark reports one element per message, so the throughput of the
+/// three paths is directly comparable across depths and runs.
+pub fn register_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PriceLevel - Cancel Paths");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(1));
+    for depth in DEPTHS {
+        let indexed = CancelRing::new(level(depth, MatchingPolicy::Fifo), depth);
+        group.bench_with_input(BenchmarkId::new("indexed", depth), &depth, |b, _| {
+            b.iter_custom(|iters| indexed.measure(iters));
+        });
+        let tombstone = CancelRing::new(level(depth, MatchingPolicy::StrictFifo), depth);
+        group.bench_with_input(BenchmarkId::new("tombstone", depth), &depth, |b, _| {
+            b.iter_custom(|iters| tombstone.measure(iters));
+        });
+        let rebuild = RebuildRing::new(depth);
+        group.bench_with_input(BenchmarkId::new("drain_rebuild", depth), &depth, |b, _| {
+            b.iter_custom(|iters| rebuild.measure_cancels(iters));
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("PriceLevel - Update Paths");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(1));
+    for depth in DEPTHS {
+        let indexed = UpdateRing::new(level(depth, MatchingPolicy::Fifo), depth);
+        group.bench_with_input(BenchmarkId::new("indexed", depth), &depth, |b, _| {
+            b.iter_custom(|iters| indexed.measure(iters));
+        });
+        let rebuild = RebuildRing::new(depth);
+        group.bench_with_input(BenchmarkId::new("drain_rebuild", depth), &depth, |b, _| {
+            b.iter_custom(|iters| rebuild.measure_updates(iters));
+        });
+    }
+    group.finish();
+}
+
+fn level(depth: u64, policy: MatchingPolicy) -> PriceLevel {
+    let price_level =
+        PriceLevel::with_config(10000, PriceLevelConfig::new().with_matching_policy(policy));
+    for id in 0..depth {
+        price_level
+            .add_order(create_standard_order(id))
+            .expect("add_order should succeed");
+    }
+    price_level
+}
+
+/// A level whose depth stays steady across measurements: each step cancels
+/// the oldest resting order and, outside the timed section, rests a fresh
+/// one at the back. The level outlives the measurement, so `next` carries
+/// the id ring over from one measurement to the next.
+struct CancelRing {
+    price_level: PriceLevel,
+    depth: u64,
+    next: Cell<u64>,
+}
+
+impl CancelRing {
+    fn new(price_level: PriceLevel, depth: u64) -> Self {
+        Self {
+            price_level,
+            depth,
+            next: Cell::new(0),
+        }
+    }
+
+    fn measure(&self, iterations: u64) -> Duration {
+        let mut elapsed = Duration::ZERO;
+        for _ in 0..iterations {
+            let oldest = self.next.get();
+            self.next.set(oldest + 1);
+            let start = Instant::now();
+            let outcome = self.price_level.update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(oldest),
+            });
+            elapsed += start.elapsed();
+            black_box(outcome.expect("cancel should succeed"));
+            self.price_level
+                .add_order(create_standard_order(oldest + self.depth))
+                .expect("add_order should succeed");
+        }
+        elapsed
+    }
+}
+
+/// A level whose orders are resized round-robin, each shrinking by one lot
+/// per update so it keeps its queue position. `updates` counts the updates
+/// made so far, which fixes both the next target and its new quantity.
+struct UpdateRing {
+    price_level: PriceLevel,
+    depth: u64,
+    updates: Cell<u64>,
+}
+
+impl UpdateRing {
+    fn new(price_level: PriceLevel, depth: u64) -> Self {
+        Self {
+            price_level,
+            depth,
+            updates: Cell::new(0),
+        }
+    }
+
+    fn measure(&self, iterations: u64) -> Duration {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let (order_id, new_quantity) = next_update(&self.updates, self.depth);
+            let outcome = self.price_level.update_order(OrderUpdate::UpdateQuantity {
+                order_id,
+                new_quantity: Quantity::new(new_quantity),
+            });
+            black_box(outcome.expect("update should succeed"));
+        }
+        start.elapsed()
+    }
+}
+
+/// The target and new quantity of the next round-robin resize.
+fn next_update(updates: &Cell<u64>, depth: u64) -> (Id, u64) {
+    let made = updates.get();
+    updates.set(made + 1);
+    (
+        Id::from_u64(made % depth),
+        INITIAL_QUANTITY - made / depth - 1,
+    )
+}
+
+/// The drain-and-rebuild baseline: a bare queue where each cancel or update
+/// pops every resting order and pushes them back in the same order, leaving
+/// out or resizing the target on the way. It runs the same id rings as the
+/// indexed benchmarks, so the cost per message is comparable.
+struct RebuildRing {
+    queue: OrderQueue,
+    depth: u64,
+    next: Cell<u64>,
+    updates: Cell<u64>,
+    drained: RefCell<Vec<Arc<OrderType<()>>>>,
+}
+
+impl RebuildRing {
+    fn new(depth: u64) -> Self {
+        let queue = OrderQueue::new();
+        for id in 0..depth {
+            queue
+                .try_push(Arc::new(create_standard_order(id)))
+                .expect("push should succeed");
+        }
+        Self {
+            queue,
+            depth,
+            next: Cell::new(0),
+            updates: Cell::new(0),
+            drained: RefCell::new(Vec::with_capacity(depth as usize)),
+        }
+    }
+
+    fn measure_cancels(&self, iterations: u64) -> Duration {
+        let mut elapsed = Duration::ZERO;
+        for _ in 0..iterations {
+            let oldest = self.next.get();
+            self.next.set(oldest + 1);
+            let target = Id::from_u64(oldest);
+            let start = Instant::now();
+            let removed = self.rebuild(|order| (order.id() != target).then(|| order.clone()));
+            elapsed += start.elapsed();
+            black_box(removed);
+            self.queue
+                .try_push(Arc::new(create_standard_order(oldest + self.depth)))
+                .expect("push should succeed");
+        }
+        elapsed
+    }
+
+    fn measure_updates(&self, iterations: u64) -> Duration {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let (target, new_quantity) = next_update(&self.updates, self.depth);
+            let changed = self.rebuild(|order| {
+                Some(if order.id() == target {
+                    Arc::new(order.with_reduced_quantity(new_quantity))
+                } else {
+                    order.clone()
+                })
+            });
+            black_box(changed);
+        }
+        start.elapsed()
+    }
+
+    /// Drain the queue and push back what `keep` returns for each order, in
+    /// queue order. Returns how many orders `keep` dropped.
+    fn rebuild<F>(&self, keep: F) -> usize
+    where
+        F: Fn(&Arc<OrderType<()>>) -> Option<Arc<OrderType<()>>>,
+    {
+        let mut drained = self.drained.borrow_mut();
+        while let Some(order) = self.queue.pop() {
+            drained.push(order);
+        }
+        let mut dropped = 0;
+        for order in drained.drain(..) {
+            match keep(&order) {
+                Some(order) => self.queue.try_push(order).expect("push should succeed"),
+                None => dropped += 1,
+            }
+        }
+        dropped
+    }
+}
+
+fn create_standard_order(id: u64) -> OrderType<()> {
+    OrderType::Standard {
+        id: Id::from_u64(id),
+        price: Price::new(10000),
+        quantity: Quantity::new(INITIAL_QUANTITY),
+        side: Side::Buy,
+        user_id: Hash32::zero(),
+        timestamp: TimestampMs::new(1616823000000 + id),
+        time_in_force: TimeInForce::Gtc,
+        extra_fields: (),
+    }
+}
@@ -73,7 +73,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
     // Benchmark MatchResult::new + add_trade sequence
     group.bench_function("match_result_add_trades", |b| {
         b.iter(|| {
-            let mut mr = MatchResult::new(Id::from_u64(1), Quantity::new(1000));
+            let mut mr: MatchResult = MatchResult::new(Id::from_u64(1), Quantity::new(1000));
             for i in 0..50_u64 {
                 let trade = Trade::new(
                     Id::from_u64(100 + i),
@@ -91,7 +91,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
 
     // Benchmark empty match result (zero trades) arithmetic
     group.bench_function("empty_match_arithmetic", |b| {
-        let empty_level = PriceLevel::new(10000);
+        let empty_level = PriceLevel::<()>::new(10000);
         let result = empty_level.match_order(
             100,
             Id::from_u64(1),
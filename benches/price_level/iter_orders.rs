@@ -46,6 +46,28 @@ pub fn register_benchmarks(c: &mut Criterion) {
         );
     }
 
+    // Priority-order walk: the non-draining `orders()` iterator against the
+    // collect-and-sort `snapshot_by_insertion_seq` it replaces for read-only
+    // consumers.
+    for order_count in [10, 100, 1000].iter() {
+        group.bench_with_input(
+            BenchmarkId::new("orders_priority_walk", order_count),
+            order_count,
+            |b, &order_count| {
+                let price_level = setup_level(order_count);
+                b.iter(|| black_box(price_level.orders().count()))
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("snapshot_by_insertion_seq", order_count),
+            order_count,
+            |b, &order_count| {
+                let price_level = setup_level(order_count);
+                b.iter(|| black_box(price_level.snapshot_by_insertion_seq().len()))
+            },
+        );
+    }
+
     group.finish();
 }
 
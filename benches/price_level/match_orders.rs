@@ -1,7 +1,7 @@
 use criterion::{BenchmarkId, Criterion};
 use pricelevel::{
-    Hash32, Id, OrderType, Price, PriceLevel, Quantity, Side, TakerKind, TimeInForce, TimestampMs,
-    UuidGenerator,
+    Hash32, Id, MatchingPolicy, OrderType, Price, PriceLevel, Quantity, Side, TakerKind,
+    TimeInForce, TimestampMs, UuidGenerator,
 };
 use std::hint::black_box;
 use std::num::NonZeroU64;
@@ -29,6 +29,25 @@ pub fn register_benchmarks(c: &mut Criterion) {
         })
     });
 
+    // Same workload under strict FIFO: the sweep additionally takes the
+    // level's exclusive guard and validates every pop's sequence.
+    group.bench_function("match_standard_orders_strict_fifo", |b| {
+        b.iter(|| {
+            let price_level = setup_standard_orders(100);
+            price_level.set_matching_policy(MatchingPolicy::StrictFifo);
+            let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+            let transaction_id_generator = UuidGenerator::new(namespace);
+            black_box(price_level.match_order(
+                50,
+                Id::from_u64(999),
+                TimeInForce::Gtc,
+                TakerKind::Standard,
+                TimestampMs::new(1_716_000_000_000),
+                &transaction_id_generator,
+            ));
+        })
+    });
+
     // Benchmark matching against iceberg orders
     group.bench_function("match_iceberg_orders", |b| {
         b.iter(|| {
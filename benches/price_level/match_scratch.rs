@@ -0,0 +1,87 @@
+use criterion::{BatchSize, Criterion};
+use pricelevel::{
+    Hash32, Id, MatchingPolicy, OrderType, Price, PriceLevel, Quantity, Side, TakerKind,
+    TimeInForce, TimestampMs, UuidGenerator,
+};
+use std::hint::black_box;
+use uuid::Uuid;
+
+const MAKERS: u64 = 64;
+
+/// Register benchmarks for the level-local match scratch buffers
+pub fn register_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PriceLevel - Match Scratch");
+
+    // A warm level: the scratch buffers were sized by earlier matches.
+    group.bench_function("pro_rata_warm_level", |b| {
+        let price_level = setup_level(MatchingPolicy::ProRata);
+        let generator = generator();
+        b.iter(|| black_box(match_once(&price_level, TimeInForce::Gtc, &generator)))
+    });
+
+    // A cold level: every match is the first one, so the buffers are grown
+    // from empty.
+    group.bench_function("pro_rata_cold_level", |b| {
+        let generator = generator();
+        b.iter_batched(
+            || setup_level(MatchingPolicy::ProRata),
+            |price_level| black_box(match_once(&price_level, TimeInForce::Gtc, &generator)),
+            BatchSize::SmallInput,
+        )
+    });
+
+    // Fill-or-kill runs the `matchable_quantity` dry run before the sweep.
+    group.bench_function("fok_warm_level", |b| {
+        let price_level = setup_level(MatchingPolicy::Fifo);
+        let generator = generator();
+        b.iter(|| black_box(match_once(&price_level, TimeInForce::Fok, &generator)))
+    });
+
+    group.finish();
+}
+
+/// Matches a small taker that touches every maker without exhausting any, so
+/// the level can be matched against repeatedly.
+fn match_once(
+    price_level: &PriceLevel,
+    time_in_force: TimeInForce,
+    generator: &UuidGenerator,
+) -> usize {
+    price_level
+        .match_order(
+            MAKERS,
+            Id::from_u64(999_999),
+            time_in_force,
+            TakerKind::Standard,
+            TimestampMs::new(1_716_000_000_000),
+            generator,
+        )
+        .trades()
+        .len()
+}
+
+fn generator() -> UuidGenerator {
+    let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    UuidGenerator::new(namespace)
+}
+
+/// Set up a level with deep makers, so repeated matches never drain it
+fn setup_level(policy: MatchingPolicy) -> PriceLevel {
+    let price_level = PriceLevel::with_matching_policy(10000, policy);
+    for i in 0..MAKERS {
+        let order = OrderType::Standard {
+            id: Id::from_u64(i),
+            price: Price::new(10000),
+            quantity: Quantity::new(1_000_000),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + i),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        };
+        price_level
+            .add_order(order)
+            .expect("add_order should succeed");
+    }
+    price_level
+}
@@ -1,14 +1,20 @@
 // benches/price_level/mod.rs
 pub mod add_orders;
+pub mod cancel_paths;
 pub mod checked_arithmetic;
 pub mod iter_orders;
 pub mod lifecycle;
 pub mod match_orders;
+pub mod match_scratch;
 pub mod mixed_operations;
 pub mod newtypes;
+#[cfg(feature = "pool")]
+pub mod order_pool;
 pub mod serialization;
+pub mod snapshot_checksum;
 pub mod snapshot_recovery;
 pub mod special_orders;
+pub mod trade_list;
 pub mod update_orders;
 
 // Import common benchmarks into the main bench group
@@ -17,11 +23,17 @@ pub fn register_benchmarks(c: &mut criterion::Criterion) {
     iter_orders::register_benchmarks(c);
     match_orders::register_benchmarks(c);
     update_orders::register_benchmarks(c);
+    cancel_paths::register_benchmarks(c);
     mixed_operations::register_benchmarks(c);
     snapshot_recovery::register_benchmarks(c);
+    snapshot_checksum::register_benchmarks(c);
     checked_arithmetic::register_benchmarks(c);
     serialization::register_benchmarks(c);
     newtypes::register_benchmarks(c);
     special_orders::register_benchmarks(c);
     lifecycle::register_benchmarks(c);
+    match_scratch::register_benchmarks(c);
+    trade_list::register_benchmarks(c);
+    #[cfg(feature = "pool")]
+    order_pool::register_benchmarks(c);
 }
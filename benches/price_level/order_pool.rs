@@ -0,0 +1,73 @@
+use criterion::Criterion;
+use pricelevel::{
+    Hash32, Id, OrderPool, OrderType, Price, PriceLevel, Quantity, Side, TakerKind, TimeInForce,
+    TimestampMs, UuidGenerator,
+};
+use std::hint::black_box;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const ORDERS: u64 = 64;
+
+/// Register benchmarks for the order allocation pool
+pub fn register_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PriceLevel - Order Pool");
+
+    // Each cycle rests a batch of makers and sweeps them all, the churn of a
+    // hot level: every admission and residual allocates without a pool.
+    group.bench_function("churn_unpooled", |b| {
+        let price_level = PriceLevel::new(10000);
+        let generator = generator();
+        b.iter(|| black_box(churn(&price_level, &generator)))
+    });
+
+    group.bench_function("churn_pooled", |b| {
+        let price_level = PriceLevel::with_order_pool(10000, Arc::new(OrderPool::default()));
+        let generator = generator();
+        b.iter(|| black_box(churn(&price_level, &generator)))
+    });
+
+    group.finish();
+}
+
+/// Rests `ORDERS` makers, partially fills each one, then sweeps the rest,
+/// recycling the match results into the level's pool when it has one.
+fn churn(price_level: &PriceLevel, generator: &UuidGenerator) -> usize {
+    for i in 0..ORDERS {
+        let order = OrderType::Standard {
+            id: Id::from_u64(i),
+            price: Price::new(10000),
+            quantity: Quantity::new(10),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + i),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        };
+        price_level
+            .add_order(order)
+            .expect("add_order should succeed");
+    }
+
+    let mut trades = 0;
+    for quantity in [ORDERS * 5, ORDERS * 5] {
+        let result = price_level.match_order(
+            quantity,
+            Id::from_u64(999_999),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1_716_000_000_000),
+            generator,
+        );
+        trades += result.trades().len();
+        if let Some(pool) = price_level.order_pool() {
+            pool.recycle(result);
+        }
+    }
+    trades
+}
+
+fn generator() -> UuidGenerator {
+    let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    UuidGenerator::new(namespace)
+}
@@ -108,7 +108,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
     let mr_str = match_result.to_string();
     group.bench_function("match_result_from_str", |b| {
         b.iter(|| {
-            black_box(MatchResult::from_str(&mr_str).unwrap());
+            black_box(MatchResult::<()>::from_str(&mr_str).unwrap());
         })
     });
 
@@ -0,0 +1,65 @@
+use super::snapshot_recovery::setup_mixed_level;
+use criterion::{BenchmarkId, Criterion, Throughput};
+use pricelevel::{ChecksumAlgorithm, PriceLevelSnapshotPackage};
+use std::hint::black_box;
+
+const ALGORITHMS: [ChecksumAlgorithm; 3] = [
+    ChecksumAlgorithm::Crc32c,
+    ChecksumAlgorithm::XxHash64,
+    ChecksumAlgorithm::Sha256,
+];
+
+/// Register benchmarks comparing package creation and validation cost across
+/// snapshot checksum algorithms, up to large levels.
+pub fn register_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PriceLevel - Snapshot Checksum");
+
+    for order_count in [200u64, 5_000] {
+        let price_level = setup_mixed_level(order_count);
+
+        for algorithm in ALGORITHMS {
+            let package = price_level.snapshot_package_with(algorithm).unwrap();
+            let json = package.to_json().unwrap();
+            group.throughput(Throughput::Bytes(json.len() as u64));
+
+            // Checksum verification only (payload re-encode + digest)
+            group.bench_with_input(
+                BenchmarkId::new(format!("validate_{algorithm}"), order_count),
+                &package,
+                |b, package| {
+                    b.iter(|| {
+                        package.validate().unwrap();
+                        black_box(());
+                    })
+                },
+            );
+
+            // Restore path: decode JSON, then validate
+            group.bench_with_input(
+                BenchmarkId::new(format!("from_json_validate_{algorithm}"), order_count),
+                &json,
+                |b, json| {
+                    b.iter(|| {
+                        let pkg: PriceLevelSnapshotPackage =
+                            PriceLevelSnapshotPackage::from_json(json).unwrap();
+                        pkg.validate().unwrap();
+                        black_box(pkg);
+                    })
+                },
+            );
+
+            // Package creation (snapshot + digest)
+            group.bench_with_input(
+                BenchmarkId::new(format!("package_creation_{algorithm}"), order_count),
+                &algorithm,
+                |b, &algorithm| {
+                    b.iter(|| {
+                        black_box(price_level.snapshot_package_with(algorithm).unwrap());
+                    })
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
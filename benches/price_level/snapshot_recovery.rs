@@ -6,7 +6,8 @@ use pricelevel::{
 use std::hint::black_box;
 use std::num::NonZeroU64;
 
-/// Register benchmarks for snapshot checksum creation, JSON roundtrip, and recovery.
+/// Register benchmarks for snapshot checksum creation, JSON roundtrip, recovery
+/// and forking.
 pub fn register_benchmarks(c: &mut Criterion) {
     let mut group = c.benchmark_group("PriceLevel - Snapshot Recovery");
 
@@ -31,7 +32,8 @@ pub fn register_benchmarks(c: &mut Criterion) {
         let price_level = setup_mixed_level(200);
         let json = price_level.snapshot_to_json().unwrap();
         b.iter(|| {
-            let pkg = PriceLevelSnapshotPackage::from_json(&json).unwrap();
+            let pkg: PriceLevelSnapshotPackage =
+                PriceLevelSnapshotPackage::from_json(&json).unwrap();
             pkg.validate().unwrap();
             black_box(pkg);
         })
@@ -42,7 +44,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
         let price_level = setup_mixed_level(200);
         let json = price_level.snapshot_to_json().unwrap();
         b.iter(|| {
-            let restored = PriceLevel::from_snapshot_json(&json).unwrap();
+            let restored = PriceLevel::<()>::from_snapshot_json(&json).unwrap();
             black_box(restored);
         })
     });
@@ -52,7 +54,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
         let price_level = setup_mixed_level(200);
         let pkg = price_level.snapshot_package().unwrap();
         let json = pkg.to_json().unwrap();
-        let deserialized = PriceLevelSnapshotPackage::from_json(&json).unwrap();
+        let deserialized = PriceLevelSnapshotPackage::<()>::from_json(&json).unwrap();
         b.iter(|| {
             deserialized.validate().unwrap();
             black_box(());
@@ -64,7 +66,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
         let price_level = setup_mixed_level(200);
         let json = price_level.snapshot_to_json().unwrap();
         b.iter(|| {
-            let pkg = PriceLevelSnapshotPackage::from_json(&json).unwrap();
+            let pkg = PriceLevelSnapshotPackage::<()>::from_json(&json).unwrap();
             let restored = PriceLevel::from_snapshot_package(pkg).unwrap();
             black_box(restored);
         })
@@ -80,11 +82,36 @@ pub fn register_benchmarks(c: &mut Criterion) {
         });
     }
 
+    // Fork against an in-memory snapshot and restore at the same depth. A
+    // fork shares the level's published snapshot and copies the queue only
+    // on its first write, without the checksum or order clones.
+    for order_count in [50, 200, 500].iter() {
+        group.bench_function(format!("fork_{order_count}_orders"), |b| {
+            let price_level = setup_mixed_level(*order_count);
+            b.iter(|| {
+                black_box(price_level.fork());
+            })
+        });
+        group.bench_function(format!("fork_first_write_{order_count}_orders"), |b| {
+            let price_level = setup_mixed_level(*order_count);
+            b.iter(|| {
+                black_box(price_level.fork().into_level());
+            })
+        });
+        group.bench_function(format!("snapshot_restore_{order_count}_orders"), |b| {
+            let price_level = setup_mixed_level(*order_count);
+            b.iter(|| {
+                let pkg = price_level.snapshot_package().unwrap();
+                black_box(PriceLevel::from_snapshot_package(pkg).unwrap());
+            })
+        });
+    }
+
     group.finish();
 }
 
 /// Set up a price level with a mix of standard, iceberg, and reserve orders.
-fn setup_mixed_level(order_count: u64) -> PriceLevel {
+pub(crate) fn setup_mixed_level(order_count: u64) -> PriceLevel {
     let price_level = PriceLevel::new(10000);
     for i in 0..order_count {
         let order = match i % 3 {
@@ -0,0 +1,100 @@
+use criterion::{BenchmarkId, Criterion};
+use pricelevel::{
+    Hash32, Id, OrderType, Price, PriceLevel, Quantity, Side, TRADE_LIST_INLINE_CAPACITY,
+    TakerKind, TimeInForce, TimestampMs, Trade, TradeList, UuidGenerator,
+};
+use std::hint::black_box;
+use uuid::Uuid;
+
+/// Register benchmarks for the inline storage of small trade lists
+pub fn register_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PriceLevel - Trade List");
+
+    // Collecting a match's trades, against the heap vector the list used to
+    // wrap.
+    for count in 1..=TRADE_LIST_INLINE_CAPACITY + 1 {
+        let trade = sample_trade();
+        group.bench_with_input(BenchmarkId::new("trade_list", count), &count, |b, &n| {
+            b.iter(|| {
+                let mut list = TradeList::with_capacity(n);
+                for _ in 0..n {
+                    list.add(trade);
+                }
+                black_box(&list);
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("vec", count), &count, |b, &n| {
+            b.iter(|| {
+                let mut trades = Vec::with_capacity(n);
+                for _ in 0..n {
+                    trades.push(trade);
+                }
+                black_box(&trades);
+            })
+        });
+    }
+
+    // The hot path: a taker filling one to three makers, inline and spilled.
+    for makers in 1..=SMALL_MATCH_MAKERS {
+        group.bench_with_input(
+            BenchmarkId::new("small_match", makers),
+            &makers,
+            |b, &makers| {
+                let price_level = PriceLevel::new(10000);
+                let generator = generator();
+                b.iter(|| black_box(small_match(&price_level, makers, &generator)))
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Makers swept by the largest `small_match` case.
+const SMALL_MATCH_MAKERS: u64 = 3;
+
+/// Rests `makers` one-lot makers and sweeps them with a single taker.
+fn small_match(price_level: &PriceLevel, makers: u64, generator: &UuidGenerator) -> usize {
+    for i in 0..makers {
+        let order = OrderType::Standard {
+            id: Id::from_u64(i),
+            price: Price::new(10000),
+            quantity: Quantity::new(1),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + i),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        };
+        price_level
+            .add_order(order)
+            .expect("add_order should succeed");
+    }
+
+    let result = price_level.match_order(
+        makers,
+        Id::from_u64(999_999),
+        TimeInForce::Ioc,
+        TakerKind::Standard,
+        TimestampMs::new(1_716_000_000_000),
+        generator,
+    );
+    result.trades().len()
+}
+
+fn sample_trade() -> Trade {
+    Trade::with_timestamp(
+        Id::from_u64(1),
+        Id::from_u64(2),
+        Id::from_u64(3),
+        Price::new(10000),
+        Quantity::new(5),
+        Side::Buy,
+        TimestampMs::new(1616823000000),
+    )
+}
+
+fn generator() -> UuidGenerator {
+    let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    UuidGenerator::new(namespace)
+}
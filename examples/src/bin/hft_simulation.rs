@@ -2,7 +2,7 @@
 
 use pricelevel::{
     Hash32, Id, OrderType, OrderUpdate, PegReferenceType, Price, PriceLevel, Quantity, Side,
-    TakerKind, TimeInForce, TimestampMs, UuidGenerator, setup_logger,
+    TakerKind, TimeInForce, TimestampMs, UpdateOutcome, UuidGenerator, setup_logger,
 };
 use std::num::NonZeroU64;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -218,7 +218,7 @@ fn main() {
                 let result = thread_price_level.update_order(OrderUpdate::Cancel { order_id });
 
                 // Count successful cancellations
-                if result.is_ok() && result.unwrap().is_some() {
+                if matches!(result, Ok(UpdateOutcome::Cancelled(..))) {
                     local_counter += 1;
                 }
 
@@ -5,7 +5,7 @@
 
 use pricelevel::{
     Hash32, Id, MatchResult, OrderType, OrderUpdate, Price, PriceLevel, Quantity, Side, TakerKind,
-    TimeInForce, TimestampMs, UuidGenerator,
+    TimeInForce, TimestampMs, UpdateOutcome, UuidGenerator,
 };
 use std::process;
 use uuid::Uuid;
@@ -61,11 +61,7 @@ fn main() {
     });
     assert_or_exit(update_result.is_ok(), "update_order should succeed");
     assert_or_exit(
-        update_result
-            .as_ref()
-            .ok()
-            .and_then(|o| o.as_ref())
-            .is_some(),
+        matches!(update_result, Ok(UpdateOutcome::UpdatedInPlace(_))),
         "updated order should be returned",
     );
     // Visible went from 1000 → 950 (order 1: 100 → 50)
@@ -88,11 +84,7 @@ fn main() {
     });
     assert_or_exit(cancel_result.is_ok(), "cancel should succeed");
     assert_or_exit(
-        cancel_result
-            .as_ref()
-            .ok()
-            .and_then(|o| o.as_ref())
-            .is_some(),
+        matches!(cancel_result, Ok(UpdateOutcome::Cancelled(..))),
         "cancelled order should be returned",
     );
     assert_eq_or_exit(price_level.order_count(), 9, "order_count after cancel");
@@ -118,10 +110,10 @@ fn main() {
         "cancel non-existent should not error",
     );
     assert_or_exit(
-        cancel_missing.ok().and_then(|o| o).is_none(),
-        "cancel non-existent should return None",
+        matches!(cancel_missing, Ok(UpdateOutcome::NotFound)),
+        "cancel non-existent should return NotFound",
     );
-    println!("  ✓ Non-existent order cancel returns None.");
+    println!("  ✓ Non-existent order cancel returns NotFound.");
 
     // --- Phase 5: Match orders ---
     println!("[Phase 5] Matching orders...");
@@ -146,7 +138,7 @@ fn main() {
     );
     assert_or_exit(match_result.is_complete(), "match should be complete");
     assert_or_exit(
-        !match_result.trades().as_vec().is_empty(),
+        !match_result.trades().as_slice().is_empty(),
         "should have trades",
     );
 
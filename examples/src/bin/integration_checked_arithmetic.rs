@@ -123,7 +123,7 @@ fn test_executed_quantity_and_value(id_gen: &UuidGenerator) {
     assert_eq_or_exit(result.filled_order_ids().len(), 2, "filled_order_ids count");
 
     // Verify individual trade accessors
-    for trade in result.trades().as_vec() {
+    for trade in result.trades().as_slice() {
         assert_eq_or_exit(trade.price(), Price::new(5_000), "trade price");
         assert_or_exit(trade.quantity().as_u64() > 0, "trade quantity > 0");
         assert_eq_or_exit(trade.taker_order_id(), Id::from_u64(999), "trade taker_id");
@@ -175,7 +175,7 @@ fn test_empty_match_result(id_gen: &UuidGenerator) {
     println!("[empty match] Zero quantity and empty level...");
 
     // Match on empty level
-    let empty_level = PriceLevel::new(10_000);
+    let empty_level: PriceLevel = PriceLevel::new(10_000);
     let result = empty_level.match_order(
         100,
         Id::from_u64(900),
@@ -236,7 +236,7 @@ fn test_error_variants() {
     assert_or_exit(err2.is_err(), "bad field value should fail");
 
     // InvalidOperation via update on same price
-    let level = PriceLevel::new(10_000);
+    let level: PriceLevel = PriceLevel::new(10_000);
     let err3 = level.update_order(pricelevel::OrderUpdate::UpdatePrice {
         order_id: Id::from_u64(1),
         new_price: Price::new(10_000), // same price
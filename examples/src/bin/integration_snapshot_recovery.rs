@@ -86,9 +86,10 @@ fn main() {
         .snapshot_package()
         .unwrap_or_else(|e| exit_err(&format!("snapshot_package: {e}")));
 
-    // Snapshot format v3 (statistics persisted since v2/#63; v3 owns the
-    // optional `stats_degraded` field, issue #129).
-    assert_eq_or_exit(package.version(), 3, "snapshot version");
+    // Snapshot format v4 (statistics persisted since v2/#63; v3 owns the
+    // optional `stats_degraded` field, issue #129; v4 the optional
+    // `mutation_sequence`).
+    assert_eq_or_exit(package.version(), 4, "snapshot version");
     assert_or_exit(
         !package.checksum().is_empty(),
         "checksum should not be empty",
@@ -169,7 +170,7 @@ fn main() {
     let json2 = original
         .snapshot_to_json()
         .unwrap_or_else(|e| exit_err(&format!("snapshot_to_json: {e}")));
-    let restored2 = PriceLevel::from_snapshot_json(&json2)
+    let restored2: PriceLevel = PriceLevel::from_snapshot_json(&json2)
         .unwrap_or_else(|e| exit_err(&format!("from_snapshot_json: {e}")));
     assert_eq_or_exit(
         restored2.order_count(),
@@ -191,7 +192,7 @@ fn main() {
     let tampered_json = serde_json::to_string(&tampered)
         .unwrap_or_else(|e| exit_err(&format!("tampered JSON serialize: {e}")));
 
-    let tampered_pkg = PriceLevelSnapshotPackage::from_json(&tampered_json)
+    let tampered_pkg = PriceLevelSnapshotPackage::<()>::from_json(&tampered_json)
         .unwrap_or_else(|e| exit_err(&format!("tampered from_json: {e}")));
 
     let validate_err = tampered_pkg.validate();
@@ -211,7 +212,7 @@ fn main() {
     }
 
     // Also verify from_snapshot_package rejects corrupted data
-    let tampered_pkg2 = PriceLevelSnapshotPackage::from_json(&tampered_json)
+    let tampered_pkg2 = PriceLevelSnapshotPackage::<()>::from_json(&tampered_json)
         .unwrap_or_else(|e| exit_err(&format!("tampered from_json 2: {e}")));
     let restore_err = PriceLevel::from_snapshot_package(tampered_pkg2);
     assert_or_exit(
@@ -107,7 +107,7 @@ fn main() {
     assert_eq_or_exit(tl_parsed.len(), 2, "TradeList roundtrip len");
 
     // Verify first trade preserved
-    let first = &tl_parsed.as_vec()[0];
+    let first = &tl_parsed.as_slice()[0];
     assert_eq_or_exit(
         first.price(),
         Price::new(9500),
@@ -166,7 +166,7 @@ fn main() {
         mr_str.contains("MatchResult:"),
         "MatchResult display prefix",
     );
-    let mr_parsed = MatchResult::from_str(&mr_str)
+    let mr_parsed: MatchResult = MatchResult::from_str(&mr_str)
         .unwrap_or_else(|e| exit_err(&format!("MatchResult::from_str: {e}")));
     assert_eq_or_exit(
         mr_parsed.order_id(),
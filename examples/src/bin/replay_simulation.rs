@@ -0,0 +1,62 @@
+// examples/src/bin/replay_simulation.rs
+
+use pricelevel::setup_logger;
+use pricelevel::sim::{OperationMix, SizeDistribution, Workload};
+use tracing::info;
+
+// Simulation parameters
+const SEED: u64 = 42;
+const PRICE: u128 = 10000;
+const INITIAL_ORDERS: usize = 1000;
+const OPERATIONS: usize = 1_000_000;
+
+fn main() {
+    setup_logger().expect("Failed to initialize logger");
+    info!("Deterministic Replay Simulation");
+    info!("===============================");
+
+    let workload = Workload::new(SEED)
+        .with_price(PRICE)
+        .with_initial_orders(INITIAL_ORDERS)
+        .with_operations(OPERATIONS)
+        .with_mix(OperationMix::new(5, 3, 2))
+        .with_maker_sizes(SizeDistribution::Uniform { min: 1, max: 100 })
+        .with_taker_sizes(SizeDistribution::Geometric { mean: 80 })
+        .with_iceberg_percent(20);
+
+    info!("Seed: {}", SEED);
+    info!("Price: {}", PRICE);
+    info!(
+        "Operations: {} after {} initial orders",
+        OPERATIONS, INITIAL_ORDERS
+    );
+
+    let report = workload.run().expect("final-state invariants should hold");
+
+    info!("\nThroughput:");
+    info!("  Operations: {}", report.operations);
+    info!("  Elapsed: {:?}", report.elapsed);
+    info!("  Rate: {:.2} operations per second", report.throughput());
+
+    info!("\nOperations:");
+    info!(
+        "  Orders added: {} ({} rejected)",
+        report.orders_added, report.rejected
+    );
+    info!(
+        "  Matches: {} ({} trades, {} makers filled)",
+        report.matches, report.trades, report.orders_filled
+    );
+    info!(
+        "  Cancels: {} ({} already filled)",
+        report.cancels, report.cancels_missed
+    );
+
+    info!("\nFinal state:");
+    info!("  Quantity added: {}", report.quantity_added);
+    info!("  Quantity executed: {}", report.executed_quantity);
+    info!("  Quantity cancelled: {}", report.quantity_cancelled);
+    info!("  Visible quantity: {}", report.visible_quantity);
+    info!("  Hidden quantity: {}", report.hidden_quantity);
+    info!("  Order count: {}", report.order_count);
+}
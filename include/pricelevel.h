@@ -0,0 +1,107 @@
+/*
+ * C ABI of the pricelevel crate, built with `--features ffi`.
+ *
+ * Mirrors `src/ffi/mod.rs`; see its documentation for the full contract.
+ * Every fallible call returns PL_OK, a negative PL_ERR_* code for a failure
+ * at the boundary, or the positive stable code of the PriceLevelError the
+ * level raised (for example 2001 for a duplicate order id).
+ */
+
+#ifndef PRICELEVEL_H
+#define PRICELEVEL_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define PL_OK 0
+#define PL_ERR_NULL_POINTER (-1)
+#define PL_ERR_INVALID_ARGUMENT (-2)
+#define PL_ERR_BUFFER_TOO_SMALL (-3)
+#define PL_ERR_PANIC (-4)
+
+#define PL_SIDE_BUY 0
+#define PL_SIDE_SELL 1
+
+#define PL_TIF_GTC 0
+#define PL_TIF_IOC 1
+#define PL_TIF_FOK 2
+#define PL_TIF_GTD 3
+#define PL_TIF_DAY 4
+#define PL_TIF_GTT 5
+
+#define PL_TAKER_STANDARD 0
+#define PL_TAKER_POST_ONLY 1
+#define PL_TAKER_MARKET_TO_LIMIT 2
+
+#define PL_OUTCOME_FILLED 0
+#define PL_OUTCOME_PARTIALLY_FILLED 1
+#define PL_OUTCOME_NOT_FILLED 2
+#define PL_OUTCOME_KILLED 3
+#define PL_OUTCOME_REJECTED 4
+
+/* Opaque level handle. */
+typedef struct PlPriceLevel PlPriceLevel;
+
+/* A resting order; a non-zero hidden_quantity makes it an iceberg. */
+typedef struct PlOrder {
+    uint64_t id;
+    uint64_t price;
+    uint64_t visible_quantity;
+    uint64_t hidden_quantity;
+    uint8_t side;          /* PL_SIDE_* */
+    uint8_t time_in_force; /* PL_TIF_* */
+    uint64_t expiry_ms;    /* read only for PL_TIF_GTD and PL_TIF_GTT */
+    uint64_t timestamp_ms;
+    uint8_t user_id[32];
+} PlOrder;
+
+/* An incoming order to match. */
+typedef struct PlTaker {
+    uint64_t id;
+    uint64_t quantity;
+    uint8_t time_in_force; /* PL_TIF_* */
+    uint8_t kind;          /* PL_TAKER_* */
+    uint64_t expiry_ms;    /* read only for PL_TIF_GTD and PL_TIF_GTT */
+    uint64_t timestamp_ms;
+} PlTaker;
+
+/* Summary of a match. */
+typedef struct PlMatchResult {
+    uint64_t executed_quantity;
+    uint64_t remaining_quantity;
+    uint64_t trade_count;
+    uint8_t outcome; /* PL_OUTCOME_* */
+} PlMatchResult;
+
+/* Never returns NULL; release with pl_level_free. */
+PlPriceLevel *pl_level_new(uint64_t price, uint64_t execution_epoch);
+
+/* Ignores NULL. */
+void pl_level_free(PlPriceLevel *level);
+
+int32_t pl_level_add_order(const PlPriceLevel *level, const PlOrder *order);
+
+int32_t pl_level_cancel_order(const PlPriceLevel *level, uint64_t order_id);
+
+/* `result` is written only when PL_OK is returned. */
+int32_t pl_level_match(const PlPriceLevel *level, const PlTaker *taker,
+                       PlMatchResult *result);
+
+/*
+ * Copies the snapshot package JSON and a NUL into `buffer`, and its length
+ * without the NUL into `length`. Returns PL_ERR_BUFFER_TOO_SMALL, with
+ * `length` set, if `capacity` is not at least `length + 1`; pass a NULL
+ * buffer and zero capacity to query the length.
+ */
+int32_t pl_level_snapshot_json(const PlPriceLevel *level, uint8_t *buffer,
+                               size_t capacity, size_t *length);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PRICELEVEL_H */
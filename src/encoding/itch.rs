@@ -0,0 +1,241 @@
+//! ITCH-style binary messages for price level mutations.
+//!
+//! Each [`ItchMessage`] encodes to a fixed-length record modelled on the
+//! Nasdaq TotalView-ITCH order messages, with the field widths this crate
+//! needs. Every integer is big-endian (network order):
+//!
+//! | Message          | Type | Layout after the type byte                                         | Bytes |
+//! |------------------|------|--------------------------------------------------------------------|-------|
+//! | `AddOrder`       | `A`  | timestamp `u64`, order id `[u8; 16]`, side `u8`, shares `u64`, price `u128` | 50 |
+//! | `OrderExecuted`  | `E`  | timestamp `u64`, order id `[u8; 16]`, shares `u64`, match id `[u8; 16]` | 49 |
+//! | `OrderCancel`    | `X`  | timestamp `u64`, order id `[u8; 16]`, cancelled shares `u64`       | 33    |
+//! | `OrderReplace`   | `U`  | timestamp `u64`, original id `[u8; 16]`, new id `[u8; 16]`, shares `u64`, price `u128` | 65 |
+//!
+//! Timestamps are Unix milliseconds. Ids are [`Id::as_bytes`]. The side is
+//! `B` or `S`. Shares are displayed quantity: hidden depth is never encoded.
+
+use crate::execution::{MatchResult, Trade};
+use crate::orders::{Id, OrderType, Side, UpdateOutcome};
+use crate::utils::{Price, Quantity, TimestampMs};
+
+/// Encoded length of [`ItchMessage::AddOrder`].
+pub const ADD_ORDER_LEN: usize = 50;
+/// Encoded length of [`ItchMessage::OrderExecuted`].
+pub const ORDER_EXECUTED_LEN: usize = 49;
+/// Encoded length of [`ItchMessage::OrderCancel`].
+pub const ORDER_CANCEL_LEN: usize = 33;
+/// Encoded length of [`ItchMessage::OrderReplace`].
+pub const ORDER_REPLACE_LEN: usize = 65;
+
+/// One ITCH-style message. See the [module docs](self) for the byte layouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItchMessage {
+    /// An order now displayed at `price`.
+    AddOrder {
+        /// When the order was admitted.
+        timestamp: TimestampMs,
+        /// The order.
+        order_id: Id,
+        /// The order's side.
+        side: Side,
+        /// Displayed quantity.
+        shares: Quantity,
+        /// The order's price.
+        price: Price,
+    },
+    /// A resting order traded.
+    OrderExecuted {
+        /// When the trade executed.
+        timestamp: TimestampMs,
+        /// The maker order.
+        order_id: Id,
+        /// Quantity executed.
+        executed_shares: Quantity,
+        /// The trade id.
+        match_id: Id,
+    },
+    /// Displayed quantity was removed from an order; the whole of it when the
+    /// order left the level.
+    OrderCancel {
+        /// When the order was cancelled.
+        timestamp: TimestampMs,
+        /// The order.
+        order_id: Id,
+        /// Quantity removed.
+        cancelled_shares: Quantity,
+    },
+    /// An order was resized or repriced; the new values replace the old.
+    OrderReplace {
+        /// When the order was replaced.
+        timestamp: TimestampMs,
+        /// The order before the replace.
+        original_order_id: Id,
+        /// The order after the replace.
+        new_order_id: Id,
+        /// Displayed quantity after the replace.
+        shares: Quantity,
+        /// Price after the replace.
+        price: Price,
+    },
+}
+
+impl ItchMessage {
+    /// The message for an order admitted at `timestamp`.
+    #[must_use]
+    pub fn add_order<T: Clone>(order: &OrderType<T>, timestamp: TimestampMs) -> Self {
+        Self::AddOrder {
+            timestamp,
+            order_id: order.id(),
+            side: order.side(),
+            shares: order.visible_quantity(),
+            price: order.price(),
+        }
+    }
+
+    /// The message for one trade against a resting order.
+    #[must_use]
+    pub fn order_executed<T>(trade: &Trade<T>) -> Self {
+        Self::OrderExecuted {
+            timestamp: trade.timestamp(),
+            order_id: trade.maker_order_id(),
+            executed_shares: trade.quantity(),
+            match_id: trade.trade_id(),
+        }
+    }
+
+    /// One [`ItchMessage::OrderExecuted`] per trade of `result`, in
+    /// execution order.
+    #[must_use]
+    pub fn from_match_result<T>(result: &MatchResult<T>) -> Vec<Self> {
+        result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(Self::order_executed)
+            .collect()
+    }
+
+    /// The message for an [`UpdateOutcome`] applied at `timestamp`, or `None`
+    /// for [`UpdateOutcome::NotFound`].
+    ///
+    /// A resize in place is an [`ItchMessage::OrderReplace`] keeping the
+    /// order id and price. A cancel, or a removal for a reprice, is an
+    /// [`ItchMessage::OrderCancel`] of the order's displayed quantity; the
+    /// order's admission at its new price is a fresh
+    /// [`ItchMessage::AddOrder`].
+    #[must_use]
+    pub fn from_update<T: Clone>(
+        outcome: &UpdateOutcome<T>,
+        timestamp: TimestampMs,
+    ) -> Option<Self> {
+        match outcome {
+            UpdateOutcome::UpdatedInPlace(order) => Some(Self::OrderReplace {
+                timestamp,
+                original_order_id: order.id(),
+                new_order_id: order.id(),
+                shares: order.visible_quantity(),
+                price: order.price(),
+            }),
+            UpdateOutcome::RemovedForReprice(order) | UpdateOutcome::Cancelled(order, _) => {
+                Some(Self::OrderCancel {
+                    timestamp,
+                    order_id: order.id(),
+                    cancelled_shares: order.visible_quantity(),
+                })
+            }
+            UpdateOutcome::NotFound => None,
+        }
+    }
+
+    /// The message type byte.
+    #[must_use]
+    pub fn message_type(&self) -> u8 {
+        match self {
+            Self::AddOrder { .. } => b'A',
+            Self::OrderExecuted { .. } => b'E',
+            Self::OrderCancel { .. } => b'X',
+            Self::OrderReplace { .. } => b'U',
+        }
+    }
+
+    /// The encoded length in bytes.
+    #[must_use]
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            Self::AddOrder { .. } => ADD_ORDER_LEN,
+            Self::OrderExecuted { .. } => ORDER_EXECUTED_LEN,
+            Self::OrderCancel { .. } => ORDER_CANCEL_LEN,
+            Self::OrderReplace { .. } => ORDER_REPLACE_LEN,
+        }
+    }
+
+    /// Appends the encoded message to `buf`, so a feed can batch messages in
+    /// one reused buffer.
+    pub fn encode_into(&self, buf: &mut Vec<u8>) {
+        buf.reserve(self.encoded_len());
+        buf.push(self.message_type());
+        match *self {
+            Self::AddOrder {
+                timestamp,
+                order_id,
+                side,
+                shares,
+                price,
+            } => {
+                buf.extend_from_slice(&timestamp.as_u64().to_be_bytes());
+                buf.extend_from_slice(&order_id.as_bytes());
+                buf.push(side_byte(side));
+                buf.extend_from_slice(&shares.as_u64().to_be_bytes());
+                buf.extend_from_slice(&price.as_u128().to_be_bytes());
+            }
+            Self::OrderExecuted {
+                timestamp,
+                order_id,
+                executed_shares,
+                match_id,
+            } => {
+                buf.extend_from_slice(&timestamp.as_u64().to_be_bytes());
+                buf.extend_from_slice(&order_id.as_bytes());
+                buf.extend_from_slice(&executed_shares.as_u64().to_be_bytes());
+                buf.extend_from_slice(&match_id.as_bytes());
+            }
+            Self::OrderCancel {
+                timestamp,
+                order_id,
+                cancelled_shares,
+            } => {
+                buf.extend_from_slice(&timestamp.as_u64().to_be_bytes());
+                buf.extend_from_slice(&order_id.as_bytes());
+                buf.extend_from_slice(&cancelled_shares.as_u64().to_be_bytes());
+            }
+            Self::OrderReplace {
+                timestamp,
+                original_order_id,
+                new_order_id,
+                shares,
+                price,
+            } => {
+                buf.extend_from_slice(&timestamp.as_u64().to_be_bytes());
+                buf.extend_from_slice(&original_order_id.as_bytes());
+                buf.extend_from_slice(&new_order_id.as_bytes());
+                buf.extend_from_slice(&shares.as_u64().to_be_bytes());
+                buf.extend_from_slice(&price.as_u128().to_be_bytes());
+            }
+        }
+    }
+
+    /// The encoded message.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.encoded_len());
+        self.encode_into(&mut buf);
+        buf
+    }
+}
+
+fn side_byte(side: Side) -> u8 {
+    match side {
+        Side::Buy => b'B',
+        Side::Sell => b'S',
+    }
+}
@@ -0,0 +1,11 @@
+//! Binary encodings of price level activity for downstream feeds.
+//!
+//! # Modules
+//!
+//! - [`itch`] — compact, fixed-layout ITCH-style messages (`AddOrder`,
+//!   `OrderExecuted`, `OrderCancel`, `OrderReplace`) built from level
+//!   mutations and match results.
+
+pub mod itch;
+
+mod tests;
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use crate::encoding::itch::{
+        ADD_ORDER_LEN, ItchMessage, ORDER_CANCEL_LEN, ORDER_EXECUTED_LEN, ORDER_REPLACE_LEN,
+    };
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderStatus, OrderType, Side, TimeInForce, UpdateOutcome};
+    use crate::price_level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+
+    fn standard(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::sequential(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    /// The 16 id bytes of `Id::sequential(id)`.
+    fn id_bytes(id: u64) -> Vec<u8> {
+        let mut bytes = vec![0; 8];
+        bytes.extend_from_slice(&id.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_add_order_layout() {
+        let message = ItchMessage::add_order(&standard(7, 25), TimestampMs::new(0x0102));
+        let bytes = message.to_bytes();
+
+        let mut expected = vec![b'A'];
+        expected.extend_from_slice(&0x0102u64.to_be_bytes());
+        expected.extend_from_slice(&id_bytes(7));
+        expected.push(b'S');
+        expected.extend_from_slice(&25u64.to_be_bytes());
+        expected.extend_from_slice(&10000u128.to_be_bytes());
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes.len(), ADD_ORDER_LEN);
+        assert_eq!(message.encoded_len(), ADD_ORDER_LEN);
+    }
+
+    #[test]
+    fn test_add_order_encodes_displayed_quantity_only() {
+        let iceberg: OrderType<()> = OrderType::IcebergOrder {
+            id: Id::sequential(1),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(5),
+            hidden_quantity: Quantity::new(95),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        };
+        let bytes = ItchMessage::add_order(&iceberg, TimestampMs::new(1)).to_bytes();
+        assert_eq!(bytes[25], b'B');
+        assert_eq!(&bytes[26..34], &5u64.to_be_bytes());
+    }
+
+    #[test]
+    fn test_order_executed_layout_from_match() {
+        let level = PriceLevel::new(10000);
+        level.add_order(standard(7, 25)).unwrap();
+        let result = level.match_order(
+            10,
+            Id::sequential(100),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(0x0304),
+            &SequentialIdGenerator::new(0),
+        );
+
+        let messages = ItchMessage::from_match_result(&result);
+        assert_eq!(messages.len(), 1);
+        let trade_id = result.trades().as_slice()[0].trade_id();
+        let bytes = messages[0].to_bytes();
+
+        let mut expected = vec![b'E'];
+        expected.extend_from_slice(&0x0304u64.to_be_bytes());
+        expected.extend_from_slice(&id_bytes(7));
+        expected.extend_from_slice(&10u64.to_be_bytes());
+        expected.extend_from_slice(&trade_id.as_bytes());
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes.len(), ORDER_EXECUTED_LEN);
+    }
+
+    #[test]
+    fn test_cancel_layout_from_update() {
+        let order = Arc::new(standard(7, 25));
+        let outcome = UpdateOutcome::Cancelled(order, OrderStatus::New);
+        let bytes = ItchMessage::from_update(&outcome, TimestampMs::new(9))
+            .unwrap()
+            .to_bytes();
+
+        let mut expected = vec![b'X'];
+        expected.extend_from_slice(&9u64.to_be_bytes());
+        expected.extend_from_slice(&id_bytes(7));
+        expected.extend_from_slice(&25u64.to_be_bytes());
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes.len(), ORDER_CANCEL_LEN);
+    }
+
+    #[test]
+    fn test_replace_layout_from_resize() {
+        let order = Arc::new(standard(7, 12));
+        let outcome = UpdateOutcome::UpdatedInPlace(order);
+        let bytes = ItchMessage::from_update(&outcome, TimestampMs::new(9))
+            .unwrap()
+            .to_bytes();
+
+        let mut expected = vec![b'U'];
+        expected.extend_from_slice(&9u64.to_be_bytes());
+        expected.extend_from_slice(&id_bytes(7));
+        expected.extend_from_slice(&id_bytes(7));
+        expected.extend_from_slice(&12u64.to_be_bytes());
+        expected.extend_from_slice(&10000u128.to_be_bytes());
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes.len(), ORDER_REPLACE_LEN);
+    }
+
+    #[test]
+    fn test_not_found_update_has_no_message() {
+        let outcome = UpdateOutcome::<()>::NotFound;
+        assert!(ItchMessage::from_update(&outcome, TimestampMs::new(9)).is_none());
+    }
+
+    #[test]
+    fn test_encode_into_appends_messages_back_to_back() {
+        let add = ItchMessage::add_order(&standard(1, 10), TimestampMs::new(1));
+        let cancel = ItchMessage::OrderCancel {
+            timestamp: TimestampMs::new(2),
+            order_id: Id::sequential(1),
+            cancelled_shares: Quantity::new(10),
+        };
+
+        let mut buf = Vec::new();
+        add.encode_into(&mut buf);
+        cancel.encode_into(&mut buf);
+        assert_eq!(buf.len(), ADD_ORDER_LEN + ORDER_CANCEL_LEN);
+        assert_eq!(&buf[..ADD_ORDER_LEN], add.to_bytes().as_slice());
+        assert_eq!(&buf[ADD_ORDER_LEN..], cancel.to_bytes().as_slice());
+    }
+}
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests {
-    use crate::errors::PriceLevelError;
+    use crate::errors::{CapacityLimit, PriceLevelError, PriceLevelErrorKind};
+    use crate::orders::{Id, Side};
     use std::error::Error;
 
     #[test]
@@ -38,6 +39,86 @@ mod tests {
         assert_eq!(error.to_string(), "Invalid value for field quantity: abc");
     }
 
+    #[test]
+    fn test_invalid_crossing_display() {
+        let error = PriceLevelError::InvalidCrossing {
+            maker_side: Side::Buy,
+            taker_side: Side::Buy,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Invalid crossing: BUY taker cannot match BUY makers"
+        );
+    }
+
+    #[test]
+    fn test_invalid_tick_and_lot_display() {
+        let error = PriceLevelError::InvalidTick {
+            price: 10_003,
+            tick_size: 5,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Invalid tick: price 10003 is not a multiple of tick size 5"
+        );
+        let error = PriceLevelError::InvalidLot {
+            quantity: 15,
+            lot_size: 10,
+            min_qty: 10,
+            max_qty: 100,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Invalid lot: quantity 15 is not a multiple of 10 between 10 and 100"
+        );
+    }
+
+    #[test]
+    fn test_capacity_exceeded_display() {
+        let error = PriceLevelError::CapacityExceeded {
+            limit: CapacityLimit::ParticipantOrders,
+            max: 3,
+        };
+        assert_eq!(error.code(), 2008);
+        assert_eq!(
+            error.to_string(),
+            "Capacity exceeded: orders per participant limit of 3 reached"
+        );
+    }
+
+    #[test]
+    fn test_rate_limited_display() {
+        let error = PriceLevelError::RateLimited {
+            order_id: Id::from_u64(7),
+        };
+        assert_eq!(error.code(), 2009);
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Rate limited: message for order {} refused",
+                Id::from_u64(7)
+            )
+        );
+    }
+
+    #[test]
+    fn test_trading_halted_display() {
+        let error = PriceLevelError::TradingHalted { price: 10_000 };
+        assert_eq!(error.code(), 2010);
+        assert_eq!(error.to_string(), "Trading halted: level 10000 is frozen");
+    }
+
+    #[test]
+    fn test_price_outside_band_display() {
+        let error = PriceLevelError::PriceOutsideBand {
+            price: 10_600,
+            lower: 9_500,
+            upper: 10_500,
+        };
+        assert_eq!(error.code(), 2011);
+        assert_eq!(error.to_string(), "Price 10600 outside band [9500, 10500]");
+    }
+
     #[test]
     fn test_invalid_operation_display() {
         let error = PriceLevelError::InvalidOperation {
@@ -60,7 +141,9 @@ mod tests {
             PriceLevelError::InvalidFormat,
             PriceLevelError::UnknownOrderType("TestOrder".to_string()),
             PriceLevelError::MissingField("id".to_string()),
-            PriceLevelError::DuplicateOrderId("42".to_string()),
+            PriceLevelError::DuplicateOrderId {
+                order_id: Id::from_u64(42),
+            },
             PriceLevelError::InvalidFieldValue {
                 field: "side".to_string(),
                 value: "MIDDLE".to_string(),
@@ -136,4 +219,157 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_structured_variants_display() {
+        let error = PriceLevelError::DuplicateOrderId {
+            order_id: Id::from_u64(7),
+        };
+        assert_eq!(
+            error.to_string(),
+            format!("Duplicate order id: {}", Id::from_u64(7))
+        );
+
+        let error = PriceLevelError::PriceMismatch {
+            expected: 10_000,
+            actual: 10_001,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Price mismatch: order price 10001 does not match level price 10000"
+        );
+
+        let error = PriceLevelError::SideMismatch {
+            expected: Side::Buy,
+            actual: Side::Sell,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Side mismatch: order side SELL is incompatible with the level's resting side BUY"
+        );
+    }
+
+    #[test]
+    fn test_error_codes_are_stable_and_unique() {
+        let errors = [
+            PriceLevelError::ParseError {
+                message: String::new(),
+            },
+            PriceLevelError::InvalidFormat,
+            PriceLevelError::UnknownOrderType(String::new()),
+            PriceLevelError::MissingField(String::new()),
+            PriceLevelError::InvalidFieldValue {
+                field: String::new(),
+                value: String::new(),
+            },
+            PriceLevelError::InvalidOperation {
+                message: String::new(),
+            },
+            PriceLevelError::DuplicateOrderId {
+                order_id: Id::from_u64(1),
+            },
+            PriceLevelError::PriceMismatch {
+                expected: 1,
+                actual: 2,
+            },
+            PriceLevelError::SideMismatch {
+                expected: Side::Buy,
+                actual: Side::Sell,
+            },
+            PriceLevelError::OrderNotFound {
+                order_id: Id::from_u64(1),
+            },
+            PriceLevelError::InvalidCrossing {
+                maker_side: Side::Buy,
+                taker_side: Side::Buy,
+            },
+            PriceLevelError::InvalidTick {
+                price: 1,
+                tick_size: 2,
+            },
+            PriceLevelError::InvalidLot {
+                quantity: 1,
+                lot_size: 2,
+                min_qty: 0,
+                max_qty: 0,
+            },
+            PriceLevelError::CapacityExceeded {
+                limit: CapacityLimit::Orders,
+                max: 0,
+            },
+            PriceLevelError::RateLimited {
+                order_id: Id::from_u64(1),
+            },
+            PriceLevelError::TradingHalted { price: 1 },
+            PriceLevelError::PriceOutsideBand {
+                price: 1,
+                lower: 2,
+                upper: 3,
+            },
+            PriceLevelError::SerializationError {
+                message: String::new(),
+                source: None,
+            },
+            PriceLevelError::DeserializationError {
+                message: String::new(),
+                source: None,
+            },
+            PriceLevelError::ChecksumMismatch {
+                expected: String::new(),
+                actual: String::new(),
+            },
+        ];
+        let codes: Vec<u16> = errors.iter().map(PriceLevelError::code).collect();
+        assert_eq!(
+            codes,
+            [
+                1001, 1002, 1003, 1004, 1005, 2000, 2001, 2002, 2003, 2004, 2005, 2006, 2007, 2008,
+                2009, 2010, 2011, 3001, 3002, 3003
+            ]
+        );
+
+        use PriceLevelErrorKind::*;
+        let kinds: Vec<PriceLevelErrorKind> = errors.iter().map(PriceLevelError::kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                InvalidInput,
+                InvalidInput,
+                InvalidInput,
+                InvalidInput,
+                InvalidInput,
+                InvalidOperation,
+                DuplicateOrder,
+                InvalidOrder,
+                InvalidOrder,
+                OrderNotFound,
+                InvalidOrder,
+                InvalidOrder,
+                InvalidOrder,
+                Rejected,
+                Rejected,
+                Rejected,
+                Rejected,
+                Persistence,
+                Persistence,
+                Persistence,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error_source_chains_to_decoder_error() {
+        let decode = serde_json::from_str::<u64>("not json").unwrap_err();
+        let expected = decode.to_string();
+        let error = PriceLevelError::deserialization(decode);
+
+        assert_eq!(error.code(), 3002);
+        assert_eq!(
+            error.to_string(),
+            format!("Deserialization error: {expected}")
+        );
+        let source = error.source().expect("decoder error is kept as the source");
+        assert_eq!(source.to_string(), expected);
+        assert!(source.downcast_ref::<serde_json::Error>().is_some());
+    }
 }
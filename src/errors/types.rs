@@ -1,4 +1,62 @@
-use std::fmt::{Debug, Display, Formatter, Result};
+use crate::orders::{Id, Side};
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter, Result};
+
+/// The underlying error a [`PriceLevelError`] was raised from, returned by
+/// [`Error::source`].
+pub type ErrorSource = Arc<dyn Error + Send + Sync + 'static>;
+
+/// The level limit a [`PriceLevelError::CapacityExceeded`] admission would
+/// have broken; see [`LevelLimits`](crate::LevelLimits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CapacityLimit {
+    /// The number of orders resting at the level.
+    Orders,
+    /// The visible plus hidden quantity resting at the level.
+    Quantity,
+    /// The number of orders one participant rests at the level.
+    ParticipantOrders,
+}
+
+impl Display for CapacityLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            CapacityLimit::Orders => write!(f, "order count"),
+            CapacityLimit::Quantity => write!(f, "total quantity"),
+            CapacityLimit::ParticipantOrders => write!(f, "orders per participant"),
+        }
+    }
+}
+
+/// The broad class of a [`PriceLevelError`], returned by
+/// [`PriceLevelError::kind`].
+///
+/// An order book built on this crate maps a level's errors onto its own by
+/// kind rather than by variant, so a variant added in a minor release lands
+/// in the right class without a new match arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PriceLevelErrorKind {
+    /// Input could not be parsed, or a field is missing or invalid.
+    InvalidInput,
+    /// The order does not fit the level: another price or side, off the
+    /// tick or lot grid, or a taker on the makers' side.
+    InvalidOrder,
+    /// The order id already rests at the level.
+    DuplicateOrder,
+    /// The named order does not rest at the level.
+    OrderNotFound,
+    /// The level refused the message as configured: a capacity limit, the
+    /// rate limiter, a halt or the price band.
+    Rejected,
+    /// A snapshot or trade could not be encoded, decoded or verified.
+    Persistence,
+    /// The operation is impossible in the level's state, such as a poisoned
+    /// level or an overflowing counter.
+    InvalidOperation,
+}
 
 /// Represents errors that can occur when processing price levels in trading operations.
 ///
@@ -17,7 +75,42 @@ use std::fmt::{Debug, Display, Formatter, Result};
 ///
 /// // Creating a missing field error
 /// let missing_field_error = PriceLevelError::MissingField("price".to_string());
+/// assert_eq!(missing_field_error.code(), 1004);
 /// ```
+///
+/// # Error codes
+///
+/// Every variant has a stable numeric [`code`](Self::code) for FFI and log
+/// correlation. Codes are grouped by the layer that raises them and are never
+/// reused: a variant's code does not change between releases, and a removed
+/// variant's code is retired.
+///
+/// | Code | Variant |
+/// |------|---------|
+/// | 1001 | `ParseError` |
+/// | 1002 | `InvalidFormat` |
+/// | 1003 | `UnknownOrderType` |
+/// | 1004 | `MissingField` |
+/// | 1005 | `InvalidFieldValue` |
+/// | 2000 | `InvalidOperation` |
+/// | 2001 | `DuplicateOrderId` |
+/// | 2002 | `PriceMismatch` |
+/// | 2003 | `SideMismatch` |
+/// | 2004 | `OrderNotFound` |
+/// | 2005 | `InvalidCrossing` |
+/// | 2006 | `InvalidTick` |
+/// | 2007 | `InvalidLot` |
+/// | 2008 | `CapacityExceeded` |
+/// | 2009 | `RateLimited` |
+/// | 2010 | `TradingHalted` |
+/// | 2011 | `PriceOutsideBand` |
+/// | 3001 | `SerializationError` |
+/// | 3002 | `DeserializationError` |
+/// | 3003 | `ChecksumMismatch` |
+///
+/// The enum is `#[non_exhaustive]`: new variants may be added in a minor
+/// release, so a `match` outside this crate needs a wildcard arm.
+#[non_exhaustive]
 pub enum PriceLevelError {
     /// Error that occurs when parsing fails with a specific message.
     ///
@@ -50,8 +143,36 @@ pub enum PriceLevelError {
     /// Admission (or a duplicate-bearing restore) is rejected atomically rather
     /// than overwriting the live order, which would leave the level's id-keyed
     /// map and its ordered index disagreeing (two sequences for one id) and its
-    /// counters double-counted. The string parameter is the offending id.
-    DuplicateOrderId(String),
+    /// counters double-counted.
+    DuplicateOrderId {
+        /// The offending id
+        order_id: Id,
+    },
+
+    /// Error indicating an order's price is not the price of the level it was
+    /// added to or restored into.
+    PriceMismatch {
+        /// The level's price
+        expected: u128,
+        /// The order's price
+        actual: u128,
+    },
+
+    /// Error indicating an order's side is not the side of the orders already
+    /// resting at the level.
+    SideMismatch {
+        /// The side the level's resting orders are on
+        expected: Side,
+        /// The order's side
+        actual: Side,
+    },
+
+    /// Error indicating an operation names an order that does not rest at the
+    /// level.
+    OrderNotFound {
+        /// The id that was not found
+        order_id: Id,
+    },
 
     /// Error indicating a field has an invalid value.
     ///
@@ -75,12 +196,16 @@ pub enum PriceLevelError {
     SerializationError {
         /// Descriptive message with the serialization failure details
         message: String,
+        /// The encoder's error, if the failure came from one
+        source: Option<ErrorSource>,
     },
 
     /// Error raised when deserialization of external data into internal structures fails.
     DeserializationError {
         /// Descriptive message with the deserialization failure details
         message: String,
+        /// The decoder's error, if the failure came from one
+        source: Option<ErrorSource>,
     },
 
     /// Error raised when a checksum validation fails while restoring a snapshot.
@@ -90,7 +215,151 @@ pub enum PriceLevelError {
         /// The checksum that was computed from the provided payload
         actual: String,
     },
+
+    /// Error indicating a taker and the makers it would match are on the same
+    /// side, which the level's [`CrossingRule`](crate::CrossingRule) rejects.
+    InvalidCrossing {
+        /// The side of the resting makers
+        maker_side: Side,
+        /// The taker side the crossing rule assigns
+        taker_side: Side,
+    },
+
+    /// Error indicating a price is not a multiple of the level's
+    /// [`SymbolSpec`](crate::SymbolSpec) tick size.
+    InvalidTick {
+        /// The off-tick price
+        price: u128,
+        /// The tick size it must be a multiple of
+        tick_size: u128,
+    },
+
+    /// Error indicating a quantity is not a multiple of the level's
+    /// [`SymbolSpec`](crate::SymbolSpec) lot size, or is outside its minimum
+    /// and maximum order quantity.
+    InvalidLot {
+        /// The rejected quantity
+        quantity: u64,
+        /// The lot size it must be a multiple of
+        lot_size: u64,
+        /// The smallest order quantity
+        min_qty: u64,
+        /// The largest order quantity
+        max_qty: u64,
+    },
+
+    /// Error indicating an admission would take a level past one of its
+    /// [`LevelLimits`](crate::LevelLimits).
+    CapacityExceeded {
+        /// The limit the admission would break
+        limit: CapacityLimit,
+        /// The configured maximum
+        max: u64,
+    },
+
+    /// Error indicating a level's
+    /// [`RateLimiter`](crate::RateLimiter) refused a message for the order's
+    /// participant.
+    RateLimited {
+        /// The order the refused message concerned
+        order_id: Id,
+    },
+
+    /// Error indicating a level frozen by
+    /// [`PriceLevel::freeze`](crate::PriceLevel::freeze) refused a match, or
+    /// a message its [`HaltPolicy`](crate::HaltPolicy) does not accept.
+    TradingHalted {
+        /// The frozen level's price
+        price: u128,
+    },
+
+    /// Error indicating a level outside its
+    /// [`PriceBand`](crate::PriceBand) refused a match.
+    PriceOutsideBand {
+        /// The level's price
+        price: u128,
+        /// The lowest price inside the band
+        lower: u128,
+        /// The highest price inside the band
+        upper: u128,
+    },
+}
+
+impl PriceLevelError {
+    /// A serialization error raised from the encoder's `error`, which is kept
+    /// as the [`Error::source`].
+    pub fn serialization<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        PriceLevelError::SerializationError {
+            message: error.to_string(),
+            source: Some(Arc::new(error)),
+        }
+    }
+
+    /// A deserialization error raised from the decoder's `error`, which is
+    /// kept as the [`Error::source`].
+    pub fn deserialization<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        PriceLevelError::DeserializationError {
+            message: error.to_string(),
+            source: Some(Arc::new(error)),
+        }
+    }
+
+    /// The variant's stable numeric code; see the table on
+    /// [`PriceLevelError`].
+    #[must_use]
+    pub const fn code(&self) -> u16 {
+        match self {
+            PriceLevelError::ParseError { .. } => 1001,
+            PriceLevelError::InvalidFormat => 1002,
+            PriceLevelError::UnknownOrderType(_) => 1003,
+            PriceLevelError::MissingField(_) => 1004,
+            PriceLevelError::InvalidFieldValue { .. } => 1005,
+            PriceLevelError::InvalidOperation { .. } => 2000,
+            PriceLevelError::DuplicateOrderId { .. } => 2001,
+            PriceLevelError::PriceMismatch { .. } => 2002,
+            PriceLevelError::SideMismatch { .. } => 2003,
+            PriceLevelError::OrderNotFound { .. } => 2004,
+            PriceLevelError::InvalidCrossing { .. } => 2005,
+            PriceLevelError::InvalidTick { .. } => 2006,
+            PriceLevelError::InvalidLot { .. } => 2007,
+            PriceLevelError::CapacityExceeded { .. } => 2008,
+            PriceLevelError::RateLimited { .. } => 2009,
+            PriceLevelError::TradingHalted { .. } => 2010,
+            PriceLevelError::PriceOutsideBand { .. } => 2011,
+            PriceLevelError::SerializationError { .. } => 3001,
+            PriceLevelError::DeserializationError { .. } => 3002,
+            PriceLevelError::ChecksumMismatch { .. } => 3003,
+        }
+    }
+
+    /// The variant's [`PriceLevelErrorKind`].
+    #[must_use]
+    pub const fn kind(&self) -> PriceLevelErrorKind {
+        match self {
+            PriceLevelError::ParseError { .. }
+            | PriceLevelError::InvalidFormat
+            | PriceLevelError::UnknownOrderType(_)
+            | PriceLevelError::MissingField(_)
+            | PriceLevelError::InvalidFieldValue { .. } => PriceLevelErrorKind::InvalidInput,
+            PriceLevelError::PriceMismatch { .. }
+            | PriceLevelError::SideMismatch { .. }
+            | PriceLevelError::InvalidCrossing { .. }
+            | PriceLevelError::InvalidTick { .. }
+            | PriceLevelError::InvalidLot { .. } => PriceLevelErrorKind::InvalidOrder,
+            PriceLevelError::DuplicateOrderId { .. } => PriceLevelErrorKind::DuplicateOrder,
+            PriceLevelError::OrderNotFound { .. } => PriceLevelErrorKind::OrderNotFound,
+            PriceLevelError::CapacityExceeded { .. }
+            | PriceLevelError::RateLimited { .. }
+            | PriceLevelError::TradingHalted { .. }
+            | PriceLevelError::PriceOutsideBand { .. } => PriceLevelErrorKind::Rejected,
+            PriceLevelError::SerializationError { .. }
+            | PriceLevelError::DeserializationError { .. }
+            | PriceLevelError::ChecksumMismatch { .. } => PriceLevelErrorKind::Persistence,
+            PriceLevelError::InvalidOperation { .. } => PriceLevelErrorKind::InvalidOperation,
+        }
+    }
 }
+
 impl Display for PriceLevelError {
     // Error formatting is off the hot match path: keep it out of line and hint
     // the optimizer that it is rarely reached.
@@ -104,57 +373,106 @@ impl Display for PriceLevelError {
                 write!(f, "Unknown order type: {order_type}")
             }
             PriceLevelError::MissingField(field) => write!(f, "Missing field: {field}"),
-            PriceLevelError::DuplicateOrderId(id) => write!(f, "Duplicate order id: {id}"),
+            PriceLevelError::DuplicateOrderId { order_id } => {
+                write!(f, "Duplicate order id: {order_id}")
+            }
+            PriceLevelError::PriceMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Price mismatch: order price {actual} does not match level price {expected}"
+                )
+            }
+            PriceLevelError::SideMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Side mismatch: order side {actual} is incompatible with the level's resting side {expected}"
+                )
+            }
+            PriceLevelError::OrderNotFound { order_id } => {
+                write!(f, "Order not found: {order_id}")
+            }
             PriceLevelError::InvalidFieldValue { field, value } => {
                 write!(f, "Invalid value for field {field}: {value}")
             }
             PriceLevelError::InvalidOperation { message } => {
                 write!(f, "Invalid operation: {message}")
             }
-            PriceLevelError::SerializationError { message } => {
+            PriceLevelError::SerializationError { message, .. } => {
                 write!(f, "Serialization error: {message}")
             }
-            PriceLevelError::DeserializationError { message } => {
+            PriceLevelError::DeserializationError { message, .. } => {
                 write!(f, "Deserialization error: {message}")
             }
             PriceLevelError::ChecksumMismatch { expected, actual } => {
                 write!(f, "Checksum mismatch: expected {expected}, got {actual}")
             }
+            PriceLevelError::InvalidCrossing {
+                maker_side,
+                taker_side,
+            } => {
+                write!(
+                    f,
+                    "Invalid crossing: {taker_side} taker cannot match {maker_side} makers"
+                )
+            }
+            PriceLevelError::InvalidTick { price, tick_size } => {
+                write!(
+                    f,
+                    "Invalid tick: price {price} is not a multiple of tick size {tick_size}"
+                )
+            }
+            PriceLevelError::InvalidLot {
+                quantity,
+                lot_size,
+                min_qty,
+                max_qty,
+            } => {
+                write!(
+                    f,
+                    "Invalid lot: quantity {quantity} is not a multiple of {lot_size} between {min_qty} and {max_qty}"
+                )
+            }
+            PriceLevelError::CapacityExceeded { limit, max } => {
+                write!(f, "Capacity exceeded: {limit} limit of {max} reached")
+            }
+            PriceLevelError::RateLimited { order_id } => {
+                write!(f, "Rate limited: message for order {order_id} refused")
+            }
+            PriceLevelError::TradingHalted { price } => {
+                write!(f, "Trading halted: level {price} is frozen")
+            }
+            PriceLevelError::PriceOutsideBand {
+                price,
+                lower,
+                upper,
+            } => {
+                write!(f, "Price {price} outside band [{lower}, {upper}]")
+            }
         }
     }
 }
 
 impl Debug for PriceLevelError {
-    // Error formatting is off the hot match path: keep it out of line and hint
-    // the optimizer that it is rarely reached.
+    // Debug renders the same message as Display.
     #[cold]
     #[inline(never)]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Error for PriceLevelError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            PriceLevelError::ParseError { message } => write!(f, "{message}"),
-            PriceLevelError::InvalidFormat => write!(f, "Invalid format"),
-            PriceLevelError::UnknownOrderType(order_type) => {
-                write!(f, "Unknown order type: {order_type}")
-            }
-            PriceLevelError::MissingField(field) => write!(f, "Missing field: {field}"),
-            PriceLevelError::DuplicateOrderId(id) => write!(f, "Duplicate order id: {id}"),
-            PriceLevelError::InvalidFieldValue { field, value } => {
-                write!(f, "Invalid value for field {field}: {value}")
-            }
-            PriceLevelError::InvalidOperation { message } => {
-                write!(f, "Invalid operation: {message}")
-            }
-            PriceLevelError::SerializationError { message } => {
-                write!(f, "Serialization error: {message}")
-            }
-            PriceLevelError::DeserializationError { message } => {
-                write!(f, "Deserialization error: {message}")
-            }
-            PriceLevelError::ChecksumMismatch { expected, actual } => {
-                write!(f, "Checksum mismatch: expected {expected}, got {actual}")
+            PriceLevelError::SerializationError {
+                source: Some(source),
+                ..
             }
+            | PriceLevelError::DeserializationError {
+                source: Some(source),
+                ..
+            } => Some(source.as_ref()),
+            _ => None,
         }
     }
 }
-
-impl std::error::Error for PriceLevelError {}
@@ -0,0 +1,121 @@
+use crate::errors::PriceLevelError;
+use crate::execution::fee::{FeeSchedule, TradeFees};
+use crate::execution::match_result::MatchResult;
+use crate::execution::trade::Trade;
+use crate::orders::{Id, OrderStatusTransition, Side};
+use crate::utils::Quantity;
+
+/// Incrementally assembles a [`MatchResult`] outside the matching engine.
+///
+/// Intended for venues that run their own allocation (multi-level sweeps,
+/// auctions, replays) and want the same result type
+/// [`PriceLevel::match_order`](crate::PriceLevel::match_order) produces, with
+/// two additions the engine deliberately leaves out:
+///
+/// - a **taker side**: when set, every trade must carry it, so a result can
+///   never mix buy- and sell-initiated fills;
+/// - a **fee schedule**: when set, maker and taker fees are attached to each
+///   trade as it is added (see [`FeeSchedule::trade_fees`]).
+///
+/// Every [`MatchResult`] invariant is still enforced by
+/// [`MatchResult::add_trade`] (taker id match, no over-fill), and
+/// [`Self::build`] derives the outcome from the accumulated fills exactly as
+/// the engine does after a sweep.
+#[derive(Debug, Clone)]
+pub struct MatchResultBuilder<T = ()> {
+    /// Result under construction.
+    result: MatchResult<T>,
+    /// Required taker side for every added trade, if constrained.
+    taker_side: Option<Side>,
+    /// Schedule applied to every added trade, if any.
+    fee_schedule: Option<FeeSchedule>,
+}
+
+impl<T> MatchResultBuilder<T> {
+    /// Starts a result for the taker `order_id` with `initial_quantity` to
+    /// fill.
+    #[must_use]
+    pub fn new(order_id: Id, initial_quantity: Quantity) -> Self {
+        Self {
+            result: MatchResult::new(order_id, initial_quantity),
+            taker_side: None,
+            fee_schedule: None,
+        }
+    }
+
+    /// Like [`Self::new`], pre-sizing the trade and filled-id vectors for
+    /// `capacity` entries.
+    #[must_use]
+    pub fn with_capacity(order_id: Id, initial_quantity: Quantity, capacity: usize) -> Self {
+        Self {
+            result: MatchResult::with_capacity(order_id, initial_quantity, capacity),
+            taker_side: None,
+            fee_schedule: None,
+        }
+    }
+
+    /// Requires every added trade to have been initiated from `side`.
+    #[must_use]
+    pub fn taker_side(mut self, side: Side) -> Self {
+        self.taker_side = Some(side);
+        self
+    }
+
+    /// Attaches fees from `schedule` to every trade added afterwards.
+    #[must_use]
+    pub fn fee_schedule(mut self, schedule: FeeSchedule) -> Self {
+        self.fee_schedule = Some(schedule);
+        self
+    }
+
+    /// Adds a trade, attaching fees if a schedule is configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the trade's taker side
+    /// differs from the configured [`Self::taker_side`], if a fee computation
+    /// overflows, or if [`MatchResult::add_trade`] rejects the trade (wrong
+    /// taker id, or an over-fill). The builder is unchanged on error.
+    pub fn add_trade(&mut self, trade: Trade<T>) -> Result<(), PriceLevelError> {
+        if let Some(side) = self.taker_side
+            && trade.taker_side() != side
+        {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!(
+                    "trade taker side {} does not match the builder's taker side {}",
+                    trade.taker_side(),
+                    side
+                ),
+            });
+        }
+        let fees = match &self.fee_schedule {
+            Some(schedule) => schedule.trade_fees(&trade)?,
+            None => TradeFees::default(),
+        };
+        self.result.add_trade_with_fees(trade, fees)
+    }
+
+    /// Records a maker order that was completely filled.
+    pub fn add_filled_order_id(&mut self, order_id: Id) {
+        self.result.add_filled_order_id(order_id);
+    }
+
+    /// Records a status change of a maker order the match traded against.
+    pub fn add_status_transition(&mut self, transition: OrderStatusTransition) {
+        self.result.add_status_transition(transition);
+    }
+
+    /// Returns the quantity still unfilled so far.
+    #[must_use]
+    pub fn remaining_quantity(&self) -> Quantity {
+        self.result.remaining_quantity()
+    }
+
+    /// Finishes the result, deriving its outcome from the accumulated fills.
+    #[must_use]
+    pub fn build(mut self) -> MatchResult<T> {
+        let remaining = self.result.remaining_quantity();
+        self.result.finalize(remaining);
+        self.result
+    }
+}
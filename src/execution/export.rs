@@ -0,0 +1,229 @@
+//! Tabular export of trades for research pipelines.
+//!
+//! [`TradeList::to_csv`] writes one CSV row per trade. With the `parquet`
+//! feature, `TradeList::to_record_batch` builds an Arrow `RecordBatch` and
+//! `TradeList::to_parquet` writes it as a Parquet file. Both formats share
+//! the columns in [`TRADE_EXPORT_COLUMNS`]; the maker extra fields are not
+//! exported.
+
+use crate::errors::PriceLevelError;
+use crate::execution::fee::{Fee, TradeFees};
+use crate::execution::list::TradeList;
+use crate::execution::trade::Trade;
+use std::io::Write;
+
+/// Columns of an exported trade, in order: the CSV header and the Parquet
+/// schema.
+///
+/// Fees are split into an amount and a currency column; an optional value
+/// that is absent is an empty CSV cell and a Parquet null.
+pub const TRADE_EXPORT_COLUMNS: [&str; 15] = [
+    "trade_id",
+    "taker_order_id",
+    "maker_order_id",
+    "price",
+    "quantity",
+    "taker_side",
+    "maker_side",
+    "timestamp",
+    "maker_fee",
+    "maker_fee_currency",
+    "taker_fee",
+    "taker_fee_currency",
+    "level_sequence",
+    "execution_sequence",
+    "clock_timestamp",
+];
+
+fn optional<V: ToString>(value: Option<V>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+fn csv_row<T>(trade: &Trade<T>, fees: TradeFees) -> String {
+    let amount = |fee: Option<Fee>| optional(fee.map(|fee| fee.amount()));
+    let currency = |fee: Option<Fee>| optional(fee.map(|fee| fee.currency()));
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+        trade.trade_id(),
+        trade.taker_order_id(),
+        trade.maker_order_id(),
+        trade.price(),
+        trade.quantity(),
+        trade.taker_side(),
+        trade.maker_side(),
+        trade.timestamp(),
+        amount(fees.maker()),
+        currency(fees.maker()),
+        amount(fees.taker()),
+        currency(fees.taker()),
+        optional(trade.level_sequence()),
+        optional(trade.execution_sequence()),
+        optional(trade.clock_timestamp()),
+    )
+}
+
+fn io_error<E: std::error::Error + Send + Sync + 'static>(format: &str, e: E) -> PriceLevelError {
+    PriceLevelError::SerializationError {
+        message: format!("trade {format}: {e}"),
+        source: Some(std::sync::Arc::new(e)),
+    }
+}
+
+impl<T> TradeList<T> {
+    /// Write the trades as CSV: the [`TRADE_EXPORT_COLUMNS`] header row, then
+    /// one row per trade in list order.
+    ///
+    /// Every cell is a number, an id or a side, so no cell needs quoting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if writing fails.
+    pub fn to_csv<W: Write>(&self, mut writer: W) -> Result<(), PriceLevelError> {
+        writeln!(writer, "{}", TRADE_EXPORT_COLUMNS.join(",")).map_err(|e| io_error("csv", e))?;
+        for (index, trade) in self.as_slice().iter().enumerate() {
+            let fees = self.trade_fees(index).unwrap_or_default();
+            writeln!(writer, "{}", csv_row(trade, fees)).map_err(|e| io_error("csv", e))?;
+        }
+        writer.flush().map_err(|e| io_error("csv", e))
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod columnar {
+    use super::{TRADE_EXPORT_COLUMNS, io_error};
+    use crate::errors::PriceLevelError;
+    use crate::execution::fee::Fee;
+    use crate::execution::list::TradeList;
+    use arrow_array::{ArrayRef, Decimal128Array, RecordBatch, StringArray, UInt64Array};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::io::Write;
+    use std::sync::Arc;
+
+    /// Largest magnitude a `Decimal128(38, 0)` column holds.
+    const MAX_DECIMAL: u128 = 10_u128.pow(38) - 1;
+
+    fn decimal(field: &str, value: u128) -> Result<i128, PriceLevelError> {
+        if value > MAX_DECIMAL {
+            return Err(PriceLevelError::SerializationError {
+                message: format!("trade parquet: {field} {value} exceeds 38 decimal digits"),
+                source: None,
+            });
+        }
+        // At most 38 digits, so it fits an `i128`.
+        Ok(value as i128)
+    }
+
+    fn fee_amount(field: &str, fee: Option<Fee>) -> Result<Option<i128>, PriceLevelError> {
+        fee.map(|fee| decimal(field, fee.amount().unsigned_abs()).map(|_| fee.amount()))
+            .transpose()
+    }
+
+    fn decimal_column(values: Vec<Option<i128>>) -> Result<ArrayRef, PriceLevelError> {
+        let array = Decimal128Array::from(values)
+            .with_precision_and_scale(38, 0)
+            .map_err(|e| io_error("parquet", e))?;
+        Ok(Arc::new(array))
+    }
+
+    fn schema() -> Schema {
+        let decimal = DataType::Decimal128(38, 0);
+        let types = [
+            (DataType::Utf8, false),
+            (DataType::Utf8, false),
+            (DataType::Utf8, false),
+            (decimal.clone(), false),
+            (DataType::UInt64, false),
+            (DataType::Utf8, false),
+            (DataType::Utf8, false),
+            (DataType::UInt64, false),
+            (decimal.clone(), true),
+            (DataType::Utf8, true),
+            (decimal, true),
+            (DataType::Utf8, true),
+            (DataType::UInt64, true),
+            (DataType::UInt64, true),
+            (DataType::UInt64, true),
+        ];
+        Schema::new(
+            TRADE_EXPORT_COLUMNS
+                .iter()
+                .zip(types)
+                .map(|(name, (data_type, nullable))| Field::new(*name, data_type, nullable))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    impl<T> TradeList<T> {
+        /// Build an Arrow `RecordBatch` of the trades, one row per trade,
+        /// with the [`TRADE_EXPORT_COLUMNS`] as its schema.
+        ///
+        /// Ids and sides are strings, prices and fee amounts are
+        /// `Decimal128(38, 0)` in ticks, and quantities, timestamps (in
+        /// milliseconds) and sequences are `UInt64`.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`PriceLevelError::SerializationError`] if a price or fee
+        /// amount has more than 38 decimal digits.
+        pub fn to_record_batch(&self) -> Result<RecordBatch, PriceLevelError> {
+            let trades = self.as_slice();
+            let strings = |f: &dyn Fn(usize) -> Option<String>| -> ArrayRef {
+                Arc::new((0..trades.len()).map(f).collect::<StringArray>())
+            };
+            let numbers = |f: &dyn Fn(usize) -> Option<u64>| -> ArrayRef {
+                Arc::new((0..trades.len()).map(f).collect::<UInt64Array>())
+            };
+
+            let prices = trades
+                .iter()
+                .map(|trade| decimal("price", trade.price().as_u128()).map(Some))
+                .collect::<Result<Vec<_>, _>>()?;
+            let fees: Vec<_> = (0..trades.len())
+                .map(|i| self.trade_fees(i).unwrap_or_default())
+                .collect();
+            let maker_fees = fees
+                .iter()
+                .map(|fees| fee_amount("maker_fee", fees.maker()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let taker_fees = fees
+                .iter()
+                .map(|fees| fee_amount("taker_fee", fees.taker()))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let columns: Vec<ArrayRef> = vec![
+                strings(&|i| Some(trades[i].trade_id().to_string())),
+                strings(&|i| Some(trades[i].taker_order_id().to_string())),
+                strings(&|i| Some(trades[i].maker_order_id().to_string())),
+                decimal_column(prices)?,
+                numbers(&|i| Some(trades[i].quantity().as_u64())),
+                strings(&|i| Some(trades[i].taker_side().to_string())),
+                strings(&|i| Some(trades[i].maker_side().to_string())),
+                numbers(&|i| Some(trades[i].timestamp().as_u64())),
+                decimal_column(maker_fees)?,
+                strings(&|i| fees[i].maker().map(|fee| fee.currency().to_string())),
+                decimal_column(taker_fees)?,
+                strings(&|i| fees[i].taker().map(|fee| fee.currency().to_string())),
+                numbers(&|i| trades[i].level_sequence()),
+                numbers(&|i| trades[i].execution_sequence()),
+                numbers(&|i| trades[i].clock_timestamp().map(|t| t.as_u64())),
+            ];
+            RecordBatch::try_new(Arc::new(schema()), columns).map_err(|e| io_error("parquet", e))
+        }
+
+        /// Write the trades as a Parquet file of one row group, with the
+        /// schema of [`Self::to_record_batch`].
+        ///
+        /// # Errors
+        ///
+        /// Returns [`PriceLevelError::SerializationError`] if a value does not
+        /// fit its column or writing fails.
+        pub fn to_parquet<W: Write + Send>(&self, writer: W) -> Result<(), PriceLevelError> {
+            let batch = self.to_record_batch()?;
+            let mut writer = parquet::arrow::ArrowWriter::try_new(writer, batch.schema(), None)
+                .map_err(|e| io_error("parquet", e))?;
+            writer.write(&batch).map_err(|e| io_error("parquet", e))?;
+            writer.close().map_err(|e| io_error("parquet", e))?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//! Liquidity classification and fee fields attached to executed trades.
+//!
+//! The matching engine itself never charges fees: a [`Trade`] leaves
+//! [`PriceLevel::match_order`](crate::PriceLevel::match_order) with no fee
+//! attached. An exchange attaches its own [`FeeSchedule`] afterwards —
+//! through [`MatchResultBuilder`](crate::MatchResultBuilder) or
+//! [`MatchResult::apply_fee_schedule`](crate::MatchResult::apply_fee_schedule)
+//! — so fee policy stays outside the hot path and no wrapper type is needed.
+//!
+//! Fees are kept beside the trades, one [`TradeFees`] per trade in the
+//! [`TradeList`](crate::TradeList), rather than on [`Trade`] itself: the
+//! engine never sets them, so a fee-less list carries no fee storage and a
+//! trade stays small and `Copy`.
+
+use crate::errors::PriceLevelError;
+use crate::execution::trade::Trade;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Basis points per unit: a rate of `10_000` bps is 100%.
+const BPS_PER_UNIT: i128 = 10_000;
+
+/// Which side of a trade an order was on with respect to liquidity.
+///
+/// The resting (maker) order *added* liquidity; the incoming (taker) order
+/// *removed* it. Venues typically price the two differently, which is what
+/// [`FeeSchedule`] encodes.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LiquidityFlag {
+    /// The order was resting in the book and provided liquidity.
+    #[serde(rename(serialize = "MAKER"))]
+    #[serde(alias = "maker", alias = "MAKER")]
+    Maker,
+    /// The order was incoming and removed liquidity.
+    #[serde(rename(serialize = "TAKER"))]
+    #[serde(alias = "taker", alias = "TAKER")]
+    Taker,
+}
+
+impl LiquidityFlag {
+    /// Returns the counterparty's liquidity flag.
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Maker => Self::Taker,
+            Self::Taker => Self::Maker,
+        }
+    }
+}
+
+impl FromStr for LiquidityFlag {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "MAKER" => Ok(Self::Maker),
+            "TAKER" => Ok(Self::Taker),
+            _ => Err(PriceLevelError::ParseError {
+                message: "Failed to parse LiquidityFlag".to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for LiquidityFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Maker => write!(f, "MAKER"),
+            Self::Taker => write!(f, "TAKER"),
+        }
+    }
+}
+
+/// A short currency or asset code a fee is denominated in (e.g. `USD`,
+/// `USDT`, `BTC`).
+///
+/// Stored inline as up to [`FeeCurrency::MAX_LEN`] ASCII alphanumeric bytes so
+/// [`Fee`] — and therefore [`Trade`] — stays `Copy`. Serialized as a plain
+/// string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FeeCurrency {
+    /// Code bytes, zero-padded past `len`.
+    bytes: [u8; FeeCurrency::MAX_LEN],
+    /// Number of meaningful bytes in `bytes`.
+    len: u8,
+}
+
+impl FeeCurrency {
+    /// Maximum length of a currency code, in bytes.
+    pub const MAX_LEN: usize = 8;
+
+    /// Creates a currency code.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidFieldValue`] if `code` is empty,
+    /// longer than [`Self::MAX_LEN`] bytes, or contains anything other than
+    /// ASCII letters and digits.
+    pub fn new(code: &str) -> Result<Self, PriceLevelError> {
+        if code.is_empty()
+            || code.len() > Self::MAX_LEN
+            || !code.bytes().all(|b| b.is_ascii_alphanumeric())
+        {
+            return Err(PriceLevelError::InvalidFieldValue {
+                field: "fee_currency".to_string(),
+                value: code.to_string(),
+            });
+        }
+        let mut bytes = [0_u8; Self::MAX_LEN];
+        bytes[..code.len()].copy_from_slice(code.as_bytes());
+        Ok(Self {
+            bytes,
+            len: code.len() as u8,
+        })
+    }
+
+    /// Returns the code as a string slice.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // `new` admits only ASCII alphanumerics, so the prefix is valid UTF-8.
+        std::str::from_utf8(&self.bytes[..usize::from(self.len)]).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for FeeCurrency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FeeCurrency {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl Serialize for FeeCurrency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for FeeCurrency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let code = String::deserialize(deserializer)?;
+        Self::new(&code).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A fee charged (positive) or rebated (negative) on one side of a trade.
+///
+/// The amount is in the same price-ticks × quantity units as
+/// [`Trade::total_value`], denominated in [`Fee::currency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Fee {
+    /// Fee amount; negative for a rebate.
+    amount: i128,
+    /// Currency the amount is denominated in.
+    currency: FeeCurrency,
+}
+
+impl Fee {
+    /// Creates a fee of `amount` in `currency`.
+    #[must_use]
+    pub fn new(amount: i128, currency: FeeCurrency) -> Self {
+        Self { amount, currency }
+    }
+
+    /// Returns the fee amount; negative for a rebate.
+    #[must_use]
+    pub fn amount(&self) -> i128 {
+        self.amount
+    }
+
+    /// Returns the currency the fee is denominated in.
+    #[must_use]
+    pub fn currency(&self) -> FeeCurrency {
+        self.currency
+    }
+
+    /// Returns `true` if this fee is a rebate (negative amount).
+    #[must_use]
+    pub fn is_rebate(&self) -> bool {
+        self.amount < 0
+    }
+}
+
+impl fmt::Display for Fee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.amount, self.currency)
+    }
+}
+
+impl FromStr for Fee {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || PriceLevelError::InvalidFieldValue {
+            field: "fee".to_string(),
+            value: s.to_string(),
+        };
+        let (amount, currency) = s.split_once('@').ok_or_else(invalid)?;
+        let amount = amount.parse::<i128>().map_err(|_| invalid())?;
+        let currency = FeeCurrency::new(currency).map_err(|_| invalid())?;
+        Ok(Self { amount, currency })
+    }
+}
+
+/// A flat maker / taker fee schedule, in basis points of trade value.
+///
+/// A negative rate is a rebate. Fees are computed as
+/// `total_value * bps / 10_000`, truncated toward zero, so a fractional unit
+/// is never charged (nor rebated).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    /// Rate applied to the maker side, in basis points.
+    maker_bps: i32,
+    /// Rate applied to the taker side, in basis points.
+    taker_bps: i32,
+    /// Currency both fees are denominated in.
+    currency: FeeCurrency,
+}
+
+impl FeeSchedule {
+    /// Creates a schedule charging `maker_bps` to makers and `taker_bps` to
+    /// takers, both in `currency`.
+    #[must_use]
+    pub fn new(maker_bps: i32, taker_bps: i32, currency: FeeCurrency) -> Self {
+        Self {
+            maker_bps,
+            taker_bps,
+            currency,
+        }
+    }
+
+    /// Returns the maker rate, in basis points.
+    #[must_use]
+    pub fn maker_bps(&self) -> i32 {
+        self.maker_bps
+    }
+
+    /// Returns the taker rate, in basis points.
+    #[must_use]
+    pub fn taker_bps(&self) -> i32 {
+        self.taker_bps
+    }
+
+    /// Returns the currency fees are denominated in.
+    #[must_use]
+    pub fn currency(&self) -> FeeCurrency {
+        self.currency
+    }
+
+    /// Returns the rate for `liquidity`, in basis points.
+    #[must_use]
+    pub fn rate_for(&self, liquidity: LiquidityFlag) -> i32 {
+        match liquidity {
+            LiquidityFlag::Maker => self.maker_bps,
+            LiquidityFlag::Taker => self.taker_bps,
+        }
+    }
+
+    /// Computes the fee `liquidity` pays on `trade`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the trade value
+    /// overflows (see [`Trade::total_value`]) or does not fit the signed fee
+    /// arithmetic.
+    pub fn fee_for<T>(
+        &self,
+        trade: &Trade<T>,
+        liquidity: LiquidityFlag,
+    ) -> Result<Fee, PriceLevelError> {
+        let overflow = || PriceLevelError::InvalidOperation {
+            message: format!("fee overflow on trade {}", trade.trade_id()),
+        };
+        let value = i128::try_from(trade.total_value()?).map_err(|_| overflow())?;
+        let amount = value
+            .checked_mul(i128::from(self.rate_for(liquidity)))
+            .ok_or_else(overflow)?
+            / BPS_PER_UNIT;
+        Ok(Fee::new(amount, self.currency))
+    }
+
+    /// Computes both the maker and the taker fee of `trade`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] under the same conditions
+    /// as [`Self::fee_for`].
+    pub fn trade_fees<T>(&self, trade: &Trade<T>) -> Result<TradeFees, PriceLevelError> {
+        Ok(TradeFees::new(
+            Some(self.fee_for(trade, LiquidityFlag::Maker)?),
+            Some(self.fee_for(trade, LiquidityFlag::Taker)?),
+        ))
+    }
+}
+
+/// The maker and taker fees attached to one trade of a
+/// [`TradeList`](crate::TradeList); see
+/// [`TradeList::trade_fees`](crate::TradeList::trade_fees).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TradeFees {
+    /// Fee charged to (or rebated to) the maker, if attached.
+    maker: Option<Fee>,
+    /// Fee charged to (or rebated to) the taker, if attached.
+    taker: Option<Fee>,
+}
+
+impl TradeFees {
+    /// Fees of `maker` for the maker side and `taker` for the taker side.
+    #[must_use]
+    pub fn new(maker: Option<Fee>, taker: Option<Fee>) -> Self {
+        Self { maker, taker }
+    }
+
+    /// Returns the fee attached to the maker side, if any.
+    #[must_use]
+    pub fn maker(&self) -> Option<Fee> {
+        self.maker
+    }
+
+    /// Returns the fee attached to the taker side, if any.
+    #[must_use]
+    pub fn taker(&self) -> Option<Fee> {
+        self.taker
+    }
+
+    /// Returns the fee attached to the side with the given liquidity flag.
+    #[must_use]
+    pub fn fee_for(&self, liquidity: LiquidityFlag) -> Option<Fee> {
+        match liquidity {
+            LiquidityFlag::Maker => self.maker,
+            LiquidityFlag::Taker => self.taker,
+        }
+    }
+
+    /// Returns `true` when neither side has a fee attached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.maker.is_none() && self.taker.is_none()
+    }
+
+    /// Writes the attached fees as the `;maker_fee=...;taker_fee=...` suffix
+    /// of a trade's string form; nothing when no fee is attached.
+    pub(crate) fn write_fields(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(fee) = self.maker {
+            write!(f, ";maker_fee={fee}")?;
+        }
+        if let Some(fee) = self.taker {
+            write!(f, ";taker_fee={fee}")?;
+        }
+        Ok(())
+    }
+
+    /// Reads the fee fields back out of a trade's string form, ignoring
+    /// every other field.
+    pub(crate) fn parse_fields(trade: &str) -> Result<Self, PriceLevelError> {
+        let mut fees = Self::default();
+        for field in trade.split(';') {
+            if let Some(fee) = field.strip_prefix("maker_fee=") {
+                fees.maker = Some(Fee::from_str(fee)?);
+            } else if let Some(fee) = field.strip_prefix("taker_fee=") {
+                fees.taker = Some(Fee::from_str(fee)?);
+            }
+        }
+        Ok(fees)
+    }
+}
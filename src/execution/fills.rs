@@ -0,0 +1,174 @@
+//! Per-maker aggregation of trades.
+//!
+//! A match can trade against the same maker more than once — an iceberg or
+//! reserve order refreshes and fills again — and a merged
+//! [`MatchResult`](crate::MatchResult) spans several sweeps.
+//! [`MakerFill::by_maker`] (or
+//! [`MatchResult::fills_by_maker`](crate::MatchResult::fills_by_maker)) folds
+//! the trades into one [`MakerFill`] per maker order.
+
+use crate::errors::PriceLevelError;
+use crate::execution::trade::Trade;
+use crate::orders::Id;
+use crate::utils::Quantity;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Everything a taker executed against one maker order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MakerFill {
+    /// The maker order traded against.
+    maker_order_id: Id,
+    /// Total quantity executed against the maker.
+    quantity: Quantity,
+    /// Total value (`price * quantity`) executed against the maker.
+    value: u128,
+    /// Number of individual trades folded into this fill.
+    trade_count: u64,
+}
+
+impl MakerFill {
+    /// Folds `trades` into one fill per distinct maker order, in the order
+    /// each maker first traded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if a maker's total
+    /// quantity overflows `u64` or its total value overflows `u128`.
+    pub fn by_maker<'a, T: 'a, I>(trades: I) -> Result<Vec<Self>, PriceLevelError>
+    where
+        I: IntoIterator<Item = &'a Trade<T>>,
+    {
+        let mut fills: Vec<Self> = Vec::new();
+        let mut positions: std::collections::HashMap<Id, usize> = std::collections::HashMap::new();
+        for trade in trades {
+            let value = trade.total_value()?;
+            match positions.get(&trade.maker_order_id()) {
+                Some(&position) => {
+                    let fill = &mut fills[position];
+                    let quantity = fill
+                        .quantity
+                        .as_u64()
+                        .checked_add(trade.quantity().as_u64())
+                        .ok_or_else(|| PriceLevelError::InvalidOperation {
+                            message: "maker fill quantity overflow".to_string(),
+                        })?;
+                    fill.value = fill.value.checked_add(value).ok_or_else(|| {
+                        PriceLevelError::InvalidOperation {
+                            message: "maker fill value overflow".to_string(),
+                        }
+                    })?;
+                    fill.quantity = Quantity::new(quantity);
+                    fill.trade_count += 1;
+                }
+                None => {
+                    positions.insert(trade.maker_order_id(), fills.len());
+                    fills.push(Self {
+                        maker_order_id: trade.maker_order_id(),
+                        quantity: trade.quantity(),
+                        value,
+                        trade_count: 1,
+                    });
+                }
+            }
+        }
+        Ok(fills)
+    }
+
+    /// Get the maker order traded against.
+    #[must_use]
+    pub fn maker_order_id(&self) -> Id {
+        self.maker_order_id
+    }
+
+    /// Get the total quantity executed against the maker.
+    #[must_use]
+    pub fn quantity(&self) -> Quantity {
+        self.quantity
+    }
+
+    /// Get the total value executed against the maker.
+    #[must_use]
+    pub fn value(&self) -> u128 {
+        self.value
+    }
+
+    /// Get the number of trades folded into this fill.
+    #[must_use]
+    pub fn trade_count(&self) -> u64 {
+        self.trade_count
+    }
+
+    /// Get the average price executed against the maker, or `None` for an
+    /// empty fill.
+    #[must_use]
+    pub fn average_price(&self) -> Option<f64> {
+        let quantity = self.quantity.as_u64();
+        (quantity > 0).then(|| self.value as f64 / quantity as f64)
+    }
+}
+
+impl fmt::Display for MakerFill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "MakerFill:maker_order_id={};quantity={};value={};trade_count={}",
+            self.maker_order_id, self.quantity, self.value, self.trade_count
+        )
+    }
+}
+
+impl FromStr for MakerFill {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields_str = s
+            .strip_prefix("MakerFill:")
+            .ok_or(PriceLevelError::InvalidFormat)?;
+
+        let mut fields = std::collections::HashMap::new();
+        for field_pair in fields_str.split(';') {
+            let kv: Vec<&str> = field_pair.split('=').collect();
+            if kv.len() == 2 {
+                fields.insert(kv[0], kv[1]);
+            }
+        }
+
+        let get_field = |field: &str| -> Result<&str, PriceLevelError> {
+            match fields.get(field) {
+                Some(result) => Ok(*result),
+                None => Err(PriceLevelError::MissingField(field.to_string())),
+            }
+        };
+        let invalid = |field: &str, value: &str| PriceLevelError::InvalidFieldValue {
+            field: field.to_string(),
+            value: value.to_string(),
+        };
+
+        let maker_order_id_str = get_field("maker_order_id")?;
+        let maker_order_id = Id::from_str(maker_order_id_str)
+            .map_err(|_| invalid("maker_order_id", maker_order_id_str))?;
+
+        let quantity_str = get_field("quantity")?;
+        let quantity =
+            Quantity::from_str(quantity_str).map_err(|_| invalid("quantity", quantity_str))?;
+
+        let value_str = get_field("value")?;
+        let value = value_str
+            .parse::<u128>()
+            .map_err(|_| invalid("value", value_str))?;
+
+        let trade_count_str = get_field("trade_count")?;
+        let trade_count = trade_count_str
+            .parse::<u64>()
+            .map_err(|_| invalid("trade_count", trade_count_str))?;
+
+        Ok(Self {
+            maker_order_id,
+            quantity,
+            value,
+            trade_count,
+        })
+    }
+}
@@ -1,60 +1,181 @@
 use crate::errors::PriceLevelError;
+use crate::execution::fee::TradeFees;
+use crate::execution::schema::SchemaVersion;
 use crate::execution::trade::Trade;
 use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 use std::fmt;
 use std::str::FromStr;
 
+/// Trades a [`TradeList`] holds inline before it spills to the heap.
+///
+/// One, for the single-maker fill that dominates matching: a larger inline
+/// buffer grows every [`MatchResult`](crate::MatchResult) by a full
+/// [`Trade`] per slot, which costs more in moves than it saves in
+/// allocations.
+pub const TRADE_LIST_INLINE_CAPACITY: usize = 1;
+
 /// A wrapper for a vector of trades to implement custom serialization.
 ///
 /// The inner collection is private to enforce append-only semantics
-/// during matching. Use [`Self::add`] to append and [`Self::as_vec`]
+/// during matching. Use [`Self::add`] to append and [`Self::as_slice`]
 /// or [`Self::into_vec`] to read.
+///
+/// Up to [`TRADE_LIST_INLINE_CAPACITY`] trades are stored inline; a longer
+/// list moves to a heap vector. The wire format is a plain sequence either
+/// way.
+///
+/// Fees attached by a [`FeeSchedule`](crate::FeeSchedule) are kept beside
+/// the trades, one [`TradeFees`] each, and read with [`Self::trade_fees`]. A
+/// list nobody attached fees to holds no fee storage at all.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct TradeList {
+#[serde(bound(deserialize = "T: Deserialize<'de> + Default"))]
+pub struct TradeList<T = ()> {
+    /// Schema version of the serialized list; see
+    /// [`EXECUTION_SCHEMA_VERSION`](crate::EXECUTION_SCHEMA_VERSION).
+    #[serde(default)]
+    schema_version: SchemaVersion,
+
     /// Ordered collection of trades.
-    trades: Vec<Trade>,
+    trades: SmallVec<[Trade<T>; TRADE_LIST_INLINE_CAPACITY]>,
+
+    /// Fees of each trade, by position; empty until fees are first
+    /// attached, then as long as `trades`.
+    #[serde(default)]
+    fees: Vec<TradeFees>,
 }
 
-impl TradeList {
+impl<T> TradeList<T> {
     /// Create a new empty trade list
     #[must_use]
     pub fn new() -> Self {
-        Self { trades: Vec::new() }
+        Self {
+            schema_version: SchemaVersion,
+            trades: SmallVec::new(),
+            fees: Vec::new(),
+        }
     }
 
     /// Create a new empty trade list with space reserved for `n` trades.
     ///
     /// Pre-allocates the backing vector to avoid repeated reallocations when
     /// the number of trades produced by a single match sweep is known or
-    /// estimable (e.g. bounded by the resting order count at a level).
+    /// estimable (e.g. bounded by the resting order count at a level). An `n`
+    /// up to [`TRADE_LIST_INLINE_CAPACITY`] allocates nothing.
     #[must_use]
     pub fn with_capacity(n: usize) -> Self {
         Self {
-            trades: Vec::with_capacity(n),
+            schema_version: SchemaVersion,
+            trades: SmallVec::with_capacity(n),
+            fees: Vec::new(),
         }
     }
 
     /// Create a trade list from an existing vector
+    ///
+    /// A vector whose capacity fits inline is moved inline and freed;
+    /// a larger one is kept as the heap storage without copying.
     #[must_use]
-    pub fn from_vec(trades: Vec<Trade>) -> Self {
-        Self { trades }
+    pub fn from_vec(trades: Vec<Trade<T>>) -> Self {
+        Self {
+            schema_version: SchemaVersion,
+            trades: SmallVec::from_vec(trades),
+            fees: Vec::new(),
+        }
     }
 
     /// Add a trade to the list
-    pub fn add(&mut self, trade: Trade) {
+    pub fn add(&mut self, trade: Trade<T>) {
+        self.add_with_fees(trade, TradeFees::default());
+    }
+
+    /// Add a trade to the list with `fees` attached to it.
+    pub fn add_with_fees(&mut self, trade: Trade<T>, fees: TradeFees) {
+        if !fees.is_empty() || !self.fees.is_empty() {
+            // A deserialized list may carry fewer entries than trades.
+            self.fees.resize(self.trades.len(), TradeFees::default());
+            self.fees.push(fees);
+        }
         self.trades.push(trade);
     }
 
-    /// Get a reference to the underlying vector
+    /// Returns the fees attached to the trade at `index`, or `None` if there
+    /// is no such trade. A trade without fees has an empty [`TradeFees`].
+    #[must_use]
+    pub fn trade_fees(&self, index: usize) -> Option<TradeFees> {
+        (index < self.trades.len()).then(|| self.fees.get(index).copied().unwrap_or_default())
+    }
+
+    /// Replaces the fees of every trade, by position; `fees` must be as long
+    /// as the list.
+    pub(crate) fn set_fees(&mut self, fees: Vec<TradeFees>) {
+        debug_assert_eq!(fees.len(), self.trades.len());
+        self.fees = fees;
+    }
+
+    /// Appends the trades of `other`, with their fees, after this list's.
+    pub(crate) fn append(&mut self, other: TradeList<T>) {
+        let TradeList { trades, fees, .. } = other;
+        for (index, trade) in trades.into_iter().enumerate() {
+            let fees = fees.get(index).copied().unwrap_or_default();
+            self.add_with_fees(trade, fees);
+        }
+    }
+
+    /// Get the trades, in the order they were added
     #[must_use]
-    pub fn as_vec(&self) -> &Vec<Trade> {
+    pub fn as_slice(&self) -> &[Trade<T>] {
         &self.trades
     }
 
+    /// Get the trades, in the order they were added.
+    ///
+    /// The list is no longer backed by a `Vec`, so this returns the same
+    /// slice as [`Self::as_slice`].
+    #[deprecated(since = "0.10.0", note = "use `TradeList::as_slice`")]
+    #[must_use]
+    pub fn as_vec(&self) -> &[Trade<T>] {
+        self.as_slice()
+    }
+
     /// Convert into a vector of trades
+    ///
+    /// A list still stored inline is copied into a new vector. Attached fees
+    /// are dropped; read them with [`Self::trade_fees`] first.
+    #[must_use]
+    pub fn into_vec(self) -> Vec<Trade<T>> {
+        self.trades.into_vec()
+    }
+
+    /// Reserve room for at least `additional` more trades.
+    #[cfg(feature = "pool")]
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.trades.reserve(additional);
+    }
+
+    /// The heap vector of a spilled list, for an
+    /// [`OrderPool`](crate::OrderPool) to recycle; an inline list has none
+    /// and yields an unallocated vector.
+    #[cfg(feature = "pool")]
+    pub(crate) fn into_heap_vec(self) -> Vec<Trade<T>> {
+        if self.trades.spilled() {
+            self.trades.into_vec()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Returns `true` once the list has outgrown its inline storage.
     #[must_use]
-    pub fn into_vec(self) -> Vec<Trade> {
-        self.trades
+    pub fn spilled(&self) -> bool {
+        self.trades.spilled()
+    }
+
+    /// Returns the number of trades the list can hold without reallocating,
+    /// never less than [`TRADE_LIST_INLINE_CAPACITY`].
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.trades.capacity()
     }
 
     /// Returns `true` when the list does not contain any trades.
@@ -68,15 +189,33 @@ impl TradeList {
     pub fn len(&self) -> usize {
         self.trades.len()
     }
+
+    /// Transform the maker extra fields type of every trade using a function,
+    /// keeping the trades in order.
+    #[must_use]
+    pub fn map_maker_extra_fields<U, F>(self, mut f: F) -> TradeList<U>
+    where
+        F: FnMut(T) -> U,
+    {
+        TradeList {
+            schema_version: SchemaVersion,
+            trades: self
+                .trades
+                .into_iter()
+                .map(|trade| trade.map_maker_extra_fields(&mut f))
+                .collect(),
+            fees: self.fees,
+        }
+    }
 }
 
-impl Default for TradeList {
+impl<T> Default for TradeList<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl fmt::Display for TradeList {
+impl<T> fmt::Display for TradeList<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Trades:[")?;
 
@@ -85,6 +224,11 @@ impl fmt::Display for TradeList {
                 write!(f, ",")?;
             }
             write!(f, "{trade}")?;
+            // Fee fields are emitted only when attached, so a fee-less trade
+            // keeps the original string form.
+            if let Some(fees) = self.fees.get(i) {
+                fees.write_fields(f)?;
+            }
         }
 
         write!(f, "]")
@@ -112,7 +256,7 @@ impl FromStr for TradeList {
             return Ok(TradeList::new());
         }
 
-        let mut trades = Vec::new();
+        let mut list = TradeList::new();
         let mut current_trade = String::new();
         let mut bracket_depth = 0;
 
@@ -120,8 +264,7 @@ impl FromStr for TradeList {
             match c {
                 ',' if bracket_depth == 0 => {
                     if !current_trade.is_empty() {
-                        let trade = Trade::from_str(&current_trade)?;
-                        trades.push(trade);
+                        list.add_parsed(&current_trade)?;
                         current_trade.clear();
                     }
                 }
@@ -138,22 +281,30 @@ impl FromStr for TradeList {
         }
 
         if !current_trade.is_empty() {
-            let trade = Trade::from_str(&current_trade)?;
-            trades.push(trade);
+            list.add_parsed(&current_trade)?;
         }
 
-        Ok(TradeList { trades })
+        Ok(list)
+    }
+}
+
+impl TradeList {
+    /// Parse one trade's string form, fee fields included, and append it.
+    fn add_parsed(&mut self, trade: &str) -> Result<(), PriceLevelError> {
+        let fees = TradeFees::parse_fields(trade)?;
+        self.add_with_fees(Trade::from_str(trade)?, fees);
+        Ok(())
     }
 }
 
-impl From<Vec<Trade>> for TradeList {
-    fn from(trades: Vec<Trade>) -> Self {
+impl<T> From<Vec<Trade<T>>> for TradeList<T> {
+    fn from(trades: Vec<Trade<T>>) -> Self {
         Self::from_vec(trades)
     }
 }
 
-impl From<TradeList> for Vec<Trade> {
-    fn from(list: TradeList) -> Self {
+impl<T> From<TradeList<T>> for Vec<Trade<T>> {
+    fn from(list: TradeList<T>) -> Self {
         list.into_vec()
     }
 }
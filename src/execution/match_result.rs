@@ -1,7 +1,11 @@
 use crate::errors::PriceLevelError;
+use crate::execution::fee::{FeeSchedule, TradeFees};
+use crate::execution::fills::MakerFill;
 use crate::execution::list::TradeList;
+use crate::execution::schema::SchemaVersion;
+use crate::execution::tape::TapePrint;
 use crate::execution::trade::Trade;
-use crate::orders::Id;
+use crate::orders::{Id, OrderRefresh, OrderStatusTransition};
 use crate::utils::Quantity;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -82,14 +86,29 @@ impl MatchOutcome {
 /// payload a valid `MatchResult` can produce still decodes unchanged; only
 /// self-contradictory input is rejected — as a serde error or a
 /// [`PriceLevelError`], never a panic.
+///
+/// # Maker extra fields
+///
+/// `T` is the extra-fields type of the level's orders
+/// ([`OrderType<T>`](crate::OrderType)). Each trade carries a clone of its
+/// maker's `extra_fields` as it rested when the trade executed (see
+/// [`Trade::maker_extra_fields`]), so client ids or account data attached to
+/// a resting order come back with its fills.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(try_from = "MatchResultWire")]
-pub struct MatchResult {
+#[serde(
+    try_from = "MatchResultWire<T>",
+    bound(deserialize = "T: Deserialize<'de> + Default")
+)]
+pub struct MatchResult<T = ()> {
+    /// Schema version of the serialized result; see
+    /// [`EXECUTION_SCHEMA_VERSION`](crate::EXECUTION_SCHEMA_VERSION).
+    schema_version: SchemaVersion,
+
     /// The ID of the incoming order that initiated the match
     order_id: Id,
 
     /// List of trades that resulted from the match
-    trades: TradeList,
+    trades: TradeList<T>,
 
     /// Remaining quantity of the incoming order after matching
     remaining_quantity: u64,
@@ -110,6 +129,20 @@ pub struct MatchResult {
     /// back (#135).
     #[serde(serialize_with = "serialize_outcome_as_some")]
     outcome: MatchOutcome,
+
+    /// The status changes of the resting orders the match traded against, in
+    /// trade order: a maker's first fill (`New -> PartiallyFilled`) and its
+    /// last (`-> Filled`).
+    status_transitions: Vec<OrderStatusTransition>,
+
+    /// The iceberg and reserve refreshes the match made, in sweep order, with
+    /// whether each refreshed order kept its queue slot.
+    refreshes: Vec<OrderRefresh>,
+
+    /// The part of `remaining_quantity` the taker's time in force cancelled
+    /// rather than left open: the whole remainder of an IOC, FOK or market
+    /// taker matched by `PriceLevel::match_taker`, zero otherwise.
+    cancelled_quantity: u64,
 }
 
 /// Serializes `outcome` wrapped in `Some` — see the field doc on
@@ -130,9 +163,14 @@ where
 /// `MatchResult` deserializes this permissive struct and then runs
 /// [`MatchResult::validated`] via the [`TryFrom`] impl below.
 #[derive(Deserialize)]
-struct MatchResultWire {
+#[serde(bound(deserialize = "T: Deserialize<'de> + Default"))]
+struct MatchResultWire<T> {
+    /// Absent from payloads written before schema versioning; a version
+    /// newer than this build's is rejected while decoding the field.
+    #[serde(default)]
+    schema_version: SchemaVersion,
     order_id: Id,
-    trades: TradeList,
+    trades: TradeList<T>,
     remaining_quantity: u64,
     is_complete: bool,
     filled_order_ids: Vec<Id>,
@@ -144,12 +182,21 @@ struct MatchResultWire {
     /// `PartiallyFilled` without trades.
     #[serde(default)]
     outcome: Option<MatchOutcome>,
+    /// Absent from payloads written before status tracking existed.
+    #[serde(default)]
+    status_transitions: Vec<OrderStatusTransition>,
+    /// Absent from payloads written before refreshes were reported.
+    #[serde(default)]
+    refreshes: Vec<OrderRefresh>,
+    /// Absent from payloads written before cancellations were reported.
+    #[serde(default)]
+    cancelled_quantity: u64,
 }
 
-impl TryFrom<MatchResultWire> for MatchResult {
+impl<T> TryFrom<MatchResultWire<T>> for MatchResult<T> {
     type Error = PriceLevelError;
 
-    fn try_from(wire: MatchResultWire) -> Result<Self, Self::Error> {
+    fn try_from(wire: MatchResultWire<T>) -> Result<Self, Self::Error> {
         let outcome = wire.outcome.unwrap_or({
             // Legacy payload (no outcome key): re-derive the benign
             // classification exactly as the pre-outcome accessors did. A
@@ -164,18 +211,22 @@ impl TryFrom<MatchResultWire> for MatchResult {
             }
         });
         MatchResult {
+            schema_version: wire.schema_version,
             order_id: wire.order_id,
             trades: wire.trades,
             remaining_quantity: wire.remaining_quantity,
             is_complete: wire.is_complete,
             filled_order_ids: wire.filled_order_ids,
             outcome,
+            status_transitions: wire.status_transitions,
+            refreshes: wire.refreshes,
+            cancelled_quantity: wire.cancelled_quantity,
         }
         .validated()
     }
 }
 
-impl MatchResult {
+impl<T> MatchResult<T> {
     /// Create a new empty match result for an incoming taker of
     /// `initial_quantity` quantity units.
     #[must_use]
@@ -186,6 +237,7 @@ impl MatchResult {
         // incomplete / NotFilled until a trade or `finalize` updates it.
         let is_complete = initial_quantity.as_u64() == 0;
         Self {
+            schema_version: SchemaVersion,
             order_id,
             trades: TradeList::new(),
             remaining_quantity: initial_quantity.as_u64(),
@@ -196,6 +248,9 @@ impl MatchResult {
             } else {
                 MatchOutcome::NotFilled
             },
+            status_transitions: Vec::new(),
+            refreshes: Vec::new(),
+            cancelled_quantity: 0,
         }
     }
 
@@ -213,6 +268,7 @@ impl MatchResult {
         // Same zero-quantity consistency as `new` (see there).
         let is_complete = initial_quantity.as_u64() == 0;
         Self {
+            schema_version: SchemaVersion,
             order_id,
             trades: TradeList::with_capacity(capacity),
             remaining_quantity: initial_quantity.as_u64(),
@@ -223,10 +279,41 @@ impl MatchResult {
             } else {
                 MatchOutcome::NotFilled
             },
+            status_transitions: Vec::new(),
+            refreshes: Vec::new(),
+            cancelled_quantity: 0,
         }
     }
 
-    /// Add a trade to this match result.
+    /// Create a new empty match result that records into `trades` and
+    /// `filled_order_ids`, which must be empty: buffers recycled by an
+    /// [`OrderPool`](crate::OrderPool). The trades get room for `capacity`
+    /// entries, inline when it is small enough.
+    #[cfg(feature = "pool")]
+    pub(crate) fn with_buffers(
+        order_id: Id,
+        initial_quantity: Quantity,
+        capacity: usize,
+        trades: Vec<Trade<T>>,
+        filled_order_ids: Vec<Id>,
+    ) -> Self {
+        debug_assert!(trades.is_empty() && filled_order_ids.is_empty());
+        let mut result = Self::new(order_id, initial_quantity);
+        result.trades = TradeList::from_vec(trades);
+        result.trades.reserve(capacity);
+        result.filled_order_ids = filled_order_ids;
+        result
+    }
+
+    /// The heap trade vector (empty and unallocated while the trades were
+    /// still inline) and the filled-id vector, for an
+    /// [`OrderPool`](crate::OrderPool) to recycle.
+    #[cfg(feature = "pool")]
+    pub(crate) fn into_buffers(self) -> (Vec<Trade<T>>, Vec<Id>) {
+        (self.trades.into_heap_vec(), self.filled_order_ids)
+    }
+
+    /// Add a trade to this match result
     ///
     /// # Errors
     ///
@@ -235,7 +322,16 @@ impl MatchResult {
     /// would underflow), which indicates an over-fill bug in the caller, or if
     /// the trade's taker order id differs from this result's incoming order id
     /// (a trade can only belong to the taker that initiated the match).
-    pub fn add_trade(&mut self, trade: Trade) -> Result<(), PriceLevelError> {
+    pub fn add_trade(&mut self, trade: Trade<T>) -> Result<(), PriceLevelError> {
+        self.add_trade_with_fees(trade, TradeFees::default())
+    }
+
+    /// [`Self::add_trade`] with `fees` attached to the trade.
+    pub(crate) fn add_trade_with_fees(
+        &mut self,
+        trade: Trade<T>,
+        fees: TradeFees,
+    ) -> Result<(), PriceLevelError> {
         if trade.taker_order_id() != self.order_id {
             return Err(PriceLevelError::InvalidOperation {
                 message: format!(
@@ -264,7 +360,7 @@ impl MatchResult {
         } else {
             MatchOutcome::PartiallyFilled
         };
-        self.trades.add(trade);
+        self.trades.add_with_fees(trade, fees);
         Ok(())
     }
 
@@ -273,6 +369,16 @@ impl MatchResult {
         self.filled_order_ids.push(order_id);
     }
 
+    /// Record a status change of a resting order this match traded against.
+    pub fn add_status_transition(&mut self, transition: OrderStatusTransition) {
+        self.status_transitions.push(transition);
+    }
+
+    /// Record a refresh of a resting order this match made.
+    pub fn add_refresh(&mut self, refresh: OrderRefresh) {
+        self.refreshes.push(refresh);
+    }
+
     /// Returns the ID of the incoming order that initiated the match.
     #[must_use]
     pub fn order_id(&self) -> Id {
@@ -281,7 +387,7 @@ impl MatchResult {
 
     /// Returns a reference to the list of trades.
     #[must_use]
-    pub fn trades(&self) -> &TradeList {
+    pub fn trades(&self) -> &TradeList<T> {
         &self.trades
     }
 
@@ -292,6 +398,18 @@ impl MatchResult {
         Quantity::new(self.remaining_quantity)
     }
 
+    /// Returns the part of the remaining quantity that was cancelled rather
+    /// than left open for the caller to rest, in quantity units.
+    ///
+    /// [`PriceLevel::match_taker`](crate::PriceLevel::match_taker) cancels
+    /// the whole remainder of an IOC or FOK taker, including a killed one,
+    /// and of a market taker, which never rests. Every other matching path
+    /// reports zero and leaves the remainder to the caller.
+    #[must_use]
+    pub fn cancelled_quantity(&self) -> Quantity {
+        Quantity::new(self.cancelled_quantity)
+    }
+
     /// Returns whether the order was completely filled.
     #[must_use]
     pub fn is_complete(&self) -> bool {
@@ -304,6 +422,28 @@ impl MatchResult {
         &self.filled_order_ids
     }
 
+    /// Returns the status changes of the resting orders this match traded
+    /// against, in trade order.
+    ///
+    /// A maker's first fill reports `New -> PartiallyFilled`, and the fill
+    /// that removes it reports `-> Filled` from whichever status it had, so
+    /// a maker filled in one trade reports only `New -> Filled`.
+    #[must_use]
+    pub fn status_transitions(&self) -> &[OrderStatusTransition] {
+        &self.status_transitions
+    }
+
+    /// Returns the iceberg and reserve refreshes this match made, in sweep
+    /// order.
+    ///
+    /// Each one reports whether the refreshed order kept its queue slot or
+    /// was re-queued to the back, under the level's
+    /// [`RefreshPriority`](crate::RefreshPriority).
+    #[must_use]
+    pub fn refreshes(&self) -> &[OrderRefresh] {
+        &self.refreshes
+    }
+
     /// Returns the terminal classification of this match.
     ///
     /// See [`MatchOutcome`] for the full set of cases and how they relate to
@@ -333,6 +473,30 @@ impl MatchResult {
         self.outcome.was_rejected()
     }
 
+    /// Attach maker and taker fees from `schedule` to every trade in this
+    /// result.
+    ///
+    /// Trades keep their order, quantities and ids; only their
+    /// [`TradeList::trade_fees`] are (re)written, so a schedule can be applied after
+    /// [`PriceLevel::match_order`](crate::PriceLevel::match_order) returns
+    /// without rebuilding the result. Either every trade gets fees or, on
+    /// error, none is modified.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if a fee computation
+    /// overflows (see [`FeeSchedule::fee_for`]).
+    pub fn apply_fee_schedule(&mut self, schedule: &FeeSchedule) -> Result<(), PriceLevelError> {
+        let fees = self
+            .trades
+            .as_slice()
+            .iter()
+            .map(|trade| schedule.trade_fees(trade))
+            .collect::<Result<Vec<_>, PriceLevelError>>()?;
+        self.trades.set_fees(fees);
+        Ok(())
+    }
+
     /// Sets the final remaining quantity, completion flag, and outcome.
     ///
     /// This is used internally by the matching engine after the matching loop
@@ -361,6 +525,8 @@ impl MatchResult {
     pub(crate) fn mark_killed(&mut self, incoming_quantity: u64) {
         self.trades = TradeList::new();
         self.filled_order_ids.clear();
+        self.status_transitions.clear();
+        self.refreshes.clear();
         self.remaining_quantity = incoming_quantity;
         self.is_complete = false;
         self.outcome = MatchOutcome::Killed;
@@ -374,11 +540,19 @@ impl MatchResult {
     pub(crate) fn mark_rejected(&mut self, incoming_quantity: u64) {
         self.trades = TradeList::new();
         self.filled_order_ids.clear();
+        self.status_transitions.clear();
+        self.refreshes.clear();
         self.remaining_quantity = incoming_quantity;
         self.is_complete = false;
         self.outcome = MatchOutcome::Rejected;
     }
 
+    /// Reports the whole remaining quantity as cancelled. Used internally by
+    /// the matching engine for a taker whose time in force forbids resting.
+    pub(crate) fn cancel_remainder(&mut self) {
+        self.cancelled_quantity = self.remaining_quantity;
+    }
+
     /// Get the total executed quantity, in quantity units.
     ///
     /// # Errors
@@ -387,7 +561,7 @@ impl MatchResult {
     /// quantities overflows `u64`.
     pub fn executed_quantity(&self) -> Result<Quantity, PriceLevelError> {
         self.trades
-            .as_vec()
+            .as_slice()
             .iter()
             .try_fold(0u64, |acc, trade| {
                 acc.checked_add(trade.quantity().as_u64()).ok_or_else(|| {
@@ -407,7 +581,7 @@ impl MatchResult {
     /// `price * quantity` product overflows `u128`, or if accumulating those
     /// products overflows `u128`.
     pub fn executed_value(&self) -> Result<u128, PriceLevelError> {
-        self.trades.as_vec().iter().try_fold(0u128, |acc, trade| {
+        self.trades.as_slice().iter().try_fold(0u128, |acc, trade| {
             let trade_value = trade
                 .price()
                 .as_u128()
@@ -442,6 +616,97 @@ impl MatchResult {
         }
     }
 
+    /// Consolidate the trades into one [`TapePrint`] per price, for public
+    /// tape reporting that must not disclose individual maker fills.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the total quantity at
+    /// any price overflows `u64`.
+    pub fn consolidated_tape(&self) -> Result<Vec<TapePrint>, PriceLevelError> {
+        TapePrint::consolidate(self.trades.as_slice())
+    }
+
+    /// Fold the trades into one [`MakerFill`] per maker order, in the order
+    /// each maker first traded, for per-counterparty accounting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if a maker's total
+    /// quantity overflows `u64` or its total value overflows `u128`.
+    pub fn fills_by_maker(&self) -> Result<Vec<MakerFill>, PriceLevelError> {
+        MakerFill::by_maker(self.trades.as_slice())
+    }
+
+    /// Append `other`, a later match of the same taker, to this result.
+    ///
+    /// Matching a taker across several levels, or re-matching its remainder,
+    /// yields one result per call; merging them gives the whole execution,
+    /// so [`Self::executed_quantity`], [`Self::average_price`] and
+    /// [`Self::fills_by_maker`] report the totals. The trades, filled order
+    /// ids, status transitions and refreshes of `other` are appended after
+    /// this result's, the remaining quantity drops by what `other` executed,
+    /// and the outcome is re-derived. A merge that executed nothing keeps a
+    /// kill or rejection, the later one winning. The cancelled quantity is
+    /// `other`'s, since its remainder is the merged one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `other` belongs to a
+    /// different taker, or if it executed more than this result has
+    /// remaining. On error `self` is left unchanged.
+    pub fn merge(&mut self, other: MatchResult<T>) -> Result<(), PriceLevelError> {
+        if other.order_id != self.order_id {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!(
+                    "cannot merge the result of order {} into the result of order {}",
+                    other.order_id, self.order_id
+                ),
+            });
+        }
+        let executed = other.executed_quantity()?.as_u64();
+        let remaining_quantity =
+            self.remaining_quantity
+                .checked_sub(executed)
+                .ok_or_else(|| PriceLevelError::InvalidOperation {
+                    message: format!(
+                        "merged executed quantity {executed} exceeds remaining quantity {}",
+                        self.remaining_quantity
+                    ),
+                })?;
+
+        let MatchResult {
+            trades,
+            filled_order_ids,
+            outcome,
+            status_transitions,
+            refreshes,
+            cancelled_quantity,
+            ..
+        } = other;
+        self.trades.append(trades);
+        self.filled_order_ids.extend(filled_order_ids);
+        self.status_transitions.extend(status_transitions);
+        self.refreshes.extend(refreshes);
+
+        let turned_away = if outcome.was_killed() || outcome.was_rejected() {
+            Some(outcome)
+        } else if self.outcome.was_killed() || self.outcome.was_rejected() {
+            Some(self.outcome)
+        } else {
+            None
+        };
+        self.finalize(Quantity::new(remaining_quantity));
+        if let Some(outcome) = turned_away
+            && self.trades.is_empty()
+            && remaining_quantity > 0
+        {
+            self.outcome = outcome;
+        }
+        self.cancelled_quantity = cancelled_quantity.min(remaining_quantity);
+        Ok(())
+    }
+
     /// Consumes `self`, returning it only if it satisfies the invariants a
     /// public-API-built [`MatchResult`] always upholds — the single validation
     /// gate both decoders ([`FromStr`] and `Deserialize` via
@@ -461,14 +726,20 @@ impl MatchResult {
     ///    overflow either.
     /// 3. **A killed / rejected result carries nothing:** a
     ///    [`MatchOutcome::Killed`] or [`MatchOutcome::Rejected`] outcome — both
-    ///    decided before any sweep — has no trades and no filled order ids, as
-    ///    [`Self::mark_killed`] / [`Self::mark_rejected`] enforce.
+    ///    decided before any sweep — has no trades, no filled order ids and no
+    ///    refreshes, as [`Self::mark_killed`] / [`Self::mark_rejected`]
+    ///    enforce.
     /// 4. **Filled ids are backed by trades:** every id in `filled_order_ids`
     ///    appears as a `maker_order_id` of some trade, because the engine only
     ///    records a filled id for a maker it just traded against
     ///    (`PriceLevel::match_order`). The reverse does not hold — a partially
     ///    filled maker trades without being recorded as filled — so this is a
     ///    one-directional subset check, not equality.
+    /// 5. **Status transitions name traded makers:** every transition's order
+    ///    id appears as a `maker_order_id` of some trade, because the engine
+    ///    records one only for a maker it just traded against.
+    /// 6. **Only the remainder is cancelled:** `cancelled_quantity` does not
+    ///    exceed `remaining_quantity`.
     ///
     /// # Errors
     ///
@@ -515,17 +786,19 @@ impl MatchResult {
                 self.remaining_quantity > 0
                     && self.trades.is_empty()
                     && self.filled_order_ids.is_empty()
+                    && self.refreshes.is_empty()
             }
         };
         if !outcome_consistent {
             return Err(PriceLevelError::InvalidOperation {
                 message: format!(
                     "{:?} outcome contradicts the decoded fields \
-                     (remaining_quantity {}, {} trade(s), {} filled id(s))",
+                     (remaining_quantity {}, {} trade(s), {} filled id(s), {} refresh(es))",
                     self.outcome,
                     self.remaining_quantity,
                     self.trades.len(),
-                    self.filled_order_ids.len()
+                    self.filled_order_ids.len(),
+                    self.refreshes.len()
                 ),
             });
         }
@@ -535,7 +808,7 @@ impl MatchResult {
         //    API-built values stay aligned.
         if let Some(alien) = self
             .trades
-            .as_vec()
+            .as_slice()
             .iter()
             .find(|trade| trade.taker_order_id() != self.order_id)
         {
@@ -558,7 +831,7 @@ impl MatchResult {
             let mut seen = std::collections::HashSet::with_capacity(self.filled_order_ids.len());
             let mut makers = self
                 .trades
-                .as_vec()
+                .as_slice()
                 .iter()
                 .map(|trade| trade.maker_order_id());
             for filled in &self.filled_order_ids {
@@ -577,11 +850,35 @@ impl MatchResult {
             }
         }
 
+        // 6. Every status transition belongs to a maker the result traded
+        //    against.
+        if let Some(orphan) = self.status_transitions.iter().find(|transition| {
+            !self
+                .trades
+                .as_slice()
+                .iter()
+                .any(|trade| trade.maker_order_id() == transition.order_id)
+        }) {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!("status transition {orphan} does not name a maker of the trades"),
+            });
+        }
+
+        // 7. Only quantity that remains can have been cancelled.
+        if self.cancelled_quantity > self.remaining_quantity {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!(
+                    "cancelled_quantity ({}) exceeds remaining_quantity ({})",
+                    self.cancelled_quantity, self.remaining_quantity
+                ),
+            });
+        }
+
         Ok(self)
     }
 }
 
-impl fmt::Display for MatchResult {
+impl<T> fmt::Display for MatchResult<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -600,7 +897,7 @@ impl fmt::Display for MatchResult {
     }
 }
 
-impl FromStr for MatchResult {
+impl<T: Default> FromStr for MatchResult<T> {
     type Err = PriceLevelError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -808,7 +1105,8 @@ impl FromStr for MatchResult {
                     value: is_complete_str.to_string(),
                 })?;
 
-        let trades = TradeList::from_str(trades_str)?;
+        // Nor does it carry maker extra fields: each trade gets the default.
+        let trades = TradeList::from_str(trades_str)?.map_maker_extra_fields(|()| T::default());
 
         let filled_order_ids = if filled_order_ids_str == "[]" {
             Vec::new()
@@ -835,7 +1133,9 @@ impl FromStr for MatchResult {
         // fields. A `Killed` / `Rejected` outcome cannot be recovered from text
         // (it is indistinguishable from `NotFilled` once the trades are gone);
         // callers that need that distinction must use the in-memory result or
-        // the JSON (serde) representation, which preserves `outcome`.
+        // the JSON (serde) representation, which preserves `outcome`. Status
+        // transitions, refreshes and the cancelled quantity are likewise
+        // serde-only.
         let outcome = if is_complete {
             MatchOutcome::Filled
         } else if trades.is_empty() {
@@ -850,12 +1150,16 @@ impl FromStr for MatchResult {
         // remainder, trade quantities that overflow, or a filled id absent from
         // the trades) is rejected rather than accepted.
         MatchResult {
+            schema_version: SchemaVersion,
             order_id,
             trades,
             remaining_quantity,
             is_complete,
             filled_order_ids,
             outcome,
+            status_transitions: Vec::new(),
+            refreshes: Vec::new(),
+            cancelled_quantity: 0,
         }
         .validated()
     }
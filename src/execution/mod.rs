@@ -9,6 +9,12 @@
 //! - [`TradeList`] — an append-only, ordered collection of trades.
 //! - [`MatchResult`] — the full outcome of a matching operation, including trades,
 //!   remaining quantity, completion status, and filled order IDs.
+//! - [`MatchResultBuilder`] — assembles a [`MatchResult`] outside the engine,
+//!   enforcing a taker side and attaching fees from a [`FeeSchedule`].
+//! - [`LiquidityFlag`], [`Fee`], [`FeeCurrency`], [`FeeSchedule`], [`TradeFees`] —
+//!   maker / taker classification and the optional fees kept per trade.
+//! - [`TapePrint`] — per-price aggregate of a match's trades for public tape
+//!   reporting, disclosing no order ids.
 //!
 //! # Checked Arithmetic
 //!
@@ -24,12 +30,25 @@
 
 mod trade;
 
+mod builder;
+mod export;
+mod fee;
+mod fills;
+
 mod list;
 mod match_result;
+mod schema;
 mod taker;
+mod tape;
 mod tests;
 
-pub use list::TradeList;
+pub use builder::MatchResultBuilder;
+pub use export::TRADE_EXPORT_COLUMNS;
+pub use fee::{Fee, FeeCurrency, FeeSchedule, LiquidityFlag, TradeFees};
+pub use fills::MakerFill;
+pub use list::{TRADE_LIST_INLINE_CAPACITY, TradeList};
 pub use match_result::{MatchOutcome, MatchResult};
+pub use schema::EXECUTION_SCHEMA_VERSION;
 pub use taker::TakerKind;
+pub use tape::TapePrint;
 pub use trade::Trade;
@@ -0,0 +1,51 @@
+//! Schema versioning of the serialized execution domain.
+//!
+//! Every serde payload of a [`Trade`](crate::Trade),
+//! [`TradeList`](crate::TradeList) and [`MatchResult`](crate::MatchResult)
+//! carries a `schema_version` field, so a consumer reading persisted or
+//! transported match outcomes can tell which layout wrote them.
+
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// Schema version written in the `schema_version` field of every serialized
+/// [`Trade`](crate::Trade), [`TradeList`](crate::TradeList) and
+/// [`MatchResult`](crate::MatchResult).
+///
+/// Bumped whenever a field is added, removed or changes meaning. Decoding
+/// accepts every version up to this one: a payload written before the field
+/// existed decodes as version `1`, and fields added since are
+/// `#[serde(default)]`. A payload from a newer schema is rejected rather than
+/// silently decoded with its new fields dropped.
+pub const EXECUTION_SCHEMA_VERSION: u32 = 1;
+
+/// Zero-sized `schema_version` field: serializes as
+/// [`EXECUTION_SCHEMA_VERSION`] and, on decode, checks the version it reads
+/// is one this build understands. `#[serde(default)]` on the field accepts
+/// payloads written before versioning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub(crate) struct SchemaVersion;
+
+impl Serialize for SchemaVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u32(EXECUTION_SCHEMA_VERSION)
+    }
+}
+
+impl<'de> Deserialize<'de> for SchemaVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let version = u32::deserialize(deserializer)?;
+        if version == 0 || version > EXECUTION_SCHEMA_VERSION {
+            return Err(de::Error::custom(format!(
+                "unsupported execution schema version {version}; this build reads versions 1 to {EXECUTION_SCHEMA_VERSION}"
+            )));
+        }
+        Ok(SchemaVersion)
+    }
+}
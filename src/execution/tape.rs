@@ -0,0 +1,140 @@
+//! Per-price consolidation of trades for public tape reporting.
+//!
+//! A public trade tape discloses how much traded at each price, not which
+//! maker orders filled. [`TapePrint::consolidate`] (or
+//! [`MatchResult::consolidated_tape`](crate::MatchResult::consolidated_tape))
+//! folds the individual fills of a match into one [`TapePrint`] per price.
+
+use crate::errors::PriceLevelError;
+use crate::execution::trade::Trade;
+use crate::utils::{Price, Quantity};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The aggregate of all trades executed at one price, as reported on a public
+/// tape. Carries no order ids, so individual maker fills are not disclosed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TapePrint {
+    /// The price the trades executed at.
+    price: Price,
+    /// Total quantity traded at `price`.
+    quantity: Quantity,
+    /// Number of individual trades folded into this print.
+    trade_count: u64,
+}
+
+impl TapePrint {
+    /// Folds `trades` into one print per distinct price, in the order each
+    /// price first appears — the sweep order for a
+    /// [`MatchResult`](crate::MatchResult).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the total quantity at
+    /// any price overflows `u64`.
+    pub fn consolidate<'a, T: 'a, I>(trades: I) -> Result<Vec<Self>, PriceLevelError>
+    where
+        I: IntoIterator<Item = &'a Trade<T>>,
+    {
+        let mut prints: Vec<Self> = Vec::new();
+        for trade in trades {
+            // A match sweeps one level, so the list holds a single price in
+            // practice and a linear scan beats hashing.
+            match prints.iter_mut().find(|print| print.price == trade.price()) {
+                Some(print) => {
+                    let quantity = print
+                        .quantity
+                        .as_u64()
+                        .checked_add(trade.quantity().as_u64())
+                        .ok_or_else(|| PriceLevelError::InvalidOperation {
+                            message: "tape quantity overflow".to_string(),
+                        })?;
+                    print.quantity = Quantity::new(quantity);
+                    print.trade_count += 1;
+                }
+                None => prints.push(Self {
+                    price: trade.price(),
+                    quantity: trade.quantity(),
+                    trade_count: 1,
+                }),
+            }
+        }
+        Ok(prints)
+    }
+
+    /// Get the price the trades executed at.
+    #[must_use]
+    pub fn price(&self) -> Price {
+        self.price
+    }
+
+    /// Get the total quantity traded at this price.
+    #[must_use]
+    pub fn quantity(&self) -> Quantity {
+        self.quantity
+    }
+
+    /// Get the number of trades folded into this print.
+    #[must_use]
+    pub fn trade_count(&self) -> u64 {
+        self.trade_count
+    }
+}
+
+impl fmt::Display for TapePrint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TapePrint:price={};quantity={};trade_count={}",
+            self.price, self.quantity, self.trade_count
+        )
+    }
+}
+
+impl FromStr for TapePrint {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields_str = s
+            .strip_prefix("TapePrint:")
+            .ok_or(PriceLevelError::InvalidFormat)?;
+
+        let mut fields = std::collections::HashMap::new();
+        for field_pair in fields_str.split(';') {
+            let kv: Vec<&str> = field_pair.split('=').collect();
+            if kv.len() == 2 {
+                fields.insert(kv[0], kv[1]);
+            }
+        }
+
+        let get_field = |field: &str| -> Result<&str, PriceLevelError> {
+            match fields.get(field) {
+                Some(result) => Ok(*result),
+                None => Err(PriceLevelError::MissingField(field.to_string())),
+            }
+        };
+        let invalid = |field: &str, value: &str| PriceLevelError::InvalidFieldValue {
+            field: field.to_string(),
+            value: value.to_string(),
+        };
+
+        let price_str = get_field("price")?;
+        let price = Price::from_str(price_str).map_err(|_| invalid("price", price_str))?;
+
+        let quantity_str = get_field("quantity")?;
+        let quantity =
+            Quantity::from_str(quantity_str).map_err(|_| invalid("quantity", quantity_str))?;
+
+        let trade_count_str = get_field("trade_count")?;
+        let trade_count = trade_count_str
+            .parse::<u64>()
+            .map_err(|_| invalid("trade_count", trade_count_str))?;
+
+        Ok(Self {
+            price,
+            quantity,
+            trade_count,
+        })
+    }
+}
@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::export::TRADE_EXPORT_COLUMNS;
+    use crate::execution::fee::{Fee, FeeCurrency, TradeFees};
+    use crate::execution::list::TradeList;
+    use crate::execution::trade::Trade;
+    use crate::orders::{Id, Side};
+    use crate::utils::{Price, Quantity, TimestampMs};
+
+    fn sample_trades() -> TradeList {
+        let usd = FeeCurrency::new("USD").unwrap();
+        let plain = Trade::with_timestamp(
+            Id::from_u64(1),
+            Id::from_u64(10),
+            Id::from_u64(20),
+            Price::new(1_000),
+            Quantity::new(5),
+            Side::Buy,
+            TimestampMs::new(1_616_823_000_000),
+        );
+        let stamped = Trade::with_timestamp(
+            Id::from_u64(2),
+            Id::from_u64(10),
+            Id::from_u64(21),
+            Price::new(1_001),
+            Quantity::new(7),
+            Side::Buy,
+            TimestampMs::new(1_616_823_000_001),
+        )
+        .with_level_sequence(42)
+        .with_clock_timestamp(TimestampMs::new(1_616_823_000_005));
+        let mut trades = TradeList::from_vec(vec![plain]);
+        trades.add_with_fees(
+            stamped,
+            TradeFees::new(Some(Fee::new(-3, usd)), Some(Fee::new(9, usd))),
+        );
+        trades
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let trades = sample_trades();
+        let mut out = Vec::new();
+        trades.to_csv(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], TRADE_EXPORT_COLUMNS.join(","));
+        let first: Vec<&str> = lines[1].split(',').collect();
+        let second: Vec<&str> = lines[2].split(',').collect();
+        assert_eq!(first.len(), TRADE_EXPORT_COLUMNS.len());
+        assert_eq!(first[0], Id::from_u64(1).to_string());
+        assert_eq!(&first[3..5], &["1000", "5"]);
+        assert_eq!(first[7], "1616823000000");
+        assert!(first[8..].iter().all(|cell| cell.is_empty()));
+        assert_eq!(&second[8..13], &["-3", "USD", "9", "USD", "42"]);
+        assert_eq!(second[13], "");
+        assert_eq!(second[14], "1616823000005");
+    }
+
+    #[test]
+    fn test_csv_export_of_an_empty_list() {
+        let mut out = Vec::new();
+        TradeList::<()>::new().to_csv(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("{}\n", TRADE_EXPORT_COLUMNS.join(","))
+        );
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_round_trip() {
+        use arrow_array::{Array, Decimal128Array, StringArray, UInt64Array};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let trades = sample_trades();
+        let batch = trades.to_record_batch().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.num_columns(), TRADE_EXPORT_COLUMNS.len());
+
+        let path = std::env::temp_dir().join(format!(
+            "pricelevel-trades-{}.parquet",
+            uuid::Uuid::new_v4()
+        ));
+        trades
+            .to_parquet(std::fs::File::create(&path).unwrap())
+            .unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<_> = reader.map(Result::unwrap).collect();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(batches.len(), 1);
+        let read = &batches[0];
+        assert_eq!(read.schema(), batch.schema());
+        let column = |name: &str| read.column(read.schema().index_of(name).unwrap()).clone();
+
+        let prices = column("price");
+        let prices = prices.as_any().downcast_ref::<Decimal128Array>().unwrap();
+        assert_eq!((prices.value(0), prices.value(1)), (1_000, 1_001));
+        let maker_fees = column("maker_fee");
+        let maker_fees = maker_fees
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert!(maker_fees.is_null(0));
+        assert_eq!(maker_fees.value(1), -3);
+        let currencies = column("taker_fee_currency");
+        let currencies = currencies.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(currencies.value(1), "USD");
+        let sequences = column("level_sequence");
+        let sequences = sequences.as_any().downcast_ref::<UInt64Array>().unwrap();
+        assert!(sequences.is_null(0));
+        assert_eq!(sequences.value(1), 42);
+        let ids = column("maker_order_id");
+        let ids = ids.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(ids.value(0), Id::from_u64(20).to_string());
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_rejects_prices_beyond_38_digits() {
+        let trades = TradeList::from_vec(vec![Trade::with_timestamp(
+            Id::from_u64(1),
+            Id::from_u64(10),
+            Id::from_u64(20),
+            Price::new(u128::MAX),
+            Quantity::new(1),
+            Side::Sell,
+            TimestampMs::new(1),
+        )]);
+        assert!(matches!(
+            trades.to_record_batch(),
+            Err(crate::errors::PriceLevelError::SerializationError { .. })
+        ));
+    }
+}
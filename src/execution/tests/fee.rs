@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::builder::MatchResultBuilder;
+    use crate::execution::fee::{Fee, FeeCurrency, FeeSchedule, LiquidityFlag, TradeFees};
+    use crate::execution::list::TradeList;
+    use crate::execution::match_result::{MatchOutcome, MatchResult};
+    use crate::execution::trade::Trade;
+    use crate::orders::{Id, Side};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::str::FromStr;
+
+    fn usd() -> FeeCurrency {
+        FeeCurrency::new("USD").expect("valid currency")
+    }
+
+    fn sample_trade(maker_id: u64, quantity: u64, side: Side) -> Trade {
+        Trade::with_timestamp(
+            Id::from_u64(1_000 + maker_id),
+            Id::from_u64(10),
+            Id::from_u64(maker_id),
+            Price::new(1_000),
+            Quantity::new(quantity),
+            side,
+            TimestampMs::new(1_616_823_000_000),
+        )
+    }
+
+    #[test]
+    fn fee_currency_validation() {
+        assert_eq!(usd().as_str(), "USD");
+        assert_eq!(
+            FeeCurrency::new("USDT1234").unwrap().to_string(),
+            "USDT1234"
+        );
+        assert!(FeeCurrency::new("").is_err());
+        assert!(FeeCurrency::new("TOOLONGXX").is_err());
+        assert!(FeeCurrency::new("US D").is_err());
+        assert!(FeeCurrency::new("U;D").is_err());
+    }
+
+    #[test]
+    fn liquidity_flag_for_trade_parties() {
+        let trade = sample_trade(20, 5, Side::Buy);
+        assert_eq!(
+            trade.liquidity_for(Id::from_u64(20)),
+            Some(LiquidityFlag::Maker)
+        );
+        assert_eq!(
+            trade.liquidity_for(Id::from_u64(10)),
+            Some(LiquidityFlag::Taker)
+        );
+        assert_eq!(trade.liquidity_for(Id::from_u64(99)), None);
+        assert_eq!(LiquidityFlag::Maker.opposite(), LiquidityFlag::Taker);
+        assert_eq!(
+            LiquidityFlag::from_str("taker").unwrap(),
+            LiquidityFlag::Taker
+        );
+    }
+
+    #[test]
+    fn schedule_computes_charges_and_rebates() {
+        // 1_000 * 50 = 50_000 value; maker rebate 2 bps, taker 5 bps.
+        let schedule = FeeSchedule::new(-2, 5, usd());
+        let fees = schedule
+            .trade_fees(&sample_trade(20, 50, Side::Buy))
+            .unwrap();
+
+        let maker = fees.maker().expect("maker fee computed");
+        let taker = fees.taker().expect("taker fee computed");
+        assert_eq!(maker.amount(), -10);
+        assert!(maker.is_rebate());
+        assert_eq!(taker.amount(), 25);
+        assert_eq!(taker.currency(), usd());
+        assert_eq!(fees.fee_for(LiquidityFlag::Taker), Some(taker));
+
+        // Truncates toward zero: 1_000 * 1 * 5 / 10_000 = 0.5 -> 0.
+        let small = schedule
+            .trade_fees(&sample_trade(20, 1, Side::Buy))
+            .unwrap();
+        assert_eq!(small.taker().unwrap().amount(), 0);
+    }
+
+    #[test]
+    fn trade_list_string_round_trip_with_and_without_fees() {
+        let plain = sample_trade(20, 5, Side::Sell);
+        let unpriced = TradeList::from_vec(vec![plain]);
+        assert!(!unpriced.to_string().contains("fee"));
+        assert_eq!(
+            TradeList::from_str(&unpriced.to_string()).unwrap(),
+            unpriced
+        );
+
+        let mut priced = TradeList::new();
+        priced.add(plain);
+        priced.add_with_fees(
+            sample_trade(21, 3, Side::Sell),
+            TradeFees::new(Some(Fee::new(-3, usd())), Some(Fee::new(7, usd()))),
+        );
+        let text = priced.to_string();
+        assert!(text.contains(";maker_fee=-3@USD;taker_fee=7@USD"));
+        let restored = TradeList::from_str(&text).unwrap();
+        assert_eq!(restored, priced);
+        assert!(restored.trade_fees(0).unwrap().is_empty());
+        assert_eq!(restored.trade_fees(1).unwrap().taker().unwrap().amount(), 7);
+    }
+
+    #[test]
+    fn trade_list_json_round_trip_and_legacy_payload() {
+        let mut priced = TradeList::new();
+        priced.add_with_fees(
+            sample_trade(20, 5, Side::Buy),
+            TradeFees::new(None, Some(Fee::new(12, usd()))),
+        );
+        let json = serde_json::to_string(&priced).unwrap();
+        assert!(json.contains("\"currency\":\"USD\""));
+        assert_eq!(serde_json::from_str::<TradeList>(&json).unwrap(), priced);
+
+        let legacy = r#"{
+            "trades": [{
+                "trade_id": "00000000-0000-0001-0000-000000000000",
+                "taker_order_id": "00000000-0000-0002-0000-000000000000",
+                "maker_order_id": "00000000-0000-0003-0000-000000000000",
+                "price": 1000,
+                "quantity": 5,
+                "taker_side": "BUY",
+                "timestamp": 1616823000000
+            }]
+        }"#;
+        let trades: TradeList = serde_json::from_str(legacy).unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades.trade_fees(0), Some(TradeFees::default()));
+        assert_eq!(trades.trade_fees(1), None);
+    }
+
+    #[test]
+    fn builder_applies_schedule_and_enforces_taker_side() {
+        let mut builder: MatchResultBuilder =
+            MatchResultBuilder::new(Id::from_u64(10), Quantity::new(100))
+                .taker_side(Side::Buy)
+                .fee_schedule(FeeSchedule::new(0, 10, usd()));
+
+        builder.add_trade(sample_trade(20, 40, Side::Buy)).unwrap();
+        builder.add_filled_order_id(Id::from_u64(20));
+        assert!(builder.add_trade(sample_trade(21, 10, Side::Sell)).is_err());
+        assert_eq!(builder.remaining_quantity(), Quantity::new(60));
+
+        let result = builder.build();
+        assert_eq!(result.outcome(), MatchOutcome::PartiallyFilled);
+        assert_eq!(result.filled_order_ids(), &[Id::from_u64(20)]);
+        let fees = result.trades().trade_fees(0).unwrap();
+        assert_eq!(fees.taker().unwrap().amount(), 40);
+        assert_eq!(fees.maker().unwrap().amount(), 0);
+    }
+
+    #[test]
+    fn builder_without_trades_is_not_filled() {
+        let result =
+            MatchResultBuilder::<()>::with_capacity(Id::from_u64(10), Quantity::new(5), 4).build();
+        assert_eq!(result.outcome(), MatchOutcome::NotFilled);
+        assert!(result.trades().is_empty());
+    }
+
+    #[test]
+    fn apply_fee_schedule_to_existing_result() {
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        result.add_trade(sample_trade(20, 30, Side::Buy)).unwrap();
+        result.add_trade(sample_trade(21, 70, Side::Buy)).unwrap();
+
+        result
+            .apply_fee_schedule(&FeeSchedule::new(-1, 3, usd()))
+            .unwrap();
+        let fees: Vec<i128> = (0..result.trades().len())
+            .map(|i| {
+                result
+                    .trades()
+                    .trade_fees(i)
+                    .unwrap()
+                    .taker()
+                    .unwrap()
+                    .amount()
+            })
+            .collect();
+        assert_eq!(fees, vec![9, 21]);
+        assert_eq!(result.outcome(), MatchOutcome::Filled);
+
+        let restored = MatchResult::<()>::from_str(&result.to_string()).unwrap();
+        assert_eq!(restored.trades(), result.trades());
+    }
+}
@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::TakerKind;
+    use crate::execution::fills::MakerFill;
+    use crate::execution::match_result::{MatchOutcome, MatchResult};
+    use crate::execution::trade::Trade;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::str::FromStr;
+
+    fn trade(trade_id: u64, maker_id: u64, price: u128, quantity: u64) -> Trade {
+        Trade::with_timestamp(
+            Id::from_u64(1_000 + trade_id),
+            Id::from_u64(10),
+            Id::from_u64(maker_id),
+            Price::new(price),
+            Quantity::new(quantity),
+            Side::Buy,
+            TimestampMs::new(1_616_823_000_000),
+        )
+    }
+
+    fn match_result(initial: u64, trades: &[Trade]) -> MatchResult {
+        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(initial));
+        for trade in trades {
+            result.add_trade(*trade).unwrap();
+        }
+        result.finalize(Quantity::new(
+            initial - trades.iter().map(|t| t.quantity().as_u64()).sum::<u64>(),
+        ));
+        result
+    }
+
+    fn resting_sell(level: &PriceLevel, id: u64, quantity: u64) {
+        level
+            .add_order(OrderType::Standard {
+                id: Id::from_u64(id),
+                price: Price::new(level.price()),
+                quantity: Quantity::new(quantity),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1_616_823_000_000),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_fills_by_maker_folds_repeated_makers() {
+        let result = match_result(
+            100,
+            &[
+                trade(1, 7, 100, 10),
+                trade(2, 8, 100, 5),
+                trade(3, 7, 101, 20),
+            ],
+        );
+
+        let fills = result.fills_by_maker().unwrap();
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0].maker_order_id(), Id::from_u64(7));
+        assert_eq!(fills[0].quantity(), Quantity::new(30));
+        assert_eq!(fills[0].value(), 100 * 10 + 101 * 20);
+        assert_eq!(fills[0].trade_count(), 2);
+        assert_eq!(fills[0].average_price(), Some(3_020.0 / 30.0));
+        assert_eq!(fills[1].maker_order_id(), Id::from_u64(8));
+        assert_eq!(fills[1].trade_count(), 1);
+
+        assert!(
+            MakerFill::by_maker(std::iter::empty::<&Trade>())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_maker_fill_display_round_trip() {
+        let fill = MakerFill::by_maker(&[trade(1, 7, 100, 10)]).unwrap()[0];
+        let text = fill.to_string();
+        assert!(text.starts_with("MakerFill:maker_order_id="));
+        assert_eq!(MakerFill::from_str(&text).unwrap(), fill);
+
+        let json = serde_json::to_string(&fill).unwrap();
+        assert_eq!(serde_json::from_str::<MakerFill>(&json).unwrap(), fill);
+
+        assert!(matches!(
+            MakerFill::from_str("TapePrint:price=1"),
+            Err(PriceLevelError::InvalidFormat)
+        ));
+        assert!(matches!(
+            MakerFill::from_str(&text.replace("value=1000", "value=x")),
+            Err(PriceLevelError::InvalidFieldValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_merge_chains_results() {
+        let mut first = match_result(100, &[trade(1, 7, 100, 40)]);
+        let second = match_result(60, &[trade(2, 8, 101, 60)]);
+
+        first.merge(second).unwrap();
+        assert_eq!(first.trades().len(), 2);
+        assert_eq!(first.remaining_quantity(), Quantity::new(0));
+        assert!(first.is_complete());
+        assert_eq!(first.outcome(), MatchOutcome::Filled);
+        assert_eq!(first.executed_quantity().unwrap(), Quantity::new(100));
+        assert_eq!(
+            first.average_price().unwrap(),
+            Some((100.0 * 40.0 + 101.0 * 60.0) / 100.0)
+        );
+    }
+
+    #[test]
+    fn test_merge_rejects_foreign_and_overfilling_results() {
+        let mut result = match_result(50, &[trade(1, 7, 100, 40)]);
+        let before = result.clone().to_string();
+
+        let foreign = MatchResult::new(Id::from_u64(11), Quantity::new(10));
+        assert!(matches!(
+            result.merge(foreign),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        let overfill = match_result(20, &[trade(2, 8, 100, 20)]);
+        assert!(matches!(
+            result.merge(overfill),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert_eq!(result.to_string(), before);
+    }
+
+    #[test]
+    fn test_merge_keeps_a_kill_without_trades() {
+        let mut result = MatchResult::<()>::new(Id::from_u64(10), Quantity::new(50));
+        result.finalize(Quantity::new(50));
+        let mut killed = MatchResult::new(Id::from_u64(10), Quantity::new(50));
+        killed.mark_killed(50);
+
+        result.merge(killed).unwrap();
+        assert!(result.was_killed());
+
+        result
+            .merge(match_result(50, &[trade(1, 7, 100, 10)]))
+            .unwrap();
+        assert_eq!(result.outcome(), MatchOutcome::PartiallyFilled);
+        assert_eq!(result.remaining_quantity(), Quantity::new(40));
+    }
+
+    #[test]
+    fn test_merge_takes_the_later_cancellation() {
+        let mut result = match_result(100, &[trade(1, 7, 100, 40)]);
+        let mut last = match_result(60, &[trade(2, 8, 101, 20)]);
+        last.cancel_remainder();
+
+        result.merge(last).unwrap();
+        assert_eq!(result.remaining_quantity(), Quantity::new(40));
+        assert_eq!(result.cancelled_quantity(), Quantity::new(40));
+
+        result
+            .merge(match_result(40, &[trade(3, 9, 102, 10)]))
+            .unwrap();
+        assert_eq!(result.cancelled_quantity(), Quantity::new(0));
+    }
+
+    #[test]
+    fn test_merge_across_levels() {
+        let generator = SequentialIdGenerator::new(0);
+        let first_level: PriceLevel = PriceLevel::new(100);
+        let second_level: PriceLevel = PriceLevel::new(101);
+        resting_sell(&first_level, 1, 30);
+        resting_sell(&first_level, 2, 20);
+        resting_sell(&second_level, 3, 40);
+
+        let taker = Id::from_u64(99);
+        let timestamp = TimestampMs::new(1_616_823_000_100);
+        let mut result = first_level.match_order(
+            80,
+            taker,
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            timestamp,
+            &generator,
+        );
+        let remaining = result.remaining_quantity().as_u64();
+        let next = second_level.match_order(
+            remaining,
+            taker,
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            timestamp,
+            &generator,
+        );
+        result.merge(next).unwrap();
+
+        assert!(result.is_complete());
+        assert_eq!(result.executed_quantity().unwrap(), Quantity::new(80));
+        assert_eq!(
+            result.filled_order_ids(),
+            &[Id::from_u64(1), Id::from_u64(2)]
+        );
+        let fills = result.fills_by_maker().unwrap();
+        let quantities: Vec<u64> = fills.iter().map(|f| f.quantity().as_u64()).collect();
+        assert_eq!(quantities, vec![30, 20, 30]);
+        assert_eq!(result.average_price().unwrap(), Some(8_030.0 / 80.0));
+    }
+}
@@ -58,12 +58,14 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_transaction_list_as_vec() {
         let transactions = create_test_transactions();
         let list = TransactionList::from_vec(transactions.clone());
         let vec_ref = list.as_vec();
 
         assert_eq!(vec_ref, &transactions);
+        assert_eq!(list.as_slice(), vec_ref);
     }
 
     #[test]
@@ -474,6 +476,7 @@ mod transaction_list_serialization_tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_as_vec() {
         let list = create_test_transaction_list();
         let vec_ref = list.as_vec();
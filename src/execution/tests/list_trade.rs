@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::execution::list::TradeList;
+    use crate::execution::list::{TRADE_LIST_INLINE_CAPACITY, TradeList};
     use crate::execution::trade::Trade;
     use crate::orders::{Id, Side};
     use crate::utils::{Price, Quantity, TimestampMs};
@@ -41,7 +41,10 @@ mod tests {
         };
 
         assert_eq!(parsed.len(), 1);
-        assert_eq!(parsed.as_vec()[0].trade_id(), list.as_vec()[0].trade_id());
+        assert_eq!(
+            parsed.as_slice()[0].trade_id(),
+            list.as_slice()[0].trade_id()
+        );
     }
 
     #[test]
@@ -49,4 +52,46 @@ mod tests {
         let result = TradeList::from_str("Transactions:[]");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn trade_list_stays_inline_up_to_its_inline_capacity() {
+        let mut list = TradeList::with_capacity(TRADE_LIST_INLINE_CAPACITY);
+        for _ in 0..TRADE_LIST_INLINE_CAPACITY {
+            list.add(sample_trade());
+        }
+        assert!(!list.spilled());
+        assert_eq!(list.capacity(), TRADE_LIST_INLINE_CAPACITY);
+
+        list.add(sample_trade());
+        assert!(list.spilled());
+        assert_eq!(list.len(), TRADE_LIST_INLINE_CAPACITY + 1);
+        assert!(list.as_slice().iter().all(|trade| *trade == sample_trade()));
+    }
+
+    #[test]
+    fn trade_list_from_vec_keeps_a_large_allocation() {
+        let trades = vec![sample_trade(); TRADE_LIST_INLINE_CAPACITY + 2];
+        let pointer = trades.as_ptr();
+        let list = TradeList::from_vec(trades);
+        assert!(list.spilled());
+        assert_eq!(list.as_slice().as_ptr(), pointer);
+        let trades = list.into_vec();
+        assert_eq!(trades.as_ptr(), pointer);
+
+        let small = TradeList::from_vec(vec![sample_trade()]);
+        assert!(!small.spilled());
+        assert_eq!(small.into_vec(), vec![sample_trade()]);
+    }
+
+    #[test]
+    fn trade_list_serializes_as_a_plain_sequence() {
+        for count in [0, 1, TRADE_LIST_INLINE_CAPACITY + 1] {
+            let trades = vec![sample_trade(); count];
+            let list = TradeList::from_vec(trades.clone());
+            let json = serde_json::to_value(&list).unwrap();
+            assert_eq!(json["trades"], serde_json::to_value(&trades).unwrap());
+            let back: TradeList = serde_json::from_value(json).unwrap();
+            assert_eq!(back, list);
+        }
+    }
 }
@@ -252,8 +252,8 @@ mod tests {
 
         // Verify transactions (need to check each one since Transaction might not implement PartialEq)
         assert_eq!(parsed.transactions.len(), original.transactions.len());
-        for (i, transaction) in original.transactions.as_vec().iter().enumerate() {
-            let parsed_transaction = &parsed.transactions.as_vec()[i];
+        for (i, transaction) in original.transactions.as_slice().iter().enumerate() {
+            let parsed_transaction = &parsed.transactions.as_slice()[i];
             assert_eq!(
                 parsed_transaction.transaction_id,
                 transaction.transaction_id
@@ -336,8 +336,8 @@ mod tests {
         let result = MatchResult::from_str(input).unwrap();
 
         assert_eq!(result.transactions.len(), 2);
-        let transaction1 = &result.transactions.as_vec()[0];
-        let transaction2 = &result.transactions.as_vec()[1];
+        let transaction1 = &result.transactions.as_slice()[0];
+        let transaction2 = &result.transactions.as_slice()[1];
 
         assert_eq!(transaction1.quantity, 30);
         assert_eq!(transaction2.quantity, 40);
@@ -3,7 +3,9 @@ mod tests {
     use crate::execution::list::TradeList;
     use crate::execution::match_result::{MatchOutcome, MatchResult};
     use crate::execution::trade::Trade;
-    use crate::orders::{Id, Side};
+    use crate::orders::{
+        Id, OrderRefresh, OrderStatus, OrderStatusTransition, RefreshPriority, Side,
+    };
     use crate::utils::{Price, Quantity, TimestampMs};
     use std::str::FromStr;
     use uuid::Uuid;
@@ -33,7 +35,7 @@ mod tests {
 
     #[test]
     fn add_trade_updates_remaining_and_trades() {
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
         assert!(result.add_trade(sample_trade(25)).is_ok());
 
         assert_eq!(result.remaining_quantity().as_u64(), 75);
@@ -43,13 +45,13 @@ mod tests {
 
     #[test]
     fn display_and_parse_use_trades_field() {
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
         assert!(result.add_trade(sample_trade(40)).is_ok());
 
         let rendered = result.to_string();
         assert!(rendered.contains(";trades=Trades:[Trade:"));
 
-        let parsed = match MatchResult::from_str(&rendered) {
+        let parsed = match MatchResult::<()>::from_str(&rendered) {
             Ok(value) => value,
             Err(error) => panic!("failed to parse match result: {error:?}"),
         };
@@ -61,7 +63,7 @@ mod tests {
     #[test]
     fn add_trade_keeps_outcome_in_sync() {
         // No trades yet -> the default benign classification.
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
         assert_eq!(result.outcome(), MatchOutcome::NotFilled);
 
         // Partial fill.
@@ -78,7 +80,7 @@ mod tests {
 
     #[test]
     fn outcome_survives_serde_json_roundtrip() {
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
         assert!(result.add_trade(sample_trade(40)).is_ok());
 
         let json = serde_json::to_string(&result).expect("serialize match result");
@@ -94,7 +96,7 @@ mod tests {
         // A JSON payload written before the `outcome` field existed must still
         // deserialize (the field is `#[serde(default)]`). Build a current JSON,
         // then strip the `outcome` key to emulate the legacy shape.
-        let result = MatchResult::new(Id::from_u64(10), Quantity::new(70));
+        let result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(70));
         let mut value: serde_json::Value =
             serde_json::to_value(&result).expect("serialize match result");
         value
@@ -113,13 +115,13 @@ mod tests {
     #[test]
     fn from_str_rejects_old_transactions_field() {
         let old_payload = "MatchResult:order_id=1;remaining_quantity=1;is_complete=false;transactions=Transactions:[];filled_order_ids=[]";
-        let parsed = MatchResult::from_str(old_payload);
+        let parsed = MatchResult::<()>::from_str(old_payload);
         assert!(parsed.is_err());
     }
 
     #[test]
     fn add_trade_rejects_underflow() {
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(10));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(10));
         let error = result.add_trade(sample_trade(11));
         assert!(error.is_err());
         assert_eq!(result.remaining_quantity().as_u64(), 10);
@@ -128,7 +130,7 @@ mod tests {
 
     #[test]
     fn executed_value_rejects_overflow() {
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(4));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(4));
 
         let trade = Trade::with_timestamp(
             Id::from_uuid(parse_uuid("6ba7b810-9dad-11d1-80b4-00c04fd430c8")),
@@ -158,7 +160,7 @@ mod tests {
     /// (never an error, never a division by zero, never NaN).
     #[test]
     fn test_average_price_zero_executed_quantity_returns_none() {
-        let result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
 
         match result.average_price() {
             Ok(None) => {}
@@ -171,7 +173,7 @@ mod tests {
     /// never NaN/Inf.
     #[test]
     fn test_average_price_exact_small_values_is_precise() {
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
 
         // price 1000, quantity 30 -> value 30_000, avg 1000.0 exactly.
         assert!(result.add_trade(sample_trade(30)).is_ok());
@@ -205,7 +207,7 @@ mod tests {
         const PRICE: u128 = (1_u128 << 53) + 1; // 9_007_199_254_740_993
         const EXACT_INT_AVG: u128 = PRICE; // quantity == 1, so average == price
 
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(1));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(1));
         let trade = Trade::with_timestamp(
             Id::from_uuid(parse_uuid("6ba7b810-9dad-11d1-80b4-00c04fd430c8")),
             Id::from_u64(10),
@@ -260,7 +262,8 @@ mod tests {
         ];
 
         for (idx, (price, quantity)) in cases.into_iter().enumerate() {
-            let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(quantity));
+            let mut result: MatchResult =
+                MatchResult::new(Id::from_u64(10), Quantity::new(quantity));
             let trade = Trade::with_timestamp(
                 Id::from_uuid(parse_uuid("6ba7b810-9dad-11d1-80b4-00c04fd430c8")),
                 Id::from_u64(10),
@@ -329,7 +332,7 @@ mod tests {
         ];
 
         for input in malformed {
-            let parsed = MatchResult::from_str(input);
+            let parsed = MatchResult::<()>::from_str(input);
             assert!(
                 parsed.is_err(),
                 "malformed multibyte input must parse to Err, got Ok for {input:?}"
@@ -352,7 +355,7 @@ mod tests {
         ];
         for input in inputs {
             assert!(
-                MatchResult::from_str(input).is_err(),
+                MatchResult::<()>::from_str(input).is_err(),
                 "unterminated multibyte bracket must parse to Err for {input:?}"
             );
         }
@@ -365,14 +368,14 @@ mod tests {
     fn display_round_trips_with_filled_ids_and_trades() {
         // Two makers (20 and 21) each fully consumed, so each is both a trade
         // maker and a filled id — the shape the engine actually produces.
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
         assert!(result.add_trade(sample_trade_with_maker(20, 30)).is_ok());
         assert!(result.add_trade(sample_trade_with_maker(21, 20)).is_ok());
         result.add_filled_order_id(Id::from_u64(20));
         result.add_filled_order_id(Id::from_u64(21));
 
         let rendered = result.to_string();
-        let parsed = match MatchResult::from_str(&rendered) {
+        let parsed = match MatchResult::<()>::from_str(&rendered) {
             Ok(value) => value,
             Err(error) => panic!("valid canonical output must round-trip: {error:?}"),
         };
@@ -401,7 +404,7 @@ mod tests {
     /// serde JSON with all fields (including `outcome`) intact.
     #[test]
     fn valid_result_with_filled_ids_round_trips_serde_json() {
-        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let mut result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
         assert!(result.add_trade(sample_trade_with_maker(20, 40)).is_ok());
         result.add_filled_order_id(Id::from_u64(20));
 
@@ -517,6 +520,94 @@ mod tests {
         assert!(serde_json::from_str::<MatchResult>(&json).is_err());
     }
 
+    /// Invariant 5: a status transition must name a maker of the trades.
+    #[test]
+    fn deserialize_rejects_status_transition_for_untraded_order() {
+        let mut base = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        assert!(base.add_trade(sample_trade_with_maker(20, 40)).is_ok());
+        base.add_status_transition(OrderStatusTransition {
+            order_id: Id::from_u64(20),
+            from: OrderStatus::New,
+            to: OrderStatus::PartiallyFilled,
+        });
+        let json = serde_json::to_string(&base).expect("serialize");
+        assert!(serde_json::from_str::<MatchResult>(&json).is_ok());
+
+        let orphan = OrderStatusTransition {
+            order_id: Id::from_u64(99),
+            from: OrderStatus::New,
+            to: OrderStatus::Filled,
+        };
+        let json = mutated_json(&base, |v| {
+            v["status_transitions"] = serde_json::json!([orphan]);
+        });
+        assert!(serde_json::from_str::<MatchResult>(&json).is_err());
+    }
+
+    /// A payload written before status transitions existed still decodes,
+    /// with none.
+    #[test]
+    fn deserialize_accepts_payload_without_status_transitions() {
+        let mut base = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        assert!(base.add_trade(sample_trade_with_maker(20, 40)).is_ok());
+        let json = mutated_json(&base, |v| {
+            v.as_object_mut()
+                .expect("object payload")
+                .remove("status_transitions");
+        });
+        let parsed: MatchResult = serde_json::from_str(&json).expect("legacy payload must decode");
+        assert!(parsed.status_transitions().is_empty());
+    }
+
+    /// Invariant 6: the cancelled quantity survives a round trip, defaults to
+    /// zero in a legacy payload and may not exceed the remainder.
+    #[test]
+    fn deserialize_checks_cancelled_quantity() {
+        let mut base = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        assert!(base.add_trade(sample_trade_with_maker(20, 40)).is_ok());
+        base.cancel_remainder();
+        let json = serde_json::to_string(&base).expect("serialize");
+        let parsed: MatchResult = serde_json::from_str(&json).expect("round trip");
+        assert_eq!(parsed.cancelled_quantity(), Quantity::new(60));
+
+        let json = mutated_json(&base, |v| {
+            v.as_object_mut()
+                .expect("object payload")
+                .remove("cancelled_quantity");
+        });
+        let parsed: MatchResult = serde_json::from_str(&json).expect("legacy payload must decode");
+        assert_eq!(parsed.cancelled_quantity(), Quantity::new(0));
+
+        let json = mutated_json(&base, |v| {
+            v["cancelled_quantity"] = serde_json::json!(61);
+        });
+        assert!(serde_json::from_str::<MatchResult>(&json).is_err());
+    }
+
+    /// A payload written before refreshes were reported still decodes, with
+    /// none.
+    #[test]
+    fn deserialize_accepts_payload_without_refreshes() {
+        let mut base = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        assert!(base.add_trade(sample_trade_with_maker(20, 40)).is_ok());
+        base.add_refresh(OrderRefresh {
+            order_id: Id::from_u64(20),
+            quantity: 40,
+            priority: RefreshPriority::Retain,
+        });
+        let json = serde_json::to_string(&base).expect("serialize");
+        let parsed: MatchResult = serde_json::from_str(&json).expect("payload must decode");
+        assert_eq!(parsed.refreshes(), base.refreshes());
+
+        let json = mutated_json(&base, |v| {
+            v.as_object_mut()
+                .expect("object payload")
+                .remove("refreshes");
+        });
+        let parsed: MatchResult = serde_json::from_str(&json).expect("legacy payload must decode");
+        assert!(parsed.refreshes().is_empty());
+    }
+
     /// Invariant 4: a filled order id with no backing trade maker is rejected.
     #[test]
     fn deserialize_rejects_filled_id_absent_from_trades() {
@@ -538,7 +629,7 @@ mod tests {
     fn from_str_rejects_complete_with_remainder() {
         let text = "MatchResult:order_id=10;remaining_quantity=5;is_complete=true;\
                     trades=Trades:[];filled_order_ids=[]";
-        assert!(MatchResult::from_str(text).is_err());
+        assert!(MatchResult::<()>::from_str(text).is_err());
     }
 
     /// `FromStr` rejects text whose trade quantities sum past `u64::MAX`.
@@ -552,7 +643,7 @@ mod tests {
             "MatchResult:order_id=10;remaining_quantity=1;is_complete=false;\
              trades={trades};filled_order_ids=[]"
         );
-        assert!(MatchResult::from_str(&text).is_err());
+        assert!(MatchResult::<()>::from_str(&text).is_err());
     }
 
     /// `FromStr` rejects text listing a filled id that is not a trade maker.
@@ -563,7 +654,7 @@ mod tests {
             "MatchResult:order_id=10;remaining_quantity=60;is_complete=false;\
              trades={trades};filled_order_ids=[20,99]"
         );
-        assert!(MatchResult::from_str(&text).is_err());
+        assert!(MatchResult::<()>::from_str(&text).is_err());
     }
 
     /// Structural tightening (#114 review follow-up): trailing content after the
@@ -577,13 +668,13 @@ mod tests {
         let text = "MatchResult:order_id=10;remaining_quantity=0;is_complete=true;\
                     trades=Trades:[];filled_order_ids=[]order_id=10";
         assert!(
-            MatchResult::from_str(text).is_err(),
+            MatchResult::<()>::from_str(text).is_err(),
             "trailing content after filled_order_ids ']' must be rejected"
         );
         // The canonical form (nothing after the ']') still parses.
         let ok = "MatchResult:order_id=10;remaining_quantity=0;is_complete=true;\
                   trades=Trades:[];filled_order_ids=[]";
-        assert!(MatchResult::from_str(ok).is_ok());
+        assert!(MatchResult::<()>::from_str(ok).is_ok());
     }
 
     // ----- property: from_str never panics on arbitrary UTF-8 -----
@@ -626,15 +717,15 @@ mod tests {
             let with_prefix = format!("MatchResult:{suffix}");
             // The result is deliberately ignored — a panic (not an Err) is the
             // only way this can fail.
-            let _ = MatchResult::from_str(&with_prefix);
-            let _ = MatchResult::from_str(&suffix);
+            let _ = MatchResult::<()>::from_str(&with_prefix);
+            let _ = MatchResult::<()>::from_str(&suffix);
         }
 
         /// Delimiter-dense fuzz: multibyte scalars adjacent to every structural
         /// delimiter still never panic.
         #[test]
         fn from_str_never_panics_on_structural_fuzz(input in structural_fuzz()) {
-            let _ = MatchResult::from_str(&input);
+            let _ = MatchResult::<()>::from_str(&input);
         }
     }
 
@@ -709,7 +800,7 @@ mod tests {
         assert!(serde_json::from_str::<MatchResult>(&json).is_err());
 
         // add_trade mirrors the guard: sample trades carry taker id 10.
-        let mut mismatched = MatchResult::new(Id::from_u64(999), Quantity::new(100));
+        let mut mismatched: MatchResult = MatchResult::new(Id::from_u64(999), Quantity::new(100));
         assert!(
             mismatched
                 .add_trade(sample_trade_with_maker(20, 40))
@@ -775,7 +866,7 @@ mod tests {
     #[test]
     fn bincode_round_trip_is_symmetric_issue_135() {
         // Empty result (NotFilled) — the minimal shape that 0.9.0 failed on.
-        let empty = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let empty: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
         let bytes = match bincode::serde::encode_to_vec(&empty, bincode::config::standard()) {
             Ok(bytes) => bytes,
             Err(error) => panic!("bincode encode failed: {error}"),
@@ -789,7 +880,7 @@ mod tests {
         assert_eq!(decoded.remaining_quantity().as_u64(), 100);
 
         // Partially filled result with a trade — outcome variant index 1.
-        let mut partial = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let mut partial = MatchResult::<()>::new(Id::from_u64(10), Quantity::new(100));
         assert!(partial.add_trade(sample_trade(25)).is_ok());
         let bytes = match bincode::serde::encode_to_vec(&partial, bincode::config::standard()) {
             Ok(bytes) => bytes,
@@ -810,7 +901,7 @@ mod tests {
     /// key still carries the bare snake_case value.
     #[test]
     fn json_payload_keeps_bare_outcome_issue_135() {
-        let result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        let result: MatchResult = MatchResult::new(Id::from_u64(10), Quantity::new(100));
         let json = match serde_json::to_string(&result) {
             Ok(json) => json,
             Err(error) => panic!("json encode failed: {error}"),
@@ -871,7 +962,7 @@ mod tests {
 
             // Display / FromStr must reparse and preserve the text-carried fields.
             let text = original.to_string();
-            let reparsed = MatchResult::from_str(&text)
+            let reparsed: MatchResult = MatchResult::from_str(&text)
                 .map_err(|e| TestCaseError::fail(format!("valid text must reparse: {e}")))?;
             prop_assert_eq!(
                 reparsed.remaining_quantity().as_u64(),
@@ -1,3 +1,8 @@
+mod export;
+mod fee;
+mod fills;
 mod list_trade;
 mod match_result_trade;
+mod schema;
+mod tape;
 mod transaction;
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::list::TradeList;
+    use crate::execution::match_result::{MatchOutcome, MatchResult};
+    use crate::execution::schema::EXECUTION_SCHEMA_VERSION;
+    use crate::execution::trade::Trade;
+    use crate::orders::{Id, Side};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use serde_json::Value;
+
+    fn sample_trade(maker_id: u64, quantity: u64) -> Trade {
+        Trade::with_timestamp(
+            Id::from_u64(500 + maker_id),
+            Id::from_u64(10),
+            Id::from_u64(maker_id),
+            Price::new(1_000),
+            Quantity::new(quantity),
+            Side::Buy,
+            TimestampMs::new(1_616_823_000_000),
+        )
+    }
+
+    fn sample_result() -> MatchResult {
+        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(100));
+        result.add_trade(sample_trade(20, 25)).unwrap();
+        result.add_trade(sample_trade(21, 75)).unwrap();
+        result.add_filled_order_id(Id::from_u64(20));
+        result
+    }
+
+    /// Removes every `schema_version` key, producing the payload a release
+    /// before schema versioning wrote.
+    fn strip_versions(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                map.remove("schema_version");
+                map.values_mut().for_each(strip_versions);
+            }
+            Value::Array(items) => items.iter_mut().for_each(strip_versions),
+            _ => {}
+        }
+    }
+
+    fn with_version(value: &Value, version: u32) -> String {
+        let mut value = value.clone();
+        value["schema_version"] = Value::from(version);
+        value.to_string()
+    }
+
+    #[test]
+    fn every_payload_carries_the_schema_version() {
+        let result = sample_result();
+        let value = serde_json::to_value(&result).unwrap();
+
+        assert_eq!(value["schema_version"], EXECUTION_SCHEMA_VERSION);
+        assert_eq!(value["trades"]["schema_version"], EXECUTION_SCHEMA_VERSION);
+        assert_eq!(
+            value["trades"]["trades"][0]["schema_version"],
+            EXECUTION_SCHEMA_VERSION
+        );
+        let trade = serde_json::to_value(sample_trade(20, 25)).unwrap();
+        assert_eq!(trade["schema_version"], EXECUTION_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn unversioned_payloads_still_decode() {
+        let result = sample_result();
+        let mut value = serde_json::to_value(&result).unwrap();
+        strip_versions(&mut value);
+
+        let decoded: MatchResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(decoded.to_string(), result.to_string());
+
+        let list: TradeList = serde_json::from_value(value["trades"].clone()).unwrap();
+        assert_eq!(&list, result.trades());
+
+        let trade: Trade = serde_json::from_value(value["trades"]["trades"][0].clone()).unwrap();
+        assert_eq!(trade, sample_trade(20, 25));
+    }
+
+    #[test]
+    fn unsupported_versions_are_rejected() {
+        let result = serde_json::to_value(sample_result()).unwrap();
+        let list = result["trades"].clone();
+        let trade = list["trades"][0].clone();
+
+        for version in [0, EXECUTION_SCHEMA_VERSION + 1] {
+            let error = serde_json::from_str::<MatchResult>(&with_version(&result, version))
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("unsupported execution schema version"));
+            assert!(serde_json::from_str::<TradeList>(&with_version(&list, version)).is_err());
+            assert!(serde_json::from_str::<Trade>(&with_version(&trade, version)).is_err());
+        }
+
+        let decoded: MatchResult =
+            serde_json::from_str(&with_version(&result, EXECUTION_SCHEMA_VERSION)).unwrap();
+        assert_eq!(decoded.outcome(), MatchOutcome::Filled);
+    }
+
+    #[test]
+    fn bincode_round_trip_carries_the_version() {
+        let result = sample_result();
+        let bytes = bincode::serde::encode_to_vec(&result, bincode::config::standard()).unwrap();
+        let (decoded, _): (MatchResult, usize) =
+            bincode::serde::decode_from_slice(&bytes, bincode::config::standard()).unwrap();
+        assert_eq!(decoded.to_string(), result.to_string());
+        assert_eq!(decoded.filled_order_ids(), &[Id::from_u64(20)]);
+    }
+}
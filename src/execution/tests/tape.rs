@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::match_result::MatchResult;
+    use crate::execution::tape::TapePrint;
+    use crate::execution::trade::Trade;
+    use crate::orders::{Id, Side};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::str::FromStr;
+
+    fn trade(maker_id: u64, price: u128, quantity: u64) -> Trade {
+        Trade::with_timestamp(
+            Id::from_u64(1_000 + maker_id),
+            Id::from_u64(10),
+            Id::from_u64(maker_id),
+            Price::new(price),
+            Quantity::new(quantity),
+            Side::Buy,
+            TimestampMs::new(1_616_823_000_000),
+        )
+    }
+
+    fn match_result(trades: &[Trade]) -> MatchResult {
+        let mut result = MatchResult::new(Id::from_u64(10), Quantity::new(1_000));
+        for trade in trades {
+            result.add_trade(*trade).unwrap();
+        }
+        result
+    }
+
+    #[test]
+    fn consolidates_fills_at_one_price_into_one_print() {
+        let result = match_result(&[trade(1, 100, 5), trade(2, 100, 7), trade(3, 100, 1)]);
+
+        let tape = result.consolidated_tape().unwrap();
+
+        assert_eq!(tape.len(), 1);
+        assert_eq!(tape[0].price(), Price::new(100));
+        assert_eq!(tape[0].quantity(), Quantity::new(13));
+        assert_eq!(tape[0].trade_count(), 3);
+    }
+
+    #[test]
+    fn keeps_prices_in_first_seen_order() {
+        let trades = [trade(1, 101, 2), trade(2, 100, 3), trade(3, 101, 4)];
+
+        let tape = TapePrint::consolidate(&trades).unwrap();
+
+        let summary: Vec<_> = tape
+            .iter()
+            .map(|print| {
+                (
+                    print.price().as_u128(),
+                    print.quantity().as_u64(),
+                    print.trade_count(),
+                )
+            })
+            .collect();
+        assert_eq!(summary, vec![(101, 6, 2), (100, 3, 1)]);
+    }
+
+    #[test]
+    fn empty_match_has_empty_tape() {
+        let result = match_result(&[]);
+
+        assert!(result.consolidated_tape().unwrap().is_empty());
+    }
+
+    #[test]
+    fn quantity_overflow_is_an_error() {
+        let trades = [trade(1, 100, u64::MAX), trade(2, 100, 1)];
+
+        assert!(matches!(
+            TapePrint::consolidate(&trades),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn print_discloses_no_order_ids() {
+        let tape = TapePrint::consolidate(&[trade(42, 100, 5)]).unwrap();
+
+        let text = tape[0].to_string();
+        let json = serde_json::to_string(&tape[0]).unwrap();
+        assert_eq!(text, "TapePrint:price=100;quantity=5;trade_count=1");
+        assert!(!json.contains("order_id"));
+    }
+
+    #[test]
+    fn print_round_trips_through_text_and_json() {
+        let print = TapePrint::consolidate(&[trade(1, 250, 5), trade(2, 250, 6)]).unwrap()[0];
+
+        assert_eq!(TapePrint::from_str(&print.to_string()).unwrap(), print);
+        let json = serde_json::to_string(&print).unwrap();
+        assert_eq!(serde_json::from_str::<TapePrint>(&json).unwrap(), print);
+        assert!(matches!(
+            TapePrint::from_str("Trade:price=1"),
+            Err(PriceLevelError::InvalidFormat)
+        ));
+        assert!(matches!(
+            TapePrint::from_str("TapePrint:price=1;quantity=2"),
+            Err(PriceLevelError::MissingField(_))
+        ));
+    }
+}
@@ -117,6 +117,53 @@ mod tests {
         assert_eq!(parsed.timestamp(), original.timestamp());
     }
 
+    #[test]
+    fn test_level_sequence_round_trip() {
+        let original = create_test_trade();
+        assert_eq!(original.level_sequence(), None);
+        assert!(!original.to_string().contains("level_sequence"));
+
+        let stamped = original.with_level_sequence(42);
+        assert_eq!(stamped.level_sequence(), Some(42));
+        let text = stamped.to_string();
+        assert!(text.ends_with(";level_sequence=42"));
+        assert_eq!(Trade::from_str(&text).unwrap(), stamped);
+
+        let json = serde_json::to_string(&stamped).unwrap();
+        assert_eq!(serde_json::from_str::<Trade>(&json).unwrap(), stamped);
+
+        let invalid = format!("{};level_sequence=x", original);
+        assert!(matches!(
+            Trade::from_str(&invalid),
+            Err(PriceLevelError::InvalidFieldValue { field, .. }) if field == "level_sequence"
+        ));
+    }
+
+    #[test]
+    fn test_clock_timestamp_round_trip() {
+        let original = create_test_trade();
+        assert_eq!(original.clock_timestamp(), None);
+        assert!(!original.to_string().contains("clock_timestamp"));
+
+        let stamped = original.with_clock_timestamp(TimestampMs::new(1616823000250));
+        assert_eq!(
+            stamped.clock_timestamp(),
+            Some(TimestampMs::new(1616823000250))
+        );
+        let text = stamped.to_string();
+        assert!(text.ends_with(";clock_timestamp=1616823000250"));
+        assert_eq!(Trade::from_str(&text).unwrap(), stamped);
+
+        let json = serde_json::to_string(&stamped).unwrap();
+        assert_eq!(serde_json::from_str::<Trade>(&json).unwrap(), stamped);
+
+        let invalid = format!("{};clock_timestamp=x", original);
+        assert!(matches!(
+            Trade::from_str(&invalid),
+            Err(PriceLevelError::InvalidFieldValue { field, .. }) if field == "clock_timestamp"
+        ));
+    }
+
     #[test]
     fn test_maker_side() {
         // Test when taker is buyer
@@ -480,4 +527,43 @@ mod transaction_serialization_tests {
         );
         assert_eq!(large_trade.total_value().unwrap(), 827115);
     }
+
+    #[test]
+    fn test_with_maker_extra_fields_keeps_every_other_field() {
+        let trade = create_test_trade().with_level_sequence(7);
+        let tagged = trade.with_maker_extra_fields(42_u64);
+
+        assert_eq!(*tagged.maker_extra_fields(), 42);
+        assert_eq!(tagged.trade_id(), trade.trade_id());
+        assert_eq!(tagged.quantity(), trade.quantity());
+        assert_eq!(tagged.level_sequence(), Some(7));
+        // The text form never carries the extra fields.
+        assert_eq!(tagged.to_string(), trade.to_string());
+    }
+
+    #[test]
+    fn test_json_without_maker_extra_fields_decodes_with_default() {
+        let tagged = create_test_trade().with_maker_extra_fields(42_u64);
+        let mut value = serde_json::to_value(tagged).unwrap();
+        assert_eq!(value["maker_extra_fields"], 42);
+
+        value.as_object_mut().unwrap().remove("maker_extra_fields");
+        let restored: Trade<u64> = serde_json::from_value(value).unwrap();
+
+        assert_eq!(*restored.maker_extra_fields(), 0);
+        assert_eq!(restored.trade_id(), tagged.trade_id());
+    }
+
+    #[test]
+    fn test_execution_sequence_round_trips_through_text_and_json() {
+        let trade = create_test_trade().with_execution_sequence(17);
+
+        let text = trade.to_string();
+        assert!(text.ends_with(";execution_sequence=17"));
+        assert_eq!(Trade::from_str(&text).unwrap(), trade);
+
+        let json = serde_json::to_string(&trade).unwrap();
+        let restored: Trade = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.execution_sequence(), Some(17));
+    }
 }
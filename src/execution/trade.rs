@@ -1,17 +1,31 @@
 use crate::errors::PriceLevelError;
+use crate::execution::fee::LiquidityFlag;
+use crate::execution::schema::SchemaVersion;
 use crate::orders::{Id, Side};
 use crate::utils::{Price, Quantity, TimestampMs};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Represents a completed trade between two orders.
 ///
 /// All fields are private to enforce immutability after construction.
 /// Use the provided accessor methods to read trade data.
+///
+/// `T` is the extra-fields type of the level's orders
+/// ([`OrderType<T>`](crate::OrderType)): a trade produced by
+/// [`PriceLevel::match_order`](crate::PriceLevel::match_order) carries a clone
+/// of the maker's `extra_fields` as it rested when the fill executed, so a
+/// downstream clearing system can attribute the fill to an account (see
+/// [`Self::maker_extra_fields`]). With the default `T = ()` the trade is
+/// unchanged in size and stays `Copy`.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
-pub struct Trade {
+pub struct Trade<T = ()> {
+    /// Schema version of the serialized trade; see
+    /// [`EXECUTION_SCHEMA_VERSION`](crate::EXECUTION_SCHEMA_VERSION).
+    #[serde(default)]
+    schema_version: SchemaVersion,
+
     /// Unique trade ID
     trade_id: Id,
 
@@ -32,6 +46,39 @@ pub struct Trade {
 
     /// Timestamp when the trade occurred in milliseconds since epoch
     timestamp: TimestampMs,
+
+    /// The level's mutation sequence right after this fill, if the level
+    /// stamps its trades.
+    ///
+    /// Links the trade to the book state it executed against: a snapshot
+    /// whose [`mutation_sequence`](crate::PriceLevelSnapshot::mutation_sequence)
+    /// is at least this value already reflects the fill. See
+    /// [`PriceLevel::set_trade_stamping`](crate::PriceLevel::set_trade_stamping).
+    #[serde(default)]
+    level_sequence: Option<u64>,
+
+    /// The execution sequence number minted with the trade id, if the
+    /// generator is sequenced (see
+    /// [`SequentialIdGenerator`](crate::SequentialIdGenerator)).
+    #[serde(default)]
+    execution_sequence: Option<u64>,
+
+    /// The level clock's reading when the match ran, if the level stamps its
+    /// trades with it.
+    ///
+    /// Unlike `timestamp`, which is the caller-supplied taker time, this is
+    /// read by the engine, so `clock_timestamp - timestamp` is the delay
+    /// between the taker's arrival and its match. See
+    /// [`PriceLevel::set_clock_stamping`](crate::PriceLevel::set_clock_stamping).
+    #[serde(default)]
+    clock_timestamp: Option<TimestampMs>,
+
+    /// The maker order's extra fields at the time of the fill.
+    ///
+    /// `#[serde(default)]` keeps payloads written before the field existed
+    /// decodable, with `T::default()`.
+    #[serde(default)]
+    maker_extra_fields: T,
 }
 
 impl Trade {
@@ -45,12 +92,39 @@ impl Trade {
         quantity: Quantity,
         taker_side: Side,
     ) -> Self {
-        let timestamp_ms = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_or(0_u64, |duration| duration.as_millis() as u64);
-        let timestamp = TimestampMs::new(timestamp_ms);
+        let timestamp = TimestampMs::now();
+
+        Self {
+            schema_version: SchemaVersion,
+            trade_id,
+            taker_order_id,
+            maker_order_id,
+            price,
+            quantity,
+            taker_side,
+            timestamp,
+            level_sequence: None,
+            execution_sequence: None,
+            clock_timestamp: None,
+            maker_extra_fields: (),
+        }
+    }
 
+    /// Creates a trade with an explicit timestamp.
+    ///
+    /// Intended for deserialization and testing where the timestamp is already known.
+    #[must_use]
+    pub fn with_timestamp(
+        trade_id: Id,
+        taker_order_id: Id,
+        maker_order_id: Id,
+        price: Price,
+        quantity: Quantity,
+        taker_side: Side,
+        timestamp: TimestampMs,
+    ) -> Self {
         Self {
+            schema_version: SchemaVersion,
             trade_id,
             taker_order_id,
             maker_order_id,
@@ -58,9 +132,15 @@ impl Trade {
             quantity,
             taker_side,
             timestamp,
+            level_sequence: None,
+            execution_sequence: None,
+            clock_timestamp: None,
+            maker_extra_fields: (),
         }
     }
+}
 
+impl<T> Trade<T> {
     /// Returns the unique trade identifier.
     #[must_use]
     pub fn trade_id(&self) -> Id {
@@ -103,30 +183,6 @@ impl Trade {
         self.timestamp
     }
 
-    /// Creates a trade with an explicit timestamp.
-    ///
-    /// Intended for deserialization and testing where the timestamp is already known.
-    #[must_use]
-    pub fn with_timestamp(
-        trade_id: Id,
-        taker_order_id: Id,
-        maker_order_id: Id,
-        price: Price,
-        quantity: Quantity,
-        taker_side: Side,
-        timestamp: TimestampMs,
-    ) -> Self {
-        Self {
-            trade_id,
-            taker_order_id,
-            maker_order_id,
-            price,
-            quantity,
-            taker_side,
-            timestamp,
-        }
-    }
-
     /// Returns the side of the maker order.
     #[must_use]
     pub fn maker_side(&self) -> Side {
@@ -136,6 +192,103 @@ impl Trade {
         }
     }
 
+    /// Returns the liquidity flag of `order_id` in this trade:
+    /// [`LiquidityFlag::Maker`] for the resting order,
+    /// [`LiquidityFlag::Taker`] for the incoming one, `None` if the order took
+    /// no part in it.
+    #[must_use]
+    pub fn liquidity_for(&self, order_id: Id) -> Option<LiquidityFlag> {
+        if order_id == self.maker_order_id {
+            Some(LiquidityFlag::Maker)
+        } else if order_id == self.taker_order_id {
+            Some(LiquidityFlag::Taker)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the level mutation sequence this trade was stamped with, if any.
+    #[must_use]
+    pub fn level_sequence(&self) -> Option<u64> {
+        self.level_sequence
+    }
+
+    /// Returns this trade stamped with the level mutation sequence `sequence`.
+    #[must_use]
+    pub fn with_level_sequence(mut self, sequence: u64) -> Self {
+        self.level_sequence = Some(sequence);
+        self
+    }
+
+    /// Returns the execution sequence number this trade was minted with, if
+    /// any.
+    ///
+    /// Sequence numbers from one
+    /// [`SequentialIdGenerator`](crate::SequentialIdGenerator) are
+    /// consecutive, so a consumer can order fills by them and treat a missing
+    /// number as a lost fill.
+    #[must_use]
+    pub fn execution_sequence(&self) -> Option<u64> {
+        self.execution_sequence
+    }
+
+    /// Returns this trade carrying the execution sequence number `sequence`.
+    #[must_use]
+    pub fn with_execution_sequence(mut self, sequence: u64) -> Self {
+        self.execution_sequence = Some(sequence);
+        self
+    }
+
+    /// Returns the level clock's reading when this trade's match ran, if the
+    /// trade was stamped with it.
+    #[must_use]
+    pub fn clock_timestamp(&self) -> Option<TimestampMs> {
+        self.clock_timestamp
+    }
+
+    /// Returns this trade stamped with the clock reading `timestamp`.
+    #[must_use]
+    pub fn with_clock_timestamp(mut self, timestamp: TimestampMs) -> Self {
+        self.clock_timestamp = Some(timestamp);
+        self
+    }
+
+    /// Returns the extra fields of the maker order this trade executed
+    /// against.
+    #[must_use]
+    pub fn maker_extra_fields(&self) -> &T {
+        &self.maker_extra_fields
+    }
+
+    /// Returns this trade carrying `maker_extra_fields` in place of its
+    /// current ones; every other field is kept.
+    #[must_use]
+    pub fn with_maker_extra_fields<U>(self, maker_extra_fields: U) -> Trade<U> {
+        self.map_maker_extra_fields(|_| maker_extra_fields)
+    }
+
+    /// Transform the maker extra fields type using a function.
+    #[must_use]
+    pub fn map_maker_extra_fields<U, F>(self, f: F) -> Trade<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Trade {
+            schema_version: SchemaVersion,
+            trade_id: self.trade_id,
+            taker_order_id: self.taker_order_id,
+            maker_order_id: self.maker_order_id,
+            price: self.price,
+            quantity: self.quantity,
+            taker_side: self.taker_side,
+            timestamp: self.timestamp,
+            level_sequence: self.level_sequence,
+            execution_sequence: self.execution_sequence,
+            clock_timestamp: self.clock_timestamp,
+            maker_extra_fields: f(self.maker_extra_fields),
+        }
+    }
+
     /// Returns the total value of this trade (`price * quantity`), in
     /// price-ticks × quantity units.
     ///
@@ -159,7 +312,9 @@ impl Trade {
     }
 }
 
-impl fmt::Display for Trade {
+// The text form does not carry the maker extra fields, so parsing it back
+// yields a `Trade<()>`.
+impl<T> fmt::Display for Trade<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -171,7 +326,17 @@ impl fmt::Display for Trade {
             self.quantity,
             self.taker_side,
             self.timestamp
-        )
+        )?;
+        if let Some(sequence) = self.level_sequence {
+            write!(f, ";level_sequence={sequence}")?;
+        }
+        if let Some(sequence) = self.execution_sequence {
+            write!(f, ";execution_sequence={sequence}")?;
+        }
+        if let Some(timestamp) = self.clock_timestamp {
+            write!(f, ";clock_timestamp={timestamp}")?;
+        }
+        Ok(())
     }
 }
 
@@ -257,7 +422,43 @@ impl FromStr for Trade {
             }
         })?;
 
+        // Optional level sequence stamp
+        let level_sequence = fields
+            .get("level_sequence")
+            .map(|v| {
+                v.parse::<u64>()
+                    .map_err(|_| PriceLevelError::InvalidFieldValue {
+                        field: "level_sequence".to_string(),
+                        value: v.to_string(),
+                    })
+            })
+            .transpose()?;
+
+        // Optional execution sequence number
+        let execution_sequence = fields
+            .get("execution_sequence")
+            .map(|v| {
+                v.parse::<u64>()
+                    .map_err(|_| PriceLevelError::InvalidFieldValue {
+                        field: "execution_sequence".to_string(),
+                        value: v.to_string(),
+                    })
+            })
+            .transpose()?;
+
+        // Optional clock stamp
+        let clock_timestamp = fields
+            .get("clock_timestamp")
+            .map(|v| {
+                TimestampMs::from_str(v).map_err(|_| PriceLevelError::InvalidFieldValue {
+                    field: "clock_timestamp".to_string(),
+                    value: v.to_string(),
+                })
+            })
+            .transpose()?;
+
         Ok(Trade {
+            schema_version: SchemaVersion,
             trade_id,
             taker_order_id,
             maker_order_id,
@@ -265,6 +466,10 @@ impl FromStr for Trade {
             quantity,
             taker_side,
             timestamp,
+            level_sequence,
+            execution_sequence,
+            clock_timestamp,
+            maker_extra_fields: (),
         })
     }
 }
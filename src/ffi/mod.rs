@@ -0,0 +1,452 @@
+//! C ABI for the core price level operations.
+//!
+//! Behind the `ffi` feature, this module exports `extern "C"` functions that
+//! let a C, C++ or Python (`ctypes` / `cffi`) trading stack drive a
+//! [`PriceLevel`] directly. `include/pricelevel.h` declares the same
+//! functions, structs and constants for C callers.
+//!
+//! A level is an opaque [`PlPriceLevel`] handle created by [`pl_level_new`]
+//! and released by [`pl_level_free`]. Orders cross the boundary as
+//! `#[repr(C)]` [`PlOrder`] and [`PlTaker`] structs whose enumerations are
+//! plain `u8` codes (`PL_SIDE_*`, `PL_TIF_*`, `PL_TAKER_*`), validated on
+//! entry. Order ids are [`Id::Sequential`] values and prices are `u64`.
+//!
+//! Every fallible call returns an `i32` status: [`PL_OK`], a negative
+//! `PL_ERR_*` code for a failure at the boundary itself, or the positive
+//! [`PriceLevelError::code`] of the error the level raised. A panic inside the
+//! level is caught and reported as [`PL_ERR_PANIC`] rather than unwinding into
+//! the caller.
+//!
+//! ```rust
+//! use pricelevel::ffi::*;
+//!
+//! let level = pl_level_new(10_000, 1);
+//! let order = PlOrder {
+//!     id: 1,
+//!     price: 10_000,
+//!     visible_quantity: 50,
+//!     hidden_quantity: 0,
+//!     side: PL_SIDE_SELL,
+//!     time_in_force: PL_TIF_GTC,
+//!     expiry_ms: 0,
+//!     timestamp_ms: 1_000,
+//!     user_id: [0; 32],
+//! };
+//! let taker = PlTaker {
+//!     id: 2,
+//!     quantity: 20,
+//!     time_in_force: PL_TIF_IOC,
+//!     kind: PL_TAKER_STANDARD,
+//!     expiry_ms: 0,
+//!     timestamp_ms: 2_000,
+//! };
+//! let mut result = PlMatchResult::default();
+//!
+//! unsafe {
+//!     assert_eq!(pl_level_add_order(level, &order), PL_OK);
+//!     assert_eq!(pl_level_match(level, &taker, &mut result), PL_OK);
+//!     pl_level_free(level);
+//! }
+//! assert_eq!(result.executed_quantity, 20);
+//! assert_eq!(result.outcome, PL_OUTCOME_FILLED);
+//! ```
+
+use crate::errors::PriceLevelError;
+use crate::execution::{MatchOutcome, TakerKind};
+use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome};
+use crate::price_level::PriceLevel;
+use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+mod tests;
+
+/// The call succeeded.
+pub const PL_OK: i32 = 0;
+/// A required pointer argument was null.
+pub const PL_ERR_NULL_POINTER: i32 = -1;
+/// An enumeration code in an argument struct is unknown.
+pub const PL_ERR_INVALID_ARGUMENT: i32 = -2;
+/// The output buffer is too small; the required length was written back.
+pub const PL_ERR_BUFFER_TOO_SMALL: i32 = -3;
+/// The level panicked while handling the call.
+pub const PL_ERR_PANIC: i32 = -4;
+
+/// [`Side::Buy`].
+pub const PL_SIDE_BUY: u8 = 0;
+/// [`Side::Sell`].
+pub const PL_SIDE_SELL: u8 = 1;
+
+/// [`TimeInForce::Gtc`].
+pub const PL_TIF_GTC: u8 = 0;
+/// [`TimeInForce::Ioc`].
+pub const PL_TIF_IOC: u8 = 1;
+/// [`TimeInForce::Fok`].
+pub const PL_TIF_FOK: u8 = 2;
+/// [`TimeInForce::Gtd`], expiring at the struct's `expiry_ms`.
+pub const PL_TIF_GTD: u8 = 3;
+/// [`TimeInForce::Day`].
+pub const PL_TIF_DAY: u8 = 4;
+/// [`TimeInForce::Gtt`], lasting the struct's `expiry_ms` after its timestamp.
+pub const PL_TIF_GTT: u8 = 5;
+
+/// [`TakerKind::Standard`].
+pub const PL_TAKER_STANDARD: u8 = 0;
+/// [`TakerKind::PostOnly`].
+pub const PL_TAKER_POST_ONLY: u8 = 1;
+/// [`TakerKind::MarketToLimit`].
+pub const PL_TAKER_MARKET_TO_LIMIT: u8 = 2;
+
+/// [`MatchOutcome::Filled`].
+pub const PL_OUTCOME_FILLED: u8 = 0;
+/// [`MatchOutcome::PartiallyFilled`].
+pub const PL_OUTCOME_PARTIALLY_FILLED: u8 = 1;
+/// [`MatchOutcome::NotFilled`].
+pub const PL_OUTCOME_NOT_FILLED: u8 = 2;
+/// [`MatchOutcome::Killed`].
+pub const PL_OUTCOME_KILLED: u8 = 3;
+/// [`MatchOutcome::Rejected`].
+pub const PL_OUTCOME_REJECTED: u8 = 4;
+
+/// Opaque handle to a price level and the execution id generator its
+/// matches draw from.
+#[derive(Debug)]
+pub struct PlPriceLevel {
+    level: PriceLevel,
+    executions: SequentialIdGenerator,
+}
+
+/// A resting order to add to a level.
+///
+/// A non-zero `hidden_quantity` makes it an iceberg order showing
+/// `visible_quantity` at a time; otherwise it is a standard limit order.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlOrder {
+    /// Sequential order id.
+    pub id: u64,
+    /// Limit price; must equal the level's price.
+    pub price: u64,
+    /// Displayed quantity.
+    pub visible_quantity: u64,
+    /// Hidden quantity, `0` for a standard order.
+    pub hidden_quantity: u64,
+    /// A `PL_SIDE_*` code.
+    pub side: u8,
+    /// A `PL_TIF_*` code.
+    pub time_in_force: u8,
+    /// Expiry in Unix milliseconds for [`PL_TIF_GTD`], or the lifetime in
+    /// milliseconds for [`PL_TIF_GTT`]; read only for those.
+    pub expiry_ms: u64,
+    /// Order timestamp in Unix milliseconds.
+    pub timestamp_ms: u64,
+    /// Owner identifier.
+    pub user_id: [u8; 32],
+}
+
+/// An incoming order to match against a level.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlTaker {
+    /// Sequential id of the taker order.
+    pub id: u64,
+    /// Quantity to match.
+    pub quantity: u64,
+    /// A `PL_TIF_*` code.
+    pub time_in_force: u8,
+    /// A `PL_TAKER_*` code.
+    pub kind: u8,
+    /// Expiry in Unix milliseconds for [`PL_TIF_GTD`], or the lifetime in
+    /// milliseconds for [`PL_TIF_GTT`]; read only for those.
+    pub expiry_ms: u64,
+    /// Match timestamp in Unix milliseconds, stamped on every trade.
+    pub timestamp_ms: u64,
+}
+
+/// Summary of a match, written by [`pl_level_match`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlMatchResult {
+    /// Quantity executed against the level.
+    pub executed_quantity: u64,
+    /// Taker quantity left unfilled.
+    pub remaining_quantity: u64,
+    /// Number of trades the match produced.
+    pub trade_count: u64,
+    /// A `PL_OUTCOME_*` code.
+    pub outcome: u8,
+}
+
+/// Creates a level at `price` whose trades take execution ids from a
+/// [`SequentialIdGenerator`] for `execution_epoch`.
+///
+/// Never returns null. Release the handle with [`pl_level_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn pl_level_new(price: u64, execution_epoch: u64) -> *mut PlPriceLevel {
+    Box::into_raw(Box::new(PlPriceLevel {
+        level: PriceLevel::new(u128::from(price)),
+        executions: SequentialIdGenerator::new(execution_epoch),
+    }))
+}
+
+/// Releases a level created by [`pl_level_new`]. A null `level` is ignored.
+///
+/// # Safety
+///
+/// `level` must be null or a handle returned by [`pl_level_new`] that has not
+/// been freed, and no other call may use it concurrently or afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pl_level_free(level: *mut PlPriceLevel) {
+    if !level.is_null() {
+        // SAFETY: the caller passes a live handle from `pl_level_new`, which
+        // came from `Box::into_raw`, and gives up every other use of it.
+        drop(unsafe { Box::from_raw(level) });
+    }
+}
+
+/// Adds `order` to the back of the level's queue.
+///
+/// Returns [`PL_OK`], [`PL_ERR_NULL_POINTER`], [`PL_ERR_INVALID_ARGUMENT`]
+/// for an unknown side or time-in-force code, or the code of the
+/// [`PriceLevel::add_order`] error.
+///
+/// # Safety
+///
+/// `level` must be a live handle from [`pl_level_new`] and `order` must point
+/// to a valid [`PlOrder`], or either may be null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pl_level_add_order(
+    level: *const PlPriceLevel,
+    order: *const PlOrder,
+) -> i32 {
+    // SAFETY: the caller guarantees both pointers are null or valid.
+    let (Some(handle), Some(order)) = (unsafe { level.as_ref() }, unsafe { order.as_ref() }) else {
+        return PL_ERR_NULL_POINTER;
+    };
+    let Some(order) = to_order(order) else {
+        return PL_ERR_INVALID_ARGUMENT;
+    };
+    guarded(|| status(handle.level.add_order(order).map(|_| ())))
+}
+
+/// Cancels the resting order `order_id`.
+///
+/// Returns [`PL_OK`], [`PL_ERR_NULL_POINTER`], the code of
+/// [`PriceLevelError::OrderNotFound`] if no such order rests at the level, or
+/// the code of the [`PriceLevel::update_order`] error.
+///
+/// # Safety
+///
+/// `level` must be a live handle from [`pl_level_new`], or null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pl_level_cancel_order(level: *const PlPriceLevel, order_id: u64) -> i32 {
+    // SAFETY: the caller guarantees the pointer is null or valid.
+    let Some(handle) = (unsafe { level.as_ref() }) else {
+        return PL_ERR_NULL_POINTER;
+    };
+    let order_id = Id::sequential(order_id);
+    guarded(|| {
+        status(
+            handle
+                .level
+                .update_order(OrderUpdate::Cancel { order_id })
+                .and_then(|outcome| match outcome {
+                    UpdateOutcome::NotFound => Err(PriceLevelError::OrderNotFound { order_id }),
+                    _ => Ok(()),
+                }),
+        )
+    })
+}
+
+/// Matches `taker` against the level and writes a summary to `result`.
+///
+/// Returns [`PL_OK`], [`PL_ERR_NULL_POINTER`], [`PL_ERR_INVALID_ARGUMENT`]
+/// for an unknown time-in-force or taker kind code, or the code of the error
+/// summing the executed quantity. `result` is written only on success.
+///
+/// # Safety
+///
+/// `level` must be a live handle from [`pl_level_new`], `taker` must point to
+/// a valid [`PlTaker`] and `result` to writable [`PlMatchResult`] storage, or
+/// any of them may be null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pl_level_match(
+    level: *const PlPriceLevel,
+    taker: *const PlTaker,
+    result: *mut PlMatchResult,
+) -> i32 {
+    // SAFETY: the caller guarantees every pointer is null or valid.
+    let (Some(handle), Some(taker), Some(result)) = (
+        unsafe { level.as_ref() },
+        unsafe { taker.as_ref() },
+        unsafe { result.as_mut() },
+    ) else {
+        return PL_ERR_NULL_POINTER;
+    };
+    let (Some(time_in_force), Some(kind)) = (
+        time_in_force(taker.time_in_force, taker.expiry_ms),
+        taker_kind(taker.kind),
+    ) else {
+        return PL_ERR_INVALID_ARGUMENT;
+    };
+    guarded(|| {
+        let matched = handle.level.match_order(
+            taker.quantity,
+            Id::sequential(taker.id),
+            time_in_force,
+            kind,
+            TimestampMs::new(taker.timestamp_ms),
+            &handle.executions,
+        );
+        let executed_quantity = match matched.executed_quantity() {
+            Ok(quantity) => quantity.as_u64(),
+            Err(error) => return code(&error),
+        };
+        *result = PlMatchResult {
+            executed_quantity,
+            remaining_quantity: matched.remaining_quantity().as_u64(),
+            trade_count: matched.trades().len() as u64,
+            outcome: outcome_code(matched.outcome()),
+        };
+        PL_OK
+    })
+}
+
+/// Writes the level's checksummed snapshot package as JSON to `buffer`,
+/// followed by a NUL byte, and its length without the NUL to `length`.
+///
+/// If `capacity` cannot hold the JSON and its NUL, nothing is written to
+/// `buffer`, the JSON length is still written to `length`, and the call
+/// returns [`PL_ERR_BUFFER_TOO_SMALL`]: retry with a buffer of at least
+/// `length + 1` bytes. Passing a null `buffer` with a zero `capacity` is the
+/// way to query the length. Otherwise returns [`PL_OK`],
+/// [`PL_ERR_NULL_POINTER`], or the code of the
+/// [`PriceLevel::snapshot_to_json`] error.
+///
+/// # Safety
+///
+/// `level` must be a live handle from [`pl_level_new`] and `length` must
+/// point to writable storage, or either may be null. `buffer` must be valid
+/// for `capacity` bytes of writes, or null when `capacity` is zero.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn pl_level_snapshot_json(
+    level: *const PlPriceLevel,
+    buffer: *mut u8,
+    capacity: usize,
+    length: *mut usize,
+) -> i32 {
+    // SAFETY: the caller guarantees both pointers are null or valid.
+    let (Some(handle), Some(length)) = (unsafe { level.as_ref() }, unsafe { length.as_mut() })
+    else {
+        return PL_ERR_NULL_POINTER;
+    };
+    if buffer.is_null() && capacity != 0 {
+        return PL_ERR_NULL_POINTER;
+    }
+    guarded(|| {
+        let json = match handle.level.snapshot_to_json() {
+            Ok(json) => json,
+            Err(error) => return code(&error),
+        };
+        *length = json.len();
+        if json.len() >= capacity {
+            return PL_ERR_BUFFER_TOO_SMALL;
+        }
+        // SAFETY: `buffer` is valid for `capacity` bytes, which exceeds
+        // `json.len()`, and a Rust `String` cannot overlap caller memory.
+        unsafe {
+            ptr::copy_nonoverlapping(json.as_ptr(), buffer, json.len());
+            buffer.add(json.len()).write(0);
+        }
+        PL_OK
+    })
+}
+
+/// Runs `call`, reporting a panic as [`PL_ERR_PANIC`] instead of letting it
+/// cross the C boundary.
+fn guarded(call: impl FnOnce() -> i32) -> i32 {
+    panic::catch_unwind(AssertUnwindSafe(call)).unwrap_or(PL_ERR_PANIC)
+}
+
+fn status(result: Result<(), PriceLevelError>) -> i32 {
+    match result {
+        Ok(()) => PL_OK,
+        Err(error) => code(&error),
+    }
+}
+
+fn code(error: &PriceLevelError) -> i32 {
+    i32::from(error.code())
+}
+
+fn side(code: u8) -> Option<Side> {
+    match code {
+        PL_SIDE_BUY => Some(Side::Buy),
+        PL_SIDE_SELL => Some(Side::Sell),
+        _ => None,
+    }
+}
+
+fn time_in_force(code: u8, expiry_ms: u64) -> Option<TimeInForce> {
+    match code {
+        PL_TIF_GTC => Some(TimeInForce::Gtc),
+        PL_TIF_IOC => Some(TimeInForce::Ioc),
+        PL_TIF_FOK => Some(TimeInForce::Fok),
+        PL_TIF_GTD => Some(TimeInForce::Gtd(expiry_ms)),
+        PL_TIF_DAY => Some(TimeInForce::Day),
+        PL_TIF_GTT => Some(TimeInForce::Gtt(expiry_ms)),
+        _ => None,
+    }
+}
+
+fn taker_kind(code: u8) -> Option<TakerKind> {
+    match code {
+        PL_TAKER_STANDARD => Some(TakerKind::Standard),
+        PL_TAKER_POST_ONLY => Some(TakerKind::PostOnly),
+        PL_TAKER_MARKET_TO_LIMIT => Some(TakerKind::MarketToLimit),
+        _ => None,
+    }
+}
+
+fn outcome_code(outcome: MatchOutcome) -> u8 {
+    match outcome {
+        MatchOutcome::Filled => PL_OUTCOME_FILLED,
+        MatchOutcome::PartiallyFilled => PL_OUTCOME_PARTIALLY_FILLED,
+        MatchOutcome::NotFilled => PL_OUTCOME_NOT_FILLED,
+        MatchOutcome::Killed => PL_OUTCOME_KILLED,
+        MatchOutcome::Rejected => PL_OUTCOME_REJECTED,
+    }
+}
+
+fn to_order(order: &PlOrder) -> Option<OrderType<()>> {
+    let id = Id::sequential(order.id);
+    let price = Price::new(u128::from(order.price));
+    let side = side(order.side)?;
+    let user_id = Hash32::new(order.user_id);
+    let timestamp = TimestampMs::new(order.timestamp_ms);
+    let time_in_force = time_in_force(order.time_in_force, order.expiry_ms)?;
+    Some(if order.hidden_quantity == 0 {
+        OrderType::Standard {
+            id,
+            price,
+            quantity: Quantity::new(order.visible_quantity),
+            side,
+            user_id,
+            timestamp,
+            time_in_force,
+            extra_fields: (),
+        }
+    } else {
+        OrderType::IcebergOrder {
+            id,
+            price,
+            visible_quantity: Quantity::new(order.visible_quantity),
+            hidden_quantity: Quantity::new(order.hidden_quantity),
+            side,
+            user_id,
+            timestamp,
+            time_in_force,
+            extra_fields: (),
+        }
+    })
+}
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::ffi::*;
+    use crate::orders::Id;
+    use crate::price_level::PriceLevelSnapshotPackage;
+    use std::ptr;
+
+    fn order(id: u64, side: u8, visible_quantity: u64, hidden_quantity: u64) -> PlOrder {
+        PlOrder {
+            id,
+            price: 10_000,
+            visible_quantity,
+            hidden_quantity,
+            side,
+            time_in_force: PL_TIF_GTC,
+            expiry_ms: 0,
+            timestamp_ms: 1_000 + id,
+            user_id: [7; 32],
+        }
+    }
+
+    fn taker(id: u64, quantity: u64, time_in_force: u8) -> PlTaker {
+        PlTaker {
+            id,
+            quantity,
+            time_in_force,
+            kind: PL_TAKER_STANDARD,
+            expiry_ms: 0,
+            timestamp_ms: 5_000,
+        }
+    }
+
+    fn snapshot_json(level: *const PlPriceLevel) -> String {
+        let mut length = 0;
+        let status = unsafe { pl_level_snapshot_json(level, ptr::null_mut(), 0, &mut length) };
+        assert_eq!(status, PL_ERR_BUFFER_TOO_SMALL);
+
+        let mut buffer = vec![0xFF_u8; length + 1];
+        let status = unsafe {
+            pl_level_snapshot_json(level, buffer.as_mut_ptr(), buffer.len(), &mut length)
+        };
+        assert_eq!(status, PL_OK);
+        assert_eq!(buffer[length], 0, "the JSON is NUL-terminated");
+        buffer.truncate(length);
+        String::from_utf8(buffer).expect("snapshot JSON is UTF-8")
+    }
+
+    #[test]
+    fn test_add_match_and_cancel_through_the_c_abi() {
+        let level = pl_level_new(10_000, 3);
+        unsafe {
+            assert_eq!(
+                pl_level_add_order(level, &order(1, PL_SIDE_SELL, 30, 0)),
+                PL_OK
+            );
+            assert_eq!(
+                pl_level_add_order(level, &order(2, PL_SIDE_SELL, 10, 40)),
+                PL_OK
+            );
+            assert_eq!(
+                pl_level_add_order(level, &order(3, PL_SIDE_SELL, 25, 0)),
+                PL_OK
+            );
+
+            let mut result = PlMatchResult::default();
+            assert_eq!(
+                pl_level_match(level, &taker(9, 35, PL_TIF_IOC), &mut result),
+                PL_OK
+            );
+            assert_eq!(
+                result,
+                PlMatchResult {
+                    executed_quantity: 35,
+                    remaining_quantity: 0,
+                    trade_count: 2,
+                    outcome: PL_OUTCOME_FILLED,
+                }
+            );
+
+            assert_eq!(pl_level_cancel_order(level, 3), PL_OK);
+            let missing = PriceLevelError::OrderNotFound {
+                order_id: Id::sequential(3),
+            };
+            assert_eq!(pl_level_cancel_order(level, 3), i32::from(missing.code()));
+
+            let package = PriceLevelSnapshotPackage::<()>::from_json(&snapshot_json(level))
+                .expect("the JSON is a snapshot package");
+            let snapshot = package.into_snapshot().expect("checksum validates");
+            assert_eq!(snapshot.orders().len(), 1, "only the iceberg rests");
+
+            pl_level_free(level);
+        }
+    }
+
+    #[test]
+    fn test_level_errors_surface_as_their_codes() {
+        let level = pl_level_new(10_000, 1);
+        unsafe {
+            assert_eq!(
+                pl_level_add_order(level, &order(1, PL_SIDE_BUY, 10, 0)),
+                PL_OK
+            );
+            let duplicate = pl_level_add_order(level, &order(1, PL_SIDE_BUY, 10, 0));
+            assert_eq!(duplicate, 2001);
+            let opposite = pl_level_add_order(level, &order(2, PL_SIDE_SELL, 10, 0));
+            assert_eq!(opposite, 2003);
+
+            let mut off_price = order(3, PL_SIDE_BUY, 10, 0);
+            off_price.price = 10_001;
+            assert_eq!(pl_level_add_order(level, &off_price), 2002);
+
+            let mut result = PlMatchResult::default();
+            assert_eq!(
+                pl_level_match(level, &taker(9, 50, PL_TIF_FOK), &mut result),
+                PL_OK
+            );
+            assert_eq!(result.outcome, PL_OUTCOME_KILLED);
+            assert_eq!(result.executed_quantity, 0);
+            assert_eq!(result.remaining_quantity, 50);
+
+            pl_level_free(level);
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments_are_rejected_at_the_boundary() {
+        let level = pl_level_new(10_000, 1);
+        unsafe {
+            assert_eq!(
+                pl_level_add_order(ptr::null(), &order(1, PL_SIDE_BUY, 10, 0)),
+                PL_ERR_NULL_POINTER
+            );
+            assert_eq!(pl_level_add_order(level, ptr::null()), PL_ERR_NULL_POINTER);
+            assert_eq!(
+                pl_level_add_order(level, &order(1, 9, 10, 0)),
+                PL_ERR_INVALID_ARGUMENT
+            );
+
+            let mut unknown_tif = order(1, PL_SIDE_BUY, 10, 0);
+            unknown_tif.time_in_force = 200;
+            assert_eq!(
+                pl_level_add_order(level, &unknown_tif),
+                PL_ERR_INVALID_ARGUMENT
+            );
+
+            let mut result = PlMatchResult::default();
+            let mut bad_kind = taker(9, 5, PL_TIF_IOC);
+            bad_kind.kind = 7;
+            assert_eq!(
+                pl_level_match(level, &bad_kind, &mut result),
+                PL_ERR_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                pl_level_match(level, &taker(9, 5, PL_TIF_IOC), ptr::null_mut()),
+                PL_ERR_NULL_POINTER
+            );
+            assert_eq!(pl_level_cancel_order(ptr::null(), 1), PL_ERR_NULL_POINTER);
+
+            let mut length = 0;
+            assert_eq!(
+                pl_level_snapshot_json(level, ptr::null_mut(), 16, &mut length),
+                PL_ERR_NULL_POINTER
+            );
+            assert_eq!(
+                pl_level_snapshot_json(level, ptr::null_mut(), 0, ptr::null_mut()),
+                PL_ERR_NULL_POINTER
+            );
+
+            pl_level_free(level);
+            pl_level_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_snapshot_buffer_must_hold_the_terminator() {
+        let level = pl_level_new(10_000, 1);
+        unsafe {
+            assert_eq!(
+                pl_level_add_order(level, &order(1, PL_SIDE_BUY, 10, 0)),
+                PL_OK
+            );
+
+            let expected = snapshot_json(level);
+            let mut buffer = vec![0xAA_u8; expected.len()];
+            let mut length = 0;
+            assert_eq!(
+                pl_level_snapshot_json(level, buffer.as_mut_ptr(), buffer.len(), &mut length),
+                PL_ERR_BUFFER_TOO_SMALL
+            );
+            assert_eq!(length, expected.len());
+            assert!(buffer.iter().all(|byte| *byte == 0xAA), "buffer untouched");
+
+            pl_level_free(level);
+        }
+    }
+}
@@ -1,3 +1,4 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![allow(unknown_lints)]
 #![allow(clippy::literal_string_with_formatting_args)]
 #![warn(clippy::missing_errors_doc)]
@@ -31,6 +32,7 @@
 //!  - **Pegged Order**: Orders that adjust their price based on a reference price
 //!  - **Market-to-Limit Order**: Orders that convert to limit orders after initial execution
 //!  - **Reserve Order**: Orders with custom replenishment logic for visible quantities
+//!  - **Stop-Market / Stop-Limit Order**: Dormant orders that activate as market-to-limit / limit orders once a reference price crosses their trigger price
 //!
 //!  ## Time-in-Force Options
 //!
@@ -59,6 +61,44 @@
 //!  - **Performance Monitoring**: Built-in statistics for monitoring execution performance
 //!  - **Order Matching Logic**: Sophisticated algorithms for matching orders at each price level
 //!
+//!  ## `no_std` Support
+//!
+//!  The `std` feature is on by default. Built with `default-features = false`, the crate is
+//!  `no_std` + `alloc` and keeps the parts that need neither threads nor an OS: [`OrderType`] and
+//!  the other order types, [`Price`], [`Quantity`], [`TimestampMs`], [`ScaledPrice`], [`Id`],
+//!  [`PriceLevelError`], the [`Clock`] trait with [`ManualClock`], and the deterministic
+//!  [`SequentialOrderIdGenerator`], [`UuidGenerator`] and [`ExecutionIdGenerator`]. Time and ids
+//!  come from the caller: a `Clock` the host advances, and generators seeded or namespaced by it.
+//!
+//!  [`PriceLevel`] itself, [`OrderQueue`], matching, [`MatchResult`], snapshots, statistics,
+//!  parsing and the encoders stay behind `std`. The level is built on `dashmap` and
+//!  `crossbeam-skiplist`, which need the standard library. The same holds for [`SystemClock`],
+//!  [`SnowflakeGenerator`], [`UuidV7Generator`], [`TimestampMs::now`], [`Id::new`],
+//!  [`Id::new_uuid`], [`Id::new_ulid`], string parsing of [`OrderType`] and
+//!  [`RandomizedReplenish::new`], which read the system clock or OS entropy, and for
+//!  [`setup_logger`]. Every other feature turns `std` on.
+//!
+//!  ```toml
+//!  [dependencies]
+//!  pricelevel = { version = "0.9", default-features = false }
+//!  ```
+//!
+//!  The clock and generators count in `AtomicU64`s, so the target needs 64-bit atomics. The
+//!  crate also declares a `cdylib`, which rustc drops on bare-metal targets; a `no_std` build on
+//!  a hosted target builds the rlib alone (`make check-no-std`).
+//!
+//!  ## Integration with OrderBook-rs
+//!
+//!  [OrderBook-rs](https://github.com/joaquinbejar/OrderBook-rs) builds its book on top of this
+//!  crate and depends on it, so the adapter layer lives here, in the [`orderbook`] module. A book
+//!  drives its levels through the [`orderbook::BookLevel`] trait, implemented for [`PriceLevel`]
+//!  and `Arc<PriceLevel>`, and indexes its orders by [`orderbook::LevelLocation`]. [`Price`],
+//!  [`Quantity`] and [`TimestampMs`] convert to and from their raw integers, [`OrderType`] and
+//!  [`Id`] pass through unchanged, and [`MatchResult`] / [`Trade`] are consumed without
+//!  conversion. [`PriceLevelError::kind`] sorts the level's errors into
+//!  [`PriceLevelErrorKind`]s for the book to map onto its own error type, which can also wrap
+//!  the error whole with `#[from]` / `?`.
+//!
 //! ## Performance Benchmark Results
 //!
 //! The `pricelevel` library has been thoroughly tested for performance in high-frequency trading scenarios. Below are the results from recent simulations conducted on an M4 Max processor, demonstrating the library's capability to handle intensive concurrent trading operations.
@@ -74,6 +114,12 @@
 //!   - 10 canceller threads (cancelling orders)
 //! - **Initial Orders**: 1000 orders seeded before simulation
 //!
+//! The `replay_simulation` example runs a seeded, single-threaded counterpart
+//! through the `sim` module, whose `Workload` reproduces the same script for
+//! the same seed and checks the level's final state. The `stress` module
+//! packages the threaded simulation itself, checking the same invariants once
+//! its threads have joined.
+//!
 //! #### Performance Metrics
 //!
 //! | Metric | Total Operations | Rate (per second) |
@@ -258,7 +304,7 @@
 //! ```rust
 //! use pricelevel::{PriceLevel, PriceLevelError};
 //!
-//! let level = PriceLevel::new(10_000);
+//! let level: PriceLevel = PriceLevel::new(10_000);
 //! // total_quantity() now returns Result
 //! let total: Result<u64, PriceLevelError> = level.total_quantity();
 //! assert_eq!(total.unwrap(), 0);
@@ -295,7 +341,7 @@
 //!
 //! | v0.6 (field) | v0.7 (accessor) |
 //! |--------------|-----------------|
-//! | `list.trades` (direct `Vec`) | [`list.as_vec()`](TradeList::as_vec) / [`list.into_vec()`](TradeList::into_vec) |
+//! | `list.trades` (direct `Vec`) | [`list.as_slice()`](TradeList::as_slice) / [`list.into_vec()`](TradeList::into_vec) |
 //! | `list.trades.push(t)` | [`list.add(t)`](TradeList::add) |
 //! | `list.trades.len()` | [`list.len()`](TradeList::len) |
 //! | `list.trades.is_empty()` | [`list.is_empty()`](TradeList::is_empty) |
@@ -318,13 +364,13 @@
 //! ```rust
 //! use pricelevel::PriceLevel;
 //!
-//! let level = PriceLevel::new(10_000);
+//! let level: PriceLevel = PriceLevel::new(10_000);
 //!
 //! // Serialize to JSON (includes checksum)
 //! let json = level.snapshot_to_json().unwrap();
 //!
 //! // Restore from JSON (validates checksum)
-//! let restored = PriceLevel::from_snapshot_json(&json).unwrap();
+//! let restored: PriceLevel = PriceLevel::from_snapshot_json(&json).unwrap();
 //! ```
 //!
 //! ### Compiler Attributes
@@ -403,9 +449,10 @@
 //!   remainder, queue untouched; otherwise filled completely.
 //! - [`TimeInForce::Ioc`]: fills `available`, discards the remainder (the taker
 //!   is never rested by this layer).
-//! - [`TimeInForce::Gtc`] / [`TimeInForce::Gtd`] / [`TimeInForce::Day`] and
-//!   [`TakerKind::MarketToLimit`]: fill `available`, report the remainder in
-//!   [`MatchResult::remaining_quantity`] for the order book to rest / convert.
+//! - [`TimeInForce::Gtc`] / [`TimeInForce::Gtd`] / [`TimeInForce::Gtt`] /
+//!   [`TimeInForce::Day`] and [`TakerKind::MarketToLimit`]: fill `available`,
+//!   report the remainder in [`MatchResult::remaining_quantity`] for the order
+//!   book to rest / convert.
 //!
 //! **New `MatchResult` signal.** A fill-or-kill *kill* and a post-only
 //! *rejection* both leave zero trades and the full remainder — indistinguishable
@@ -464,6 +511,33 @@
 //! either version, so a legacy v2 package's SHA-256 still matches. New snapshots
 //! are written at v3. No code changes are required at the call sites.
 //!
+//! ## Migration Guide (snapshot format v3 → v4)
+//!
+//! `SNAPSHOT_FORMAT_VERSION` is bumped from `3` to `4`. Version 4 owns the
+//! optional `mutation_sequence` snapshot field: the level's
+//! [`PriceLevel::mutation_sequence`] at capture, written only when non-zero. It
+//! pairs with [`Trade::level_sequence`](crate::execution::Trade::level_sequence)
+//! (see [`PriceLevel::set_trade_stamping`]) so a journal of trades can be placed
+//! exactly against the snapshots around it.
+//!
+//! Restore stays **backward compatible**: v2, v3 and v4 packages all validate,
+//! and a pre-v4 payload restores with a sequence of `0`. A restored level
+//! resumes its mutation sequence from the snapshot. Readers older than this
+//! release reject a v4 payload that carries the field. No code changes are
+//! required at the call sites.
+//!
+//! ## Migration Guide (snapshot format v4 → v5)
+//!
+//! `SNAPSHOT_FORMAT_VERSION` is bumped from `4` to `5`. Version 5 owns the
+//! optional `order_ages` statistics field: the level's resting-order age
+//! histograms (`PriceLevelStatistics::order_age_histogram`), written only
+//! once a fill or a removal has recorded an age.
+//!
+//! Restore stays **backward compatible**: v2 through v5 packages all validate,
+//! and a pre-v5 payload restores with empty age histograms. Readers older than
+//! this release reject a v5 payload that carries the field. No code changes are
+//! required at the call sites.
+//!
 //! ## Migration Guide (`Trade::total_value` is now checked)
 //!
 //! [`Trade::total_value`](crate::execution::Trade::total_value) now returns
@@ -612,22 +686,146 @@
 //! was a footgun with no safe use; construct queues through [`PriceLevel`]'s
 //! public surface instead.
 //!
+//! ## Migration Guide (`update_order` returns `UpdateOutcome` — breaking)
+//!
+//! [`PriceLevel::update_order`] now returns `Result<UpdateOutcome, PriceLevelError>`
+//! instead of `Result<Option<Arc<OrderType<()>>>, PriceLevelError>`. The old
+//! `Some(order)` meant "cancelled", "resized here" or "removed so the book can
+//! re-insert it at a new price", and callers had to infer which from the update
+//! they sent. [`UpdateOutcome`] names the case:
+//!
+//! - `UpdatedInPlace(order)` — resized at this level (same-price
+//!   `UpdateQuantity`, `UpdatePriceAndQuantity` or `Replace`).
+//! - `RemovedForReprice(order)` — a price-changing update; re-insert `order` at
+//!   the new price.
+//! - `Cancelled(order, status)` — a `Cancel`; `status` is the one the order
+//!   rested with, and `status_transition()` reports the change to `Cancelled`.
+//! - `NotFound` — the old `None`.
+//!
+//! `is_found()` replaces `is_some()`, and `order()` / `into_order()` recover the
+//! old `Option` where the distinction does not matter. Errors are unchanged.
+//!
+//! ## Migration Guide (generic extra fields — breaking)
+//!
+//! [`PriceLevel`], [`OrderQueue`], [`PriceLevelSnapshot`],
+//! [`PriceLevelSnapshotPackage`], [`PriceLevelData`], [`UpdateOutcome`],
+//! [`MatchResult`], [`MatchResultBuilder`], [`Trade`] and [`TradeList`] now take
+//! the order payload type `T` of [`OrderType<T>`], defaulting to `()`, so
+//! existing type names keep their meaning. A level built as
+//! `PriceLevel<MyFields>` rests, matches, updates and snapshots
+//! `OrderType<MyFields>`, and each [`Trade`] it emits carries the maker's payload
+//! (`Trade::maker_extra_fields`).
+//!
+//! Type defaults do not drive inference, so calls whose arguments never mention
+//! `T` now need it spelled out where nothing else pins it:
+//!
+//! ```rust
+//! use pricelevel::PriceLevel;
+//!
+//! // Was: `let level = PriceLevel::new(10_000);`
+//! let level: PriceLevel = PriceLevel::new(10_000);
+//! let json = level.snapshot_to_json().unwrap();
+//!
+//! // Was: `PriceLevel::from_snapshot_json(&json)`
+//! let restored = PriceLevel::<()>::from_snapshot_json(&json).unwrap();
+//! assert_eq!(restored.price(), 10_000);
+//! ```
+//!
+//! The same applies to `PriceLevelSnapshotPackage::from_json`,
+//! `MatchResult::new`, `MatchResultBuilder::new`, `TradeList::new`, the `FromStr`
+//! impls and `UpdateOutcome::NotFound`. Code that already passes an
+//! `OrderType<()>` (such as a following `add_order`) infers `T` as before.
+//! `Trade::new` still builds a `Trade<()>`. Serialized trades now carry a
+//! `maker_extra_fields` field; older payloads without it decode with
+//! `T::default()`.
+//!
+
+extern crate alloc;
 
 mod orders;
+#[cfg(feature = "std")]
 mod price_level;
 mod utils;
 
 mod errors;
+#[cfg(feature = "std")]
 mod execution;
 
+#[cfg(feature = "std")]
+pub mod encoding;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod orderbook;
+#[cfg(feature = "std")]
+pub mod parse;
 pub mod prelude;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "std")]
+pub mod sim;
+#[cfg(feature = "std")]
+pub mod stress;
+#[cfg(feature = "testing")]
+pub mod testing;
 
-pub use errors::PriceLevelError;
-pub use execution::{MatchOutcome, MatchResult, TakerKind, Trade, TradeList};
+pub use errors::{CapacityLimit, ErrorSource, PriceLevelError, PriceLevelErrorKind};
+#[cfg(feature = "std")]
+pub use execution::{
+    EXECUTION_SCHEMA_VERSION, Fee, FeeCurrency, FeeSchedule, LiquidityFlag, MakerFill,
+    MatchOutcome, MatchResult, MatchResultBuilder, TRADE_EXPORT_COLUMNS,
+    TRADE_LIST_INLINE_CAPACITY, TakerKind, TapePrint, Trade, TradeFees, TradeList,
+};
 pub use orders::DEFAULT_RESERVE_REPLENISH_AMOUNT;
 pub use orders::PegReferenceType;
-pub use orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+pub use orders::{
+    DefaultReplenishment, OrderRefresh, RandomizedReplenish, RefreshPriority, ReplenishTrigger,
+    Replenishment, ReplenishmentContext, ReplenishmentStrategy,
+};
+pub use orders::{
+    Hash32, Id, OrderFillState, OrderKey, OrderReplacement, OrderStatus, OrderStatusTransition,
+    OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome,
+};
+#[cfg(feature = "redb")]
+pub use price_level::RedbSnapshotStore;
+#[cfg(feature = "metrics")]
+pub use price_level::prometheus_text;
+#[cfg(feature = "std")]
 pub use price_level::{
-    OrderQueue, PriceLevel, PriceLevelData, PriceLevelSnapshot, PriceLevelSnapshotPackage,
+    AlertCondition, AmendRecord, AuctionBook, AuctionEquilibrium, AuctionPhase,
+    BOOK_SNAPSHOT_FORMAT_VERSION, BandAction, BandWidth, BookAnalytics, BookSnapshot,
+    BookSnapshotPackage, BoundedAdd, BoundedLevel, ChecksumAlgorithm, ConflatedUpdate, Conflator,
+    CrossingRule, DEFAULT_AMEND_HISTORY_CAPACITY, DEFAULT_EXECUTION_CHANNEL_CAPACITY,
+    DEFAULT_RESERVATION_TIMEOUT_MS, DepthLadder, DepthRung, DeterministicReplay, ExecutionOverflow,
+    ExpiryWarning, HaltPolicy, HiddenPriority, LatencyBucket, LatencyHistogram, LatencyOperation,
+    LevelFork, LevelLimits, MAX_QUEUE_SHARDS, MarketDataRecord, MatchPlan, MatchingPolicy,
+    MboRecord, MbpRecord, MultiBook, OrderAgeBucket, OrderAgeHistogram, OrderAgeOutcome,
+    OrderQueue, OverflowPolicy, OverflowStats, PlannedFill, PriceBand, PriceLevel,
+    PriceLevelConfig, PriceLevelData, PriceLevelSnapshot, PriceLevelSnapshotPackage,
+    PriceLevelView, PriorityTiers, PrunePolicy, QuarantineReason, QuarantinedOrder, QueueAudit,
+    QueuePosition, RateLimitedOperation, RateLimiter, ReservationGuard,
+    STATISTICS_CSV_SCHEMA_VERSION, Sequenced, SessionCancellation, SessionTag, SessionTagged,
+    SnapshotFinding, SnapshotKey, SnapshotSchedule, SnapshotScheduler, SnapshotStore,
+    SnapshotValidationReport, StatisticsAlert, StatisticsAnalytics, StatisticsCsvWriter,
+    StatisticsWindow, SymbolKey, SymbolSpec, SymbolStatistics, TierTable, TokenBucketRateLimiter,
+    UncrossResult, write_market_data_csv, write_market_data_json,
+};
+#[cfg(feature = "rkyv")]
+pub use price_level::{ArchivedOrder, PriceLevelArchive};
+#[cfg(feature = "async")]
+pub use price_level::{DEFAULT_EVENT_CAPACITY, PriceLevelEvent};
+#[cfg(feature = "pool")]
+pub use price_level::{DEFAULT_ORDER_POOL_CAPACITY, OrderPool};
+#[cfg(feature = "fix")]
+pub use price_level::{
+    FIX_SOH, FixEntryType, FixMdEntry, FixUpdateAction, fix_full_refresh, fix_incremental_refresh,
+};
+pub use utils::{
+    Clock, ExecutionIdGenerator, LogicalClock, ManualClock, OrderIdGenerator, Price, Quantity,
+    ScaledPrice, SequentialIdGenerator, SequentialOrderIdGenerator, TimestampMs, UuidGenerator,
+};
+#[cfg(feature = "std")]
+pub use utils::{
+    MAX_SNOWFLAKE_NODE, SNOWFLAKE_NODE_BITS, SNOWFLAKE_SEQUENCE_BITS, SnowflakeGenerator,
+    SystemClock, UuidV7Generator, setup_logger,
 };
-pub use utils::{Price, Quantity, TimestampMs, UuidGenerator, setup_logger};
@@ -0,0 +1,285 @@
+//! Adapter layer for an order book built on this crate, such as
+//! [OrderBook-rs](https://github.com/joaquinbejar/OrderBook-rs).
+//!
+//! The book depends on this crate, not the other way round, so the adapter
+//! lives here as traits and types the book consumes without conversions:
+//!
+//! - [`BookLevel`] — the level surface a book drives: admission, updates,
+//!   matching and the depth reads. It is implemented for [`PriceLevel`] and
+//!   for `Arc<PriceLevel>`, the form a book keeps its levels in, so a book
+//!   generic over `L: BookLevel<T>` takes either, or a test double.
+//! - [`LevelLocation`] — where an order rests: its level's price and side,
+//!   the value of a book's order-id index. It converts to and from the raw
+//!   `(u128, Side)` pair and is read straight off an [`OrderType`].
+//! - [`Price`], [`Quantity`] and [`TimestampMs`] convert losslessly to and
+//!   from their raw integers, so a book keyed by raw price shares the
+//!   level's newtypes instead of copying them.
+//! - [`PriceLevelError::kind`] sorts a level's errors into
+//!   [`PriceLevelErrorKind`]s, which a book maps onto its own error type.
+//!
+//! Orders, [`MatchResult`]s and their trades are passed through as they
+//! are: the book stores the `Arc<OrderType<T>>`s a level returns and reads
+//! the match result's trades in place.
+//!
+//! ```rust
+//! use pricelevel::orderbook::{BookLevel, LevelLocation};
+//! use pricelevel::{
+//!     Hash32, Id, OrderType, Price, PriceLevel, Quantity, SequentialIdGenerator, Side,
+//!     TakerKind, TimeInForce, TimestampMs,
+//! };
+//! use std::collections::HashMap;
+//! use std::sync::Arc;
+//!
+//! fn rest<L: BookLevel<()>>(
+//!     level: &L,
+//!     locations: &mut HashMap<Id, LevelLocation>,
+//!     order: OrderType<()>,
+//! ) {
+//!     let order = level.add_order(order).unwrap();
+//!     locations.insert(order.id(), LevelLocation::of(&order));
+//! }
+//!
+//! let level = Arc::new(PriceLevel::new(10_000));
+//! let mut locations = HashMap::new();
+//! rest(
+//!     &level,
+//!     &mut locations,
+//!     OrderType::Standard {
+//!         id: Id::from_u64(1),
+//!         price: Price::from(10_000_u128),
+//!         quantity: Quantity::from(5_u64),
+//!         side: Side::Sell,
+//!         user_id: Hash32::zero(),
+//!         timestamp: TimestampMs::from(1_000_u64),
+//!         time_in_force: TimeInForce::Gtc,
+//!         extra_fields: (),
+//!     },
+//! );
+//! assert_eq!(locations[&Id::from_u64(1)], LevelLocation::new(10_000, Side::Sell));
+//!
+//! let result = level.match_order(
+//!     2,
+//!     Id::from_u64(2),
+//!     TimeInForce::Ioc,
+//!     TakerKind::Standard,
+//!     TimestampMs::from(1_001_u64),
+//!     &SequentialIdGenerator::new(0),
+//! );
+//! assert_eq!(result.executed_quantity().unwrap().as_u64(), 2);
+//! assert_eq!(BookLevel::visible_quantity(&level), 3);
+//! ```
+
+use crate::errors::PriceLevelError;
+use crate::execution::{MatchResult, TakerKind};
+use crate::orders::{Id, OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome};
+use crate::price_level::{PriceLevel, PriceLevelSnapshot};
+use crate::utils::{ExecutionIdGenerator, Price, TimestampMs};
+#[cfg(doc)]
+use crate::{errors::PriceLevelErrorKind, utils::Quantity};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+mod tests;
+
+/// The surface of a price level an order book drives.
+///
+/// Each method is the [`PriceLevel`] method of the same name; see it for the
+/// full contract. A book written against this trait runs on levels held
+/// directly or behind an `Arc`.
+pub trait BookLevel<T> {
+    /// The level's price.
+    fn price(&self) -> u128;
+
+    /// The displayed quantity resting at the level.
+    fn visible_quantity(&self) -> u64;
+
+    /// The hidden quantity resting at the level.
+    fn hidden_quantity(&self) -> u64;
+
+    /// The number of orders resting at the level.
+    fn order_count(&self) -> usize;
+
+    /// Admit `order` to the level, returning it as it rests.
+    ///
+    /// # Errors
+    ///
+    /// Returns what [`PriceLevel::add_order`] returns.
+    fn add_order(&self, order: OrderType<T>) -> Result<Arc<OrderType<T>>, PriceLevelError>;
+
+    /// Apply `update` to one of the level's orders.
+    ///
+    /// # Errors
+    ///
+    /// Returns what [`PriceLevel::update_order`] returns.
+    fn update_order(&self, update: OrderUpdate) -> Result<UpdateOutcome<T>, PriceLevelError>;
+
+    /// Match a taker of `incoming_quantity` against the level.
+    fn match_order<G>(
+        &self,
+        incoming_quantity: u64,
+        taker_order_id: Id,
+        taker_tif: TimeInForce,
+        taker_kind: TakerKind,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> MatchResult<T>
+    where
+        G: ExecutionIdGenerator + ?Sized;
+
+    /// A consistent snapshot of the level.
+    fn snapshot(&self) -> PriceLevelSnapshot<T>;
+}
+
+impl<T: Clone> BookLevel<T> for PriceLevel<T> {
+    fn price(&self) -> u128 {
+        PriceLevel::price(self)
+    }
+
+    fn visible_quantity(&self) -> u64 {
+        PriceLevel::visible_quantity(self)
+    }
+
+    fn hidden_quantity(&self) -> u64 {
+        PriceLevel::hidden_quantity(self)
+    }
+
+    fn order_count(&self) -> usize {
+        PriceLevel::order_count(self)
+    }
+
+    fn add_order(&self, order: OrderType<T>) -> Result<Arc<OrderType<T>>, PriceLevelError> {
+        PriceLevel::add_order(self, order)
+    }
+
+    fn update_order(&self, update: OrderUpdate) -> Result<UpdateOutcome<T>, PriceLevelError> {
+        PriceLevel::update_order(self, update)
+    }
+
+    fn match_order<G>(
+        &self,
+        incoming_quantity: u64,
+        taker_order_id: Id,
+        taker_tif: TimeInForce,
+        taker_kind: TakerKind,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> MatchResult<T>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        PriceLevel::match_order(
+            self,
+            incoming_quantity,
+            taker_order_id,
+            taker_tif,
+            taker_kind,
+            timestamp,
+            trade_id_generator,
+        )
+    }
+
+    fn snapshot(&self) -> PriceLevelSnapshot<T> {
+        PriceLevel::snapshot(self)
+    }
+}
+
+impl<T, L: BookLevel<T> + ?Sized> BookLevel<T> for Arc<L> {
+    fn price(&self) -> u128 {
+        (**self).price()
+    }
+
+    fn visible_quantity(&self) -> u64 {
+        (**self).visible_quantity()
+    }
+
+    fn hidden_quantity(&self) -> u64 {
+        (**self).hidden_quantity()
+    }
+
+    fn order_count(&self) -> usize {
+        (**self).order_count()
+    }
+
+    fn add_order(&self, order: OrderType<T>) -> Result<Arc<OrderType<T>>, PriceLevelError> {
+        (**self).add_order(order)
+    }
+
+    fn update_order(&self, update: OrderUpdate) -> Result<UpdateOutcome<T>, PriceLevelError> {
+        (**self).update_order(update)
+    }
+
+    fn match_order<G>(
+        &self,
+        incoming_quantity: u64,
+        taker_order_id: Id,
+        taker_tif: TimeInForce,
+        taker_kind: TakerKind,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> MatchResult<T>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        (**self).match_order(
+            incoming_quantity,
+            taker_order_id,
+            taker_tif,
+            taker_kind,
+            timestamp,
+            trade_id_generator,
+        )
+    }
+
+    fn snapshot(&self) -> PriceLevelSnapshot<T> {
+        (**self).snapshot()
+    }
+}
+
+/// Where an order rests in a book: the price of its level and the side of
+/// the book that level is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelLocation {
+    /// The level's price.
+    pub price: Price,
+    /// The book side the level is on.
+    pub side: Side,
+}
+
+impl LevelLocation {
+    /// The location of the level at `price` on `side`.
+    #[must_use]
+    pub const fn new(price: u128, side: Side) -> Self {
+        Self {
+            price: Price::new(price),
+            side,
+        }
+    }
+
+    /// The location `order` rests at: its price and side.
+    #[must_use]
+    pub fn of<T: Clone>(order: &OrderType<T>) -> Self {
+        Self {
+            price: order.price(),
+            side: order.side(),
+        }
+    }
+}
+
+impl From<(u128, Side)> for LevelLocation {
+    fn from((price, side): (u128, Side)) -> Self {
+        Self::new(price, side)
+    }
+}
+
+impl From<LevelLocation> for (u128, Side) {
+    fn from(location: LevelLocation) -> Self {
+        (location.price.as_u128(), location.side)
+    }
+}
+
+impl fmt::Display for LevelLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.side, self.price)
+    }
+}
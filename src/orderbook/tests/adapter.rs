@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelErrorKind;
+    use crate::execution::TakerKind;
+    use crate::orderbook::{BookLevel, LevelLocation};
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome};
+    use crate::price_level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn order(id: u64, quantity: u64) -> OrderType<()> {
+        order_at(id, 100, quantity)
+    }
+
+    fn order_at(id: u64, price: u128, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    /// A minimal book over any level: rests orders, indexes their locations
+    /// and maps the level's errors by kind.
+    fn rest_all<L: BookLevel<()>>(
+        level: &L,
+        orders: Vec<OrderType<()>>,
+    ) -> (HashMap<Id, LevelLocation>, Vec<PriceLevelErrorKind>) {
+        let mut locations = HashMap::new();
+        let mut errors = Vec::new();
+        for order in orders {
+            match level.add_order(order) {
+                Ok(order) => {
+                    locations.insert(order.id(), LevelLocation::of(&order));
+                }
+                Err(err) => errors.push(err.kind()),
+            }
+        }
+        (locations, errors)
+    }
+
+    #[test]
+    fn test_book_level_drives_a_shared_level() {
+        let level = Arc::new(PriceLevel::new(100));
+        let (locations, errors) = rest_all(
+            &level,
+            vec![order(1, 10), order(2, 5), order(1, 3), order_at(3, 101, 4)],
+        );
+        assert_eq!(
+            errors,
+            [
+                PriceLevelErrorKind::DuplicateOrder,
+                PriceLevelErrorKind::InvalidOrder
+            ]
+        );
+        assert_eq!(locations.len(), 2);
+        assert_eq!(
+            locations[&Id::from_u64(2)],
+            LevelLocation::new(100, Side::Sell)
+        );
+        assert_eq!(BookLevel::price(&level), 100);
+        assert_eq!(BookLevel::order_count(&level), 2);
+
+        let result = BookLevel::match_order(
+            &level,
+            12,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823000100),
+            &SequentialIdGenerator::new(0),
+        );
+        assert_eq!(result.trades().len(), 2);
+        assert_eq!(BookLevel::visible_quantity(&level), 3);
+
+        let outcome = BookLevel::update_order(
+            &level,
+            OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            },
+        )
+        .unwrap();
+        assert!(matches!(outcome, UpdateOutcome::Cancelled(..)));
+        assert_eq!(BookLevel::snapshot(&level).order_count(), 0);
+
+        let missing = BookLevel::update_order(
+            &level,
+            OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            },
+        );
+        assert!(matches!(missing, Ok(UpdateOutcome::NotFound)));
+    }
+
+    #[test]
+    fn test_book_level_on_an_owned_level() {
+        let level = PriceLevel::new(100);
+        let (locations, errors) = rest_all(&level, vec![order(1, 10)]);
+        assert!(errors.is_empty());
+        assert_eq!(locations.len(), 1);
+        assert_eq!(BookLevel::hidden_quantity(&level), 0);
+        assert_eq!(BookLevel::visible_quantity(&level), 10);
+    }
+
+    #[test]
+    fn test_level_location_conversions() {
+        let location = LevelLocation::from((250_u128, Side::Buy));
+        assert_eq!(location.price, Price::from(250_u128));
+        assert_eq!(location.side, Side::Buy);
+        assert_eq!(<(u128, Side)>::from(location), (250, Side::Buy));
+        assert_eq!(location.to_string(), "BUY@250");
+
+        let json = serde_json::to_string(&location).unwrap();
+        assert_eq!(json, r#"{"price":250,"side":"BUY"}"#);
+        assert_eq!(
+            serde_json::from_str::<LevelLocation>(&json).unwrap(),
+            location
+        );
+    }
+}
@@ -1,9 +1,11 @@
 //! Base order definitions
 
 use crate::errors::PriceLevelError;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::fmt;
-use std::str::FromStr;
 
 /// Represents the side of an order
 #[repr(u8)]
@@ -119,7 +121,7 @@ impl Hash32 {
 
         let mut bytes = [0u8; 32];
         for (i, chunk) in s.as_bytes().chunks(2).enumerate() {
-            let hex_str = std::str::from_utf8(chunk).map_err(|_| PriceLevelError::ParseError {
+            let hex_str = core::str::from_utf8(chunk).map_err(|_| PriceLevelError::ParseError {
                 message: "Invalid UTF-8 in hex string".to_string(),
             })?;
             bytes[i] =
@@ -0,0 +1,38 @@
+use crate::orders::OrderStatus;
+use crate::utils::Quantity;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// How much of a resting order has executed and how much is left, as
+/// reported by [`PriceLevel::order_fill_state`](crate::PriceLevel::order_fill_state).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OrderFillState {
+    /// Quantity executed against the order at this level so far.
+    pub cumulative_executed: Quantity,
+    /// Quantity still resting: visible plus hidden depth.
+    pub leaves_quantity: Quantity,
+}
+
+impl OrderFillState {
+    /// The status these quantities imply for a resting order:
+    /// [`OrderStatus::PartiallyFilled`] once anything has executed,
+    /// [`OrderStatus::New`] before.
+    #[must_use]
+    pub fn status(&self) -> OrderStatus {
+        if self.cumulative_executed.as_u64() > 0 {
+            OrderStatus::PartiallyFilled
+        } else {
+            OrderStatus::New
+        }
+    }
+}
+
+impl fmt::Display for OrderFillState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "executed={};leaves={}",
+            self.cumulative_executed, self.leaves_quantity
+        )
+    }
+}
@@ -0,0 +1,111 @@
+use crate::orders::OrderType;
+use crate::utils::Id;
+use alloc::sync::Arc;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+/// An order wrapper whose equality and hash are defined by the order **id**
+/// alone.
+///
+/// [`OrderType`]'s own `PartialEq` is structural: two values are equal only
+/// when every field matches, so a partially filled order no longer equals its
+/// original, and the same id with a different payload compares unequal. That
+/// is the right semantics for asserting on exact state, but the wrong one for
+/// user-side collections tracking *which* orders exist. `OrderKey` gives those
+/// collections identity semantics:
+///
+/// - `a == b` iff `a.id() == b.id()`, regardless of price, quantity, or any
+///   other field;
+/// - `hash(a)` is `hash(a.id())`, so `Hash` is consistent with `Eq`;
+/// - `OrderKey<T>: Borrow<Id>`, so a `HashMap<OrderKey, _>` or
+///   `HashSet<OrderKey>` can be queried with a bare [`Id`].
+///
+/// The wrapped order is held behind an [`Arc`], so keys built from the
+/// `Arc<OrderType<()>>` values a [`PriceLevel`](crate::PriceLevel) hands out
+/// share the allocation rather than copying the payload.
+pub struct OrderKey<T = ()> {
+    order: Arc<OrderType<T>>,
+}
+
+impl<T> OrderKey<T> {
+    /// Wraps a shared order.
+    #[must_use]
+    pub fn new(order: Arc<OrderType<T>>) -> Self {
+        Self { order }
+    }
+
+    /// Returns the id that defines this key's identity.
+    #[must_use]
+    pub fn id(&self) -> Id {
+        *self.order.id_ref()
+    }
+
+    /// Returns the wrapped order.
+    #[must_use]
+    pub fn order(&self) -> &Arc<OrderType<T>> {
+        &self.order
+    }
+
+    /// Unwraps the key, returning the shared order.
+    #[must_use]
+    pub fn into_inner(self) -> Arc<OrderType<T>> {
+        self.order
+    }
+}
+
+impl<T> Clone for OrderKey<T> {
+    fn clone(&self) -> Self {
+        Self {
+            order: Arc::clone(&self.order),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OrderKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OrderKey").field(&self.order).finish()
+    }
+}
+
+impl<T> PartialEq for OrderKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
+    }
+}
+
+impl<T> Eq for OrderKey<T> {}
+
+impl<T> Hash for OrderKey<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Must hash exactly like `Id` for the `Borrow<Id>` impl to be sound.
+        self.id().hash(state);
+    }
+}
+
+impl<T> Borrow<Id> for OrderKey<T> {
+    fn borrow(&self) -> &Id {
+        self.order.id_ref()
+    }
+}
+
+impl<T> Deref for OrderKey<T> {
+    type Target = OrderType<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.order
+    }
+}
+
+impl<T> From<Arc<OrderType<T>>> for OrderKey<T> {
+    fn from(order: Arc<OrderType<T>>) -> Self {
+        Self::new(order)
+    }
+}
+
+impl<T> From<OrderType<T>> for OrderKey<T> {
+    fn from(order: OrderType<T>) -> Self {
+        Self::new(Arc::new(order))
+    }
+}
@@ -5,40 +5,71 @@
 //! # Key Types
 //!
 //! - [`OrderType`] — enum covering all supported order variants (Standard, Iceberg,
-//!   Reserve, PostOnly, TrailingStop, PeggedOrder, MarketToLimit).
+//!   Reserve, PostOnly, TrailingStop, PeggedOrder, Market, MarketToLimit,
+//!   StopMarket, StopLimit).
 //! - [`OrderUpdate`] — enum for order mutations (update price, quantity, cancel, replace).
+//! - [`UpdateOutcome`] — what applying an [`OrderUpdate`] did (resized in place,
+//!   removed for a reprice, cancelled, or not found).
+//! - [`OrderReplacement`] — the cancelled order and its replacement from a
+//!   cancel/replace.
+//! - [`OrderStatus`] / [`OrderStatusTransition`] — exchange-style order state
+//!   (new, partially filled, filled, cancelled, expired, rejected) and the
+//!   changes a level reports.
+//! - [`OrderFillState`] — a resting order's cumulative executed and leaves
+//!   quantity.
 //! - [`Id`] — flexible identifier supporting UUID, ULID, and sequential (`u64`) formats.
 //! - [`Side`] — `Buy` or `Sell`, with `#[repr(u8)]` for compact representation.
 //! - [`TimeInForce`] — order duration policies (GTC, IOC, FOK, GTD, Day),
 //!   with `#[repr(u8)]`.
 //! - [`Hash32`] — opaque 32-byte user identifier.
 //! - [`PegReferenceType`] — reference price type for pegged orders.
+//! - [`ReplenishmentStrategy`] — how iceberg and reserve orders refresh their
+//!   visible part from hidden quantity ([`DefaultReplenishment`] is the
+//!   built-in rule, [`RandomizedReplenish`] draws display sizes from a band).
+//! - [`OrderKey`] — order wrapper with id-based equality and hashing, for use
+//!   as a key in user-side maps and sets.
 //!
 //! # Order Lifecycle
 //!
 //! Orders are constructed as [`OrderType`] variants and added to a
 //! [`PriceLevel`](crate::PriceLevel) via [`add_order()`](crate::PriceLevel::add_order).
 //! Mutations are applied via [`update_order()`](crate::PriceLevel::update_order)
-//! using [`OrderUpdate`] variants.
+//! using [`OrderUpdate`] variants, and report an [`UpdateOutcome`].
 
 mod base;
 
+mod fill_state;
+
+mod key;
+
 mod order_type;
 
 mod pegged;
 
+mod replenishment;
+
 mod status;
 
 mod time_in_force;
 
 mod update;
 
+mod update_outcome;
+
 mod tests;
 
 pub use crate::utils::Id;
 pub use base::{Hash32, Side};
+pub use fill_state::OrderFillState;
+pub use key::OrderKey;
 pub use order_type::DEFAULT_RESERVE_REPLENISH_AMOUNT;
 pub use order_type::OrderType;
 pub use pegged::PegReferenceType;
+pub use replenishment::{
+    DefaultReplenishment, OrderRefresh, RandomizedReplenish, RefreshPriority, ReplenishTrigger,
+    Replenishment, ReplenishmentContext, ReplenishmentStrategy,
+};
+pub use status::{OrderStatus, OrderStatusTransition};
 pub use time_in_force::TimeInForce;
 pub use update::OrderUpdate;
+pub use update_outcome::{OrderReplacement, UpdateOutcome};
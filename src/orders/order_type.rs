@@ -1,15 +1,29 @@
 //! Limit order type definitions
 
+#[cfg(feature = "std")]
 use crate::OrderQueue;
+#[cfg(feature = "std")]
 use crate::errors::PriceLevelError;
-use crate::orders::{Hash32, Id, PegReferenceType, Side, TimeInForce};
+use crate::orders::{
+    DefaultReplenishment, Hash32, Id, PegReferenceType, ReplenishTrigger, ReplenishmentContext,
+    ReplenishmentStrategy, Side, TimeInForce,
+};
+#[cfg(feature = "std")]
+use crate::parse::{ParseOptions, RecordFields};
 use crate::utils::{Price, Quantity, TimestampMs};
+use alloc::format;
+use alloc::string::ToString;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::num::NonZeroU64;
+#[cfg(feature = "std")]
+use core::str::FromStr;
 use serde::{Deserialize, Serialize};
-use std::fmt;
-use std::num::NonZeroU64;
-use std::str::FromStr;
-use std::sync::Arc;
 
+#[cfg(feature = "std")]
 fn user_id_from_str(value: &str) -> Result<Hash32, PriceLevelError> {
     let value = value.strip_prefix("0x").unwrap_or(value);
     Hash32::from_hex(value).map_err(|_| PriceLevelError::InvalidFieldValue {
@@ -34,6 +48,12 @@ pub const DEFAULT_RESERVE_REPLENISH_AMOUNT: NonZeroU64 = match NonZeroU64::new(8
 };
 
 /// Represents different types of limit orders
+///
+/// `PartialEq` / `Eq` are **structural**: two orders compare equal only when
+/// every field, including quantity and extra fields, matches. To key
+/// collections by order identity instead, wrap orders in
+/// [`OrderKey`](crate::orders::OrderKey), whose equality and hash are defined
+/// by the order id alone.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum OrderType<T> {
     /// Standard limit order
@@ -146,6 +166,26 @@ pub enum OrderType<T> {
         extra_fields: T,
     },
 
+    /// Market order: a taker with no limit price that takes whatever its
+    /// level offers. It never rests; see [`OrderType::price`] for the price
+    /// it reports
+    Market {
+        /// The order ID
+        id: Id,
+        /// The quantity of the order
+        quantity: Quantity,
+        /// The side of the order (buy or sell)
+        side: Side,
+        /// Owner identifier for fast lookup (32 bytes)
+        user_id: Hash32,
+        /// When the order was created
+        timestamp: TimestampMs,
+        /// Time-in-force policy
+        time_in_force: TimeInForce,
+        /// Additional custom fields
+        extra_fields: T,
+    },
+
     /// Market-to-limit order that converts to limit after initial execution
     MarketToLimit {
         /// The order ID
@@ -166,6 +206,55 @@ pub enum OrderType<T> {
         extra_fields: T,
     },
 
+    /// Stop-market order that rests dormant until the market trades through
+    /// its trigger price, then activates as a [`OrderType::MarketToLimit`]
+    /// order at `price` (see [`OrderType::activate`])
+    StopMarket {
+        /// The order ID
+        id: Id,
+        /// The price of the order (the level it rests at and the limit it
+        /// converts to once activated)
+        price: Price,
+        /// The quantity of the order
+        quantity: Quantity,
+        /// The side of the order (buy or sell)
+        side: Side,
+        /// Owner identifier for fast lookup (32 bytes)
+        user_id: Hash32,
+        /// When the order was created
+        timestamp: TimestampMs,
+        /// Time-in-force policy
+        time_in_force: TimeInForce,
+        /// Reference price at which the stop activates
+        trigger_price: Price,
+        /// Additional custom fields
+        extra_fields: T,
+    },
+
+    /// Stop-limit order that rests dormant until the market trades through
+    /// its trigger price, then activates as a [`OrderType::Standard`] limit
+    /// order at `price` (see [`OrderType::activate`])
+    StopLimit {
+        /// The order ID
+        id: Id,
+        /// The limit price of the order once activated
+        price: Price,
+        /// The quantity of the order
+        quantity: Quantity,
+        /// The side of the order (buy or sell)
+        side: Side,
+        /// Owner identifier for fast lookup (32 bytes)
+        user_id: Hash32,
+        /// When the order was created
+        timestamp: TimestampMs,
+        /// Time-in-force policy
+        time_in_force: TimeInForce,
+        /// Reference price at which the stop activates
+        trigger_price: Price,
+        /// Additional custom fields
+        extra_fields: T,
+    },
+
     /// Reserve order with custom replenishment
     /// if `replenish_amount` is None, it uses DEFAULT_RESERVE_REPLENISH_AMOUNT
     /// if `auto_replenish` is false, and visible quantity is below threshold, it will not replenish
@@ -200,6 +289,54 @@ pub enum OrderType<T> {
         /// Additional custom fields
         extra_fields: T,
     },
+
+    /// Fully non-displayed order: its whole quantity is hidden, so it never
+    /// counts toward a level's visible quantity or its market data. It trades
+    /// like a standard limit order, after the displayed orders at its price
+    /// unless the level's [`HiddenPriority`](crate::HiddenPriority) lets it
+    /// keep its time priority.
+    Hidden {
+        /// The order ID
+        id: Id,
+        /// The price of the order
+        price: Price,
+        /// The (hidden) quantity of the order
+        quantity: Quantity,
+        /// The side of the order (buy or sell)
+        side: Side,
+        /// Owner identifier for fast lookup (32 bytes)
+        user_id: Hash32,
+        /// When the order was created
+        timestamp: TimestampMs,
+        /// Time-in-force policy
+        time_in_force: TimeInForce,
+        /// Additional custom fields
+        extra_fields: T,
+    },
+}
+
+impl<T> OrderType<T> {
+    /// Borrow the order ID in place.
+    ///
+    /// Unlike [`Self::id`] this needs no `T: Clone` bound and hands out a
+    /// reference, which is what [`OrderKey`](crate::orders::OrderKey)'s
+    /// `Borrow<Id>` impl requires.
+    #[inline]
+    pub(crate) fn id_ref(&self) -> &Id {
+        match self {
+            Self::Standard { id, .. }
+            | Self::IcebergOrder { id, .. }
+            | Self::PostOnly { id, .. }
+            | Self::TrailingStop { id, .. }
+            | Self::PeggedOrder { id, .. }
+            | Self::Market { id, .. }
+            | Self::MarketToLimit { id, .. }
+            | Self::StopMarket { id, .. }
+            | Self::StopLimit { id, .. }
+            | Self::ReserveOrder { id, .. }
+            | Self::Hidden { id, .. } => id,
+        }
+    }
 }
 
 impl<T: Clone> OrderType<T> {
@@ -213,8 +350,12 @@ impl<T: Clone> OrderType<T> {
             Self::PostOnly { id, .. } => *id,
             Self::TrailingStop { id, .. } => *id,
             Self::PeggedOrder { id, .. } => *id,
+            Self::Market { id, .. } => *id,
             Self::MarketToLimit { id, .. } => *id,
+            Self::StopMarket { id, .. } => *id,
+            Self::StopLimit { id, .. } => *id,
             Self::ReserveOrder { id, .. } => *id,
+            Self::Hidden { id, .. } => *id,
         }
     }
 
@@ -227,23 +368,38 @@ impl<T: Clone> OrderType<T> {
             | Self::PostOnly { user_id, .. }
             | Self::TrailingStop { user_id, .. }
             | Self::PeggedOrder { user_id, .. }
+            | Self::Market { user_id, .. }
             | Self::MarketToLimit { user_id, .. }
-            | Self::ReserveOrder { user_id, .. } => *user_id,
+            | Self::StopMarket { user_id, .. }
+            | Self::StopLimit { user_id, .. }
+            | Self::ReserveOrder { user_id, .. }
+            | Self::Hidden { user_id, .. } => *user_id,
         }
     }
 
     /// Get the price
+    ///
+    /// A [`Self::Market`] order has no limit, so it reports the most
+    /// aggressive price for its side — [`u128::MAX`] for a buy, zero for a
+    /// sell — which crosses every level.
     #[must_use]
     #[inline]
     pub fn price(&self) -> Price {
         match self {
+            Self::Market { side, .. } => match side {
+                Side::Buy => Price::new(u128::MAX),
+                Side::Sell => Price::ZERO,
+            },
             Self::Standard { price, .. } => *price,
             Self::IcebergOrder { price, .. } => *price,
             Self::PostOnly { price, .. } => *price,
             Self::TrailingStop { price, .. } => *price,
             Self::PeggedOrder { price, .. } => *price,
             Self::MarketToLimit { price, .. } => *price,
+            Self::StopMarket { price, .. } => *price,
+            Self::StopLimit { price, .. } => *price,
             Self::ReserveOrder { price, .. } => *price,
+            Self::Hidden { price, .. } => *price,
         }
     }
 
@@ -259,16 +415,38 @@ impl<T: Clone> OrderType<T> {
             Self::PostOnly { quantity, .. } => *quantity,
             Self::TrailingStop { quantity, .. } => *quantity,
             Self::PeggedOrder { quantity, .. } => *quantity,
+            Self::Market { quantity, .. } => *quantity,
             Self::MarketToLimit { quantity, .. } => *quantity,
+            Self::StopMarket { quantity, .. } => *quantity,
+            Self::StopLimit { quantity, .. } => *quantity,
             Self::ReserveOrder {
                 visible_quantity, ..
             } => *visible_quantity,
+            Self::Hidden { .. } => Quantity::ZERO,
+        }
+    }
+
+    /// Get the visible quantity this order contributes to its level's
+    /// displayed depth, in quantity units.
+    ///
+    /// The same as [`Self::visible_quantity`], except for a pending stop
+    /// ([`Self::StopMarket`] / [`Self::StopLimit`]): it is dormant and shows
+    /// nothing until [`Self::activate`] converts it, so it contributes
+    /// [`Quantity::ZERO`].
+    #[must_use]
+    #[inline]
+    pub fn working_visible_quantity(&self) -> Quantity {
+        if self.is_stop() {
+            Quantity::ZERO
+        } else {
+            self.visible_quantity()
         }
     }
 
     /// Get the hidden quantity, in quantity units.
     ///
-    /// Order types without a hidden tranche return [`Quantity::ZERO`].
+    /// A [`Self::Hidden`] order's whole quantity is hidden. Order types
+    /// without a hidden tranche return [`Quantity::ZERO`].
     #[must_use]
     #[inline]
     pub fn hidden_quantity(&self) -> Quantity {
@@ -279,6 +457,7 @@ impl<T: Clone> OrderType<T> {
             Self::ReserveOrder {
                 hidden_quantity, ..
             } => *hidden_quantity,
+            Self::Hidden { quantity, .. } => *quantity,
             _ => Quantity::ZERO,
         }
     }
@@ -299,10 +478,18 @@ impl<T: Clone> OrderType<T> {
     ///   `auto_replenish` is set; without auto-replenish a drained reserve is
     ///   dropped by the sweep without ever filling, so it is *not* matchable
     ///   depth.
+    /// - A **hidden** order with quantity is matchable: it trades straight
+    ///   from its hidden quantity.
     /// - Every other zero-visible order (no hidden to draw on) is not matchable.
+    /// - A pending **stop** ([`Self::StopMarket`] / [`Self::StopLimit`]) is never
+    ///   matchable, whatever its quantity: it is dormant until
+    ///   [`Self::activate`] converts it, and the sweep passes over it.
     #[must_use]
     #[inline]
     pub fn is_matchable(&self) -> bool {
+        if self.is_stop() {
+            return false;
+        }
         if self.visible_quantity().as_u64() > 0 {
             return true;
         }
@@ -315,6 +502,7 @@ impl<T: Clone> OrderType<T> {
                 auto_replenish,
                 ..
             } => *auto_replenish && hidden_quantity.as_u64() > 0,
+            Self::Hidden { quantity, .. } => quantity.as_u64() > 0,
             _ => false,
         }
     }
@@ -329,8 +517,12 @@ impl<T: Clone> OrderType<T> {
             Self::PostOnly { side, .. } => *side,
             Self::TrailingStop { side, .. } => *side,
             Self::PeggedOrder { side, .. } => *side,
+            Self::Market { side, .. } => *side,
             Self::MarketToLimit { side, .. } => *side,
+            Self::StopMarket { side, .. } => *side,
+            Self::StopLimit { side, .. } => *side,
             Self::ReserveOrder { side, .. } => *side,
+            Self::Hidden { side, .. } => *side,
         }
     }
 
@@ -343,8 +535,12 @@ impl<T: Clone> OrderType<T> {
             Self::PostOnly { time_in_force, .. } => *time_in_force,
             Self::TrailingStop { time_in_force, .. } => *time_in_force,
             Self::PeggedOrder { time_in_force, .. } => *time_in_force,
+            Self::Market { time_in_force, .. } => *time_in_force,
             Self::MarketToLimit { time_in_force, .. } => *time_in_force,
+            Self::StopMarket { time_in_force, .. } => *time_in_force,
+            Self::StopLimit { time_in_force, .. } => *time_in_force,
             Self::ReserveOrder { time_in_force, .. } => *time_in_force,
+            Self::Hidden { time_in_force, .. } => *time_in_force,
         }
     }
 
@@ -358,8 +554,12 @@ impl<T: Clone> OrderType<T> {
             Self::PostOnly { timestamp, .. } => *timestamp,
             Self::TrailingStop { timestamp, .. } => *timestamp,
             Self::PeggedOrder { timestamp, .. } => *timestamp,
+            Self::Market { timestamp, .. } => *timestamp,
             Self::MarketToLimit { timestamp, .. } => *timestamp,
+            Self::StopMarket { timestamp, .. } => *timestamp,
+            Self::StopLimit { timestamp, .. } => *timestamp,
             Self::ReserveOrder { timestamp, .. } => *timestamp,
+            Self::Hidden { timestamp, .. } => *timestamp,
         }
     }
 
@@ -369,6 +569,26 @@ impl<T: Clone> OrderType<T> {
         self.time_in_force().is_immediate()
     }
 
+    /// When the order expires: its `Gtd` date, or its timestamp plus its
+    /// `Gtt` duration. `None` for a time in force without a fixed expiry.
+    #[must_use]
+    pub fn expires_at(&self) -> Option<TimestampMs> {
+        self.time_in_force()
+            .expires_at(self.timestamp().as_u64())
+            .map(TimestampMs::new)
+    }
+
+    /// Check if the order has expired by `now`; a `Day` order expires at
+    /// `market_close`, if given.
+    #[must_use]
+    pub fn is_expired(&self, now: TimestampMs, market_close: Option<TimestampMs>) -> bool {
+        self.time_in_force().is_expired_at(
+            self.timestamp().as_u64(),
+            now.as_u64(),
+            market_close.map(TimestampMs::as_u64),
+        )
+    }
+
     /// Check if the order is fill-or-kill
     #[must_use]
     pub fn is_fill_or_kill(&self) -> bool {
@@ -381,6 +601,272 @@ impl<T: Clone> OrderType<T> {
         matches!(self, Self::PostOnly { .. })
     }
 
+    /// Check if this is a fully non-displayed [`Self::Hidden`] order
+    #[must_use]
+    #[inline]
+    pub fn is_hidden(&self) -> bool {
+        matches!(self, Self::Hidden { .. })
+    }
+
+    /// Check if this is a market order, which has no limit price
+    #[must_use]
+    pub fn is_market(&self) -> bool {
+        matches!(self, Self::Market { .. })
+    }
+
+    /// Check if this is a pending (not yet activated) stop order
+    #[must_use]
+    #[inline]
+    pub fn is_stop(&self) -> bool {
+        matches!(self, Self::StopMarket { .. } | Self::StopLimit { .. })
+    }
+
+    /// Get the trigger price of a stop order, or `None` for every other variant
+    #[must_use]
+    pub fn trigger_price(&self) -> Option<Price> {
+        match self {
+            Self::StopMarket { trigger_price, .. } | Self::StopLimit { trigger_price, .. } => {
+                Some(*trigger_price)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `reference_price` satisfies this stop order's trigger
+    /// condition.
+    ///
+    /// A buy stop triggers once the reference price rises to or above its
+    /// trigger price; a sell stop triggers once it falls to or below it. Every
+    /// non-stop variant returns `false`.
+    #[must_use]
+    pub fn is_triggered_by(&self, reference_price: Price) -> bool {
+        match self.trigger_price() {
+            Some(trigger) => match self.side() {
+                Side::Buy => reference_price >= trigger,
+                Side::Sell => reference_price <= trigger,
+            },
+            None => false,
+        }
+    }
+
+    /// Convert a pending stop order into the live order it activates as.
+    ///
+    /// A [`Self::StopMarket`] becomes a [`Self::MarketToLimit`] and a
+    /// [`Self::StopLimit`] becomes a [`Self::Standard`] limit order; both keep
+    /// the id, price, quantity, side, owner, timestamp, time-in-force and extra
+    /// fields, and drop the trigger price. Returns `None` for every non-stop
+    /// variant. The trigger condition is **not** checked here — see
+    /// [`Self::is_triggered_by`].
+    #[must_use]
+    pub fn activate(&self) -> Option<Self> {
+        match self {
+            Self::StopMarket {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields,
+                ..
+            } => Some(Self::MarketToLimit {
+                id: *id,
+                price: *price,
+                quantity: *quantity,
+                side: *side,
+                user_id: *user_id,
+                timestamp: *timestamp,
+                time_in_force: *time_in_force,
+                extra_fields: extra_fields.clone(),
+            }),
+            Self::StopLimit {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields,
+                ..
+            } => Some(Self::Standard {
+                id: *id,
+                price: *price,
+                quantity: *quantity,
+                side: *side,
+                user_id: *user_id,
+                timestamp: *timestamp,
+                time_in_force: *time_in_force,
+                extra_fields: extra_fields.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Get the current stop level of a trailing stop, or `None` for every other
+    /// variant.
+    ///
+    /// The stop trails `last_reference_price` by `trail_amount`: below it for a
+    /// sell, above it for a buy. Both bounds saturate rather than wrap.
+    #[must_use]
+    pub fn trailing_stop_price(&self) -> Option<Price> {
+        match self {
+            Self::TrailingStop {
+                side,
+                trail_amount,
+                last_reference_price,
+                ..
+            } => {
+                let reference = last_reference_price.as_u128();
+                let trail = u128::from(trail_amount.as_u64());
+                Some(Price::new(match side {
+                    Side::Sell => reference.saturating_sub(trail),
+                    Side::Buy => reference.saturating_add(trail),
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if `market_price` has reached this trailing stop's stop
+    /// level.
+    ///
+    /// A sell trailing stop is reached once the market falls to or below its
+    /// stop level, a buy trailing stop once the market rises to or above it.
+    /// A trailing stop whose `last_reference_price` is still zero has not been
+    /// seeded by [`Self::with_trailing_reference`] yet and is never reached.
+    /// Every other variant returns `false`.
+    #[must_use]
+    pub fn is_trailing_stop_reached(&self, market_price: Price) -> bool {
+        match self {
+            Self::TrailingStop {
+                side,
+                last_reference_price,
+                ..
+            } if *last_reference_price != Price::ZERO => {
+                let Some(stop) = self.trailing_stop_price() else {
+                    return false;
+                };
+                match side {
+                    Side::Sell => market_price <= stop,
+                    Side::Buy => market_price >= stop,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Return a clone of this trailing stop with its reference price ratcheted
+    /// to `market_price`, or `None` if the reference does not move.
+    ///
+    /// The reference only ever moves in the order's favour: up for a sell (the
+    /// stop follows a rising market), down for a buy (the stop follows a
+    /// falling market). A zero `last_reference_price` is treated as unseeded
+    /// and is set to `market_price` unconditionally. Returns `None` for every
+    /// other variant.
+    #[must_use]
+    pub fn with_trailing_reference(&self, market_price: Price) -> Option<Self> {
+        match self {
+            Self::TrailingStop {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                trail_amount,
+                last_reference_price,
+                extra_fields,
+            } => {
+                let moves = *last_reference_price == Price::ZERO
+                    || match side {
+                        Side::Sell => market_price > *last_reference_price,
+                        Side::Buy => market_price < *last_reference_price,
+                    };
+                moves.then(|| Self::TrailingStop {
+                    id: *id,
+                    price: *price,
+                    quantity: *quantity,
+                    side: *side,
+                    user_id: *user_id,
+                    timestamp: *timestamp,
+                    time_in_force: *time_in_force,
+                    trail_amount: *trail_amount,
+                    last_reference_price: market_price,
+                    extra_fields: extra_fields.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Return a clone of this order with its time in force replaced by
+    /// `time_in_force`; every other field, including the quantities, is kept.
+    #[must_use]
+    pub fn with_time_in_force(&self, time_in_force: TimeInForce) -> Self {
+        let mut order = self.clone();
+        match &mut order {
+            Self::Standard {
+                time_in_force: tif, ..
+            }
+            | Self::IcebergOrder {
+                time_in_force: tif, ..
+            }
+            | Self::PostOnly {
+                time_in_force: tif, ..
+            }
+            | Self::TrailingStop {
+                time_in_force: tif, ..
+            }
+            | Self::PeggedOrder {
+                time_in_force: tif, ..
+            }
+            | Self::Market {
+                time_in_force: tif, ..
+            }
+            | Self::MarketToLimit {
+                time_in_force: tif, ..
+            }
+            | Self::StopMarket {
+                time_in_force: tif, ..
+            }
+            | Self::StopLimit {
+                time_in_force: tif, ..
+            }
+            | Self::ReserveOrder {
+                time_in_force: tif, ..
+            }
+            | Self::Hidden {
+                time_in_force: tif, ..
+            } => *tif = time_in_force,
+        }
+        order
+    }
+
+    /// Return a clone of this order with its limit price replaced by `price`;
+    /// every other field, including the timestamp, is kept. A
+    /// [`Self::Market`] order has no limit price and is returned unchanged.
+    #[must_use]
+    pub fn with_price(&self, price: Price) -> Self {
+        let mut order = self.clone();
+        match &mut order {
+            Self::Market { .. } => {}
+            Self::Standard { price: p, .. }
+            | Self::IcebergOrder { price: p, .. }
+            | Self::PostOnly { price: p, .. }
+            | Self::TrailingStop { price: p, .. }
+            | Self::PeggedOrder { price: p, .. }
+            | Self::MarketToLimit { price: p, .. }
+            | Self::StopMarket { price: p, .. }
+            | Self::StopLimit { price: p, .. }
+            | Self::ReserveOrder { price: p, .. }
+            | Self::Hidden { price: p, .. } => *p = price,
+        }
+        order
+    }
+
     /// Return a clone of this order with its resting (visible / main) quantity
     /// reset to `new_quantity`, in quantity units.
     ///
@@ -388,7 +874,8 @@ impl<T: Clone> OrderType<T> {
     /// [`Self::match_against`]) or an `OrderUpdate::UpdateQuantity` leaves the
     /// residual maker at exactly `new_quantity` rather than its original size.
     /// For the single-quantity variants (`Standard`, `PostOnly`,
-    /// `TrailingStop`, `PeggedOrder`, `MarketToLimit`) this rewrites the
+    /// `TrailingStop`, `PeggedOrder`, `Market`, `MarketToLimit`, `StopMarket`,
+    /// `StopLimit`, `Hidden`) this rewrites the
     /// `quantity` field; for the two-tranche variants (`IcebergOrder`,
     /// `ReserveOrder`) it rewrites the *visible* quantity and preserves the
     /// hidden tranche together with every order-type-specific field (trail /
@@ -510,6 +997,23 @@ impl<T: Clone> OrderType<T> {
                 reference_price_type: *reference_price_type,
                 extra_fields: extra_fields.clone(),
             },
+            Self::Market {
+                id,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields,
+                ..
+            } => Self::Market {
+                id: *id,
+                quantity: new_quantity,
+                side: *side,
+                user_id: *user_id,
+                timestamp: *timestamp,
+                time_in_force: *time_in_force,
+                extra_fields: extra_fields.clone(),
+            },
             Self::MarketToLimit {
                 id,
                 price,
@@ -529,6 +1033,48 @@ impl<T: Clone> OrderType<T> {
                 time_in_force: *time_in_force,
                 extra_fields: extra_fields.clone(),
             },
+            Self::StopMarket {
+                id,
+                price,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                trigger_price,
+                extra_fields,
+                ..
+            } => Self::StopMarket {
+                id: *id,
+                price: *price,
+                quantity: new_quantity,
+                side: *side,
+                user_id: *user_id,
+                timestamp: *timestamp,
+                time_in_force: *time_in_force,
+                trigger_price: *trigger_price,
+                extra_fields: extra_fields.clone(),
+            },
+            Self::StopLimit {
+                id,
+                price,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                trigger_price,
+                extra_fields,
+                ..
+            } => Self::StopLimit {
+                id: *id,
+                price: *price,
+                quantity: new_quantity,
+                side: *side,
+                user_id: *user_id,
+                timestamp: *timestamp,
+                time_in_force: *time_in_force,
+                trigger_price: *trigger_price,
+                extra_fields: extra_fields.clone(),
+            },
             // Reserve rewrites the visible tranche (mirroring `IcebergOrder`),
             // preserving the hidden quantity and the replenishment policy.
             Self::ReserveOrder {
@@ -558,6 +1104,25 @@ impl<T: Clone> OrderType<T> {
                 auto_replenish: *auto_replenish,
                 extra_fields: extra_fields.clone(),
             },
+            Self::Hidden {
+                id,
+                price,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields,
+                ..
+            } => Self::Hidden {
+                id: *id,
+                price: *price,
+                quantity: new_quantity,
+                side: *side,
+                user_id: *user_id,
+                timestamp: *timestamp,
+                time_in_force: *time_in_force,
+                extra_fields: extra_fields.clone(),
+            },
         }
     }
 
@@ -639,15 +1204,20 @@ impl<T: Clone> OrderType<T> {
                     used_hidden,
                 )
             }
-            // Single-tranche variants have no hidden reserve to draw from, so a
-            // refresh is a no-op that draws `0`. Listed explicitly (rather than
-            // via a `_` fallback) so a future variant with a hidden tranche is a
-            // compile error here until it defines its own refresh behaviour.
+            // Single-tranche variants have no hidden reserve to draw from, and
+            // a hidden order never displays any, so a refresh is a no-op that
+            // draws `0`. Listed explicitly (rather than via a `_` fallback) so a
+            // future variant with a hidden tranche is a compile error here
+            // until it defines its own refresh behaviour.
             Self::Standard { .. }
             | Self::PostOnly { .. }
             | Self::TrailingStop { .. }
             | Self::PeggedOrder { .. }
-            | Self::MarketToLimit { .. } => (self.clone(), 0),
+            | Self::Market { .. }
+            | Self::MarketToLimit { .. }
+            | Self::StopMarket { .. }
+            | Self::StopLimit { .. }
+            | Self::Hidden { .. } => (self.clone(), 0),
         }
     }
 }
@@ -661,6 +1231,10 @@ impl<T: Clone> OrderType<T> {
     /// - The quantity that was reduced from hidden portion (for iceberg/reserve orders)
     /// - The remaining quantity of the incoming order
     ///
+    /// A [`Self::Hidden`] order trades straight from its hidden quantity, so
+    /// its consumed quantity leaves the hidden portion and nothing is drawn
+    /// into view: the third element stays `0`.
+    ///
     /// # Overflow
     ///
     /// The only quantity *addition* on any match path is a reserve order's
@@ -673,8 +1247,40 @@ impl<T: Clone> OrderType<T> {
     /// aside", so the step fails atomically: no trade, maker and taker
     /// unchanged. Every other path uses only subtraction / `min`, which cannot
     /// overflow.
+    ///
+    /// Iceberg and reserve refreshes follow [`DefaultReplenishment`]; see
+    /// [`Self::match_against_with`] to plug in another strategy.
     #[must_use]
     pub fn match_against(&self, incoming_quantity: u64) -> (u64, Option<Self>, u64, u64) {
+        self.match_against_with(incoming_quantity, &DefaultReplenishment, None)
+    }
+
+    /// [`Self::match_against`], with iceberg and reserve refreshes sized and
+    /// timestamped by `strategy`.
+    ///
+    /// `match_timestamp` is the time of the match, passed on to the strategy
+    /// in its [`ReplenishmentContext`]. The strategy's quantity is clamped to
+    /// `1..=hidden`, so whatever it returns, a refresh draws real depth and
+    /// the overflow guard above still applies.
+    #[must_use]
+    pub fn match_against_with(
+        &self,
+        incoming_quantity: u64,
+        strategy: &dyn ReplenishmentStrategy,
+        match_timestamp: Option<TimestampMs>,
+    ) -> (u64, Option<Self>, u64, u64) {
+        let replenish = |trigger, visible_remaining, hidden: u64, default_quantity| {
+            let decision = strategy.replenish(&ReplenishmentContext {
+                order_id: self.id(),
+                trigger,
+                visible_remaining,
+                hidden_quantity: hidden,
+                default_quantity,
+                order_timestamp: self.timestamp(),
+                match_timestamp,
+            });
+            (decision.quantity.clamp(1, hidden), decision.timestamp)
+        };
         match self {
             Self::Standard {
                 id,
@@ -750,7 +1356,12 @@ impl<T: Clone> OrderType<T> {
                         } else {
                             visible_quantity.as_u64()
                         };
-                        let refresh_qty = std::cmp::min(hidden_quantity.as_u64(), tranche);
+                        let (refresh_qty, refreshed_at) = replenish(
+                            ReplenishTrigger::IcebergTranche,
+                            0,
+                            hidden_quantity.as_u64(),
+                            core::cmp::min(hidden_quantity.as_u64(), tranche),
+                        );
                         let new_hidden = hidden_quantity.as_u64() - refresh_qty;
 
                         // Create updated order with refreshed quantities
@@ -763,7 +1374,7 @@ impl<T: Clone> OrderType<T> {
                                 hidden_quantity: Quantity::new(new_hidden),
                                 side: *side,
                                 user_id: *user_id,
-                                timestamp: *timestamp,
+                                timestamp: refreshed_at,
                                 time_in_force: *time_in_force,
                                 extra_fields: extra_fields.clone(),
                             }),
@@ -818,7 +1429,7 @@ impl<T: Clone> OrderType<T> {
                     replenish_threshold.as_u64()
                 };
 
-                let replenish_qty = replenish_amount
+                let default_qty = replenish_amount
                     .unwrap_or(DEFAULT_RESERVE_REPLENISH_AMOUNT)
                     .get()
                     .min(hidden_quantity.as_u64());
@@ -831,6 +1442,12 @@ impl<T: Clone> OrderType<T> {
                     // Verify if we need and can replenish
                     if hidden_quantity.as_u64() > 0 && *auto_replenish {
                         // Restore from the hidden quantity
+                        let (replenish_qty, refreshed_at) = replenish(
+                            ReplenishTrigger::ReserveDepleted,
+                            0,
+                            hidden_quantity.as_u64(),
+                            default_qty,
+                        );
                         let new_hidden = hidden_quantity.as_u64() - replenish_qty;
 
                         (
@@ -842,7 +1459,7 @@ impl<T: Clone> OrderType<T> {
                                 hidden_quantity: Quantity::new(new_hidden),
                                 side: *side,
                                 user_id: *user_id,
-                                timestamp: *timestamp,
+                                timestamp: refreshed_at,
                                 time_in_force: *time_in_force,
                                 replenish_threshold: *replenish_threshold,
                                 replenish_amount: *replenish_amount,
@@ -869,7 +1486,7 @@ impl<T: Clone> OrderType<T> {
                         // Refreshed visible is `new_visible + replenish_qty`.
                         // This sum is provably `<= u64::MAX` for any order the
                         // level admits: `new_visible <= visible_quantity`,
-                        // `replenish_qty` is capped by `.min(hidden_quantity)`
+                        // `replenish_qty` is clamped to `hidden_quantity`
                         // above, and `PriceLevel::add_order` /
                         // `PriceLevelSnapshot::refresh_aggregates` reject any
                         // order whose own `visible + hidden` overflows `u64`,
@@ -883,6 +1500,12 @@ impl<T: Clone> OrderType<T> {
                         // no-progress sentinel both the real sweep and the
                         // fill-or-kill dry run detect and set aside — never a
                         // partial or a manufactured (wrapped) fill.
+                        let (replenish_qty, refreshed_at) = replenish(
+                            ReplenishTrigger::ReserveBelowThreshold,
+                            new_visible,
+                            hidden_quantity.as_u64(),
+                            default_qty,
+                        );
                         let Some(refreshed_visible) = new_visible.checked_add(replenish_qty) else {
                             return (0, Some(self.clone()), 0, incoming_quantity);
                         };
@@ -898,7 +1521,7 @@ impl<T: Clone> OrderType<T> {
                                 hidden_quantity: Quantity::new(new_hidden),
                                 side: *side,
                                 user_id: *user_id,
-                                timestamp: *timestamp,
+                                timestamp: refreshed_at,
                                 time_in_force: *time_in_force,
                                 replenish_threshold: *replenish_threshold,
                                 replenish_amount: *replenish_amount,
@@ -933,6 +1556,30 @@ impl<T: Clone> OrderType<T> {
                 }
             }
 
+            // A pending stop is dormant: it takes no part in matching until
+            // `activate` converts it. Hand it back unchanged with the
+            // no-progress sentinel, which the sweep and the fill-or-kill dry run
+            // treat as "set this maker aside", should one ever reach them.
+            Self::StopMarket { .. } | Self::StopLimit { .. } => {
+                (0, Some(self.clone()), 0, incoming_quantity)
+            }
+
+            // A hidden order has one (hidden) quantity: match it like a
+            // standard order, leaving nothing drawn into view.
+            Self::Hidden { quantity, .. } => {
+                let quantity = quantity.as_u64();
+                if quantity <= incoming_quantity {
+                    (quantity, None, 0, incoming_quantity - quantity)
+                } else {
+                    (
+                        incoming_quantity,
+                        Some(self.with_reduced_quantity(quantity - incoming_quantity)),
+                        0,
+                        0,
+                    )
+                }
+            }
+
             // Single-quantity variants with no hidden tranche: match against
             // the whole (visible) quantity and, on a partial fill, rewrite the
             // residual to exactly the untaken remainder via
@@ -944,6 +1591,7 @@ impl<T: Clone> OrderType<T> {
             Self::PostOnly { .. }
             | Self::TrailingStop { .. }
             | Self::PeggedOrder { .. }
+            | Self::Market { .. }
             | Self::MarketToLimit { .. } => {
                 let visible_qty = self.visible_quantity().as_u64();
 
@@ -979,8 +1627,12 @@ impl<T> OrderType<T> {
             Self::PostOnly { extra_fields, .. } => extra_fields,
             Self::TrailingStop { extra_fields, .. } => extra_fields,
             Self::PeggedOrder { extra_fields, .. } => extra_fields,
+            Self::Market { extra_fields, .. } => extra_fields,
             Self::MarketToLimit { extra_fields, .. } => extra_fields,
+            Self::StopMarket { extra_fields, .. } => extra_fields,
+            Self::StopLimit { extra_fields, .. } => extra_fields,
             Self::ReserveOrder { extra_fields, .. } => extra_fields,
+            Self::Hidden { extra_fields, .. } => extra_fields,
         }
     }
 
@@ -992,8 +1644,12 @@ impl<T> OrderType<T> {
             Self::PostOnly { extra_fields, .. } => extra_fields,
             Self::TrailingStop { extra_fields, .. } => extra_fields,
             Self::PeggedOrder { extra_fields, .. } => extra_fields,
+            Self::Market { extra_fields, .. } => extra_fields,
             Self::MarketToLimit { extra_fields, .. } => extra_fields,
+            Self::StopMarket { extra_fields, .. } => extra_fields,
+            Self::StopLimit { extra_fields, .. } => extra_fields,
             Self::ReserveOrder { extra_fields, .. } => extra_fields,
+            Self::Hidden { extra_fields, .. } => extra_fields,
         }
     }
 
@@ -1109,6 +1765,23 @@ impl<T> OrderType<T> {
                 reference_price_type,
                 extra_fields: f(extra_fields),
             },
+            Self::Market {
+                id,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields,
+            } => OrderType::Market {
+                id,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: f(extra_fields),
+            },
             Self::MarketToLimit {
                 id,
                 price,
@@ -1128,6 +1801,48 @@ impl<T> OrderType<T> {
                 time_in_force,
                 extra_fields: f(extra_fields),
             },
+            Self::StopMarket {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                trigger_price,
+                extra_fields,
+            } => OrderType::StopMarket {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                trigger_price,
+                extra_fields: f(extra_fields),
+            },
+            Self::StopLimit {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                trigger_price,
+                extra_fields,
+            } => OrderType::StopLimit {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                trigger_price,
+                extra_fields: f(extra_fields),
+            },
             Self::ReserveOrder {
                 id,
                 price,
@@ -1155,6 +1870,25 @@ impl<T> OrderType<T> {
                 auto_replenish,
                 extra_fields: f(extra_fields),
             },
+            Self::Hidden {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields,
+            } => OrderType::Hidden {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: f(extra_fields),
+            },
         }
     }
 }
@@ -1165,32 +1899,42 @@ impl<T> OrderType<T> {
 /// Examples:
 /// - Standard:id=123;price=10000;quantity=5;side=BUY;timestamp=1616823000000;time_in_force=GTC
 /// - IcebergOrder:id=124;price=10000;visible_quantity=1;hidden_quantity=4;side=SELL;timestamp=1616823000000;time_in_force=GTC
+/// - Market:id=125;quantity=5;side=BUY;timestamp=1616823000000;time_in_force=IOC (no `price`)
+#[cfg(feature = "std")]
 impl<T: Default> FromStr for OrderType<T> {
     type Err = PriceLevelError;
 
+    /// Parses leniently within
+    /// [`ParseLimits::DEFAULT`](crate::parse::ParseLimits::DEFAULT); see
+    /// [`Self::from_str_with`].
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(s, &ParseOptions::DEFAULT)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Default> OrderType<T> {
+    /// Parse an order from its `Display` form under `options`: strict or
+    /// lenient about unknown, repeated and malformed fields, and within the
+    /// options' limits.
+    ///
+    /// # Errors
+    ///
+    /// As [`crate::parse::order`].
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, PriceLevelError> {
+        crate::parse::order(s, options)
+    }
+
+    /// The `FromStr` parser without the input limits.
+    pub(crate) fn parse_with(s: &str, strict: bool) -> Result<Self, PriceLevelError> {
         let parts: Vec<&str> = s.split(':').collect();
         if parts.len() != 2 {
             return Err(PriceLevelError::InvalidFormat);
         }
 
         let order_type = parts[0];
-        let fields_str = parts[1];
-
-        let mut fields = std::collections::HashMap::new();
-        for field_pair in fields_str.split(';') {
-            let kv: Vec<&str> = field_pair.split('=').collect();
-            if kv.len() == 2 {
-                fields.insert(kv[0], kv[1]);
-            }
-        }
-
-        let get_field = |field: &str| -> Result<&str, PriceLevelError> {
-            match fields.get(field) {
-                Some(result) => Ok(*result),
-                None => Err(PriceLevelError::MissingField(field.to_string())),
-            }
-        };
+        let fields = RecordFields::split("order", parts[1], strict)?;
+        let get_field = |field: &str| fields.require(field);
 
         let parse_quantity = |field: &str, value: &str| -> Result<Quantity, PriceLevelError> {
             Quantity::from_str(value).map_err(|_| PriceLevelError::InvalidFieldValue {
@@ -1229,8 +1973,12 @@ impl<T: Default> FromStr for OrderType<T> {
             value: id_str.to_string(),
         })?;
 
-        let price_str = get_field("price")?;
-        let price = parse_price("price", price_str)?;
+        // A market order has no limit price, so it carries no `price` field.
+        let price = if order_type == "Market" {
+            Price::ZERO
+        } else {
+            parse_price("price", get_field("price")?)?
+        };
 
         let side_str = get_field("side")?;
         let side: Side = Side::from_str(side_str)?;
@@ -1247,7 +1995,7 @@ impl<T: Default> FromStr for OrderType<T> {
         };
 
         // Parse specific order types
-        match order_type {
+        let order = match order_type {
             "Standard" => {
                 let quantity_str = get_field("quantity")?;
                 let quantity = parse_quantity("quantity", quantity_str)?;
@@ -1356,6 +2104,20 @@ impl<T: Default> FromStr for OrderType<T> {
                     extra_fields: T::default(),
                 })
             }
+            "Market" => {
+                let quantity_str = get_field("quantity")?;
+                let quantity = parse_quantity("quantity", quantity_str)?;
+
+                Ok(OrderType::Market {
+                    id,
+                    quantity,
+                    side,
+                    user_id,
+                    timestamp,
+                    time_in_force,
+                    extra_fields: T::default(),
+                })
+            }
             "MarketToLimit" => {
                 let quantity_str = get_field("quantity")?;
                 let quantity = parse_quantity("quantity", quantity_str)?;
@@ -1371,6 +2133,39 @@ impl<T: Default> FromStr for OrderType<T> {
                     extra_fields: T::default(),
                 })
             }
+            "StopMarket" | "StopLimit" => {
+                let quantity_str = get_field("quantity")?;
+                let quantity = parse_quantity("quantity", quantity_str)?;
+
+                let trigger_price_str = get_field("trigger_price")?;
+                let trigger_price = parse_price("trigger_price", trigger_price_str)?;
+
+                if order_type == "StopMarket" {
+                    Ok(OrderType::StopMarket {
+                        id,
+                        price,
+                        quantity,
+                        side,
+                        user_id,
+                        timestamp,
+                        time_in_force,
+                        trigger_price,
+                        extra_fields: T::default(),
+                    })
+                } else {
+                    Ok(OrderType::StopLimit {
+                        id,
+                        price,
+                        quantity,
+                        side,
+                        user_id,
+                        timestamp,
+                        time_in_force,
+                        trigger_price,
+                        extra_fields: T::default(),
+                    })
+                }
+            }
             "ReserveOrder" => {
                 let visible_quantity_str = get_field("visible_quantity")?;
                 let visible_quantity = parse_quantity("visible_quantity", visible_quantity_str)?;
@@ -1424,8 +2219,25 @@ impl<T: Default> FromStr for OrderType<T> {
                     extra_fields: T::default(),
                 })
             }
+            "Hidden" => {
+                let quantity_str = get_field("quantity")?;
+                let quantity = parse_quantity("quantity", quantity_str)?;
+
+                Ok(OrderType::Hidden {
+                    id,
+                    price,
+                    quantity,
+                    side,
+                    user_id,
+                    timestamp,
+                    time_in_force,
+                    extra_fields: T::default(),
+                })
+            }
             _ => Err(PriceLevelError::UnknownOrderType(order_type.to_string())),
-        }
+        }?;
+        fields.finish()?;
+        Ok(order)
     }
 }
 
@@ -1552,6 +2364,26 @@ impl<T> fmt::Display for OrderType<T> {
                     reference_price_type
                 )
             }
+            OrderType::Market {
+                id,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: _,
+            } => {
+                write!(
+                    f,
+                    "Market:id={};quantity={};side={};user_id={};timestamp={};time_in_force={}",
+                    id,
+                    quantity,
+                    format!("{side:?}").to_uppercase(),
+                    user_id,
+                    timestamp,
+                    time_in_force
+                )
+            }
             OrderType::MarketToLimit {
                 id,
                 price,
@@ -1574,6 +2406,54 @@ impl<T> fmt::Display for OrderType<T> {
                     time_in_force
                 )
             }
+            OrderType::StopMarket {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                trigger_price,
+                extra_fields: _,
+            } => {
+                write!(
+                    f,
+                    "StopMarket:id={};price={};quantity={};side={};user_id={};timestamp={};time_in_force={};trigger_price={}",
+                    id,
+                    price,
+                    quantity,
+                    format!("{side:?}").to_uppercase(),
+                    user_id,
+                    timestamp,
+                    time_in_force,
+                    trigger_price
+                )
+            }
+            OrderType::StopLimit {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                trigger_price,
+                extra_fields: _,
+            } => {
+                write!(
+                    f,
+                    "StopLimit:id={};price={};quantity={};side={};user_id={};timestamp={};time_in_force={};trigger_price={}",
+                    id,
+                    price,
+                    quantity,
+                    format!("{side:?}").to_uppercase(),
+                    user_id,
+                    timestamp,
+                    time_in_force,
+                    trigger_price
+                )
+            }
             OrderType::ReserveOrder {
                 id,
                 price,
@@ -1604,12 +2484,35 @@ impl<T> fmt::Display for OrderType<T> {
                     auto_replenish
                 )
             }
+            OrderType::Hidden {
+                id,
+                price,
+                quantity,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: _,
+            } => {
+                write!(
+                    f,
+                    "Hidden:id={};price={};quantity={};side={};user_id={};timestamp={};time_in_force={}",
+                    id,
+                    price,
+                    quantity,
+                    format!("{side:?}").to_uppercase(),
+                    user_id,
+                    timestamp,
+                    time_in_force
+                )
+            }
         }
     }
 }
 
-impl From<OrderQueue> for Vec<Arc<OrderType<()>>> {
-    fn from(queue: OrderQueue) -> Self {
+#[cfg(feature = "std")]
+impl<T: Clone> From<OrderQueue<T>> for Vec<Arc<OrderType<T>>> {
+    fn from(queue: OrderQueue<T>) -> Self {
         queue.to_vec()
     }
 }
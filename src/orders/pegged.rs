@@ -1,7 +1,8 @@
 use crate::errors::PriceLevelError;
+use alloc::string::ToString;
+use core::fmt;
+use core::str::FromStr;
 use serde::{Deserialize, Serialize};
-use std::fmt;
-use std::str::FromStr;
 
 /// Reference price type for pegged orders
 #[repr(u8)]
@@ -0,0 +1,240 @@
+use crate::orders::Id;
+use crate::utils::TimestampMs;
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+#[cfg(feature = "std")]
+use std::hash::BuildHasher;
+
+/// Why a hidden order is moving hidden quantity into its visible part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReplenishTrigger {
+    /// An iceberg order's visible tranche was fully taken.
+    IcebergTranche,
+    /// An auto-replenishing reserve order's visible part was fully taken.
+    ReserveDepleted,
+    /// An auto-replenishing reserve order's visible part fell below its
+    /// replenish threshold.
+    ReserveBelowThreshold,
+}
+
+/// Whether an iceberg or reserve order keeps its place in the queue when it
+/// refreshes its visible part.
+///
+/// Set per level with
+/// [`PriceLevelConfig::with_refresh_priority`](crate::PriceLevelConfig::with_refresh_priority);
+/// exchanges differ on which rule applies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshPriority {
+    /// The refreshed order is re-queued behind every order resting at the
+    /// level, carrying the timestamp its [`ReplenishmentStrategy`] chose.
+    #[default]
+    Requeue,
+    /// The refreshed order keeps its queue slot and its original timestamp.
+    Retain,
+}
+
+impl fmt::Display for RefreshPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RefreshPriority::Requeue => write!(f, "REQUEUE"),
+            RefreshPriority::Retain => write!(f, "RETAIN"),
+        }
+    }
+}
+
+/// One refresh of a resting order during a match, returned in
+/// [`MatchResult::refreshes`](crate::MatchResult::refreshes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OrderRefresh {
+    /// The refreshed order.
+    pub order_id: Id,
+    /// Hidden quantity moved into its visible part.
+    pub quantity: u64,
+    /// Whether it kept its queue slot or was re-queued to the back.
+    pub priority: RefreshPriority,
+}
+
+impl fmt::Display for OrderRefresh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.order_id, self.quantity, self.priority)
+    }
+}
+
+/// What a [`ReplenishmentStrategy`] is asked to decide on.
+///
+/// Built by [`OrderType::match_against_with`](crate::OrderType::match_against_with)
+/// only when a refresh will happen, so `hidden_quantity` is never zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplenishmentContext {
+    /// The replenishing order.
+    pub order_id: Id,
+    /// Why it replenishes.
+    pub trigger: ReplenishTrigger,
+    /// Visible quantity left by the fill, before the refresh is added.
+    pub visible_remaining: u64,
+    /// Hidden quantity available to draw from.
+    pub hidden_quantity: u64,
+    /// What the built-in rule draws: the current tranche size for an
+    /// iceberg, the replenish amount for a reserve order, capped by
+    /// `hidden_quantity`.
+    pub default_quantity: u64,
+    /// The order's timestamp before the refresh.
+    pub order_timestamp: TimestampMs,
+    /// The timestamp of the match that triggers the refresh, or `None` when
+    /// the refresh is only projected (a fill-or-kill feasibility query made
+    /// outside a match).
+    pub match_timestamp: Option<TimestampMs>,
+}
+
+/// A strategy's decision: how much to draw, and the refreshed order's
+/// timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Replenishment {
+    /// Hidden quantity to move into the visible part. Clamped to at least `1`
+    /// and at most the hidden quantity, so a refresh always makes progress
+    /// and never draws depth the order does not have.
+    pub quantity: u64,
+    /// Timestamp the refreshed order carries.
+    pub timestamp: TimestampMs,
+}
+
+/// Decides how an iceberg or auto-replenishing reserve order refreshes its
+/// visible part from its hidden quantity.
+///
+/// Set per level with
+/// [`PriceLevelConfig::with_replenishment_strategy`](crate::PriceLevelConfig::with_replenishment_strategy).
+/// It is consulted under the maker's entry lock in the match sweep, so it must
+/// not block or call back into the level. Only the refresh sizes and
+/// timestamps are up to the strategy: when a refresh happens stays with the
+/// order type, and whether it loses time priority with the level's
+/// [`RefreshPriority`].
+///
+/// A strategy that is not a pure function of its context (randomized display
+/// sizes, for instance) is fine for matching, and fill-or-kill stays exact: the
+/// total an order can fill does not depend on how it is split into tranches.
+/// Rebuilding a level from its event stream reapplies the strategy, so it only
+/// reproduces the original tranches when the strategy is deterministic.
+pub trait ReplenishmentStrategy: fmt::Debug + Send + Sync {
+    /// Decide the refresh described by `context`.
+    fn replenish(&self, context: &ReplenishmentContext) -> Replenishment;
+}
+
+/// The built-in replenishment: draw
+/// [`ReplenishmentContext::default_quantity`] and keep the order's timestamp.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefaultReplenishment;
+
+impl ReplenishmentStrategy for DefaultReplenishment {
+    fn replenish(&self, context: &ReplenishmentContext) -> Replenishment {
+        Replenishment {
+            quantity: context.default_quantity,
+            timestamp: context.order_timestamp,
+        }
+    }
+}
+
+/// Replenishes with a random quantity drawn uniformly from `[min, max]`, so
+/// the refreshed display size does not give the order away as a hidden one.
+///
+/// Applies to every refresh: iceberg tranches and both reserve triggers. The
+/// drawn quantity is still capped by the hidden quantity left, and the
+/// refreshed order keeps its timestamp, as under [`DefaultReplenishment`].
+///
+/// The generator is a lock-free SplitMix64 stream. [`Self::with_seed`] makes
+/// the sequence of draws reproducible (for tests and replays);
+/// [`Self::new`] seeds it from the process's random hasher keys. It is not
+/// cryptographically secure, which display randomization does not need.
+///
+/// ```rust
+/// use pricelevel::{PriceLevel, PriceLevelConfig, RandomizedReplenish};
+/// use std::sync::Arc;
+///
+/// let config = PriceLevelConfig::new()
+///     .with_replenishment_strategy(Arc::new(RandomizedReplenish::new(50, 150)));
+/// let level: PriceLevel = PriceLevel::with_config(10_000, config);
+/// ```
+#[derive(Debug)]
+pub struct RandomizedReplenish {
+    min: u64,
+    max: u64,
+    state: AtomicU64,
+}
+
+impl RandomizedReplenish {
+    /// A strategy drawing from `[min, max]`, seeded from the process's random
+    /// hasher keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is zero or greater than `max`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn new(min: u64, max: u64) -> Self {
+        Self::with_seed(
+            min,
+            max,
+            RandomState::new().hash_one(min ^ max.rotate_left(32)),
+        )
+    }
+
+    /// A strategy drawing from `[min, max]` whose draws are fixed by `seed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` is zero or greater than `max`.
+    #[must_use]
+    pub fn with_seed(min: u64, max: u64, seed: u64) -> Self {
+        assert!(
+            min >= 1 && min <= max,
+            "replenish band must satisfy 1 <= min <= max, got [{min}, {max}]"
+        );
+        Self {
+            min,
+            max,
+            state: AtomicU64::new(seed),
+        }
+    }
+
+    /// Smallest quantity drawn.
+    #[must_use]
+    pub fn min(&self) -> u64 {
+        self.min
+    }
+
+    /// Largest quantity drawn.
+    #[must_use]
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Next quantity in `[min, max]`.
+    fn draw(&self) -> u64 {
+        // SplitMix64: advancing the state is a single atomic add, so
+        // concurrent sweeps on different levels sharing one strategy each get
+        // a distinct draw without a lock.
+        let mut z = self
+            .state
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        // Multiply-shift maps the 64-bit draw onto the band. `min >= 1`, so
+        // the span cannot overflow.
+        let span = self.max - self.min + 1;
+        self.min + ((u128::from(z) * u128::from(span)) >> 64) as u64
+    }
+}
+
+impl ReplenishmentStrategy for RandomizedReplenish {
+    fn replenish(&self, context: &ReplenishmentContext) -> Replenishment {
+        Replenishment {
+            quantity: self.draw(),
+            timestamp: context.order_timestamp,
+        }
+    }
+}
@@ -1,49 +1,57 @@
 use crate::errors::PriceLevelError;
+use crate::orders::Id;
+use alloc::format;
+use core::fmt;
+use core::str::FromStr;
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
 
-/// Represents the current status of an order in the system
+/// The lifecycle state of an order, following exchange order-state
+/// semantics.
+///
+/// A resting order is [`OrderStatus::New`] until its first fill and
+/// [`OrderStatus::PartiallyFilled`] after it, until it leaves the level as
+/// [`OrderStatus::Filled`] or [`OrderStatus::Cancelled`].
+/// [`PriceLevel::order_status`](crate::PriceLevel::order_status) reports the
+/// state of a resting order, and the level returns each change as an
+/// [`OrderStatusTransition`]. [`OrderStatus::Expired`] and
+/// [`OrderStatus::Rejected`] complete the set for the composing book, which
+/// owns the clock and admission policy.
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum OrderStatus {
-    /// Order has been created but not yet processed
+    /// Accepted and resting, with no fill yet.
     New,
 
-    /// Order is active in the order book
-    Active,
-
-    /// Order has been partially filled
+    /// Resting with at least one fill and quantity left.
     PartiallyFilled,
 
-    /// Order has been completely filled
+    /// Completely filled and removed from the level.
     Filled,
 
-    /// Order has been canceled by the user
-    Canceled,
-
-    /// Order has been rejected by the system
-    Rejected,
+    /// Cancelled and removed from the level.
+    Cancelled,
 
-    /// Order has expired (for time-bounded orders)
+    /// Removed because its time in force ran out.
     Expired,
+
+    /// Refused without resting.
+    Rejected,
 }
 
 impl OrderStatus {
-    /// Returns true if the order is still active in the book
-    #[allow(dead_code)]
+    /// Returns true if the order still rests (new or partially filled).
     #[must_use]
     pub fn is_active(&self) -> bool {
-        matches!(self, Self::Active | Self::PartiallyFilled)
+        matches!(self, Self::New | Self::PartiallyFilled)
     }
 
     /// Returns true if the order has been terminated
-    /// (filled, canceled, rejected, or expired)
-    #[allow(dead_code)]
+    /// (filled, cancelled, rejected, or expired)
     #[must_use]
     pub fn is_terminated(&self) -> bool {
         matches!(
             self,
-            Self::Filled | Self::Canceled | Self::Rejected | Self::Expired
+            Self::Filled | Self::Cancelled | Self::Rejected | Self::Expired
         )
     }
 }
@@ -54,12 +62,11 @@ impl FromStr for OrderStatus {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_uppercase().as_str() {
             "NEW" => Ok(OrderStatus::New),
-            "ACTIVE" => Ok(OrderStatus::Active),
             "PARTIALLYFILLED" => Ok(OrderStatus::PartiallyFilled),
             "FILLED" => Ok(OrderStatus::Filled),
-            "CANCELED" => Ok(OrderStatus::Canceled),
-            "REJECTED" => Ok(OrderStatus::Rejected),
+            "CANCELLED" | "CANCELED" => Ok(OrderStatus::Cancelled),
             "EXPIRED" => Ok(OrderStatus::Expired),
+            "REJECTED" => Ok(OrderStatus::Rejected),
             _ => Err(PriceLevelError::ParseError {
                 message: format!("Invalid OrderStatus: {s}"),
             }),
@@ -67,16 +74,34 @@ impl FromStr for OrderStatus {
     }
 }
 
-impl std::fmt::Display for OrderStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             OrderStatus::New => write!(f, "NEW"),
-            OrderStatus::Active => write!(f, "ACTIVE"),
             OrderStatus::PartiallyFilled => write!(f, "PARTIALLYFILLED"),
             OrderStatus::Filled => write!(f, "FILLED"),
-            OrderStatus::Canceled => write!(f, "CANCELED"),
-            OrderStatus::Rejected => write!(f, "REJECTED"),
+            OrderStatus::Cancelled => write!(f, "CANCELLED"),
             OrderStatus::Expired => write!(f, "EXPIRED"),
+            OrderStatus::Rejected => write!(f, "REJECTED"),
         }
     }
 }
+
+/// One change of an order's [`OrderStatus`] at a price level, returned in
+/// [`MatchResult::status_transitions`](crate::MatchResult::status_transitions)
+/// and by [`UpdateOutcome::status_transition`](crate::UpdateOutcome::status_transition).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OrderStatusTransition {
+    /// The order whose status changed.
+    pub order_id: Id,
+    /// The status before the change.
+    pub from: OrderStatus,
+    /// The status after the change.
+    pub to: OrderStatus,
+}
+
+impl fmt::Display for OrderStatusTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}->{}", self.order_id, self.from, self.to)
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests_order_fill_state {
+    use crate::orders::OrderStatus;
+    use crate::orders::fill_state::OrderFillState;
+    use crate::utils::Quantity;
+
+    fn state(executed: u64, leaves: u64) -> OrderFillState {
+        OrderFillState {
+            cumulative_executed: Quantity::new(executed),
+            leaves_quantity: Quantity::new(leaves),
+        }
+    }
+
+    #[test]
+    fn test_status_follows_executed_quantity() {
+        assert_eq!(state(0, 10).status(), OrderStatus::New);
+        assert_eq!(state(4, 6).status(), OrderStatus::PartiallyFilled);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(state(4, 6).to_string(), "executed=4;leaves=6");
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let original = state(4, 6);
+        let json = serde_json::to_string(&original).unwrap();
+        let decoded: OrderFillState = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, original);
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use crate::orders::{Hash32, Id, OrderKey, OrderType, Side, TimeInForce};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+
+    fn create_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_equality_is_by_id_only() {
+        let original = create_order(1, 100);
+        let partially_filled = create_order(1, 40);
+
+        // Structural equality distinguishes the payloads...
+        assert_ne!(original, partially_filled);
+        // ...while the key treats them as the same order.
+        assert_eq!(OrderKey::from(original), OrderKey::from(partially_filled));
+        assert_ne!(
+            OrderKey::from(create_order(1, 100)),
+            OrderKey::from(create_order(2, 100))
+        );
+    }
+
+    #[test]
+    fn test_set_deduplicates_and_looks_up_by_id() {
+        let mut set = HashSet::new();
+        assert!(set.insert(OrderKey::from(create_order(1, 100))));
+        assert!(!set.insert(OrderKey::from(create_order(1, 5))));
+        assert!(set.insert(OrderKey::from(create_order(2, 100))));
+        assert_eq!(set.len(), 2);
+
+        assert!(set.contains(&Id::from_u64(1)));
+        assert!(!set.contains(&Id::from_u64(3)));
+        let stored = set.get(&Id::from_u64(1)).expect("key present");
+        assert_eq!(stored.visible_quantity(), Quantity::new(100));
+    }
+
+    #[test]
+    fn test_map_key_shares_allocation() {
+        let order = Arc::new(create_order(7, 10));
+        let mut map = HashMap::new();
+        map.insert(OrderKey::new(Arc::clone(&order)), "resting");
+
+        assert_eq!(map.get(&Id::from_u64(7)), Some(&"resting"));
+        let (key, _) = map.iter().next().expect("one entry");
+        assert!(Arc::ptr_eq(key.order(), &order));
+        assert_eq!(key.id(), Id::from_u64(7));
+        assert_eq!(key.side(), Side::Buy);
+        assert!(Arc::ptr_eq(&key.clone().into_inner(), &order));
+    }
+}
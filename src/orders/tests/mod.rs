@@ -1,6 +1,10 @@
 mod base;
+mod fill_state;
+mod key;
 mod order_type;
 mod pegged;
+mod replenishment;
 mod status;
 mod time_in_force;
 mod update;
+mod update_outcome;
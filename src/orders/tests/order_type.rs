@@ -253,6 +253,25 @@ mod tests {
         assert!(!create_reserve_order().is_post_only());
     }
 
+    #[test]
+    fn test_with_time_in_force() {
+        for order in [
+            create_standard_order(),
+            create_iceberg_order(),
+            create_post_only_order(),
+            create_trailing_stop_order(),
+            create_pegged_order(),
+            create_market_to_limit_order(),
+            create_reserve_order(),
+        ] {
+            let updated = order.with_time_in_force(TimeInForce::Gtd(42));
+            assert_eq!(updated.time_in_force(), TimeInForce::Gtd(42));
+            assert_eq!(updated.id(), order.id());
+            assert_eq!(updated.visible_quantity(), order.visible_quantity());
+            assert_eq!(updated.hidden_quantity(), order.hidden_quantity());
+        }
+    }
+
     #[test]
     fn test_with_reduced_quantity() {
         // Test standard order
@@ -707,6 +726,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hidden_order_quantities_and_round_trip() {
+        let order = OrderType::<()>::Hidden {
+            id: Id::from_u64(125),
+            price: Price::new(10000),
+            quantity: Quantity::new(7),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        };
+        assert!(order.is_hidden());
+        assert!(order.is_matchable());
+        assert_eq!(order.visible_quantity(), Quantity::ZERO);
+        assert_eq!(order.hidden_quantity(), Quantity::new(7));
+
+        let (consumed, updated, hidden_reduced, remaining) = order.match_against(3);
+        assert_eq!((consumed, hidden_reduced, remaining), (3, 0, 0));
+        assert_eq!(updated.unwrap().hidden_quantity(), Quantity::new(4));
+
+        let text = order.to_string();
+        assert!(text.starts_with("Hidden:id="));
+        let parsed: OrderType<()> = OrderType::from_str(&text).unwrap();
+        assert_eq!(parsed.to_string(), text);
+    }
+
     #[test]
     fn test_roundtrip_display_parse() {
         // Test that converting to string and parsing back works correctly
@@ -1691,3 +1737,240 @@ mod from_str_specific_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod stop_order_tests {
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::str::FromStr;
+
+    fn create_stop_limit_order(side: Side, trigger: u128) -> OrderType<()> {
+        OrderType::<()>::StopLimit {
+            id: Id::from_u64(130),
+            price: Price::new(10000),
+            quantity: Quantity::new(5),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            trigger_price: Price::new(trigger),
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_stop_trigger_condition_by_side() {
+        let buy_stop = create_stop_limit_order(Side::Buy, 10100);
+        assert!(buy_stop.is_stop());
+        assert_eq!(buy_stop.trigger_price(), Some(Price::new(10100)));
+        assert!(!buy_stop.is_triggered_by(Price::new(10099)));
+        assert!(buy_stop.is_triggered_by(Price::new(10100)));
+        assert!(buy_stop.is_triggered_by(Price::new(10200)));
+
+        let sell_stop = create_stop_limit_order(Side::Sell, 9900);
+        assert!(!sell_stop.is_triggered_by(Price::new(9901)));
+        assert!(sell_stop.is_triggered_by(Price::new(9900)));
+        assert!(sell_stop.is_triggered_by(Price::new(9000)));
+
+        let standard = OrderType::<()>::Standard {
+            id: Id::from_u64(123),
+            price: Price::new(10000),
+            quantity: Quantity::new(5),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        };
+        assert!(!standard.is_stop());
+        assert_eq!(standard.trigger_price(), None);
+        assert!(!standard.is_triggered_by(Price::new(u128::MAX)));
+        assert!(standard.activate().is_none());
+    }
+
+    #[test]
+    fn test_stop_activation_converts_variant() {
+        let stop_limit = create_stop_limit_order(Side::Buy, 10100);
+        match stop_limit.activate() {
+            Some(OrderType::Standard {
+                id,
+                price,
+                quantity,
+                side,
+                ..
+            }) => {
+                assert_eq!(id, Id::from_u64(130));
+                assert_eq!(price, Price::new(10000));
+                assert_eq!(quantity, Quantity::new(5));
+                assert_eq!(side, Side::Buy);
+            }
+            other => panic!("StopLimit must activate as Standard, got {other:?}"),
+        }
+
+        let stop_market = OrderType::<()>::StopMarket {
+            id: Id::from_u64(131),
+            price: Price::new(10000),
+            quantity: Quantity::new(7),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Ioc,
+            trigger_price: Price::new(9950),
+            extra_fields: (),
+        };
+        match stop_market.activate() {
+            Some(OrderType::MarketToLimit {
+                quantity,
+                time_in_force,
+                ..
+            }) => {
+                assert_eq!(quantity, Quantity::new(7));
+                assert_eq!(time_in_force, TimeInForce::Ioc);
+            }
+            other => panic!("StopMarket must activate as MarketToLimit, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_pending_stop_is_dormant_for_matching() {
+        let stop = create_stop_limit_order(Side::Sell, 9900);
+        assert!(!stop.is_matchable());
+        let (consumed, updated, hidden_reduced, remaining) = stop.match_against(3);
+        assert_eq!(consumed, 0);
+        assert_eq!(updated, Some(stop));
+        assert_eq!(hidden_reduced, 0);
+        assert_eq!(remaining, 3);
+    }
+
+    #[test]
+    fn test_stop_orders_string_roundtrip() {
+        let stop = create_stop_limit_order(Side::Buy, 10100);
+        let text = stop.to_string();
+        assert!(text.starts_with("StopLimit:"));
+        assert!(text.contains("trigger_price=10100"));
+        let parsed = OrderType::<()>::from_str(&text).expect("StopLimit must parse");
+        assert_eq!(parsed, stop);
+
+        let stop_market = OrderType::<()>::from_str(
+            "StopMarket:id=5;price=10000;quantity=2;side=SELL;timestamp=1616823000000;time_in_force=GTC;trigger_price=9900",
+        )
+        .expect("StopMarket must parse");
+        assert_eq!(stop_market.trigger_price(), Some(Price::new(9900)));
+        assert_eq!(stop_market.visible_quantity(), Quantity::new(2));
+
+        let missing = OrderType::<()>::from_str(
+            "StopMarket:id=5;price=10000;quantity=2;side=SELL;timestamp=1616823000000;time_in_force=GTC",
+        );
+        assert!(missing.is_err());
+    }
+
+    fn create_trailing_stop(side: Side, trail: u64, reference: u128) -> OrderType<()> {
+        OrderType::<()>::TrailingStop {
+            id: Id::from_u64(131),
+            price: Price::new(10000),
+            quantity: Quantity::new(5),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            trail_amount: Quantity::new(trail),
+            last_reference_price: Price::new(reference),
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_trailing_stop_sell_ratchets_up_and_triggers() {
+        let order = create_trailing_stop(Side::Sell, 100, 10000);
+        assert_eq!(order.trailing_stop_price(), Some(Price::new(9900)));
+
+        // A falling market never lowers the reference.
+        assert!(order.with_trailing_reference(Price::new(9950)).is_none());
+
+        let ratcheted = order
+            .with_trailing_reference(Price::new(10300))
+            .expect("rising market ratchets a sell stop");
+        assert_eq!(ratcheted.trailing_stop_price(), Some(Price::new(10200)));
+        assert!(!ratcheted.is_trailing_stop_reached(Price::new(10201)));
+        assert!(ratcheted.is_trailing_stop_reached(Price::new(10200)));
+    }
+
+    #[test]
+    fn test_trailing_stop_buy_ratchets_down_and_triggers() {
+        let order = create_trailing_stop(Side::Buy, 50, 10000);
+        assert_eq!(order.trailing_stop_price(), Some(Price::new(10050)));
+        assert!(order.with_trailing_reference(Price::new(10010)).is_none());
+
+        let ratcheted = order
+            .with_trailing_reference(Price::new(9800))
+            .expect("falling market ratchets a buy stop");
+        assert_eq!(ratcheted.trailing_stop_price(), Some(Price::new(9850)));
+        assert!(!ratcheted.is_trailing_stop_reached(Price::new(9849)));
+        assert!(ratcheted.is_trailing_stop_reached(Price::new(9850)));
+    }
+
+    #[test]
+    fn test_trailing_stop_unseeded_reference() {
+        let order = create_trailing_stop(Side::Buy, 50, 0);
+        assert!(!order.is_trailing_stop_reached(Price::new(1_000_000)));
+
+        let seeded = order
+            .with_trailing_reference(Price::new(10000))
+            .expect("a zero reference is always seeded");
+        assert_eq!(seeded.trailing_stop_price(), Some(Price::new(10050)));
+
+        let stop = create_stop_limit_order(Side::Buy, 10100);
+        assert_eq!(stop.trailing_stop_price(), None);
+        assert!(stop.with_trailing_reference(Price::new(1)).is_none());
+        assert!(!stop.is_trailing_stop_reached(Price::new(1)));
+    }
+}
+
+#[cfg(test)]
+mod market_order_tests {
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::parse::ParseOptions;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::str::FromStr;
+
+    fn create_market_order(side: Side) -> OrderType<()> {
+        OrderType::<()>::Market {
+            id: Id::from_u64(140),
+            quantity: Quantity::new(7),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Ioc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_market_order_reports_the_most_aggressive_price() {
+        let buy = create_market_order(Side::Buy);
+        assert!(buy.is_market());
+        assert_eq!(buy.price(), Price::new(u128::MAX));
+        assert_eq!(create_market_order(Side::Sell).price(), Price::ZERO);
+        assert_eq!(buy.with_price(Price::new(10000)), buy);
+        assert_eq!(buy.visible_quantity(), Quantity::new(7));
+        assert_eq!(buy.hidden_quantity(), Quantity::ZERO);
+        assert_eq!(
+            buy.with_reduced_quantity(3).visible_quantity(),
+            Quantity::new(3)
+        );
+        assert!(!create_market_order(Side::Buy).is_stop());
+    }
+
+    #[test]
+    fn test_market_order_string_roundtrip() {
+        let market = create_market_order(Side::Sell);
+        let text = market.to_string();
+        assert!(text.starts_with("Market:"));
+        assert!(!text.contains("price="));
+        let parsed = OrderType::<()>::from_str(&text).expect("Market must parse");
+        assert_eq!(parsed, market);
+
+        let priced = format!("{text};price=10000");
+        assert!(OrderType::<()>::from_str_with(&priced, &ParseOptions::STRICT).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+#[cfg(test)]
+mod tests {
+    use crate::orders::{
+        DefaultReplenishment, Hash32, Id, OrderType, RandomizedReplenish, ReplenishTrigger,
+        Replenishment, ReplenishmentContext, ReplenishmentStrategy, Side, TimeInForce,
+    };
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::num::NonZeroU64;
+    use std::sync::Mutex;
+
+    /// Draws a fixed quantity, restamps the order with the match time, and
+    /// records every context it is asked about.
+    #[derive(Debug)]
+    struct Fixed {
+        quantity: u64,
+        seen: Mutex<Vec<ReplenishmentContext>>,
+    }
+
+    impl Fixed {
+        fn new(quantity: u64) -> Self {
+            Self {
+                quantity,
+                seen: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    impl ReplenishmentStrategy for Fixed {
+        fn replenish(&self, context: &ReplenishmentContext) -> Replenishment {
+            self.seen.lock().unwrap().push(*context);
+            Replenishment {
+                quantity: self.quantity,
+                timestamp: context.match_timestamp.unwrap_or(context.order_timestamp),
+            }
+        }
+    }
+
+    fn iceberg(visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(1),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn reserve(visible: u64, hidden: u64, threshold: u64) -> OrderType<()> {
+        OrderType::ReserveOrder {
+            id: Id::from_u64(2),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            replenish_threshold: Quantity::new(threshold),
+            replenish_amount: NonZeroU64::new(20),
+            auto_replenish: true,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_default_strategy_matches_match_against() {
+        for order in [iceberg(10, 25), reserve(10, 50, 4)] {
+            for take in [3, 7, 10, 12] {
+                assert_eq!(
+                    order.match_against_with(take, &DefaultReplenishment, None),
+                    order.match_against(take)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_iceberg_refresh_follows_the_strategy() {
+        let strategy = Fixed::new(4);
+        let now = TimestampMs::new(1616823005000);
+        let (consumed, updated, hidden_reduced, remaining) =
+            iceberg(10, 25).match_against_with(12, &strategy, Some(now));
+
+        assert_eq!((consumed, hidden_reduced, remaining), (10, 4, 2));
+        let updated = updated.expect("refreshed iceberg");
+        assert_eq!(updated.visible_quantity(), Quantity::new(4));
+        assert_eq!(updated.hidden_quantity(), Quantity::new(21));
+        assert_eq!(updated.timestamp(), now);
+
+        let seen = strategy.seen.lock().unwrap();
+        assert_eq!(
+            seen.as_slice(),
+            &[ReplenishmentContext {
+                order_id: Id::from_u64(1),
+                trigger: ReplenishTrigger::IcebergTranche,
+                visible_remaining: 0,
+                hidden_quantity: 25,
+                default_quantity: 10,
+                order_timestamp: TimestampMs::new(1616823000000),
+                match_timestamp: Some(now),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_strategy_quantity_is_clamped_to_the_hidden_depth() {
+        let (_, updated, hidden_reduced, _) =
+            iceberg(10, 25).match_against_with(10, &Fixed::new(0), None);
+        assert_eq!(hidden_reduced, 1);
+        assert_eq!(updated.unwrap().visible_quantity(), Quantity::new(1));
+
+        let (_, updated, hidden_reduced, _) =
+            iceberg(10, 25).match_against_with(10, &Fixed::new(u64::MAX), None);
+        assert_eq!(hidden_reduced, 25);
+        let updated = updated.unwrap();
+        assert_eq!(updated.visible_quantity(), Quantity::new(25));
+        assert_eq!(updated.hidden_quantity(), Quantity::new(0));
+    }
+
+    #[test]
+    fn test_reserve_triggers_reach_the_strategy() {
+        let strategy = Fixed::new(7);
+
+        // Below the threshold: the refresh tops up what is left.
+        let (consumed, updated, hidden_reduced, _) =
+            reserve(10, 50, 4).match_against_with(8, &strategy, None);
+        assert_eq!((consumed, hidden_reduced), (8, 7));
+        assert_eq!(updated.unwrap().visible_quantity(), Quantity::new(9));
+
+        // Depleted: the refresh replaces the visible part.
+        let (_, updated, hidden_reduced, _) =
+            reserve(10, 50, 4).match_against_with(10, &strategy, None);
+        assert_eq!(hidden_reduced, 7);
+        assert_eq!(updated.unwrap().visible_quantity(), Quantity::new(7));
+
+        // Above the threshold nothing is refreshed, so nothing is asked.
+        let _ = reserve(10, 50, 4).match_against_with(3, &strategy, None);
+
+        let seen = strategy.seen.lock().unwrap();
+        let triggers: Vec<_> = seen
+            .iter()
+            .map(|c| (c.trigger, c.visible_remaining))
+            .collect();
+        assert_eq!(
+            triggers,
+            vec![
+                (ReplenishTrigger::ReserveBelowThreshold, 2),
+                (ReplenishTrigger::ReserveDepleted, 0),
+            ]
+        );
+        assert!(seen.iter().all(|c| c.default_quantity == 20));
+    }
+
+    fn context(hidden: u64) -> ReplenishmentContext {
+        ReplenishmentContext {
+            order_id: Id::from_u64(1),
+            trigger: ReplenishTrigger::IcebergTranche,
+            visible_remaining: 0,
+            hidden_quantity: hidden,
+            default_quantity: 10,
+            order_timestamp: TimestampMs::new(1616823000000),
+            match_timestamp: Some(TimestampMs::new(1616823005000)),
+        }
+    }
+
+    fn draws(strategy: &RandomizedReplenish, n: usize) -> Vec<u64> {
+        (0..n)
+            .map(|_| strategy.replenish(&context(1_000)).quantity)
+            .collect()
+    }
+
+    #[test]
+    fn test_randomized_draws_stay_in_the_band() {
+        let strategy = RandomizedReplenish::with_seed(5, 9, 42);
+        assert_eq!((strategy.min(), strategy.max()), (5, 9));
+        let drawn = draws(&strategy, 500);
+        assert!(drawn.iter().all(|q| (5..=9).contains(q)));
+        // Every value of a narrow band turns up.
+        for q in 5..=9 {
+            assert!(drawn.contains(&q), "{q} never drawn");
+        }
+        // The refreshed order keeps its timestamp.
+        assert_eq!(
+            strategy.replenish(&context(1_000)).timestamp,
+            TimestampMs::new(1616823000000)
+        );
+    }
+
+    #[test]
+    fn test_randomized_draws_are_fixed_by_the_seed() {
+        let first = draws(&RandomizedReplenish::with_seed(1, 1_000, 7), 32);
+        assert_eq!(
+            first,
+            draws(&RandomizedReplenish::with_seed(1, 1_000, 7), 32)
+        );
+        assert_ne!(
+            first,
+            draws(&RandomizedReplenish::with_seed(1, 1_000, 8), 32)
+        );
+        // A one-value band is a fixed refresh.
+        assert!(
+            draws(&RandomizedReplenish::new(4, 4), 8)
+                .iter()
+                .all(|&q| q == 4)
+        );
+    }
+
+    #[test]
+    fn test_randomized_refresh_is_capped_by_the_hidden_depth() {
+        let strategy = RandomizedReplenish::with_seed(50, 60, 1);
+        let (_, updated, hidden_reduced, _) =
+            iceberg(10, 25).match_against_with(10, &strategy, None);
+        assert_eq!(hidden_reduced, 25);
+        assert_eq!(updated.unwrap().hidden_quantity(), Quantity::new(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "replenish band must satisfy")]
+    fn test_randomized_rejects_an_empty_band() {
+        let _ = RandomizedReplenish::with_seed(10, 9, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "replenish band must satisfy")]
+    fn test_randomized_rejects_a_zero_minimum() {
+        let _ = RandomizedReplenish::new(0, 9);
+    }
+}
@@ -1,16 +1,16 @@
 #[cfg(test)]
 mod tests_order_status {
-    use crate::orders::status::OrderStatus;
+    use crate::orders::Id;
+    use crate::orders::status::{OrderStatus, OrderStatusTransition};
     use std::str::FromStr;
 
     #[test]
     fn test_is_active() {
-        assert!(OrderStatus::Active.is_active());
+        assert!(OrderStatus::New.is_active());
         assert!(OrderStatus::PartiallyFilled.is_active());
 
-        assert!(!OrderStatus::New.is_active());
         assert!(!OrderStatus::Filled.is_active());
-        assert!(!OrderStatus::Canceled.is_active());
+        assert!(!OrderStatus::Cancelled.is_active());
         assert!(!OrderStatus::Rejected.is_active());
         assert!(!OrderStatus::Expired.is_active());
     }
@@ -18,12 +18,11 @@ mod tests_order_status {
     #[test]
     fn test_is_terminated() {
         assert!(OrderStatus::Filled.is_terminated());
-        assert!(OrderStatus::Canceled.is_terminated());
+        assert!(OrderStatus::Cancelled.is_terminated());
         assert!(OrderStatus::Rejected.is_terminated());
         assert!(OrderStatus::Expired.is_terminated());
 
         assert!(!OrderStatus::New.is_terminated());
-        assert!(!OrderStatus::Active.is_terminated());
         assert!(!OrderStatus::PartiallyFilled.is_terminated());
     }
 
@@ -32,10 +31,9 @@ mod tests_order_status {
         // Test that no status can be both active and terminated
         for status in [
             OrderStatus::New,
-            OrderStatus::Active,
             OrderStatus::PartiallyFilled,
             OrderStatus::Filled,
-            OrderStatus::Canceled,
+            OrderStatus::Cancelled,
             OrderStatus::Rejected,
             OrderStatus::Expired,
         ] {
@@ -50,10 +48,6 @@ mod tests_order_status {
     fn test_from_str_valid() {
         // Test all valid status values with exact case
         assert_eq!(OrderStatus::from_str("NEW").unwrap(), OrderStatus::New);
-        assert_eq!(
-            OrderStatus::from_str("ACTIVE").unwrap(),
-            OrderStatus::Active
-        );
         assert_eq!(
             OrderStatus::from_str("PARTIALLYFILLED").unwrap(),
             OrderStatus::PartiallyFilled
@@ -62,9 +56,14 @@ mod tests_order_status {
             OrderStatus::from_str("FILLED").unwrap(),
             OrderStatus::Filled
         );
+        assert_eq!(
+            OrderStatus::from_str("CANCELLED").unwrap(),
+            OrderStatus::Cancelled
+        );
+        // The US spelling is accepted as an alias.
         assert_eq!(
             OrderStatus::from_str("CANCELED").unwrap(),
-            OrderStatus::Canceled
+            OrderStatus::Cancelled
         );
         assert_eq!(
             OrderStatus::from_str("REJECTED").unwrap(),
@@ -78,8 +77,8 @@ mod tests_order_status {
         // Test with different cases
         assert_eq!(OrderStatus::from_str("new").unwrap(), OrderStatus::New);
         assert_eq!(
-            OrderStatus::from_str("Active").unwrap(),
-            OrderStatus::Active
+            OrderStatus::from_str("Cancelled").unwrap(),
+            OrderStatus::Cancelled
         );
         assert_eq!(
             OrderStatus::from_str("partiallyFilled").unwrap(),
@@ -108,10 +107,9 @@ mod tests_order_status {
     fn test_display() {
         // Test that display outputs the expected string
         assert_eq!(OrderStatus::New.to_string(), "NEW");
-        assert_eq!(OrderStatus::Active.to_string(), "ACTIVE");
         assert_eq!(OrderStatus::PartiallyFilled.to_string(), "PARTIALLYFILLED");
         assert_eq!(OrderStatus::Filled.to_string(), "FILLED");
-        assert_eq!(OrderStatus::Canceled.to_string(), "CANCELED");
+        assert_eq!(OrderStatus::Cancelled.to_string(), "CANCELLED");
         assert_eq!(OrderStatus::Rejected.to_string(), "REJECTED");
         assert_eq!(OrderStatus::Expired.to_string(), "EXPIRED");
     }
@@ -120,10 +118,6 @@ mod tests_order_status {
     fn test_serialization() {
         // Test serialization
         assert_eq!(serde_json::to_string(&OrderStatus::New).unwrap(), "\"New\"");
-        assert_eq!(
-            serde_json::to_string(&OrderStatus::Active).unwrap(),
-            "\"Active\""
-        );
         assert_eq!(
             serde_json::to_string(&OrderStatus::PartiallyFilled).unwrap(),
             "\"PartiallyFilled\""
@@ -133,8 +127,8 @@ mod tests_order_status {
             "\"Filled\""
         );
         assert_eq!(
-            serde_json::to_string(&OrderStatus::Canceled).unwrap(),
-            "\"Canceled\""
+            serde_json::to_string(&OrderStatus::Cancelled).unwrap(),
+            "\"Cancelled\""
         );
         assert_eq!(
             serde_json::to_string(&OrderStatus::Rejected).unwrap(),
@@ -153,10 +147,6 @@ mod tests_order_status {
             serde_json::from_str::<OrderStatus>("\"New\"").unwrap(),
             OrderStatus::New
         );
-        assert_eq!(
-            serde_json::from_str::<OrderStatus>("\"Active\"").unwrap(),
-            OrderStatus::Active
-        );
         assert_eq!(
             serde_json::from_str::<OrderStatus>("\"PartiallyFilled\"").unwrap(),
             OrderStatus::PartiallyFilled
@@ -166,8 +156,8 @@ mod tests_order_status {
             OrderStatus::Filled
         );
         assert_eq!(
-            serde_json::from_str::<OrderStatus>("\"Canceled\"").unwrap(),
-            OrderStatus::Canceled
+            serde_json::from_str::<OrderStatus>("\"Cancelled\"").unwrap(),
+            OrderStatus::Cancelled
         );
         assert_eq!(
             serde_json::from_str::<OrderStatus>("\"Rejected\"").unwrap(),
@@ -184,10 +174,9 @@ mod tests_order_status {
         // Test round trip from enum to string and back
         for status in [
             OrderStatus::New,
-            OrderStatus::Active,
             OrderStatus::PartiallyFilled,
             OrderStatus::Filled,
-            OrderStatus::Canceled,
+            OrderStatus::Cancelled,
             OrderStatus::Rejected,
             OrderStatus::Expired,
         ] {
@@ -196,4 +185,23 @@ mod tests_order_status {
             assert_eq!(status, parsed_back);
         }
     }
+
+    #[test]
+    fn test_transition_display_and_serde() {
+        let transition = OrderStatusTransition {
+            order_id: Id::from_u64(7),
+            from: OrderStatus::New,
+            to: OrderStatus::PartiallyFilled,
+        };
+        assert_eq!(
+            transition.to_string(),
+            format!("{}:NEW->PARTIALLYFILLED", Id::from_u64(7))
+        );
+
+        let json = serde_json::to_string(&transition).unwrap();
+        assert_eq!(
+            serde_json::from_str::<OrderStatusTransition>(&json).unwrap(),
+            transition
+        );
+    }
 }
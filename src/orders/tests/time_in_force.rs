@@ -59,6 +59,27 @@ mod tests {
         assert!(tif.is_expired(1700, Some(market_close)));
     }
 
+    #[test]
+    fn test_gtt_expires_relative_to_the_order_timestamp() {
+        let tif = TimeInForce::Gtt(500);
+        assert!(tif.has_expiry());
+        assert_eq!(tif.expires_at(1_000), Some(1_500));
+        assert_eq!(TimeInForce::Gtd(2_000).expires_at(1_000), Some(2_000));
+        assert_eq!(TimeInForce::Day.expires_at(1_000), None);
+        assert!(!tif.is_expired_at(1_000, 1_499, None));
+        assert!(tif.is_expired_at(1_000, 1_500, None));
+        // Without the order timestamp the expiry is unknown.
+        assert!(!tif.is_expired(u64::MAX, None));
+        assert_eq!(TimeInForce::Gtt(u64::MAX).expires_at(1), Some(u64::MAX));
+
+        assert_eq!(tif.to_string(), "GTT-500");
+        assert_eq!(TimeInForce::from_str("gtt-500").unwrap(), tif);
+        assert!(TimeInForce::from_str("GTT-soon").is_err());
+        let json = serde_json::to_string(&tif).unwrap();
+        assert_eq!(json, r#"{"GTT":500}"#);
+        assert_eq!(serde_json::from_str::<TimeInForce>(&json).unwrap(), tif);
+    }
+
     #[test]
     fn test_non_expiring_types() {
         assert!(!TimeInForce::Gtc.is_expired(9999, Some(1000)));
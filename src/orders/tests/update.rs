@@ -386,4 +386,32 @@ mod tests_order_update {
             "Cancel:order_id=00000000-0000-007b-0000-000000000000"
         );
     }
+
+    #[test]
+    fn test_update_order_id() {
+        let id = Id::from_u64(7);
+        let updates = [
+            OrderUpdate::UpdatePrice {
+                order_id: id,
+                new_price: Price::new(1),
+            },
+            OrderUpdate::UpdateQuantity {
+                order_id: id,
+                new_quantity: Quantity::new(1),
+            },
+            OrderUpdate::UpdatePriceAndQuantity {
+                order_id: id,
+                new_price: Price::new(1),
+                new_quantity: Quantity::new(1),
+            },
+            OrderUpdate::Cancel { order_id: id },
+            OrderUpdate::Replace {
+                order_id: id,
+                price: Price::new(1),
+                quantity: Quantity::new(1),
+                side: Side::Buy,
+            },
+        ];
+        assert!(updates.iter().all(|update| update.order_id() == id));
+    }
 }
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crate::orders::{
+        Hash32, Id, OrderStatus, OrderStatusTransition, OrderType, Side, TimeInForce, UpdateOutcome,
+    };
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::sync::Arc;
+
+    fn create_order(id: u64) -> Arc<OrderType<()>> {
+        Arc::new(OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(100),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        })
+    }
+
+    #[test]
+    fn test_found_variants_carry_the_order() {
+        for outcome in [
+            UpdateOutcome::UpdatedInPlace(create_order(1)),
+            UpdateOutcome::RemovedForReprice(create_order(1)),
+            UpdateOutcome::Cancelled(create_order(1), OrderStatus::New),
+        ] {
+            assert!(outcome.is_found());
+            assert_eq!(outcome.order().map(|o| o.id()), Some(Id::from_u64(1)));
+            assert_eq!(outcome.into_order().map(|o| o.id()), Some(Id::from_u64(1)));
+        }
+    }
+
+    #[test]
+    fn test_is_removed() {
+        assert!(!UpdateOutcome::UpdatedInPlace(create_order(1)).is_removed());
+        assert!(UpdateOutcome::RemovedForReprice(create_order(1)).is_removed());
+        assert!(UpdateOutcome::Cancelled(create_order(1), OrderStatus::New).is_removed());
+        assert!(!UpdateOutcome::<()>::NotFound.is_removed());
+    }
+
+    #[test]
+    fn test_not_found_carries_nothing() {
+        let outcome: UpdateOutcome = UpdateOutcome::NotFound;
+        assert!(!outcome.is_found());
+        assert!(outcome.order().is_none());
+        assert!(outcome.into_order().is_none());
+    }
+
+    #[test]
+    fn test_only_a_cancel_reports_a_status_transition() {
+        let cancelled = UpdateOutcome::Cancelled(create_order(1), OrderStatus::PartiallyFilled);
+        assert_eq!(
+            cancelled.status_transition(),
+            Some(OrderStatusTransition {
+                order_id: Id::from_u64(1),
+                from: OrderStatus::PartiallyFilled,
+                to: OrderStatus::Cancelled,
+            })
+        );
+
+        assert!(
+            UpdateOutcome::UpdatedInPlace(create_order(1))
+                .status_transition()
+                .is_none()
+        );
+        assert!(
+            UpdateOutcome::RemovedForReprice(create_order(1))
+                .status_transition()
+                .is_none()
+        );
+        assert!(UpdateOutcome::<()>::NotFound.status_transition().is_none());
+    }
+}
@@ -1,7 +1,9 @@
 use crate::errors::PriceLevelError;
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
 use serde::{Deserialize, Serialize};
-use std::fmt;
-use std::str::FromStr;
 
 /// Specifies how long an order remains active before it is executed or expires.
 #[repr(u8)]
@@ -38,6 +40,17 @@ pub enum TimeInForce {
     #[serde(alias = "gtd", alias = "GTD")]
     Gtd(u64),
 
+    /// Good 'Til Time - The order remains active for the given number of
+    /// MILLISECONDS after its own timestamp, for short-lived quotes such as a
+    /// 500 ms quote.
+    ///
+    /// The expiry is relative, so it is only known together with the order:
+    /// read it with [`OrderType::expires_at`](crate::OrderType::expires_at),
+    /// or [`Self::expires_at`] given the order's timestamp.
+    #[serde(rename(serialize = "GTT"))]
+    #[serde(alias = "gtt", alias = "GTT")]
+    Gtt(u64),
+
     /// Good for the trading Day - The order remains active until the end of the current trading day.
     #[serde(rename(serialize = "DAY"))]
     #[serde(alias = "day", alias = "DAY")]
@@ -54,10 +67,28 @@ impl TimeInForce {
     /// Returns true if the order has a specific expiration time
     #[must_use]
     pub fn has_expiry(&self) -> bool {
-        matches!(self, Self::Gtd(_) | Self::Day)
+        matches!(self, Self::Gtd(_) | Self::Gtt(_) | Self::Day)
+    }
+
+    /// The expiry, in milliseconds since the epoch, of an order with this
+    /// time in force placed at `order_timestamp`: the `Gtd` date, or the
+    /// order's timestamp plus the `Gtt` duration (saturating). `None` for the
+    /// other variants, including `Day`, whose close the caller knows.
+    #[must_use]
+    pub fn expires_at(&self, order_timestamp: u64) -> Option<u64> {
+        match self {
+            Self::Gtd(expiry) => Some(*expiry),
+            Self::Gtt(duration_ms) => Some(order_timestamp.saturating_add(*duration_ms)),
+            _ => None,
+        }
     }
 
     /// Checks if an order with this time in force has expired
+    ///
+    /// A `Gtt` expiry is relative to the order's timestamp, which this method
+    /// does not know, so a `Gtt` order is never reported expired here; use
+    /// [`Self::is_expired_at`] or
+    /// [`OrderType::is_expired`](crate::OrderType::is_expired).
     #[must_use]
     pub fn is_expired(&self, current_timestamp: u64, market_close_timestamp: Option<u64>) -> bool {
         match self {
@@ -72,6 +103,21 @@ impl TimeInForce {
             _ => false,
         }
     }
+
+    /// Checks if an order with this time in force, placed at
+    /// `order_timestamp`, has expired by `current_timestamp`.
+    #[must_use]
+    pub fn is_expired_at(
+        &self,
+        order_timestamp: u64,
+        current_timestamp: u64,
+        market_close_timestamp: Option<u64>,
+    ) -> bool {
+        match self.expires_at(order_timestamp) {
+            Some(expiry) => current_timestamp >= expiry,
+            None => self.is_expired(current_timestamp, market_close_timestamp),
+        }
+    }
 }
 
 impl fmt::Display for TimeInForce {
@@ -81,6 +127,7 @@ impl fmt::Display for TimeInForce {
             TimeInForce::Ioc => write!(f, "IOC"),
             TimeInForce::Fok => write!(f, "FOK"),
             TimeInForce::Gtd(expiry) => write!(f, "GTD-{expiry}"),
+            TimeInForce::Gtt(duration_ms) => write!(f, "GTT-{duration_ms}"),
             TimeInForce::Day => write!(f, "DAY"),
         }
     }
@@ -110,6 +157,12 @@ impl FromStr for TimeInForce {
                     }),
                 }
             }
+            s if s.starts_with("GTT-") => match s["GTT-".len()..].parse::<u64>() {
+                Ok(duration_ms) => Ok(TimeInForce::Gtt(duration_ms)),
+                Err(_) => Err(PriceLevelError::ParseError {
+                    message: format!("Invalid duration in GTT: {}", &s["GTT-".len()..]),
+                }),
+            },
             _ => Err(PriceLevelError::ParseError {
                 message: format!("Invalid TimeInForce: {s}"),
             }),
@@ -1,8 +1,12 @@
 use crate::errors::PriceLevelError;
 use crate::orders::{Id, Side};
 use crate::utils::{Price, Quantity};
+use alloc::collections::BTreeMap;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
 
 /// Represents a request to update an existing order
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -59,6 +63,20 @@ pub enum OrderUpdate {
     },
 }
 
+impl OrderUpdate {
+    /// The id of the order the update targets.
+    #[must_use]
+    pub fn order_id(&self) -> Id {
+        match *self {
+            Self::UpdatePrice { order_id, .. }
+            | Self::UpdateQuantity { order_id, .. }
+            | Self::UpdatePriceAndQuantity { order_id, .. }
+            | Self::Cancel { order_id }
+            | Self::Replace { order_id, .. } => order_id,
+        }
+    }
+}
+
 impl FromStr for OrderUpdate {
     type Err = PriceLevelError;
 
@@ -71,7 +89,7 @@ impl FromStr for OrderUpdate {
         let update_type = parts[0];
         let fields_str = parts[1];
 
-        let mut fields = std::collections::HashMap::new();
+        let mut fields = BTreeMap::new();
         for field_pair in fields_str.split(';') {
             let kv: Vec<&str> = field_pair.split('=').collect();
             if kv.len() == 2 {
@@ -167,8 +185,8 @@ impl FromStr for OrderUpdate {
     }
 }
 
-impl std::fmt::Display for OrderUpdate {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl fmt::Display for OrderUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             OrderUpdate::UpdatePrice {
                 order_id,
@@ -0,0 +1,117 @@
+use crate::orders::{Id, OrderStatus, OrderStatusTransition, OrderType};
+use alloc::sync::Arc;
+
+/// What [`PriceLevel::update_order`](crate::PriceLevel::update_order) did with
+/// an [`OrderUpdate`](crate::OrderUpdate).
+///
+/// Each variant that found the order carries it, so a caller can branch on the
+/// kind of change instead of inferring it from the update it sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateOutcome<T = ()> {
+    /// The order was resized and still rests at this level; carries the order
+    /// as it now rests. A size increase also moved it to the back of the queue.
+    UpdatedInPlace(Arc<OrderType<T>>),
+
+    /// The update moved the order to another price: it was removed from this
+    /// level and is returned unchanged for the caller to re-insert at the new
+    /// price.
+    RemovedForReprice(Arc<OrderType<T>>),
+
+    /// The order was cancelled and removed from this level; carries the order
+    /// as it rested when removed and the status it had then
+    /// ([`OrderStatus::New`] or [`OrderStatus::PartiallyFilled`]).
+    Cancelled(Arc<OrderType<T>>, OrderStatus),
+
+    /// No order with the requested id rests at this level (never added,
+    /// already filled, or removed concurrently). Nothing changed.
+    NotFound,
+}
+
+impl<T> UpdateOutcome<T> {
+    /// Returns `true` unless the outcome is [`Self::NotFound`].
+    #[must_use]
+    pub fn is_found(&self) -> bool {
+        !matches!(self, Self::NotFound)
+    }
+
+    /// Returns `true` if the order no longer rests at this level
+    /// ([`Self::RemovedForReprice`] or [`Self::Cancelled`]).
+    #[must_use]
+    pub fn is_removed(&self) -> bool {
+        matches!(self, Self::RemovedForReprice(_) | Self::Cancelled(..))
+    }
+
+    /// Returns the order carried by the outcome, if any.
+    #[must_use]
+    pub fn order(&self) -> Option<&Arc<OrderType<T>>> {
+        match self {
+            Self::UpdatedInPlace(order)
+            | Self::RemovedForReprice(order)
+            | Self::Cancelled(order, _) => Some(order),
+            Self::NotFound => None,
+        }
+    }
+
+    /// Consumes the outcome and returns the order it carries, if any.
+    #[must_use]
+    pub fn into_order(self) -> Option<Arc<OrderType<T>>> {
+        match self {
+            Self::UpdatedInPlace(order)
+            | Self::RemovedForReprice(order)
+            | Self::Cancelled(order, _) => Some(order),
+            Self::NotFound => None,
+        }
+    }
+}
+
+impl<T: Clone> UpdateOutcome<T> {
+    /// Returns the status change the update made, if any.
+    ///
+    /// Only a cancel ends an order's life here. A resize, or a move to another
+    /// price, leaves the order working.
+    #[must_use]
+    pub fn status_transition(&self) -> Option<OrderStatusTransition> {
+        match self {
+            Self::Cancelled(order, from) => Some(OrderStatusTransition {
+                order_id: order.id(),
+                from: *from,
+                to: OrderStatus::Cancelled,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// What [`PriceLevel::cancel_replace`](crate::PriceLevel::cancel_replace) did:
+/// the order it cancelled and the replacement it admitted in its place, as
+/// one mutation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderReplacement<T = ()> {
+    /// The cancelled order, as it rested when removed.
+    pub replaced: Arc<OrderType<T>>,
+    /// The status the cancelled order rested with ([`OrderStatus::New`] or
+    /// [`OrderStatus::PartiallyFilled`]).
+    pub replaced_status: OrderStatus,
+    /// The replacement, as it now rests at the back of the queue.
+    pub replacement: Arc<OrderType<T>>,
+}
+
+impl<T: Clone> OrderReplacement<T> {
+    /// The id of the cancelled order, which the level records against the
+    /// replacement (see
+    /// [`PriceLevel::orig_order_id`](crate::PriceLevel::orig_order_id)).
+    #[must_use]
+    pub fn orig_order_id(&self) -> Id {
+        self.replaced.id()
+    }
+
+    /// The status change of the cancelled order.
+    #[must_use]
+    pub fn status_transition(&self) -> OrderStatusTransition {
+        OrderStatusTransition {
+            order_id: self.replaced.id(),
+            from: self.replaced_status,
+            to: OrderStatus::Cancelled,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Fuzzing harness for the parser entry points (`--cfg fuzzing` only).
+//!
+//! Each function takes the fuzzer's raw bytes, skips input that is not
+//! UTF-8, and parses it within [`ParseLimits::DEFAULT`]. A parser may reject
+//! any input but must not panic, and whatever it accepts must survive a
+//! `Display` round trip. What a strict parse accepts, a lenient one must
+//! accept identically. A broken property panics, which the fuzzer reports as
+//! a crash.
+
+use super::{ParseLimits, ParseOptions};
+use crate::orders::OrderType;
+
+fn text(data: &[u8]) -> Option<&str> {
+    std::str::from_utf8(data).ok()
+}
+
+/// Parse an order; an accepted one must reparse from its `Display` form to
+/// an equal order, and a strict parse may only accept what the lenient one
+/// does.
+pub fn order(data: &[u8]) {
+    let Some(input) = text(data) else { return };
+    let lenient = super::order::<()>(input, &ParseOptions::DEFAULT);
+    if let Ok(strict) = super::order::<()>(input, &ParseOptions::STRICT) {
+        assert_eq!(
+            lenient.as_ref().ok(),
+            Some(&strict),
+            "strict parse accepted an order the lenient parse did not"
+        );
+    }
+    if let Ok(order) = lenient {
+        let reparsed: OrderType<()> = super::order(&order.to_string(), &ParseOptions::DEFAULT)
+            .expect("a parsed order reparses from its display form");
+        assert_eq!(
+            order, reparsed,
+            "order display round trip changed the order"
+        );
+    }
+}
+
+/// Parse a price level; an accepted one must reparse from its `Display`
+/// form with the same price, quantities and order count.
+pub fn price_level(data: &[u8]) {
+    let Some(input) = text(data) else { return };
+    if let Ok(level) = super::price_level::<()>(input, &ParseOptions::DEFAULT) {
+        let reparsed = super::price_level::<()>(&level.to_string(), &ParseOptions::DEFAULT)
+            .expect("a parsed price level reparses from its display form");
+        assert_eq!(level.price(), reparsed.price());
+        assert_eq!(level.visible_quantity(), reparsed.visible_quantity());
+        assert_eq!(level.hidden_quantity(), reparsed.hidden_quantity());
+        assert_eq!(level.order_count(), reparsed.order_count());
+    }
+}
+
+/// Parse a snapshot header; an accepted one must reparse from its
+/// `Display` form to the same header.
+pub fn snapshot(data: &[u8]) {
+    let Some(input) = text(data) else { return };
+    if let Ok(snapshot) = super::snapshot::<()>(input, &ParseOptions::DEFAULT) {
+        let display = snapshot.to_string();
+        let reparsed = super::snapshot::<()>(&display, &ParseOptions::DEFAULT)
+            .expect("a parsed snapshot reparses from its display form");
+        assert_eq!(display, reparsed.to_string());
+    }
+}
+
+/// Decode a snapshot package and, if it decodes, validate it. Neither step
+/// may panic.
+pub fn snapshot_package(data: &[u8]) {
+    let Some(input) = text(data) else { return };
+    if let Ok(package) = super::snapshot_package::<()>(input, &ParseLimits::DEFAULT) {
+        let _ = package.validate();
+    }
+}
+
+/// Decode a book snapshot package and, if it decodes, validate it. Neither
+/// step may panic.
+pub fn book_snapshot_package(data: &[u8]) {
+    let Some(input) = text(data) else { return };
+    if let Ok(package) = super::book_snapshot_package::<()>(input, &ParseLimits::DEFAULT) {
+        let _ = package.validate();
+    }
+}
@@ -0,0 +1,436 @@
+//! Bounded parsing of untrusted text and JSON.
+//!
+//! The `FromStr` impls of [`OrderType`], [`PriceLevel`] and
+//! [`PriceLevelSnapshot`], and the `from_json` constructors of
+//! [`PriceLevelSnapshotPackage`] and [`BookSnapshotPackage`], are hand-rolled
+//! or trust their input's shape. Every one of them goes through this module,
+//! which checks the input against a [`ParseLimits`] before any allocation
+//! proportional to it: the input length, the number and length of
+//! `key=value` fields, and the number of orders.
+//!
+//! The trait impls parse leniently within [`ParseLimits::DEFAULT`]. Call
+//! the functions here (or `OrderType::from_str_with` /
+//! `PriceLevel::from_str_with`) with [`ParseOptions`] to parse strictly or
+//! with tighter (or looser) limits:
+//!
+//! ```rust
+//! use pricelevel::PriceLevelError;
+//! use pricelevel::parse::{self, ParseOptions};
+//!
+//! let options = ParseOptions::STRICT.with_max_orders(1);
+//! let input = "PriceLevel:price=10000;orders=[\
+//!     Standard:id=1;price=10000;quantity=5;side=SELL;timestamp=1;time_in_force=GTC,\
+//!     Standard:id=2;price=10000;quantity=5;side=SELL;timestamp=2;time_in_force=GTC]";
+//!
+//! let error = parse::price_level::<()>(input, &options).unwrap_err();
+//! assert!(matches!(error, PriceLevelError::ParseError { .. }));
+//! ```
+//!
+//! With `--cfg fuzzing` (as set by `cargo fuzz`), `fuzz` exposes one
+//! harness function per entry point for the targets under `fuzz/`.
+
+use crate::errors::PriceLevelError;
+use crate::orders::OrderType;
+use crate::price_level::{
+    BookSnapshotPackage, PriceLevel, PriceLevelSnapshot, PriceLevelSnapshotPackage,
+};
+use serde::de::DeserializeOwned;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+#[cfg(fuzzing)]
+pub mod fuzz;
+
+mod tests;
+
+/// Bounds a parser enforces on its input.
+///
+/// A violated bound is a [`PriceLevelError::ParseError`] naming the bound,
+/// raised before the parser allocates for the offending part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseLimits {
+    max_input_len: usize,
+    max_field_len: usize,
+    max_fields: usize,
+    max_orders: usize,
+}
+
+impl ParseLimits {
+    /// The limits of the `FromStr` and `from_json` entry points: inputs up
+    /// to 256 MiB, up to 64 fields of up to 256 bytes per record, and up to
+    /// 1 000 000 orders.
+    pub const DEFAULT: Self = Self {
+        max_input_len: 256 * 1024 * 1024,
+        max_field_len: 256,
+        max_fields: 64,
+        max_orders: 1_000_000,
+    };
+
+    /// Sets the longest input, in bytes.
+    #[must_use]
+    pub const fn with_max_input_len(mut self, max_input_len: usize) -> Self {
+        self.max_input_len = max_input_len;
+        self
+    }
+
+    /// Sets the longest `key=value` field of a record, in bytes.
+    #[must_use]
+    pub const fn with_max_field_len(mut self, max_field_len: usize) -> Self {
+        self.max_field_len = max_field_len;
+        self
+    }
+
+    /// Sets the most `key=value` fields in a record.
+    #[must_use]
+    pub const fn with_max_fields(mut self, max_fields: usize) -> Self {
+        self.max_fields = max_fields;
+        self
+    }
+
+    /// Sets the most orders in a price level, queue or snapshot.
+    #[must_use]
+    pub const fn with_max_orders(mut self, max_orders: usize) -> Self {
+        self.max_orders = max_orders;
+        self
+    }
+
+    /// Get the longest input, in bytes.
+    #[must_use]
+    pub const fn max_input_len(&self) -> usize {
+        self.max_input_len
+    }
+
+    /// Get the longest `key=value` field of a record, in bytes.
+    #[must_use]
+    pub const fn max_field_len(&self) -> usize {
+        self.max_field_len
+    }
+
+    /// Get the most `key=value` fields in a record.
+    #[must_use]
+    pub const fn max_fields(&self) -> usize {
+        self.max_fields
+    }
+
+    /// Get the most orders in a price level, queue or snapshot.
+    #[must_use]
+    pub const fn max_orders(&self) -> usize {
+        self.max_orders
+    }
+
+    /// Check the length of a whole input.
+    pub(crate) fn check_input(&self, what: &str, input: &str) -> Result<(), PriceLevelError> {
+        if input.len() > self.max_input_len {
+            return Err(exceeded(what, "max_input_len", self.max_input_len));
+        }
+        Ok(())
+    }
+
+    /// Check a `Prefix:key=value;key=value` record: its length, then the
+    /// number and length of its fields.
+    pub(crate) fn check_record(&self, what: &str, record: &str) -> Result<(), PriceLevelError> {
+        self.check_input(what, record)?;
+        let body = record.split_once(':').map_or(record, |(_, body)| body);
+        let mut fields = 0usize;
+        for field in body.split(';') {
+            fields += 1;
+            if fields > self.max_fields {
+                return Err(exceeded(what, "max_fields", self.max_fields));
+            }
+            if field.len() > self.max_field_len {
+                return Err(exceeded(what, "max_field_len", self.max_field_len));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that `count` orders are allowed.
+    pub(crate) fn check_orders(&self, what: &str, count: usize) -> Result<(), PriceLevelError> {
+        if count > self.max_orders {
+            return Err(exceeded(what, "max_orders", self.max_orders));
+        }
+        Ok(())
+    }
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+fn exceeded(what: &str, limit: &str, value: usize) -> PriceLevelError {
+    PriceLevelError::ParseError {
+        message: format!("{what} exceeds {limit} of {value}"),
+    }
+}
+
+/// How the text parsers treat their input: the [`ParseLimits`] they enforce
+/// and whether they are strict.
+///
+/// A lenient parse (the default, and the behaviour of `FromStr`) skips what
+/// it does not recognise: unknown fields, pairs without exactly one `=`, and
+/// for a repeated field keeps the last value. A strict parse rejects all of
+/// those as [`PriceLevelError::ParseError`], and a strict price level also
+/// rejects `visible_quantity`, `hidden_quantity` or `order_count` fields that
+/// disagree with its orders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    strict: bool,
+    limits: ParseLimits,
+}
+
+impl ParseOptions {
+    /// Lenient parsing within [`ParseLimits::DEFAULT`], as `FromStr` does.
+    pub const DEFAULT: Self = Self {
+        strict: false,
+        limits: ParseLimits::DEFAULT,
+    };
+
+    /// Strict parsing within [`ParseLimits::DEFAULT`].
+    pub const STRICT: Self = Self {
+        strict: true,
+        limits: ParseLimits::DEFAULT,
+    };
+
+    /// Sets whether the parse is strict.
+    #[must_use]
+    pub const fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets every limit at once.
+    #[must_use]
+    pub const fn with_limits(mut self, limits: ParseLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the longest input, in bytes.
+    #[must_use]
+    pub const fn with_max_input_len(mut self, max_input_len: usize) -> Self {
+        self.limits = self.limits.with_max_input_len(max_input_len);
+        self
+    }
+
+    /// Sets the longest `key=value` field of a record, in bytes.
+    #[must_use]
+    pub const fn with_max_field_len(mut self, max_field_len: usize) -> Self {
+        self.limits = self.limits.with_max_field_len(max_field_len);
+        self
+    }
+
+    /// Sets the most `key=value` fields in a record.
+    #[must_use]
+    pub const fn with_max_fields(mut self, max_fields: usize) -> Self {
+        self.limits = self.limits.with_max_fields(max_fields);
+        self
+    }
+
+    /// Sets the most orders in a price level or queue.
+    #[must_use]
+    pub const fn with_max_orders(mut self, max_orders: usize) -> Self {
+        self.limits = self.limits.with_max_orders(max_orders);
+        self
+    }
+
+    /// Get whether the parse is strict.
+    #[must_use]
+    pub const fn strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Get the limits.
+    #[must_use]
+    pub const fn limits(&self) -> &ParseLimits {
+        &self.limits
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl From<ParseLimits> for ParseOptions {
+    /// Lenient parsing within `limits`.
+    fn from(limits: ParseLimits) -> Self {
+        Self::DEFAULT.with_limits(limits)
+    }
+}
+
+/// The `key=value` fields of one record, remembering which the parser read
+/// so a strict parse can reject the rest.
+pub(crate) struct RecordFields<'a> {
+    what: &'static str,
+    strict: bool,
+    fields: HashMap<&'a str, &'a str>,
+    read: RefCell<HashSet<&'a str>>,
+}
+
+impl<'a> RecordFields<'a> {
+    /// Split `body` on `;` into fields. Empty segments are skipped; a strict
+    /// split rejects a segment without exactly one `=` and a repeated key.
+    pub(crate) fn split(
+        what: &'static str,
+        body: &'a str,
+        strict: bool,
+    ) -> Result<Self, PriceLevelError> {
+        let mut fields = HashMap::new();
+        for pair in body.split(';').filter(|pair| !pair.is_empty()) {
+            let Some((key, value)) = pair
+                .split_once('=')
+                .filter(|(_, value)| !value.contains('='))
+            else {
+                if strict {
+                    return Err(PriceLevelError::ParseError {
+                        message: format!("{what} has malformed field `{pair}`"),
+                    });
+                }
+                continue;
+            };
+            if fields.insert(key, value).is_some() && strict {
+                return Err(PriceLevelError::ParseError {
+                    message: format!("{what} repeats field `{key}`"),
+                });
+            }
+        }
+        Ok(Self {
+            what,
+            strict,
+            fields,
+            read: RefCell::new(HashSet::new()),
+        })
+    }
+
+    /// The value of `field`, if present.
+    pub(crate) fn get(&self, field: &str) -> Option<&'a str> {
+        let (key, value) = self.fields.get_key_value(field)?;
+        self.read.borrow_mut().insert(*key);
+        Some(*value)
+    }
+
+    /// The value of `field`, or [`PriceLevelError::MissingField`].
+    pub(crate) fn require(&self, field: &str) -> Result<&'a str, PriceLevelError> {
+        self.get(field)
+            .ok_or_else(|| PriceLevelError::MissingField(field.to_string()))
+    }
+
+    /// In a strict parse, reject any field the parser did not read.
+    pub(crate) fn finish(&self) -> Result<(), PriceLevelError> {
+        if !self.strict {
+            return Ok(());
+        }
+        let read = self.read.borrow();
+        let mut unknown: Vec<&str> = self
+            .fields
+            .keys()
+            .filter(|key| !read.contains(*key))
+            .copied()
+            .collect();
+        unknown.sort_unstable();
+        match unknown.first() {
+            Some(key) => Err(PriceLevelError::ParseError {
+                message: format!("{} has unknown field `{key}`", self.what),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parse an order from its `Display` form under `options`.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::ParseError`] if the input breaks a limit or,
+/// when strict, has an unknown, repeated or malformed field, and otherwise
+/// the errors of [`OrderType::from_str`](std::str::FromStr).
+pub fn order<T: Default>(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<OrderType<T>, PriceLevelError> {
+    options.limits.check_record("order", input)?;
+    OrderType::parse_with(input, options.strict)
+}
+
+/// Parse a price level from its `Display` form under `options`, each order
+/// parsed as by [`order`].
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::ParseError`] if the input breaks a limit or,
+/// when strict, has an unknown, repeated or malformed field,
+/// [`PriceLevelError::InvalidFieldValue`] if, when strict, a stated quantity
+/// or order count disagrees with the orders, and otherwise the errors of
+/// [`PriceLevel::from_str`](std::str::FromStr).
+pub fn price_level<T: Clone + Default>(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<PriceLevel<T>, PriceLevelError> {
+    options.limits.check_input("price level", input)?;
+    PriceLevel::parse_bounded(input, options)
+}
+
+/// Parse a snapshot header from its `Display` form under `options`.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::ParseError`] if the input breaks a limit or,
+/// when strict, has an unknown, repeated or malformed field, and otherwise
+/// the errors of [`PriceLevelSnapshot::from_str`](std::str::FromStr).
+pub fn snapshot<T>(
+    input: &str,
+    options: &ParseOptions,
+) -> Result<PriceLevelSnapshot<T>, PriceLevelError> {
+    options.limits.check_record("snapshot", input)?;
+    PriceLevelSnapshot::parse_with(input, options.strict)
+}
+
+/// Decode a snapshot package from JSON within `limits`.
+///
+/// The JSON length is checked before decoding, which bounds what the decoder
+/// allocates; the order count is checked once decoded. The package is not
+/// checksum-validated.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::ParseError`] if the input breaks a limit and
+/// [`PriceLevelError::DeserializationError`] if it is not a snapshot package.
+pub fn snapshot_package<T: Clone + DeserializeOwned>(
+    json: &str,
+    limits: &ParseLimits,
+) -> Result<PriceLevelSnapshotPackage<T>, PriceLevelError> {
+    limits.check_input("snapshot package", json)?;
+    let package: PriceLevelSnapshotPackage<T> = decode_json(json)?;
+    limits.check_orders("snapshot package", package.snapshot().orders().len())?;
+    Ok(package)
+}
+
+/// Decode a book snapshot package from JSON within `limits`, the order
+/// limit applying to the orders of every level together.
+///
+/// # Errors
+///
+/// As [`snapshot_package`].
+pub fn book_snapshot_package<T: Clone + DeserializeOwned>(
+    json: &str,
+    limits: &ParseLimits,
+) -> Result<BookSnapshotPackage<T>, PriceLevelError> {
+    limits.check_input("book snapshot package", json)?;
+    let package: BookSnapshotPackage<T> = decode_json(json)?;
+    let orders = package
+        .snapshot()
+        .levels()
+        .iter()
+        .fold(0usize, |total, level| {
+            total.saturating_add(level.orders().len())
+        });
+    limits.check_orders("book snapshot package", orders)?;
+    Ok(package)
+}
+
+fn decode_json<T: DeserializeOwned>(json: &str) -> Result<T, PriceLevelError> {
+    serde_json::from_str(json).map_err(PriceLevelError::deserialization)
+}
@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::parse::{self, ParseLimits, ParseOptions};
+    use crate::price_level::{
+        BookSnapshot, BookSnapshotPackage, OrderQueue, PriceLevel, PriceLevelSnapshot,
+        PriceLevelSnapshotPackage,
+    };
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::str::FromStr;
+
+    fn order(id: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10_000),
+            quantity: Quantity::new(5),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1_616_823_000_000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn level(orders: u64) -> PriceLevel {
+        let level = PriceLevel::new(10_000);
+        for id in 1..=orders {
+            level.add_order(order(id)).unwrap();
+        }
+        level
+    }
+
+    fn is_limit_error(result: Result<impl Sized, PriceLevelError>, limit: &str) -> bool {
+        match result {
+            Err(PriceLevelError::ParseError { message }) => message.contains(limit),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn test_default_limits_accept_display_forms() {
+        let text = order(1).to_string();
+        assert_eq!(
+            parse::order::<()>(&text, &ParseOptions::DEFAULT).unwrap(),
+            order(1)
+        );
+
+        let text = level(3).to_string();
+        let parsed = parse::price_level::<()>(&text, &ParseOptions::DEFAULT).unwrap();
+        assert_eq!(parsed.order_count(), 3);
+
+        let text = level(3).snapshot().to_string();
+        let parsed = parse::snapshot::<()>(&text, &ParseOptions::DEFAULT).unwrap();
+        assert_eq!(parsed.to_string(), text);
+    }
+
+    #[test]
+    fn test_order_limits() {
+        let text = order(1).to_string();
+        assert!(is_limit_error(
+            parse::order::<()>(&text, &ParseOptions::DEFAULT.with_max_input_len(10)),
+            "max_input_len"
+        ));
+        assert!(is_limit_error(
+            parse::order::<()>(&text, &ParseOptions::DEFAULT.with_max_fields(3)),
+            "max_fields"
+        ));
+        assert!(is_limit_error(
+            parse::order::<()>(&text, &ParseOptions::DEFAULT.with_max_field_len(8)),
+            "max_field_len"
+        ));
+    }
+
+    #[test]
+    fn test_from_str_rejects_oversized_field() {
+        let text = format!(
+            "Standard:id={};price=10000;quantity=5;side=SELL;timestamp=1;time_in_force=GTC",
+            "1".repeat(1_000)
+        );
+        assert!(is_limit_error(
+            OrderType::<()>::from_str(&text),
+            "max_field_len"
+        ));
+
+        let text = format!("PriceLevel:price=10000;orders=[{text}]");
+        assert!(is_limit_error(
+            PriceLevel::<()>::from_str(&text),
+            "max_field_len"
+        ));
+
+        let text = format!("OrderQueue:orders=[{}]", "x".repeat(1_000));
+        assert!(is_limit_error(
+            OrderQueue::<()>::from_str(&text),
+            "max_field_len"
+        ));
+    }
+
+    #[test]
+    fn test_from_str_rejects_too_many_fields() {
+        let text = format!("PriceLevelSnapshot:price=1{}", ";a=b".repeat(100));
+        assert!(is_limit_error(
+            PriceLevelSnapshot::<()>::from_str(&text),
+            "max_fields"
+        ));
+    }
+
+    #[test]
+    fn test_price_level_order_limit() {
+        let text = level(2).to_string();
+        assert!(is_limit_error(
+            parse::price_level::<()>(&text, &ParseOptions::DEFAULT.with_max_orders(1)),
+            "max_orders"
+        ));
+        let parsed =
+            parse::price_level::<()>(&text, &ParseOptions::DEFAULT.with_max_orders(2)).unwrap();
+        assert_eq!(parsed.order_count(), 2);
+    }
+
+    #[test]
+    fn test_snapshot_package_limits() {
+        let json = PriceLevelSnapshotPackage::new(level(3).snapshot())
+            .unwrap()
+            .to_json()
+            .unwrap();
+        assert!(is_limit_error(
+            parse::snapshot_package::<()>(&json, &ParseLimits::DEFAULT.with_max_orders(2)),
+            "max_orders"
+        ));
+        assert!(is_limit_error(
+            parse::snapshot_package::<()>(&json, &ParseLimits::DEFAULT.with_max_input_len(16)),
+            "max_input_len"
+        ));
+        let package = PriceLevelSnapshotPackage::<()>::from_json(&json).unwrap();
+        assert_eq!(package.snapshot().orders().len(), 3);
+        assert!(package.validate().is_ok());
+    }
+
+    #[test]
+    fn test_book_snapshot_package_counts_every_level() {
+        let book = BookSnapshot::new("BTC-USD", 1, TimestampMs::new(1))
+            .with_level(level(2).snapshot())
+            .with_level(level(2).snapshot());
+        let json = BookSnapshotPackage::new(book).unwrap().to_json().unwrap();
+        assert!(is_limit_error(
+            parse::book_snapshot_package::<()>(&json, &ParseLimits::DEFAULT.with_max_orders(3)),
+            "max_orders"
+        ));
+        assert!(
+            parse::book_snapshot_package::<()>(&json, &ParseLimits::DEFAULT.with_max_orders(4))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_malformed_json_is_a_deserialization_error() {
+        assert!(matches!(
+            PriceLevelSnapshotPackage::<()>::from_json("{\"version\":"),
+            Err(PriceLevelError::DeserializationError { .. })
+        ));
+    }
+
+    #[cfg(fuzzing)]
+    #[test]
+    fn test_fuzz_harness_accepts_display_forms() {
+        parse::fuzz::order(order(1).to_string().as_bytes());
+        parse::fuzz::price_level(level(3).to_string().as_bytes());
+        parse::fuzz::snapshot(level(3).snapshot().to_string().as_bytes());
+        let json = PriceLevelSnapshotPackage::new(level(3).snapshot())
+            .unwrap()
+            .to_json()
+            .unwrap();
+        parse::fuzz::snapshot_package(json.as_bytes());
+        parse::fuzz::order(&[0xff, 0xfe]);
+        parse::fuzz::price_level(b"PriceLevel:price=1;orders=[)");
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::parse::{self, ParseLimits, ParseOptions};
+    use crate::price_level::{PriceLevel, PriceLevelSnapshot};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::str::FromStr;
+
+    const ORDER: &str =
+        "Standard:id=1;price=10000;quantity=5;side=SELL;timestamp=1;time_in_force=GTC";
+
+    fn level(orders: u64) -> PriceLevel {
+        let level = PriceLevel::new(10_000);
+        for id in 1..=orders {
+            level
+                .add_order(OrderType::Standard {
+                    id: Id::from_u64(id),
+                    price: Price::new(10_000),
+                    quantity: Quantity::new(5),
+                    side: Side::Sell,
+                    user_id: Hash32::zero(),
+                    timestamp: TimestampMs::new(id),
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                })
+                .unwrap();
+        }
+        level
+    }
+
+    fn parse_error_mentions(result: Result<impl Sized, PriceLevelError>, text: &str) -> bool {
+        match result {
+            Err(PriceLevelError::ParseError { message }) => message.contains(text),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn test_options_defaults() {
+        assert!(!ParseOptions::default().strict());
+        assert!(ParseOptions::STRICT.strict());
+        assert_eq!(ParseOptions::default().limits(), &ParseLimits::DEFAULT);
+        let options = ParseOptions::STRICT.with_max_orders(3);
+        assert_eq!(options.limits().max_orders(), 3);
+        assert!(options.strict());
+        assert!(!ParseOptions::from(ParseLimits::DEFAULT).strict());
+    }
+
+    #[test]
+    fn test_display_forms_parse_strictly() {
+        let order = OrderType::<()>::from_str_with(ORDER, &ParseOptions::STRICT).unwrap();
+        assert_eq!(order, OrderType::from_str(ORDER).unwrap());
+
+        let text = level(3).to_string();
+        let parsed = PriceLevel::<()>::from_str_with(&text, &ParseOptions::STRICT).unwrap();
+        assert_eq!(parsed.order_count(), 3);
+        assert_eq!(parsed.visible_quantity(), 15);
+
+        let text = level(3).snapshot().to_string();
+        assert!(parse::snapshot::<()>(&text, &ParseOptions::STRICT).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_order_field() {
+        let text = format!("{ORDER};colour=blue");
+        assert!(OrderType::<()>::from_str(&text).is_ok());
+        assert!(parse_error_mentions(
+            OrderType::<()>::from_str_with(&text, &ParseOptions::STRICT),
+            "unknown field `colour`"
+        ));
+    }
+
+    #[test]
+    fn test_repeated_order_field() {
+        let text = format!("{ORDER};quantity=7");
+        let lenient = OrderType::<()>::from_str(&text).unwrap();
+        assert_eq!(lenient.visible_quantity().as_u64(), 7);
+        assert!(parse_error_mentions(
+            OrderType::<()>::from_str_with(&text, &ParseOptions::STRICT),
+            "repeats field `quantity`"
+        ));
+    }
+
+    #[test]
+    fn test_malformed_order_field() {
+        for extra in ["flag", "a=b=c"] {
+            let text = format!("{ORDER};{extra}");
+            assert!(OrderType::<()>::from_str(&text).is_ok());
+            assert!(parse_error_mentions(
+                OrderType::<()>::from_str_with(&text, &ParseOptions::STRICT),
+                "malformed field"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_unknown_price_level_field() {
+        let text = level(1)
+            .to_string()
+            .replacen(";orders=", ";venue=X;orders=", 1);
+        assert!(PriceLevel::<()>::from_str(&text).is_ok());
+        assert!(parse_error_mentions(
+            PriceLevel::<()>::from_str_with(&text, &ParseOptions::STRICT),
+            "unknown field `venue`"
+        ));
+    }
+
+    #[test]
+    fn test_strict_price_level_checks_orders() {
+        let text = format!("PriceLevel:price=10000;orders=[{ORDER};colour=blue]");
+        assert!(PriceLevel::<()>::from_str(&text).is_ok());
+        assert!(PriceLevel::<()>::from_str_with(&text, &ParseOptions::STRICT).is_err());
+    }
+
+    #[test]
+    fn test_strict_price_level_checks_stated_totals() {
+        let text = level(2)
+            .to_string()
+            .replacen("order_count=2", "order_count=5", 1);
+        assert_eq!(PriceLevel::<()>::from_str(&text).unwrap().order_count(), 2);
+        assert!(matches!(
+            PriceLevel::<()>::from_str_with(&text, &ParseOptions::STRICT),
+            Err(PriceLevelError::InvalidFieldValue { field, value })
+                if field == "order_count" && value == "5"
+        ));
+
+        let text = level(2)
+            .to_string()
+            .replacen("visible_quantity=10", "visible_quantity=9", 1);
+        assert!(matches!(
+            PriceLevel::<()>::from_str_with(&text, &ParseOptions::STRICT),
+            Err(PriceLevelError::InvalidFieldValue { field, .. }) if field == "visible_quantity"
+        ));
+    }
+
+    #[test]
+    fn test_strict_snapshot_header() {
+        let text = format!("{};extra=1", level(1).snapshot());
+        assert!(PriceLevelSnapshot::<()>::from_str(&text).is_ok());
+        assert!(parse_error_mentions(
+            parse::snapshot::<()>(&text, &ParseOptions::STRICT),
+            "unknown field `extra`"
+        ));
+    }
+
+    #[test]
+    fn test_strict_options_keep_limits() {
+        let text = level(2).to_string();
+        assert!(parse_error_mentions(
+            PriceLevel::<()>::from_str_with(&text, &ParseOptions::STRICT.with_max_orders(1)),
+            "max_orders"
+        ));
+    }
+}
@@ -6,10 +6,37 @@
 //! use pricelevel::prelude::*;
 //! ```
 
-pub use crate::errors::PriceLevelError;
-pub use crate::execution::{MatchOutcome, MatchResult, TakerKind, Trade, TradeList};
+pub use crate::errors::{CapacityLimit, PriceLevelError, PriceLevelErrorKind};
+#[cfg(feature = "std")]
+pub use crate::execution::{
+    Fee, FeeCurrency, FeeSchedule, LiquidityFlag, MakerFill, MatchOutcome, MatchResult,
+    MatchResultBuilder, TakerKind, TapePrint, Trade, TradeFees, TradeList,
+};
 pub use crate::orders::DEFAULT_RESERVE_REPLENISH_AMOUNT;
 pub use crate::orders::PegReferenceType;
-pub use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
-pub use crate::price_level::{OrderQueue, PriceLevel, PriceLevelData, PriceLevelSnapshot};
-pub use crate::utils::{Price, Quantity, TimestampMs, UuidGenerator, setup_logger};
+pub use crate::orders::{
+    Hash32, Id, OrderFillState, OrderKey, OrderReplacement, OrderStatus, OrderStatusTransition,
+    OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome,
+};
+#[cfg(feature = "std")]
+pub use crate::price_level::{
+    AlertCondition, AmendRecord, AuctionBook, AuctionEquilibrium, AuctionPhase, BandAction,
+    BandWidth, BookAnalytics, BookSnapshot, BookSnapshotPackage, BoundedAdd, BoundedLevel,
+    ChecksumAlgorithm, ConflatedUpdate, Conflator, CrossingRule, DepthLadder, DepthRung,
+    DeterministicReplay, ExpiryWarning, HaltPolicy, HiddenPriority, LatencyBucket,
+    LatencyHistogram, LatencyOperation, LevelFork, LevelLimits, MatchPlan, MatchingPolicy,
+    MboRecord, MbpRecord, MultiBook, OrderAgeHistogram, OrderAgeOutcome, OrderQueue,
+    OverflowPolicy, OverflowStats, PlannedFill, PriceBand, PriceLevel, PriceLevelData,
+    PriceLevelSnapshot, PriceLevelView, PriorityTiers, PrunePolicy, QuarantineReason,
+    QuarantinedOrder, QueueAudit, QueuePosition, RateLimitedOperation, RateLimiter,
+    ReservationGuard, Sequenced, SessionCancellation, SessionTag, SessionTagged, SnapshotKey,
+    SnapshotSchedule, SnapshotScheduler, SnapshotStore, StatisticsAlert, StatisticsAnalytics,
+    StatisticsCsvWriter, StatisticsWindow, SymbolKey, SymbolSpec, SymbolStatistics, TierTable,
+    TokenBucketRateLimiter, UncrossResult,
+};
+pub use crate::utils::{
+    Clock, ExecutionIdGenerator, LogicalClock, ManualClock, OrderIdGenerator, Price, Quantity,
+    ScaledPrice, SequentialIdGenerator, SequentialOrderIdGenerator, TimestampMs, UuidGenerator,
+};
+#[cfg(feature = "std")]
+pub use crate::utils::{SnowflakeGenerator, SystemClock, UuidV7Generator, setup_logger};
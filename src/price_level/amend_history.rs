@@ -0,0 +1,94 @@
+use crate::orders::{Id, OrderUpdate};
+use crate::utils::TimestampMs;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Number of updates kept per order by
+/// [`PriceLevel::enable_amend_history`](crate::PriceLevel::enable_amend_history)
+/// when no other capacity is given.
+pub const DEFAULT_AMEND_HISTORY_CAPACITY: usize = 32;
+
+/// One update applied to a resting order, as recorded by a level's amend
+/// history (see [`PriceLevel::order_history`](crate::PriceLevel::order_history)).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AmendRecord {
+    /// The update exactly as it was applied; its
+    /// [`OrderUpdate::order_id`] names the order.
+    pub update: OrderUpdate,
+    /// The level clock's reading when the update committed.
+    pub timestamp: TimestampMs,
+    /// The [`PriceLevel::mutation_sequence`](crate::PriceLevel::mutation_sequence)
+    /// the update committed at.
+    pub sequence: u64,
+}
+
+/// The amend history of one price level: a bounded ring of records per
+/// order. Configuration plus an audit trail: never cloned or snapshotted.
+#[derive(Debug, Default)]
+pub(crate) struct AmendHistory {
+    /// Records kept per order; `0` while recording is off.
+    capacity: AtomicUsize,
+    records: DashMap<Id, VecDeque<AmendRecord>>,
+}
+
+impl AmendHistory {
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Start recording `capacity` updates per order, trimming the rings
+    /// already kept; `0` stops recording and drops them.
+    pub(crate) fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+        if capacity == 0 {
+            self.records.clear();
+            return;
+        }
+        for mut ring in self.records.iter_mut() {
+            while ring.len() > capacity {
+                ring.pop_front();
+            }
+        }
+    }
+
+    /// Append `record` to its order's ring, evicting the oldest record of a
+    /// full ring. A no-op while recording is off.
+    pub(crate) fn record(&self, record: AmendRecord) {
+        let capacity = self.capacity();
+        if capacity == 0 {
+            return;
+        }
+        let mut ring = self.records.entry(record.update.order_id()).or_default();
+        if ring.len() >= capacity {
+            ring.pop_front();
+        }
+        ring.push_back(record);
+    }
+
+    pub(crate) fn order(&self, order_id: Id) -> Vec<AmendRecord> {
+        self.records
+            .get(&order_id)
+            .map(|ring| ring.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Every kept record, oldest sequence first.
+    pub(crate) fn all(&self) -> Vec<AmendRecord> {
+        let mut records: Vec<AmendRecord> = self
+            .records
+            .iter()
+            .flat_map(|ring| ring.iter().copied().collect::<Vec<_>>())
+            .collect();
+        records.sort_by_key(|record| record.sequence);
+        records
+    }
+
+    /// Drop every kept record, returning them oldest sequence first.
+    pub(crate) fn take(&self) -> Vec<AmendRecord> {
+        let records = self.all();
+        self.records.clear();
+        records
+    }
+}
@@ -0,0 +1,368 @@
+//! Zero-copy archive of a price level (feature `rkyv`).
+//!
+//! [`PriceLevelData::to_archive_bytes`] and
+//! [`PriceLevelSnapshotPackage::to_archive_bytes`] write a level as an rkyv
+//! archive. [`PriceLevelArchive::access`] validates those bytes once and then
+//! answers totals and order lookups straight from the buffer — typically a
+//! memory-mapped file — without deserializing the level.
+//!
+//! Each order keeps its id, side and quantities in archived form for queries,
+//! plus its JSON encoding, decoded only on [`ArchivedOrder::to_order`]. The
+//! archive is a read-only query format: it carries a package's version and
+//! checksum for provenance, but not its statistics, so restore from the
+//! checksummed package itself.
+
+use crate::errors::PriceLevelError;
+use crate::orders::{Id, OrderType, Side};
+use crate::price_level::checksum::ChecksumAlgorithm;
+use crate::price_level::level::PriceLevelData;
+use crate::price_level::snapshot::PriceLevelSnapshotPackage;
+use crate::utils::{Price, Quantity, TimestampMs};
+use rkyv::Archive;
+use rkyv::rancor::Error as RkyvError;
+use rkyv::util::AlignedVec;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::str::FromStr;
+
+/// Archived form of a level. Field order is the archive layout.
+#[derive(Archive, rkyv::Serialize)]
+struct LevelRecord {
+    price: u128,
+    visible_quantity: u64,
+    hidden_quantity: u64,
+    /// Format version of the package archived, `None` for `PriceLevelData`.
+    package_version: Option<u32>,
+    /// Checksum algorithm and checksum of the package archived.
+    checksum: Option<(String, String)>,
+    /// Orders in queue (FIFO) order.
+    orders: Vec<OrderRecord>,
+    /// Positions into `orders`, sorted by order id, for binary-search lookup.
+    index: Vec<u32>,
+}
+
+#[derive(Archive, rkyv::Serialize)]
+struct OrderRecord {
+    /// The order id's string form; the lookup key.
+    id: String,
+    is_buy: bool,
+    visible_quantity: u64,
+    hidden_quantity: u64,
+    timestamp: u64,
+    /// The full order as JSON, decoded on demand.
+    order_json: String,
+}
+
+impl LevelRecord {
+    fn new<'a, T, I>(
+        price: u128,
+        visible_quantity: u64,
+        hidden_quantity: u64,
+        orders: I,
+    ) -> Result<Self, PriceLevelError>
+    where
+        T: Clone + Serialize + 'a,
+        I: IntoIterator<Item = &'a OrderType<T>>,
+    {
+        let orders = orders
+            .into_iter()
+            .map(OrderRecord::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut index = (0..orders.len())
+            .map(|position| {
+                u32::try_from(position).map_err(|_| PriceLevelError::InvalidOperation {
+                    message: "too many orders to archive".to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        index.sort_by(|a, b| orders[*a as usize].id.cmp(&orders[*b as usize].id));
+        Ok(Self {
+            price,
+            visible_quantity,
+            hidden_quantity,
+            package_version: None,
+            checksum: None,
+            orders,
+            index,
+        })
+    }
+
+    fn to_bytes(&self) -> Result<AlignedVec, PriceLevelError> {
+        rkyv::to_bytes::<RkyvError>(self).map_err(PriceLevelError::serialization)
+    }
+}
+
+impl OrderRecord {
+    fn new<T: Clone + Serialize>(order: &OrderType<T>) -> Result<Self, PriceLevelError> {
+        let order_json = serde_json::to_string(order).map_err(PriceLevelError::serialization)?;
+        Ok(Self {
+            id: order.id_ref().to_string(),
+            is_buy: order.side() == Side::Buy,
+            visible_quantity: order.visible_quantity().as_u64(),
+            hidden_quantity: order.hidden_quantity().as_u64(),
+            timestamp: order.timestamp().as_u64(),
+            order_json,
+        })
+    }
+}
+
+impl<T: Clone + Serialize> PriceLevelData<T> {
+    /// Writes the level as an rkyv archive readable with
+    /// [`PriceLevelArchive::access`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if an order cannot be
+    /// encoded, and [`PriceLevelError::InvalidOperation`] if the level holds
+    /// more than `u32::MAX` orders.
+    pub fn to_archive_bytes(&self) -> Result<AlignedVec, PriceLevelError> {
+        LevelRecord::new(
+            self.price,
+            self.visible_quantity,
+            self.hidden_quantity,
+            &self.orders,
+        )?
+        .to_bytes()
+    }
+}
+
+impl<T: Clone + Serialize> PriceLevelSnapshotPackage<T> {
+    /// Writes the package's snapshot as an rkyv archive readable with
+    /// [`PriceLevelArchive::access`], recording the package version and
+    /// checksum. The checksum is not re-validated; call [`Self::validate`]
+    /// first if the package came from untrusted storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if an order cannot be
+    /// encoded, and [`PriceLevelError::InvalidOperation`] if the snapshot holds
+    /// more than `u32::MAX` orders.
+    pub fn to_archive_bytes(&self) -> Result<AlignedVec, PriceLevelError> {
+        let snapshot = self.snapshot();
+        let mut record = LevelRecord::new(
+            snapshot.price().as_u128(),
+            snapshot.visible_quantity().as_u64(),
+            snapshot.hidden_quantity().as_u64(),
+            snapshot.iter_orders().map(AsRef::as_ref),
+        )?;
+        record.package_version = Some(self.version());
+        record.checksum = Some((
+            self.checksum_algorithm().to_string(),
+            self.checksum().to_string(),
+        ));
+        record.to_bytes()
+    }
+}
+
+/// A validated, zero-copy view of an archive written by
+/// [`PriceLevelData::to_archive_bytes`] or
+/// [`PriceLevelSnapshotPackage::to_archive_bytes`].
+///
+/// Every query reads the borrowed buffer in place; order lookup is a binary
+/// search over an id index stored in the archive.
+#[derive(Clone, Copy)]
+pub struct PriceLevelArchive<'a> {
+    record: &'a ArchivedLevelRecord,
+}
+
+impl<'a> PriceLevelArchive<'a> {
+    /// Validates `bytes` as a level archive and returns a view over them.
+    ///
+    /// Validation walks the archive once; queries after that are free of
+    /// further checks. `bytes` must be 16-byte aligned, as the buffer returned
+    /// by `to_archive_bytes` and a memory-mapped file are.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::DeserializationError`] if `bytes` are not a
+    /// well-formed level archive or are misaligned.
+    pub fn access(bytes: &'a [u8]) -> Result<Self, PriceLevelError> {
+        let record = rkyv::access::<ArchivedLevelRecord, RkyvError>(bytes)
+            .map_err(PriceLevelError::deserialization)?;
+        Ok(Self { record })
+    }
+
+    /// Get the level's price.
+    #[must_use]
+    pub fn price(&self) -> Price {
+        Price::new(self.record.price.to_native())
+    }
+
+    /// Get the level's recorded visible quantity.
+    #[must_use]
+    pub fn visible_quantity(&self) -> Quantity {
+        Quantity::new(self.record.visible_quantity.to_native())
+    }
+
+    /// Get the level's recorded hidden quantity.
+    #[must_use]
+    pub fn hidden_quantity(&self) -> Quantity {
+        Quantity::new(self.record.hidden_quantity.to_native())
+    }
+
+    /// Get the total quantity (visible + hidden).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `visible + hidden`
+    /// overflows `u64`.
+    pub fn total_quantity(&self) -> Result<Quantity, PriceLevelError> {
+        self.visible_quantity()
+            .as_u64()
+            .checked_add(self.hidden_quantity().as_u64())
+            .map(Quantity::new)
+            .ok_or_else(|| PriceLevelError::InvalidOperation {
+                message: "archive total quantity overflow".to_string(),
+            })
+    }
+
+    /// Get the number of orders in the archive.
+    #[must_use]
+    pub fn order_count(&self) -> usize {
+        self.record.orders.len()
+    }
+
+    /// Get the format version of the archived package, or `None` for an
+    /// archive of [`PriceLevelData`].
+    #[must_use]
+    pub fn package_version(&self) -> Option<u32> {
+        self.record
+            .package_version
+            .as_ref()
+            .map(|version| version.to_native())
+    }
+
+    /// Get the checksum algorithm and checksum of the archived package, or
+    /// `None` for an archive of [`PriceLevelData`].
+    #[must_use]
+    pub fn package_checksum(&self) -> Option<(ChecksumAlgorithm, &'a str)> {
+        let (algorithm, checksum) = self
+            .record
+            .checksum
+            .as_ref()
+            .map(|pair| (&pair.0, &pair.1))?;
+        let algorithm = ChecksumAlgorithm::from_str(algorithm.as_str()).ok()?;
+        Some((algorithm, checksum.as_str()))
+    }
+
+    /// Iterate over the orders in queue (FIFO) order.
+    pub fn orders(&self) -> impl Iterator<Item = ArchivedOrder<'a>> + 'a {
+        self.record
+            .orders
+            .iter()
+            .map(|record| ArchivedOrder { record })
+    }
+
+    /// Get the order at `position` in queue order.
+    #[must_use]
+    pub fn order_at(&self, position: usize) -> Option<ArchivedOrder<'a>> {
+        self.record
+            .orders
+            .get(position)
+            .map(|record| ArchivedOrder { record })
+    }
+
+    /// Find an order by id without scanning the queue.
+    #[must_use]
+    pub fn find_order(&self, id: Id) -> Option<ArchivedOrder<'a>> {
+        let key = id.to_string();
+        let orders = &self.record.orders;
+        let index = &self.record.index;
+        let slot = index
+            .binary_search_by(|position| {
+                orders[position.to_native() as usize]
+                    .id
+                    .as_str()
+                    .cmp(key.as_str())
+            })
+            .ok()?;
+        self.order_at(index[slot].to_native() as usize)
+    }
+}
+
+impl fmt::Debug for PriceLevelArchive<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriceLevelArchive")
+            .field("price", &self.price())
+            .field("order_count", &self.order_count())
+            .finish_non_exhaustive()
+    }
+}
+
+/// One order of a [`PriceLevelArchive`], read in place.
+#[derive(Clone, Copy)]
+pub struct ArchivedOrder<'a> {
+    record: &'a ArchivedOrderRecord,
+}
+
+impl<'a> ArchivedOrder<'a> {
+    /// Get the order id in its string form.
+    #[must_use]
+    pub fn id_str(&self) -> &'a str {
+        self.record.id.as_str()
+    }
+
+    /// Get the order id.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidFieldValue`] if the archived id does
+    /// not parse.
+    pub fn id(&self) -> Result<Id, PriceLevelError> {
+        Id::from_str(self.id_str()).map_err(|_| PriceLevelError::InvalidFieldValue {
+            field: "id".to_string(),
+            value: self.id_str().to_string(),
+        })
+    }
+
+    /// Get the order's side.
+    #[must_use]
+    pub fn side(&self) -> Side {
+        if self.record.is_buy {
+            Side::Buy
+        } else {
+            Side::Sell
+        }
+    }
+
+    /// Get the order's visible quantity.
+    #[must_use]
+    pub fn visible_quantity(&self) -> Quantity {
+        Quantity::new(self.record.visible_quantity.to_native())
+    }
+
+    /// Get the order's hidden quantity.
+    #[must_use]
+    pub fn hidden_quantity(&self) -> Quantity {
+        Quantity::new(self.record.hidden_quantity.to_native())
+    }
+
+    /// Get the order's timestamp.
+    #[must_use]
+    pub fn timestamp(&self) -> TimestampMs {
+        TimestampMs::new(self.record.timestamp.to_native())
+    }
+
+    /// Decode the full order. This is the only query that deserializes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::DeserializationError`] if the archived order
+    /// does not decode as an `OrderType<T>`.
+    pub fn to_order<T: DeserializeOwned>(&self) -> Result<OrderType<T>, PriceLevelError> {
+        serde_json::from_str(self.record.order_json.as_str())
+            .map_err(PriceLevelError::deserialization)
+    }
+}
+
+impl fmt::Debug for ArchivedOrder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivedOrder")
+            .field("id", &self.id_str())
+            .field("side", &self.side())
+            .field("visible_quantity", &self.visible_quantity())
+            .field("hidden_quantity", &self.hidden_quantity())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,500 @@
+use crate::errors::PriceLevelError;
+use crate::execution::Trade;
+use crate::orders::{Id, OrderType, Side};
+use crate::price_level::config::PriceLevelConfig;
+use crate::price_level::halt::HaltPolicy;
+use crate::price_level::level::PriceLevel;
+use crate::utils::{ExecutionIdGenerator, Price, Quantity, SequentialIdGenerator, TimestampMs};
+use crossbeam_skiplist::SkipMap;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+/// The taker id the uncross sweeps each level with; never a resting order's.
+const AUCTION_TAKER: Id = Id::Uuid(Uuid::nil());
+
+/// The trading phase of an [`AuctionBook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionPhase {
+    /// Orders accumulate without matching, so the book may cross.
+    Call,
+    /// The book is uncrossed and trades continuously through its levels.
+    Continuous,
+}
+
+impl fmt::Display for AuctionPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Call => write!(f, "CALL"),
+            Self::Continuous => write!(f, "CONTINUOUS"),
+        }
+    }
+}
+
+/// The price an auction uncrosses at and what executes there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AuctionEquilibrium {
+    /// The equilibrium price.
+    pub price: u128,
+    /// Quantity that executes at `price`.
+    pub volume: u64,
+    /// Buy quantity at or above `price` left unexecuted.
+    pub buy_surplus: u64,
+    /// Sell quantity at or below `price` left unexecuted.
+    pub sell_surplus: u64,
+}
+
+impl AuctionEquilibrium {
+    /// The volume-maximizing price for bid depth `bids` and ask depth `asks`,
+    /// each a `(price, quantity)` per level, or `None` if the book does not
+    /// cross.
+    ///
+    /// Candidates are the level prices of both sides. The price executing
+    /// the most wins; a tie goes to the smallest surplus, then by market
+    /// pressure to the highest price if every tied candidate leaves buyers
+    /// over, and otherwise to the lowest.
+    #[must_use]
+    pub fn compute(bids: &[(u128, u64)], asks: &[(u128, u64)]) -> Option<Self> {
+        let sum = |depth: &[(u128, u64)], keep: &dyn Fn(u128) -> bool| {
+            depth
+                .iter()
+                .filter(|(price, _)| keep(*price))
+                .fold(0_u64, |total, (_, quantity)| {
+                    total.saturating_add(*quantity)
+                })
+        };
+        let mut prices: Vec<u128> = bids.iter().chain(asks).map(|(price, _)| *price).collect();
+        prices.sort_unstable();
+        prices.dedup();
+
+        let candidates: Vec<Self> = prices
+            .into_iter()
+            .filter_map(|price| {
+                let buy = sum(bids, &|bid| bid >= price);
+                let sell = sum(asks, &|ask| ask <= price);
+                let volume = buy.min(sell);
+                (volume > 0).then_some(Self {
+                    price,
+                    volume,
+                    buy_surplus: buy - volume,
+                    sell_surplus: sell - volume,
+                })
+            })
+            .collect();
+        let volume = candidates.iter().map(|c| c.volume).max()?;
+        let surplus = |c: &Self| c.buy_surplus.max(c.sell_surplus);
+        let imbalance = candidates
+            .iter()
+            .filter(|c| c.volume == volume)
+            .map(surplus)
+            .min()?;
+        let tied: Vec<Self> = candidates
+            .into_iter()
+            .filter(|c| c.volume == volume && surplus(c) == imbalance)
+            .collect();
+        // Ascending by price, as the candidates were built.
+        if tied.iter().all(|c| c.buy_surplus > 0) {
+            tied.last().copied()
+        } else {
+            tied.first().copied()
+        }
+    }
+}
+
+/// What [`AuctionBook::uncross`] executed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UncrossResult<T = ()> {
+    /// The equilibrium the book uncrossed at, or `None` if it did not cross
+    /// and nothing traded.
+    pub equilibrium: Option<AuctionEquilibrium>,
+    /// The auction trades, all at the equilibrium price, pairing buy and sell
+    /// orders in priority order: bids from the highest price and asks from
+    /// the lowest, each level in its own allocation order. An auction trade
+    /// has no aggressor; the buy order is reported as the taker and the sell
+    /// order as the maker, whose extra fields the trade carries.
+    pub trades: Vec<Trade<T>>,
+}
+
+/// One maker fill of an uncross sweep, before it is paired with the other
+/// side.
+pub(crate) struct AuctionFill<T> {
+    pub(crate) order_id: Id,
+    pub(crate) quantity: u64,
+    pub(crate) extra_fields: T,
+}
+
+/// A two-sided book for an opening or closing auction: orders accumulate in
+/// a call phase without matching, then [`Self::uncross`] executes everything
+/// that crosses at one equilibrium price and opens continuous trading.
+///
+/// Bids and asks are kept in separate [`PriceLevel`]s built from one
+/// [`PriceLevelConfig`], so a crossed call book can hold both sides at one
+/// price. During the call every level is frozen under [`HaltPolicy::new`]:
+/// it accepts adds, cancels and amends but refuses takers. Once uncrossed
+/// the levels trade as usual; takers are matched on them directly, reached
+/// with [`Self::level`], and [`Self::add_order`] refuses an order that would
+/// cross the book. [`Self::start_call`] freezes the book again for a closing
+/// auction.
+///
+/// Book messages — [`Self::add_order`], [`Self::uncross`] and
+/// [`Self::start_call`] — run one at a time. Orders should be added through
+/// the book: one added to a level directly is not checked for crossing.
+///
+/// ```rust
+/// use pricelevel::{
+///     AuctionBook, AuctionPhase, Hash32, Id, OrderType, Price, PriceLevelConfig, Quantity,
+///     SequentialIdGenerator, Side, TimeInForce, TimestampMs,
+/// };
+///
+/// let order = |id: u64, price: u128, quantity: u64, side: Side| OrderType::Standard {
+///     id: Id::from_u64(id),
+///     price: Price::new(price),
+///     quantity: Quantity::new(quantity),
+///     side,
+///     user_id: Hash32::zero(),
+///     timestamp: TimestampMs::new(id),
+///     time_in_force: TimeInForce::Gtc,
+///     extra_fields: (),
+/// };
+///
+/// let book = AuctionBook::<()>::new(PriceLevelConfig::new());
+/// book.add_order(order(1, 101, 10, Side::Buy)).unwrap();
+/// book.add_order(order(2, 99, 6, Side::Sell)).unwrap();
+/// book.add_order(order(3, 100, 6, Side::Sell)).unwrap();
+/// assert_eq!(book.indicative().unwrap().price, 100);
+///
+/// let result = book
+///     .uncross(TimestampMs::new(1_000), &SequentialIdGenerator::new(0))
+///     .unwrap();
+/// assert_eq!(result.equilibrium.unwrap().volume, 10);
+/// assert_eq!(result.trades.len(), 2);
+/// assert_eq!(book.phase(), AuctionPhase::Continuous);
+/// assert_eq!(book.best_ask(), Some(100));
+/// ```
+#[derive(Debug)]
+pub struct AuctionBook<T = ()> {
+    config: PriceLevelConfig,
+    bids: SkipMap<u128, Arc<PriceLevel<T>>>,
+    asks: SkipMap<u128, Arc<PriceLevel<T>>>,
+    /// The phase, locked by every book message.
+    phase: Mutex<AuctionPhase>,
+}
+
+impl<T: Clone> AuctionBook<T> {
+    /// Creates an empty book in the call phase, whose levels will be built
+    /// from `config`.
+    #[must_use]
+    pub fn new(config: PriceLevelConfig) -> Self {
+        Self {
+            config,
+            bids: SkipMap::new(),
+            asks: SkipMap::new(),
+            phase: Mutex::new(AuctionPhase::Call),
+        }
+    }
+
+    /// The book's trading phase.
+    #[must_use]
+    pub fn phase(&self) -> AuctionPhase {
+        *self.lock_phase()
+    }
+
+    /// Routes `order` to the level of its side at its price, creating the
+    /// level on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] in the continuous phase
+    /// if `order` would cross the best price of the other side, and any
+    /// error [`PriceLevel::add_order`] returns.
+    pub fn add_order(&self, order: OrderType<T>) -> Result<Arc<OrderType<T>>, PriceLevelError> {
+        let phase = self.lock_phase();
+        let price = order.price().as_u128();
+        let side = order.side();
+        if *phase == AuctionPhase::Continuous {
+            let crosses = match side {
+                Side::Buy => self.best_ask().is_some_and(|ask| ask <= price),
+                Side::Sell => self.best_bid().is_some_and(|bid| bid >= price),
+            };
+            if crosses {
+                return Err(PriceLevelError::InvalidOperation {
+                    message: format!(
+                        "order {} at {price} would cross the book; match it first",
+                        order.id()
+                    ),
+                });
+            }
+        }
+        let level = self
+            .side(side)
+            .get_or_insert_with(price, || {
+                let level = PriceLevel::with_config(price, self.config.clone());
+                if *phase == AuctionPhase::Call {
+                    level.freeze();
+                }
+                Arc::new(level)
+            })
+            .value()
+            .clone();
+        level.add_order(order)
+    }
+
+    /// Returns the level of `side` at `price`, or `None` if there is none.
+    #[must_use]
+    pub fn level(&self, side: Side, price: u128) -> Option<Arc<PriceLevel<T>>> {
+        self.side(side)
+            .get(&price)
+            .map(|entry| entry.value().clone())
+    }
+
+    /// Returns the levels of `side` in priority order: bids from the
+    /// highest price, asks from the lowest.
+    #[must_use]
+    pub fn levels(&self, side: Side) -> Vec<Arc<PriceLevel<T>>> {
+        let levels = self.side(side).iter().map(|entry| entry.value().clone());
+        match side {
+            Side::Buy => levels.rev().collect(),
+            Side::Sell => levels.collect(),
+        }
+    }
+
+    /// The highest bid price with resting quantity.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<u128> {
+        self.levels(Side::Buy)
+            .iter()
+            .find(|level| level.total_quantity().is_ok_and(|quantity| quantity > 0))
+            .map(|level| level.price())
+    }
+
+    /// The lowest ask price with resting quantity.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<u128> {
+        self.levels(Side::Sell)
+            .iter()
+            .find(|level| level.total_quantity().is_ok_and(|quantity| quantity > 0))
+            .map(|level| level.price())
+    }
+
+    /// The equilibrium [`Self::uncross`] would execute at now, read from
+    /// the configuration's clock, for publishing an indicative price during
+    /// the call; `None` if the book does not cross.
+    #[must_use]
+    pub fn indicative(&self) -> Option<AuctionEquilibrium> {
+        let timestamp = self.config.clock().now();
+        AuctionEquilibrium::compute(
+            &Self::depth(&self.levels(Side::Buy), timestamp),
+            &Self::depth(&self.levels(Side::Sell), timestamp),
+        )
+    }
+
+    /// End the call: execute everything that crosses at the equilibrium
+    /// price, then open continuous trading.
+    ///
+    /// Each level is closed to orders while the equilibrium is computed and
+    /// swept, so the depth priced is the depth executed. Each side is swept
+    /// level by level in priority order, each level in its own allocation
+    /// order, and the fills are paired into [`UncrossResult::trades`]
+    /// stamped with `timestamp` and ids from `trade_id_generator`. The
+    /// levels' statistics and subscribers see their sweeps at the level
+    /// price. The levels are then unfrozen, drained ones included, leaving
+    /// a book that does not cross.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the book is not in
+    /// the call phase, or if the two sides swept different quantities. The
+    /// fills are then reported by neither side: the levels stay frozen and
+    /// the book stays in the call phase, so nothing trades on it until it is
+    /// inspected.
+    pub fn uncross<G>(
+        &self,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> Result<UncrossResult<T>, PriceLevelError>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        let mut phase = self.lock_phase();
+        if *phase != AuctionPhase::Call {
+            return Err(PriceLevelError::InvalidOperation {
+                message: "uncross outside the call phase".to_string(),
+            });
+        }
+        let bids = self.levels(Side::Buy);
+        let asks = self.levels(Side::Sell);
+        for level in bids.iter().chain(&asks) {
+            level.freeze_with(HaltPolicy::closed());
+        }
+        let bid_depth = Self::depth(&bids, timestamp);
+        let ask_depth = Self::depth(&asks, timestamp);
+        let equilibrium = AuctionEquilibrium::compute(&bid_depth, &ask_depth);
+
+        let mut trades = Vec::new();
+        if let Some(equilibrium) = equilibrium {
+            let price = equilibrium.price;
+            let buys = Self::fill(&bids, &bid_depth, equilibrium.volume, timestamp, |bid| {
+                bid >= price
+            });
+            let sells = Self::fill(&asks, &ask_depth, equilibrium.volume, timestamp, |ask| {
+                ask <= price
+            });
+            trades = Self::pair(buys, sells, price, timestamp, trade_id_generator).inspect_err(
+                |err| {
+                    tracing::error!(
+                        volume = equilibrium.volume,
+                        error = %err,
+                        "auction uncross aborted; the book stays frozen in the call phase"
+                    );
+                },
+            )?;
+        }
+
+        for level in bids.iter().chain(&asks) {
+            level.unfreeze();
+        }
+        *phase = AuctionPhase::Continuous;
+        Ok(UncrossResult {
+            equilibrium,
+            trades,
+        })
+    }
+
+    /// Return to the call phase for a closing auction: every level is frozen
+    /// under [`HaltPolicy::new`] and orders accumulate until the next
+    /// [`Self::uncross`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the book is already
+    /// in the call phase.
+    pub fn start_call(&self) -> Result<(), PriceLevelError> {
+        let mut phase = self.lock_phase();
+        if *phase == AuctionPhase::Call {
+            return Err(PriceLevelError::InvalidOperation {
+                message: "the auction call has already started".to_string(),
+            });
+        }
+        for level in self
+            .levels(Side::Buy)
+            .iter()
+            .chain(&self.levels(Side::Sell))
+        {
+            level.freeze();
+        }
+        *phase = AuctionPhase::Call;
+        Ok(())
+    }
+
+    fn side(&self, side: Side) -> &SkipMap<u128, Arc<PriceLevel<T>>> {
+        match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        }
+    }
+
+    fn lock_phase(&self) -> MutexGuard<'_, AuctionPhase> {
+        self.phase.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn depth(levels: &[Arc<PriceLevel<T>>], timestamp: TimestampMs) -> Vec<(u128, u64)> {
+        levels
+            .iter()
+            .map(|level| (level.price(), level.auction_depth(AUCTION_TAKER, timestamp)))
+            .collect()
+    }
+
+    /// Sweep up to `volume` from the `levels` whose price `crosses`, in
+    /// priority order, returning the maker fills.
+    fn fill(
+        levels: &[Arc<PriceLevel<T>>],
+        depth: &[(u128, u64)],
+        volume: u64,
+        timestamp: TimestampMs,
+        crosses: impl Fn(u128) -> bool,
+    ) -> Vec<AuctionFill<T>> {
+        // The level trades are not reported, so their ids are throwaway.
+        let ids = SequentialIdGenerator::new(0);
+        let mut remaining = volume;
+        let mut fills = Vec::new();
+        for (level, &(price, available)) in levels.iter().zip(depth) {
+            if remaining == 0 || !crosses(price) {
+                break;
+            }
+            let quantity = remaining.min(available);
+            if quantity == 0 {
+                continue;
+            }
+            let result = level.auction_fill(quantity, AUCTION_TAKER, timestamp, &ids);
+            for trade in result.trades().as_slice() {
+                let quantity = trade.quantity().as_u64();
+                remaining = remaining.saturating_sub(quantity);
+                fills.push(AuctionFill {
+                    order_id: trade.maker_order_id(),
+                    quantity,
+                    extra_fields: trade.maker_extra_fields().clone(),
+                });
+            }
+        }
+        fills
+    }
+
+    /// Pair buy and sell fills, in order, into trades at `price`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`], pairing nothing, if the
+    /// buys and sells do not add up to the same quantity.
+    pub(crate) fn pair<G>(
+        buys: Vec<AuctionFill<T>>,
+        sells: Vec<AuctionFill<T>>,
+        price: u128,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> Result<Vec<Trade<T>>, PriceLevelError>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        let bought: u64 = buys.iter().map(|fill| fill.quantity).sum();
+        let sold: u64 = sells.iter().map(|fill| fill.quantity).sum();
+        if bought != sold {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!(
+                    "auction sides swept unequal quantities: bought {bought}, sold {sold}"
+                ),
+            });
+        }
+        let mut trades = Vec::new();
+        let mut buys = buys.into_iter().peekable();
+        let mut sells = sells.into_iter().peekable();
+        while let (Some(buy), Some(sell)) = (buys.peek_mut(), sells.peek_mut()) {
+            let quantity = buy.quantity.min(sell.quantity);
+            let (trade_id, sequence) = trade_id_generator.next_execution();
+            let trade = Trade::with_timestamp(
+                trade_id,
+                buy.order_id,
+                sell.order_id,
+                Price::new(price),
+                Quantity::new(quantity),
+                Side::Buy,
+                timestamp,
+            )
+            .with_maker_extra_fields(sell.extra_fields.clone());
+            trades.push(match sequence {
+                Some(sequence) => trade.with_execution_sequence(sequence),
+                None => trade,
+            });
+            buy.quantity -= quantity;
+            sell.quantity -= quantity;
+            if buy.quantity == 0 {
+                buys.next();
+            }
+            if sell.quantity == 0 {
+                sells.next();
+            }
+        }
+        Ok(trades)
+    }
+}
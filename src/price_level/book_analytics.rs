@@ -0,0 +1,137 @@
+use crate::errors::PriceLevelError;
+use crate::price_level::depth_ladder::{DepthLadder, DepthRung};
+use crate::price_level::level::PriceLevel;
+use crate::price_level::snapshot::PriceLevelSnapshot;
+use serde::{Deserialize, Serialize};
+
+/// Reference pricing signals of a book's displayed depth, as returned by
+/// [`DepthLadder::analytics`].
+///
+/// A plain, serializable copy, so strategies and dashboards read the same
+/// definitions. Each metric is `None` unless the book shows depth on both
+/// sides. With `q_b` and `q_a` the best bid and ask sizes and `p_b` and `p_a`
+/// their prices:
+///
+/// - `imbalance` is `(q_b - q_a) / (q_b + q_a)`, in `[-1.0, 1.0]`: positive
+///   when the bid is the heavier side.
+/// - `microprice` is `(p_b * q_a + p_a * q_b) / (q_b + q_a)`: the mid leaned
+///   towards the thinner side, where the next trade is likelier to move the
+///   price.
+/// - `depth_weighted_mid` is the midpoint of each side's size-weighted average
+///   price over its best [`depth`](Self::depth) rungs.
+///
+/// ```rust
+/// use pricelevel::{BookAnalytics, DepthLadder, MbpRecord, Price, Quantity, Side};
+///
+/// let level = |price, side, size| MbpRecord {
+///     price: Price::new(price),
+///     side,
+///     size: Quantity::new(size),
+///     order_count: 1,
+/// };
+/// let ladder = DepthLadder::new(&[
+///     level(99, Side::Buy, 30),
+///     level(101, Side::Sell, 10),
+/// ])
+/// .unwrap();
+///
+/// let analytics: BookAnalytics = ladder.analytics(1);
+/// assert_eq!(analytics.mid, Some(100.0));
+/// assert_eq!(analytics.imbalance, Some(0.5));
+/// assert_eq!(analytics.microprice, Some(100.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct BookAnalytics {
+    /// Rungs per side the depth-weighted mid averages over.
+    pub depth: usize,
+    /// Midpoint of the best bid and ask.
+    pub mid: Option<f64>,
+    /// Top-of-book size imbalance, in `[-1.0, 1.0]`.
+    pub imbalance: Option<f64>,
+    /// Size-weighted microprice of the best bid and ask.
+    pub microprice: Option<f64>,
+    /// Midpoint of the size-weighted average prices of each side's best
+    /// `depth` rungs; also `None` for a `depth` of zero.
+    pub depth_weighted_mid: Option<f64>,
+}
+
+impl BookAnalytics {
+    /// The analytics of `ladder`, averaging the depth-weighted mid over its
+    /// best `depth` rungs per side. A ladder with a tick above one yields
+    /// them over its buckets rather than the exact prices.
+    #[must_use]
+    pub fn from_ladder(ladder: &DepthLadder, depth: usize) -> Self {
+        let (Some(bid), Some(ask)) = (ladder.bids().first(), ladder.asks().first()) else {
+            return Self {
+                depth,
+                ..Self::default()
+            };
+        };
+        let (bid_price, ask_price) = (bid.price.as_u128() as f64, ask.price.as_u128() as f64);
+        let (bid_size, ask_size) = (bid.size.as_u64() as f64, ask.size.as_u64() as f64);
+        let top_size = bid_size + ask_size;
+        let depth_weighted_mid = average_price(ladder.bids(), depth)
+            .zip(average_price(ladder.asks(), depth))
+            .map(|(bid, ask)| (bid + ask) / 2.0);
+        Self {
+            depth,
+            mid: Some((bid_price + ask_price) / 2.0),
+            imbalance: Some((bid_size - ask_size) / top_size),
+            microprice: Some((bid_price * ask_size + ask_price * bid_size) / top_size),
+            depth_weighted_mid,
+        }
+    }
+
+    /// The analytics of the displayed depth of `levels`, each contributing
+    /// its [`PriceLevel::mbp_record`]: a level resting buys is a bid, one
+    /// resting sells an ask.
+    ///
+    /// # Errors
+    ///
+    /// As [`DepthLadder::new`], and if a level's displayed size overflows
+    /// `u64`.
+    pub fn from_levels<'a, T, I>(levels: I, depth: usize) -> Result<Self, PriceLevelError>
+    where
+        T: Clone + 'a,
+        I: IntoIterator<Item = &'a PriceLevel<T>>,
+    {
+        let mut records = Vec::new();
+        for level in levels {
+            records.extend(level.mbp_record()?);
+        }
+        Ok(Self::from_ladder(&DepthLadder::new(&records)?, depth))
+    }
+
+    /// The analytics of level snapshots, such as
+    /// [`BookSnapshot::levels`](crate::BookSnapshot::levels).
+    ///
+    /// # Errors
+    ///
+    /// As [`DepthLadder::from_snapshots`].
+    pub fn from_snapshots<T: Clone>(
+        snapshots: &[PriceLevelSnapshot<T>],
+        depth: usize,
+    ) -> Result<Self, PriceLevelError> {
+        Ok(Self::from_ladder(
+            &DepthLadder::from_snapshots(snapshots, 1)?,
+            depth,
+        ))
+    }
+}
+
+/// Size-weighted average price of the best `depth` rungs, or `None` if
+/// there are none.
+fn average_price(rungs: &[DepthRung], depth: usize) -> Option<f64> {
+    let rungs = &rungs[..depth.min(rungs.len())];
+    if rungs.is_empty() {
+        return None;
+    }
+    let (value, size) = rungs.iter().fold((0.0, 0.0), |(value, size), rung| {
+        let quantity = rung.size.as_u64() as f64;
+        (
+            value + rung.price.as_u128() as f64 * quantity,
+            size + quantity,
+        )
+    });
+    Some(value / size)
+}
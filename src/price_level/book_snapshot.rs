@@ -0,0 +1,405 @@
+use crate::errors::PriceLevelError;
+use crate::orders::Side;
+use crate::price_level::checksum::{ChecksumAlgorithm, crc32, push_decimal, push_stripped};
+use crate::price_level::level::PriceLevel;
+use crate::price_level::snapshot::PriceLevelSnapshot;
+use crate::utils::{Price, TimestampMs};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+/// Format version for checksum-enabled book snapshots. New packages are
+/// written at this version.
+///
+/// - **Version 1** is the current shape: the book metadata plus one
+///   [`PriceLevelSnapshot`] per level, each in the current level snapshot
+///   shape (level format version 5).
+pub const BOOK_SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// One side's displayed levels, as `(price, quantity)`.
+type Depth = Vec<(u128, u64)>;
+
+/// A point-in-time snapshot of many price levels of one book, with the
+/// metadata needed to resume it: the instrument symbol, the book's own
+/// sequence number, and the capture timestamp.
+///
+/// Wrap it in a [`BookSnapshotPackage`] to persist it under one checksum and
+/// restore the whole book with a single call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookSnapshot<T = ()> {
+    /// The instrument the book trades.
+    symbol: String,
+    /// The book's sequence number when the snapshot was taken. Opaque to this
+    /// crate: typically the last applied market-data or journal sequence.
+    sequence: u64,
+    /// When the snapshot was taken.
+    timestamp: TimestampMs,
+    /// One snapshot per level, in capture order.
+    levels: Vec<PriceLevelSnapshot<T>>,
+}
+
+impl<T: Clone> BookSnapshot<T> {
+    /// Creates a book snapshot with no levels.
+    #[must_use]
+    pub fn new(symbol: impl Into<String>, sequence: u64, timestamp: TimestampMs) -> Self {
+        Self {
+            symbol: symbol.into(),
+            sequence,
+            timestamp,
+            levels: Vec::new(),
+        }
+    }
+
+    /// Snapshots every level of `levels`, in iteration order.
+    ///
+    /// Each level is snapshotted on its own; a caller needing the levels
+    /// mutually consistent must quiesce the book for the capture.
+    #[must_use]
+    pub fn capture<'a, I>(
+        symbol: impl Into<String>,
+        sequence: u64,
+        timestamp: TimestampMs,
+        levels: I,
+    ) -> Self
+    where
+        T: 'a,
+        I: IntoIterator<Item = &'a PriceLevel<T>>,
+    {
+        let mut snapshot = Self::new(symbol, sequence, timestamp);
+        snapshot.levels = levels.into_iter().map(PriceLevel::snapshot).collect();
+        snapshot
+    }
+
+    /// Appends a level snapshot.
+    #[must_use]
+    pub fn with_level(mut self, level: PriceLevelSnapshot<T>) -> Self {
+        self.levels.push(level);
+        self
+    }
+
+    /// Returns the instrument symbol.
+    #[must_use]
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// Returns the book's sequence number at capture.
+    #[must_use]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Returns the capture timestamp.
+    #[must_use]
+    pub fn timestamp(&self) -> TimestampMs {
+        self.timestamp
+    }
+
+    /// Returns the level snapshots, in capture order.
+    #[must_use]
+    pub fn levels(&self) -> &[PriceLevelSnapshot<T>] {
+        &self.levels
+    }
+
+    /// Returns the snapshot of the level at `price`, if the book has one.
+    #[must_use]
+    pub fn level(&self, price: Price) -> Option<&PriceLevelSnapshot<T>> {
+        self.levels.iter().find(|level| level.price() == price)
+    }
+
+    /// Kraken-style CRC-32 of the book's top `depth` displayed levels each
+    /// side, for a feed handler to check this book against the checksum an
+    /// exchange publishes with its book updates.
+    ///
+    /// The payload concatenates the asks from the lowest price up, then the
+    /// bids from the highest price down, each level as its price then its
+    /// displayed quantity: the digits of the feed's decimal strings with the
+    /// point and leading zeros removed, which for prices in ticks and
+    /// quantities in lots are their integer digits. Levels with nothing
+    /// displayed are skipped. The checksum is CRC-32 (IEEE) of the payload's
+    /// bytes, compared unsigned.
+    #[must_use]
+    pub fn checksum_crc32(&self, depth: usize) -> u32 {
+        let (bids, asks) = self.displayed_depth(depth);
+        let mut payload = String::new();
+        for (price, quantity) in asks.into_iter().chain(bids) {
+            push_stripped(&mut payload, price);
+            push_stripped(&mut payload, u128::from(quantity));
+        }
+        crc32(payload.as_bytes())
+    }
+
+    /// OKX-style CRC-32 of the book's top `depth` displayed levels each
+    /// side, signed as that feed publishes it.
+    ///
+    /// The payload alternates the `i`-th best bid and the `i`-th best ask
+    /// for each `i` below `depth`, each present level as `price:quantity`,
+    /// all joined with `:`. Prices and quantities are in units of
+    /// `10^-price_decimals` and `10^-quantity_decimals`, written as the
+    /// feed's decimal strings without trailing fractional zeros: a price of
+    /// `847698` at two decimals is `8476.98`, and `847600` is `8476`.
+    #[must_use]
+    pub fn checksum_crc32_okx(
+        &self,
+        depth: usize,
+        price_decimals: u32,
+        quantity_decimals: u32,
+    ) -> i32 {
+        let (bids, asks) = self.displayed_depth(depth);
+        let mut payload = String::new();
+        for index in 0..bids.len().max(asks.len()) {
+            for &(price, quantity) in [bids.get(index), asks.get(index)].into_iter().flatten() {
+                if !payload.is_empty() {
+                    payload.push(':');
+                }
+                push_decimal(&mut payload, price, price_decimals);
+                payload.push(':');
+                push_decimal(&mut payload, u128::from(quantity), quantity_decimals);
+            }
+        }
+        // The feed publishes the unsigned CRC reinterpreted as signed.
+        crc32(payload.as_bytes()) as i32
+    }
+
+    /// The top `depth` bid levels from the highest price down and ask
+    /// levels from the lowest price up, as `(price, displayed quantity)`,
+    /// leaving out levels with nothing displayed.
+    fn displayed_depth(&self, depth: usize) -> (Depth, Depth) {
+        let mut bids = Vec::new();
+        let mut asks = Vec::new();
+        for level in &self.levels {
+            let displayed = level.visible_quantity().as_u64();
+            let Some(order) = level.orders().first() else {
+                continue;
+            };
+            if displayed == 0 {
+                continue;
+            }
+            let entry = (level.price().as_u128(), displayed);
+            match order.side() {
+                Side::Buy => bids.push(entry),
+                Side::Sell => asks.push(entry),
+            }
+        }
+        bids.sort_unstable_by_key(|&(price, _)| Reverse(price));
+        asks.sort_unstable_by_key(|&(price, _)| price);
+        bids.truncate(depth);
+        asks.truncate(depth);
+        (bids, asks)
+    }
+
+    /// Restores every level, in capture order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if two levels share a
+    /// price, [`PriceLevelError::DuplicateOrderId`] if an order id appears in
+    /// more than one level, and any error
+    /// [`PriceLevel::from_snapshot`] returns for a single level.
+    pub fn restore(self) -> Result<Vec<PriceLevel<T>>, PriceLevelError> {
+        self.check_book_invariants()?;
+        self.levels
+            .into_iter()
+            .map(PriceLevel::from_snapshot)
+            .collect()
+    }
+
+    /// Checks the invariants that span levels; each level's own invariants
+    /// are left to [`PriceLevel::from_snapshot`].
+    fn check_book_invariants(&self) -> Result<(), PriceLevelError> {
+        let mut prices = HashSet::with_capacity(self.levels.len());
+        let mut order_ids = HashSet::new();
+        for level in &self.levels {
+            if !prices.insert(level.price()) {
+                return Err(PriceLevelError::InvalidOperation {
+                    message: format!("book snapshot repeats price level {}", level.price()),
+                });
+            }
+            // A repeat inside one level is that level's own error; only a
+            // repeat across levels is reported here.
+            let level_ids: HashSet<_> = level.iter_orders().map(|order| order.id()).collect();
+            if let Some(id) = level_ids.iter().find(|id| order_ids.contains(*id)) {
+                return Err(PriceLevelError::DuplicateOrderId { order_id: *id });
+            }
+            order_ids.extend(level_ids);
+        }
+        Ok(())
+    }
+}
+
+/// Serialized representation of a book snapshot with one checksum over every
+/// level and the book metadata.
+///
+/// All fields are private to protect checksum integrity.
+/// Use the provided accessor methods to read package data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookSnapshotPackage<T = ()> {
+    /// Version of the serialized book schema to support future migrations.
+    version: u32,
+    /// Captured book snapshot.
+    snapshot: BookSnapshot<T>,
+    /// Algorithm that produced [`Self::checksum`].
+    #[serde(default)]
+    checksum_algorithm: ChecksumAlgorithm,
+    /// Hex-encoded checksum used to validate the snapshot integrity.
+    checksum: String,
+}
+
+impl<T> BookSnapshotPackage<T> {
+    /// Returns the schema version of this package.
+    #[must_use]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns a reference to the contained book snapshot.
+    #[must_use]
+    pub fn snapshot(&self) -> &BookSnapshot<T> {
+        &self.snapshot
+    }
+
+    /// Returns the hex-encoded checksum.
+    #[must_use]
+    pub fn checksum(&self) -> &str {
+        &self.checksum
+    }
+
+    /// Returns the algorithm the checksum was computed with.
+    #[must_use]
+    pub fn checksum_algorithm(&self) -> ChecksumAlgorithm {
+        self.checksum_algorithm
+    }
+}
+
+impl<T: Clone + Serialize> BookSnapshotPackage<T> {
+    /// Creates a new book package computing the SHA-256 checksum over the
+    /// whole snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if refreshing a level's
+    /// aggregates overflows a quantity, or [`PriceLevelError::SerializationError`]
+    /// if the snapshot cannot be encoded while computing its checksum.
+    pub fn new(snapshot: BookSnapshot<T>) -> Result<Self, PriceLevelError> {
+        Self::with_checksum_algorithm(snapshot, ChecksumAlgorithm::Sha256)
+    }
+
+    /// Creates a new book package whose checksum is computed with
+    /// `algorithm`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::new`].
+    pub fn with_checksum_algorithm(
+        mut snapshot: BookSnapshot<T>,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<Self, PriceLevelError> {
+        for level in &mut snapshot.levels {
+            level.refresh_aggregates()?;
+        }
+
+        let checksum = Self::compute_checksum(&snapshot, algorithm)?;
+
+        Ok(Self {
+            version: BOOK_SNAPSHOT_FORMAT_VERSION,
+            snapshot,
+            checksum_algorithm: algorithm,
+            checksum,
+        })
+    }
+
+    /// Serializes the package to JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if the package cannot be
+    /// encoded to a JSON string.
+    pub fn to_json(&self) -> Result<String, PriceLevelError> {
+        serde_json::to_string(self).map_err(PriceLevelError::serialization)
+    }
+
+    /// Deserializes a package from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::DeserializationError`] if `data` is not a
+    /// valid JSON representation of a book package. The returned package is
+    /// not yet checksum-validated; call [`Self::validate`] or
+    /// [`Self::restore`] to verify integrity.
+    ///
+    /// Decodes within [`ParseLimits::DEFAULT`](crate::parse::ParseLimits::DEFAULT),
+    /// returning [`PriceLevelError::ParseError`] for an input over a limit;
+    /// see [`crate::parse::book_snapshot_package`].
+    pub fn from_json(data: &str) -> Result<Self, PriceLevelError>
+    where
+        T: DeserializeOwned,
+    {
+        crate::parse::book_snapshot_package(data, &crate::parse::ParseLimits::DEFAULT)
+    }
+
+    /// Validates the format version and the checksum over the whole snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the package's format
+    /// version is not [`BOOK_SNAPSHOT_FORMAT_VERSION`],
+    /// [`PriceLevelError::SerializationError`] if the snapshot cannot be
+    /// re-encoded to recompute the checksum, and
+    /// [`PriceLevelError::ChecksumMismatch`] if the recomputed checksum does
+    /// not match the stored one.
+    // Cold path, like the level package's `validate`.
+    #[inline(never)]
+    pub fn validate(&self) -> Result<(), PriceLevelError> {
+        if self.version != BOOK_SNAPSHOT_FORMAT_VERSION {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!(
+                    "Unsupported book snapshot version: {} (expected {})",
+                    self.version, BOOK_SNAPSHOT_FORMAT_VERSION
+                ),
+            });
+        }
+
+        let computed = Self::compute_checksum(&self.snapshot, self.checksum_algorithm)?;
+        if computed != self.checksum {
+            return Err(PriceLevelError::ChecksumMismatch {
+                expected: self.checksum.clone(),
+                actual: computed,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the package after validating it and returns the contained
+    /// book snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::validate`].
+    pub fn into_snapshot(self) -> Result<BookSnapshot<T>, PriceLevelError> {
+        self.validate()?;
+        Ok(self.snapshot)
+    }
+
+    /// Validates the package and restores every level, in capture order —
+    /// whole-book recovery in one call.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::validate`], then those of
+    /// [`BookSnapshot::restore`].
+    pub fn restore(self) -> Result<Vec<PriceLevel<T>>, PriceLevelError> {
+        self.into_snapshot()?.restore()
+    }
+
+    #[inline(never)]
+    fn compute_checksum(
+        snapshot: &BookSnapshot<T>,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<String, PriceLevelError> {
+        let payload = serde_json::to_vec(snapshot).map_err(PriceLevelError::serialization)?;
+
+        Ok(algorithm.digest_hex(&payload))
+    }
+}
@@ -0,0 +1,336 @@
+use crate::errors::{CapacityLimit, PriceLevelError};
+use crate::execution::{MatchResult, TakerKind};
+use crate::orders::{Id, OrderStatus, OrderType, OrderUpdate, TimeInForce, UpdateOutcome};
+use crate::price_level::config::PriceLevelConfig;
+use crate::price_level::halt::HaltPolicy;
+use crate::price_level::level::PriceLevel;
+use crate::utils::{ExecutionIdGenerator, TimestampMs};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// What a [`BoundedLevel`] does with an add beyond its bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Refuse the add with [`PriceLevelError::CapacityExceeded`].
+    #[default]
+    Reject,
+    /// Park the order in the level's overflow store, to rest once the level
+    /// drains below its bound.
+    Spill,
+}
+
+/// Where [`BoundedLevel::add_order`] put an order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoundedAdd<T = ()> {
+    /// The order rests at the level, as returned by
+    /// [`PriceLevel::add_order`].
+    Resting(Arc<OrderType<T>>),
+    /// The order waits in the overflow store, `position` orders from its
+    /// front.
+    Spilled {
+        /// Number of spilled orders ahead of this one.
+        position: usize,
+    },
+}
+
+/// Counters of a [`BoundedLevel`]'s overflow handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct OverflowStats {
+    /// Orders parked in the overflow store.
+    pub spilled: u64,
+    /// Spilled orders since moved onto the level.
+    pub promoted: u64,
+    /// Spilled orders cancelled before they were promoted.
+    pub cancelled: u64,
+    /// Adds refused at the bound under [`OverflowPolicy::Reject`].
+    pub rejected: u64,
+    /// Orders waiting in the overflow store now.
+    pub pending: usize,
+}
+
+/// A price level resting at most a fixed number of orders, for deployments
+/// that must bound a level's memory.
+///
+/// An add beyond the bound is refused or, under [`OverflowPolicy::Spill`],
+/// parked in a secondary overflow store. Spilled orders are neither
+/// displayed nor matched. As matches and cancels through the bounded level
+/// drain it below its bound, the oldest spilled orders are promoted onto
+/// it, in the order they spilled and behind every order already resting.
+/// While anything is spilled, new adds spill too, so an order never rests
+/// ahead of an older spilled one.
+///
+/// The level itself is reachable through [`Self::level`] for reads and for
+/// operations the bounded level does not wrap. It is built with a
+/// [`LevelLimits::with_max_orders`](crate::LevelLimits::with_max_orders) at the bound, so an add made on it
+/// directly is refused rather than overshooting; call [`Self::promote`]
+/// after draining it directly.
+///
+/// ```rust
+/// use pricelevel::{
+///     BoundedAdd, BoundedLevel, Hash32, Id, OrderType, OverflowPolicy, Price, Quantity, Side,
+///     TakerKind, TimeInForce, TimestampMs, SequentialIdGenerator,
+/// };
+///
+/// let bounded: BoundedLevel = BoundedLevel::new(10_000, 1, OverflowPolicy::Spill);
+/// let order = |id| OrderType::Standard {
+///     id: Id::from_u64(id),
+///     price: Price::new(10_000),
+///     quantity: Quantity::new(10),
+///     side: Side::Sell,
+///     user_id: Hash32::zero(),
+///     timestamp: TimestampMs::new(id),
+///     time_in_force: TimeInForce::Gtc,
+///     extra_fields: (),
+/// };
+/// assert!(matches!(bounded.add_order(order(1)), Ok(BoundedAdd::Resting(_))));
+/// assert!(matches!(bounded.add_order(order(2)), Ok(BoundedAdd::Spilled { position: 0 })));
+///
+/// bounded.match_order(
+///     10,
+///     Id::from_u64(99),
+///     TimeInForce::Ioc,
+///     TakerKind::Standard,
+///     TimestampMs::new(3),
+///     &SequentialIdGenerator::new(0),
+/// );
+/// assert_eq!(bounded.level().order_count(), 1);
+/// assert_eq!(bounded.stats().promoted, 1);
+/// ```
+#[derive(Debug)]
+pub struct BoundedLevel<T = ()> {
+    level: PriceLevel<T>,
+    max_orders: usize,
+    policy: OverflowPolicy,
+    /// Spilled orders, oldest first. Adds and promotions take it for their
+    /// whole step, so they serialize against each other.
+    overflow: Mutex<VecDeque<OrderType<T>>>,
+    spilled: AtomicU64,
+    promoted: AtomicU64,
+    cancelled: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl<T: Clone> BoundedLevel<T> {
+    /// A bounded level at `price` resting at most `max_orders` orders, with
+    /// the default configuration.
+    #[must_use]
+    pub fn new(price: u128, max_orders: usize, policy: OverflowPolicy) -> Self {
+        Self::with_config(price, PriceLevelConfig::new(), max_orders, policy)
+    }
+
+    /// A bounded level at `price` built with `config`, resting at most
+    /// `max_orders` orders. A tighter order limit already in `config` wins.
+    #[must_use]
+    pub fn with_config(
+        price: u128,
+        config: PriceLevelConfig,
+        max_orders: usize,
+        policy: OverflowPolicy,
+    ) -> Self {
+        let limits = config.limits().unwrap_or_default();
+        let bound = limits.max_orders().min(max_orders as u64);
+        let config = config.with_limits(limits.with_max_orders(bound));
+        Self {
+            level: PriceLevel::with_config(price, config),
+            max_orders: usize::try_from(bound).unwrap_or(max_orders),
+            policy,
+            overflow: Mutex::new(VecDeque::new()),
+            spilled: AtomicU64::new(0),
+            promoted: AtomicU64::new(0),
+            cancelled: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    /// The underlying level.
+    #[must_use]
+    pub fn level(&self) -> &PriceLevel<T> {
+        &self.level
+    }
+
+    /// The most orders the level rests.
+    #[must_use]
+    pub fn max_orders(&self) -> usize {
+        self.max_orders
+    }
+
+    /// What an add beyond the bound does.
+    #[must_use]
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Whether the next add would be spilled or refused: the level is at its
+    /// bound or orders are already waiting. Advisory while others add.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.level.order_count() >= self.max_orders || !self.lock_overflow().is_empty()
+    }
+
+    /// Number of orders waiting in the overflow store.
+    #[must_use]
+    pub fn overflow_len(&self) -> usize {
+        self.lock_overflow().len()
+    }
+
+    /// The overflow store's counters.
+    #[must_use]
+    pub fn stats(&self) -> OverflowStats {
+        OverflowStats {
+            spilled: self.spilled.load(Ordering::Relaxed),
+            promoted: self.promoted.load(Ordering::Relaxed),
+            cancelled: self.cancelled.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            pending: self.overflow_len(),
+        }
+    }
+
+    /// Rest `order` at the level or, past the bound, spill or refuse it.
+    ///
+    /// A spilled order is validated as [`PriceLevel::add_order`] would
+    /// validate it, so it is refused now rather than on promotion.
+    ///
+    /// # Errors
+    ///
+    /// Returns what [`PriceLevel::add_order`] returns; a
+    /// [`PriceLevelError::DuplicateOrderId`] for an id already spilled; and,
+    /// under [`OverflowPolicy::Reject`], a
+    /// [`PriceLevelError::CapacityExceeded`] for an add past the bound.
+    pub fn add_order(&self, order: OrderType<T>) -> Result<BoundedAdd<T>, PriceLevelError> {
+        let mut overflow = self.lock_overflow();
+        if overflow.is_empty() && self.level.order_count() < self.max_orders {
+            return self.level.add_order(order).map(BoundedAdd::Resting);
+        }
+        if self.policy == OverflowPolicy::Reject {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            return Err(PriceLevelError::CapacityExceeded {
+                limit: CapacityLimit::Orders,
+                max: self.max_orders as u64,
+            });
+        }
+
+        self.level.check_halt(HaltPolicy::allows_adds)?;
+        self.level.check_admission(&order)?;
+        if let Some(front) = overflow.front()
+            && front.side() != order.side()
+        {
+            return Err(PriceLevelError::SideMismatch {
+                expected: front.side(),
+                actual: order.side(),
+            });
+        }
+        let order_id = order.id();
+        if self.level.order_status(order_id).is_some()
+            || overflow.iter().any(|spilled| spilled.id() == order_id)
+        {
+            return Err(PriceLevelError::DuplicateOrderId { order_id });
+        }
+
+        overflow.push_back(order);
+        self.spilled.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(
+            price = self.level.price(),
+            order_id = %order_id,
+            pending = overflow.len(),
+            "bounded level: order spilled to overflow"
+        );
+        Ok(BoundedAdd::Spilled {
+            position: overflow.len() - 1,
+        })
+    }
+
+    /// [`PriceLevel::match_order`], then promote spilled orders into the
+    /// room the match made.
+    pub fn match_order<G>(
+        &self,
+        incoming_quantity: u64,
+        taker_order_id: Id,
+        taker_tif: TimeInForce,
+        taker_kind: TakerKind,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> MatchResult<T>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        let result = self.level.match_order(
+            incoming_quantity,
+            taker_order_id,
+            taker_tif,
+            taker_kind,
+            timestamp,
+            trade_id_generator,
+        );
+        self.promote();
+        result
+    }
+
+    /// [`PriceLevel::update_order`], for an order resting or spilled, then
+    /// promote spilled orders into any room the update made.
+    ///
+    /// A spilled order can only be cancelled: its outcome is
+    /// [`UpdateOutcome::Cancelled`] with [`OrderStatus::New`].
+    ///
+    /// # Errors
+    ///
+    /// Returns what [`PriceLevel::update_order`] returns, and a
+    /// [`PriceLevelError::InvalidOperation`] for an amend of a spilled
+    /// order.
+    pub fn update_order(&self, update: OrderUpdate) -> Result<UpdateOutcome<T>, PriceLevelError> {
+        let order_id = update.order_id();
+        {
+            let mut overflow = self.lock_overflow();
+            if let Some(index) = overflow.iter().position(|order| order.id() == order_id) {
+                if !matches!(update, OrderUpdate::Cancel { .. }) {
+                    return Err(PriceLevelError::InvalidOperation {
+                        message: format!("order {order_id} is spilled and can only be cancelled"),
+                    });
+                }
+                let order = overflow.remove(index).expect("index was just found");
+                self.cancelled.fetch_add(1, Ordering::Relaxed);
+                return Ok(UpdateOutcome::Cancelled(Arc::new(order), OrderStatus::New));
+            }
+        }
+        let outcome = self.level.update_order(update)?;
+        self.promote();
+        Ok(outcome)
+    }
+
+    /// Move the oldest spilled orders onto the level while it is below its
+    /// bound, returning how many moved.
+    ///
+    /// Promotion stops at the first order the level refuses — it is full
+    /// again, frozen against adds, or rate limiting them — leaving that
+    /// order at the front of the overflow store for the next call. An order
+    /// the level can never take, such as one whose id was since added to the
+    /// level directly, holds the store until it is cancelled.
+    pub fn promote(&self) -> usize {
+        let mut overflow = self.lock_overflow();
+        let mut promoted = 0;
+        while self.level.order_count() < self.max_orders {
+            let Some(order) = overflow.pop_front() else {
+                break;
+            };
+            if let Err(err) = self.level.add_order(order.clone()) {
+                tracing::debug!(
+                    price = self.level.price(),
+                    order_id = %order.id(),
+                    error = %err,
+                    "bounded level: promotion refused"
+                );
+                overflow.push_front(order);
+                break;
+            }
+            promoted += 1;
+        }
+        self.promoted.fetch_add(promoted as u64, Ordering::Relaxed);
+        promoted
+    }
+
+    fn lock_overflow(&self) -> MutexGuard<'_, VecDeque<OrderType<T>>> {
+        self.overflow.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,153 @@
+use crate::errors::PriceLevelError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+/// The checksum a [`PriceLevelSnapshotPackage`](crate::PriceLevelSnapshotPackage)
+/// stores over its serialized snapshot.
+///
+/// The algorithm is recorded in the package header, so
+/// [`PriceLevelSnapshotPackage::validate`](crate::PriceLevelSnapshotPackage::validate)
+/// always re-checks a package with the algorithm that wrote it. Every
+/// implementation picks the fastest code path the CPU offers at run time
+/// (SSE 4.2 / ARMv8 CRC instructions for CRC32C, SHA extensions for
+/// SHA-256) and falls back to portable code elsewhere.
+///
+/// - [`ChecksumAlgorithm::Sha256`] — cryptographic: detects deliberate
+///   tampering as well as corruption. The default, and the only algorithm
+///   packages written before the header field existed could carry.
+/// - [`ChecksumAlgorithm::Crc32c`] — detects accidental corruption (storage,
+///   transport) at a fraction of SHA-256's cost. Not tamper-evident.
+/// - [`ChecksumAlgorithm::XxHash64`] — a fast 64-bit non-cryptographic hash
+///   with a lower collision rate than CRC32C. Not tamper-evident.
+///
+/// For large levels the checksum dominates validation, so a package that only
+/// needs corruption detection restores markedly faster with CRC32C or xxHash64
+/// (see the `PriceLevel - Snapshot Checksum` benchmark group).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ChecksumAlgorithm {
+    /// CRC-32C (Castagnoli), hex-encoded as 8 digits.
+    #[serde(rename = "crc32c")]
+    Crc32c,
+    /// xxHash64 with seed `0`, hex-encoded as 16 digits.
+    #[serde(rename = "xxhash64")]
+    XxHash64,
+    /// SHA-256, hex-encoded as 64 digits.
+    #[default]
+    #[serde(rename = "sha256")]
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    /// Returns the lowercase hex digest of `payload` under this algorithm.
+    #[must_use]
+    pub fn digest_hex(self, payload: &[u8]) -> String {
+        match self {
+            Self::Crc32c => format!("{:08x}", crc32c::crc32c(payload)),
+            Self::XxHash64 => format!("{:016x}", xxhash_rust::xxh64::xxh64(payload, 0)),
+            Self::Sha256 => {
+                use sha2::{Digest, Sha256};
+
+                // `digest` 0.11 returns the digest as a `hybrid_array::Array`,
+                // which — unlike the `generic_array::GenericArray` from 0.10 —
+                // does not implement `LowerHex`. Encode the raw SHA-256 bytes to
+                // lowercase hex by hand; the output is byte-identical to the
+                // 0.10 `format!("{:x}", ..)` encoding.
+                let digest = Sha256::digest(payload);
+                let mut hex = String::with_capacity(digest.len() * 2);
+                for byte in digest {
+                    // Writing to a `String` is infallible.
+                    let _ = write!(hex, "{byte:02x}");
+                }
+                hex
+            }
+        }
+    }
+
+    /// Returns `true` if the algorithm detects deliberate tampering, not only
+    /// accidental corruption.
+    #[must_use]
+    pub fn is_cryptographic(self) -> bool {
+        matches!(self, Self::Sha256)
+    }
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Crc32c => write!(f, "CRC32C"),
+            Self::XxHash64 => write!(f, "XXHASH64"),
+            Self::Sha256 => write!(f, "SHA256"),
+        }
+    }
+}
+
+impl FromStr for ChecksumAlgorithm {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "CRC32C" => Ok(Self::Crc32c),
+            "XXHASH64" | "XXH64" => Ok(Self::XxHash64),
+            "SHA256" | "SHA-256" => Ok(Self::Sha256),
+            _ => Err(PriceLevelError::ParseError {
+                message: format!("Failed to parse ChecksumAlgorithm: {s}"),
+            }),
+        }
+    }
+}
+
+/// The CRC-32 table of the reflected IEEE 802.3 polynomial.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0_u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+    table
+};
+
+/// CRC-32 (IEEE 802.3, as zlib computes it) of `payload`: the checksum
+/// exchange book feeds publish. Not CRC-32C, which
+/// [`ChecksumAlgorithm::Crc32c`] uses.
+pub(crate) fn crc32(payload: &[u8]) -> u32 {
+    !payload.iter().fold(u32::MAX, |crc, byte| {
+        CRC32_TABLE[((crc ^ u32::from(*byte)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Append `value` as a Kraken checksum field: the digits of the feed's
+/// decimal string with the point and leading zeros removed, which for a
+/// value in ticks or lots is its integer digits, and nothing for zero.
+pub(crate) fn push_stripped(payload: &mut String, value: u128) {
+    if value != 0 {
+        // Writing to a `String` is infallible.
+        let _ = write!(payload, "{value}");
+    }
+}
+
+/// Append `value`, in units of `10^-decimals`, as the decimal string an
+/// OKX-style feed publishes: no trailing fractional zeros, and no point for
+/// a whole number.
+pub(crate) fn push_decimal(payload: &mut String, value: u128, decimals: u32) {
+    let digits = format!("{value:0>width$}", width = decimals as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+    payload.push_str(whole);
+    let fraction = fraction.trim_end_matches('0');
+    if !fraction.is_empty() {
+        payload.push('.');
+        payload.push_str(fraction);
+    }
+}
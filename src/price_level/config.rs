@@ -0,0 +1,249 @@
+use crate::orders::{DefaultReplenishment, RefreshPriority, ReplenishmentStrategy};
+use crate::price_level::crossing::CrossingRule;
+use crate::price_level::limits::LevelLimits;
+use crate::price_level::policy::{HiddenPriority, MatchingPolicy};
+use crate::price_level::priority_tiers::PriorityTiers;
+use crate::price_level::symbol_spec::SymbolSpec;
+use crate::utils::{Clock, SystemClock};
+use std::sync::Arc;
+
+/// Most shards a level's order queue index can be split across.
+pub const MAX_QUEUE_SHARDS: usize = 64;
+
+/// Settings a [`PriceLevel`](crate::PriceLevel) is built with, applied by
+/// [`PriceLevel::with_config`](crate::PriceLevel::with_config).
+///
+/// The default is the configuration of
+/// [`PriceLevel::new`](crate::PriceLevel::new): FIFO matching, the
+/// opposite-of-maker crossing rule, an unsharded queue, and
+/// [`DefaultReplenishment`] with refreshed orders re-queued to the back,
+/// hidden orders trading after displayed ones, no priority tiers, the
+/// [`SystemClock`], no [`SymbolSpec`] and no [`LevelLimits`].
+///
+/// ```rust
+/// use pricelevel::{MatchingPolicy, PriceLevel, PriceLevelConfig};
+///
+/// let config = PriceLevelConfig::new()
+///     .with_matching_policy(MatchingPolicy::Fifo)
+///     .with_queue_shards(8);
+/// let level: PriceLevel = PriceLevel::with_config(10_000, config);
+/// assert_eq!(level.queue_shards(), 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PriceLevelConfig {
+    matching_policy: MatchingPolicy,
+    crossing_rule: CrossingRule,
+    queue_shards: usize,
+    replenishment: Arc<dyn ReplenishmentStrategy>,
+    refresh_priority: RefreshPriority,
+    hidden_priority: HiddenPriority,
+    priority_tiers: Option<Arc<dyn PriorityTiers>>,
+    clock: Arc<dyn Clock>,
+    latency_tracking: bool,
+    symbol_spec: Option<SymbolSpec>,
+    limits: Option<LevelLimits>,
+}
+
+impl PriceLevelConfig {
+    /// The default configuration.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            matching_policy: MatchingPolicy::default(),
+            crossing_rule: CrossingRule::default(),
+            queue_shards: 1,
+            replenishment: Arc::new(DefaultReplenishment),
+            refresh_priority: RefreshPriority::default(),
+            hidden_priority: HiddenPriority::default(),
+            priority_tiers: None,
+            clock: Arc::new(SystemClock::new()),
+            latency_tracking: false,
+            symbol_spec: None,
+            limits: None,
+        }
+    }
+
+    /// The same configuration, matching under `policy`.
+    #[must_use]
+    pub fn with_matching_policy(mut self, policy: MatchingPolicy) -> Self {
+        self.matching_policy = policy;
+        self
+    }
+
+    /// The same configuration, assigning taker sides under `rule`.
+    #[must_use]
+    pub fn with_crossing_rule(mut self, rule: CrossingRule) -> Self {
+        self.crossing_rule = rule;
+        self
+    }
+
+    /// The same configuration, splitting the order queue's time-priority
+    /// index across `shards` skip lists keyed by a hash of the order id.
+    ///
+    /// Meant for levels holding hundreds of thousands of orders, where one
+    /// index becomes the contention point between concurrent admissions,
+    /// cancels and matches. A sharded level matches its shards' oldest orders
+    /// round-robin, so time priority is kept within a shard but only
+    /// approximately across shards; under [`MatchingPolicy::StrictFifo`] it
+    /// still matches the exact oldest order first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero or above [`MAX_QUEUE_SHARDS`].
+    #[must_use]
+    pub fn with_queue_shards(mut self, shards: usize) -> Self {
+        assert!(
+            (1..=MAX_QUEUE_SHARDS).contains(&shards),
+            "queue shards must be between 1 and {MAX_QUEUE_SHARDS}, got {shards}"
+        );
+        self.queue_shards = shards;
+        self
+    }
+
+    /// The same configuration, refreshing iceberg and reserve orders with
+    /// `strategy`.
+    #[must_use]
+    pub fn with_replenishment_strategy(mut self, strategy: Arc<dyn ReplenishmentStrategy>) -> Self {
+        self.replenishment = strategy;
+        self
+    }
+
+    /// The same configuration, keeping or dropping the time priority of a
+    /// refreshed iceberg or reserve order under `priority`.
+    ///
+    /// Under [`RefreshPriority::Retain`] the refreshed order keeps its queue
+    /// slot and original timestamp, whatever timestamp the replenishment
+    /// strategy chose. Each refresh is reported in
+    /// [`MatchResult::refreshes`](crate::MatchResult::refreshes).
+    #[must_use]
+    pub fn with_refresh_priority(mut self, priority: RefreshPriority) -> Self {
+        self.refresh_priority = priority;
+        self
+    }
+
+    /// The same configuration, ordering hidden orders against displayed ones
+    /// under `priority`.
+    #[must_use]
+    pub fn with_hidden_priority(mut self, priority: HiddenPriority) -> Self {
+        self.hidden_priority = priority;
+        self
+    }
+
+    /// The same configuration, sweeping makers tier by tier under `tiers`;
+    /// see [`PriorityTiers`].
+    #[must_use]
+    pub fn with_priority_tiers(mut self, tiers: Arc<dyn PriorityTiers>) -> Self {
+        self.priority_tiers = Some(tiers);
+        self
+    }
+
+    /// The same configuration, reading the current time from `clock`.
+    ///
+    /// The level's statistics measure elapsed time against it, and with
+    /// [`PriceLevel::set_clock_stamping`](crate::PriceLevel::set_clock_stamping)
+    /// its trades carry its reading. A [`ManualClock`](crate::ManualClock)
+    /// makes those metrics deterministic in tests.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The same configuration, recording per-call latency histograms of
+    /// `add_order`, `match_order` and `update_order` when `enabled`; read
+    /// them from the level's [`stats`](crate::PriceLevel::stats).
+    #[must_use]
+    pub fn with_latency_tracking(mut self, enabled: bool) -> Self {
+        self.latency_tracking = enabled;
+        self
+    }
+
+    /// The same configuration, rejecting orders and resizes off the tick and
+    /// lot increments of `spec`; see
+    /// [`PriceLevel::symbol_spec`](crate::PriceLevel::symbol_spec).
+    #[must_use]
+    pub fn with_symbol_spec(mut self, spec: SymbolSpec) -> Self {
+        self.symbol_spec = Some(spec);
+        self
+    }
+
+    /// The same configuration, refusing admissions past `limits`; see
+    /// [`PriceLevel::limits`](crate::PriceLevel::limits).
+    #[must_use]
+    pub fn with_limits(mut self, limits: LevelLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+
+    /// The matching policy.
+    #[must_use]
+    pub fn matching_policy(&self) -> MatchingPolicy {
+        self.matching_policy
+    }
+
+    /// The crossing rule.
+    #[must_use]
+    pub fn crossing_rule(&self) -> CrossingRule {
+        self.crossing_rule
+    }
+
+    /// Number of skip lists the queue index is split across.
+    #[must_use]
+    pub fn queue_shards(&self) -> usize {
+        self.queue_shards
+    }
+
+    /// The replenishment strategy.
+    #[must_use]
+    pub fn replenishment_strategy(&self) -> &Arc<dyn ReplenishmentStrategy> {
+        &self.replenishment
+    }
+
+    /// Whether refreshed orders keep their queue slot.
+    #[must_use]
+    pub fn refresh_priority(&self) -> RefreshPriority {
+        self.refresh_priority
+    }
+
+    /// Where hidden orders trade relative to displayed ones.
+    #[must_use]
+    pub fn hidden_priority(&self) -> HiddenPriority {
+        self.hidden_priority
+    }
+
+    /// The participant tiers the FIFO sweep trades in, if any.
+    #[must_use]
+    pub fn priority_tiers(&self) -> Option<&Arc<dyn PriorityTiers>> {
+        self.priority_tiers.as_ref()
+    }
+
+    /// The clock the level reads the current time from.
+    #[must_use]
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Whether the level records per-call latency.
+    #[must_use]
+    pub fn latency_tracking(&self) -> bool {
+        self.latency_tracking
+    }
+
+    /// The tick and lot increments orders are validated against, if any.
+    #[must_use]
+    pub fn symbol_spec(&self) -> Option<SymbolSpec> {
+        self.symbol_spec
+    }
+
+    /// The capacity limits admissions are checked against, if any.
+    #[must_use]
+    pub fn limits(&self) -> Option<LevelLimits> {
+        self.limits
+    }
+}
+
+impl Default for PriceLevelConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,252 @@
+use crate::errors::PriceLevelError;
+use crate::orders::Side;
+#[cfg(feature = "async")]
+use crate::price_level::events::PriceLevelEvent;
+use crate::price_level::level::PriceLevel;
+use crate::utils::{Price, Quantity, TimestampMs};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// One level's changes over a [`Conflator`] window, consolidated into a
+/// single market-data update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflatedUpdate {
+    /// The level's price.
+    pub price: Price,
+    /// The side displayed at the level, or `None` once nothing is.
+    pub side: Option<Side>,
+    /// Displayed size when the update was emitted.
+    pub size: Quantity,
+    /// Displayed orders when the update was emitted.
+    pub order_count: u64,
+    /// Quantity traded at the level since its previous update.
+    pub traded_quantity: Quantity,
+    /// Trades at the level since its previous update.
+    pub trade_count: u64,
+    /// Mutations conflated into this update.
+    pub events: u64,
+    /// When the update was emitted.
+    pub timestamp: TimestampMs,
+}
+
+/// A level with changes not yet emitted, or emitted within the window.
+#[derive(Debug)]
+struct ConflatedLevel<T> {
+    level: Arc<PriceLevel<T>>,
+    traded_quantity: u64,
+    trade_count: u64,
+    events: u64,
+    last_emitted: Option<TimestampMs>,
+}
+
+/// Coalesces per-mutation level changes into at most one update per level
+/// per interval, for distribution channels without the bandwidth for every
+/// event.
+///
+/// Feed it each change — `ingest` for a `PriceLevelEvent` from
+/// `PriceLevel::subscribe` with the `async` feature, or
+/// [`Self::record_change`] and [`Self::record_trade`] from any other
+/// source — and [`Self::poll`] it on a timer. A poll emits each changed level whose interval has run since
+/// its previous update, so a quiet level's first change goes out on the
+/// next poll and a busy level's at most once per interval. An update
+/// carries the level's displayed depth as it stands when emitted and the
+/// trade volume summed since the level's previous update.
+///
+/// Times are passed in, so a replay conflates deterministically.
+///
+/// ```rust
+/// use pricelevel::{
+///     Conflator, Hash32, Id, OrderType, Price, PriceLevel, Quantity, Side, TimeInForce,
+///     TimestampMs,
+/// };
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let level: Arc<PriceLevel> = Arc::new(PriceLevel::new(100));
+/// let conflator = Conflator::new(Duration::from_millis(50));
+/// for id in 1..=3 {
+///     level
+///         .add_order(OrderType::Standard {
+///             id: Id::from_u64(id),
+///             price: Price::new(100),
+///             quantity: Quantity::new(10),
+///             side: Side::Sell,
+///             user_id: Hash32::zero(),
+///             timestamp: TimestampMs::new(id),
+///             time_in_force: TimeInForce::Gtc,
+///             extra_fields: (),
+///         })
+///         .unwrap();
+///     conflator.record_change(&level);
+/// }
+///
+/// let updates = conflator.poll(TimestampMs::new(1_000)).unwrap();
+/// assert_eq!(updates.len(), 1);
+/// assert_eq!(updates[0].size, Quantity::new(30));
+/// assert_eq!(updates[0].events, 3);
+/// ```
+#[derive(Debug)]
+pub struct Conflator<T = ()> {
+    interval_ms: u64,
+    levels: Mutex<BTreeMap<u128, ConflatedLevel<T>>>,
+    ingested: AtomicU64,
+    emitted: AtomicU64,
+}
+
+impl<T: Clone> Conflator<T> {
+    /// A conflator emitting at most one update per level per `interval`.
+    #[must_use]
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval_ms: u64::try_from(interval.as_millis()).unwrap_or(u64::MAX),
+            levels: Mutex::new(BTreeMap::new()),
+            ingested: AtomicU64::new(0),
+            emitted: AtomicU64::new(0),
+        }
+    }
+
+    /// The coalescing interval.
+    #[must_use]
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+
+    /// Number of changes recorded so far.
+    #[must_use]
+    pub fn ingested(&self) -> u64 {
+        self.ingested.load(Ordering::Relaxed)
+    }
+
+    /// Number of updates emitted so far; against [`Self::ingested`], the
+    /// conflation ratio.
+    #[must_use]
+    pub fn emitted(&self) -> u64 {
+        self.emitted.load(Ordering::Relaxed)
+    }
+
+    /// Number of levels with changes waiting to be emitted.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.lock_levels()
+            .values()
+            .filter(|pending| pending.events > 0)
+            .count()
+    }
+
+    /// Record `event`, published by `level`.
+    #[cfg(feature = "async")]
+    pub fn ingest(&self, level: &Arc<PriceLevel<T>>, event: &PriceLevelEvent<T>) {
+        match event {
+            PriceLevelEvent::Trade(trade) => {
+                self.record_trade(level, trade.quantity().as_u64());
+            }
+            _ => self.record_change(level),
+        }
+    }
+
+    /// Record a change to `level` other than a trade.
+    pub fn record_change(&self, level: &Arc<PriceLevel<T>>) {
+        self.record(level, 0, 0);
+    }
+
+    /// Record a trade of `quantity` at `level`.
+    pub fn record_trade(&self, level: &Arc<PriceLevel<T>>, quantity: u64) {
+        self.record(level, quantity, 1);
+    }
+
+    /// Emit an update for each changed level whose interval has run since
+    /// its previous update, in ascending price order. The rest stay pending
+    /// for a later poll.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if a level's displayed
+    /// size overflows `u64`; nothing is emitted then.
+    pub fn poll(&self, now: TimestampMs) -> Result<Vec<ConflatedUpdate>, PriceLevelError> {
+        self.emit(now, false)
+    }
+
+    /// Emit an update for every changed level, interval or not: for a
+    /// channel closing or a subscriber joining.
+    ///
+    /// # Errors
+    ///
+    /// Returns what [`Self::poll`] returns.
+    pub fn flush(&self, now: TimestampMs) -> Result<Vec<ConflatedUpdate>, PriceLevelError> {
+        self.emit(now, true)
+    }
+
+    fn record(&self, level: &Arc<PriceLevel<T>>, traded_quantity: u64, trade_count: u64) {
+        let mut levels = self.lock_levels();
+        let pending = levels
+            .entry(level.price())
+            .or_insert_with(|| ConflatedLevel {
+                level: Arc::clone(level),
+                traded_quantity: 0,
+                trade_count: 0,
+                events: 0,
+                last_emitted: None,
+            });
+        pending.traded_quantity = pending.traded_quantity.saturating_add(traded_quantity);
+        pending.trade_count += trade_count;
+        pending.events += 1;
+        self.ingested.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn emit(&self, now: TimestampMs, all: bool) -> Result<Vec<ConflatedUpdate>, PriceLevelError> {
+        let mut levels = self.lock_levels();
+        let due = |pending: &ConflatedLevel<T>| {
+            pending.events > 0
+                && (all
+                    || pending.last_emitted.is_none_or(|last| {
+                        now.as_u64().saturating_sub(last.as_u64()) >= self.interval_ms
+                    }))
+        };
+
+        // Read every due level before resetting any, so an error leaves the
+        // pending changes in place.
+        let mut updates = Vec::new();
+        for (price, pending) in levels.iter() {
+            if !due(pending) {
+                continue;
+            }
+            let record = pending.level.mbp_record()?;
+            updates.push(ConflatedUpdate {
+                price: Price::new(*price),
+                side: record.map(|record| record.side),
+                size: record.map_or(Quantity::new(0), |record| record.size),
+                order_count: record.map_or(0, |record| record.order_count),
+                traded_quantity: Quantity::new(pending.traded_quantity),
+                trade_count: pending.trade_count,
+                events: pending.events,
+                timestamp: now,
+            });
+        }
+        for update in &updates {
+            if let Some(pending) = levels.get_mut(&update.price.as_u128()) {
+                pending.traded_quantity = 0;
+                pending.trade_count = 0;
+                pending.events = 0;
+                pending.last_emitted = Some(now);
+            }
+        }
+        // A level quiet for a whole interval is forgotten, so the map holds
+        // only the levels that are changing.
+        levels.retain(|_, pending| {
+            pending.events > 0
+                || pending.last_emitted.is_some_and(|last| {
+                    now.as_u64().saturating_sub(last.as_u64()) < self.interval_ms
+                })
+        });
+        self.emitted
+            .fetch_add(updates.len() as u64, Ordering::Relaxed);
+        Ok(updates)
+    }
+
+    fn lock_levels(&self) -> MutexGuard<'_, BTreeMap<u128, ConflatedLevel<T>>> {
+        self.levels.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
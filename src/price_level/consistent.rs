@@ -0,0 +1,103 @@
+use crate::price_level::snapshot::PriceLevelSnapshot;
+use crossbeam_epoch::{self as epoch, Atomic, Owned};
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
+/// The last snapshot `PriceLevel::snapshot_consistent` validated, handed to
+/// later callers until the level mutates.
+///
+/// Held behind a `crossbeam-epoch` atomic pointer: a reader clones the `Arc`
+/// under a pinned guard, and a replaced view is reclaimed only once every
+/// guard pinned before the swap is gone, so neither readers nor the
+/// publishing walk ever lock.
+pub(crate) struct ConsistentView<T> {
+    current: Atomic<Published<T>>,
+}
+
+/// A published view with the mutation sequence it was taken at.
+struct Published<T> {
+    mutation_sequence: u64,
+    snapshot: Arc<PriceLevelSnapshot<T>>,
+}
+
+impl<T> ConsistentView<T> {
+    /// The published view, if one has been validated yet.
+    pub(crate) fn load(&self) -> Option<Arc<PriceLevelSnapshot<T>>> {
+        let guard = epoch::pin();
+        let current = self.current.load(Ordering::Acquire, &guard);
+        // SAFETY: a non-null pointer was published by `publish` and is only
+        // reclaimed through `defer_destroy`, which waits for this pinned guard.
+        unsafe { current.as_ref() }.map(|published| Arc::clone(&published.snapshot))
+    }
+
+    /// Publish `snapshot`, taken at `mutation_sequence`, unless a view at the
+    /// same or a later sequence is already published. Losing a race to
+    /// another walk keeps theirs.
+    pub(crate) fn publish(&self, mutation_sequence: u64, snapshot: &Arc<PriceLevelSnapshot<T>>)
+    where
+        T: Send + Sync,
+    {
+        let guard = epoch::pin();
+        let current = self.current.load(Ordering::Acquire, &guard);
+        // SAFETY: as in `load`.
+        if let Some(published) = unsafe { current.as_ref() }
+            && published.mutation_sequence >= mutation_sequence
+        {
+            return;
+        }
+        let replaced = self.current.compare_exchange(
+            current,
+            Owned::new(Published {
+                mutation_sequence,
+                snapshot: Arc::clone(snapshot),
+            }),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+            &guard,
+        );
+        if replaced.is_ok() && !current.is_null() {
+            // SAFETY: `current` is now unreachable from `self.current`; readers
+            // that loaded it before the swap are pinned, and the destruction
+            // waits for them. `T: Send + Sync`, so whichever thread collects
+            // the garbage may drop it.
+            unsafe { guard.defer_destroy(current) };
+        }
+    }
+}
+
+// Not derived: a derive would demand `T: Default`.
+impl<T> Default for ConsistentView<T> {
+    fn default() -> Self {
+        Self {
+            current: Atomic::null(),
+        }
+    }
+}
+
+impl<T> Drop for ConsistentView<T> {
+    fn drop(&mut self) {
+        // SAFETY: `&mut self` means no other thread can reach the pointer any
+        // more, and views replaced earlier were handed to `defer_destroy`, so
+        // this is the only owner of the current one.
+        unsafe {
+            let current = self.current.load(Ordering::Relaxed, epoch::unprotected());
+            if !current.is_null() {
+                drop(current.into_owned());
+            }
+        }
+    }
+}
+
+// Not derived: the snapshot's `Debug` would demand `T: Debug`.
+impl<T> fmt::Debug for ConsistentView<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = epoch::pin();
+        let current = self.current.load(Ordering::Acquire, &guard);
+        // SAFETY: as in `load`.
+        let published = unsafe { current.as_ref() }.map(|published| published.mutation_sequence);
+        f.debug_struct("ConsistentView")
+            .field("mutation_sequence", &published)
+            .finish()
+    }
+}
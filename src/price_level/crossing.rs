@@ -0,0 +1,111 @@
+use crate::errors::PriceLevelError;
+use crate::orders::Side;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How [`PriceLevel::match_order`](crate::PriceLevel::match_order) decides
+/// the taker side of each trade, and which maker / taker pairings it refuses.
+///
+/// The rule is set per level ([`PriceLevel::with_crossing_rule`](crate::PriceLevel::with_crossing_rule)
+/// / [`PriceLevel::set_crossing_rule`](crate::PriceLevel::set_crossing_rule))
+/// and read once at the start of every match.
+///
+/// - [`CrossingRule::OppositeOfMaker`] — the taker is assumed to be on the
+///   other side of every maker it trades with. The default, and the only
+///   behaviour before crossing rules existed.
+/// - [`CrossingRule::TakerBuys`] / [`CrossingRule::TakerSells`] — every taker
+///   at this level is on the given side, typically the level's opposite side
+///   in the composing book. A taker presented against makers on that same
+///   side is an invalid crossing: `match_order` rejects it with no trade, and
+///   [`PriceLevel::try_match_order`](crate::PriceLevel::try_match_order)
+///   reports it as [`PriceLevelError::InvalidCrossing`]. Synthetic internal
+///   flows that route a same-side order to a level are caught here instead of
+///   printing a trade with both legs on one side.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossingRule {
+    /// The taker is on the opposite side of each maker.
+    #[default]
+    OppositeOfMaker = 0,
+    /// Every taker buys; sell makers only.
+    TakerBuys = 1,
+    /// Every taker sells; buy makers only.
+    TakerSells = 2,
+}
+
+impl CrossingRule {
+    /// The rule fixing every taker to `side`.
+    #[must_use]
+    pub fn taker(side: Side) -> Self {
+        match side {
+            Side::Buy => Self::TakerBuys,
+            Side::Sell => Self::TakerSells,
+        }
+    }
+
+    /// Decodes the `#[repr(u8)]` discriminant stored in the level's atomic.
+    /// Unknown values fall back to [`CrossingRule::OppositeOfMaker`].
+    #[must_use]
+    pub(crate) fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::TakerBuys,
+            2 => Self::TakerSells,
+            _ => Self::OppositeOfMaker,
+        }
+    }
+
+    /// The taker side the rule fixes, or `None` for
+    /// [`CrossingRule::OppositeOfMaker`].
+    #[must_use]
+    pub fn fixed_taker_side(self) -> Option<Side> {
+        match self {
+            Self::OppositeOfMaker => None,
+            Self::TakerBuys => Some(Side::Buy),
+            Self::TakerSells => Some(Side::Sell),
+        }
+    }
+
+    /// The taker side of a trade against a maker on `maker_side`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidCrossing`] if the rule fixes the
+    /// taker to `maker_side`.
+    pub fn taker_side(self, maker_side: Side) -> Result<Side, PriceLevelError> {
+        match self.fixed_taker_side() {
+            None => Ok(maker_side.opposite()),
+            Some(taker_side) if taker_side != maker_side => Ok(taker_side),
+            Some(taker_side) => Err(PriceLevelError::InvalidCrossing {
+                maker_side,
+                taker_side,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for CrossingRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OppositeOfMaker => write!(f, "OPPOSITE_OF_MAKER"),
+            Self::TakerBuys => write!(f, "TAKER_BUYS"),
+            Self::TakerSells => write!(f, "TAKER_SELLS"),
+        }
+    }
+}
+
+impl FromStr for CrossingRule {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "OPPOSITE_OF_MAKER" | "OPPOSITEOFMAKER" => Ok(Self::OppositeOfMaker),
+            "TAKER_BUYS" | "TAKERBUYS" => Ok(Self::TakerBuys),
+            "TAKER_SELLS" | "TAKERSELLS" => Ok(Self::TakerSells),
+            _ => Err(PriceLevelError::ParseError {
+                message: format!("Failed to parse CrossingRule: {s}"),
+            }),
+        }
+    }
+}
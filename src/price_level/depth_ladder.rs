@@ -0,0 +1,221 @@
+use crate::errors::PriceLevelError;
+use crate::orders::Side;
+use crate::price_level::book_analytics::BookAnalytics;
+use crate::price_level::market_data::{MboRecord, MbpRecord};
+use crate::price_level::snapshot::PriceLevelSnapshot;
+use crate::utils::{Price, Quantity};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One rung of a [`DepthLadder`]: the displayed depth at a price bucket and
+/// the depth up to and including it from the best price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthRung {
+    /// The bucket's price: a multiple of the ladder's tick.
+    pub price: Price,
+    /// Displayed size in the bucket.
+    pub size: Quantity,
+    /// Displayed size from the best bucket through this one.
+    pub cumulative_size: Quantity,
+    /// Number of displayed orders in the bucket.
+    pub order_count: u64,
+}
+
+/// Cumulative displayed depth of both sides of a book, for depth charts.
+///
+/// Built from the market-by-price records (or snapshots) of the book's
+/// levels: a level whose resting side is [`Side::Buy`] is a bid, one on
+/// [`Side::Sell`] an ask. Prices are grouped into buckets of `tick` — bids
+/// rounded down and asks rounded up to a multiple of it, so a bucket never
+/// shows depth at a better price than it rests — and each side is ordered
+/// from its best price outward, its cumulative size running the same way.
+///
+/// ```rust
+/// use pricelevel::{DepthLadder, MbpRecord, Price, Quantity, Side};
+///
+/// let level = |price, side, size| MbpRecord {
+///     price: Price::new(price),
+///     side,
+///     size: Quantity::new(size),
+///     order_count: 1,
+/// };
+/// let levels = [
+///     level(9_990, Side::Buy, 5),
+///     level(9_995, Side::Buy, 3),
+///     level(10_005, Side::Sell, 4),
+///     level(10_012, Side::Sell, 6),
+/// ];
+///
+/// let ladder = DepthLadder::with_tick(&levels, 10).unwrap();
+/// let prices: Vec<u128> = ladder.bids().iter().map(|r| r.price.as_u128()).collect();
+/// assert_eq!(prices, [9_990]);
+/// assert_eq!(ladder.bids()[0].size.as_u64(), 8);
+/// assert_eq!(ladder.asks()[1].cumulative_size.as_u64(), 10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthLadder {
+    /// Width of a price bucket.
+    tick: u128,
+    /// Bid buckets, highest price first.
+    bids: Vec<DepthRung>,
+    /// Ask buckets, lowest price first.
+    asks: Vec<DepthRung>,
+}
+
+impl DepthLadder {
+    /// A ladder with one rung per distinct price.
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::with_tick`].
+    pub fn new(levels: &[MbpRecord]) -> Result<Self, PriceLevelError> {
+        Self::with_tick(levels, 1)
+    }
+
+    /// A ladder grouping prices into buckets of `tick`. Records for the same
+    /// bucket and side are summed; empty records are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `tick` is zero, if
+    /// rounding an ask up to the tick overflows `u128`, or if a bucket's size,
+    /// order count or a side's cumulative size overflows `u64`.
+    pub fn with_tick(levels: &[MbpRecord], tick: u128) -> Result<Self, PriceLevelError> {
+        if tick == 0 {
+            return Err(PriceLevelError::InvalidOperation {
+                message: "depth ladder tick must be positive".to_string(),
+            });
+        }
+        let mut bids: BTreeMap<u128, (u64, u64)> = BTreeMap::new();
+        let mut asks: BTreeMap<u128, (u64, u64)> = BTreeMap::new();
+        for level in levels.iter().filter(|level| level.size.as_u64() > 0) {
+            let price = level.price.as_u128();
+            let (bucket, side) = match level.side {
+                Side::Buy => (price - price % tick, &mut bids),
+                Side::Sell => (
+                    price
+                        .div_ceil(tick)
+                        .checked_mul(tick)
+                        .ok_or_else(|| overflow("ask bucket price"))?,
+                    &mut asks,
+                ),
+            };
+            let (size, orders) = side.entry(bucket).or_insert((0, 0));
+            *size = size
+                .checked_add(level.size.as_u64())
+                .ok_or_else(|| overflow("bucket size"))?;
+            *orders = orders
+                .checked_add(level.order_count)
+                .ok_or_else(|| overflow("bucket order count"))?;
+        }
+        Ok(Self {
+            tick,
+            bids: cumulate(bids.into_iter().rev())?,
+            asks: cumulate(asks.into_iter())?,
+        })
+    }
+
+    /// A ladder from level snapshots, such as
+    /// [`BookSnapshot::levels`](crate::BookSnapshot::levels), each
+    /// contributing its displayed depth as [`PriceLevel::mbp_record`] would.
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::with_tick`], and if a level's displayed size overflows
+    /// `u64`.
+    ///
+    /// [`PriceLevel::mbp_record`]: crate::PriceLevel::mbp_record
+    pub fn from_snapshots<T: Clone>(
+        snapshots: &[PriceLevelSnapshot<T>],
+        tick: u128,
+    ) -> Result<Self, PriceLevelError> {
+        let mut levels = Vec::with_capacity(snapshots.len());
+        for snapshot in snapshots {
+            let orders = MboRecord::from_snapshot(snapshot);
+            // A level's resting orders share one side; split in case a
+            // corrupted snapshot mixes them.
+            for side in [Side::Buy, Side::Sell] {
+                let records: Vec<MboRecord> = orders
+                    .iter()
+                    .filter(|record| record.side == side)
+                    .copied()
+                    .collect();
+                levels.extend(MbpRecord::aggregate(&records)?);
+            }
+        }
+        Self::with_tick(&levels, tick)
+    }
+
+    /// Get the width of a price bucket.
+    #[must_use]
+    pub fn tick(&self) -> u128 {
+        self.tick
+    }
+
+    /// Get the bid buckets, highest price first.
+    #[must_use]
+    pub fn bids(&self) -> &[DepthRung] {
+        &self.bids
+    }
+
+    /// Get the ask buckets, lowest price first.
+    #[must_use]
+    pub fn asks(&self) -> &[DepthRung] {
+        &self.asks
+    }
+
+    /// Get the bucket nearest the spread on `side`, or `None` if it is empty.
+    #[must_use]
+    pub fn best(&self, side: Side) -> Option<&DepthRung> {
+        match side {
+            Side::Buy => self.bids.first(),
+            Side::Sell => self.asks.first(),
+        }
+    }
+
+    /// Get the total displayed size on `side`.
+    #[must_use]
+    pub fn total_size(&self, side: Side) -> Quantity {
+        let rungs = match side {
+            Side::Buy => &self.bids,
+            Side::Sell => &self.asks,
+        };
+        rungs
+            .last()
+            .map_or(Quantity::new(0), |rung| rung.cumulative_size)
+    }
+
+    /// The book's top-of-book imbalance, microprice and mids, with the
+    /// depth-weighted mid averaged over the best `depth` rungs per side.
+    #[must_use]
+    pub fn analytics(&self, depth: usize) -> BookAnalytics {
+        BookAnalytics::from_ladder(self, depth)
+    }
+}
+
+fn overflow(what: &str) -> PriceLevelError {
+    PriceLevelError::InvalidOperation {
+        message: format!("depth ladder {what} overflow"),
+    }
+}
+
+/// Turn `(bucket, (size, orders))` pairs, best first, into rungs with a
+/// running cumulative size.
+fn cumulate(
+    buckets: impl Iterator<Item = (u128, (u64, u64))>,
+) -> Result<Vec<DepthRung>, PriceLevelError> {
+    let mut cumulative = 0u64;
+    buckets
+        .map(|(price, (size, order_count))| {
+            cumulative = cumulative
+                .checked_add(size)
+                .ok_or_else(|| overflow("cumulative size"))?;
+            Ok(DepthRung {
+                price: Price::new(price),
+                size: Quantity::new(size),
+                cumulative_size: Quantity::new(cumulative),
+                order_count,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,230 @@
+use crate::execution::Trade;
+use crate::orders::{OrderType, UpdateOutcome};
+use crate::price_level::sequenced::Sequenced;
+use async_channel::{Receiver, Sender, TrySendError};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Events buffered per subscriber by
+/// [`PriceLevel::subscribe`](crate::PriceLevel::subscribe).
+pub const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
+/// A change to a price level, delivered to the streams returned by
+/// [`PriceLevel::subscribe`](crate::PriceLevel::subscribe).
+///
+/// Events are published after the change commits, in the order the
+/// publishing threads reach the channel: one matcher's trades arrive in
+/// execution order, while changes racing on other threads interleave with
+/// them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceLevelEvent<T = ()> {
+    /// An order was admitted; carries the order as it rests.
+    OrderAdded(Arc<OrderType<T>>),
+    /// An order was resized, re-dated or had its trailing reference ratcheted;
+    /// carries the order as it now rests.
+    OrderUpdated(Arc<OrderType<T>>),
+    /// A pending stop was activated and moved to the back of the queue;
+    /// carries the activated order.
+    OrderActivated(Arc<OrderType<T>>),
+    /// An order was cancelled or removed to move to another price; carries the
+    /// order as it rested when removed.
+    OrderRemoved(Arc<OrderType<T>>),
+    /// A cancel/replace cancelled an order and admitted its replacement at
+    /// the back of the queue, as one mutation.
+    OrderReplaced {
+        /// The cancelled order, as it rested when removed.
+        replaced: Arc<OrderType<T>>,
+        /// The replacement, as it now rests.
+        replacement: Arc<OrderType<T>>,
+    },
+    /// A taker executed against a resting order. Boxed to keep the event as
+    /// small as the order variants.
+    Trade(Box<Trade<T>>),
+    /// A trade published earlier was busted and its statistics reversed, and
+    /// its maker possibly reinstated at the front of the queue, as one
+    /// mutation.
+    TradeBusted {
+        /// The busted trade.
+        trade: Box<Trade<T>>,
+        /// The maker put back at the front of the queue, as it now rests.
+        reinstated: Option<Arc<OrderType<T>>>,
+    },
+}
+
+impl<T> PriceLevelEvent<T> {
+    /// The event for a committed [`UpdateOutcome`], or `None` for
+    /// [`UpdateOutcome::NotFound`].
+    pub(crate) fn from_update(outcome: &UpdateOutcome<T>) -> Option<Self> {
+        match outcome {
+            UpdateOutcome::UpdatedInPlace(order) => Some(Self::OrderUpdated(Arc::clone(order))),
+            UpdateOutcome::RemovedForReprice(order) | UpdateOutcome::Cancelled(order, _) => {
+                Some(Self::OrderRemoved(Arc::clone(order)))
+            }
+            UpdateOutcome::NotFound => None,
+        }
+    }
+}
+
+/// One subscriber's channel: bare events for
+/// [`PriceLevel::subscribe`](crate::PriceLevel::subscribe), or events stamped
+/// with their mutation sequence for
+/// [`PriceLevel::subscribe_sequenced`](crate::PriceLevel::subscribe_sequenced).
+enum Subscriber<T> {
+    Plain(Sender<PriceLevelEvent<T>>),
+    Sequenced(Sender<Sequenced<PriceLevelEvent<T>>>),
+}
+
+impl<T> Subscriber<T> {
+    fn is_closed(&self) -> bool {
+        match self {
+            Self::Plain(sender) => sender.is_closed(),
+            Self::Sequenced(sender) => sender.is_closed(),
+        }
+    }
+
+    /// Closes the channel; `true` if this call closed it.
+    fn close(&self) -> bool {
+        match self {
+            Self::Plain(sender) => sender.close(),
+            Self::Sequenced(sender) => sender.close(),
+        }
+    }
+}
+
+impl<T: Clone> Subscriber<T> {
+    /// Offers `event` without blocking; a rejected message is dropped, only
+    /// the reason is returned.
+    fn offer(&self, sequence: u64, event: &PriceLevelEvent<T>) -> Result<(), TrySendError<()>> {
+        let full = match self {
+            Self::Plain(sender) => match sender.try_send(event.clone()) {
+                Ok(()) => return Ok(()),
+                Err(err) => err.is_full(),
+            },
+            Self::Sequenced(sender) => {
+                match sender.try_send(Sequenced::new(sequence, event.clone())) {
+                    Ok(()) => return Ok(()),
+                    Err(err) => err.is_full(),
+                }
+            }
+        };
+        Err(if full {
+            TrySendError::Full(())
+        } else {
+            TrySendError::Closed(())
+        })
+    }
+}
+
+/// The event subscribers of one price level. Runtime wiring, like the expiry
+/// hook: never cloned or serialized.
+pub(crate) struct EventSubscribers<T> {
+    senders: RwLock<Vec<Subscriber<T>>>,
+    /// `true` while at least one subscriber is registered, so publishing is a
+    /// single atomic load otherwise.
+    any: AtomicBool,
+    /// Subscribers disconnected because their buffer was full.
+    lagged: AtomicU64,
+}
+
+impl<T> Default for EventSubscribers<T> {
+    // Not derived: a derive would demand `T: Default`.
+    fn default() -> Self {
+        Self {
+            senders: RwLock::new(Vec::new()),
+            any: AtomicBool::new(false),
+            lagged: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<T> fmt::Debug for EventSubscribers<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventSubscribers")
+            .field("subscribers", &self.count())
+            .field("lagged", &self.lagged())
+            .finish()
+    }
+}
+
+impl<T> EventSubscribers<T> {
+    pub(crate) fn subscribe(&self, capacity: usize) -> Receiver<PriceLevelEvent<T>> {
+        let (sender, receiver) = async_channel::bounded(capacity);
+        self.register(Subscriber::Plain(sender));
+        receiver
+    }
+
+    pub(crate) fn subscribe_sequenced(
+        &self,
+        capacity: usize,
+    ) -> Receiver<Sequenced<PriceLevelEvent<T>>> {
+        let (sender, receiver) = async_channel::bounded(capacity);
+        self.register(Subscriber::Sequenced(sender));
+        receiver
+    }
+
+    fn register(&self, subscriber: Subscriber<T>) {
+        let mut senders = self.senders.write().unwrap_or_else(|e| e.into_inner());
+        senders.push(subscriber);
+        self.any.store(true, Ordering::Release);
+    }
+
+    /// Whether anyone is subscribed; a cheap check before building events.
+    pub(crate) fn is_active(&self) -> bool {
+        self.any.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.senders
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|sender| !sender.is_closed())
+            .count()
+    }
+
+    pub(crate) fn lagged(&self) -> u64 {
+        self.lagged.load(Ordering::Relaxed)
+    }
+}
+
+impl<T: Clone> EventSubscribers<T> {
+    /// Offers `events`, each with the mutation sequence it committed at, to
+    /// every subscriber without blocking. A subscriber whose buffer is full is
+    /// disconnected rather than waited for: its stream yields what it had
+    /// buffered and then ends, so the gap is never silent. Dropped and
+    /// disconnected subscribers are pruned once the read lock is released.
+    pub(crate) fn publish<I>(&self, events: I)
+    where
+        I: IntoIterator<Item = (u64, PriceLevelEvent<T>)>,
+    {
+        if !self.is_active() {
+            return;
+        }
+        let mut prune = false;
+        {
+            let senders = self.senders.read().unwrap_or_else(|e| e.into_inner());
+            for (sequence, event) in events {
+                for sender in senders.iter() {
+                    match sender.offer(sequence, &event) {
+                        Ok(()) => {}
+                        Err(TrySendError::Full(_)) => {
+                            // Count only the close that disconnected it: a
+                            // racing publisher may find the same full buffer.
+                            if sender.close() {
+                                self.lagged.fetch_add(1, Ordering::Relaxed);
+                            }
+                            prune = true;
+                        }
+                        Err(TrySendError::Closed(_)) => prune = true,
+                    }
+                }
+            }
+        }
+        if prune {
+            let mut senders = self.senders.write().unwrap_or_else(|e| e.into_inner());
+            senders.retain(|sender| !sender.is_closed());
+            self.any.store(!senders.is_empty(), Ordering::Release);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use crate::errors::PriceLevelError;
+use crate::execution::Trade;
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Trades buffered by
+/// [`PriceLevel::execution_channel`](crate::PriceLevel::execution_channel).
+pub const DEFAULT_EXECUTION_CHANNEL_CAPACITY: usize = 1024;
+
+/// What [`PriceLevel::match_order`](crate::PriceLevel::match_order) does with
+/// a trade when an execution channel's buffer is full.
+///
+/// - [`ExecutionOverflow::DropNewest`] — the trade is not delivered to that
+///   channel and counted in
+///   [`PriceLevel::dropped_executions`](crate::PriceLevel::dropped_executions).
+///   Matching never waits on a consumer. The default.
+/// - [`ExecutionOverflow::Backpressure`] — the matching thread blocks until
+///   the consumer makes room, so no trade is lost. A fill-or-kill or strict
+///   FIFO sweep still holds the level's exclusive guard at that point, so a
+///   stalled consumer also stalls that level's admissions and updates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionOverflow {
+    /// Skip the trade for a full channel and count it.
+    #[default]
+    DropNewest,
+    /// Block the matcher until the channel has room.
+    Backpressure,
+}
+
+impl fmt::Display for ExecutionOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DropNewest => write!(f, "DROP_NEWEST"),
+            Self::Backpressure => write!(f, "BACKPRESSURE"),
+        }
+    }
+}
+
+impl FromStr for ExecutionOverflow {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "DROP_NEWEST" | "DROPNEWEST" => Ok(Self::DropNewest),
+            "BACKPRESSURE" => Ok(Self::Backpressure),
+            _ => Err(PriceLevelError::ParseError {
+                message: format!("Failed to parse ExecutionOverflow: {s}"),
+            }),
+        }
+    }
+}
+
+/// The execution channels of one price level. Runtime wiring, like the
+/// expiry hook: never cloned or serialized.
+pub(crate) struct ExecutionChannels<T> {
+    senders: RwLock<Vec<(Sender<Trade<T>>, ExecutionOverflow)>>,
+    /// `true` while at least one channel is open, so a match without
+    /// consumers pays a single atomic load.
+    any: AtomicBool,
+    /// Trades a [`ExecutionOverflow::DropNewest`] channel was full for.
+    dropped: AtomicU64,
+}
+
+impl<T> Default for ExecutionChannels<T> {
+    // Not derived: a derive would demand `T: Default`.
+    fn default() -> Self {
+        Self {
+            senders: RwLock::new(Vec::new()),
+            any: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<T> fmt::Debug for ExecutionChannels<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutionChannels")
+            .field("active", &self.is_active())
+            .field("dropped", &self.dropped())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> ExecutionChannels<T> {
+    pub(crate) fn open(&self, capacity: usize, overflow: ExecutionOverflow) -> Receiver<Trade<T>> {
+        let (sender, receiver) = crossbeam_channel::bounded(capacity);
+        let mut senders = self.senders.write().unwrap_or_else(|e| e.into_inner());
+        senders.push((sender, overflow));
+        self.any.store(true, Ordering::Release);
+        receiver
+    }
+
+    /// Whether any channel is open; a cheap check before cloning trades.
+    pub(crate) fn is_active(&self) -> bool {
+        self.any.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<T: Clone> ExecutionChannels<T> {
+    /// Delivers every trade of `trades`, in order, to every open channel
+    /// under its overflow policy. The senders are cloned out of the lock
+    /// first, so a backpressured send never blocks a concurrent
+    /// [`Self::open`]. Channels whose receiver was dropped are pruned.
+    pub(crate) fn publish(&self, trades: &[Trade<T>]) {
+        if trades.is_empty() || !self.is_active() {
+            return;
+        }
+        let senders = self
+            .senders
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+
+        let mut disconnected = Vec::new();
+        for (sender, overflow) in &senders {
+            for trade in trades {
+                let delivered = match overflow {
+                    ExecutionOverflow::DropNewest => match sender.try_send(trade.clone()) {
+                        Ok(()) => true,
+                        Err(TrySendError::Full(_)) => {
+                            self.dropped.fetch_add(1, Ordering::Relaxed);
+                            true
+                        }
+                        Err(TrySendError::Disconnected(_)) => false,
+                    },
+                    ExecutionOverflow::Backpressure => sender.send(trade.clone()).is_ok(),
+                };
+                if !delivered {
+                    disconnected.push(sender);
+                    break;
+                }
+            }
+        }
+
+        if !disconnected.is_empty() {
+            let mut senders = self.senders.write().unwrap_or_else(|e| e.into_inner());
+            senders
+                .retain(|(sender, _)| !disconnected.iter().any(|gone| gone.same_channel(sender)));
+            self.any.store(!senders.is_empty(), Ordering::Release);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::orders::{Id, OrderType};
+use crate::utils::TimestampMs;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Advance notice that a resting `Gtd` or `Gtt` order is about to expire,
+/// passed to the
+/// callback registered with
+/// [`PriceLevel::set_expiry_warning`](crate::PriceLevel::set_expiry_warning).
+///
+/// The callback runs while the order still rests, so a strategy can keep it
+/// alive with
+/// [`PriceLevel::extend_expiry`](crate::PriceLevel::extend_expiry) without
+/// losing its queue position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiryWarning<T = ()> {
+    /// The order as it rested when the warning was raised.
+    pub order: Arc<OrderType<T>>,
+    /// The order's `Gtd` or `Gtt` expiry.
+    pub expires_at: TimestampMs,
+    /// The clock reading that raised the warning, within the lead time of
+    /// [`Self::expires_at`].
+    pub now: TimestampMs,
+}
+
+/// Callback invoked with each raised [`ExpiryWarning`].
+pub(crate) type ExpiryCallback<T> = Arc<dyn Fn(&ExpiryWarning<T>) + Send + Sync>;
+
+/// The registered lead time and callback.
+struct ExpiryHook<T> {
+    lead_time_ms: u64,
+    callback: ExpiryCallback<T>,
+}
+
+/// The expiry-warning hook of one price level and the orders it has already
+/// warned. Runtime configuration, like statistics alerts: never cloned or
+/// serialized.
+pub(crate) struct ExpiryWarnings<T> {
+    hook: RwLock<Option<ExpiryHook<T>>>,
+    /// Orders inside their warning window at the last poll, with the expiry
+    /// they were warned for. An order is warned once per expiry: extending it
+    /// changes the expiry and re-arms the warning.
+    warned: Mutex<HashMap<Id, u64>>,
+    /// `true` while a hook is registered, so polling skips the queue walk
+    /// otherwise.
+    any: AtomicBool,
+}
+
+impl<T> Default for ExpiryWarnings<T> {
+    // Not derived: a derive would demand `T: Default`.
+    fn default() -> Self {
+        Self {
+            hook: RwLock::new(None),
+            warned: Mutex::new(HashMap::new()),
+            any: AtomicBool::new(false),
+        }
+    }
+}
+
+impl<T> fmt::Debug for ExpiryWarnings<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExpiryWarnings")
+            .field("lead_time_ms", &self.lead_time_ms())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> ExpiryWarnings<T> {
+    pub(crate) fn set(&self, lead_time_ms: u64, callback: ExpiryCallback<T>) {
+        let mut hook = self.hook.write().unwrap_or_else(|e| e.into_inner());
+        *hook = Some(ExpiryHook {
+            lead_time_ms,
+            callback,
+        });
+        self.warned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        self.any.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut hook = self.hook.write().unwrap_or_else(|e| e.into_inner());
+        *hook = None;
+        self.warned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        self.any.store(false, Ordering::Release);
+    }
+
+    /// Whether a hook is registered; a cheap check before collecting orders.
+    pub(crate) fn is_set(&self) -> bool {
+        self.any.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn lead_time_ms(&self) -> Option<u64> {
+        self.hook
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|hook| hook.lead_time_ms)
+    }
+}
+
+impl<T: Clone> ExpiryWarnings<T> {
+    /// Raises a warning for every order of `orders` whose `Gtd` or `Gtt` expiry is
+    /// within the lead time of `now` and that has not been warned for that
+    /// expiry yet. Callbacks run after both locks are released, so a callback
+    /// may extend an order or replace the hook. Returns the number raised.
+    pub(crate) fn poll<I>(&self, orders: I, now: TimestampMs) -> usize
+    where
+        I: IntoIterator<Item = Arc<OrderType<T>>>,
+    {
+        if !self.any.load(Ordering::Acquire) {
+            return 0;
+        }
+        let (lead_time_ms, callback) = {
+            let hook = self.hook.read().unwrap_or_else(|e| e.into_inner());
+            match hook.as_ref() {
+                Some(hook) => (hook.lead_time_ms, Arc::clone(&hook.callback)),
+                None => return 0,
+            }
+        };
+
+        let mut raised = Vec::new();
+        {
+            let mut warned = self.warned.lock().unwrap_or_else(|e| e.into_inner());
+            // Rebuilt from scratch each poll, so an order that left the level,
+            // expired, or was extended out of its window drops out on its own.
+            let mut in_window = HashMap::with_capacity(warned.len());
+            for order in orders {
+                let Some(expiry) = order.expires_at().map(TimestampMs::as_u64) else {
+                    continue;
+                };
+                let now_ms = now.as_u64();
+                if now_ms >= expiry || now_ms < expiry.saturating_sub(lead_time_ms) {
+                    continue;
+                }
+                if warned.get(&order.id()) != Some(&expiry) {
+                    raised.push(ExpiryWarning {
+                        order: Arc::clone(&order),
+                        expires_at: TimestampMs::new(expiry),
+                        now,
+                    });
+                }
+                in_window.insert(order.id(), expiry);
+            }
+            *warned = in_window;
+        }
+
+        for warning in &raised {
+            callback(warning);
+        }
+        raised.len()
+    }
+}
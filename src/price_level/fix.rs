@@ -0,0 +1,291 @@
+//! FIX 4.4 market data message bodies for price level content and changes.
+//!
+//! Renders `MarketDataSnapshotFullRefresh` (`35=W`) and
+//! `MarketDataIncrementalRefresh` (`35=X`) bodies as `tag=value` fields
+//! separated by [`FIX_SOH`]. A body starts at `35=` and stops before the
+//! trailer: the gateway adds `8=`, `9=`, its session header fields and
+//! `10=`. Rendering is plain string building, so enabling the `fix` feature
+//! adds no dependency.
+
+use crate::errors::PriceLevelError;
+use crate::execution::Trade;
+use crate::orders::{Id, Side};
+use crate::price_level::level::PriceLevel;
+use crate::price_level::market_data::{MboRecord, MbpRecord};
+use crate::price_level::snapshot::PriceLevelSnapshot;
+use crate::utils::{Price, Quantity};
+use std::fmt::Write;
+
+/// The FIX field delimiter.
+pub const FIX_SOH: char = '\u{1}';
+
+/// `MDUpdateAction` (tag 279) of an incremental refresh entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FixUpdateAction {
+    /// `0` — the entry is added.
+    New,
+    /// `1` — the entry's size changed.
+    Change,
+    /// `2` — the entry is removed.
+    Delete,
+}
+
+impl FixUpdateAction {
+    /// The tag 279 value.
+    #[must_use]
+    pub fn code(self) -> char {
+        match self {
+            Self::New => '0',
+            Self::Change => '1',
+            Self::Delete => '2',
+        }
+    }
+}
+
+/// `MDEntryType` (tag 269) of an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FixEntryType {
+    /// `0` — resting buy depth.
+    Bid,
+    /// `1` — resting sell depth.
+    Offer,
+    /// `2` — an execution.
+    Trade,
+}
+
+impl FixEntryType {
+    /// The book entry type for depth resting on `side`.
+    #[must_use]
+    pub fn for_side(side: Side) -> Self {
+        match side {
+            Side::Buy => Self::Bid,
+            Side::Sell => Self::Offer,
+        }
+    }
+
+    /// The tag 269 value.
+    #[must_use]
+    pub fn code(self) -> char {
+        match self {
+            Self::Bid => '0',
+            Self::Offer => '1',
+            Self::Trade => '2',
+        }
+    }
+}
+
+/// One repeating-group entry of a market data message.
+///
+/// Optional fields are emitted only when set: `entry_id` as `MDEntryID`
+/// (278), `position` as `MDEntryPositionNo` (290) and `order_count` as
+/// `NumberOfOrders` (346).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixMdEntry {
+    /// Tag 279; ignored by a full refresh.
+    pub update_action: FixUpdateAction,
+    /// Tag 269.
+    pub entry_type: FixEntryType,
+    /// Tag 270.
+    pub price: Price,
+    /// Tag 271.
+    pub size: Quantity,
+    /// Tag 278: the order id of an order-level entry, or the trade id.
+    pub entry_id: Option<Id>,
+    /// Tag 290: the 1-based queue position of an order-level entry.
+    pub position: Option<u64>,
+    /// Tag 346: the order count of a price-level entry.
+    pub order_count: Option<u64>,
+}
+
+impl FixMdEntry {
+    /// A new order-level entry for a market-by-order record, at its queue
+    /// position.
+    #[must_use]
+    pub fn from_mbo(record: &MboRecord) -> Self {
+        Self {
+            update_action: FixUpdateAction::New,
+            entry_type: FixEntryType::for_side(record.side),
+            price: record.price,
+            size: record.size,
+            entry_id: Some(record.order_id),
+            position: Some(record.priority.saturating_add(1)),
+            order_count: None,
+        }
+    }
+
+    /// A new price-level entry for a market-by-price record.
+    #[must_use]
+    pub fn from_mbp(record: &MbpRecord) -> Self {
+        Self {
+            update_action: FixUpdateAction::New,
+            entry_type: FixEntryType::for_side(record.side),
+            price: record.price,
+            size: record.size,
+            entry_id: None,
+            position: None,
+            order_count: Some(record.order_count),
+        }
+    }
+
+    /// A trade entry, identified by the trade id.
+    #[must_use]
+    pub fn from_trade<T>(trade: &Trade<T>) -> Self {
+        Self {
+            update_action: FixUpdateAction::New,
+            entry_type: FixEntryType::Trade,
+            price: trade.price(),
+            size: trade.quantity(),
+            entry_id: Some(trade.trade_id()),
+            position: None,
+            order_count: None,
+        }
+    }
+
+    /// The same entry with `action` as its tag 279.
+    #[must_use]
+    pub fn with_update_action(mut self, action: FixUpdateAction) -> Self {
+        self.update_action = action;
+        self
+    }
+
+    fn write_fields(&self, out: &mut String) {
+        field(out, 269, self.entry_type.code());
+        field(out, 270, self.price);
+        field(out, 271, self.size);
+        if let Some(entry_id) = self.entry_id {
+            field(out, 278, entry_id);
+        }
+        if let Some(position) = self.position {
+            field(out, 290, position);
+        }
+        if let Some(order_count) = self.order_count {
+            field(out, 346, order_count);
+        }
+    }
+}
+
+/// Renders a `MarketDataSnapshotFullRefresh` (`35=W`) body for `symbol`
+/// carrying `entries` in order. Each entry's update action is ignored.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is empty or
+/// contains [`FIX_SOH`].
+pub fn fix_full_refresh(symbol: &str, entries: &[FixMdEntry]) -> Result<String, PriceLevelError> {
+    check_symbol(symbol)?;
+    let mut out = String::new();
+    field(&mut out, 35, 'W');
+    field(&mut out, 55, symbol);
+    field(&mut out, 268, entries.len());
+    for entry in entries {
+        entry.write_fields(&mut out);
+    }
+    Ok(out)
+}
+
+/// Renders a `MarketDataIncrementalRefresh` (`35=X`) body carrying
+/// `entries` in order, each tagged with `symbol`.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is empty or
+/// contains [`FIX_SOH`].
+pub fn fix_incremental_refresh(
+    symbol: &str,
+    entries: &[FixMdEntry],
+) -> Result<String, PriceLevelError> {
+    check_symbol(symbol)?;
+    let mut out = String::new();
+    field(&mut out, 35, 'X');
+    field(&mut out, 268, entries.len());
+    for entry in entries {
+        field(&mut out, 279, entry.update_action.code());
+        field(&mut out, 55, symbol);
+        entry.write_fields(&mut out);
+    }
+    Ok(out)
+}
+
+impl<T: Clone> PriceLevelSnapshot<T> {
+    /// Renders the snapshot's displayed orders as a full refresh for
+    /// `symbol`, one order-level entry per [`MboRecord`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is empty or
+    /// contains [`FIX_SOH`].
+    pub fn to_fix_full_refresh(&self, symbol: &str) -> Result<String, PriceLevelError> {
+        let entries: Vec<FixMdEntry> = MboRecord::from_snapshot(self)
+            .iter()
+            .map(FixMdEntry::from_mbo)
+            .collect();
+        fix_full_refresh(symbol, &entries)
+    }
+}
+
+impl<T: Clone> PriceLevel<T> {
+    /// Renders the level's displayed orders ([`Self::mbo_records`]) as a full
+    /// refresh for `symbol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is empty or
+    /// contains [`FIX_SOH`].
+    pub fn to_fix_full_refresh(&self, symbol: &str) -> Result<String, PriceLevelError> {
+        self.public_snapshot().to_fix_full_refresh(symbol)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> crate::price_level::events::PriceLevelEvent<T> {
+    /// The incremental refresh entry for this event: an admission is a `New`
+    /// order entry, an update, stop activation or replacement a `Change` of
+    /// the order now resting, a removal a `Delete`, a trade a `New` trade
+    /// entry and a bust a `Delete` of the busted trade's entry. Order entries
+    /// carry the order's displayed size and no queue position.
+    #[must_use]
+    pub fn to_fix_entry(&self) -> FixMdEntry
+    where
+        T: Clone,
+    {
+        use crate::price_level::events::PriceLevelEvent;
+
+        let (action, order) = match self {
+            PriceLevelEvent::OrderAdded(order) => (FixUpdateAction::New, order),
+            PriceLevelEvent::OrderUpdated(order) | PriceLevelEvent::OrderActivated(order) => {
+                (FixUpdateAction::Change, order)
+            }
+            PriceLevelEvent::OrderRemoved(order) => (FixUpdateAction::Delete, order),
+            PriceLevelEvent::OrderReplaced { replacement, .. } => {
+                (FixUpdateAction::Change, replacement)
+            }
+            PriceLevelEvent::Trade(trade) => return FixMdEntry::from_trade(trade),
+            PriceLevelEvent::TradeBusted { trade, .. } => {
+                return FixMdEntry::from_trade(trade).with_update_action(FixUpdateAction::Delete);
+            }
+        };
+        FixMdEntry {
+            update_action: action,
+            entry_type: FixEntryType::for_side(order.side()),
+            price: order.price(),
+            size: order.visible_quantity(),
+            entry_id: Some(order.id()),
+            position: None,
+            order_count: None,
+        }
+    }
+}
+
+fn field(out: &mut String, tag: u32, value: impl std::fmt::Display) {
+    // Writing to a `String` cannot fail.
+    let _ = write!(out, "{tag}={value}{FIX_SOH}");
+}
+
+fn check_symbol(symbol: &str) -> Result<(), PriceLevelError> {
+    if symbol.is_empty() || symbol.contains(FIX_SOH) {
+        return Err(PriceLevelError::InvalidOperation {
+            message: format!("invalid FIX symbol: {symbol:?}"),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use crate::orders::Id;
+use crate::price_level::level::PriceLevel;
+use crate::price_level::snapshot::PriceLevelSnapshot;
+use crate::utils::TimestampMs;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+
+/// The state a [`LevelFork`] copies its queue from: a consistent snapshot of
+/// the forked level and the per-order records a snapshot does not carry.
+///
+/// Shared, never mutated: every pending fork of the same state holds the same
+/// `Arc`.
+pub(crate) struct ForkBase<T> {
+    /// The orders, counters and statistics at the fork point.
+    pub(crate) snapshot: Arc<PriceLevelSnapshot<T>>,
+    /// Executed quantity of partially filled orders.
+    pub(crate) executed: Vec<(Id, u64)>,
+    /// When each withheld order is displayed.
+    pub(crate) display_delays: Vec<(Id, TimestampMs)>,
+    /// The order each replacement was made from.
+    pub(crate) replaced_from: Vec<(Id, Id)>,
+}
+
+/// A copy-on-write fork of a [`PriceLevel`], from [`PriceLevel::fork`], for
+/// a backtester exploring scenarios from one book state.
+///
+/// Taking a fork copies no orders. It holds a level with the forked level's
+/// configuration, halt, price band, stamping, counters and statistics but an
+/// empty queue, and shares the forked level's
+/// [consistent snapshot](PriceLevel::snapshot_consistent), which itself is
+/// shared with the level while the level does not mutate. The queue is copied
+/// from the snapshot on the first call to [`Self::level`], the only way to
+/// mutate the fork; the reads below answer from the counters and the shared
+/// snapshot without it. Forking a fork that has not been written shares the
+/// same snapshot again, so a tree of scenarios pays for a queue only in the
+/// branches it trades on.
+///
+/// Orders are immutable, so a copied queue shares them with the level rather
+/// than cloning them: a match or amend replaces an order with a new one on
+/// its own side only. The runtime wiring stays with the level: the fork has
+/// no event subscribers, execution channels, rate limiter, expiry warning,
+/// amend history, quarantine or outstanding
+/// [reservations](PriceLevel::reserve).
+///
+/// ```rust
+/// use pricelevel::{
+///     Hash32, Id, OrderType, Price, PriceLevel, Quantity, SequentialIdGenerator, Side,
+///     TakerKind, TimeInForce, TimestampMs,
+/// };
+///
+/// let level: PriceLevel = PriceLevel::new(10_000);
+/// level
+///     .add_order(OrderType::Standard {
+///         id: Id::from_u64(1),
+///         price: Price::new(10_000),
+///         quantity: Quantity::new(10),
+///         side: Side::Sell,
+///         user_id: Hash32::zero(),
+///         timestamp: TimestampMs::new(1_000),
+///         time_in_force: TimeInForce::Gtc,
+///         extra_fields: (),
+///     })
+///     .unwrap();
+///
+/// let fork = level.fork();
+/// assert_eq!(fork.visible_quantity(), 10);
+/// assert!(!fork.is_copied());
+///
+/// fork.level().match_order(
+///     4,
+///     Id::from_u64(2),
+///     TimeInForce::Ioc,
+///     TakerKind::Standard,
+///     TimestampMs::new(2_000),
+///     &SequentialIdGenerator::new(0),
+/// );
+/// assert!(fork.is_copied());
+/// assert_eq!(fork.visible_quantity(), 6);
+/// assert_eq!(level.visible_quantity(), 10);
+/// ```
+pub struct LevelFork<T = ()> {
+    /// The fork's level; its queue is empty until `copied` is set.
+    level: PriceLevel<T>,
+    /// The state the queue is copied from, taken by the copy.
+    base: Mutex<Option<Arc<ForkBase<T>>>>,
+    /// Set once the queue has been copied.
+    copied: OnceLock<()>,
+}
+
+impl<T> LevelFork<T>
+where
+    T: Clone + Send + Sync,
+{
+    /// A fork of `base` over `level`, a level with `base`'s counters and an
+    /// empty queue.
+    pub(crate) fn new(level: PriceLevel<T>, base: Arc<ForkBase<T>>) -> Self {
+        Self {
+            level,
+            base: Mutex::new(Some(base)),
+            copied: OnceLock::new(),
+        }
+    }
+
+    /// The fork's level, to match, add, cancel or reconfigure.
+    ///
+    /// The first call copies the queue from the shared snapshot, in time
+    /// linear in the depth at the fork point; later calls return the copy.
+    pub fn level(&self) -> &PriceLevel<T> {
+        self.copied.get_or_init(|| {
+            if let Some(base) = self.lock_base().take() {
+                self.level.fill_fork(&base);
+            }
+        });
+        &self.level
+    }
+
+    /// The fork's level, copying the queue first if no write has yet.
+    #[must_use]
+    pub fn into_level(self) -> PriceLevel<T> {
+        self.level();
+        self.level
+    }
+
+    /// Whether the queue has been copied, by a call to [`Self::level`].
+    #[must_use]
+    pub fn is_copied(&self) -> bool {
+        self.copied.get().is_some()
+    }
+
+    /// A fork of this fork. While this fork has not been written, the new one
+    /// shares its snapshot and copies nothing either.
+    #[must_use]
+    pub fn fork(&self) -> LevelFork<T> {
+        if !self.is_copied()
+            && let Some(base) = self.lock_base().clone()
+        {
+            return LevelFork::new(self.level.fork_shell(&base.snapshot), base);
+        }
+        self.level().fork()
+    }
+
+    /// The fork's orders and aggregates: the shared snapshot of the fork
+    /// point until the queue is copied, then a consistent snapshot of the
+    /// fork's own level.
+    #[must_use]
+    pub fn snapshot(&self) -> Arc<PriceLevelSnapshot<T>> {
+        if !self.is_copied()
+            && let Some(base) = self.lock_base().as_ref()
+        {
+            return Arc::clone(&base.snapshot);
+        }
+        self.level().snapshot_consistent()
+    }
+
+    /// The fork's displayed quantity.
+    #[must_use]
+    pub fn visible_quantity(&self) -> u64 {
+        self.level.visible_quantity()
+    }
+
+    /// The fork's hidden quantity.
+    #[must_use]
+    pub fn hidden_quantity(&self) -> u64 {
+        self.level.hidden_quantity()
+    }
+
+    /// The number of orders resting at the fork.
+    #[must_use]
+    pub fn order_count(&self) -> usize {
+        self.level.order_count()
+    }
+
+    /// The fork's [`PriceLevel::mutation_sequence`].
+    #[must_use]
+    pub fn mutation_sequence(&self) -> u64 {
+        self.level.mutation_sequence()
+    }
+
+    // A poisoned lock only means a copy panicked midway; the `Option` it
+    // guards is still whole.
+    fn lock_base(&self) -> MutexGuard<'_, Option<Arc<ForkBase<T>>>> {
+        self.base.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// Not derived: the level's `Debug` would demand `T: Debug`.
+impl<T: Clone> fmt::Debug for LevelFork<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LevelFork")
+            .field("price", &self.level.price())
+            .field("copied", &self.copied.get().is_some())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,121 @@
+/// What a frozen level still accepts while its trading is halted; see
+/// [`PriceLevel::freeze_with`](crate::PriceLevel::freeze_with).
+///
+/// A frozen level never matches: every taker is rejected. Order entry is
+/// configurable, so one policy models a regulatory halt that accepts nothing
+/// and another an auction call that keeps collecting orders. Each message
+/// type is accepted unless disabled; a refused one fails with
+/// [`PriceLevelError::TradingHalted`](crate::PriceLevelError::TradingHalted).
+///
+/// ```rust
+/// use pricelevel::{HaltPolicy, PriceLevel};
+///
+/// let level: PriceLevel = PriceLevel::new(10_000);
+/// level.freeze_with(HaltPolicy::new().with_adds(false));
+/// assert!(level.is_frozen());
+/// assert!(!level.halt_policy().unwrap().allows_adds());
+///
+/// level.unfreeze();
+/// assert_eq!(level.halt_policy(), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HaltPolicy {
+    adds: bool,
+    cancels: bool,
+    amends: bool,
+}
+
+/// Bit of an encoded policy marking the level frozen; zero is not frozen.
+const FROZEN: u8 = 1;
+const ADDS: u8 = 1 << 1;
+const CANCELS: u8 = 1 << 2;
+const AMENDS: u8 = 1 << 3;
+
+impl HaltPolicy {
+    /// A halt that stops matching only: adds, cancels and amends are still
+    /// accepted.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            adds: true,
+            cancels: true,
+            amends: true,
+        }
+    }
+
+    /// A halt that accepts nothing: no add, cancel or amend.
+    #[must_use]
+    pub const fn closed() -> Self {
+        Self {
+            adds: false,
+            cancels: false,
+            amends: false,
+        }
+    }
+
+    /// The same policy, accepting admissions or not.
+    #[must_use]
+    pub const fn with_adds(mut self, allowed: bool) -> Self {
+        self.adds = allowed;
+        self
+    }
+
+    /// The same policy, accepting cancels or not.
+    #[must_use]
+    pub const fn with_cancels(mut self, allowed: bool) -> Self {
+        self.cancels = allowed;
+        self
+    }
+
+    /// The same policy, accepting amends (resizes, reprices and replaces)
+    /// or not.
+    #[must_use]
+    pub const fn with_amends(mut self, allowed: bool) -> Self {
+        self.amends = allowed;
+        self
+    }
+
+    /// Whether admissions are accepted.
+    #[must_use]
+    pub const fn allows_adds(&self) -> bool {
+        self.adds
+    }
+
+    /// Whether cancels are accepted.
+    #[must_use]
+    pub const fn allows_cancels(&self) -> bool {
+        self.cancels
+    }
+
+    /// Whether amends are accepted.
+    #[must_use]
+    pub const fn allows_amends(&self) -> bool {
+        self.amends
+    }
+
+    /// The policy packed into one byte for an atomic, never zero.
+    pub(crate) const fn encode(self) -> u8 {
+        FROZEN
+            | if self.adds { ADDS } else { 0 }
+            | if self.cancels { CANCELS } else { 0 }
+            | if self.amends { AMENDS } else { 0 }
+    }
+
+    /// The policy [`Self::encode`] packed, or `None` for zero: not frozen.
+    pub(crate) const fn decode(bits: u8) -> Option<Self> {
+        if bits & FROZEN == 0 {
+            return None;
+        }
+        Some(Self {
+            adds: bits & ADDS != 0,
+            cancels: bits & CANCELS != 0,
+            amends: bits & AMENDS != 0,
+        })
+    }
+}
+
+impl Default for HaltPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
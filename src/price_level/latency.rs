@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Sub-buckets per power of two: each bucket spans at most 1/8 of its lower
+/// bound, so a reported latency is within 12.5% of the recorded one.
+const SUB_BUCKET_BITS: u32 = 3;
+pub(crate) const SUB_BUCKETS: u64 = 1 << SUB_BUCKET_BITS;
+
+/// Buckets covering every `u64` nanosecond count: the first `SUB_BUCKETS`
+/// values exactly, then `SUB_BUCKETS` per remaining power of two.
+pub(crate) const BUCKETS: usize = ((64 - SUB_BUCKET_BITS + 1) as usize) * SUB_BUCKETS as usize;
+
+/// A [`PriceLevel`](crate::PriceLevel) operation whose latency is recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyOperation {
+    /// [`PriceLevel::add_order`](crate::PriceLevel::add_order).
+    AddOrder,
+    /// [`PriceLevel::match_order`](crate::PriceLevel::match_order).
+    MatchOrder,
+    /// [`PriceLevel::update_order`](crate::PriceLevel::update_order).
+    UpdateOrder,
+}
+
+impl LatencyOperation {
+    /// Every recorded operation, in a stable order.
+    pub const ALL: [LatencyOperation; 3] = [
+        LatencyOperation::AddOrder,
+        LatencyOperation::MatchOrder,
+        LatencyOperation::UpdateOrder,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for LatencyOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LatencyOperation::AddOrder => write!(f, "add_order"),
+            LatencyOperation::MatchOrder => write!(f, "match_order"),
+            LatencyOperation::UpdateOrder => write!(f, "update_order"),
+        }
+    }
+}
+
+/// One non-empty bucket of a [`LatencyHistogram`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyBucket {
+    /// Smallest latency the bucket holds, in nanoseconds.
+    pub lower_ns: u64,
+    /// Largest latency the bucket holds, in nanoseconds.
+    pub upper_ns: u64,
+    /// Calls whose latency fell in the bucket.
+    pub count: u64,
+}
+
+/// Per-call latency of one operation, as returned by
+/// `PriceLevelStatistics::latency_histogram`.
+///
+/// Latencies are bucketed HDR-style: exact below 8ns, then eight buckets per
+/// power of two, so a percentile is reported to within 12.5%. Only non-empty
+/// buckets are listed, in ascending order, which keeps the serialized form
+/// small for dashboards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    /// The operation measured.
+    pub operation: LatencyOperation,
+    /// Calls recorded.
+    pub count: u64,
+    /// Sum of the recorded latencies, in nanoseconds (saturating).
+    pub sum_ns: u64,
+    /// Smallest recorded latency, in nanoseconds; `0` when empty.
+    pub min_ns: u64,
+    /// Largest recorded latency, in nanoseconds; `0` when empty.
+    pub max_ns: u64,
+    /// The non-empty buckets, ascending.
+    pub buckets: Vec<LatencyBucket>,
+}
+
+impl LatencyHistogram {
+    /// Mean latency, or `None` when nothing was recorded.
+    #[must_use]
+    pub fn mean(&self) -> Option<Duration> {
+        self.sum_ns
+            .checked_div(self.count)
+            .map(Duration::from_nanos)
+    }
+
+    /// Latency at quantile `q` (`0.0..=1.0`, clamped): the upper bound of the
+    /// bucket holding the `q`-th call, capped at [`Self::max_ns`]. `None` when
+    /// nothing was recorded.
+    #[must_use]
+    pub fn percentile(&self, q: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let q = if q.is_nan() { 0.0 } else { q.clamp(0.0, 1.0) };
+        let rank = ((q * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0_u64;
+        for bucket in &self.buckets {
+            seen = seen.saturating_add(bucket.count);
+            if seen >= rank {
+                return Some(Duration::from_nanos(bucket.upper_ns.min(self.max_ns)));
+            }
+        }
+        Some(Duration::from_nanos(self.max_ns))
+    }
+}
+
+/// Bucket index of a latency of `ns` nanoseconds.
+pub(crate) fn bucket_of(ns: u64) -> usize {
+    if ns < SUB_BUCKETS {
+        return ns as usize;
+    }
+    let exponent = 63 - ns.leading_zeros();
+    let sub = (ns >> (exponent - SUB_BUCKET_BITS)) & (SUB_BUCKETS - 1);
+    ((exponent - SUB_BUCKET_BITS + 1) as usize) * SUB_BUCKETS as usize + sub as usize
+}
+
+/// Inclusive nanosecond bounds of bucket `index`.
+pub(crate) fn bucket_bounds(index: usize) -> (u64, u64) {
+    let sub_buckets = SUB_BUCKETS as usize;
+    if index < sub_buckets {
+        return (index as u64, index as u64);
+    }
+    let exponent = (index / sub_buckets) as u32 + SUB_BUCKET_BITS - 1;
+    let sub = (index % sub_buckets) as u64;
+    let width = 1_u64 << (exponent - SUB_BUCKET_BITS);
+    let lower = (1_u64 << exponent) + sub * width;
+    (lower, lower + (width - 1))
+}
+
+/// Lock-free histogram of one operation.
+#[derive(Debug)]
+struct AtomicHistogram {
+    buckets: Box<[AtomicU64]>,
+    sum_ns: AtomicU64,
+    min_ns: AtomicU64,
+    max_ns: AtomicU64,
+}
+
+impl AtomicHistogram {
+    fn new() -> Self {
+        Self {
+            buckets: (0..BUCKETS).map(|_| AtomicU64::new(0)).collect(),
+            sum_ns: AtomicU64::new(0),
+            min_ns: AtomicU64::new(u64::MAX),
+            max_ns: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, ns: u64) {
+        self.buckets[bucket_of(ns)].fetch_add(1, Ordering::Relaxed);
+        // `fetch_update` with an always-`Some` closure cannot fail.
+        let _ = self
+            .sum_ns
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |sum| {
+                Some(sum.saturating_add(ns))
+            });
+        self.min_ns.fetch_min(ns, Ordering::Relaxed);
+        self.max_ns.fetch_max(ns, Ordering::Relaxed);
+    }
+
+    fn clear(&self) {
+        for bucket in self.buckets.iter() {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.sum_ns.store(0, Ordering::Relaxed);
+        self.min_ns.store(u64::MAX, Ordering::Relaxed);
+        self.max_ns.store(0, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self, operation: LatencyOperation) -> LatencyHistogram {
+        let buckets: Vec<LatencyBucket> = self
+            .buckets
+            .iter()
+            .enumerate()
+            .filter_map(|(index, bucket)| {
+                let count = bucket.load(Ordering::Relaxed);
+                (count > 0).then(|| {
+                    let (lower_ns, upper_ns) = bucket_bounds(index);
+                    LatencyBucket {
+                        lower_ns,
+                        upper_ns,
+                        count,
+                    }
+                })
+            })
+            .collect();
+        // The count is the bucket total, so percentiles over a snapshot taken
+        // mid-recording stay within the listed buckets.
+        let count = buckets.iter().map(|bucket| bucket.count).sum();
+        let max_ns = self.max_ns.load(Ordering::Relaxed);
+        LatencyHistogram {
+            operation,
+            count,
+            sum_ns: self.sum_ns.load(Ordering::Relaxed),
+            min_ns: if count == 0 {
+                0
+            } else {
+                self.min_ns.load(Ordering::Relaxed).min(max_ns)
+            },
+            max_ns,
+            buckets,
+        }
+    }
+}
+
+/// Per-operation latency histograms kept by a level's statistics.
+///
+/// Off until enabled; the buckets are allocated on first enable, so a level
+/// that never records latency pays one flag load per call. Runtime
+/// observability, like the statistics window: never cloned or serialized.
+#[derive(Debug, Default)]
+pub(crate) struct LatencyRecorder {
+    enabled: AtomicBool,
+    histograms: OnceLock<[AtomicHistogram; 3]>,
+}
+
+impl LatencyRecorder {
+    pub(crate) fn set_enabled(&self, enabled: bool) {
+        if enabled {
+            self.histograms.get_or_init(|| {
+                [
+                    AtomicHistogram::new(),
+                    AtomicHistogram::new(),
+                    AtomicHistogram::new(),
+                ]
+            });
+        }
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Starts timing one call of `operation`, or does nothing when disabled.
+    pub(crate) fn start(&self, operation: LatencyOperation) -> LatencyTimer<'_> {
+        LatencyTimer {
+            recorder: self,
+            operation,
+            started: self.is_enabled().then(Instant::now),
+        }
+    }
+
+    fn record(&self, operation: LatencyOperation, elapsed: Duration) {
+        if let Some(histograms) = self.histograms.get() {
+            let ns = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+            histograms[operation.index()].record(ns);
+        }
+    }
+
+    pub(crate) fn histogram(&self, operation: LatencyOperation) -> Option<LatencyHistogram> {
+        if !self.is_enabled() {
+            return None;
+        }
+        self.histograms
+            .get()
+            .map(|histograms| histograms[operation.index()].snapshot(operation))
+    }
+
+    pub(crate) fn clear(&self) {
+        if let Some(histograms) = self.histograms.get() {
+            for histogram in histograms {
+                histogram.clear();
+            }
+        }
+    }
+}
+
+/// Times one call while alive, recording it on drop so every return path of
+/// the timed method is covered.
+pub(crate) struct LatencyTimer<'a> {
+    recorder: &'a LatencyRecorder,
+    operation: LatencyOperation,
+    started: Option<Instant>,
+}
+
+impl Drop for LatencyTimer<'_> {
+    fn drop(&mut self) {
+        if let Some(started) = self.started {
+            self.recorder.record(self.operation, started.elapsed());
+        }
+    }
+}
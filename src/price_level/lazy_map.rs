@@ -0,0 +1,82 @@
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use dashmap::mapref::multiple::RefMulti;
+use dashmap::mapref::one::Ref;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::OnceLock;
+
+/// A `DashMap` allocated on its first insertion.
+///
+/// A `DashMap` allocates its shards when it is built, so a level keeping
+/// several side maps that most levels never write would pay for all of them
+/// up front. Reads of a map never written see it empty and allocate nothing;
+/// once allocated, the map stays until the level is dropped.
+pub(crate) struct LazyMap<K, V>(OnceLock<DashMap<K, V>>);
+
+impl<K, V> Default for LazyMap<K, V> {
+    fn default() -> Self {
+        Self(OnceLock::new())
+    }
+}
+
+// Not derived: `DashMap`'s `Debug` demands `K: Eq + Hash`.
+impl<K, V> fmt::Debug for LazyMap<K, V>
+where
+    K: Eq + Hash + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("LazyMap").field(&self.0).finish()
+    }
+}
+
+impl<K, V> LazyMap<K, V>
+where
+    K: Eq + Hash,
+{
+    /// Whether the map has been allocated, by a first insertion.
+    #[cfg(test)]
+    pub(crate) fn is_allocated(&self) -> bool {
+        self.0.get().is_some()
+    }
+
+    /// The map, allocating it if this is its first write.
+    fn map(&self) -> &DashMap<K, V> {
+        self.0.get_or_init(DashMap::new)
+    }
+
+    pub(crate) fn insert(&self, key: K, value: V) -> Option<V> {
+        self.map().insert(key, value)
+    }
+
+    pub(crate) fn entry(&self, key: K) -> Entry<'_, K, V> {
+        self.map().entry(key)
+    }
+
+    pub(crate) fn get(&self, key: &K) -> Option<Ref<'_, K, V>> {
+        self.0.get()?.get(key)
+    }
+
+    pub(crate) fn contains_key(&self, key: &K) -> bool {
+        self.0.get().is_some_and(|map| map.contains_key(key))
+    }
+
+    pub(crate) fn remove(&self, key: &K) -> Option<(K, V)> {
+        self.0.get()?.remove(key)
+    }
+
+    pub(crate) fn remove_if(&self, key: &K, f: impl FnOnce(&K, &V) -> bool) -> Option<(K, V)> {
+        self.0.get()?.remove_if(key, f)
+    }
+
+    pub(crate) fn retain(&self, f: impl FnMut(&K, &mut V) -> bool) {
+        if let Some(map) = self.0.get() {
+            map.retain(f);
+        }
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = RefMulti<'_, K, V>> {
+        self.0.get().into_iter().flat_map(DashMap::iter)
+    }
+}
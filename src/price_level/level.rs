@@ -1,18 +1,64 @@
 //! Core price level implementation
 
-use crate::UuidGenerator;
-use crate::errors::PriceLevelError;
+use crate::ExecutionIdGenerator;
+use crate::errors::{CapacityLimit, PriceLevelError};
 use crate::execution::{MatchResult, TakerKind, Trade};
-use crate::orders::{Id, OrderType, OrderUpdate, Side, TimeInForce};
-use crate::price_level::order_queue::{FrontAction, FrontOutcome, OrderQueue, UpdateDecision};
-use crate::price_level::{PriceLevelSnapshot, PriceLevelSnapshotPackage, PriceLevelStatistics};
-use crate::utils::{Price, Quantity, TimestampMs};
+use crate::orders::{
+    DefaultReplenishment, Id, OrderFillState, OrderRefresh, OrderReplacement, OrderStatus,
+    OrderStatusTransition, OrderType, OrderUpdate, RefreshPriority, Replenishment,
+    ReplenishmentContext, ReplenishmentStrategy, Side, TimeInForce, UpdateOutcome,
+};
+use crate::parse::{ParseOptions, RecordFields};
+use crate::price_level::amend_history::{
+    AmendHistory, AmendRecord, DEFAULT_AMEND_HISTORY_CAPACITY,
+};
+use crate::price_level::config::PriceLevelConfig;
+use crate::price_level::consistent::ConsistentView;
+use crate::price_level::crossing::CrossingRule;
+#[cfg(feature = "async")]
+use crate::price_level::events::{DEFAULT_EVENT_CAPACITY, EventSubscribers, PriceLevelEvent};
+use crate::price_level::execution_channel::{
+    DEFAULT_EXECUTION_CHANNEL_CAPACITY, ExecutionChannels, ExecutionOverflow,
+};
+use crate::price_level::expiry::ExpiryWarnings;
+use crate::price_level::fork::{ForkBase, LevelFork};
+use crate::price_level::halt::HaltPolicy;
+use crate::price_level::latency::LatencyOperation;
+use crate::price_level::lazy_map::LazyMap;
+use crate::price_level::limits::LevelLimits;
+use crate::price_level::market_data::{MboRecord, MbpRecord};
+use crate::price_level::match_plan::{MatchPlan, PlannedFill};
+use crate::price_level::order_queue::{
+    FrontAction, FrontOutcome, FrontScan, OrderQueue, UpdateDecision,
+};
+use crate::price_level::policy::{HiddenPriority, MatchingPolicy};
+#[cfg(feature = "pool")]
+use crate::price_level::pool::OrderPool;
+use crate::price_level::price_band::{PriceBand, PriceBandHook};
+use crate::price_level::priority_tiers::PriorityTiers;
+use crate::price_level::queue_audit::{QueueAudit, QueuePosition};
+use crate::price_level::rate_limit::{RateLimitHook, RateLimitedOperation, RateLimiter};
+use crate::price_level::reservation::{DEFAULT_RESERVATION_TIMEOUT_MS, ReservationGuard};
+use crate::price_level::scratch::{MatchScratch, ScratchSlot};
+use crate::price_level::sequenced::Sequenced;
+use crate::price_level::session::SessionTagged;
+use crate::price_level::statistics_alerts::DeferredAlerts;
+use crate::price_level::symbol_spec::SymbolSpec;
+use crate::price_level::{
+    ChecksumAlgorithm, ExpiryWarning, PriceLevelSnapshot, PriceLevelSnapshotPackage,
+    PriceLevelStatistics, QuarantineReason, QuarantinedOrder,
+};
+use crate::utils::{Clock, Price, Quantity, TimestampMs};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use std::str::FromStr;
 
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, RwLock};
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
 
 /// Bit layout of the [`PriceLevel::topology`] word (issue #126): the high two
 /// bits carry the pinned-side tag, the low bits the resting-order count. Packing
@@ -64,6 +110,50 @@ mod topology {
     }
 }
 
+/// Walks [`PriceLevel::snapshot_consistent`] attempts before settling for the
+/// last view it published.
+const CONSISTENT_SNAPSHOT_ATTEMPTS: u32 = 64;
+
+/// Trailing window the fill estimates read the execution rate over: the
+/// longest the statistics keep.
+const FILL_ESTIMATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// The exclusive side of [`PriceLevel::fok_guard`], counted in
+/// [`PriceLevel::exclusive_sections`] on the way in (by
+/// [`PriceLevel::fok_write`]) and on the way out (here, before the lock is
+/// released).
+struct ExclusiveSection<'a> {
+    _guard: RwLockWriteGuard<'a, ()>,
+    sections: &'a AtomicU64,
+}
+
+impl Drop for ExclusiveSection<'_> {
+    fn drop(&mut self) {
+        self.sections.fetch_add(1, Ordering::Release);
+    }
+}
+
+/// The shared side of [`PriceLevel::fok_guard`] as a mutator holds it,
+/// counted in [`PriceLevel::shared_mutators`] from entry (by
+/// [`PriceLevel::fok_read_mutator`]) until it is dropped.
+struct SharedSection<'a> {
+    _guard: RwLockReadGuard<'a, ()>,
+    mutators: &'a AtomicU64,
+}
+
+impl Drop for SharedSection<'_> {
+    fn drop(&mut self) {
+        self.mutators.fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// The side of [`PriceLevel::fok_guard`] a mutator holds: the shared side
+/// normally, the exclusive side while [`PriceLevel::sequencing`] is on.
+enum MutatorSection<'a> {
+    Shared { _section: SharedSection<'a> },
+    Exclusive { _section: ExclusiveSection<'a> },
+}
+
 // Deterministic race seam for the post-only decision boundary (issue #130).
 //
 // `match_order` fires `fire_post_only_decision_hook` BETWEEN the post-only depth
@@ -113,6 +203,51 @@ fn fire_post_only_decision_hook() {
     }
 }
 
+// Deterministic race seam for the fill commit. The sweep fires
+// `fire_fill_commit_hook` after a fill is committed in the queue and before
+// the mutation sequence covers it, so a test can read the level in that window
+// (e.g. `snapshot_consistent`) without relying on scheduler stress.
+#[cfg(test)]
+thread_local! {
+    static FILL_COMMIT_HOOK: std::cell::RefCell<Option<Box<dyn FnMut()>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Install a hook fired between a fill's queue commit and its sequence bump
+/// (test seam). Returns a guard that clears the hook on drop.
+#[cfg(test)]
+pub(crate) fn set_fill_commit_hook(hook: Box<dyn FnMut()>) -> FillCommitHookGuard {
+    FILL_COMMIT_HOOK.with(|slot| *slot.borrow_mut() = Some(hook));
+    FillCommitHookGuard
+}
+
+/// Clears the fill commit hook when dropped (test seam).
+#[cfg(test)]
+pub(crate) struct FillCommitHookGuard;
+
+#[cfg(test)]
+impl Drop for FillCommitHookGuard {
+    fn drop(&mut self) {
+        FILL_COMMIT_HOOK.with(|slot| *slot.borrow_mut() = None);
+    }
+}
+
+/// Fire the fill commit hook if one is installed (test seam).
+#[cfg(test)]
+fn fire_fill_commit_hook() {
+    // Taken out while firing, as in `fire_post_only_decision_hook`.
+    let hook = FILL_COMMIT_HOOK.with(|slot| slot.borrow_mut().take());
+    if let Some(mut hook) = hook {
+        hook();
+        FILL_COMMIT_HOOK.with(|slot| {
+            let mut slot = slot.borrow_mut();
+            if slot.is_none() {
+                *slot = Some(hook);
+            }
+        });
+    }
+}
+
 /// A price level in a limit order book, lock-free on the match path.
 ///
 /// A `Gtc` / `Ioc` / `Day` match runs entirely on atomic counters and lock-free
@@ -159,7 +294,7 @@ fn fire_post_only_decision_hook() {
 /// side pin / un-pin, and `snapshot` retries its materialization if the epoch
 /// moves under it (see there).
 #[derive(Debug)]
-pub struct PriceLevel {
+pub struct PriceLevel<T = ()> {
     /// The price of this level
     price: u128,
 
@@ -184,7 +319,7 @@ pub struct PriceLevel {
     topology_epoch: AtomicU64,
 
     /// Queue of orders at this price level
-    orders: OrderQueue,
+    orders: OrderQueue<T>,
 
     /// Statistics for this price level
     stats: Arc<PriceLevelStatistics>,
@@ -227,10 +362,226 @@ pub struct PriceLevel {
     /// re-reads it, and retries on change: a stable epoch across the scan means
     /// no mutation committed during it, giving the post-only verdict a
     /// linearization point instead of a torn read.
+    ///
+    /// Every fill the sweep commits bumps it too, so its value names a queue
+    /// state: it is the public [`Self::mutation_sequence`], recorded in
+    /// snapshots and (with [`Self::set_trade_stamping`]) in each trade.
     mutation_epoch: AtomicU64,
+
+    /// Number of times the exclusive side of [`Self::fok_guard`] has been
+    /// entered or left, so it is odd while a fill-or-kill or strict-FIFO
+    /// sweep holds it. [`Self::snapshot_consistent`] reads it around its walk
+    /// instead of taking the guard, so it never delays such a sweep and never
+    /// reports one half done.
+    exclusive_sections: AtomicU64,
+
+    /// Number of mutators inside the shared side of [`Self::fok_guard`]. A
+    /// mutator enters before it changes the queue and leaves only after
+    /// bumping [`Self::mutation_epoch`], so while this is non-zero the queue
+    /// may hold a change no sequence covers yet, and
+    /// [`Self::snapshot_consistent`] discards any walk it overlaps.
+    shared_mutators: AtomicU64,
+
+    /// The level's [`MatchingPolicy`], stored as its `#[repr(u8)]`
+    /// discriminant. Read once (`Relaxed`) at the start of each
+    /// [`Self::match_order`]; it is configuration, not queue state, so it is
+    /// not part of a snapshot and a restored level starts as
+    /// [`MatchingPolicy::Fifo`].
+    matching_policy: AtomicU8,
+
+    /// The level's [`CrossingRule`], stored as its `#[repr(u8)]`
+    /// discriminant. Configuration like the matching policy: not part of a
+    /// snapshot, and a restored level starts as
+    /// [`CrossingRule::OppositeOfMaker`].
+    crossing_rule: AtomicU8,
+
+    /// Highest insertion sequence a [`MatchingPolicy::StrictFifo`] sweep has
+    /// executed. A front maker below it would trade out of arrival order, so
+    /// the strict sweep refuses it (see [`Self::fifo_violations`]).
+    fifo_high_water: AtomicU64,
+
+    /// Number of strict-FIFO sweeps stopped by the sequence check.
+    fifo_violations: AtomicU64,
+
+    /// Whether [`Self::match_order`] stamps each trade with the mutation
+    /// sequence its fill committed at. Configuration, like the matching
+    /// policy: not part of a snapshot, and a restored level starts unstamped.
+    trade_stamping: AtomicBool,
+
+    /// Whether [`Self::match_order`] stamps each trade with the level clock's
+    /// reading. Configuration: a restored level starts unstamped.
+    clock_stamping: AtomicBool,
+
+    /// Whether every mutator takes the exclusive side of [`Self::fok_guard`],
+    /// so mutations commit one at a time in [`Self::mutation_sequence`]
+    /// order. Configuration: a restored level starts unsequenced.
+    sequencing: AtomicBool,
+
+    /// The [`HaltPolicy`] the level is frozen under, encoded, or zero while
+    /// it trades (see [`Self::freeze_with`]). Operational state: a restored
+    /// level starts trading.
+    halt: AtomicU8,
+
+    /// Quantity earmarked by outstanding [`Self::reserve`] guards. Changed
+    /// only by a reservation (in the exclusive section), its release, or the
+    /// commit that consumes it; while non-zero every sweep takes the
+    /// exclusive section so it can honour the earmark.
+    reserved_quantity: AtomicU64,
+
+    /// Outstanding reservations by id, with their quantity and expiry,
+    /// allocated by the first reservation. Held in memory only: a snapshot
+    /// carries no reservation.
+    reservations: LazyMap<u64, (u64, TimestampMs)>,
+
+    /// Source of reservation ids.
+    next_reservation: AtomicU64,
+
+    /// Orders admitted with a display delay that the clock has not yet
+    /// released, keyed by id, with the time each becomes displayed (see
+    /// [`Self::add_order_with_display_delay`]). An entry whose order has since
+    /// left the level is stale and contributes nothing; it is pruned by the
+    /// next [`Self::release_displays`] or re-admission of the id. Allocated
+    /// by the first delayed admission; not part of a snapshot.
+    display_delays: LazyMap<Id, TimestampMs>,
+
+    /// Number of entries in [`Self::display_delays`], so the quantity
+    /// accessors skip the registry walk when no order is withheld.
+    display_delay_count: AtomicUsize,
+
+    /// The id of the order each resting replacement was admitted in place
+    /// of by [`Self::cancel_replace`], keyed by the replacement's id. An
+    /// entry whose order has left the level is stale; it is pruned as the
+    /// order leaves or by the next admission of the id. Allocated by the
+    /// first replacement; not part of a snapshot.
+    replaced_from: LazyMap<Id, Id>,
+
+    /// Number of entries in [`Self::replaced_from`], so an order leaving the
+    /// level skips the map when no replacement rests.
+    replacement_count: AtomicUsize,
+
+    /// Cumulative executed quantity of each resting order that has traded
+    /// at least once, behind [`Self::order_fill_state`]; an entry makes
+    /// [`Self::order_status`] report [`OrderStatus::PartiallyFilled`]. Written
+    /// by the matcher after each committed fill, so a reader racing a match
+    /// may see the order's leaves before its executed quantity catches up.
+    /// An id leaves the map with its order; the map is allocated by the
+    /// first partial fill. Not part of a snapshot: a restored order reports
+    /// nothing executed until its next fill.
+    executed: LazyMap<Id, u64>,
+
+    /// Dead-letter list of malformed makers the match sweep removed instead
+    /// of trading (see [`Self::quarantined_orders`]). Appended only on that
+    /// cold path, so a plain mutex; not part of a snapshot.
+    quarantine: Mutex<Vec<QuarantinedOrder<T>>>,
+
+    /// Lifetime number of quarantined makers, including those already taken
+    /// off [`Self::quarantine`] by [`Self::take_quarantined`].
+    quarantine_count: AtomicU64,
+
+    /// Per-order ring of the updates applied to resting orders, recorded
+    /// only once [`Self::enable_amend_history`] is called (see
+    /// [`Self::order_history`]). An audit trail: not part of a snapshot.
+    amend_history: AmendHistory,
+
+    /// The callback warned ahead of each resting `Gtd` order's expiry (see
+    /// [`Self::set_expiry_warning`]) and the orders it has already been
+    /// called for. Configuration, like the matching policy: not part of a
+    /// snapshot.
+    expiry_warnings: ExpiryWarnings<T>,
+
+    /// The message throttle consulted before each admission, update and
+    /// cancel (see [`Self::set_rate_limiter`]). Configuration: not part of a
+    /// snapshot.
+    rate_limits: RateLimitHook<T>,
+
+    /// The price collar checked before each match (see
+    /// [`Self::set_price_band`]). Configuration: not part of a snapshot.
+    price_band: PriceBandHook,
+
+    /// Number of matches a [`BandAction::Flag`](crate::BandAction::Flag)
+    /// band let through outside it.
+    band_breaches: AtomicU64,
+
+    /// Reusable buffers for the temporaries of [`Self::match_order`] and
+    /// [`Self::matchable_quantity`] (the set-aside set, queue snapshots, the
+    /// allocation plan), so a steady match stream does not allocate them per
+    /// call. Checked out with a non-blocking `try_lock`; a busy slot falls back
+    /// to fresh buffers rather than waiting.
+    scratch: ScratchSlot<T>,
+
+    /// The channels handed out by [`Self::execution_channel`]. Runtime
+    /// wiring, like the expiry hook: not part of a snapshot.
+    executions: ExecutionChannels<T>,
+
+    /// The streams handed out by [`Self::subscribe`]. Runtime wiring, like
+    /// the expiry hook: not part of a snapshot.
+    #[cfg(feature = "async")]
+    events: EventSubscribers<T>,
+
+    /// The last view [`Self::snapshot_consistent`] validated, reused while the
+    /// level does not mutate. A cache of queue state, so not part of a
+    /// snapshot itself.
+    consistent_view: ConsistentView<T>,
+
+    /// Sizes and timestamps iceberg / reserve refreshes in the match sweep,
+    /// set by [`Self::with_config`]. Runtime configuration, like the matching
+    /// policy: never snapshotted.
+    replenishment: Arc<dyn ReplenishmentStrategy>,
+
+    /// Whether a refresh keeps the order's queue slot, set by
+    /// [`Self::with_config`]. Never snapshotted.
+    refresh_priority: RefreshPriority,
+
+    /// Whether hidden orders trade after displayed ones, set by
+    /// [`Self::with_config`]. Never snapshotted.
+    hidden_priority: HiddenPriority,
+
+    /// The participant tiers the FIFO sweep trades in, set by
+    /// [`Self::with_config`]. Never snapshotted.
+    priority_tiers: Option<Arc<dyn PriorityTiers>>,
+
+    /// Tick and lot increments admissions and resizes are validated against,
+    /// set by [`Self::with_config`]. Never snapshotted.
+    symbol_spec: Option<SymbolSpec>,
+
+    /// Capacity limits admissions are checked against, set by
+    /// [`Self::with_config`]. Never snapshotted.
+    limits: Option<LevelLimits>,
+
+    /// Source of the current time for the level and its statistics, set by
+    /// [`Self::with_config`]. Never snapshotted: a restored level reads the
+    /// [`SystemClock`](crate::SystemClock).
+    clock: Arc<dyn Clock>,
+}
+
+/// A level's strategy with the refreshed order's timestamp pinned to its
+/// original one, for a refresh that keeps its queue slot under
+/// [`RefreshPriority::Retain`].
+#[derive(Debug)]
+struct RetainedTimestamp<'a>(&'a dyn ReplenishmentStrategy);
+
+impl ReplenishmentStrategy for RetainedTimestamp<'_> {
+    fn replenish(&self, context: &ReplenishmentContext) -> Replenishment {
+        Replenishment {
+            timestamp: context.order_timestamp,
+            ..self.0.replenish(context)
+        }
+    }
+}
+
+/// One maker step of a dry-run sweep (see [`PriceLevel::dry_sweep`]).
+struct DryStep<'a, T> {
+    /// The maker as it rested before the step.
+    maker: &'a OrderType<T>,
+    /// Quantity the taker took from the maker.
+    consumed: u64,
+    /// Hidden quantity a refresh moved into the maker's displayed tranche.
+    hidden_reduced: u64,
+    /// Whether the step left nothing of the maker resting.
+    fully_consumed: bool,
 }
 
-impl PriceLevel {
+impl<T: Clone> PriceLevel<T> {
     /// Reconstructs a price level directly from a snapshot.
     ///
     /// The rebuilt level carries the per-level statistics persisted in the
@@ -244,10 +595,12 @@ impl PriceLevel {
     /// Returns [`PriceLevelError::InvalidOperation`] if any restored order's own
     /// visible + hidden total overflows `u64`, or if recomputing the snapshot's
     /// level aggregates overflows `u64` — the same per-order and per-level
-    /// invariants [`Self::add_order`] enforces at admission — or
+    /// invariants [`Self::add_order`] enforces at admission. Returns
     /// [`PriceLevelError::DuplicateOrderId`] if the snapshot's orders vector
-    /// repeats an order id.
-    pub fn from_snapshot(mut snapshot: PriceLevelSnapshot) -> Result<Self, PriceLevelError> {
+    /// repeats an order id, and [`PriceLevelError::PriceMismatch`] or
+    /// [`PriceLevelError::SideMismatch`] if an order is off the level's price
+    /// or side.
+    pub fn from_snapshot(mut snapshot: PriceLevelSnapshot<T>) -> Result<Self, PriceLevelError> {
         snapshot.refresh_aggregates()?;
 
         // Reject a snapshot whose orders vector repeats an id. Building the
@@ -260,7 +613,9 @@ impl PriceLevel {
             let mut seen = std::collections::HashSet::with_capacity(orders.len());
             for order in orders {
                 if !seen.insert(order.id()) {
-                    return Err(PriceLevelError::DuplicateOrderId(order.id().to_string()));
+                    return Err(PriceLevelError::DuplicateOrderId {
+                        order_id: order.id(),
+                    });
                 }
             }
         }
@@ -275,21 +630,17 @@ impl PriceLevel {
             let mut level_side = None;
             for order in snapshot.orders() {
                 if order.price().as_u128() != level_price {
-                    return Err(PriceLevelError::InvalidOperation {
-                        message: format!(
-                            "snapshot order price {} does not match level price {level_price}",
-                            order.price().as_u128()
-                        ),
+                    return Err(PriceLevelError::PriceMismatch {
+                        expected: level_price,
+                        actual: order.price().as_u128(),
                     });
                 }
                 match level_side {
                     None => level_side = Some(order.side()),
                     Some(side) if side != order.side() => {
-                        return Err(PriceLevelError::InvalidOperation {
-                            message: format!(
-                                "snapshot order side {:?} is incompatible with the level side {side:?}",
-                                order.side()
-                            ),
+                        return Err(PriceLevelError::SideMismatch {
+                            expected: side,
+                            actual: order.side(),
                         });
                     }
                     Some(_) => {}
@@ -302,8 +653,12 @@ impl PriceLevel {
         let visible_quantity = snapshot.visible_quantity().as_u64();
         let hidden_quantity = snapshot.hidden_quantity().as_u64();
         let price = snapshot.price().as_u128();
+        // Resume the mutation sequence where the snapshot left it, so trades
+        // stamped after the restore keep ordering after the snapshot.
+        let mutation_sequence = snapshot.mutation_sequence();
         // Clone the persisted statistics before consuming the snapshot's orders.
         let stats = (*snapshot.statistics()).clone();
+        let clock = Arc::clone(stats.clock());
         let queue = OrderQueue::from(snapshot.into_orders());
 
         // Pin the restored side alongside the restored count in the topology word
@@ -323,7 +678,44 @@ impl PriceLevel {
             stats: Arc::new(stats),
             fok_guard: RwLock::new(()),
             level_poisoned: AtomicBool::new(false),
-            mutation_epoch: AtomicU64::new(0),
+            mutation_epoch: AtomicU64::new(mutation_sequence),
+            exclusive_sections: AtomicU64::new(0),
+            shared_mutators: AtomicU64::new(0),
+            matching_policy: AtomicU8::new(MatchingPolicy::Fifo as u8),
+            crossing_rule: AtomicU8::new(CrossingRule::OppositeOfMaker as u8),
+            fifo_high_water: AtomicU64::new(0),
+            fifo_violations: AtomicU64::new(0),
+            trade_stamping: AtomicBool::new(false),
+            clock_stamping: AtomicBool::new(false),
+            sequencing: AtomicBool::new(false),
+            halt: AtomicU8::new(0),
+            display_delays: LazyMap::default(),
+            display_delay_count: AtomicUsize::new(0),
+            replaced_from: LazyMap::default(),
+            replacement_count: AtomicUsize::new(0),
+            executed: LazyMap::default(),
+            quarantine: Mutex::new(Vec::new()),
+            quarantine_count: AtomicU64::new(0),
+            amend_history: AmendHistory::default(),
+            expiry_warnings: ExpiryWarnings::default(),
+            rate_limits: RateLimitHook::default(),
+            price_band: PriceBandHook::default(),
+            band_breaches: AtomicU64::new(0),
+            reserved_quantity: AtomicU64::new(0),
+            reservations: LazyMap::default(),
+            next_reservation: AtomicU64::new(0),
+            scratch: ScratchSlot::default(),
+            executions: ExecutionChannels::default(),
+            #[cfg(feature = "async")]
+            events: EventSubscribers::default(),
+            consistent_view: ConsistentView::default(),
+            replenishment: Arc::new(DefaultReplenishment),
+            refresh_priority: RefreshPriority::Requeue,
+            hidden_priority: HiddenPriority::DisplayedFirst,
+            priority_tiers: None,
+            symbol_spec: None,
+            limits: None,
+            clock,
         })
     }
 
@@ -332,7 +724,7 @@ impl PriceLevel {
     /// # Errors
     ///
     /// Returns [`PriceLevelError::ChecksumMismatch`] if the package's embedded
-    /// SHA-256 checksum does not match its payload (tampered or corrupted
+    /// checksum (under its recorded [`ChecksumAlgorithm`]) does not match its payload (tampered or corrupted
     /// snapshot), [`PriceLevelError::SerializationError`] if re-encoding the
     /// payload to recompute that checksum fails,
     /// [`PriceLevelError::InvalidOperation`] if the package carries an
@@ -340,8 +732,11 @@ impl PriceLevel {
     /// [`PriceLevelError`] from rebuilding the level out of the validated
     /// snapshot.
     pub fn from_snapshot_package(
-        package: PriceLevelSnapshotPackage,
-    ) -> Result<Self, PriceLevelError> {
+        package: PriceLevelSnapshotPackage<T>,
+    ) -> Result<Self, PriceLevelError>
+    where
+        T: Serialize,
+    {
         let snapshot = package.into_snapshot()?;
         Self::from_snapshot(snapshot)
     }
@@ -352,22 +747,27 @@ impl PriceLevel {
     ///
     /// Returns [`PriceLevelError::DeserializationError`] if `data` is not a
     /// valid snapshot-package JSON document, [`PriceLevelError::ChecksumMismatch`]
-    /// if the decoded package's SHA-256 checksum does not match its payload,
+    /// if the decoded package's checksum does not match its payload,
     /// [`PriceLevelError::SerializationError`] if re-encoding the payload to
     /// recompute that checksum fails, [`PriceLevelError::InvalidOperation`]
     /// on an unsupported snapshot format version, and
     /// [`PriceLevelError::DuplicateOrderId`] if the decoded snapshot's orders
     /// vector repeats an order id.
-    pub fn from_snapshot_json(data: &str) -> Result<Self, PriceLevelError> {
+    pub fn from_snapshot_json(data: &str) -> Result<Self, PriceLevelError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
         let package = PriceLevelSnapshotPackage::from_json(data)?;
         Self::from_snapshot_package(package)
     }
 }
 
-impl PriceLevel {
+impl<T: Clone> PriceLevel<T> {
     /// Create a new price level
     #[must_use]
     pub fn new(price: u128) -> Self {
+        let stats = PriceLevelStatistics::new();
+        let clock = Arc::clone(stats.clock());
         Self {
             price,
             visible_quantity: AtomicU64::new(0),
@@ -376,13 +776,433 @@ impl PriceLevel {
             topology: AtomicU64::new(topology::pack(topology::TAG_UNPINNED, 0)),
             topology_epoch: AtomicU64::new(0),
             orders: OrderQueue::new(),
-            stats: Arc::new(PriceLevelStatistics::new()),
+            stats: Arc::new(stats),
             fok_guard: RwLock::new(()),
             level_poisoned: AtomicBool::new(false),
             mutation_epoch: AtomicU64::new(0),
+            exclusive_sections: AtomicU64::new(0),
+            shared_mutators: AtomicU64::new(0),
+            matching_policy: AtomicU8::new(MatchingPolicy::Fifo as u8),
+            crossing_rule: AtomicU8::new(CrossingRule::OppositeOfMaker as u8),
+            fifo_high_water: AtomicU64::new(0),
+            fifo_violations: AtomicU64::new(0),
+            trade_stamping: AtomicBool::new(false),
+            clock_stamping: AtomicBool::new(false),
+            sequencing: AtomicBool::new(false),
+            halt: AtomicU8::new(0),
+            display_delays: LazyMap::default(),
+            display_delay_count: AtomicUsize::new(0),
+            replaced_from: LazyMap::default(),
+            replacement_count: AtomicUsize::new(0),
+            executed: LazyMap::default(),
+            quarantine: Mutex::new(Vec::new()),
+            quarantine_count: AtomicU64::new(0),
+            amend_history: AmendHistory::default(),
+            expiry_warnings: ExpiryWarnings::default(),
+            rate_limits: RateLimitHook::default(),
+            price_band: PriceBandHook::default(),
+            band_breaches: AtomicU64::new(0),
+            reserved_quantity: AtomicU64::new(0),
+            reservations: LazyMap::default(),
+            next_reservation: AtomicU64::new(0),
+            scratch: ScratchSlot::default(),
+            executions: ExecutionChannels::default(),
+            #[cfg(feature = "async")]
+            events: EventSubscribers::default(),
+            consistent_view: ConsistentView::default(),
+            replenishment: Arc::new(DefaultReplenishment),
+            refresh_priority: RefreshPriority::Requeue,
+            hidden_priority: HiddenPriority::DisplayedFirst,
+            priority_tiers: None,
+            symbol_spec: None,
+            limits: None,
+            clock,
+        }
+    }
+
+    /// Create a new price level with the settings in `config`.
+    ///
+    /// Like the matching policy and crossing rule, the queue's shard count,
+    /// the replenishment strategy, the refresh and hidden priorities, the
+    /// priority tiers, the clock, the symbol spec and the limits are runtime
+    /// configuration: snapshots, clones and deserialized levels are rebuilt
+    /// unsharded, with [`DefaultReplenishment`], [`RefreshPriority::Requeue`],
+    /// [`HiddenPriority::DisplayedFirst`], no tiers, the
+    /// [`SystemClock`](crate::SystemClock), no symbol spec and no limits.
+    #[must_use]
+    pub fn with_config(price: u128, config: PriceLevelConfig) -> Self {
+        let mut level = Self::new(price);
+        level.orders = OrderQueue::with_shards(config.queue_shards());
+        level.replenishment = Arc::clone(config.replenishment_strategy());
+        level.refresh_priority = config.refresh_priority();
+        level.hidden_priority = config.hidden_priority();
+        level.priority_tiers = config.priority_tiers().cloned();
+        level.symbol_spec = config.symbol_spec();
+        level.limits = config.limits();
+        level.clock = Arc::clone(config.clock());
+        level.stats = Arc::new(PriceLevelStatistics::with_clock(Arc::clone(config.clock())));
+        level.stats.set_latency_tracking(config.latency_tracking());
+        level.set_matching_policy(config.matching_policy());
+        level.set_crossing_rule(config.crossing_rule());
+        level
+    }
+
+    /// The tick and lot increments the level validates orders against; set
+    /// through [`PriceLevelConfig::with_symbol_spec`].
+    ///
+    /// With a spec, [`Self::add_order`] rejects an order whose price is off
+    /// the tick ([`PriceLevelError::InvalidTick`]) or whose visible or hidden
+    /// quantity is off the lot, or whose total is outside the spec's bounds
+    /// ([`PriceLevelError::InvalidLot`]). [`Self::update_order`] applies the
+    /// same checks to a resized order, and checks the new price and quantity
+    /// of a price-changing update before removing the order.
+    #[must_use]
+    pub fn symbol_spec(&self) -> Option<SymbolSpec> {
+        self.symbol_spec
+    }
+
+    /// The capacity limits the level admits orders against; set through
+    /// [`PriceLevelConfig::with_limits`].
+    ///
+    /// With limits, [`Self::add_order`] rejects an order that would take the
+    /// level past its order count, its visible plus hidden quantity, or the
+    /// order count of the order's participant with
+    /// [`PriceLevelError::CapacityExceeded`]. Admissions then run one at a
+    /// time, so concurrent ones can never overshoot a limit together.
+    #[must_use]
+    pub fn limits(&self) -> Option<LevelLimits> {
+        self.limits
+    }
+
+    /// The strategy refreshing iceberg and reserve orders; set through
+    /// [`PriceLevelConfig::with_replenishment_strategy`].
+    #[must_use]
+    pub fn replenishment_strategy(&self) -> &Arc<dyn ReplenishmentStrategy> {
+        &self.replenishment
+    }
+
+    /// The clock the level and its statistics read the current time from; set
+    /// through [`PriceLevelConfig::with_clock`].
+    #[must_use]
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    /// Whether a refreshed iceberg or reserve order keeps its queue slot; set
+    /// through [`PriceLevelConfig::with_refresh_priority`].
+    #[must_use]
+    pub fn refresh_priority(&self) -> RefreshPriority {
+        self.refresh_priority
+    }
+
+    /// Whether hidden orders trade after the displayed liquidity at the
+    /// level; set through [`PriceLevelConfig::with_hidden_priority`].
+    #[must_use]
+    pub fn hidden_priority(&self) -> HiddenPriority {
+        self.hidden_priority
+    }
+
+    /// The participant tiers a FIFO sweep trades in, if any; set through
+    /// [`PriceLevelConfig::with_priority_tiers`].
+    #[must_use]
+    pub fn priority_tiers(&self) -> Option<&Arc<dyn PriorityTiers>> {
+        self.priority_tiers.as_ref()
+    }
+
+    /// Whether a sweep under `policy` visits the makers in passes (see
+    /// [`Self::sweep_pass`]) rather than in one walk of the queue. Strict
+    /// FIFO never does, since it may not trade an older maker after a
+    /// younger one.
+    fn sweeps_in_passes(&self, policy: MatchingPolicy) -> bool {
+        self.sweeps_hidden_last(policy)
+            || (policy == MatchingPolicy::Fifo && self.priority_tiers.is_some())
+    }
+
+    /// Whether a sweep under `policy` trades the hidden orders after the
+    /// displayed ones. The queue keeps hidden orders in an index of their
+    /// own, so the sweep takes each pass's displayed makers from one index
+    /// and its hidden makers from the other, without stepping over either.
+    fn sweeps_hidden_last(&self, policy: MatchingPolicy) -> bool {
+        policy != MatchingPolicy::StrictFifo
+            && self.hidden_priority == HiddenPriority::DisplayedFirst
+    }
+
+    /// The pass of a sweep under `policy` in which `order` trades: its
+    /// participant's tier under FIFO, then displayed before hidden under
+    /// [`HiddenPriority::DisplayedFirst`]. Lower passes trade first, each in
+    /// time priority.
+    fn sweep_pass(&self, order: &OrderType<T>, policy: MatchingPolicy) -> u16 {
+        let tier = match &self.priority_tiers {
+            Some(tiers) if policy == MatchingPolicy::Fifo => tiers.tier(order.user_id()),
+            _ => 0,
+        };
+        let hidden = self.hidden_priority == HiddenPriority::DisplayedFirst && order.is_hidden();
+        u16::from(tier) * 2 + u16::from(hidden)
+    }
+
+    /// [`OrderType::match_against_with`] under the level's replenishment
+    /// strategy and refresh priority.
+    fn match_maker(
+        &self,
+        order: &OrderType<T>,
+        quantity: u64,
+        match_timestamp: Option<TimestampMs>,
+    ) -> (u64, Option<OrderType<T>>, u64, u64) {
+        match self.refresh_priority {
+            RefreshPriority::Requeue => {
+                order.match_against_with(quantity, &*self.replenishment, match_timestamp)
+            }
+            RefreshPriority::Retain => order.match_against_with(
+                quantity,
+                &RetainedTimestamp(&*self.replenishment),
+                match_timestamp,
+            ),
+        }
+    }
+
+    /// Number of shards the order queue's time-priority index is split
+    /// across; `1` unless set through [`PriceLevelConfig::with_queue_shards`].
+    #[must_use]
+    pub fn queue_shards(&self) -> usize {
+        self.orders.shard_count()
+    }
+
+    /// Create a new price level that matches under `policy`.
+    #[must_use]
+    pub fn with_matching_policy(price: u128, policy: MatchingPolicy) -> Self {
+        let level = Self::new(price);
+        level.set_matching_policy(policy);
+        level
+    }
+
+    /// Create a new price level that takes order allocations and match-result
+    /// buffers from `pool` and returns evicted orders to it.
+    ///
+    /// The pool is runtime wiring: snapshots, clones and deserialized levels
+    /// do not carry it. Hand spent results back with [`OrderPool::recycle`].
+    #[cfg(feature = "pool")]
+    #[must_use]
+    pub fn with_order_pool(price: u128, pool: Arc<OrderPool<T>>) -> Self {
+        let mut level = Self::new(price);
+        level.orders = std::mem::take(&mut level.orders).with_pool(pool);
+        level
+    }
+
+    /// The pool set by [`Self::with_order_pool`], if any.
+    #[cfg(feature = "pool")]
+    #[must_use]
+    pub fn order_pool(&self) -> Option<&Arc<OrderPool<T>>> {
+        self.orders.pool()
+    }
+
+    /// Get the matching policy applied by [`Self::match_order`].
+    #[must_use]
+    pub fn matching_policy(&self) -> MatchingPolicy {
+        MatchingPolicy::from_u8(self.matching_policy.load(Ordering::Relaxed))
+    }
+
+    /// Change the matching policy.
+    ///
+    /// Takes effect from the next [`Self::match_order`]; a match already in
+    /// progress completes under the policy it started with. Entering or
+    /// leaving [`MatchingPolicy::StrictFifo`] also switches the queue's
+    /// tombstone mode; tombstones left behind on leaving are reaped.
+    pub fn set_matching_policy(&self, policy: MatchingPolicy) {
+        let strict = policy == MatchingPolicy::StrictFifo;
+        self.orders.set_tombstone_mode(strict);
+        let previous = self.matching_policy.swap(policy as u8, Ordering::Relaxed);
+        if strict {
+            // A fresh strict period validates against its own executions only.
+            if previous != MatchingPolicy::StrictFifo as u8 {
+                self.fifo_high_water.store(0, Ordering::Relaxed);
+            }
+        } else {
+            self.orders.compact_tombstones();
+        }
+    }
+
+    /// Create a new price level that assigns taker sides under `rule`.
+    #[must_use]
+    pub fn with_crossing_rule(price: u128, rule: CrossingRule) -> Self {
+        let level = Self::new(price);
+        level.set_crossing_rule(rule);
+        level
+    }
+
+    /// Get the crossing rule applied by [`Self::match_order`].
+    #[must_use]
+    pub fn crossing_rule(&self) -> CrossingRule {
+        CrossingRule::from_u8(self.crossing_rule.load(Ordering::Relaxed))
+    }
+
+    /// Change the crossing rule.
+    ///
+    /// Takes effect from the next [`Self::match_order`]; a match already in
+    /// progress completes under the rule it started with.
+    pub fn set_crossing_rule(&self, rule: CrossingRule) {
+        self.crossing_rule.store(rule as u8, Ordering::Relaxed);
+    }
+
+    /// Number of [`MatchingPolicy::StrictFifo`] sweeps stopped because the
+    /// front maker's insertion sequence was below one already executed.
+    ///
+    /// The strict sweep serializes against every mutator, so this stays `0`;
+    /// a non-zero value means an ordering invariant was broken and the affected
+    /// sweeps were cut short rather than trade out of arrival order.
+    #[must_use]
+    pub fn fifo_violations(&self) -> u64 {
+        self.fifo_violations.load(Ordering::Relaxed)
+    }
+
+    /// Get the level's mutation sequence.
+    ///
+    /// Starts at `0` and increases by one on every committed admission,
+    /// cancel, resize, stop activation, trailing-stop ratchet, expiry
+    /// extension and fill, so each value names one state of the queue. [`Self::snapshot`] records it, and a stamped trade
+    /// ([`Self::set_trade_stamping`]) carries the value its fill committed at;
+    /// a snapshot reflects a trade exactly when its sequence is at least the
+    /// trade's.
+    #[must_use]
+    pub fn mutation_sequence(&self) -> u64 {
+        self.mutation_epoch.load(Ordering::Acquire)
+    }
+
+    /// Whether [`Self::match_order`] stamps trades with the mutation sequence.
+    #[must_use]
+    pub fn trade_stamping(&self) -> bool {
+        self.trade_stamping.load(Ordering::Relaxed)
+    }
+
+    /// Enable or disable stamping each trade with the [`Self::mutation_sequence`]
+    /// its fill committed at (see [`Trade::level_sequence`]).
+    ///
+    /// Off by default. Takes effect from the next [`Self::match_order`]. The
+    /// sequence advances on every fill either way; only the stamp is optional.
+    pub fn set_trade_stamping(&self, enabled: bool) {
+        self.trade_stamping.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether [`Self::match_order`] stamps trades with the clock's reading.
+    #[must_use]
+    pub fn clock_stamping(&self) -> bool {
+        self.clock_stamping.load(Ordering::Relaxed)
+    }
+
+    /// Enable or disable stamping each trade with the level [`Self::clock`]'s
+    /// reading when its match ran (see [`Trade::clock_timestamp`]).
+    ///
+    /// Off by default, so the match path reads no clock. When on, the clock
+    /// is read once per [`Self::match_order`] call, and every trade of the
+    /// match carries the same reading.
+    pub fn set_clock_stamping(&self, enabled: bool) {
+        self.clock_stamping.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether the level's mutations are totally ordered by their sequence.
+    #[must_use]
+    pub fn sequencing(&self) -> bool {
+        self.sequencing.load(Ordering::Relaxed)
+    }
+
+    /// Enable or disable total-order sequencing of the level's mutations.
+    ///
+    /// By default, admissions, updates and sweeps run concurrently, and two
+    /// racing changes may advance [`Self::mutation_sequence`] in the opposite
+    /// order to the one in which they reached the queue. With sequencing on,
+    /// every mutator — including every [`Self::match_order`], whatever its
+    /// time in force — runs in the level's exclusive section, so changes
+    /// commit one at a time and each is stamped with the sequence it
+    /// committed at: on its trades ([`Trade::level_sequence`], stamped as if
+    /// [`Self::set_trade_stamping`] were on), on the result of
+    /// [`Self::add_order_sequenced`] and [`Self::update_order_sequenced`],
+    /// and, with the `async` feature, on the events of
+    /// `subscribe_sequenced`, which are delivered in sequence order. A consumer can then rebuild the level's
+    /// exact history by sorting on the sequence.
+    ///
+    /// Off by default: it trades the concurrency of the shared mutator path
+    /// for the ordering. Takes effect from the next mutation.
+    pub fn set_sequencing(&self, enabled: bool) {
+        self.sequencing.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether the level is frozen by [`Self::freeze`] or
+    /// [`Self::freeze_with`].
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        self.halt.load(Ordering::Acquire) != 0
+    }
+
+    /// The policy the level is frozen under, or `None` while it trades.
+    #[must_use]
+    pub fn halt_policy(&self) -> Option<HaltPolicy> {
+        HaltPolicy::decode(self.halt.load(Ordering::Acquire))
+    }
+
+    /// Halt trading at the level, still accepting adds, cancels and amends:
+    /// [`Self::freeze_with`] under [`HaltPolicy::new`].
+    pub fn freeze(&self) {
+        self.freeze_with(HaltPolicy::new());
+    }
+
+    /// Halt trading at the level until [`Self::unfreeze`], for a regulatory
+    /// halt or an auction call.
+    ///
+    /// While frozen, every taker with a positive quantity is refused:
+    /// [`Self::match_order`] returns a `Rejected` result with no trade, and
+    /// [`Self::try_match_order`], [`Self::match_taker`],
+    /// [`Self::propose_match`] and [`Self::commit_match`] return
+    /// [`PriceLevelError::TradingHalted`]. Adds, cancels and amends the
+    /// `policy` does not accept fail with the same error, leaving the level
+    /// unchanged. Expiry sweeps and stop activations are not messages and
+    /// still run. Freezing a frozen level replaces its policy.
+    ///
+    /// The flag is set in the exclusive section, so once this returns no
+    /// match is in progress. It is not part of a snapshot.
+    pub fn freeze_with(&self, policy: HaltPolicy) {
+        self.set_halt_bits(policy.encode());
+    }
+
+    /// Resume trading at a level frozen by [`Self::freeze`]. A no-op on a
+    /// level that trades.
+    pub fn unfreeze(&self) {
+        self.set_halt_bits(0);
+    }
+
+    /// Store an encoded [`HaltPolicy`], zero to unfreeze, as
+    /// [`Self::freeze_with`] does.
+    pub(crate) fn set_halt_bits(&self, bits: u8) {
+        let _section = self.fok_write();
+        self.halt.store(bits, Ordering::Release);
+    }
+
+    /// Refuse with [`PriceLevelError::TradingHalted`] while the level is
+    /// frozen under a policy that does not `allow` the message.
+    pub(crate) fn check_halt(&self, allow: fn(&HaltPolicy) -> bool) -> Result<(), PriceLevelError> {
+        match self.halt_policy() {
+            Some(policy) if !allow(&policy) => {
+                Err(PriceLevelError::TradingHalted { price: self.price })
+            }
+            _ => Ok(()),
         }
     }
 
+    /// Number of tombstones (queue keys of cancelled or re-sequenced orders)
+    /// still awaiting removal. Only [`MatchingPolicy::StrictFifo`] leaves
+    /// tombstones; advisory while mutators run.
+    #[must_use]
+    pub fn tombstone_count(&self) -> usize {
+        self.orders.tombstone_count()
+    }
+
+    /// Remove every tombstone from the queue, returning how many were reaped.
+    ///
+    /// Takes the level's exclusive guard, so it waits for an in-flight strict
+    /// sweep and excludes mutators for the duration of the scan.
+    pub fn compact_tombstones(&self) -> usize {
+        let _guard = self.fok_write();
+        self.orders.compact_tombstones()
+    }
+
     /// Get the price of this level
     #[must_use]
     pub fn price(&self) -> u128 {
@@ -402,6 +1222,11 @@ impl PriceLevel {
     /// read as approximate; for a reading where the counters and the order list
     /// are guaranteed mutually consistent, take a [`Self::snapshot`] and read
     /// from it.
+    ///
+    /// Orders still inside their display delay (see
+    /// [`Self::add_order_with_display_delay`]) are counted in
+    /// [`Self::hidden_quantity`] instead until [`Self::release_displays`]
+    /// displays them.
     #[must_use]
     pub fn visible_quantity(&self) -> u64 {
         // `Relaxed`: this counter is advisory / eventually-consistent (see the
@@ -410,18 +1235,43 @@ impl PriceLevel {
         // ordering between producers and consumers, and `snapshot()` is the
         // mutually-consistent view. Nothing is published or synchronized through
         // this load, so `Acquire` would buy nothing.
-        self.visible_quantity.load(Ordering::Relaxed)
+        let visible = self.visible_quantity.load(Ordering::Relaxed);
+        visible.saturating_sub(self.withheld_display_quantity())
     }
 
     /// Get the hidden quantity, in quantity units.
     ///
+    /// Includes the visible tranche of every order still inside its display
+    /// delay (see [`Self::add_order_with_display_delay`]).
+    ///
     /// Advisory / eventually-consistent under concurrent mutation — see
     /// [`Self::visible_quantity`]; use [`Self::snapshot`] for a consistent view.
     #[must_use]
     pub fn hidden_quantity(&self) -> u64 {
         // `Relaxed`: advisory counter, no happens-before rides on it — see
         // `visible_quantity` for the full rationale.
-        self.hidden_quantity.load(Ordering::Relaxed)
+        let hidden = self.hidden_quantity.load(Ordering::Relaxed);
+        hidden.saturating_add(self.withheld_display_quantity())
+    }
+
+    /// Visible quantity of the orders still inside their display delay, which
+    /// [`Self::visible_quantity`] reports as hidden. Zero, without touching the
+    /// registry, when no order is withheld.
+    ///
+    /// The walk and the counters it adjusts are read at different instants, so
+    /// under a racing fill the withheld sum can briefly exceed the visible
+    /// counter. Both accessors are advisory, so they saturate on that transient
+    /// instead of reporting an error.
+    fn withheld_display_quantity(&self) -> u64 {
+        if self.display_delay_count.load(Ordering::Relaxed) == 0 {
+            return 0;
+        }
+        self.display_delays
+            .iter()
+            .filter_map(|entry| self.orders.find(*entry.key()))
+            .fold(0u64, |total, order| {
+                total.saturating_add(order.working_visible_quantity().as_u64())
+            })
     }
 
     /// Get the total quantity (visible + hidden), in quantity units.
@@ -453,6 +1303,76 @@ impl PriceLevel {
         topology::count(self.topology.load(Ordering::Relaxed)) as usize
     }
 
+    /// Verify the level's internal bookkeeping against its queue.
+    ///
+    /// Checks that the visible and hidden counters equal the sums over the
+    /// queued orders, that [`Self::order_count`] equals the queue length, that
+    /// the queue's priority index agrees with its order map, and that fill
+    /// state is kept only for resting orders. The counters are advisory while
+    /// writers run, so call this once the level is quiescent, e.g. after every
+    /// stress-test thread has joined.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] naming the first
+    /// invariant that does not hold.
+    #[cfg(feature = "testing")]
+    pub fn check_invariants(&self) -> Result<(), PriceLevelError> {
+        fn violated(message: String) -> PriceLevelError {
+            PriceLevelError::InvalidOperation {
+                message: format!("price level invariant violated: {message}"),
+            }
+        }
+
+        let orders = self.snapshot_by_insertion_seq();
+        let (mut visible, mut hidden) = (0u64, 0u64);
+        for order in &orders {
+            visible = visible
+                .checked_add(order.working_visible_quantity().as_u64())
+                .ok_or_else(|| violated("queued visible quantity overflows u64".to_string()))?;
+            hidden = hidden
+                .checked_add(order.hidden_quantity().as_u64())
+                .ok_or_else(|| violated("queued hidden quantity overflows u64".to_string()))?;
+        }
+
+        let visible_counter = self.visible_quantity.load(Ordering::Relaxed);
+        if visible_counter != visible {
+            return Err(violated(format!(
+                "visible counter {visible_counter} != queued visible {visible}"
+            )));
+        }
+        let hidden_counter = self.hidden_quantity.load(Ordering::Relaxed);
+        if hidden_counter != hidden {
+            return Err(violated(format!(
+                "hidden counter {hidden_counter} != queued hidden {hidden}"
+            )));
+        }
+        let count = self.order_count();
+        if count != self.orders.len() || count != orders.len() {
+            return Err(violated(format!(
+                "order count {count} != queue length {} ({} by priority)",
+                self.orders.len(),
+                orders.len()
+            )));
+        }
+        if !self.orders.debug_map_index_consistent() {
+            return Err(violated(
+                "queue index disagrees with its order map".to_string(),
+            ));
+        }
+        if let Some(entry) = self
+            .executed
+            .iter()
+            .find(|entry| self.orders.find(*entry.key()).is_none())
+        {
+            return Err(violated(format!(
+                "fill state kept for order {}, which does not rest here",
+                entry.key()
+            )));
+        }
+        Ok(())
+    }
+
     /// The side currently pinned at this level, or `None` if the level is empty
     /// (Unpinned). Advisory: a concurrent admission / drain can change it right
     /// after the read.
@@ -461,6 +1381,16 @@ impl PriceLevel {
         topology::side_of_tag(topology::tag(self.topology.load(Ordering::Relaxed)))
     }
 
+    /// Whether a taker may cross this level's resting side under `rule`. An
+    /// empty level has nothing to cross and always passes. Advisory like
+    /// [`Self::pinned_side`]; the sweep re-checks each maker.
+    fn check_crossing(&self, rule: CrossingRule) -> Result<(), PriceLevelError> {
+        match self.pinned_side() {
+            Some(maker_side) => rule.taker_side(maker_side).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
     /// Reserve one admission slot for a `side` order: pin the side (or verify it
     /// matches the pinned side) and increment the resting-order count, in a
     /// single compare-exchange (issue #126).
@@ -477,8 +1407,9 @@ impl PriceLevel {
     ///
     /// # Errors
     ///
-    /// [`PriceLevelError::InvalidOperation`] if `side` is incompatible with the
-    /// pinned side of a non-empty level, or if the count would exceed
+    /// [`PriceLevelError::SideMismatch`] if `side` is incompatible with the
+    /// pinned side of a non-empty level, or
+    /// [`PriceLevelError::InvalidOperation`] if the count would exceed
     /// [`topology::COUNT_MASK`].
     fn topology_admit(&self, side: Side) -> Result<bool, PriceLevelError> {
         let my_tag = topology::tag_of(side);
@@ -514,11 +1445,9 @@ impl PriceLevel {
                 }
             } else {
                 // Non-empty level pinned to the opposite side: reject.
-                let resting = topology::side_of_tag(tag);
-                return Err(PriceLevelError::InvalidOperation {
-                    message: format!(
-                        "order side {side:?} is incompatible with the level's resting side {resting:?}"
-                    ),
+                return Err(PriceLevelError::SideMismatch {
+                    expected: side.opposite(),
+                    actual: side,
                 });
             }
             // Lost the CAS to a concurrent mutation; reload and retry.
@@ -571,15 +1500,16 @@ impl PriceLevel {
     /// Bump the mutation epoch on a committed add / cancel / resize so a racing
     /// post-only depth scan retries (issue #130). `Release` so the queue mutation
     /// that precedes it happens-before a scanner's `Acquire` read of the epoch.
+    /// Returns the new value, the sequence the mutation committed at.
     #[inline]
-    fn bump_mutation_epoch(&self) {
-        self.mutation_epoch.fetch_add(1, Ordering::Release);
+    fn bump_mutation_epoch(&self) -> u64 {
+        self.mutation_epoch.fetch_add(1, Ordering::Release) + 1
     }
 
     /// Returns `true` if `orders` is empty or every order shares one side — the
     /// single-side coherence [`Self::from_snapshot`] requires. Used as the
     /// termination backstop for `snapshot`'s torn-topology retry (issue #126).
-    fn is_single_side(orders: &[Arc<OrderType<()>>]) -> bool {
+    fn is_single_side(orders: &[Arc<OrderType<T>>]) -> bool {
         let mut side = None;
         for order in orders {
             match side {
@@ -604,7 +1534,7 @@ impl PriceLevel {
     /// sticky [`Self::level_poisoned`] flag so the level then fails fast (issue
     /// #130): a poison means a holder panicked mid-operation.
     #[inline]
-    fn fok_read(&self) -> std::sync::RwLockReadGuard<'_, ()> {
+    fn fok_read(&self) -> RwLockReadGuard<'_, ()> {
         self.fok_guard.read().unwrap_or_else(|poison| {
             self.mark_poisoned();
             poison.into_inner()
@@ -614,12 +1544,52 @@ impl PriceLevel {
     /// Acquire the fill-or-kill guard's **exclusive (write)** side — held across
     /// a fill-or-kill dry-run + sweep so no mutator can change the matchable
     /// depth mid-decision. A poisoned lock is recovered (see [`Self::fok_read`]).
+    /// Entering and leaving are counted in [`Self::exclusive_sections`].
     #[inline]
-    fn fok_write(&self) -> std::sync::RwLockWriteGuard<'_, ()> {
-        self.fok_guard.write().unwrap_or_else(|poison| {
+    fn fok_write(&self) -> ExclusiveSection<'_> {
+        let guard = self.fok_guard.write().unwrap_or_else(|poison| {
             self.mark_poisoned();
             poison.into_inner()
-        })
+        });
+        self.exclusive_sections.fetch_add(1, Ordering::Relaxed);
+        // Order the count before every queue change the section makes, so a
+        // reader that sees a change also sees the section open.
+        std::sync::atomic::fence(Ordering::Release);
+        ExclusiveSection {
+            _guard: guard,
+            sections: &self.exclusive_sections,
+        }
+    }
+
+    /// Acquire the fill-or-kill guard's shared side for a mutator, counted in
+    /// [`Self::shared_mutators`] until the returned section is dropped.
+    #[inline]
+    fn fok_read_mutator(&self) -> SharedSection<'_> {
+        let guard = self.fok_read();
+        self.shared_mutators.fetch_add(1, Ordering::Relaxed);
+        // As in `fok_write`: the count is visible before any queue change.
+        std::sync::atomic::fence(Ordering::Release);
+        SharedSection {
+            _guard: guard,
+            mutators: &self.shared_mutators,
+        }
+    }
+
+    /// Enter a mutator's side of [`Self::fok_guard`]: the shared side
+    /// ([`Self::fok_read_mutator`]), or the exclusive side
+    /// ([`Self::fok_write`]) while [`Self::sequencing`] is on, so the mutation
+    /// commits alone.
+    #[inline]
+    fn mutator_section(&self) -> MutatorSection<'_> {
+        if self.sequencing() {
+            MutatorSection::Exclusive {
+                _section: self.fok_write(),
+            }
+        } else {
+            MutatorSection::Shared {
+                _section: self.fok_read_mutator(),
+            }
+        }
     }
 
     /// Trip the sticky poison flag when a [`Self::fok_guard`] poison is recovered
@@ -674,6 +1644,20 @@ impl PriceLevel {
         }));
     }
 
+    /// Capacity retained by the level's pooled match scratch (test seam), or
+    /// `None` while it is checked out.
+    #[cfg(test)]
+    pub(crate) fn test_scratch_capacity(&self) -> Option<usize> {
+        self.scratch.retained_orders_capacity()
+    }
+
+    /// Skew the visible counter away from the queue (test seam for
+    /// [`Self::check_invariants`]).
+    #[cfg(all(test, feature = "testing"))]
+    pub(crate) fn test_skew_visible_counter(&self, delta: u64) {
+        self.visible_quantity.fetch_add(delta, Ordering::Relaxed);
+    }
+
     /// Add an order to this price level.
     ///
     /// Decides the order's id IDENTITY first, then reserves its visible /
@@ -724,37 +1708,347 @@ impl PriceLevel {
     ///
     /// # Errors
     ///
-    /// Returns [`PriceLevelError::InvalidOperation`] if the order's price does
-    /// not match the level's, if its side is incompatible with the resting
-    /// side, if the order's own visible + hidden total overflows `u64`, or if
-    /// admitting it would overflow the level's visible-quantity,
-    /// hidden-quantity, or order-count counter; or
-    /// [`PriceLevelError::DuplicateOrderId`] if an order with the same id
-    /// already rests at this level. A duplicate id takes precedence over a
-    /// counter overflow. In every case the level is unchanged.
-    pub fn add_order(&self, order: OrderType<()>) -> Result<Arc<OrderType<()>>, PriceLevelError> {
-        // Hold the fill-or-kill guard's shared side for this admission so a
-        // concurrent fill-or-kill match sees a stable depth (issue #112). This
-        // is an uncontended shared acquisition in the common case (no FOK).
-        let _fok = self.fok_read();
-        // Fail fast if a prior panic poisoned the guard (or this very acquisition
-        // just recovered one): the level may be half-mutated (issue #130).
-        self.poison_check()?;
+    /// Returns [`PriceLevelError::PriceMismatch`] if the order's price does
+    /// not match the level's, [`PriceLevelError::SideMismatch`] if its side is
+    /// incompatible with the resting side,
+    /// [`PriceLevelError::InvalidOperation`] if the order is an
+    /// [`OrderType::Market`] order, which never rests, if the order's own
+    /// visible + hidden total overflows `u64`, or if admitting it would
+    /// overflow the level's visible-quantity, hidden-quantity, or order-count
+    /// counter; or [`PriceLevelError::DuplicateOrderId`] if an order with the
+    /// same id already rests at this level. A duplicate id takes precedence
+    /// over a counter overflow. With a [`Self::symbol_spec`], also returns
+    /// [`PriceLevelError::InvalidTick`] or [`PriceLevelError::InvalidLot`] for
+    /// an off-increment order, with [`Self::limits`],
+    /// [`PriceLevelError::CapacityExceeded`] for an order past a limit, and
+    /// with a [rate limiter](Self::set_rate_limiter),
+    /// [`PriceLevelError::RateLimited`] for a refused admission, and on a
+    /// [frozen](Self::freeze_with) level refusing adds,
+    /// [`PriceLevelError::TradingHalted`]. In every case the level is
+    /// unchanged.
+    pub fn add_order(&self, order: OrderType<T>) -> Result<Arc<OrderType<T>>, PriceLevelError> {
+        let _latency = self.stats.time(LatencyOperation::AddOrder);
+        self.admit(order, None).map(Sequenced::into_value)
+    }
 
-        // -------- Admission topology invariants (cheapest checks, no mutation) --------
-        //
-        // A level holds orders at exactly one price and one side. Reject a
-        // mismatch BEFORE reserving any counter capacity, so the level is left
-        // completely unchanged. Price is the cheapest check (two `u128`s), so it
-        // goes first; the side is derived from whatever is already resting.
-        if order.price().as_u128() != self.price {
-            return Err(PriceLevelError::InvalidOperation {
-                message: format!(
-                    "order price {} does not match level price {}",
-                    order.price().as_u128(),
-                    self.price
-                ),
-            });
+    /// [`Self::add_order`], stamping the admitted order with the
+    /// [`Self::mutation_sequence`] its admission committed at.
+    ///
+    /// The stamp totally orders the admission against the level's other
+    /// mutations when [`Self::set_sequencing`] is on (see [`Sequenced`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::add_order`], leaving the level
+    /// unchanged.
+    pub fn add_order_sequenced(
+        &self,
+        order: OrderType<T>,
+    ) -> Result<Sequenced<Arc<OrderType<T>>>, PriceLevelError> {
+        let _latency = self.stats.time(LatencyOperation::AddOrder);
+        self.admit(order, None)
+    }
+
+    /// Add an order that is withheld from display for `delay_ms` milliseconds
+    /// after its placement timestamp, modelling venues with a display delay.
+    ///
+    /// The order rests and matches exactly as one admitted by
+    /// [`Self::add_order`] — only its visibility changes. Until it is
+    /// displayed, its visible tranche is reported in
+    /// [`Self::hidden_quantity`] rather than [`Self::visible_quantity`], and
+    /// [`Self::public_snapshot`] leaves it out. The delay is enforced by the
+    /// caller's clock, like [`TimeInForce::is_expired`]: the order is displayed
+    /// by the first [`Self::release_displays`] call whose `now` reaches
+    /// [`OrderType::timestamp`] + `delay_ms`. A `delay_ms` of zero displays
+    /// the order immediately.
+    ///
+    /// The delay is registered atomically with the order's publication, so no
+    /// reader ever sees it displayed early, and a rejected admission registers
+    /// nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::add_order`], leaving the level
+    /// unchanged.
+    pub fn add_order_with_display_delay(
+        &self,
+        order: OrderType<T>,
+        delay_ms: u64,
+    ) -> Result<Arc<OrderType<T>>, PriceLevelError> {
+        let display_at = (delay_ms > 0)
+            .then(|| TimestampMs::new(order.timestamp().as_u64().saturating_add(delay_ms)));
+        self.admit(order, display_at).map(Sequenced::into_value)
+    }
+
+    /// Admit a batch of orders in one step, for warm-starting a level from
+    /// historical orders at startup. Returns the number of orders loaded.
+    ///
+    /// Every order is validated as [`Self::add_order`] would validate it,
+    /// and the batch as a whole against the level's side, its
+    /// [`Self::limits`] and its counters, before anything is published. The
+    /// orders then join the back of the queue sorted by timestamp, orders with
+    /// equal timestamps keeping their order in `orders`, and the counters are
+    /// set once from the batch totals. The load commits in the exclusive
+    /// section at a single [`Self::mutation_sequence`].
+    ///
+    /// Unlike a loop of [`Self::add_order`] calls, the load is neither
+    /// charged to the rate limiter nor published to subscribers: a consumer
+    /// of `subscribe` starts from a [`Self::snapshot`] taken after it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error [`Self::add_order`] would report for an order
+    /// of the batch, [`PriceLevelError::DuplicateOrderId`] if the batch
+    /// repeats an id or names one already resting,
+    /// [`PriceLevelError::SideMismatch`] if it mixes sides, and
+    /// [`PriceLevelError::CapacityExceeded`] if it takes the level past a
+    /// limit. In every case the level is unchanged.
+    pub fn load_bulk(&self, mut orders: Vec<OrderType<T>>) -> Result<usize, PriceLevelError> {
+        let _section = self.fok_write();
+        self.poison_check()?;
+        self.check_halt(HaltPolicy::allows_adds)?;
+        if orders.is_empty() {
+            return Ok(0);
+        }
+
+        let overflow = || PriceLevelError::InvalidOperation {
+            message: "price level quantity overflow on bulk load".to_string(),
+        };
+        let side = self.pinned_side().unwrap_or(orders[0].side());
+        let mut ids = HashSet::with_capacity(orders.len());
+        let mut batch_total = 0_u64;
+        for order in &orders {
+            if order.side() != side {
+                return Err(PriceLevelError::SideMismatch {
+                    expected: side,
+                    actual: order.side(),
+                });
+            }
+            let total_qty = self.check_admission(order)?;
+            batch_total = batch_total.checked_add(total_qty).ok_or_else(overflow)?;
+            if !ids.insert(order.id()) || self.orders.find(order.id()).is_some() {
+                return Err(PriceLevelError::DuplicateOrderId {
+                    order_id: order.id(),
+                });
+            }
+        }
+        let (visible, hidden) = Self::queue_totals(&orders)?;
+        let visible = visible
+            .checked_add(self.visible_quantity.load(Ordering::Relaxed))
+            .ok_or_else(overflow)?;
+        let hidden = hidden
+            .checked_add(self.hidden_quantity.load(Ordering::Relaxed))
+            .ok_or_else(overflow)?;
+        let count = self.order_count() + orders.len();
+        if let Some(limits) = &self.limits {
+            self.check_bulk_limits(limits, &orders, count, batch_total)?;
+        }
+
+        // Validated: from here on nothing fails.
+        orders.sort_by_key(|order| order.timestamp());
+        for order in orders {
+            let order_id = order.id();
+            let quantity = order
+                .visible_quantity()
+                .as_u64()
+                .saturating_add(order.hidden_quantity().as_u64());
+            // Drop any stale registration a previous order with this id left.
+            self.set_display_delay(order_id, None);
+            self.set_replaced_from(order_id, None);
+            // Every id was checked absent, so this cannot be a duplicate.
+            let _ = self.orders.try_push(self.orders.allocate(order));
+            self.stats.record_order_added_with_quantity(quantity);
+        }
+        self.store_counters(visible, hidden, Some(side), count as u64);
+        Ok(ids.len())
+    }
+
+    /// [`Self::check_limits`] for a [`Self::load_bulk`] batch of `orders`,
+    /// leaving `count` orders and adding `batch_total` quantity.
+    fn check_bulk_limits(
+        &self,
+        limits: &LevelLimits,
+        orders: &[OrderType<T>],
+        count: usize,
+        batch_total: u64,
+    ) -> Result<(), PriceLevelError> {
+        let exceeded = |limit, max| Err(PriceLevelError::CapacityExceeded { limit, max });
+        if count as u64 > limits.max_orders() {
+            return exceeded(CapacityLimit::Orders, limits.max_orders());
+        }
+        let exceeds_quantity = match self
+            .total_quantity()
+            .ok()
+            .and_then(|resting| resting.checked_add(batch_total))
+        {
+            Some(total) => total > limits.max_quantity(),
+            None => limits.max_quantity() < u64::MAX,
+        };
+        if exceeds_quantity {
+            return exceeded(CapacityLimit::Quantity, limits.max_quantity());
+        }
+        if limits.limits_participants() {
+            let mut per_participant = HashMap::new();
+            for user_id in self
+                .orders
+                .iter_orders()
+                .map(|order| order.user_id())
+                .chain(orders.iter().map(OrderType::user_id))
+            {
+                let count = per_participant.entry(user_id).or_insert(0);
+                *count += 1;
+                if *count > limits.max_orders_per_participant() {
+                    return exceeded(
+                        CapacityLimit::ParticipantOrders,
+                        limits.max_orders_per_participant(),
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that admitting `order`, of `total_qty` visible plus hidden
+    /// quantity, keeps the level within `limits`, counting the level without
+    /// the resting order it `replaces`, if any. Run in the exclusive section.
+    /// A resting duplicate is reported as such, as it would be without
+    /// limits.
+    fn check_limits(
+        &self,
+        limits: &LevelLimits,
+        order: &OrderType<T>,
+        total_qty: u64,
+        replaces: Option<&OrderType<T>>,
+    ) -> Result<(), PriceLevelError> {
+        let replaced_id = replaces.map(|replaced| replaced.id());
+        if replaced_id != Some(order.id()) && self.orders.find(order.id()).is_some() {
+            return Err(PriceLevelError::DuplicateOrderId {
+                order_id: order.id(),
+            });
+        }
+        let exceeded = |limit, max| Err(PriceLevelError::CapacityExceeded { limit, max });
+        let resting_orders = self.order_count() - usize::from(replaces.is_some());
+        if resting_orders as u64 >= limits.max_orders() {
+            return exceeded(CapacityLimit::Orders, limits.max_orders());
+        }
+        // An unrepresentable total is past any set limit; an unbounded level
+        // leaves it to the counter overflow checks.
+        let released = match replaces {
+            Some(replaced) => replaced
+                .visible_quantity()
+                .as_u64()
+                .checked_add(replaced.hidden_quantity().as_u64()),
+            None => Some(0),
+        };
+        let exceeds_quantity = match self
+            .total_quantity()
+            .ok()
+            .zip(released)
+            .and_then(|(resting, released)| resting.checked_sub(released))
+            .and_then(|resting| resting.checked_add(total_qty))
+        {
+            Some(total) => total > limits.max_quantity(),
+            None => limits.max_quantity() < u64::MAX,
+        };
+        if exceeds_quantity {
+            return exceeded(CapacityLimit::Quantity, limits.max_quantity());
+        }
+        if limits.limits_participants() {
+            let user_id = order.user_id();
+            let resting = self
+                .orders
+                .iter_orders()
+                .filter(|resting| resting.user_id() == user_id && Some(resting.id()) != replaced_id)
+                .count();
+            if resting as u64 >= limits.max_orders_per_participant() {
+                return exceeded(
+                    CapacityLimit::ParticipantOrders,
+                    limits.max_orders_per_participant(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Shared body of [`Self::add_order`] and
+    /// [`Self::add_order_with_display_delay`]. `display_at`, when set, is
+    /// registered in the reservation step so the delay is published together
+    /// with the order. Returns the order stamped with the sequence its
+    /// admission committed at.
+    fn admit(
+        &self,
+        order: OrderType<T>,
+        display_at: Option<TimestampMs>,
+    ) -> Result<Sequenced<Arc<OrderType<T>>>, PriceLevelError> {
+        // Hold the fill-or-kill guard's shared side for this admission so a
+        // concurrent fill-or-kill match sees a stable depth (issue #112). This
+        // is an uncontended shared acquisition in the common case (no FOK);
+        // under sequencing it is the exclusive side, so the admission commits
+        // alone. So it is under limits, which are checked against the level's
+        // counters and must not be overshot by two admissions at once.
+        let _section = if self.limits.is_some() {
+            MutatorSection::Exclusive {
+                _section: self.fok_write(),
+            }
+        } else {
+            self.mutator_section()
+        };
+        // Fail fast if a prior panic poisoned the guard (or this very acquisition
+        // just recovered one): the level may be half-mutated (issue #130).
+        self.poison_check()?;
+        self.check_halt(HaltPolicy::allows_adds)?;
+        // Every admission message is charged, whether or not it would have
+        // been admitted.
+        if !self.rate_limits.allows(
+            order.extra_fields(),
+            RateLimitedOperation::Add,
+            self.clock.now(),
+        ) {
+            return Err(PriceLevelError::RateLimited {
+                order_id: order.id(),
+            });
+        }
+
+        let order_arc = self.admit_held(order, display_at, None, false)?;
+
+        // Signal the committed mutation so a racing post-only depth scan retries
+        // (issue #130).
+        let sequence = self.bump_mutation_epoch();
+
+        #[cfg(feature = "async")]
+        self.events.publish([(
+            sequence,
+            PriceLevelEvent::OrderAdded(Arc::clone(&order_arc)),
+        )]);
+
+        Ok(Sequenced::new(sequence, order_arc))
+    }
+
+    /// The admission checks that need no counter reservation: a market
+    /// order, the price, the symbol spec, the pinned side and the order's own
+    /// total. Returns the order's visible plus hidden quantity.
+    pub(crate) fn check_admission(&self, order: &OrderType<T>) -> Result<u64, PriceLevelError> {
+        // -------- Admission topology invariants (cheapest checks, no mutation) --------
+        //
+        // A level holds orders at exactly one price and one side. Reject a
+        // mismatch BEFORE reserving any counter capacity, so the level is left
+        // completely unchanged. Price is the cheapest check (two `u128`s), so it
+        // goes first; the side is derived from whatever is already resting.
+        // A market order has no price to rest at, so it is refused outright.
+        if order.is_market() {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!("market order {} cannot rest", order.id()),
+            });
+        }
+        if order.price().as_u128() != self.price {
+            return Err(PriceLevelError::PriceMismatch {
+                expected: self.price,
+                actual: order.price().as_u128(),
+            });
+        }
+        // Off-increment orders are rejected next, still before any mutation.
+        if let Some(spec) = &self.symbol_spec {
+            spec.check_order(order)?;
         }
         // The level's side is pinned in the topology word (issue #126): the
         // first maker pins it, later same-side makers join, and the drain that
@@ -768,15 +2062,14 @@ impl PriceLevel {
         if let Some(resting_side) = self.pinned_side()
             && order_side != resting_side
         {
-            return Err(PriceLevelError::InvalidOperation {
-                message: format!(
-                    "order side {order_side:?} is incompatible with the level's resting side {resting_side:?}"
-                ),
+            return Err(PriceLevelError::SideMismatch {
+                expected: resting_side,
+                actual: order_side,
             });
         }
 
         // Calculate quantities.
-        let visible_qty = order.visible_quantity().as_u64();
+        let visible_qty = order.working_visible_quantity().as_u64();
         let hidden_qty = order.hidden_quantity().as_u64();
 
         // Reject an order whose OWN visible + hidden total is not representable
@@ -790,10 +2083,36 @@ impl PriceLevel {
         // is `<= visible + hidden`, so it can only overflow `u64` when the
         // order's own total already does. Enforcing the invariant here makes that
         // replenish add provably overflow-free for every admitted order.
-        if visible_qty.checked_add(hidden_qty).is_none() {
+        let Some(total_qty) = visible_qty.checked_add(hidden_qty) else {
             return Err(PriceLevelError::InvalidOperation {
                 message: "order total quantity overflows u64".to_string(),
             });
+        };
+        Ok(total_qty)
+    }
+
+    /// Validate and publish `order`, inside the caller's mutator section and
+    /// after its poison and rate checks. `replaces`, when set, is the id of
+    /// the order a [`Self::cancel_replace`] admits it in place of, recorded
+    /// with the publication like a display delay. With `front` the order goes
+    /// ahead of the queue instead of behind it. Bumps no epoch and publishes
+    /// no event: the caller does both once its mutation commits.
+    fn admit_held(
+        &self,
+        order: OrderType<T>,
+        display_at: Option<TimestampMs>,
+        replaces: Option<Id>,
+        front: bool,
+    ) -> Result<Arc<OrderType<T>>, PriceLevelError> {
+        let total_qty = self.check_admission(&order)?;
+        let order_side = order.side();
+        let visible_qty = order.working_visible_quantity().as_u64();
+        let hidden_qty = order.hidden_quantity().as_u64();
+
+        // Capacity limits, decided in the exclusive section entered above so
+        // no other mutation moves the counters they read.
+        if let Some(limits) = &self.limits {
+            self.check_limits(limits, &order, total_qty, None)?;
         }
 
         // Publish through `try_push_with`, which decides id IDENTITY FIRST under
@@ -821,8 +2140,9 @@ impl PriceLevel {
         // after rolling back the visible + hidden reservations this call made
         // (a commutative, concurrency-safe undo), leaving the topology word
         // untouched and `try_push_with` publishing nothing.
-        let order_arc = Arc::new(order);
-        self.orders.try_push_with(order_arc.clone(), || {
+        let order_id = order.id();
+        let order_arc = self.orders.allocate(order);
+        let reserve = || {
             if self
                 .visible_quantity
                 .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
@@ -874,15 +2194,24 @@ impl PriceLevel {
                 }
             }
 
+            // The admission is now committed. Register the display delay before
+            // the publish, so the order is never observable as displayed, and
+            // drop any stale delay a previous order with this id left behind.
+            self.set_display_delay(order_id, display_at);
+            self.set_replaced_from(order_id, replaces);
+
             Ok(())
-        })?;
+        };
+        if front {
+            self.orders
+                .try_push_front_with(order_arc.clone(), reserve)?;
+        } else {
+            self.orders.try_push_with(order_arc.clone(), reserve)?;
+        }
 
         // Update statistics only after a committed admission.
-        self.stats.record_order_added();
-
-        // Signal the committed mutation so a racing post-only depth scan retries
-        // (issue #130).
-        self.bump_mutation_epoch();
+        self.stats
+            .record_order_added_with_quantity(visible_qty.saturating_add(hidden_qty));
 
         Ok(order_arc)
     }
@@ -891,16 +2220,63 @@ impl PriceLevel {
     ///
     /// The iteration order is not guaranteed to be stable. Use [`Self::snapshot_orders`]
     /// when deterministic ordering is required.
-    pub fn iter_orders(&self) -> impl Iterator<Item = Arc<OrderType<()>>> + '_ {
+    pub fn iter_orders(&self) -> impl Iterator<Item = Arc<OrderType<T>>> + '_ {
         self.orders.iter_orders()
     }
 
+    /// Walks the resting orders in queue-consumption order — the order
+    /// [`Self::match_order`] would fill them — without allocating and without
+    /// mutating the queue.
+    ///
+    /// Unlike [`Self::snapshot_by_insertion_seq`] this neither collects nor
+    /// sorts, and unlike [`Self::iter_orders`] it follows time priority. It is
+    /// safe to run alongside matching and admissions: the walk is lock-free
+    /// and never blocks a mutator. It is weakly consistent, though — each order
+    /// is observed as it is when reached, and an order re-sequenced to the
+    /// tail during the walk can appear twice. Take [`Self::snapshot`] when a
+    /// point-in-time view is needed.
+    pub fn orders(&self) -> impl Iterator<Item = Arc<OrderType<T>>> + '_ {
+        self.orders.orders()
+    }
+
     /// Materializes a deterministic snapshot of orders sorted by timestamp.
     #[must_use]
-    pub fn snapshot_orders(&self) -> Vec<Arc<OrderType<()>>> {
+    pub fn snapshot_orders(&self) -> Vec<Arc<OrderType<T>>> {
         self.orders.snapshot_vec()
     }
 
+    /// The status of `order_id` while it rests here: [`OrderStatus::New`]
+    /// until its first fill, [`OrderStatus::PartiallyFilled`] after it.
+    /// `None` once it has left the level; the terminal status is reported by
+    /// the call that removed it ([`MatchResult::status_transitions`] or
+    /// [`UpdateOutcome::status_transition`]).
+    #[must_use]
+    pub fn order_status(&self, order_id: Id) -> Option<OrderStatus> {
+        self.order_fill_state(order_id).map(|state| state.status())
+    }
+
+    /// How much of `order_id` has executed at this level and how much is
+    /// left, without replaying its trades. `None` once the order has left the
+    /// level.
+    ///
+    /// The leaves quantity is the order's live visible plus hidden depth, so
+    /// it also reflects resizes. Like the level's quantity counters the pair
+    /// is advisory while a match is running against the order.
+    #[must_use]
+    pub fn order_fill_state(&self, order_id: Id) -> Option<OrderFillState> {
+        let order = self.orders.find(order_id)?;
+        let executed = self.executed.get(&order_id).map_or(0, |entry| *entry);
+        Some(OrderFillState {
+            cumulative_executed: Quantity::new(executed),
+            leaves_quantity: Quantity::new(
+                order
+                    .visible_quantity()
+                    .as_u64()
+                    .saturating_add(order.hidden_quantity().as_u64()),
+            ),
+        })
+    }
+
     /// Materializes the resting orders in the exact order [`Self::match_order`]
     /// consumes them: ascending **insertion sequence** (the oldest order first).
     ///
@@ -915,7 +2291,7 @@ impl PriceLevel {
     /// Like `snapshot_orders`, this is a point-in-time view: a concurrent
     /// mutation after the call can change the queue.
     #[must_use]
-    pub fn snapshot_by_insertion_seq(&self) -> Vec<Arc<OrderType<()>>> {
+    pub fn snapshot_by_insertion_seq(&self) -> Vec<Arc<OrderType<T>>> {
         self.orders.snapshot_by_seq()
     }
 
@@ -933,10 +2309,166 @@ impl PriceLevel {
     ///
     /// Like `snapshot_by_insertion_seq`, this is a point-in-time view: a
     /// concurrent mutation after the call can change the queue.
-    pub fn snapshot_by_seq_into(&self, out: &mut Vec<Arc<OrderType<()>>>) {
+    pub fn snapshot_by_seq_into(&self, out: &mut Vec<Arc<OrderType<T>>>) {
         self.orders.snapshot_by_seq_into(out);
     }
 
+    /// The exact time priority of every order resting here: each order's
+    /// place in the queue, timestamp and the sequence it holds that place
+    /// by, captured at one point for surveillance (see [`QueueAudit`]).
+    ///
+    /// Like [`Self::snapshot`] it holds the shared side of the fill-or-kill
+    /// guard, so it never sees an exclusive sweep half applied, and the
+    /// recorded [`QueueAudit::mutation_sequence`] is read before the walk.
+    #[must_use]
+    pub fn queue_audit(&self) -> QueueAudit {
+        let _fok = self.fok_read();
+        let mutation_sequence = self.mutation_sequence();
+        let positions = self
+            .orders
+            .snapshot_entries()
+            .into_iter()
+            .enumerate()
+            .map(|(position, (arrival_sequence, order))| QueuePosition {
+                position,
+                order_id: order.id(),
+                timestamp: order.timestamp(),
+                arrival_sequence,
+                visible_quantity: order.visible_quantity().as_u64(),
+                hidden_quantity: order.hidden_quantity().as_u64(),
+            })
+            .collect();
+        QueueAudit {
+            price: Price::new(self.price),
+            captured_at: self.clock.now(),
+            mutation_sequence,
+            positions,
+        }
+    }
+
+    /// Where `order_id` stands in the queue: its zero-based index, `0` being
+    /// the order the next sweep trades against first, and the displayed
+    /// quantity resting ahead of it. `None` if the order is not resting here.
+    ///
+    /// Under FIFO with [`PriorityTiers`], an order stands behind every order of
+    /// a higher tier, and under [`HiddenPriority::DisplayedFirst`] a hidden
+    /// order stands behind every displayed one of its tier: the sweep trades
+    /// them only once those are exhausted.
+    ///
+    /// Read in one walk under the shared side of the fill-or-kill guard, as
+    /// [`Self::queue_audit`], without disturbing the queue. To place many
+    /// orders, [`Self::queue_positions`] walks the queue once for all of them.
+    #[must_use]
+    pub fn queue_position(&self, order_id: Id) -> Option<(usize, u64)> {
+        self.queue_positions(&[order_id])[0]
+    }
+
+    /// [`Self::queue_position`] of each of `order_ids`, in the same order,
+    /// from a single walk of the queue.
+    #[must_use]
+    pub fn queue_positions(&self, order_ids: &[Id]) -> Vec<Option<(usize, u64)>> {
+        let found = self.queue_standings(order_ids);
+        order_ids
+            .iter()
+            .map(|order_id| found.get(order_id).map(|&(index, ahead, _)| (index, ahead)))
+            .collect()
+    }
+
+    /// The index, displayed quantity ahead and own displayed quantity of each
+    /// of `order_ids` resting here, from one walk of the queue.
+    ///
+    /// The walk follows the order a FIFO sweep trades in: by
+    /// [`Self::sweep_pass`] when the level sweeps in passes, then by arrival.
+    fn queue_standings(&self, order_ids: &[Id]) -> HashMap<Id, (usize, u64, u64)> {
+        let wanted: HashSet<Id> = order_ids.iter().copied().collect();
+        let mut found = HashMap::with_capacity(wanted.len());
+        let _fok = self.fok_read();
+        let mut entries = self.orders.snapshot_entries();
+        let policy = self.matching_policy();
+        if self.sweeps_in_passes(policy) {
+            // Stable, so each pass keeps its arrival order.
+            entries.sort_by_key(|(_, order)| self.sweep_pass(order, policy));
+        }
+        let mut ahead = 0_u64;
+        for (index, (_, order)) in entries.into_iter().enumerate() {
+            let visible = order.working_visible_quantity().as_u64();
+            if wanted.contains(&order.id()) {
+                found.insert(order.id(), (index, ahead, visible));
+                if found.len() == wanted.len() {
+                    break;
+                }
+            }
+            ahead = ahead.saturating_add(visible);
+        }
+        found
+    }
+
+    /// An **estimate** of how long until `order_id`'s displayed quantity is
+    /// filled, if executions continue at the level's recent pace.
+    ///
+    /// The displayed quantity ahead of the order plus its own, divided by the
+    /// quantity executed per second over the trailing minute of
+    /// [`Self::stats`]. A heuristic for queue-jump decisions, not a
+    /// prediction: it assumes the recent execution rate holds, ignores
+    /// cancellations ahead (which only shorten the wait) and the hidden
+    /// quantity of icebergs and reserves. The rate follows the execution
+    /// timestamps, so it needs them in the level clock's time.
+    ///
+    /// `None` if the order is not resting here, or nothing executed in the
+    /// last minute; `Some(Duration::ZERO)` for an order with nothing left to
+    /// wait for.
+    #[must_use]
+    pub fn estimate_time_to_fill(&self, order_id: Id) -> Option<Duration> {
+        let needed = self.fill_requirement(order_id)?;
+        if needed == 0 {
+            return Some(Duration::ZERO);
+        }
+        let rate = self
+            .stats
+            .stats_window(FILL_ESTIMATE_WINDOW)
+            .quantity_per_second();
+        if rate <= 0.0 {
+            return None;
+        }
+        Duration::try_from_secs_f64(needed as f64 / rate).ok()
+    }
+
+    /// An **estimate** of the probability that `order_id`'s displayed
+    /// quantity is filled within `horizon_ms`, in `[0.0, 1.0]`.
+    ///
+    /// Executions are modelled as a Poisson stream at the level's rate over
+    /// the statistics' trailing minute, each of the window's mean execution
+    /// size; the order fills once enough of them arrive to consume the
+    /// displayed quantity ahead of it and its own. The same caveats as
+    /// [`Self::estimate_time_to_fill`] apply: it is a heuristic that assumes
+    /// the recent flow continues.
+    ///
+    /// `None` if the order is not resting here; `Some(0.0)` if nothing
+    /// executed in the last minute.
+    #[must_use]
+    pub fn estimate_fill_probability(&self, order_id: Id, horizon_ms: u64) -> Option<f64> {
+        let needed = self.fill_requirement(order_id)?;
+        if needed == 0 {
+            return Some(1.0);
+        }
+        let window = self.stats.stats_window(FILL_ESTIMATE_WINDOW);
+        if window.executions == 0 || window.quantity == 0 {
+            return Some(0.0);
+        }
+        let mean_size = window.quantity as f64 / window.executions as f64;
+        let executions_needed = (needed as f64 / mean_size).ceil();
+        let expected = window.executions_per_second() * horizon_ms as f64 / 1_000.0;
+        Some(poisson_at_least(expected, executions_needed))
+    }
+
+    /// The displayed quantity that must trade before `order_id` is filled:
+    /// the quantity ahead of it plus its own.
+    fn fill_requirement(&self, order_id: Id) -> Option<u64> {
+        self.queue_standings(&[order_id])
+            .get(&order_id)
+            .map(|&(_, ahead, own)| ahead.saturating_add(own))
+    }
+
     /// Returns `true` if any resting order has matchable depth, i.e. a positive
     /// taker would cross at this level.
     ///
@@ -976,13 +2508,101 @@ impl PriceLevel {
         }
     }
 
+    /// Per-maker allocation for a non-FIFO [`MatchingPolicy`], written to
+    /// `scratch.plan` in the order the sweep should visit the makers.
+    ///
+    /// Candidates are the makers the sweep could trade — not the taker itself
+    /// and not a pending stop — with a positive **displayed** quantity, read
+    /// from a queue snapshot in insertion-sequence (time-priority) order. The
+    /// allocations sum to `min(incoming_quantity, displayed depth)`:
+    ///
+    /// - `ProRata`: `floor(incoming * displayed / total)` each; the `< n` units
+    ///   lost to rounding go one apiece to the oldest makers. When the depth
+    ///   does not exceed the taker, every maker is simply filled in full.
+    /// - `SizeTimePriority`: makers ordered by displayed quantity descending
+    ///   (a stable sort, so ties keep time priority), each filled in full until
+    ///   the taker is exhausted.
+    /// - `Fifo`: the same greedy fill in plain time priority.
+    ///
+    /// The plan is advisory: [`Self::match_order`] re-decides every step
+    /// against the live order under its entry lock, and anything the plan
+    /// cannot place is swept FIFO afterwards.
+    fn allocation_plan(
+        &self,
+        policy: MatchingPolicy,
+        incoming_quantity: u64,
+        taker_id: Id,
+        scratch: &mut MatchScratch<T>,
+    ) {
+        let MatchScratch {
+            orders,
+            makers,
+            allocations,
+            plan,
+            ..
+        } = scratch;
+        plan.clear();
+        self.orders.snapshot_by_seq_into(orders);
+        makers.clear();
+        makers.extend(
+            orders
+                .drain(..)
+                .filter(|order| order.id() != taker_id && !order.is_stop())
+                .map(|order| (order.id(), order.visible_quantity().as_u64()))
+                .filter(|&(_, displayed)| displayed > 0),
+        );
+        let total: u128 = makers.iter().map(|&(_, q)| u128::from(q)).sum();
+
+        if policy == MatchingPolicy::ProRata && total > u128::from(incoming_quantity) {
+            // `incoming < total`, so every floor share is strictly below the
+            // maker's displayed quantity and each maker has room for one more
+            // unit: a single pass places the whole rounding remainder.
+            allocations.clear();
+            allocations.extend(makers.iter().map(|&(_, q)| {
+                // Fits in u64: the share is below `q`.
+                (u128::from(incoming_quantity) * u128::from(q) / total) as u64
+            }));
+            let mut leftover = incoming_quantity - allocations.iter().sum::<u64>();
+            for allocation in allocations.iter_mut() {
+                if leftover == 0 {
+                    break;
+                }
+                *allocation += 1;
+                leftover -= 1;
+            }
+            plan.extend(
+                makers
+                    .iter()
+                    .zip(allocations.iter())
+                    .filter(|&(_, &allocation)| allocation > 0)
+                    .map(|(&(id, _), &allocation)| (id, allocation)),
+            );
+            return;
+        }
+
+        if policy == MatchingPolicy::SizeTimePriority {
+            makers.sort_by_key(|&(_, displayed)| std::cmp::Reverse(displayed));
+        }
+        let mut left = incoming_quantity;
+        for &(id, displayed) in makers.iter() {
+            if left == 0 {
+                break;
+            }
+            let allocation = displayed.min(left);
+            plan.push_back((id, allocation));
+            left -= allocation;
+        }
+    }
+
     /// Computes how much of `incoming_quantity` this level could actually fill
     /// for a taker, in quantity units, **without mutating the queue**.
     ///
     /// This is a deterministic dry run of the FIFO sweep: it replays
-    /// [`OrderType::match_against`] over a snapshot of the resting queue in the
+    /// [`OrderType::match_against_with`] under the level's
+    /// [`ReplenishmentStrategy`] over a snapshot of the resting queue in the
     /// same price-time order the real sweep uses, including iceberg / reserve
-    /// replenishment (a refreshed tranche is re-queued at the tail) and the
+    /// replenishment (a refreshed tranche is re-queued at the tail, or kept at
+    /// the front under [`RefreshPriority::Retain`]) and the
     /// removal of a non-replenishing reserve once its visible part is drained.
     /// It also models the sweep's **replenish-headroom abort** (issue
     /// #124/#130): it tracks the level's visible counter as the sweep would
@@ -1008,20 +2628,78 @@ impl PriceLevel {
     /// from the real `match_order` behavior.
     #[must_use]
     pub fn matchable_quantity(&self, incoming_quantity: u64, taker_id: Id) -> u64 {
+        self.matchable_quantity_at(incoming_quantity, taker_id, None, None)
+    }
+
+    /// [`Self::matchable_quantity`], telling the replenishment strategy the
+    /// refreshes it sizes belong to a match at `match_timestamp`. With
+    /// `fills`, each maker step the sweep would take is recorded there, in
+    /// sweep order.
+    fn matchable_quantity_at(
+        &self,
+        incoming_quantity: u64,
+        taker_id: Id,
+        match_timestamp: Option<TimestampMs>,
+        fills: Option<&mut Vec<PlannedFill>>,
+    ) -> u64 {
         if incoming_quantity == 0 {
             return 0;
         }
 
-        // Snapshot the resting orders. `snapshot_orders()` is ordered by
-        // `(timestamp, sequence)` whereas the real sweep pops by pure insertion
-        // sequence; these coincide when timestamps are monotonic with insertion
-        // (the normal case). The two can only differ in *visit order*, never in
-        // the fillable *total*: every maker (including a fully-drained
-        // replenishing iceberg/auto-reserve) contributes the same amount
-        // regardless of when it is visited, so the sum this returns is exactly
-        // what the sweep would consume — which is all fill-or-kill depends on.
-        let mut pending: std::collections::VecDeque<Arc<OrderType<()>>> =
-            self.snapshot_orders().into();
+        // Snapshot the resting orders into the level's reusable scratch, in
+        // insertion-sequence order — the order the real sweep pops. Visit order
+        // never changes the fillable *total* anyway: every maker (including a
+        // fully-drained replenishing iceberg/auto-reserve) contributes the same
+        // amount regardless of when it is visited, so the sum this returns is
+        // exactly what the sweep would consume — which is all fill-or-kill
+        // depends on.
+        let mut scratch = self.scratch.take();
+        self.orders.snapshot_by_seq_into(&mut scratch.orders);
+        match fills {
+            Some(fills) => self.dry_sweep(
+                &mut scratch,
+                incoming_quantity,
+                taker_id,
+                match_timestamp,
+                &mut |step| {
+                    if step.consumed > 0 {
+                        fills.push(PlannedFill::new(step.maker.id(), step.consumed));
+                    }
+                },
+            ),
+            None => self.dry_sweep(
+                &mut scratch,
+                incoming_quantity,
+                taker_id,
+                match_timestamp,
+                &mut |_| {},
+            ),
+        }
+    }
+
+    /// The sweep of [`Self::match_order`] replayed without changing the
+    /// level: `incoming_quantity` taken from `scratch.orders`, the resting
+    /// queue in insertion-sequence order, first through the makers and
+    /// allocations of `scratch.plan` (an
+    /// [allocation plan](Self::allocation_plan)), then in time priority. Each
+    /// maker step is passed to `on_step` in sweep order; returns the quantity
+    /// filled.
+    fn dry_sweep(
+        &self,
+        scratch: &mut MatchScratch<T>,
+        incoming_quantity: u64,
+        taker_id: Id,
+        match_timestamp: Option<TimestampMs>,
+        on_step: &mut dyn FnMut(DryStep<'_, T>),
+    ) -> u64 {
+        let MatchScratch {
+            orders,
+            pending,
+            plan,
+            ..
+        } = scratch;
+        let policy = self.matching_policy();
+        let strict = policy == MatchingPolicy::StrictFifo;
         let mut remaining = incoming_quantity;
         let mut filled: u64 = 0;
 
@@ -1036,20 +2714,113 @@ impl PriceLevel {
         // value cannot drift and the projection is exact — a would-abort here
         // therefore correctly makes the fill-or-kill infeasible (killed) rather
         // than approving a taker the sweep would abort mid-fill (a partial fill).
-        let mut projected_visible = self.visible_quantity();
+        let mut projected_visible = self.visible_quantity.load(Ordering::Relaxed);
+
+        // Pending stops rest outside the queue the sweep walks.
+        orders.retain(|order| !order.is_stop());
+
+        // The planned makers first, each capped at its allocation, as the
+        // sweep drains its plan before falling through to time priority. A
+        // refreshed tranche that loses priority moves behind every maker the
+        // plan left resting.
+        if plan.is_empty() {
+            pending.extend(orders.drain(..));
+        } else {
+            let positions: HashMap<Id, usize> = orders
+                .iter()
+                .enumerate()
+                .map(|(position, order)| (order.id(), position))
+                .collect();
+            // `None` where the plan consumed or requeued the maker.
+            let mut resting: Vec<Option<Arc<OrderType<T>>>> = orders.drain(..).map(Some).collect();
+            let mut requeued = Vec::new();
+            while remaining > 0
+                && let Some((maker_id, allocation)) = plan.pop_front()
+            {
+                let Some(order) = positions
+                    .get(&maker_id)
+                    .and_then(|&position| resting[position].as_ref())
+                else {
+                    continue;
+                };
+                let take = allocation.min(remaining);
+                let (consumed, updated_order, hidden_reduced, leftover) =
+                    self.match_maker(order, take, match_timestamp);
+                let new_remaining = remaining - (take - leftover);
+                // A planned maker making no progress stays where it rests for
+                // the time-priority pass, as the sweep leaves it.
+                if consumed == 0
+                    && hidden_reduced == 0
+                    && new_remaining == remaining
+                    && updated_order.is_some()
+                {
+                    continue;
+                }
+                let Some(next) =
+                    Self::project_visible(projected_visible, order, consumed, hidden_reduced)
+                else {
+                    return filled;
+                };
+                projected_visible = next;
+                filled += consumed;
+                remaining = new_remaining;
+                on_step(DryStep {
+                    maker: order,
+                    consumed,
+                    hidden_reduced,
+                    fully_consumed: updated_order.is_none(),
+                });
+                let position = positions[&maker_id];
+                resting[position] = match updated_order {
+                    Some(updated)
+                        if hidden_reduced > 0
+                            && self.refresh_priority == RefreshPriority::Requeue =>
+                    {
+                        requeued.push(Arc::new(updated));
+                        None
+                    }
+                    updated => updated.map(Arc::new),
+                };
+            }
+            pending.extend(resting.into_iter().flatten().chain(requeued));
+        }
+
+        // Makers of a later pass (a lower tier, or hidden behind displayed
+        // liquidity) the sweep passes over until the current pass is
+        // exhausted, then visits in time priority: the same passes.
+        let passes = self.sweeps_in_passes(policy);
+        let mut pass = 0;
+        let mut deferred = Vec::new();
 
         while remaining > 0 {
             let Some(order) = pending.pop_front() else {
-                break;
+                let Some(next) = deferred.iter().map(|(pass, _)| *pass).min() else {
+                    break;
+                };
+                pass = next;
+                pending.extend(deferred.drain(..).map(|(_, order)| order));
+                continue;
             };
+            if passes {
+                let order_pass = self.sweep_pass(&order, policy);
+                if order_pass > pass {
+                    deferred.push((order_pass, order));
+                    continue;
+                }
+            }
             // Self-trade prevention parity: the real sweep skips a maker sharing
             // the taker id (`SelfTradeSkipped`), so the dry run must skip it too,
             // or fill-or-kill would predict depth the sweep will not take.
+            // Under strict FIFO the sweep stops there instead, so the dry run
+            // must stop too.
             if order.id() == taker_id {
+                if strict {
+                    break;
+                }
                 continue;
             }
             let (consumed, updated_order, hidden_reduced, new_remaining) =
-                order.match_against(remaining);
+                self.match_maker(&order, remaining, match_timestamp);
 
             // No-progress safety guard, identical in shape to the real sweep
             // (see `match_order`): a front maker that consumes nothing, draws no
@@ -1060,12 +2831,16 @@ impl PriceLevel {
             // nothing to `filled`, and the makers behind it are still visited.
             // Keeping this logic identical to the real sweep is what guarantees
             // `matchable_quantity` predicts exactly what `match_order` consumes,
-            // which fill-or-kill depends on.
+            // which fill-or-kill depends on. A strict-FIFO sweep stops at such
+            // a maker, so the dry run stops with it.
             if consumed == 0
                 && hidden_reduced == 0
                 && new_remaining == remaining
                 && updated_order.is_some()
             {
+                if strict {
+                    break;
+                }
                 continue;
             }
 
@@ -1073,27 +2848,12 @@ impl PriceLevel {
             // STOP on a would-abort so this maker (and everything behind it)
             // contributes nothing — the same terminal state the real sweep
             // reaches (issue #130).
-            if hidden_reduced > 0 {
-                // Replenish: checked net delta `- consumed + hidden_reduced`. A
-                // failure is the #124 abort: the maker is set aside untouched and
-                // the sweep ends, so do NOT count `consumed` and break.
-                match projected_visible
-                    .checked_sub(consumed)
-                    .and_then(|v| v.checked_add(hidden_reduced))
-                {
-                    Some(next) => projected_visible = next,
-                    None => break,
-                }
-            } else {
-                // Pure consume: visible only decreases, so it cannot abort. Track
-                // it (checked, never wraps: `consumed <= projected_visible`) so a
-                // later replenish's headroom test is accurate; a defensive
-                // underflow stops the dry run conservatively.
-                let Some(next) = projected_visible.checked_sub(consumed) else {
-                    break;
-                };
-                projected_visible = next;
-            }
+            let Some(next) =
+                Self::project_visible(projected_visible, &order, consumed, hidden_reduced)
+            else {
+                break;
+            };
+            projected_visible = next;
 
             // `consumed <= remaining <= incoming_quantity`, so this sum cannot
             // overflow `u64`; checked anyway per the no-saturate/no-wrap rule.
@@ -1102,12 +2862,22 @@ impl PriceLevel {
                 None => break,
             };
             remaining = new_remaining;
+            on_step(DryStep {
+                maker: &order,
+                consumed,
+                hidden_reduced,
+                fully_consumed: updated_order.is_none(),
+            });
 
             if let Some(updated) = updated_order {
-                if hidden_reduced > 0 {
+                if hidden_reduced > 0 && self.refresh_priority == RefreshPriority::Requeue {
                     // Replenished tranche loses time priority -> back of queue,
                     // exactly as the real sweep re-queues it.
                     pending.push_back(Arc::new(updated));
+                } else if hidden_reduced > 0 {
+                    // Replenished tranche keeps its slot: the sweep matches it
+                    // again before anything behind it.
+                    pending.push_front(Arc::new(updated));
                 } else {
                     // Pure partial fill keeps front position; the taker is now
                     // exhausted (`remaining == 0`) so the loop ends next check.
@@ -1119,6 +2889,29 @@ impl PriceLevel {
         filled
     }
 
+    /// The level's visible counter after a dry-run step moves it from
+    /// `visible`, as the sweep moves it: a replenish applies the checked net
+    /// delta `- consumed + hidden_reduced`, a displayed fill only decreases
+    /// it, and a hidden order's fill comes out of the hidden counter. `None`
+    /// is the #124 replenish-headroom abort (or a defensive underflow) at
+    /// which the sweep stops.
+    fn project_visible(
+        visible: u64,
+        order: &OrderType<T>,
+        consumed: u64,
+        hidden_reduced: u64,
+    ) -> Option<u64> {
+        if hidden_reduced > 0 {
+            visible
+                .checked_sub(consumed)
+                .and_then(|visible| visible.checked_add(hidden_reduced))
+        } else if order.is_hidden() {
+            Some(visible)
+        } else {
+            visible.checked_sub(consumed)
+        }
+    }
+
     /// Matches an incoming taker order against existing orders at this price level.
     ///
     /// The sweep consumes resting makers in strict price-time (FIFO) order until
@@ -1144,7 +2937,8 @@ impl PriceLevel {
     /// - [`TimeInForce::Ioc`]: fills `available` and discards the remainder.
     ///   The taker is never enqueued here (this layer never rests a taker), so
     ///   the remainder is simply reported and dropped by the caller.
-    /// - [`TimeInForce::Gtc`] / [`TimeInForce::Gtd`] / [`TimeInForce::Day`]:
+    /// - [`TimeInForce::Gtc`] / [`TimeInForce::Gtd`] / [`TimeInForce::Gtt`] /
+    ///   [`TimeInForce::Day`]:
     ///   fills `available`; the remainder is reported in
     ///   [`MatchResult::remaining_quantity`] for the order book to rest.
     /// - [`TakerKind::MarketToLimit`]: fills `available`; the remainder is
@@ -1157,10 +2951,11 @@ impl PriceLevel {
     /// apart from "the level had no liquidity".
     ///
     /// Time-in-force EXPIRY of resting **makers** is still NOT enforced here: a
-    /// resting maker's `Gtd` / `Day` expiry is not consulted, so an expired
+    /// resting maker's `Gtd` / `Gtt` / `Day` expiry is not consulted, so an expired
     /// maker still matches. Evicting or skipping expired makers is the
     /// caller's / order book's responsibility, keeping the match path a pure,
-    /// deterministic sweep over the resting queue.
+    /// deterministic sweep over the resting queue; [`Self::expire_orders`]
+    /// evicts them.
     ///
     /// # Arguments
     ///
@@ -1175,7 +2970,10 @@ impl PriceLevel {
     ///   statistics. It is threaded in from the caller so the match path never
     ///   reads the wall clock — guaranteeing a deterministic, replayable trade
     ///   stream for a fixed input.
-    /// * `trade_id_generator`: An atomic counter used to generate unique trade IDs.
+    /// * `trade_id_generator`: The [`ExecutionIdGenerator`] minting each
+    ///   trade's id: a [`UuidGenerator`](crate::UuidGenerator), or a
+    ///   [`SequentialIdGenerator`](crate::SequentialIdGenerator) to also number
+    ///   the trades (see [`Trade::execution_sequence`]).
     ///
     /// [`Trade`]: crate::execution::Trade
     /// [`TimeInForce`]: crate::orders::TimeInForce
@@ -1219,6 +3017,27 @@ impl PriceLevel {
     /// one logical path (see the type-level note on [`PriceLevel`]), because the
     /// side is derived from the live queue rather than stored.
     ///
+    /// ## Matching policy
+    ///
+    /// Makers are consumed according to the level's [`MatchingPolicy`], read
+    /// once at the start of the call. Under [`MatchingPolicy::Fifo`] the sweep
+    /// walks the queue front to back. Under the allocation policies it first
+    /// fills each maker its planned share (targeted, under the same per-entry
+    /// lock and with the same commit actions as the FIFO sweep), then sweeps
+    /// whatever remains in FIFO order. Under [`MatchingPolicy::StrictFifo`] the
+    /// sweep holds the level's exclusive guard for its whole duration, stops at
+    /// (rather than walks past) a maker it cannot progress, and refuses any
+    /// maker whose insertion sequence is below one it already executed.
+    /// Self-match, post-only and fill-or-kill handling are identical under
+    /// every policy.
+    ///
+    /// ## Trade stamping
+    ///
+    /// Every committed fill advances [`Self::mutation_sequence`] by one. With
+    /// [`Self::set_trade_stamping`] enabled, each trade also carries the value
+    /// its fill committed at ([`Trade::level_sequence`]), linking it to the
+    /// snapshots taken before and after it.
+    ///
     /// ## PostOnly and fill-or-kill are atomic with the sweep (issue #112)
     ///
     /// Both the post-only and the fill-or-kill decisions are made
@@ -1263,16 +3082,86 @@ impl PriceLevel {
     /// `PriceLevelStatistics::stats_degraded` flag is set (sticky,
     /// snapshot-persisted) so the under-count is observable. The emitted trades
     /// and the `MatchResult` are unaffected (issue #117).
-    pub fn match_order(
+    ///
+    /// # Crossing rule
+    ///
+    /// Each trade's taker side comes from the level's [`CrossingRule`]. Under
+    /// the default [`CrossingRule::OppositeOfMaker`] it is the opposite of the
+    /// maker's side. Under a rule that fixes the taker side, a taker meeting
+    /// makers on that same side is an invalid crossing: the match is
+    /// `Rejected` with no trade and the level untouched. Use
+    /// [`Self::try_match_order`] to receive it as
+    /// [`PriceLevelError::InvalidCrossing`] instead.
+    ///
+    /// # Panic safety and malformed makers
+    ///
+    /// The sweep does not panic on a malformed resting order, and it does not
+    /// let one corrupt the level's counters. A maker whose quantities cannot be
+    /// matched safely — empty, an overflowing visible + hidden total, or a
+    /// non-replenishing reserve with only hidden depth (see
+    /// [`QuarantineReason`]), typically restored from a corrupted snapshot — is
+    /// removed under its entry lock without a trade, its quantities are
+    /// released from the counters exactly as a cancel would, an `ERROR`
+    /// event is logged, and the order moves to the level's dead-letter list
+    /// ([`Self::quarantined_orders`] / [`Self::take_quarantined`]). The sweep
+    /// then advances to the next maker, so the taker still fills against the
+    /// well-formed depth behind it.
+    ///
+    /// # Reservations
+    ///
+    /// Quantity earmarked by an outstanding [`Self::reserve`] guard is not
+    /// matchable: the sweep stops short of it, leaving the rest of the taker
+    /// unfilled, and a fill-or-kill taker that would need it is killed.
+    ///
+    /// # Trading halts
+    ///
+    /// A level frozen by [`Self::freeze`] rejects every taker: the match is
+    /// `Rejected` with no trade and the level untouched. So does a level
+    /// outside a rejecting [price band](Self::set_price_band).
+    pub fn match_order<G>(
         &self,
         incoming_quantity: u64,
         taker_order_id: Id,
         taker_tif: TimeInForce,
         taker_kind: TakerKind,
         timestamp: TimestampMs,
-        trade_id_generator: &UuidGenerator,
-    ) -> MatchResult {
-        // -------- Fail-fast on a poisoned level (issue #130) --------
+        trade_id_generator: &G,
+    ) -> MatchResult<T>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        self.match_order_reserving(
+            incoming_quantity,
+            taker_order_id,
+            taker_tif,
+            taker_kind,
+            timestamp,
+            trade_id_generator,
+            0,
+        )
+    }
+
+    /// Body of [`Self::match_order`], for a taker that holds `own_reserved`
+    /// of the level's reserved quantity: a committing [`ReservationGuard`]
+    /// may match its own earmark, but not anyone else's.
+    #[allow(clippy::too_many_arguments)]
+    fn match_order_reserving<G>(
+        &self,
+        incoming_quantity: u64,
+        taker_order_id: Id,
+        taker_tif: TimeInForce,
+        taker_kind: TakerKind,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+        own_reserved: u64,
+    ) -> MatchResult<T>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        // Records on every return below, including the refusals.
+        let _latency = self.stats.time(LatencyOperation::MatchOrder);
+
+        // -------- Fail-fast on a poisoned level (issue #130) --------
         //
         // If a guard holder panicked mid-operation the level may be half-mutated
         // and cannot be trusted to match. `match_order` returns [`MatchResult`],
@@ -1312,6 +3201,28 @@ impl PriceLevel {
             return result;
         }
 
+        // -------- Crossing rule --------
+        //
+        // Every resting order shares the pinned side, so one check against it
+        // decides the whole level. A maker admitted on the other side after
+        // this read (the level drained and re-pinned) is caught per maker in
+        // the sweep below.
+        let crossing = self.crossing_rule();
+        if incoming_quantity > 0
+            && let Err(err) = self.check_crossing(crossing)
+        {
+            tracing::debug!(
+                taker_order_id = %taker_order_id,
+                incoming_quantity,
+                price = self.price,
+                error = %err,
+                "taker rejected: invalid crossing"
+            );
+            let mut result = MatchResult::new(taker_order_id, Quantity::new(incoming_quantity));
+            result.mark_rejected(incoming_quantity);
+            return result;
+        }
+
         // -------- Taker TIF / kind pre-checks (before any queue mutation) --------
         //
         // PostOnly: must NEVER take liquidity (issue #112). A positive PostOnly
@@ -1357,17 +3268,99 @@ impl PriceLevel {
         // `update_order` can change the matchable depth between the two: with a
         // stable queue the sweep consumes exactly what the dry-run predicted, so
         // an FOK either fills in full or (insufficient depth) is killed with the
-        // queue and counters untouched — never a partial fill. `_fok_guard` is
-        // `Some` only for a positive FOK taker; it drops at the end of the
-        // method (after the sweep). The non-FOK paths take no guard.
-        let _fok_guard = if matches!(taker_tif, TimeInForce::Fok) && incoming_quantity > 0 {
-            let guard = self.fok_write();
-            // Acquiring the write guard may have just recovered a poison; refuse
-            // to match a half-mutated level rather than sweep it (issue #130).
-            if self.is_poisoned() {
-                return MatchResult::new(taker_order_id, Quantity::new(incoming_quantity));
-            }
-            let available = self.matchable_quantity(incoming_quantity, taker_order_id);
+        // queue and counters untouched — never a partial fill. The section
+        // drops at the end of the method (after the sweep). The other paths
+        // hold the shared side, so a reservation is never decided while a
+        // sweep runs.
+        //
+        // A strict-FIFO sweep takes the same exclusive side for every TIF, so
+        // no admission, cancel or amend — and no other sweep — interleaves with
+        // it: the trades it emits are ordered against every other mutation.
+        // So does every sweep of a sequenced level, which also stamps its
+        // trades so each carries its place in that order, and every sweep
+        // while quantity is reserved, so two sweeps cannot each stop short of
+        // the earmark and together consume it.
+        let policy = self.matching_policy();
+        let strict = policy == MatchingPolicy::StrictFifo;
+        let fill_or_kill = matches!(taker_tif, TimeInForce::Fok);
+        // Opened before the section so it closes after it: alert callbacks
+        // run once the guard is released.
+        let _alerts = DeferredAlerts::begin();
+        let _section = self.sweep_section(fill_or_kill || strict || self.sequencing());
+        // Acquiring the guard may have just recovered a poison; refuse to match
+        // a half-mutated level rather than sweep it (issue #130).
+        if self.is_poisoned() {
+            return MatchResult::new(taker_order_id, Quantity::new(incoming_quantity));
+        }
+        // A freeze takes the exclusive side, so the flag read under the guard
+        // cannot change until this sweep is done.
+        if incoming_quantity > 0 && self.is_frozen() {
+            tracing::debug!(
+                taker_order_id = %taker_order_id,
+                incoming_quantity,
+                price = self.price,
+                "taker rejected: trading halted"
+            );
+            let mut result = MatchResult::new(taker_order_id, Quantity::new(incoming_quantity));
+            result.mark_rejected(incoming_quantity);
+            return result;
+        }
+        if incoming_quantity > 0
+            && let Err(err) = self.check_band(true)
+        {
+            tracing::debug!(
+                taker_order_id = %taker_order_id,
+                incoming_quantity,
+                price = self.price,
+                error = %err,
+                "taker rejected: outside the price band"
+            );
+            let mut result = MatchResult::new(taker_order_id, Quantity::new(incoming_quantity));
+            result.mark_rejected(incoming_quantity);
+            return result;
+        }
+        self.sweep_held(
+            incoming_quantity,
+            taker_order_id,
+            taker_tif,
+            timestamp,
+            trade_id_generator,
+            own_reserved,
+            policy,
+            crossing,
+        )
+    }
+
+    /// The sweep of [`Self::match_order`], run once its pre-checks passed and
+    /// its side of [`Self::fok_guard`] is held: `policy` is the matching
+    /// policy the side was chosen for, `crossing` the rule the pre-check
+    /// applied. The body [`Self::commit_match`] runs in its own exclusive
+    /// section.
+    #[allow(clippy::too_many_arguments)]
+    fn sweep_held<G>(
+        &self,
+        incoming_quantity: u64,
+        taker_order_id: Id,
+        taker_tif: TimeInForce,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+        own_reserved: u64,
+        policy: MatchingPolicy,
+        crossing: CrossingRule,
+    ) -> MatchResult<T>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        let stamp_trades = self.trade_stamping() || self.sequencing();
+        let clock_stamp = self.clock_stamping().then(|| self.clock.now());
+        let strict = policy == MatchingPolicy::StrictFifo;
+        let fill_or_kill = matches!(taker_tif, TimeInForce::Fok);
+        // Depth earmarked by other takers' reservations is not swept.
+        let sweepable = incoming_quantity.min(self.unreserved_depth(own_reserved));
+        if fill_or_kill && incoming_quantity > 0 {
+            let available = self
+                .matchable_quantity_at(incoming_quantity, taker_order_id, Some(timestamp), None)
+                .min(sweepable);
             if available < incoming_quantity {
                 tracing::debug!(
                     taker_order_id = %taker_order_id,
@@ -1380,10 +3373,7 @@ impl PriceLevel {
                 result.mark_killed(incoming_quantity);
                 return result;
             }
-            Some(guard)
-        } else {
-            None
-        };
+        }
 
         // A single sweep emits at most one trade and at most one filled-order
         // id per resting order it actually consumes. Two independent upper
@@ -1397,10 +3387,29 @@ impl PriceLevel {
         // immediately frees. The bound is advisory — `order_count` is read
         // `Relaxed` and both `Vec`s still grow if a concurrent `add_order` lands
         // mid-sweep — so it is a hint, not a cap.
-        let capacity = (incoming_quantity as usize).min(self.order_count());
+        let capacity = (sweepable as usize).min(self.order_count());
+        #[cfg(feature = "pool")]
+        let mut result = match self.orders.pool() {
+            Some(pool) => {
+                let (trades, filled) = pool.buffers(capacity);
+                MatchResult::with_buffers(
+                    taker_order_id,
+                    Quantity::new(incoming_quantity),
+                    capacity,
+                    trades,
+                    filled,
+                )
+            }
+            None => MatchResult::with_capacity(
+                taker_order_id,
+                Quantity::new(incoming_quantity),
+                capacity,
+            ),
+        };
+        #[cfg(not(feature = "pool"))]
         let mut result =
             MatchResult::with_capacity(taker_order_id, Quantity::new(incoming_quantity), capacity);
-        let mut remaining = incoming_quantity;
+        let mut remaining = sweepable;
 
         // No-progress safety guard. A maker that yields no progress
         // (`consumed == 0`, re-queued unchanged, `remaining` not decreased)
@@ -1417,9 +3426,12 @@ impl PriceLevel {
         //
         // `match_against`'s own progress fix means this guard should never fire
         // for the iceberg/reserve states it now handles; it is defense-in-depth
-        // against any future zero-progress shape (e.g. a degenerate residual
-        // from `with_reduced_quantity(0)`).
-        let mut set_aside: std::collections::HashSet<u64> = std::collections::HashSet::new();
+        // against any future zero-progress shape the malformed-maker
+        // quarantine (see [`QuarantineReason`]) does not already remove.
+        //
+        // The set lives in the level's reusable scratch, together with the
+        // allocation plan below, so steady matching does not allocate either.
+        let mut scratch = self.scratch.take();
 
         // Per-step bookkeeping carried out of the locked decision closure. The
         // trade / stats / counter work is done AFTER the closure returns so it
@@ -1427,14 +3439,18 @@ impl PriceLevel {
         // concurrent cancel rides on the `FrontAction` the queue committed under
         // the lock (see `OrderQueue::match_front`), not on when these counters
         // move (they are advisory — issue #68).
-        struct StepData {
+        struct StepData<T> {
             consumed: u64,
             hidden_reduced: u64,
             fully_consumed: bool,
             maker_id: Id,
-            maker_side: crate::orders::Side,
+            /// The taker side the crossing rule assigned against this maker.
+            taker_side: Side,
             maker_price: u128,
             maker_timestamp: u64,
+            /// The maker's extra fields, returned with the trade in the
+            /// `MatchResult`.
+            maker_extra_fields: T,
             /// Hidden quantity stranded by a full consume with no replenishment
             /// (drained reserve / leftover iceberg hidden), to subtract from the
             /// hidden counter.
@@ -1443,21 +3459,22 @@ impl PriceLevel {
             new_remaining: u64,
             /// `true` when this step's level-counter deltas were already applied
             /// INSIDE the locked decision closure (the replenish path, issue
-            /// #128). The post-lock body then skips re-applying them so the
+            /// #128, and every fill of a hidden order). The post-lock body then skips re-applying them so the
             /// counters move exactly once.
             counters_committed: bool,
         }
 
         // Either the maker progressed (carrying `StepData`), was parked
         // (`SetAside` for the no-progress guard, `SelfTradeSkipped` for a maker
-        // whose id equals the taker's), or forced the sweep to abort because
+        // whose id equals the taker's), was quarantined as malformed
+        // (`Quarantined`), or forced the sweep to abort because
         // committing its replenishment would overflow the level's visible
         // counter (`Abort`). The parked / abort variants thread the maker's id
         // (and, where relevant, its insertion seq) OUT of the locked decision
         // closure so the caller's `warn!` / `debug!` can name the maker without
         // logging inside the per-entry lock.
-        enum StepResult {
-            Progressed(StepData),
+        enum StepResult<T> {
+            Progressed(StepData<T>),
             SetAside {
                 maker_id: Id,
                 seq: u64,
@@ -1475,155 +3492,348 @@ impl PriceLevel {
             Abort {
                 maker_id: Id,
             },
+            /// Strict FIFO only: the front maker's insertion sequence is below
+            /// one this level already executed, so trading it would break
+            /// arrival order. The maker is left untouched and the sweep stops.
+            OutOfSequence {
+                maker_id: Id,
+                seq: u64,
+            },
+            /// The maker is on the side the crossing rule fixes for the taker.
+            /// It is left untouched and the sweep stops: every maker behind it
+            /// rests on the same side.
+            InvalidCrossing {
+                maker_id: Id,
+                seq: u64,
+            },
+            /// The maker trades in a later pass — a lower priority tier, or
+            /// hidden behind displayed makers not yet exhausted. It is parked
+            /// and revisited in time priority once the passes before it are.
+            Deferred {
+                maker_id: Id,
+                seq: u64,
+                pass: u16,
+            },
+            /// The maker is malformed (see [`QuarantineReason`]): the queue
+            /// removed it without a trade, and the post-lock body releases its
+            /// counters and moves it to the dead-letter list.
+            Quarantined {
+                order: Arc<OrderType<T>>,
+                reason: QuarantineReason,
+                seq: u64,
+            },
         }
 
-        while remaining > 0 {
-            let outcome = self.orders.match_front(&mut set_aside, |seq, order_arc| {
-                // Self-trade prevention, DEFENSE-IN-DEPTH (issue #126). The
-                // common case is already handled terminally before the sweep: if
-                // the taker id rests here, `match_order` returns `Rejected` with
-                // no trades. This in-sweep skip covers only the narrow race where
-                // the taker's own order is admitted AFTER that pre-check but
-                // before the sweep reaches its slot. Deterministic in every build
-                // profile (not a debug-only assert): a resting maker must never
-                // trade against a taker carrying the same id. Skip it — park its
-                // sequence like a no-progress maker so the sweep advances to the
-                // makers behind it — rather than emit a self-trade. The maker is
-                // left untouched (no trade, counters and queue unchanged).
-                //
-                // Scope: this is ORDER-ID identity — an order can never match
-                // *itself*. It is NOT account/owner-level self-trade prevention:
-                // two distinct order ids owned by the same `user_id` will still
-                // trade here. Account-level STP is the composing order book's
-                // responsibility (it knows the owner relationships this level
-                // does not).
-                if order_arc.id() == taker_order_id {
-                    return (
-                        FrontAction::SetAside,
-                        StepResult::SelfTradeSkipped {
-                            maker_id: order_arc.id(),
-                            seq,
-                        },
-                    );
-                }
+        // The pass the sweep is trading (see `sweep_pass`): makers of later
+        // passes are passed over until the current one is exhausted. Strict
+        // FIFO never defers them, since its sweep may not trade an older
+        // maker after a younger one.
+        let passes = self.sweeps_in_passes(policy);
+        let hidden_last = self.sweeps_hidden_last(policy);
+        let current_pass = Cell::new(0);
+
+        // The per-maker match decision, run under the maker's entry lock by
+        // either `OrderQueue::match_front` (FIFO) or `OrderQueue::match_entry`
+        // (allocation plan). `take` caps what this maker may fill; `remaining`
+        // is the taker's remainder before this maker.
+        let decide_step = |seq: u64,
+                           order_arc: &OrderType<T>,
+                           take: u64,
+                           remaining: u64|
+         -> (FrontAction<T>, StepResult<T>) {
+            // Self-trade prevention, DEFENSE-IN-DEPTH (issue #126). The
+            // common case is already handled terminally before the sweep: if
+            // the taker id rests here, `match_order` returns `Rejected` with
+            // no trades. This in-sweep skip covers only the narrow race where
+            // the taker's own order is admitted AFTER that pre-check but
+            // before the sweep reaches its slot. Deterministic in every build
+            // profile (not a debug-only assert): a resting maker must never
+            // trade against a taker carrying the same id. Skip it — park its
+            // sequence like a no-progress maker so the sweep advances to the
+            // makers behind it — rather than emit a self-trade. The maker is
+            // left untouched (no trade, counters and queue unchanged).
+            //
+            // Scope: this is ORDER-ID identity — an order can never match
+            // *itself*. It is NOT account/owner-level self-trade prevention:
+            // two distinct order ids owned by the same `user_id` will still
+            // trade here. Account-level STP is the composing order book's
+            // responsibility (it knows the owner relationships this level
+            // does not).
+            if order_arc.id() == taker_order_id {
+                return (
+                    FrontAction::SetAside,
+                    StepResult::SelfTradeSkipped {
+                        maker_id: order_arc.id(),
+                        seq,
+                    },
+                );
+            }
 
-                let (consumed, updated_order, hidden_reduced, new_remaining) =
-                    order_arc.match_against(remaining);
+            // A malformed maker is never handed to `match_against`: it could
+            // only spin, emit a zero-quantity trade, or strand hidden depth in
+            // the counters. Remove it under the entry lock, like a full
+            // consume, but without a trade.
+            if let Some(reason) = QuarantineReason::inspect(order_arc) {
+                return (
+                    FrontAction::Remove,
+                    StepResult::Quarantined {
+                        order: Arc::new(order_arc.clone()),
+                        reason,
+                        seq,
+                    },
+                );
+            }
 
-                // Detect a non-progressing maker: nothing consumed, no hidden
-                // drawn, the taker's remaining unchanged, and the maker handed
-                // back to us to re-queue. Park it and advance. Thread the maker
-                // id + seq out so the caller can name it in the no-progress
-                // `warn!` without logging under the per-entry lock.
-                if consumed == 0
-                    && hidden_reduced == 0
-                    && new_remaining == remaining
-                    && updated_order.is_some()
-                {
+            // Higher tiers, and displayed liquidity, trade first: a maker of a
+            // later pass is parked, untouched, and the sweep returns to it
+            // once the passes before it are exhausted.
+            if passes {
+                let pass = self.sweep_pass(order_arc, policy);
+                if pass > current_pass.get() {
                     return (
                         FrontAction::SetAside,
-                        StepResult::SetAside {
+                        StepResult::Deferred {
                             maker_id: order_arc.id(),
                             seq,
+                            pass,
                         },
                     );
                 }
+            }
 
-                let maker_id = order_arc.id();
-                let maker_side = order_arc.side();
-                let maker_price = order_arc.price().as_u128();
-                let maker_timestamp = order_arc.timestamp().as_u64();
-
-                // Hidden stranded by a full consume that does not replenish:
-                // an iceberg / reserve whose visible was fully taken but whose
-                // hidden is dropped (non-auto reserve, or a leftover the
-                // `match_against` chose not to refresh). Identical condition to
-                // the pre-#81 sweep's full-consume cleanup branch.
-                let hidden_stranded = if updated_order.is_none() && hidden_reduced == 0 {
-                    match order_arc {
-                        OrderType::IcebergOrder {
-                            hidden_quantity, ..
-                        }
-                        | OrderType::ReserveOrder {
-                            hidden_quantity, ..
-                        } if hidden_quantity.as_u64() > 0 => hidden_quantity.as_u64(),
-                        _ => 0,
+            // The crossing rule, per maker: the pre-sweep check read the pinned
+            // side once, so a maker admitted on the taker's own side since (the
+            // level drained and re-pinned mid-sweep) is caught here, untouched.
+            let Ok(taker_side) = crossing.taker_side(order_arc.side()) else {
+                return (
+                    FrontAction::SetAside,
+                    StepResult::InvalidCrossing {
+                        maker_id: order_arc.id(),
+                        seq,
+                    },
+                );
+            };
+
+            // Strict FIFO: validate the pop against the highest sequence
+            // executed so far. Equal is fine (a partially filled maker keeps
+            // its sequence across sweeps); lower means a maker that arrived
+            // earlier is trading after a later one.
+            if strict && seq < self.fifo_high_water.load(Ordering::Relaxed) {
+                return (
+                    FrontAction::SetAside,
+                    StepResult::OutOfSequence {
+                        maker_id: order_arc.id(),
+                        seq,
+                    },
+                );
+            }
+
+            // `take` is what this maker may fill: the taker's whole remainder
+            // under FIFO, the maker's planned allocation under an
+            // allocation-plan policy. Re-express the leftover relative to the
+            // taker's full remainder.
+            let (consumed, updated_order, hidden_reduced, leftover) =
+                self.match_maker(order_arc, take, Some(timestamp));
+            let new_remaining = remaining - (take - leftover);
+
+            // Detect a non-progressing maker: nothing consumed, no hidden
+            // drawn, the taker's remaining unchanged, and the maker handed
+            // back to us to re-queue. Park it and advance. Thread the maker
+            // id + seq out so the caller can name it in the no-progress
+            // `warn!` without logging under the per-entry lock.
+            if consumed == 0
+                && hidden_reduced == 0
+                && new_remaining == remaining
+                && updated_order.is_some()
+            {
+                return (
+                    FrontAction::SetAside,
+                    StepResult::SetAside {
+                        maker_id: order_arc.id(),
+                        seq,
+                    },
+                );
+            }
+
+            let maker_id = order_arc.id();
+            let maker_price = order_arc.price().as_u128();
+            let maker_timestamp = order_arc.timestamp().as_u64();
+
+            // Hidden stranded by a full consume that does not replenish:
+            // an iceberg / reserve whose visible was fully taken but whose
+            // hidden is dropped (non-auto reserve, or a leftover the
+            // `match_against` chose not to refresh). Identical condition to
+            // the pre-#81 sweep's full-consume cleanup branch.
+            let hidden_stranded = if updated_order.is_none() && hidden_reduced == 0 {
+                match order_arc {
+                    OrderType::IcebergOrder {
+                        hidden_quantity, ..
                     }
-                } else {
-                    0
-                };
+                    | OrderType::ReserveOrder {
+                        hidden_quantity, ..
+                    } if hidden_quantity.as_u64() > 0 => hidden_quantity.as_u64(),
+                    _ => 0,
+                }
+            } else {
+                0
+            };
 
-                let fully_consumed = updated_order.is_none();
-
-                // Compute the action. For a replenishment, PUBLISH this step's
-                // level-counter transition HERE — under the maker's entry lock,
-                // before returning the action (issue #128) — so a concurrent
-                // `UpdateQuantity` that next locks this same entry observes the
-                // level counters already consistent with the replenished queue.
-                // Applying it only after the entry released would leave the
-                // counter transiently BELOW the queue's visible sum, and the
-                // update's `old -> new` decrease could then underflow (`0 - 100`
-                // wrap). `counters_committed` tells the post-lock body to skip
-                // re-applying this step's deltas so the counters move exactly once.
-                let mut counters_committed = false;
-                let action = match updated_order {
-                    None => FrontAction::Remove,
-                    Some(updated) => {
-                        if hidden_reduced > 0 {
-                            // Replenishment: a fresh tranche moves hidden ->
-                            // visible. Apply the visible NET delta
-                            // (`- consumed + hidden_reduced`) as ONE checked RMW
-                            // plus the hidden decrement, atomically visible before
-                            // the entry lock releases. The checked `fetch_update`
-                            // supersedes the old load-only `fits` pre-check: even
-                            // when every resting order's own total fits `u64`, the
-                            // level's visible SUM can exceed `u64::MAX` once hidden
-                            // depth converts to visible, so a net delta that would
-                            // overflow ABORTS the step (`SetAside` mutates nothing,
-                            // emits no trade, ends the sweep) — no younger maker
-                            // trades past this FIFO front and the counter never
-                            // wraps. The stuck depth is unreachable until a cancel
-                            // / downsize frees headroom.
-                            let net_ok = self
-                                .visible_quantity
-                                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
-                                    c.checked_sub(consumed)
-                                        .and_then(|v| v.checked_add(hidden_reduced))
-                                })
-                                .is_ok();
-                            if !net_ok {
-                                return (FrontAction::SetAside, StepResult::Abort { maker_id });
+            let fully_consumed = updated_order.is_none();
+
+            // A hidden maker's fill comes out of the hidden counter, never the
+            // visible one: commit it here, under the entry lock like a
+            // replenishment, so the post-lock body leaves visible alone.
+            let mut counters_committed = false;
+            if order_arc.is_hidden() && consumed > 0 {
+                self.hidden_quantity.fetch_sub(consumed, Ordering::Relaxed);
+                counters_committed = true;
+            }
+
+            // Compute the action. For a replenishment, PUBLISH this step's
+            // level-counter transition HERE — under the maker's entry lock,
+            // before returning the action (issue #128) — so a concurrent
+            // `UpdateQuantity` that next locks this same entry observes the
+            // level counters already consistent with the replenished queue.
+            // Applying it only after the entry released would leave the
+            // counter transiently BELOW the queue's visible sum, and the
+            // update's `old -> new` decrease could then underflow (`0 - 100`
+            // wrap). `counters_committed` tells the post-lock body to skip
+            // re-applying this step's deltas so the counters move exactly once.
+            let action = match updated_order {
+                None => FrontAction::Remove,
+                Some(updated) => {
+                    if hidden_reduced > 0 {
+                        // Replenishment: a fresh tranche moves hidden ->
+                        // visible. Apply the visible NET delta
+                        // (`- consumed + hidden_reduced`) as ONE checked RMW
+                        // plus the hidden decrement, atomically visible before
+                        // the entry lock releases. The checked `fetch_update`
+                        // supersedes the old load-only `fits` pre-check: even
+                        // when every resting order's own total fits `u64`, the
+                        // level's visible SUM can exceed `u64::MAX` once hidden
+                        // depth converts to visible, so a net delta that would
+                        // overflow ABORTS the step (`SetAside` mutates nothing,
+                        // emits no trade, ends the sweep) — no younger maker
+                        // trades past this FIFO front and the counter never
+                        // wraps. The stuck depth is unreachable until a cancel
+                        // / downsize frees headroom.
+                        let net_ok = self
+                            .visible_quantity
+                            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
+                                c.checked_sub(consumed)
+                                    .and_then(|v| v.checked_add(hidden_reduced))
+                            })
+                            .is_ok();
+                        if !net_ok {
+                            return (FrontAction::SetAside, StepResult::Abort { maker_id });
+                        }
+                        self.hidden_quantity
+                            .fetch_sub(hidden_reduced, Ordering::Relaxed);
+                        counters_committed = true;
+                        // Refreshed tranche loses priority unless the level
+                        // retains it.
+                        match self.refresh_priority {
+                            RefreshPriority::Requeue => {
+                                FrontAction::ReplaceAtTail(self.orders.allocate(updated))
+                            }
+                            RefreshPriority::Retain => {
+                                FrontAction::KeepInPlace(self.orders.allocate(updated))
                             }
-                            self.hidden_quantity
-                                .fetch_sub(hidden_reduced, Ordering::Relaxed);
-                            counters_committed = true;
-                            // Refreshed tranche loses priority.
-                            FrontAction::ReplaceAtTail(Arc::new(updated))
-                        } else {
-                            // Pure partial fill: keep priority in place.
-                            FrontAction::KeepInPlace(Arc::new(updated))
                         }
+                    } else {
+                        // Pure partial fill: keep priority in place.
+                        FrontAction::KeepInPlace(self.orders.allocate(updated))
                     }
-                };
+                }
+            };
 
-                let data = StepData {
-                    consumed,
-                    hidden_reduced,
-                    fully_consumed,
-                    maker_id,
-                    maker_side,
-                    maker_price,
-                    maker_timestamp,
-                    hidden_stranded,
-                    new_remaining,
-                    counters_committed,
-                };
+            if strict {
+                self.fifo_high_water.fetch_max(seq, Ordering::Relaxed);
+            }
 
-                (action, StepResult::Progressed(data))
-            });
+            let data = StepData {
+                consumed,
+                hidden_reduced,
+                fully_consumed,
+                maker_id,
+                taker_side,
+                maker_price,
+                maker_timestamp,
+                maker_extra_fields: order_arc.extra_fields().clone(),
+                hidden_stranded,
+                new_remaining,
+                counters_committed,
+            };
+
+            (action, StepResult::Progressed(data))
+        };
+
+        // Non-FIFO policies pre-compute who fills how much. The plan is drained
+        // first; whatever it leaves (hidden depth replenished mid-sweep, a
+        // maker cancelled since planning) falls through to the FIFO sweep.
+        if !policy.is_fifo() {
+            self.allocation_plan(policy, sweepable, taker_order_id, &mut scratch);
+        }
+        let MatchScratch {
+            set_aside, plan, ..
+        } = &mut *scratch;
+        // Passes and insertion sequences of the makers later passes trade.
+        let mut deferred: Vec<(u16, u64)> = Vec::new();
+
+        while remaining > 0 {
+            let outcome = match plan.pop_front() {
+                Some((maker_id, allocation)) => {
+                    let take = allocation.min(remaining);
+                    match self.orders.match_entry(maker_id, |seq, order_arc| {
+                        decide_step(seq, order_arc, take, remaining)
+                    }) {
+                        Some(result) => FrontOutcome::Matched { result },
+                        // Gone since planning; its share falls to the FIFO tail.
+                        None => continue,
+                    }
+                }
+                None => {
+                    // An even pass trades displayed makers and an odd one
+                    // hidden makers (see `sweep_pass`), each from its own
+                    // index when hidden orders trade last.
+                    let scan = if !hidden_last {
+                        FrontScan::All
+                    } else if current_pass.get().is_multiple_of(2) {
+                        FrontScan::Displayed
+                    } else {
+                        FrontScan::Hidden
+                    };
+                    self.orders.match_front(scan, set_aside, |seq, order_arc| {
+                        decide_step(seq, order_arc, remaining, remaining)
+                    })
+                }
+            };
 
             match outcome {
-                FrontOutcome::Empty => break,
+                FrontOutcome::Empty => {
+                    // The current pass is exhausted: sweep the next one, the
+                    // hidden makers of this tier or the parked makers of a
+                    // later one, in time priority.
+                    let pass = current_pass.get();
+                    let hidden_pass = (hidden_last && pass.is_multiple_of(2)).then_some(pass + 1);
+                    if let Some(next) = deferred
+                        .iter()
+                        .map(|(pass, _)| *pass)
+                        .chain(hidden_pass)
+                        .min()
+                    {
+                        current_pass.set(next);
+                        deferred.retain(|&(pass, seq)| {
+                            let due = pass == next;
+                            if due {
+                                set_aside.remove(&seq);
+                            }
+                            !due
+                        });
+                        continue;
+                    }
+                    break;
+                }
                 FrontOutcome::Matched { result: step } => {
                     let data = match step {
                         StepResult::SetAside { maker_id, seq } => {
@@ -1638,6 +3848,10 @@ impl PriceLevel {
                                 seq,
                                 "match sweep: front maker made no progress; set aside to avoid re-pop"
                             );
+                            // Strict FIFO never trades past the front.
+                            if strict {
+                                break;
+                            }
                             continue;
                         }
                         StepResult::Abort { maker_id } => {
@@ -1671,6 +3885,59 @@ impl PriceLevel {
                                 seq,
                                 "match sweep: front maker shares the taker id; skipped to prevent self-trade"
                             );
+                            if strict {
+                                break;
+                            }
+                            continue;
+                        }
+                        StepResult::OutOfSequence { maker_id, seq } => {
+                            // Never traded: the queue committed a no-op.
+                            self.fifo_violations.fetch_add(1, Ordering::Relaxed);
+                            tracing::error!(
+                                price = self.price,
+                                remaining,
+                                order_id = %maker_id,
+                                seq,
+                                high_water = self.fifo_high_water.load(Ordering::Relaxed),
+                                "strict fifo sweep stopped: front maker is older than an executed maker"
+                            );
+                            break;
+                        }
+                        StepResult::InvalidCrossing { maker_id, seq } => {
+                            // Never traded: the queue committed a no-op.
+                            tracing::warn!(
+                                price = self.price,
+                                remaining,
+                                order_id = %maker_id,
+                                seq,
+                                crossing_rule = %crossing,
+                                "match sweep stopped: maker is on the taker's side under the crossing rule"
+                            );
+                            break;
+                        }
+                        StepResult::Deferred {
+                            maker_id,
+                            seq,
+                            pass,
+                        } => {
+                            tracing::trace!(
+                                price = self.price,
+                                remaining,
+                                order_id = %maker_id,
+                                seq,
+                                pass,
+                                "match sweep: maker deferred to a later pass"
+                            );
+                            deferred.push((pass, seq));
+                            continue;
+                        }
+                        StepResult::Quarantined { order, reason, seq } => {
+                            // Removed from the queue by the committed action; no
+                            // trade was emitted, so `remaining` is unchanged.
+                            // Release exactly what the order carried, as a cancel
+                            // would, and advance — even under strict FIFO, since
+                            // nothing traded past it.
+                            self.quarantine_maker(order, reason, taker_order_id, timestamp, seq);
                             continue;
                         }
                         StepResult::Progressed(data) => data,
@@ -1692,22 +3959,40 @@ impl PriceLevel {
                                 .fetch_sub(data.consumed, Ordering::Relaxed);
                         }
 
-                        let trade_id = Id::from_uuid(trade_id_generator.next());
+                        let (trade_id, execution_sequence) = trade_id_generator.next_execution();
 
                         // A resting maker can never be the taker here: a maker
                         // sharing the taker id is skipped (`SelfTradeSkipped`)
                         // before it reaches this point, so no self-trade is ever
                         // emitted — deterministically, in every build profile.
 
-                        let trade = Trade::with_timestamp(
+                        let mut trade = Trade::with_timestamp(
                             trade_id,
                             taker_order_id,
                             data.maker_id,
                             Price::new(self.price),
                             Quantity::new(data.consumed),
-                            data.maker_side.opposite(),
+                            data.taker_side,
                             timestamp,
-                        );
+                        )
+                        .with_maker_extra_fields(data.maker_extra_fields);
+
+                        #[cfg(test)]
+                        fire_fill_commit_hook();
+
+                        // The fill is committed in the queue: advance the
+                        // mutation sequence past it, so a snapshot taken from
+                        // here on reports a sequence that covers this trade.
+                        let sequence = self.mutation_epoch.fetch_add(1, Ordering::Release) + 1;
+                        if stamp_trades {
+                            trade = trade.with_level_sequence(sequence);
+                        }
+                        if let Some(execution_sequence) = execution_sequence {
+                            trade = trade.with_execution_sequence(execution_sequence);
+                        }
+                        if let Some(clock_stamp) = clock_stamp {
+                            trade = trade.with_clock_timestamp(clock_stamp);
+                        }
 
                         if result.add_trade(trade).is_err() {
                             remaining = new_remaining;
@@ -1718,6 +4003,26 @@ impl PriceLevel {
                             result.add_filled_order_id(data.maker_id);
                         }
 
+                        // A maker's first fill makes it partially filled; its
+                        // last one (it left the queue) makes it filled.
+                        let from = if data.fully_consumed {
+                            self.forget_status(data.maker_id)
+                        } else {
+                            self.record_partial_fill(data.maker_id, data.consumed)
+                        };
+                        let to = if data.fully_consumed {
+                            OrderStatus::Filled
+                        } else {
+                            OrderStatus::PartiallyFilled
+                        };
+                        if from != to {
+                            result.add_status_transition(OrderStatusTransition {
+                                order_id: data.maker_id,
+                                from,
+                                to,
+                            });
+                        }
+
                         // The trade is already committed (added to `result` and
                         // the queue mutated) — statistics recording cannot fail
                         // it retroactively. Recording is all-or-nothing (issue
@@ -1757,12 +4062,22 @@ impl PriceLevel {
                         }
                     }
 
+                    if data.hidden_reduced > 0 {
+                        result.add_refresh(OrderRefresh {
+                            order_id: data.maker_id,
+                            quantity: data.hidden_reduced,
+                            priority: self.refresh_priority,
+                        });
+                    }
+
                     remaining = new_remaining;
 
                     if data.fully_consumed {
                         // Maker fully consumed and removed inside `match_front`.
                         // Decrement the count and un-pin if this drained the level
                         // (issue #126); the removal already happened-before here.
+                        // A maker leaving without a trade leaves no status behind.
+                        self.executed.remove(&data.maker_id);
                         if self.topology_release_one() {
                             self.bump_topology_epoch();
                         }
@@ -1794,214 +4109,2855 @@ impl PriceLevel {
             }
         }
 
-        result.finalize(Quantity::new(remaining));
+        // The reserved depth the sweep stopped short of stays unfilled.
+        result.finalize(Quantity::new(
+            remaining.saturating_add(incoming_quantity - sweepable),
+        ));
+
+        // Early returns above emit no trades, so these are the only publishes.
+        self.executions.publish(result.trades().as_slice());
+        #[cfg(feature = "async")]
+        if self.events.is_active() {
+            // An unstamped trade is published under the sequence the sweep
+            // left behind.
+            let swept = self.mutation_sequence();
+            self.events
+                .publish(result.trades().as_slice().iter().map(|trade| {
+                    (
+                        trade.level_sequence().unwrap_or(swept),
+                        PriceLevelEvent::Trade(Box::new(trade.clone())),
+                    )
+                }));
+        }
 
         result
     }
 
-    /// Create a snapshot of the current price level state
+    /// [`Self::match_order`], reporting an invalid crossing as an error
+    /// instead of a `Rejected` result.
     ///
-    /// All aggregates are derived from a single materialized order vector so the
-    /// snapshot is internally consistent under concurrent mutation: the counter
-    /// fields can never disagree with a sum over the snapshot's own `orders`. We
-    /// fold the vector instead of reading the live atomic counters separately,
-    /// which would be a torn read (the atomics could advance between the counter
-    /// load and the order materialization).
+    /// # Errors
     ///
-    /// The `orders` vector is materialized in **queue-consumption order**
-    /// (ascending insertion sequence — the exact order [`Self::match_order`]
-    /// consumes resting orders), not the `(timestamp, sequence)` display order
-    /// of [`Self::snapshot_orders`]. Because [`Self::from_snapshot`] re-enqueues
-    /// in vector order, a restore reproduces the live queue's priority exactly —
-    /// including the "sizing an order up loses time priority" demotion, where an
-    /// order that was moved to the back of the queue keeps its original
-    /// admission timestamp. Using the timestamp view here would let such an
-    /// order sort back to its old position and wrongly regain front priority on
-    /// restore.
-    #[must_use]
-    pub fn snapshot(&self) -> PriceLevelSnapshot {
-        // Hold the fill-or-kill guard's SHARED side across the materialization
-        // (issue #130) so a snapshot can never capture a multi-maker fill-or-kill
-        // mid-transaction: the FOK holds the EXCLUSIVE side across its dry-run and
-        // sweep, so this read waits for it to fully commit or is excluded before
-        // it starts — the snapshot sees the pre- or post-FOK state, never a
-        // partial sweep. Ordinary mutators (`add_order` / `update_order`) also
-        // take the shared side, so they run concurrently with this read (read vs
-        // read) and are handled by the topology-epoch retry below for side
-        // transitions. `snapshot` intentionally does NOT poison-check: it stays
-        // available on a poisoned level for diagnostics / reconstruction.
-        let _fok = self.fok_read();
+    /// Returns [`PriceLevelError::TradingHalted`] if the level is frozen,
+    /// [`PriceLevelError::PriceOutsideBand`] if it is outside a rejecting
+    /// [price band](Self::set_price_band), and
+    /// [`PriceLevelError::InvalidCrossing`] if the level's
+    /// [`CrossingRule`] fixes the taker to the side its makers rest on. The
+    /// level is left untouched. A level that drains and re-pins to the other
+    /// side between this check and the sweep is still caught by the sweep,
+    /// which then returns a result with no trade.
+    pub fn try_match_order<G>(
+        &self,
+        incoming_quantity: u64,
+        taker_order_id: Id,
+        taker_tif: TimeInForce,
+        taker_kind: TakerKind,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> Result<MatchResult<T>, PriceLevelError>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        if incoming_quantity > 0 {
+            self.check_halt(|_| false)?;
+            self.check_band(false)?;
+            self.check_crossing(self.crossing_rule())?;
+        }
+        Ok(self.match_order(
+            incoming_quantity,
+            taker_order_id,
+            taker_tif,
+            taker_kind,
+            timestamp,
+            trade_id_generator,
+        ))
+    }
 
-        // Materialize the orders exactly once, in queue-consumption (insertion
-        // sequence) order so a snapshot round-trip re-enqueues them in identical
-        // priority order; every aggregate is derived from this same snapshot so
-        // they are mutually consistent by construction.
-        //
-        // Guard against a TORN topology (issue #126): a walk that spans a
-        // drain-then-re-admit to the opposite side could capture old-side and
-        // new-side orders together, producing a checksummed snapshot that
-        // `from_snapshot` would reject for mixed sides. `topology_epoch` is
-        // bumped on every side pin / un-pin, so if it moves across the walk we
-        // retry. The `is_single_side` fallback GUARANTEES termination: a stable
-        // epoch already implies a coherent walk (a tear requires a transition,
-        // which bumps the epoch), so we only ever loop while a walk actually came
-        // back mixed-side, which needs an in-progress opposite-side flip — once
-        // flipping stops (finite writers) the next walk is coherent and returns.
-        let orders = loop {
-            let epoch_before = self.topology_epoch.load(Ordering::Acquire);
-            let orders = self.snapshot_by_insertion_seq();
-            let epoch_after = self.topology_epoch.load(Ordering::Acquire);
+    /// Match a full taker order against this level, honoring its side,
+    /// limit price, time in force and kind.
+    ///
+    /// The taker's visible plus hidden quantity is swept as by
+    /// [`Self::match_order`], with its time in force and a [`TakerKind`]
+    /// derived from its variant: [`OrderType::PostOnly`] never takes
+    /// liquidity, [`OrderType::MarketToLimit`] reports its residual for the
+    /// book to rest, and every other variant is a standard taker. A
+    /// [`OrderType::Market`] taker has no limit and crosses any level; a
+    /// limit taker whose price does not reach this level — a buy below it or
+    /// a sell above it — trades nothing, and a fill-or-kill one is killed.
+    ///
+    /// The remainder of a taker that may not rest — an IOC or FOK one,
+    /// killed or not, and any market order — is reported as
+    /// [`MatchResult::cancelled_quantity`]. Any other remainder is left open
+    /// for the caller to rest.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] for a pending stop
+    /// order, which cannot take liquidity until activated, or a taker whose
+    /// visible plus hidden quantity overflows `u64`; returns
+    /// [`PriceLevelError::InvalidCrossing`] if the taker's side is not the
+    /// side the level's [`CrossingRule`] assigns a taker against its
+    /// resting orders, [`PriceLevelError::TradingHalted`] if the level is
+    /// frozen, and [`PriceLevelError::PriceOutsideBand`] if it is outside a
+    /// rejecting [price band](Self::set_price_band). The level is left
+    /// untouched.
+    pub fn match_taker<G>(
+        &self,
+        taker: &OrderType<T>,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> Result<MatchResult<T>, PriceLevelError>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        if taker.is_stop() {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!("stop order {} cannot take liquidity", taker.id()),
+            });
+        }
+        self.check_halt(|_| false)?;
+        self.check_band(false)?;
+        let quantity = taker
+            .visible_quantity()
+            .as_u64()
+            .checked_add(taker.hidden_quantity().as_u64())
+            .ok_or_else(|| PriceLevelError::InvalidOperation {
+                message: format!("taker {} quantity overflows u64", taker.id()),
+            })?;
+        let taker_side = taker.side();
+        if quantity > 0
+            && let Some(maker_side) = self.pinned_side()
+        {
+            let expected = self.crossing_rule().taker_side(maker_side)?;
+            if taker_side != expected {
+                return Err(PriceLevelError::InvalidCrossing {
+                    maker_side,
+                    taker_side,
+                });
+            }
+        }
+
+        let limit = taker.price().as_u128();
+        let marketable = match taker_side {
+            Side::Buy => limit >= self.price,
+            Side::Sell => limit <= self.price,
+        };
+        let mut result = if marketable {
+            let kind = match taker {
+                OrderType::PostOnly { .. } => TakerKind::PostOnly,
+                OrderType::MarketToLimit { .. } => TakerKind::MarketToLimit,
+                _ => TakerKind::Standard,
+            };
+            self.match_order(
+                quantity,
+                taker.id(),
+                taker.time_in_force(),
+                kind,
+                timestamp,
+                trade_id_generator,
+            )
+        } else {
+            let mut result = MatchResult::new(taker.id(), Quantity::new(quantity));
+            if taker.is_fill_or_kill() && quantity > 0 {
+                result.mark_killed(quantity);
+            }
+            result
+        };
+        if taker.time_in_force().is_immediate() || taker.is_market() {
+            result.cancel_remainder();
+        }
+        Ok(result)
+    }
+
+    /// Earmark `quantity` of this level's visible depth for a taker still
+    /// being risk-checked, for [`DEFAULT_RESERVATION_TIMEOUT_MS`] of the
+    /// level's clock (see [`Self::reserve_with_timeout`]).
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::reserve_with_timeout`].
+    pub fn reserve(&self, quantity: u64) -> Result<ReservationGuard<'_, T>, PriceLevelError> {
+        self.reserve_with_timeout(quantity, DEFAULT_RESERVATION_TIMEOUT_MS)
+    }
+
+    /// Earmark `quantity` of this level's visible depth for a taker still
+    /// being risk-checked, releasing it automatically once the level's clock
+    /// has advanced `timeout_ms`.
+    ///
+    /// The reservation is decided in the level's exclusive section, against
+    /// the visible quantity not already reserved, so two reservations never
+    /// earmark the same depth and no sweep is running while it is taken.
+    /// From then on the quantity is withheld from every other taker until
+    /// the returned guard commits it, rolls it back or is dropped, or the
+    /// reservation expires; see [`ReservationGuard`]. While any quantity is
+    /// reserved, sweeps at the level run one at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `quantity` is zero,
+    /// exceeds the visible quantity not already reserved, or if the level
+    /// has been poisoned by a panicked operation. Nothing is reserved.
+    pub fn reserve_with_timeout(
+        &self,
+        quantity: u64,
+        timeout_ms: u64,
+    ) -> Result<ReservationGuard<'_, T>, PriceLevelError> {
+        let _section = self.fok_write();
+        self.poison_check()?;
+        if quantity == 0 {
+            return Err(PriceLevelError::InvalidOperation {
+                message: "cannot reserve a zero quantity".to_string(),
+            });
+        }
+
+        let now = self.clock.now();
+        self.release_expired_reservations(now);
+        let reserved = self.reserved_quantity.load(Ordering::Acquire);
+        let available = self.visible_quantity().saturating_sub(reserved);
+        if quantity > available {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!(
+                    "cannot reserve {quantity}: only {available} visible quantity is unreserved"
+                ),
+            });
+        }
+
+        let id = self.next_reservation.fetch_add(1, Ordering::Relaxed);
+        let expires_at = TimestampMs::new(now.as_u64().saturating_add(timeout_ms));
+        self.reservations.insert(id, (quantity, expires_at));
+        self.reserved_quantity
+            .fetch_add(quantity, Ordering::Release);
+        Ok(ReservationGuard::new(self, id, quantity, expires_at))
+    }
+
+    /// Quantity earmarked by outstanding reservations, including any that
+    /// have expired but not yet been released.
+    #[must_use]
+    pub fn reserved_quantity(&self) -> u64 {
+        self.reserved_quantity.load(Ordering::Acquire)
+    }
+
+    /// Sweep the quantity of reservation `id` for [`ReservationGuard::commit`],
+    /// then release it. The entry leaves the map first, so expiry cannot
+    /// release it mid-sweep, but its quantity stays counted until the sweep
+    /// ends, so no other taker claims it meanwhile.
+    pub(crate) fn commit_reservation<G>(
+        &self,
+        id: u64,
+        quantity: u64,
+        taker_order_id: Id,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> Result<MatchResult<T>, PriceLevelError>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        if self.reservations.remove(&id).is_none() {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!("reservation {id} expired before it was committed"),
+            });
+        }
+        let result = self.match_order_reserving(
+            quantity,
+            taker_order_id,
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            timestamp,
+            trade_id_generator,
+            quantity,
+        );
+        self.reserved_quantity
+            .fetch_sub(quantity, Ordering::Release);
+        Ok(result)
+    }
+
+    /// Release every reservation whose expiry `now` has reached.
+    fn release_expired_reservations(&self, now: TimestampMs) {
+        self.reservations.retain(|_, (quantity, expires_at)| {
+            if *expires_at > now {
+                return true;
+            }
+            self.reserved_quantity
+                .fetch_sub(*quantity, Ordering::Release);
+            false
+        });
+    }
+
+    /// Enter a sweep's side of [`Self::fok_guard`]: the exclusive side if
+    /// `exclusive` or while any quantity is reserved, the shared side
+    /// otherwise. The reserved quantity is re-checked under the shared side:
+    /// a reservation taken before it was acquired needs the exclusive side
+    /// to be honoured, and none can be taken while it is held.
+    fn sweep_section(&self, exclusive: bool) -> MutatorSection<'_> {
+        if !exclusive && self.reserved_quantity.load(Ordering::Acquire) == 0 {
+            let shared = self.fok_read_mutator();
+            if self.reserved_quantity.load(Ordering::Acquire) == 0 {
+                return MutatorSection::Shared { _section: shared };
+            }
+        }
+        MutatorSection::Exclusive {
+            _section: self.fok_write(),
+        }
+    }
+
+    /// The depth a sweep holding `own_reserved` of the reserved quantity may
+    /// take: everything but other takers' earmarks, after releasing the
+    /// reservations that have expired. Unbounded while nothing is reserved.
+    fn unreserved_depth(&self, own_reserved: u64) -> u64 {
+        if self.reserved_quantity.load(Ordering::Acquire) == 0 {
+            return u64::MAX;
+        }
+        self.release_expired_reservations(self.clock.now());
+        let others = self
+            .reserved_quantity
+            .load(Ordering::Acquire)
+            .saturating_sub(own_reserved);
+        if others == 0 {
+            return u64::MAX;
+        }
+        self.visible_quantity()
+            .saturating_add(self.hidden_quantity())
+            .saturating_sub(others)
+    }
+
+    /// Plan how a taker of `quantity` would sweep this level, without
+    /// changing it, so external pre-trade checks can inspect the fills before
+    /// [`Self::commit_match`] executes them or [`Self::abort_match`] drops
+    /// them.
+    ///
+    /// The plan is what an immediate-or-cancel [`Self::match_order`] would
+    /// fill at the moment of the call: it skips depth earmarked by
+    /// reservations, and it stamps its trades with `timestamp` so a
+    /// time-dependent replenishment strategy sizes the same refreshes at
+    /// commit. A plan that cannot fill everything is still returned; see
+    /// [`MatchPlan::is_complete`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the level allocates
+    /// by a policy other than FIFO or strict FIFO, if `taker_order_id` rests
+    /// at this level, or if the level has been poisoned by a panicked
+    /// operation; returns [`PriceLevelError::InvalidCrossing`] if the level's
+    /// crossing rule rejects a taker against its resting side,
+    /// [`PriceLevelError::TradingHalted`] if the level is frozen, and
+    /// [`PriceLevelError::PriceOutsideBand`] if it is outside a rejecting
+    /// [price band](Self::set_price_band).
+    pub fn propose_match(
+        &self,
+        quantity: u64,
+        taker_order_id: Id,
+        timestamp: TimestampMs,
+    ) -> Result<MatchPlan, PriceLevelError> {
+        let _section = self.fok_write();
+        self.poison_check()?;
+        if quantity > 0 {
+            self.check_halt(|_| false)?;
+            self.check_band(false)?;
+        }
+        let policy = self.matching_policy();
+        if !policy.is_fifo() {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!("cannot plan a match under the {policy} policy"),
+            });
+        }
+        if quantity > 0 && self.orders.find(taker_order_id).is_some() {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!("taker {taker_order_id} rests at this level"),
+            });
+        }
+        if quantity > 0 {
+            self.check_crossing(self.crossing_rule())?;
+        }
+
+        let sweepable = quantity.min(self.unreserved_depth(0));
+        let mut fills = Vec::new();
+        self.matchable_quantity_at(sweepable, taker_order_id, Some(timestamp), Some(&mut fills));
+        Ok(MatchPlan::new(
+            self.price,
+            taker_order_id,
+            quantity,
+            timestamp,
+            self.mutation_sequence(),
+            self.reserved_quantity(),
+            policy,
+            fills,
+        ))
+    }
+
+    /// Execute `plan` as an immediate-or-cancel taker, provided the level
+    /// has not changed since [`Self::propose_match`] computed it.
+    ///
+    /// The check and the sweep run in the level's exclusive section, so
+    /// nothing commits between them: the trades are exactly the plan's
+    /// fills, in order, unless the replenishment strategy sizes refreshes
+    /// non-deterministically.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::PriceMismatch`] if the plan was computed
+    /// at another price, and [`PriceLevelError::InvalidOperation`] if it is
+    /// stale — any mutation, reservation or policy change has committed at
+    /// the level since — or if the level has been poisoned by a panicked
+    /// operation, [`PriceLevelError::TradingHalted`] if the level is frozen,
+    /// and [`PriceLevelError::PriceOutsideBand`] if it is outside a
+    /// rejecting [price band](Self::set_price_band). The level is unchanged.
+    pub fn commit_match<G>(
+        &self,
+        plan: MatchPlan,
+        trade_id_generator: &G,
+    ) -> Result<MatchResult<T>, PriceLevelError>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        if plan.price() != self.price {
+            return Err(PriceLevelError::PriceMismatch {
+                expected: self.price,
+                actual: plan.price(),
+            });
+        }
+        let _latency = self.stats.time(LatencyOperation::MatchOrder);
+        let _section = self.fok_write();
+        self.poison_check()?;
+        if plan.quantity() > 0 {
+            self.check_halt(|_| false)?;
+            self.check_band(true)?;
+        }
+        if !self.plan_is_current(&plan) {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!(
+                    "match plan for taker {} is stale: the level changed since sequence {}",
+                    plan.taker_order_id(),
+                    plan.sequence()
+                ),
+            });
+        }
+        Ok(self.sweep_held(
+            plan.quantity(),
+            plan.taker_order_id(),
+            TimeInForce::Ioc,
+            plan.timestamp(),
+            trade_id_generator,
+            0,
+            plan.policy(),
+            self.crossing_rule(),
+        ))
+    }
+
+    /// Discard `plan` without trading, returning whether it was still
+    /// current — whether [`Self::commit_match`] would have accepted it.
+    ///
+    /// The level holds nothing for an outstanding plan, so aborting changes
+    /// no state; a caller whose check rejected the plan learns whether the
+    /// level moved while the check ran.
+    pub fn abort_match(&self, plan: MatchPlan) -> bool {
+        let _section = self.fok_write();
+        plan.price() == self.price && self.plan_is_current(&plan)
+    }
+
+    /// Whether nothing has changed at the level since `plan` was computed,
+    /// for a caller in the exclusive section. Releases expired reservations
+    /// first: an expiry frees depth the plan did not count, so it makes the
+    /// plan stale too.
+    fn plan_is_current(&self, plan: &MatchPlan) -> bool {
+        self.release_expired_reservations(self.clock.now());
+        self.mutation_sequence() == plan.sequence()
+            && self.reserved_quantity() == plan.reserved()
+            && self.matching_policy() == plan.policy()
+    }
+
+    /// The quantity an auction uncross can execute against this level for
+    /// `taker_order_id`: its matchable depth less other takers' reservations.
+    /// Read in the exclusive section, like the sweep of [`Self::auction_fill`].
+    pub(crate) fn auction_depth(&self, taker_order_id: Id, timestamp: TimestampMs) -> u64 {
+        let _section = self.fok_write();
+        if self.is_poisoned() {
+            return 0;
+        }
+        self.matchable_quantity_at(u64::MAX, taker_order_id, Some(timestamp), None)
+            .min(self.unreserved_depth(0))
+    }
+
+    /// Sweep `quantity` for an auction uncross as an immediate-or-cancel
+    /// taker, in the level's priority. Unlike [`Self::match_order`] it runs
+    /// while the level is frozen: the auction owns the halt.
+    pub(crate) fn auction_fill<G>(
+        &self,
+        quantity: u64,
+        taker_order_id: Id,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> MatchResult<T>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        let _latency = self.stats.time(LatencyOperation::MatchOrder);
+        let _section = self.fok_write();
+        if self.is_poisoned() {
+            return MatchResult::new(taker_order_id, Quantity::new(quantity));
+        }
+        self.sweep_held(
+            quantity,
+            taker_order_id,
+            TimeInForce::Ioc,
+            timestamp,
+            trade_id_generator,
+            0,
+            self.matching_policy(),
+            self.crossing_rule(),
+        )
+    }
+
+    /// The [`MatchResult`] [`Self::match_order`] would return for this taker
+    /// right now, without changing the level: a what-if match for a router
+    /// weighing venues before it commits.
+    ///
+    /// The taker is checked as [`Self::match_order`] checks it and then
+    /// replayed over the resting queue, the way [`Self::propose_match`] plans
+    /// a sweep: under the level's matching policy (an allocation plan first
+    /// under a non-FIFO one), hidden priority, tiers, replenishment strategy
+    /// and refresh priority, stopping short of depth earmarked by other
+    /// takers' [reservations](Self::reserve). The fills, refreshes, status
+    /// transitions and outcome are those a match committing now would
+    /// produce, and its trades are stamped as that match would stamp them.
+    /// Nothing reaches the level: no queue or counter change, statistics,
+    /// events or execution channel trades, and `trade_id_generator` draws
+    /// ids for trades that never happen.
+    ///
+    /// The replay runs in the level's exclusive section, like a fill-or-kill
+    /// match, so no sweep or mutation interleaves with it; it walks the queue
+    /// once and copies no orders. It promises nothing about the next real
+    /// match: use a [plan](Self::propose_match) when the fills must be held
+    /// to.
+    pub fn simulate_match<G>(
+        &self,
+        incoming_quantity: u64,
+        taker_order_id: Id,
+        taker_tif: TimeInForce,
+        taker_kind: TakerKind,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> MatchResult<T>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        let mut result = MatchResult::new(taker_order_id, Quantity::new(incoming_quantity));
+        let _section = self.fok_write();
+        if self.is_poisoned() {
+            return result;
+        }
+        if incoming_quantity > 0
+            && (self.orders.find(taker_order_id).is_some()
+                || self.check_crossing(self.crossing_rule()).is_err()
+                || self.is_frozen()
+                || self.check_band(false).is_err())
+        {
+            result.mark_rejected(incoming_quantity);
+            return result;
+        }
+        if taker_kind.is_post_only() && incoming_quantity > 0 {
+            if self.has_matchable_depth(taker_order_id) {
+                result.mark_rejected(incoming_quantity);
+            }
+            return result;
+        }
+
+        let sweepable = incoming_quantity.min(self.unreserved_depth(0));
+        if matches!(taker_tif, TimeInForce::Fok)
+            && incoming_quantity > 0
+            && self
+                .matchable_quantity_at(incoming_quantity, taker_order_id, Some(timestamp), None)
+                .min(sweepable)
+                < incoming_quantity
+        {
+            result.mark_killed(incoming_quantity);
+            return result;
+        }
+
+        let taker_side = self
+            .pinned_side()
+            .and_then(|side| self.crossing_rule().taker_side(side).ok());
+        let stamp_trades = self.trade_stamping() || self.sequencing();
+        let clock_stamp = self.clock_stamping().then(|| self.clock.now());
+        let mut sequence = self.mutation_sequence();
+        // Makers already filled earlier in this replay.
+        let mut touched = HashSet::new();
+
+        let policy = self.matching_policy();
+        let mut scratch = self.scratch.take();
+        if !policy.is_fifo() {
+            self.allocation_plan(policy, sweepable, taker_order_id, &mut scratch);
+        }
+        self.orders.snapshot_by_seq_into(&mut scratch.orders);
+        let filled = self.dry_sweep(
+            &mut scratch,
+            sweepable,
+            taker_order_id,
+            Some(timestamp),
+            &mut |step| {
+                let maker_id = step.maker.id();
+                if step.consumed > 0
+                    && let Some(taker_side) = taker_side
+                {
+                    let (trade_id, execution_sequence) = trade_id_generator.next_execution();
+                    let mut trade = Trade::with_timestamp(
+                        trade_id,
+                        taker_order_id,
+                        maker_id,
+                        Price::new(self.price),
+                        Quantity::new(step.consumed),
+                        taker_side,
+                        timestamp,
+                    )
+                    .with_maker_extra_fields(step.maker.extra_fields().clone());
+                    sequence += 1;
+                    if stamp_trades {
+                        trade = trade.with_level_sequence(sequence);
+                    }
+                    if let Some(execution_sequence) = execution_sequence {
+                        trade = trade.with_execution_sequence(execution_sequence);
+                    }
+                    if let Some(clock_stamp) = clock_stamp {
+                        trade = trade.with_clock_timestamp(clock_stamp);
+                    }
+                    if result.add_trade(trade).is_err() {
+                        return;
+                    }
+                    if step.fully_consumed {
+                        result.add_filled_order_id(maker_id);
+                    }
+                    let from = if touched.insert(maker_id)
+                        && self
+                            .executed
+                            .get(&maker_id)
+                            .is_none_or(|executed| *executed == 0)
+                    {
+                        OrderStatus::New
+                    } else {
+                        OrderStatus::PartiallyFilled
+                    };
+                    let to = if step.fully_consumed {
+                        OrderStatus::Filled
+                    } else {
+                        OrderStatus::PartiallyFilled
+                    };
+                    if from != to {
+                        result.add_status_transition(OrderStatusTransition {
+                            order_id: maker_id,
+                            from,
+                            to,
+                        });
+                    }
+                }
+                if step.hidden_reduced > 0 {
+                    result.add_refresh(OrderRefresh {
+                        order_id: maker_id,
+                        quantity: step.hidden_reduced,
+                        priority: self.refresh_priority,
+                    });
+                }
+            },
+        );
+        result.finalize(Quantity::new(incoming_quantity - filled));
+        result
+    }
+
+    /// A copy-on-write fork of the level, for a backtester forking book
+    /// state to explore a scenario; see [`LevelFork`].
+    ///
+    /// The fork shares [`Self::snapshot_consistent`], which is itself shared
+    /// while the level does not mutate, and copies only the level's
+    /// configuration, counters, statistics and the execution, display-delay
+    /// and replacement records of its orders. The queue is copied on the
+    /// fork's first write. From then on the two evolve separately: matches,
+    /// adds and cancels on either never reach the other.
+    #[must_use]
+    pub fn fork(&self) -> LevelFork<T>
+    where
+        T: Send + Sync,
+    {
+        let snapshot = self.snapshot_consistent();
+        let shell = self.fork_shell(&snapshot);
+        let base = ForkBase {
+            executed: self
+                .executed
+                .iter()
+                .map(|entry| (*entry.key(), *entry.value()))
+                .collect(),
+            display_delays: if self.display_delay_count.load(Ordering::Relaxed) == 0 {
+                Vec::new()
+            } else {
+                self.display_delays
+                    .iter()
+                    .map(|entry| (*entry.key(), *entry.value()))
+                    .collect()
+            },
+            replaced_from: if self.replacement_count.load(Ordering::Relaxed) == 0 {
+                Vec::new()
+            } else {
+                self.replaced_from
+                    .iter()
+                    .map(|entry| (*entry.key(), *entry.value()))
+                    .collect()
+            },
+            snapshot,
+        };
+        LevelFork::new(shell, Arc::new(base))
+    }
+
+    /// An unwired level with this level's configuration, halt, band and
+    /// stamping, and `snapshot`'s counters, statistics and sequence, but an
+    /// empty queue: a [`LevelFork`] before its first write.
+    pub(crate) fn fork_shell(&self, snapshot: &PriceLevelSnapshot<T>) -> Self {
+        let mut shell = self.empty_like();
+        shell.stats = Arc::new(
+            snapshot
+                .statistics()
+                .with_clock_of(Arc::clone(&self.clock), self.stats.latency_tracking()),
+        );
+        shell.set_trade_stamping(self.trade_stamping());
+        shell.set_clock_stamping(self.clock_stamping());
+        shell.set_sequencing(self.sequencing());
+        shell
+            .halt
+            .store(self.halt.load(Ordering::Acquire), Ordering::Relaxed);
+        if let Some(band) = self.price_band.get() {
+            shell.price_band.set(band);
+        }
+        shell.store_counters(
+            snapshot.visible_quantity().as_u64(),
+            snapshot.hidden_quantity().as_u64(),
+            snapshot.orders().first().map(|order| order.side()),
+            snapshot.orders().len() as u64,
+        );
+        // Stamp as the level would: from the sequence the snapshot is at.
+        shell
+            .mutation_epoch
+            .store(snapshot.mutation_sequence(), Ordering::Release);
+        shell
+    }
+
+    /// Copy `base`'s queue and the records of its orders into a level built
+    /// by [`Self::fork_shell`] from the same snapshot.
+    pub(crate) fn fill_fork(&self, base: &ForkBase<T>) {
+        for order in base.snapshot.orders() {
+            // A snapshot's ids are unique, so this cannot be a duplicate.
+            let _ = self.orders.try_push(Arc::clone(order));
+        }
+        // Records are read after the snapshot: keep only those of its orders.
+        for &(order_id, executed) in &base.executed {
+            if self.orders.find(order_id).is_some() {
+                self.executed.insert(order_id, executed);
+            }
+        }
+        for &(order_id, display_at) in &base.display_delays {
+            if self.orders.find(order_id).is_some() {
+                self.set_display_delay(order_id, Some(display_at));
+            }
+        }
+        for &(order_id, orig_order_id) in &base.replaced_from {
+            if self.orders.find(order_id).is_some() {
+                self.set_replaced_from(order_id, Some(orig_order_id));
+            }
+        }
+    }
+
+    /// Create a snapshot of the current price level state
+    ///
+    /// All aggregates are derived from a single materialized order vector so the
+    /// snapshot is internally consistent under concurrent mutation: the counter
+    /// fields can never disagree with a sum over the snapshot's own `orders`. We
+    /// fold the vector instead of reading the live atomic counters separately,
+    /// which would be a torn read (the atomics could advance between the counter
+    /// load and the order materialization).
+    ///
+    /// The `orders` vector is materialized in **queue-consumption order**
+    /// (ascending insertion sequence — the exact order [`Self::match_order`]
+    /// consumes resting orders), not the `(timestamp, sequence)` display order
+    /// of [`Self::snapshot_orders`]. Because [`Self::from_snapshot`] re-enqueues
+    /// in vector order, a restore reproduces the live queue's priority exactly —
+    /// including the "sizing an order up loses time priority" demotion, where an
+    /// order that was moved to the back of the queue keeps its original
+    /// admission timestamp. Using the timestamp view here would let such an
+    /// order sort back to its old position and wrongly regain front priority on
+    /// restore.
+    #[must_use]
+    pub fn snapshot(&self) -> PriceLevelSnapshot<T> {
+        // Hold the fill-or-kill guard's SHARED side across the materialization
+        // (issue #130) so a snapshot can never capture a multi-maker fill-or-kill
+        // mid-transaction: the FOK holds the EXCLUSIVE side across its dry-run and
+        // sweep, so this read waits for it to fully commit or is excluded before
+        // it starts — the snapshot sees the pre- or post-FOK state, never a
+        // partial sweep. Ordinary mutators (`add_order` / `update_order`) also
+        // take the shared side, so they run concurrently with this read (read vs
+        // read) and are handled by the topology-epoch retry below for side
+        // transitions. `snapshot` intentionally does NOT poison-check: it stays
+        // available on a poisoned level for diagnostics / reconstruction.
+        let _fok = self.fok_read();
+
+        // Read the mutation sequence BEFORE the walk: every mutation it counts
+        // happened-before this `Acquire` load, so the walk is guaranteed to
+        // reflect it. A mutation committing during the walk may or may not be
+        // captured, but is never counted — the recorded sequence is a lower
+        // bound, and exact on a quiescent level.
+        let mutation_sequence = self.mutation_sequence();
+
+        // Materialize the orders exactly once, in queue-consumption (insertion
+        // sequence) order so a snapshot round-trip re-enqueues them in identical
+        // priority order; every aggregate is derived from this same snapshot so
+        // they are mutually consistent by construction.
+        //
+        // Guard against a TORN topology (issue #126): a walk that spans a
+        // drain-then-re-admit to the opposite side could capture old-side and
+        // new-side orders together, producing a checksummed snapshot that
+        // `from_snapshot` would reject for mixed sides. `topology_epoch` is
+        // bumped on every side pin / un-pin, so if it moves across the walk we
+        // retry. The `is_single_side` fallback GUARANTEES termination: a stable
+        // epoch already implies a coherent walk (a tear requires a transition,
+        // which bumps the epoch), so we only ever loop while a walk actually came
+        // back mixed-side, which needs an in-progress opposite-side flip — once
+        // flipping stops (finite writers) the next walk is coherent and returns.
+        let orders = loop {
+            let epoch_before = self.topology_epoch.load(Ordering::Acquire);
+            let orders = self.snapshot_by_insertion_seq();
+            let epoch_after = self.topology_epoch.load(Ordering::Acquire);
             if epoch_before == epoch_after || Self::is_single_side(&orders) {
                 break orders;
             }
-            // A side transition raced the walk AND left a mixed-side view; retry.
+            // A side transition raced the walk AND left a mixed-side view; retry.
+        };
+
+        self.snapshot_of(orders, mutation_sequence)
+    }
+
+    /// Create a snapshot only if the level changed since `last_seen`, a
+    /// [`Self::mutation_sequence`] value from an earlier call.
+    ///
+    /// Returns `None`, without walking the queue, while the mutation sequence
+    /// still reads `last_seen`; otherwise the sequence the snapshot recorded
+    /// and the snapshot, as [`Self::snapshot`]. Pass the returned sequence to
+    /// the next call. It is a lower bound, so a mutation racing the walk makes
+    /// the next call snapshot again rather than be missed. Start a poller
+    /// from `u64::MAX` to always get a first snapshot.
+    #[must_use]
+    pub fn snapshot_if_changed(&self, last_seen: u64) -> Option<(u64, PriceLevelSnapshot<T>)> {
+        if self.mutation_sequence() == last_seen {
+            return None;
+        }
+        let snapshot = self.snapshot();
+        Some((snapshot.mutation_sequence(), snapshot))
+    }
+
+    /// Create a snapshot that is exactly the level's state at one mutation
+    /// sequence, without locking.
+    ///
+    /// [`Self::snapshot`] takes the shared side of the fill-or-kill guard, so
+    /// it waits behind a fill-or-kill or strict-FIFO sweep and delays the next
+    /// one for the length of its walk; and its walk is weakly consistent, so
+    /// under a lock-free match it can capture a later maker's fill without an
+    /// earlier one's. This read takes no lock. It walks the queue between two
+    /// reads of [`Self::mutation_sequence`] and of the counts of mutators in
+    /// the guard, and keeps the walk only if the sequence did not move and no
+    /// mutator was inside the guard at either read. A mutator enters the guard
+    /// before it changes the queue and bumps the sequence before it leaves, so
+    /// a change not yet covered by a sequence is never kept. The returned
+    /// snapshot then guarantees:
+    ///
+    /// - **Point in time.** Its orders and aggregates are the queue exactly as
+    ///   it stood at its recorded
+    ///   [`mutation_sequence`](PriceLevelSnapshot::mutation_sequence): every
+    ///   mutation up to that sequence is reflected and none after it.
+    /// - **No partial sweeps.** It never shows a fill-or-kill or strict-FIFO
+    ///   sweep half applied.
+    /// - **Shared, not copied.** The validated view is published through an
+    ///   epoch-reclaimed pointer, so while the level does not mutate, later
+    ///   calls return the same `Arc` without walking the queue.
+    /// - **Writers are not delayed.** Matchers and mutators take no part in the
+    ///   protocol beyond counting themselves in and out of the guard they
+    ///   already take.
+    ///
+    /// A walk that overlaps a mutation is discarded and retried. After a
+    /// bounded number of retries under sustained mutation, the last published
+    /// view is returned instead: still a point-in-time view, but of an earlier
+    /// sequence than [`Self::mutation_sequence`] now reports. Until a first
+    /// view is published the read keeps retrying, yielding between walks, and
+    /// returns as soon as one walk runs without a concurrent mutation.
+    ///
+    /// Statistics are copied at the walk, as in [`Self::snapshot`]; they are
+    /// independent counters and not covered by the guarantees above.
+    #[must_use]
+    pub fn snapshot_consistent(&self) -> Arc<PriceLevelSnapshot<T>>
+    where
+        T: Send + Sync,
+    {
+        let published = self.consistent_view.load();
+        let mut attempts = 0;
+        loop {
+            let sections_before = self.exclusive_sections.load(Ordering::Acquire);
+            let mutators_before = self.shared_mutators.load(Ordering::Acquire);
+            let sequence_before = self.mutation_epoch.load(Ordering::Acquire);
+            // An odd count means an exclusive sweep is under way, and a shared
+            // mutator may have changed the queue without a sequence yet: no
+            // walk can be trusted until they finish.
+            if sections_before.is_multiple_of(2) && mutators_before == 0 {
+                if let Some(view) = &published
+                    && view.mutation_sequence() == sequence_before
+                {
+                    return Arc::clone(view);
+                }
+                let orders = self.snapshot_by_insertion_seq();
+                // A walk that saw a mutator's change sees it counted in below,
+                // or, once it left, its sequence bump.
+                std::sync::atomic::fence(Ordering::Acquire);
+                let sections_after = self.exclusive_sections.load(Ordering::Acquire);
+                let mutators_after = self.shared_mutators.load(Ordering::Acquire);
+                let sequence_after = self.mutation_epoch.load(Ordering::Relaxed);
+                if sequence_before == sequence_after
+                    && sections_before == sections_after
+                    && mutators_after == 0
+                {
+                    let view = Arc::new(self.snapshot_of(orders, sequence_before));
+                    self.consistent_view.publish(sequence_before, &view);
+                    return view;
+                }
+            }
+            attempts += 1;
+            if attempts >= CONSISTENT_SNAPSHOT_ATTEMPTS
+                && let Some(view) = &published
+            {
+                return Arc::clone(view);
+            }
+            std::thread::yield_now();
+        }
+    }
+
+    /// Fold a materialized, queue-consumption-ordered `orders` vector into a
+    /// snapshot recorded at `mutation_sequence`, deriving every aggregate from
+    /// the vector itself.
+    fn snapshot_of(
+        &self,
+        orders: Vec<Arc<OrderType<T>>>,
+        mutation_sequence: u64,
+    ) -> PriceLevelSnapshot<T> {
+        let order_count = orders.len();
+
+        let mut visible_quantity: u64 = 0;
+        let mut hidden_quantity: u64 = 0;
+
+        for order in &orders {
+            // Checked arithmetic per the crate's no-saturate/no-wrap rule.
+            // `snapshot()` is infallible (changing it would ripple to
+            // `snapshot_package` / `snapshot_to_json` and every caller), so the
+            // overflow branch needs a value, not a `Result`. That branch is
+            // unreachable for any state the level can represent: the level tracks
+            // the same running total in a `u64` atomic counter, so a sum that
+            // overflows `u64` here is one the level itself could never have held.
+            // On that impossible branch we fall back to the live atomic counter —
+            // the engine's own authoritative `u64` total (best-effort, since the
+            // branch cannot occur for representable state).
+            match visible_quantity.checked_add(order.working_visible_quantity().as_u64()) {
+                Some(total) => visible_quantity = total,
+                None => {
+                    debug_assert!(false, "snapshot visible quantity overflow is unreachable");
+                    visible_quantity = self.visible_quantity.load(Ordering::Relaxed);
+                }
+            }
+
+            match hidden_quantity.checked_add(order.hidden_quantity().as_u64()) {
+                Some(total) => hidden_quantity = total,
+                None => {
+                    debug_assert!(false, "snapshot hidden quantity overflow is unreachable");
+                    hidden_quantity = self.hidden_quantity.load(Ordering::Relaxed);
+                }
+            }
+        }
+
+        // Persist the per-level statistics alongside the aggregates so the
+        // snapshot round-trip reproduces the recorded execution history. The
+        // clone snapshots the eight atomic counters (best-effort, like every
+        // other read path); statistics are independent counters, not part of
+        // the queue / counter consistency invariant.
+        PriceLevelSnapshot::from_raw_parts_with_stats(
+            Price::new(self.price),
+            Quantity::new(visible_quantity),
+            Quantity::new(hidden_quantity),
+            order_count,
+            orders,
+            (*self.stats).clone(),
+        )
+        .with_mutation_sequence(mutation_sequence)
+    }
+
+    /// Serialize the current price level state into a checksum-protected snapshot package.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if computing the snapshot's
+    /// aggregate quantities overflows while building the package's checksummed
+    /// payload, or [`PriceLevelError::SerializationError`] if encoding the
+    /// snapshot payload to compute its SHA-256 checksum fails.
+    pub fn snapshot_package(&self) -> Result<PriceLevelSnapshotPackage<T>, PriceLevelError>
+    where
+        T: Serialize,
+    {
+        PriceLevelSnapshotPackage::new(self.snapshot())
+    }
+
+    /// Serialize the current price level state into a snapshot package whose
+    /// checksum is computed with `algorithm` instead of the default SHA-256.
+    ///
+    /// See [`ChecksumAlgorithm`] for the integrity / speed trade-off.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::snapshot_package`].
+    pub fn snapshot_package_with(
+        &self,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<PriceLevelSnapshotPackage<T>, PriceLevelError>
+    where
+        T: Serialize,
+    {
+        PriceLevelSnapshotPackage::with_checksum_algorithm(self.snapshot(), algorithm)
+    }
+
+    /// Serialize the current price level state to JSON, including checksum metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if building the snapshot
+    /// package overflows an aggregate quantity, or
+    /// [`PriceLevelError::SerializationError`] if the package cannot be encoded
+    /// to JSON.
+    pub fn snapshot_to_json(&self) -> Result<String, PriceLevelError>
+    where
+        T: Serialize,
+    {
+        self.snapshot_package()?.to_json()
+    }
+}
+
+impl<T: Clone> PriceLevel<T> {
+    /// Apply an update to an existing order at this price level.
+    ///
+    /// # Quantity-update priority policy
+    ///
+    /// For [`OrderUpdate::UpdateQuantity`] (and the same-price branch of
+    /// [`OrderUpdate::UpdatePriceAndQuantity`]) this method follows the
+    /// conventional exchange price-time-priority rules:
+    ///
+    /// - **Decrease or unchanged total quantity** keeps the maker's queue
+    ///   position. The stored order is updated *in place* at its existing
+    ///   insertion sequence, so it is consumed at the same point in FIFO order
+    ///   as before. Reducing size never forfeits time priority.
+    /// - **Increase in total quantity** demotes the order to the *back* of the
+    ///   queue (it is assigned a fresh insertion sequence). Sizing an order up
+    ///   loses time priority, matching standard exchange behaviour.
+    ///
+    /// Total quantity is `visible + hidden`; the branch is chosen by comparing
+    /// the order's total before and after the update. Every order variant is
+    /// resized by [`OrderType::with_reduced_quantity`] (single-quantity
+    /// variants rewrite their `quantity`; two-tranche variants rewrite the
+    /// visible tranche and keep hidden), so the branch reflects the real size
+    /// change rather than a silent no-op.
+    ///
+    /// # Applied to the live maker (issue #115)
+    ///
+    /// The resize, the priority decision, and the level-counter update are all
+    /// derived from the order **currently resident in the queue**, computed and
+    /// committed together under a single per-entry lock — never from a pre-read.
+    /// A concurrent match or replenishment that commits first is therefore fully
+    /// reflected: an update applies `new_quantity` to the *live* visible tranche
+    /// and preserves the *live* hidden depth, so it can never resurrect executed
+    /// or cancelled quantity, and the increase-vs-decrease policy is chosen from
+    /// the live total (never stale). The level counters are validated with
+    /// checked math before the queue mutates, so an update that would overflow a
+    /// level counter is rejected with the level left unchanged.
+    ///
+    /// # Outcome
+    ///
+    /// - [`UpdateOutcome::UpdatedInPlace`] — a same-price resize
+    ///   ([`OrderUpdate::UpdateQuantity`], or a same-price
+    ///   [`OrderUpdate::UpdatePriceAndQuantity`] / [`OrderUpdate::Replace`]);
+    ///   carries the resized order.
+    /// - [`UpdateOutcome::RemovedForReprice`] — a price-changing update; the
+    ///   order is removed from this level and returned for the caller to
+    ///   re-insert at its new price.
+    /// - [`UpdateOutcome::Cancelled`] — an [`OrderUpdate::Cancel`]; carries the
+    ///   removed order and the status it rested with.
+    /// - [`UpdateOutcome::NotFound`] — no order with that id rests here.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if an
+    /// [`OrderUpdate::UpdatePrice`] / [`OrderUpdate::Replace`] would not move
+    /// the order to a different price level, if computing an order's total
+    /// quantity overflows `u64`, or if an [`OrderUpdate::UpdateQuantity`] would
+    /// overflow the level's visible- or hidden-quantity counter (the maker and
+    /// its queue position are left unchanged in that case). With a
+    /// [`Self::symbol_spec`], returns [`PriceLevelError::InvalidTick`] or
+    /// [`PriceLevelError::InvalidLot`] for an off-increment update, leaving the
+    /// order where it was. With a [rate limiter](Self::set_rate_limiter),
+    /// returns [`PriceLevelError::RateLimited`] for a refused update or
+    /// cancel, leaving the order untouched. On a [frozen](Self::freeze_with)
+    /// level refusing cancels or amends, returns
+    /// [`PriceLevelError::TradingHalted`] for one.
+    pub fn update_order(&self, update: OrderUpdate) -> Result<UpdateOutcome<T>, PriceLevelError> {
+        self.update_order_sequenced(update)
+            .map(Sequenced::into_value)
+    }
+
+    /// [`Self::update_order`], stamping the outcome with the
+    /// [`Self::mutation_sequence`] the update committed at.
+    ///
+    /// An [`UpdateOutcome::NotFound`] changed nothing and carries the
+    /// sequence of the state it was decided against. The stamp totally
+    /// orders the update against the level's other mutations when
+    /// [`Self::set_sequencing`] is on (see [`Sequenced`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::update_order`].
+    pub fn update_order_sequenced(
+        &self,
+        update: OrderUpdate,
+    ) -> Result<Sequenced<UpdateOutcome<T>>, PriceLevelError> {
+        let _latency = self.stats.time(LatencyOperation::UpdateOrder);
+        // Hold the fill-or-kill guard's shared side for the whole update so a
+        // concurrent fill-or-kill match cannot observe the depth shrink (cancel
+        // / down-size) or grow mid-decision (issue #112). Uncontended in the
+        // common case (no FOK running). Acquired exactly ONCE here: the
+        // same-price branches of `UpdatePriceAndQuantity` / `Replace` delegate
+        // to the guard-free `update_order_inner` rather than recursing into this
+        // method, because std `RwLock` reads are NOT reentrant. A recursive
+        // `read()` with a writer queued between the two acquisitions (the lock
+        // is writer-preferring, so the queued writer blocks the second reader)
+        // would deadlock against a `fok_write` waiting on the first reader.
+        let _section = self.mutator_section();
+        // Fail fast on a poisoned level (issue #130).
+        self.poison_check()?;
+        self.check_halt(if matches!(update, OrderUpdate::Cancel { .. }) {
+            HaltPolicy::allows_cancels
+        } else {
+            HaltPolicy::allows_amends
+        })?;
+        // Charged to the participant of the order the update names; an update
+        // of an order not resting here has no participant and is not charged.
+        if self.rate_limits.is_set()
+            && let Some(order) = self.orders.find(update.order_id())
+        {
+            let operation = if matches!(update, OrderUpdate::Cancel { .. }) {
+                RateLimitedOperation::Cancel
+            } else {
+                RateLimitedOperation::Update
+            };
+            if !self
+                .rate_limits
+                .allows(order.extra_fields(), operation, self.clock.now())
+            {
+                return Err(PriceLevelError::RateLimited {
+                    order_id: update.order_id(),
+                });
+            }
+        }
+        let outcome = self.update_order_inner(update)?;
+        Ok(self.finish_update(update, outcome))
+    }
+
+    /// Stamp, record and publish an update [`Self::update_order_inner`]
+    /// decided, inside the caller's mutator section.
+    fn finish_update(
+        &self,
+        update: OrderUpdate,
+        outcome: UpdateOutcome<T>,
+    ) -> Sequenced<UpdateOutcome<T>> {
+        // A committed mutation (the order was found and cancelled / resized /
+        // moved) bumps the mutation epoch so a racing post-only depth scan
+        // retries (issue #130). `NotFound` and `Err` change nothing, so they do
+        // not bump.
+        let sequence = if outcome.is_found() {
+            self.bump_mutation_epoch()
+        } else {
+            self.mutation_sequence()
+        };
+        if outcome.is_found() && self.amend_history.capacity() > 0 {
+            self.amend_history.record(AmendRecord {
+                update,
+                timestamp: self.clock.now(),
+                sequence,
+            });
+        }
+        #[cfg(feature = "async")]
+        self.events
+            .publish(PriceLevelEvent::from_update(&outcome).map(|event| (sequence, event)));
+        Sequenced::new(sequence, outcome)
+    }
+
+    /// Cancel the order `order_id` and admit `replacement` in its place, as
+    /// one mutation.
+    ///
+    /// Unlike an [`OrderUpdate::Replace`], which resizes or moves the order
+    /// under its own id, this is an exchange-style cancel/replace: the
+    /// replacement is a new order, under a new id or the same one, that joins
+    /// the back of the queue, and the level records the id it replaced
+    /// ([`Self::orig_order_id`]) for as long as it rests. The replacement must
+    /// rest at this level's price and on the replaced order's side; a move to
+    /// another price is the book's cancel plus add. A display delay of the
+    /// replaced order is not carried over.
+    ///
+    /// Both halves commit in the exclusive section at one
+    /// [`Self::mutation_sequence`], and subscribers receive a single
+    /// `PriceLevelEvent::OrderReplaced`, so a journal never records one half
+    /// without the other. The rate limiter charges it as an update of the
+    /// replaced order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::OrderNotFound`] if `order_id` does not rest
+    /// here, [`PriceLevelError::SideMismatch`] if the replacement is on the
+    /// other side, [`PriceLevelError::DuplicateOrderId`] if its id belongs to
+    /// another resting order, and otherwise the errors of [`Self::add_order`]
+    /// and [`Self::update_order`], decided as if the replaced order had
+    /// already left. In every case the level is unchanged.
+    pub fn cancel_replace(
+        &self,
+        order_id: Id,
+        replacement: OrderType<T>,
+    ) -> Result<OrderReplacement<T>, PriceLevelError> {
+        self.cancel_replace_sequenced(order_id, replacement)
+            .map(Sequenced::into_value)
+    }
+
+    /// [`Self::cancel_replace`], stamping the result with the
+    /// [`Self::mutation_sequence`] the replacement committed at.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::cancel_replace`].
+    pub fn cancel_replace_sequenced(
+        &self,
+        order_id: Id,
+        replacement: OrderType<T>,
+    ) -> Result<Sequenced<OrderReplacement<T>>, PriceLevelError> {
+        let _latency = self.stats.time(LatencyOperation::UpdateOrder);
+        // Exclusive, so nothing moves the counters and limits validated below
+        // between the cancel and the admission.
+        let _section = self.fok_write();
+        self.poison_check()?;
+        self.check_halt(HaltPolicy::allows_amends)?;
+        let Some(replaced) = self.orders.find(order_id) else {
+            return Err(PriceLevelError::OrderNotFound { order_id });
+        };
+        if !self.rate_limits.allows(
+            replaced.extra_fields(),
+            RateLimitedOperation::Update,
+            self.clock.now(),
+        ) {
+            return Err(PriceLevelError::RateLimited { order_id });
+        }
+
+        // Decide every rejection before the cancel, so a refused replacement
+        // leaves the replaced order where it was.
+        if replacement.side() != replaced.side() {
+            return Err(PriceLevelError::SideMismatch {
+                expected: replaced.side(),
+                actual: replacement.side(),
+            });
+        }
+        self.check_admission(&replacement)?;
+        if replacement.id() != order_id && self.orders.find(replacement.id()).is_some() {
+            return Err(PriceLevelError::DuplicateOrderId {
+                order_id: replacement.id(),
+            });
+        }
+        let swapped = |counter: &AtomicU64, released: Quantity, added: Quantity| {
+            counter
+                .load(Ordering::Relaxed)
+                .checked_sub(released.as_u64())
+                .and_then(|rest| rest.checked_add(added.as_u64()))
+        };
+        if swapped(
+            &self.visible_quantity,
+            replaced.working_visible_quantity(),
+            replacement.working_visible_quantity(),
+        )
+        .is_none()
+            || swapped(
+                &self.hidden_quantity,
+                replaced.hidden_quantity(),
+                replacement.hidden_quantity(),
+            )
+            .is_none()
+        {
+            return Err(PriceLevelError::InvalidOperation {
+                message: "price level quantity overflow on replacement".to_string(),
+            });
+        }
+        if let Some(limits) = &self.limits {
+            let total_qty = replacement
+                .visible_quantity()
+                .as_u64()
+                .saturating_add(replacement.hidden_quantity().as_u64());
+            self.check_limits(limits, &replacement, total_qty, Some(&replaced))?;
+        }
+
+        let (replaced, replaced_status) =
+            match self.update_order_inner(OrderUpdate::Cancel { order_id })? {
+                UpdateOutcome::Cancelled(order, status) => (order, status),
+                _ => return Err(PriceLevelError::OrderNotFound { order_id }),
+            };
+        let replacement = match self.admit_held(replacement, None, Some(order_id), false) {
+            Ok(replacement) => replacement,
+            Err(err) => {
+                // Unreachable after the checks above; put the cancelled order
+                // back rather than lose it.
+                let _ = self.admit_held(
+                    Arc::unwrap_or_clone(Arc::clone(&replaced)),
+                    None,
+                    None,
+                    false,
+                );
+                return Err(err);
+            }
+        };
+
+        let sequence = self.bump_mutation_epoch();
+        #[cfg(feature = "async")]
+        self.events.publish([(
+            sequence,
+            PriceLevelEvent::OrderReplaced {
+                replaced: Arc::clone(&replaced),
+                replacement: Arc::clone(&replacement),
+            },
+        )]);
+        Ok(Sequenced::new(
+            sequence,
+            OrderReplacement {
+                replaced,
+                replaced_status,
+                replacement,
+            },
+        ))
+    }
+
+    /// Reverse `trade`, a fill at this level that the venue busted after the
+    /// fact, and with `reinstate` give the maker its quantity back.
+    ///
+    /// The trade's execution, quantity and value are taken back out of
+    /// [`Self::stats`] (see `PriceLevelStatistics::record_bust` for what
+    /// stays). `reinstate` is the maker order to rest again, under the
+    /// trade's maker id and side and carrying the quantity to give back —
+    /// normally the maker as it rested, resized to `trade.quantity()`. It is
+    /// admitted at the FRONT of the queue, ahead of every order resting, the
+    /// priority the busted quantity held when it traded; several reinstated
+    /// makers end up in the reverse of their reinstatement order, so bust a
+    /// sweep's trades last first. Under [`MatchingPolicy::StrictFifo`] the
+    /// reinstatement restarts the arrival-order check.
+    ///
+    /// The bust commits in the exclusive section at one
+    /// [`Self::mutation_sequence`], and subscribers receive a single
+    /// `PriceLevelEvent::TradeBusted` carrying the trade and the reinstated
+    /// maker. Returns the reinstated order.
+    ///
+    /// # Limitations
+    ///
+    /// The level keeps no trade history, so it trusts `trade`: it checks the
+    /// price but cannot tell whether the trade executed here, or whether it
+    /// was already busted — a second bust reverses the statistics twice. The
+    /// taker's side of the trade, any fees, and the execution channel are
+    /// the caller's to correct. A maker still resting cannot be reinstated
+    /// under its own id: resize it with [`Self::update_order`] instead. The
+    /// bust is neither charged to the rate limiter nor refused by a
+    /// [halt](Self::freeze_with), as a venue correcting its record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::PriceMismatch`] if the trade did not print
+    /// at this level's price, [`PriceLevelError::InvalidOperation`] if
+    /// `reinstate` is not the trade's maker, and otherwise the errors of
+    /// [`Self::add_order`] for the reinstated order, including
+    /// [`PriceLevelError::DuplicateOrderId`] while the maker still rests. In
+    /// every case the level is unchanged.
+    pub fn bust_execution(
+        &self,
+        trade: &Trade<T>,
+        reinstate: Option<OrderType<T>>,
+    ) -> Result<Option<Arc<OrderType<T>>>, PriceLevelError> {
+        if trade.price().as_u128() != self.price {
+            return Err(PriceLevelError::PriceMismatch {
+                expected: self.price,
+                actual: trade.price().as_u128(),
+            });
+        }
+        if let Some(order) = &reinstate
+            && (order.id() != trade.maker_order_id() || order.side() != trade.maker_side())
+        {
+            return Err(PriceLevelError::InvalidOperation {
+                message: format!(
+                    "order {} is not the maker of trade {}",
+                    order.id(),
+                    trade.trade_id()
+                ),
+            });
+        }
+        let _section = self.fok_write();
+        self.poison_check()?;
+
+        let reinstated = match reinstate {
+            Some(order) => {
+                let order = self.admit_held(order, None, None, true)?;
+                // The reinstated maker sits below the sequences already
+                // executed; it is ahead of them by decision, not by a race.
+                self.fifo_high_water.store(0, Ordering::Relaxed);
+                Some(order)
+            }
+            None => None,
+        };
+        self.stats
+            .record_bust(trade.quantity().as_u64(), self.price);
+
+        let sequence = self.bump_mutation_epoch();
+        #[cfg(feature = "async")]
+        self.events.publish([(
+            sequence,
+            PriceLevelEvent::TradeBusted {
+                trade: Box::new(trade.clone()),
+                reinstated: reinstated.clone(),
+            },
+        )]);
+        #[cfg(not(feature = "async"))]
+        let _ = sequence;
+        Ok(reinstated)
+    }
+
+    /// The id of the order `order_id` was admitted in place of by
+    /// [`Self::cancel_replace`], while it rests here. `None` for an order
+    /// admitted any other way, or once it has left the level.
+    #[must_use]
+    pub fn orig_order_id(&self, order_id: Id) -> Option<Id> {
+        if self.replacement_count.load(Ordering::Relaxed) == 0 {
+            return None;
+        }
+        let orig_order_id = *self.replaced_from.get(&order_id)?;
+        self.orders.find(order_id).map(|_| orig_order_id)
+    }
+
+    /// Move every order resting at `other` into this level, re-priced to this
+    /// level's price, and re-sequence the combined queue by order timestamp.
+    ///
+    /// This is the re-bucketing step of a tick-size change: merging the level
+    /// at `10_001` into the one at `10_000` leaves a single queue in which each
+    /// order keeps the time priority its timestamp gives it, orders with equal
+    /// timestamps keeping this level's first. `other` is left empty. Returns
+    /// the number of orders moved.
+    ///
+    /// Both levels are held in their exclusive section for the whole move, so
+    /// no admission or update interleaves with it, and the visible, hidden
+    /// and order-count counters of both are reconciled from their queues
+    /// before it is released. Like [`Self::from_snapshot`], the move assumes
+    /// no match is running on either level. Each order's partial-fill state
+    /// and display delay move with it; the statistics stay with the level
+    /// that recorded them. With the `async` feature, `other` publishes an
+    /// `PriceLevelEvent::OrderRemoved` and this level an
+    /// `PriceLevelEvent::OrderAdded` for each moved order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `other` is this level,
+    /// either level is poisoned, or the merged quantities overflow,
+    /// [`PriceLevelError::SideMismatch`] if the two levels rest orders on
+    /// opposite sides, [`PriceLevelError::DuplicateOrderId`] if an order id
+    /// rests at both, and [`PriceLevelError::InvalidTick`] or
+    /// [`PriceLevelError::InvalidLot`] if a re-priced order fails this level's
+    /// symbol spec. Neither level is changed on error.
+    pub fn merge_from(&self, other: &PriceLevel<T>) -> Result<usize, PriceLevelError> {
+        if std::ptr::eq(self, other) {
+            return Err(PriceLevelError::InvalidOperation {
+                message: "cannot merge a price level into itself".to_string(),
+            });
+        }
+        // Take the two exclusive sections in address order, so two merges
+        // running in opposite directions cannot deadlock.
+        let _sections = if std::ptr::from_ref(self) < std::ptr::from_ref(other) {
+            let own = self.fok_write();
+            (own, other.fok_write())
+        } else {
+            let theirs = other.fok_write();
+            (self.fok_write(), theirs)
+        };
+        self.poison_check()?;
+        other.poison_check()?;
+
+        let incoming = other.orders.snapshot_by_seq();
+        let Some(first) = incoming.first() else {
+            return Ok(0);
+        };
+        let side = first.side();
+        if let Some(resting_side) = self.pinned_side()
+            && side != resting_side
+        {
+            return Err(PriceLevelError::SideMismatch {
+                expected: resting_side,
+                actual: side,
+            });
+        }
+        let price = Price::new(self.price);
+        let mut moved = Vec::with_capacity(incoming.len());
+        for order in &incoming {
+            if self.orders.find(order.id()).is_some() {
+                return Err(PriceLevelError::DuplicateOrderId {
+                    order_id: order.id(),
+                });
+            }
+            let order = order.with_price(price);
+            if let Some(spec) = &self.symbol_spec {
+                spec.check_order(&order)?;
+            }
+            moved.push(order);
+        }
+        let resting = self.orders.snapshot_by_seq();
+        let (visible, hidden) = Self::queue_totals(resting.iter().map(Arc::as_ref).chain(&moved))?;
+
+        // Validated: from here on nothing fails. Empty `other` first.
+        for order in &incoming {
+            let order_id = order.id();
+            let _ = other.orders.remove(order_id);
+            if let Some((_, executed)) = other.executed.remove(&order_id) {
+                self.executed.insert(order_id, executed);
+            }
+            if let Some((_, display_at)) = other.display_delays.remove(&order_id) {
+                other.display_delay_count.fetch_sub(1, Ordering::Relaxed);
+                self.set_display_delay(order_id, Some(display_at));
+            }
+            if let Some((_, orig_order_id)) = other.replaced_from.remove(&order_id) {
+                other.replacement_count.fetch_sub(1, Ordering::Relaxed);
+                self.set_replaced_from(order_id, Some(orig_order_id));
+            }
+        }
+        other.store_counters(0, 0, None, 0);
+
+        // Then rebuild this level's queue in timestamp order. The sort is
+        // stable and this level's orders come first, so they win ties.
+        let moved_count = moved.len();
+        let mut merged = resting.clone();
+        merged.extend(moved.into_iter().map(|order| self.orders.allocate(order)));
+        merged.sort_by_key(|order| order.timestamp());
+        for order in &resting {
+            let _ = self.orders.remove(order.id());
+        }
+        for order in &merged {
+            // Every id was removed above or checked absent, so this cannot
+            // be a duplicate.
+            let _ = self.orders.try_push(Arc::clone(order));
+        }
+        self.store_counters(visible, hidden, Some(side), merged.len() as u64);
+
+        // Both exclusive sections are still held, so each level's sequence is
+        // the one the merge committed at.
+        #[cfg(feature = "async")]
+        {
+            let sequence = other.mutation_sequence();
+            other.events.publish(
+                incoming
+                    .into_iter()
+                    .map(|order| (sequence, PriceLevelEvent::OrderRemoved(order))),
+            );
+            let sequence = self.mutation_sequence();
+            let added: HashSet<Id> = resting.iter().map(|order| order.id()).collect();
+            self.events.publish(
+                merged
+                    .into_iter()
+                    .filter(|order| !added.contains(&order.id()))
+                    .map(|order| (sequence, PriceLevelEvent::OrderAdded(order))),
+            );
+        }
+
+        Ok(moved_count)
+    }
+
+    /// Move the resting orders for which `predicate` returns `true` out of
+    /// this level into a new level at the same price, and return it.
+    ///
+    /// The new level is built with this level's configuration (queue shards,
+    /// replenishment strategy, refresh priority, symbol spec, clock, matching
+    /// policy and crossing rule) and keeps the moved orders in their current
+    /// queue order. Pair it with [`Self::merge_from`] to re-bucket part of a
+    /// level into another price.
+    ///
+    /// Like [`Self::merge_from`], the split runs in this level's exclusive
+    /// section, reconciles the counters of both levels from their queues,
+    /// carries each order's partial-fill state and display delay, assumes no
+    /// match is running on the level, and leaves the statistics behind. With
+    /// the `async` feature this level publishes an
+    /// `PriceLevelEvent::OrderRemoved` for each moved order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the level is poisoned.
+    pub fn split_off<F>(&self, mut predicate: F) -> Result<PriceLevel<T>, PriceLevelError>
+    where
+        F: FnMut(&OrderType<T>) -> bool,
+    {
+        let _section = self.fok_write();
+        self.poison_check()?;
+
+        let split = self.empty_like();
+        let (taken, kept): (Vec<_>, Vec<_>) = self
+            .orders
+            .snapshot_by_seq()
+            .into_iter()
+            .partition(|order| predicate(order));
+        if taken.is_empty() {
+            return Ok(split);
+        }
+        for order in &taken {
+            let order_id = order.id();
+            let _ = self.orders.remove(order_id);
+            let _ = split.orders.try_push(Arc::clone(order));
+            if let Some((_, executed)) = self.executed.remove(&order_id) {
+                split.executed.insert(order_id, executed);
+            }
+            if let Some((_, display_at)) = self.display_delays.remove(&order_id) {
+                self.display_delay_count.fetch_sub(1, Ordering::Relaxed);
+                split.set_display_delay(order_id, Some(display_at));
+            }
+            if let Some((_, orig_order_id)) = self.replaced_from.remove(&order_id) {
+                self.replacement_count.fetch_sub(1, Ordering::Relaxed);
+                split.set_replaced_from(order_id, Some(orig_order_id));
+            }
+        }
+        // A subset of a level's orders cannot overflow its counters.
+        let (visible, hidden) = Self::queue_totals(kept.iter().map(Arc::as_ref))?;
+        let side = kept.first().map(|order| order.side());
+        self.store_counters(visible, hidden, side, kept.len() as u64);
+        let (visible, hidden) = Self::queue_totals(taken.iter().map(Arc::as_ref))?;
+        let side = taken.first().map(|order| order.side());
+        split.store_counters(visible, hidden, side, taken.len() as u64);
+
+        #[cfg(feature = "async")]
+        {
+            let sequence = self.mutation_sequence();
+            self.events.publish(
+                taken
+                    .into_iter()
+                    .map(|order| (sequence, PriceLevelEvent::OrderRemoved(order))),
+            );
+        }
+
+        Ok(split)
+    }
+
+    /// An empty level at this level's price with its runtime configuration.
+    fn empty_like(&self) -> Self {
+        let mut level = Self::new(self.price);
+        level.orders = OrderQueue::with_shards(self.orders.shard_count());
+        #[cfg(feature = "pool")]
+        if let Some(pool) = self.orders.pool() {
+            level.orders = std::mem::take(&mut level.orders).with_pool(Arc::clone(pool));
+        }
+        level.replenishment = Arc::clone(&self.replenishment);
+        level.refresh_priority = self.refresh_priority;
+        level.hidden_priority = self.hidden_priority;
+        level.priority_tiers = self.priority_tiers.clone();
+        level.symbol_spec = self.symbol_spec;
+        level.limits = self.limits;
+        level.clock = Arc::clone(&self.clock);
+        level.stats = Arc::new(PriceLevelStatistics::with_clock(Arc::clone(&self.clock)));
+        level
+            .stats
+            .set_latency_tracking(self.stats.latency_tracking());
+        level.set_matching_policy(self.matching_policy());
+        level.set_crossing_rule(self.crossing_rule());
+        level
+    }
+
+    /// The visible and hidden totals of `orders`.
+    fn queue_totals<'a, I>(orders: I) -> Result<(u64, u64), PriceLevelError>
+    where
+        T: 'a,
+        I: IntoIterator<Item = &'a OrderType<T>>,
+    {
+        let overflow = || PriceLevelError::InvalidOperation {
+            message: "price level quantity overflow".to_string(),
         };
+        orders
+            .into_iter()
+            .try_fold((0_u64, 0_u64), |(visible, hidden), order| {
+                Ok((
+                    visible
+                        .checked_add(order.working_visible_quantity().as_u64())
+                        .ok_or_else(overflow)?,
+                    hidden
+                        .checked_add(order.hidden_quantity().as_u64())
+                        .ok_or_else(overflow)?,
+                ))
+            })
+    }
 
-        let order_count = orders.len();
+    /// Overwrite the counters and topology word with totals reconciled from
+    /// the queue. Only called inside the exclusive section, with the queue
+    /// already holding exactly `count` orders on `side`.
+    fn store_counters(&self, visible: u64, hidden: u64, side: Option<Side>, count: u64) {
+        self.visible_quantity.store(visible, Ordering::Relaxed);
+        self.hidden_quantity.store(hidden, Ordering::Relaxed);
+        let tag = match side {
+            Some(side) if count > 0 => topology::tag_of(side),
+            _ => topology::TAG_UNPINNED,
+        };
+        let previous = self
+            .topology
+            .swap(topology::pack(tag, count), Ordering::AcqRel);
+        if topology::tag(previous) != tag {
+            self.bump_topology_epoch();
+        }
+        self.bump_mutation_epoch();
+    }
 
-        let mut visible_quantity: u64 = 0;
-        let mut hidden_quantity: u64 = 0;
+    /// Activate every pending stop order at this level whose trigger condition
+    /// is met by `reference_price`.
+    ///
+    /// A buy stop triggers once `reference_price` rises to or above its trigger
+    /// price, a sell stop once it falls to or below it (see
+    /// [`OrderType::is_triggered_by`]). Each triggered order is converted in
+    /// place by [`OrderType::activate`] — a [`OrderType::StopMarket`] becomes a
+    /// [`OrderType::MarketToLimit`], a [`OrderType::StopLimit`] becomes a
+    /// [`OrderType::Standard`] — and from then on it is ordinary liquidity that
+    /// [`Self::match_order`] consumes. Until then a pending stop rests dormant
+    /// in an index of its own, which the sweep never visits, and it never
+    /// counts as matchable depth. Only that index is walked here, so the cost
+    /// of a trigger check grows with the pending stops, not the queue.
+    ///
+    /// An activated stop is moved to the **back** of the queue (a fresh
+    /// insertion sequence), exactly as a newly entered order would be, so it
+    /// never gains time priority over liquidity that arrived while it was
+    /// dormant. A pending stop counts in neither [`Self::visible_quantity`]
+    /// nor any displayed view, so activation is when its quantity joins the
+    /// level's depth.
+    ///
+    /// Each conversion is decided against the live order under its per-entry
+    /// lock, so a concurrent cancel either removes the stop first (it is not
+    /// reported) or cancels the activated order afterwards. Stops are visited
+    /// in insertion-sequence order and returned in that order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the level has been
+    /// poisoned by a panicked operation.
+    pub fn trigger_orders(
+        &self,
+        reference_price: Price,
+    ) -> Result<Vec<Arc<OrderType<T>>>, PriceLevelError> {
+        // Mutator side of the fill-or-kill guard, like every other mutator, so
+        // a fill-or-kill dry run never sees depth appear mid-decision.
+        let _section = self.mutator_section();
+        self.poison_check()?;
 
-        for order in &orders {
-            // Checked arithmetic per the crate's no-saturate/no-wrap rule.
-            // `snapshot()` is infallible (changing it would ripple to
-            // `snapshot_package` / `snapshot_to_json` and every caller), so the
-            // overflow branch needs a value, not a `Result`. That branch is
-            // unreachable for any state the level can represent: the level tracks
-            // the same running total in a `u64` atomic counter, so a sum that
-            // overflows `u64` here is one the level itself could never have held.
-            // On that impossible branch we fall back to the live atomic counter —
-            // the engine's own authoritative `u64` total (best-effort, since the
-            // branch cannot occur for representable state).
-            match visible_quantity.checked_add(order.visible_quantity().as_u64()) {
-                Some(total) => visible_quantity = total,
-                None => {
-                    debug_assert!(false, "snapshot visible quantity overflow is unreachable");
-                    visible_quantity = self.visible_quantity();
+        let mut activated = Vec::new();
+        for order in self.orders.pending_stops() {
+            if !order.is_triggered_by(reference_price) {
+                continue;
+            }
+            let outcome = self.orders.update_entry(order.id(), |live| {
+                // Re-check against the live order: a concurrent activation or
+                // replacement may have committed since the snapshot.
+                match live.activate() {
+                    Some(live_order) if live.is_triggered_by(reference_price) => {
+                        // The stop was dormant; its quantity joins the depth
+                        // now, under the entry lock, as an admission's would.
+                        let joined = live_order.working_visible_quantity().as_u64();
+                        self.visible_quantity
+                            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
+                                c.checked_add(joined)
+                            })
+                            .map_err(|_| PriceLevelError::InvalidOperation {
+                                message: "price level visible quantity overflow on activation"
+                                    .to_string(),
+                            })?;
+                        Ok(UpdateDecision::ReplaceAtTail(Arc::new(live_order)))
+                    }
+                    _ => Err(PriceLevelError::InvalidOperation {
+                        message: "order is no longer a triggered stop".to_string(),
+                    }),
                 }
+            });
+            if let Some(Ok(order)) = outcome {
+                // Activation creates matchable depth; signal it so a racing
+                // post-only depth scan retries. Once per activation, so a
+                // consistent snapshot never sees half a batch under one
+                // sequence.
+                let sequence = self.bump_mutation_epoch();
+                activated.push((sequence, order));
             }
+        }
+        #[cfg(feature = "async")]
+        self.events
+            .publish(activated.iter().map(|(sequence, order)| {
+                (
+                    *sequence,
+                    PriceLevelEvent::OrderActivated(Arc::clone(order)),
+                )
+            }));
+        Ok(activated.into_iter().map(|(_, order)| order).collect())
+    }
 
-            match hidden_quantity.checked_add(order.hidden_quantity().as_u64()) {
-                Some(total) => hidden_quantity = total,
-                None => {
-                    debug_assert!(false, "snapshot hidden quantity overflow is unreachable");
-                    hidden_quantity = self.hidden_quantity();
+    /// Recalculate every trailing stop at this level against `market_price`
+    /// and report the ones whose stop level has been reached.
+    ///
+    /// Each [`OrderType::TrailingStop`] first has its stop level checked with
+    /// [`OrderType::is_trailing_stop_reached`]; a reached order is returned
+    /// unchanged, in queue-consumption order, for the caller to act on (cancel
+    /// and re-submit as a market order, route to another level, …). Otherwise
+    /// its `last_reference_price` is ratcheted by
+    /// [`OrderType::with_trailing_reference`] — up for a sell, down for a buy —
+    /// so the stop follows a favourable move and never gives ground back.
+    ///
+    /// The two cases are mutually exclusive for a given `market_price`: a move
+    /// that ratchets the reference is, by construction, away from the stop.
+    /// Both are decided on the live order under its per-entry lock, so a
+    /// concurrent call's ratchet is never missed, and the ratchet is committed
+    /// there at the order's existing insertion sequence; the quantity is unchanged, so neither the
+    /// level counters nor the order's time priority move. A trailing stop
+    /// cancelled concurrently is simply skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the level has been
+    /// poisoned by a panicked operation.
+    pub fn update_trailing_stops(
+        &self,
+        market_price: Price,
+    ) -> Result<Vec<Arc<OrderType<T>>>, PriceLevelError> {
+        let _section = self.mutator_section();
+        self.poison_check()?;
+
+        let mut reached = Vec::new();
+        for order in self.snapshot_by_insertion_seq() {
+            if !matches!(*order, OrderType::TrailingStop { .. }) {
+                continue;
+            }
+            // Decide against the live order, under its entry lock: a
+            // concurrent ratchet or update may have moved its reference since
+            // the snapshot, and a stale reference would miss a stop the live
+            // one has reached. A rejection only means there was nothing to
+            // move, so it is neither counted nor published.
+            let mut hit = None;
+            let outcome = self.orders.update_entry(order.id(), |live| {
+                if live.is_trailing_stop_reached(market_price) {
+                    hit = Some(Arc::clone(live));
+                    return Err(PriceLevelError::InvalidOperation {
+                        message: "trailing stop reached".to_string(),
+                    });
                 }
+                match live.with_trailing_reference(market_price) {
+                    Some(ratcheted) => Ok(UpdateDecision::KeepInPlace(Arc::new(ratcheted))),
+                    None => Err(PriceLevelError::InvalidOperation {
+                        message: "trailing stop reference unchanged".to_string(),
+                    }),
+                }
+            });
+            if let Some(live) = hit {
+                reached.push(live);
+                continue;
+            }
+            if let Some(Ok(ratcheted)) = outcome {
+                let sequence = self.bump_mutation_epoch();
+                #[cfg(feature = "async")]
+                self.events
+                    .publish([(sequence, PriceLevelEvent::OrderUpdated(ratcheted))]);
+                #[cfg(not(feature = "async"))]
+                let _ = (sequence, ratcheted);
             }
         }
+        Ok(reached)
+    }
 
-        // Persist the per-level statistics alongside the aggregates so the
-        // snapshot round-trip reproduces the recorded execution history. The
-        // clone snapshots the eight atomic counters (best-effort, like every
-        // other read path); statistics are independent counters, not part of
-        // the queue / counter consistency invariant.
-        PriceLevelSnapshot::from_raw_parts_with_stats(
-            Price::new(self.price),
-            Quantity::new(visible_quantity),
-            Quantity::new(hidden_quantity),
-            order_count,
-            orders,
-            (*self.stats).clone(),
-        )
+    /// Display every delayed order whose display time `now` has reached.
+    ///
+    /// Orders admitted by [`Self::add_order_with_display_delay`] stay withheld
+    /// until this is called with a `now` at or past their placement timestamp
+    /// plus the delay; from then on their visible tranche counts in
+    /// [`Self::visible_quantity`] and they appear in
+    /// [`Self::public_snapshot`]. Their queue position and quantity are
+    /// untouched — visibility is not a queue mutation. A delay left behind by
+    /// an order that has since been filled, cancelled or repriced away is
+    /// pruned once due, without being reported; until then it withholds
+    /// nothing.
+    ///
+    /// Returns the orders displayed by this call, in queue-consumption order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the level has been
+    /// poisoned by a panicked operation.
+    pub fn release_displays(
+        &self,
+        now: TimestampMs,
+    ) -> Result<Vec<Arc<OrderType<T>>>, PriceLevelError> {
+        self.poison_check()?;
+        if self.display_delay_count.load(Ordering::Relaxed) == 0 {
+            return Ok(Vec::new());
+        }
+
+        let due: Vec<Id> = self
+            .display_delays
+            .iter()
+            .filter(|entry| *entry.value() <= now)
+            .map(|entry| *entry.key())
+            .collect();
+
+        let mut released = HashSet::with_capacity(due.len());
+        for order_id in due {
+            // Re-check under the shard lock: a re-admission may have replaced
+            // the delay since the walk above.
+            let removed = self
+                .display_delays
+                .remove_if(&order_id, |_, display_at| *display_at <= now);
+            if removed.is_some() {
+                self.display_delay_count.fetch_sub(1, Ordering::Relaxed);
+                released.insert(order_id);
+            }
+        }
+
+        if released.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .snapshot_by_insertion_seq()
+            .into_iter()
+            .filter(|order| released.contains(&order.id()))
+            .collect())
+    }
+
+    /// The time at which `order_id` will be displayed, if it rests here and is
+    /// still withheld by a display delay.
+    #[must_use]
+    pub fn display_time(&self, order_id: Id) -> Option<TimestampMs> {
+        if self.display_delay_count.load(Ordering::Relaxed) == 0 {
+            return None;
+        }
+        let display_at = *self.display_delays.get(&order_id)?;
+        self.orders.find(order_id).map(|_| display_at)
+    }
+
+    /// Take a snapshot of the displayed book at this level: like
+    /// [`Self::snapshot`], minus every pending stop and every order still
+    /// inside its display delay, with the aggregates recomputed over the
+    /// orders that remain.
+    ///
+    /// Use this for market-data publication. [`Self::snapshot`] keeps the
+    /// withheld orders because it is the level's persistence format; note that
+    /// the delays themselves are not persisted, so a level restored from a
+    /// snapshot displays every order.
+    #[must_use]
+    pub fn public_snapshot(&self) -> PriceLevelSnapshot<T> {
+        let mut snapshot = self.snapshot();
+        let delayed = self.display_delay_count.load(Ordering::Relaxed) != 0;
+        if !delayed && !snapshot.orders().iter().any(|order| order.is_stop()) {
+            return snapshot;
+        }
+        snapshot.retain_orders(|order| {
+            !order.is_stop() && (!delayed || !self.display_delays.contains_key(&order.id()))
+        });
+        snapshot
+    }
+
+    /// The displayed orders of this level as market-by-order records, in
+    /// queue-consumption order: [`Self::public_snapshot`] rendered for
+    /// market-data tools. Write them with
+    /// [`write_market_data_csv`](crate::write_market_data_csv) or
+    /// [`write_market_data_json`](crate::write_market_data_json).
+    #[must_use]
+    pub fn mbo_records(&self) -> Vec<MboRecord> {
+        MboRecord::from_snapshot(&self.public_snapshot())
+    }
+
+    /// The displayed depth of this level as one market-by-price record, or
+    /// `None` if nothing is displayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the displayed size
+    /// overflows `u64`.
+    pub fn mbp_record(&self) -> Result<Option<MbpRecord>, PriceLevelError> {
+        MbpRecord::aggregate(&self.mbo_records())
+    }
+
+    /// Consult `limiter` before each admission, update and cancel at this
+    /// level, replacing any limiter registered before.
+    ///
+    /// The limiter is handed the extra fields of the order the message
+    /// concerns — the admitted order, or the resting order an update or
+    /// cancel names — and the level clock's reading. A refused message fails
+    /// with [`PriceLevelError::RateLimited`] before it changes anything. An
+    /// update naming an order that does not rest here is not consulted on.
+    /// Matching is never throttled. Like the expiry warning, the limiter is
+    /// runtime configuration: snapshots and clones do not carry it.
+    pub fn set_rate_limiter(&self, limiter: Arc<dyn RateLimiter<T>>) {
+        self.rate_limits.set(limiter);
+    }
+
+    /// Remove the limiter registered with [`Self::set_rate_limiter`].
+    pub fn clear_rate_limiter(&self) {
+        self.rate_limits.clear();
+    }
+
+    /// Whether a limiter is registered with [`Self::set_rate_limiter`].
+    #[must_use]
+    pub fn has_rate_limiter(&self) -> bool {
+        self.rate_limits.is_set()
+    }
+
+    /// Check every taker at this level against `band` before it matches,
+    /// replacing any band set before.
+    ///
+    /// While the level's price is outside the band, a
+    /// [`BandAction::Reject`](crate::BandAction::Reject) band refuses every
+    /// taker with a positive quantity as a halt does: [`Self::match_order`]
+    /// returns a `Rejected` result with no trade, and
+    /// [`Self::try_match_order`], [`Self::match_taker`],
+    /// [`Self::propose_match`] and [`Self::commit_match`] return
+    /// [`PriceLevelError::PriceOutsideBand`]. A
+    /// [`BandAction::Flag`](crate::BandAction::Flag) band lets the taker
+    /// trade and counts it in [`Self::band_breaches`]. The band is read
+    /// afresh for every taker, so moving its reference takes effect at the
+    /// next match; it is runtime configuration that snapshots do not carry.
+    pub fn set_price_band(&self, band: Arc<PriceBand>) {
+        self.price_band.set(band);
+    }
+
+    /// Remove the band set with [`Self::set_price_band`].
+    pub fn clear_price_band(&self) {
+        self.price_band.clear();
+    }
+
+    /// The band set with [`Self::set_price_band`], if any.
+    #[must_use]
+    pub fn price_band(&self) -> Option<Arc<PriceBand>> {
+        self.price_band.get()
+    }
+
+    /// Number of takers a flagging band let trade outside it.
+    #[must_use]
+    pub fn band_breaches(&self) -> u64 {
+        self.band_breaches.load(Ordering::Relaxed)
+    }
+
+    /// Refuse a taker with [`PriceLevelError::PriceOutsideBand`] if the
+    /// level is outside a rejecting band; with `record`, count and log one a
+    /// flagging band lets through.
+    fn check_band(&self, record: bool) -> Result<(), PriceLevelError> {
+        if self.price_band.check(self.price)? && record {
+            self.band_breaches.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(price = self.price, "taker matched outside the price band");
+        }
+        Ok(())
+    }
+
+    /// Register `callback` to be warned `lead_time_ms` milliseconds before each
+    /// resting [`TimeInForce::Gtd`] or [`TimeInForce::Gtt`] order expires,
+    /// replacing any callback registered before.
+    ///
+    /// Expiry runs on the caller's clock, like [`Self::release_displays`] and
+    /// [`Self::expire_orders`], so the warning does too: each
+    /// [`Self::poll_expiry_warnings`] call whose `now` falls within
+    /// `lead_time_ms` before an order's expiry (and before the expiry itself)
+    /// passes that order to `callback` as an [`ExpiryWarning`]. An order is
+    /// warned once per expiry; one extended with [`Self::extend_expiry`] is
+    /// warned again as its new expiry approaches. An order first polled after
+    /// its expiry is never warned.
+    ///
+    /// The callback runs on the polling thread after the level's internal
+    /// locks are released, so it may call [`Self::extend_expiry`], cancel the
+    /// order, or replace the callback.
+    pub fn set_expiry_warning<F>(&self, lead_time_ms: u64, callback: F)
+    where
+        F: Fn(&ExpiryWarning<T>) + Send + Sync + 'static,
+    {
+        self.expiry_warnings.set(lead_time_ms, Arc::new(callback));
+    }
+
+    /// Remove the callback registered with [`Self::set_expiry_warning`].
+    pub fn clear_expiry_warning(&self) {
+        self.expiry_warnings.clear();
+    }
+
+    /// The lead time of the registered expiry warning, if any.
+    #[must_use]
+    pub fn expiry_warning_lead_time(&self) -> Option<u64> {
+        self.expiry_warnings.lead_time_ms()
+    }
+
+    /// Warn the callback registered with [`Self::set_expiry_warning`] about
+    /// every resting `Gtd` or `Gtt` order whose expiry `now` is within the
+    /// lead time of and that has not been warned for that expiry yet.
+    ///
+    /// Orders are visited in queue-consumption order. Returns the number of
+    /// warnings raised; zero, without walking the queue, when no callback is
+    /// registered.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the level has been
+    /// poisoned by a panicked operation.
+    pub fn poll_expiry_warnings(&self, now: TimestampMs) -> Result<usize, PriceLevelError> {
+        self.poison_check()?;
+        if !self.expiry_warnings.is_set() {
+            return Ok(0);
+        }
+        Ok(self
+            .expiry_warnings
+            .poll(self.snapshot_by_insertion_seq(), now))
+    }
+
+    /// Move the expiry of the resting `Gtd` or `Gtt` order `order_id` to the
+    /// later `new_expiry`, keeping its queue position. A `Gtt` order keeps
+    /// its timestamp and has its duration lengthened to reach `new_expiry`.
+    ///
+    /// This is the refresh an [`ExpiryWarning`] callback is meant for: unlike
+    /// cancelling and re-submitting, the order keeps its insertion sequence.
+    /// The change is committed under the order's per-entry lock; quantities
+    /// are untouched, so the level counters do not move.
+    ///
+    /// Returns [`UpdateOutcome::UpdatedInPlace`] with the order as it now
+    /// rests, or [`UpdateOutcome::NotFound`] if no order with that id rests
+    /// here.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the order is neither
+    /// `Gtd` nor `Gtt`, if `new_expiry` is not later than its current expiry, or if the
+    /// level has been poisoned by a panicked operation. The order is unchanged
+    /// in every case.
+    pub fn extend_expiry(
+        &self,
+        order_id: Id,
+        new_expiry: TimestampMs,
+    ) -> Result<UpdateOutcome<T>, PriceLevelError> {
+        let _section = self.mutator_section();
+        self.poison_check()?;
+
+        let outcome = self.orders.update_entry(order_id, |live| {
+            let time_in_force = match live.time_in_force() {
+                TimeInForce::Gtd(_) => TimeInForce::Gtd(new_expiry.as_u64()),
+                TimeInForce::Gtt(_) => TimeInForce::Gtt(
+                    new_expiry
+                        .as_u64()
+                        .saturating_sub(live.timestamp().as_u64()),
+                ),
+                other => {
+                    return Err(PriceLevelError::InvalidOperation {
+                        message: format!(
+                            "order {order_id} has time in force {other}, not GTD or GTT"
+                        ),
+                    });
+                }
+            };
+            match live.expires_at() {
+                Some(expiry) if new_expiry > expiry => Ok(UpdateDecision::KeepInPlace(Arc::new(
+                    live.with_time_in_force(time_in_force),
+                ))),
+                expiry => Err(PriceLevelError::InvalidOperation {
+                    message: format!(
+                        "new expiry {new_expiry} is not later than the current expiry {}",
+                        expiry.unwrap_or_default()
+                    ),
+                }),
+            }
+        });
+        let (sequence, outcome) = match outcome {
+            Some(Ok(order)) => (
+                self.bump_mutation_epoch(),
+                UpdateOutcome::UpdatedInPlace(order),
+            ),
+            Some(Err(err)) => return Err(err),
+            None => (self.mutation_sequence(), UpdateOutcome::NotFound),
+        };
+        #[cfg(feature = "async")]
+        self.events
+            .publish(PriceLevelEvent::from_update(&outcome).map(|event| (sequence, event)));
+        #[cfg(not(feature = "async"))]
+        let _ = sequence;
+        Ok(outcome)
+    }
+
+    /// Remove every resting order whose time in force has run out by `now`:
+    /// a `Gtd` order at its date, a `Gtt` order its duration after its
+    /// timestamp, and a `Day` order at `market_close`, if given.
+    ///
+    /// The level does not expire orders on its own; this is the sweep a
+    /// caller runs on its clock, as often as its shortest-lived quotes need.
+    /// Each expired order is cancelled as by [`Self::update_order`], so the
+    /// counters, statistics, amend history and events move exactly as for a
+    /// cancel, except that a registered rate limiter is not consulted: an
+    /// expiry is not a participant's message.
+    ///
+    /// Returns the expired orders as they rested, in queue-consumption order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the level has been
+    /// poisoned by a panicked operation.
+    pub fn expire_orders(
+        &self,
+        now: TimestampMs,
+        market_close: Option<TimestampMs>,
+    ) -> Result<Vec<Arc<OrderType<T>>>, PriceLevelError> {
+        self.cancel_where(|order| order.is_expired(now, market_close))
+    }
+
+    /// Cancel every resting order entered on `session_id`, for
+    /// cancel-on-disconnect: the orders whose extra fields name that session
+    /// (see [`SessionTagged`]).
+    ///
+    /// Each order is cancelled as by [`Self::update_order`], so the counters,
+    /// statistics, amend history and events move exactly as for a cancel,
+    /// except that a registered rate limiter is not consulted: a disconnect
+    /// is not a participant's message, and must not be throttled. An order
+    /// the session enters while this runs may survive it; a venue stops
+    /// accepting the session's orders first.
+    ///
+    /// Returns the cancelled orders as they rested, in queue-consumption
+    /// order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the level has been
+    /// poisoned by a panicked operation.
+    pub fn cancel_session(&self, session_id: u64) -> Result<Vec<Arc<OrderType<T>>>, PriceLevelError>
+    where
+        T: SessionTagged,
+    {
+        self.cancel_where(|order| order.extra_fields().session_id() == Some(session_id))
+    }
+
+    /// Cancel every resting order matching `doomed`, as [`Self::update_order`]
+    /// would but without consulting the rate limiter, returning the
+    /// cancelled orders in queue-consumption order.
+    fn cancel_where<F>(&self, doomed: F) -> Result<Vec<Arc<OrderType<T>>>, PriceLevelError>
+    where
+        F: Fn(&OrderType<T>) -> bool,
+    {
+        let _section = self.mutator_section();
+        self.poison_check()?;
+
+        let mut cancelled = Vec::new();
+        for order in self.orders.snapshot_by_seq() {
+            if !doomed(&order) {
+                continue;
+            }
+            let update = OrderUpdate::Cancel {
+                order_id: order.id(),
+            };
+            let outcome = self.update_order_inner(update)?;
+            // An order filled or cancelled since the walk is simply gone.
+            if let UpdateOutcome::Cancelled(order, _) = &outcome {
+                cancelled.push(Arc::clone(order));
+            }
+            self.finish_update(update, outcome);
+        }
+        Ok(cancelled)
+    }
+
+    /// The malformed makers [`Self::match_order`] has quarantined and not yet
+    /// taken, oldest first.
+    ///
+    /// A resting order whose quantities cannot be matched safely (see
+    /// [`QuarantineReason`]) is removed by the sweep that reaches it — no
+    /// trade, its visible and hidden quantity released from the level's
+    /// counters as a cancel would — and kept here with the reason, the taker
+    /// and the sweep's timestamp. The list lives in memory only: it is not
+    /// part of a snapshot.
+    #[must_use]
+    pub fn quarantined_orders(&self) -> Vec<QuarantinedOrder<T>> {
+        self.quarantine
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Drain the dead-letter list, returning its entries oldest first (see
+    /// [`Self::quarantined_orders`]).
+    pub fn take_quarantined(&self) -> Vec<QuarantinedOrder<T>> {
+        std::mem::take(&mut *self.quarantine.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Number of makers quarantined over the level's lifetime, including those
+    /// already drained by [`Self::take_quarantined`].
+    #[must_use]
+    pub fn quarantine_count(&self) -> u64 {
+        self.quarantine_count.load(Ordering::Relaxed)
+    }
+
+    /// Start recording every update applied to this level's resting orders,
+    /// keeping the latest [`DEFAULT_AMEND_HISTORY_CAPACITY`] per order (see
+    /// [`Self::set_amend_history_capacity`]).
+    pub fn enable_amend_history(&self) {
+        self.set_amend_history_capacity(DEFAULT_AMEND_HISTORY_CAPACITY);
+    }
+
+    /// Keep the latest `capacity` updates applied to each resting order,
+    /// each with the clock reading and [`Self::mutation_sequence`] it
+    /// committed at; `0`, the default, stops recording and drops the
+    /// history.
+    ///
+    /// Only updates that found their order are recorded, by
+    /// [`Self::update_order`] and [`Self::update_order_sequenced`]; a cancel
+    /// or re-price that removes the order is its last record. Records
+    /// outlive their order until [`Self::take_amend_history`] drains them,
+    /// so a long-lived level recording history should drain it
+    /// periodically. Lowering the capacity trims each kept history to its
+    /// latest records.
+    pub fn set_amend_history_capacity(&self, capacity: usize) {
+        self.amend_history.set_capacity(capacity);
+    }
+
+    /// Updates kept per order, `0` while amend history is off.
+    #[must_use]
+    pub fn amend_history_capacity(&self) -> usize {
+        self.amend_history.capacity()
+    }
+
+    /// The recorded updates of `order_id`, oldest first. Empty if none were
+    /// recorded, including while amend history is off.
+    #[must_use]
+    pub fn order_history(&self, order_id: Id) -> Vec<AmendRecord> {
+        self.amend_history.order(order_id)
+    }
+
+    /// Every recorded update at the level, in [`Self::mutation_sequence`]
+    /// order.
+    #[must_use]
+    pub fn amend_history(&self) -> Vec<AmendRecord> {
+        self.amend_history.all()
     }
 
-    /// Serialize the current price level state into a checksum-protected snapshot package.
+    /// Drain the recorded updates, returning them in
+    /// [`Self::mutation_sequence`] order. Recording continues.
+    pub fn take_amend_history(&self) -> Vec<AmendRecord> {
+        self.amend_history.take()
+    }
+
+    /// The recorded updates of `order_id` as a JSON array, oldest first.
     ///
     /// # Errors
     ///
-    /// Returns [`PriceLevelError::InvalidOperation`] if computing the snapshot's
-    /// aggregate quantities overflows while building the package's checksummed
-    /// payload, or [`PriceLevelError::SerializationError`] if encoding the
-    /// snapshot payload to compute its SHA-256 checksum fails.
-    pub fn snapshot_package(&self) -> Result<PriceLevelSnapshotPackage, PriceLevelError> {
-        PriceLevelSnapshotPackage::new(self.snapshot())
+    /// Returns [`PriceLevelError::SerializationError`] if serialization fails.
+    pub fn order_history_json(&self, order_id: Id) -> Result<String, PriceLevelError> {
+        serde_json::to_string(&self.order_history(order_id)).map_err(PriceLevelError::serialization)
     }
 
-    /// Serialize the current price level state to JSON, including checksum metadata.
+    /// Every recorded update at the level as a JSON array, in
+    /// [`Self::mutation_sequence`] order.
     ///
     /// # Errors
     ///
-    /// Returns [`PriceLevelError::InvalidOperation`] if building the snapshot
-    /// package overflows an aggregate quantity, or
-    /// [`PriceLevelError::SerializationError`] if the package cannot be encoded
-    /// to JSON.
-    pub fn snapshot_to_json(&self) -> Result<String, PriceLevelError> {
-        self.snapshot_package()?.to_json()
+    /// Returns [`PriceLevelError::SerializationError`] if serialization fails.
+    pub fn amend_history_json(&self) -> Result<String, PriceLevelError> {
+        serde_json::to_string(&self.amend_history()).map_err(PriceLevelError::serialization)
     }
-}
 
-impl PriceLevel {
-    /// Apply an update to an existing order at this price level.
+    /// Open a channel receiving every trade [`Self::match_order`] executes at
+    /// this level, buffering up to [`DEFAULT_EXECUTION_CHANNEL_CAPACITY`]
+    /// trades and dropping those that do not fit (see
+    /// [`Self::execution_channel_with`]).
+    pub fn execution_channel(&self) -> crossbeam_channel::Receiver<Trade<T>> {
+        self.execution_channel_with(
+            DEFAULT_EXECUTION_CHANNEL_CAPACITY,
+            ExecutionOverflow::DropNewest,
+        )
+    }
+
+    /// Open a channel receiving every trade [`Self::match_order`] executes at
+    /// this level, buffering up to `capacity` trades, with `overflow`
+    /// deciding what a full buffer does.
     ///
-    /// # Quantity-update priority policy
+    /// Each match delivers its trades in execution order, after the sweep
+    /// completes. Every open channel receives every trade. A channel
+    /// under [`ExecutionOverflow::DropNewest`] skips trades it has no room
+    /// for and counts them in [`Self::dropped_executions`]; one under
+    /// [`ExecutionOverflow::Backpressure`] blocks the matcher until its
+    /// consumer catches up. Dropping the receiver closes the channel.
     ///
-    /// For [`OrderUpdate::UpdateQuantity`] (and the same-price branch of
-    /// [`OrderUpdate::UpdatePriceAndQuantity`]) this method follows the
-    /// conventional exchange price-time-priority rules:
+    /// A `capacity` of zero makes a rendezvous channel: under
+    /// `Backpressure` each trade waits for a consumer to take it; under
+    /// `DropNewest` only trades a consumer is already waiting for arrive.
+    pub fn execution_channel_with(
+        &self,
+        capacity: usize,
+        overflow: ExecutionOverflow,
+    ) -> crossbeam_channel::Receiver<Trade<T>> {
+        self.executions.open(capacity, overflow)
+    }
+
+    /// Lifetime number of trades an [`ExecutionOverflow::DropNewest`]
+    /// execution channel dropped because its buffer was full. A trade two
+    /// channels dropped counts twice.
+    #[must_use]
+    pub fn dropped_executions(&self) -> u64 {
+        self.executions.dropped()
+    }
+
+    /// Subscribe to this level's changes as an async stream, buffering up to
+    /// [`DEFAULT_EVENT_CAPACITY`] events (see [`Self::subscribe_with_capacity`]).
+    #[cfg(feature = "async")]
+    pub fn subscribe(&self) -> impl futures_core::Stream<Item = PriceLevelEvent<T>> + Send + 'static
+    where
+        T: Send + Sync + 'static,
+    {
+        self.subscribe_with_capacity(DEFAULT_EVENT_CAPACITY)
+    }
+
+    /// Subscribe to this level's changes as an async stream buffering up to
+    /// `capacity` events.
     ///
-    /// - **Decrease or unchanged total quantity** keeps the maker's queue
-    ///   position. The stored order is updated *in place* at its existing
-    ///   insertion sequence, so it is consumed at the same point in FIFO order
-    ///   as before. Reducing size never forfeits time priority.
-    /// - **Increase in total quantity** demotes the order to the *back* of the
-    ///   queue (it is assigned a fresh insertion sequence). Sizing an order up
-    ///   loses time priority, matching standard exchange behaviour.
+    /// Every committed admission, update, stop activation, removal (cancel,
+    /// reprice or quarantine) and trade is delivered as a [`PriceLevelEvent`],
+    /// published after the change commits; the stream is complete enough for
+    /// [`Self::from_events`] to rebuild the level. Publishing never blocks the level: events go through a bounded
+    /// lock-free channel, and a subscriber that falls `capacity` events behind
+    /// is disconnected — its stream yields the events already buffered and
+    /// then ends. A consumer that sees its stream end should resubscribe and
+    /// resynchronise from [`Self::snapshot`]. Dropping the stream
+    /// unsubscribes.
     ///
-    /// Total quantity is `visible + hidden`; the branch is chosen by comparing
-    /// the order's total before and after the update. Every order variant is
-    /// resized by [`OrderType::with_reduced_quantity`] (single-quantity
-    /// variants rewrite their `quantity`; two-tranche variants rewrite the
-    /// visible tranche and keep hidden), so the branch reflects the real size
-    /// change rather than a silent no-op.
+    /// The stream is runtime-agnostic: it works under tokio or any other
+    /// executor.
     ///
-    /// # Applied to the live maker (issue #115)
+    /// # Panics
     ///
-    /// The resize, the priority decision, and the level-counter update are all
-    /// derived from the order **currently resident in the queue**, computed and
-    /// committed together under a single per-entry lock — never from a pre-read.
-    /// A concurrent match or replenishment that commits first is therefore fully
-    /// reflected: an update applies `new_quantity` to the *live* visible tranche
-    /// and preserves the *live* hidden depth, so it can never resurrect executed
-    /// or cancelled quantity, and the increase-vs-decrease policy is chosen from
-    /// the live total (never stale). The level counters are validated with
-    /// checked math before the queue mutates, so an update that would overflow a
-    /// level counter is rejected with the level left unchanged.
+    /// Panics if `capacity` is zero.
+    #[cfg(feature = "async")]
+    pub fn subscribe_with_capacity(
+        &self,
+        capacity: usize,
+    ) -> impl futures_core::Stream<Item = PriceLevelEvent<T>> + Send + 'static
+    where
+        T: Send + Sync + 'static,
+    {
+        self.events.subscribe(capacity)
+    }
+
+    /// Subscribe to this level's changes as an async stream of events stamped
+    /// with their mutation sequence, buffering up to
+    /// [`DEFAULT_EVENT_CAPACITY`] events (see
+    /// [`Self::subscribe_sequenced_with_capacity`]).
+    #[cfg(feature = "async")]
+    pub fn subscribe_sequenced(
+        &self,
+    ) -> impl futures_core::Stream<Item = Sequenced<PriceLevelEvent<T>>> + Send + 'static
+    where
+        T: Send + Sync + 'static,
+    {
+        self.subscribe_sequenced_with_capacity(DEFAULT_EVENT_CAPACITY)
+    }
+
+    /// [`Self::subscribe_with_capacity`], stamping each event with the
+    /// [`Self::mutation_sequence`] of the change that produced it.
+    ///
+    /// Events of one change share its sequence: a sweep's trades each carry
+    /// the sequence of their own fill, while the orders moved by one
+    /// [`Self::merge_from`] or [`Self::split_off`] share the sequence of the
+    /// move. With [`Self::set_sequencing`] on, events are published inside
+    /// the change's exclusive section, so the stream delivers them in
+    /// sequence order and its sequences line up with those of trades and
+    /// sequenced results. Without it, the stamps are still those of the
+    /// committed changes, but concurrent changes may be delivered, and may
+    /// have committed, out of sequence order; an unstamped trade (see
+    /// [`Self::set_trade_stamping`]) is then published under the sequence its
+    /// sweep left behind.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    #[cfg(feature = "async")]
+    pub fn subscribe_sequenced_with_capacity(
+        &self,
+        capacity: usize,
+    ) -> impl futures_core::Stream<Item = Sequenced<PriceLevelEvent<T>>> + Send + 'static
+    where
+        T: Send + Sync + 'static,
+    {
+        self.events.subscribe_sequenced(capacity)
+    }
+
+    /// The number of live event subscribers.
+    #[cfg(feature = "async")]
+    pub fn subscriber_count(&self) -> usize {
+        self.events.count()
+    }
+
+    /// Lifetime number of subscribers disconnected because they fell a full
+    /// buffer behind.
+    #[cfg(feature = "async")]
+    pub fn lagged_subscriber_count(&self) -> u64 {
+        self.events.lagged()
+    }
+
+    /// Rebuild a level at `price` by applying `events` in order with
+    /// [`Self::apply_event`].
+    ///
+    /// Fed the complete event stream of a level from its creation, the result
+    /// holds the same orders in the same queue order, with the same visible
+    /// and hidden quantities and fill state. Configuration (matching policy,
+    /// crossing rule, hooks) is not part of the stream and starts at its
+    /// defaults.
     ///
     /// # Errors
     ///
-    /// Returns [`PriceLevelError::InvalidOperation`] if an
-    /// [`OrderUpdate::UpdatePrice`] / [`OrderUpdate::Replace`] would not move
-    /// the order to a different price level, if computing an order's total
-    /// quantity overflows `u64`, or if an [`OrderUpdate::UpdateQuantity`] would
-    /// overflow the level's visible- or hidden-quantity counter (the maker and
-    /// its queue position are left unchanged in that case).
-    #[must_use = "the updated order (or None when the order is absent) must be handled"]
-    pub fn update_order(
+    /// Returns the error of the first event that cannot be applied.
+    #[cfg(feature = "async")]
+    pub fn from_events<I>(price: u128, events: I) -> Result<Self, PriceLevelError>
+    where
+        I: IntoIterator<Item = PriceLevelEvent<T>>,
+    {
+        let level = Self::new(price);
+        for event in events {
+            level.apply_event(event)?;
+        }
+        Ok(level)
+    }
+
+    /// Apply one event of another level's [`Self::subscribe`] stream to this
+    /// level, reproducing the change it records.
+    ///
+    /// An admission re-adds the order and a removal cancels it. An update
+    /// replaces the resting order with the one carried, moving it to the back
+    /// of the queue only if its total quantity grew, exactly as a resize
+    /// would; an activation replaces it at the back, and a replacement is
+    /// re-applied by [`Self::cancel_replace`]. A trade fills its maker
+    /// by the traded quantity, replenishing or removing it as the original
+    /// sweep did, and a bust is re-applied by [`Self::bust_execution`]. Applied events are published to this level's own
+    /// subscribers.
+    ///
+    /// Display delays are not carried by events, so a replayed order is
+    /// displayed at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::OrderNotFound`] if the event names an
+    /// order that does not rest here, [`PriceLevelError::InvalidOperation`] if a trade's quantity is not exactly
+    /// what its maker would fill, or if the level has been poisoned by a
+    /// panicked operation. Admissions fail as [`Self::add_order`] does.
+    #[cfg(feature = "async")]
+    pub fn apply_event(&self, event: PriceLevelEvent<T>) -> Result<(), PriceLevelError> {
+        match event {
+            PriceLevelEvent::OrderAdded(order) => {
+                self.add_order(Arc::unwrap_or_clone(order)).map(|_| ())
+            }
+            PriceLevelEvent::OrderUpdated(order) => self.replay_replace(order, false),
+            PriceLevelEvent::OrderActivated(order) => self.replay_replace(order, true),
+            PriceLevelEvent::OrderRemoved(order) => {
+                let order_id = order.id();
+                match self.update_order(OrderUpdate::Cancel { order_id })? {
+                    UpdateOutcome::NotFound => Err(unknown_replay_order(order_id)),
+                    _ => Ok(()),
+                }
+            }
+            PriceLevelEvent::OrderReplaced {
+                replaced,
+                replacement,
+            } => self
+                .cancel_replace(replaced.id(), Arc::unwrap_or_clone(replacement))
+                .map(|_| ()),
+            PriceLevelEvent::Trade(trade) => self.replay_fill(*trade),
+            PriceLevelEvent::TradeBusted { trade, reinstated } => self
+                .bust_execution(&trade, reinstated.map(Arc::unwrap_or_clone))
+                .map(|_| ()),
+        }
+    }
+
+    /// Replace the resting order sharing `order`'s id with `order`, at the
+    /// back of the queue if `to_tail` or if its total quantity grew.
+    #[cfg(feature = "async")]
+    fn replay_replace(
         &self,
-        update: OrderUpdate,
-    ) -> Result<Option<Arc<OrderType<()>>>, PriceLevelError> {
-        // Hold the fill-or-kill guard's shared side for the whole update so a
-        // concurrent fill-or-kill match cannot observe the depth shrink (cancel
-        // / down-size) or grow mid-decision (issue #112). Uncontended in the
-        // common case (no FOK running). Acquired exactly ONCE here: the
-        // same-price branches of `UpdatePriceAndQuantity` / `Replace` delegate
-        // to the guard-free `update_order_inner` rather than recursing into this
-        // method, because std `RwLock` reads are NOT reentrant. A recursive
-        // `read()` with a writer queued between the two acquisitions (the lock
-        // is writer-preferring, so the queued writer blocks the second reader)
-        // would deadlock against a `fok_write` waiting on the first reader.
-        let _fok = self.fok_read();
-        // Fail fast on a poisoned level (issue #130).
+        order: Arc<OrderType<T>>,
+        to_tail: bool,
+    ) -> Result<(), PriceLevelError> {
+        let _section = self.mutator_section();
         self.poison_check()?;
-        let result = self.update_order_inner(update);
-        // A committed mutation (`Ok(Some(_))` — the order was found and
-        // cancelled / resized / moved) bumps the mutation epoch so a racing
-        // post-only depth scan retries (issue #130). `Ok(None)` (not found) and
-        // `Err` change nothing, so they do not bump.
-        if matches!(result, Ok(Some(_))) {
-            self.bump_mutation_epoch();
+
+        let order_id = order.id();
+        let visible_counter = &self.visible_quantity;
+        let hidden_counter = &self.hidden_quantity;
+        let event_order = Arc::clone(&order);
+        let outcome = self.orders.update_entry(order_id, |live| {
+            let old_visible = live.working_visible_quantity().as_u64();
+            let old_hidden = live.hidden_quantity().as_u64();
+            let new_visible = order.working_visible_quantity().as_u64();
+            let new_hidden = order.hidden_quantity().as_u64();
+            reserve_counter(visible_counter, old_visible, new_visible)?;
+            if let Err(err) = reserve_counter(hidden_counter, old_hidden, new_hidden) {
+                unreserve_counter(visible_counter, old_visible, new_visible);
+                return Err(err);
+            }
+            let grew = u128::from(new_visible) + u128::from(new_hidden)
+                > u128::from(old_visible) + u128::from(old_hidden);
+            if to_tail || grew {
+                Ok(UpdateDecision::ReplaceAtTail(order))
+            } else {
+                Ok(UpdateDecision::KeepInPlace(order))
+            }
+        });
+        match outcome {
+            None => Err(unknown_replay_order(order_id)),
+            Some(Err(err)) => Err(err),
+            Some(Ok(_)) => {
+                let sequence = self.bump_mutation_epoch();
+                self.events.publish([(
+                    sequence,
+                    if to_tail {
+                        PriceLevelEvent::OrderActivated(event_order)
+                    } else {
+                        PriceLevelEvent::OrderUpdated(event_order)
+                    },
+                )]);
+                Ok(())
+            }
         }
-        result
+    }
+
+    /// Fill the maker of `trade` by the traded quantity, as the sweep that
+    /// produced it did.
+    #[cfg(feature = "async")]
+    fn replay_fill(&self, trade: Trade<T>) -> Result<(), PriceLevelError> {
+        let _alerts = DeferredAlerts::begin();
+        let _section = self.mutator_section();
+        self.poison_check()?;
+
+        let maker_id = trade.maker_order_id();
+        let quantity = trade.quantity().as_u64();
+        let outcome = self.orders.match_entry(maker_id, |_, order| {
+            let (consumed, updated, hidden_reduced, _) =
+                self.match_maker(order, quantity, Some(trade.timestamp()));
+            if consumed != quantity {
+                return (
+                    FrontAction::SetAside,
+                    Err(PriceLevelError::InvalidOperation {
+                        message: format!(
+                            "trade of {quantity} does not match a fill of order {maker_id} ({consumed})"
+                        ),
+                    }),
+                );
+            }
+            // Hidden a full consume leaves behind, as in the sweep. A hidden
+            // order's fill is all hidden quantity; none is left behind.
+            let hidden = order.is_hidden();
+            let stranded = if updated.is_none() && hidden_reduced == 0 && !hidden {
+                order.hidden_quantity().as_u64()
+            } else {
+                0
+            };
+            let maker_price = order.price().as_u128();
+            let maker_timestamp = order.timestamp().as_u64();
+            let action = match updated {
+                None => FrontAction::Remove,
+                Some(updated)
+                    if hidden_reduced > 0 && self.refresh_priority == RefreshPriority::Requeue =>
+                {
+                    FrontAction::ReplaceAtTail(self.orders.allocate(updated))
+                }
+                Some(updated) => FrontAction::KeepInPlace(self.orders.allocate(updated)),
+            };
+            let fully_consumed = matches!(action, FrontAction::Remove);
+            (
+                action,
+                Ok((
+                    fully_consumed,
+                    hidden,
+                    hidden_reduced,
+                    stranded,
+                    maker_price,
+                    maker_timestamp,
+                )),
+            )
+        });
+        let (fully_consumed, hidden, hidden_reduced, stranded, maker_price, maker_timestamp) =
+            match outcome {
+                None => return Err(unknown_replay_order(maker_id)),
+                Some(result) => result?,
+            };
+
+        if hidden {
+            self.hidden_quantity.fetch_sub(quantity, Ordering::Relaxed);
+        } else {
+            self.visible_quantity.fetch_sub(quantity, Ordering::Relaxed);
+        }
+        if fully_consumed {
+            self.forget_status(maker_id);
+            if self.topology_release_one() {
+                self.bump_topology_epoch();
+            }
+            self.hidden_quantity.fetch_sub(stranded, Ordering::Relaxed);
+        } else {
+            self.record_partial_fill(maker_id, quantity);
+            self.hidden_quantity
+                .fetch_sub(hidden_reduced, Ordering::Relaxed);
+            self.visible_quantity
+                .fetch_add(hidden_reduced, Ordering::Relaxed);
+        }
+        let sequence = self.bump_mutation_epoch();
+        // A dropped record only degrades the statistics, as in the sweep.
+        let _ = self.stats.record_execution(
+            quantity,
+            maker_price,
+            maker_timestamp,
+            trade.timestamp().as_u64(),
+        );
+        self.events
+            .publish([(sequence, PriceLevelEvent::Trade(Box::new(trade)))]);
+        Ok(())
+    }
+
+    /// How long `order` has rested, by the level clock, for the removed-order
+    /// age histogram; `None` for an order with a zero timestamp, whose age is
+    /// unknown, as for the waiting time of a fill.
+    fn resting_age(&self, order: &OrderType<T>) -> Option<u64> {
+        let timestamp = order.timestamp().as_u64();
+        (timestamp > 0).then(|| self.clock.now().as_u64().saturating_sub(timestamp))
+    }
+
+    /// Drops the fill accounting and replacement link of `order_id` as its
+    /// order leaves the level, returning the status it rested with.
+    fn forget_status(&self, order_id: Id) -> OrderStatus {
+        self.set_replaced_from(order_id, None);
+        if self.executed.remove(&order_id).is_some() {
+            OrderStatus::PartiallyFilled
+        } else {
+            OrderStatus::New
+        }
+    }
+
+    /// Adds `quantity` to the executed quantity of `order_id`, a maker left
+    /// resting by a fill, returning the status it rested with before.
+    ///
+    /// The fill committed in the queue before this runs, so a racing cancel
+    /// or full consume may already have removed the maker and forgotten its
+    /// state; the entry is then dropped again rather than left behind.
+    fn record_partial_fill(&self, order_id: Id, quantity: u64) -> OrderStatus {
+        let from = {
+            let mut executed = self.executed.entry(order_id).or_insert(0);
+            let from = if *executed == 0 {
+                OrderStatus::New
+            } else {
+                OrderStatus::PartiallyFilled
+            };
+            *executed = executed.saturating_add(quantity);
+            from
+        };
+        if self.orders.find(order_id).is_none() {
+            self.executed.remove(&order_id);
+        }
+        from
+    }
+
+    /// Post-lock bookkeeping for a maker the sweep removed as malformed: the
+    /// queue removal is already committed, so release its counters, record
+    /// the removal, and append it to the dead-letter list.
+    fn quarantine_maker(
+        &self,
+        order: Arc<OrderType<T>>,
+        reason: QuarantineReason,
+        taker_order_id: Id,
+        timestamp: TimestampMs,
+        seq: u64,
+    ) {
+        // `Relaxed`: advisory counters (issue #68), exactly as a cancel
+        // releases them; the removal inside the queue carries the
+        // happens-before.
+        self.visible_quantity
+            .fetch_sub(order.working_visible_quantity().as_u64(), Ordering::Relaxed);
+        self.hidden_quantity
+            .fetch_sub(order.hidden_quantity().as_u64(), Ordering::Relaxed);
+        if self.topology_release_one() {
+            self.bump_topology_epoch();
+        }
+        let sequence = self.bump_mutation_epoch();
+        self.stats.record_order_removed_with_age(
+            order
+                .visible_quantity()
+                .as_u64()
+                .saturating_add(order.hidden_quantity().as_u64()),
+            self.resting_age(&order),
+        );
+        self.forget_status(order.id());
+        self.quarantine_count.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "async")]
+        self.events
+            .publish([(sequence, PriceLevelEvent::OrderRemoved(Arc::clone(&order)))]);
+        #[cfg(not(feature = "async"))]
+        let _ = sequence;
+
+        tracing::error!(
+            price = self.price,
+            order_id = %order.id(),
+            seq,
+            reason = %reason,
+            "match sweep: malformed maker quarantined; removed without a trade"
+        );
+
+        self.quarantine
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(QuarantinedOrder {
+                order,
+                reason,
+                taker_order_id,
+                timestamp,
+            });
+    }
+
+    /// Record (`Some`) or clear (`None`) the display delay of `order_id`,
+    /// keeping [`Self::display_delay_count`] in step with the registry.
+    fn set_display_delay(&self, order_id: Id, display_at: Option<TimestampMs>) {
+        match display_at {
+            Some(display_at) => {
+                if self.display_delays.insert(order_id, display_at).is_none() {
+                    self.display_delay_count.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            None => {
+                if self.display_delay_count.load(Ordering::Relaxed) != 0
+                    && self.display_delays.remove(&order_id).is_some()
+                {
+                    self.display_delay_count.fetch_sub(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Record (`Some`) or clear (`None`) the id `order_id` replaced, keeping
+    /// [`Self::replacement_count`] in step with the map.
+    fn set_replaced_from(&self, order_id: Id, orig_order_id: Option<Id>) {
+        match orig_order_id {
+            Some(orig_order_id) => {
+                if self.replaced_from.insert(order_id, orig_order_id).is_none() {
+                    self.replacement_count.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            None => {
+                if self.replacement_count.load(Ordering::Relaxed) != 0
+                    && self.replaced_from.remove(&order_id).is_some()
+                {
+                    self.replacement_count.fetch_sub(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Check the target of a price-changing update against the symbol spec
+    /// before the order is removed: the new price must be on tick and the new
+    /// quantity, if any, a multiple of the lot. The level the order moves to
+    /// checks the rest when it is re-added.
+    fn check_reprice(
+        &self,
+        price: Price,
+        quantity: Option<Quantity>,
+    ) -> Result<(), PriceLevelError> {
+        let Some(spec) = &self.symbol_spec else {
+            return Ok(());
+        };
+        spec.check_price(price)?;
+        quantity.map_or(Ok(()), |quantity| spec.check_lot(quantity.as_u64()))
     }
 
     /// Guard-free body of [`Self::update_order`].
@@ -2011,10 +6967,7 @@ impl PriceLevel {
     /// the same-price `UpdatePriceAndQuantity` / `Replace` branches re-enter it
     /// without taking a second, non-reentrant [`std::sync::RwLock`] read
     /// (issue #112).
-    fn update_order_inner(
-        &self,
-        update: OrderUpdate,
-    ) -> Result<Option<Arc<OrderType<()>>>, PriceLevelError> {
+    fn update_order_inner(&self, update: OrderUpdate) -> Result<UpdateOutcome<T>, PriceLevelError> {
         match update {
             OrderUpdate::UpdatePrice {
                 order_id,
@@ -2023,6 +6976,7 @@ impl PriceLevel {
                 // If price changes, this order needs to be moved to a different price level
                 // So we remove it from this level and return it for re-insertion elsewhere
                 if new_price != Price::new(self.price) {
+                    self.check_reprice(new_price, None)?;
                     let order = self.orders.remove(order_id);
 
                     if let Some(ref order_arc) = order {
@@ -2030,7 +6984,7 @@ impl PriceLevel {
                         // from the queue above. `Relaxed` on all three: advisory
                         // counters (issue #68); the `OrderQueue::remove` carries
                         // the happens-before, not these counters.
-                        let visible_qty = order_arc.visible_quantity().as_u64();
+                        let visible_qty = order_arc.working_visible_quantity().as_u64();
                         let hidden_qty = order_arc.hidden_quantity().as_u64();
 
                         self.visible_quantity
@@ -2044,10 +6998,16 @@ impl PriceLevel {
                         }
 
                         // Update statistics
-                        self.stats.record_order_removed();
+                        self.stats.record_order_removed_with_age(
+                            visible_qty.saturating_add(hidden_qty),
+                            self.resting_age(order_arc),
+                        );
                     }
 
-                    Ok(order)
+                    Ok(order.map_or(UpdateOutcome::NotFound, |order| {
+                        self.forget_status(order_id);
+                        UpdateOutcome::RemovedForReprice(order)
+                    }))
                 } else {
                     // If price is the same, this is a no-op at the price level
                     // (Should be handled at the order book level)
@@ -2061,14 +7021,8 @@ impl PriceLevel {
                 order_id,
                 new_quantity,
             } => {
-                // Overflow-checked forward reservation of a level counter for a
-                // component moving `old -> new`. BOTH directions use a checked
-                // `fetch_update` and reject before any queue mutation: an
-                // increase must not overflow `u64`, and a decrease must not
-                // underflow it (issue #128 defense). `Relaxed`: advisory counters
-                // (issue #68).
-                //
-                // The underflow guard is a belt-and-suspenders backstop. It is
+                // The counters are reserved with `reserve_counter`, whose
+                // underflow guard is a belt-and-suspenders backstop. It is
                 // unreachable in practice because issue #128's structural fix
                 // publishes the match sweep's replenish counter transition UNDER
                 // the maker's entry lock: by the time this `UpdateQuantity` holds
@@ -2077,38 +7031,6 @@ impl PriceLevel {
                 // contribution, so `counter >= old >= old - new` and the subtract
                 // cannot go negative. The check simply refuses to wrap if that
                 // invariant were ever violated, leaving the level untouched.
-                fn reserve(
-                    counter: &std::sync::atomic::AtomicU64,
-                    old: u64,
-                    new: u64,
-                ) -> Result<(), PriceLevelError> {
-                    let result = if new >= old {
-                        let delta = new - old;
-                        counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
-                            c.checked_add(delta)
-                        })
-                    } else {
-                        let delta = old - new;
-                        counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
-                            c.checked_sub(delta)
-                        })
-                    };
-                    result
-                        .map(|_| ())
-                        .map_err(|_| PriceLevelError::InvalidOperation {
-                            message: "price level quantity counter overflow on update".to_string(),
-                        })
-                }
-                // Undo this call's own `old -> new` reservation (commutative with
-                // concurrent deltas — it reverses exactly what it added).
-                fn unreserve(counter: &std::sync::atomic::AtomicU64, old: u64, new: u64) {
-                    if new >= old {
-                        counter.fetch_sub(new - old, Ordering::Relaxed);
-                    } else {
-                        counter.fetch_add(old - new, Ordering::Relaxed);
-                    }
-                }
-
                 let visible_counter = &self.visible_quantity;
                 let hidden_counter = &self.hidden_quantity;
 
@@ -2123,7 +7045,7 @@ impl PriceLevel {
                 // update that would overflow a level counter is rejected with the
                 // level (and queue) untouched.
                 let outcome = self.orders.update_entry(order_id, |live| {
-                    let old_visible = live.visible_quantity().as_u64();
+                    let old_visible = live.working_visible_quantity().as_u64();
                     let old_hidden = live.hidden_quantity().as_u64();
                     let live_total = old_visible.checked_add(old_hidden).ok_or_else(|| {
                         PriceLevelError::InvalidOperation {
@@ -2135,7 +7057,10 @@ impl PriceLevel {
                     // exactly `new_quantity` for every variant and preserves the
                     // LIVE hidden depth (never restored from a pre-read).
                     let new_order = live.with_reduced_quantity(new_quantity.as_u64());
-                    let new_visible = new_order.visible_quantity().as_u64();
+                    if let Some(spec) = &self.symbol_spec {
+                        spec.check_order(&new_order)?;
+                    }
+                    let new_visible = new_order.working_visible_quantity().as_u64();
                     let new_hidden = new_order.hidden_quantity().as_u64();
                     let new_total = new_visible.checked_add(new_hidden).ok_or_else(|| {
                         PriceLevelError::InvalidOperation {
@@ -2146,9 +7071,9 @@ impl PriceLevel {
                     // Validate + reserve the level counters before mutating the
                     // queue. On a hidden overflow, roll the visible reservation
                     // back so a rejected update leaves the counters unchanged.
-                    reserve(visible_counter, old_visible, new_visible)?;
-                    if let Err(err) = reserve(hidden_counter, old_hidden, new_hidden) {
-                        unreserve(visible_counter, old_visible, new_visible);
+                    reserve_counter(visible_counter, old_visible, new_visible)?;
+                    if let Err(err) = reserve_counter(hidden_counter, old_hidden, new_hidden) {
+                        unreserve_counter(visible_counter, old_visible, new_visible);
                         return Err(err);
                     }
 
@@ -2162,8 +7087,8 @@ impl PriceLevel {
                 });
 
                 match outcome {
-                    None => Ok(None), // Order not found / concurrently removed.
-                    Some(result) => result.map(Some),
+                    None => Ok(UpdateOutcome::NotFound), // Not found / concurrently removed.
+                    Some(result) => result.map(UpdateOutcome::UpdatedInPlace),
                 }
             }
 
@@ -2174,6 +7099,7 @@ impl PriceLevel {
             } => {
                 // If price changes, remove the order and let the order book handle re-insertion
                 if new_price != Price::new(self.price) {
+                    self.check_reprice(new_price, Some(new_quantity))?;
                     let order = self.orders.remove(order_id);
 
                     if let Some(ref order_arc) = order {
@@ -2181,7 +7107,7 @@ impl PriceLevel {
                         // from the queue above. `Relaxed` on all three: advisory
                         // counters (issue #68); the `OrderQueue::remove` carries
                         // the happens-before, not these counters.
-                        let visible_qty = order_arc.visible_quantity().as_u64();
+                        let visible_qty = order_arc.working_visible_quantity().as_u64();
                         let hidden_qty = order_arc.hidden_quantity().as_u64();
 
                         self.visible_quantity
@@ -2195,9 +7121,15 @@ impl PriceLevel {
                         }
 
                         // Update statistics
-                        self.stats.record_order_removed();
+                        self.stats.record_order_removed_with_age(
+                            visible_qty.saturating_add(hidden_qty),
+                            self.resting_age(order_arc),
+                        );
                     }
-                    Ok(order)
+                    Ok(order.map_or(UpdateOutcome::NotFound, |order| {
+                        self.forget_status(order_id);
+                        UpdateOutcome::RemovedForReprice(order)
+                    }))
                 } else {
                     // If price is the same, just update the quantity (reuse
                     // logic). Call the guard-free inner body — we already hold
@@ -2219,7 +7151,7 @@ impl PriceLevel {
                     // the queue above. `Relaxed` on all three: advisory counters
                     // (issue #68); the `OrderQueue::remove` carries the
                     // happens-before, not these counters.
-                    let visible_qty = order_arc.visible_quantity().as_u64();
+                    let visible_qty = order_arc.working_visible_quantity().as_u64();
                     let hidden_qty = order_arc.hidden_quantity().as_u64();
 
                     self.visible_quantity
@@ -2233,10 +7165,16 @@ impl PriceLevel {
                     }
 
                     // Update statistics
-                    self.stats.record_order_removed();
+                    self.stats.record_order_removed_with_age(
+                        visible_qty.saturating_add(hidden_qty),
+                        self.resting_age(order_arc),
+                    );
                 }
 
-                Ok(order)
+                Ok(order.map_or(UpdateOutcome::NotFound, |order| {
+                    let status = self.forget_status(order_id);
+                    UpdateOutcome::Cancelled(order, status)
+                }))
             }
 
             OrderUpdate::Replace {
@@ -2248,6 +7186,7 @@ impl PriceLevel {
                 // For replacement, check if the price is changing
                 if price != Price::new(self.price) {
                     // If price is different, remove the order and let order book handle re-insertion
+                    self.check_reprice(price, Some(quantity))?;
                     let order = self.orders.remove(order_id);
 
                     if let Some(ref order_arc) = order {
@@ -2255,7 +7194,7 @@ impl PriceLevel {
                         // from the queue above. `Relaxed` on all three: advisory
                         // counters (issue #68); the `OrderQueue::remove` carries
                         // the happens-before, not these counters.
-                        let visible_qty = order_arc.visible_quantity().as_u64();
+                        let visible_qty = order_arc.working_visible_quantity().as_u64();
                         let hidden_qty = order_arc.hidden_quantity().as_u64();
 
                         self.visible_quantity
@@ -2269,10 +7208,16 @@ impl PriceLevel {
                         }
 
                         // Update statistics
-                        self.stats.record_order_removed();
+                        self.stats.record_order_removed_with_age(
+                            visible_qty.saturating_add(hidden_qty),
+                            self.resting_age(order_arc),
+                        );
                     }
 
-                    Ok(order)
+                    Ok(order.map_or(UpdateOutcome::NotFound, |order| {
+                        self.forget_status(order_id);
+                        UpdateOutcome::RemovedForReprice(order)
+                    }))
                 } else {
                     // If price is the same, just update the quantity. Call the
                     // guard-free inner body — we already hold the fill-or-kill
@@ -2300,7 +7245,7 @@ impl PriceLevel {
 /// persistence.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct PriceLevelData {
+pub struct PriceLevelData<T = ()> {
     /// The price of this level
     pub price: u128,
     /// Total visible quantity at this price level
@@ -2310,11 +7255,11 @@ pub struct PriceLevelData {
     /// Number of orders at this price level
     pub order_count: usize,
     /// Orders at this price level
-    pub orders: Vec<OrderType<()>>,
+    pub orders: Vec<OrderType<T>>,
 }
 
-impl From<&PriceLevel> for PriceLevelData {
-    fn from(price_level: &PriceLevel) -> Self {
+impl<T: Clone> From<&PriceLevel<T>> for PriceLevelData<T> {
+    fn from(price_level: &PriceLevel<T>) -> Self {
         Self {
             price: price_level.price(),
             visible_quantity: price_level.visible_quantity(),
@@ -2328,13 +7273,13 @@ impl From<&PriceLevel> for PriceLevelData {
             orders: price_level
                 .snapshot_by_insertion_seq()
                 .into_iter()
-                .map(|order_arc| *order_arc)
+                .map(Arc::unwrap_or_clone)
                 .collect(),
         }
     }
 }
 
-impl TryFrom<&PriceLevelSnapshot> for PriceLevel {
+impl<T: Clone> TryFrom<&PriceLevelSnapshot<T>> for PriceLevel<T> {
     type Error = PriceLevelError;
 
     /// Rebuilds a price level from a borrowed snapshot.
@@ -2354,16 +7299,16 @@ impl TryFrom<&PriceLevelSnapshot> for PriceLevel {
     /// vector repeats an id, or [`PriceLevelError::InvalidOperation`] if a
     /// per-order or level aggregate overflows `u64` — see
     /// [`PriceLevel::from_snapshot`].
-    fn try_from(value: &PriceLevelSnapshot) -> Result<Self, Self::Error> {
-        PriceLevel::from_snapshot(value.clone())
+    fn try_from(value: &PriceLevelSnapshot<T>) -> Result<Self, Self::Error> {
+        Self::from_snapshot(value.clone())
     }
 }
 
-impl TryFrom<PriceLevelData> for PriceLevel {
+impl<T: Clone> TryFrom<PriceLevelData<T>> for PriceLevel<T> {
     type Error = PriceLevelError;
 
-    fn try_from(data: PriceLevelData) -> Result<Self, Self::Error> {
-        let price_level = PriceLevel::new(data.price);
+    fn try_from(data: PriceLevelData<T>) -> Result<Self, Self::Error> {
+        let price_level = Self::new(data.price);
 
         // Add orders to the price level. Propagate an admission overflow rather
         // than panicking while reconstructing from external data.
@@ -2376,20 +7321,43 @@ impl TryFrom<PriceLevelData> for PriceLevel {
 }
 
 // Implement custom serialization for the atomic types
-impl Serialize for PriceLevel {
+impl<T: Clone + Serialize> Serialize for PriceLevel<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         // Convert to a serializable representation
-        let data: PriceLevelData = self.into();
+        let data: PriceLevelData<T> = self.into();
         data.serialize(serializer)
     }
 }
 
-impl FromStr for PriceLevel {
+impl<T: Clone + Default> FromStr for PriceLevel<T> {
     type Err = PriceLevelError;
+
+    /// Parses leniently within
+    /// [`ParseLimits::DEFAULT`](crate::parse::ParseLimits::DEFAULT); see
+    /// [`Self::from_str_with`].
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with(s, &ParseOptions::DEFAULT)
+    }
+}
+
+impl<T: Clone + Default> PriceLevel<T> {
+    /// Parse a price level from its `Display` form under `options`: strict or
+    /// lenient about unknown, repeated and malformed fields (in the level and
+    /// each order), and within the options' limits.
+    ///
+    /// # Errors
+    ///
+    /// As [`crate::parse::price_level`].
+    pub fn from_str_with(s: &str, options: &ParseOptions) -> Result<Self, PriceLevelError> {
+        crate::parse::price_level(s, options)
+    }
+
+    /// The `FromStr` parser, checking each order and the order count against
+    /// `options`. The caller has checked the input length.
+    pub(crate) fn parse_bounded(s: &str, options: &ParseOptions) -> Result<Self, PriceLevelError> {
         use std::borrow::Cow;
 
         if !s.starts_with("PriceLevel:") {
@@ -2400,7 +7368,7 @@ impl FromStr for PriceLevel {
 
         let content = &s["PriceLevel:".len()..];
 
-        let mut parts = std::collections::HashMap::new();
+        let mut orders = None;
         let remaining_content: Cow<str>;
 
         if let Some(orders_start) = content.find("orders=[") {
@@ -2413,7 +7381,7 @@ impl FromStr for PriceLevel {
                     + orders_start;
 
             let orders_str = &content[orders_start + "orders=[".len()..orders_end];
-            parts.insert("orders", orders_str);
+            orders = Some(orders_str);
 
             let before_orders = &content[..orders_start];
             let after_orders = &content[orders_end + 1..];
@@ -2422,40 +7390,39 @@ impl FromStr for PriceLevel {
             remaining_content = Cow::Borrowed(content);
         }
 
-        for part in remaining_content.split(';').filter(|s| !s.is_empty()) {
-            let mut iter = part.splitn(2, '=');
-            if let (Some(key), Some(value)) = (iter.next(), iter.next()) {
-                parts.insert(key, value);
-            }
-        }
-
-        let price = parts
+        let fields = RecordFields::split("price level", &remaining_content, options.strict())?;
+        let price = fields
             .get("price")
             .and_then(|v| v.parse::<u128>().ok())
             .ok_or_else(|| PriceLevelError::ParseError {
                 message: "Missing or invalid price".to_string(),
             })?;
 
-        let price_level = PriceLevel::new(price);
+        let price_level = Self::new(price);
 
-        if let Some(orders_part) = parts.get("orders")
+        if let Some(orders_part) = orders
             && !orders_part.is_empty()
         {
             let mut bracket_level = 0;
             let mut last_split = 0;
+            let mut order_count = 0usize;
+            let mut add = |order_str: &str| -> Result<(), PriceLevelError> {
+                order_count += 1;
+                options.limits().check_orders("price level", order_count)?;
+                let order = crate::parse::order::<T>(order_str, options).map_err(|e| {
+                    PriceLevelError::ParseError {
+                        message: format!("Order parse error: {e}"),
+                    }
+                })?;
+                price_level.add_order(order).map(|_| ())
+            };
 
             for (i, c) in orders_part.char_indices() {
                 match c {
                     '(' | '[' => bracket_level += 1,
                     ')' | ']' => bracket_level -= 1,
                     ',' if bracket_level == 0 => {
-                        let order_str = &orders_part[last_split..i];
-                        let order = OrderType::<()>::from_str(order_str).map_err(|e| {
-                            PriceLevelError::ParseError {
-                                message: format!("Order parse error: {e}"),
-                            }
-                        })?;
-                        price_level.add_order(order)?;
+                        add(&orders_part[last_split..i])?;
                         last_split = i + 1;
                     }
                     _ => {}
@@ -2464,53 +7431,80 @@ impl FromStr for PriceLevel {
 
             let order_str = &orders_part[last_split..];
             if !order_str.is_empty() {
-                let order = OrderType::<()>::from_str(order_str).map_err(|e| {
-                    PriceLevelError::ParseError {
-                        message: format!("Order parse error: {e}"),
-                    }
-                })?;
-                price_level.add_order(order)?;
+                add(order_str)?;
+            }
+        }
+
+        // A strict parse holds the stated totals to the parsed orders.
+        if options.strict() {
+            let totals = [
+                ("visible_quantity", price_level.visible_quantity()),
+                ("hidden_quantity", price_level.hidden_quantity()),
+                ("order_count", price_level.order_count() as u64),
+            ];
+            for (field, actual) in totals {
+                if let Some(stated) = fields.get(field)
+                    && stated.parse::<u64>() != Ok(actual)
+                {
+                    return Err(PriceLevelError::InvalidFieldValue {
+                        field: field.to_string(),
+                        value: stated.to_string(),
+                    });
+                }
             }
         }
+        fields.finish()?;
 
         Ok(price_level)
     }
 }
 
-impl<'de> Deserialize<'de> for PriceLevel {
+impl<'de, T: Clone + Deserialize<'de>> Deserialize<'de> for PriceLevel<T> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
         // Deserialize into the data representation
-        let data = PriceLevelData::deserialize(deserializer)?;
+        let data = PriceLevelData::<T>::deserialize(deserializer)?;
 
         // Convert to PriceLevel
-        PriceLevel::try_from(data).map_err(serde::de::Error::custom)
+        Self::try_from(data).map_err(serde::de::Error::custom)
     }
 }
 
-impl PartialEq for PriceLevel {
+impl<T> PriceLevel<T> {
+    /// Release reservation `id` if it is still outstanding, for
+    /// [`ReservationGuard::rollback`] and its drop. Not bound by `T: Clone`,
+    /// since the guard's drop is not.
+    pub(crate) fn release_reservation(&self, id: u64) {
+        if let Some((_, (quantity, _))) = self.reservations.remove(&id) {
+            self.reserved_quantity
+                .fetch_sub(quantity, Ordering::Release);
+        }
+    }
+}
+
+impl<T> PartialEq for PriceLevel<T> {
     fn eq(&self, other: &Self) -> bool {
         self.price == other.price
     }
 }
 
-impl Eq for PriceLevel {}
+impl<T> Eq for PriceLevel<T> {}
 
-impl PartialOrd for PriceLevel {
+impl<T> PartialOrd for PriceLevel<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for PriceLevel {
+impl<T> Ord for PriceLevel<T> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.price.cmp(&other.price)
     }
 }
 
-impl Display for PriceLevel {
+impl<T: Clone> Display for PriceLevel<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -2533,3 +7527,90 @@ impl Display for PriceLevel {
         write!(f, "]")
     }
 }
+
+/// Overflow-checked forward reservation of a level counter for a component
+/// moving `old -> new`. BOTH directions use a checked `fetch_update` and
+/// reject before any queue mutation: an increase must not overflow `u64`, and
+/// a decrease must not underflow it (issue #128 defense). `Relaxed`: advisory
+/// counters (issue #68).
+fn reserve_counter(
+    counter: &std::sync::atomic::AtomicU64,
+    old: u64,
+    new: u64,
+) -> Result<(), PriceLevelError> {
+    let result = if new >= old {
+        let delta = new - old;
+        counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
+            c.checked_add(delta)
+        })
+    } else {
+        let delta = old - new;
+        counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
+            c.checked_sub(delta)
+        })
+    };
+    result
+        .map(|_| ())
+        .map_err(|_| PriceLevelError::InvalidOperation {
+            message: "price level quantity counter overflow on update".to_string(),
+        })
+}
+
+/// Undo a [`reserve_counter`] call's own `old -> new` reservation (commutative
+/// with concurrent deltas — it reverses exactly what it added).
+fn unreserve_counter(counter: &std::sync::atomic::AtomicU64, old: u64, new: u64) {
+    if new >= old {
+        counter.fetch_sub(new - old, Ordering::Relaxed);
+    } else {
+        counter.fetch_add(old - new, Ordering::Relaxed);
+    }
+}
+
+/// Probability that a Poisson variable of mean `mean` is at least `count`.
+///
+/// Summed term by term in log space, so a large mean does not underflow;
+/// above a mean of a thousand the normal approximation takes over.
+fn poisson_at_least(mean: f64, count: f64) -> f64 {
+    if count <= 0.0 {
+        return 1.0;
+    }
+    if mean <= 0.0 {
+        return 0.0;
+    }
+    if mean > 1_000.0 {
+        let z = (count - 0.5 - mean) / mean.sqrt();
+        return (0.5 * erfc(z / std::f64::consts::SQRT_2)).clamp(0.0, 1.0);
+    }
+    // Beyond 40 standard deviations the tail is below `f64` precision.
+    if count > mean + 40.0 * mean.sqrt() + 40.0 {
+        return 0.0;
+    }
+    let mut log_term = -mean;
+    let mut below = log_term.exp();
+    let mut i = 1.0;
+    while i < count {
+        log_term += mean.ln() - f64::ln(i);
+        below += log_term.exp();
+        i += 1.0;
+    }
+    (1.0 - below).clamp(0.0, 1.0)
+}
+
+/// Complementary error function (Abramowitz and Stegun 7.1.26, absolute
+/// error below `1.5e-7`).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.327_591_1 * z);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let tail = poly * (-z * z).exp();
+    if x >= 0.0 { tail } else { 2.0 - tail }
+}
+
+/// The error for a replayed event naming an order that does not rest here.
+#[cfg(feature = "async")]
+fn unknown_replay_order(order_id: Id) -> PriceLevelError {
+    PriceLevelError::OrderNotFound { order_id }
+}
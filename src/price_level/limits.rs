@@ -0,0 +1,110 @@
+/// Capacity limits a level enforces on admission: the most orders it rests,
+/// the most visible plus hidden quantity, and the most orders any one
+/// participant (an order's [`user_id`](crate::OrderType::user_id)) rests.
+///
+/// A level built with [`PriceLevelConfig::with_limits`] rejects an order
+/// that would take it past any limit with
+/// [`PriceLevelError::CapacityExceeded`], leaving the level unchanged. The
+/// limits are checked only on admission: a resize or a restore is never
+/// refused by them. Every limit is unbounded unless set.
+///
+/// ```rust
+/// use pricelevel::{
+///     CapacityLimit, Hash32, Id, LevelLimits, OrderType, Price, PriceLevel, PriceLevelConfig,
+///     PriceLevelError, Quantity, Side, TimeInForce, TimestampMs,
+/// };
+///
+/// let limits = LevelLimits::new().with_max_orders(1);
+/// let level: PriceLevel =
+///     PriceLevel::with_config(10_000, PriceLevelConfig::new().with_limits(limits));
+/// let order = |id| OrderType::Standard {
+///     id: Id::from_u64(id),
+///     price: Price::new(10_000),
+///     quantity: Quantity::new(10),
+///     side: Side::Buy,
+///     user_id: Hash32::zero(),
+///     timestamp: TimestampMs::new(1),
+///     time_in_force: TimeInForce::Gtc,
+///     extra_fields: (),
+/// };
+/// level.add_order(order(1)).unwrap();
+/// assert!(matches!(
+///     level.add_order(order(2)),
+///     Err(PriceLevelError::CapacityExceeded { limit: CapacityLimit::Orders, max: 1 })
+/// ));
+/// ```
+///
+/// [`PriceLevelConfig::with_limits`]: crate::PriceLevelConfig::with_limits
+/// [`PriceLevelError::CapacityExceeded`]: crate::PriceLevelError::CapacityExceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LevelLimits {
+    max_orders: u64,
+    max_quantity: u64,
+    max_orders_per_participant: u64,
+}
+
+impl LevelLimits {
+    /// Limits that admit any order.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max_orders: u64::MAX,
+            max_quantity: u64::MAX,
+            max_orders_per_participant: u64::MAX,
+        }
+    }
+
+    /// The same limits, resting at most `max_orders` orders.
+    #[must_use]
+    pub fn with_max_orders(mut self, max_orders: u64) -> Self {
+        self.max_orders = max_orders;
+        self
+    }
+
+    /// The same limits, resting at most `max_quantity` visible plus hidden
+    /// quantity.
+    #[must_use]
+    pub fn with_max_quantity(mut self, max_quantity: u64) -> Self {
+        self.max_quantity = max_quantity;
+        self
+    }
+
+    /// The same limits, resting at most `max_orders` orders per participant.
+    #[must_use]
+    pub fn with_max_orders_per_participant(mut self, max_orders: u64) -> Self {
+        self.max_orders_per_participant = max_orders;
+        self
+    }
+
+    /// The most orders the level rests; unbounded unless set.
+    #[must_use]
+    pub fn max_orders(&self) -> u64 {
+        self.max_orders
+    }
+
+    /// The most visible plus hidden quantity the level rests; unbounded
+    /// unless set.
+    #[must_use]
+    pub fn max_quantity(&self) -> u64 {
+        self.max_quantity
+    }
+
+    /// The most orders one participant rests at the level; unbounded unless
+    /// set.
+    #[must_use]
+    pub fn max_orders_per_participant(&self) -> u64 {
+        self.max_orders_per_participant
+    }
+
+    /// Whether the per-participant limit is set, so admission has to count
+    /// the participant's resting orders.
+    pub(crate) fn limits_participants(&self) -> bool {
+        self.max_orders_per_participant != u64::MAX
+    }
+}
+
+impl Default for LevelLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,165 @@
+use crate::errors::PriceLevelError;
+use crate::orders::{Id, Side};
+use crate::price_level::snapshot::PriceLevelSnapshot;
+use crate::utils::{Price, Quantity};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::sync::Arc;
+
+/// One displayed order in market-by-order (MBO) form, as produced by
+/// [`PriceLevel::mbo_records`](crate::PriceLevel::mbo_records).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MboRecord {
+    /// The level's price.
+    pub price: Price,
+    /// The order's side.
+    pub side: Side,
+    /// Displayed size: the order's visible quantity. Hidden depth is never
+    /// disclosed.
+    pub size: Quantity,
+    /// Queue position among the displayed orders; `0` is the next to fill.
+    pub priority: u64,
+    /// The order's id.
+    pub order_id: Id,
+}
+
+/// The displayed depth of one price level in market-by-price (MBP) form, as
+/// produced by [`PriceLevel::mbp_record`](crate::PriceLevel::mbp_record).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MbpRecord {
+    /// The level's price.
+    pub price: Price,
+    /// The side resting at the level.
+    pub side: Side,
+    /// Total displayed size.
+    pub size: Quantity,
+    /// Number of displayed orders.
+    pub order_count: u64,
+}
+
+/// A market-data record the CSV and JSON writers accept.
+pub trait MarketDataRecord: Serialize {
+    /// The CSV header row, without the line terminator.
+    const CSV_HEADER: &'static str;
+
+    /// The record as one CSV row, without the line terminator.
+    fn csv_row(&self) -> String;
+}
+
+impl MarketDataRecord for MboRecord {
+    const CSV_HEADER: &'static str = "price,side,size,priority,order_id";
+
+    fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{}",
+            self.price, self.side, self.size, self.priority, self.order_id
+        )
+    }
+}
+
+impl MarketDataRecord for MbpRecord {
+    const CSV_HEADER: &'static str = "price,side,size,order_count";
+
+    fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{}",
+            self.price, self.side, self.size, self.order_count
+        )
+    }
+}
+
+impl MboRecord {
+    /// The displayed orders of `snapshot`, in queue-consumption order. Orders
+    /// with nothing visible are left out and take no priority.
+    #[must_use]
+    pub fn from_snapshot<T: Clone>(snapshot: &PriceLevelSnapshot<T>) -> Vec<Self> {
+        snapshot
+            .orders()
+            .iter()
+            .filter(|order| order.working_visible_quantity().as_u64() > 0)
+            .enumerate()
+            .map(|(priority, order)| Self {
+                price: snapshot.price(),
+                side: order.side(),
+                size: order.working_visible_quantity(),
+                priority: priority as u64,
+                order_id: order.id(),
+            })
+            .collect()
+    }
+}
+
+impl MbpRecord {
+    /// Aggregates `records` — the MBO records of one level — into its MBP
+    /// record, or `None` if there are none.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the total size
+    /// overflows `u64`.
+    pub fn aggregate(records: &[MboRecord]) -> Result<Option<Self>, PriceLevelError> {
+        let Some(first) = records.first() else {
+            return Ok(None);
+        };
+        let size = records.iter().try_fold(0u64, |acc, record| {
+            acc.checked_add(record.size.as_u64())
+                .ok_or_else(|| PriceLevelError::InvalidOperation {
+                    message: "market-by-price size overflow".to_string(),
+                })
+        })?;
+        Ok(Some(Self {
+            price: first.price,
+            side: first.side,
+            size: Quantity::new(size),
+            order_count: records.len() as u64,
+        }))
+    }
+}
+
+/// Writes `records` as CSV: the header row, then one row per record.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::SerializationError`] if writing fails.
+pub fn write_market_data_csv<R, W>(records: &[R], mut writer: W) -> Result<(), PriceLevelError>
+where
+    R: MarketDataRecord,
+    W: Write,
+{
+    writeln!(writer, "{}", R::CSV_HEADER).map_err(|e| io_error("csv", e))?;
+    for record in records {
+        writeln!(writer, "{}", record.csv_row()).map_err(|e| io_error("csv", e))?;
+    }
+    writer.flush().map_err(|e| io_error("csv", e))
+}
+
+/// Writes `records` as JSON Lines: one JSON object per line, so a replay
+/// tool can stream the output.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::SerializationError`] if a record cannot be
+/// serialized or writing fails.
+pub fn write_market_data_json<R, W>(records: &[R], mut writer: W) -> Result<(), PriceLevelError>
+where
+    R: MarketDataRecord,
+    W: Write,
+{
+    for record in records {
+        serde_json::to_writer(&mut writer, record).map_err(|e| {
+            PriceLevelError::SerializationError {
+                message: format!("market data json: {e}"),
+                source: Some(Arc::new(e)),
+            }
+        })?;
+        writeln!(writer).map_err(|e| io_error("json", e))?;
+    }
+    writer.flush().map_err(|e| io_error("json", e))
+}
+
+fn io_error(format: &str, e: std::io::Error) -> PriceLevelError {
+    PriceLevelError::SerializationError {
+        message: format!("market data {format}: {e}"),
+        source: Some(Arc::new(e)),
+    }
+}
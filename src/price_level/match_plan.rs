@@ -0,0 +1,139 @@
+use crate::orders::Id;
+use crate::price_level::policy::MatchingPolicy;
+use crate::utils::TimestampMs;
+
+/// One maker step of a [`MatchPlan`]: the resting order the sweep would
+/// trade against and the quantity it would take from it.
+///
+/// A replenishing iceberg can appear more than once, once per tranche.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlannedFill {
+    order_id: Id,
+    quantity: u64,
+}
+
+impl PlannedFill {
+    pub(crate) fn new(order_id: Id, quantity: u64) -> Self {
+        Self { order_id, quantity }
+    }
+
+    /// The resting order the step trades against.
+    #[must_use]
+    pub fn order_id(&self) -> Id {
+        self.order_id
+    }
+
+    /// The quantity the step takes from it.
+    #[must_use]
+    pub fn quantity(&self) -> u64 {
+        self.quantity
+    }
+}
+
+/// A tentative match computed by [`PriceLevel::propose_match`] without
+/// touching the level, to be executed by [`PriceLevel::commit_match`] or
+/// discarded by [`PriceLevel::abort_match`] once external pre-trade checks
+/// have run on it.
+///
+/// The plan records the level state it was computed against. A commit is
+/// refused once any mutation has committed at the level since — an
+/// admission, cancel, resize, sweep, reservation or policy change — so a
+/// committed plan trades exactly the fills it lists.
+///
+/// [`PriceLevel::propose_match`]: crate::PriceLevel::propose_match
+/// [`PriceLevel::commit_match`]: crate::PriceLevel::commit_match
+/// [`PriceLevel::abort_match`]: crate::PriceLevel::abort_match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchPlan {
+    price: u128,
+    taker_order_id: Id,
+    quantity: u64,
+    timestamp: TimestampMs,
+    sequence: u64,
+    reserved: u64,
+    policy: MatchingPolicy,
+    fills: Vec<PlannedFill>,
+}
+
+impl MatchPlan {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        price: u128,
+        taker_order_id: Id,
+        quantity: u64,
+        timestamp: TimestampMs,
+        sequence: u64,
+        reserved: u64,
+        policy: MatchingPolicy,
+        fills: Vec<PlannedFill>,
+    ) -> Self {
+        Self {
+            price,
+            taker_order_id,
+            quantity,
+            timestamp,
+            sequence,
+            reserved,
+            policy,
+            fills,
+        }
+    }
+
+    /// The price of the level the plan was computed at.
+    #[must_use]
+    pub fn price(&self) -> u128 {
+        self.price
+    }
+
+    /// The taker the plan matches for.
+    #[must_use]
+    pub fn taker_order_id(&self) -> Id {
+        self.taker_order_id
+    }
+
+    /// The quantity the taker asked for.
+    #[must_use]
+    pub fn quantity(&self) -> u64 {
+        self.quantity
+    }
+
+    /// The time the trades of a committed plan are stamped with.
+    #[must_use]
+    pub fn timestamp(&self) -> TimestampMs {
+        self.timestamp
+    }
+
+    /// The [`PriceLevel::mutation_sequence`] the plan was computed against.
+    ///
+    /// [`PriceLevel::mutation_sequence`]: crate::PriceLevel::mutation_sequence
+    #[must_use]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// The maker steps the sweep would take, in sweep order.
+    #[must_use]
+    pub fn fills(&self) -> &[PlannedFill] {
+        &self.fills
+    }
+
+    /// The total quantity the plan fills.
+    #[must_use]
+    pub fn planned_quantity(&self) -> u64 {
+        self.fills.iter().map(PlannedFill::quantity).sum()
+    }
+
+    /// Returns `true` if the plan fills the whole requested quantity.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.planned_quantity() == self.quantity
+    }
+
+    pub(crate) fn reserved(&self) -> u64 {
+        self.reserved
+    }
+
+    pub(crate) fn policy(&self) -> MatchingPolicy {
+        self.policy
+    }
+}
@@ -26,34 +26,290 @@
 //! - [`PriceLevelSnapshot`] — a point-in-time snapshot of all orders at a price level.
 //! - [`PriceLevelSnapshotPackage`] — a checksum-protected wrapper around a snapshot for
 //!   safe persistence and recovery via JSON.
+//! - [`BookSnapshot`] / [`BookSnapshotPackage`] — many level snapshots of one book
+//!   with its symbol, sequence and timestamp under a single checksum, restored
+//!   in one call.
+//! - [`SnapshotScheduler`] / [`SnapshotSchedule`] — snapshots of a level or book
+//!   taken on an interval or mutation count, rate-capped and coalesced, delivered
+//!   to a callback or channel.
+//! - [`SnapshotValidationReport`] / [`SnapshotFinding`] — every problem a package
+//!   would hit on restore, collected without restoring it.
+//! - [`ChecksumAlgorithm`] — the package checksum: SHA-256 (default, tamper-evident),
+//!   or CRC32C / xxHash64 for fast corruption-only detection on large levels.
 //! - [`PriceLevelStatistics`] — real-time execution statistics (orders added/removed/executed,
 //!   quantity/value executed, average price, waiting times).
+//! - [`StatisticsCsvWriter`] — appends periodic statistics snapshots to a versioned,
+//!   size-rotated CSV file for retaining performance history.
+//! - [`StatisticsWindow`] — execution totals and per-second rates over a trailing
+//!   time window, from [`PriceLevelStatistics::stats_window`].
+//! - [`AlertCondition`] / [`StatisticsAlert`] — thresholds on execution rates and
+//!   waiting time that invoke a callback, via [`PriceLevelStatistics::add_alert`].
+//! - [`StatisticsAnalytics`] — VWAP, order sizes, add-to-cancel ratio and order
+//!   flow imbalance, from [`PriceLevelStatistics::analytics`].
+//! - [`LatencyHistogram`] — opt-in per-call latency of `add_order`, `match_order`
+//!   and `update_order`, from [`PriceLevelStatistics::latency_histogram`].
+//! - [`OrderAgeHistogram`] — how long orders rested before a fill or a removal,
+//!   from [`PriceLevelStatistics::order_age_histogram`] and persisted with
+//!   snapshots.
+//! - `prometheus_text` / `to_prometheus` (feature `metrics`) — Prometheus text
+//!   exposition of statistics and depth for one level or a whole book.
+//! - `FixMdEntry` / `fix_full_refresh` / `fix_incremental_refresh` (feature
+//!   `fix`) — FIX 4.4 market data bodies (`35=W` / `35=X`) for a level's
+//!   displayed orders, its depth, and its events.
+//! - `PriceLevelArchive` (feature `rkyv`) — a zero-copy view of a level archived
+//!   with `to_archive_bytes`, answering totals and order lookups in place.
+//! - [`ExecutionOverflow`] — what a full channel from
+//!   [`PriceLevel::execution_channel_with`] does with a trade: drop it, or
+//!   block the matcher until the consumer catches up.
+//! - `PriceLevelEvent` / `PriceLevel::subscribe` (feature `async`) — an async
+//!   stream of a level's admissions, updates, removals and trades over a
+//!   bounded lock-free channel.
+//! - [`MboRecord`] / [`MbpRecord`] — a level's displayed book as market-by-order
+//!   and market-by-price records, written as CSV or JSON Lines by
+//!   [`write_market_data_csv`] / [`write_market_data_json`] for replay tools.
+//! - [`DepthLadder`] / [`DepthRung`] — cumulative displayed depth of both sides
+//!   of a book, grouped into price buckets, for depth charts.
+//! - [`BookAnalytics`] — top-of-book imbalance, microprice and depth-weighted
+//!   mid of a book's displayed depth, from [`DepthLadder::analytics`].
 //! - [`OrderQueue`] — the underlying lock-free order queue based on crossbeam.
+//! - [`QuarantinedOrder`] / [`QuarantineReason`] — malformed resting orders the
+//!   match sweep moved to the level's dead-letter list instead of trading.
+//! - [`AmendRecord`] — one update applied to a resting order, kept in the
+//!   level's opt-in per-order amend history ([`PriceLevel::order_history`]).
+//! - [`QueueAudit`] / [`QueuePosition`] — every resting order's queue position,
+//!   timestamp and priority sequence at one point, for verifying that fills
+//!   respected price-time priority.
+//! - [`ExpiryWarning`] — advance notice, on the caller's clock, that a resting
+//!   `Gtd` or `Gtt` order is about to expire, so it can be extended in place.
+//! - [`MatchingPolicy`] — how a level allocates a taker across its makers (FIFO,
+//!   pro-rata, size-time priority, strict FIFO).
+//! - [`HiddenPriority`] — whether hidden orders trade after a level's displayed
+//!   liquidity or in time priority with it.
+//! - [`CrossingRule`] — how a level assigns the taker side of its trades, and
+//!   which same-side crossings it rejects.
+//! - [`PriceLevelConfig`] — the construction-time settings of a level,
+//!   including how many shards its order queue's index is split across.
+//! - [`MatchPlan`] / [`PlannedFill`] — a taker's sweep computed by
+//!   [`PriceLevel::propose_match`] without trading, committed or aborted
+//!   after external pre-trade checks.
+//! - [`SymbolSpec`] — tick size, lot size and order size bounds a configured
+//!   level rejects off-increment orders and resizes against.
+//! - [`LevelLimits`] — the most orders, quantity and orders per participant a
+//!   configured level admits.
+//! - [`RateLimiter`] / [`TokenBucketRateLimiter`] — a per-participant message
+//!   throttle a level consults before each admission, update and cancel.
+//! - [`LevelFork`] — a copy-on-write fork of a level from
+//!   [`PriceLevel::fork`], whose queue is copied on its first write.
+//! - [`HaltPolicy`] — what a level frozen by [`PriceLevel::freeze_with`] still
+//!   accepts while its matching is halted.
+//! - [`AuctionBook`] / [`AuctionEquilibrium`] — a two-sided book that collects
+//!   orders in an auction call and uncrosses them at one equilibrium price.
+//! - [`PriceBand`] / [`BandWidth`] / [`BandAction`] — a runtime-adjustable price
+//!   collar a level checks before each match.
+//! - [`PriorityTiers`] / [`TierTable`] — participant classes a FIFO level
+//!   matches tier by tier at the same price.
+//! - [`BoundedLevel`] — a level resting at most a fixed number of orders,
+//!   spilling or refusing the rest.
+//! - [`DeterministicReplay`] — the logical clock and seeded ids of a
+//!   byte-for-byte reproducible replay.
+//! - [`Conflator`] — coalesces level changes into at most one
+//!   [`ConflatedUpdate`] per level per interval.
+//! - [`SessionTagged`] / [`SessionTag`] — the session an order was entered
+//!   on, whose orders `cancel_session` mass-cancels on disconnect.
+//! - [`SnapshotStore`] / [`SnapshotKey`] / [`PrunePolicy`] — durable snapshot
+//!   packages keyed by symbol, price and sequence, pruned by policy;
+//!   `RedbSnapshotStore` (feature `redb`) keeps them in an embedded database.
 //!
 //! # Snapshot Persistence
 //!
-//! Snapshots can be serialized to JSON with SHA-256 checksum protection:
+//! Snapshots can be serialized to JSON with SHA-256 checksum protection
+//! (or a faster [`ChecksumAlgorithm`] via [`PriceLevel::snapshot_package_with`]):
 //!
 //! ```rust
 //! use pricelevel::PriceLevel;
 //!
-//! let level = PriceLevel::new(10_000);
+//! let level: PriceLevel = PriceLevel::new(10_000);
 //! let json = level.snapshot_to_json().unwrap();
-//! let restored = PriceLevel::from_snapshot_json(&json).unwrap();
+//! let restored: PriceLevel = PriceLevel::from_snapshot_json(&json).unwrap();
 //! ```
+//!
+//! [`PriceLevel::save_to_path`] and [`PriceLevel::load_from_path`] keep that
+//! JSON in a file, replaced atomically on each save and checksum-verified on
+//! load.
+
+mod amend_history;
+
+mod auction;
+
+mod latency;
+
+mod lazy_map;
 
 mod level;
 
+mod limits;
+
+mod market_data;
+
+mod match_plan;
+
+mod multi_book;
+
+#[cfg(feature = "rkyv")]
+mod archive;
+
+mod book_analytics;
+
+mod book_snapshot;
+
+mod bounded;
+
+mod checksum;
+
+mod config;
+
+mod conflation;
+
+mod consistent;
+
+mod crossing;
+
+mod depth_ladder;
+
 mod snapshot;
 
+mod snapshot_scheduler;
+
+mod snapshot_store;
+
+mod snapshot_validation;
+
 mod entry;
 
+mod execution_channel;
+
+#[cfg(feature = "async")]
+mod events;
+
+mod expiry;
+
+#[cfg(feature = "fix")]
+mod fix;
+
+mod fork;
+
+mod halt;
+
+mod order_age;
+
 mod order_queue;
 
+mod persistence;
+
+mod policy;
+
+mod price_band;
+
+mod priority_tiers;
+
+#[cfg(feature = "pool")]
+mod pool;
+
+#[cfg(feature = "metrics")]
+mod prometheus;
+
+mod quarantine;
+
+#[cfg(feature = "redb")]
+mod redb_store;
+
+mod queue_audit;
+
+mod rate_limit;
+
+mod replay;
+
+mod reservation;
+
+mod scratch;
+
+mod sequence_index;
+
+mod sequenced;
+
+mod session;
+
 mod statistics;
+
+mod statistics_alerts;
+
+mod statistics_analytics;
+
+mod statistics_csv;
+
+mod statistics_window;
+
+mod symbol_spec;
+
 mod tests;
+mod view;
 
+pub use amend_history::{AmendRecord, DEFAULT_AMEND_HISTORY_CAPACITY};
+#[cfg(feature = "rkyv")]
+pub use archive::{ArchivedOrder, PriceLevelArchive};
+pub use auction::{AuctionBook, AuctionEquilibrium, AuctionPhase, UncrossResult};
+pub use book_analytics::BookAnalytics;
+pub use book_snapshot::{BOOK_SNAPSHOT_FORMAT_VERSION, BookSnapshot, BookSnapshotPackage};
+pub use bounded::{BoundedAdd, BoundedLevel, OverflowPolicy, OverflowStats};
+pub use checksum::ChecksumAlgorithm;
+pub use config::{MAX_QUEUE_SHARDS, PriceLevelConfig};
+pub use conflation::{ConflatedUpdate, Conflator};
+pub use crossing::CrossingRule;
+pub use depth_ladder::{DepthLadder, DepthRung};
+#[cfg(feature = "async")]
+pub use events::{DEFAULT_EVENT_CAPACITY, PriceLevelEvent};
+pub use execution_channel::{DEFAULT_EXECUTION_CHANNEL_CAPACITY, ExecutionOverflow};
+pub use expiry::ExpiryWarning;
+#[cfg(feature = "fix")]
+pub use fix::{
+    FIX_SOH, FixEntryType, FixMdEntry, FixUpdateAction, fix_full_refresh, fix_incremental_refresh,
+};
+pub use fork::LevelFork;
+pub use halt::HaltPolicy;
+pub use latency::{LatencyBucket, LatencyHistogram, LatencyOperation};
 pub use level::{PriceLevel, PriceLevelData};
+pub use limits::LevelLimits;
+pub use market_data::{
+    MarketDataRecord, MboRecord, MbpRecord, write_market_data_csv, write_market_data_json,
+};
+pub use match_plan::{MatchPlan, PlannedFill};
+pub use multi_book::{MultiBook, SessionCancellation, SymbolKey, SymbolStatistics};
+pub use order_age::{OrderAgeBucket, OrderAgeHistogram, OrderAgeOutcome};
 pub use order_queue::OrderQueue;
+pub use policy::{HiddenPriority, MatchingPolicy};
+#[cfg(feature = "pool")]
+pub use pool::{DEFAULT_ORDER_POOL_CAPACITY, OrderPool};
+pub use price_band::{BandAction, BandWidth, PriceBand};
+pub use priority_tiers::{PriorityTiers, TierTable};
+#[cfg(feature = "metrics")]
+pub use prometheus::prometheus_text;
+pub use quarantine::{QuarantineReason, QuarantinedOrder};
+pub use queue_audit::{QueueAudit, QueuePosition};
+pub use rate_limit::{RateLimitedOperation, RateLimiter, TokenBucketRateLimiter};
+#[cfg(feature = "redb")]
+pub use redb_store::RedbSnapshotStore;
+pub use replay::DeterministicReplay;
+pub use reservation::{DEFAULT_RESERVATION_TIMEOUT_MS, ReservationGuard};
+pub use sequenced::Sequenced;
+pub use session::{SessionTag, SessionTagged};
 pub use snapshot::{PriceLevelSnapshot, PriceLevelSnapshotPackage};
+pub use snapshot_scheduler::{SnapshotSchedule, SnapshotScheduler};
+pub use snapshot_store::{PrunePolicy, SnapshotKey, SnapshotStore};
+pub use snapshot_validation::{SnapshotFinding, SnapshotValidationReport};
 pub use statistics::PriceLevelStatistics;
+pub use statistics_alerts::{AlertCondition, StatisticsAlert};
+pub use statistics_analytics::StatisticsAnalytics;
+pub use statistics_csv::{STATISTICS_CSV_SCHEMA_VERSION, StatisticsCsvWriter};
+pub use statistics_window::StatisticsWindow;
+pub use symbol_spec::SymbolSpec;
+pub use view::PriceLevelView;
@@ -0,0 +1,539 @@
+use crate::errors::PriceLevelError;
+use crate::orders::OrderType;
+use crate::price_level::book_snapshot::BookSnapshot;
+use crate::price_level::config::PriceLevelConfig;
+use crate::price_level::halt::HaltPolicy;
+use crate::price_level::level::PriceLevel;
+use crate::price_level::session::SessionTagged;
+use crate::utils::TimestampMs;
+use crossbeam_skiplist::SkipMap;
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The key a [`MultiBook`] files an instrument under: its symbol, or a
+/// numeric instrument id for engines that intern symbols.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SymbolKey {
+    /// A numeric instrument id.
+    Id(u32),
+    /// A symbol such as `"BTC-USD"`.
+    Name(String),
+}
+
+impl From<&str> for SymbolKey {
+    fn from(symbol: &str) -> Self {
+        Self::Name(symbol.to_string())
+    }
+}
+
+impl From<String> for SymbolKey {
+    fn from(symbol: String) -> Self {
+        Self::Name(symbol)
+    }
+}
+
+impl From<u32> for SymbolKey {
+    fn from(id: u32) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl fmt::Display for SymbolKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Name(symbol) => write!(f, "{symbol}"),
+            Self::Id(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+/// The statistics of every level of one symbol, summed.
+///
+/// Counts and quantities are read from each level's advisory counters, so
+/// under concurrent traffic the roll-up is as eventually-consistent as they
+/// are. Sums are checked: one that would overflow fails the roll-up rather
+/// than report a clamped figure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolStatistics {
+    /// The symbol the figures belong to.
+    pub symbol: SymbolKey,
+    /// Number of levels the symbol holds, empty ones included.
+    pub level_count: usize,
+    /// Resting orders across the levels.
+    pub order_count: usize,
+    /// Displayed quantity across the levels.
+    pub visible_quantity: u64,
+    /// Hidden quantity across the levels.
+    pub hidden_quantity: u64,
+    /// Orders added, per `PriceLevelStatistics::orders_added`.
+    pub orders_added: usize,
+    /// Orders removed, per `PriceLevelStatistics::orders_removed`.
+    pub orders_removed: usize,
+    /// Orders executed, per `PriceLevelStatistics::orders_executed`.
+    pub orders_executed: usize,
+    /// Quantity executed across the levels.
+    pub quantity_executed: u64,
+    /// Notional value executed across the levels.
+    pub value_executed: u64,
+}
+
+/// What [`MultiBook::cancel_session`] did: the orders it cancelled and the
+/// levels it could not sweep.
+#[derive(Debug)]
+pub struct SessionCancellation<T> {
+    /// The cancelled orders with their symbols, in the order of
+    /// [`MultiBook::symbols`], then ascending price, then queue order.
+    pub cancelled: Vec<(SymbolKey, Arc<OrderType<T>>)>,
+    /// Each level whose sweep failed, by symbol and price, with its error.
+    /// The session's orders on it are still resting.
+    pub failed: Vec<(SymbolKey, u128, PriceLevelError)>,
+}
+
+impl<T> SessionCancellation<T> {
+    /// Whether every level was swept, so none of the session's orders is
+    /// left resting.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// One symbol's levels, keyed by price, and the configuration new levels
+/// are built from.
+#[derive(Debug)]
+struct SymbolBook<T> {
+    config: PriceLevelConfig,
+    levels: SkipMap<u128, Arc<PriceLevel<T>>>,
+    /// The encoded [`HaltPolicy`] the symbol is frozen under, zero while it
+    /// trades; applied to levels created while it is set.
+    halt: AtomicU8,
+}
+
+/// A registry of independent books for a multi-instrument engine, each a set
+/// of [`PriceLevel`]s filed under a [`SymbolKey`].
+///
+/// Symbols are registered with the [`PriceLevelConfig`] their levels are
+/// built from, so a [`SymbolSpec`](crate::SymbolSpec) in it applies to the
+/// whole symbol. [`Self::add_order`] routes an order to the level at its
+/// price, creating the level on first use; matching, updates and everything
+/// else run on the level itself, reached with [`Self::level`]. Every method
+/// takes `&self`: symbols and levels are held in concurrent maps, and no
+/// call holds a registry lock while it works on a level.
+///
+/// ```rust
+/// use pricelevel::{
+///     Hash32, Id, MultiBook, OrderType, Price, PriceLevelConfig, Quantity, Side, SymbolKey,
+///     TimeInForce, TimestampMs,
+/// };
+///
+/// let book = MultiBook::<()>::new();
+/// book.register("BTC-USD", PriceLevelConfig::new()).unwrap();
+/// book.register(7_u32, PriceLevelConfig::new()).unwrap();
+///
+/// let order = OrderType::Standard {
+///     id: Id::from_u64(1),
+///     price: Price::new(10_000),
+///     quantity: Quantity::new(5),
+///     side: Side::Sell,
+///     user_id: Hash32::zero(),
+///     timestamp: TimestampMs::new(1_000),
+///     time_in_force: TimeInForce::Gtc,
+///     extra_fields: (),
+/// };
+/// book.add_order("BTC-USD", order).unwrap();
+///
+/// let stats = book.statistics("BTC-USD").unwrap();
+/// assert_eq!(stats.visible_quantity, 5);
+/// assert_eq!(book.statistics(7_u32).unwrap().order_count, 0);
+/// assert_eq!(book.symbols(), [SymbolKey::Id(7), SymbolKey::from("BTC-USD")]);
+/// ```
+#[derive(Debug)]
+pub struct MultiBook<T = ()> {
+    books: DashMap<SymbolKey, Arc<SymbolBook<T>>>,
+}
+
+impl<T: Clone> Default for MultiBook<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> MultiBook<T> {
+    /// Creates a registry with no symbols.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            books: DashMap::new(),
+        }
+    }
+
+    /// Registers `symbol`, whose levels will be built from `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is already
+    /// registered.
+    pub fn register(
+        &self,
+        symbol: impl Into<SymbolKey>,
+        config: PriceLevelConfig,
+    ) -> Result<(), PriceLevelError> {
+        let symbol = symbol.into();
+        match self.books.entry(symbol) {
+            dashmap::Entry::Occupied(entry) => Err(PriceLevelError::InvalidOperation {
+                message: format!("symbol {} is already registered", entry.key()),
+            }),
+            dashmap::Entry::Vacant(entry) => {
+                entry.insert(Arc::new(SymbolBook {
+                    config,
+                    levels: SkipMap::new(),
+                    halt: AtomicU8::new(0),
+                }));
+                Ok(())
+            }
+        }
+    }
+
+    /// Removes `symbol` and returns its levels in ascending price order, or
+    /// `None` if it was not registered.
+    pub fn deregister(&self, symbol: impl Into<SymbolKey>) -> Option<Vec<Arc<PriceLevel<T>>>> {
+        self.books
+            .remove(&symbol.into())
+            .map(|(_, book)| Self::collect_levels(&book))
+    }
+
+    /// Returns `true` if `symbol` is registered.
+    #[must_use]
+    pub fn contains(&self, symbol: impl Into<SymbolKey>) -> bool {
+        self.books.contains_key(&symbol.into())
+    }
+
+    /// Returns the registered symbols in ascending order, ids before names.
+    #[must_use]
+    pub fn symbols(&self) -> Vec<SymbolKey> {
+        let mut symbols: Vec<_> = self.books.iter().map(|book| book.key().clone()).collect();
+        symbols.sort_unstable();
+        symbols
+    }
+
+    /// Returns the number of registered symbols.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.books.len()
+    }
+
+    /// Returns `true` if no symbol is registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.books.is_empty()
+    }
+
+    /// Adds `order` to the level of `symbol` at the order's price, creating
+    /// the level from the symbol's configuration if it has none there yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is not
+    /// registered, and any error [`PriceLevel::add_order`] returns.
+    pub fn add_order(
+        &self,
+        symbol: impl Into<SymbolKey>,
+        order: OrderType<T>,
+    ) -> Result<Arc<OrderType<T>>, PriceLevelError> {
+        let book = self.book(&symbol.into())?;
+        let price = order.price().as_u128();
+        let mut created = None;
+        let level = book
+            .levels
+            .get_or_insert_with(price, || {
+                let level = PriceLevel::with_config(price, book.config.clone());
+                let halt = book.halt.load(Ordering::Acquire);
+                level.set_halt_bits(halt);
+                created = Some(halt);
+                Arc::new(level)
+            })
+            .value()
+            .clone();
+        // A freeze or unfreeze that walked the levels before this one was
+        // inserted missed it; it changed the symbol's state first, so catch
+        // up with it here.
+        if let Some(applied) = created {
+            let halt = book.halt.load(Ordering::Acquire);
+            if halt != applied {
+                level.set_halt_bits(halt);
+            }
+        }
+        level.add_order(order)
+    }
+
+    /// Halt trading on every level of `symbol`, still accepting adds,
+    /// cancels and amends: [`Self::freeze_with`] under [`HaltPolicy::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is not
+    /// registered.
+    pub fn freeze(&self, symbol: impl Into<SymbolKey>) -> Result<(), PriceLevelError> {
+        self.freeze_with(symbol, HaltPolicy::new())
+    }
+
+    /// Freeze every level of `symbol` under `policy` (see
+    /// [`PriceLevel::freeze_with`]), including the levels [`Self::add_order`]
+    /// creates until [`Self::unfreeze`]. Levels are frozen one after another,
+    /// so a match on one may still run while another is already frozen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is not
+    /// registered.
+    pub fn freeze_with(
+        &self,
+        symbol: impl Into<SymbolKey>,
+        policy: HaltPolicy,
+    ) -> Result<(), PriceLevelError> {
+        let book = self.book(&symbol.into())?;
+        book.halt.store(policy.encode(), Ordering::Release);
+        for level in Self::collect_levels(&book) {
+            level.freeze_with(policy);
+        }
+        Ok(())
+    }
+
+    /// Resume trading on every level of `symbol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is not
+    /// registered.
+    pub fn unfreeze(&self, symbol: impl Into<SymbolKey>) -> Result<(), PriceLevelError> {
+        let book = self.book(&symbol.into())?;
+        book.halt.store(0, Ordering::Release);
+        for level in Self::collect_levels(&book) {
+            level.unfreeze();
+        }
+        Ok(())
+    }
+
+    /// The policy `symbol` is frozen under, or `None` while it trades or if
+    /// it is not registered.
+    #[must_use]
+    pub fn halt_policy(&self, symbol: impl Into<SymbolKey>) -> Option<HaltPolicy> {
+        let book = self.book(&symbol.into()).ok()?;
+        HaltPolicy::decode(book.halt.load(Ordering::Acquire))
+    }
+
+    /// Returns the level of `symbol` at `price`, or `None` if the symbol is
+    /// not registered or has no level there.
+    #[must_use]
+    pub fn level(&self, symbol: impl Into<SymbolKey>, price: u128) -> Option<Arc<PriceLevel<T>>> {
+        let book = self.book(&symbol.into()).ok()?;
+        book.levels.get(&price).map(|entry| entry.value().clone())
+    }
+
+    /// Returns the levels of `symbol` in ascending price order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is not
+    /// registered.
+    pub fn levels(
+        &self,
+        symbol: impl Into<SymbolKey>,
+    ) -> Result<Vec<Arc<PriceLevel<T>>>, PriceLevelError> {
+        let book = self.book(&symbol.into())?;
+        Ok(Self::collect_levels(&book))
+    }
+
+    /// Snapshots every level of `symbol`, in ascending price order, as a
+    /// [`BookSnapshot`] named after the symbol.
+    ///
+    /// Each level is snapshotted on its own; see [`BookSnapshot::capture`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is not
+    /// registered.
+    pub fn snapshot(
+        &self,
+        symbol: impl Into<SymbolKey>,
+        sequence: u64,
+        timestamp: TimestampMs,
+    ) -> Result<BookSnapshot<T>, PriceLevelError> {
+        let symbol = symbol.into();
+        let levels = self.levels(symbol.clone())?;
+        Ok(BookSnapshot::capture(
+            symbol.to_string(),
+            sequence,
+            timestamp,
+            levels.iter().map(Arc::as_ref),
+        ))
+    }
+
+    /// Rolls the statistics of every level of `symbol` up into one
+    /// [`SymbolStatistics`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `symbol` is not
+    /// registered, or if a sum across its levels overflows.
+    pub fn statistics(
+        &self,
+        symbol: impl Into<SymbolKey>,
+    ) -> Result<SymbolStatistics, PriceLevelError> {
+        let symbol = symbol.into();
+        let book = self.book(&symbol)?;
+        Self::roll_up(symbol, &book)
+    }
+
+    /// Rolls up the statistics of every registered symbol, in the order of
+    /// [`Self::symbols`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if a symbol's sums
+    /// overflow, as [`Self::statistics`] does.
+    pub fn all_statistics(&self) -> Result<Vec<SymbolStatistics>, PriceLevelError> {
+        let mut books: Vec<_> = self
+            .books
+            .iter()
+            .map(|book| (book.key().clone(), Arc::clone(book.value())))
+            .collect();
+        books.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        books
+            .into_iter()
+            .map(|(symbol, book)| Self::roll_up(symbol, &book))
+            .collect()
+    }
+
+    /// Cancels every resting order entered on `session_id`, on every level
+    /// of every symbol, for cancel-on-disconnect (see
+    /// [`PriceLevel::cancel_session`]).
+    ///
+    /// A level that fails is recorded in [`SessionCancellation::failed`] and
+    /// the sweep goes on, so one poisoned level neither hides the orders
+    /// already cancelled nor leaves the session's orders elsewhere resting.
+    /// Levels are swept one at a time, so a level created by a concurrent
+    /// add after the walk reached its symbol is not swept.
+    pub fn cancel_session(&self, session_id: u64) -> SessionCancellation<T>
+    where
+        T: SessionTagged,
+    {
+        let mut cancellation = SessionCancellation {
+            cancelled: Vec::new(),
+            failed: Vec::new(),
+        };
+        for symbol in self.symbols() {
+            // A symbol deregistered since the listing has nothing to cancel.
+            let Ok(book) = self.book(&symbol) else {
+                continue;
+            };
+            for level in Self::collect_levels(&book) {
+                match level.cancel_session(session_id) {
+                    Ok(orders) => cancellation
+                        .cancelled
+                        .extend(orders.into_iter().map(|order| (symbol.clone(), order))),
+                    Err(err) => cancellation
+                        .failed
+                        .push((symbol.clone(), level.price(), err)),
+                }
+            }
+        }
+        cancellation
+    }
+
+    /// Clones the book out of the registry so no shard lock is held while
+    /// the caller works on its levels.
+    fn book(&self, symbol: &SymbolKey) -> Result<Arc<SymbolBook<T>>, PriceLevelError> {
+        self.books
+            .get(symbol)
+            .map(|book| Arc::clone(book.value()))
+            .ok_or_else(|| PriceLevelError::InvalidOperation {
+                message: format!("symbol {symbol} is not registered"),
+            })
+    }
+
+    fn collect_levels(book: &SymbolBook<T>) -> Vec<Arc<PriceLevel<T>>> {
+        book.levels
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect()
+    }
+
+    fn roll_up(
+        symbol: SymbolKey,
+        book: &SymbolBook<T>,
+    ) -> Result<SymbolStatistics, PriceLevelError> {
+        fn sum<N>(symbol: &SymbolKey, field: &str, total: Option<N>) -> Result<N, PriceLevelError> {
+            total.ok_or_else(|| PriceLevelError::InvalidOperation {
+                message: format!("symbol {symbol} {field} overflow"),
+            })
+        }
+
+        let mut rolled = SymbolStatistics {
+            symbol,
+            level_count: 0,
+            order_count: 0,
+            visible_quantity: 0,
+            hidden_quantity: 0,
+            orders_added: 0,
+            orders_removed: 0,
+            orders_executed: 0,
+            quantity_executed: 0,
+            value_executed: 0,
+        };
+        for entry in book.levels.iter() {
+            let level = entry.value();
+            let stats = level.stats();
+            let symbol = &rolled.symbol;
+            rolled.level_count += 1;
+            rolled.order_count = sum(
+                symbol,
+                "order_count",
+                rolled.order_count.checked_add(level.order_count()),
+            )?;
+            rolled.visible_quantity = sum(
+                symbol,
+                "visible_quantity",
+                rolled
+                    .visible_quantity
+                    .checked_add(level.visible_quantity()),
+            )?;
+            rolled.hidden_quantity = sum(
+                symbol,
+                "hidden_quantity",
+                rolled.hidden_quantity.checked_add(level.hidden_quantity()),
+            )?;
+            rolled.orders_added = sum(
+                symbol,
+                "orders_added",
+                rolled.orders_added.checked_add(stats.orders_added()),
+            )?;
+            rolled.orders_removed = sum(
+                symbol,
+                "orders_removed",
+                rolled.orders_removed.checked_add(stats.orders_removed()),
+            )?;
+            rolled.orders_executed = sum(
+                symbol,
+                "orders_executed",
+                rolled.orders_executed.checked_add(stats.orders_executed()),
+            )?;
+            rolled.quantity_executed = sum(
+                symbol,
+                "quantity_executed",
+                rolled
+                    .quantity_executed
+                    .checked_add(stats.quantity_executed()),
+            )?;
+            rolled.value_executed = sum(
+                symbol,
+                "value_executed",
+                rolled.value_executed.checked_add(stats.value_executed()),
+            )?;
+        }
+        Ok(rolled)
+    }
+}
@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Power-of-two age buckets: bucket `0` holds an age of zero and bucket `i`
+/// the ages in `[2^(i-1), 2^i - 1]` milliseconds, covering every `u64`.
+const AGE_BUCKETS: usize = 65;
+
+/// How a resting order's time at a level ended, for
+/// `PriceLevelStatistics::order_age_histogram`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderAgeOutcome {
+    /// A taker traded against the order. Every fill is recorded, so an order
+    /// filled in three parts contributes three ages.
+    Executed,
+    /// The order left the level without trading: cancelled, repriced away,
+    /// expired or quarantined.
+    Removed,
+}
+
+impl OrderAgeOutcome {
+    /// Every outcome, in a stable order.
+    pub const ALL: [OrderAgeOutcome; 2] = [OrderAgeOutcome::Executed, OrderAgeOutcome::Removed];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl fmt::Display for OrderAgeOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderAgeOutcome::Executed => write!(f, "executed"),
+            OrderAgeOutcome::Removed => write!(f, "removed"),
+        }
+    }
+}
+
+/// One non-empty bucket of an [`OrderAgeHistogram`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderAgeBucket {
+    /// Smallest age the bucket holds, in milliseconds.
+    pub lower_ms: u64,
+    /// Largest age the bucket holds, in milliseconds.
+    pub upper_ms: u64,
+    /// Orders whose age fell in the bucket.
+    pub count: u64,
+}
+
+/// How long orders rested at a level before one [`OrderAgeOutcome`], as
+/// returned by `PriceLevelStatistics::order_age_histogram`.
+///
+/// An age is the time from the order's own timestamp to the fill or
+/// removal, in milliseconds: the fill's execution timestamp, or the level
+/// clock's reading for a removal. Orders with a zero timestamp are not
+/// recorded. Ages are bucketed by powers of two, so a percentile is reported
+/// to within a factor of two; only non-empty buckets are listed, ascending.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderAgeHistogram {
+    /// The outcome measured.
+    pub outcome: OrderAgeOutcome,
+    /// Ages recorded.
+    pub count: u64,
+    /// Sum of the recorded ages, in milliseconds (saturating).
+    pub sum_ms: u64,
+    /// Smallest recorded age, in milliseconds; `0` when empty.
+    pub min_ms: u64,
+    /// Largest recorded age, in milliseconds; `0` when empty.
+    pub max_ms: u64,
+    /// The non-empty buckets, ascending.
+    pub buckets: Vec<OrderAgeBucket>,
+}
+
+impl OrderAgeHistogram {
+    /// Mean age, or `None` when nothing was recorded.
+    #[must_use]
+    pub fn mean(&self) -> Option<Duration> {
+        self.sum_ms
+            .checked_div(self.count)
+            .map(Duration::from_millis)
+    }
+
+    /// Age at quantile `q` (`0.0..=1.0`, clamped): the upper bound of the
+    /// bucket holding the `q`-th order, capped at [`Self::max_ms`]. `None`
+    /// when nothing was recorded.
+    #[must_use]
+    pub fn percentile(&self, q: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        let q = if q.is_nan() { 0.0 } else { q.clamp(0.0, 1.0) };
+        let rank = ((q * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0_u64;
+        for bucket in &self.buckets {
+            seen = seen.saturating_add(bucket.count);
+            if seen >= rank {
+                return Some(Duration::from_millis(bucket.upper_ms.min(self.max_ms)));
+            }
+        }
+        Some(Duration::from_millis(self.max_ms))
+    }
+}
+
+/// Bucket index of an age of `ms` milliseconds.
+fn bucket_of(ms: u64) -> usize {
+    (u64::BITS - ms.leading_zeros()) as usize
+}
+
+/// Inclusive millisecond bounds of bucket `index`.
+fn bucket_bounds(index: usize) -> (u64, u64) {
+    match index {
+        0 => (0, 0),
+        _ => {
+            let lower = 1_u64 << (index - 1);
+            (lower, lower + (lower - 1))
+        }
+    }
+}
+
+/// Lock-free age counters of one outcome.
+#[derive(Debug)]
+struct AgeCounters {
+    buckets: [AtomicU64; AGE_BUCKETS],
+    sum_ms: AtomicU64,
+    min_ms: AtomicU64,
+    max_ms: AtomicU64,
+}
+
+impl AgeCounters {
+    fn new() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            sum_ms: AtomicU64::new(0),
+            min_ms: AtomicU64::new(u64::MAX),
+            max_ms: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, ms: u64) {
+        self.buckets[bucket_of(ms)].fetch_add(1, Ordering::Relaxed);
+        // `fetch_update` with an always-`Some` closure cannot fail.
+        let _ = self
+            .sum_ms
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |sum| {
+                Some(sum.saturating_add(ms))
+            });
+        self.min_ms.fetch_min(ms, Ordering::Relaxed);
+        self.max_ms.fetch_max(ms, Ordering::Relaxed);
+    }
+
+    fn clear(&self) {
+        for bucket in &self.buckets {
+            bucket.store(0, Ordering::Relaxed);
+        }
+        self.sum_ms.store(0, Ordering::Relaxed);
+        self.min_ms.store(u64::MAX, Ordering::Relaxed);
+        self.max_ms.store(0, Ordering::Relaxed);
+    }
+
+    /// Replace the counters with `histogram`'s. Each bucket is placed by its
+    /// lower bound.
+    fn restore(&self, histogram: &OrderAgeHistogram) {
+        self.clear();
+        for bucket in &histogram.buckets {
+            self.buckets[bucket_of(bucket.lower_ms)].fetch_add(bucket.count, Ordering::Relaxed);
+        }
+        self.sum_ms.store(histogram.sum_ms, Ordering::Relaxed);
+        if histogram.count > 0 {
+            self.min_ms.store(histogram.min_ms, Ordering::Relaxed);
+            self.max_ms.store(histogram.max_ms, Ordering::Relaxed);
+        }
+    }
+
+    fn snapshot(&self, outcome: OrderAgeOutcome) -> OrderAgeHistogram {
+        let buckets: Vec<OrderAgeBucket> = self
+            .buckets
+            .iter()
+            .enumerate()
+            .filter_map(|(index, bucket)| {
+                let count = bucket.load(Ordering::Relaxed);
+                (count > 0).then(|| {
+                    let (lower_ms, upper_ms) = bucket_bounds(index);
+                    OrderAgeBucket {
+                        lower_ms,
+                        upper_ms,
+                        count,
+                    }
+                })
+            })
+            .collect();
+        // The count is the bucket total, so percentiles over a histogram read
+        // mid-recording stay within the listed buckets.
+        let count = buckets.iter().map(|bucket| bucket.count).sum();
+        let max_ms = self.max_ms.load(Ordering::Relaxed);
+        OrderAgeHistogram {
+            outcome,
+            count,
+            sum_ms: self.sum_ms.load(Ordering::Relaxed),
+            min_ms: if count == 0 {
+                0
+            } else {
+                self.min_ms.load(Ordering::Relaxed).min(max_ms)
+            },
+            max_ms,
+            buckets,
+        }
+    }
+}
+
+/// Resting-order age histograms kept by a level's statistics.
+///
+/// The counters are allocated on the first recorded age, so a level that
+/// never fills or removes an order carries one empty cell. Unlike the latency
+/// histograms they are level state: cloned and serialized with the
+/// statistics, so a snapshot carries them.
+#[derive(Debug, Default)]
+pub(crate) struct OrderAges {
+    counters: OnceLock<Box<[AgeCounters; 2]>>,
+}
+
+impl OrderAges {
+    fn counters(&self) -> &[AgeCounters; 2] {
+        self.counters
+            .get_or_init(|| Box::new([AgeCounters::new(), AgeCounters::new()]))
+    }
+
+    pub(crate) fn record(&self, outcome: OrderAgeOutcome, age_ms: u64) {
+        self.counters()[outcome.index()].record(age_ms);
+    }
+
+    pub(crate) fn histogram(&self, outcome: OrderAgeOutcome) -> OrderAgeHistogram {
+        match self.counters.get() {
+            Some(counters) => counters[outcome.index()].snapshot(outcome),
+            None => AgeCounters::new().snapshot(outcome),
+        }
+    }
+
+    /// The histograms of the outcomes with at least one age, in
+    /// [`OrderAgeOutcome::ALL`] order: the serialized form.
+    pub(crate) fn recorded(&self) -> Vec<OrderAgeHistogram> {
+        match self.counters.get() {
+            Some(_) => OrderAgeOutcome::ALL
+                .into_iter()
+                .map(|outcome| self.histogram(outcome))
+                .filter(|histogram| histogram.count > 0)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Ages restored from `histograms`, the serialized form.
+    pub(crate) fn from_recorded(histograms: &[OrderAgeHistogram]) -> Self {
+        let ages = Self::default();
+        for histogram in histograms {
+            ages.counters()[histogram.outcome.index()].restore(histogram);
+        }
+        ages
+    }
+
+    pub(crate) fn clear(&self) {
+        if let Some(counters) = self.counters.get() {
+            for counter in counters.iter() {
+                counter.clear();
+            }
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use crate::errors::PriceLevelError;
 use crate::orders::{Id, OrderType};
+#[cfg(feature = "pool")]
+use crate::price_level::pool::OrderPool;
+use crate::price_level::sequence_index::SequenceIndex;
 use crossbeam_skiplist::SkipMap;
 use dashmap::DashMap;
-use dashmap::mapref::entry::Entry;
+use dashmap::mapref::entry::{Entry, OccupiedEntry};
 use serde::de::{SeqAccess, Visitor};
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -12,7 +15,11 @@ use std::fmt::Display;
 use std::marker::PhantomData;
 use std::str::FromStr;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// The first tail sequence. Sequences start mid-range so there is room to
+/// place an order ahead of every resting one.
+pub(crate) const FIRST_SEQ: u64 = 1 << 63;
 
 /// A thread-safe queue of orders with specialized operations.
 ///
@@ -22,16 +29,39 @@ use std::sync::atomic::{AtomicU64, Ordering};
 /// front of the queue: the residual is re-inserted at its *original* sequence,
 /// instead of being appended to the tail.
 #[derive(Debug)]
-pub struct OrderQueue {
+pub struct OrderQueue<T = ()> {
     /// A map of order IDs to `(insertion sequence, order)` for O(1) lookups.
     /// The sequence travels with the value so it can be recovered on pop and
     /// reused when re-inserting a partial-fill residual.
-    orders: DashMap<Id, (u64, Arc<OrderType<()>>)>,
+    orders: DashMap<Id, (u64, Arc<OrderType<T>>)>,
     /// Ordered index `sequence -> Id`. The lowest sequence is the front
-    /// (oldest) order, so iteration / pop honours strict time priority.
-    index: SkipMap<u64, Id>,
-    /// Monotonic source of insertion sequences.
+    /// (oldest) order, so iteration / pop honours strict time priority. Split
+    /// into id-hashed shards when the queue is built with
+    /// [`OrderQueue::with_shards`].
+    index: SequenceIndex,
+    /// Hidden orders ([`OrderType::Hidden`]), `sequence -> Id`, kept out of
+    /// `index` so a sweep trading displayed liquidity first never has to
+    /// step over them (see [`FrontScan`]).
+    hidden: SequenceIndex,
+    /// Pending stop orders, `sequence -> Id`, kept out of `index` so the match
+    /// sweep never visits them. [`OrderType::activate`] moves an order from
+    /// here into `index` at a fresh tail sequence.
+    stops: SkipMap<u64, Id>,
+    /// Monotonic source of insertion sequences, counting up from
+    /// [`FIRST_SEQ`].
     next_seq: AtomicU64,
+    /// Source of front sequences, counting down from [`FIRST_SEQ`], for the
+    /// orders [`OrderQueue::try_push_front_with`] places ahead of the queue.
+    front_seq: AtomicU64,
+    /// Tombstone mode (the level's [`MatchingPolicy::StrictFifo`](crate::MatchingPolicy::StrictFifo)).
+    /// While set, a cancel or a priority-losing amend leaves the old `index`
+    /// key behind as a tombstone instead of removing it, so the index is only
+    /// ever appended at the tail and consumed at the front.
+    tombstones: AtomicBool,
+    /// Source of order allocations and sink for evicted orders, set by
+    /// [`PriceLevel::with_order_pool`](crate::PriceLevel::with_order_pool).
+    #[cfg(feature = "pool")]
+    pool: Option<Arc<OrderPool<T>>>,
 }
 
 /// The mutation a matcher decides to apply to the front maker it is currently
@@ -44,18 +74,18 @@ pub struct OrderQueue {
 /// is never called) or entirely after the commit (it observes the residual /
 /// emptiness the matcher left behind). A cancel can never be lost mid-decision.
 #[derive(Debug)]
-pub(crate) enum FrontAction {
+pub(crate) enum FrontAction<T> {
     /// The maker was fully consumed: remove it from `orders` and drop its index
     /// entry. After this the id no longer rests at the level.
     Remove,
     /// Pure partial fill: keep the maker at its current insertion sequence
     /// (and therefore its price-time / FIFO position) by swapping the stored
     /// value to the residual in place under the per-entry lock.
-    KeepInPlace(Arc<OrderType<()>>),
+    KeepInPlace(Arc<OrderType<T>>),
     /// Iceberg / reserve replenishment: the refreshed tranche loses time
     /// priority, so remove the old entry and re-queue the new order at the tail
     /// with a fresh insertion sequence.
-    ReplaceAtTail(Arc<OrderType<()>>),
+    ReplaceAtTail(Arc<OrderType<T>>),
     /// The maker made no progress this sweep (a degenerate zero-progress shape).
     /// Leave it untouched in `orders`/`index`; the caller sets its sequence
     /// aside so the sweep advances to the maker behind it without re-popping it.
@@ -66,16 +96,16 @@ pub(crate) enum FrontAction {
 /// to commit, after deriving it from the **live** stored order under the entry
 /// lock. Mirrors the [`FrontAction`] precedent for the match sweep.
 #[derive(Debug)]
-pub(crate) enum UpdateDecision {
+pub(crate) enum UpdateDecision<T> {
     /// Decrease / unchanged total: swap the stored value to the resized order at
     /// its existing insertion sequence, keeping its price-time position.
-    KeepInPlace(Arc<OrderType<()>>),
+    KeepInPlace(Arc<OrderType<T>>),
     /// Increase in total: demote the resized order to a fresh tail sequence
     /// (losing time priority) by minting a new sequence, swapping the stored
     /// `(seq, order)` pair in place, and re-keying the index — all under the
     /// entry lock the update already holds. Same shape as the
     /// [`FrontAction::ReplaceAtTail`] the match sweep commits.
-    ReplaceAtTail(Arc<OrderType<()>>),
+    ReplaceAtTail(Arc<OrderType<T>>),
 }
 
 /// The outcome of a single [`OrderQueue::match_front`] step, reported back to
@@ -93,14 +123,179 @@ pub(crate) enum FrontOutcome<R> {
     Empty,
 }
 
-impl OrderQueue {
+/// Which resting orders an [`OrderQueue::match_front`] scan takes its
+/// front from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FrontScan {
+    /// Every matchable order, in time priority.
+    All,
+    /// Every matchable order except the hidden ones.
+    Displayed,
+    /// Only the hidden orders.
+    Hidden,
+}
+
+/// The index an order's key lives in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Keys {
+    Displayed,
+    Hidden,
+    Stop,
+}
+
+impl Keys {
+    fn of<T: Clone>(order: &OrderType<T>) -> Self {
+        if order.is_stop() {
+            Self::Stop
+        } else if order.is_hidden() {
+            Self::Hidden
+        } else {
+            Self::Displayed
+        }
+    }
+}
+
+/// Merge two ascending `(sequence, id)` walks into one.
+fn merge_by_seq(
+    a: impl Iterator<Item = (u64, Id)>,
+    b: impl Iterator<Item = (u64, Id)>,
+) -> impl Iterator<Item = (u64, Id)> {
+    let mut a = a.peekable();
+    let mut b = b.peekable();
+    std::iter::from_fn(move || match (a.peek(), b.peek()) {
+        (Some(&(a_seq, _)), Some(&(b_seq, _))) if b_seq < a_seq => b.next(),
+        (Some(_), _) => a.next(),
+        (None, _) => b.next(),
+    })
+}
+
+impl<T: Clone> OrderQueue<T> {
     /// Create a new empty order queue
     #[must_use]
     pub fn new() -> Self {
+        Self::with_shards(1)
+    }
+
+    /// Create a new empty order queue whose time-priority index is split
+    /// across `shards` skip lists, keyed by a hash of the order id.
+    ///
+    /// One shard is the plain queue. Several spread the index traffic of a
+    /// very deep level over independent skip lists; the match sweep then takes
+    /// the shards' fronts round-robin, so time priority holds within a shard
+    /// and only approximately across them. In tombstone mode (strict FIFO) the
+    /// sweep always takes the exact oldest order instead. A `shards` of `0` is
+    /// treated as `1`.
+    #[must_use]
+    pub fn with_shards(shards: usize) -> Self {
         Self {
             orders: DashMap::new(),
-            index: SkipMap::new(),
-            next_seq: AtomicU64::new(0),
+            index: SequenceIndex::new(shards),
+            hidden: SequenceIndex::new(shards),
+            stops: SkipMap::new(),
+            next_seq: AtomicU64::new(FIRST_SEQ),
+            front_seq: AtomicU64::new(FIRST_SEQ),
+            tombstones: AtomicBool::new(false),
+            #[cfg(feature = "pool")]
+            pool: None,
+        }
+    }
+
+    /// Number of skip lists the time-priority index is split across (see
+    /// [`OrderQueue::with_shards`]).
+    #[must_use]
+    #[inline]
+    pub fn shard_count(&self) -> usize {
+        self.index.shard_count()
+    }
+
+    /// The index shard `order_id`'s keys live in.
+    #[cfg(test)]
+    pub(crate) fn shard_of(&self, order_id: Id) -> usize {
+        self.index.shard_index(order_id)
+    }
+
+    /// The same queue, allocating from and recycling into `pool`.
+    #[cfg(feature = "pool")]
+    #[must_use]
+    pub(crate) fn with_pool(mut self, pool: Arc<OrderPool<T>>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// The pool set by [`Self::with_pool`], if any.
+    #[cfg(feature = "pool")]
+    pub(crate) fn pool(&self) -> Option<&Arc<OrderPool<T>>> {
+        self.pool.as_ref()
+    }
+
+    /// Move `order` into an allocation for the queue: a pooled one if the
+    /// queue has a pool.
+    pub(crate) fn allocate(&self, order: OrderType<T>) -> Arc<OrderType<T>> {
+        #[cfg(feature = "pool")]
+        if let Some(pool) = &self.pool {
+            return pool.acquire(order);
+        }
+        Arc::new(order)
+    }
+
+    /// Drop an order the queue evicted, offering its allocation to the pool
+    /// first. Runs with no entry lock held (issue #128).
+    fn retire(&self, evicted: Option<Arc<OrderType<T>>>) {
+        #[cfg(feature = "pool")]
+        if let Some(pool) = &self.pool {
+            if let Some(order) = evicted {
+                pool.release(order);
+            }
+            return;
+        }
+        drop(evicted);
+    }
+
+    /// The time-priority index of the hidden orders or of the others.
+    fn time_index(&self, hidden: bool) -> &SequenceIndex {
+        if hidden { &self.hidden } else { &self.index }
+    }
+
+    /// Publish `order_id`'s key at `seq` in the index of its `keys`.
+    fn link(&self, seq: u64, order_id: Id, keys: Keys) {
+        match keys {
+            Keys::Stop => {
+                self.stops.insert(seq, order_id);
+            }
+            Keys::Hidden | Keys::Displayed => {
+                self.time_index(keys == Keys::Hidden).insert(seq, order_id);
+            }
+        }
+    }
+
+    /// Drop the key [`Self::link`] published.
+    fn drop_key(&self, seq: u64, order_id: Id, keys: Keys) {
+        match keys {
+            Keys::Stop => {
+                self.stops.remove(&seq);
+            }
+            Keys::Hidden | Keys::Displayed => {
+                self.time_index(keys == Keys::Hidden).remove(seq, order_id);
+            }
+        }
+    }
+
+    /// [`Self::drop_key`], except that a time-priority key stays behind as a
+    /// tombstone in tombstone mode; a stop key never does, as no front scan
+    /// walks the stop index.
+    fn unlink(&self, seq: u64, order_id: Id, keys: Keys) {
+        if keys == Keys::Stop || !self.tombstone_mode() {
+            self.drop_key(seq, order_id, keys);
+        }
+    }
+
+    /// Move `order_id`'s key at `seq` when an order swapped in place belongs
+    /// in another index than the one it replaced.
+    fn rekey(&self, seq: u64, order_id: Id, old: &OrderType<T>, new: &OrderType<T>) {
+        let (old, new) = (Keys::of(old), Keys::of(new));
+        if old != new {
+            self.link(seq, order_id, new);
+            self.drop_key(seq, order_id, old);
         }
     }
 
@@ -116,15 +311,16 @@ impl OrderQueue {
     /// deliberately not part of the public API — like [`OrderQueue::reinsert`],
     /// it is `#[cfg(test)]`.
     #[cfg(test)]
-    pub(crate) fn push(&self, order: Arc<OrderType<()>>) {
+    pub(crate) fn push(&self, order: Arc<OrderType<T>>) {
         // `Relaxed` is sufficient: only the uniqueness and monotonicity of the
         // counter matter. The happens-before ordering between concurrent
         // producers/consumers is provided by the lock-free `index`/`orders`
         // structures, not by this counter, so no synchronization rides on it.
         let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
         let order_id = order.id();
+        let keys = Keys::of(&order);
         self.orders.insert(order_id, (seq, order));
-        self.index.insert(seq, order_id);
+        self.link(seq, order_id, keys);
     }
 
     /// Insert an order only if its id is not already present — the admission
@@ -146,7 +342,7 @@ impl OrderQueue {
     /// Returns [`PriceLevelError::DuplicateOrderId`] if an order with the same
     /// id already rests in the queue.
     #[must_use = "a rejected duplicate must be handled, not ignored"]
-    pub fn try_push(&self, order: Arc<OrderType<()>>) -> Result<(), PriceLevelError> {
+    pub fn try_push(&self, order: Arc<OrderType<T>>) -> Result<(), PriceLevelError> {
         self.try_push_with(order, || Ok(()))
     }
 
@@ -197,15 +393,49 @@ impl OrderQueue {
     #[must_use = "a rejected admission must be handled, not ignored"]
     pub(crate) fn try_push_with<F>(
         &self,
-        order: Arc<OrderType<()>>,
+        order: Arc<OrderType<T>>,
         reserve: F,
     ) -> Result<(), PriceLevelError>
+    where
+        F: FnOnce() -> Result<(), PriceLevelError>,
+    {
+        self.try_insert_with(order, reserve, false)
+    }
+
+    /// [`OrderQueue::try_push_with`], placing the order at the FRONT of the
+    /// queue: ahead of every order resting, including any placed at the front
+    /// before it. With several shards it heads its own shard.
+    ///
+    /// # Errors
+    ///
+    /// Returns what [`OrderQueue::try_push_with`] returns.
+    #[must_use = "a rejected admission must be handled, not ignored"]
+    pub(crate) fn try_push_front_with<F>(
+        &self,
+        order: Arc<OrderType<T>>,
+        reserve: F,
+    ) -> Result<(), PriceLevelError>
+    where
+        F: FnOnce() -> Result<(), PriceLevelError>,
+    {
+        self.try_insert_with(order, reserve, true)
+    }
+
+    /// Shared body of [`OrderQueue::try_push_with`] and
+    /// [`OrderQueue::try_push_front_with`].
+    fn try_insert_with<F>(
+        &self,
+        order: Arc<OrderType<T>>,
+        reserve: F,
+        front: bool,
+    ) -> Result<(), PriceLevelError>
     where
         F: FnOnce() -> Result<(), PriceLevelError>,
     {
         let order_id = order.id();
+        let keys = Keys::of(&order);
         match self.orders.entry(order_id) {
-            Entry::Occupied(_) => Err(PriceLevelError::DuplicateOrderId(order_id.to_string())),
+            Entry::Occupied(_) => Err(PriceLevelError::DuplicateOrderId { order_id }),
             Entry::Vacant(slot) => {
                 // Identity is already decided (this arm means the id is free).
                 // Run the caller's reservation before publishing; on failure
@@ -216,12 +446,16 @@ impl OrderQueue {
                 // Mint the sequence only now that the id is free and the
                 // reservation committed, so neither a rejected duplicate nor a
                 // failed reservation leaves a gap in the sequence.
-                let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+                let seq = if front {
+                    self.front_seq.fetch_sub(1, Ordering::Relaxed) - 1
+                } else {
+                    self.next_seq.fetch_add(1, Ordering::Relaxed)
+                };
                 // Hold the shard lock across BOTH publications: the map insert
                 // returns a guard that keeps the lock, the index entry is added
                 // while it is held, and only then is the guard dropped.
                 let guard = slot.insert((seq, order));
-                self.index.insert(seq, order_id);
+                self.link(seq, order_id, keys);
                 drop(guard);
                 Ok(())
             }
@@ -236,12 +470,21 @@ impl OrderQueue {
     /// per-entry lock via [`OrderQueue::match_front`]); this remains the backing
     /// of the destructive [`OrderQueue::pop`] used by tests and queue draining.
     #[must_use]
-    pub(crate) fn pop_entry(&self) -> Option<(u64, Arc<OrderType<()>>)> {
+    pub(crate) fn pop_entry(&self) -> Option<(u64, Arc<OrderType<T>>)> {
         loop {
-            // `pop_front` atomically removes the lowest-sequence index entry.
-            let entry = self.index.pop_front()?;
-            let popped_seq = *entry.key();
-            let order_id = *entry.value();
+            // `pop_front` atomically removes the lowest-sequence index entry,
+            // taken from whichever of the two time-priority indexes holds the
+            // lower front.
+            let hidden = match (self.index.first(), self.hidden.first()) {
+                (Some(displayed), Some(hidden)) => hidden < displayed,
+                (displayed, _) => displayed.is_none(),
+            };
+            let Some((popped_seq, order_id)) = self.time_index(hidden).pop_front() else {
+                if self.index.first().is_none() && self.hidden.first().is_none() {
+                    return None;
+                }
+                continue;
+            };
             // Validate the maker's STORED sequence against the key we popped,
             // under the map entry lock (issue #127). A concurrent
             // `resequence_to_tail` may have demoted this id to a fresh tail
@@ -266,7 +509,7 @@ impl OrderQueue {
 
     /// Attempt to pop an order from the queue (front / oldest first).
     #[must_use]
-    pub fn pop(&self) -> Option<Arc<OrderType<()>>> {
+    pub fn pop(&self) -> Option<Arc<OrderType<T>>> {
         self.pop_entry().map(|(_, order)| order)
     }
 
@@ -321,26 +564,49 @@ impl OrderQueue {
     /// [`FrontAction::ReplaceAtTail`] re-prioritisation swaps the value and
     /// re-sequences it in place rather than removing-then-re-pushing — so the
     /// lost-cancel window is closed for every action, not just the partial fill.
+    ///
+    /// `scan` picks the orders the front is taken from. Hidden orders keep
+    /// their keys in an index of their own, so a sweep trading displayed
+    /// liquidity first scans [`FrontScan::Displayed`] and then
+    /// [`FrontScan::Hidden`] instead of stepping over every hidden order ahead
+    /// of the displayed ones. [`FrontScan::All`] takes the lower of the two
+    /// fronts.
     pub(crate) fn match_front<F, R>(
         &self,
+        scan: FrontScan,
         set_aside: &mut HashSet<u64>,
         decide: F,
     ) -> FrontOutcome<R>
     where
-        F: FnOnce(u64, &OrderType<()>) -> (FrontAction, R),
+        F: FnOnce(u64, &OrderType<T>) -> (FrontAction<T>, R),
     {
         loop {
             // Find the lowest-sequence index entry not already set aside this
-            // sweep. `index.iter()` yields entries in ascending sequence order
-            // (front = oldest = highest time priority).
-            let Some((seq, order_id)) = self
-                .index
-                .iter()
-                .find(|e| !set_aside.contains(e.key()))
-                .map(|e| (*e.key(), *e.value()))
-            else {
+            // sweep (front = oldest = highest time priority). A sharded index
+            // takes its shards' fronts in turn instead, except in tombstone
+            // mode, whose strict FIFO needs the exact front.
+            let exact = self.tombstone_mode();
+            let front = |hidden: bool| {
+                self.time_index(hidden)
+                    .front(set_aside, exact)
+                    .map(|(seq, order_id)| (seq, order_id, hidden))
+            };
+            let front = match scan {
+                FrontScan::Displayed => front(false),
+                FrontScan::Hidden => front(true),
+                FrontScan::All => match (front(false), front(true)) {
+                    (Some(displayed), Some(hidden)) => Some(if hidden.0 < displayed.0 {
+                        hidden
+                    } else {
+                        displayed
+                    }),
+                    (displayed, hidden) => displayed.or(hidden),
+                },
+            };
+            let Some((seq, order_id, hidden)) = front else {
                 return FrontOutcome::Empty;
             };
+            let index = self.time_index(hidden);
 
             // Lock the maker's `orders` entry. `entry` takes the shard write
             // lock, which a concurrent `cancel`'s `remove` must also take, so the
@@ -352,10 +618,10 @@ impl OrderQueue {
                     // with the next front candidate. The cancel already
                     // decremented the counters, so there is nothing to account
                     // here.
-                    self.index.remove(&seq);
+                    index.remove(seq, order_id);
                     continue;
                 }
-                Entry::Occupied(mut occupied) => {
+                Entry::Occupied(occupied) => {
                     // Stale front-selection guard (issue #119). The `(seq, id)`
                     // pair was read from the index BEFORE this entry lock was
                     // taken. A concurrent quantity-increase demotion (the
@@ -373,7 +639,7 @@ impl OrderQueue {
                     // the front maker being finite (the single-logical-writer
                     // update contract), as with the `Vacant` self-heal.
                     if occupied.get().0 != seq {
-                        self.index.remove(&seq);
+                        index.remove(seq, order_id);
                         continue;
                     }
 
@@ -386,90 +652,22 @@ impl OrderQueue {
                     // escapes into a `FrontAction`).
                     let (action, result) = decide(seq, occupied.get().1.as_ref());
 
+                    // Commit under the entry lock; the helper releases it on
+                    // every arm and hands back what must run unlocked.
+                    let (park_seq, evicted) = self.commit_front_action(occupied, seq, &action);
+
                     // A `SetAside` records a sequence into the caller's scratch
-                    // `HashSet`, whose first insert allocates. Defer that insert
+                    // `HashSet`, whose first insert allocates. It is deferred
                     // until AFTER the entry lock is released (issue #126) so no
                     // allocation ever runs under the shard lock — the set is
                     // per-sweep scratch owned by the caller, never shared, so it
-                    // needs no lock protection. The other actions commit their
-                    // queue mutations here, under the lock, as before.
-                    let mut park_seq: Option<u64> = None;
-                    // The order swapped OUT of the slot by a partial fill /
-                    // replenish, captured with `mem::replace` and dropped only
-                    // AFTER the entry lock is released (issue #128), so a
-                    // last-reference deallocation never runs under the shard lock.
-                    let mut evicted: Option<Arc<OrderType<()>>> = None;
-                    // Every arm releases the entry lock by the time it finishes
-                    // (either `occupied.remove()` consumes it, or an explicit
-                    // `drop`), so the deferred `set_aside` insert and the evicted
-                    // order's drop below never run under the shard lock.
-                    match &action {
-                        FrontAction::Remove => {
-                            // Full consume: remove the entry under the lock, then
-                            // drop its index entry. A cancel cannot also remove it
-                            // (the entry is gone), so no double counter decrement.
-                            // `remove` consumes the guard, releasing the lock
-                            // before the removed value is dropped.
-                            let _ = occupied.remove();
-                            self.index.remove(&seq);
-                        }
-                        FrontAction::KeepInPlace(residual) => {
-                            // Partial fill keeping priority: swap the stored value
-                            // to the residual in place, keeping the same
-                            // sequence/index entry. Still under the entry lock.
-                            evicted = Some(std::mem::replace(
-                                &mut occupied.get_mut().1,
-                                residual.clone(),
-                            ));
-                            drop(occupied);
-                        }
-                        FrontAction::ReplaceAtTail(refreshed) => {
-                            // Replenished tranche loses time priority, but the
-                            // maker keeps the SAME id and must stay resident in
-                            // `orders` so a concurrent cancel cannot slip into a
-                            // remove-then-push gap. So: mint a fresh tail sequence
-                            // and swap BOTH the value and its stored sequence in
-                            // place under the entry lock; only the index is
-                            // re-keyed (old seq -> new seq) afterwards.
-                            let new_seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
-                            {
-                                let slot = occupied.get_mut();
-                                slot.0 = new_seq;
-                                evicted = Some(std::mem::replace(&mut slot.1, refreshed.clone()));
-                            }
-                            // `occupied` still holds the per-entry lock here, so
-                            // re-keying the index — a different structure
-                            // (`SkipMap`), no deadlock — happens while a concurrent
-                            // cancel is still excluded from the entry. Insert the
-                            // NEW key BEFORE removing the old (issue #127) so the
-                            // id is never transiently absent from the index and a
-                            // concurrent front scan can never miss it. Once the
-                            // lock is released the value already carries `new_seq`,
-                            // so a cancel removes `orders[id]` and `index[new_seq]`
-                            // consistently. The only residue a race can leave is a
-                            // stale `index[seq|new_seq] -> id` entry pointing at an
-                            // already-removed id, which the next `match_front`
-                            // self-heals on the `Vacant` branch. No order and no
-                            // counter update is ever lost.
-                            self.index.insert(new_seq, order_id);
-                            self.index.remove(&seq);
-                            drop(occupied);
-                        }
-                        FrontAction::SetAside => {
-                            // No progress: leave the entry untouched. Release the
-                            // lock and park its sequence below.
-                            drop(occupied);
-                            park_seq = Some(seq);
-                        }
-                    }
-
-                    // The entry lock is released on every arm above; a
-                    // possibly-allocating scratch-set insert and the evicted
-                    // order's drop now run unlocked.
+                    // needs no lock protection. The evicted order's drop is
+                    // deferred likewise (issue #128).
                     if let Some(seq) = park_seq {
                         set_aside.insert(seq);
                     }
-                    drop(evicted);
+                    self.retire(evicted);
+                    index.advance();
 
                     return FrontOutcome::Matched { result };
                 }
@@ -477,6 +675,126 @@ impl OrderQueue {
         }
     }
 
+    /// Commit a [`FrontAction`] decided for the maker held by `occupied`
+    /// (stored at `seq`), under that entry's lock.
+    ///
+    /// Shared by [`OrderQueue::match_front`] and [`OrderQueue::match_entry`] so
+    /// both paths apply exactly the same queue transitions. Every arm releases
+    /// the entry lock before returning (either `occupied.remove()` consumes it,
+    /// or an explicit `drop`). The returned sequence to park (for
+    /// [`FrontAction::SetAside`]) and the order evicted by a full consume,
+    /// partial fill or replenish are handed back so the caller can insert / drop them
+    /// **unlocked** (issues #126 / #128).
+    fn commit_front_action(
+        &self,
+        mut occupied: OccupiedEntry<'_, Id, (u64, Arc<OrderType<T>>)>,
+        seq: u64,
+        action: &FrontAction<T>,
+    ) -> (Option<u64>, Option<Arc<OrderType<T>>>) {
+        let order_id = *occupied.key();
+        let mut park_seq: Option<u64> = None;
+        // The order swapped OUT of the slot by a partial fill /
+        // replenish, captured with `mem::replace` and dropped only
+        // AFTER the entry lock is released (issue #128), so a
+        // last-reference deallocation never runs under the shard lock.
+        let mut evicted: Option<Arc<OrderType<T>>> = None;
+        // Every arm releases the entry lock by the time it finishes
+        // (either `occupied.remove()` consumes it, or an explicit
+        // `drop`), so the caller's deferred `set_aside` insert and the
+        // evicted order's drop never run under the shard lock.
+        match action {
+            FrontAction::Remove => {
+                // Full consume: remove the entry under the lock, then
+                // drop its index entry. A cancel cannot also remove it
+                // (the entry is gone), so no double counter decrement.
+                // `remove` consumes the guard, releasing the lock
+                // before the removed value is retired.
+                let order = occupied.remove().1;
+                self.drop_key(seq, order_id, Keys::of(&order));
+                evicted = Some(order);
+            }
+            FrontAction::KeepInPlace(residual) => {
+                // Partial fill keeping priority: swap the stored value
+                // to the residual in place, keeping the same
+                // sequence/index entry. Still under the entry lock.
+                let old = std::mem::replace(&mut occupied.get_mut().1, residual.clone());
+                self.rekey(seq, order_id, &old, residual);
+                evicted = Some(old);
+                drop(occupied);
+            }
+            FrontAction::ReplaceAtTail(refreshed) => {
+                // Replenished tranche loses time priority, but the
+                // maker keeps the SAME id and must stay resident in
+                // `orders` so a concurrent cancel cannot slip into a
+                // remove-then-push gap. So: mint a fresh tail sequence
+                // and swap BOTH the value and its stored sequence in
+                // place under the entry lock; only the index is
+                // re-keyed (old seq -> new seq) afterwards.
+                let new_seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+                let old = {
+                    let slot = occupied.get_mut();
+                    slot.0 = new_seq;
+                    std::mem::replace(&mut slot.1, refreshed.clone())
+                };
+                // `occupied` still holds the per-entry lock here, so
+                // re-keying the index — a different structure
+                // (`SkipMap`), no deadlock — happens while a concurrent
+                // cancel is still excluded from the entry. Insert the
+                // NEW key BEFORE removing the old (issue #127) so the
+                // id is never transiently absent from the index and a
+                // concurrent front scan can never miss it. Once the
+                // lock is released the value already carries `new_seq`,
+                // so a cancel removes `orders[id]` and `index[new_seq]`
+                // consistently. The only residue a race can leave is a
+                // stale `index[seq|new_seq] -> id` entry pointing at an
+                // already-removed id, which the next `match_front`
+                // self-heals on the `Vacant` branch. No order and no
+                // counter update is ever lost.
+                self.link(new_seq, order_id, Keys::of(refreshed));
+                self.drop_key(seq, order_id, Keys::of(&old));
+                evicted = Some(old);
+                drop(occupied);
+            }
+            FrontAction::SetAside => {
+                // No progress: leave the entry untouched. Release the
+                // lock; the caller parks its sequence.
+                drop(occupied);
+                park_seq = Some(seq);
+            }
+        }
+        (park_seq, evicted)
+    }
+
+    /// Match against one **specific** resting maker, identified by `order_id`,
+    /// rather than the FIFO front.
+    ///
+    /// The non-FIFO counterpart of [`OrderQueue::match_front`], used by the
+    /// allocation-plan sweeps of the pro-rata and size-priority matching
+    /// policies. `decide` receives the maker's insertion `seq` and the live
+    /// order under the per-entry lock, exactly as in `match_front`, and the
+    /// returned [`FrontAction`] is committed by the same code path, so the
+    /// cancel-atomicity and unlocked-drop guarantees documented there carry
+    /// over unchanged. A [`FrontAction::SetAside`] simply leaves the maker
+    /// untouched (a plan visits each maker once, so there is nothing to park).
+    ///
+    /// Returns `None` if the id no longer rests in the queue (a concurrent
+    /// cancel or full consume won the race); `decide` is then never called.
+    pub(crate) fn match_entry<F, R>(&self, order_id: Id, decide: F) -> Option<R>
+    where
+        F: FnOnce(u64, &OrderType<T>) -> (FrontAction<T>, R),
+    {
+        match self.orders.entry(order_id) {
+            Entry::Vacant(_) => None,
+            Entry::Occupied(occupied) => {
+                let seq = occupied.get().0;
+                let (action, result) = decide(seq, occupied.get().1.as_ref());
+                let (_, evicted) = self.commit_front_action(occupied, seq, &action);
+                self.retire(evicted);
+                Some(result)
+            }
+        }
+    }
+
     /// Atomically derive, decide, and commit an update against the **live**
     /// stored order for `order_id`, all inside the per-entry lock (issue #115).
     ///
@@ -488,8 +806,8 @@ impl OrderQueue {
     /// the lock was taken, so an update can never resurrect executed or
     /// cancelled quantity, and the priority policy can never be chosen from a
     /// stale total. This mirrors the [`OrderQueue::match_front`] decision-closure
-    /// pattern; the closure must not let a reference into the live order escape
-    /// its return value.
+    /// pattern; the closure may clone the live `Arc` out, but must not let a
+    /// reference into the live order escape its return value.
     ///
     /// Returns:
     /// - `None` if the id is not present (concurrently removed / never existed);
@@ -506,9 +824,9 @@ impl OrderQueue {
         &self,
         order_id: Id,
         decide: F,
-    ) -> Option<Result<Arc<OrderType<()>>, PriceLevelError>>
+    ) -> Option<Result<Arc<OrderType<T>>, PriceLevelError>>
     where
-        F: FnOnce(&OrderType<()>) -> Result<UpdateDecision, PriceLevelError>,
+        F: FnOnce(&Arc<OrderType<T>>) -> Result<UpdateDecision<T>, PriceLevelError>,
     {
         match self.orders.entry(order_id) {
             Entry::Vacant(_) => None,
@@ -516,7 +834,7 @@ impl OrderQueue {
                 // Derive + decide against the LIVE stored order under the lock.
                 // The borrow ends with the `decide` call (it returns owned data),
                 // so `get_mut()` below is free to commit.
-                let decision = match decide(occupied.get().1.as_ref()) {
+                let decision = match decide(&occupied.get().1) {
                     Ok(decision) => decision,
                     Err(err) => return Some(Err(err)),
                 };
@@ -534,8 +852,13 @@ impl OrderQueue {
                 // inside the shard's critical section.
                 let (committed, evicted) = match decision {
                     UpdateDecision::KeepInPlace(new_order) => {
+                        let seq = occupied.get().0;
                         let evicted =
                             std::mem::replace(&mut occupied.get_mut().1, new_order.clone());
+                        // A replacement of another kind (a stop, a hidden
+                        // order) moves its key to its own index at the same
+                        // sequence.
+                        self.rekey(seq, order_id, &evicted, &new_order);
                         (new_order, evicted)
                     }
                     UpdateDecision::ReplaceAtTail(new_order) => {
@@ -553,15 +876,19 @@ impl OrderQueue {
                         // concurrent front scan can never return `Empty` with
                         // liquidity resting. The transient two-key window is
                         // discarded on selection by the stale-front guard
-                        // (the stored sequence is already `new_seq`).
-                        self.index.insert(new_seq, order_id);
-                        self.index.remove(&old_seq);
+                        // (the stored sequence is already `new_seq`). In
+                        // tombstone mode the old key is left for the front
+                        // scan to reap the same way. An activated stop moves
+                        // from the stop index into the time-priority index
+                        // here.
+                        self.link(new_seq, order_id, Keys::of(&new_order));
+                        self.unlink(old_seq, order_id, Keys::of(&evicted));
                         (new_order, evicted)
                     }
                 };
                 // Release the shard lock, THEN drop the evicted order.
                 drop(occupied);
-                drop(evicted);
+                self.retire(Some(evicted));
                 Some(Ok(committed))
             }
         }
@@ -578,58 +905,193 @@ impl OrderQueue {
     /// the whole time (closing the lost-cancel window). This helper survives
     /// only as a queue-priority test fixture and is therefore `#[cfg(test)]`.
     #[cfg(test)]
-    pub(crate) fn reinsert(&self, seq: u64, order: Arc<OrderType<()>>) {
+    pub(crate) fn reinsert(&self, seq: u64, order: Arc<OrderType<T>>) {
         let order_id = order.id();
+        let keys = Keys::of(&order);
         self.orders.insert(order_id, (seq, order));
-        self.index.insert(seq, order_id);
+        self.link(seq, order_id, keys);
     }
 
     /// Search for an order with the given ID. O(1) operation.
     #[must_use]
     #[inline]
-    pub fn find(&self, order_id: Id) -> Option<Arc<OrderType<()>>> {
+    pub fn find(&self, order_id: Id) -> Option<Arc<OrderType<T>>> {
         self.orders.get(&order_id).map(|o| o.value().1.clone())
     }
 
     /// Remove an order with the given ID.
-    /// Returns the removed order if found. Cleans both the map and the index.
+    /// Returns the removed order if found. Cleans both the map and the index,
+    /// except in tombstone mode, where the index key is left for the front
+    /// scan to reap.
     #[must_use]
-    pub fn remove(&self, order_id: Id) -> Option<Arc<OrderType<()>>> {
+    pub fn remove(&self, order_id: Id) -> Option<Arc<OrderType<T>>> {
         let (_, (seq, order)) = self.orders.remove(&order_id)?;
-        self.index.remove(&seq);
+        self.unlink(seq, order_id, Keys::of(&order));
         Some(order)
     }
 
-    /// Test-only invariant check: the id-keyed map and the ordered index are
-    /// **1:1**. Must be called only at quiescence (no concurrent mutation), when
+    /// Walk the pending stop orders in insertion-sequence order, without
+    /// visiting the rest of the queue.
+    ///
+    /// Weakly consistent like [`OrderQueue::orders`]: a stop activated or
+    /// cancelled during the walk is skipped once its key is gone.
+    pub(crate) fn pending_stops(&self) -> impl Iterator<Item = Arc<OrderType<T>>> + '_ {
+        self.stops
+            .iter()
+            .filter_map(|entry| self.resolve(*entry.key(), *entry.value()))
+    }
+
+    /// The order `order_id` if it still rests at `seq`.
+    fn resolve(&self, seq: u64, order_id: Id) -> Option<Arc<OrderType<T>>> {
+        let slot = self.orders.get(&order_id)?;
+        let (stored_seq, order) = slot.value();
+        (*stored_seq == seq).then(|| order.clone())
+    }
+
+    /// Whether cancels and priority-losing amends leave tombstones in the
+    /// index (see [`OrderQueue::set_tombstone_mode`]).
+    #[must_use]
+    #[inline]
+    pub(crate) fn tombstone_mode(&self) -> bool {
+        self.tombstones.load(Ordering::Relaxed)
+    }
+
+    /// Switch tombstone mode on or off.
+    ///
+    /// In tombstone mode [`OrderQueue::remove`] and the re-sequencing commit
+    /// of [`OrderQueue::update_entry`] drop only the `orders` entry and leave
+    /// the old `seq -> id` index key in place. Every index key is then removed
+    /// only when a front scan ([`OrderQueue::match_front`] /
+    /// [`OrderQueue::pop_entry`]) reaches it — the same `Vacant` / stale-front
+    /// self-heal that already reaps a cancel racing a match — so the index
+    /// behaves as an append-only log consumed strictly from the front.
+    ///
+    /// Switching it off does not reap existing tombstones; call
+    /// [`OrderQueue::compact_tombstones`] for that. Left in place they stay
+    /// harmless, since every front scan skips them.
+    pub(crate) fn set_tombstone_mode(&self, enabled: bool) {
+        self.tombstones.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Number of index keys that no longer point at a resting order.
+    ///
+    /// Advisory: exact at quiescence, approximate while mutators run.
+    #[must_use]
+    pub(crate) fn tombstone_count(&self) -> usize {
+        (self.index.len() + self.hidden.len())
+            .saturating_sub(self.orders.len().saturating_sub(self.stops.len()))
+    }
+
+    /// Remove every tombstone from the index, returning how many were reaped.
+    ///
+    /// A key is a tombstone when its id is no longer in `orders` or is stored
+    /// under a different sequence. Sequences are never reused, so a key that
+    /// is a tombstone once stays one, and removing it can never unlink a live
+    /// order: a freshly admitted order's map entry is published before its
+    /// index key (both under the shard lock), so a live key always resolves.
+    pub(crate) fn compact_tombstones(&self) -> usize {
+        let mut reaped = 0;
+        for entry in self.index.entries().chain(self.hidden.entries()) {
+            let seq = *entry.key();
+            let live = self
+                .orders
+                .get(entry.value())
+                .is_some_and(|slot| slot.value().0 == seq);
+            if !live && entry.remove() {
+                reaped += 1;
+            }
+        }
+        reaped
+    }
+
+    /// Invariant check for tests and `PriceLevel::check_invariants`: the
+    /// id-keyed map and the ordered indexes are **1:1**. Must be called only at quiescence (no concurrent mutation), when
     /// every in-flight operation has completed.
     ///
     /// Verifies there are exactly as many index entries as map entries and that
     /// every index entry `seq -> id` points to a map entry whose stored sequence
-    /// is exactly `seq`. A split index entry (two sequences for one id — the
-    /// publication-race bug closed by [`OrderQueue::try_push_with`] holding the
-    /// shard lock across both publications) makes the index longer than the map,
-    /// so this returns `false`.
-    #[cfg(test)]
+    /// is exactly `seq`, each from the index of its kind: the stop index, the
+    /// hidden index or the time-priority index. A split index entry (two sequences
+    /// for one id — the publication-race bug closed by
+    /// [`OrderQueue::try_push_with`] holding the shard lock across both
+    /// publications) makes the index longer than the map, so this returns
+    /// `false`. In tombstone mode the index may legitimately be longer, so
+    /// only map -> index reachability is checked.
+    #[cfg(any(test, feature = "testing"))]
     #[must_use]
     pub(crate) fn debug_map_index_consistent(&self) -> bool {
-        if self.index.len() != self.orders.len() {
+        let reachable = |seq: u64, id: Id, order: &OrderType<T>| match Keys::of(order) {
+            Keys::Stop => self
+                .stops
+                .get(&seq)
+                .is_some_and(|entry| *entry.value() == id),
+            Keys::Hidden => self.hidden.contains(seq, id),
+            Keys::Displayed => self.index.contains(seq, id),
+        };
+        if self.tombstone_mode() {
+            // Tombstones are expected extra index keys; what must hold is that
+            // every resting order is reachable under exactly its stored key.
+            return self.orders.iter().all(|slot| {
+                let (seq, order) = slot.value();
+                reachable(*seq, *slot.key(), order)
+            });
+        }
+        if self.index.len() + self.hidden.len() + self.stops.len() != self.orders.len() {
             return false;
         }
-        self.index.iter().all(|entry| {
-            let seq = *entry.key();
-            let id = *entry.value();
+        let keyed = |seq: u64, id: Id, keys: Keys| {
             self.orders
                 .get(&id)
-                .is_some_and(|slot| slot.value().0 == seq)
-        })
+                .is_some_and(|slot| slot.value().0 == seq && Keys::of(&slot.value().1) == keys)
+        };
+        self.index
+            .iter()
+            .all(|(seq, id)| keyed(seq, id, Keys::Displayed))
+            && self
+                .hidden
+                .iter()
+                .all(|(seq, id)| keyed(seq, id, Keys::Hidden))
+            && self
+                .stops
+                .iter()
+                .all(|entry| keyed(*entry.key(), *entry.value(), Keys::Stop))
     }
 
     /// Iterate through current orders without materializing an intermediate vector.
-    pub fn iter_orders(&self) -> impl Iterator<Item = Arc<OrderType<()>>> + '_ {
+    ///
+    /// The order of iteration is the id map's, not time priority; use
+    /// [`OrderQueue::orders`] to walk the queue front to back.
+    pub fn iter_orders(&self) -> impl Iterator<Item = Arc<OrderType<T>>> + '_ {
         self.orders.iter().map(|entry| entry.value().1.clone())
     }
 
+    /// Walk the resting orders front to back — ascending insertion sequence,
+    /// the order the level's match sweep consumes them — without
+    /// allocating and without mutating the queue. Pending stops, which the
+    /// sweep does not consume, are merged in at their sequences.
+    ///
+    /// The walk follows the `index` skip list (merging the shards of a sharded
+    /// queue, the hidden index and the stop index by sequence), whose iteration is lock-free and epoch-protected: a concurrent push, pop or removal never blocks it
+    /// and never invalidates it. Each index key is resolved against the id map
+    /// and yielded only if the order still rests at that sequence, so
+    /// tombstones and keys of already-removed orders are skipped.
+    ///
+    /// The walk is weakly consistent: it reflects each order as it is when
+    /// reached, not one point in time. An order re-sequenced to the tail while
+    /// the walk is in progress (an iceberg refresh, a size increase) can be
+    /// yielded at both its old and its new position. Use
+    /// [`OrderQueue::snapshot_vec`] or the level's
+    /// `snapshot_by_insertion_seq` when a duplicate-free, point-in-time view is
+    /// required.
+    pub fn orders(&self) -> impl Iterator<Item = Arc<OrderType<T>>> + '_ {
+        let stops = self
+            .stops
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()));
+        merge_by_seq(merge_by_seq(self.index.iter(), self.hidden.iter()), stops)
+            .filter_map(|(seq, order_id)| self.resolve(seq, order_id))
+    }
+
     /// Materialize a stable snapshot vector sorted by `(timestamp, sequence)`.
     ///
     /// The insertion sequence is used as a deterministic tiebreak so orders
@@ -640,8 +1102,8 @@ impl OrderQueue {
     /// live queue order — including the "sizing up loses time priority"
     /// demotion — survives a restore.
     #[must_use]
-    pub fn snapshot_vec(&self) -> Vec<Arc<OrderType<()>>> {
-        let mut orders: Vec<(u64, Arc<OrderType<()>>)> =
+    pub fn snapshot_vec(&self) -> Vec<Arc<OrderType<T>>> {
+        let mut orders: Vec<(u64, Arc<OrderType<T>>)> =
             self.orders.iter().map(|o| o.value().clone()).collect();
         orders.sort_by_key(|(seq, o)| (o.timestamp(), *seq));
         orders.into_iter().map(|(_, o)| o).collect()
@@ -649,7 +1111,7 @@ impl OrderQueue {
 
     /// Convert the queue to a vector (for compatibility and snapshots).
     #[must_use]
-    pub fn to_vec(&self) -> Vec<Arc<OrderType<()>>> {
+    pub fn to_vec(&self) -> Vec<Arc<OrderType<T>>> {
         self.snapshot_vec()
     }
 
@@ -679,7 +1141,7 @@ impl OrderQueue {
     /// price-time priority — including the "sizing up loses time priority"
     /// demotion — is preserved across a restore.
     #[must_use]
-    pub(crate) fn snapshot_by_seq(&self) -> Vec<Arc<OrderType<()>>> {
+    pub(crate) fn snapshot_by_seq(&self) -> Vec<Arc<OrderType<T>>> {
         let mut out = Vec::new();
         self.snapshot_by_seq_into(&mut out);
         out
@@ -696,11 +1158,19 @@ impl OrderQueue {
     /// committed-pair guarantees are identical to
     /// [`OrderQueue::snapshot_by_seq`]; the only difference is where the result
     /// lands.
-    pub(crate) fn snapshot_by_seq_into(&self, out: &mut Vec<Arc<OrderType<()>>>) {
+    pub(crate) fn snapshot_by_seq_into(&self, out: &mut Vec<Arc<OrderType<T>>>) {
+        out.clear();
+        out.extend(self.snapshot_entries().into_iter().map(|(_, order)| order));
+    }
+
+    /// The resting orders with their insertion sequences, in ascending
+    /// sequence order: [`OrderQueue::snapshot_by_seq`] keeping the sequence
+    /// each order holds its priority by.
+    pub(crate) fn snapshot_entries(&self) -> Vec<(u64, Arc<OrderType<T>>)> {
         // Build from the `orders` map (one entry per id) so a concurrent
         // re-sequencing can never surface an order twice or at a mixed
         // priority; see `snapshot_by_seq` for the full rationale.
-        let mut pairs: Vec<(u64, Arc<OrderType<()>>)> = self
+        let mut pairs: Vec<(u64, Arc<OrderType<T>>)> = self
             .orders
             .iter()
             .map(|entry| entry.value().clone())
@@ -709,8 +1179,7 @@ impl OrderQueue {
         // across live orders (the tail-appending paths mint distinct seqs via
         // `fetch_add`; an in-place update keeps the order's own seq).
         pairs.sort_unstable_by_key(|(seq, _)| *seq);
-        out.clear();
-        out.extend(pairs.into_iter().map(|(_, order)| order));
+        pairs
     }
 
     /// Creates a new `OrderQueue` instance and populates it with orders from the provided vector.
@@ -742,7 +1211,7 @@ impl OrderQueue {
     /// upstream-validated restore.
     #[allow(dead_code)]
     #[must_use]
-    pub(crate) fn from_vec(orders: Vec<Arc<OrderType<()>>>) -> Self {
+    pub(crate) fn from_vec(orders: Vec<Arc<OrderType<T>>>) -> Self {
         let queue = OrderQueue::new();
         for order in orders {
             // Keep-first on a duplicate id: the index must stay 1:1.
@@ -772,13 +1241,13 @@ impl OrderQueue {
     }
 }
 
-impl Default for OrderQueue {
+impl<T: Clone> Default for OrderQueue<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 // Implement serialization for OrderQueue
-impl Serialize for OrderQueue {
+impl<T: Clone + Serialize> Serialize for OrderQueue<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -799,7 +1268,7 @@ impl Serialize for OrderQueue {
     }
 }
 
-impl FromStr for OrderQueue {
+impl<T: Clone + Default> FromStr for OrderQueue<T> {
     type Err = PriceLevelError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if !s.starts_with("OrderQueue:orders=[") || !s.ends_with(']') {
@@ -808,15 +1277,19 @@ impl FromStr for OrderQueue {
             });
         }
 
+        let options = crate::parse::ParseOptions::DEFAULT;
+        options.limits().check_input("order queue", s)?;
         let content = &s["OrderQueue:orders=[".len()..s.len() - 1];
         let queue = OrderQueue::new();
 
         if !content.is_empty() {
-            for order_str in content.split(',') {
-                let order =
-                    OrderType::from_str(order_str).map_err(|e| PriceLevelError::ParseError {
+            for (index, order_str) in content.split(',').enumerate() {
+                options.limits().check_orders("order queue", index + 1)?;
+                let order = crate::parse::order(order_str, &options).map_err(|e| {
+                    PriceLevelError::ParseError {
                         message: format!("Order parse error: {e}"),
-                    })?;
+                    }
+                })?;
                 // Reject a repeated id rather than overwriting it.
                 queue.try_push(Arc::new(order))?;
             }
@@ -826,7 +1299,7 @@ impl FromStr for OrderQueue {
     }
 }
 
-impl Display for OrderQueue {
+impl<T: Clone> Display for OrderQueue<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "OrderQueue:orders=[")?;
         let mut first = true;
@@ -841,11 +1314,11 @@ impl Display for OrderQueue {
     }
 }
 
-impl From<Vec<Arc<OrderType<()>>>> for OrderQueue {
+impl<T: Clone> From<Vec<Arc<OrderType<T>>>> for OrderQueue<T> {
     /// Infallible conversion: a repeated id is dropped (keep-first) so the map
     /// and index stay 1:1. Restore paths that must reject duplicates validate
     /// uniqueness upstream (see [`crate::price_level::PriceLevel::from_snapshot`]).
-    fn from(orders: Vec<Arc<OrderType<()>>>) -> Self {
+    fn from(orders: Vec<Arc<OrderType<T>>>) -> Self {
         let queue = OrderQueue::new();
         for order in orders {
             let _ = queue.try_push(order);
@@ -855,11 +1328,11 @@ impl From<Vec<Arc<OrderType<()>>>> for OrderQueue {
 }
 
 // Custom visitor for deserializing OrderQueue
-struct OrderQueueVisitor {
-    marker: PhantomData<fn() -> OrderQueue>,
+struct OrderQueueVisitor<T> {
+    marker: PhantomData<fn() -> OrderQueue<T>>,
 }
 
-impl OrderQueueVisitor {
+impl<T> OrderQueueVisitor<T> {
     fn new() -> Self {
         OrderQueueVisitor {
             marker: PhantomData,
@@ -867,14 +1340,14 @@ impl OrderQueueVisitor {
     }
 }
 
-impl<'de> Visitor<'de> for OrderQueueVisitor {
-    type Value = OrderQueue;
+impl<'de, T: Clone + Deserialize<'de>> Visitor<'de> for OrderQueueVisitor<T> {
+    type Value = OrderQueue<T>;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("a sequence of orders")
     }
 
-    fn visit_seq<V>(self, mut seq: V) -> Result<OrderQueue, V::Error>
+    fn visit_seq<V>(self, mut seq: V) -> Result<OrderQueue<T>, V::Error>
     where
         V: SeqAccess<'de>,
     {
@@ -882,7 +1355,7 @@ impl<'de> Visitor<'de> for OrderQueueVisitor {
 
         // Deserialize each order and add it to the queue, rejecting a repeated
         // id rather than silently overwriting it.
-        while let Some(order) = seq.next_element::<OrderType<()>>()? {
+        while let Some(order) = seq.next_element::<OrderType<T>>()? {
             queue
                 .try_push(Arc::new(order))
                 .map_err(serde::de::Error::custom)?;
@@ -893,7 +1366,7 @@ impl<'de> Visitor<'de> for OrderQueueVisitor {
 }
 
 // Implement deserialization for OrderQueue
-impl<'de> Deserialize<'de> for OrderQueue {
+impl<'de, T: Clone + Deserialize<'de>> Deserialize<'de> for OrderQueue<T> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
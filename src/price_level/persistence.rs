@@ -0,0 +1,152 @@
+use crate::errors::PriceLevelError;
+use crate::price_level::checksum::ChecksumAlgorithm;
+use crate::price_level::level::PriceLevel;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Distinguishes temp files of concurrent saves within one process.
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl<T: Clone> PriceLevel<T> {
+    /// Writes the level's checksum-protected snapshot package to `path` as
+    /// JSON, replacing any file there atomically.
+    ///
+    /// The package is written to a temp file beside `path`, flushed to disk
+    /// and renamed over `path`, so a reader or a crash mid-save sees the
+    /// previous file or the new one, never a torn mix. On Unix the directory
+    /// is synced too, so the rename itself survives a power loss. JSON is the
+    /// format because it is the one that restores a level in full; the rkyv
+    /// archive leaves statistics out.
+    ///
+    /// ```rust
+    /// use pricelevel::PriceLevel;
+    ///
+    /// let path = std::env::temp_dir().join(format!("level-{}.json", std::process::id()));
+    /// let level: PriceLevel = PriceLevel::new(10_000);
+    /// level.save_to_path(&path).unwrap();
+    /// let restored: PriceLevel = PriceLevel::load_from_path(&path).unwrap();
+    /// assert_eq!(restored.price(), 10_000);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::snapshot_package`],
+    /// [`PriceLevelError::InvalidOperation`] if `path` names no file, and
+    /// [`PriceLevelError::SerializationError`] if the file cannot be written
+    /// or renamed into place; `path` is left untouched then. A failed
+    /// directory sync after the rename is only logged, since `path` already
+    /// holds the new file.
+    pub fn save_to_path(&self, path: impl AsRef<Path>) -> Result<(), PriceLevelError>
+    where
+        T: Serialize,
+    {
+        let json = self.snapshot_package()?.to_json()?;
+        write_atomic(path.as_ref(), json.as_bytes())
+    }
+
+    /// [`Self::save_to_path`] with the package checksum computed by
+    /// `algorithm` instead of the default SHA-256.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Self::save_to_path`].
+    pub fn save_to_path_with(
+        &self,
+        path: impl AsRef<Path>,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<(), PriceLevelError>
+    where
+        T: Serialize,
+    {
+        let json = self.snapshot_package_with(algorithm)?.to_json()?;
+        write_atomic(path.as_ref(), json.as_bytes())
+    }
+
+    /// Restores a level from a file written by [`Self::save_to_path`],
+    /// verifying the package checksum before rebuilding anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::DeserializationError`] if the file cannot
+    /// be read or is not UTF-8, and otherwise the errors of
+    /// [`Self::from_snapshot_json`] — notably
+    /// [`PriceLevelError::ChecksumMismatch`] for a corrupted or tampered file.
+    pub fn load_from_path(path: impl AsRef<Path>) -> Result<Self, PriceLevelError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let path = path.as_ref();
+        let json = fs::read_to_string(path).map_err(|e| io_error(path, e, false))?;
+        Self::from_snapshot_json(&json)
+    }
+}
+
+/// Writes `bytes` to a temp file beside `path`, syncs it and renames it over
+/// `path`. The temp file is removed if any step fails.
+///
+/// Once the rename succeeds `path` holds the new file, so a failure to sync
+/// the directory after it is logged rather than returned: reporting an error
+/// would tell the caller the old file is still there.
+fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), PriceLevelError> {
+    let temp = temp_path(path)?;
+    let result = write_synced(&temp, bytes).and_then(|()| fs::rename(&temp, path));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp);
+        return Err(io_error(path, e, true));
+    }
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        if let Err(error) = File::open(dir).and_then(|dir| dir.sync_all()) {
+            tracing::warn!(
+                path = %path.display(),
+                %error,
+                "snapshot file renamed into place, but its directory could not be synced"
+            );
+        }
+    }
+    Ok(())
+}
+
+fn write_synced(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+/// A fresh temp path in `path`'s directory, so the rename stays on one
+/// filesystem.
+fn temp_path(path: &Path) -> Result<PathBuf, PriceLevelError> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| PriceLevelError::InvalidOperation {
+            message: format!("snapshot file {}: path names no file", path.display()),
+        })?;
+    let mut temp = name.to_os_string();
+    temp.push(format!(
+        ".tmp.{}.{}",
+        std::process::id(),
+        TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    Ok(path.with_file_name(temp))
+}
+
+fn io_error(path: &Path, e: std::io::Error, writing: bool) -> PriceLevelError {
+    let message = format!("snapshot file {}: {e}", path.display());
+    let source: Option<Arc<dyn std::error::Error + Send + Sync>> = Some(Arc::new(e));
+    if writing {
+        PriceLevelError::SerializationError { message, source }
+    } else {
+        PriceLevelError::DeserializationError { message, source }
+    }
+}
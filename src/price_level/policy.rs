@@ -0,0 +1,153 @@
+use crate::errors::PriceLevelError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How [`PriceLevel::match_order`](crate::PriceLevel::match_order) allocates
+/// an incoming quantity across the makers resting at a level.
+///
+/// The policy is set per level ([`PriceLevel::with_matching_policy`](crate::PriceLevel::with_matching_policy)
+/// / [`PriceLevel::set_matching_policy`](crate::PriceLevel::set_matching_policy))
+/// and read once at the start of every match.
+///
+/// - [`MatchingPolicy::Fifo`] — strict price-time priority: the oldest maker
+///   is filled first, in full, before the next one is touched. The default.
+/// - [`MatchingPolicy::ProRata`] — every maker receives a share proportional
+///   to its displayed quantity. Shares are rounded **down**; the units lost to
+///   rounding are then handed out one at a time in time priority to makers
+///   that still have room, so the allocation always sums to
+///   `min(incoming, displayed depth)`.
+/// - [`MatchingPolicy::SizeTimePriority`] — the largest displayed maker is
+///   filled first; equal sizes fall back to time priority.
+/// - [`MatchingPolicy::StrictFifo`] — FIFO with an execution-order guarantee:
+///   the trades a level emits are always in arrival (insertion-sequence)
+///   order, across sweeps and under concurrent mutators. See below.
+///
+/// Both non-FIFO policies allocate over **displayed** (visible) quantity. Any
+/// remainder left after the allocation — hidden depth that replenishes during
+/// the sweep, or a maker cancelled after the allocation was computed — is
+/// swept in time priority, so a fill-or-kill approved by
+/// [`PriceLevel::matchable_quantity`](crate::PriceLevel::matchable_quantity)
+/// still fills in full under every policy.
+///
+/// # Strict FIFO
+///
+/// Plain [`MatchingPolicy::Fifo`] is price-time priority on a best-effort
+/// basis: a sweep walks past a maker it cannot progress (a no-progress or
+/// same-id maker) and trades younger makers behind it, and a sweep runs
+/// concurrently with admissions, cancels and amends. `StrictFifo` removes every
+/// one of those reorderings:
+///
+/// - **Serialized sweeps.** Every sweep takes the level's exclusive guard (the
+///   one fill-or-kill already holds), so no admission, cancel or amend
+///   interleaves with it and concurrent takers execute one after another.
+/// - **Stop, never skip.** A front maker the sweep cannot progress ends the
+///   sweep instead of being passed over. Pending stop orders never reach the
+///   sweep: they rest outside the queue until activation sequences them at
+///   the tail.
+/// - **Sequence-validated pops.** The level records the highest insertion
+///   sequence it has executed. A front maker below that mark would trade out
+///   of arrival order, so the sweep refuses it, stops, and counts the
+///   violation in [`PriceLevel::fifo_violations`](crate::PriceLevel::fifo_violations)
+///   (always `0` unless an invariant is broken).
+/// - **Tombstoned structural operations.** A cancel, or an amend that loses
+///   priority, leaves its old queue key behind as a tombstone instead of
+///   unlinking it mid-queue. Keys are removed only as the sweep reaches them
+///   at the front, so the queue is an append-only log consumed from the front.
+///   [`PriceLevel::compact_tombstones`](crate::PriceLevel::compact_tombstones)
+///   reaps them on demand.
+///
+/// The cost is throughput. Sweeps no longer overlap with other mutators, and
+/// a level with heavy cancel traffic and little matching holds its
+/// tombstones, which every front scan must step over, until they are reached
+/// or compacted.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchingPolicy {
+    /// Strict first-in, first-out (price-time priority).
+    #[default]
+    Fifo = 0,
+    /// Pro-rata allocation by displayed quantity, remainder by time priority.
+    ProRata = 1,
+    /// Largest displayed quantity first, ties by time priority.
+    SizeTimePriority = 2,
+    /// FIFO with serialized, sequence-validated sweeps and tombstoned cancels.
+    StrictFifo = 3,
+}
+
+impl MatchingPolicy {
+    /// Decodes the `#[repr(u8)]` discriminant stored in the level's atomic.
+    /// Unknown values fall back to [`MatchingPolicy::Fifo`].
+    #[must_use]
+    pub(crate) fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::ProRata,
+            2 => Self::SizeTimePriority,
+            3 => Self::StrictFifo,
+            _ => Self::Fifo,
+        }
+    }
+
+    /// Returns `true` if the policy matches in time priority, i.e. needs no
+    /// allocation plan.
+    #[must_use]
+    pub fn is_fifo(self) -> bool {
+        matches!(self, Self::Fifo | Self::StrictFifo)
+    }
+}
+
+impl fmt::Display for MatchingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fifo => write!(f, "FIFO"),
+            Self::ProRata => write!(f, "PRO_RATA"),
+            Self::SizeTimePriority => write!(f, "SIZE_TIME_PRIORITY"),
+            Self::StrictFifo => write!(f, "STRICT_FIFO"),
+        }
+    }
+}
+
+impl FromStr for MatchingPolicy {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "FIFO" => Ok(Self::Fifo),
+            "PRO_RATA" | "PRORATA" => Ok(Self::ProRata),
+            "SIZE_TIME_PRIORITY" | "SIZETIMEPRIORITY" => Ok(Self::SizeTimePriority),
+            "STRICT_FIFO" | "STRICTFIFO" => Ok(Self::StrictFifo),
+            _ => Err(PriceLevelError::ParseError {
+                message: format!("Failed to parse MatchingPolicy: {s}"),
+            }),
+        }
+    }
+}
+
+/// Where hidden orders ([`OrderType::Hidden`](crate::OrderType::Hidden))
+/// stand in a level's queue when a taker sweeps it.
+///
+/// Set per level with
+/// [`PriceLevelConfig::with_hidden_priority`](crate::PriceLevelConfig::with_hidden_priority).
+/// Under [`MatchingPolicy::StrictFifo`] hidden orders always keep their time
+/// priority, since its sweep only moves forward through the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HiddenPriority {
+    /// Hidden orders trade only once the displayed liquidity at the level is
+    /// exhausted, among themselves in time priority.
+    #[default]
+    DisplayedFirst,
+    /// Hidden orders keep their place in time priority alongside displayed
+    /// ones.
+    TimePriority,
+}
+
+impl fmt::Display for HiddenPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DisplayedFirst => write!(f, "DISPLAYED_FIRST"),
+            Self::TimePriority => write!(f, "TIME_PRIORITY"),
+        }
+    }
+}
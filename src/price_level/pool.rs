@@ -0,0 +1,176 @@
+use crate::execution::{MatchResult, Trade};
+use crate::orders::{Id, OrderType};
+use crossbeam_channel::{Receiver, Sender};
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Spare order allocations and match-result buffers an [`OrderPool`] keeps
+/// by default, each.
+pub const DEFAULT_ORDER_POOL_CAPACITY: usize = 1024;
+
+/// Largest element capacity a recycled match-result buffer keeps, so one
+/// sweep over a very deep level does not pin its peak buffers in the pool.
+const MAX_RETAINED_BUFFER_CAPACITY: usize = 4096;
+
+/// The trade and filled-id vectors of a recycled [`MatchResult`].
+type ResultBuffers<T> = (Vec<Trade<T>>, Vec<Id>);
+
+/// A bounded pool of order allocations and match-result buffers.
+///
+/// A level built with
+/// [`PriceLevel::with_order_pool`](crate::PriceLevel::with_order_pool)
+/// draws the `Arc` of every admitted order and of every partial-fill residual
+/// from the pool, and hands back each order it evicts or fully consumes. Only
+/// an allocation nothing else still references is kept, so an order a
+/// snapshot or a caller holds is simply dropped. Trade and filled-id vectors
+/// come back through [`Self::recycle`] once the caller is done with a
+/// [`MatchResult`].
+///
+/// Both free lists are lock-free bounded channels: recycling into a full pool
+/// drops the allocation, and acquiring from an empty one allocates. One pool
+/// may be shared by many levels.
+pub struct OrderPool<T = ()> {
+    orders: FreeList<Arc<OrderType<T>>>,
+    buffers: FreeList<ResultBuffers<T>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<T> OrderPool<T> {
+    /// Create a pool keeping up to `capacity` spare order allocations and
+    /// `capacity` match-result buffers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "order pool capacity must be positive");
+        Self {
+            orders: FreeList::new(capacity),
+            buffers: FreeList::new(capacity),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Move `order` into a pooled allocation, or a fresh one if the pool is
+    /// empty.
+    pub fn acquire(&self, order: OrderType<T>) -> Arc<OrderType<T>> {
+        while let Some(mut spare) = self.orders.take() {
+            // Only unshared allocations are pooled, so this always succeeds;
+            // a spare that fails it is dropped rather than trusted.
+            if let Some(slot) = Arc::get_mut(&mut spare) {
+                *slot = order;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return spare;
+            }
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        Arc::new(order)
+    }
+
+    /// Offer `order`'s allocation back to the pool. Returns `true` if it was
+    /// kept; an allocation still referenced elsewhere, or one arriving at a
+    /// full pool, is dropped.
+    pub fn release(&self, mut order: Arc<OrderType<T>>) -> bool {
+        if Arc::get_mut(&mut order).is_none() {
+            return false;
+        }
+        self.orders.put(order)
+    }
+
+    /// Keep the trade and filled-id vectors of a consumed `result` for the
+    /// next match against a pooled level. Returns `true` if they were kept.
+    pub fn recycle(&self, result: MatchResult<T>) -> bool {
+        let (mut trades, mut filled) = result.into_buffers();
+        trades.clear();
+        filled.clear();
+        trades.shrink_to(MAX_RETAINED_BUFFER_CAPACITY);
+        filled.shrink_to(MAX_RETAINED_BUFFER_CAPACITY);
+        self.buffers.put((trades, filled))
+    }
+
+    /// Empty trade and filled-id vectors, reused from the pool when it has a
+    /// pair; the filled-id vector has room for `capacity` entries. The trade
+    /// vector is left as is: the match result reserves its trades itself,
+    /// inline when `capacity` is small.
+    pub(crate) fn buffers(&self, capacity: usize) -> ResultBuffers<T> {
+        match self.buffers.take() {
+            Some((trades, mut filled)) => {
+                filled.reserve(capacity);
+                (trades, filled)
+            }
+            None => (Vec::new(), Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Lifetime number of acquisitions served from a pooled allocation.
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Lifetime number of acquisitions that had to allocate.
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Spare order allocations currently held.
+    #[must_use]
+    pub fn retained_orders(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Match-result buffer pairs currently held.
+    #[must_use]
+    pub fn retained_buffers(&self) -> usize {
+        self.buffers.len()
+    }
+}
+
+impl<T> Default for OrderPool<T> {
+    // Not derived: a derive would demand `T: Default`.
+    fn default() -> Self {
+        Self::new(DEFAULT_ORDER_POOL_CAPACITY)
+    }
+}
+
+impl<T> fmt::Debug for OrderPool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderPool")
+            .field("retained_orders", &self.retained_orders())
+            .field("retained_buffers", &self.retained_buffers())
+            .field("hits", &self.hits())
+            .field("misses", &self.misses())
+            .finish()
+    }
+}
+
+/// A bounded lock-free free list.
+struct FreeList<V> {
+    sender: Sender<V>,
+    receiver: Receiver<V>,
+}
+
+impl<V> FreeList<V> {
+    fn new(capacity: usize) -> Self {
+        let (sender, receiver) = crossbeam_channel::bounded(capacity);
+        Self { sender, receiver }
+    }
+
+    /// Keep `value` unless the list is full.
+    fn put(&self, value: V) -> bool {
+        self.sender.try_send(value).is_ok()
+    }
+
+    fn take(&self) -> Option<V> {
+        self.receiver.try_recv().ok()
+    }
+
+    fn len(&self) -> usize {
+        self.receiver.len()
+    }
+}
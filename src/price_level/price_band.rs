@@ -0,0 +1,321 @@
+use crate::errors::PriceLevelError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering, fence};
+use std::sync::{Arc, RwLock};
+
+/// How far a [`PriceBand`] reaches either side of its reference price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BandWidth {
+    /// A share of the reference price in basis points: 100 is 1%.
+    BasisPoints(u32),
+    /// A fixed distance in price units.
+    Absolute(u128),
+}
+
+/// What a level does with a taker whose match would print outside its band.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BandAction {
+    /// Refuse the taker, as [`PriceLevelError::PriceOutsideBand`].
+    #[default]
+    Reject,
+    /// Let it trade, counting the breach in
+    /// [`PriceLevel::band_breaches`](crate::PriceLevel::band_breaches).
+    Flag,
+}
+
+/// A price collar around a reference price, checked before every match at
+/// the levels it is set on (see
+/// [`PriceLevel::set_price_band`](crate::PriceLevel::set_price_band)): a
+/// level outside the band refuses or flags its takers.
+///
+/// The reference, width and action are atomics, so a market-data thread can
+/// move the reference as the market trades while matches read it. Readers
+/// always see the three from one update, never a mix. Share one band across
+/// the levels of a book with an [`Arc`].
+///
+/// ```rust
+/// use pricelevel::{BandAction, BandWidth, PriceBand};
+///
+/// let band = PriceBand::new(10_000, BandWidth::BasisPoints(500), BandAction::Reject);
+/// assert_eq!(band.bounds(), (9_500, 10_500));
+/// assert!(!band.contains(10_600));
+///
+/// band.set_reference(10_200);
+/// assert!(band.contains(10_600));
+/// ```
+pub struct PriceBand {
+    /// Odd while a setter writes; a reader retries if it moved.
+    version: AtomicU64,
+    reference: [AtomicU64; 2],
+    width: [AtomicU64; 2],
+    /// [`ABSOLUTE`] and [`FLAG`] bits.
+    mode: AtomicU8,
+}
+
+const ABSOLUTE: u8 = 1;
+const FLAG: u8 = 1 << 1;
+
+/// One consistent reading of a [`PriceBand`].
+#[derive(Debug, Clone, Copy)]
+struct BandState {
+    reference: u128,
+    width: BandWidth,
+    action: BandAction,
+}
+
+impl BandState {
+    fn bounds(self) -> (u128, u128) {
+        let distance = match self.width {
+            BandWidth::Absolute(distance) => distance,
+            BandWidth::BasisPoints(bps) => {
+                let bps = u128::from(bps);
+                // Split so the product cannot overflow for any reference.
+                (self.reference / 10_000)
+                    .saturating_mul(bps)
+                    .saturating_add(self.reference % 10_000 * bps / 10_000)
+            }
+        };
+        (
+            self.reference.saturating_sub(distance),
+            self.reference.saturating_add(distance),
+        )
+    }
+}
+
+fn split(value: u128) -> (u64, u64) {
+    ((value >> 64) as u64, value as u64)
+}
+
+fn join(pair: &[AtomicU64; 2]) -> u128 {
+    (u128::from(pair[0].load(Ordering::Relaxed)) << 64)
+        | u128::from(pair[1].load(Ordering::Relaxed))
+}
+
+impl PriceBand {
+    /// A band of `width` either side of `reference`, breached as `action`
+    /// says.
+    #[must_use]
+    pub fn new(reference: u128, width: BandWidth, action: BandAction) -> Self {
+        let band = Self {
+            version: AtomicU64::new(0),
+            reference: [AtomicU64::new(0), AtomicU64::new(0)],
+            width: [AtomicU64::new(0), AtomicU64::new(0)],
+            mode: AtomicU8::new(0),
+        };
+        band.write(BandState {
+            reference,
+            width,
+            action,
+        });
+        band
+    }
+
+    /// The reference price.
+    #[must_use]
+    pub fn reference(&self) -> u128 {
+        self.load().reference
+    }
+
+    /// The band's width.
+    #[must_use]
+    pub fn width(&self) -> BandWidth {
+        self.load().width
+    }
+
+    /// What a breach does.
+    #[must_use]
+    pub fn action(&self) -> BandAction {
+        self.load().action
+    }
+
+    /// The lowest and highest prices inside the band, inclusive, saturating
+    /// at the ends of `u128`.
+    #[must_use]
+    pub fn bounds(&self) -> (u128, u128) {
+        self.load().bounds()
+    }
+
+    /// Whether `price` is inside the band.
+    #[must_use]
+    pub fn contains(&self, price: u128) -> bool {
+        let (lower, upper) = self.bounds();
+        (lower..=upper).contains(&price)
+    }
+
+    /// Move the band to `reference`, keeping its width and action.
+    pub fn set_reference(&self, reference: u128) {
+        self.update(|state| state.reference = reference);
+    }
+
+    /// Change the band's width.
+    pub fn set_width(&self, width: BandWidth) {
+        self.update(|state| state.width = width);
+    }
+
+    /// Change what a breach does.
+    pub fn set_action(&self, action: BandAction) {
+        self.update(|state| state.action = action);
+    }
+
+    /// Read the three settings from one update.
+    fn load(&self) -> BandState {
+        loop {
+            let version = self.version.load(Ordering::Acquire);
+            if version & 1 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+            let state = self.read();
+            fence(Ordering::Acquire);
+            if self.version.load(Ordering::Relaxed) == version {
+                return state;
+            }
+        }
+    }
+
+    /// Apply `change` to the current settings, one setter at a time.
+    fn update(&self, change: impl FnOnce(&mut BandState)) {
+        let mut version = self.version.load(Ordering::Relaxed);
+        loop {
+            if version & 1 == 1 {
+                std::hint::spin_loop();
+                version = self.version.load(Ordering::Relaxed);
+                continue;
+            }
+            match self.version.compare_exchange_weak(
+                version,
+                version + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => version = current,
+            }
+        }
+        fence(Ordering::Release);
+        let mut state = self.read();
+        change(&mut state);
+        self.write(state);
+        self.version.store(version + 2, Ordering::Release);
+    }
+
+    /// The settings as stored, torn if a setter is writing.
+    fn read(&self) -> BandState {
+        let width = join(&self.width);
+        let mode = self.mode.load(Ordering::Relaxed);
+        BandState {
+            reference: join(&self.reference),
+            width: if mode & ABSOLUTE != 0 {
+                BandWidth::Absolute(width)
+            } else {
+                // Only ever stored from a `u32`.
+                BandWidth::BasisPoints(width as u32)
+            },
+            action: if mode & FLAG != 0 {
+                BandAction::Flag
+            } else {
+                BandAction::Reject
+            },
+        }
+    }
+
+    fn write(&self, state: BandState) {
+        let (hi, lo) = split(state.reference);
+        self.reference[0].store(hi, Ordering::Relaxed);
+        self.reference[1].store(lo, Ordering::Relaxed);
+        let (width, mut mode) = match state.width {
+            BandWidth::BasisPoints(bps) => (u128::from(bps), 0),
+            BandWidth::Absolute(distance) => (distance, ABSOLUTE),
+        };
+        let (hi, lo) = split(width);
+        self.width[0].store(hi, Ordering::Relaxed);
+        self.width[1].store(lo, Ordering::Relaxed);
+        if state.action == BandAction::Flag {
+            mode |= FLAG;
+        }
+        self.mode.store(mode, Ordering::Relaxed);
+    }
+}
+
+impl fmt::Debug for PriceBand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.load();
+        f.debug_struct("PriceBand")
+            .field("reference", &state.reference)
+            .field("width", &state.width)
+            .field("action", &state.action)
+            .finish()
+    }
+}
+
+/// The price band registered with a level. Runtime configuration, like the
+/// rate limiter: never cloned or serialized.
+pub(crate) struct PriceBandHook {
+    band: RwLock<Option<Arc<PriceBand>>>,
+    /// `true` while a band is registered, so unbanded levels skip the lock.
+    any: AtomicBool,
+}
+
+impl Default for PriceBandHook {
+    fn default() -> Self {
+        Self {
+            band: RwLock::new(None),
+            any: AtomicBool::new(false),
+        }
+    }
+}
+
+impl fmt::Debug for PriceBandHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriceBandHook")
+            .field("band", &self.get())
+            .finish()
+    }
+}
+
+impl PriceBandHook {
+    pub(crate) fn set(&self, band: Arc<PriceBand>) {
+        *self.band.write().unwrap_or_else(|e| e.into_inner()) = Some(band);
+        self.any.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn clear(&self) {
+        *self.band.write().unwrap_or_else(|e| e.into_inner()) = None;
+        self.any.store(false, Ordering::Release);
+    }
+
+    pub(crate) fn get(&self) -> Option<Arc<PriceBand>> {
+        if !self.any.load(Ordering::Acquire) {
+            return None;
+        }
+        self.band.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Check a match at `price` against the registered band: `Ok(false)`
+    /// inside it or with none, `Ok(true)` for a breach to flag.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::PriceOutsideBand`] for a breach to reject.
+    pub(crate) fn check(&self, price: u128) -> Result<bool, PriceLevelError> {
+        let Some(band) = self.get() else {
+            return Ok(false);
+        };
+        let state = band.load();
+        let (lower, upper) = state.bounds();
+        if (lower..=upper).contains(&price) {
+            return Ok(false);
+        }
+        match state.action {
+            BandAction::Flag => Ok(true),
+            BandAction::Reject => Err(PriceLevelError::PriceOutsideBand {
+                price,
+                lower,
+                upper,
+            }),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::orders::Hash32;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Assigns each participant the priority tier its orders trade in, for
+/// venues that give some participant classes (designated market makers,
+/// member brokers) priority at the same price.
+///
+/// Set per level with
+/// [`PriceLevelConfig::with_priority_tiers`](crate::PriceLevelConfig::with_priority_tiers).
+/// A taker then sweeps the level tier by tier: every tier-0 maker trades
+/// before any tier-1 maker, and so on, each tier in time priority. Hidden
+/// orders trade after the displayed ones of their own tier under
+/// [`HiddenPriority::DisplayedFirst`](crate::HiddenPriority::DisplayedFirst).
+///
+/// Tiers order the FIFO sweep. A pro-rata or size-time allocation ignores
+/// them, as [`MatchingPolicy::StrictFifo`](crate::MatchingPolicy::StrictFifo)
+/// does, since its sweep may not trade an older maker after a younger one.
+/// The classifier is consulted under the maker's entry lock during the
+/// sweep, so it must not block or call back into the level.
+pub trait PriorityTiers: fmt::Debug + Send + Sync {
+    /// The tier of orders from participant `user_id`; 0 trades first.
+    fn tier(&self, user_id: Hash32) -> u8;
+}
+
+/// A [`PriorityTiers`] table: listed participants get their tier, everyone
+/// else the default.
+///
+/// ```rust
+/// use pricelevel::{Hash32, PriorityTiers, TierTable};
+///
+/// let market_maker = Hash32::new([7; 32]);
+/// let tiers = TierTable::new(1).with_tier(market_maker, 0);
+/// assert_eq!(tiers.tier(market_maker), 0);
+/// assert_eq!(tiers.tier(Hash32::zero()), 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TierTable {
+    tiers: HashMap<Hash32, u8>,
+    default_tier: u8,
+}
+
+impl TierTable {
+    /// A table placing every participant in `default_tier`.
+    #[must_use]
+    pub fn new(default_tier: u8) -> Self {
+        Self {
+            tiers: HashMap::new(),
+            default_tier,
+        }
+    }
+
+    /// The same table, placing `user_id` in `tier`.
+    #[must_use]
+    pub fn with_tier(mut self, user_id: Hash32, tier: u8) -> Self {
+        self.tiers.insert(user_id, tier);
+        self
+    }
+
+    /// The tier of participants the table does not list.
+    #[must_use]
+    pub fn default_tier(&self) -> u8 {
+        self.default_tier
+    }
+}
+
+impl PriorityTiers for TierTable {
+    fn tier(&self, user_id: Hash32) -> u8 {
+        self.tiers
+            .get(&user_id)
+            .copied()
+            .unwrap_or(self.default_tier)
+    }
+}
@@ -0,0 +1,295 @@
+//! Prometheus text exposition (format 0.0.4) for level statistics.
+//!
+//! Rendering is plain string building with no client library, so enabling the
+//! `metrics` feature adds no dependency. Every function returns a complete
+//! exposition body that can be served as-is from a `/metrics` endpoint.
+
+use crate::price_level::level::PriceLevel;
+use crate::price_level::statistics::PriceLevelStatistics;
+use std::fmt::Write;
+
+/// Prefix of every exported metric name.
+const PREFIX: &str = "pricelevel";
+
+/// Metric families emitted per level: `(name, type, help)`.
+const STATISTICS_FAMILIES: [(&str, &str, &str); 9] = [
+    (
+        "orders_added_total",
+        "counter",
+        "Orders added to the level.",
+    ),
+    (
+        "orders_removed_total",
+        "counter",
+        "Orders removed from the level without execution.",
+    ),
+    (
+        "orders_executed_total",
+        "counter",
+        "Maker executions recorded at the level.",
+    ),
+    (
+        "quantity_executed_total",
+        "counter",
+        "Quantity executed at the level.",
+    ),
+    (
+        "value_executed_total",
+        "counter",
+        "Value (quantity * price) executed at the level.",
+    ),
+    (
+        "waiting_time_milliseconds_total",
+        "counter",
+        "Accumulated maker waiting time before execution, in milliseconds.",
+    ),
+    (
+        "last_execution_timestamp_milliseconds",
+        "gauge",
+        "Unix time of the most recent execution, in milliseconds (0 if none).",
+    ),
+    (
+        "statistics_start_timestamp_milliseconds",
+        "gauge",
+        "Unix time statistics tracking started or was last reset, in milliseconds.",
+    ),
+    (
+        "statistics_degraded",
+        "gauge",
+        "1 if at least one execution was dropped from the statistics, else 0.",
+    ),
+];
+
+/// Depth gauges emitted per level: `(name, help)`.
+const DEPTH_FAMILIES: [(&str, &str); 3] = [
+    (
+        "visible_quantity",
+        "Displayed quantity resting at the level.",
+    ),
+    ("hidden_quantity", "Hidden quantity resting at the level."),
+    ("order_count", "Orders resting at the level."),
+];
+
+/// Book-wide aggregates emitted by [`prometheus_text`]: `(name, type, help)`.
+const BOOK_FAMILIES: [(&str, &str, &str); 7] = [
+    ("book_levels", "gauge", "Price levels in the book."),
+    (
+        "book_visible_quantity",
+        "gauge",
+        "Displayed quantity resting across all levels.",
+    ),
+    (
+        "book_hidden_quantity",
+        "gauge",
+        "Hidden quantity resting across all levels.",
+    ),
+    (
+        "book_order_count",
+        "gauge",
+        "Orders resting across all levels.",
+    ),
+    (
+        "book_orders_executed_total",
+        "counter",
+        "Maker executions recorded across all levels.",
+    ),
+    (
+        "book_quantity_executed_total",
+        "counter",
+        "Quantity executed across all levels.",
+    ),
+    (
+        "book_value_executed_total",
+        "counter",
+        "Value executed across all levels.",
+    ),
+];
+
+impl PriceLevelStatistics {
+    /// Renders the statistics in the Prometheus text exposition format.
+    ///
+    /// Lifetime aggregates are exported as `pricelevel_*_total` counters and
+    /// point-in-time values as gauges; every sample carries `labels`. The
+    /// values come from one consistent read (the same seqlock-protected view
+    /// as `Clone`), so a scrape never mixes a pre- and post-execution state.
+    ///
+    /// Label names are sanitized to `[a-zA-Z_][a-zA-Z0-9_]*` (invalid
+    /// characters become `_`) and label values are escaped, so arbitrary
+    /// input always yields a parseable exposition.
+    #[must_use]
+    pub fn to_prometheus(&self, labels: &[(&str, &str)]) -> String {
+        let mut out = String::new();
+        let values = statistics_values(&self.clone());
+        let labels = render_labels(labels, None);
+        for ((name, kind, help), value) in STATISTICS_FAMILIES.iter().zip(values) {
+            write_header(&mut out, name, kind, help);
+            write_sample(&mut out, name, &labels, value);
+        }
+        out
+    }
+}
+
+impl<T: Clone> PriceLevel<T> {
+    /// Renders this level's statistics and depth in the Prometheus text
+    /// exposition format.
+    ///
+    /// Emits the `PriceLevelStatistics` families plus
+    /// `pricelevel_visible_quantity`, `pricelevel_hidden_quantity` and
+    /// `pricelevel_order_count` gauges. Every sample carries `labels` and a
+    /// `price` label with the level's price; a caller-supplied `price` label
+    /// is dropped. Depth gauges are the level's advisory counters.
+    #[must_use]
+    pub fn to_prometheus(&self, labels: &[(&str, &str)]) -> String {
+        prometheus_text(std::iter::once(self), labels)
+    }
+}
+
+/// Renders many levels — typically one side or the whole of a book — as a
+/// single Prometheus exposition.
+///
+/// Each family appears once with one sample per level, distinguished by the
+/// `price` label, followed by `pricelevel_book_*` aggregates summed over all
+/// the levels (carrying only `labels`). A caller-supplied `price` label is
+/// dropped, as that name is reserved for the level price. Aggregated counters
+/// saturate at `u64::MAX` rather than wrap.
+#[must_use]
+pub fn prometheus_text<'a, T, I>(levels: I, labels: &[(&str, &str)]) -> String
+where
+    T: Clone + 'a,
+    I: IntoIterator<Item = &'a PriceLevel<T>>,
+{
+    struct Row {
+        labels: String,
+        statistics: [u64; STATISTICS_FAMILIES.len()],
+        depth: [u64; DEPTH_FAMILIES.len()],
+    }
+
+    // `price` is reserved for the per-level label, so a caller-supplied one
+    // is dropped everywhere, including from the book aggregates.
+    let labels: Vec<(&str, &str)> = labels
+        .iter()
+        .filter(|(name, _)| sanitize_label_name(name) != "price")
+        .copied()
+        .collect();
+    let rows: Vec<Row> = levels
+        .into_iter()
+        .map(|level| Row {
+            labels: render_labels(&labels, Some(level.price())),
+            statistics: statistics_values(&level.stats().as_ref().clone()),
+            depth: [
+                level.visible_quantity(),
+                level.hidden_quantity(),
+                level.order_count() as u64,
+            ],
+        })
+        .collect();
+
+    let mut out = String::new();
+    for (index, (name, kind, help)) in STATISTICS_FAMILIES.iter().enumerate() {
+        write_header(&mut out, name, kind, help);
+        for row in &rows {
+            write_sample(&mut out, name, &row.labels, row.statistics[index]);
+        }
+    }
+    for (index, (name, help)) in DEPTH_FAMILIES.iter().enumerate() {
+        write_header(&mut out, name, "gauge", help);
+        for row in &rows {
+            write_sample(&mut out, name, &row.labels, row.depth[index]);
+        }
+    }
+
+    let sum = |value: fn(&Row) -> u64| {
+        rows.iter()
+            .fold(0_u64, |total, row| total.saturating_add(value(row)))
+    };
+    let book = [
+        rows.len() as u64,
+        sum(|row| row.depth[0]),
+        sum(|row| row.depth[1]),
+        sum(|row| row.depth[2]),
+        sum(|row| row.statistics[2]),
+        sum(|row| row.statistics[3]),
+        sum(|row| row.statistics[4]),
+    ];
+    let book_labels = render_labels(&labels, None);
+    for ((name, kind, help), value) in BOOK_FAMILIES.iter().zip(book) {
+        write_header(&mut out, name, kind, help);
+        write_sample(&mut out, name, &book_labels, value);
+    }
+    out
+}
+
+/// Values of [`STATISTICS_FAMILIES`], in order, from a consistent copy.
+fn statistics_values(stats: &PriceLevelStatistics) -> [u64; STATISTICS_FAMILIES.len()] {
+    [
+        stats.orders_added() as u64,
+        stats.orders_removed() as u64,
+        stats.orders_executed() as u64,
+        stats.quantity_executed(),
+        stats.value_executed(),
+        stats.sum_waiting_time(),
+        stats.last_execution_time(),
+        stats.first_arrival_time(),
+        u64::from(stats.stats_degraded()),
+    ]
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    // Writing to a `String` cannot fail.
+    let _ = writeln!(out, "# HELP {PREFIX}_{name} {help}");
+    let _ = writeln!(out, "# TYPE {PREFIX}_{name} {kind}");
+}
+
+fn write_sample(out: &mut String, name: &str, labels: &str, value: u64) {
+    let _ = writeln!(out, "{PREFIX}_{name}{labels} {value}");
+}
+
+/// Renders `{name="value",...}` (or nothing when there are no labels), with
+/// an optional trailing `price` label.
+fn render_labels(labels: &[(&str, &str)], price: Option<u128>) -> String {
+    let mut pairs: Vec<(String, String)> = labels
+        .iter()
+        .map(|(name, value)| (sanitize_label_name(name), escape_label_value(value)))
+        .collect();
+    if let Some(price) = price {
+        pairs.push(("price".to_string(), price.to_string()));
+    }
+    if pairs.is_empty() {
+        return String::new();
+    }
+    let body: Vec<String> = pairs
+        .into_iter()
+        .map(|(name, value)| format!("{name}=\"{value}\""))
+        .collect();
+    format!("{{{}}}", body.join(","))
+}
+
+fn sanitize_label_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.is_empty() || sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn escape_label_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,88 @@
+use crate::errors::PriceLevelError;
+use crate::orders::{Id, OrderType};
+use crate::utils::TimestampMs;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Why [`PriceLevel::match_order`](crate::PriceLevel::match_order) moved a
+/// resting order to the level's dead-letter list instead of trading it.
+///
+/// None of these shapes can be admitted by
+/// [`PriceLevel::add_order`](crate::PriceLevel::add_order) with a meaningful
+/// fill, but they can reach the queue through a corrupted snapshot or a
+/// hand-built one. Matching them would either spin without progress, emit a
+/// zero-quantity trade, or silently drop hidden depth from the counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuarantineReason {
+    /// Visible and hidden quantity are both zero: there is nothing to trade.
+    EmptyOrder,
+    /// The order's own visible + hidden total overflows `u64`.
+    QuantityOverflow,
+    /// A reserve order with no visible quantity and replenishment disabled:
+    /// its hidden depth can never be shown, so it can never trade.
+    StrandedHidden,
+}
+
+impl QuarantineReason {
+    /// Classifies a resting order, returning `None` for a well-formed one.
+    pub(crate) fn inspect<T: Clone>(order: &OrderType<T>) -> Option<Self> {
+        let visible = order.visible_quantity().as_u64();
+        let hidden = order.hidden_quantity().as_u64();
+        if visible.checked_add(hidden).is_none() {
+            return Some(Self::QuantityOverflow);
+        }
+        if visible == 0 && hidden == 0 {
+            return Some(Self::EmptyOrder);
+        }
+        match order {
+            OrderType::ReserveOrder {
+                auto_replenish: false,
+                ..
+            } if visible == 0 => Some(Self::StrandedHidden),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for QuarantineReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyOrder => write!(f, "EMPTY_ORDER"),
+            Self::QuantityOverflow => write!(f, "QUANTITY_OVERFLOW"),
+            Self::StrandedHidden => write!(f, "STRANDED_HIDDEN"),
+        }
+    }
+}
+
+impl FromStr for QuarantineReason {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "EMPTY_ORDER" => Ok(Self::EmptyOrder),
+            "QUANTITY_OVERFLOW" => Ok(Self::QuantityOverflow),
+            "STRANDED_HIDDEN" => Ok(Self::StrandedHidden),
+            _ => Err(PriceLevelError::ParseError {
+                message: format!("Failed to parse QuarantineReason: {s}"),
+            }),
+        }
+    }
+}
+
+/// A resting order removed from a level by the match sweep because it was
+/// malformed, kept on the level's dead-letter list for inspection (see
+/// [`PriceLevel::quarantined_orders`](crate::PriceLevel::quarantined_orders)).
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuarantinedOrder<T = ()> {
+    /// The order exactly as it rested when the sweep reached it.
+    pub order: Arc<OrderType<T>>,
+    /// Why it was quarantined.
+    pub reason: QuarantineReason,
+    /// The taker whose sweep found it.
+    pub taker_order_id: Id,
+    /// Timestamp of that sweep.
+    pub timestamp: TimestampMs,
+}
@@ -0,0 +1,78 @@
+use crate::errors::PriceLevelError;
+use crate::orders::Id;
+use crate::utils::{Price, TimestampMs};
+use serde::{Deserialize, Serialize};
+
+/// Where one resting order stood in its level's queue when a
+/// [`QueueAudit`] was taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuePosition {
+    /// Zero-based place in the queue: `0` is the order the next sweep
+    /// trades against first.
+    pub position: usize,
+    /// The resting order.
+    pub order_id: Id,
+    /// The order's own timestamp.
+    pub timestamp: TimestampMs,
+    /// The queue sequence the order holds its priority by. Assigned on
+    /// admission and re-assigned when an update costs the order its place
+    /// (a size-up moves it to the back), so it can be later than the
+    /// sequences of orders admitted after it.
+    pub arrival_sequence: u64,
+    /// The order's displayed quantity.
+    pub visible_quantity: u64,
+    /// The order's undisplayed quantity.
+    pub hidden_quantity: u64,
+}
+
+/// The exact time priority of every order resting at a level, from
+/// [`PriceLevel::queue_audit`](crate::PriceLevel::queue_audit).
+///
+/// Surveillance checks fills against it: between two audits, a FIFO level
+/// must have traded each maker only after every maker ahead of it was
+/// exhausted. Positions are listed front to back, in the order
+/// [`PriceLevel::match_order`](crate::PriceLevel::match_order) consumes them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueAudit {
+    /// The level's price.
+    pub price: Price,
+    /// The level clock's reading when the audit was taken.
+    pub captured_at: TimestampMs,
+    /// The level's
+    /// [`mutation_sequence`](crate::PriceLevel::mutation_sequence) when the
+    /// audit was taken. As for a snapshot, every mutation up to it is
+    /// reflected.
+    pub mutation_sequence: u64,
+    /// Every resting order, front of the queue first.
+    pub positions: Vec<QueuePosition>,
+}
+
+impl QueueAudit {
+    /// The position of `order_id`, if it was resting when the audit was
+    /// taken.
+    #[must_use]
+    pub fn position_of(&self, order_id: Id) -> Option<&QueuePosition> {
+        self.positions
+            .iter()
+            .find(|position| position.order_id == order_id)
+    }
+
+    /// Serializes the audit to JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if serialization fails.
+    pub fn to_json(&self) -> Result<String, PriceLevelError> {
+        serde_json::to_string(self).map_err(PriceLevelError::serialization)
+    }
+
+    /// Deserializes an audit from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::DeserializationError`] if `data` is not a
+    /// valid JSON representation of an audit.
+    pub fn from_json(data: &str) -> Result<Self, PriceLevelError> {
+        serde_json::from_str(data).map_err(PriceLevelError::deserialization)
+    }
+}
@@ -0,0 +1,257 @@
+use crate::utils::TimestampMs;
+use dashmap::DashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// The message a [`RateLimiter`] is consulted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitedOperation {
+    /// An order admission ([`PriceLevel::add_order`](crate::PriceLevel::add_order)
+    /// and its variants).
+    Add,
+    /// An [`OrderUpdate`](crate::OrderUpdate) other than a cancel.
+    Update,
+    /// An [`OrderUpdate::Cancel`](crate::OrderUpdate::Cancel).
+    Cancel,
+}
+
+/// A message throttle a level consults before each admission, update and
+/// cancel; see [`PriceLevel::set_rate_limiter`](crate::PriceLevel::set_rate_limiter).
+///
+/// The limiter is handed the extra fields of the order the message concerns,
+/// from which it derives the participant to charge, so the level needs no
+/// notion of participants itself. A refused message is rejected with
+/// [`PriceLevelError::RateLimited`](crate::PriceLevelError::RateLimited)
+/// before it changes anything.
+pub trait RateLimiter<T = ()>: Send + Sync {
+    /// Charge one `operation` to the participant named by `extra_fields` at
+    /// `now`, the level clock's reading. Returns `false` to refuse it.
+    fn try_acquire(
+        &self,
+        extra_fields: &T,
+        operation: RateLimitedOperation,
+        now: TimestampMs,
+    ) -> bool;
+}
+
+/// Thousandths of a token, so refills below one token per millisecond
+/// accrue exactly.
+const MILLI_TOKENS: u64 = 1_000;
+
+/// Names the participant of an order's extra fields, if it has one.
+type ParticipantOf<T, K> = Box<dyn Fn(&T) -> Option<K> + Send + Sync>;
+
+/// One participant's bucket, in thousandths of a token.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    milli_tokens: u64,
+    refilled_at: u64,
+}
+
+/// A reference [`RateLimiter`]: one token bucket per participant, holding up
+/// to `capacity` messages and refilled at `refill_per_second` messages a
+/// second of the level clock.
+///
+/// Each message of any [`RateLimitedOperation`] costs one token. A
+/// participant starts with a full bucket; a message whose participant
+/// extractor returns `None` is never throttled.
+///
+/// ```rust
+/// use pricelevel::{RateLimitedOperation, RateLimiter, TimestampMs, TokenBucketRateLimiter};
+///
+/// // Participants are the `u64` carried as extra fields.
+/// let limiter = TokenBucketRateLimiter::new(2, 1, |account: &u64| Some(*account));
+/// let now = TimestampMs::new(0);
+/// assert!(limiter.try_acquire(&7, RateLimitedOperation::Add, now));
+/// assert!(limiter.try_acquire(&7, RateLimitedOperation::Cancel, now));
+/// assert!(!limiter.try_acquire(&7, RateLimitedOperation::Add, now));
+/// assert!(limiter.try_acquire(&8, RateLimitedOperation::Add, now));
+/// assert!(limiter.try_acquire(&7, RateLimitedOperation::Add, TimestampMs::new(1_000)));
+/// ```
+pub struct TokenBucketRateLimiter<T, K> {
+    capacity: u64,
+    refill_per_second: u64,
+    participant: ParticipantOf<T, K>,
+    buckets: DashMap<K, Bucket>,
+}
+
+impl<T, K: Eq + Hash> TokenBucketRateLimiter<T, K> {
+    /// A limiter allowing each participant bursts of `capacity` messages and
+    /// `refill_per_second` messages a second after that, with `participant`
+    /// naming the participant of an order's extra fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new<F>(capacity: u64, refill_per_second: u64, participant: F) -> Self
+    where
+        F: Fn(&T) -> Option<K> + Send + Sync + 'static,
+    {
+        assert!(capacity > 0, "token bucket capacity must be positive");
+        Self {
+            capacity,
+            refill_per_second,
+            participant: Box::new(participant),
+            buckets: DashMap::new(),
+        }
+    }
+
+    /// The most messages a participant can send at once.
+    #[must_use]
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// The messages a participant's bucket regains each second.
+    #[must_use]
+    pub fn refill_per_second(&self) -> u64 {
+        self.refill_per_second
+    }
+
+    /// The whole messages `participant` could send at `now`; the capacity
+    /// for a participant not seen yet.
+    #[must_use]
+    pub fn available(&self, participant: &K, now: TimestampMs) -> u64 {
+        self.buckets
+            .get(participant)
+            .map_or(self.capacity, |bucket| {
+                self.refilled(*bucket, now.as_u64()).milli_tokens / MILLI_TOKENS
+            })
+    }
+
+    /// Forget every participant's bucket, refilling them all.
+    pub fn reset(&self) {
+        self.buckets.clear();
+    }
+
+    fn full(&self, now: u64) -> Bucket {
+        Bucket {
+            milli_tokens: self.capacity.saturating_mul(MILLI_TOKENS),
+            refilled_at: now,
+        }
+    }
+
+    /// `bucket` topped up for the time elapsed until `now`. A clock that
+    /// went backwards refills nothing.
+    fn refilled(&self, bucket: Bucket, now: u64) -> Bucket {
+        let elapsed = now.saturating_sub(bucket.refilled_at);
+        // A second refills `refill_per_second` tokens, so each millisecond
+        // refills that many thousandths.
+        let milli_tokens = bucket
+            .milli_tokens
+            .saturating_add(elapsed.saturating_mul(self.refill_per_second))
+            .min(self.capacity.saturating_mul(MILLI_TOKENS));
+        Bucket {
+            milli_tokens,
+            refilled_at: now.max(bucket.refilled_at),
+        }
+    }
+}
+
+impl<T, K> RateLimiter<T> for TokenBucketRateLimiter<T, K>
+where
+    K: Eq + Hash + Send + Sync,
+{
+    fn try_acquire(
+        &self,
+        extra_fields: &T,
+        _operation: RateLimitedOperation,
+        now: TimestampMs,
+    ) -> bool {
+        let Some(participant) = (self.participant)(extra_fields) else {
+            return true;
+        };
+        let now = now.as_u64();
+        let mut bucket = self
+            .buckets
+            .entry(participant)
+            .or_insert_with(|| self.full(now));
+        let refilled = self.refilled(*bucket, now);
+        match refilled.milli_tokens.checked_sub(MILLI_TOKENS) {
+            Some(milli_tokens) => {
+                *bucket = Bucket {
+                    milli_tokens,
+                    ..refilled
+                };
+                true
+            }
+            None => {
+                *bucket = refilled;
+                false
+            }
+        }
+    }
+}
+
+impl<T, K: Eq + Hash> fmt::Debug for TokenBucketRateLimiter<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenBucketRateLimiter")
+            .field("capacity", &self.capacity)
+            .field("refill_per_second", &self.refill_per_second)
+            .field("participants", &self.buckets.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The rate limiter registered with a level. Runtime configuration, like the
+/// expiry-warning hook: never cloned or serialized.
+pub(crate) struct RateLimitHook<T> {
+    limiter: RwLock<Option<Arc<dyn RateLimiter<T>>>>,
+    /// `true` while a limiter is registered, so unthrottled levels skip the
+    /// lock.
+    any: AtomicBool,
+}
+
+impl<T> Default for RateLimitHook<T> {
+    // Not derived: a derive would demand `T: Default`.
+    fn default() -> Self {
+        Self {
+            limiter: RwLock::new(None),
+            any: AtomicBool::new(false),
+        }
+    }
+}
+
+impl<T> fmt::Debug for RateLimitHook<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimitHook")
+            .field("set", &self.is_set())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> RateLimitHook<T> {
+    pub(crate) fn set(&self, limiter: Arc<dyn RateLimiter<T>>) {
+        *self.limiter.write().unwrap_or_else(|e| e.into_inner()) = Some(limiter);
+        self.any.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn clear(&self) {
+        *self.limiter.write().unwrap_or_else(|e| e.into_inner()) = None;
+        self.any.store(false, Ordering::Release);
+    }
+
+    pub(crate) fn is_set(&self) -> bool {
+        self.any.load(Ordering::Acquire)
+    }
+
+    /// Whether the registered limiter, if any, lets `operation` through.
+    pub(crate) fn allows(
+        &self,
+        extra_fields: &T,
+        operation: RateLimitedOperation,
+        now: TimestampMs,
+    ) -> bool {
+        if !self.is_set() {
+            return true;
+        }
+        let limiter = self
+            .limiter
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        limiter.is_none_or(|limiter| limiter.try_acquire(extra_fields, operation, now))
+    }
+}
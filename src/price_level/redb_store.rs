@@ -0,0 +1,222 @@
+//! A [`SnapshotStore`] on an embedded redb database (feature `redb`).
+
+use crate::errors::PriceLevelError;
+use crate::price_level::multi_book::SymbolKey;
+use crate::price_level::snapshot::PriceLevelSnapshotPackage;
+use crate::price_level::snapshot_store::{PrunePolicy, SnapshotKey, SnapshotStore};
+use redb::backends::InMemoryBackend;
+use redb::{Database, ReadableTable, TableDefinition};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::path::Path;
+
+/// A symbol as stored: variant tag, numeric id and name. The tag keeps the
+/// store's key order that of [`SymbolKey`], ids before names.
+type RawSymbol<'a> = (u8, u32, &'a str);
+
+/// A [`SnapshotKey`] as stored: the symbol, price and sequence.
+type RawKey<'a> = (u8, u32, &'a str, u128, u64);
+
+/// Packages as JSON, under their encoded keys.
+const PACKAGES: TableDefinition<RawKey<'static>, &str> = TableDefinition::new("snapshot_packages");
+
+const TAG_ID: u8 = 0;
+const TAG_NAME: u8 = 1;
+
+/// A [`SnapshotStore`] keeping packages in a redb database: a single file,
+/// crash-safe, with every put and prune its own committed transaction.
+///
+/// Packages are stored as their JSON encoding, so a store written by one
+/// build reads back in another as long as the package format version is
+/// supported.
+///
+/// ```rust
+/// use pricelevel::{PriceLevel, RedbSnapshotStore, SnapshotStore, SymbolKey};
+///
+/// let store = RedbSnapshotStore::in_memory().unwrap();
+/// let symbol = SymbolKey::from("BTC-USD");
+/// let level: PriceLevel = PriceLevel::new(10_000);
+/// store.put_level(&symbol, &level).unwrap();
+///
+/// let restored: PriceLevel = store.restore_latest(&symbol, 10_000).unwrap().unwrap();
+/// assert_eq!(restored.price(), 10_000);
+/// ```
+pub struct RedbSnapshotStore {
+    db: Database,
+}
+
+impl fmt::Debug for RedbSnapshotStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedbSnapshotStore").finish_non_exhaustive()
+    }
+}
+
+impl RedbSnapshotStore {
+    /// Open the store in the database file at `path`, creating the file if
+    /// it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if the database
+    /// cannot be opened or initialized.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PriceLevelError> {
+        let db = Database::create(path).map_err(write_error)?;
+        Self::from_database(db)
+    }
+
+    /// A store held in memory, gone when dropped: for tests and dry runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns what [`Self::open`] returns.
+    pub fn in_memory() -> Result<Self, PriceLevelError> {
+        let db = Database::builder()
+            .create_with_backend(InMemoryBackend::new())
+            .map_err(write_error)?;
+        Self::from_database(db)
+    }
+
+    fn from_database(db: Database) -> Result<Self, PriceLevelError> {
+        // Create the table up front, so reads never meet a missing one.
+        let txn = db.begin_write().map_err(write_error)?;
+        txn.open_table(PACKAGES).map_err(write_error)?;
+        txn.commit().map_err(write_error)?;
+        Ok(Self { db })
+    }
+}
+
+impl<T: Clone + Serialize + DeserializeOwned> SnapshotStore<T> for RedbSnapshotStore {
+    fn put(
+        &self,
+        key: &SnapshotKey,
+        package: &PriceLevelSnapshotPackage<T>,
+    ) -> Result<(), PriceLevelError> {
+        let json = package.to_json()?;
+        let (tag, id, name) = raw_symbol(&key.symbol);
+        let txn = self.db.begin_write().map_err(write_error)?;
+        {
+            let mut table = txn.open_table(PACKAGES).map_err(write_error)?;
+            table
+                .insert((tag, id, name, key.price, key.sequence), json.as_str())
+                .map_err(write_error)?;
+        }
+        txn.commit().map_err(write_error)
+    }
+
+    fn get(
+        &self,
+        key: &SnapshotKey,
+    ) -> Result<Option<PriceLevelSnapshotPackage<T>>, PriceLevelError> {
+        let (tag, id, name) = raw_symbol(&key.symbol);
+        let txn = self.db.begin_read().map_err(read_error)?;
+        let table = txn.open_table(PACKAGES).map_err(read_error)?;
+        table
+            .get((tag, id, name, key.price, key.sequence))
+            .map_err(read_error)?
+            .map(|json| PriceLevelSnapshotPackage::from_json(json.value()))
+            .transpose()
+    }
+
+    fn latest(
+        &self,
+        symbol: &SymbolKey,
+        price: u128,
+    ) -> Result<Option<(SnapshotKey, PriceLevelSnapshotPackage<T>)>, PriceLevelError> {
+        let (tag, id, name) = raw_symbol(symbol);
+        let txn = self.db.begin_read().map_err(read_error)?;
+        let table = txn.open_table(PACKAGES).map_err(read_error)?;
+        let mut range = table
+            .range((tag, id, name, price, 0)..=(tag, id, name, price, u64::MAX))
+            .map_err(read_error)?;
+        let Some(entry) = range.next_back() else {
+            return Ok(None);
+        };
+        let (key, json) = entry.map_err(read_error)?;
+        let key = snapshot_key(key.value())?;
+        let package = PriceLevelSnapshotPackage::from_json(json.value())?;
+        Ok(Some((key, package)))
+    }
+
+    fn keys(&self) -> Result<Vec<SnapshotKey>, PriceLevelError> {
+        let txn = self.db.begin_read().map_err(read_error)?;
+        let table = txn.open_table(PACKAGES).map_err(read_error)?;
+        table
+            .iter()
+            .map_err(read_error)?
+            .map(|entry| snapshot_key(entry.map_err(read_error)?.0.value()))
+            .collect()
+    }
+
+    fn prune(&self, policy: PrunePolicy) -> Result<usize, PriceLevelError> {
+        let txn = self.db.begin_write().map_err(write_error)?;
+        let removed = {
+            let mut table = txn.open_table(PACKAGES).map_err(write_error)?;
+            // Keys in descending order, so each level's latest come first.
+            let mut doomed: Vec<SnapshotKey> = Vec::new();
+            let mut previous: Option<SnapshotKey> = None;
+            let mut kept = 0;
+            for entry in table.iter().map_err(write_error)?.rev() {
+                let key = snapshot_key(entry.map_err(write_error)?.0.value())?;
+                let remove = match policy {
+                    PrunePolicy::BelowSequence(below) => key.sequence < below,
+                    PrunePolicy::KeepLatest(keep) => {
+                        let same_level = previous.as_ref().is_some_and(|previous| {
+                            previous.symbol == key.symbol && previous.price == key.price
+                        });
+                        kept = if same_level { kept + 1 } else { 1 };
+                        kept > keep
+                    }
+                };
+                if remove {
+                    doomed.push(key.clone());
+                }
+                previous = Some(key);
+            }
+            for key in &doomed {
+                let (tag, id, name) = raw_symbol(&key.symbol);
+                table
+                    .remove((tag, id, name, key.price, key.sequence))
+                    .map_err(write_error)?;
+            }
+            doomed.len()
+        };
+        txn.commit().map_err(write_error)?;
+        Ok(removed)
+    }
+}
+
+fn raw_symbol(symbol: &SymbolKey) -> RawSymbol<'_> {
+    match symbol {
+        SymbolKey::Id(id) => (TAG_ID, *id, ""),
+        SymbolKey::Name(name) => (TAG_NAME, 0, name.as_str()),
+    }
+}
+
+fn snapshot_key(
+    (tag, id, name, price, sequence): RawKey<'_>,
+) -> Result<SnapshotKey, PriceLevelError> {
+    let symbol = match tag {
+        TAG_ID => SymbolKey::Id(id),
+        TAG_NAME => SymbolKey::Name(name.to_string()),
+        _ => {
+            return Err(PriceLevelError::DeserializationError {
+                message: format!("snapshot store: unknown symbol tag {tag}"),
+                source: None,
+            });
+        }
+    };
+    Ok(SnapshotKey {
+        symbol,
+        price,
+        sequence,
+    })
+}
+
+fn write_error(e: impl Into<redb::Error>) -> PriceLevelError {
+    PriceLevelError::serialization(e.into())
+}
+
+fn read_error(e: impl Into<redb::Error>) -> PriceLevelError {
+    PriceLevelError::deserialization(e.into())
+}
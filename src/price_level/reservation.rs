@@ -0,0 +1,133 @@
+use crate::errors::PriceLevelError;
+use crate::execution::MatchResult;
+use crate::orders::Id;
+use crate::price_level::level::PriceLevel;
+use crate::utils::{ExecutionIdGenerator, TimestampMs};
+use std::fmt;
+
+/// Time a reservation made by [`PriceLevel::reserve`] holds its quantity
+/// before it is released automatically, in milliseconds of the level's clock.
+pub const DEFAULT_RESERVATION_TIMEOUT_MS: u64 = 1_000;
+
+/// Quantity earmarked at a price level by [`PriceLevel::reserve`] while a
+/// risk check decides whether to trade it.
+///
+/// While the guard is outstanding, no other taker can match the reserved
+/// quantity: their sweeps stop short of it, and a fill-or-kill taker that
+/// would need it is killed. The guard ends in one of three ways:
+///
+/// - [`Self::commit`] matches the reserved quantity as an immediate-or-cancel
+///   taker, and releases whatever it could not fill;
+/// - [`Self::rollback`], or dropping the guard, releases the quantity
+///   untouched;
+/// - once the level's clock passes [`Self::expires_at`], the next sweep or
+///   reservation at the level releases it, and a later commit fails.
+///
+/// A reservation earmarks quantity, not particular orders: resting orders
+/// can still be cancelled or resized while it is held, in which case the
+/// commit fills only what remains.
+pub struct ReservationGuard<'a, T = ()> {
+    level: &'a PriceLevel<T>,
+    id: u64,
+    quantity: u64,
+    expires_at: TimestampMs,
+    settled: bool,
+}
+
+impl<T: Clone> fmt::Debug for ReservationGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReservationGuard")
+            .field("price", &self.level.price())
+            .field("id", &self.id)
+            .field("quantity", &self.quantity)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+impl<'a, T> ReservationGuard<'a, T> {
+    pub(crate) fn new(
+        level: &'a PriceLevel<T>,
+        id: u64,
+        quantity: u64,
+        expires_at: TimestampMs,
+    ) -> Self {
+        Self {
+            level,
+            id,
+            quantity,
+            expires_at,
+            settled: false,
+        }
+    }
+
+    /// The reservation's id, unique within its level.
+    #[must_use]
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The quantity reserved.
+    #[must_use]
+    pub fn quantity(&self) -> u64 {
+        self.quantity
+    }
+
+    /// The time, on the level's clock, from which the reservation may be
+    /// released automatically.
+    #[must_use]
+    pub fn expires_at(&self) -> TimestampMs {
+        self.expires_at
+    }
+}
+
+impl<T: Clone> ReservationGuard<'_, T> {
+    /// Returns `true` if the level's clock has reached [`Self::expires_at`].
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.level.clock().now() >= self.expires_at
+    }
+
+    /// Match the reserved quantity as an immediate-or-cancel taker.
+    ///
+    /// The sweep runs exactly as [`PriceLevel::match_order`] would, with the
+    /// reserved quantity available to it alone; quantity it cannot fill —
+    /// depth cancelled since the reservation — is released with the rest.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the reservation
+    /// expired and was released before the commit. The level is unchanged.
+    pub fn commit<G>(
+        mut self,
+        taker_order_id: Id,
+        timestamp: TimestampMs,
+        trade_id_generator: &G,
+    ) -> Result<MatchResult<T>, PriceLevelError>
+    where
+        G: ExecutionIdGenerator + ?Sized,
+    {
+        self.settled = true;
+        self.level.commit_reservation(
+            self.id,
+            self.quantity,
+            taker_order_id,
+            timestamp,
+            trade_id_generator,
+        )
+    }
+
+    /// Release the reserved quantity without trading it.
+    pub fn rollback(mut self) {
+        self.settled = true;
+        self.level.release_reservation(self.id);
+    }
+}
+
+impl<T> Drop for ReservationGuard<'_, T> {
+    fn drop(&mut self) {
+        if !self.settled {
+            self.level.release_reservation(self.id);
+        }
+    }
+}
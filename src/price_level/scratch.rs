@@ -0,0 +1,145 @@
+use crate::orders::{Id, OrderType};
+use std::collections::{HashSet, VecDeque};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+
+/// Largest element capacity a pooled buffer keeps between matches.
+///
+/// A one-off sweep over a very deep level would otherwise pin its peak buffers
+/// for the life of the level; anything above this is released on return.
+const MAX_RETAINED_CAPACITY: usize = 4096;
+
+/// Temporary collections a match needs for the duration of one call.
+///
+/// Every field is empty whenever the scratch is not checked out; only the
+/// allocated capacity survives between calls.
+#[derive(Debug)]
+pub(crate) struct MatchScratch<T> {
+    /// Insertion sequences parked by the current sweep (see
+    /// `OrderQueue::match_front`).
+    pub(crate) set_aside: HashSet<u64>,
+    /// Queue snapshot in insertion-sequence order.
+    pub(crate) orders: Vec<Arc<OrderType<T>>>,
+    /// Work queue of the fill-or-kill dry run.
+    pub(crate) pending: VecDeque<Arc<OrderType<T>>>,
+    /// `(maker id, displayed quantity)` candidates of an allocation plan.
+    pub(crate) makers: Vec<(Id, u64)>,
+    /// Per-maker shares while an allocation plan is being rounded.
+    pub(crate) allocations: Vec<u64>,
+    /// The allocation plan being executed by the sweep.
+    pub(crate) plan: VecDeque<(Id, u64)>,
+}
+
+// Not derived: a derive would demand `T: Default`, which the buffers do not
+// need.
+impl<T> Default for MatchScratch<T> {
+    fn default() -> Self {
+        Self {
+            set_aside: HashSet::new(),
+            orders: Vec::new(),
+            pending: VecDeque::new(),
+            makers: Vec::new(),
+            allocations: Vec::new(),
+            plan: VecDeque::new(),
+        }
+    }
+}
+
+impl<T> MatchScratch<T> {
+    /// Empties every buffer, keeping capacity up to
+    /// [`MAX_RETAINED_CAPACITY`] elements.
+    fn recycle(&mut self) {
+        self.set_aside.clear();
+        self.orders.clear();
+        self.pending.clear();
+        self.makers.clear();
+        self.allocations.clear();
+        self.plan.clear();
+        self.set_aside.shrink_to(MAX_RETAINED_CAPACITY);
+        self.orders.shrink_to(MAX_RETAINED_CAPACITY);
+        self.pending.shrink_to(MAX_RETAINED_CAPACITY);
+        self.makers.shrink_to(MAX_RETAINED_CAPACITY);
+        self.allocations.shrink_to(MAX_RETAINED_CAPACITY);
+        self.plan.shrink_to(MAX_RETAINED_CAPACITY);
+    }
+}
+
+/// A level-local, reusable [`MatchScratch`].
+///
+/// Checked out with [`ScratchSlot::take`] at the start of a match and returned
+/// when the guard drops, so a steady stream of matches against the same level
+/// reuses one set of buffers instead of allocating fresh ones per call. The
+/// slot never blocks: if it is already checked out (a concurrent read-only
+/// dry run, or a nested call) or poisoned, `take` hands back a fresh, owned
+/// scratch instead — the caller behaves identically, it only pays the
+/// allocations the slot would have saved.
+#[derive(Debug)]
+pub(crate) struct ScratchSlot<T> {
+    slot: Mutex<MatchScratch<T>>,
+}
+
+impl<T> Default for ScratchSlot<T> {
+    fn default() -> Self {
+        Self {
+            slot: Mutex::new(MatchScratch::default()),
+        }
+    }
+}
+
+impl<T> ScratchSlot<T> {
+    /// Check out the pooled scratch, or a fresh one if it is busy.
+    pub(crate) fn take(&self) -> ScratchGuard<'_, T> {
+        match self.slot.try_lock() {
+            Ok(guard) => ScratchGuard::Pooled(guard),
+            Err(TryLockError::WouldBlock) | Err(TryLockError::Poisoned(_)) => {
+                ScratchGuard::Owned(MatchScratch::default())
+            }
+        }
+    }
+
+    /// Capacity currently retained by the pooled `orders` buffer, or `None`
+    /// if the slot is checked out.
+    #[cfg(test)]
+    pub(crate) fn retained_orders_capacity(&self) -> Option<usize> {
+        self.slot.try_lock().ok().map(|s| s.orders.capacity())
+    }
+}
+
+/// A checked-out [`MatchScratch`]; recycled into its slot on drop.
+pub(crate) enum ScratchGuard<'a, T> {
+    /// The level's pooled scratch.
+    Pooled(MutexGuard<'a, MatchScratch<T>>),
+    /// A one-off scratch used while the pooled one was unavailable.
+    Owned(MatchScratch<T>),
+}
+
+impl<T> Deref for ScratchGuard<'_, T> {
+    type Target = MatchScratch<T>;
+
+    fn deref(&self) -> &MatchScratch<T> {
+        match self {
+            Self::Pooled(guard) => guard,
+            Self::Owned(scratch) => scratch,
+        }
+    }
+}
+
+impl<T> DerefMut for ScratchGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut MatchScratch<T> {
+        match self {
+            Self::Pooled(guard) => guard,
+            Self::Owned(scratch) => scratch,
+        }
+    }
+}
+
+impl<T> Drop for ScratchGuard<'_, T> {
+    fn drop(&mut self) {
+        // Release the `Arc`s the buffers still reference before the scratch
+        // goes back to the slot, so a pooled buffer never keeps a cancelled
+        // or filled order alive.
+        if let Self::Pooled(guard) = self {
+            guard.recycle();
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::orders::Id;
+use crossbeam_skiplist::SkipMap;
+use crossbeam_skiplist::map::{Entry, Iter};
+use smallvec::SmallVec;
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, BuildHasherDefault};
+use std::iter::Peekable;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The `OrderQueue`'s ordered `sequence -> Id` index, split across one or
+/// more skip lists.
+///
+/// With one shard this is the plain time-priority index. With several, each
+/// order's key lives in the shard its id hashes to, so concurrent admissions,
+/// cancels and fills of different orders touch different skip lists instead
+/// of all contending on the head and tail of one. The shard depends only on
+/// the id, so re-sequencing an order (a replenish, a size increase) keeps it
+/// in the same shard.
+///
+/// Within a shard keys stay in insertion-sequence order. Across shards the
+/// match sweep takes fronts round-robin (see [`SequenceIndex::front`]), which
+/// keeps time priority only approximately; the exact global front is used
+/// whenever the caller asks for it.
+#[derive(Debug)]
+pub(crate) struct SequenceIndex {
+    shards: Box<[SkipMap<u64, Id>]>,
+    /// The shard the next round-robin front is taken from.
+    cursor: AtomicUsize,
+}
+
+impl SequenceIndex {
+    /// An empty index over `shards` skip lists (at least one).
+    pub(crate) fn new(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1)).map(|_| SkipMap::new()).collect(),
+            cursor: AtomicUsize::new(0),
+        }
+    }
+
+    /// Number of skip lists the index is split across.
+    #[inline]
+    pub(crate) fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Position of the shard holding `order_id`'s keys.
+    #[inline]
+    pub(crate) fn shard_index(&self, order_id: Id) -> usize {
+        if self.shards.len() == 1 {
+            return 0;
+        }
+        // A fixed-key hasher, so the shard is stable for the queue's lifetime.
+        let hash = BuildHasherDefault::<DefaultHasher>::default().hash_one(order_id);
+        (hash % self.shards.len() as u64) as usize
+    }
+
+    /// The skip list holding `order_id`'s keys.
+    #[inline]
+    fn shard_of(&self, order_id: Id) -> &SkipMap<u64, Id> {
+        &self.shards[self.shard_index(order_id)]
+    }
+
+    /// Record `order_id` at `seq`.
+    #[inline]
+    pub(crate) fn insert(&self, seq: u64, order_id: Id) {
+        self.shard_of(order_id).insert(seq, order_id);
+    }
+
+    /// Drop the key `seq` of `order_id`. Returns whether it was present.
+    #[inline]
+    pub(crate) fn remove(&self, seq: u64, order_id: Id) -> bool {
+        self.shard_of(order_id).remove(&seq).is_some()
+    }
+
+    /// Whether `seq` is a key of `order_id`.
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn contains(&self, seq: u64, order_id: Id) -> bool {
+        self.shard_of(order_id)
+            .get(&seq)
+            .is_some_and(|entry| *entry.value() == order_id)
+    }
+
+    /// Total number of keys, tombstones included.
+    pub(crate) fn len(&self) -> usize {
+        self.shards.iter().map(SkipMap::len).sum()
+    }
+
+    /// The lowest sequence across every shard, if any.
+    pub(crate) fn first(&self) -> Option<u64> {
+        self.shards
+            .iter()
+            .filter_map(|shard| shard.front().map(|entry| *entry.key()))
+            .min()
+    }
+
+    /// Atomically remove and return the lowest-sequence key.
+    ///
+    /// With several shards the shard holding the lowest front is chosen first
+    /// and its front popped; a concurrent pop of that front makes this pop the
+    /// shard's next key instead, so the result is the exact global front only
+    /// at quiescence.
+    pub(crate) fn pop_front(&self) -> Option<(u64, Id)> {
+        loop {
+            let shard = self
+                .shards
+                .iter()
+                .filter_map(|shard| shard.front().map(|entry| (*entry.key(), shard)))
+                .min_by_key(|(seq, _)| *seq)?
+                .1;
+            if let Some(entry) = shard.pop_front() {
+                return Some((*entry.key(), *entry.value()));
+            }
+        }
+    }
+
+    /// The front key the match sweep should take next, skipping the sequences
+    /// in `set_aside`.
+    ///
+    /// With `exact` (or a single shard) this is the lowest sequence across
+    /// every shard, i.e. strict time priority. Otherwise it is the front of the
+    /// first non-empty shard at or after the round-robin cursor: each shard is
+    /// consumed in time priority and the non-empty shards take turns. The
+    /// cursor only moves on [`SequenceIndex::advance`].
+    pub(crate) fn front(&self, set_aside: &HashSet<u64>, exact: bool) -> Option<(u64, Id)> {
+        let first = |shard: &SkipMap<u64, Id>| {
+            shard
+                .iter()
+                .find(|entry| !set_aside.contains(entry.key()))
+                .map(|entry| (*entry.key(), *entry.value()))
+        };
+        if exact || self.shards.len() == 1 {
+            return self
+                .shards
+                .iter()
+                .filter_map(first)
+                .min_by_key(|(seq, _)| *seq);
+        }
+        let start = self.cursor.load(Ordering::Relaxed);
+        (0..self.shards.len())
+            .find_map(|step| first(&self.shards[(start + step) % self.shards.len()]))
+    }
+
+    /// Pass the round-robin turn to the next shard.
+    #[inline]
+    pub(crate) fn advance(&self) {
+        if self.shards.len() > 1 {
+            self.cursor.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Every key, shard by shard, in no particular global order. The entries
+    /// can be removed in place.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = Entry<'_, u64, Id>> + '_ {
+        self.shards.iter().flat_map(SkipMap::iter)
+    }
+
+    /// Every key in ascending sequence, merging the shards as it goes.
+    pub(crate) fn iter(&self) -> Merged<'_> {
+        Merged {
+            heads: self
+                .shards
+                .iter()
+                .map(|shard| shard.iter().peekable())
+                .collect(),
+        }
+    }
+}
+
+/// Ascending-sequence walk over every shard of a [`SequenceIndex`].
+///
+/// Each step yields the smallest of the shards' next keys. An unsharded
+/// index keeps its single cursor inline, so walking it does not allocate.
+pub(crate) struct Merged<'a> {
+    heads: SmallVec<[Peekable<Iter<'a, u64, Id>>; 1]>,
+}
+
+impl Iterator for Merged<'_> {
+    type Item = (u64, Id);
+
+    fn next(&mut self) -> Option<(u64, Id)> {
+        let mut lowest: Option<(usize, u64)> = None;
+        for (shard, head) in self.heads.iter_mut().enumerate() {
+            if let Some(entry) = head.peek() {
+                let seq = *entry.key();
+                if lowest.is_none_or(|(_, best)| seq < best) {
+                    lowest = Some((shard, seq));
+                }
+            }
+        }
+        let (shard, _) = lowest?;
+        self.heads[shard]
+            .next()
+            .map(|entry| (*entry.key(), *entry.value()))
+    }
+}
@@ -0,0 +1,50 @@
+/// A value stamped with the [`PriceLevel::mutation_sequence`] of the change
+/// that produced it.
+///
+/// Returned by [`PriceLevel::add_order_sequenced`] and
+/// [`PriceLevel::update_order_sequenced`], and, with the `async` feature,
+/// carried by the streams of `PriceLevel::subscribe_sequenced`. With
+/// [`PriceLevel::set_sequencing`] enabled, the sequences a level hands out
+/// totally order its mutations: sorting by them reproduces the order in which
+/// the changes committed. Without it the sequences are still unique per
+/// committed change, but concurrent changes may commit in a different order
+/// than their sequences suggest.
+///
+/// A result that changed nothing — an update that found no order — carries
+/// the sequence of the state it was decided against.
+///
+/// [`PriceLevel::mutation_sequence`]: crate::PriceLevel::mutation_sequence
+/// [`PriceLevel::add_order_sequenced`]: crate::PriceLevel::add_order_sequenced
+/// [`PriceLevel::update_order_sequenced`]: crate::PriceLevel::update_order_sequenced
+/// [`PriceLevel::set_sequencing`]: crate::PriceLevel::set_sequencing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sequenced<V> {
+    sequence: u64,
+    value: V,
+}
+
+impl<V> Sequenced<V> {
+    /// Stamps `value` with `sequence`.
+    #[must_use]
+    pub fn new(sequence: u64, value: V) -> Self {
+        Self { sequence, value }
+    }
+
+    /// The mutation sequence the value was stamped with.
+    #[must_use]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// The stamped value.
+    #[must_use]
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    /// Consumes the stamp, returning the value.
+    #[must_use]
+    pub fn into_value(self) -> V {
+        self.value
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// Extra fields naming the trading session their order was entered on, for
+/// cancel-on-disconnect.
+///
+/// A venue offering cancel-on-disconnect tags each order with its session
+/// when it is entered and, when the session drops, mass-cancels what is left
+/// of them with
+/// [`PriceLevel::cancel_session`](crate::PriceLevel::cancel_session) or
+/// [`MultiBook::cancel_session`](crate::MultiBook::cancel_session). An order
+/// naming no session survives every disconnect, as a venue's opted-out
+/// orders do.
+pub trait SessionTagged {
+    /// The session the order was entered on, if any.
+    fn session_id(&self) -> Option<u64>;
+}
+
+/// Extra fields carrying a session tag and nothing else, for levels that
+/// need no other per-order fields.
+///
+/// ```rust
+/// use pricelevel::{SessionTag, SessionTagged};
+///
+/// assert_eq!(SessionTag::new(7).session_id(), Some(7));
+/// assert_eq!(SessionTag::default().session_id(), None);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionTag {
+    /// The session the order was entered on; `None` for an order kept on
+    /// disconnect.
+    pub session_id: Option<u64>,
+}
+
+impl SessionTag {
+    /// A tag for an order entered on `session_id`.
+    #[must_use]
+    pub fn new(session_id: u64) -> Self {
+        Self {
+            session_id: Some(session_id),
+        }
+    }
+}
+
+impl SessionTagged for SessionTag {
+    fn session_id(&self) -> Option<u64> {
+        self.session_id
+    }
+}
@@ -1,12 +1,14 @@
 use crate::errors::PriceLevelError;
 use crate::orders::OrderType;
+use crate::price_level::checksum::{ChecksumAlgorithm, crc32, push_stripped};
+use crate::price_level::snapshot_validation::{SnapshotFinding, SnapshotValidationReport};
 use crate::price_level::statistics::PriceLevelStatistics;
 use crate::utils::{Price, Quantity};
-use serde::de::{self, MapAccess, Visitor};
+use serde::de::{self, DeserializeOwned, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use sha2::{Digest, Sha256};
 use std::fmt;
+use std::marker::PhantomData;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -14,7 +16,7 @@ use std::sync::Arc;
 /// at a given point in time, including the price, visible and hidden quantities, order count, the orders
 /// at that level, and the per-level execution statistics.
 #[derive(Debug, Default, Clone)]
-pub struct PriceLevelSnapshot {
+pub struct PriceLevelSnapshot<T = ()> {
     /// The price of this level, in price ticks.
     price: Price,
     /// Total visible quantity at this level, in quantity units.
@@ -24,7 +26,7 @@ pub struct PriceLevelSnapshot {
     /// Number of orders at this level.
     order_count: usize,
     /// Orders at this level.
-    orders: Vec<Arc<OrderType<()>>>,
+    orders: Vec<Arc<OrderType<T>>>,
     /// Per-level execution statistics captured at snapshot time.
     ///
     /// Carries the eight counters (orders added / removed / executed, quantity
@@ -33,9 +35,15 @@ pub struct PriceLevelSnapshot {
     /// rather than a zeroed set. Persisted via [`PriceLevelStatistics`]'s own
     /// serde shape and covered by the package SHA-256 checksum.
     statistics: PriceLevelStatistics,
+    /// The level's mutation sequence when the snapshot was taken.
+    ///
+    /// Every mutation up to this sequence is reflected in `orders`; a trade
+    /// whose [`level_sequence`](crate::Trade::level_sequence) is at most this
+    /// value executed before the snapshot. Serialized only when non-zero.
+    mutation_sequence: u64,
 }
 
-impl PriceLevelSnapshot {
+impl<T: Clone> PriceLevelSnapshot<T> {
     /// Create a new empty snapshot at the given price.
     #[must_use]
     pub fn new(price: Price) -> Self {
@@ -46,6 +54,7 @@ impl PriceLevelSnapshot {
             order_count: 0,
             orders: Vec::new(),
             statistics: PriceLevelStatistics::new(),
+            mutation_sequence: 0,
         }
     }
 
@@ -66,7 +75,7 @@ impl PriceLevelSnapshot {
     /// visible / hidden quantities overflows `u64`.
     pub fn with_orders(
         price: Price,
-        orders: Vec<Arc<OrderType<()>>>,
+        orders: Vec<Arc<OrderType<T>>>,
     ) -> Result<Self, PriceLevelError> {
         Self::with_orders_and_stats(price, orders, PriceLevelStatistics::new())
     }
@@ -80,7 +89,7 @@ impl PriceLevelSnapshot {
     /// visible / hidden quantities overflows `u64`.
     pub fn with_orders_and_stats(
         price: Price,
-        orders: Vec<Arc<OrderType<()>>>,
+        orders: Vec<Arc<OrderType<T>>>,
         statistics: PriceLevelStatistics,
     ) -> Result<Self, PriceLevelError> {
         let mut snapshot = Self {
@@ -90,6 +99,7 @@ impl PriceLevelSnapshot {
             order_count: 0,
             orders,
             statistics,
+            mutation_sequence: 0,
         };
         snapshot.refresh_aggregates()?;
         Ok(snapshot)
@@ -125,19 +135,61 @@ impl PriceLevelSnapshot {
         &self.statistics
     }
 
+    /// Returns the level's mutation sequence when this snapshot was taken
+    /// (`0` for a hand-built snapshot or one written before the field existed).
+    #[must_use]
+    pub fn mutation_sequence(&self) -> u64 {
+        self.mutation_sequence
+    }
+
+    /// Returns this snapshot recording `mutation_sequence` as the level's
+    /// mutation sequence.
+    #[must_use]
+    pub fn with_mutation_sequence(mut self, mutation_sequence: u64) -> Self {
+        self.mutation_sequence = mutation_sequence;
+        self
+    }
+
     /// Returns a reference to the orders in this snapshot.
     ///
     /// The vector order is significant: it is the queue-consumption order a
     /// restore reproduces, since [`crate::price_level::PriceLevel::from_snapshot`]
     /// re-enqueues the orders in vector order.
     #[must_use]
-    pub fn orders(&self) -> &[Arc<OrderType<()>>] {
+    pub fn orders(&self) -> &[Arc<OrderType<T>>] {
         &self.orders
     }
 
+    /// Kraken-style CRC-32 of the first `depth` displayed orders, in queue
+    /// order, for a feed handler to check this level against an exchange's
+    /// order-level checksum.
+    ///
+    /// The payload concatenates each order's price then displayed quantity,
+    /// each the digits of the feed's decimal string with the point and
+    /// leading zeros removed — for a price in ticks and a quantity in lots,
+    /// their integer digits. Fully hidden orders are not displayed, so they
+    /// are skipped. The checksum is CRC-32 (IEEE) of the payload's bytes.
+    #[must_use]
+    pub fn checksum_crc32(&self, depth: usize) -> u32 {
+        let mut payload = String::new();
+        for order in self
+            .orders
+            .iter()
+            .filter(|order| order.working_visible_quantity().as_u64() > 0)
+            .take(depth)
+        {
+            push_stripped(&mut payload, self.price.as_u128());
+            push_stripped(
+                &mut payload,
+                u128::from(order.working_visible_quantity().as_u64()),
+            );
+        }
+        crc32(payload.as_bytes())
+    }
+
     /// Consumes the snapshot and returns the inner orders vector.
     #[must_use]
-    pub fn into_orders(self) -> Vec<Arc<OrderType<()>>> {
+    pub fn into_orders(self) -> Vec<Arc<OrderType<T>>> {
         self.orders
     }
 
@@ -154,7 +206,7 @@ impl PriceLevelSnapshot {
         visible_quantity: Quantity,
         hidden_quantity: Quantity,
         order_count: usize,
-        orders: Vec<Arc<OrderType<()>>>,
+        orders: Vec<Arc<OrderType<T>>>,
     ) -> Self {
         Self::from_raw_parts_with_stats(
             price,
@@ -177,7 +229,7 @@ impl PriceLevelSnapshot {
         visible_quantity: Quantity,
         hidden_quantity: Quantity,
         order_count: usize,
-        orders: Vec<Arc<OrderType<()>>>,
+        orders: Vec<Arc<OrderType<T>>>,
         statistics: PriceLevelStatistics,
     ) -> Self {
         Self {
@@ -187,6 +239,7 @@ impl PriceLevelSnapshot {
             order_count,
             orders,
             statistics,
+            mutation_sequence: 0,
         }
     }
 
@@ -207,10 +260,24 @@ impl PriceLevelSnapshot {
     }
 
     /// Get an iterator over the orders in this snapshot
-    pub fn iter_orders(&self) -> impl Iterator<Item = &Arc<OrderType<()>>> {
+    pub fn iter_orders(&self) -> impl Iterator<Item = &Arc<OrderType<T>>> {
         self.orders.iter()
     }
 
+    /// Keeps only the orders `keep` accepts and recomputes the aggregates over
+    /// them. The aggregates of a subset of a level's orders cannot overflow
+    /// where the full set's did not; if the full set's did, the previous
+    /// aggregates are kept.
+    pub(crate) fn retain_orders<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Arc<OrderType<T>>) -> bool,
+    {
+        self.orders.retain(|order| keep(order));
+        if self.refresh_aggregates().is_err() {
+            self.order_count = self.orders.len();
+        }
+    }
+
     /// Recomputes aggregate fields (`visible_quantity`, `hidden_quantity`, and `order_count`) based on current orders.
     ///
     /// # Errors
@@ -242,7 +309,7 @@ impl PriceLevelSnapshot {
                 })?;
 
             visible_total = visible_total
-                .checked_add(order.visible_quantity().as_u64())
+                .checked_add(order.working_visible_quantity().as_u64())
                 .ok_or_else(|| PriceLevelError::InvalidOperation {
                     message: "snapshot visible quantity overflow".to_string(),
                 })?;
@@ -268,38 +335,50 @@ impl PriceLevelSnapshot {
 ///   [`PriceLevelSnapshotPackage::validate`] with a version mismatch.
 /// - **Version 2** (issue #63) persists per-level [`PriceLevelStatistics`] as an
 ///   8-field statistics payload (no `stats_degraded`).
-/// - **Version 3** (issue #129) is the current shape: it owns the optional 9th
-///   `stats_degraded` statistics field. A degraded level (which serializes that
-///   field) is a v3 payload, so it is no longer mislabelled v2 where an old
-///   8-field-only reader would choke on the unknown field.
+/// - **Version 3** (issue #129) owns the optional 9th `stats_degraded`
+///   statistics field. A degraded level (which serializes that field) is a v3
+///   payload, so it is no longer mislabelled v2 where an old 8-field-only
+///   reader would choke on the unknown field.
+/// - **Version 4** owns the optional `mutation_sequence` snapshot field,
+///   written only when non-zero, that links the snapshot to the trades
+///   stamped with [`Trade::level_sequence`](crate::Trade::level_sequence).
+/// - **Version 5** is the current shape: it owns the optional `order_ages`
+///   statistics field, the resting-order age histograms, written only once an
+///   age has been recorded.
 ///
-/// [`PriceLevelSnapshotPackage::validate`] accepts BOTH v2 (legacy, 8-field,
-/// `stats_degraded` defaults `false`) and v3, so old snapshots keep restoring;
-/// v1 is still rejected. Checksum recomputation is version-agnostic — a
-/// non-degraded level serializes 8 fields under either version, so a legacy v2
-/// package's SHA-256 still matches.
-pub const SNAPSHOT_FORMAT_VERSION: u32 = 3;
+/// [`PriceLevelSnapshotPackage::validate`] accepts v2 (legacy, 8-field,
+/// `stats_degraded` defaults `false`), v3, v4 and v5, so old snapshots keep
+/// restoring; v1 is still rejected. Checksum recomputation is
+/// version-agnostic — optional fields are serialized only when set, so a
+/// legacy package decodes and re-encodes to the same bytes and its SHA-256
+/// still matches.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 5;
 
 /// The set of snapshot format versions [`PriceLevelSnapshotPackage::validate`]
-/// accepts on restore: the current [`SNAPSHOT_FORMAT_VERSION`] (v3) and the
-/// legacy v2 (issue #129). v1 (statistics-less) is not accepted.
-const SUPPORTED_SNAPSHOT_VERSIONS: &[u32] = &[2, 3];
+/// accepts on restore: the current [`SNAPSHOT_FORMAT_VERSION`] (v5) and the
+/// legacy v2, v3 and v4 (issue #129). v1 (statistics-less) is not accepted.
+const SUPPORTED_SNAPSHOT_VERSIONS: &[u32] = &[2, 3, 4, 5];
 
 /// Serialized representation of a price level snapshot including checksum validation metadata.
 ///
 /// All fields are private to protect checksum integrity.
 /// Use the provided accessor methods to read package data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PriceLevelSnapshotPackage {
+pub struct PriceLevelSnapshotPackage<T = ()> {
     /// Version of the serialized snapshot schema to support future migrations.
     version: u32,
     /// Captured snapshot data.
-    snapshot: PriceLevelSnapshot,
+    snapshot: PriceLevelSnapshot<T>,
+    /// Algorithm that produced [`Self::checksum`]. Packages written before the
+    /// field existed carry no value and decode as [`ChecksumAlgorithm::Sha256`],
+    /// the only algorithm they could have used.
+    #[serde(default)]
+    checksum_algorithm: ChecksumAlgorithm,
     /// Hex-encoded checksum used to validate the snapshot integrity.
     checksum: String,
 }
 
-impl PriceLevelSnapshotPackage {
+impl<T> PriceLevelSnapshotPackage<T> {
     /// Returns the schema version of this package.
     #[must_use]
     pub fn version(&self) -> u32 {
@@ -308,7 +387,7 @@ impl PriceLevelSnapshotPackage {
 
     /// Returns a reference to the contained snapshot.
     #[must_use]
-    pub fn snapshot(&self) -> &PriceLevelSnapshot {
+    pub fn snapshot(&self) -> &PriceLevelSnapshot<T> {
         &self.snapshot
     }
 
@@ -317,10 +396,17 @@ impl PriceLevelSnapshotPackage {
     pub fn checksum(&self) -> &str {
         &self.checksum
     }
+
+    /// Returns the algorithm the checksum was computed with.
+    #[must_use]
+    pub fn checksum_algorithm(&self) -> ChecksumAlgorithm {
+        self.checksum_algorithm
+    }
 }
 
-impl PriceLevelSnapshotPackage {
-    /// Creates a new snapshot package computing the checksum for the provided snapshot.
+impl<T: Clone + Serialize> PriceLevelSnapshotPackage<T> {
+    /// Creates a new snapshot package computing the SHA-256 checksum for the
+    /// provided snapshot.
     ///
     /// # Errors
     ///
@@ -328,14 +414,36 @@ impl PriceLevelSnapshotPackage {
     /// aggregates overflows a quantity, or [`PriceLevelError::SerializationError`]
     /// if the snapshot payload cannot be encoded while computing its SHA-256
     /// checksum.
-    pub fn new(mut snapshot: PriceLevelSnapshot) -> Result<Self, PriceLevelError> {
+    pub fn new(snapshot: PriceLevelSnapshot<T>) -> Result<Self, PriceLevelError> {
+        Self::with_checksum_algorithm(snapshot, ChecksumAlgorithm::Sha256)
+    }
+
+    /// Creates a new snapshot package whose checksum is computed with
+    /// `algorithm`. The algorithm is recorded in the package header so
+    /// [`Self::validate`] re-checks it the same way.
+    ///
+    /// Prefer [`ChecksumAlgorithm::Crc32c`] or [`ChecksumAlgorithm::XxHash64`]
+    /// when the package only needs protection against accidental corruption
+    /// and restore latency matters; keep the default SHA-256 when it must be
+    /// tamper-evident.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if refreshing the snapshot
+    /// aggregates overflows a quantity, or [`PriceLevelError::SerializationError`]
+    /// if the snapshot payload cannot be encoded while computing its checksum.
+    pub fn with_checksum_algorithm(
+        mut snapshot: PriceLevelSnapshot<T>,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<Self, PriceLevelError> {
         snapshot.refresh_aggregates()?;
 
-        let checksum = Self::compute_checksum(&snapshot)?;
+        let checksum = Self::compute_checksum(&snapshot, algorithm)?;
 
         Ok(Self {
             version: SNAPSHOT_FORMAT_VERSION,
             snapshot,
+            checksum_algorithm: algorithm,
             checksum,
         })
     }
@@ -347,9 +455,7 @@ impl PriceLevelSnapshotPackage {
     /// Returns [`PriceLevelError::SerializationError`] if the package cannot be
     /// encoded to a JSON string.
     pub fn to_json(&self) -> Result<String, PriceLevelError> {
-        serde_json::to_string(self).map_err(|error| PriceLevelError::SerializationError {
-            message: error.to_string(),
-        })
+        serde_json::to_string(self).map_err(PriceLevelError::serialization)
     }
 
     /// Deserializes a package from JSON.
@@ -360,10 +466,15 @@ impl PriceLevelSnapshotPackage {
     /// valid JSON representation of a snapshot package. The returned package is
     /// not yet checksum-validated; call [`Self::validate`] or
     /// [`Self::into_snapshot`] to verify integrity.
-    pub fn from_json(data: &str) -> Result<Self, PriceLevelError> {
-        serde_json::from_str(data).map_err(|error| PriceLevelError::DeserializationError {
-            message: error.to_string(),
-        })
+    ///
+    /// Decodes within [`ParseLimits::DEFAULT`](crate::parse::ParseLimits::DEFAULT),
+    /// returning [`PriceLevelError::ParseError`] for an input over a limit;
+    /// see [`crate::parse::snapshot_package`].
+    pub fn from_json(data: &str) -> Result<Self, PriceLevelError>
+    where
+        T: DeserializeOwned,
+    {
+        crate::parse::snapshot_package(data, &crate::parse::ParseLimits::DEFAULT)
     }
 
     /// Validates the checksum contained in the package against the serialized snapshot data.
@@ -373,8 +484,9 @@ impl PriceLevelSnapshotPackage {
     /// Returns [`PriceLevelError::InvalidOperation`] if the package's format
     /// version is not `SNAPSHOT_FORMAT_VERSION`, [`PriceLevelError::SerializationError`]
     /// if the snapshot payload cannot be re-encoded to recompute the checksum,
-    /// and [`PriceLevelError::ChecksumMismatch`] if the recomputed SHA-256
-    /// checksum does not match the stored one (tampered or corrupted snapshot).
+    /// and [`PriceLevelError::ChecksumMismatch`] if the checksum recomputed with
+    /// [`Self::checksum_algorithm`] does not match the stored one (tampered or
+    /// corrupted snapshot).
     // Snapshot restoration / validation is a cold path: keep it out of line.
     #[inline(never)]
     pub fn validate(&self) -> Result<(), PriceLevelError> {
@@ -387,7 +499,7 @@ impl PriceLevelSnapshotPackage {
             });
         }
 
-        let computed = Self::compute_checksum(&self.snapshot)?;
+        let computed = Self::compute_checksum(&self.snapshot, self.checksum_algorithm)?;
         if computed != self.checksum {
             return Err(PriceLevelError::ChecksumMismatch {
                 expected: self.checksum.clone(),
@@ -398,6 +510,36 @@ impl PriceLevelSnapshotPackage {
         Ok(())
     }
 
+    /// Checks the package without restoring it and returns every finding —
+    /// unsupported version, checksum mismatch, duplicate order ids, orders at
+    /// the wrong price or side, and recorded totals that disagree with the
+    /// orders — rather than stopping at the first like [`Self::validate`].
+    // Cold path, like `validate`.
+    #[inline(never)]
+    #[must_use]
+    pub fn validation_report(&self) -> SnapshotValidationReport {
+        let mut report = SnapshotValidationReport::new(self.version, self.checksum_algorithm);
+        if !SUPPORTED_SNAPSHOT_VERSIONS.contains(&self.version) {
+            report.push(SnapshotFinding::UnsupportedVersion {
+                version: self.version,
+            });
+        }
+        match Self::compute_checksum(&self.snapshot, self.checksum_algorithm) {
+            Ok(computed) if computed != self.checksum => {
+                report.push(SnapshotFinding::ChecksumMismatch {
+                    expected: self.checksum.clone(),
+                    actual: computed,
+                });
+            }
+            Ok(_) => {}
+            Err(error) => report.push(SnapshotFinding::ChecksumUnavailable {
+                message: error.to_string(),
+            }),
+        }
+        report.inspect_snapshot(&self.snapshot);
+        report
+    }
+
     /// Consumes the package after validating the checksum and returns the contained snapshot.
     ///
     /// # Errors
@@ -407,41 +549,23 @@ impl PriceLevelSnapshotPackage {
     /// [`PriceLevelError::SerializationError`] if the payload cannot be
     /// re-encoded, and [`PriceLevelError::ChecksumMismatch`] if the stored
     /// checksum does not match the recomputed one.
-    pub fn into_snapshot(self) -> Result<PriceLevelSnapshot, PriceLevelError> {
+    pub fn into_snapshot(self) -> Result<PriceLevelSnapshot<T>, PriceLevelError> {
         self.validate()?;
         Ok(self.snapshot)
     }
 
     #[inline(never)]
-    fn compute_checksum(snapshot: &PriceLevelSnapshot) -> Result<String, PriceLevelError> {
-        use std::fmt::Write as _;
+    fn compute_checksum(
+        snapshot: &PriceLevelSnapshot<T>,
+        algorithm: ChecksumAlgorithm,
+    ) -> Result<String, PriceLevelError> {
+        let payload = serde_json::to_vec(snapshot).map_err(PriceLevelError::serialization)?;
 
-        let payload =
-            serde_json::to_vec(snapshot).map_err(|error| PriceLevelError::SerializationError {
-                message: error.to_string(),
-            })?;
-
-        let mut hasher = Sha256::new();
-        hasher.update(payload);
-
-        // `digest` 0.11 returns the digest as a `hybrid_array::Array`, which —
-        // unlike the `generic_array::GenericArray` from 0.10 — does not
-        // implement `LowerHex`. Encode the raw SHA-256 bytes to lowercase hex
-        // by hand. The bytes are defined by the algorithm and are unchanged, so
-        // the produced checksum string is byte-identical to the 0.10 output.
-        let checksum_bytes = hasher.finalize();
-        let mut checksum = String::with_capacity(checksum_bytes.len() * 2);
-        for byte in checksum_bytes {
-            // Writing to a `String` is infallible; `{byte:02x}` is the same
-            // lowercase, zero-padded, two-hex-digits-per-byte encoding the
-            // previous `format!("{:x}", checksum_bytes)` produced.
-            let _ = write!(checksum, "{byte:02x}");
-        }
-        Ok(checksum)
+        Ok(algorithm.digest_hex(&payload))
     }
 }
 
-impl Serialize for PriceLevelSnapshot {
+impl<T: Serialize> Serialize for PriceLevelSnapshot<T> {
     // Snapshot serialization is a cold path (taken/restored, not per-match):
     // keep it out of line.
     #[inline(never)]
@@ -449,7 +573,12 @@ impl Serialize for PriceLevelSnapshot {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("PriceLevelSnapshot", 6)?;
+        // `mutation_sequence` is written only when set, so a snapshot without
+        // one (every pre-v4 payload) re-encodes byte-identically and its
+        // checksum still matches.
+        let sequenced = self.mutation_sequence != 0;
+        let field_count = if sequenced { 7 } else { 6 };
+        let mut state = serializer.serialize_struct("PriceLevelSnapshot", field_count)?;
 
         state.serialize_field("price", &self.price)?;
         state.serialize_field("visible_quantity", &self.visible_quantity)?;
@@ -457,21 +586,24 @@ impl Serialize for PriceLevelSnapshot {
         state.serialize_field("order_count", &self.order_count)?;
 
         // Serialize the borrowed orders rather than deep-copying every
-        // `OrderType<()>` by value (issue #72). `Serialize for &T` forwards to
-        // `T`'s impl, so a sequence of `&OrderType<()>` produces byte-identical
-        // output to the previous `Vec<OrderType<()>>` — the checksum and
+        // `OrderType<T>` by value (issue #72). `Serialize for &T` forwards to
+        // `T`'s impl, so a sequence of `&OrderType<T>` produces byte-identical
+        // output to the previous `Vec<OrderType<T>>` — the checksum and
         // round-trip are unchanged — while only copying `Arc` pointers, not the
         // whole order payload.
-        let borrowed_orders: Vec<&OrderType<()>> = self.orders.iter().map(Arc::as_ref).collect();
+        let borrowed_orders: Vec<&OrderType<T>> = self.orders.iter().map(Arc::as_ref).collect();
 
         state.serialize_field("orders", &borrowed_orders)?;
         state.serialize_field("statistics", &self.statistics)?;
+        if sequenced {
+            state.serialize_field("mutation_sequence", &self.mutation_sequence)?;
+        }
 
         state.end()
     }
 }
 
-impl<'de> Deserialize<'de> for PriceLevelSnapshot {
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for PriceLevelSnapshot<T> {
     // Snapshot restoration is a cold path (taken/restored, not per-match):
     // keep it out of line.
     #[inline(never)]
@@ -486,6 +618,7 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
             OrderCount,
             Orders,
             Statistics,
+            MutationSequence,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -499,7 +632,7 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
                     type Value = Field;
 
                     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                        formatter.write_str("`price`, `visible_quantity`, `hidden_quantity`, `order_count`, `orders`, or `statistics`")
+                        formatter.write_str("`price`, `visible_quantity`, `hidden_quantity`, `order_count`, `orders`, `statistics`, or `mutation_sequence`")
                     }
 
                     fn visit_str<E>(self, value: &str) -> Result<Field, E>
@@ -513,6 +646,7 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
                             "order_count" => Ok(Field::OrderCount),
                             "orders" => Ok(Field::Orders),
                             "statistics" => Ok(Field::Statistics),
+                            "mutation_sequence" => Ok(Field::MutationSequence),
                             _ => Err(de::Error::unknown_field(
                                 value,
                                 &[
@@ -522,6 +656,7 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
                                     "order_count",
                                     "orders",
                                     "statistics",
+                                    "mutation_sequence",
                                 ],
                             )),
                         }
@@ -532,16 +667,16 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
             }
         }
 
-        struct PriceLevelSnapshotVisitor;
+        struct PriceLevelSnapshotVisitor<T>(PhantomData<fn() -> T>);
 
-        impl<'de> Visitor<'de> for PriceLevelSnapshotVisitor {
-            type Value = PriceLevelSnapshot;
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for PriceLevelSnapshotVisitor<T> {
+            type Value = PriceLevelSnapshot<T>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("struct PriceLevelSnapshot")
             }
 
-            fn visit_map<V>(self, mut map: V) -> Result<PriceLevelSnapshot, V::Error>
+            fn visit_map<V>(self, mut map: V) -> Result<PriceLevelSnapshot<T>, V::Error>
             where
                 V: MapAccess<'de>,
             {
@@ -551,6 +686,7 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
                 let mut order_count = None;
                 let mut orders = None;
                 let mut statistics = None;
+                let mut mutation_sequence = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -582,7 +718,7 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
                             if orders.is_some() {
                                 return Err(de::Error::duplicate_field("orders"));
                             }
-                            let plain_orders: Vec<OrderType<()>> = map.next_value()?;
+                            let plain_orders: Vec<OrderType<T>> = map.next_value()?;
                             orders = Some(plain_orders.into_iter().map(Arc::new).collect());
                         }
                         Field::Statistics => {
@@ -591,6 +727,12 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
                             }
                             statistics = Some(map.next_value()?);
                         }
+                        Field::MutationSequence => {
+                            if mutation_sequence.is_some() {
+                                return Err(de::Error::duplicate_field("mutation_sequence"));
+                            }
+                            mutation_sequence = Some(map.next_value()?);
+                        }
                     }
                 }
 
@@ -609,6 +751,8 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
                 // still rejects unknown fields). A genuine v1 *package* is
                 // rejected up-front by `validate()`'s version check regardless.
                 let statistics = statistics.unwrap_or_default();
+                // Absent before v4, and omitted whenever it is zero.
+                let mutation_sequence = mutation_sequence.unwrap_or_default();
 
                 Ok(PriceLevelSnapshot {
                     price,
@@ -617,6 +761,7 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
                     order_count,
                     orders,
                     statistics,
+                    mutation_sequence,
                 })
             }
         }
@@ -628,12 +773,17 @@ impl<'de> Deserialize<'de> for PriceLevelSnapshot {
             "order_count",
             "orders",
             "statistics",
+            "mutation_sequence",
         ];
-        deserializer.deserialize_struct("PriceLevelSnapshot", FIELDS, PriceLevelSnapshotVisitor)
+        deserializer.deserialize_struct(
+            "PriceLevelSnapshot",
+            FIELDS,
+            PriceLevelSnapshotVisitor(PhantomData),
+        )
     }
 }
 
-impl fmt::Display for PriceLevelSnapshot {
+impl<T> fmt::Display for PriceLevelSnapshot<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -643,31 +793,27 @@ impl fmt::Display for PriceLevelSnapshot {
     }
 }
 
-impl FromStr for PriceLevelSnapshot {
+impl<T> FromStr for PriceLevelSnapshot<T> {
     type Err = PriceLevelError;
 
+    /// Parses leniently within
+    /// [`ParseLimits::DEFAULT`](crate::parse::ParseLimits::DEFAULT); see
+    /// [`crate::parse::snapshot`].
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        crate::parse::snapshot(s, &crate::parse::ParseOptions::DEFAULT)
+    }
+}
+
+impl<T> PriceLevelSnapshot<T> {
+    /// The `FromStr` parser without the input limits.
+    pub(crate) fn parse_with(s: &str, strict: bool) -> Result<Self, PriceLevelError> {
         let parts: Vec<&str> = s.split(':').collect();
         if parts.len() != 2 || parts[0] != "PriceLevelSnapshot" {
             return Err(PriceLevelError::InvalidFormat);
         }
 
-        let fields_str = parts[1];
-        let mut fields = std::collections::HashMap::new();
-
-        for field_pair in fields_str.split(';') {
-            let kv: Vec<&str> = field_pair.split('=').collect();
-            if kv.len() == 2 {
-                fields.insert(kv[0], kv[1]);
-            }
-        }
-
-        let get_field = |field: &str| -> Result<&str, PriceLevelError> {
-            match fields.get(field) {
-                Some(result) => Ok(*result),
-                None => Err(PriceLevelError::MissingField(field.to_string())),
-            }
-        };
+        let fields = crate::parse::RecordFields::split("snapshot", parts[1], strict)?;
+        let get_field = |field: &str| fields.require(field);
 
         let parse_u64 = |field: &str, value: &str| -> Result<u64, PriceLevelError> {
             value
@@ -708,6 +854,7 @@ impl FromStr for PriceLevelSnapshot {
 
         let order_count_str = get_field("order_count")?;
         let order_count = parse_usize("order_count", order_count_str)?;
+        fields.finish()?;
 
         // Create a new snapshot - note that orders and statistics cannot be
         // serialized/deserialized in this simple, human-readable format. Use the
@@ -719,6 +866,7 @@ impl FromStr for PriceLevelSnapshot {
             order_count,
             orders: Vec::new(),
             statistics: PriceLevelStatistics::new(),
+            mutation_sequence: 0,
         })
     }
 }
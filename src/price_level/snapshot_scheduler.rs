@@ -0,0 +1,251 @@
+use crate::price_level::book_snapshot::BookSnapshot;
+use crate::price_level::level::PriceLevel;
+use crate::price_level::snapshot::PriceLevelSnapshot;
+use crate::utils::{Clock, SystemClock, TimestampMs};
+use crossbeam_channel::{Receiver, Sender};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// When a [`SnapshotScheduler`] takes a snapshot.
+///
+/// A snapshot is due once [`Self::with_interval`] has elapsed since the
+/// previous one, or once [`Self::with_mutations`] mutations have been applied
+/// since it, whichever comes first, but never sooner than
+/// [`Self::with_min_interval`] after it. An interval snapshot is skipped while
+/// nothing changed, so an idle level publishes nothing. The first poll is
+/// always due, so a subscriber starts from a full image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SnapshotSchedule {
+    interval: Option<Duration>,
+    mutations: Option<u64>,
+    min_interval: Duration,
+}
+
+impl SnapshotSchedule {
+    /// A schedule with no trigger: only the first poll is due.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot a changed level every `interval`.
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Snapshot after `mutations` mutations. A value of zero is taken as one.
+    #[must_use]
+    pub fn with_mutations(mut self, mutations: u64) -> Self {
+        self.mutations = Some(mutations.max(1));
+        self
+    }
+
+    /// Never snapshot twice within `min_interval`, however many mutations
+    /// arrive: the rate cap of a burst.
+    #[must_use]
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// The interval trigger, if set.
+    #[must_use]
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// The mutation-count trigger, if set.
+    #[must_use]
+    pub fn mutations(&self) -> Option<u64> {
+        self.mutations
+    }
+
+    /// The shortest gap between two snapshots.
+    #[must_use]
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+}
+
+/// Where a scheduler delivers its snapshots.
+enum Sink<S> {
+    Callback(Box<dyn FnMut(S) + Send>),
+    Channel(Sender<S>),
+}
+
+/// Takes snapshots of a level, or of a book of levels, on a
+/// [`SnapshotSchedule`], so a market-data publisher need not hand-roll timers
+/// around [`PriceLevel::snapshot`].
+///
+/// The scheduler spawns nothing: the publisher calls [`Self::poll`] (or
+/// `poll_book` for a [`BookSnapshot`] scheduler) from its own loop, as often
+/// as it likes, and a snapshot is taken only when one is due. Mutations
+/// between two polls coalesce into the one snapshot of the latest state.
+/// Snapshots go to a callback ([`Self::with_callback`]) or a bounded channel
+/// ([`Self::with_channel`]); a full or disconnected channel skips the
+/// snapshot without taking it, and the next poll tries again.
+///
+/// Time comes from the scheduler's own clock, the wall clock unless
+/// [`Self::with_clock`] sets one. Mutations are counted from
+/// [`PriceLevel::mutation_sequence`] for a level, and from the book sequence
+/// the caller passes for a book.
+pub struct SnapshotScheduler<S> {
+    schedule: SnapshotSchedule,
+    sink: Sink<S>,
+    clock: Arc<dyn Clock>,
+    /// Time and sequence of the last delivered snapshot.
+    last: Option<(TimestampMs, u64)>,
+    delivered: u64,
+    skipped: u64,
+}
+
+impl<S> fmt::Debug for SnapshotScheduler<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapshotScheduler")
+            .field("schedule", &self.schedule)
+            .field("clock", &self.clock)
+            .field("last", &self.last)
+            .field("delivered", &self.delivered)
+            .field("skipped", &self.skipped)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> SnapshotScheduler<S> {
+    fn with_sink(schedule: SnapshotSchedule, sink: Sink<S>) -> Self {
+        Self {
+            schedule,
+            sink,
+            clock: Arc::new(SystemClock::new()),
+            last: None,
+            delivered: 0,
+            skipped: 0,
+        }
+    }
+
+    /// A scheduler handing each snapshot to `callback`, on the polling
+    /// thread.
+    #[must_use]
+    pub fn with_callback(
+        schedule: SnapshotSchedule,
+        callback: impl FnMut(S) + Send + 'static,
+    ) -> Self {
+        Self::with_sink(schedule, Sink::Callback(Box::new(callback)))
+    }
+
+    /// A scheduler sending snapshots down a channel holding at most
+    /// `capacity` of them (at least one), with its receiving end.
+    #[must_use]
+    pub fn with_channel(schedule: SnapshotSchedule, capacity: usize) -> (Self, Receiver<S>) {
+        let (sender, receiver) = crossbeam_channel::bounded(capacity.max(1));
+        (Self::with_sink(schedule, Sink::Channel(sender)), receiver)
+    }
+
+    /// Read time from `clock` instead of the wall clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The schedule.
+    #[must_use]
+    pub fn schedule(&self) -> SnapshotSchedule {
+        self.schedule
+    }
+
+    /// Snapshots delivered.
+    #[must_use]
+    pub fn delivered(&self) -> u64 {
+        self.delivered
+    }
+
+    /// Due snapshots skipped because the channel was full or disconnected.
+    #[must_use]
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Time of the last delivered snapshot, `None` before the first.
+    #[must_use]
+    pub fn last_snapshot_at(&self) -> Option<TimestampMs> {
+        self.last.map(|(at, _)| at)
+    }
+
+    /// Forget the last snapshot, so the next poll is due: for a subscriber
+    /// that asks for a fresh image.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// Whether a snapshot is due at `now` for a source at `sequence`.
+    fn is_due(&self, now: TimestampMs, sequence: u64) -> bool {
+        let Some((at, last_sequence)) = self.last else {
+            return true;
+        };
+        let elapsed = Duration::from_millis(now.as_u64().saturating_sub(at.as_u64()));
+        if elapsed < self.schedule.min_interval {
+            return false;
+        }
+        let mutations = sequence.wrapping_sub(last_sequence);
+        let by_count = self
+            .schedule
+            .mutations
+            .is_some_and(|every| mutations >= every);
+        let by_time = mutations > 0
+            && self
+                .schedule
+                .interval
+                .is_some_and(|interval| elapsed >= interval);
+        by_count || by_time
+    }
+
+    /// Take and deliver a snapshot if one is due at `sequence`.
+    fn poll_with(&mut self, sequence: u64, take: impl FnOnce(TimestampMs) -> S) -> bool {
+        let now = self.clock.now();
+        if !self.is_due(now, sequence) {
+            return false;
+        }
+        match &mut self.sink {
+            Sink::Callback(callback) => callback(take(now)),
+            Sink::Channel(sender) => {
+                // Check for room before taking the snapshot, so a slow
+                // consumer costs no wasted captures.
+                if sender.is_full() || sender.try_send(take(now)).is_err() {
+                    self.skipped += 1;
+                    return false;
+                }
+            }
+        }
+        self.last = Some((now, sequence));
+        self.delivered += 1;
+        true
+    }
+}
+
+impl<T: Clone> SnapshotScheduler<PriceLevelSnapshot<T>> {
+    /// Deliver a snapshot of `level` if one is due. Returns whether one was
+    /// delivered.
+    pub fn poll(&mut self, level: &PriceLevel<T>) -> bool {
+        self.poll_with(level.mutation_sequence(), |_| level.snapshot())
+    }
+}
+
+impl<T: Clone> SnapshotScheduler<BookSnapshot<T>> {
+    /// Deliver a [`BookSnapshot::capture`] of `levels` if one is due, stamped
+    /// with the scheduler's clock. `sequence` is the book's own, increasing
+    /// by one per mutation; the difference from the last snapshot's counts
+    /// the mutations. Returns whether a snapshot was delivered.
+    pub fn poll_book<'a, I>(&mut self, symbol: &str, sequence: u64, levels: I) -> bool
+    where
+        T: 'a,
+        I: IntoIterator<Item = &'a PriceLevel<T>>,
+    {
+        self.poll_with(sequence, |now| {
+            BookSnapshot::capture(symbol, sequence, now, levels)
+        })
+    }
+}
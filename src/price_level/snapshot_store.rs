@@ -0,0 +1,151 @@
+use crate::errors::PriceLevelError;
+use crate::price_level::level::PriceLevel;
+use crate::price_level::multi_book::SymbolKey;
+use crate::price_level::snapshot::PriceLevelSnapshotPackage;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where a [`SnapshotStore`] files a package: the level's symbol and price,
+/// and the level's mutation sequence when the snapshot was taken.
+///
+/// Keys order by symbol, then price, then sequence, so a level's packages
+/// are contiguous and its latest is the greatest.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SnapshotKey {
+    /// The instrument the level belongs to.
+    pub symbol: SymbolKey,
+    /// The level's price.
+    pub price: u128,
+    /// The level's mutation sequence at the snapshot.
+    pub sequence: u64,
+}
+
+impl SnapshotKey {
+    /// A key for `symbol`'s level at `price`, as of `sequence`.
+    #[must_use]
+    pub fn new(symbol: impl Into<SymbolKey>, price: u128, sequence: u64) -> Self {
+        Self {
+            symbol: symbol.into(),
+            price,
+            sequence,
+        }
+    }
+}
+
+impl fmt::Display for SnapshotKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}#{}", self.symbol, self.price, self.sequence)
+    }
+}
+
+/// Which packages [`SnapshotStore::prune`] removes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PrunePolicy {
+    /// Keep each level's `n` latest packages and remove the rest. `0`
+    /// empties the store.
+    KeepLatest(usize),
+    /// Remove every package taken before the given sequence.
+    BelowSequence(u64),
+}
+
+/// Durable storage of versioned snapshot packages, for recovery that
+/// resumes from a level's last saved state rather than from scratch.
+///
+/// A store keeps whole [`PriceLevelSnapshotPackage`]s under a
+/// [`SnapshotKey`], so each package carries its format version and checksum
+/// and is verified when restored. Storing a key a second time replaces its
+/// package. With the `redb` feature, `RedbSnapshotStore` implements it on an
+/// embedded database.
+pub trait SnapshotStore<T: Clone + Serialize + DeserializeOwned> {
+    /// Store `package` under `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if the package cannot
+    /// be encoded or written.
+    fn put(
+        &self,
+        key: &SnapshotKey,
+        package: &PriceLevelSnapshotPackage<T>,
+    ) -> Result<(), PriceLevelError>;
+
+    /// The package stored under `key`, not yet checksum-validated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::DeserializationError`] if the store cannot
+    /// be read or the package cannot be decoded.
+    fn get(
+        &self,
+        key: &SnapshotKey,
+    ) -> Result<Option<PriceLevelSnapshotPackage<T>>, PriceLevelError>;
+
+    /// The package of `symbol`'s level at `price` with the greatest
+    /// sequence, and its key.
+    ///
+    /// # Errors
+    ///
+    /// Returns what [`Self::get`] returns.
+    fn latest(
+        &self,
+        symbol: &SymbolKey,
+        price: u128,
+    ) -> Result<Option<(SnapshotKey, PriceLevelSnapshotPackage<T>)>, PriceLevelError>;
+
+    /// Every stored key, in key order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::DeserializationError`] if the store cannot
+    /// be read.
+    fn keys(&self) -> Result<Vec<SnapshotKey>, PriceLevelError>;
+
+    /// Remove the packages `policy` selects and return how many went.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if the store cannot
+    /// be updated; nothing is removed then.
+    fn prune(&self, policy: PrunePolicy) -> Result<usize, PriceLevelError>;
+
+    /// Snapshot `level` of `symbol` and store the package under the level's
+    /// price and current mutation sequence, returning that key.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`PriceLevel::snapshot_package`] and
+    /// [`Self::put`].
+    fn put_level(
+        &self,
+        symbol: &SymbolKey,
+        level: &PriceLevel<T>,
+    ) -> Result<SnapshotKey, PriceLevelError> {
+        let package = level.snapshot_package()?;
+        let key = SnapshotKey {
+            symbol: symbol.clone(),
+            price: level.price(),
+            sequence: package.snapshot().mutation_sequence(),
+        };
+        self.put(&key, &package)?;
+        Ok(key)
+    }
+
+    /// Rebuild `symbol`'s level at `price` from its latest package, after
+    /// verifying the package's checksum.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Self::latest`] and
+    /// [`PriceLevel::from_snapshot_package`], notably
+    /// [`PriceLevelError::ChecksumMismatch`] for a corrupted package.
+    fn restore_latest(
+        &self,
+        symbol: &SymbolKey,
+        price: u128,
+    ) -> Result<Option<PriceLevel<T>>, PriceLevelError> {
+        self.latest(symbol, price)?
+            .map(|(_, package)| PriceLevel::from_snapshot_package(package))
+            .transpose()
+    }
+}
@@ -0,0 +1,310 @@
+use crate::orders::{Id, OrderType, Side};
+use crate::price_level::checksum::ChecksumAlgorithm;
+use crate::price_level::snapshot::PriceLevelSnapshot;
+use crate::utils::{Price, Quantity};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+/// One problem found by
+/// [`PriceLevelSnapshotPackage::validation_report`](crate::PriceLevelSnapshotPackage::validation_report).
+///
+/// Most findings make
+/// [`PriceLevel::from_snapshot_package`](crate::PriceLevel::from_snapshot_package)
+/// fail (see [`Self::blocks_restore`]). An aggregate mismatch does not — the
+/// restore recomputes the totals from the orders — but it still means the
+/// payload was not written by this crate unaltered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SnapshotFinding {
+    /// The package's format version is not one this crate restores.
+    UnsupportedVersion {
+        /// The version recorded in the package.
+        version: u32,
+    },
+    /// The checksum recomputed over the snapshot does not match the stored one.
+    ChecksumMismatch {
+        /// The checksum stored in the package.
+        expected: String,
+        /// The checksum recomputed from the snapshot payload.
+        actual: String,
+    },
+    /// The snapshot payload could not be re-encoded to recompute its checksum.
+    ChecksumUnavailable {
+        /// The encoder's error message.
+        message: String,
+    },
+    /// The recorded order count differs from the number of orders.
+    OrderCountMismatch {
+        /// The count recorded in the snapshot.
+        recorded: usize,
+        /// The number of orders the snapshot holds.
+        actual: usize,
+    },
+    /// The recorded visible quantity differs from the sum over the orders.
+    VisibleQuantityMismatch {
+        /// The total recorded in the snapshot.
+        recorded: Quantity,
+        /// The sum of the orders' visible quantities.
+        actual: Quantity,
+    },
+    /// The recorded hidden quantity differs from the sum over the orders.
+    HiddenQuantityMismatch {
+        /// The total recorded in the snapshot.
+        recorded: Quantity,
+        /// The sum of the orders' hidden quantities.
+        actual: Quantity,
+    },
+    /// Summing the orders' visible or hidden quantities overflows `u64`, so no
+    /// order-derived total exists to compare against.
+    TotalQuantityOverflow,
+    /// An order's own visible + hidden total overflows `u64`.
+    OrderQuantityOverflow {
+        /// The offending order.
+        order_id: Id,
+    },
+    /// An order id appears more than once; reported once per repeat.
+    DuplicateOrderId {
+        /// The repeated id.
+        order_id: Id,
+    },
+    /// An order rests at a price other than the level's.
+    PriceMismatch {
+        /// The offending order.
+        order_id: Id,
+        /// The order's price.
+        price: Price,
+    },
+    /// An order's side differs from the side of the level's first order.
+    SideMismatch {
+        /// The offending order.
+        order_id: Id,
+        /// The order's side.
+        side: Side,
+        /// The side of the level's first order.
+        level_side: Side,
+    },
+}
+
+impl SnapshotFinding {
+    /// Whether restoring the package would fail on this finding.
+    #[must_use]
+    pub fn blocks_restore(&self) -> bool {
+        !matches!(
+            self,
+            Self::OrderCountMismatch { .. }
+                | Self::VisibleQuantityMismatch { .. }
+                | Self::HiddenQuantityMismatch { .. }
+        )
+    }
+}
+
+impl fmt::Display for SnapshotFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion { version } => {
+                write!(f, "unsupported snapshot version {version}")
+            }
+            Self::ChecksumMismatch { expected, actual } => {
+                write!(f, "checksum mismatch: expected {expected}, got {actual}")
+            }
+            Self::ChecksumUnavailable { message } => {
+                write!(f, "checksum could not be recomputed: {message}")
+            }
+            Self::OrderCountMismatch { recorded, actual } => {
+                write!(
+                    f,
+                    "order count {recorded} recorded, {actual} orders present"
+                )
+            }
+            Self::VisibleQuantityMismatch { recorded, actual } => {
+                write!(
+                    f,
+                    "visible quantity {recorded} recorded, orders sum to {actual}"
+                )
+            }
+            Self::HiddenQuantityMismatch { recorded, actual } => {
+                write!(
+                    f,
+                    "hidden quantity {recorded} recorded, orders sum to {actual}"
+                )
+            }
+            Self::TotalQuantityOverflow => write!(f, "order quantities overflow u64"),
+            Self::OrderQuantityOverflow { order_id } => {
+                write!(f, "order {order_id} total quantity overflows u64")
+            }
+            Self::DuplicateOrderId { order_id } => write!(f, "duplicate order id {order_id}"),
+            Self::PriceMismatch { order_id, price } => {
+                write!(f, "order {order_id} rests at price {price}")
+            }
+            Self::SideMismatch {
+                order_id,
+                side,
+                level_side,
+            } => write!(f, "order {order_id} is {side}, level is {level_side}"),
+        }
+    }
+}
+
+/// Structured findings from checking a snapshot package without restoring it,
+/// for pre-flight checks in recovery tooling.
+///
+/// Unlike [`PriceLevelSnapshotPackage::validate`](crate::PriceLevelSnapshotPackage::validate),
+/// which stops at the first failure, the report collects every finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotValidationReport {
+    /// The package's format version.
+    version: u32,
+    /// The algorithm the package checksum was computed with.
+    checksum_algorithm: ChecksumAlgorithm,
+    /// The number of orders the snapshot holds.
+    order_count: usize,
+    /// Every finding, in the order the checks ran.
+    findings: Vec<SnapshotFinding>,
+}
+
+impl SnapshotValidationReport {
+    pub(crate) fn new(version: u32, checksum_algorithm: ChecksumAlgorithm) -> Self {
+        Self {
+            version,
+            checksum_algorithm,
+            order_count: 0,
+            findings: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, finding: SnapshotFinding) {
+        self.findings.push(finding);
+    }
+
+    /// Runs the order-level checks `PriceLevel::from_snapshot` enforces, plus
+    /// the recorded aggregates against the order sums.
+    pub(crate) fn inspect_snapshot<T: Clone>(&mut self, snapshot: &PriceLevelSnapshot<T>) {
+        let orders = snapshot.orders();
+        self.order_count = orders.len();
+        if snapshot.order_count() != orders.len() {
+            self.push(SnapshotFinding::OrderCountMismatch {
+                recorded: snapshot.order_count(),
+                actual: orders.len(),
+            });
+        }
+
+        let mut seen = HashSet::with_capacity(orders.len());
+        let mut level_side = None;
+        let mut visible_total = Some(0u64);
+        let mut hidden_total = Some(0u64);
+        for order in orders {
+            let order_id = order.id();
+            if !seen.insert(order_id) {
+                self.push(SnapshotFinding::DuplicateOrderId { order_id });
+            }
+            if order.price() != snapshot.price() {
+                self.push(SnapshotFinding::PriceMismatch {
+                    order_id,
+                    price: order.price(),
+                });
+            }
+            match level_side {
+                None => level_side = Some(order.side()),
+                Some(side) if side != order.side() => {
+                    self.push(SnapshotFinding::SideMismatch {
+                        order_id,
+                        side: order.side(),
+                        level_side: side,
+                    });
+                }
+                Some(_) => {}
+            }
+            self.inspect_quantities(order, &mut visible_total, &mut hidden_total);
+        }
+
+        match (visible_total, hidden_total) {
+            (Some(visible), Some(hidden)) => {
+                if snapshot.visible_quantity().as_u64() != visible {
+                    self.push(SnapshotFinding::VisibleQuantityMismatch {
+                        recorded: snapshot.visible_quantity(),
+                        actual: Quantity::new(visible),
+                    });
+                }
+                if snapshot.hidden_quantity().as_u64() != hidden {
+                    self.push(SnapshotFinding::HiddenQuantityMismatch {
+                        recorded: snapshot.hidden_quantity(),
+                        actual: Quantity::new(hidden),
+                    });
+                }
+            }
+            _ => self.push(SnapshotFinding::TotalQuantityOverflow),
+        }
+    }
+
+    fn inspect_quantities<T: Clone>(
+        &mut self,
+        order: &OrderType<T>,
+        visible_total: &mut Option<u64>,
+        hidden_total: &mut Option<u64>,
+    ) {
+        let visible = order.working_visible_quantity().as_u64();
+        let hidden = order.hidden_quantity().as_u64();
+        if visible.checked_add(hidden).is_none() {
+            self.push(SnapshotFinding::OrderQuantityOverflow {
+                order_id: order.id(),
+            });
+        }
+        *visible_total = visible_total.and_then(|total| total.checked_add(visible));
+        *hidden_total = hidden_total.and_then(|total| total.checked_add(hidden));
+    }
+
+    /// Returns the package's format version.
+    #[must_use]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the algorithm the package checksum was computed with.
+    #[must_use]
+    pub fn checksum_algorithm(&self) -> ChecksumAlgorithm {
+        self.checksum_algorithm
+    }
+
+    /// Returns the number of orders the snapshot holds.
+    #[must_use]
+    pub fn order_count(&self) -> usize {
+        self.order_count
+    }
+
+    /// Returns every finding, in the order the checks ran.
+    #[must_use]
+    pub fn findings(&self) -> &[SnapshotFinding] {
+        &self.findings
+    }
+
+    /// Whether the package passed every check.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Whether [`PriceLevel::from_snapshot_package`](crate::PriceLevel::from_snapshot_package)
+    /// would succeed: no finding [blocks restore](SnapshotFinding::blocks_restore).
+    #[must_use]
+    pub fn is_restorable(&self) -> bool {
+        !self.findings.iter().any(SnapshotFinding::blocks_restore)
+    }
+}
+
+impl fmt::Display for SnapshotValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "snapshot v{} ({}, {} orders): {} finding(s)",
+            self.version,
+            self.checksum_algorithm,
+            self.order_count,
+            self.findings.len()
+        )?;
+        for finding in &self.findings {
+            write!(f, "\n- {finding}")?;
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,20 @@
 use crate::errors::PriceLevelError;
+use crate::price_level::latency::{
+    LatencyHistogram, LatencyOperation, LatencyRecorder, LatencyTimer,
+};
+use crate::price_level::order_age::{OrderAgeHistogram, OrderAgeOutcome, OrderAges};
+use crate::price_level::statistics_alerts::{AlertCondition, AlertRules, StatisticsAlert};
+use crate::price_level::statistics_analytics::{OrderFlow, StatisticsAnalytics};
+use crate::price_level::statistics_window::{ExecutionWindow, StatisticsWindow};
+use crate::utils::{Clock, SystemClock, TimestampMs};
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
 
 /// Tracks performance statistics for a price level.
 ///
@@ -34,10 +43,23 @@ use std::time::{SystemTime, UNIX_EPOCH};
 /// ([`record_execution`](Self::record_execution) / [`reset`](Self::reset)) and
 /// back to even on its exit, both `Release`; a reader loads it `Acquire`, copies
 /// the fields, `Acquire`-fences, re-loads it, and retries if it changed or was
-/// odd. Writers are serialized by the engine model (one matcher per level +
-/// `reset`'s quiescence contract), which the seqlock assumes. The lone
-/// read-modify-write loop in [`checked_fetch_add_u64`](Self::checked_fetch_add_u64)
-/// is a standard `compare_exchange_weak` CAS retry.
+/// odd. A writer enters only while holding the `stats_writer` mutex, so two
+/// writers — concurrent matchers recording on the same level, or a
+/// [`reset`](Self::reset) racing a recording — are mutually exclusive and never
+/// interleave, and a writer that finds another in its section parks instead of
+/// spinning on the sequence. The remaining read-modify-write loop in
+/// [`checked_fetch_add_u64`](Self::checked_fetch_add_u64) is a standard
+/// `compare_exchange_weak` CAS retry.
+///
+/// # Windowed metrics
+///
+/// Besides the lifetime aggregates, every accepted execution is added to a
+/// ring of one-second buckets covering the last minute, keyed by the
+/// execution timestamp. [`stats_window`](Self::stats_window) sums the buckets
+/// overlapping a trailing window and reports per-second rates, so a dashboard
+/// does not have to diff the lifetime counters itself. The ring is runtime
+/// observability only: it is not cloned, serialized or checksummed, and a
+/// restored level starts with an empty window.
 #[derive(Debug)]
 pub struct PriceLevelStatistics {
     /// Number of orders added
@@ -79,26 +101,69 @@ pub struct PriceLevelStatistics {
     /// / [`reset`](Self::reset)) is mutating. Purely internal — never serialized
     /// — so a restored / cloned value starts even (0).
     stats_seq: AtomicU64,
+
+    /// Serializes the seqlock writers. Held for the whole write section, so
+    /// a contending writer parks on it rather than spinning. Never serialized.
+    stats_writer: Mutex<()>,
+
+    /// Trailing per-second execution totals behind
+    /// [`stats_window`](Self::stats_window). Written inside the seqlock write
+    /// section; never cloned or serialized.
+    window: ExecutionWindow,
+
+    /// Alert conditions registered with [`add_alert`](Self::add_alert).
+    /// Runtime configuration: never cloned or serialized.
+    alerts: AlertRules,
+
+    /// Source of the current time for the reads that are not handed a
+    /// timestamp. Runtime configuration: a clone or a restored copy reads the
+    /// [`SystemClock`].
+    clock: Arc<dyn Clock>,
+
+    /// Per-call latency histograms behind
+    /// [`latency_histogram`](Self::latency_histogram). Off until enabled;
+    /// never cloned or serialized.
+    latency: LatencyRecorder,
+
+    /// Quantities added, cancelled and executed, and the added-size
+    /// distribution, behind [`analytics`](Self::analytics). Never cloned or
+    /// serialized.
+    flow: OrderFlow,
+
+    /// How long orders rested before each fill or removal, behind
+    /// [`order_age_histogram`](Self::order_age_histogram). Cloned and
+    /// serialized, outside the seqlock.
+    ages: OrderAges,
 }
 
 /// RAII guard bracketing a statistics WRITE section for the seqlock (issue
-/// #129). Constructing it bumps `stats_seq` to odd; dropping it bumps back to
-/// even, so a concurrent multi-field reader retries if it overlapped either
-/// increment. Using a guard keeps the section correct across the early returns
-/// in [`PriceLevelStatistics::record_execution`].
+/// #129). Constructing it takes the writer mutex — parking while another
+/// writer is in its section — and bumps `stats_seq` from even to odd;
+/// dropping it bumps back to even and releases the mutex, so a concurrent
+/// multi-field reader retries if it overlapped either increment. Using a
+/// guard keeps the section correct across the early returns in
+/// [`PriceLevelStatistics::record_execution`].
 struct WriteSeqGuard<'a> {
     seq: &'a AtomicU64,
+    // Dropped after `Drop::drop` has closed the section.
+    _writer: MutexGuard<'a, ()>,
 }
 
 impl<'a> WriteSeqGuard<'a> {
     #[inline]
-    fn new(seq: &'a AtomicU64) -> Self {
-        // Enter: even -> odd. `Relaxed` RMW plus a `Release` fence so the field
-        // writes that follow cannot be reordered before the odd marker a reader
-        // watches for.
+    fn new(writer: &'a Mutex<()>, seq: &'a AtomicU64) -> Self {
+        // The mutex guards no data, so a writer that panicked mid-section
+        // leaves nothing to recover; the sequence was closed by its guard.
+        let writer = writer.lock().unwrap_or_else(PoisonError::into_inner);
+        // Enter: even -> odd. The mutex orders this section after the previous
+        // writer's; the `Release` fence keeps the field writes that follow from
+        // being reordered before the odd marker a reader watches for.
         seq.fetch_add(1, Ordering::Relaxed);
         std::sync::atomic::fence(Ordering::Release);
-        Self { seq }
+        Self {
+            seq,
+            _writer: writer,
+        }
     }
 }
 
@@ -128,7 +193,7 @@ struct StatsData {
 }
 
 impl PriceLevelStatistics {
-    fn checked_fetch_add_u64(
+    pub(crate) fn checked_fetch_add_u64(
         target: &AtomicU64,
         value: u64,
         field: &str,
@@ -208,8 +273,8 @@ impl PriceLevelStatistics {
     /// # Liveness
     ///
     /// The loop's work PER attempt is bounded (one sequence load + a nine-field
-    /// copy), and it converges under the advisory writer-serialization contract
-    /// (one matcher per level + `reset`'s quiescence): a writer holds the section
+    /// copy), and it converges because writers are mutually exclusive (see the
+    /// write guard): a writer holds the section
     /// for only a short, allocation-free burst before dropping the guard back to
     /// even, and `record_execution` is finite, so a reader exits on the first
     /// iteration when uncontended and otherwise as soon as recording quiesces
@@ -259,28 +324,46 @@ impl PriceLevelStatistics {
             sum_waiting_time: AtomicU64::new(data.sum_waiting_time),
             stats_degraded: AtomicBool::new(data.stats_degraded),
             stats_seq: AtomicU64::new(0),
+            stats_writer: Mutex::new(()),
+            window: ExecutionWindow::default(),
+            alerts: AlertRules::default(),
+            clock: Arc::new(SystemClock::new()),
+            latency: LatencyRecorder::default(),
+            flow: OrderFlow::default(),
+            ages: OrderAges::default(),
         }
     }
 
-    #[inline]
-    fn current_timestamp_milliseconds() -> Result<u64, PriceLevelError> {
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|error| PriceLevelError::InvalidOperation {
-                message: format!("system clock error while reading unix time: {error}"),
-            })
-            .map(|duration| duration.as_millis() as u64)
+    /// A [`Clone`] of these statistics reading time from `clock`, with
+    /// latency tracking as `latency_tracking`.
+    pub(crate) fn with_clock_of(&self, clock: Arc<dyn Clock>, latency_tracking: bool) -> Self {
+        let mut statistics = self.clone();
+        statistics.clock = clock;
+        statistics.set_latency_tracking(latency_tracking);
+        statistics
     }
 
     #[inline]
-    fn current_timestamp_milliseconds_or_zero() -> u64 {
-        Self::current_timestamp_milliseconds().unwrap_or(0)
+    fn now_ms(&self) -> u64 {
+        self.clock.now().as_u64()
     }
 
-    /// Create new empty statistics
+    /// Create new empty statistics, reading time from the [`SystemClock`].
     #[must_use]
     pub fn new() -> Self {
-        let current_time = Self::current_timestamp_milliseconds_or_zero();
+        Self::with_clock(Arc::new(SystemClock::new()))
+    }
+
+    /// Create new empty statistics reading time from `clock`.
+    ///
+    /// The clock stamps [`first_arrival_time`](Self::first_arrival_time) here
+    /// and on [`reset`](Self::reset), and is the "now" of
+    /// [`time_since_last_execution`](Self::time_since_last_execution) and
+    /// [`stats_window`](Self::stats_window). Recorded executions keep the
+    /// timestamps they are given.
+    #[must_use]
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        let current_time = clock.now().as_u64();
 
         Self {
             orders_added: AtomicUsize::new(0),
@@ -293,9 +376,22 @@ impl PriceLevelStatistics {
             sum_waiting_time: AtomicU64::new(0),
             stats_degraded: AtomicBool::new(false),
             stats_seq: AtomicU64::new(0),
+            stats_writer: Mutex::new(()),
+            window: ExecutionWindow::default(),
+            alerts: AlertRules::default(),
+            clock,
+            latency: LatencyRecorder::default(),
+            flow: OrderFlow::default(),
+            ages: OrderAges::default(),
         }
     }
 
+    /// The clock these statistics read the current time from.
+    #[must_use]
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
     /// Record a new order being added
     pub fn record_order_added(&self) {
         self.orders_added.fetch_add(1, Ordering::Relaxed);
@@ -306,6 +402,38 @@ impl PriceLevelStatistics {
         self.orders_removed.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// Record a new order of total (visible + hidden) `quantity` being added,
+    /// feeding the order-size and imbalance [`analytics`](Self::analytics).
+    /// A quantity that would overflow the flow totals is dropped and marks
+    /// the statistics [degraded](Self::stats_degraded).
+    pub fn record_order_added_with_quantity(&self, quantity: u64) {
+        self.record_order_added();
+        if self.flow.record_added(quantity).is_err() {
+            self.mark_degraded();
+        }
+    }
+
+    /// Record an order with `quantity` still resting being removed without
+    /// execution, feeding the imbalance [`analytics`](Self::analytics). An
+    /// overflow is dropped and marks the statistics degraded, as above.
+    pub fn record_order_removed_with_quantity(&self, quantity: u64) {
+        self.record_order_removed();
+        if self.flow.record_cancelled(quantity).is_err() {
+            self.mark_degraded();
+        }
+    }
+
+    /// As [`record_order_removed_with_quantity`](Self::record_order_removed_with_quantity),
+    /// also recording that the order rested `age_ms` milliseconds in the
+    /// [`OrderAgeOutcome::Removed`] histogram. `None` records no age, as for
+    /// an order with a zero timestamp.
+    pub fn record_order_removed_with_age(&self, quantity: u64, age_ms: Option<u64>) {
+        self.record_order_removed_with_quantity(quantity);
+        if let Some(age_ms) = age_ms {
+            self.ages.record(OrderAgeOutcome::Removed, age_ms);
+        }
+    }
+
     /// Record an order execution.
     ///
     /// The `execution_timestamp` is the taker timestamp threaded in from the
@@ -324,9 +452,8 @@ impl PriceLevelStatistics {
     /// added — sound by the same call-backed reservation argument as the #111 /
     /// #113 rollbacks: the subtracted units are exactly the units this call
     /// added, so the undo is commutative with concurrent `record_execution`
-    /// deltas). That call-backed argument assumes no concurrent
-    /// [`reset`](Self::reset) `store(0)` races the rollback — see the quiescence
-    /// contract on `reset`. So a caller never observes a partial contribution in
+    /// deltas). No [`reset`](Self::reset) `store(0)` can race the rollback:
+    /// both are seqlock writers, which exclude each other. So a caller never observes a partial contribution in
     /// the final state. On any failure — a validation error or a counter overflow —
     /// the sticky [`stats_degraded`](Self::stats_degraded) flag is set: the
     /// dropped execution is then observable, even though the caller
@@ -342,12 +469,33 @@ impl PriceLevelStatistics {
     /// accumulations overflow, if the value (`quantity * price`) overflows
     /// `u128`/`u64`, or if `order_timestamp` is strictly greater than
     /// `execution_timestamp` (a maker arriving in the future of execution).
+    ///
+    /// # Alerts
+    ///
+    /// After an accepted execution, every condition registered with
+    /// [`add_alert`](Self::add_alert) is evaluated at `execution_timestamp`,
+    /// outside the seqlock write section, and the callback of each newly
+    /// breached one runs on this thread before this returns.
     pub fn record_execution(
         &self,
         quantity: u64,
         price: u128,
         order_timestamp: u64,
         execution_timestamp: u64,
+    ) -> Result<(), PriceLevelError> {
+        self.record_execution_aggregates(quantity, price, order_timestamp, execution_timestamp)?;
+        self.alerts.evaluate(self, execution_timestamp);
+        Ok(())
+    }
+
+    /// The seqlock-guarded, all-or-nothing body of
+    /// [`record_execution`](Self::record_execution).
+    fn record_execution_aggregates(
+        &self,
+        quantity: u64,
+        price: u128,
+        order_timestamp: u64,
+        execution_timestamp: u64,
     ) -> Result<(), PriceLevelError> {
         let current_time = execution_timestamp;
 
@@ -356,7 +504,7 @@ impl PriceLevelStatistics {
         // in-flight prefix, and `reset` — also a writer — cannot interleave this
         // transaction's rollback. The guard's `Drop` closes the section (back to
         // even) on EVERY return path below, including the early validation errors.
-        let _write = WriteSeqGuard::new(&self.stats_seq);
+        let _write = WriteSeqGuard::new(&self.stats_writer, &self.stats_seq);
 
         // Validate everything that can fail BEFORE mutating any counter, so a
         // rejected record leaves the statistics untouched. Any failure marks the
@@ -442,10 +590,54 @@ impl PriceLevelStatistics {
         // seqlock, but cheap and independently correct.
         self.last_execution_time
             .fetch_max(current_time, Ordering::Relaxed);
+        self.window.record(current_time, quantity, value_u64);
+        if self.flow.record_executed(quantity).is_err() {
+            self.mark_degraded();
+        }
+        if let Some(waiting_time) = waiting_time {
+            self.ages.record(OrderAgeOutcome::Executed, waiting_time);
+        }
 
         Ok(())
     }
 
+    /// Take a busted execution of `quantity` at `price` back out of the
+    /// execution aggregates: one execution, its quantity and its value.
+    ///
+    /// Aggregates smaller than the bust — reset since, or the execution's
+    /// record dropped — clamp at zero and mark the statistics degraded. The
+    /// waiting time, the last execution time, the rolling window, the order
+    /// flow and the age histograms keep the execution: they do not record
+    /// enough to take one out.
+    pub fn record_bust(&self, quantity: u64, price: u128) {
+        let value = u128::from(quantity)
+            .checked_mul(price)
+            .and_then(|value| u64::try_from(value).ok())
+            .unwrap_or(u64::MAX);
+        let _write = WriteSeqGuard::new(&self.stats_writer, &self.stats_seq);
+        let executed = self
+            .orders_executed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
+                Some(c.saturating_sub(1))
+            })
+            .unwrap_or_default();
+        let executed_quantity = self
+            .quantity_executed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
+                Some(c.saturating_sub(quantity))
+            })
+            .unwrap_or_default();
+        let executed_value = self
+            .value_executed
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |c| {
+                Some(c.saturating_sub(value))
+            })
+            .unwrap_or_default();
+        if executed == 0 || executed_quantity < quantity || executed_value < value {
+            self.mark_degraded();
+        }
+    }
+
     /// Get total number of orders added
     #[must_use]
     pub fn orders_added(&self) -> usize {
@@ -488,8 +680,9 @@ impl PriceLevelStatistics {
     /// Get the statistics initialization timestamp, in milliseconds since the
     /// Unix epoch.
     ///
-    /// Set when the statistics are created and on [`reset`](Self::reset) with the
-    /// current wall-clock time (`0` if the system clock could not be read). It is
+    /// Set when the statistics are created and on [`reset`](Self::reset) from the
+    /// statistics' [`clock`](Self::clock) (`0` if the system clock could not be
+    /// read). It is
     /// **not** updated on order arrival, so it marks when statistics tracking
     /// began for this level, not the first order's actual arrival time.
     #[must_use]
@@ -558,41 +751,42 @@ impl PriceLevelStatistics {
         }
     }
 
-    /// Get time since last execution (in milliseconds)
+    /// Get time since last execution (in milliseconds), measured against the
+    /// statistics' [`clock`](Self::clock).
+    ///
+    /// `None` before the first execution, or if the last execution timestamp
+    /// is ahead of the clock.
     #[must_use]
     pub fn time_since_last_execution(&self) -> Option<u64> {
         let last = self.last_execution_time.load(Ordering::Relaxed);
         if last == 0 {
             None
         } else {
-            let current_time = Self::current_timestamp_milliseconds_or_zero();
-            current_time.checked_sub(last)
+            self.now_ms().checked_sub(last)
         }
     }
 
-    /// Reset all statistics to zero (and re-stamp `first_arrival_time`).
-    ///
-    /// # Quiescence contract
+    /// Reset all statistics to zero (and re-stamp `first_arrival_time`),
+    /// including the windowed metrics, order-flow analytics, latency
+    /// histograms and resting-order ages.
     ///
-    /// This must only be called on a **quiescent** level — with no in-flight
-    /// [`record_execution`](Self::record_execution) (and hence no in-flight
-    /// `PriceLevel::match_order`). `reset` participates in the seqlock as a
-    /// WRITER (issue #129), so it can never interleave the middle of a
-    /// `record_execution` transaction's rollback (which would otherwise wrap a
-    /// counter toward `u64::MAX` via a `store(0)` racing a `fetch_sub`) — but the
-    /// seqlock protects READERS, it does not serialize two writers. The
-    /// single-matcher-per-level model already serializes `record_execution`, and
-    /// this quiescence requirement extends that to `reset`. No engine path calls
-    /// `reset` during matching, so the race does not occur today; it remains a
-    /// caller obligation because `reset` is public. A multi-field reader
+    /// Safe to call while the level is matching. `reset` is a seqlock WRITER
+    /// (issue #129) and writers exclude each other, so it runs entirely before
+    /// or entirely after any in-flight
+    /// [`record_execution`](Self::record_execution) — it can never interleave a
+    /// recording's rollback (which would otherwise wrap a counter toward
+    /// `u64::MAX` via a `store(0)` racing a `fetch_sub`). A multi-field reader
     /// (`Clone` / serialize) racing `reset` retries and observes either the
-    /// pre-reset or fully-reset state, never a mix.
+    /// pre-reset or fully-reset state, never a mix. The `orders_added` /
+    /// `orders_removed` counters are single-field increments outside the
+    /// seqlock, so one landing concurrently with a reset is counted on either
+    /// side of it.
     pub fn reset(&self) {
-        let current_time = Self::current_timestamp_milliseconds_or_zero();
+        let current_time = self.now_ms();
 
         // Seqlock write section: a concurrent multi-field reader retries rather
         // than capture a half-reset copy.
-        let _write = WriteSeqGuard::new(&self.stats_seq);
+        let _write = WriteSeqGuard::new(&self.stats_writer, &self.stats_seq);
 
         self.orders_added.store(0, Ordering::Relaxed);
         self.orders_removed.store(0, Ordering::Relaxed);
@@ -604,6 +798,192 @@ impl PriceLevelStatistics {
             .store(current_time, Ordering::Relaxed);
         self.sum_waiting_time.store(0, Ordering::Relaxed);
         self.stats_degraded.store(false, Ordering::Relaxed);
+        self.window.clear();
+        self.alerts.rearm();
+        self.latency.clear();
+        self.flow.clear();
+        self.ages.clear();
+    }
+
+    /// Enable or disable recording the per-call latency of the level's
+    /// `add_order`, `match_order` and `update_order`.
+    ///
+    /// Off by default. The histograms are allocated on first enable and kept
+    /// when disabled, so re-enabling resumes them; [`reset`](Self::reset)
+    /// clears them. Timing reads a monotonic [`Instant`](std::time::Instant),
+    /// not the statistics' clock. Also set through
+    /// [`PriceLevelConfig::with_latency_tracking`](crate::PriceLevelConfig::with_latency_tracking).
+    pub fn set_latency_tracking(&self, enabled: bool) {
+        self.latency.set_enabled(enabled);
+    }
+
+    /// Whether per-call latency is being recorded.
+    #[must_use]
+    pub fn latency_tracking(&self) -> bool {
+        self.latency.is_enabled()
+    }
+
+    /// The latency recorded for `operation` so far, or `None` while latency
+    /// tracking is off.
+    ///
+    /// Each bucket is read `Relaxed`, so a histogram read while the level is
+    /// busy may miss calls still being recorded; it never lists a call
+    /// twice.
+    #[must_use]
+    pub fn latency_histogram(&self, operation: LatencyOperation) -> Option<LatencyHistogram> {
+        self.latency.histogram(operation)
+    }
+
+    /// Time one call of `operation` until the returned timer drops.
+    pub(crate) fn time(&self, operation: LatencyOperation) -> LatencyTimer<'_> {
+        self.latency.start(operation)
+    }
+
+    /// How long orders rested at the level before `outcome`.
+    ///
+    /// Every fill [`record_execution`](Self::record_execution) accepts
+    /// records its maker's waiting time as an [`OrderAgeOutcome::Executed`]
+    /// age; the level records an [`OrderAgeOutcome::Removed`] age for each
+    /// order it drops without trading. The counters are lock-free, so a
+    /// histogram read while the level is busy may miss ages still being
+    /// recorded. Unlike the latency histograms, the ages are persisted with
+    /// a snapshot.
+    #[must_use]
+    pub fn order_age_histogram(&self, outcome: OrderAgeOutcome) -> OrderAgeHistogram {
+        self.ages.histogram(outcome)
+    }
+
+    /// Execution totals over the trailing `window`, ending at the statistics'
+    /// [`clock`](Self::clock) reading.
+    ///
+    /// Executions are bucketed by the execution timestamp passed to
+    /// [`record_execution`](Self::record_execution), so this is meaningful
+    /// when those timestamps are in the clock's time; otherwise use
+    /// [`stats_window_at`](Self::stats_window_at) with the caller's own clock.
+    /// The window is rounded up to whole seconds and capped at one minute; see
+    /// [`StatisticsWindow`].
+    #[must_use]
+    pub fn stats_window(&self, window: Duration) -> StatisticsWindow {
+        self.stats_window_at(window, self.now_ms())
+    }
+
+    /// Execution totals over the trailing `window` ending at `now_ms`
+    /// (milliseconds, in the same clock as the recorded execution timestamps).
+    #[must_use]
+    pub fn stats_window_at(&self, window: Duration, now_ms: u64) -> StatisticsWindow {
+        self.window.totals(window, now_ms)
+    }
+
+    /// Executions per second over the trailing `window`, ending now.
+    ///
+    /// Shorthand for `stats_window(window).executions_per_second()`.
+    #[must_use]
+    pub fn executions_per_second(&self, window: Duration) -> f64 {
+        self.stats_window(window).executions_per_second()
+    }
+
+    /// Volume-weighted average execution price over the trailing `window`,
+    /// ending now, or `None` if nothing executed in it.
+    ///
+    /// Shorthand for `stats_window(window).average_price()`.
+    #[must_use]
+    pub fn vwap(&self, window: Duration) -> Option<f64> {
+        self.stats_window(window).average_price()
+    }
+
+    /// Mean total quantity of the orders added with
+    /// [`record_order_added_with_quantity`](Self::record_order_added_with_quantity),
+    /// at the size they rested with.
+    #[must_use]
+    pub fn average_order_size(&self) -> Option<f64> {
+        self.flow.average_size().unwrap_or_else(|_| {
+            self.mark_degraded();
+            None
+        })
+    }
+
+    /// Median total quantity of the orders added, at the size they rested
+    /// with.
+    ///
+    /// Sizes are kept in fixed logarithmic buckets, so the median is exact
+    /// below 8 and otherwise the upper bound of its bucket, within 12.5%.
+    #[must_use]
+    pub fn median_order_size(&self) -> Option<u64> {
+        self.flow.median_size().unwrap_or_else(|_| {
+            self.mark_degraded();
+            None
+        })
+    }
+
+    /// Orders added per order removed without execution, or `None` before the
+    /// first removal.
+    #[must_use]
+    pub fn add_to_cancel_ratio(&self) -> Option<f64> {
+        let removed = self.orders_removed.load(Ordering::Relaxed);
+        (removed > 0).then(|| self.orders_added.load(Ordering::Relaxed) as f64 / removed as f64)
+    }
+
+    /// Order flow imbalance of the level: quantity added minus quantity
+    /// cancelled and executed, over their sum.
+    ///
+    /// In `[-1.0, 1.0]`: positive while the queue is being built up, negative
+    /// while it is being drained. `None` before any sized flow was recorded.
+    #[must_use]
+    pub fn order_flow_imbalance(&self) -> Option<f64> {
+        self.flow.imbalance()
+    }
+
+    /// Every derived metric at once: [`vwap`](Self::vwap) over `window`, the
+    /// order sizes, the add-to-cancel ratio and the order flow imbalance.
+    ///
+    /// Each metric is read lock-free and independently, so under concurrent
+    /// recording the set is advisory, like the other point accessors.
+    #[must_use]
+    pub fn analytics(&self, window: Duration) -> StatisticsAnalytics {
+        let totals = self.stats_window(window);
+        StatisticsAnalytics {
+            window_ms: totals.window_ms,
+            vwap: totals.average_price(),
+            average_order_size: self.average_order_size(),
+            median_order_size: self.median_order_size(),
+            add_to_cancel_ratio: self.add_to_cancel_ratio(),
+            order_flow_imbalance: self.order_flow_imbalance(),
+        }
+    }
+
+    /// Register `callback` to be invoked with a [`StatisticsAlert`] whenever
+    /// `condition` becomes breached.
+    ///
+    /// Conditions are evaluated after each accepted
+    /// [`record_execution`](Self::record_execution), in the clock of the
+    /// recorded execution timestamps. An alert is edge-triggered: it fires when
+    /// the metric first rises above the threshold and not again until it has
+    /// fallen back to or below it (or [`reset`](Self::reset) re-arms it), so a
+    /// sustained breach does not flood the callback.
+    ///
+    /// The callback runs synchronously on the recording thread. When the
+    /// execution is recorded by `PriceLevel::match_order`, it runs once the
+    /// match has released the level's guard, just before `match_order`
+    /// returns, so it may freeze the level, change its configuration or
+    /// trade on it. It may also read these statistics and register or clear
+    /// alerts. Alerts are runtime configuration: they are not cloned,
+    /// serialized or restored with a snapshot.
+    pub fn add_alert<F>(&self, condition: AlertCondition, callback: F)
+    where
+        F: Fn(&StatisticsAlert) + Send + Sync + 'static,
+    {
+        self.alerts.add(condition, Arc::new(callback));
+    }
+
+    /// Remove every registered alert condition.
+    pub fn clear_alerts(&self) {
+        self.alerts.clear();
+    }
+
+    /// Number of registered alert conditions.
+    #[must_use]
+    pub fn alert_count(&self) -> usize {
+        self.alerts.len()
     }
 }
 
@@ -623,8 +1003,14 @@ impl Clone for PriceLevelStatistics {
     /// pre- and post-`record_execution` prefix — the seqlock retries until it
     /// captures a state the level actually held. A restored level therefore
     /// carries the recorded statistics rather than a fresh, zeroed set.
+    ///
+    /// The resting-order age histograms are copied alongside, outside the
+    /// seqlock: like the other single-field counters, an age recorded
+    /// concurrently lands on either side of the copy.
     fn clone(&self) -> Self {
-        Self::from_data(self.read_consistent())
+        let mut statistics = Self::from_data(self.read_consistent());
+        statistics.ages = OrderAges::from_recorded(&self.ages.recorded());
+        statistics
     }
 }
 
@@ -743,6 +1129,13 @@ impl FromStr for PriceLevelStatistics {
             sum_waiting_time: AtomicU64::new(sum_waiting_time),
             stats_degraded: AtomicBool::new(stats_degraded),
             stats_seq: AtomicU64::new(0),
+            stats_writer: Mutex::new(()),
+            window: ExecutionWindow::default(),
+            alerts: AlertRules::default(),
+            clock: Arc::new(SystemClock::new()),
+            latency: LatencyRecorder::default(),
+            flow: OrderFlow::default(),
+            ages: OrderAges::default(),
         })
     }
 }
@@ -768,8 +1161,13 @@ impl Serialize for PriceLevelStatistics {
         // degraded level adds the 9th field (the v3-only shape); `Deserialize` /
         // `FromStr` default a missing flag to `false`, so both directions
         // round-trip.
+        //
+        // `order_ages` follows the same rule: written only once an age has
+        // been recorded, so a level that never filled or removed an order
+        // keeps its pre-v5 bytes.
         let degraded = d.stats_degraded;
-        let field_count = if degraded { 9 } else { 8 };
+        let order_ages = self.ages.recorded();
+        let field_count = 8 + usize::from(degraded) + usize::from(!order_ages.is_empty());
         let mut state = serializer.serialize_struct("PriceLevelStatistics", field_count)?;
 
         state.serialize_field("orders_added", &d.orders_added)?;
@@ -783,6 +1181,9 @@ impl Serialize for PriceLevelStatistics {
         if degraded {
             state.serialize_field("stats_degraded", &true)?;
         }
+        if !order_ages.is_empty() {
+            state.serialize_field("order_ages", &order_ages)?;
+        }
 
         state.end()
     }
@@ -803,6 +1204,7 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
             FirstArrivalTime,
             SumWaitingTime,
             StatsDegraded,
+            OrderAges,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -833,6 +1235,7 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                             "first_arrival_time" => Ok(Field::FirstArrivalTime),
                             "sum_waiting_time" => Ok(Field::SumWaitingTime),
                             "stats_degraded" => Ok(Field::StatsDegraded),
+                            "order_ages" => Ok(Field::OrderAges),
                             _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -864,6 +1267,7 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                 let mut first_arrival_time = None;
                 let mut sum_waiting_time = None;
                 let mut stats_degraded = None;
+                let mut order_ages: Option<Vec<OrderAgeHistogram>> = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -921,6 +1325,12 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                             }
                             stats_degraded = Some(map.next_value()?);
                         }
+                        Field::OrderAges => {
+                            if order_ages.is_some() {
+                                return Err(de::Error::duplicate_field("order_ages"));
+                            }
+                            order_ages = Some(map.next_value()?);
+                        }
                     }
                 }
 
@@ -931,14 +1341,16 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                 let value_executed = value_executed.unwrap_or(0);
                 let last_execution_time = last_execution_time.unwrap_or(0);
 
-                let first_arrival_time = first_arrival_time.unwrap_or_else(|| {
-                    PriceLevelStatistics::current_timestamp_milliseconds_or_zero()
-                });
+                let first_arrival_time =
+                    first_arrival_time.unwrap_or_else(|| TimestampMs::now().as_u64());
 
                 let sum_waiting_time = sum_waiting_time.unwrap_or(0);
                 // Optional for backward compatibility: a payload written before
                 // the field existed decodes with the flag cleared.
                 let stats_degraded = stats_degraded.unwrap_or(false);
+                // Absent before snapshot v5, and omitted while no age has been
+                // recorded.
+                let order_ages = order_ages.unwrap_or_default();
 
                 Ok(PriceLevelStatistics {
                     orders_added: AtomicUsize::new(orders_added),
@@ -951,6 +1363,13 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                     sum_waiting_time: AtomicU64::new(sum_waiting_time),
                     stats_degraded: AtomicBool::new(stats_degraded),
                     stats_seq: AtomicU64::new(0),
+                    stats_writer: Mutex::new(()),
+                    window: ExecutionWindow::default(),
+                    alerts: AlertRules::default(),
+                    clock: Arc::new(SystemClock::new()),
+                    latency: LatencyRecorder::default(),
+                    flow: OrderFlow::default(),
+                    ages: OrderAges::from_recorded(&order_ages),
                 })
             }
         }
@@ -965,6 +1384,7 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
             "first_arrival_time",
             "sum_waiting_time",
             "stats_degraded",
+            "order_ages",
         ];
 
         deserializer.deserialize_struct("PriceLevelStatistics", FIELDS, StatisticsVisitor)
@@ -0,0 +1,237 @@
+use crate::price_level::statistics::PriceLevelStatistics;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// A threshold on a level's statistics that raises a [`StatisticsAlert`] when
+/// crossed, registered with `PriceLevelStatistics::add_alert`.
+///
+/// Rate conditions read the trailing execution window (see
+/// `PriceLevelStatistics::stats_window_at`), so their `window` is resolved at
+/// one-second granularity and capped at one minute.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// Quantity executed per second over the trailing `window` exceeds
+    /// `threshold`.
+    QuantityPerSecondAbove {
+        /// Trailing window the rate is measured over.
+        window: Duration,
+        /// Rate, in quantity units per second, above which the alert fires.
+        threshold: f64,
+    },
+    /// Executions per second over the trailing `window` exceed `threshold`.
+    ExecutionsPerSecondAbove {
+        /// Trailing window the rate is measured over.
+        window: Duration,
+        /// Rate, in executions per second, above which the alert fires.
+        threshold: f64,
+    },
+    /// The lifetime average waiting time of executed makers exceeds
+    /// `threshold_ms` milliseconds.
+    AverageWaitingTimeAbove {
+        /// Average waiting time, in milliseconds, above which the alert fires.
+        threshold_ms: f64,
+    },
+}
+
+impl AlertCondition {
+    /// The threshold the observed value is compared against.
+    #[must_use]
+    pub fn threshold(&self) -> f64 {
+        match *self {
+            Self::QuantityPerSecondAbove { threshold, .. }
+            | Self::ExecutionsPerSecondAbove { threshold, .. } => threshold,
+            Self::AverageWaitingTimeAbove { threshold_ms } => threshold_ms,
+        }
+    }
+
+    /// The value of the watched metric at `now_ms`.
+    fn observe(&self, stats: &PriceLevelStatistics, now_ms: u64) -> f64 {
+        match *self {
+            Self::QuantityPerSecondAbove { window, .. } => {
+                stats.stats_window_at(window, now_ms).quantity_per_second()
+            }
+            Self::ExecutionsPerSecondAbove { window, .. } => stats
+                .stats_window_at(window, now_ms)
+                .executions_per_second(),
+            Self::AverageWaitingTimeAbove { .. } => stats.average_waiting_time().unwrap_or(0.0),
+        }
+    }
+}
+
+/// A raised alert, passed to the callback registered for its condition.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StatisticsAlert {
+    /// The condition that was crossed.
+    pub condition: AlertCondition,
+    /// The observed value of the watched metric, above the condition's
+    /// threshold.
+    pub observed: f64,
+    /// Execution timestamp (milliseconds) of the recording that raised it.
+    pub timestamp_ms: u64,
+}
+
+/// Callback invoked with each raised [`StatisticsAlert`].
+pub(crate) type AlertCallback = Arc<dyn Fn(&StatisticsAlert) + Send + Sync>;
+
+type RaisedAlert = (AlertCallback, StatisticsAlert);
+
+thread_local! {
+    /// Alerts raised on this thread while a [`DeferredAlerts`] is open, or
+    /// `None` when none is.
+    static DEFERRED: RefCell<Option<Vec<RaisedAlert>>> = const { RefCell::new(None) };
+}
+
+/// Holds back the alert callbacks raised on this thread until dropped.
+///
+/// A level opens one before taking its mutator section and drops it after
+/// the section, so callbacks run with the level's guard released and may
+/// freeze the level or match on it. Only the outermost one invokes the
+/// callbacks.
+pub(crate) struct DeferredAlerts {
+    outermost: bool,
+}
+
+impl DeferredAlerts {
+    pub(crate) fn begin() -> Self {
+        let outermost = DEFERRED.with(|deferred| {
+            let mut deferred = deferred.borrow_mut();
+            deferred.is_none() && {
+                *deferred = Some(Vec::new());
+                true
+            }
+        });
+        Self { outermost }
+    }
+}
+
+impl Drop for DeferredAlerts {
+    fn drop(&mut self) {
+        if !self.outermost {
+            return;
+        }
+        let raised = DEFERRED
+            .with(|deferred| deferred.borrow_mut().take())
+            .unwrap_or_default();
+        // Unwinding out of a sweep: its alerts are dropped with it.
+        if std::thread::panicking() {
+            return;
+        }
+        let raised = DEFERRED.with(|deferred| match deferred.borrow_mut().as_mut() {
+            Some(deferred) => {
+                deferred.extend(raised);
+                Vec::new()
+            }
+            None => raised,
+        });
+        for (callback, alert) in raised {
+            callback(&alert);
+        }
+    }
+}
+
+/// A registered condition, its callback, and whether it is currently breached.
+struct AlertRule {
+    condition: AlertCondition,
+    callback: AlertCallback,
+    /// Set while the condition holds, so a breach alerts once on entry rather
+    /// than on every execution; cleared when the metric falls back to or
+    /// below the threshold, re-arming the rule.
+    breached: AtomicBool,
+}
+
+impl fmt::Debug for AlertRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlertRule")
+            .field("condition", &self.condition)
+            .field("breached", &self.breached.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+/// The alert rules of one [`PriceLevelStatistics`]. Runtime configuration,
+/// like the execution window: never cloned or serialized.
+#[derive(Debug, Default)]
+pub(crate) struct AlertRules {
+    rules: RwLock<Vec<AlertRule>>,
+    /// `true` once any rule is registered, so recording skips the lock when
+    /// no alert is configured.
+    any: AtomicBool,
+}
+
+impl AlertRules {
+    pub(crate) fn add(&self, condition: AlertCondition, callback: AlertCallback) {
+        let mut rules = self.rules.write().unwrap_or_else(|e| e.into_inner());
+        rules.push(AlertRule {
+            condition,
+            callback,
+            breached: AtomicBool::new(false),
+        });
+        self.any.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut rules = self.rules.write().unwrap_or_else(|e| e.into_inner());
+        rules.clear();
+        self.any.store(false, Ordering::Release);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.rules.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Re-arms every rule, so a condition still breached after a statistics
+    /// reset alerts again.
+    pub(crate) fn rearm(&self) {
+        if !self.any.load(Ordering::Acquire) {
+            return;
+        }
+        let rules = self.rules.read().unwrap_or_else(|e| e.into_inner());
+        for rule in rules.iter() {
+            rule.breached.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Evaluates every rule against `stats` at `now_ms` and invokes the
+    /// callback of each rule that has just become breached. Callbacks run
+    /// after the rule lock is released, so a callback may register or clear
+    /// alerts, and are queued instead while a [`DeferredAlerts`] is open.
+    pub(crate) fn evaluate(&self, stats: &PriceLevelStatistics, now_ms: u64) {
+        if !self.any.load(Ordering::Acquire) {
+            return;
+        }
+        let mut raised = Vec::new();
+        {
+            let rules = self.rules.read().unwrap_or_else(|e| e.into_inner());
+            for rule in rules.iter() {
+                let observed = rule.condition.observe(stats, now_ms);
+                if observed > rule.condition.threshold() {
+                    if !rule.breached.swap(true, Ordering::Relaxed) {
+                        let alert = StatisticsAlert {
+                            condition: rule.condition,
+                            observed,
+                            timestamp_ms: now_ms,
+                        };
+                        raised.push((Arc::clone(&rule.callback), alert));
+                    }
+                } else {
+                    rule.breached.store(false, Ordering::Relaxed);
+                }
+            }
+        }
+        let raised = DEFERRED.with(|deferred| match deferred.borrow_mut().as_mut() {
+            Some(deferred) => {
+                deferred.extend(raised);
+                Vec::new()
+            }
+            None => raised,
+        });
+        for (callback, alert) in raised {
+            callback(&alert);
+        }
+    }
+}
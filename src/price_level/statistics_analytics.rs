@@ -0,0 +1,157 @@
+use crate::errors::PriceLevelError;
+use crate::price_level::latency::{BUCKETS, bucket_bounds, bucket_of};
+use crate::price_level::statistics::PriceLevelStatistics;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Derived order-flow analytics of a level, as returned by
+/// `PriceLevelStatistics::analytics`.
+///
+/// A plain, serializable copy for dashboards and logs. Each metric is `None`
+/// when its denominator is zero. The order sizes and the imbalance cover the
+/// flow the level has seen since it was created or last reset; they are not
+/// persisted with a snapshot, so a restored level starts them afresh.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct StatisticsAnalytics {
+    /// Effective span of the VWAP window, in milliseconds.
+    pub window_ms: u64,
+    /// Volume-weighted average execution price over the window.
+    pub vwap: Option<f64>,
+    /// Mean total quantity of the orders added to the level.
+    pub average_order_size: Option<f64>,
+    /// Median total quantity of the orders added to the level, within 12.5%.
+    pub median_order_size: Option<u64>,
+    /// Orders added per order removed without execution.
+    pub add_to_cancel_ratio: Option<f64>,
+    /// Net quantity flow into the level, in `[-1.0, 1.0]`.
+    pub order_flow_imbalance: Option<f64>,
+}
+
+/// Lock-free quantity flow behind the order-size and imbalance analytics.
+///
+/// Added sizes are bucketed like the latency histograms (exact below 8, then
+/// eight buckets per power of two); the buckets are allocated on the first
+/// sized add. Runtime observability, like the statistics window: never cloned
+/// or serialized. Every sum is checked, per the no-saturate/no-wrap rule: an
+/// overflow is returned as an error, and the owning statistics mark
+/// themselves degraded rather than report a clamped figure.
+#[derive(Debug, Default)]
+pub(crate) struct OrderFlow {
+    quantity_added: AtomicU64,
+    quantity_cancelled: AtomicU64,
+    quantity_executed: AtomicU64,
+    max_size: AtomicU64,
+    sizes: OnceLock<Box<[AtomicU64]>>,
+}
+
+fn overflow(field: &str) -> PriceLevelError {
+    PriceLevelError::InvalidOperation {
+        message: format!("{field} overflow"),
+    }
+}
+
+impl OrderFlow {
+    /// Records an added order; on overflow nothing is recorded.
+    pub(crate) fn record_added(&self, quantity: u64) -> Result<(), PriceLevelError> {
+        let sizes = self
+            .sizes
+            .get_or_init(|| (0..BUCKETS).map(|_| AtomicU64::new(0)).collect());
+        PriceLevelStatistics::checked_fetch_add_u64(
+            &self.quantity_added,
+            quantity,
+            "quantity_added",
+        )?;
+        if let Err(err) =
+            PriceLevelStatistics::checked_fetch_add_u64(&sizes[bucket_of(quantity)], 1, "sizes")
+        {
+            self.quantity_added.fetch_sub(quantity, Ordering::Relaxed);
+            return Err(err);
+        }
+        self.max_size.fetch_max(quantity, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub(crate) fn record_cancelled(&self, quantity: u64) -> Result<(), PriceLevelError> {
+        PriceLevelStatistics::checked_fetch_add_u64(
+            &self.quantity_cancelled,
+            quantity,
+            "quantity_cancelled",
+        )
+    }
+
+    pub(crate) fn record_executed(&self, quantity: u64) -> Result<(), PriceLevelError> {
+        PriceLevelStatistics::checked_fetch_add_u64(
+            &self.quantity_executed,
+            quantity,
+            "quantity_executed",
+        )
+    }
+
+    pub(crate) fn clear(&self) {
+        if let Some(sizes) = self.sizes.get() {
+            for bucket in sizes.iter() {
+                bucket.store(0, Ordering::Relaxed);
+            }
+        }
+        self.quantity_added.store(0, Ordering::Relaxed);
+        self.quantity_cancelled.store(0, Ordering::Relaxed);
+        self.quantity_executed.store(0, Ordering::Relaxed);
+        self.max_size.store(0, Ordering::Relaxed);
+    }
+
+    fn count_orders(counts: impl IntoIterator<Item = u64>) -> Result<u64, PriceLevelError> {
+        counts.into_iter().try_fold(0_u64, |total, count| {
+            total
+                .checked_add(count)
+                .ok_or_else(|| overflow("sized orders"))
+        })
+    }
+
+    pub(crate) fn average_size(&self) -> Result<Option<f64>, PriceLevelError> {
+        let Some(sizes) = self.sizes.get() else {
+            return Ok(None);
+        };
+        let orders = Self::count_orders(sizes.iter().map(|bucket| bucket.load(Ordering::Relaxed)))?;
+        Ok(
+            (orders > 0)
+                .then(|| self.quantity_added.load(Ordering::Relaxed) as f64 / orders as f64),
+        )
+    }
+
+    /// Upper bound of the bucket holding the middle order, capped at the
+    /// largest size seen.
+    pub(crate) fn median_size(&self) -> Result<Option<u64>, PriceLevelError> {
+        let Some(sizes) = self.sizes.get() else {
+            return Ok(None);
+        };
+        let counts: Vec<u64> = sizes
+            .iter()
+            .map(|bucket| bucket.load(Ordering::Relaxed))
+            .collect();
+        let orders = Self::count_orders(counts.iter().copied())?;
+        if orders == 0 {
+            return Ok(None);
+        }
+        let rank = orders.div_ceil(2);
+        let max_size = self.max_size.load(Ordering::Relaxed);
+        let mut seen = 0_u64;
+        for (index, count) in counts.into_iter().enumerate() {
+            // Cannot overflow: `seen` never exceeds the checked `orders`.
+            seen += count;
+            if seen >= rank {
+                return Ok(Some(bucket_bounds(index).1.min(max_size)));
+            }
+        }
+        Ok(Some(max_size))
+    }
+
+    /// `(added - cancelled - executed) / (added + cancelled + executed)`.
+    pub(crate) fn imbalance(&self) -> Option<f64> {
+        let added = self.quantity_added.load(Ordering::Relaxed) as f64;
+        let removed = self.quantity_cancelled.load(Ordering::Relaxed) as f64
+            + self.quantity_executed.load(Ordering::Relaxed) as f64;
+        let total = added + removed;
+        (total > 0.0).then(|| (added - removed) / total)
+    }
+}
@@ -0,0 +1,269 @@
+use crate::errors::PriceLevelError;
+use crate::price_level::level::PriceLevel;
+use crate::price_level::statistics::PriceLevelStatistics;
+use crate::utils::TimestampMs;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Schema version written in the header of every statistics CSV file.
+///
+/// Bumped whenever the column set or the meaning of a column changes, so a
+/// consumer can tell which layout a retained file uses without guessing from
+/// the column names.
+pub const STATISTICS_CSV_SCHEMA_VERSION: u32 = 1;
+
+/// Column names emitted on the second header line, in row order.
+const STATISTICS_CSV_COLUMNS: &str = "timestamp_ms,price,orders_added,orders_removed,orders_executed,quantity_executed,value_executed,last_execution_time,first_arrival_time,sum_waiting_time,stats_degraded";
+
+/// Appends periodic `PriceLevelStatistics` snapshots to a CSV file.
+///
+/// Every file starts with a two-line header: a `#`-prefixed schema line
+/// (`# pricelevel-statistics schema_version=N`) followed by the column names.
+/// Each [`Self::append`] writes one row built from a **consistent** read of the
+/// statistics (the same seqlock-protected view used by `Clone` and `Display`),
+/// so a row never mixes a pre- and post-execution state.
+///
+/// # Rotation
+///
+/// With [`Self::with_rotation`] configured, a row that would push the active
+/// file past `max_bytes` first rotates it: `stats.csv` becomes `stats.csv.1`,
+/// `stats.csv.1` becomes `stats.csv.2`, and so on, keeping at most
+/// `max_files` rotated files. A fresh file with a new header is then started.
+/// Opening an existing file whose header does not carry the current schema
+/// also rotates it aside, so a single file never mixes two schemas.
+///
+/// The writer uses only `std` and holds no locks on the level; it is intended
+/// to be driven from a single periodic task.
+#[derive(Debug)]
+pub struct StatisticsCsvWriter {
+    /// Path of the active CSV file.
+    path: PathBuf,
+    /// Size threshold, in bytes, above which the active file is rotated.
+    /// `None` disables rotation.
+    max_bytes: Option<u64>,
+    /// Number of rotated files retained next to the active one.
+    max_files: usize,
+    /// Open handle to the active file, opened lazily on the first append.
+    file: Option<File>,
+    /// Bytes currently in the active file, header included.
+    bytes_written: u64,
+}
+
+impl StatisticsCsvWriter {
+    /// Creates a writer targeting `path`, with rotation disabled.
+    ///
+    /// The file is not touched until the first [`Self::append`].
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes: None,
+            max_files: 0,
+            file: None,
+            bytes_written: 0,
+        }
+    }
+
+    /// Enables size-based rotation.
+    ///
+    /// The active file is rotated before a row would take it past `max_bytes`;
+    /// at most `max_files` rotated files (`<path>.1` … `<path>.N`) are kept.
+    /// With `max_files == 0` the old file is simply discarded on rotation.
+    /// A file always receives at least one row, even if a single row exceeds
+    /// `max_bytes`.
+    #[must_use]
+    pub fn with_rotation(mut self, max_bytes: u64, max_files: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self.max_files = max_files;
+        self
+    }
+
+    /// Returns the path of the active CSV file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the schema header line written at the top of every file.
+    #[must_use]
+    pub fn schema_header() -> String {
+        format!("# pricelevel-statistics schema_version={STATISTICS_CSV_SCHEMA_VERSION}")
+    }
+
+    /// Appends one row for `stats`, observed at `timestamp` for the level at
+    /// `price`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if the file cannot be
+    /// opened, rotated, or written.
+    pub fn append(
+        &mut self,
+        timestamp: TimestampMs,
+        price: u128,
+        stats: &PriceLevelStatistics,
+    ) -> Result<(), PriceLevelError> {
+        // `Clone` is the consistent multi-field read; reading the accessors of
+        // the live statistics one by one could tear across an execution.
+        let snapshot = stats.clone();
+        let row = format!(
+            "{},{},{},{},{},{},{},{},{},{},{}\n",
+            timestamp.as_u64(),
+            price,
+            snapshot.orders_added(),
+            snapshot.orders_removed(),
+            snapshot.orders_executed(),
+            snapshot.quantity_executed(),
+            snapshot.value_executed(),
+            snapshot.last_execution_time(),
+            snapshot.first_arrival_time(),
+            snapshot.sum_waiting_time(),
+            snapshot.stats_degraded(),
+        );
+        self.write_row(row.as_bytes())
+    }
+
+    /// Appends one row for `level`'s statistics, observed at `timestamp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if the file cannot be
+    /// opened, rotated, or written.
+    pub fn append_level(
+        &mut self,
+        level: &PriceLevel,
+        timestamp: TimestampMs,
+    ) -> Result<(), PriceLevelError> {
+        self.append(timestamp, level.price(), &level.stats())
+    }
+
+    /// Flushes the active file to the operating system.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::SerializationError`] if the flush fails.
+    pub fn flush(&mut self) -> Result<(), PriceLevelError> {
+        match self.file.as_mut() {
+            Some(file) => file.flush().map_err(io_error),
+            None => Ok(()),
+        }
+    }
+
+    fn write_row(&mut self, row: &[u8]) -> Result<(), PriceLevelError> {
+        if self.file.is_none() {
+            self.open()?;
+        }
+
+        let header_len = header_bytes().len() as u64;
+        if let Some(max_bytes) = self.max_bytes
+            && self.bytes_written > header_len
+            && self.bytes_written.saturating_add(row.len() as u64) > max_bytes
+        {
+            self.rotate()?;
+            self.open()?;
+        }
+
+        let file = self
+            .file
+            .as_mut()
+            .ok_or_else(|| io_message("file not open"))?;
+        file.write_all(row).map_err(io_error)?;
+        self.bytes_written = self.bytes_written.saturating_add(row.len() as u64);
+        Ok(())
+    }
+
+    /// Opens the active file for append, writing the header if it is new and
+    /// rotating it aside first if it carries a different schema.
+    fn open(&mut self) -> Result<(), PriceLevelError> {
+        if self.path.exists() && !self.has_current_schema()? {
+            self.rotate()?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(io_error)?;
+        let mut len = file.metadata().map_err(io_error)?.len();
+        if len == 0 {
+            let header = header_bytes();
+            file.write_all(header.as_bytes()).map_err(io_error)?;
+            len = header.len() as u64;
+        }
+
+        self.file = Some(file);
+        self.bytes_written = len;
+        Ok(())
+    }
+
+    fn has_current_schema(&self) -> Result<bool, PriceLevelError> {
+        let file = File::open(&self.path).map_err(io_error)?;
+        let mut first = String::new();
+        BufReader::new(file)
+            .read_line(&mut first)
+            .map_err(io_error)?;
+        Ok(first.is_empty() || first.trim_end() == Self::schema_header())
+    }
+
+    /// Shifts `<path>.N-1` → `<path>.N` down to `<path>` → `<path>.1`,
+    /// dropping whatever falls past `max_files`.
+    fn rotate(&mut self) -> Result<(), PriceLevelError> {
+        if let Some(mut file) = self.file.take() {
+            file.flush().map_err(io_error)?;
+        }
+        self.bytes_written = 0;
+
+        if self.max_files == 0 {
+            return remove_if_exists(&self.path);
+        }
+
+        remove_if_exists(&self.rotated_path(self.max_files))?;
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(index + 1)).map_err(io_error)?;
+            }
+        }
+        if self.path.exists() {
+            fs::rename(&self.path, self.rotated_path(1)).map_err(io_error)?;
+        }
+        Ok(())
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+}
+
+fn header_bytes() -> String {
+    format!(
+        "{}\n{STATISTICS_CSV_COLUMNS}\n",
+        StatisticsCsvWriter::schema_header()
+    )
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), PriceLevelError> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(io_error(e)),
+    }
+}
+
+fn io_error(e: std::io::Error) -> PriceLevelError {
+    PriceLevelError::SerializationError {
+        message: format!("statistics csv: {e}"),
+        source: Some(Arc::new(e)),
+    }
+}
+
+fn io_message(message: &str) -> PriceLevelError {
+    PriceLevelError::SerializationError {
+        message: format!("statistics csv: {message}"),
+        source: None,
+    }
+}
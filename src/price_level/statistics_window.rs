@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Width of one ring bucket, in milliseconds.
+pub(crate) const WINDOW_BUCKET_MS: u64 = 1_000;
+
+/// Number of ring buckets; with [`WINDOW_BUCKET_MS`] this is the longest
+/// window the ring can answer (one minute).
+pub(crate) const WINDOW_BUCKETS: usize = 60;
+
+/// Execution totals over a trailing time window, as returned by
+/// `PriceLevelStatistics::stats_window`.
+///
+/// The window is resolved at one-second granularity: it covers every whole
+/// bucket that overlaps `[now - window, now]`, so the effective span ([`Self::window_ms`]) is the request rounded up to whole
+/// buckets and capped at one minute. Rates divide by that effective span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct StatisticsWindow {
+    /// Effective span the totals cover, in milliseconds.
+    pub window_ms: u64,
+    /// Executions recorded in the window.
+    pub executions: u64,
+    /// Quantity executed in the window.
+    pub quantity: u64,
+    /// Value (`quantity * price`) executed in the window.
+    pub value: u64,
+}
+
+impl StatisticsWindow {
+    fn per_second(&self, total: u64) -> f64 {
+        if self.window_ms == 0 {
+            0.0
+        } else {
+            total as f64 * 1_000.0 / self.window_ms as f64
+        }
+    }
+
+    /// Executions per second over the window.
+    #[must_use]
+    pub fn executions_per_second(&self) -> f64 {
+        self.per_second(self.executions)
+    }
+
+    /// Quantity executed per second over the window.
+    #[must_use]
+    pub fn quantity_per_second(&self) -> f64 {
+        self.per_second(self.quantity)
+    }
+
+    /// Value executed per second over the window.
+    #[must_use]
+    pub fn value_per_second(&self) -> f64 {
+        self.per_second(self.value)
+    }
+
+    /// Volume-weighted average price over the window, or `None` if nothing
+    /// executed.
+    #[must_use]
+    pub fn average_price(&self) -> Option<f64> {
+        (self.quantity > 0).then(|| self.value as f64 / self.quantity as f64)
+    }
+}
+
+/// One ring slot: the totals of a single bucket, tagged with the absolute
+/// bucket number (`timestamp / WINDOW_BUCKET_MS`) they belong to.
+#[derive(Debug, Default)]
+struct WindowBucket {
+    bucket: AtomicU64,
+    executions: AtomicU64,
+    quantity: AtomicU64,
+    value: AtomicU64,
+}
+
+/// Fixed ring of per-bucket execution totals backing the windowed metrics.
+///
+/// A slot is recycled the first time an execution lands in a newer bucket
+/// that maps onto it, so the ring always holds the most recent
+/// [`WINDOW_BUCKETS`] buckets that saw activity. Writes happen inside the
+/// statistics' seqlock write section, which serializes writers; reads are
+/// lock-free `Relaxed` loads and, like the other point accessors, advisory
+/// while recording is in progress.
+#[derive(Debug)]
+pub(crate) struct ExecutionWindow {
+    buckets: [WindowBucket; WINDOW_BUCKETS],
+}
+
+impl Default for ExecutionWindow {
+    fn default() -> Self {
+        Self {
+            buckets: std::array::from_fn(|_| WindowBucket::default()),
+        }
+    }
+}
+
+impl ExecutionWindow {
+    /// Adds one execution at `timestamp_ms`. An execution older than the
+    /// bucket now occupying its slot has already left every window and is
+    /// dropped. Callers must hold the statistics write section.
+    pub(crate) fn record(&self, timestamp_ms: u64, quantity: u64, value: u64) {
+        let bucket = timestamp_ms / WINDOW_BUCKET_MS;
+        let slot = &self.buckets[(bucket % WINDOW_BUCKETS as u64) as usize];
+        let current = slot.bucket.load(Ordering::Relaxed);
+        if bucket < current {
+            return;
+        }
+        if bucket > current {
+            slot.executions.store(0, Ordering::Relaxed);
+            slot.quantity.store(0, Ordering::Relaxed);
+            slot.value.store(0, Ordering::Relaxed);
+            slot.bucket.store(bucket, Ordering::Relaxed);
+        }
+        // Window totals saturate rather than fail: the all-or-nothing checked
+        // aggregates have already accepted this execution.
+        let add = |target: &AtomicU64, delta: u64| {
+            let _ = target.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| {
+                Some(v.saturating_add(delta))
+            });
+        };
+        add(&slot.executions, 1);
+        add(&slot.quantity, quantity);
+        add(&slot.value, value);
+    }
+
+    /// Totals of the buckets overlapping `[now_ms - window, now_ms]`.
+    pub(crate) fn totals(&self, window: Duration, now_ms: u64) -> StatisticsWindow {
+        let requested = u64::try_from(window.as_millis()).unwrap_or(u64::MAX);
+        let span = requested
+            .div_ceil(WINDOW_BUCKET_MS)
+            .clamp(1, WINDOW_BUCKETS as u64);
+        let newest = now_ms / WINDOW_BUCKET_MS;
+        let oldest = newest.saturating_sub(span - 1);
+
+        let mut totals = StatisticsWindow {
+            window_ms: span * WINDOW_BUCKET_MS,
+            ..StatisticsWindow::default()
+        };
+        for slot in &self.buckets {
+            let bucket = slot.bucket.load(Ordering::Relaxed);
+            if bucket < oldest || bucket > newest {
+                continue;
+            }
+            totals.executions = totals
+                .executions
+                .saturating_add(slot.executions.load(Ordering::Relaxed));
+            totals.quantity = totals
+                .quantity
+                .saturating_add(slot.quantity.load(Ordering::Relaxed));
+            totals.value = totals
+                .value
+                .saturating_add(slot.value.load(Ordering::Relaxed));
+        }
+        totals
+    }
+
+    /// Empties every bucket. Callers must hold the statistics write section.
+    pub(crate) fn clear(&self) {
+        for slot in &self.buckets {
+            slot.bucket.store(0, Ordering::Relaxed);
+            slot.executions.store(0, Ordering::Relaxed);
+            slot.quantity.store(0, Ordering::Relaxed);
+            slot.value.store(0, Ordering::Relaxed);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::errors::PriceLevelError;
+use crate::orders::OrderType;
+use crate::utils::{Price, Quantity};
+
+/// The trading increments of a symbol: the tick size prices must be a
+/// multiple of, the lot size quantities must be a multiple of, and the
+/// smallest and largest order quantity.
+///
+/// A level built with [`PriceLevelConfig::with_symbol_spec`] rejects, before
+/// touching any counter, an order added off-tick or off-lot and a resize that
+/// would leave one off-lot. A book applies one spec to all of its levels by
+/// building each from the same configuration.
+///
+/// ```rust
+/// use pricelevel::{Price, PriceLevelError, Quantity, SymbolSpec};
+///
+/// let spec = SymbolSpec::new(5, 10).with_max_qty(1_000);
+/// assert!(spec.check_price(Price::new(10_005)).is_ok());
+/// assert!(matches!(
+///     spec.check_price(Price::new(10_003)),
+///     Err(PriceLevelError::InvalidTick { .. })
+/// ));
+/// assert!(matches!(
+///     spec.check_quantity(Quantity::new(1_010)),
+///     Err(PriceLevelError::InvalidLot { .. })
+/// ));
+/// ```
+///
+/// [`PriceLevelConfig::with_symbol_spec`]: crate::PriceLevelConfig::with_symbol_spec
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SymbolSpec {
+    tick_size: u128,
+    lot_size: u64,
+    min_qty: u64,
+    max_qty: u64,
+}
+
+impl SymbolSpec {
+    /// A spec with the given tick and lot size, accepting any positive
+    /// multiple of the lot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tick_size` or `lot_size` is zero.
+    #[must_use]
+    pub fn new(tick_size: u128, lot_size: u64) -> Self {
+        assert!(tick_size > 0, "tick size must be positive");
+        assert!(lot_size > 0, "lot size must be positive");
+        Self {
+            tick_size,
+            lot_size,
+            min_qty: lot_size,
+            max_qty: u64::MAX,
+        }
+    }
+
+    /// The same spec, rejecting orders smaller than `min_qty`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_qty` is above the maximum quantity.
+    #[must_use]
+    pub fn with_min_qty(mut self, min_qty: u64) -> Self {
+        assert!(
+            min_qty <= self.max_qty,
+            "min quantity {min_qty} is above max quantity {}",
+            self.max_qty
+        );
+        self.min_qty = min_qty;
+        self
+    }
+
+    /// The same spec, rejecting orders larger than `max_qty`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_qty` is below the minimum quantity.
+    #[must_use]
+    pub fn with_max_qty(mut self, max_qty: u64) -> Self {
+        assert!(
+            max_qty >= self.min_qty,
+            "max quantity {max_qty} is below min quantity {}",
+            self.min_qty
+        );
+        self.max_qty = max_qty;
+        self
+    }
+
+    /// The price increment.
+    #[must_use]
+    pub fn tick_size(&self) -> u128 {
+        self.tick_size
+    }
+
+    /// The quantity increment.
+    #[must_use]
+    pub fn lot_size(&self) -> u64 {
+        self.lot_size
+    }
+
+    /// The smallest order quantity; the lot size unless set.
+    #[must_use]
+    pub fn min_qty(&self) -> u64 {
+        self.min_qty
+    }
+
+    /// The largest order quantity; unbounded unless set.
+    #[must_use]
+    pub fn max_qty(&self) -> u64 {
+        self.max_qty
+    }
+
+    /// Check that `price` is a multiple of the tick size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidTick`] if it is not.
+    pub fn check_price(&self, price: Price) -> Result<(), PriceLevelError> {
+        if price.as_u128().is_multiple_of(self.tick_size) {
+            Ok(())
+        } else {
+            Err(PriceLevelError::InvalidTick {
+                price: price.as_u128(),
+                tick_size: self.tick_size,
+            })
+        }
+    }
+
+    /// Check that `quantity` is a multiple of the lot size between the
+    /// minimum and maximum quantity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidLot`] if it is not.
+    pub fn check_quantity(&self, quantity: Quantity) -> Result<(), PriceLevelError> {
+        let quantity = quantity.as_u64();
+        self.check_lot(quantity)?;
+        if (self.min_qty..=self.max_qty).contains(&quantity) {
+            Ok(())
+        } else {
+            Err(self.invalid_lot(quantity))
+        }
+    }
+
+    /// Check an order's price against the tick size, its visible and hidden
+    /// quantities against the lot size, and its total quantity against the
+    /// minimum and maximum.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidTick`] for an off-tick price and
+    /// [`PriceLevelError::InvalidLot`] for an off-lot or out-of-range
+    /// quantity. A total that overflows `u64` is out of range.
+    pub fn check_order<T: Clone>(&self, order: &OrderType<T>) -> Result<(), PriceLevelError> {
+        self.check_price(order.price())?;
+        let visible = order.visible_quantity().as_u64();
+        let hidden = order.hidden_quantity().as_u64();
+        self.check_lot(visible)?;
+        self.check_lot(hidden)?;
+        let total = visible
+            .checked_add(hidden)
+            .ok_or_else(|| self.invalid_lot(u64::MAX))?;
+        self.check_quantity(Quantity::new(total))
+    }
+
+    /// Check only that `quantity` is a multiple of the lot size.
+    pub(crate) fn check_lot(&self, quantity: u64) -> Result<(), PriceLevelError> {
+        if quantity.is_multiple_of(self.lot_size) {
+            Ok(())
+        } else {
+            Err(self.invalid_lot(quantity))
+        }
+    }
+
+    fn invalid_lot(&self, quantity: u64) -> PriceLevelError {
+        PriceLevelError::InvalidLot {
+            quantity,
+            lot_size: self.lot_size,
+            min_qty: self.min_qty,
+            max_qty: self.max_qty,
+        }
+    }
+}
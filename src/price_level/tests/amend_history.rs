@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{AmendRecord, PriceLevel, PriceLevelConfig};
+    use crate::utils::{ManualClock, Price, Quantity, TimestampMs};
+    use std::sync::Arc;
+
+    fn order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn resize(id: u64, quantity: u64) -> OrderUpdate {
+        OrderUpdate::UpdateQuantity {
+            order_id: Id::from_u64(id),
+            new_quantity: Quantity::new(quantity),
+        }
+    }
+
+    #[test]
+    fn test_amend_history_is_off_by_default() {
+        let level = PriceLevel::new(10000);
+        level.add_order(order(1, 10)).unwrap();
+        level.update_order(resize(1, 5)).unwrap();
+        assert_eq!(level.amend_history_capacity(), 0);
+        assert!(level.order_history(Id::from_u64(1)).is_empty());
+    }
+
+    #[test]
+    fn test_order_history_records_each_update_with_its_time() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(1_000)));
+        let level: PriceLevel =
+            PriceLevel::with_config(10000, PriceLevelConfig::new().with_clock(clock.clone()));
+        level.enable_amend_history();
+        level.add_order(order(1, 10)).unwrap();
+        level.add_order(order(2, 10)).unwrap();
+
+        level.update_order(resize(1, 8)).unwrap();
+        clock.set(TimestampMs::new(2_000));
+        level.update_order(resize(2, 4)).unwrap();
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        // Finds nothing, so records nothing.
+        level.update_order(resize(7, 1)).unwrap();
+
+        let history = level.order_history(Id::from_u64(1));
+        assert_eq!(history.len(), 2);
+        assert!(matches!(
+            history[0].update,
+            OrderUpdate::UpdateQuantity { .. }
+        ));
+        assert_eq!(history[0].timestamp, TimestampMs::new(1_000));
+        assert!(matches!(history[1].update, OrderUpdate::Cancel { .. }));
+        assert_eq!(history[1].timestamp, TimestampMs::new(2_000));
+        assert_eq!(history[1].sequence, level.mutation_sequence());
+        assert!(level.order_history(Id::from_u64(7)).is_empty());
+
+        let all = level.amend_history();
+        assert_eq!(all.len(), 3);
+        assert!(
+            all.windows(2)
+                .all(|pair| pair[0].sequence < pair[1].sequence)
+        );
+        assert_eq!(all[1].update.order_id(), Id::from_u64(2));
+    }
+
+    #[test]
+    fn test_history_ring_keeps_the_latest_records() {
+        let level = PriceLevel::new(10000);
+        level.set_amend_history_capacity(3);
+        level.add_order(order(1, 100)).unwrap();
+        for quantity in (90..95).rev() {
+            level.update_order(resize(1, quantity)).unwrap();
+        }
+        let kept: Vec<_> = level
+            .order_history(Id::from_u64(1))
+            .iter()
+            .map(|record| match record.update {
+                OrderUpdate::UpdateQuantity { new_quantity, .. } => new_quantity.as_u64(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(kept, vec![92, 91, 90]);
+
+        level.set_amend_history_capacity(1);
+        assert_eq!(level.order_history(Id::from_u64(1)).len(), 1);
+        assert_eq!(level.take_amend_history().len(), 1);
+        assert!(level.amend_history().is_empty());
+
+        level.set_amend_history_capacity(0);
+        level.update_order(resize(1, 80)).unwrap();
+        assert!(level.amend_history().is_empty());
+    }
+
+    #[test]
+    fn test_order_history_exports_to_json() {
+        let level = PriceLevel::new(10000);
+        level.enable_amend_history();
+        level.add_order(order(1, 10)).unwrap();
+        level.update_order(resize(1, 6)).unwrap();
+
+        let json = level.order_history_json(Id::from_u64(1)).unwrap();
+        let records: Vec<AmendRecord> = serde_json::from_str(&json).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].update.order_id(), Id::from_u64(1));
+        assert_eq!(level.amend_history_json().unwrap(), json);
+        assert_eq!(level.order_history_json(Id::from_u64(2)).unwrap(), "[]");
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::level::{PriceLevel, PriceLevelData};
+    use crate::price_level::{ChecksumAlgorithm, PriceLevelArchive};
+    use crate::utils::{Price, Quantity, TimestampMs};
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn create_iceberg_order(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn create_level() -> PriceLevel {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_standard_order(30, 5)).unwrap();
+        level.add_order(create_iceberg_order(4, 2, 8)).unwrap();
+        level.add_order(create_standard_order(17, 7)).unwrap();
+        level
+    }
+
+    #[test]
+    fn test_archive_answers_totals_in_place() {
+        let level = create_level();
+        let bytes = PriceLevelData::from(&level).to_archive_bytes().unwrap();
+
+        let archive = PriceLevelArchive::access(&bytes).unwrap();
+
+        assert_eq!(archive.price(), Price::new(10000));
+        assert_eq!(archive.visible_quantity(), Quantity::new(14));
+        assert_eq!(archive.hidden_quantity(), Quantity::new(8));
+        assert_eq!(archive.total_quantity().unwrap(), Quantity::new(22));
+        assert_eq!(archive.order_count(), 3);
+        assert_eq!(archive.package_version(), None);
+        assert_eq!(archive.package_checksum(), None);
+    }
+
+    #[test]
+    fn test_archive_keeps_queue_order_and_finds_by_id() {
+        let level = create_level();
+        let bytes = PriceLevelData::from(&level).to_archive_bytes().unwrap();
+        let archive = PriceLevelArchive::access(&bytes).unwrap();
+
+        let ids: Vec<Id> = archive.orders().map(|order| order.id().unwrap()).collect();
+        assert_eq!(
+            ids,
+            vec![Id::from_u64(30), Id::from_u64(4), Id::from_u64(17)]
+        );
+
+        let iceberg = archive.find_order(Id::from_u64(4)).unwrap();
+        assert_eq!(iceberg.side(), Side::Sell);
+        assert_eq!(iceberg.visible_quantity(), Quantity::new(2));
+        assert_eq!(iceberg.hidden_quantity(), Quantity::new(8));
+        assert_eq!(iceberg.timestamp(), TimestampMs::new(1616823000004));
+        assert_eq!(
+            iceberg.to_order::<()>().unwrap(),
+            create_iceberg_order(4, 2, 8)
+        );
+        assert!(archive.find_order(Id::from_u64(30)).is_some());
+        assert!(archive.find_order(Id::from_u64(99)).is_none());
+    }
+
+    #[test]
+    fn test_package_archive_records_provenance() {
+        let level = create_level();
+        let package = level
+            .snapshot_package_with(ChecksumAlgorithm::XxHash64)
+            .unwrap();
+        let bytes = package.to_archive_bytes().unwrap();
+
+        let archive = PriceLevelArchive::access(&bytes).unwrap();
+
+        assert_eq!(archive.package_version(), Some(package.version()));
+        assert_eq!(
+            archive.package_checksum(),
+            Some((ChecksumAlgorithm::XxHash64, package.checksum()))
+        );
+        assert_eq!(archive.order_count(), 3);
+        assert_eq!(archive.total_quantity().unwrap(), Quantity::new(22));
+    }
+
+    #[test]
+    fn test_empty_level_archives() {
+        let level: PriceLevel = PriceLevel::new(500);
+        let bytes = PriceLevelData::from(&level).to_archive_bytes().unwrap();
+
+        let archive = PriceLevelArchive::access(&bytes).unwrap();
+
+        assert_eq!(archive.order_count(), 0);
+        assert_eq!(archive.orders().count(), 0);
+        assert!(archive.find_order(Id::from_u64(1)).is_none());
+    }
+
+    #[test]
+    fn test_corrupt_archive_is_rejected() {
+        let level = create_level();
+        let bytes = PriceLevelData::from(&level).to_archive_bytes().unwrap();
+
+        let truncated = &bytes[..bytes.len() / 2];
+        assert!(matches!(
+            PriceLevelArchive::access(truncated),
+            Err(PriceLevelError::DeserializationError { .. })
+        ));
+    }
+}
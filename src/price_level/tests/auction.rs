@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::{MatchOutcome, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{AuctionBook, AuctionEquilibrium, AuctionPhase, PriceLevelConfig};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn order(id: u64, price: u128, quantity: u64, side: Side) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn crossed() -> AuctionBook {
+        let book = AuctionBook::new(PriceLevelConfig::new());
+        book.add_order(order(1, 102, 5, Side::Buy)).unwrap();
+        book.add_order(order(2, 100, 8, Side::Buy)).unwrap();
+        book.add_order(order(3, 99, 4, Side::Buy)).unwrap();
+        book.add_order(order(4, 98, 6, Side::Sell)).unwrap();
+        book.add_order(order(5, 100, 5, Side::Sell)).unwrap();
+        book.add_order(order(6, 101, 9, Side::Sell)).unwrap();
+        book
+    }
+
+    #[test]
+    fn test_equilibrium_maximizes_volume_then_breaks_ties() {
+        assert_eq!(AuctionEquilibrium::compute(&[], &[]), None);
+        assert_eq!(AuctionEquilibrium::compute(&[(99, 5)], &[(100, 5)]), None);
+
+        // 100 executes 11, more than 98 (6) or 101 (5).
+        let equilibrium =
+            AuctionEquilibrium::compute(&[(102, 5), (100, 8), (99, 4)], &[(98, 6), (100, 5)])
+                .unwrap();
+        assert_eq!(equilibrium.price, 100);
+        assert_eq!(equilibrium.volume, 11);
+        assert_eq!((equilibrium.buy_surplus, equilibrium.sell_surplus), (2, 0));
+
+        // Equal volume and surplus at 99 and 101: buyers over pushes it up,
+        // sellers over pushes it down.
+        let buyers = AuctionEquilibrium::compute(&[(101, 12)], &[(99, 10)]).unwrap();
+        assert_eq!((buyers.price, buyers.buy_surplus), (101, 2));
+        let sellers = AuctionEquilibrium::compute(&[(101, 10)], &[(99, 12)]).unwrap();
+        assert_eq!((sellers.price, sellers.sell_surplus), (99, 2));
+        let even = AuctionEquilibrium::compute(&[(101, 10)], &[(99, 10)]).unwrap();
+        assert_eq!(even.price, 99);
+    }
+
+    #[test]
+    fn test_call_phase_accumulates_without_matching() {
+        let book = crossed();
+        assert_eq!(book.phase(), AuctionPhase::Call);
+        // Both sides rest at 100 while the book is crossed.
+        assert_eq!(book.best_bid(), Some(102));
+        assert_eq!(book.best_ask(), Some(98));
+
+        let level = book.level(Side::Sell, 100).unwrap();
+        let result = level.match_order(
+            5,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        );
+        assert_eq!(result.outcome(), MatchOutcome::Rejected);
+        assert_eq!(book.indicative().unwrap().price, 100);
+    }
+
+    #[test]
+    fn test_uncross_trades_at_one_price_and_opens_continuous_trading() {
+        let book = crossed();
+        let result = book
+            .uncross(
+                TimestampMs::new(1616823001000),
+                &SequentialIdGenerator::new(0),
+            )
+            .unwrap();
+        assert_eq!(result.equilibrium.unwrap().volume, 11);
+
+        let trades: Vec<_> = result
+            .trades
+            .iter()
+            .map(|trade| {
+                assert_eq!(trade.price(), Price::new(100));
+                assert_eq!(trade.taker_side(), Side::Buy);
+                (
+                    trade.taker_order_id(),
+                    trade.maker_order_id(),
+                    trade.quantity().as_u64(),
+                )
+            })
+            .collect();
+        let id = Id::from_u64;
+        assert_eq!(
+            trades,
+            [(id(1), id(4), 5), (id(2), id(4), 1), (id(2), id(5), 5)]
+        );
+        let sequences: Vec<_> = result
+            .trades
+            .iter()
+            .map(|trade| trade.execution_sequence())
+            .collect();
+        assert_eq!(sequences, [Some(1), Some(2), Some(3)]);
+
+        assert_eq!(book.phase(), AuctionPhase::Continuous);
+        assert_eq!(book.best_bid(), Some(100));
+        assert_eq!(book.best_ask(), Some(101));
+        assert_eq!(book.level(Side::Buy, 100).unwrap().visible_quantity(), 2);
+
+        // Continuous trading: levels match again, crossing adds are refused.
+        let level = book.level(Side::Sell, 101).unwrap();
+        let result = level.match_order(
+            3,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823002000),
+            &SequentialIdGenerator::new(0),
+        );
+        assert_eq!(result.outcome(), MatchOutcome::Filled);
+        assert!(matches!(
+            book.add_order(order(7, 101, 1, Side::Buy)),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        book.add_order(order(8, 100, 1, Side::Buy)).unwrap();
+        assert!(
+            book.uncross(
+                TimestampMs::new(1616823002000),
+                &SequentialIdGenerator::new(0)
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_closing_call_freezes_the_book_again() {
+        let book = crossed();
+        assert!(book.start_call().is_err());
+        book.uncross(
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        )
+        .unwrap();
+
+        book.start_call().unwrap();
+        assert!(book.level(Side::Sell, 101).unwrap().is_frozen());
+        book.add_order(order(7, 101, 4, Side::Buy)).unwrap();
+        let result = book
+            .uncross(
+                TimestampMs::new(1616823002000),
+                &SequentialIdGenerator::new(0),
+            )
+            .unwrap();
+        let equilibrium = result.equilibrium.unwrap();
+        assert_eq!((equilibrium.price, equilibrium.volume), (101, 4));
+        assert_eq!(book.level(Side::Sell, 101).unwrap().visible_quantity(), 5);
+
+        // A call that does not cross opens with no trade.
+        book.start_call().unwrap();
+        let result = book
+            .uncross(
+                TimestampMs::new(1616823003000),
+                &SequentialIdGenerator::new(0),
+            )
+            .unwrap();
+        assert_eq!(result.equilibrium, None);
+        assert!(result.trades.is_empty());
+        assert!(!book.level(Side::Buy, 100).unwrap().is_frozen());
+    }
+
+    #[test]
+    fn test_uncross_refuses_to_pair_unequal_sides() {
+        use crate::price_level::auction::AuctionFill;
+
+        let fill = |id: u64, quantity: u64| AuctionFill {
+            order_id: Id::from_u64(id),
+            quantity,
+            extra_fields: (),
+        };
+        let pair = |buys, sells| {
+            AuctionBook::<()>::pair(
+                buys,
+                sells,
+                100,
+                TimestampMs::new(1616823001000),
+                &SequentialIdGenerator::new(0),
+            )
+        };
+
+        let unequal = pair(vec![fill(1, 5)], vec![fill(2, 3)]);
+        assert!(matches!(
+            unequal,
+            Err(PriceLevelError::InvalidOperation { message }) if message.contains("bought 5, sold 3")
+        ));
+
+        let trades = pair(vec![fill(1, 5)], vec![fill(2, 3), fill(3, 2)]).unwrap();
+        let quantities: Vec<u64> = trades
+            .iter()
+            .map(|trade| trade.quantity().as_u64())
+            .collect();
+        assert_eq!(quantities, [3, 2]);
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{BookAnalytics, DepthLadder, MbpRecord, PriceLevel};
+    use crate::utils::{Price, Quantity, TimestampMs};
+
+    fn mbp(price: u128, side: Side, size: u64) -> MbpRecord {
+        MbpRecord {
+            price: Price::new(price),
+            side,
+            size: Quantity::new(size),
+            order_count: 1,
+        }
+    }
+
+    fn level(price: u128, side: Side, quantities: &[u64]) -> PriceLevel {
+        let level = PriceLevel::new(price);
+        for (index, quantity) in quantities.iter().enumerate() {
+            let id = price as u64 * 100 + index as u64;
+            level
+                .add_order(OrderType::Standard {
+                    id: Id::from_u64(id),
+                    price: Price::new(price),
+                    quantity: Quantity::new(*quantity),
+                    side,
+                    user_id: Hash32::zero(),
+                    timestamp: TimestampMs::new(1616823000000 + id),
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                })
+                .unwrap();
+        }
+        level
+    }
+
+    fn book() -> DepthLadder {
+        DepthLadder::new(&[
+            mbp(98, Side::Buy, 10),
+            mbp(99, Side::Buy, 30),
+            mbp(101, Side::Sell, 10),
+            mbp(104, Side::Sell, 30),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_top_of_book_metrics() {
+        let analytics = book().analytics(1);
+        assert_eq!(analytics.depth, 1);
+        assert_eq!(analytics.mid, Some(100.0));
+        assert_eq!(analytics.imbalance, Some(0.5));
+        assert_eq!(analytics.microprice, Some(100.5));
+        assert_eq!(analytics.depth_weighted_mid, Some(100.0));
+    }
+
+    #[test]
+    fn test_microprice_leans_towards_the_thinner_side() {
+        let ladder = DepthLadder::new(&[mbp(99, Side::Buy, 10), mbp(101, Side::Sell, 30)]).unwrap();
+        let analytics = ladder.analytics(1);
+        assert_eq!(analytics.imbalance, Some(-0.5));
+        assert_eq!(analytics.microprice, Some(99.5));
+    }
+
+    #[test]
+    fn test_depth_weighted_mid_averages_each_side() {
+        // Bids: (98 * 10 + 99 * 30) / 40 = 98.75; asks: (101 * 10 + 104 * 30) / 40 = 103.25.
+        assert_eq!(book().analytics(2).depth_weighted_mid, Some(101.0));
+        assert_eq!(book().analytics(10).depth_weighted_mid, Some(101.0));
+        assert_eq!(book().analytics(0).depth_weighted_mid, None);
+        assert_eq!(book().analytics(0).mid, Some(100.0));
+    }
+
+    #[test]
+    fn test_one_sided_book_has_no_metrics() {
+        let ladder = DepthLadder::new(&[mbp(99, Side::Buy, 10)]).unwrap();
+        assert_eq!(
+            ladder.analytics(3),
+            BookAnalytics {
+                depth: 3,
+                ..BookAnalytics::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_levels_and_snapshots_agree() {
+        let levels = [
+            level(98, Side::Buy, &[4, 6]),
+            level(99, Side::Buy, &[30]),
+            level(101, Side::Sell, &[10]),
+            level(104, Side::Sell, &[20, 10]),
+            PriceLevel::new(105),
+        ];
+        let from_levels = BookAnalytics::from_levels(&levels, 2).unwrap();
+        assert_eq!(from_levels, book().analytics(2));
+
+        let snapshots: Vec<_> = levels.iter().map(PriceLevel::snapshot).collect();
+        assert_eq!(
+            BookAnalytics::from_snapshots(&snapshots, 2).unwrap(),
+            from_levels
+        );
+    }
+
+    #[test]
+    fn test_analytics_serde_round_trip() {
+        let analytics = book().analytics(2);
+        let json = serde_json::to_string(&analytics).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BookAnalytics>(&json).unwrap(),
+            analytics
+        );
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::level::PriceLevel;
+    use crate::price_level::{
+        BOOK_SNAPSHOT_FORMAT_VERSION, BookSnapshot, BookSnapshotPackage, ChecksumAlgorithm,
+    };
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use serde_json::Value;
+
+    fn create_order(id: u64, price: u128, quantity: u64, side: Side) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn create_book() -> Vec<PriceLevel> {
+        let bid = PriceLevel::new(9_900);
+        bid.add_order(create_order(1, 9_900, 10, Side::Buy))
+            .unwrap();
+        bid.add_order(create_order(2, 9_900, 5, Side::Buy)).unwrap();
+        let ask = PriceLevel::new(10_100);
+        ask.add_order(create_order(3, 10_100, 7, Side::Sell))
+            .unwrap();
+        vec![bid, ask]
+    }
+
+    #[test]
+    fn test_book_package_roundtrip_restores_every_level() {
+        let book = create_book();
+        let snapshot = BookSnapshot::capture("BTC-USD", 42, TimestampMs::new(1_700_000_000), &book);
+        let package = BookSnapshotPackage::new(snapshot).unwrap();
+        assert_eq!(package.version(), BOOK_SNAPSHOT_FORMAT_VERSION);
+
+        let json = package.to_json().unwrap();
+        let decoded: BookSnapshotPackage = BookSnapshotPackage::from_json(&json).unwrap();
+        assert_eq!(decoded.snapshot().symbol(), "BTC-USD");
+        assert_eq!(decoded.snapshot().sequence(), 42);
+        assert_eq!(
+            decoded.snapshot().timestamp(),
+            TimestampMs::new(1_700_000_000)
+        );
+
+        let restored = decoded.restore().unwrap();
+        assert_eq!(restored.len(), 2);
+        for (original, restored) in book.iter().zip(&restored) {
+            assert_eq!(restored.price(), original.price());
+            assert_eq!(restored.visible_quantity(), original.visible_quantity());
+            assert_eq!(
+                restored.snapshot_by_insertion_seq(),
+                original.snapshot_by_insertion_seq()
+            );
+        }
+    }
+
+    #[test]
+    fn test_level_lookup_by_price() {
+        let book = create_book();
+        let snapshot = BookSnapshot::capture("BTC-USD", 1, TimestampMs::new(0), &book);
+
+        assert_eq!(snapshot.levels().len(), 2);
+        let ask = snapshot.level(Price::new(10_100)).unwrap();
+        assert_eq!(ask.order_count(), 1);
+        assert!(snapshot.level(Price::new(10_000)).is_none());
+    }
+
+    #[test]
+    fn test_checksum_covers_metadata() {
+        let book = create_book();
+        let package = BookSnapshotPackage::with_checksum_algorithm(
+            BookSnapshot::capture("BTC-USD", 7, TimestampMs::new(0), &book),
+            ChecksumAlgorithm::Crc32c,
+        )
+        .unwrap();
+        let mut value: Value = serde_json::from_str(&package.to_json().unwrap()).unwrap();
+        value["snapshot"]["sequence"] = Value::from(8u64);
+
+        let tampered = BookSnapshotPackage::<()>::from_json(&value.to_string()).unwrap();
+
+        assert!(matches!(
+            tampered.validate(),
+            Err(PriceLevelError::ChecksumMismatch { .. })
+        ));
+        assert!(tampered.restore().is_err());
+    }
+
+    #[test]
+    fn test_unsupported_version_rejected() {
+        let package =
+            BookSnapshotPackage::new(BookSnapshot::<()>::new("BTC-USD", 1, TimestampMs::new(0)))
+                .unwrap();
+        let mut value: Value = serde_json::from_str(&package.to_json().unwrap()).unwrap();
+        value["version"] = Value::from(99u32);
+
+        let future = BookSnapshotPackage::<()>::from_json(&value.to_string()).unwrap();
+
+        assert!(matches!(
+            future.validate(),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_restore_rejects_repeated_price() {
+        let book = create_book();
+        let snapshot = BookSnapshot::new("BTC-USD", 1, TimestampMs::new(0))
+            .with_level(book[0].snapshot())
+            .with_level(book[0].snapshot());
+
+        assert!(matches!(
+            snapshot.restore(),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_restore_rejects_order_id_in_two_levels() {
+        let bid = PriceLevel::new(9_900);
+        bid.add_order(create_order(1, 9_900, 10, Side::Buy))
+            .unwrap();
+        let ask = PriceLevel::new(10_100);
+        ask.add_order(create_order(1, 10_100, 10, Side::Sell))
+            .unwrap();
+        let package = BookSnapshotPackage::new(BookSnapshot::capture(
+            "BTC-USD",
+            1,
+            TimestampMs::new(0),
+            [&bid, &ask],
+        ))
+        .unwrap();
+
+        assert!(matches!(
+            package.restore(),
+            Err(PriceLevelError::DuplicateOrderId { .. })
+        ));
+    }
+
+    #[test]
+    fn test_empty_book_roundtrips() {
+        let package =
+            BookSnapshotPackage::new(BookSnapshot::<()>::new("ETH-USD", 0, TimestampMs::new(0)))
+                .unwrap();
+
+        let restored = BookSnapshotPackage::<()>::from_json(&package.to_json().unwrap())
+            .unwrap()
+            .restore()
+            .unwrap();
+
+        assert!(restored.is_empty());
+    }
+
+    #[test]
+    fn test_kraken_checksum_orders_asks_then_bids() {
+        let level = |price: u128, quantity: u64, side: Side| {
+            let level = PriceLevel::new(price);
+            level
+                .add_order(create_order(price as u64, price, quantity, side))
+                .unwrap();
+            level
+        };
+        let book = vec![
+            level(99, 4, Side::Buy),
+            level(102, 7, Side::Sell),
+            level(100, 3, Side::Buy),
+            level(101, 5, Side::Sell),
+            PriceLevel::new(98),
+        ];
+        let snapshot = BookSnapshot::capture("BTC-USD", 1, TimestampMs::new(1), &book);
+
+        // "101" "5" "102" "7" "100" "3" "99" "4"
+        assert_eq!(snapshot.checksum_crc32(10), 482_282_898);
+        // "101" "5" "100" "3"
+        assert_eq!(snapshot.checksum_crc32(1), 783_017_879);
+    }
+
+    #[test]
+    fn test_okx_checksum_interleaves_bids_and_asks() {
+        let level = |price: u128, quantity: u64, side: Side| {
+            let level = PriceLevel::new(price);
+            level
+                .add_order(create_order(price as u64, price, quantity, side))
+                .unwrap();
+            level
+        };
+        let book = vec![
+            level(33_661, 7, Side::Buy),
+            level(33_660, 6, Side::Buy),
+            level(33_668, 9, Side::Sell),
+            level(33_680, 8, Side::Sell),
+        ];
+        let snapshot = BookSnapshot::capture("BTC-USDT", 1, TimestampMs::new(1), &book);
+
+        // The published example: "3366.1:7:3366.8:9:3366:6:3368:8".
+        assert_eq!(snapshot.checksum_crc32_okx(25, 1, 0), -1_881_014_294);
+    }
+}
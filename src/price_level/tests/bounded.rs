@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::{CapacityLimit, PriceLevelError};
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderStatus, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{BoundedAdd, BoundedLevel, OverflowPolicy, OverflowStats};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn order(id: u64, side: Side) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(10),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn take(bounded: &BoundedLevel, quantity: u64) -> MatchResult<()> {
+        bounded.match_order(
+            quantity,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    fn resting_ids(bounded: &BoundedLevel) -> Vec<Id> {
+        bounded
+            .level()
+            .snapshot_orders()
+            .iter()
+            .map(|order| order.id())
+            .collect()
+    }
+
+    #[test]
+    fn test_reject_policy_refuses_adds_past_the_bound() {
+        let bounded = BoundedLevel::new(100, 2, OverflowPolicy::Reject);
+        assert!(matches!(
+            bounded.add_order(order(1, Side::Sell)),
+            Ok(BoundedAdd::Resting(_))
+        ));
+        bounded.add_order(order(2, Side::Sell)).unwrap();
+        assert!(bounded.is_full());
+        assert!(matches!(
+            bounded.add_order(order(3, Side::Sell)),
+            Err(PriceLevelError::CapacityExceeded {
+                limit: CapacityLimit::Orders,
+                max: 2,
+            })
+        ));
+        // The level itself enforces the bound on a direct add.
+        assert!(bounded.level().add_order(order(4, Side::Sell)).is_err());
+        assert_eq!(bounded.stats().rejected, 1);
+        assert_eq!(bounded.level().order_count(), 2);
+    }
+
+    #[test]
+    fn test_spilled_orders_are_promoted_in_order_as_the_level_drains() {
+        let bounded = BoundedLevel::new(100, 2, OverflowPolicy::Spill);
+        for id in 1..=2 {
+            bounded.add_order(order(id, Side::Sell)).unwrap();
+        }
+        assert!(matches!(
+            bounded.add_order(order(3, Side::Sell)),
+            Ok(BoundedAdd::Spilled { position: 0 })
+        ));
+        assert!(matches!(
+            bounded.add_order(order(4, Side::Sell)),
+            Ok(BoundedAdd::Spilled { position: 1 })
+        ));
+        assert_eq!(bounded.level().visible_quantity(), 20);
+
+        let result = take(&bounded, 10);
+        assert_eq!(result.trades().as_slice().len(), 1);
+        assert_eq!(resting_ids(&bounded), [Id::from_u64(2), Id::from_u64(3)]);
+
+        // A later add queues behind the spilled order, not ahead of it.
+        assert!(matches!(
+            bounded.add_order(order(5, Side::Sell)),
+            Ok(BoundedAdd::Spilled { position: 1 })
+        ));
+        bounded
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+        assert_eq!(resting_ids(&bounded), [Id::from_u64(3), Id::from_u64(4)]);
+        assert_eq!(
+            bounded.stats(),
+            OverflowStats {
+                spilled: 3,
+                promoted: 2,
+                cancelled: 0,
+                rejected: 0,
+                pending: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_spilled_orders_are_validated_and_cancellable() {
+        let bounded = BoundedLevel::new(100, 1, OverflowPolicy::Spill);
+        bounded.add_order(order(1, Side::Sell)).unwrap();
+        bounded.add_order(order(2, Side::Sell)).unwrap();
+
+        assert!(matches!(
+            bounded.add_order(order(2, Side::Sell)),
+            Err(PriceLevelError::DuplicateOrderId { order_id }) if order_id == Id::from_u64(2)
+        ));
+        assert!(matches!(
+            bounded.add_order(order(3, Side::Buy)),
+            Err(PriceLevelError::SideMismatch { .. })
+        ));
+        assert!(matches!(
+            bounded.update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(2),
+                new_quantity: Quantity::new(5),
+            }),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+
+        let outcome = bounded
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            crate::orders::UpdateOutcome::Cancelled(order, OrderStatus::New)
+                if order.id() == Id::from_u64(2)
+        ));
+        assert_eq!(bounded.overflow_len(), 0);
+        assert_eq!(bounded.stats().cancelled, 1);
+    }
+
+    #[test]
+    fn test_promotion_waits_for_a_frozen_level() {
+        let bounded = BoundedLevel::new(100, 1, OverflowPolicy::Spill);
+        bounded.add_order(order(1, Side::Sell)).unwrap();
+        bounded.add_order(order(2, Side::Sell)).unwrap();
+        bounded
+            .level()
+            .freeze_with(crate::price_level::HaltPolicy::new().with_adds(false));
+        bounded
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        assert_eq!(bounded.overflow_len(), 1);
+
+        bounded.level().unfreeze();
+        assert_eq!(bounded.promote(), 1);
+        assert_eq!(resting_ids(&bounded), [Id::from_u64(2)]);
+    }
+}
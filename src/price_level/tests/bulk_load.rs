@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{LevelLimits, PriceLevel, PriceLevelConfig};
+    use crate::utils::{Price, Quantity, TimestampMs};
+
+    fn order(id: u64, quantity: u64, timestamp: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn iceberg(id: u64, visible: u64, hidden: u64, timestamp: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn queue(level: &PriceLevel) -> Vec<Id> {
+        level.orders().map(|order| order.id()).collect()
+    }
+
+    #[test]
+    fn test_load_sorts_by_timestamp_and_sets_counters() {
+        let level: PriceLevel = PriceLevel::new(100);
+        let sequence = level.mutation_sequence();
+        let loaded = level
+            .load_bulk(vec![
+                order(1, 10, 30),
+                iceberg(2, 5, 20, 10),
+                order(3, 7, 20),
+                order(4, 1, 10),
+            ])
+            .unwrap();
+
+        assert_eq!(loaded, 4);
+        assert_eq!(
+            queue(&level),
+            [2, 4, 3, 1].map(Id::from_u64),
+            "timestamp order, ties kept in batch order"
+        );
+        assert_eq!(level.visible_quantity(), 23);
+        assert_eq!(level.hidden_quantity(), 20);
+        assert_eq!(level.order_count(), 4);
+        assert_eq!(level.stats().orders_added(), 4);
+        assert_eq!(level.mutation_sequence(), sequence + 1);
+
+        // Later orders join behind the loaded ones.
+        level.load_bulk(vec![order(5, 2, 0)]).unwrap();
+        assert_eq!(queue(&level).last(), Some(&Id::from_u64(5)));
+        assert_eq!(level.load_bulk(Vec::new()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_rejected_batch_leaves_the_level_unchanged() {
+        let level: PriceLevel = PriceLevel::with_config(
+            100,
+            PriceLevelConfig::new().with_limits(LevelLimits::new().with_max_orders(3)),
+        );
+        level.add_order(order(1, 10, 1)).unwrap();
+        let sequence = level.mutation_sequence();
+
+        let mut sell = order(3, 5, 2);
+        if let OrderType::Standard { side, .. } = &mut sell {
+            *side = Side::Sell;
+        }
+        let duplicate = level.load_bulk(vec![order(2, 5, 2), order(2, 5, 3)]);
+        let resting = level.load_bulk(vec![order(1, 5, 2)]);
+        let mixed = level.load_bulk(vec![order(2, 5, 2), sell]);
+        let over = level.load_bulk(vec![order(2, 5, 2), order(3, 5, 3), order(4, 5, 4)]);
+
+        assert!(matches!(
+            duplicate,
+            Err(PriceLevelError::DuplicateOrderId { .. })
+        ));
+        assert!(matches!(
+            resting,
+            Err(PriceLevelError::DuplicateOrderId { .. })
+        ));
+        assert!(matches!(mixed, Err(PriceLevelError::SideMismatch { .. })));
+        assert!(matches!(
+            over,
+            Err(PriceLevelError::CapacityExceeded { .. })
+        ));
+        assert_eq!(level.mutation_sequence(), sequence);
+        assert_eq!(queue(&level), [Id::from_u64(1)]);
+        assert_eq!(level.visible_quantity(), 10);
+
+        assert_eq!(
+            level
+                .load_bulk(vec![order(2, 5, 2), order(3, 5, 3)])
+                .unwrap(),
+            2
+        );
+    }
+}
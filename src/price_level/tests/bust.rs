@@ -0,0 +1,189 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::{TakerKind, Trade};
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{MatchingPolicy, PriceLevel, PriceLevelStatistics};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn standard(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn take(level: &PriceLevel, quantity: u64) -> Vec<Trade> {
+        level
+            .match_order(
+                quantity,
+                Id::from_u64(99),
+                TimeInForce::Ioc,
+                TakerKind::Standard,
+                TimestampMs::new(1616823001000),
+                &SequentialIdGenerator::new(0),
+            )
+            .trades()
+            .as_slice()
+            .to_vec()
+    }
+
+    fn queue(level: &PriceLevel) -> Vec<Id> {
+        level
+            .snapshot_by_insertion_seq()
+            .iter()
+            .map(|order| order.id())
+            .collect()
+    }
+
+    #[test]
+    fn test_bust_reverses_execution_statistics() {
+        let level = PriceLevel::new(100);
+        level.add_order(standard(1, 10)).unwrap();
+        level.add_order(standard(2, 10)).unwrap();
+        let trades = take(&level, 15);
+        assert_eq!(level.stats().orders_executed(), 2);
+
+        let sequence = level.mutation_sequence();
+        let reinstated = level.bust_execution(&trades[1], None).unwrap();
+        assert!(reinstated.is_none());
+        assert_eq!(level.stats().orders_executed(), 1);
+        assert_eq!(level.stats().quantity_executed(), 10);
+        assert_eq!(level.stats().value_executed(), 1_000);
+        assert!(!level.stats().stats_degraded());
+        assert_eq!(level.mutation_sequence(), sequence + 1);
+        // Without reinstatement the book is untouched.
+        assert_eq!(level.visible_quantity(), 5);
+        assert_eq!(queue(&level), vec![Id::from_u64(2)]);
+    }
+
+    #[test]
+    fn test_bust_reinstates_filled_maker_at_front() {
+        let level = PriceLevel::new(100);
+        for id in 1..=3 {
+            level.add_order(standard(id, 10)).unwrap();
+        }
+        let trades = take(&level, 10);
+        assert_eq!(queue(&level), vec![Id::from_u64(2), Id::from_u64(3)]);
+
+        let reinstated = level
+            .bust_execution(&trades[0], Some(standard(1, 10)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(reinstated.id(), Id::from_u64(1));
+        assert_eq!(
+            queue(&level),
+            vec![Id::from_u64(1), Id::from_u64(2), Id::from_u64(3)]
+        );
+        assert_eq!(level.visible_quantity(), 30);
+        assert_eq!(level.order_count(), 3);
+        assert_eq!(level.stats().orders_executed(), 0);
+
+        let trades = take(&level, 5);
+        assert_eq!(trades[0].maker_order_id(), Id::from_u64(1));
+    }
+
+    #[test]
+    fn test_later_reinstatement_goes_ahead_of_earlier() {
+        let level = PriceLevel::new(100);
+        for id in 1..=3 {
+            level.add_order(standard(id, 10)).unwrap();
+        }
+        let trades = take(&level, 20);
+        // Last trade first restores the original queue.
+        level
+            .bust_execution(&trades[1], Some(standard(2, 10)))
+            .unwrap();
+        level
+            .bust_execution(&trades[0], Some(standard(1, 10)))
+            .unwrap();
+        assert_eq!(
+            queue(&level),
+            vec![Id::from_u64(1), Id::from_u64(2), Id::from_u64(3)]
+        );
+    }
+
+    #[test]
+    fn test_bust_rejections_leave_level_unchanged() {
+        let level = PriceLevel::new(100);
+        level.add_order(standard(1, 10)).unwrap();
+        level.add_order(standard(2, 10)).unwrap();
+        let trades = take(&level, 4);
+        let sequence = level.mutation_sequence();
+
+        // The maker still rests.
+        assert!(matches!(
+            level.bust_execution(&trades[0], Some(standard(1, 4))),
+            Err(PriceLevelError::DuplicateOrderId { order_id }) if order_id == Id::from_u64(1)
+        ));
+        // Not the trade's maker.
+        assert!(matches!(
+            level.bust_execution(&trades[0], Some(standard(3, 4))),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        // Printed at another price.
+        let elsewhere = Trade::with_timestamp(
+            Id::from_u64(50),
+            Id::from_u64(99),
+            Id::from_u64(1),
+            Price::new(101),
+            Quantity::new(4),
+            Side::Buy,
+            TimestampMs::new(1616823001000),
+        );
+        assert!(matches!(
+            level.bust_execution(&elsewhere, None),
+            Err(PriceLevelError::PriceMismatch {
+                expected: 100,
+                actual: 101
+            })
+        ));
+
+        assert_eq!(level.mutation_sequence(), sequence);
+        assert_eq!(level.stats().orders_executed(), 1);
+        assert_eq!(level.visible_quantity(), 16);
+    }
+
+    #[test]
+    fn test_reinstated_maker_trades_under_strict_fifo() {
+        let level = PriceLevel::new(100);
+        level.set_matching_policy(MatchingPolicy::StrictFifo);
+        level.add_order(standard(1, 10)).unwrap();
+        level.add_order(standard(2, 10)).unwrap();
+        let trades = take(&level, 15);
+        level
+            .bust_execution(&trades[0], Some(standard(1, 10)))
+            .unwrap();
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+
+        let trades = take(&level, 10);
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].maker_order_id(), Id::from_u64(1));
+        assert_eq!(level.fifo_violations(), 0);
+    }
+
+    #[test]
+    fn test_record_bust_clamps_and_marks_degraded() {
+        let stats = PriceLevelStatistics::new();
+        stats.record_execution(5, 100, 0, 1_000).unwrap();
+        stats.record_bust(5, 100);
+        assert_eq!(stats.orders_executed(), 0);
+        assert_eq!(stats.quantity_executed(), 0);
+        assert!(!stats.stats_degraded());
+
+        stats.record_bust(5, 100);
+        assert_eq!(stats.orders_executed(), 0);
+        assert_eq!(stats.value_executed(), 0);
+        assert!(stats.stats_degraded());
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderStatus, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{LevelLimits, PriceLevel, PriceLevelConfig};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn order(id: u64, quantity: u64, side: Side) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn resting(config: PriceLevelConfig) -> PriceLevel {
+        let level = PriceLevel::with_config(100, config);
+        level.add_order(order(1, 10, Side::Sell)).unwrap();
+        level.add_order(order(2, 5, Side::Sell)).unwrap();
+        level
+    }
+
+    fn queue(level: &PriceLevel) -> Vec<Id> {
+        level.orders().map(|order| order.id()).collect()
+    }
+
+    #[test]
+    fn test_replacement_records_the_original_id() {
+        let level = resting(PriceLevelConfig::new());
+        level.match_order(
+            4,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        );
+        let before = level.mutation_sequence();
+
+        let replaced = level
+            .cancel_replace_sequenced(Id::from_u64(1), order(3, 8, Side::Sell))
+            .unwrap();
+        assert_eq!(replaced.sequence(), before + 1);
+        let replaced = replaced.into_value();
+        assert_eq!(replaced.orig_order_id(), Id::from_u64(1));
+        assert_eq!(replaced.replaced.visible_quantity(), Quantity::new(6));
+        assert_eq!(replaced.replaced_status, OrderStatus::PartiallyFilled);
+        assert_eq!(replaced.status_transition().to, OrderStatus::Cancelled);
+        assert_eq!(replaced.replacement.id(), Id::from_u64(3));
+
+        assert_eq!(queue(&level), [Id::from_u64(2), Id::from_u64(3)]);
+        assert_eq!(level.visible_quantity(), 13);
+        assert_eq!(level.orig_order_id(Id::from_u64(3)), Some(Id::from_u64(1)));
+        assert_eq!(level.orig_order_id(Id::from_u64(2)), None);
+        assert_eq!(level.order_status(Id::from_u64(3)), Some(OrderStatus::New));
+
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(3),
+            })
+            .unwrap();
+        assert_eq!(level.orig_order_id(Id::from_u64(3)), None);
+    }
+
+    #[test]
+    fn test_replacement_may_keep_the_id() {
+        let level = resting(PriceLevelConfig::new());
+        level
+            .cancel_replace(Id::from_u64(1), order(1, 3, Side::Sell))
+            .unwrap();
+        assert_eq!(queue(&level), [Id::from_u64(2), Id::from_u64(1)]);
+        assert_eq!(level.orig_order_id(Id::from_u64(1)), Some(Id::from_u64(1)));
+        assert_eq!(level.visible_quantity(), 8);
+    }
+
+    #[test]
+    fn test_rejected_replacement_leaves_the_level_unchanged() {
+        let level =
+            resting(PriceLevelConfig::new().with_limits(LevelLimits::new().with_max_quantity(20)));
+        let sequence = level.mutation_sequence();
+        let mut off_price = order(3, 5, Side::Sell);
+        if let OrderType::Standard { price, .. } = &mut off_price {
+            *price = Price::new(101);
+        }
+
+        let rejections = [
+            (9, order(3, 5, Side::Sell)),
+            (1, order(3, 5, Side::Buy)),
+            (1, order(2, 5, Side::Sell)),
+            (1, off_price),
+            (1, order(3, 16, Side::Sell)),
+        ];
+        let errors: Vec<_> = rejections
+            .into_iter()
+            .map(|(id, replacement)| {
+                level
+                    .cancel_replace(Id::from_u64(id), replacement)
+                    .unwrap_err()
+            })
+            .collect();
+        assert!(matches!(errors[0], PriceLevelError::OrderNotFound { .. }));
+        assert!(matches!(errors[1], PriceLevelError::SideMismatch { .. }));
+        assert!(matches!(
+            errors[2],
+            PriceLevelError::DuplicateOrderId { .. }
+        ));
+        assert!(matches!(errors[3], PriceLevelError::PriceMismatch { .. }));
+        assert!(matches!(
+            errors[4],
+            PriceLevelError::CapacityExceeded { .. }
+        ));
+
+        assert_eq!(level.mutation_sequence(), sequence);
+        assert_eq!(queue(&level), [Id::from_u64(1), Id::from_u64(2)]);
+        assert_eq!(level.visible_quantity(), 15);
+
+        // Within the limit once the replaced order's quantity is released.
+        level
+            .cancel_replace(Id::from_u64(1), order(3, 15, Side::Sell))
+            .unwrap();
+        assert_eq!(level.visible_quantity(), 20);
+    }
+}
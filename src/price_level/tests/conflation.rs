@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{Conflator, PriceLevel};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn standard(id: u64, price: u128, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn take(level: &PriceLevel, quantity: u64) -> u64 {
+        level
+            .match_order(
+                quantity,
+                Id::from_u64(99),
+                TimeInForce::Ioc,
+                TakerKind::Standard,
+                TimestampMs::new(1616823001000),
+                &SequentialIdGenerator::new(0),
+            )
+            .executed_quantity()
+            .unwrap()
+            .as_u64()
+    }
+
+    #[test]
+    fn test_changes_within_interval_coalesce_into_latest_depth() {
+        let level = Arc::new(PriceLevel::new(100));
+        let conflator = Conflator::new(Duration::from_millis(100));
+        for id in 1..=4 {
+            level.add_order(standard(id, 100, 10)).unwrap();
+            conflator.record_change(&level);
+        }
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+        conflator.record_change(&level);
+        assert_eq!(conflator.pending(), 1);
+
+        let updates = conflator.poll(TimestampMs::new(1_000)).unwrap();
+        assert_eq!(updates.len(), 1);
+        let update = updates[0];
+        assert_eq!(update.price, Price::new(100));
+        assert_eq!(update.side, Some(Side::Sell));
+        assert_eq!(update.size, Quantity::new(30));
+        assert_eq!(update.order_count, 3);
+        assert_eq!(update.events, 5);
+        assert_eq!(update.timestamp, TimestampMs::new(1_000));
+        assert_eq!(conflator.ingested(), 5);
+        assert_eq!(conflator.emitted(), 1);
+        assert_eq!(conflator.pending(), 0);
+    }
+
+    #[test]
+    fn test_busy_level_emits_at_most_once_per_interval() {
+        let level = Arc::new(PriceLevel::new(100));
+        let conflator = Conflator::new(Duration::from_millis(100));
+        level.add_order(standard(1, 100, 10)).unwrap();
+        conflator.record_change(&level);
+        assert_eq!(conflator.poll(TimestampMs::new(1_000)).unwrap().len(), 1);
+
+        level.add_order(standard(2, 100, 5)).unwrap();
+        conflator.record_change(&level);
+        assert!(conflator.poll(TimestampMs::new(1_050)).unwrap().is_empty());
+        assert_eq!(conflator.pending(), 1);
+
+        level.add_order(standard(3, 100, 5)).unwrap();
+        conflator.record_change(&level);
+        let updates = conflator.poll(TimestampMs::new(1_100)).unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].size, Quantity::new(20));
+        assert_eq!(updates[0].events, 2);
+    }
+
+    #[test]
+    fn test_quiet_level_emits_on_next_poll_after_interval() {
+        let level = Arc::new(PriceLevel::new(100));
+        let conflator = Conflator::new(Duration::from_millis(100));
+        level.add_order(standard(1, 100, 10)).unwrap();
+        conflator.record_change(&level);
+        conflator.poll(TimestampMs::new(1_000)).unwrap();
+
+        // Quiet for a whole interval: forgotten, so its next change goes out
+        // on the next poll.
+        assert!(conflator.poll(TimestampMs::new(1_200)).unwrap().is_empty());
+        level.add_order(standard(2, 100, 10)).unwrap();
+        conflator.record_change(&level);
+        assert_eq!(conflator.poll(TimestampMs::new(1_210)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_trade_volume_sums_and_resets_after_emission() {
+        let level = Arc::new(PriceLevel::new(100));
+        let conflator = Conflator::new(Duration::from_millis(100));
+        level.add_order(standard(1, 100, 50)).unwrap();
+        conflator.record_change(&level);
+        for quantity in [5, 7] {
+            let executed = take(&level, quantity);
+            conflator.record_trade(&level, executed);
+        }
+
+        let updates = conflator.poll(TimestampMs::new(1_000)).unwrap();
+        assert_eq!(updates[0].traded_quantity, Quantity::new(12));
+        assert_eq!(updates[0].trade_count, 2);
+        assert_eq!(updates[0].size, Quantity::new(38));
+
+        conflator.record_change(&level);
+        let updates = conflator.poll(TimestampMs::new(1_100)).unwrap();
+        assert_eq!(updates[0].traded_quantity, Quantity::new(0));
+        assert_eq!(updates[0].trade_count, 0);
+    }
+
+    #[test]
+    fn test_flush_ignores_interval_and_orders_by_price() {
+        let high = Arc::new(PriceLevel::new(101));
+        let low = Arc::new(PriceLevel::new(100));
+        let conflator = Conflator::new(Duration::from_secs(60));
+        high.add_order(standard(1, 101, 10)).unwrap();
+        conflator.record_change(&high);
+        low.add_order(standard(2, 100, 10)).unwrap();
+        conflator.record_change(&low);
+        conflator.poll(TimestampMs::new(1_000)).unwrap();
+
+        high.add_order(standard(3, 101, 10)).unwrap();
+        conflator.record_change(&high);
+        low.add_order(standard(4, 100, 10)).unwrap();
+        conflator.record_change(&low);
+        assert!(conflator.poll(TimestampMs::new(1_001)).unwrap().is_empty());
+
+        let updates = conflator.flush(TimestampMs::new(1_001)).unwrap();
+        let prices: Vec<_> = updates.iter().map(|update| update.price).collect();
+        assert_eq!(prices, vec![Price::new(100), Price::new(101)]);
+        assert_eq!(conflator.pending(), 0);
+    }
+
+    #[test]
+    fn test_emptied_level_reports_no_side() {
+        let level = Arc::new(PriceLevel::new(100));
+        let conflator = Conflator::new(Duration::from_millis(100));
+        level.add_order(standard(1, 100, 10)).unwrap();
+        conflator.record_change(&level);
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        conflator.record_change(&level);
+
+        let updates = conflator.poll(TimestampMs::new(1_000)).unwrap();
+        assert_eq!(updates[0].side, None);
+        assert_eq!(updates[0].size, Quantity::new(0));
+        assert_eq!(updates[0].order_count, 0);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_ingest_counts_trade_events() {
+        use futures_lite::StreamExt;
+        use futures_lite::future::{block_on, poll_once};
+
+        let level = Arc::new(PriceLevel::new(100));
+        let mut events = Box::pin(level.subscribe());
+        let conflator = Conflator::new(Duration::from_millis(100));
+        level.add_order(standard(1, 100, 20)).unwrap();
+        take(&level, 8);
+
+        block_on(async {
+            while let Some(Some(event)) = poll_once(events.next()).await {
+                conflator.ingest(&level, &event);
+            }
+        });
+        let updates = conflator.poll(TimestampMs::new(1_000)).unwrap();
+        assert_eq!(updates[0].trade_count, 1);
+        assert_eq!(updates[0].traded_quantity, Quantity::new(8));
+        assert_eq!(updates[0].size, Quantity::new(12));
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{BookSnapshot, DepthLadder, DepthRung, MbpRecord, PriceLevel};
+    use crate::utils::{Price, Quantity, TimestampMs};
+
+    fn mbp(price: u128, side: Side, size: u64, order_count: u64) -> MbpRecord {
+        MbpRecord {
+            price: Price::new(price),
+            side,
+            size: Quantity::new(size),
+            order_count,
+        }
+    }
+
+    fn rung(price: u128, size: u64, cumulative_size: u64, order_count: u64) -> DepthRung {
+        DepthRung {
+            price: Price::new(price),
+            size: Quantity::new(size),
+            cumulative_size: Quantity::new(cumulative_size),
+            order_count,
+        }
+    }
+
+    fn level(price: u128, side: Side, quantities: &[u64]) -> PriceLevel {
+        let level = PriceLevel::new(price);
+        for (index, quantity) in quantities.iter().enumerate() {
+            let id = price as u64 * 100 + index as u64;
+            level
+                .add_order(OrderType::Standard {
+                    id: Id::from_u64(id),
+                    price: Price::new(price),
+                    quantity: Quantity::new(*quantity),
+                    side,
+                    user_id: Hash32::zero(),
+                    timestamp: TimestampMs::new(1616823000000 + id),
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                })
+                .unwrap();
+        }
+        level
+    }
+
+    #[test]
+    fn test_sides_ordered_from_best_price() {
+        let levels = [
+            mbp(98, Side::Buy, 4, 2),
+            mbp(101, Side::Sell, 3, 1),
+            mbp(99, Side::Buy, 5, 1),
+            mbp(103, Side::Sell, 7, 3),
+            mbp(102, Side::Sell, 2, 1),
+        ];
+        let ladder = DepthLadder::new(&levels).unwrap();
+        assert_eq!(ladder.tick(), 1);
+        assert_eq!(ladder.bids(), &[rung(99, 5, 5, 1), rung(98, 4, 9, 2)]);
+        assert_eq!(
+            ladder.asks(),
+            &[rung(101, 3, 3, 1), rung(102, 2, 5, 1), rung(103, 7, 12, 3)]
+        );
+        assert_eq!(ladder.best(Side::Buy), Some(&rung(99, 5, 5, 1)));
+        assert_eq!(ladder.best(Side::Sell), Some(&rung(101, 3, 3, 1)));
+        assert_eq!(ladder.total_size(Side::Buy), Quantity::new(9));
+        assert_eq!(ladder.total_size(Side::Sell), Quantity::new(12));
+    }
+
+    #[test]
+    fn test_tick_rounds_bids_down_and_asks_up() {
+        let levels = [
+            mbp(1_000, Side::Buy, 1, 1),
+            mbp(1_009, Side::Buy, 2, 1),
+            mbp(1_010, Side::Buy, 4, 1),
+            mbp(1_011, Side::Sell, 3, 1),
+            mbp(1_020, Side::Sell, 5, 2),
+            mbp(1_021, Side::Sell, 6, 1),
+        ];
+        let ladder = DepthLadder::with_tick(&levels, 10).unwrap();
+        assert_eq!(ladder.bids(), &[rung(1_010, 4, 4, 1), rung(1_000, 3, 7, 2)]);
+        assert_eq!(
+            ladder.asks(),
+            &[rung(1_020, 8, 8, 3), rung(1_030, 6, 14, 1)]
+        );
+    }
+
+    #[test]
+    fn test_empty_records_and_sides() {
+        let levels = [mbp(100, Side::Buy, 0, 0), mbp(101, Side::Sell, 2, 1)];
+        let ladder = DepthLadder::new(&levels).unwrap();
+        assert!(ladder.bids().is_empty());
+        assert_eq!(ladder.best(Side::Buy), None);
+        assert_eq!(ladder.total_size(Side::Buy), Quantity::new(0));
+        assert_eq!(ladder.asks().len(), 1);
+
+        let ladder = DepthLadder::new(&[]).unwrap();
+        assert!(ladder.bids().is_empty() && ladder.asks().is_empty());
+    }
+
+    #[test]
+    fn test_zero_tick_rejected() {
+        assert!(matches!(
+            DepthLadder::with_tick(&[mbp(100, Side::Buy, 1, 1)], 0),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_overflow_rejected() {
+        let levels = [mbp(u128::MAX, Side::Sell, 1, 1)];
+        assert!(DepthLadder::with_tick(&levels, 10).is_err());
+
+        let levels = [mbp(100, Side::Buy, u64::MAX, 1), mbp(99, Side::Buy, 1, 1)];
+        assert!(matches!(
+            DepthLadder::new(&levels),
+            Err(PriceLevelError::InvalidOperation { message }) if message.contains("cumulative")
+        ));
+        assert!(DepthLadder::with_tick(&levels, 10).is_err());
+    }
+
+    #[test]
+    fn test_from_book_snapshot_levels() {
+        let book = BookSnapshot::new("BTC-USD", 1, TimestampMs::new(1))
+            .with_level(level(99, Side::Buy, &[3, 4]).snapshot())
+            .with_level(level(97, Side::Buy, &[1]).snapshot())
+            .with_level(level(101, Side::Sell, &[5]).snapshot())
+            .with_level(level(104, Side::Sell, &[2, 2, 2]).snapshot());
+        let ladder = DepthLadder::from_snapshots(book.levels(), 2).unwrap();
+        assert_eq!(ladder.bids(), &[rung(98, 7, 7, 2), rung(96, 1, 8, 1)]);
+        assert_eq!(ladder.asks(), &[rung(102, 5, 5, 1), rung(104, 6, 11, 3)]);
+
+        let level = level(99, Side::Buy, &[3, 4]);
+        let ladder = DepthLadder::from_snapshots(&[level.snapshot()], 1).unwrap();
+        let record = level.mbp_record().unwrap().unwrap();
+        assert_eq!(ladder.bids(), &[rung(99, record.size.as_u64(), 7, 2)]);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let ladder =
+            DepthLadder::with_tick(&[mbp(99, Side::Buy, 5, 1), mbp(101, Side::Sell, 3, 1)], 2)
+                .unwrap();
+        let json = serde_json::to_string(&ladder).unwrap();
+        let parsed: DepthLadder = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, ladder);
+    }
+}
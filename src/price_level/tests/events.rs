@@ -0,0 +1,504 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::PriceLevelEvent;
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use futures_lite::future::{block_on, poll_once};
+    use futures_lite::{Stream, StreamExt};
+    use proptest::prelude::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn match_taker(level: &PriceLevel, taker_id: u64, quantity: u64) {
+        level.match_order(
+            quantity,
+            Id::from_u64(taker_id),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        );
+    }
+
+    #[test]
+    fn test_subscribe_streams_every_committed_change_in_order() {
+        let level = PriceLevel::new(10000);
+        let mut events = Box::pin(level.subscribe());
+
+        level.add_order(create_standard_order(1, 10)).unwrap();
+        level.add_order(create_standard_order(2, 5)).unwrap();
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(2),
+                new_quantity: Quantity::new(3),
+            })
+            .unwrap();
+        match_taker(&level, 100, 4);
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+
+        block_on(async {
+            match events.next().await {
+                Some(PriceLevelEvent::OrderAdded(order)) => assert_eq!(order.id(), Id::from_u64(1)),
+                other => panic!("expected OrderAdded, got {other:?}"),
+            }
+            assert!(matches!(
+                events.next().await,
+                Some(PriceLevelEvent::OrderAdded(_))
+            ));
+            match events.next().await {
+                Some(PriceLevelEvent::OrderUpdated(order)) => {
+                    assert_eq!(order.visible_quantity(), Quantity::new(3));
+                }
+                other => panic!("expected OrderUpdated, got {other:?}"),
+            }
+            match events.next().await {
+                Some(PriceLevelEvent::Trade(trade)) => {
+                    assert_eq!(trade.maker_order_id(), Id::from_u64(1));
+                    assert_eq!(trade.quantity(), Quantity::new(4));
+                }
+                other => panic!("expected Trade, got {other:?}"),
+            }
+            match events.next().await {
+                Some(PriceLevelEvent::OrderRemoved(order)) => {
+                    assert_eq!(order.id(), Id::from_u64(2));
+                }
+                other => panic!("expected OrderRemoved, got {other:?}"),
+            }
+        });
+    }
+
+    #[test]
+    fn test_unmatched_and_not_found_changes_publish_nothing() {
+        let level = PriceLevel::new(10000);
+        let mut events = Box::pin(level.subscribe());
+
+        match_taker(&level, 100, 4);
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(9),
+            })
+            .unwrap();
+        level.add_order(create_standard_order(1, 10)).unwrap();
+
+        let first = block_on(events.next());
+        assert!(matches!(first, Some(PriceLevelEvent::OrderAdded(_))));
+    }
+
+    #[test]
+    fn test_every_subscriber_receives_each_event() {
+        let level = PriceLevel::new(10000);
+        let mut first = Box::pin(level.subscribe());
+        let mut second = Box::pin(level.subscribe());
+        assert_eq!(level.subscriber_count(), 2);
+
+        level.add_order(create_standard_order(1, 10)).unwrap();
+
+        assert_eq!(
+            block_on(first.next()),
+            block_on(second.next()),
+            "both subscribers see the same event"
+        );
+    }
+
+    #[test]
+    fn test_sequenced_stream_stamps_events_in_commit_order() {
+        let level = Arc::new(PriceLevel::new(10000));
+        level.set_sequencing(true);
+        let mut plain = Box::pin(level.subscribe());
+        let sequenced = level.subscribe_sequenced_with_capacity(4096);
+        assert_eq!(level.subscriber_count(), 2);
+
+        let added = level
+            .add_order_sequenced(create_standard_order(1, 10))
+            .unwrap();
+        let handles: Vec<_> = (0..4_u64)
+            .map(|thread_id| {
+                let level = Arc::clone(&level);
+                thread::spawn(move || {
+                    for i in 0..25 {
+                        let id = 10 + thread_id * 100 + i;
+                        level.add_order(create_standard_order(id, 2)).unwrap();
+                        match_taker(&level, 10_000 + id, 1);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // The plain stream is unchanged by a sequenced subscriber.
+        assert!(matches!(
+            block_on(plain.next()),
+            Some(PriceLevelEvent::OrderAdded(_))
+        ));
+
+        let stamped = drain(&mut Box::pin(sequenced));
+        assert_eq!(stamped[0].sequence(), added.sequence());
+        let sequences: Vec<u64> = stamped.iter().map(|event| event.sequence()).collect();
+        let expected: Vec<u64> = (1..=level.mutation_sequence()).collect();
+        assert_eq!(sequences, expected, "one event per change, in order");
+        for event in &stamped {
+            if let PriceLevelEvent::Trade(trade) = event.value() {
+                assert_eq!(trade.level_sequence(), Some(event.sequence()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_dropping_the_stream_unsubscribes() {
+        let level = PriceLevel::new(10000);
+        let events = level.subscribe();
+        assert_eq!(level.subscriber_count(), 1);
+
+        drop(events);
+        assert_eq!(level.subscriber_count(), 0);
+
+        // Publishing to the dropped subscriber prunes it without error.
+        level.add_order(create_standard_order(1, 10)).unwrap();
+        assert_eq!(level.subscriber_count(), 0);
+        assert_eq!(level.lagged_subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_lagging_subscriber_is_disconnected_after_its_buffer() {
+        let level = PriceLevel::new(10000);
+        let mut slow = Box::pin(level.subscribe_with_capacity(2));
+        let mut fast = Box::pin(level.subscribe_with_capacity(16));
+
+        for id in 1..=3 {
+            level.add_order(create_standard_order(id, 10)).unwrap();
+        }
+        assert_eq!(level.lagged_subscriber_count(), 1);
+        assert_eq!(level.subscriber_count(), 1);
+
+        // The slow stream yields what it buffered, then ends: the gap is never
+        // silent.
+        let slow_events: Vec<_> = block_on(slow.as_mut().collect());
+        assert_eq!(slow_events.len(), 2);
+
+        // The other subscriber is unaffected.
+        for id in 1..=3 {
+            match block_on(fast.next()) {
+                Some(PriceLevelEvent::OrderAdded(order)) => {
+                    assert_eq!(order.id(), Id::from_u64(id));
+                }
+                other => panic!("expected OrderAdded, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_is_consumed_from_another_thread() {
+        let level = PriceLevel::new(10000);
+        let events = level.subscribe();
+
+        let consumer = thread::spawn(move || block_on(events.take(100).count()));
+        for id in 0..100 {
+            level.add_order(create_standard_order(id, 10)).unwrap();
+        }
+
+        assert_eq!(consumer.join().unwrap(), 100);
+    }
+
+    fn create_iceberg_order(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    /// Every event already buffered on `events`, without waiting for more.
+    fn drain<S, E>(events: &mut S) -> Vec<E>
+    where
+        S: Stream<Item = E> + Unpin,
+    {
+        let mut drained = Vec::new();
+        while let Some(Some(event)) = block_on(poll_once(events.next())) {
+            drained.push(event);
+        }
+        drained
+    }
+
+    fn assert_same_state(original: &PriceLevel, rebuilt: &PriceLevel) {
+        assert_eq!(
+            rebuilt.snapshot_by_insertion_seq(),
+            original.snapshot_by_insertion_seq(),
+            "same orders in the same queue order"
+        );
+        assert_eq!(rebuilt.visible_quantity(), original.visible_quantity());
+        assert_eq!(rebuilt.hidden_quantity(), original.hidden_quantity());
+        assert_eq!(rebuilt.order_count(), original.order_count());
+        for order in original.snapshot_orders() {
+            assert_eq!(
+                rebuilt.order_fill_state(order.id()),
+                original.order_fill_state(order.id())
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_events_rebuilds_fills_replenishment_and_resizes() {
+        let level = PriceLevel::new(10000);
+        let mut events = Box::pin(level.subscribe());
+
+        level.add_order(create_standard_order(1, 10)).unwrap();
+        level.add_order(create_iceberg_order(2, 5, 12)).unwrap();
+        level.add_order(create_standard_order(3, 8)).unwrap();
+        // Fills 1, drains 2's visible tranche (replenished at the tail) and
+        // partially fills 3.
+        match_taker(&level, 100, 18);
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(3),
+                new_quantity: Quantity::new(20),
+            })
+            .unwrap();
+        level.add_order(create_standard_order(4, 6)).unwrap();
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(4),
+            })
+            .unwrap();
+
+        let rebuilt = PriceLevel::from_events(10000, drain(&mut events)).unwrap();
+        assert_same_state(&level, &rebuilt);
+        assert_eq!(rebuilt.order_count(), 2);
+        assert!(rebuilt.order_fill_state(Id::from_u64(2)).is_some());
+    }
+
+    #[test]
+    fn test_cancel_replace_publishes_one_event_and_replays() {
+        let level = PriceLevel::new(10000);
+        let mut events = Box::pin(level.subscribe());
+
+        level.add_order(create_standard_order(1, 10)).unwrap();
+        level.add_order(create_standard_order(2, 5)).unwrap();
+        level
+            .cancel_replace(Id::from_u64(1), create_standard_order(3, 7))
+            .unwrap();
+
+        let published = drain(&mut events);
+        assert_eq!(published.len(), 3);
+        match &published[2] {
+            PriceLevelEvent::OrderReplaced {
+                replaced,
+                replacement,
+            } => {
+                assert_eq!(replaced.id(), Id::from_u64(1));
+                assert_eq!(replacement.id(), Id::from_u64(3));
+            }
+            other => panic!("expected a replacement, got {other:?}"),
+        }
+
+        let rebuilt = PriceLevel::from_events(10000, published).unwrap();
+        assert_same_state(&level, &rebuilt);
+        assert_eq!(
+            rebuilt.orig_order_id(Id::from_u64(3)),
+            Some(Id::from_u64(1))
+        );
+    }
+
+    #[test]
+    fn test_bust_publishes_one_event_and_replays() {
+        let level = PriceLevel::new(10000);
+        let mut events = Box::pin(level.subscribe());
+
+        level.add_order(create_standard_order(1, 10)).unwrap();
+        level.add_order(create_standard_order(2, 5)).unwrap();
+        let result = level.match_order(
+            10,
+            Id::from_u64(100),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        );
+        let trade = result.trades().as_slice()[0];
+        level
+            .bust_execution(&trade, Some(create_standard_order(1, 10)))
+            .unwrap();
+
+        let published = drain(&mut events);
+        assert_eq!(published.len(), 4);
+        match &published[3] {
+            PriceLevelEvent::TradeBusted { trade, reinstated } => {
+                assert_eq!(trade.maker_order_id(), Id::from_u64(1));
+                assert_eq!(
+                    reinstated.as_ref().map(|order| order.id()),
+                    Some(trade.maker_order_id())
+                );
+            }
+            other => panic!("expected a bust, got {other:?}"),
+        }
+
+        let rebuilt = PriceLevel::from_events(10000, published).unwrap();
+        assert_same_state(&level, &rebuilt);
+        assert_eq!(rebuilt.snapshot_by_insertion_seq()[0].id(), Id::from_u64(1));
+    }
+
+    #[test]
+    fn test_from_events_rebuilds_stop_activation_and_trailing_ratchet() {
+        let level = PriceLevel::new(10000);
+        let mut events = Box::pin(level.subscribe());
+
+        level
+            .add_order(OrderType::StopLimit {
+                id: Id::from_u64(1),
+                price: Price::new(10000),
+                quantity: Quantity::new(10),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1616823000001),
+                time_in_force: TimeInForce::Gtc,
+                trigger_price: Price::new(9900),
+                extra_fields: (),
+            })
+            .unwrap();
+        level
+            .add_order(OrderType::TrailingStop {
+                id: Id::from_u64(2),
+                price: Price::new(10000),
+                quantity: Quantity::new(10),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1616823000002),
+                time_in_force: TimeInForce::Gtc,
+                trail_amount: Quantity::new(100),
+                last_reference_price: Price::new(10000),
+                extra_fields: (),
+            })
+            .unwrap();
+        level.add_order(create_standard_order(3, 10)).unwrap();
+
+        assert!(
+            level
+                .update_trailing_stops(Price::new(10500))
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(level.trigger_orders(Price::new(9900)).unwrap().len(), 1);
+
+        let replayed = drain(&mut events);
+        assert!(
+            replayed
+                .iter()
+                .any(|event| matches!(event, PriceLevelEvent::OrderActivated(_)))
+        );
+        let rebuilt = PriceLevel::from_events(10000, replayed).unwrap();
+        assert_same_state(&level, &rebuilt);
+    }
+
+    #[test]
+    fn test_apply_event_rejects_unknown_orders_and_mismatched_trades() {
+        let level = PriceLevel::new(10000);
+        let mut events = Box::pin(level.subscribe());
+        level.add_order(create_standard_order(1, 10)).unwrap();
+        match_taker(&level, 100, 4);
+        let replayed = drain(&mut events);
+
+        let empty: PriceLevel = PriceLevel::new(10000);
+        assert!(empty.apply_event(replayed[1].clone()).is_err());
+
+        let short = PriceLevel::new(10000);
+        short.add_order(create_standard_order(1, 3)).unwrap();
+        assert!(short.apply_event(replayed[1].clone()).is_err());
+        assert_eq!(
+            short.visible_quantity(),
+            3,
+            "a rejected trade changes nothing"
+        );
+    }
+
+    /// One step of an arbitrary level history.
+    #[derive(Debug, Clone)]
+    enum Step {
+        Add { visible: u64, hidden: u64 },
+        Match(u64),
+        Resize { pick: usize, quantity: u64 },
+        Cancel { pick: usize },
+    }
+
+    fn step_strategy() -> impl Strategy<Value = Step> {
+        prop_oneof![
+            3 => (1u64..=50, prop_oneof![Just(0u64), 1u64..=60])
+                .prop_map(|(visible, hidden)| Step::Add { visible, hidden }),
+            3 => (1u64..=120).prop_map(Step::Match),
+            1 => (any::<usize>(), 1u64..=60)
+                .prop_map(|(pick, quantity)| Step::Resize { pick, quantity }),
+            1 => any::<usize>().prop_map(|pick| Step::Cancel { pick }),
+        ]
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig { cases: 256, ..ProptestConfig::default() })]
+
+        /// Replaying the event stream of an arbitrary history rebuilds an
+        /// equivalent level.
+        #[test]
+        fn prop_from_events_matches_original(steps in prop::collection::vec(step_strategy(), 0..40)) {
+            let level = PriceLevel::new(10000);
+            let mut events = Box::pin(level.subscribe_with_capacity(4096));
+            let mut next_id = 1;
+            for (taker, step) in steps.into_iter().enumerate() {
+                match step {
+                    Step::Add { visible, hidden } => {
+                        let order = if hidden == 0 {
+                            create_standard_order(next_id, visible)
+                        } else {
+                            create_iceberg_order(next_id, visible, hidden)
+                        };
+                        level.add_order(order).unwrap();
+                        next_id += 1;
+                    }
+                    Step::Match(quantity) => match_taker(&level, 1_000 + taker as u64, quantity),
+                    Step::Resize { .. } | Step::Cancel { .. } if level.order_count() == 0 => {}
+                    Step::Resize { pick, quantity } => {
+                        let orders = level.snapshot_orders();
+                        let order_id = orders[pick % orders.len()].id();
+                        level
+                            .update_order(OrderUpdate::UpdateQuantity {
+                                order_id,
+                                new_quantity: Quantity::new(quantity),
+                            })
+                            .unwrap();
+                    }
+                    Step::Cancel { pick } => {
+                        let orders = level.snapshot_orders();
+                        let order_id = orders[pick % orders.len()].id();
+                        level.update_order(OrderUpdate::Cancel { order_id }).unwrap();
+                    }
+                }
+            }
+
+            let rebuilt = PriceLevel::from_events(10000, drain(&mut events)).unwrap();
+            assert_same_state(&level, &rebuilt);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::Trade;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{
+        FIX_SOH, FixEntryType, FixMdEntry, FixUpdateAction, MbpRecord, PriceLevel,
+        fix_incremental_refresh,
+    };
+    use crate::utils::{Price, Quantity, TimestampMs};
+
+    fn standard(id: u64, quantity: u64, side: Side) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    /// The body with `|` for SOH, for readable assertions.
+    fn readable(body: &str) -> String {
+        body.replace(FIX_SOH, "|")
+    }
+
+    #[test]
+    fn test_full_refresh_lists_displayed_orders_in_queue_order() {
+        let level = PriceLevel::new(10000);
+        level.add_order(standard(1, 10, Side::Buy)).unwrap();
+        level.add_order(standard(2, 5, Side::Buy)).unwrap();
+
+        let body = level.to_fix_full_refresh("BTC-USD").unwrap();
+        assert_eq!(
+            readable(&body),
+            "35=W|55=BTC-USD|268=2|\
+             269=0|270=10000|271=10|278=00000000-0000-0001-0000-000000000000|290=1|\
+             269=0|270=10000|271=5|278=00000000-0000-0002-0000-000000000000|290=2|"
+        );
+    }
+
+    #[test]
+    fn test_empty_level_full_refresh_has_no_entries() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        let body = level.to_fix_full_refresh("BTC-USD").unwrap();
+        assert_eq!(readable(&body), "35=W|55=BTC-USD|268=0|");
+    }
+
+    #[test]
+    fn test_incremental_refresh_tags_each_entry() {
+        let depth = FixMdEntry::from_mbp(&MbpRecord {
+            price: Price::new(10000),
+            side: Side::Sell,
+            size: Quantity::new(25),
+            order_count: 3,
+        })
+        .with_update_action(FixUpdateAction::Change);
+        let trade = FixMdEntry::from_trade(&Trade::<()>::with_timestamp(
+            Id::from_u64(7),
+            Id::from_u64(100),
+            Id::from_u64(1),
+            Price::new(10000),
+            Quantity::new(4),
+            Side::Buy,
+            TimestampMs::new(1),
+        ));
+
+        let body = fix_incremental_refresh("BTC-USD", &[depth, trade]).unwrap();
+        assert_eq!(
+            readable(&body),
+            "35=X|268=2|\
+             279=1|55=BTC-USD|269=1|270=10000|271=25|346=3|\
+             279=0|55=BTC-USD|269=2|270=10000|271=4|278=00000000-0000-0007-0000-000000000000|"
+        );
+    }
+
+    #[test]
+    fn test_symbol_with_delimiter_is_rejected() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        assert!(level.to_fix_full_refresh("").is_err());
+        assert!(level.to_fix_full_refresh("BTC\u{1}USD").is_err());
+        assert!(fix_incremental_refresh("", &[]).is_err());
+    }
+
+    #[test]
+    fn test_entry_type_follows_side() {
+        assert_eq!(FixEntryType::for_side(Side::Buy).code(), '0');
+        assert_eq!(FixEntryType::for_side(Side::Sell).code(), '1');
+        assert_eq!(FixEntryType::Trade.code(), '2');
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_events_map_to_incremental_entries() {
+        use crate::execution::TakerKind;
+        use crate::price_level::PriceLevelEvent;
+        use crate::utils::SequentialIdGenerator;
+        use futures_lite::StreamExt;
+        use futures_lite::future::block_on;
+
+        let level = PriceLevel::new(10000);
+        let mut events = Box::pin(level.subscribe());
+        level.add_order(standard(1, 10, Side::Sell)).unwrap();
+        level.match_order(
+            4,
+            Id::from_u64(100),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &SequentialIdGenerator::new(0),
+        );
+
+        let added = block_on(events.next()).unwrap();
+        assert!(matches!(added, PriceLevelEvent::OrderAdded(_)));
+        let entry = added.to_fix_entry();
+        assert_eq!(entry.update_action, FixUpdateAction::New);
+        assert_eq!(entry.entry_type, FixEntryType::Offer);
+        assert_eq!(entry.size, Quantity::new(10));
+        assert_eq!(entry.entry_id, Some(Id::from_u64(1)));
+
+        let trade = block_on(events.next()).unwrap().to_fix_entry();
+        assert_eq!(trade.entry_type, FixEntryType::Trade);
+        assert_eq!(trade.size, Quantity::new(4));
+    }
+}
@@ -0,0 +1,57 @@
+//! Order and taker fixtures shared by the level's unit tests.
+
+use crate::errors::PriceLevelError;
+use crate::execution::{MatchResult, TakerKind};
+use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+use crate::price_level::PriceLevel;
+use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+/// A GTC standard order, timestamped by its id so ids rest in order.
+pub(crate) fn standard(
+    id: u64,
+    price: u128,
+    quantity: u64,
+    side: Side,
+    user_id: Hash32,
+) -> OrderType<()> {
+    OrderType::Standard {
+        id: Id::from_u64(id),
+        price: Price::new(price),
+        quantity: Quantity::new(quantity),
+        side,
+        user_id,
+        timestamp: TimestampMs::new(1616823000000 + id),
+        time_in_force: TimeInForce::Gtc,
+        extra_fields: (),
+    }
+}
+
+/// A [`standard`] sell of the zero user.
+pub(crate) fn sell(id: u64, price: u128, quantity: u64) -> OrderType<()> {
+    standard(id, price, quantity, Side::Sell, Hash32::zero())
+}
+
+/// An IOC taker of `quantity`, id 99, matched against `level`.
+pub(crate) fn take(level: &PriceLevel, quantity: u64) -> MatchResult {
+    level.match_order(
+        quantity,
+        Id::from_u64(99),
+        TimeInForce::Ioc,
+        TakerKind::Standard,
+        TimestampMs::new(1616823001000),
+        &SequentialIdGenerator::new(0),
+    )
+}
+
+/// [`take`] through [`PriceLevel::try_match_order`], which reports a refused
+/// taker as an error.
+pub(crate) fn try_take(level: &PriceLevel, quantity: u64) -> Result<MatchResult, PriceLevelError> {
+    level.try_match_order(
+        quantity,
+        Id::from_u64(99),
+        TimeInForce::Ioc,
+        TakerKind::Standard,
+        TimestampMs::new(1616823001000),
+        &SequentialIdGenerator::new(0),
+    )
+}
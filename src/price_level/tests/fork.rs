@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{HaltPolicy, PriceLevel};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+
+    fn standard(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn resting() -> PriceLevel {
+        let level = PriceLevel::new(100);
+        for id in 1..=3 {
+            level.add_order(standard(id, 10)).unwrap();
+        }
+        level
+    }
+
+    fn take(level: &PriceLevel, quantity: u64) -> MatchResult {
+        level.match_order(
+            quantity,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    #[test]
+    fn test_fork_shares_orders_and_copies_state() {
+        let level = resting();
+        take(&level, 4);
+        let fork = level.fork();
+
+        assert_eq!(fork.visible_quantity(), level.visible_quantity());
+        assert_eq!(fork.order_count(), level.order_count());
+        assert_eq!(fork.mutation_sequence(), level.mutation_sequence());
+        assert_eq!(fork.level().stats().orders_executed(), 1);
+        for (forked, original) in fork
+            .level()
+            .snapshot()
+            .orders()
+            .iter()
+            .zip(level.snapshot().orders())
+        {
+            assert!(Arc::ptr_eq(forked, original));
+        }
+    }
+
+    #[test]
+    fn test_fork_copies_the_queue_on_first_write() {
+        let level = resting();
+        let fork = level.fork();
+        let nested = fork.fork();
+
+        assert!(!fork.is_copied());
+        assert!(Arc::ptr_eq(&fork.snapshot(), &level.snapshot_consistent()));
+        assert!(Arc::ptr_eq(&nested.snapshot(), &fork.snapshot()));
+        assert_eq!(nested.visible_quantity(), 30);
+
+        take(fork.level(), 5);
+        assert!(fork.is_copied());
+        assert!(!nested.is_copied());
+        assert_eq!(fork.snapshot().visible_quantity(), Quantity::new(25));
+        assert_eq!(nested.snapshot().visible_quantity(), Quantity::new(30));
+        assert_eq!(nested.into_level().order_count(), 3);
+    }
+
+    #[test]
+    fn test_fork_evolves_independently() {
+        let level = resting();
+        let fork = level.fork();
+
+        let result = take(fork.level(), 15);
+        assert_eq!(result.executed_quantity().unwrap(), Quantity::new(15));
+        fork.level().add_order(standard(4, 10)).unwrap();
+        assert_eq!(fork.visible_quantity(), 25);
+        assert_eq!(fork.level().stats().orders_executed(), 2);
+
+        assert_eq!(level.visible_quantity(), 30);
+        assert_eq!(level.order_count(), 3);
+        assert_eq!(level.stats().orders_executed(), 0);
+
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(3),
+            })
+            .unwrap();
+        assert_eq!(level.order_count(), 2);
+        assert_eq!(fork.order_count(), 3);
+    }
+
+    #[test]
+    fn test_fork_keeps_the_halt() {
+        let level = resting();
+        level.freeze_with(HaltPolicy::new());
+        let fork = level.fork().into_level();
+        assert!(fork.is_frozen());
+
+        fork.unfreeze();
+        assert_eq!(
+            take(&fork, 5).executed_quantity().unwrap(),
+            Quantity::new(5)
+        );
+        assert!(level.is_frozen());
+        assert_eq!(level.visible_quantity(), 30);
+    }
+}
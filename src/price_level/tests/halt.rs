@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::MatchOutcome;
+    use crate::orders::{Id, OrderUpdate};
+    use crate::price_level::tests::fixtures::{sell, take, try_take};
+    use crate::price_level::{HaltPolicy, MultiBook, PriceLevel, PriceLevelConfig};
+    use crate::utils::{Quantity, TimestampMs};
+
+    fn halted<V: std::fmt::Debug>(result: Result<V, PriceLevelError>) -> bool {
+        matches!(result, Err(PriceLevelError::TradingHalted { price: 100 }))
+    }
+
+    #[test]
+    fn test_frozen_level_refuses_takers_but_accepts_orders() {
+        let level: PriceLevel = PriceLevel::new(100);
+        level.add_order(sell(1, 100, 10)).unwrap();
+        level.freeze();
+        assert!(level.is_frozen());
+        assert_eq!(level.halt_policy(), Some(HaltPolicy::new()));
+
+        let result = take(&level, 4);
+        assert!(result.trades().as_slice().is_empty());
+        assert_eq!(result.outcome(), MatchOutcome::Rejected);
+        assert!(halted(try_take(&level, 4)));
+        assert!(halted(level.propose_match(
+            4,
+            Id::from_u64(99),
+            TimestampMs::new(1616823001000)
+        )));
+        assert_eq!(level.visible_quantity(), 10);
+
+        // The default policy still takes adds, amends and cancels.
+        level.add_order(sell(2, 100, 5)).unwrap();
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(2),
+                new_quantity: Quantity::new(3),
+            })
+            .unwrap();
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        assert_eq!(level.visible_quantity(), 3);
+
+        level.unfreeze();
+        assert!(!level.is_frozen());
+        assert_eq!(
+            try_take(&level, 2).unwrap().executed_quantity().unwrap(),
+            Quantity::new(2)
+        );
+    }
+
+    #[test]
+    fn test_closed_halt_leaves_the_level_unchanged() {
+        let level: PriceLevel = PriceLevel::new(100);
+        level.add_order(sell(1, 100, 10)).unwrap();
+        level.freeze_with(HaltPolicy::closed().with_cancels(true));
+        let sequence = level.mutation_sequence();
+
+        assert!(halted(level.add_order(sell(2, 100, 5))));
+        assert!(halted(level.update_order(OrderUpdate::UpdateQuantity {
+            order_id: Id::from_u64(1),
+            new_quantity: Quantity::new(3),
+        })));
+        assert!(halted(
+            level.cancel_replace(Id::from_u64(1), sell(3, 100, 5))
+        ));
+        assert!(halted(level.load_bulk(vec![sell(4, 100, 5)])));
+        assert_eq!(level.mutation_sequence(), sequence);
+        assert_eq!(level.order_count(), 1);
+        assert_eq!(level.visible_quantity(), 10);
+
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        assert_eq!(level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_book_freeze_covers_new_levels() {
+        let book: MultiBook = MultiBook::new();
+        book.register("BTC-USD", PriceLevelConfig::new()).unwrap();
+        book.add_order("BTC-USD", sell(1, 100, 10)).unwrap();
+
+        book.freeze_with("BTC-USD", HaltPolicy::new().with_cancels(false))
+            .unwrap();
+        assert!(book.freeze("ETH-USD").is_err());
+        assert_eq!(
+            book.halt_policy("BTC-USD"),
+            Some(HaltPolicy::new().with_cancels(false))
+        );
+        book.add_order("BTC-USD", sell(2, 101, 10)).unwrap();
+        for price in [100, 101] {
+            let level = book.level("BTC-USD", price).unwrap();
+            assert_eq!(level.halt_policy(), book.halt_policy("BTC-USD"));
+        }
+
+        book.unfreeze("BTC-USD").unwrap();
+        assert_eq!(book.halt_policy("BTC-USD"), None);
+        book.add_order("BTC-USD", sell(3, 102, 10)).unwrap();
+        for price in [100, 101, 102] {
+            assert!(!book.level("BTC-USD", price).unwrap().is_frozen());
+        }
+    }
+}
@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{HiddenPriority, MatchingPolicy, PriceLevel, PriceLevelConfig};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn id(id: u64) -> Id {
+        Id::from_u64(id)
+    }
+
+    fn standard(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn hidden(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Hidden {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    /// A hidden order resting ahead of a displayed one.
+    fn resting(config: PriceLevelConfig) -> PriceLevel {
+        let level = PriceLevel::with_config(100, config);
+        level.add_order(hidden(1, 5)).unwrap();
+        level.add_order(standard(2, 5)).unwrap();
+        level
+    }
+
+    fn take(level: &PriceLevel, quantity: u64, tif: TimeInForce) -> MatchResult<()> {
+        level.match_order(
+            quantity,
+            Id::from_u64(99),
+            tif,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    fn fills(result: &MatchResult<()>) -> Vec<(Id, u64)> {
+        result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|trade| (trade.maker_order_id(), trade.quantity().as_u64()))
+            .collect()
+    }
+
+    #[test]
+    fn test_queue_position_follows_the_displayed_first_sweep() {
+        let level = resting(PriceLevelConfig::new());
+        // The hidden order arrived first, but trades after the displayed one.
+        assert_eq!(level.queue_position(id(2)), Some((0, 0)));
+        assert_eq!(level.queue_position(id(1)), Some((1, 5)));
+
+        assert_eq!(fills(&take(&level, 1, TimeInForce::Ioc)), vec![(id(2), 1)]);
+
+        // Time priority puts the hidden order first.
+        let level =
+            resting(PriceLevelConfig::new().with_hidden_priority(HiddenPriority::TimePriority));
+        assert_eq!(level.queue_position(id(1)), Some((0, 0)));
+        assert_eq!(level.queue_position(id(2)), Some((1, 0)));
+    }
+
+    #[test]
+    fn test_hidden_quantity_is_never_displayed() {
+        let level = resting(PriceLevelConfig::new());
+        assert_eq!(level.hidden_priority(), HiddenPriority::DisplayedFirst);
+        assert_eq!(level.visible_quantity(), 5);
+        assert_eq!(level.hidden_quantity(), 5);
+        assert_eq!(level.order_count(), 2);
+
+        let records = level.mbo_records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].order_id, Id::from_u64(2));
+        assert_eq!(level.mbp_record().unwrap().unwrap().size, Quantity::new(5));
+
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        assert_eq!(level.hidden_quantity(), 0);
+        assert_eq!(level.visible_quantity(), 5);
+    }
+
+    #[test]
+    fn test_displayed_liquidity_trades_first() {
+        let level = resting(PriceLevelConfig::new());
+        let plan = level
+            .propose_match(7, Id::from_u64(99), TimestampMs::new(1616823001000))
+            .unwrap();
+        let planned: Vec<_> = plan
+            .fills()
+            .iter()
+            .map(|fill| (fill.order_id(), fill.quantity()))
+            .collect();
+
+        let result = take(&level, 7, TimeInForce::Ioc);
+        assert_eq!(fills(&result), [(id(2), 5), (id(1), 2)]);
+        assert_eq!(planned, fills(&result));
+        assert_eq!(level.visible_quantity(), 0);
+        assert_eq!(level.hidden_quantity(), 3);
+    }
+
+    #[test]
+    fn test_time_priority_keeps_queue_order() {
+        let level =
+            resting(PriceLevelConfig::new().with_hidden_priority(HiddenPriority::TimePriority));
+        let result = take(&level, 7, TimeInForce::Ioc);
+        assert_eq!(fills(&result), [(id(1), 5), (id(2), 2)]);
+        assert_eq!(level.visible_quantity(), 3);
+        assert_eq!(level.hidden_quantity(), 0);
+
+        // Strict FIFO never trades an older maker after a younger one.
+        let strict =
+            resting(PriceLevelConfig::new().with_matching_policy(MatchingPolicy::StrictFifo));
+        assert_eq!(
+            fills(&take(&strict, 7, TimeInForce::Ioc)),
+            [(id(1), 5), (id(2), 2)]
+        );
+    }
+
+    #[test]
+    fn test_fill_or_kill_counts_hidden_depth() {
+        let level: PriceLevel = PriceLevel::new(100);
+        level.add_order(hidden(1, 5)).unwrap();
+        assert_eq!(level.matchable_quantity(10, Id::from_u64(99)), 5);
+
+        let killed = take(&level, 6, TimeInForce::Fok);
+        assert!(killed.trades().as_slice().is_empty());
+        assert_eq!(level.hidden_quantity(), 5);
+
+        let filled = take(&level, 5, TimeInForce::Fok);
+        assert_eq!(fills(&filled), [(id(1), 5)]);
+        assert_eq!(level.order_count(), 0);
+        assert_eq!(level.hidden_quantity(), 0);
+    }
+
+    #[test]
+    fn test_displayed_first_sweep_reaches_displayed_orders_behind_hidden_depth() {
+        let level: PriceLevel = PriceLevel::new(100);
+        for i in 1..=16 {
+            level.add_order(hidden(i, 1)).unwrap();
+        }
+        level.add_order(standard(17, 5)).unwrap();
+        level.add_order(hidden(18, 1)).unwrap();
+        level.add_order(standard(19, 5)).unwrap();
+
+        // The walk still interleaves both kinds in arrival order.
+        let walked: Vec<Id> = level.orders().map(|order| order.id()).collect();
+        assert_eq!(walked, (1..=19).map(id).collect::<Vec<_>>());
+
+        let result = take(&level, 12, TimeInForce::Ioc);
+        assert_eq!(
+            fills(&result),
+            [(id(17), 5), (id(19), 5), (id(1), 1), (id(2), 1)]
+        );
+        assert_eq!(level.visible_quantity(), 0);
+        assert_eq!(level.hidden_quantity(), 15);
+        assert_eq!(level.order_count(), 15);
+    }
+}
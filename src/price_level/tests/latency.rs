@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::latency::{BUCKETS, SUB_BUCKETS, bucket_bounds, bucket_of};
+    use crate::price_level::{
+        LatencyBucket, LatencyHistogram, LatencyOperation, PriceLevel, PriceLevelConfig,
+        PriceLevelStatistics,
+    };
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::time::Duration;
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn histogram(buckets: &[(u64, u64, u64)]) -> LatencyHistogram {
+        let buckets: Vec<LatencyBucket> = buckets
+            .iter()
+            .map(|&(lower_ns, upper_ns, count)| LatencyBucket {
+                lower_ns,
+                upper_ns,
+                count,
+            })
+            .collect();
+        LatencyHistogram {
+            operation: LatencyOperation::MatchOrder,
+            count: buckets.iter().map(|bucket| bucket.count).sum(),
+            sum_ns: 0,
+            min_ns: buckets.first().map_or(0, |bucket| bucket.lower_ns),
+            max_ns: buckets.last().map_or(0, |bucket| bucket.upper_ns),
+            buckets,
+        }
+    }
+
+    #[test]
+    fn test_buckets_tile_the_u64_range() {
+        let mut next = 0_u64;
+        for index in 0..BUCKETS {
+            let (lower, upper) = bucket_bounds(index);
+            assert_eq!(lower, next, "bucket {index}");
+            assert_eq!(bucket_of(lower), index);
+            assert_eq!(bucket_of(upper), index);
+            next = upper.wrapping_add(1);
+        }
+        assert_eq!(next, 0, "the last bucket ends at u64::MAX");
+    }
+
+    #[test]
+    fn test_bucket_width_is_within_an_eighth() {
+        for index in SUB_BUCKETS as usize..BUCKETS {
+            let (lower, upper) = bucket_bounds(index);
+            assert!(upper - lower < lower / SUB_BUCKETS + 1);
+        }
+    }
+
+    #[test]
+    fn test_percentiles_walk_the_buckets() {
+        let histogram = histogram(&[(100, 111, 90), (1_000, 1_119, 9), (8_192, 9_215, 1)]);
+
+        assert_eq!(histogram.percentile(0.5), Some(Duration::from_nanos(111)));
+        assert_eq!(histogram.percentile(0.9), Some(Duration::from_nanos(111)));
+        assert_eq!(
+            histogram.percentile(0.99),
+            Some(Duration::from_nanos(1_119))
+        );
+        assert_eq!(histogram.percentile(1.0), Some(Duration::from_nanos(9_215)));
+        assert_eq!(
+            histogram.percentile(f64::NAN),
+            Some(Duration::from_nanos(111))
+        );
+        assert_eq!(self::histogram(&[]).percentile(0.5), None);
+        assert_eq!(self::histogram(&[]).mean(), None);
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        level.add_order(create_standard_order(1, 5)).unwrap();
+
+        assert!(!level.stats().latency_tracking());
+        assert_eq!(
+            level.stats().latency_histogram(LatencyOperation::AddOrder),
+            None
+        );
+    }
+
+    #[test]
+    fn test_level_records_each_operation() {
+        let level: PriceLevel =
+            PriceLevel::with_config(10000, PriceLevelConfig::new().with_latency_tracking(true));
+        level.add_order(create_standard_order(1, 5)).unwrap();
+        level.add_order(create_standard_order(2, 5)).unwrap();
+        let _ = level.match_order(
+            3,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823000100),
+            &SequentialIdGenerator::new(0),
+        );
+        let _ = level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+
+        let stats = level.stats();
+        let counts: Vec<u64> = LatencyOperation::ALL
+            .iter()
+            .map(|&operation| stats.latency_histogram(operation).unwrap().count)
+            .collect();
+        assert_eq!(counts, vec![2, 1, 1]);
+
+        let adds = stats.latency_histogram(LatencyOperation::AddOrder).unwrap();
+        assert!(adds.min_ns <= adds.max_ns);
+        assert!(adds.percentile(1.0).unwrap() <= Duration::from_nanos(adds.max_ns));
+        assert!(adds.mean().is_some());
+
+        let json = serde_json::to_string(&adds).unwrap();
+        assert!(json.contains("\"operation\":\"add_order\""));
+        assert_eq!(
+            serde_json::from_str::<LatencyHistogram>(&json).unwrap(),
+            adds
+        );
+    }
+
+    #[test]
+    fn test_reset_clears_and_disable_keeps_the_histograms() {
+        let stats = PriceLevelStatistics::new();
+        stats.set_latency_tracking(true);
+        drop(stats.time(LatencyOperation::UpdateOrder));
+        assert_eq!(
+            stats
+                .latency_histogram(LatencyOperation::UpdateOrder)
+                .unwrap()
+                .count,
+            1
+        );
+
+        stats.set_latency_tracking(false);
+        drop(stats.time(LatencyOperation::UpdateOrder));
+        assert_eq!(stats.latency_histogram(LatencyOperation::UpdateOrder), None);
+
+        stats.set_latency_tracking(true);
+        let resumed = stats
+            .latency_histogram(LatencyOperation::UpdateOrder)
+            .unwrap();
+        assert_eq!(resumed.count, 1);
+
+        stats.reset();
+        let cleared = stats
+            .latency_histogram(LatencyOperation::UpdateOrder)
+            .unwrap();
+        assert_eq!(cleared.count, 0);
+        assert!(cleared.buckets.is_empty());
+        assert_eq!(cleared.min_ns, 0);
+    }
+}
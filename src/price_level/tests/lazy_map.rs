@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::price_level::lazy_map::LazyMap;
+
+    #[test]
+    fn test_lazy_map_reads_allocate_nothing() {
+        let map: LazyMap<u64, u64> = LazyMap::default();
+        assert!(map.get(&1).is_none());
+        assert!(!map.contains_key(&1));
+        assert!(map.remove(&1).is_none());
+        assert!(map.remove_if(&1, |_, _| true).is_none());
+        map.retain(|_, _| false);
+        assert_eq!(map.iter().count(), 0);
+        assert!(!map.is_allocated());
+    }
+
+    #[test]
+    fn test_lazy_map_allocates_on_first_write() {
+        let map: LazyMap<u64, u64> = LazyMap::default();
+        assert!(map.insert(1, 10).is_none());
+        assert!(map.is_allocated());
+        *map.entry(2).or_insert(0) += 5;
+        assert_eq!(map.get(&2).map(|entry| *entry), Some(5));
+        assert!(map.contains_key(&1));
+
+        map.retain(|_, value| *value > 5);
+        assert_eq!(map.iter().count(), 1);
+        assert!(map.remove_if(&1, |_, value| *value > 10).is_none());
+        assert_eq!(map.remove(&1), Some((1, 10)));
+        assert!(map.is_allocated());
+    }
+}
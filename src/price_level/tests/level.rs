@@ -1,8 +1,10 @@
 #[cfg(test)]
 mod tests {
     use crate::errors::PriceLevelError;
-    use crate::execution::{MatchOutcome, MatchResult, TakerKind};
-    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, PegReferenceType, Side, TimeInForce};
+    use crate::execution::{MatchOutcome, MatchResult, TRADE_LIST_INLINE_CAPACITY, TakerKind};
+    use crate::orders::{
+        Hash32, Id, OrderType, OrderUpdate, PegReferenceType, Side, TimeInForce, UpdateOutcome,
+    };
     use crate::price_level::PriceLevelSnapshotPackage;
     use crate::price_level::level::{PriceLevel, PriceLevelData};
     use crate::price_level::snapshot::SNAPSHOT_FORMAT_VERSION;
@@ -53,7 +55,7 @@ mod tests {
         let json = package
             .to_json()
             .expect("Failed to serialize snapshot package");
-        let restored = PriceLevel::from_snapshot_json(&json)
+        let restored: PriceLevel = PriceLevel::from_snapshot_json(&json)
             .expect("Failed to restore price level from snapshot JSON");
 
         assert_eq!(restored.price(), price_level.price());
@@ -98,8 +100,9 @@ mod tests {
             );
         }
         let tampered_json = serde_json::to_string(&value).expect("JSON serialization failed");
-        let tampered_package = PriceLevelSnapshotPackage::from_json(&tampered_json)
-            .expect("Deserialization should still succeed");
+        let tampered_package: PriceLevelSnapshotPackage =
+            PriceLevelSnapshotPackage::from_json(&tampered_json)
+                .expect("Deserialization should still succeed");
 
         let err = PriceLevel::from_snapshot_package(tampered_package)
             .expect_err("Restoration should fail due to checksum mismatch");
@@ -161,7 +164,7 @@ mod tests {
             .snapshot_to_json()
             .expect("Failed to serialize snapshot to JSON");
 
-        let restored = PriceLevel::from_snapshot_json(&json)
+        let restored: PriceLevel = PriceLevel::from_snapshot_json(&json)
             .expect("Failed to restore price level from snapshot JSON");
 
         let restored_stats = restored.stats();
@@ -220,7 +223,7 @@ mod tests {
         }
         let downgraded_json = serde_json::to_string(&value).expect("JSON serialization failed");
 
-        let err = PriceLevel::from_snapshot_json(&downgraded_json)
+        let err = PriceLevel::<()>::from_snapshot_json(&downgraded_json)
             .expect_err("Restoration should reject a v1 package");
 
         match err {
@@ -540,7 +543,7 @@ mod tests {
 
     #[test]
     fn test_price_level_creation() {
-        let price_level = PriceLevel::new(10000);
+        let price_level: PriceLevel = PriceLevel::new(10000);
 
         assert_eq!(price_level.price(), 10000);
         assert_eq!(price_level.visible_quantity(), 0);
@@ -638,8 +641,8 @@ mod tests {
 
         assert!(result.is_ok());
         let removed = result.unwrap();
-        assert!(removed.is_some());
-        assert_eq!(removed.unwrap().id(), Id::from_u64(1));
+        assert!(matches!(removed, UpdateOutcome::Cancelled(..)));
+        assert_eq!(removed.into_order().unwrap().id(), Id::from_u64(1));
         assert_eq!(price_level.visible_quantity(), 50);
         assert_eq!(price_level.hidden_quantity(), 200);
         assert_eq!(price_level.order_count(), 1);
@@ -651,7 +654,7 @@ mod tests {
 
         assert!(result.is_ok());
         let removed = result.unwrap();
-        assert!(removed.is_some());
+        assert!(removed.is_found());
         assert_eq!(price_level.visible_quantity(), 0);
         assert_eq!(price_level.hidden_quantity(), 0);
         assert_eq!(price_level.order_count(), 0);
@@ -662,13 +665,96 @@ mod tests {
         });
 
         assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        assert!(result.unwrap().order().is_none());
 
         // Verify stats
         assert_eq!(price_level.stats().orders_added(), 2);
         assert_eq!(price_level.stats().orders_removed(), 2);
     }
 
+    #[test]
+    fn test_update_order_outcome_variants() {
+        let price_level = PriceLevel::new(10000);
+        for id in 1..=3 {
+            price_level
+                .add_order(create_standard_order(id, 10000, 100))
+                .expect("add_order should succeed");
+        }
+
+        let resized = price_level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(1),
+                new_quantity: Quantity::new(40),
+            })
+            .unwrap();
+        match &resized {
+            UpdateOutcome::UpdatedInPlace(order) => {
+                assert_eq!(order.visible_quantity(), Quantity::new(40));
+            }
+            other => panic!("expected UpdatedInPlace, got {other:?}"),
+        }
+        assert!(!resized.is_removed());
+
+        // A same-price replace is a resize, not a relocation.
+        let replaced = price_level
+            .update_order(OrderUpdate::Replace {
+                order_id: Id::from_u64(2),
+                price: Price::new(10000),
+                quantity: Quantity::new(150),
+                side: Side::Buy,
+            })
+            .unwrap();
+        assert!(matches!(replaced, UpdateOutcome::UpdatedInPlace(_)));
+
+        let moved = price_level
+            .update_order(OrderUpdate::UpdatePriceAndQuantity {
+                order_id: Id::from_u64(2),
+                new_price: Price::new(10100),
+                new_quantity: Quantity::new(10),
+            })
+            .unwrap();
+        // Returned as it rested here; the caller applies the new quantity.
+        match &moved {
+            UpdateOutcome::RemovedForReprice(order) => {
+                assert_eq!(order.id(), Id::from_u64(2));
+                assert_eq!(order.visible_quantity(), Quantity::new(150));
+            }
+            other => panic!("expected RemovedForReprice, got {other:?}"),
+        }
+        assert!(moved.is_removed());
+
+        let cancelled = price_level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(3),
+            })
+            .unwrap();
+        assert!(matches!(cancelled, UpdateOutcome::Cancelled(..)));
+        assert_eq!(
+            cancelled.into_order().map(|o| o.id()),
+            Some(Id::from_u64(3))
+        );
+
+        for update in [
+            OrderUpdate::Cancel {
+                order_id: Id::from_u64(3),
+            },
+            OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(3),
+                new_quantity: Quantity::new(1),
+            },
+            OrderUpdate::UpdatePrice {
+                order_id: Id::from_u64(3),
+                new_price: Price::new(9900),
+            },
+        ] {
+            assert_eq!(
+                price_level.update_order(update).unwrap(),
+                UpdateOutcome::NotFound
+            );
+        }
+        assert_eq!(price_level.order_count(), 1);
+    }
+
     #[test]
     fn test_iter_orders() {
         let price_level = PriceLevel::new(10000);
@@ -718,7 +804,7 @@ mod tests {
         assert_eq!(price_level.order_count(), 0);
 
         assert_eq!(match_result.trades().len(), 1);
-        let transaction = &match_result.trades().as_vec()[0];
+        let transaction = &match_result.trades().as_slice()[0];
         assert_eq!(transaction.taker_order_id(), taker_id);
         assert_eq!(transaction.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction.price(), Price::new(10000));
@@ -786,8 +872,8 @@ mod tests {
         let first = run();
         let second = run();
 
-        let first_trades = first.trades().as_vec();
-        let second_trades = second.trades().as_vec();
+        let first_trades = first.trades().as_slice();
+        let second_trades = second.trades().as_slice();
 
         // Crossed two full makers (40 + 30) and partially filled the third (20).
         assert_eq!(first_trades.len(), 3);
@@ -834,7 +920,7 @@ mod tests {
 
         // Verificar las transacciones generadas
         assert_eq!(match_result.trades().len(), 1);
-        let transaction = &match_result.trades().as_vec()[0];
+        let transaction = &match_result.trades().as_slice()[0];
         assert_eq!(transaction.taker_order_id(), taker_id);
         assert_eq!(transaction.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction.price(), Price::new(10000));
@@ -877,7 +963,7 @@ mod tests {
         assert_eq!(price_level.order_count(), 0);
 
         assert_eq!(match_result.trades().len(), 1);
-        let transaction = &match_result.trades().as_vec()[0];
+        let transaction = &match_result.trades().as_slice()[0];
         assert_eq!(transaction.taker_order_id(), taker_id);
         assert_eq!(transaction.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction.price(), Price::new(10000));
@@ -925,7 +1011,7 @@ mod tests {
         assert_eq!(match_result.trades().len(), 1);
 
         // Assertions about the generated transaction
-        let transaction = &match_result.trades().as_vec()[0];
+        let transaction = &match_result.trades().as_slice()[0];
         assert_eq!(transaction.taker_order_id(), taker_id);
         assert_eq!(transaction.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction.price(), Price::new(10000));
@@ -948,7 +1034,7 @@ mod tests {
         assert_eq!(price_level.visible_quantity(), 50); // Visible quantity replenished
         assert_eq!(price_level.hidden_quantity(), 0); // Hidden quantity reduced
         assert_eq!(price_level.order_count(), 1);
-        let transaction = &match_result.trades().as_vec()[0];
+        let transaction = &match_result.trades().as_slice()[0];
 
         assert_eq!(transaction.taker_order_id(), taker_id);
         assert_eq!(transaction.maker_order_id(), Id::from_u64(1));
@@ -1008,7 +1094,7 @@ mod tests {
         assert_eq!(match_result.trades().len(), 1);
 
         // Assertions about the generated transaction
-        let transaction = &match_result.trades().as_vec()[0];
+        let transaction = &match_result.trades().as_slice()[0];
         assert_eq!(transaction.taker_order_id(), taker_id);
         assert_eq!(transaction.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction.price(), Price::new(10000));
@@ -1031,7 +1117,7 @@ mod tests {
         assert_eq!(price_level.visible_quantity(), 50); // Visible quantity replenished
         assert_eq!(price_level.hidden_quantity(), 50); // Hidden quantity reduced
         assert_eq!(price_level.order_count(), 1);
-        let transaction = &match_result.trades().as_vec()[0];
+        let transaction = &match_result.trades().as_slice()[0];
 
         assert_eq!(transaction.taker_order_id(), taker_id);
         assert_eq!(transaction.maker_order_id(), Id::from_u64(1));
@@ -1435,7 +1521,7 @@ mod tests {
         assert_eq!(match_result.trades().len(), 1);
 
         // Validate the transaction details
-        let transaction = &match_result.trades().as_vec()[0];
+        let transaction = &match_result.trades().as_slice()[0];
         assert_eq!(transaction.taker_order_id(), taker_id);
         assert_eq!(transaction.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction.price(), Price::new(10000));
@@ -1460,7 +1546,7 @@ mod tests {
         assert_eq!(price_level.hidden_quantity(), 20); // 100 - 80 (replenish amount) = 20
         assert_eq!(price_level.order_count(), 1);
 
-        let transaction = &match_result.trades().as_vec()[0];
+        let transaction = &match_result.trades().as_slice()[0];
         assert_eq!(transaction.taker_order_id(), taker_id);
         assert_eq!(transaction.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction.price(), Price::new(10000));
@@ -1490,14 +1576,14 @@ mod tests {
         // Verify the correct number and sizes of transactions
         assert_eq!(match_result.trades().len(), 2); // One for visible, one for hidden
 
-        let transaction1 = &match_result.trades().as_vec()[0];
+        let transaction1 = &match_result.trades().as_slice()[0];
         assert_eq!(transaction1.taker_order_id(), taker_id);
         assert_eq!(transaction1.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction1.price(), Price::new(10000));
         assert_eq!(transaction1.quantity(), Quantity::new(90)); // First consumes all visible
         assert_eq!(transaction1.taker_side(), Side::Buy);
 
-        let transaction2 = &match_result.trades().as_vec()[1];
+        let transaction2 = &match_result.trades().as_slice()[1];
         assert_eq!(transaction2.taker_order_id(), taker_id);
         assert_eq!(transaction2.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction2.price(), Price::new(10000));
@@ -1730,7 +1816,7 @@ mod tests {
         assert!(!result.is_complete());
         assert_eq!(result.remaining_quantity().as_u64(), 50);
         assert_eq!(result.trades().len(), 1);
-        assert_eq!(result.trades().as_vec()[0].quantity(), Quantity::new(100));
+        assert_eq!(result.trades().as_slice()[0].quantity(), Quantity::new(100));
         assert_eq!(result.filled_order_ids().len(), 1);
         assert_eq!(price_level.order_count(), 0);
         assert_match_result_consistent(&result, 10000, Side::Buy);
@@ -1741,7 +1827,7 @@ mod tests {
         // Empty level: matching against a PostOnly-free, empty `PriceLevel`
         // yields no trades, the full incoming quantity remains, and the result
         // is not complete.
-        let price_level = PriceLevel::new(10000);
+        let price_level: PriceLevel = PriceLevel::new(10000);
         let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
         let trade_id_generator = UuidGenerator::new(namespace);
 
@@ -1850,7 +1936,7 @@ mod tests {
         assert!(!result.is_complete());
         assert_eq!(result.remaining_quantity().as_u64(), 40);
         assert_eq!(result.trades().len(), 1);
-        assert_eq!(result.trades().as_vec()[0].quantity(), Quantity::new(80));
+        assert_eq!(result.trades().as_slice()[0].quantity(), Quantity::new(80));
         assert_eq!(result.filled_order_ids().len(), 1);
         assert_eq!(price_level.order_count(), 0);
         assert_match_result_consistent(&result, 10000, Side::Sell);
@@ -1860,7 +1946,7 @@ mod tests {
     fn test_match_trailing_stop_empty_level_no_trades() {
         // Empty level: no resting orders -> no trades, full remainder, not
         // complete.
-        let price_level = PriceLevel::new(10000);
+        let price_level: PriceLevel = PriceLevel::new(10000);
         let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
         let trade_id_generator = UuidGenerator::new(namespace);
 
@@ -1967,9 +2053,9 @@ mod tests {
         assert!(!result.is_complete());
         assert_eq!(result.remaining_quantity().as_u64(), 40);
         assert_eq!(result.trades().len(), 1);
-        assert_eq!(result.trades().as_vec()[0].quantity(), Quantity::new(90));
+        assert_eq!(result.trades().as_slice()[0].quantity(), Quantity::new(90));
         // Pass-through fills at the level price, NOT a pegged reference price.
-        assert_eq!(result.trades().as_vec()[0].price(), Price::new(10000));
+        assert_eq!(result.trades().as_slice()[0].price(), Price::new(10000));
         assert_eq!(result.filled_order_ids().len(), 1);
         assert_eq!(price_level.order_count(), 0);
         assert_match_result_consistent(&result, 10000, Side::Buy);
@@ -1979,7 +2065,7 @@ mod tests {
     fn test_match_pegged_empty_level_no_trades() {
         // Empty level: no resting orders -> no trades, full remainder, not
         // complete.
-        let price_level = PriceLevel::new(10000);
+        let price_level: PriceLevel = PriceLevel::new(10000);
         let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
         let trade_id_generator = UuidGenerator::new(namespace);
 
@@ -2087,7 +2173,7 @@ mod tests {
         assert!(!result.is_complete());
         assert_eq!(result.remaining_quantity().as_u64(), 40);
         assert_eq!(result.trades().len(), 1);
-        assert_eq!(result.trades().as_vec()[0].quantity(), Quantity::new(60));
+        assert_eq!(result.trades().as_slice()[0].quantity(), Quantity::new(60));
         assert_eq!(result.filled_order_ids().len(), 1);
         assert_eq!(price_level.order_count(), 0);
         assert_match_result_consistent(&result, 10000, Side::Buy);
@@ -2097,7 +2183,7 @@ mod tests {
     fn test_match_market_to_limit_empty_level_no_trades() {
         // Empty level: no resting orders -> no trades, full remainder, not
         // complete.
-        let price_level = PriceLevel::new(10000);
+        let price_level: PriceLevel = PriceLevel::new(10000);
         let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
         let trade_id_generator = UuidGenerator::new(namespace);
 
@@ -2186,7 +2272,8 @@ mod tests {
         let json = price_level
             .snapshot_to_json()
             .expect("snapshot must serialize");
-        let restored = PriceLevel::from_snapshot_json(&json).expect("snapshot must restore");
+        let restored: PriceLevel =
+            PriceLevel::from_snapshot_json(&json).expect("snapshot must restore");
         let restored_orders = restored.snapshot_by_insertion_seq();
         assert_eq!(restored_orders.len(), 1);
         assert_eq!(
@@ -2275,6 +2362,7 @@ mod tests {
                 new_quantity: Quantity::new(decrease_to),
             })
             .expect("decrease update should succeed")
+            .into_order()
             .expect("order must be present");
         assert_eq!(
             updated.visible_quantity().as_u64(),
@@ -2316,6 +2404,7 @@ mod tests {
                 new_quantity: Quantity::new(increase_to),
             })
             .expect("increase update should succeed")
+            .into_order()
             .expect("order must be present");
         assert_eq!(
             updated.visible_quantity().as_u64(),
@@ -2561,7 +2650,7 @@ mod tests {
     #[test]
     fn test_match_fok_taker_killed_against_empty_level() {
         // Empty level cannot fill any positive FOK taker -> killed.
-        let price_level = PriceLevel::new(10000);
+        let price_level: PriceLevel = PriceLevel::new(10000);
         let trade_gen = fok_namespace_gen();
 
         let result = price_level.match_order(
@@ -2677,7 +2766,7 @@ mod tests {
     fn test_match_post_only_taker_accepted_on_empty_level() {
         // No matchable depth -> the post-only taker does not cross and is NOT
         // rejected. It simply finds nothing to fill (NotFilled).
-        let price_level = PriceLevel::new(10000);
+        let price_level: PriceLevel = PriceLevel::new(10000);
         let trade_gen = fok_namespace_gen();
 
         let result = price_level.match_order(
@@ -3016,17 +3105,17 @@ mod tests {
 
         assert_eq!(match_result.trades().len(), 3);
 
-        let transaction1 = &match_result.trades().as_vec()[0];
+        let transaction1 = &match_result.trades().as_slice()[0];
         assert_eq!(transaction1.taker_order_id(), taker_id);
         assert_eq!(transaction1.maker_order_id(), Id::from_u64(1));
         assert_eq!(transaction1.quantity(), Quantity::new(50));
 
-        let transaction2 = &match_result.trades().as_vec()[1];
+        let transaction2 = &match_result.trades().as_slice()[1];
         assert_eq!(transaction2.taker_order_id(), taker_id);
         assert_eq!(transaction2.maker_order_id(), Id::from_u64(2));
         assert_eq!(transaction2.quantity(), Quantity::new(75));
 
-        let transaction3 = &match_result.trades().as_vec()[2];
+        let transaction3 = &match_result.trades().as_slice()[2];
         assert_eq!(transaction3.taker_order_id(), taker_id);
         assert_eq!(transaction3.maker_order_id(), Id::from_u64(3));
         assert_eq!(transaction3.quantity(), Quantity::new(15));
@@ -3096,8 +3185,8 @@ mod tests {
         // The order should be removed from this price level (to be inserted in another price level)
         assert!(result.is_ok());
         let removed_order = result.unwrap();
-        assert!(removed_order.is_some());
-        assert_eq!(removed_order.unwrap().id(), Id::from_u64(1));
+        assert!(matches!(removed_order, UpdateOutcome::RemovedForReprice(_)));
+        assert_eq!(removed_order.into_order().unwrap().id(), Id::from_u64(1));
 
         // The price level should now be empty
         assert_eq!(price_level.visible_quantity(), 0);
@@ -3143,8 +3232,15 @@ mod tests {
         // The order should be updated with the new quantity
         assert!(result.is_ok());
         let updated_order = result.unwrap();
-        assert!(updated_order.is_some());
-        assert_eq!(updated_order.unwrap().visible_quantity().as_u64(), 150);
+        assert!(updated_order.is_found());
+        assert_eq!(
+            updated_order
+                .into_order()
+                .unwrap()
+                .visible_quantity()
+                .as_u64(),
+            150
+        );
 
         // The price level should reflect the new quantity
         assert_eq!(price_level.visible_quantity(), 150);
@@ -3161,8 +3257,15 @@ mod tests {
         // The order should be updated with the new quantity
         assert!(result.is_ok());
         let updated_order = result.unwrap();
-        assert!(updated_order.is_some());
-        assert_eq!(updated_order.unwrap().visible_quantity().as_u64(), 50);
+        assert!(updated_order.is_found());
+        assert_eq!(
+            updated_order
+                .into_order()
+                .unwrap()
+                .visible_quantity()
+                .as_u64(),
+            50
+        );
 
         // The price level should reflect the new quantity
         assert_eq!(price_level.visible_quantity(), 50);
@@ -3176,7 +3279,7 @@ mod tests {
 
         let result = price_level.update_order(update);
         assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        assert!(result.unwrap().order().is_none());
     }
 
     #[test]
@@ -3199,8 +3302,11 @@ mod tests {
         });
         assert!(result.is_ok());
         let updated = result.unwrap();
-        assert!(updated.is_some());
-        assert_eq!(updated.unwrap().visible_quantity().as_u64(), 40);
+        assert!(updated.is_found());
+        assert_eq!(
+            updated.into_order().unwrap().visible_quantity().as_u64(),
+            40
+        );
 
         // Match a quantity that only consumes the first resting order. A (id 1)
         // must be hit before B (id 2).
@@ -3216,7 +3322,7 @@ mod tests {
             &trade_id_generator,
         );
 
-        let trades = match_result.trades().as_vec();
+        let trades = match_result.trades().as_slice();
         assert_eq!(trades.len(), 1);
         // The first (and only) trade must name A as the maker: A kept its
         // position despite the reduction.
@@ -3244,8 +3350,11 @@ mod tests {
         });
         assert!(result.is_ok());
         let updated = result.unwrap();
-        assert!(updated.is_some());
-        assert_eq!(updated.unwrap().visible_quantity().as_u64(), 150);
+        assert!(updated.is_found());
+        assert_eq!(
+            updated.into_order().unwrap().visible_quantity().as_u64(),
+            150
+        );
 
         // A subsequent match that only consumes the first resting order must
         // now hit B (id 2) before the resized A (id 1).
@@ -3261,7 +3370,7 @@ mod tests {
             &trade_id_generator,
         );
 
-        let trades = match_result.trades().as_vec();
+        let trades = match_result.trades().as_slice();
         assert_eq!(trades.len(), 1);
         // B is now at the front: it is matched before the resized A.
         assert_eq!(trades[0].maker_order_id(), Id::from_u64(2));
@@ -3345,8 +3454,8 @@ mod tests {
         // The order should be removed from this price level (to be inserted in another price level)
         assert!(result.is_ok());
         let removed_order = result.unwrap();
-        assert!(removed_order.is_some());
-        assert_eq!(removed_order.unwrap().id(), Id::from_u64(1));
+        assert!(removed_order.is_found());
+        assert_eq!(removed_order.into_order().unwrap().id(), Id::from_u64(1));
 
         // The price level should now be empty
         assert_eq!(price_level.visible_quantity(), 0);
@@ -3369,8 +3478,15 @@ mod tests {
         // The order should be updated with the new quantity
         assert!(result.is_ok());
         let updated_order = result.unwrap();
-        assert!(updated_order.is_some());
-        assert_eq!(updated_order.unwrap().visible_quantity().as_u64(), 150);
+        assert!(updated_order.is_found());
+        assert_eq!(
+            updated_order
+                .into_order()
+                .unwrap()
+                .visible_quantity()
+                .as_u64(),
+            150
+        );
 
         // The price level should reflect the new quantity
         assert_eq!(price_level.visible_quantity(), 150);
@@ -3400,8 +3516,8 @@ mod tests {
         // The order should be removed from this price level (to be inserted in another price level)
         assert!(result.is_ok());
         let removed_order = result.unwrap();
-        assert!(removed_order.is_some());
-        assert_eq!(removed_order.unwrap().id(), Id::from_u64(1));
+        assert!(removed_order.is_found());
+        assert_eq!(removed_order.into_order().unwrap().id(), Id::from_u64(1));
 
         // The price level should now be empty
         assert_eq!(price_level.visible_quantity(), 0);
@@ -3425,8 +3541,15 @@ mod tests {
         // The order should be updated with the new quantity
         assert!(result.is_ok());
         let updated_order = result.unwrap();
-        assert!(updated_order.is_some());
-        assert_eq!(updated_order.unwrap().visible_quantity().as_u64(), 150);
+        assert!(updated_order.is_found());
+        assert_eq!(
+            updated_order
+                .into_order()
+                .unwrap()
+                .visible_quantity()
+                .as_u64(),
+            150
+        );
 
         // The price level should reflect the new quantity
         assert_eq!(price_level.visible_quantity(), 150);
@@ -3538,7 +3661,7 @@ mod tests {
             .expect("add_order should succeed");
 
         let input = "PriceLevel:price=10000;visible_quantity=375;hidden_quantity=200;order_count=5;orders=[Standard:id=00000000-0000-0001-0000-000000000000;price=10000;quantity=50;side=BUY;timestamp=1616823000000;time_in_force=GTC,Standard:id=00000000-0000-0002-0000-000000000000;price=10000;quantity=75;side=BUY;timestamp=1616823000001;time_in_force=GTC,Standard:id=00000000-0000-0003-0000-000000000000;price=10000;quantity=100;side=BUY;timestamp=1616823000002;time_in_force=GTD-1617000000000,ReserveOrder:id=00000000-0000-0004-0000-000000000000;price=10000;visible_quantity=100;hidden_quantity=100;side=BUY;timestamp=1616823000003;time_in_force=GTC;replenish_threshold=20;replenish_amount=None;auto_replenish=true,IcebergOrder:id=00000000-0000-0005-0000-000000000000;price=10000;visible_quantity=50;hidden_quantity=100;side=BUY;timestamp=1616823000004;time_in_force=GTC]";
-        let result = PriceLevel::from_str(input);
+        let result = PriceLevel::<()>::from_str(input);
 
         if let Err(ref err) = result {
             error!("Error parsing PriceLevel: {:?}", err);
@@ -3696,7 +3819,7 @@ mod tests {
         });
 
         assert!(result.is_ok());
-        assert!(result.unwrap().is_some());
+        assert!(result.unwrap().is_found());
         assert_eq!(price_level.visible_quantity(), 0);
         assert_eq!(price_level.order_count(), 0);
     }
@@ -3718,7 +3841,7 @@ mod tests {
         });
 
         assert!(result.is_ok());
-        let updated_order = result.unwrap().unwrap();
+        let updated_order = result.unwrap().into_order().unwrap();
         assert_eq!(updated_order.visible_quantity().as_u64(), 150);
         assert_eq!(price_level.visible_quantity(), 150);
         assert_eq!(price_level.order_count(), 1);
@@ -3793,7 +3916,7 @@ mod tests {
     #[test]
     fn test_price_level_update_quantity_order_not_found() {
         // Test line 282
-        let price_level = PriceLevel::new(10000);
+        let price_level: PriceLevel = PriceLevel::new(10000);
         // No orders added
 
         // Try to update quantity of a non-existent order
@@ -3805,7 +3928,7 @@ mod tests {
         let result = price_level.update_order(update);
         // Should return Ok(None) when order not found
         assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        assert!(result.unwrap().order().is_none());
     }
 
     #[test]
@@ -3842,7 +3965,7 @@ mod tests {
                     order_id: Id::from_u64(1)
                 })
                 .unwrap()
-                .is_some()
+                .is_found()
         );
 
         // Now try to update it after it's been removed
@@ -3853,7 +3976,7 @@ mod tests {
 
         let result = price_level.update_order(update);
         assert!(result.is_ok());
-        assert!(result.unwrap().is_none());
+        assert!(result.unwrap().order().is_none());
     }
 
     #[test]
@@ -3884,7 +4007,7 @@ mod tests {
 
         let result = price_level.update_order(update);
         assert!(result.is_ok());
-        assert!(result.unwrap().is_some());
+        assert!(result.unwrap().is_found());
 
         // Verify quantity increased
         assert_eq!(price_level.visible_quantity(), 100);
@@ -3971,7 +4094,7 @@ mod tests {
 
         let result = price_level.update_order(update);
         assert!(result.is_ok());
-        assert!(result.unwrap().is_some());
+        assert!(result.unwrap().is_found());
 
         // Verify quantity was updated but price remained the same
         assert_eq!(price_level.visible_quantity(), 100);
@@ -4089,11 +4212,11 @@ mod tests {
         );
         assert_eq!(first.trades().len(), 1);
         assert_eq!(
-            first.trades().as_vec()[0].maker_order_id(),
+            first.trades().as_slice()[0].maker_order_id(),
             Id::from_u64(1),
             "first aggressor must hit A"
         );
-        assert_eq!(first.trades().as_vec()[0].quantity(), Quantity::new(60));
+        assert_eq!(first.trades().as_slice()[0].quantity(), Quantity::new(60));
         // A(40) + B(100) still resting.
         assert_eq!(price_level.visible_quantity(), 140);
         assert_eq!(price_level.order_count(), 2);
@@ -4109,7 +4232,7 @@ mod tests {
         );
         assert_eq!(second.trades().len(), 2);
 
-        let t0 = &second.trades().as_vec()[0];
+        let t0 = &second.trades().as_slice()[0];
         assert_eq!(
             t0.maker_order_id(),
             Id::from_u64(1),
@@ -4117,7 +4240,7 @@ mod tests {
         );
         assert_eq!(t0.quantity(), Quantity::new(40));
 
-        let t1 = &second.trades().as_vec()[1];
+        let t1 = &second.trades().as_slice()[1];
         assert_eq!(t1.maker_order_id(), Id::from_u64(2));
         assert_eq!(t1.quantity(), Quantity::new(10));
 
@@ -4191,7 +4314,10 @@ mod tests {
             &trade_ids,
         );
         assert_eq!(first.trades().len(), 1);
-        assert_eq!(first.trades().as_vec()[0].maker_order_id(), Id::from_u64(1));
+        assert_eq!(
+            first.trades().as_slice()[0].maker_order_id(),
+            Id::from_u64(1)
+        );
 
         // Next aggressor must now hit O (id=2) FIRST, because the refreshed
         // iceberg tranche lost its priority to the tail.
@@ -4204,7 +4330,7 @@ mod tests {
             &trade_ids,
         );
         assert_eq!(
-            second.trades().as_vec()[0].maker_order_id(),
+            second.trades().as_slice()[0].maker_order_id(),
             Id::from_u64(2),
             "refreshed iceberg tranche must lose time priority"
         );
@@ -4237,7 +4363,7 @@ mod tests {
             Ok(j) => j,
             Err(e) => panic!("snapshot_to_json failed: {e}"),
         };
-        let restored = match PriceLevel::from_snapshot_json(&json) {
+        let restored: PriceLevel = match PriceLevel::from_snapshot_json(&json) {
             Ok(r) => r,
             Err(e) => panic!("from_snapshot_json failed: {e}"),
         };
@@ -4254,11 +4380,11 @@ mod tests {
             &restored_trade_ids,
         );
         assert_eq!(
-            result.trades().as_vec()[0].maker_order_id(),
+            result.trades().as_slice()[0].maker_order_id(),
             Id::from_u64(1),
             "restored level must keep A's residual ahead of B"
         );
-        assert_eq!(result.trades().as_vec()[0].quantity(), Quantity::new(40));
+        assert_eq!(result.trades().as_slice()[0].quantity(), Quantity::new(40));
     }
 
     fn assert_snapshot_internally_consistent(snapshot: &crate::price_level::PriceLevelSnapshot) {
@@ -4418,7 +4544,7 @@ mod tests {
             "is_complete must agree with remaining_quantity == 0"
         );
 
-        let trades = result.trades().as_vec();
+        let trades = result.trades().as_slice();
 
         // executed_quantity == sum of trade quantities. Use checked addition to
         // mirror `executed_quantity()`'s own checked arithmetic (and avoid a
@@ -4484,7 +4610,7 @@ mod tests {
         maker_side: Side,
     ) {
         let taker_id = result.order_id();
-        for trade in result.trades().as_vec() {
+        for trade in result.trades().as_slice() {
             assert_ne!(
                 trade.maker_order_id(),
                 trade.taker_order_id(),
@@ -4693,7 +4819,7 @@ mod tests {
 
         assert!(result.is_complete());
         assert_eq!(result.remaining_quantity().as_u64(), 0);
-        assert!(!result.trades().as_vec().is_empty());
+        assert!(!result.trades().as_slice().is_empty());
         assert_eq!(result.filled_order_ids().len(), 0);
         assert_match_result_consistent(&result, 10000, Side::Sell);
     }
@@ -4723,7 +4849,7 @@ mod tests {
 
         assert!(result.is_complete());
         assert_eq!(result.remaining_quantity().as_u64(), 0);
-        assert!(!result.trades().as_vec().is_empty());
+        assert!(!result.trades().as_slice().is_empty());
         assert_match_result_consistent(&result, 10000, Side::Sell);
     }
 
@@ -4771,7 +4897,7 @@ mod tests {
         let first = run();
         let second = run();
 
-        assert_eq!(first.trades().as_vec(), second.trades().as_vec());
+        assert_eq!(first.trades().as_slice(), second.trades().as_slice());
         assert_match_result_consistent(&first, 10000, Side::Sell);
         assert_match_result_consistent(&second, 10000, Side::Sell);
     }
@@ -5307,7 +5433,7 @@ mod tests {
         let json = price_level
             .snapshot_to_json()
             .expect("snapshot_to_json after degenerate match");
-        let restored =
+        let restored: PriceLevel =
             PriceLevel::from_snapshot_json(&json).expect("from_snapshot_json round-trip");
         assert_eq!(restored.visible_quantity(), price_level.visible_quantity());
         assert_eq!(restored.hidden_quantity(), price_level.hidden_quantity());
@@ -5448,7 +5574,7 @@ mod tests {
             // The cancel is never lost: either it removed the maker (Some), or
             // the match fully consumed it first (None). It is NEVER the case
             // that the maker is left silently resting with the cancel no-op'd.
-            let cancel_won = cancelled.is_some();
+            let cancel_won = cancelled.is_found();
 
             // Whatever the interleaving, the level's counters must agree with the
             // queue, and the maker must NOT be silently resting at full quantity.
@@ -5471,7 +5597,7 @@ mod tests {
                 .expect("executed_quantity must not error")
                 .as_u64();
             let cancelled_qty = cancelled
-                .as_ref()
+                .order()
                 .map_or(0, |o| o.visible_quantity().as_u64());
 
             assert_eq!(
@@ -5592,7 +5718,7 @@ mod tests {
                 .expect("executed_quantity must not error")
                 .as_u64();
             let cancelled_qty = cancelled
-                .as_ref()
+                .order()
                 .map_or(0, |o| o.visible_quantity().as_u64());
 
             let snapshot = level.snapshot();
@@ -5677,7 +5803,7 @@ mod tests {
         );
         let consumed: Vec<Id> = result
             .trades()
-            .as_vec()
+            .as_slice()
             .iter()
             .map(|t| t.maker_order_id())
             .collect();
@@ -5712,7 +5838,7 @@ mod tests {
                 new_quantity: Quantity::new(150),
             })
             .expect("upsize update should succeed");
-        assert!(updated.is_some(), "maker 1 must still be present");
+        assert!(updated.is_found(), "maker 1 must still be present");
 
         let data = PriceLevelData::from(&level);
         let json = serde_json::to_string(&data).expect("serialize PriceLevelData");
@@ -5761,6 +5887,7 @@ mod tests {
                 new_quantity: Quantity::new(150),
             })
             .expect("upsize update should succeed")
+            .into_order()
             .expect("maker 1 must still be present");
         assert_eq!(updated.visible_quantity().as_u64(), 150);
 
@@ -5781,7 +5908,7 @@ mod tests {
         let json = level
             .snapshot_to_json()
             .expect("snapshot_to_json should succeed");
-        let restored =
+        let restored: PriceLevel =
             PriceLevel::from_snapshot_json(&json).expect("from_snapshot_json should succeed");
 
         // The restored level reproduces the demoted consumption order exactly.
@@ -5818,7 +5945,7 @@ mod tests {
         );
         let makers: Vec<Id> = result
             .trades()
-            .as_vec()
+            .as_slice()
             .iter()
             .map(|t| t.maker_order_id())
             .collect();
@@ -5877,7 +6004,7 @@ mod tests {
         let json = level
             .snapshot_to_json()
             .expect("snapshot_to_json should succeed");
-        let restored =
+        let restored: PriceLevel =
             PriceLevel::from_snapshot_json(&json).expect("from_snapshot_json should succeed");
 
         // The restored level reproduces the demoted consumption order exactly.
@@ -5909,7 +6036,7 @@ mod tests {
             &generator,
         );
         let mut deduped: Vec<Id> = Vec::new();
-        for trade in drain.trades().as_vec() {
+        for trade in drain.trades().as_slice() {
             let maker = trade.maker_order_id();
             if deduped.last() != Some(&maker) {
                 deduped.push(maker);
@@ -6046,8 +6173,88 @@ mod tests {
     }
 
     #[test]
-    fn test_snapshot_by_insertion_seq_empty_level() {
+    fn test_orders_follows_consumption_order_without_draining() {
         let level = PriceLevel::new(10_000);
+        level
+            .add_order(create_iceberg_order(1, 10_000, 10, 40))
+            .expect("add_order should succeed");
+        level
+            .add_order(create_sell_standard_order(2, 10_000, 100))
+            .expect("add_order should succeed");
+        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+        let generator = UuidGenerator::new(namespace);
+        // Replenish the iceberg so it moves behind the standard maker.
+        let _ = level.match_order(
+            10,
+            Id::from_u64(999),
+            TimeInForce::Gtc,
+            TakerKind::Standard,
+            TimestampMs::new(1_700_000_000_000),
+            &generator,
+        );
+
+        let walked: Vec<Id> = level.orders().map(|o| o.id()).collect();
+        let by_seq: Vec<Id> = level
+            .snapshot_by_insertion_seq()
+            .iter()
+            .map(|o| o.id())
+            .collect();
+        assert_eq!(walked, vec![Id::from_u64(2), Id::from_u64(1)]);
+        assert_eq!(walked, by_seq);
+        assert_eq!(level.order_count(), 2);
+    }
+
+    #[test]
+    fn test_orders_walk_concurrent_with_matching() {
+        use std::sync::Arc;
+        use std::thread;
+
+        const N: u64 = 200;
+        let level = Arc::new(PriceLevel::new(10_000));
+        for id in 0..N {
+            level
+                .add_order(create_sell_standard_order(id, 10_000, 1))
+                .expect("add_order should succeed");
+        }
+
+        let matcher = {
+            let level = Arc::clone(&level);
+            thread::spawn(move || {
+                let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+                let generator = UuidGenerator::new(namespace);
+                for _ in 0..N {
+                    let _ = level.match_order(
+                        1,
+                        Id::from_u64(9_999),
+                        TimeInForce::Gtc,
+                        TakerKind::Standard,
+                        TimestampMs::new(1_700_000_000_000),
+                        &generator,
+                    );
+                }
+            })
+        };
+
+        // The matcher only consumes the front and nothing is re-sequenced, so
+        // every walk follows admission order — a subsequence of it, since a
+        // fill can remove an order the walk has not reached yet — and walks
+        // only shrink.
+        let admitted: Vec<Id> = (0..N).map(Id::from_u64).collect();
+        let mut previous_len = usize::MAX;
+        for _ in 0..50 {
+            let walked: Vec<Id> = level.orders().map(|o| o.id()).collect();
+            let mut rest = admitted.iter();
+            assert!(walked.iter().all(|id| rest.any(|a| a == id)));
+            assert!(walked.len() <= previous_len);
+            previous_len = walked.len();
+        }
+        matcher.join().expect("matcher thread panicked");
+        assert_eq!(level.orders().count(), 0);
+    }
+
+    #[test]
+    fn test_snapshot_by_insertion_seq_empty_level() {
+        let level: PriceLevel = PriceLevel::new(10_000);
         assert!(level.snapshot_by_insertion_seq().is_empty());
     }
 
@@ -6311,13 +6518,15 @@ mod tests {
             &generator,
         );
 
-        assert_eq!(result.trades().as_vec().len(), 1, "exactly one fill");
+        // The trade list never holds fewer than its inline slots.
+        assert_eq!(result.trades().as_slice().len(), 1, "exactly one fill");
         assert!(
-            result.trades().as_vec().capacity() <= incoming as usize,
+            result.trades().capacity() <= (incoming as usize).max(TRADE_LIST_INLINE_CAPACITY),
             "trade buffer must be bounded by incoming quantity ({incoming}), not \
              level depth (200); was {}",
-            result.trades().as_vec().capacity()
+            result.trades().capacity()
         );
+        assert!(!result.trades().spilled(), "a one-fill result stays inline");
     }
 
     #[test]
@@ -6344,12 +6553,16 @@ mod tests {
             &generator,
         );
 
-        assert_eq!(result.trades().as_vec().len(), 3, "all three makers filled");
+        assert_eq!(
+            result.trades().as_slice().len(),
+            3,
+            "all three makers filled"
+        );
         assert!(
-            result.trades().as_vec().capacity() <= 3,
+            result.trades().capacity() <= 3.max(TRADE_LIST_INLINE_CAPACITY),
             "trade buffer must be bounded by order count (3), not the incoming \
              quantity (10000); was {}",
-            result.trades().as_vec().capacity()
+            result.trades().capacity()
         );
     }
     // ------------------------------------------------------------------
@@ -6403,7 +6616,7 @@ mod tests {
         );
 
         // The snapshot still round-trips, and the rejected order is absent.
-        let restored =
+        let restored: PriceLevel =
             PriceLevel::from_snapshot_json(&after_json).expect("snapshot must round-trip");
         assert_eq!(restored.visible_quantity(), u64::MAX);
         assert_eq!(restored.order_count(), 1);
@@ -6712,7 +6925,7 @@ mod tests {
         );
         let makers: Vec<Id> = result
             .trades()
-            .as_vec()
+            .as_slice()
             .iter()
             .map(|t| t.maker_order_id())
             .collect();
@@ -6751,8 +6964,8 @@ mod tests {
         // Re-submit id 1 with a DIFFERENT quantity: must be rejected, never
         // overwrite the live order.
         match level.add_order(create_standard_order(1, 10_000, 999)) {
-            Err(PriceLevelError::DuplicateOrderId(id)) => {
-                assert_eq!(id, Id::from_u64(1).to_string())
+            Err(PriceLevelError::DuplicateOrderId { order_id }) => {
+                assert_eq!(order_id, Id::from_u64(1))
             }
             other => panic!("expected DuplicateOrderId, got {other:?}"),
         }
@@ -6799,8 +7012,8 @@ mod tests {
         ];
         for dup in duplicates {
             match level.add_order(dup) {
-                Err(PriceLevelError::DuplicateOrderId(id)) => {
-                    assert_eq!(id, Id::from_u64(1).to_string())
+                Err(PriceLevelError::DuplicateOrderId { order_id }) => {
+                    assert_eq!(order_id, Id::from_u64(1))
                 }
                 other => panic!("expected DuplicateOrderId across variants, got {other:?}"),
             }
@@ -6902,7 +7115,7 @@ mod tests {
             );
             let makers: Vec<Id> = result
                 .trades()
-                .as_vec()
+                .as_slice()
                 .iter()
                 .map(|t| t.maker_order_id())
                 .collect();
@@ -6927,8 +7140,8 @@ mod tests {
 
         // Direct from_snapshot rejects deterministically — no level built.
         match PriceLevel::from_snapshot(snapshot.clone()) {
-            Err(PriceLevelError::DuplicateOrderId(id)) => {
-                assert_eq!(id, Id::from_u64(1).to_string())
+            Err(PriceLevelError::DuplicateOrderId { order_id }) => {
+                assert_eq!(order_id, Id::from_u64(1))
             }
             other => panic!("expected DuplicateOrderId from from_snapshot, got {other:?}"),
         }
@@ -6941,8 +7154,8 @@ mod tests {
             .expect("package must serialize");
         assert!(
             matches!(
-                PriceLevel::from_snapshot_json(&json),
-                Err(PriceLevelError::DuplicateOrderId(_))
+                PriceLevel::<()>::from_snapshot_json(&json),
+                Err(PriceLevelError::DuplicateOrderId { .. })
             ),
             "from_snapshot_json must reject a duplicate-id snapshot"
         );
@@ -6967,8 +7180,8 @@ mod tests {
         // Re-submit id 1 with a positive quantity: reserving it WOULD overflow
         // the visible counter, but the duplicate id takes precedence.
         match level.add_order(create_standard_order(1, 10_000, 100)) {
-            Err(PriceLevelError::DuplicateOrderId(id)) => {
-                assert_eq!(id, Id::from_u64(1).to_string());
+            Err(PriceLevelError::DuplicateOrderId { order_id }) => {
+                assert_eq!(order_id, Id::from_u64(1));
             }
             other => {
                 panic!("expected DuplicateOrderId (identity before counters), got {other:?}")
@@ -7000,10 +7213,10 @@ mod tests {
         let before = level.snapshot_to_json().expect("snapshot before");
         // An order at a different price must be rejected, level unchanged.
         match level.add_order(create_standard_order(2, 10_001, 50)) {
-            Err(PriceLevelError::InvalidOperation { message }) => {
-                assert!(message.contains("price"), "unexpected message: {message}");
+            Err(PriceLevelError::PriceMismatch { expected, actual }) => {
+                assert_eq!((expected, actual), (10_000, 10_001));
             }
-            other => panic!("expected wrong-price InvalidOperation, got {other:?}"),
+            other => panic!("expected PriceMismatch, got {other:?}"),
         }
         assert_eq!(level.order_count(), 1);
         assert_eq!(
@@ -7028,8 +7241,8 @@ mod tests {
         .expect("snapshot construction must succeed");
 
         match PriceLevel::try_from(&snapshot) {
-            Err(PriceLevelError::DuplicateOrderId(id)) => {
-                assert_eq!(id, Id::from_u64(7).to_string());
+            Err(PriceLevelError::DuplicateOrderId { order_id }) => {
+                assert_eq!(order_id, Id::from_u64(7));
             }
             other => panic!("expected DuplicateOrderId from TryFrom<&Snapshot>, got {other:?}"),
         }
@@ -7059,10 +7272,10 @@ mod tests {
         let before = level.snapshot_to_json().expect("snapshot before");
         // A Sell maker is incompatible with the Buy level.
         match level.add_order(create_sell_standard_order(2, 10_000, 50)) {
-            Err(PriceLevelError::InvalidOperation { message }) => {
-                assert!(message.contains("side"), "unexpected message: {message}");
+            Err(PriceLevelError::SideMismatch { expected, actual }) => {
+                assert_eq!((expected, actual), (Side::Buy, Side::Sell));
             }
-            other => panic!("expected mixed-side InvalidOperation, got {other:?}"),
+            other => panic!("expected SideMismatch, got {other:?}"),
         }
         assert_eq!(level.order_count(), 1);
         assert_eq!(
@@ -7217,7 +7430,7 @@ mod tests {
         assert!(filled.is_complete(), "non-self FOK 100 fills 40 + 60");
         let makers: Vec<Id> = filled
             .trades()
-            .as_vec()
+            .as_slice()
             .iter()
             .map(|t| t.maker_order_id())
             .collect();
@@ -7273,10 +7486,22 @@ mod tests {
             );
             // The loser is rejected with an incompatible-side error.
             if let Err(err) = &buy_res {
-                assert!(matches!(err, PriceLevelError::InvalidOperation { .. }));
+                assert!(matches!(
+                    err,
+                    PriceLevelError::SideMismatch {
+                        expected: Side::Sell,
+                        actual: Side::Buy
+                    }
+                ));
             }
             if let Err(err) = &sell_res {
-                assert!(matches!(err, PriceLevelError::InvalidOperation { .. }));
+                assert!(matches!(
+                    err,
+                    PriceLevelError::SideMismatch {
+                        expected: Side::Buy,
+                        actual: Side::Sell
+                    }
+                ));
             }
 
             // The level holds exactly one order; snapshot is single-side; the
@@ -7293,6 +7518,7 @@ mod tests {
                     order_id: Id::from_u64(winner_id),
                 })
                 .expect("cancel winner")
+                .into_order()
                 .expect("winner was resting");
             assert_eq!(level.order_count(), 0, "iter {iter}: drained");
             // Whichever side lost the race can now be admitted into the empty level.
@@ -7388,7 +7614,10 @@ mod tests {
         assert!(
             matches!(
                 PriceLevel::from_snapshot(wrong_price),
-                Err(PriceLevelError::InvalidOperation { .. })
+                Err(PriceLevelError::PriceMismatch {
+                    expected: 10_000,
+                    actual: 10_001
+                })
             ),
             "from_snapshot must reject a wrong-price order"
         );
@@ -7405,7 +7634,10 @@ mod tests {
         assert!(
             matches!(
                 PriceLevel::from_snapshot(mixed_side),
-                Err(PriceLevelError::InvalidOperation { .. })
+                Err(PriceLevelError::SideMismatch {
+                    expected: Side::Buy,
+                    actual: Side::Sell
+                })
             ),
             "from_snapshot must reject a mixed-side snapshot"
         );
@@ -7468,7 +7700,7 @@ mod tests {
             // rests (the lost-cancel / resurrection bug the old remove+push
             // demotion allowed).
             assert!(
-                cancelled.is_some(),
+                cancelled.is_found(),
                 "iter {iter}: cancel returned None (the order was momentarily absent — resurrection window)"
             );
             assert!(
@@ -7521,7 +7753,7 @@ mod tests {
             let admit = admitter.join().expect("admitter panicked");
 
             assert!(
-                matches!(admit, Err(PriceLevelError::DuplicateOrderId(_))),
+                matches!(admit, Err(PriceLevelError::DuplicateOrderId { .. })),
                 "iter {iter}: duplicate admission must always be rejected (id never leaves the map); got {admit:?}"
             );
 
@@ -7597,7 +7829,7 @@ mod tests {
                     let generator = UuidGenerator::new(Uuid::from_u128(0xBEEF_0000 + iter as u128));
                     let record = |result: &MatchResult| {
                         let mut guard = consumed.lock().expect("lock");
-                        for trade in result.trades().as_vec() {
+                        for trade in result.trades().as_slice() {
                             guard.push(trade.maker_order_id());
                         }
                     };
@@ -7627,7 +7859,7 @@ mod tests {
                             &generator,
                         );
                         record(&result);
-                        if result.trades().as_vec().is_empty() {
+                        if result.trades().as_slice().is_empty() {
                             break; // safety: no progress
                         }
                     }
@@ -7757,6 +7989,7 @@ mod tests {
                 new_quantity: Quantity::new(30),
             })
             .expect("update ok")
+            .into_order()
             .expect("maker present");
         let updated = level
             .snapshot_by_insertion_seq()
@@ -7872,7 +8105,7 @@ mod tests {
             // The cancel is the only remover; it always wins and the order is
             // gone — never cancel-None-yet-resting (resurrection).
             assert!(
-                cancelled.is_some(),
+                cancelled.is_found(),
                 "iter {iter}: cancel returned None (resurrection window)"
             );
             assert!(
@@ -8036,7 +8269,7 @@ mod tests {
             100
         );
         assert_eq!(
-            result.trades().as_vec()[0].maker_order_id(),
+            result.trades().as_slice()[0].maker_order_id(),
             Id::from_u64(1)
         );
 
@@ -8058,7 +8291,7 @@ mod tests {
 
         // The degraded flag round-trips through a snapshot.
         let json = level.snapshot_to_json().expect("snapshot json");
-        let restored = PriceLevel::from_snapshot_json(&json).expect("restore json");
+        let restored: PriceLevel = PriceLevel::from_snapshot_json(&json).expect("restore json");
         assert!(
             restored.stats().stats_degraded(),
             "the degraded flag must round-trip through a snapshot"
@@ -8084,7 +8317,7 @@ mod tests {
         );
         // The package validates (checksum over the 8-field statistics bytes) and
         // restores — i.e. an old-format fixture passes `validate`.
-        let restored =
+        let restored: PriceLevel =
             PriceLevel::from_snapshot_json(&json).expect("old-format package must validate");
         assert!(!restored.stats().stats_degraded());
         assert_eq!(restored.order_count(), 1);
@@ -8271,7 +8504,7 @@ mod tests {
                 );
                 assert_eq!(result.remaining_quantity().as_u64(), 0, "iter {iter}");
                 assert!(
-                    cancelled.is_none(),
+                    cancelled.order().is_none(),
                     "iter {iter}: id_b was consumed, so the cancel finds nothing"
                 );
             } else {
@@ -8292,7 +8525,7 @@ mod tests {
                     "iter {iter}: a killed FOK leaves the whole taker unfilled"
                 );
                 assert_eq!(
-                    cancelled.map(|o| o.id()),
+                    cancelled.into_order().map(|o| o.id()),
                     Some(id_b),
                     "iter {iter}: the cancel that won removed id_b"
                 );
@@ -8438,7 +8671,7 @@ mod tests {
                 );
                 assert_eq!(result.remaining_quantity().as_u64(), 0, "iter {iter}");
                 assert!(
-                    updated.is_none(),
+                    updated.order().is_none(),
                     "iter {iter}: id_b was consumed, so the same-price update finds nothing"
                 );
             } else {
@@ -8455,7 +8688,7 @@ mod tests {
                     "iter {iter}: a killed FOK leaves the whole taker unfilled"
                 );
                 assert_eq!(
-                    updated.map(|o| o.id()),
+                    updated.into_order().map(|o| o.id()),
                     Some(id_b),
                     "iter {iter}: the resize that won returned the prior id_b order"
                 );
@@ -8628,7 +8861,7 @@ mod tests_eq {
     #[test]
     fn test_price_level_partial_eq() {
         // Create two price levels with the same price
-        let price_level1 = PriceLevel::new(10000);
+        let price_level1: PriceLevel = PriceLevel::new(10000);
         let price_level2 = PriceLevel::new(10000);
 
         // Create a price level with a different price
@@ -8645,7 +8878,7 @@ mod tests_eq {
     #[test]
     fn test_price_level_eq() {
         // Test Eq trait (reflexivity, symmetry, transitivity)
-        let price_level1 = PriceLevel::new(10000);
+        let price_level1: PriceLevel = PriceLevel::new(10000);
         let price_level2 = PriceLevel::new(10000);
         let price_level3 = PriceLevel::new(10000);
 
@@ -8664,7 +8897,7 @@ mod tests_eq {
 
     #[test]
     fn test_price_level_partial_ord() {
-        let price_level1 = PriceLevel::new(10000);
+        let price_level1: PriceLevel = PriceLevel::new(10000);
         let price_level2 = PriceLevel::new(10500);
         let price_level3 = PriceLevel::new(9500);
 
@@ -8687,7 +8920,7 @@ mod tests_eq {
     #[test]
     fn test_price_level_ord() {
         // Create some price levels
-        let price_level1 = PriceLevel::new(9000);
+        let price_level1: PriceLevel = PriceLevel::new(9000);
         let price_level2 = PriceLevel::new(10000);
         let price_level3 = PriceLevel::new(11000);
 
@@ -8708,3 +8941,4106 @@ mod tests_eq {
         assert_eq!(price_level2.cmp(&price_level2), std::cmp::Ordering::Equal);
     }
 }
+
+#[cfg(test)]
+mod tests_stop_orders {
+    use crate::UuidGenerator;
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use uuid::Uuid;
+
+    static TIMESTAMP_COUNTER: AtomicU64 = AtomicU64::new(1616823000000);
+
+    fn create_standard_order(id: u64, price: u128, quantity: u64) -> OrderType<()> {
+        let timestamp = TIMESTAMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn create_stop_order(id: u64, price: u128, quantity: u64, trigger: u128) -> OrderType<()> {
+        let timestamp = TIMESTAMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+        OrderType::StopLimit {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            trigger_price: Price::new(trigger),
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_pending_stop_is_skipped_by_match() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_stop_order(1, 10000, 50, 10100))
+            .expect("add_order should succeed");
+        level
+            .add_order(create_standard_order(2, 10000, 30))
+            .expect("add_order should succeed");
+
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        let result = level.match_order(
+            100,
+            Id::from_u64(99),
+            TimeInForce::Gtc,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &generator,
+        );
+
+        // Only the standard maker trades; the dormant stop keeps resting.
+        assert_eq!(result.trades().len(), 1);
+        assert_eq!(
+            result.trades().as_slice()[0].maker_order_id(),
+            Id::from_u64(2)
+        );
+        assert_eq!(result.remaining_quantity(), Quantity::new(70));
+        assert_eq!(level.order_count(), 1);
+        assert_eq!(level.matchable_quantity(100, Id::from_u64(99)), 0);
+    }
+
+    #[test]
+    fn test_trigger_orders_activates_and_demotes() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_stop_order(1, 10000, 50, 10100))
+            .expect("add_order should succeed");
+        level
+            .add_order(create_stop_order(2, 10000, 20, 10200))
+            .expect("add_order should succeed");
+        level
+            .add_order(create_standard_order(3, 10000, 30))
+            .expect("add_order should succeed");
+
+        // Below every trigger: nothing activates, and the dormant stops are
+        // not displayed depth.
+        let activated = level
+            .trigger_orders(Price::new(10050))
+            .expect("trigger_orders should succeed");
+        assert!(activated.is_empty());
+        assert_eq!(level.visible_quantity(), 30);
+
+        // Reaches the first trigger only; its quantity joins the depth.
+        let activated = level
+            .trigger_orders(Price::new(10100))
+            .expect("trigger_orders should succeed");
+        assert_eq!(activated.len(), 1);
+        assert_eq!(activated[0].id(), Id::from_u64(1));
+        assert!(matches!(*activated[0], OrderType::Standard { .. }));
+        assert_eq!(level.visible_quantity(), 80);
+
+        // The activated stop joined the back of the queue, behind order 3.
+        let ids: Vec<Id> = level
+            .snapshot_by_insertion_seq()
+            .iter()
+            .map(|order| order.id())
+            .collect();
+        assert_eq!(ids, vec![Id::from_u64(2), Id::from_u64(3), Id::from_u64(1)]);
+
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        let result = level.match_order(
+            100,
+            Id::from_u64(99),
+            TimeInForce::Gtc,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &generator,
+        );
+        assert_eq!(result.executed_quantity().expect("qty"), Quantity::new(80));
+        assert_eq!(level.order_count(), 1);
+        assert!(level.snapshot_orders()[0].is_stop());
+        assert_eq!(level.visible_quantity(), 0);
+    }
+
+    #[test]
+    fn test_pending_stops_are_not_displayed() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_stop_order(1, 10000, 50, 10100))
+            .expect("add_order should succeed");
+        level
+            .add_order(create_standard_order(2, 10000, 30))
+            .expect("add_order should succeed");
+
+        let snapshot = level.snapshot();
+        assert_eq!(snapshot.order_count(), 2);
+        assert_eq!(snapshot.visible_quantity(), Quantity::new(30));
+        assert_eq!(
+            snapshot.checksum_crc32(10),
+            level.public_snapshot().checksum_crc32(10)
+        );
+
+        let public = level.public_snapshot();
+        assert_eq!(public.order_count(), 1);
+        assert_eq!(public.orders()[0].id(), Id::from_u64(2));
+        let mbo = level.mbo_records();
+        assert_eq!(mbo.len(), 1);
+        assert_eq!(mbo[0].priority, 0);
+        let mbp = level.mbp_record().unwrap().unwrap();
+        assert_eq!((mbp.size, mbp.order_count), (Quantity::new(30), 1));
+
+        // Nothing displayed rests ahead of order 2.
+        assert_eq!(level.queue_position(Id::from_u64(2)), Some((1, 0)));
+
+        // A cancelled stop releases nothing it never counted.
+        level
+            .update_order(crate::orders::OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .expect("cancel should succeed");
+        assert_eq!(level.visible_quantity(), 30);
+    }
+
+    fn create_trailing_stop(id: u64, side: Side, trail: u64, reference: u128) -> OrderType<()> {
+        let timestamp = TIMESTAMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+        OrderType::TrailingStop {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(10),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            trail_amount: Quantity::new(trail),
+            last_reference_price: Price::new(reference),
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_update_trailing_stops_ratchets_and_reports() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_trailing_stop(1, Side::Buy, 100, 10000))
+            .expect("add_order should succeed");
+        level
+            .add_order(create_standard_order(2, 10000, 30))
+            .expect("add_order should succeed");
+        level
+            .add_order(create_trailing_stop(3, Side::Buy, 300, 10000))
+            .expect("add_order should succeed");
+
+        // Market sells off: both references ratchet down, nothing is reached.
+        let reached = level
+            .update_trailing_stops(Price::new(9500))
+            .expect("update_trailing_stops should succeed");
+        assert!(reached.is_empty());
+
+        let stops: Vec<_> = level
+            .snapshot_by_insertion_seq()
+            .into_iter()
+            .filter_map(|order| order.trailing_stop_price())
+            .collect();
+        assert_eq!(stops, vec![Price::new(9600), Price::new(9800)]);
+
+        // Market bounces to 9650: only the tighter stop (9600) is reached,
+        // and the wider one keeps its ratcheted reference.
+        let reached = level
+            .update_trailing_stops(Price::new(9650))
+            .expect("update_trailing_stops should succeed");
+        assert_eq!(reached.len(), 1);
+        assert_eq!(reached[0].id(), Id::from_u64(1));
+
+        // Ratcheting keeps queue position and quantity.
+        let ids: Vec<Id> = level
+            .snapshot_by_insertion_seq()
+            .iter()
+            .map(|order| order.id())
+            .collect();
+        assert_eq!(ids, vec![Id::from_u64(1), Id::from_u64(2), Id::from_u64(3)]);
+        assert_eq!(level.visible_quantity(), 50);
+        assert_eq!(level.order_count(), 3);
+    }
+
+    #[test]
+    fn test_update_trailing_stops_never_misses_a_concurrent_ratchet() {
+        use std::sync::Barrier;
+        use std::thread;
+
+        const ITERATIONS: usize = 50;
+        const STOPS: u64 = 64;
+
+        for iter in 0..ITERATIONS {
+            // Sell stops trailing 100 below 10000. One call ratchets them to
+            // 10200 (stop 10100); the other sees 10050, which ratchets the
+            // old reference but reaches the new stop.
+            let level = PriceLevel::new(10000);
+            for id in 1..=STOPS {
+                level
+                    .add_order(create_trailing_stop(id, Side::Sell, 100, 10000))
+                    .expect("add_order should succeed");
+            }
+            let before = level.mutation_sequence();
+            let barrier = Barrier::new(2);
+
+            let reached = thread::scope(|scope| {
+                scope.spawn(|| {
+                    barrier.wait();
+                    level.update_trailing_stops(Price::new(10200)).unwrap();
+                });
+                let dip = scope.spawn(|| {
+                    barrier.wait();
+                    level.update_trailing_stops(Price::new(10050)).unwrap()
+                });
+                dip.join().expect("thread panicked")
+            });
+
+            // The rise ratchets every stop. The dip, for each stop, either ran
+            // first and ratcheted it or ran after the rise and reported it;
+            // deciding on a stale reference would do neither.
+            let dip_ratchets = level.mutation_sequence() - before - STOPS;
+            assert_eq!(
+                reached.len() as u64 + dip_ratchets,
+                STOPS,
+                "iter {iter}: {} reached, {dip_ratchets} ratcheted by the dip",
+                reached.len()
+            );
+            for order in &reached {
+                assert_eq!(order.trailing_stop_price(), Some(Price::new(10100)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_matching_policy {
+    use crate::UuidGenerator;
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::MatchingPolicy;
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use uuid::Uuid;
+
+    static TIMESTAMP_COUNTER: AtomicU64 = AtomicU64::new(1616823000000);
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        let timestamp = TIMESTAMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn create_iceberg_order(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        let timestamp = TIMESTAMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn level_with(policy: MatchingPolicy, orders: Vec<OrderType<()>>) -> PriceLevel {
+        let level = PriceLevel::with_matching_policy(10000, policy);
+        for order in orders {
+            level.add_order(order).expect("add_order should succeed");
+        }
+        level
+    }
+
+    fn take(level: &PriceLevel, quantity: u64, tif: TimeInForce) -> MatchResult {
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        level.match_order(
+            quantity,
+            Id::from_u64(999),
+            tif,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &generator,
+        )
+    }
+
+    fn fills(result: &MatchResult) -> Vec<(Id, u64)> {
+        result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|t| (t.maker_order_id(), t.quantity().as_u64()))
+            .collect()
+    }
+
+    #[test]
+    fn test_default_policy_is_fifo() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        assert_eq!(level.matching_policy(), MatchingPolicy::Fifo);
+        level.set_matching_policy(MatchingPolicy::ProRata);
+        assert_eq!(level.matching_policy(), MatchingPolicy::ProRata);
+    }
+
+    #[test]
+    fn test_pro_rata_rounds_down_and_distributes_remainder_by_time() {
+        let level = level_with(
+            MatchingPolicy::ProRata,
+            vec![
+                create_standard_order(1, 60),
+                create_standard_order(2, 30),
+                create_standard_order(3, 10),
+            ],
+        );
+
+        // Shares of 7: 4.2 / 2.1 / 0.7 -> 4 / 2 / 0, and the one rounded-off
+        // unit goes to the oldest maker.
+        let result = take(&level, 7, TimeInForce::Gtc);
+        assert_eq!(
+            fills(&result),
+            vec![(Id::from_u64(1), 5), (Id::from_u64(2), 2)]
+        );
+        assert_eq!(result.remaining_quantity(), Quantity::ZERO);
+        assert_eq!(level.visible_quantity(), 93);
+        assert_eq!(level.order_count(), 3);
+    }
+
+    #[test]
+    fn test_pro_rata_proportional_split() {
+        let level = level_with(
+            MatchingPolicy::ProRata,
+            vec![
+                create_standard_order(1, 60),
+                create_standard_order(2, 30),
+                create_standard_order(3, 10),
+            ],
+        );
+        let result = take(&level, 50, TimeInForce::Gtc);
+        assert_eq!(
+            fills(&result),
+            vec![
+                (Id::from_u64(1), 30),
+                (Id::from_u64(2), 15),
+                (Id::from_u64(3), 5)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pro_rata_taker_larger_than_depth_fills_everyone() {
+        let level = level_with(
+            MatchingPolicy::ProRata,
+            vec![create_standard_order(1, 20), create_standard_order(2, 10)],
+        );
+        let result = take(&level, 100, TimeInForce::Gtc);
+        assert_eq!(result.executed_quantity().unwrap(), Quantity::new(30));
+        assert_eq!(result.filled_order_ids().len(), 2);
+        assert_eq!(level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_size_time_priority_prefers_largest_then_oldest() {
+        let level = level_with(
+            MatchingPolicy::SizeTimePriority,
+            vec![
+                create_standard_order(1, 10),
+                create_standard_order(2, 50),
+                create_standard_order(3, 50),
+            ],
+        );
+        let result = take(&level, 70, TimeInForce::Gtc);
+        assert_eq!(
+            fills(&result),
+            vec![(Id::from_u64(2), 50), (Id::from_u64(3), 20)]
+        );
+        assert_eq!(level.visible_quantity(), 40);
+    }
+
+    #[test]
+    fn test_fok_fills_hidden_depth_under_pro_rata() {
+        let level = level_with(
+            MatchingPolicy::ProRata,
+            vec![
+                create_iceberg_order(1, 10, 40),
+                create_standard_order(2, 10),
+            ],
+        );
+        // Displayed depth is 20, but the iceberg's hidden tranche makes 60
+        // matchable: the FIFO tail after the pro-rata plan must deliver it.
+        assert_eq!(level.matchable_quantity(45, Id::from_u64(999)), 45);
+        let result = take(&level, 45, TimeInForce::Fok);
+        assert!(result.is_complete());
+        assert_eq!(result.executed_quantity().unwrap(), Quantity::new(45));
+    }
+
+    #[test]
+    fn test_matching_policy_string_round_trip() {
+        for policy in [
+            MatchingPolicy::Fifo,
+            MatchingPolicy::ProRata,
+            MatchingPolicy::SizeTimePriority,
+        ] {
+            assert_eq!(
+                MatchingPolicy::from_str(&policy.to_string()).unwrap(),
+                policy
+            );
+        }
+        assert!(MatchingPolicy::from_str("lifo").is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_match_scratch {
+    use crate::UuidGenerator;
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::MatchingPolicy;
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use uuid::Uuid;
+
+    static TIMESTAMP_COUNTER: AtomicU64 = AtomicU64::new(1616823000000);
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        let timestamp = TIMESTAMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn level_with(policy: MatchingPolicy, makers: u64, quantity: u64) -> PriceLevel {
+        let level = PriceLevel::with_matching_policy(10000, policy);
+        for id in 0..makers {
+            level
+                .add_order(create_standard_order(id, quantity))
+                .expect("add_order should succeed");
+        }
+        level
+    }
+
+    fn take(level: &PriceLevel, quantity: u64, tif: TimeInForce) -> MatchResult {
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        level.match_order(
+            quantity,
+            Id::from_u64(999),
+            tif,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &generator,
+        )
+    }
+
+    #[test]
+    fn test_scratch_capacity_is_retained_between_matches() {
+        let level = level_with(MatchingPolicy::ProRata, 16, 100);
+        assert_eq!(level.test_scratch_capacity(), Some(0));
+
+        let result = take(&level, 16, TimeInForce::Gtc);
+        assert_eq!(result.trades().len(), 16);
+        let warm = level.test_scratch_capacity().expect("slot returned");
+        assert!(warm >= 16, "snapshot buffer kept its capacity: {warm}");
+
+        let result = take(&level, 16, TimeInForce::Fok);
+        assert_eq!(result.trades().len(), 16);
+        assert_eq!(level.test_scratch_capacity(), Some(warm));
+    }
+
+    #[test]
+    fn test_scratch_does_not_keep_orders_alive() {
+        let level = level_with(MatchingPolicy::ProRata, 4, 10);
+        let first = level.snapshot_orders()[0].clone();
+        // Ours, the queue's.
+        assert_eq!(Arc::strong_count(&first), 2);
+
+        // Every maker is partially filled and replaced in the queue, so only
+        // our handle survives: neither the snapshot nor the plan buffers kept
+        // the pre-fill order alive.
+        let result = take(&level, 8, TimeInForce::Fok);
+        assert_eq!(result.executed_quantity().unwrap().as_u64(), 8);
+        assert_eq!(Arc::strong_count(&first), 1);
+        for order in level.snapshot_orders() {
+            assert_eq!(Arc::strong_count(&order), 2);
+        }
+    }
+
+    #[test]
+    fn test_repeated_matches_are_unaffected_by_reuse() {
+        let pooled = level_with(MatchingPolicy::SizeTimePriority, 8, 50);
+        for _ in 0..5 {
+            let fresh = PriceLevel::from_snapshot(pooled.snapshot()).expect("valid snapshot");
+            fresh.set_matching_policy(MatchingPolicy::SizeTimePriority);
+            let expected = take(&fresh, 30, TimeInForce::Gtc);
+            let actual = take(&pooled, 30, TimeInForce::Gtc);
+            let quantities = |r: &MatchResult| {
+                r.trades()
+                    .as_slice()
+                    .iter()
+                    .map(|t| (t.maker_order_id(), t.quantity()))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(quantities(&expected), quantities(&actual));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_strict_fifo {
+    use crate::UuidGenerator;
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::MatchingPolicy;
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use uuid::Uuid;
+
+    static TIMESTAMP_COUNTER: AtomicU64 = AtomicU64::new(1616823000000);
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        let timestamp = TIMESTAMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn create_stop_limit_order(id: u64, quantity: u64) -> OrderType<()> {
+        let timestamp = TIMESTAMP_COUNTER.fetch_add(1, Ordering::SeqCst);
+        OrderType::StopLimit {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            trigger_price: Price::new(9000),
+            extra_fields: (),
+        }
+    }
+
+    fn strict_level(makers: &[(u64, u64)]) -> PriceLevel {
+        let level = PriceLevel::with_matching_policy(10000, MatchingPolicy::StrictFifo);
+        for &(id, quantity) in makers {
+            level
+                .add_order(create_standard_order(id, quantity))
+                .expect("add_order should succeed");
+        }
+        level
+    }
+
+    fn take(level: &PriceLevel, taker: u64, quantity: u64, tif: TimeInForce) -> MatchResult {
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        level.match_order(
+            quantity,
+            Id::from_u64(taker),
+            tif,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &generator,
+        )
+    }
+
+    fn makers(result: &MatchResult) -> Vec<Id> {
+        result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|t| t.maker_order_id())
+            .collect()
+    }
+
+    fn ids(raw: &[u64]) -> Vec<Id> {
+        raw.iter().copied().map(Id::from_u64).collect()
+    }
+
+    #[test]
+    fn test_strict_fifo_parse_and_display() {
+        assert_eq!(MatchingPolicy::StrictFifo.to_string(), "STRICT_FIFO");
+        assert_eq!(
+            MatchingPolicy::from_str("strict_fifo").unwrap(),
+            MatchingPolicy::StrictFifo
+        );
+        assert!(MatchingPolicy::StrictFifo.is_fifo());
+        assert!(MatchingPolicy::Fifo.is_fifo());
+        assert!(!MatchingPolicy::ProRata.is_fifo());
+        let json = serde_json::to_string(&MatchingPolicy::StrictFifo).unwrap();
+        assert_eq!(json, "\"strict_fifo\"");
+    }
+
+    #[test]
+    fn test_strict_fifo_executes_in_arrival_order_across_sweeps() {
+        let level = strict_level(&[(1, 5), (2, 5), (3, 5), (4, 5)]);
+        let mut executed = Vec::new();
+        for _ in 0..7 {
+            executed.extend(makers(&take(&level, 900, 3, TimeInForce::Ioc)));
+        }
+        executed.dedup();
+        assert_eq!(executed, ids(&[1, 2, 3, 4]));
+        assert_eq!(level.order_count(), 0);
+        assert_eq!(level.fifo_violations(), 0);
+    }
+
+    #[test]
+    fn test_strict_fifo_cancel_leaves_tombstone_reaped_by_sweep() {
+        let level = strict_level(&[(1, 10), (2, 10), (3, 10)]);
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .expect("cancel");
+        assert_eq!(level.order_count(), 2);
+        assert_eq!(level.tombstone_count(), 1);
+
+        let result = take(&level, 900, 20, TimeInForce::Gtc);
+        assert_eq!(makers(&result), ids(&[1, 3]));
+        assert_eq!(level.tombstone_count(), 0);
+        assert_eq!(level.fifo_violations(), 0);
+    }
+
+    #[test]
+    fn test_strict_fifo_amend_increase_moves_to_tail_via_tombstone() {
+        let level = strict_level(&[(1, 10), (2, 10), (3, 10)]);
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(1),
+                new_quantity: Quantity::new(15),
+            })
+            .expect("amend");
+        assert_eq!(level.tombstone_count(), 1);
+
+        let result = take(&level, 900, 35, TimeInForce::Gtc);
+        assert_eq!(makers(&result), ids(&[2, 3, 1]));
+        assert_eq!(level.tombstone_count(), 0);
+    }
+
+    #[test]
+    fn test_strict_fifo_passes_over_pending_stop() {
+        let level = PriceLevel::with_matching_policy(10000, MatchingPolicy::StrictFifo);
+        level
+            .add_order(create_stop_limit_order(1, 10))
+            .expect("add stop");
+        level
+            .add_order(create_standard_order(2, 10))
+            .expect("add maker");
+
+        let result = take(&level, 900, 10, TimeInForce::Fok);
+        assert_eq!(makers(&result), ids(&[2]));
+        assert_eq!(level.order_count(), 1);
+        assert_eq!(level.fifo_violations(), 0);
+    }
+
+    #[test]
+    fn test_strict_fifo_fok_feasibility_matches_sweep() {
+        let level = strict_level(&[(1, 10), (2, 10)]);
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .expect("cancel");
+        assert_eq!(level.matchable_quantity(20, Id::from_u64(900)), 10);
+        let killed = take(&level, 900, 20, TimeInForce::Fok);
+        assert!(killed.trades().as_slice().is_empty());
+        let filled = take(&level, 900, 10, TimeInForce::Fok);
+        assert_eq!(makers(&filled), ids(&[2]));
+    }
+
+    #[test]
+    fn test_leaving_strict_fifo_compacts_tombstones() {
+        let level = strict_level(&[(1, 10), (2, 10), (3, 10)]);
+        for id in [1, 2] {
+            level
+                .update_order(OrderUpdate::Cancel {
+                    order_id: Id::from_u64(id),
+                })
+                .expect("cancel");
+        }
+        assert_eq!(level.tombstone_count(), 2);
+        assert_eq!(level.compact_tombstones(), 2);
+
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(3),
+            })
+            .expect("cancel");
+        assert_eq!(level.tombstone_count(), 1);
+        level.set_matching_policy(MatchingPolicy::Fifo);
+        assert_eq!(level.tombstone_count(), 0);
+    }
+
+    #[test]
+    fn test_strict_fifo_concurrent_execution_order_equals_arrival_order() {
+        const MAKERS: u64 = 400;
+        let level = Arc::new(PriceLevel::with_matching_policy(
+            10000,
+            MatchingPolicy::StrictFifo,
+        ));
+        // Every executed trade, recorded by whichever taker thread emitted
+        // it. The log lock is held across each match, so log order is
+        // execution order; admissions and cancels still race the sweeps.
+        let executed = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        std::thread::scope(|scope| {
+            let producer = {
+                let level = Arc::clone(&level);
+                scope.spawn(move || {
+                    for id in 0..MAKERS {
+                        level
+                            .add_order(create_standard_order(id, 3))
+                            .expect("admit");
+                        if id % 7 == 3 {
+                            let _ = level.update_order(OrderUpdate::Cancel {
+                                order_id: Id::from_u64(id - 2),
+                            });
+                        }
+                    }
+                })
+            };
+            for taker in 0..2_u64 {
+                let level = Arc::clone(&level);
+                let executed = Arc::clone(&executed);
+                scope.spawn(move || {
+                    for _ in 0..300 {
+                        let mut log = executed.lock().unwrap();
+                        let result = take(&level, 10_000 + taker, 2, TimeInForce::Ioc);
+                        log.extend(makers(&result));
+                    }
+                });
+            }
+            producer.join().expect("producer panicked");
+        });
+
+        let arrival = |id: &Id| (0..MAKERS).position(|raw| Id::from_u64(raw) == *id);
+        let executed: Vec<usize> = executed
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|id| arrival(id).expect("known maker"))
+            .collect();
+        assert!(!executed.is_empty());
+        assert!(
+            executed.windows(2).all(|w| w[0] <= w[1]),
+            "trades must follow arrival order"
+        );
+        assert_eq!(level.fifo_violations(), 0);
+        level.compact_tombstones();
+        assert_eq!(level.tombstone_count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod tests_trade_stamping {
+    use crate::UuidGenerator;
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use uuid::Uuid;
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn take(level: &PriceLevel, quantity: u64) -> MatchResult {
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        level.match_order(
+            quantity,
+            Id::from_u64(999),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823000100),
+            &generator,
+        )
+    }
+
+    fn sequences(result: &MatchResult) -> Vec<Option<u64>> {
+        result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|trade| trade.level_sequence())
+            .collect()
+    }
+
+    #[test]
+    fn test_mutation_sequence_counts_every_committed_mutation() {
+        let level = PriceLevel::new(10000);
+        assert_eq!(level.mutation_sequence(), 0);
+
+        level.add_order(create_standard_order(1, 5)).unwrap();
+        level.add_order(create_standard_order(2, 5)).unwrap();
+        assert_eq!(level.mutation_sequence(), 2);
+
+        // One fill per maker touched.
+        let result = take(&level, 7);
+        assert_eq!(result.trades().len(), 2);
+        assert_eq!(level.mutation_sequence(), 4);
+
+        let _ = level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+        assert_eq!(level.mutation_sequence(), 5);
+
+        // A taker that finds nothing commits nothing.
+        let _ = take(&level, 1);
+        assert_eq!(level.mutation_sequence(), 5);
+    }
+
+    #[test]
+    fn test_trades_unstamped_by_default() {
+        let level = PriceLevel::new(10000);
+        assert!(!level.trade_stamping());
+        level.add_order(create_standard_order(1, 5)).unwrap();
+
+        let result = take(&level, 3);
+        assert_eq!(sequences(&result), vec![None]);
+        // The sequence still advances for the fill.
+        assert_eq!(level.mutation_sequence(), 2);
+    }
+
+    #[test]
+    fn test_stamped_trades_link_to_snapshots() {
+        let level = PriceLevel::new(10000);
+        level.set_trade_stamping(true);
+        level.add_order(create_standard_order(1, 5)).unwrap();
+        level.add_order(create_standard_order(2, 5)).unwrap();
+
+        let before = level.snapshot();
+        assert_eq!(before.mutation_sequence(), 2);
+
+        let result = take(&level, 8);
+        assert_eq!(sequences(&result), vec![Some(3), Some(4)]);
+
+        let after = level.snapshot();
+        assert_eq!(after.mutation_sequence(), 4);
+        for trade in result.trades().as_slice() {
+            let sequence = trade.level_sequence().unwrap();
+            assert!(sequence > before.mutation_sequence());
+            assert!(sequence <= after.mutation_sequence());
+        }
+    }
+
+    #[test]
+    fn test_restored_level_resumes_mutation_sequence() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_standard_order(1, 5)).unwrap();
+        level.add_order(create_standard_order(2, 5)).unwrap();
+        let _ = take(&level, 1);
+        assert_eq!(level.mutation_sequence(), 3);
+
+        let json = level.snapshot_to_json().unwrap();
+        let restored = PriceLevel::from_snapshot_json(&json).unwrap();
+        assert_eq!(restored.mutation_sequence(), 3);
+        // Stamping is configuration and is not restored.
+        assert!(!restored.trade_stamping());
+
+        restored.set_trade_stamping(true);
+        let result = take(&restored, 1);
+        assert_eq!(sequences(&result), vec![Some(4)]);
+    }
+}
+
+#[cfg(test)]
+mod tests_display_delay {
+    use crate::UuidGenerator;
+    use crate::errors::PriceLevelError;
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use uuid::Uuid;
+
+    const PLACED_AT: u64 = 1616823000000;
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(PLACED_AT),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn create_iceberg_order(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(PLACED_AT),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_delayed_order_is_hidden_until_released() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_standard_order(1, 10)).unwrap();
+        level
+            .add_order_with_display_delay(create_iceberg_order(2, 5, 20), 100)
+            .unwrap();
+
+        assert_eq!(level.visible_quantity(), 10);
+        assert_eq!(level.hidden_quantity(), 25);
+        assert_eq!(level.total_quantity().unwrap(), 35);
+        assert_eq!(
+            level.display_time(Id::from_u64(2)),
+            Some(TimestampMs::new(PLACED_AT + 100))
+        );
+        assert_eq!(level.display_time(Id::from_u64(1)), None);
+
+        // Before the delay elapses nothing is released.
+        let released = level
+            .release_displays(TimestampMs::new(PLACED_AT + 99))
+            .unwrap();
+        assert!(released.is_empty());
+        assert_eq!(level.visible_quantity(), 10);
+
+        let released = level
+            .release_displays(TimestampMs::new(PLACED_AT + 100))
+            .unwrap();
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].id(), Id::from_u64(2));
+        assert_eq!(level.visible_quantity(), 15);
+        assert_eq!(level.hidden_quantity(), 20);
+        assert_eq!(level.display_time(Id::from_u64(2)), None);
+    }
+
+    #[test]
+    fn test_public_snapshot_excludes_delayed_orders() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_standard_order(1, 10)).unwrap();
+        level
+            .add_order_with_display_delay(create_standard_order(2, 7), 50)
+            .unwrap();
+
+        let public = level.public_snapshot();
+        assert_eq!(public.order_count(), 1);
+        assert_eq!(public.orders()[0].id(), Id::from_u64(1));
+        assert_eq!(public.visible_quantity(), Quantity::new(10));
+
+        // The persistence snapshot keeps every resting order.
+        let full = level.snapshot();
+        assert_eq!(full.order_count(), 2);
+        assert_eq!(full.visible_quantity(), Quantity::new(17));
+
+        level
+            .release_displays(TimestampMs::new(PLACED_AT + 50))
+            .unwrap();
+        assert_eq!(level.public_snapshot().order_count(), 2);
+    }
+
+    #[test]
+    fn test_delayed_order_matches_while_withheld() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order_with_display_delay(create_standard_order(1, 10), 1_000)
+            .unwrap();
+        assert_eq!(level.visible_quantity(), 0);
+
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        let result = level.match_order(
+            4,
+            Id::from_u64(999),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(PLACED_AT + 1),
+            &generator,
+        );
+        assert_eq!(result.executed_quantity().unwrap(), Quantity::new(4));
+        assert_eq!(level.visible_quantity(), 0);
+        assert_eq!(level.hidden_quantity(), 6);
+
+        level
+            .release_displays(TimestampMs::new(PLACED_AT + 1_000))
+            .unwrap();
+        assert_eq!(level.visible_quantity(), 6);
+        assert_eq!(level.hidden_quantity(), 0);
+    }
+
+    #[test]
+    fn test_zero_delay_displays_immediately() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order_with_display_delay(create_standard_order(1, 10), 0)
+            .unwrap();
+        assert_eq!(level.visible_quantity(), 10);
+        assert_eq!(level.display_time(Id::from_u64(1)), None);
+    }
+
+    #[test]
+    fn test_stale_delay_does_not_mask_readmitted_order() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order_with_display_delay(create_standard_order(1, 10), 100)
+            .unwrap();
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        assert_eq!(level.display_time(Id::from_u64(1)), None);
+
+        // The same id re-admitted without a delay is displayed at once.
+        level.add_order(create_standard_order(1, 8)).unwrap();
+        assert_eq!(level.visible_quantity(), 8);
+        assert_eq!(level.hidden_quantity(), 0);
+    }
+
+    #[test]
+    fn test_rejected_delayed_admission_registers_nothing() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_standard_order(1, 10)).unwrap();
+
+        let err = level
+            .add_order_with_display_delay(create_standard_order(1, 5), 100)
+            .unwrap_err();
+        assert!(matches!(err, PriceLevelError::DuplicateOrderId { .. }));
+        assert_eq!(level.display_time(Id::from_u64(1)), None);
+        assert_eq!(level.visible_quantity(), 10);
+    }
+}
+
+#[cfg(test)]
+mod tests_quarantine {
+    use crate::UuidGenerator;
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::QuarantineReason;
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::num::NonZeroU64;
+    use uuid::Uuid;
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn create_iceberg_order(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn create_reserve_order(id: u64, visible: u64, hidden: u64, auto: bool) -> OrderType<()> {
+        OrderType::ReserveOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            replenish_threshold: Quantity::new(0),
+            replenish_amount: NonZeroU64::new(5),
+            auto_replenish: auto,
+            extra_fields: (),
+        }
+    }
+
+    fn match_taker(level: &PriceLevel, quantity: u64) -> crate::execution::MatchResult {
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        level.match_order(
+            quantity,
+            Id::from_u64(999),
+            TimeInForce::Gtc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &generator,
+        )
+    }
+
+    #[test]
+    fn test_empty_maker_is_quarantined_and_sweep_continues() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_standard_order(1, 0)).unwrap();
+        level.add_order(create_standard_order(2, 10)).unwrap();
+
+        let result = match_taker(&level, 4);
+
+        let trades = result.trades().as_slice();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].maker_order_id(), Id::from_u64(2));
+        assert_eq!(trades[0].quantity(), Quantity::new(4));
+        assert!(result.filled_order_ids().is_empty());
+
+        assert_eq!(level.order_count(), 1);
+        assert_eq!(level.visible_quantity(), 6);
+        assert_eq!(level.quarantine_count(), 1);
+
+        let quarantined = level.quarantined_orders();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(quarantined[0].order.id(), Id::from_u64(1));
+        assert_eq!(quarantined[0].reason, QuarantineReason::EmptyOrder);
+        assert_eq!(quarantined[0].taker_order_id, Id::from_u64(999));
+        assert_eq!(quarantined[0].timestamp, TimestampMs::new(1616823001000));
+        assert_eq!(level.stats().orders_removed(), 1);
+    }
+
+    #[test]
+    fn test_stranded_reserve_releases_hidden_counter() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_reserve_order(1, 0, 50, false))
+            .unwrap();
+        assert_eq!(level.hidden_quantity(), 50);
+
+        let result = match_taker(&level, 10);
+
+        assert!(result.trades().as_slice().is_empty());
+        assert_eq!(result.remaining_quantity(), Quantity::new(10));
+        assert_eq!(level.order_count(), 0);
+        assert_eq!(level.visible_quantity(), 0);
+        assert_eq!(level.hidden_quantity(), 0);
+
+        let quarantined = level.take_quarantined();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(quarantined[0].reason, QuarantineReason::StrandedHidden);
+        assert_eq!(*quarantined[0].order, create_reserve_order(1, 0, 50, false));
+
+        // Drained, but the lifetime count keeps it.
+        assert!(level.quarantined_orders().is_empty());
+        assert_eq!(level.quarantine_count(), 1);
+        // An emptied level accepts the opposite side again.
+        let mut buy = create_standard_order(2, 5);
+        if let OrderType::Standard { side, .. } = &mut buy {
+            *side = Side::Buy;
+        }
+        assert!(level.add_order(buy).is_ok());
+    }
+
+    #[test]
+    fn test_corrupted_snapshot_iceberg_is_quarantined_on_match() {
+        let source = PriceLevel::new(10000);
+        source.add_order(create_iceberg_order(1, 0, 0)).unwrap();
+        source.add_order(create_iceberg_order(2, 5, 20)).unwrap();
+        let json = source.snapshot_to_json().unwrap();
+
+        let level = PriceLevel::from_snapshot_json(&json).unwrap();
+        let result = match_taker(&level, 5);
+
+        assert_eq!(result.executed_quantity().unwrap(), Quantity::new(5));
+        assert_eq!(level.order_count(), 1);
+        assert_eq!(level.visible_quantity(), 5);
+        assert_eq!(level.hidden_quantity(), 15);
+        let quarantined = level.quarantined_orders();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(quarantined[0].order.id(), Id::from_u64(1));
+        assert_eq!(quarantined[0].reason, QuarantineReason::EmptyOrder);
+    }
+
+    #[test]
+    fn test_well_formed_hidden_depth_is_not_quarantined() {
+        let level = PriceLevel::new(10000);
+        // A zero-visible iceberg replenishes from its hidden depth, and an
+        // auto-replenishing reserve refreshes: neither is malformed.
+        level.add_order(create_iceberg_order(1, 0, 10)).unwrap();
+        level
+            .add_order(create_reserve_order(2, 0, 10, true))
+            .unwrap();
+
+        let result = match_taker(&level, 20);
+
+        assert!(result.executed_quantity().unwrap() > Quantity::new(0));
+        assert_eq!(level.quarantine_count(), 0);
+        assert!(level.quarantined_orders().is_empty());
+    }
+
+    #[test]
+    fn test_quarantine_reason_display_and_parse() {
+        for reason in [
+            QuarantineReason::EmptyOrder,
+            QuarantineReason::QuantityOverflow,
+            QuarantineReason::StrandedHidden,
+        ] {
+            assert_eq!(
+                reason.to_string().parse::<QuarantineReason>().unwrap(),
+                reason
+            );
+        }
+        assert_eq!(
+            "stranded_hidden".parse::<QuarantineReason>().unwrap(),
+            QuarantineReason::StrandedHidden
+        );
+        assert!("bogus".parse::<QuarantineReason>().is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_extra_fields {
+    use crate::UuidGenerator;
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use serde::{Deserialize, Serialize};
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+    struct Tag {
+        account: String,
+        session: u32,
+    }
+
+    fn tag(account: &str, session: u32) -> Tag {
+        Tag {
+            account: account.to_string(),
+            session,
+        }
+    }
+
+    fn create_tagged_order(id: u64, quantity: u64, extra_fields: Tag) -> OrderType<Tag> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields,
+        }
+    }
+
+    fn match_taker(level: &PriceLevel<Tag>, quantity: u64) -> MatchResult<Tag> {
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        level.match_order(
+            quantity,
+            Id::from_u64(999),
+            TimeInForce::Gtc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &generator,
+        )
+    }
+
+    #[test]
+    fn test_match_reports_maker_extra_fields_per_trade() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_tagged_order(1, 5, tag("alpha", 1)))
+            .unwrap();
+        level
+            .add_order(create_tagged_order(2, 10, tag("beta", 2)))
+            .unwrap();
+
+        let result = match_taker(&level, 8);
+
+        let trades = result.trades().as_slice();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].maker_order_id(), Id::from_u64(1));
+        assert_eq!(trades[0].maker_extra_fields(), &tag("alpha", 1));
+        assert_eq!(trades[1].maker_order_id(), Id::from_u64(2));
+        assert_eq!(trades[1].maker_extra_fields(), &tag("beta", 2));
+
+        // The partially filled maker keeps its extra fields in the queue.
+        let resting = level.snapshot_orders();
+        assert_eq!(resting.len(), 1);
+        assert_eq!(resting[0].extra_fields(), &tag("beta", 2));
+    }
+
+    #[test]
+    fn test_update_preserves_extra_fields() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_tagged_order(1, 10, tag("alpha", 7)))
+            .unwrap();
+
+        let outcome = level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(1),
+                new_quantity: Quantity::new(4),
+            })
+            .unwrap();
+
+        assert!(outcome.is_found());
+        let resting = level.snapshot_orders();
+        assert_eq!(resting[0].visible_quantity(), Quantity::new(4));
+        assert_eq!(resting[0].extra_fields(), &tag("alpha", 7));
+    }
+
+    #[test]
+    fn test_snapshot_json_round_trip_keeps_extra_fields() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_tagged_order(1, 10, tag("alpha", 1)))
+            .unwrap();
+        level
+            .add_order(create_tagged_order(2, 20, tag("beta", 2)))
+            .unwrap();
+
+        let json = level.snapshot_to_json().unwrap();
+        let restored: PriceLevel<Tag> = PriceLevel::from_snapshot_json(&json).unwrap();
+
+        let extras: Vec<Tag> = restored
+            .snapshot_orders()
+            .iter()
+            .map(|order| order.extra_fields().clone())
+            .collect();
+        assert_eq!(extras, vec![tag("alpha", 1), tag("beta", 2)]);
+    }
+
+    #[test]
+    fn test_match_result_json_round_trip_keeps_extra_fields() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_tagged_order(1, 5, tag("alpha", 1)))
+            .unwrap();
+        let result = match_taker(&level, 5);
+
+        let json = serde_json::to_string(&result).unwrap();
+        let restored: MatchResult<Tag> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.trades(), result.trades());
+        assert_eq!(
+            restored.trades().as_slice()[0].maker_extra_fields(),
+            &tag("alpha", 1)
+        );
+    }
+
+    #[test]
+    fn test_match_result_json_without_extra_fields_fills_defaults() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(create_tagged_order(1, 5, tag("alpha", 1)))
+            .unwrap();
+        let result = match_taker(&level, 5);
+
+        let mut value = serde_json::to_value(&result).unwrap();
+        value["trades"]["trades"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("maker_extra_fields");
+        let restored: MatchResult<Tag> = serde_json::from_value(value).unwrap();
+
+        assert_eq!(restored.trades().len(), 1);
+        assert_eq!(
+            restored.trades().as_slice()[0].maker_extra_fields(),
+            &Tag::default()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_expiry_warning {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce, UpdateOutcome};
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::sync::{Arc, Mutex};
+
+    fn create_order(id: u64, time_in_force: TimeInForce) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(10),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1_000),
+            time_in_force,
+            extra_fields: (),
+        }
+    }
+
+    /// Registers a warning that records each warned order id and expiry.
+    fn record_warnings(level: &PriceLevel, lead_time_ms: u64) -> Arc<Mutex<Vec<(Id, u64)>>> {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        level.set_expiry_warning(lead_time_ms, move |warning| {
+            sink.lock()
+                .unwrap()
+                .push((warning.order.id(), warning.expires_at.as_u64()));
+        });
+        seen
+    }
+
+    #[test]
+    fn test_warning_fires_once_inside_lead_time() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        level
+            .add_order(create_order(1, TimeInForce::Gtd(10_000)))
+            .unwrap();
+        level.add_order(create_order(2, TimeInForce::Gtc)).unwrap();
+        let seen = record_warnings(&level, 500);
+
+        assert_eq!(
+            level.poll_expiry_warnings(TimestampMs::new(9_000)).unwrap(),
+            0
+        );
+        assert_eq!(
+            level.poll_expiry_warnings(TimestampMs::new(9_500)).unwrap(),
+            1
+        );
+        assert_eq!(
+            level.poll_expiry_warnings(TimestampMs::new(9_900)).unwrap(),
+            0
+        );
+        // Past the expiry the order is no longer warned.
+        assert_eq!(
+            level
+                .poll_expiry_warnings(TimestampMs::new(10_000))
+                .unwrap(),
+            0
+        );
+
+        assert_eq!(*seen.lock().unwrap(), vec![(Id::from_u64(1), 10_000)]);
+    }
+
+    #[test]
+    fn test_extend_expiry_keeps_queue_position_and_rearms() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        level
+            .add_order(create_order(1, TimeInForce::Gtd(10_000)))
+            .unwrap();
+        level
+            .add_order(create_order(2, TimeInForce::Gtd(20_000)))
+            .unwrap();
+        let seen = record_warnings(&level, 500);
+
+        assert_eq!(
+            level.poll_expiry_warnings(TimestampMs::new(9_600)).unwrap(),
+            1
+        );
+        let outcome = level
+            .extend_expiry(Id::from_u64(1), TimestampMs::new(30_000))
+            .unwrap();
+        assert!(matches!(outcome, UpdateOutcome::UpdatedInPlace(ref order)
+            if order.time_in_force() == TimeInForce::Gtd(30_000)));
+
+        let ids: Vec<Id> = level.orders().map(|order| order.id()).collect();
+        assert_eq!(ids, vec![Id::from_u64(1), Id::from_u64(2)]);
+        assert_eq!(level.visible_quantity(), 20);
+
+        assert_eq!(
+            level
+                .poll_expiry_warnings(TimestampMs::new(29_700))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![(Id::from_u64(1), 10_000), (Id::from_u64(1), 30_000)]
+        );
+    }
+
+    #[test]
+    fn test_callback_can_extend_the_warned_order() {
+        let level: Arc<PriceLevel> = Arc::new(PriceLevel::new(10000));
+        level
+            .add_order(create_order(1, TimeInForce::Gtd(10_000)))
+            .unwrap();
+        let handle = Arc::downgrade(&level);
+        level.set_expiry_warning(1_000, move |warning| {
+            if let Some(level) = handle.upgrade() {
+                let extended = warning.expires_at.as_u64() + 60_000;
+                level
+                    .extend_expiry(warning.order.id(), TimestampMs::new(extended))
+                    .unwrap();
+            }
+        });
+
+        assert_eq!(
+            level.poll_expiry_warnings(TimestampMs::new(9_500)).unwrap(),
+            1
+        );
+        let order = level.orders().next().unwrap();
+        assert_eq!(order.time_in_force(), TimeInForce::Gtd(70_000));
+    }
+
+    #[test]
+    fn test_extend_expiry_rejects_non_gtd_and_earlier_expiry() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        level
+            .add_order(create_order(1, TimeInForce::Gtd(10_000)))
+            .unwrap();
+        level.add_order(create_order(2, TimeInForce::Gtc)).unwrap();
+
+        assert!(matches!(
+            level.extend_expiry(Id::from_u64(1), TimestampMs::new(10_000)),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert!(matches!(
+            level.extend_expiry(Id::from_u64(2), TimestampMs::new(50_000)),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert!(matches!(
+            level.extend_expiry(Id::from_u64(3), TimestampMs::new(50_000)),
+            Ok(UpdateOutcome::NotFound)
+        ));
+        assert_eq!(
+            level.orders().next().unwrap().time_in_force(),
+            TimeInForce::Gtd(10_000)
+        );
+    }
+
+    #[test]
+    fn test_gtt_orders_are_warned_extended_and_expired() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        level
+            .add_order(create_order(1, TimeInForce::Gtt(500)))
+            .unwrap();
+        level
+            .add_order(create_order(2, TimeInForce::Gtd(10_000)))
+            .unwrap();
+        level.add_order(create_order(3, TimeInForce::Day)).unwrap();
+        let seen = record_warnings(&level, 100);
+
+        assert_eq!(
+            level.poll_expiry_warnings(TimestampMs::new(1_450)).unwrap(),
+            1
+        );
+        assert_eq!(*seen.lock().unwrap(), vec![(Id::from_u64(1), 1_500)]);
+
+        let outcome = level
+            .extend_expiry(Id::from_u64(1), TimestampMs::new(2_000))
+            .unwrap();
+        assert!(matches!(outcome, UpdateOutcome::UpdatedInPlace(ref order)
+            if order.time_in_force() == TimeInForce::Gtt(1_000)));
+
+        assert!(
+            level
+                .expire_orders(TimestampMs::new(1_999), None)
+                .unwrap()
+                .is_empty()
+        );
+        let expired = level.expire_orders(TimestampMs::new(2_000), None).unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id(), Id::from_u64(1));
+        assert_eq!(level.order_count(), 2);
+        assert_eq!(level.visible_quantity(), 20);
+
+        // A `Day` order expires only at the given close.
+        let expired = level
+            .expire_orders(TimestampMs::new(10_000), Some(TimestampMs::new(10_000)))
+            .unwrap();
+        let ids: Vec<Id> = expired.iter().map(|order| order.id()).collect();
+        assert_eq!(ids, vec![Id::from_u64(2), Id::from_u64(3)]);
+        assert_eq!(level.order_count(), 0);
+        assert_eq!(level.visible_quantity(), 0);
+    }
+
+    #[test]
+    fn test_clear_expiry_warning_stops_polling() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        level
+            .add_order(create_order(1, TimeInForce::Gtd(10_000)))
+            .unwrap();
+        let seen = record_warnings(&level, 500);
+        assert_eq!(level.expiry_warning_lead_time(), Some(500));
+
+        level.clear_expiry_warning();
+
+        assert_eq!(level.expiry_warning_lead_time(), None);
+        assert_eq!(
+            level.poll_expiry_warnings(TimestampMs::new(9_800)).unwrap(),
+            0
+        );
+        assert!(seen.lock().unwrap().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests_execution_sequence {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use crate::{ExecutionIdGenerator, SequentialIdGenerator, UuidGenerator};
+    use uuid::Uuid;
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn match_with<G: ExecutionIdGenerator + ?Sized>(
+        level: &PriceLevel,
+        quantity: u64,
+        taker: u64,
+        generator: &G,
+    ) -> crate::execution::MatchResult {
+        level.match_order(
+            quantity,
+            Id::from_u64(taker),
+            TimeInForce::Gtc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            generator,
+        )
+    }
+
+    #[test]
+    fn test_sequential_generator_numbers_trades_across_levels() {
+        let first = PriceLevel::new(10000);
+        let second = PriceLevel::new(10000);
+        for id in 1..=3 {
+            first.add_order(create_standard_order(id, 5)).unwrap();
+            second.add_order(create_standard_order(id + 10, 5)).unwrap();
+        }
+        let generator = SequentialIdGenerator::new(9);
+
+        let a = match_with(&first, 15, 100, &generator);
+        let b = match_with(&second, 10, 101, &generator);
+
+        let trades: Vec<_> = a
+            .trades()
+            .as_slice()
+            .iter()
+            .chain(b.trades().as_slice())
+            .copied()
+            .collect();
+        let sequences: Vec<Option<u64>> = trades.iter().map(|t| t.execution_sequence()).collect();
+        assert_eq!(sequences, (1..=5).map(Some).collect::<Vec<_>>());
+        for trade in &trades {
+            assert_eq!(
+                trade.trade_id(),
+                generator.id_for(trade.execution_sequence().unwrap())
+            );
+        }
+        assert_eq!(generator.last_sequence(), 5);
+    }
+
+    #[test]
+    fn test_uuid_generator_leaves_trades_unsequenced() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_standard_order(1, 5)).unwrap();
+        let generator = UuidGenerator::new(Uuid::new_v4());
+
+        let result = match_with(&level, 5, 100, &generator);
+
+        assert_eq!(result.trades().as_slice()[0].execution_sequence(), None);
+    }
+
+    #[test]
+    fn test_match_accepts_a_dyn_generator() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_standard_order(1, 5)).unwrap();
+        let generator: Box<dyn ExecutionIdGenerator> = Box::new(SequentialIdGenerator::new(0));
+
+        let result = match_with(&level, 5, 100, generator.as_ref());
+
+        assert_eq!(result.trades().as_slice()[0].execution_sequence(), Some(1));
+    }
+}
+
+#[cfg(test)]
+mod tests_snapshot_under_matching {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::PriceLevelSnapshotPackage;
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use crate::{SequentialIdGenerator, UuidGenerator};
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+    use uuid::Uuid;
+
+    const PRICE: u128 = 10_000;
+
+    fn create_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(PRICE),
+            quantity: Quantity::new(quantity),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_continuous_snapshots_stay_restorable_under_active_matching() {
+        // One snapshot reader loops for as long as a single matcher, two
+        // adders and a canceller mutate the level. Every snapshot it takes
+        // must package, validate and restore, and the recorded mutation
+        // sequence must never go backwards.
+        const ADDER_THREADS: u64 = 2;
+        const OPS_PER_THREAD: u64 = 2_000;
+
+        let level = Arc::new(PriceLevel::new(PRICE));
+        for id in 0..200 {
+            level.add_order(create_order(id, 10)).unwrap();
+        }
+        let generator = Arc::new(SequentialIdGenerator::new(1));
+        let done = Arc::new(AtomicBool::new(false));
+        let barrier = Arc::new(Barrier::new(ADDER_THREADS as usize + 3));
+
+        let reader = {
+            let level = Arc::clone(&level);
+            let done = Arc::clone(&done);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                let mut last_sequence = 0;
+                let mut snapshots = 0usize;
+                while !done.load(Ordering::Acquire) || snapshots == 0 {
+                    let snapshot = level.snapshot();
+                    assert!(snapshot.mutation_sequence() >= last_sequence);
+                    last_sequence = snapshot.mutation_sequence();
+
+                    let ids: HashSet<Id> = snapshot.iter_orders().map(|o| o.id()).collect();
+                    assert_eq!(ids.len(), snapshot.order_count());
+
+                    let package = PriceLevelSnapshotPackage::new(snapshot).unwrap();
+                    assert!(package.validation_report().is_clean());
+                    let restored = PriceLevel::<()>::from_snapshot_package(package).unwrap();
+                    assert_eq!(restored.order_count(), ids.len());
+                    snapshots += 1;
+                }
+                snapshots
+            })
+        };
+
+        let mut writers = Vec::new();
+        for t in 0..ADDER_THREADS {
+            let level = Arc::clone(&level);
+            let barrier = Arc::clone(&barrier);
+            writers.push(thread::spawn(move || {
+                barrier.wait();
+                for i in 0..OPS_PER_THREAD {
+                    let id = 1_000_000 * (t + 1) + i;
+                    level.add_order(create_order(id, 1 + i % 5)).unwrap();
+                }
+            }));
+        }
+        {
+            let level = Arc::clone(&level);
+            let barrier = Arc::clone(&barrier);
+            writers.push(thread::spawn(move || {
+                barrier.wait();
+                for i in 0..OPS_PER_THREAD {
+                    let _ = level.update_order(OrderUpdate::Cancel {
+                        order_id: Id::from_u64(1_000_000 + i),
+                    });
+                }
+            }));
+        }
+        {
+            let level = Arc::clone(&level);
+            let generator = Arc::clone(&generator);
+            let barrier = Arc::clone(&barrier);
+            writers.push(thread::spawn(move || {
+                barrier.wait();
+                for i in 0..OPS_PER_THREAD {
+                    level.match_order(
+                        3,
+                        Id::from_u64(9_000_000 + i),
+                        TimeInForce::Gtc,
+                        TakerKind::Standard,
+                        TimestampMs::new(1_716_000_000_000),
+                        &generator,
+                    );
+                }
+            }));
+        }
+
+        for writer in writers {
+            writer.join().unwrap();
+        }
+        done.store(true, Ordering::Release);
+        assert!(reader.join().unwrap() > 0);
+
+        // Quiescent now: a final snapshot restores to the live level exactly.
+        let restored = PriceLevel::<()>::from_snapshot(level.snapshot()).unwrap();
+        assert_eq!(restored.order_count(), level.order_count());
+        assert_eq!(restored.visible_quantity(), level.visible_quantity());
+        assert_eq!(
+            restored.snapshot_by_insertion_seq(),
+            level.snapshot_by_insertion_seq()
+        );
+    }
+
+    #[test]
+    fn test_matching_progresses_while_snapshots_run() {
+        // A reader snapshotting in a tight loop must not starve the matcher:
+        // every match still fills against the resting depth.
+        let level = Arc::new(PriceLevel::new(PRICE));
+        for id in 0..500 {
+            level.add_order(create_order(id, 10)).unwrap();
+        }
+        let done = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let level = Arc::clone(&level);
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                while !done.load(Ordering::Acquire) {
+                    std::hint::black_box(level.snapshot());
+                }
+            })
+        };
+
+        let generator = UuidGenerator::new(Uuid::from_u128(7));
+        let mut filled = 0;
+        for i in 0..1_000 {
+            let result = level.match_order(
+                5,
+                Id::from_u64(9_000_000 + i),
+                TimeInForce::Gtc,
+                TakerKind::Standard,
+                TimestampMs::new(1_716_000_000_000),
+                &generator,
+            );
+            filled += result.executed_quantity().unwrap().as_u64();
+        }
+        done.store(true, Ordering::Release);
+        reader.join().unwrap();
+
+        assert_eq!(filled, 5_000);
+        assert_eq!(level.order_count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod tests_crossing_rule {
+    use crate::errors::PriceLevelError;
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::CrossingRule;
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::str::FromStr;
+
+    fn create_order(id: u64, side: Side, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn level_with(rule: CrossingRule, side: Side) -> PriceLevel {
+        let level = PriceLevel::with_crossing_rule(10000, rule);
+        level.add_order(create_order(1, side, 10)).unwrap();
+        level.add_order(create_order(2, side, 10)).unwrap();
+        level
+    }
+
+    fn take(level: &PriceLevel, quantity: u64) -> crate::MatchResult {
+        level.match_order(
+            quantity,
+            Id::from_u64(999),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    #[test]
+    fn test_default_rule_takes_the_opposite_of_the_maker() {
+        let level = level_with(CrossingRule::default(), Side::Sell);
+        assert_eq!(level.crossing_rule(), CrossingRule::OppositeOfMaker);
+
+        let result = take(&level, 5);
+        let trade = &result.trades().as_slice()[0];
+        assert_eq!(trade.taker_side(), Side::Buy);
+    }
+
+    #[test]
+    fn test_fixed_taker_side_sets_every_trade_side() {
+        let level = level_with(CrossingRule::TakerSells, Side::Buy);
+
+        let result = take(&level, 15);
+        assert_eq!(result.trades().len(), 2);
+        assert!(
+            result
+                .trades()
+                .as_slice()
+                .iter()
+                .all(|trade| trade.taker_side() == Side::Sell)
+        );
+    }
+
+    #[test]
+    fn test_same_side_crossing_is_rejected_untouched() {
+        let level = level_with(CrossingRule::TakerBuys, Side::Buy);
+        let sequence = level.mutation_sequence();
+
+        let result = take(&level, 5);
+        assert!(result.outcome().was_rejected());
+        assert!(result.trades().is_empty());
+        assert_eq!(result.remaining_quantity(), Quantity::new(5));
+        assert_eq!(level.visible_quantity(), 20);
+        assert_eq!(level.order_count(), 2);
+        assert_eq!(level.mutation_sequence(), sequence);
+    }
+
+    #[test]
+    fn test_try_match_order_reports_invalid_crossing() {
+        let level = level_with(CrossingRule::TakerSells, Side::Sell);
+
+        let err = level
+            .try_match_order(
+                5,
+                Id::from_u64(999),
+                TimeInForce::Ioc,
+                TakerKind::Standard,
+                TimestampMs::new(1),
+                &SequentialIdGenerator::new(0),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            PriceLevelError::InvalidCrossing {
+                maker_side: Side::Sell,
+                taker_side: Side::Sell,
+            }
+        ));
+        assert_eq!(level.visible_quantity(), 20);
+
+        level.set_crossing_rule(CrossingRule::taker(Side::Buy));
+        let result = level
+            .try_match_order(
+                5,
+                Id::from_u64(999),
+                TimeInForce::Ioc,
+                TakerKind::Standard,
+                TimestampMs::new(1),
+                &SequentialIdGenerator::new(0),
+            )
+            .unwrap();
+        assert_eq!(result.executed_quantity().unwrap(), Quantity::new(5));
+    }
+
+    #[test]
+    fn test_empty_level_is_not_an_invalid_crossing() {
+        let level: PriceLevel = PriceLevel::with_crossing_rule(10000, CrossingRule::TakerBuys);
+
+        let result = take(&level, 5);
+        assert!(!result.outcome().was_rejected());
+        assert!(result.trades().is_empty());
+    }
+
+    #[test]
+    fn test_rule_taker_side() {
+        assert_eq!(
+            CrossingRule::OppositeOfMaker.taker_side(Side::Buy).unwrap(),
+            Side::Sell
+        );
+        assert_eq!(
+            CrossingRule::TakerBuys.taker_side(Side::Sell).unwrap(),
+            Side::Buy
+        );
+        assert!(CrossingRule::TakerBuys.taker_side(Side::Buy).is_err());
+        assert_eq!(CrossingRule::OppositeOfMaker.fixed_taker_side(), None);
+        assert_eq!(CrossingRule::taker(Side::Sell), CrossingRule::TakerSells);
+    }
+
+    #[test]
+    fn test_crossing_rule_parse_and_display() {
+        for rule in [
+            CrossingRule::OppositeOfMaker,
+            CrossingRule::TakerBuys,
+            CrossingRule::TakerSells,
+        ] {
+            assert_eq!(CrossingRule::from_str(&rule.to_string()).unwrap(), rule);
+        }
+        assert_eq!(
+            CrossingRule::from_str("taker_sells").unwrap(),
+            CrossingRule::TakerSells
+        );
+        assert!(CrossingRule::from_str("sideways").is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_execution_channel {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::ExecutionOverflow;
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn create_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn level_with_orders(count: u64) -> PriceLevel {
+        let level = PriceLevel::new(10000);
+        for id in 1..=count {
+            level.add_order(create_order(id, 10)).unwrap();
+        }
+        level
+    }
+
+    fn take(level: &PriceLevel, taker_id: u64, quantity: u64) -> crate::MatchResult {
+        level.match_order(
+            quantity,
+            Id::from_u64(taker_id),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    #[test]
+    fn test_channel_receives_every_trade_in_execution_order() {
+        let level = level_with_orders(3);
+        let executions = level.execution_channel();
+
+        let result = take(&level, 100, 25);
+
+        let received: Vec<_> = executions.try_iter().collect();
+        assert_eq!(&received, result.trades().as_slice());
+        let makers: Vec<_> = received.iter().map(|t| t.maker_order_id()).collect();
+        assert_eq!(
+            makers,
+            vec![Id::from_u64(1), Id::from_u64(2), Id::from_u64(3)]
+        );
+    }
+
+    #[test]
+    fn test_every_channel_receives_each_trade() {
+        let level = level_with_orders(1);
+        let first = level.execution_channel();
+        let second = level.execution_channel_with(4, ExecutionOverflow::Backpressure);
+
+        take(&level, 100, 5);
+
+        assert_eq!(first.try_recv().unwrap(), second.try_recv().unwrap());
+    }
+
+    #[test]
+    fn test_unmatched_taker_sends_nothing() {
+        let level: PriceLevel = PriceLevel::new(10000);
+        let executions = level.execution_channel();
+
+        take(&level, 100, 5);
+
+        assert!(executions.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_drop_newest_counts_trades_that_do_not_fit() {
+        let level = level_with_orders(5);
+        let executions = level.execution_channel_with(2, ExecutionOverflow::DropNewest);
+
+        let result = take(&level, 100, 50);
+        assert_eq!(result.trades().len(), 5);
+
+        assert_eq!(executions.try_iter().count(), 2);
+        assert_eq!(level.dropped_executions(), 3);
+    }
+
+    #[test]
+    fn test_backpressure_delivers_every_trade() {
+        let level = Arc::new(level_with_orders(50));
+        let executions = level.execution_channel_with(1, ExecutionOverflow::Backpressure);
+
+        let matcher = {
+            let level = Arc::clone(&level);
+            thread::spawn(move || {
+                for taker in 0..50 {
+                    take(&level, 1_000 + taker, 10);
+                }
+            })
+        };
+        let received = executions.iter().take(50).count();
+        matcher.join().unwrap();
+
+        assert_eq!(received, 50);
+        assert_eq!(level.dropped_executions(), 0);
+    }
+
+    #[test]
+    fn test_dropped_receiver_does_not_block_matching() {
+        let level = level_with_orders(3);
+        let executions = level.execution_channel_with(1, ExecutionOverflow::Backpressure);
+        drop(executions);
+
+        let result = take(&level, 100, 25);
+
+        assert_eq!(result.trades().len(), 3);
+        assert_eq!(level.dropped_executions(), 0);
+    }
+
+    #[test]
+    fn test_execution_overflow_parse_and_display() {
+        for overflow in [
+            ExecutionOverflow::DropNewest,
+            ExecutionOverflow::Backpressure,
+        ] {
+            assert_eq!(
+                ExecutionOverflow::from_str(&overflow.to_string()).unwrap(),
+                overflow
+            );
+        }
+        assert_eq!(ExecutionOverflow::default(), ExecutionOverflow::DropNewest);
+        assert!(ExecutionOverflow::from_str("spill").is_err());
+    }
+}
+
+#[cfg(test)]
+mod tests_order_status {
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{
+        Hash32, Id, OrderStatus, OrderStatusTransition, OrderType, OrderUpdate, Side, TimeInForce,
+        UpdateOutcome,
+    };
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn create_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn take(level: &PriceLevel, taker_id: u64, quantity: u64) -> MatchResult {
+        level.match_order(
+            quantity,
+            Id::from_u64(taker_id),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    fn transition(id: u64, from: OrderStatus, to: OrderStatus) -> OrderStatusTransition {
+        OrderStatusTransition {
+            order_id: Id::from_u64(id),
+            from,
+            to,
+        }
+    }
+
+    #[test]
+    fn test_resting_order_is_new_until_filled() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_order(1, 10)).unwrap();
+        assert_eq!(level.order_status(Id::from_u64(1)), Some(OrderStatus::New));
+        assert_eq!(level.order_status(Id::from_u64(2)), None);
+    }
+
+    #[test]
+    fn test_partial_then_full_fill_reports_each_transition_once() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_order(1, 10)).unwrap();
+
+        let first = take(&level, 100, 4);
+        assert_eq!(
+            first.status_transitions(),
+            &[transition(
+                1,
+                OrderStatus::New,
+                OrderStatus::PartiallyFilled
+            )]
+        );
+        assert_eq!(
+            level.order_status(Id::from_u64(1)),
+            Some(OrderStatus::PartiallyFilled)
+        );
+
+        // A second partial fill is no status change.
+        let second = take(&level, 101, 3);
+        assert!(second.status_transitions().is_empty());
+
+        let last = take(&level, 102, 3);
+        assert_eq!(
+            last.status_transitions(),
+            &[transition(
+                1,
+                OrderStatus::PartiallyFilled,
+                OrderStatus::Filled
+            )]
+        );
+        assert_eq!(level.order_status(Id::from_u64(1)), None);
+    }
+
+    #[test]
+    fn test_sweep_reports_transitions_in_trade_order() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_order(1, 10)).unwrap();
+        level.add_order(create_order(2, 10)).unwrap();
+
+        let result = take(&level, 100, 15);
+        assert_eq!(
+            result.status_transitions(),
+            &[
+                transition(1, OrderStatus::New, OrderStatus::Filled),
+                transition(2, OrderStatus::New, OrderStatus::PartiallyFilled),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cancel_reports_the_status_it_rested_with() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_order(1, 10)).unwrap();
+        level.add_order(create_order(2, 10)).unwrap();
+        take(&level, 100, 4);
+
+        let partial = level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        assert!(matches!(
+            partial,
+            UpdateOutcome::Cancelled(_, OrderStatus::PartiallyFilled)
+        ));
+        assert_eq!(
+            partial.status_transition(),
+            Some(transition(
+                1,
+                OrderStatus::PartiallyFilled,
+                OrderStatus::Cancelled
+            ))
+        );
+
+        let untouched = level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+        assert_eq!(
+            untouched.status_transition(),
+            Some(transition(2, OrderStatus::New, OrderStatus::Cancelled))
+        );
+    }
+
+    #[test]
+    fn test_readmitted_id_starts_new() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_order(1, 10)).unwrap();
+        take(&level, 100, 4);
+        level
+            .update_order(OrderUpdate::UpdatePrice {
+                order_id: Id::from_u64(1),
+                new_price: Price::new(10100),
+            })
+            .unwrap();
+
+        level.add_order(create_order(1, 10)).unwrap();
+        assert_eq!(level.order_status(Id::from_u64(1)), Some(OrderStatus::New));
+    }
+
+    #[test]
+    fn test_status_transitions_round_trip_through_json() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_order(1, 10)).unwrap();
+        let result = take(&level, 100, 4);
+
+        let json = serde_json::to_string(&result).unwrap();
+        let decoded: MatchResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.status_transitions(), result.status_transitions());
+    }
+}
+
+#[cfg(test)]
+mod tests_order_fill_state {
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderFillState, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn standard(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn iceberg(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn take(level: &PriceLevel, taker_id: u64, quantity: u64) -> MatchResult {
+        level.match_order(
+            quantity,
+            Id::from_u64(taker_id),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    fn state(executed: u64, leaves: u64) -> OrderFillState {
+        OrderFillState {
+            cumulative_executed: Quantity::new(executed),
+            leaves_quantity: Quantity::new(leaves),
+        }
+    }
+
+    #[test]
+    fn test_fill_state_accumulates_across_matches() {
+        let level = PriceLevel::new(10000);
+        level.add_order(standard(1, 10)).unwrap();
+        assert_eq!(level.order_fill_state(Id::from_u64(1)), Some(state(0, 10)));
+
+        take(&level, 100, 3);
+        take(&level, 101, 4);
+        assert_eq!(level.order_fill_state(Id::from_u64(1)), Some(state(7, 3)));
+
+        take(&level, 102, 3);
+        assert_eq!(level.order_fill_state(Id::from_u64(1)), None);
+    }
+
+    #[test]
+    fn test_iceberg_leaves_include_hidden_depth() {
+        let level = PriceLevel::new(10000);
+        level.add_order(iceberg(1, 5, 15)).unwrap();
+
+        // Consumes the displayed tranche and replenishes from the reserve.
+        take(&level, 100, 5);
+        assert_eq!(level.order_fill_state(Id::from_u64(1)), Some(state(5, 15)));
+    }
+
+    #[test]
+    fn test_resize_changes_leaves_but_keeps_executed() {
+        let level = PriceLevel::new(10000);
+        level.add_order(standard(1, 10)).unwrap();
+        take(&level, 100, 4);
+
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(1),
+                new_quantity: Quantity::new(2),
+            })
+            .unwrap();
+        assert_eq!(level.order_fill_state(Id::from_u64(1)), Some(state(4, 2)));
+    }
+
+    #[test]
+    fn test_cancel_drops_fill_state() {
+        let level = PriceLevel::new(10000);
+        level.add_order(standard(1, 10)).unwrap();
+        take(&level, 100, 4);
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        assert_eq!(level.order_fill_state(Id::from_u64(1)), None);
+
+        level.add_order(standard(1, 10)).unwrap();
+        assert_eq!(level.order_fill_state(Id::from_u64(1)), Some(state(0, 10)));
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests_check_invariants {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn create_iceberg_order(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_check_invariants_holds_through_fills_and_updates() {
+        let level = PriceLevel::new(10000);
+        assert!(level.check_invariants().is_ok());
+
+        level.add_order(create_iceberg_order(1, 10, 30)).unwrap();
+        level.add_order(create_iceberg_order(2, 5, 0)).unwrap();
+        level.match_order(
+            25,
+            Id::from_u64(100),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        );
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(1),
+                new_quantity: Quantity::new(4),
+            })
+            .unwrap();
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+
+        assert!(level.check_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_check_invariants_reports_a_skewed_counter() {
+        let level = PriceLevel::new(10000);
+        level.add_order(create_iceberg_order(1, 10, 30)).unwrap();
+        level.test_skew_visible_counter(1);
+
+        let err = level.check_invariants().unwrap_err();
+        assert!(err.to_string().contains("visible counter 11"), "{err}");
+    }
+}
+
+#[cfg(test)]
+mod tests_snapshot_consistent {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{PriceLevel, PriceLevelSnapshot};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn create_order(id: u64, quantity: u64, time_in_force: TimeInForce) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force,
+            extra_fields: (),
+        }
+    }
+
+    fn take(level: &PriceLevel, taker: u64, quantity: u64, tif: TimeInForce) -> u64 {
+        level
+            .match_order(
+                quantity,
+                Id::from_u64(taker),
+                tif,
+                TakerKind::Standard,
+                TimestampMs::new(1616823001000),
+                &SequentialIdGenerator::new(0),
+            )
+            .remaining_quantity()
+            .as_u64()
+    }
+
+    #[test]
+    fn test_snapshot_if_changed_skips_quiet_levels() {
+        let level = PriceLevel::new(10000);
+        let (seen, first) = level.snapshot_if_changed(u64::MAX).unwrap();
+        assert_eq!(seen, 0);
+        assert_eq!(first.order_count(), 0);
+        assert!(level.snapshot_if_changed(seen).is_none());
+
+        level
+            .add_order(create_order(1, 10, TimeInForce::Gtc))
+            .unwrap();
+        assert_eq!(take(&level, 100, 4, TimeInForce::Ioc), 0);
+        let (seen, snapshot) = level.snapshot_if_changed(seen).unwrap();
+        assert_eq!(seen, level.mutation_sequence());
+        assert_eq!(snapshot.visible_quantity(), Quantity::new(6));
+        assert!(level.snapshot_if_changed(seen).is_none());
+    }
+
+    #[test]
+    fn test_quiet_level_shares_one_view() {
+        let level = PriceLevel::new(10000);
+        for id in 1..=3 {
+            level
+                .add_order(create_order(id, 10, TimeInForce::Gtc))
+                .unwrap();
+        }
+
+        let first = level.snapshot_consistent();
+        let again = level.snapshot_consistent();
+        assert!(Arc::ptr_eq(&first, &again));
+        assert_eq!(first.mutation_sequence(), level.mutation_sequence());
+        assert_eq!(first.orders(), level.snapshot().orders());
+        assert_eq!(first.visible_quantity(), Quantity::new(30));
+
+        assert_eq!(take(&level, 100, 15, TimeInForce::Ioc), 0);
+        let after = level.snapshot_consistent();
+        assert!(!Arc::ptr_eq(&first, &after));
+        assert_eq!(after.mutation_sequence(), level.mutation_sequence());
+        assert_eq!(after.visible_quantity(), Quantity::new(15));
+        assert_eq!(after.order_count(), 2);
+        // The earlier view is untouched by the fill.
+        assert_eq!(first.order_count(), 3);
+    }
+
+    #[test]
+    fn test_in_place_changes_advance_the_sequence() {
+        let level = PriceLevel::new(10000);
+        level
+            .add_order(OrderType::TrailingStop {
+                id: Id::from_u64(1),
+                price: Price::new(10000),
+                quantity: Quantity::new(5),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1616823000000),
+                time_in_force: TimeInForce::Gtc,
+                trail_amount: Quantity::new(100),
+                last_reference_price: Price::new(10100),
+                extra_fields: (),
+            })
+            .unwrap();
+        level
+            .add_order(create_order(2, 5, TimeInForce::Gtd(1616900000000)))
+            .unwrap();
+        for id in [3, 4] {
+            level
+                .add_order(OrderType::StopLimit {
+                    id: Id::from_u64(id),
+                    price: Price::new(10000),
+                    quantity: Quantity::new(5),
+                    side: Side::Sell,
+                    user_id: Hash32::zero(),
+                    timestamp: TimestampMs::new(1616823000000),
+                    time_in_force: TimeInForce::Gtc,
+                    trigger_price: Price::new(9900),
+                    extra_fields: (),
+                })
+                .unwrap();
+        }
+        let before = level.snapshot_consistent();
+        assert_eq!(before.mutation_sequence(), 4);
+
+        level.update_trailing_stops(Price::new(10200)).unwrap();
+        assert_eq!(level.mutation_sequence(), 5);
+        level
+            .extend_expiry(Id::from_u64(2), TimestampMs::new(1617000000000))
+            .unwrap();
+        assert_eq!(level.mutation_sequence(), 6);
+        // One step per activation, not one per batch.
+        assert_eq!(level.trigger_orders(Price::new(9900)).unwrap().len(), 2);
+        assert_eq!(level.mutation_sequence(), 8);
+
+        let after = level.snapshot_consistent();
+        assert_eq!(after.mutation_sequence(), 8);
+        assert_ne!(before.orders(), after.orders());
+    }
+
+    #[test]
+    fn test_concurrent_views_are_never_torn() {
+        // One-lot makers with ascending ids (recovered from their
+        // timestamps) are consumed front to back, so
+        // every real state of the queue holds a contiguous range of ids. A
+        // walk that caught one maker before its fill and a later one after
+        // its fill would show a gap.
+        let level = Arc::new(PriceLevel::new(10000));
+        for id in 0..64 {
+            level
+                .add_order(create_order(id, 1, TimeInForce::Gtc))
+                .unwrap();
+        }
+        let done = Arc::new(AtomicBool::new(false));
+
+        let writer = {
+            let level = Arc::clone(&level);
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                let mut next = 64;
+                for round in 0..2_000_u64 {
+                    // Alternate lock-free sweeps with fill-or-kill ones.
+                    let tif = if round % 2 == 0 {
+                        TimeInForce::Ioc
+                    } else {
+                        TimeInForce::Fok
+                    };
+                    assert_eq!(take(&level, 1_000_000 + round, 3, tif), 0);
+                    for _ in 0..3 {
+                        level
+                            .add_order(create_order(next, 1, TimeInForce::Gtc))
+                            .unwrap();
+                        next += 1;
+                    }
+                }
+                done.store(true, Ordering::Release);
+            })
+        };
+
+        let mut views = 0;
+        while !done.load(Ordering::Acquire) {
+            let view = level.snapshot_consistent();
+            assert!(view.mutation_sequence() <= level.mutation_sequence());
+            let ids: BTreeSet<u64> = view
+                .orders()
+                .iter()
+                .map(|order| order.timestamp().as_u64() - 1616823000000)
+                .collect();
+            assert_eq!(ids.len(), view.order_count());
+            if let (Some(first), Some(last)) = (ids.first(), ids.last()) {
+                assert_eq!(
+                    last - first + 1,
+                    ids.len() as u64,
+                    "torn view at sequence {}: {ids:?}",
+                    view.mutation_sequence()
+                );
+            }
+            assert_eq!(view.visible_quantity().as_u64(), ids.len() as u64);
+            views += 1;
+        }
+        writer.join().unwrap();
+        assert!(views > 0);
+        assert_eq!(
+            level.snapshot_consistent().mutation_sequence(),
+            level.mutation_sequence()
+        );
+    }
+
+    #[test]
+    fn test_view_never_shows_a_fill_its_sequence_does_not_cover() {
+        let level = Arc::new(PriceLevel::new(10000));
+        level
+            .add_order(create_order(1, 1, TimeInForce::Gtc))
+            .unwrap();
+        let published = level.snapshot_consistent();
+        // Move past the published view, so a read must walk the queue.
+        level
+            .add_order(create_order(2, 1, TimeInForce::Gtc))
+            .unwrap();
+        let unfilled = level.mutation_sequence();
+
+        // Read the level with maker 1 already gone from the queue but the
+        // sequence not yet advanced past its fill.
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let _hook = {
+            let level = Arc::clone(&level);
+            let seen = Arc::clone(&seen);
+            crate::price_level::level::set_fill_commit_hook(Box::new(move || {
+                seen.lock().unwrap().push(level.snapshot_consistent());
+            }))
+        };
+        assert_eq!(take(&level, 100, 1, TimeInForce::Ioc), 0);
+
+        // The walk overlaps the fill and is discarded; the read settles for
+        // the last published view, which predates the fill.
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert!(seen[0].mutation_sequence() <= unfilled);
+        assert!(Arc::ptr_eq(&seen[0], &published));
+        assert!(
+            seen[0]
+                .orders()
+                .iter()
+                .any(|order| order.id() == Id::from_u64(1))
+        );
+        assert_eq!(level.snapshot_consistent().order_count(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_views_match_trade_sequences() {
+        // Every view must hold exactly the makers admitted at or before its
+        // sequence and not yet filled by then, as the stamped trades and the
+        // admissions' sequences record. A fill the queue has committed but the
+        // sequence does not cover yet would show as a maker missing early.
+        let level = Arc::new(PriceLevel::new(10000));
+        level.set_trade_stamping(true);
+        let mut admitted = Vec::new();
+        for id in 0..64 {
+            let order = level
+                .add_order_sequenced(create_order(id, 1, TimeInForce::Gtc))
+                .unwrap();
+            admitted.push((id, order.sequence()));
+        }
+        let done = Arc::new(AtomicBool::new(false));
+
+        let matchers: Vec<_> = (0..2_u64)
+            .map(|thread| {
+                let level = Arc::clone(&level);
+                std::thread::spawn(move || {
+                    let mut fills = Vec::new();
+                    for round in 0..600_u64 {
+                        let result = level.match_order(
+                            2,
+                            Id::from_u64(1_000_000 + thread * 10_000 + round),
+                            TimeInForce::Ioc,
+                            TakerKind::Standard,
+                            TimestampMs::new(1616823001000),
+                            &SequentialIdGenerator::new(0),
+                        );
+                        for trade in result.trades().as_slice() {
+                            let sequence = trade.level_sequence().expect("stamped trade");
+                            fills.push((trade.maker_order_id(), sequence));
+                        }
+                    }
+                    fills
+                })
+            })
+            .collect();
+        let adder = {
+            let level = Arc::clone(&level);
+            std::thread::spawn(move || {
+                let mut admitted = Vec::new();
+                for id in 64..1_264 {
+                    let order = level
+                        .add_order_sequenced(create_order(id, 1, TimeInForce::Gtc))
+                        .unwrap();
+                    admitted.push((id, order.sequence()));
+                }
+                admitted
+            })
+        };
+        let stopper = {
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                let fills: Vec<_> = matchers
+                    .into_iter()
+                    .flat_map(|matcher| matcher.join().unwrap())
+                    .collect();
+                let admitted = adder.join().unwrap();
+                done.store(true, Ordering::Release);
+                (fills, admitted)
+            })
+        };
+
+        let mut views: Vec<Arc<PriceLevelSnapshot>> = Vec::new();
+        while !done.load(Ordering::Acquire) {
+            let view = level.snapshot_consistent();
+            if views.last().is_none_or(|last| !Arc::ptr_eq(last, &view)) {
+                views.push(view);
+            }
+        }
+        let (fills, later) = stopper.join().unwrap();
+        admitted.extend(later);
+        let filled_at: HashMap<Id, u64> = fills.into_iter().collect();
+
+        assert!(!views.is_empty());
+        for view in &views {
+            let sequence = view.mutation_sequence();
+            let expected: HashSet<Id> = admitted
+                .iter()
+                .filter(|(_, admitted_at)| *admitted_at <= sequence)
+                .map(|(id, _)| Id::from_u64(*id))
+                .filter(|id| filled_at.get(id).is_none_or(|filled| *filled > sequence))
+                .collect();
+            let held: HashSet<Id> = view.orders().iter().map(|order| order.id()).collect();
+            assert_eq!(held, expected, "view at sequence {sequence}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_queue_shards {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{CrossingRule, MatchingPolicy, PriceLevel, PriceLevelConfig};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn create_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn take(level: &PriceLevel, taker: u64, quantity: u64) -> Vec<Id> {
+        level
+            .match_order(
+                quantity,
+                Id::from_u64(taker),
+                TimeInForce::Ioc,
+                TakerKind::Standard,
+                TimestampMs::new(1616823001000),
+                &SequentialIdGenerator::new(0),
+            )
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|trade| trade.maker_order_id())
+            .collect()
+    }
+
+    #[test]
+    fn test_with_config_applies_every_setting() {
+        let config = PriceLevelConfig::new()
+            .with_matching_policy(MatchingPolicy::ProRata)
+            .with_crossing_rule(CrossingRule::TakerBuys)
+            .with_queue_shards(8);
+        let level: PriceLevel = PriceLevel::with_config(10000, config);
+        assert_eq!(level.matching_policy(), MatchingPolicy::ProRata);
+        assert_eq!(level.crossing_rule(), CrossingRule::TakerBuys);
+        assert_eq!(level.queue_shards(), 8);
+
+        let plain: PriceLevel = PriceLevel::with_config(10000, PriceLevelConfig::default());
+        assert_eq!(plain.queue_shards(), 1);
+        assert_eq!(plain.matching_policy(), MatchingPolicy::Fifo);
+    }
+
+    #[test]
+    #[should_panic(expected = "queue shards must be between 1 and")]
+    fn test_with_queue_shards_rejects_zero() {
+        let _ = PriceLevelConfig::new().with_queue_shards(0);
+    }
+
+    #[test]
+    fn test_sharded_level_fills_every_maker_once() {
+        let level = PriceLevel::with_config(10000, PriceLevelConfig::new().with_queue_shards(4));
+        for id in 1..=40 {
+            level.add_order(create_order(id, 10)).unwrap();
+        }
+
+        let mut makers = take(&level, 1000, 155);
+        assert_eq!(level.visible_quantity(), 245);
+        makers.extend(take(&level, 1001, 245));
+        assert_eq!(level.order_count(), 0);
+
+        let unique: std::collections::HashSet<Id> = makers.iter().copied().collect();
+        // 41 trades: the first take left one maker partially filled.
+        assert_eq!(makers.len(), 41);
+        assert_eq!(unique.len(), 40);
+        let snapshot = level.snapshot();
+        assert!(snapshot.orders().is_empty());
+    }
+
+    #[test]
+    fn test_sharded_strict_fifo_keeps_arrival_order() {
+        let config = PriceLevelConfig::new()
+            .with_matching_policy(MatchingPolicy::StrictFifo)
+            .with_queue_shards(4);
+        let level = PriceLevel::with_config(10000, config);
+        for id in 1..=20 {
+            level.add_order(create_order(id, 10)).unwrap();
+        }
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(4),
+            })
+            .unwrap();
+
+        let makers = take(&level, 1000, 190);
+        let expected: Vec<Id> = (1..=20).filter(|&id| id != 4).map(Id::from_u64).collect();
+        assert_eq!(makers, expected);
+        assert_eq!(level.fifo_violations(), 0);
+    }
+
+    #[test]
+    fn test_sharded_level_concurrent_churn_conserves_quantity() {
+        const ADDERS: u64 = 4;
+        const PER_ADDER: u64 = 500;
+
+        let level = Arc::new(PriceLevel::with_config(
+            10000,
+            PriceLevelConfig::new().with_queue_shards(8),
+        ));
+        let done = Arc::new(AtomicBool::new(false));
+
+        let adders: Vec<_> = (0..ADDERS)
+            .map(|adder| {
+                let level = Arc::clone(&level);
+                std::thread::spawn(move || {
+                    let mut cancelled = 0;
+                    for i in 0..PER_ADDER {
+                        let id = adder * PER_ADDER + i + 1;
+                        level.add_order(create_order(id, 2)).unwrap();
+                        if i % 5 == 0
+                            && level
+                                .update_order(OrderUpdate::Cancel {
+                                    order_id: Id::from_u64(id),
+                                })
+                                .unwrap()
+                                .is_found()
+                        {
+                            cancelled += 2;
+                        }
+                    }
+                    cancelled
+                })
+            })
+            .collect();
+
+        let matcher = {
+            let level = Arc::clone(&level);
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                let mut filled = 0;
+                let mut taker = 1_000_000;
+                while !done.load(Ordering::Relaxed) {
+                    taker += 1;
+                    filled += 3 - level
+                        .match_order(
+                            3,
+                            Id::from_u64(taker),
+                            TimeInForce::Ioc,
+                            TakerKind::Standard,
+                            TimestampMs::new(1616823001000),
+                            &SequentialIdGenerator::new(0),
+                        )
+                        .remaining_quantity()
+                        .as_u64();
+                }
+                filled
+            })
+        };
+
+        let cancelled: u64 = adders.into_iter().map(|h| h.join().unwrap()).sum();
+        done.store(true, Ordering::Relaxed);
+        let filled = matcher.join().unwrap();
+
+        let resting: u64 = level
+            .snapshot()
+            .orders()
+            .iter()
+            .map(|o| o.visible_quantity().as_u64())
+            .sum();
+        assert_eq!(filled + cancelled + resting, ADDERS * PER_ADDER * 2);
+        assert_eq!(resting, level.visible_quantity());
+    }
+}
+
+#[cfg(test)]
+mod tests_replenishment_strategy {
+    use crate::execution::TakerKind;
+    use crate::orders::{
+        Hash32, Id, OrderType, ReplenishTrigger, Replenishment, ReplenishmentContext,
+        ReplenishmentStrategy, Side, TimeInForce,
+    };
+    use crate::price_level::{PriceLevel, PriceLevelConfig};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+
+    /// Three-lot tranches, restamped with the time of the refreshing match.
+    #[derive(Debug)]
+    struct SmallTranches;
+
+    impl ReplenishmentStrategy for SmallTranches {
+        fn replenish(&self, context: &ReplenishmentContext) -> Replenishment {
+            Replenishment {
+                quantity: 3,
+                timestamp: context.match_timestamp.unwrap_or(context.order_timestamp),
+            }
+        }
+    }
+
+    fn level() -> PriceLevel {
+        let level = PriceLevel::with_config(
+            10000,
+            PriceLevelConfig::new().with_replenishment_strategy(Arc::new(SmallTranches)),
+        );
+        level
+            .add_order(OrderType::IcebergOrder {
+                id: Id::from_u64(1),
+                price: Price::new(10000),
+                visible_quantity: Quantity::new(5),
+                hidden_quantity: Quantity::new(10),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1616823000000),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+        level
+    }
+
+    fn take(level: &PriceLevel, quantity: u64, tif: TimeInForce, at: u64) -> u64 {
+        level
+            .match_order(
+                quantity,
+                Id::from_u64(100),
+                tif,
+                TakerKind::Standard,
+                TimestampMs::new(at),
+                &SequentialIdGenerator::new(0),
+            )
+            .remaining_quantity()
+            .as_u64()
+    }
+
+    #[test]
+    fn test_level_refreshes_with_its_strategy() {
+        let level = level();
+        assert_eq!(take(&level, 5, TimeInForce::Ioc, 1616823009000), 0);
+        assert_eq!(level.visible_quantity(), 3);
+        assert_eq!(level.hidden_quantity(), 7);
+        let resting = level.snapshot().orders()[0].clone();
+        assert_eq!(resting.timestamp(), TimestampMs::new(1616823009000));
+    }
+
+    #[test]
+    fn test_fill_or_kill_stays_exact_under_a_strategy() {
+        let level = level();
+        assert_eq!(level.matchable_quantity(20, Id::from_u64(100)), 15);
+        assert_eq!(take(&level, 16, TimeInForce::Fok, 1616823009000), 16);
+        assert_eq!(level.visible_quantity(), 5);
+
+        assert_eq!(take(&level, 15, TimeInForce::Fok, 1616823009000), 0);
+        assert_eq!(level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_default_config_keeps_the_built_in_rule() {
+        let level = PriceLevel::<()>::new(10000);
+        let context = ReplenishmentContext {
+            order_id: Id::from_u64(1),
+            trigger: ReplenishTrigger::ReserveDepleted,
+            visible_remaining: 0,
+            hidden_quantity: 40,
+            default_quantity: 20,
+            order_timestamp: TimestampMs::new(7),
+            match_timestamp: Some(TimestampMs::new(9)),
+        };
+        assert_eq!(
+            level.replenishment_strategy().replenish(&context),
+            Replenishment {
+                quantity: 20,
+                timestamp: TimestampMs::new(7),
+            }
+        );
+    }
+
+    #[test]
+    fn test_randomized_tranches_stay_in_the_band() {
+        use crate::orders::RandomizedReplenish;
+
+        let level = PriceLevel::with_config(
+            10000,
+            PriceLevelConfig::new()
+                .with_replenishment_strategy(Arc::new(RandomizedReplenish::with_seed(2, 4, 11))),
+        );
+        level
+            .add_order(OrderType::IcebergOrder {
+                id: Id::from_u64(1),
+                price: Price::new(10000),
+                visible_quantity: Quantity::new(3),
+                hidden_quantity: Quantity::new(60),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1616823000000),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+
+        // Take one displayed tranche at a time until the order is gone.
+        let mut filled = 0;
+        while level.order_count() > 0 {
+            let displayed = level.visible_quantity();
+            assert!(
+                (2..=4).contains(&displayed) || level.hidden_quantity() == 0,
+                "tranche of {displayed} outside the band"
+            );
+            assert_eq!(take(&level, displayed, TimeInForce::Ioc, 1616823009000), 0);
+            filled += displayed;
+        }
+        assert_eq!(filled, 63);
+    }
+}
+
+#[cfg(test)]
+mod tests_refresh_priority {
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{
+        Hash32, Id, OrderRefresh, OrderType, RefreshPriority, Replenishment, ReplenishmentContext,
+        ReplenishmentStrategy, Side, TimeInForce,
+    };
+    use crate::price_level::{PriceLevel, PriceLevelConfig};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+
+    /// Default tranches, restamped with the time of the refreshing match.
+    #[derive(Debug)]
+    struct Restamp;
+
+    impl ReplenishmentStrategy for Restamp {
+        fn replenish(&self, context: &ReplenishmentContext) -> Replenishment {
+            Replenishment {
+                quantity: context.default_quantity,
+                timestamp: context.match_timestamp.unwrap_or(context.order_timestamp),
+            }
+        }
+    }
+
+    /// An iceberg (id 1) showing 5 of 15, with a 5-lot standard order (id 2)
+    /// behind it.
+    fn level(priority: RefreshPriority) -> PriceLevel {
+        let level = PriceLevel::with_config(
+            10000,
+            PriceLevelConfig::new()
+                .with_replenishment_strategy(Arc::new(Restamp))
+                .with_refresh_priority(priority),
+        );
+        level
+            .add_order(OrderType::IcebergOrder {
+                id: Id::from_u64(1),
+                price: Price::new(10000),
+                visible_quantity: Quantity::new(5),
+                hidden_quantity: Quantity::new(10),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1616823000000),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+        level
+            .add_order(OrderType::Standard {
+                id: Id::from_u64(2),
+                price: Price::new(10000),
+                quantity: Quantity::new(5),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1616823001000),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+        level
+    }
+
+    fn take(level: &PriceLevel, quantity: u64, tif: TimeInForce) -> MatchResult {
+        level.match_order(
+            quantity,
+            Id::from_u64(100),
+            tif,
+            TakerKind::Standard,
+            TimestampMs::new(1616823009000),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    #[test]
+    fn test_requeue_moves_the_refreshed_order_to_the_back() {
+        let level = level(RefreshPriority::Requeue);
+        assert_eq!(level.refresh_priority(), RefreshPriority::Requeue);
+
+        let first = take(&level, 5, TimeInForce::Ioc);
+        assert_eq!(
+            first.refreshes(),
+            &[OrderRefresh {
+                order_id: Id::from_u64(1),
+                quantity: 5,
+                priority: RefreshPriority::Requeue,
+            }]
+        );
+        let resting = level.snapshot().orders()[1].clone();
+        assert_eq!(resting.id(), Id::from_u64(1));
+        assert_eq!(resting.timestamp(), TimestampMs::new(1616823009000));
+
+        let second = take(&level, 5, TimeInForce::Ioc);
+        assert_eq!(
+            second.trades().as_slice()[0].maker_order_id(),
+            Id::from_u64(2)
+        );
+        assert!(second.refreshes().is_empty());
+    }
+
+    #[test]
+    fn test_retain_keeps_the_slot_and_timestamp() {
+        let level = level(RefreshPriority::Retain);
+
+        let first = take(&level, 5, TimeInForce::Ioc);
+        assert_eq!(
+            first.refreshes(),
+            &[OrderRefresh {
+                order_id: Id::from_u64(1),
+                quantity: 5,
+                priority: RefreshPriority::Retain,
+            }]
+        );
+        let resting = level.snapshot().orders()[0].clone();
+        assert_eq!(resting.id(), Id::from_u64(1));
+        assert_eq!(resting.timestamp(), TimestampMs::new(1616823000000));
+        assert_eq!(level.visible_quantity(), 10);
+        assert_eq!(level.hidden_quantity(), 5);
+
+        let second = take(&level, 5, TimeInForce::Ioc);
+        assert_eq!(
+            second.trades().as_slice()[0].maker_order_id(),
+            Id::from_u64(1)
+        );
+    }
+
+    #[test]
+    fn test_retain_sweeps_the_whole_iceberg_first() {
+        let level = level(RefreshPriority::Retain);
+
+        let result = take(&level, 16, TimeInForce::Ioc);
+        let makers: Vec<Id> = result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|trade| trade.maker_order_id())
+            .collect();
+        assert_eq!(
+            makers,
+            vec![
+                Id::from_u64(1),
+                Id::from_u64(1),
+                Id::from_u64(1),
+                Id::from_u64(2)
+            ]
+        );
+        assert_eq!(result.refreshes().len(), 2);
+        assert_eq!(level.visible_quantity(), 4);
+    }
+
+    #[test]
+    fn test_fill_or_kill_stays_exact_when_retaining() {
+        let level = level(RefreshPriority::Retain);
+        assert_eq!(level.matchable_quantity(30, Id::from_u64(100)), 20);
+
+        let killed = take(&level, 21, TimeInForce::Fok);
+        assert!(killed.was_killed());
+        assert!(killed.refreshes().is_empty());
+
+        let filled = take(&level, 20, TimeInForce::Fok);
+        assert!(filled.is_complete());
+        assert_eq!(level.order_count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod tests_clock {
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{PriceLevel, PriceLevelConfig};
+    use crate::utils::{ManualClock, Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const PLACED_AT: u64 = 1616823000000;
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(PLACED_AT),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn take(level: &PriceLevel, quantity: u64, taker_timestamp: u64) -> MatchResult {
+        level.match_order(
+            quantity,
+            Id::from_u64(999),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(taker_timestamp),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    fn level_with(clock: &Arc<ManualClock>) -> PriceLevel {
+        PriceLevel::with_config(10000, PriceLevelConfig::new().with_clock(clock.clone()))
+    }
+
+    #[test]
+    fn test_statistics_read_the_configured_clock() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(PLACED_AT)));
+        let level = level_with(&clock);
+        assert_eq!(level.stats().first_arrival_time(), PLACED_AT);
+        level.add_order(create_standard_order(1, 5)).unwrap();
+
+        let _ = take(&level, 2, PLACED_AT + 100);
+        clock.set(TimestampMs::new(PLACED_AT + 400));
+        assert_eq!(level.stats().time_since_last_execution(), Some(300));
+    }
+
+    #[test]
+    fn test_trades_unstamped_by_default() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(PLACED_AT)));
+        let level = level_with(&clock);
+        assert!(!level.clock_stamping());
+        level.add_order(create_standard_order(1, 5)).unwrap();
+
+        let result = take(&level, 2, PLACED_AT);
+        assert_eq!(result.trades().as_slice()[0].clock_timestamp(), None);
+    }
+
+    #[test]
+    fn test_clock_stamp_measures_match_latency() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(PLACED_AT)));
+        let level = level_with(&clock);
+        level.set_clock_stamping(true);
+        level.add_order(create_standard_order(1, 5)).unwrap();
+        level.add_order(create_standard_order(2, 5)).unwrap();
+
+        clock.advance(Duration::from_millis(250));
+        let result = take(&level, 8, PLACED_AT + 200);
+        for trade in result.trades().as_slice() {
+            let stamp = trade.clock_timestamp().unwrap();
+            assert_eq!(stamp, TimestampMs::new(PLACED_AT + 250));
+            assert_eq!(
+                stamp.checked_duration_since(trade.timestamp()),
+                Some(Duration::from_millis(50))
+            );
+        }
+    }
+
+    #[test]
+    fn test_restored_level_reads_the_system_clock() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(1_000)));
+        let level = level_with(&clock);
+        level.set_clock_stamping(true);
+
+        let json = level.snapshot_to_json().unwrap();
+        let restored: PriceLevel = PriceLevel::from_snapshot_json(&json).unwrap();
+        assert!(!restored.clock_stamping());
+        assert!(restored.clock().now() > TimestampMs::new(1_000));
+    }
+}
+
+#[cfg(test)]
+mod tests_merge_split {
+    use crate::errors::PriceLevelError;
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn iceberg_order(
+        id: u64,
+        price: u128,
+        visible: u64,
+        hidden: u64,
+        timestamp: u64,
+    ) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn timed_order(
+        id: u64,
+        price: u128,
+        quantity: u64,
+        side: Side,
+        timestamp: u64,
+    ) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn queue_ids(level: &PriceLevel) -> Vec<Id> {
+        level
+            .snapshot_by_insertion_seq()
+            .iter()
+            .map(|order| order.id())
+            .collect()
+    }
+
+    fn ids<const N: usize>(ids: [u64; N]) -> Vec<Id> {
+        ids.into_iter().map(Id::from_u64).collect()
+    }
+
+    #[test]
+    fn test_merge_from_interleaves_by_timestamp_and_reprices() {
+        let target = PriceLevel::new(10_000);
+        let source = PriceLevel::new(10_001);
+        target
+            .add_order(timed_order(1, 10_000, 10, Side::Buy, 100))
+            .unwrap();
+        target
+            .add_order(timed_order(2, 10_000, 20, Side::Buy, 300))
+            .unwrap();
+        source
+            .add_order(timed_order(3, 10_001, 5, Side::Buy, 200))
+            .unwrap();
+        source
+            .add_order(iceberg_order(4, 10_001, 7, 30, 900))
+            .unwrap();
+        source
+            .add_order(timed_order(5, 10_001, 1, Side::Buy, 300))
+            .unwrap();
+
+        assert_eq!(target.merge_from(&source).unwrap(), 3);
+
+        // Order 5 ties with order 2 and queues behind it.
+        assert_eq!(queue_ids(&target), ids([1, 3, 2, 5, 4]));
+        assert!(
+            target
+                .snapshot_orders()
+                .iter()
+                .all(|order| order.price() == Price::new(10_000))
+        );
+        assert_eq!(target.order_count(), 5);
+        assert_eq!(target.visible_quantity(), 43);
+        assert_eq!(target.hidden_quantity(), 30);
+
+        assert_eq!(source.order_count(), 0);
+        assert_eq!(source.visible_quantity(), 0);
+        assert_eq!(source.hidden_quantity(), 0);
+        // The drained source is un-pinned and accepts either side again.
+        source
+            .add_order(timed_order(6, 10_001, 1, Side::Sell, 400))
+            .unwrap();
+
+        // The merged level matches in its new time priority.
+        let result = target.match_order(
+            15,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(500),
+            &SequentialIdGenerator::new(0),
+        );
+        assert_eq!(
+            result.filled_order_ids(),
+            [Id::from_u64(1), Id::from_u64(3)]
+        );
+        assert_eq!(
+            result.trades().as_slice()[1].price(),
+            Price::new(10_000),
+            "moved makers trade at the merged price"
+        );
+    }
+
+    #[test]
+    fn test_merge_from_rejects_conflicts_without_changing_either_level() {
+        let target = PriceLevel::new(10_000);
+        let source = PriceLevel::new(10_001);
+        target
+            .add_order(timed_order(1, 10_000, 10, Side::Buy, 100))
+            .unwrap();
+
+        source
+            .add_order(timed_order(1, 10_001, 5, Side::Buy, 50))
+            .unwrap();
+        assert!(matches!(
+            target.merge_from(&source),
+            Err(PriceLevelError::DuplicateOrderId { .. })
+        ));
+        assert_eq!((target.order_count(), source.order_count()), (1, 1));
+
+        let sells = PriceLevel::new(10_002);
+        sells
+            .add_order(timed_order(2, 10_002, 5, Side::Sell, 50))
+            .unwrap();
+        assert!(matches!(
+            target.merge_from(&sells),
+            Err(PriceLevelError::SideMismatch {
+                expected: Side::Buy,
+                actual: Side::Sell
+            })
+        ));
+        assert_eq!(sells.order_count(), 1);
+
+        assert!(matches!(
+            target.merge_from(&target),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert_eq!(target.merge_from(&PriceLevel::new(9_999)).unwrap(), 0);
+        assert_eq!(queue_ids(&target), ids([1]));
+        assert_eq!(target.visible_quantity(), 10);
+    }
+
+    #[test]
+    fn test_split_off_moves_matching_orders_and_reconciles_counters() {
+        let level = PriceLevel::new(10_000);
+        level
+            .add_order(timed_order(1, 10_000, 10, Side::Sell, 100))
+            .unwrap();
+        level
+            .add_order(timed_order(2, 10_000, 20, Side::Sell, 200))
+            .unwrap();
+        level
+            .add_order(timed_order(3, 10_000, 30, Side::Sell, 300))
+            .unwrap();
+
+        let split = level
+            .split_off(|order| order.visible_quantity().as_u64() >= 20)
+            .unwrap();
+        assert_eq!(queue_ids(&level), ids([1]));
+        assert_eq!(level.visible_quantity(), 10);
+        assert_eq!(level.order_count(), 1);
+        assert_eq!(queue_ids(&split), ids([2, 3]));
+        assert_eq!(split.price(), 10_000);
+        assert_eq!(split.visible_quantity(), 50);
+        assert_eq!(split.order_count(), 2);
+
+        // Re-bucket the split part into a coarser level.
+        let coarse = PriceLevel::new(10_010);
+        assert_eq!(coarse.merge_from(&split).unwrap(), 2);
+        assert_eq!(coarse.visible_quantity(), 50);
+        assert_eq!(split.order_count(), 0);
+
+        // Splitting nothing leaves the level untouched.
+        let empty = level.split_off(|_| false).unwrap();
+        assert_eq!(empty.order_count(), 0);
+        assert_eq!(level.order_count(), 1);
+
+        // Splitting everything empties and un-pins the level.
+        let all = level.split_off(|_| true).unwrap();
+        assert_eq!(all.order_count(), 1);
+        assert_eq!(level.order_count(), 0);
+        level
+            .add_order(timed_order(4, 10_000, 1, Side::Buy, 400))
+            .unwrap();
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_merge_and_split_keep_invariants() {
+        let target = PriceLevel::new(10_000);
+        let source = PriceLevel::new(10_005);
+        for id in 0..20 {
+            target
+                .add_order(timed_order(id, 10_000, 3, Side::Buy, id * 2))
+                .unwrap();
+            source
+                .add_order(iceberg_order(100 + id, 10_005, 2, 4, id * 2 + 1))
+                .unwrap();
+        }
+        target.merge_from(&source).unwrap();
+        target.check_invariants().unwrap();
+        source.check_invariants().unwrap();
+
+        let split = target
+            .split_off(|order| order.hidden_quantity().as_u64() > 0)
+            .unwrap();
+        target.check_invariants().unwrap();
+        split.check_invariants().unwrap();
+        assert_eq!(split.order_count(), 20);
+        assert_eq!(target.order_count(), 20);
+    }
+}
+
+#[cfg(test)]
+mod tests_sequencing {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome};
+    use crate::price_level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_sequenced_results_carry_the_committed_sequence() {
+        let level = PriceLevel::new(10000);
+        assert!(!level.sequencing());
+        level.set_sequencing(true);
+        assert!(level.sequencing());
+
+        let first = level.add_order_sequenced(order(1, 10)).unwrap();
+        assert_eq!(first.sequence(), 1);
+        assert_eq!(first.value().id(), Id::from_u64(1));
+        assert_eq!(
+            level.add_order_sequenced(order(2, 10)).unwrap().sequence(),
+            2
+        );
+        assert!(level.add_order_sequenced(order(2, 10)).is_err());
+        assert_eq!(level.mutation_sequence(), 2);
+
+        let resized = level
+            .update_order_sequenced(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(2),
+                new_quantity: Quantity::new(4),
+            })
+            .unwrap();
+        assert_eq!(resized.sequence(), 3);
+        assert!(matches!(resized.value(), UpdateOutcome::UpdatedInPlace(_)));
+
+        // Nothing changed: the stamp is the state the miss was decided against.
+        let missed = level
+            .update_order_sequenced(OrderUpdate::Cancel {
+                order_id: Id::from_u64(9),
+            })
+            .unwrap();
+        assert_eq!(missed.sequence(), 3);
+        assert!(matches!(missed.into_value(), UpdateOutcome::NotFound));
+
+        // Sequencing stamps trades without trade stamping being enabled.
+        assert!(!level.trade_stamping());
+        let result = level.match_order(
+            12,
+            Id::from_u64(100),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        );
+        let sequences: Vec<Option<u64>> = result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|trade| trade.level_sequence())
+            .collect();
+        assert_eq!(sequences, [Some(4), Some(5)]);
+        assert_eq!(level.mutation_sequence(), 5);
+    }
+
+    #[test]
+    fn test_sequencing_totally_orders_concurrent_mutations() {
+        const THREADS: u64 = 4;
+        const ORDERS: u64 = 50;
+        let level = Arc::new(PriceLevel::new(10000));
+        level.set_sequencing(true);
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|thread_id| {
+                let level = Arc::clone(&level);
+                thread::spawn(move || {
+                    let executions = SequentialIdGenerator::new(thread_id * 1_000);
+                    let mut stamps = Vec::new();
+                    for i in 0..ORDERS {
+                        let id = thread_id * 1_000 + i;
+                        let added = level.add_order_sequenced(order(id, 2)).unwrap();
+                        stamps.push((added.sequence(), Some(id), "add"));
+                        if i % 3 == 0 {
+                            let cancelled = level
+                                .update_order_sequenced(OrderUpdate::Cancel {
+                                    order_id: Id::from_u64(id),
+                                })
+                                .unwrap();
+                            if cancelled.value().is_found() {
+                                stamps.push((cancelled.sequence(), Some(id), "cancel"));
+                            }
+                        } else {
+                            let result = level.match_order(
+                                1,
+                                Id::from_u64(900_000 + id),
+                                TimeInForce::Ioc,
+                                TakerKind::Standard,
+                                TimestampMs::new(1616823001000),
+                                &executions,
+                            );
+                            for trade in result.trades().as_slice() {
+                                stamps.push((trade.level_sequence().unwrap(), None, "fill"));
+                            }
+                        }
+                    }
+                    stamps
+                })
+            })
+            .collect();
+        let mut stamps: Vec<_> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+        stamps.sort_unstable();
+
+        // Every committed mutation got its own sequence, with no gaps.
+        let sequences: Vec<u64> = stamps.iter().map(|(sequence, _, _)| *sequence).collect();
+        let expected: Vec<u64> = (1..=stamps.len() as u64).collect();
+        assert_eq!(sequences, expected);
+        assert_eq!(level.mutation_sequence(), stamps.len() as u64);
+
+        // Each cancel is ordered after the admission of the order it removed.
+        let mut added = HashMap::new();
+        for (sequence, id, kind) in &stamps {
+            match (*kind, id) {
+                ("add", Some(id)) => {
+                    added.insert(*id, *sequence);
+                }
+                ("cancel", Some(id)) => assert!(added[id] < *sequence),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_match_taker {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::PriceLevel;
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn maker(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn market(id: u64, quantity: u64, side: Side, tif: TimeInForce) -> OrderType<()> {
+        OrderType::Market {
+            id: Id::from_u64(id),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823001000),
+            time_in_force: tif,
+            extra_fields: (),
+        }
+    }
+
+    fn limit(id: u64, price: u128, quantity: u64, tif: TimeInForce) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823001000),
+            time_in_force: tif,
+            extra_fields: (),
+        }
+    }
+
+    fn level() -> PriceLevel {
+        let level = PriceLevel::new(10000);
+        level.add_order(maker(1, 10)).unwrap();
+        level.add_order(maker(2, 10)).unwrap();
+        level
+    }
+
+    const NOW: TimestampMs = TimestampMs::new(1616823002000);
+
+    #[test]
+    fn test_market_taker_sweeps_and_never_rests() {
+        let level = level();
+        let ids = SequentialIdGenerator::new(0);
+        let result = level
+            .match_taker(&market(9, 15, Side::Buy, TimeInForce::Ioc), NOW, &ids)
+            .unwrap();
+        assert_eq!(result.executed_quantity().unwrap().as_u64(), 15);
+        assert_eq!(level.total_quantity().unwrap(), 5);
+
+        let err = level
+            .add_order(market(10, 5, Side::Sell, TimeInForce::Gtc))
+            .unwrap_err();
+        assert!(matches!(err, PriceLevelError::InvalidOperation { .. }));
+        assert_eq!(level.order_count(), 1);
+    }
+
+    #[test]
+    fn test_taker_time_in_force_is_honored() {
+        let level = level();
+        let ids = SequentialIdGenerator::new(0);
+        let killed = level
+            .match_taker(&market(9, 25, Side::Buy, TimeInForce::Fok), NOW, &ids)
+            .unwrap();
+        assert!(killed.trades().as_slice().is_empty());
+        assert_eq!(level.total_quantity().unwrap(), 20);
+
+        let partial = level
+            .match_taker(&market(9, 25, Side::Buy, TimeInForce::Ioc), NOW, &ids)
+            .unwrap();
+        assert_eq!(partial.executed_quantity().unwrap().as_u64(), 20);
+        assert_eq!(partial.remaining_quantity().as_u64(), 5);
+    }
+
+    #[test]
+    fn test_limit_taker_must_reach_the_level() {
+        let level = level();
+        let ids = SequentialIdGenerator::new(0);
+        let short = level
+            .match_taker(&limit(9, 9999, 5, TimeInForce::Fok), NOW, &ids)
+            .unwrap();
+        assert!(short.trades().as_slice().is_empty());
+        assert_eq!(short.remaining_quantity().as_u64(), 5);
+        assert_eq!(level.total_quantity().unwrap(), 20);
+
+        let through = level
+            .match_taker(&limit(9, 10001, 5, TimeInForce::Gtc), NOW, &ids)
+            .unwrap();
+        assert_eq!(through.executed_quantity().unwrap().as_u64(), 5);
+    }
+
+    #[test]
+    fn test_taker_on_the_resting_side_is_refused() {
+        let level = level();
+        let ids = SequentialIdGenerator::new(0);
+        let err = level
+            .match_taker(&market(9, 5, Side::Sell, TimeInForce::Ioc), NOW, &ids)
+            .unwrap_err();
+        assert!(matches!(err, PriceLevelError::InvalidCrossing { .. }));
+        assert_eq!(level.total_quantity().unwrap(), 20);
+    }
+
+    #[test]
+    fn test_immediate_taker_remainder_is_cancelled() {
+        let level = level();
+        let ids = SequentialIdGenerator::new(0);
+        let ioc = level
+            .match_taker(&limit(9, 10000, 8, TimeInForce::Ioc), NOW, &ids)
+            .unwrap();
+        assert_eq!(ioc.remaining_quantity().as_u64(), 0);
+        assert_eq!(ioc.cancelled_quantity().as_u64(), 0);
+
+        let killed = level
+            .match_taker(&limit(9, 10000, 25, TimeInForce::Fok), NOW, &ids)
+            .unwrap();
+        assert!(killed.was_killed());
+        assert_eq!(killed.cancelled_quantity().as_u64(), 25);
+
+        let market = level
+            .match_taker(&market(9, 20, Side::Buy, TimeInForce::Gtc), NOW, &ids)
+            .unwrap();
+        assert_eq!(market.executed_quantity().unwrap().as_u64(), 12);
+        assert_eq!(market.cancelled_quantity().as_u64(), 8);
+    }
+
+    #[test]
+    fn test_remainder_is_left_open_only_for_resting_takers() {
+        let level = level();
+        let ids = SequentialIdGenerator::new(0);
+        let gtc = level
+            .match_taker(&limit(9, 10000, 25, TimeInForce::Gtc), NOW, &ids)
+            .unwrap();
+        assert_eq!(gtc.remaining_quantity().as_u64(), 5);
+        assert_eq!(gtc.cancelled_quantity().as_u64(), 0);
+
+        let short = level
+            .match_taker(&limit(9, 9999, 5, TimeInForce::Ioc), NOW, &ids)
+            .unwrap();
+        assert_eq!(short.cancelled_quantity().as_u64(), 5);
+    }
+}
+
+#[cfg(test)]
+mod tests_fill_estimates {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{PriceLevel, PriceLevelConfig};
+    use crate::utils::{ManualClock, Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const EPOCH: u64 = 1_700_000_000_000;
+
+    fn level() -> PriceLevel {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(EPOCH + 10_000)));
+        let level = PriceLevel::with_config(10000, PriceLevelConfig::new().with_clock(clock));
+        for id in 1..=4 {
+            level
+                .add_order(OrderType::Standard {
+                    id: Id::from_u64(id),
+                    price: Price::new(10000),
+                    quantity: Quantity::new(10),
+                    side: Side::Sell,
+                    user_id: Hash32::zero(),
+                    timestamp: TimestampMs::new(EPOCH),
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                })
+                .unwrap();
+        }
+        level
+    }
+
+    fn take(level: &PriceLevel, quantity: u64, at: u64) {
+        level.match_order(
+            quantity,
+            Id::from_u64(900 + at),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(EPOCH + at),
+            &SequentialIdGenerator::new(at),
+        );
+    }
+
+    #[test]
+    fn test_no_recent_executions_gives_no_estimate() {
+        let level = level();
+        assert_eq!(level.estimate_time_to_fill(Id::from_u64(2)), None);
+        assert_eq!(
+            level.estimate_fill_probability(Id::from_u64(2), 60_000),
+            Some(0.0)
+        );
+        assert_eq!(level.estimate_time_to_fill(Id::from_u64(9)), None);
+        assert_eq!(
+            level.estimate_fill_probability(Id::from_u64(9), 1_000),
+            None
+        );
+    }
+
+    #[test]
+    fn test_estimates_follow_the_queue() {
+        let level = level();
+        take(&level, 6, 1_000);
+        take(&level, 6, 5_000);
+
+        // Queue: order 2 with 8 left, then orders 3 and 4 with 10 each.
+        let window = level.stats().stats_window(Duration::from_secs(60));
+        assert_eq!((window.executions, window.quantity), (3, 12));
+        let second = level.estimate_time_to_fill(Id::from_u64(2)).unwrap();
+        let last = level.estimate_time_to_fill(Id::from_u64(4)).unwrap();
+        assert!((second.as_secs_f64() - 8.0 / window.quantity_per_second()).abs() < 1e-6);
+        assert!(last > second);
+
+        let near = level
+            .estimate_fill_probability(Id::from_u64(4), 1_000)
+            .unwrap();
+        let far = level
+            .estimate_fill_probability(Id::from_u64(4), 600_000)
+            .unwrap();
+        assert!((0.0..0.01).contains(&near));
+        assert!(far > 0.99 && far <= 1.0);
+        // A long enough horizon takes the normal approximation.
+        let certain = level
+            .estimate_fill_probability(Id::from_u64(4), 100_000_000)
+            .unwrap();
+        assert!((certain - 1.0).abs() < 1e-9);
+        assert_eq!(
+            level.estimate_fill_probability(Id::from_u64(4), 0),
+            Some(0.0)
+        );
+        let front = level
+            .estimate_fill_probability(Id::from_u64(2), 60_000)
+            .unwrap();
+        assert!(
+            front
+                > level
+                    .estimate_fill_probability(Id::from_u64(4), 60_000)
+                    .unwrap()
+        );
+    }
+}
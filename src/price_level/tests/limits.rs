@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::{CapacityLimit, PriceLevelError};
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::tests::fixtures::{standard, take};
+    use crate::price_level::{LevelLimits, PriceLevel, PriceLevelConfig};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::sync::Arc;
+    use std::thread;
+
+    fn level(limits: LevelLimits) -> PriceLevel {
+        PriceLevel::with_config(10_000, PriceLevelConfig::new().with_limits(limits))
+    }
+
+    fn order(id: u64, user: u8, quantity: u64) -> OrderType<()> {
+        standard(id, 10_000, quantity, Side::Buy, Hash32::new([user; 32]))
+    }
+
+    fn is_exceeded(result: Result<impl Sized, PriceLevelError>, limit: CapacityLimit) -> bool {
+        matches!(result, Err(PriceLevelError::CapacityExceeded { limit: l, .. }) if l == limit)
+    }
+
+    #[test]
+    fn test_limits_default_to_unbounded() {
+        let limits = LevelLimits::default();
+        assert_eq!(limits.max_orders(), u64::MAX);
+        assert_eq!(limits.max_quantity(), u64::MAX);
+        assert_eq!(limits.max_orders_per_participant(), u64::MAX);
+        assert_eq!(PriceLevelConfig::new().limits(), None);
+        assert_eq!(PriceLevel::<()>::new(10_000).limits(), None);
+
+        let limits = LevelLimits::new().with_max_orders(2);
+        assert_eq!(level(limits).limits(), Some(limits));
+    }
+
+    #[test]
+    fn test_max_orders_refuses_and_frees_on_cancel() {
+        let level = level(LevelLimits::new().with_max_orders(2));
+        level.add_order(order(1, 1, 10)).unwrap();
+        level.add_order(order(2, 2, 10)).unwrap();
+        assert!(is_exceeded(
+            level.add_order(order(3, 3, 10)),
+            CapacityLimit::Orders
+        ));
+        assert_eq!(level.order_count(), 2);
+
+        // A duplicate is still reported as one at capacity.
+        assert!(matches!(
+            level.add_order(order(2, 2, 10)),
+            Err(PriceLevelError::DuplicateOrderId { .. })
+        ));
+
+        take(&level, 10);
+        level.add_order(order(3, 3, 10)).unwrap();
+    }
+
+    #[test]
+    fn test_max_quantity_counts_hidden_quantity() {
+        let level = level(LevelLimits::new().with_max_quantity(100));
+        level
+            .add_order(OrderType::IcebergOrder {
+                id: Id::from_u64(1),
+                price: Price::new(10_000),
+                visible_quantity: Quantity::new(10),
+                hidden_quantity: Quantity::new(80),
+                side: Side::Buy,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1616823000001),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+        let err = level.add_order(order(2, 1, 11)).unwrap_err();
+        assert!(matches!(
+            err,
+            PriceLevelError::CapacityExceeded {
+                limit: CapacityLimit::Quantity,
+                max: 100
+            }
+        ));
+        level.add_order(order(2, 1, 10)).unwrap();
+        assert_eq!(level.total_quantity().unwrap(), 100);
+    }
+
+    #[test]
+    fn test_max_orders_per_participant() {
+        let level = level(LevelLimits::new().with_max_orders_per_participant(2));
+        level.add_order(order(1, 1, 10)).unwrap();
+        level.add_order(order(2, 1, 10)).unwrap();
+        assert!(is_exceeded(
+            level.add_order(order(3, 1, 10)),
+            CapacityLimit::ParticipantOrders
+        ));
+        level.add_order(order(3, 2, 10)).unwrap();
+
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        level.add_order(order(4, 1, 10)).unwrap();
+        assert_eq!(level.order_count(), 3);
+    }
+
+    #[test]
+    fn test_concurrent_admissions_never_overshoot() {
+        let level = Arc::new(level(LevelLimits::new().with_max_orders(10)));
+        let handles: Vec<_> = (0..4)
+            .map(|thread_id| {
+                let level = Arc::clone(&level);
+                thread::spawn(move || {
+                    (0..10)
+                        .filter(|i| level.add_order(order(thread_id * 10 + i, 1, 1)).is_ok())
+                        .count()
+                })
+            })
+            .collect();
+        let admitted: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+        assert_eq!(admitted, 10);
+        assert_eq!(level.order_count(), 10);
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{
+        MboRecord, MbpRecord, PriceLevel, write_market_data_csv, write_market_data_json,
+    };
+    use crate::utils::{Price, Quantity, TimestampMs};
+
+    fn standard(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn iceberg(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn mbo(id: u64, size: u64, priority: u64) -> MboRecord {
+        MboRecord {
+            price: Price::new(10000),
+            side: Side::Sell,
+            size: Quantity::new(size),
+            priority,
+            order_id: Id::from_u64(id),
+        }
+    }
+
+    #[test]
+    fn test_mbo_records_follow_queue_order_and_hide_reserve() {
+        let level = PriceLevel::new(10000);
+        level.add_order(standard(1, 10)).unwrap();
+        level.add_order(iceberg(2, 5, 50)).unwrap();
+
+        assert_eq!(level.mbo_records(), vec![mbo(1, 10, 0), mbo(2, 5, 1)]);
+    }
+
+    #[test]
+    fn test_mbp_record_aggregates_displayed_depth() {
+        let level = PriceLevel::new(10000);
+        assert_eq!(level.mbp_record().unwrap(), None);
+
+        level.add_order(standard(1, 10)).unwrap();
+        level.add_order(iceberg(2, 5, 50)).unwrap();
+        assert_eq!(
+            level.mbp_record().unwrap(),
+            Some(MbpRecord {
+                price: Price::new(10000),
+                side: Side::Sell,
+                size: Quantity::new(15),
+                order_count: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_display_delayed_orders_are_not_exported() {
+        let level = PriceLevel::new(10000);
+        level.add_order(standard(1, 10)).unwrap();
+        level
+            .add_order_with_display_delay(standard(2, 7), 5_000)
+            .unwrap();
+
+        assert_eq!(level.mbo_records(), vec![mbo(1, 10, 0)]);
+    }
+
+    #[test]
+    fn test_mbp_aggregate_rejects_overflow() {
+        let records = [mbo(1, u64::MAX, 0), mbo(2, 1, 1)];
+        assert!(MbpRecord::aggregate(&records).is_err());
+    }
+
+    #[test]
+    fn test_csv_writer_emits_header_and_rows() {
+        let mut out = Vec::new();
+        write_market_data_csv(&[mbo(1, 10, 0), mbo(2, 5, 1)], &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "price,side,size,priority,order_id\n\
+             10000,SELL,10,0,00000000-0000-0001-0000-000000000000\n\
+             10000,SELL,5,1,00000000-0000-0002-0000-000000000000\n"
+        );
+
+        let mut out = Vec::new();
+        let mbp = MbpRecord::aggregate(&[mbo(1, 10, 0)]).unwrap().unwrap();
+        write_market_data_csv(&[mbp], &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "price,side,size,order_count\n10000,SELL,10,1\n"
+        );
+    }
+
+    #[test]
+    fn test_json_writer_emits_one_record_per_line() {
+        let records = [mbo(1, 10, 0), mbo(2, 5, 1)];
+        let mut out = Vec::new();
+        write_market_data_json(&records, &mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        let decoded: Vec<MboRecord> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(decoded, records);
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{MatchingPolicy, PriceLevel};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn iceberg_order(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn level_with_depth() -> PriceLevel {
+        let level = PriceLevel::new(10000);
+        level.add_order(iceberg_order(1, 10, 10)).unwrap();
+        level.add_order(iceberg_order(2, 15, 0)).unwrap();
+        level
+    }
+
+    const NOW: TimestampMs = TimestampMs::new(1616823000100);
+
+    #[test]
+    fn test_propose_match_plans_without_mutating() {
+        let level = level_with_depth();
+        let sequence = level.mutation_sequence();
+
+        let plan = level.propose_match(28, Id::from_u64(9), NOW).unwrap();
+        assert_eq!(plan.price(), 10000);
+        assert_eq!(plan.sequence(), sequence);
+        let steps: Vec<_> = plan
+            .fills()
+            .iter()
+            .map(|fill| (fill.order_id(), fill.quantity()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (Id::from_u64(1), 10),
+                (Id::from_u64(2), 15),
+                (Id::from_u64(1), 3)
+            ]
+        );
+        assert_eq!(plan.planned_quantity(), 28);
+        assert!(plan.is_complete());
+
+        assert_eq!(level.mutation_sequence(), sequence);
+        assert_eq!(level.visible_quantity(), 25);
+        assert_eq!(level.hidden_quantity(), 10);
+    }
+
+    #[test]
+    fn test_commit_match_executes_the_planned_fills() {
+        let level = level_with_depth();
+        let plan = level.propose_match(28, Id::from_u64(9), NOW).unwrap();
+        let planned: Vec<_> = plan.fills().to_vec();
+
+        let result = level
+            .commit_match(plan, &SequentialIdGenerator::new(0))
+            .unwrap();
+        let trades: Vec<_> = result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|trade| (trade.maker_order_id(), trade.quantity().as_u64()))
+            .collect();
+        let expected: Vec<_> = planned
+            .iter()
+            .map(|fill| (fill.order_id(), fill.quantity()))
+            .collect();
+        assert_eq!(trades, expected);
+        assert_eq!(result.remaining_quantity().as_u64(), 0);
+        assert_eq!(level.total_quantity().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_stale_plan_is_refused() {
+        let level = level_with_depth();
+        let plan = level.propose_match(5, Id::from_u64(9), NOW).unwrap();
+        level.add_order(iceberg_order(3, 5, 0)).unwrap();
+
+        assert!(!level.abort_match(plan.clone()));
+        let err = level
+            .commit_match(plan, &SequentialIdGenerator::new(0))
+            .unwrap_err();
+        assert!(matches!(err, PriceLevelError::InvalidOperation { .. }));
+        assert_eq!(level.total_quantity().unwrap(), 40);
+
+        let plan = level.propose_match(5, Id::from_u64(9), NOW).unwrap();
+        let guard = level.reserve(5).unwrap();
+        assert!(
+            level
+                .commit_match(plan, &SequentialIdGenerator::new(0))
+                .is_err()
+        );
+        guard.rollback();
+    }
+
+    #[test]
+    fn test_abort_and_refusals() {
+        let level = level_with_depth();
+        let plan = level.propose_match(5, Id::from_u64(9), NOW).unwrap();
+        assert!(level.abort_match(plan));
+        assert_eq!(level.total_quantity().unwrap(), 35);
+
+        let other = PriceLevel::<()>::new(10001);
+        let plan = level.propose_match(5, Id::from_u64(9), NOW).unwrap();
+        assert!(matches!(
+            other.commit_match(plan, &SequentialIdGenerator::new(0)),
+            Err(PriceLevelError::PriceMismatch { .. })
+        ));
+
+        assert!(level.propose_match(5, Id::from_u64(1), NOW).is_err());
+        level.set_matching_policy(MatchingPolicy::ProRata);
+        assert!(level.propose_match(5, Id::from_u64(9), NOW).is_err());
+    }
+
+    #[test]
+    fn test_plan_skips_reserved_depth() {
+        let level = level_with_depth();
+        let guard = level.reserve(20).unwrap();
+        let plan = level.propose_match(30, Id::from_u64(9), NOW).unwrap();
+        assert_eq!(plan.planned_quantity(), 15);
+        assert!(!plan.is_complete());
+        drop(guard);
+    }
+}
@@ -1,5 +1,52 @@
+mod amend_history;
+#[cfg(feature = "rkyv")]
+mod archive;
+mod auction;
+mod book_analytics;
+mod book_snapshot;
+mod bounded;
+mod bulk_load;
+mod bust;
+mod cancel_replace;
+mod conflation;
+mod depth_ladder;
 mod entry;
+#[cfg(feature = "async")]
+mod events;
+#[cfg(feature = "fix")]
+mod fix;
+#[cfg(test)]
+mod fixtures;
+mod fork;
+mod halt;
+mod hidden_orders;
+mod latency;
+mod lazy_map;
 mod level;
+mod limits;
+mod market_data;
+mod match_plan;
+mod multi_book;
+mod order_age;
 mod order_queue;
+mod persistence;
+#[cfg(feature = "pool")]
+mod pool;
+mod price_band;
+mod priority_tiers;
+#[cfg(feature = "metrics")]
+mod prometheus;
+mod queue_audit;
+mod rate_limit;
+#[cfg(feature = "redb")]
+mod redb_store;
+mod replay;
+mod reservation;
+mod session;
+mod simulate_match;
 mod snapshot;
+mod snapshot_scheduler;
 mod statistics;
+mod statistics_csv;
+mod symbol_spec;
+mod view;
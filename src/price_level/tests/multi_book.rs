@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{
+        BookSnapshotPackage, MultiBook, PriceLevelConfig, SymbolKey, SymbolSpec,
+    };
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+    use std::thread;
+
+    fn order(id: u64, price: u128, quantity: u64, side: Side) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn book() -> MultiBook {
+        let book = MultiBook::new();
+        book.register("BTC-USD", PriceLevelConfig::new()).unwrap();
+        book.register("ETH-USD", PriceLevelConfig::new()).unwrap();
+        book
+    }
+
+    #[test]
+    fn test_register_rejects_duplicates_and_routes_by_key() {
+        let book = book();
+        assert!(matches!(
+            book.register("BTC-USD", PriceLevelConfig::new()),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        book.register(42_u32, PriceLevelConfig::new()).unwrap();
+        assert_eq!(book.len(), 3);
+        assert!(book.contains(42_u32));
+        assert!(!book.contains("42"));
+        assert_eq!(
+            book.symbols(),
+            [
+                SymbolKey::Id(42),
+                SymbolKey::from("BTC-USD"),
+                SymbolKey::from("ETH-USD"),
+            ]
+        );
+
+        assert!(matches!(
+            book.add_order("SOL-USD", order(1, 100, 5, Side::Buy)),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert!(book.statistics("SOL-USD").is_err());
+        assert!(book.level("SOL-USD", 100).is_none());
+    }
+
+    #[test]
+    fn test_symbols_keep_independent_levels() {
+        let book = book();
+        book.add_order("BTC-USD", order(1, 100, 5, Side::Sell))
+            .unwrap();
+        book.add_order("BTC-USD", order(2, 100, 7, Side::Sell))
+            .unwrap();
+        book.add_order("BTC-USD", order(3, 99, 4, Side::Buy))
+            .unwrap();
+        // The same order id is independent in another symbol.
+        book.add_order("ETH-USD", order(1, 100, 9, Side::Sell))
+            .unwrap();
+
+        let prices: Vec<u128> = book
+            .levels("BTC-USD")
+            .unwrap()
+            .iter()
+            .map(|level| level.price())
+            .collect();
+        assert_eq!(prices, [99, 100]);
+        assert_eq!(book.level("BTC-USD", 100).unwrap().order_count(), 2);
+        assert_eq!(book.level("ETH-USD", 100).unwrap().visible_quantity(), 9);
+        assert!(book.level("ETH-USD", 99).is_none());
+
+        let executions = SequentialIdGenerator::new(0);
+        let result = book.level("BTC-USD", 100).unwrap().match_order(
+            6,
+            Id::from_u64(10),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823000100),
+            &executions,
+        );
+        assert!(result.is_complete());
+
+        let btc = book.statistics("BTC-USD").unwrap();
+        assert_eq!(btc.symbol, SymbolKey::from("BTC-USD"));
+        assert_eq!(btc.level_count, 2);
+        assert_eq!(btc.order_count, 2);
+        assert_eq!(btc.visible_quantity, 10);
+        assert_eq!(btc.orders_added, 3);
+        assert_eq!(btc.orders_executed, 2);
+        assert_eq!(btc.quantity_executed, 6);
+        assert_eq!(btc.value_executed, 600);
+
+        let all = book.all_statistics().unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0], btc);
+        assert_eq!(all[1].visible_quantity, 9);
+        assert_eq!(all[1].quantity_executed, 0);
+    }
+
+    #[test]
+    fn test_statistics_overflow_is_an_error() {
+        let book = book();
+        book.add_order("BTC-USD", order(1, 100, u64::MAX, Side::Sell))
+            .unwrap();
+        book.add_order("BTC-USD", order(2, 101, 1, Side::Sell))
+            .unwrap();
+        book.add_order("ETH-USD", order(3, 100, 5, Side::Sell))
+            .unwrap();
+
+        // The displayed quantity across the levels does not fit a u64.
+        assert!(matches!(
+            book.statistics("BTC-USD"),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert!(book.all_statistics().is_err());
+        assert_eq!(book.statistics("ETH-USD").unwrap().visible_quantity, 5);
+    }
+
+    #[test]
+    fn test_symbol_config_applies_to_every_level() {
+        let book = MultiBook::new();
+        let config = PriceLevelConfig::new().with_symbol_spec(SymbolSpec::new(5, 10));
+        book.register(1_u32, config).unwrap();
+
+        book.add_order(1_u32, order(1, 100, 20, Side::Buy)).unwrap();
+        assert!(matches!(
+            book.add_order(1_u32, order(2, 103, 20, Side::Buy)),
+            Err(PriceLevelError::InvalidTick { .. })
+        ));
+        assert!(matches!(
+            book.add_order(1_u32, order(3, 105, 15, Side::Buy)),
+            Err(PriceLevelError::InvalidLot { .. })
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_names_the_symbol_and_restores() {
+        let book = book();
+        book.add_order("BTC-USD", order(1, 101, 5, Side::Sell))
+            .unwrap();
+        book.add_order("BTC-USD", order(2, 100, 3, Side::Sell))
+            .unwrap();
+
+        let snapshot = book
+            .snapshot("BTC-USD", 77, TimestampMs::new(1616823000500))
+            .unwrap();
+        assert_eq!(snapshot.symbol(), "BTC-USD");
+        assert_eq!(snapshot.sequence(), 77);
+        let prices: Vec<u128> = snapshot
+            .levels()
+            .iter()
+            .map(|level| level.price().as_u128())
+            .collect();
+        assert_eq!(prices, [100, 101]);
+
+        let json = BookSnapshotPackage::new(snapshot)
+            .unwrap()
+            .to_json()
+            .unwrap();
+        let restored = BookSnapshotPackage::<()>::from_json(&json)
+            .unwrap()
+            .restore()
+            .unwrap();
+        assert_eq!(restored.len(), 2);
+        assert!(book.snapshot(7_u32, 0, TimestampMs::new(0)).is_err());
+    }
+
+    #[test]
+    fn test_deregister_returns_the_levels() {
+        let book = book();
+        book.add_order("ETH-USD", order(1, 100, 5, Side::Buy))
+            .unwrap();
+        let levels = book.deregister("ETH-USD").unwrap();
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels[0].visible_quantity(), 5);
+        assert!(!book.contains("ETH-USD"));
+        assert!(book.deregister("ETH-USD").is_none());
+    }
+
+    #[test]
+    fn test_concurrent_adds_share_one_level() {
+        let book = Arc::new(book());
+        let handles: Vec<_> = (0..4_u64)
+            .map(|thread_id| {
+                let book = Arc::clone(&book);
+                thread::spawn(move || {
+                    for i in 0..50 {
+                        let id = thread_id * 1_000 + i;
+                        book.add_order("BTC-USD", order(id, 100, 1, Side::Buy))
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let stats = book.statistics("BTC-USD").unwrap();
+        assert_eq!(stats.level_count, 1);
+        assert_eq!(stats.order_count, 200);
+        assert_eq!(stats.visible_quantity, 200);
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{
+        OrderAgeBucket, OrderAgeOutcome, PriceLevel, PriceLevelConfig, PriceLevelStatistics,
+    };
+    use crate::utils::{ManualClock, Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const EPOCH: u64 = 1_700_000_000_000;
+
+    fn order(id: u64, quantity: u64, timestamp: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_execution_ages_are_bucketed() {
+        let stats = PriceLevelStatistics::new();
+        for waiting in [0, 1, 3, 5, 700] {
+            stats
+                .record_execution(1, 100, EPOCH, EPOCH + waiting)
+                .unwrap();
+        }
+        // A zero maker timestamp has no age.
+        stats.record_execution(1, 100, 0, EPOCH).unwrap();
+
+        let histogram = stats.order_age_histogram(OrderAgeOutcome::Executed);
+        assert_eq!(histogram.count, 5);
+        assert_eq!(histogram.sum_ms, 709);
+        assert_eq!((histogram.min_ms, histogram.max_ms), (0, 700));
+        let bucket = |lower_ms, upper_ms, count| OrderAgeBucket {
+            lower_ms,
+            upper_ms,
+            count,
+        };
+        assert_eq!(
+            histogram.buckets,
+            [
+                bucket(0, 0, 1),
+                bucket(1, 1, 1),
+                bucket(2, 3, 1),
+                bucket(4, 7, 1),
+                bucket(512, 1_023, 1),
+            ]
+        );
+        assert_eq!(histogram.mean(), Some(Duration::from_millis(141)));
+        assert_eq!(histogram.percentile(0.5), Some(Duration::from_millis(3)));
+        assert_eq!(histogram.percentile(1.0), Some(Duration::from_millis(700)));
+        assert_eq!(stats.order_age_histogram(OrderAgeOutcome::Removed).count, 0);
+    }
+
+    #[test]
+    fn test_removal_ages_and_reset() {
+        let stats = PriceLevelStatistics::new();
+        stats.record_order_removed_with_age(10, Some(2_000));
+        stats.record_order_removed_with_age(10, None);
+        assert_eq!(stats.orders_removed(), 2);
+
+        let histogram = stats.order_age_histogram(OrderAgeOutcome::Removed);
+        assert_eq!(histogram.count, 1);
+        assert_eq!(histogram.percentile(0.9), Some(Duration::from_secs(2)));
+
+        stats.reset();
+        let histogram = stats.order_age_histogram(OrderAgeOutcome::Removed);
+        assert_eq!(histogram.count, 0);
+        assert_eq!(histogram.mean(), None);
+        assert_eq!(histogram.percentile(0.5), None);
+    }
+
+    #[test]
+    fn test_ages_survive_clone_and_serde() {
+        let stats = PriceLevelStatistics::new();
+        let plain = serde_json::to_string(&stats).unwrap();
+        assert!(!plain.contains("order_ages"));
+
+        stats.record_execution(1, 100, EPOCH, EPOCH + 40).unwrap();
+        stats.record_order_removed_with_age(5, Some(9));
+        let cloned = stats.clone();
+        let json = serde_json::to_string(&stats).unwrap();
+        let decoded: PriceLevelStatistics = serde_json::from_str(&json).unwrap();
+        for outcome in OrderAgeOutcome::ALL {
+            let histogram = stats.order_age_histogram(outcome);
+            assert_eq!(histogram.count, 1);
+            assert_eq!(cloned.order_age_histogram(outcome), histogram);
+            assert_eq!(decoded.order_age_histogram(outcome), histogram);
+        }
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+    }
+
+    #[test]
+    fn test_level_records_fill_and_cancel_ages() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(EPOCH)));
+        let level: PriceLevel =
+            PriceLevel::with_config(100, PriceLevelConfig::new().with_clock(clock.clone()));
+        level.add_order(order(1, 10, EPOCH)).unwrap();
+        level.add_order(order(2, 10, EPOCH)).unwrap();
+
+        level.match_order(
+            10,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(EPOCH + 250),
+            &SequentialIdGenerator::new(0),
+        );
+        clock.advance(Duration::from_secs(3));
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            })
+            .unwrap();
+
+        let stats = level.stats();
+        let executed = stats.order_age_histogram(OrderAgeOutcome::Executed);
+        assert_eq!((executed.count, executed.max_ms), (1, 250));
+        let removed = stats.order_age_histogram(OrderAgeOutcome::Removed);
+        assert_eq!((removed.count, removed.max_ms), (1, 3_000));
+    }
+
+    #[test]
+    fn test_snapshot_carries_ages() {
+        let level: PriceLevel = PriceLevel::new(100);
+        level.add_order(order(1, 10, EPOCH)).unwrap();
+        level.add_order(order(2, 10, EPOCH)).unwrap();
+        level.match_order(
+            4,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(EPOCH + 60),
+            &SequentialIdGenerator::new(0),
+        );
+
+        let json = level.snapshot_to_json().unwrap();
+        assert!(json.contains("order_ages"));
+        let restored: PriceLevel = PriceLevel::from_snapshot_json(&json).unwrap();
+        assert_eq!(
+            restored
+                .stats()
+                .order_age_histogram(OrderAgeOutcome::Executed),
+            level.stats().order_age_histogram(OrderAgeOutcome::Executed)
+        );
+    }
+}
@@ -57,7 +57,7 @@ mod tests {
         let input = "OrderQueue:orders=[Standard:id=00000000-0000-0001-0000-000000000000;price=1000;quantity=10;side=BUY;timestamp=1616823000000;time_in_force=GTC,Standard:id=00000000-0000-0002-0000-000000000000;price=1100;quantity=20;side=BUY;timestamp=1616823000000;time_in_force=GTC]";
 
         // Try parsing
-        let parsed_queue = match OrderQueue::from_str(input) {
+        let parsed_queue: OrderQueue = match OrderQueue::from_str(input) {
             Ok(q) => q,
             Err(e) => {
                 info!("Parse error: {:?}", e);
@@ -89,7 +89,7 @@ mod tests {
         assert!(has_order2, "Second order not found or incorrect");
 
         // Test round-trip parsing
-        let round_trip_queue = OrderQueue::from_str(&display_string).unwrap();
+        let round_trip_queue = OrderQueue::<()>::from_str(&display_string).unwrap();
         let round_trip_orders = round_trip_queue.to_vec();
 
         assert_eq!(
@@ -158,7 +158,7 @@ mod tests {
         let string_rep = queue.to_string();
 
         // Parse back from string
-        let parsed_queue = match OrderQueue::from_str(&string_rep) {
+        let parsed_queue: OrderQueue = match OrderQueue::from_str(&string_rep) {
             Ok(q) => q,
             Err(e) => {
                 info!("Parse error: {:?}", e);
@@ -179,7 +179,7 @@ mod tests {
 
     #[test]
     fn test_order_queue_to_vec_empty() {
-        let queue = OrderQueue::new();
+        let queue: OrderQueue = OrderQueue::new();
 
         // test_to_vec on empty queue
         let vec = queue.to_vec();
@@ -223,7 +223,7 @@ mod tests {
     fn test_order_queue_from_str_invalid_order() {
         // Test with an invalid order format
         let input = "OrderQueue:orders=[InvalidOrder:id=1]";
-        let result = OrderQueue::from_str(input);
+        let result = OrderQueue::<()>::from_str(input);
 
         assert!(result.is_err());
     }
@@ -366,7 +366,7 @@ mod tests {
         let complex_input = "OrderQueue:orders=[Standard:id=00000000-0000-0001-0000-000000000000;price=1000;quantity=10;side=BUY;timestamp=1616823000000;time_in_force=GTC,IcebergOrder:id=00000000-0000-0002-0000-000000000000;price=1000;visible_quantity=5;hidden_quantity=15;side=SELL;timestamp=1616823000001;time_in_force=GTC]";
 
         // Parse the complex input
-        let result = OrderQueue::from_str(complex_input);
+        let result = OrderQueue::<()>::from_str(complex_input);
         assert!(result.is_ok());
 
         let queue = result.unwrap();
@@ -379,24 +379,24 @@ mod tests {
 
         // Test parsing with empty orders
         let empty_orders = "OrderQueue:orders=[]";
-        let result = OrderQueue::from_str(empty_orders);
+        let result = OrderQueue::<()>::from_str(empty_orders);
         assert!(result.is_ok());
         let queue = result.unwrap();
         assert!(queue.is_empty());
 
         // Test parsing with invalid format (no "OrderQueue:" prefix)
         let invalid_input = "orders=[Standard:id=1;price=1000;quantity=10;side=BUY;timestamp=1616823000000;time_in_force=GTC]";
-        let result = OrderQueue::from_str(invalid_input);
+        let result = OrderQueue::<()>::from_str(invalid_input);
         assert!(result.is_err());
 
         // Test parsing with malformed content (missing closing bracket)
         let malformed_input = "OrderQueue:orders=[Standard:id=00000000-0000-0001-0000-000000000000;price=1000;quantity=10;side=BUY;timestamp=1616823000000;time_in_force=GTC";
-        let result = OrderQueue::from_str(malformed_input);
+        let result = OrderQueue::<()>::from_str(malformed_input);
         assert!(result.is_err());
 
         // Test parsing with invalid order type
         let invalid_order = "OrderQueue:orders=[InvalidOrder:id=00000000-0000-0001-0000-000000000000;price=1000;quantity=10;side=BUY;timestamp=1616823000000;time_in_force=GTC]";
-        let result = OrderQueue::from_str(invalid_order);
+        let result = OrderQueue::<()>::from_str(invalid_order);
         assert!(result.is_err());
     }
 
@@ -619,7 +619,9 @@ mod tests {
         // must therefore NEVER return `Empty` — the liquidity is always resting.
         // Under the old remove-then-insert order the maker vanished from the
         // index between the two ops and a scan could miss it.
-        use crate::price_level::order_queue::{FrontAction, FrontOutcome, UpdateDecision};
+        use crate::price_level::order_queue::{
+            FrontAction, FrontOutcome, FrontScan, UpdateDecision,
+        };
         use std::collections::HashSet;
         use std::sync::Arc as StdArc;
         use std::sync::atomic::{AtomicBool, Ordering};
@@ -651,8 +653,9 @@ mod tests {
             // A no-op probe: whatever the front is, park it (leaves it resting)
             // and report we found one. The maker always rests, so this must be
             // `Matched`, never `Empty`.
-            let outcome =
-                queue.match_front(&mut set_aside, |_seq, _order| (FrontAction::SetAside, ()));
+            let outcome = queue.match_front(FrontScan::All, &mut set_aside, |_seq, _order| {
+                (FrontAction::SetAside, ())
+            });
             assert!(
                 matches!(outcome, FrontOutcome::Matched { .. }),
                 "front scan returned Empty while the resident maker rests (issue #127)"
@@ -770,4 +773,287 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_tombstone_mode_remove_leaves_index_key() {
+        let queue = OrderQueue::new();
+        queue.set_tombstone_mode(true);
+        for id in 1..=3 {
+            queue
+                .try_push(Arc::new(create_test_order(id, 1000u128, 10)))
+                .expect("admit");
+        }
+
+        assert!(queue.remove(Id::from_u64(2)).is_some());
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.tombstone_count(), 1);
+        assert!(queue.debug_map_index_consistent());
+
+        // The tombstone is reaped as the front scan passes it.
+        let popped: Vec<Id> = std::iter::from_fn(|| queue.pop()).map(|o| o.id()).collect();
+        assert_eq!(popped, vec![Id::from_u64(1), Id::from_u64(3)]);
+        assert_eq!(queue.tombstone_count(), 0);
+    }
+
+    #[test]
+    fn test_tombstone_mode_resequence_leaves_old_key() {
+        use crate::price_level::order_queue::UpdateDecision;
+
+        let queue = OrderQueue::new();
+        queue.set_tombstone_mode(true);
+        for id in 1..=2 {
+            queue
+                .try_push(Arc::new(create_test_order(id, 1000u128, 10)))
+                .expect("admit");
+        }
+
+        let amended = queue.update_entry(Id::from_u64(1), |_| {
+            Ok(UpdateDecision::ReplaceAtTail(Arc::new(create_test_order(
+                1, 1000u128, 20,
+            ))))
+        });
+        assert!(matches!(amended, Some(Ok(_))));
+        assert_eq!(queue.tombstone_count(), 1);
+        assert!(queue.debug_map_index_consistent());
+
+        let popped: Vec<Id> = std::iter::from_fn(|| queue.pop()).map(|o| o.id()).collect();
+        assert_eq!(popped, vec![Id::from_u64(2), Id::from_u64(1)]);
+        assert!(queue.is_empty());
+        assert_eq!(queue.tombstone_count(), 0);
+    }
+
+    #[test]
+    fn test_compact_tombstones_keeps_live_orders() {
+        let queue = OrderQueue::new();
+        queue.set_tombstone_mode(true);
+        for id in 1..=5 {
+            queue
+                .try_push(Arc::new(create_test_order(id, 1000u128, 10)))
+                .expect("admit");
+        }
+        for id in [1, 3, 5] {
+            assert!(queue.remove(Id::from_u64(id)).is_some());
+        }
+        assert_eq!(queue.tombstone_count(), 3);
+
+        assert_eq!(queue.compact_tombstones(), 3);
+        assert_eq!(queue.compact_tombstones(), 0);
+        queue.set_tombstone_mode(false);
+        assert!(queue.debug_map_index_consistent());
+        let popped: Vec<Id> = std::iter::from_fn(|| queue.pop()).map(|o| o.id()).collect();
+        assert_eq!(popped, vec![Id::from_u64(2), Id::from_u64(4)]);
+    }
+
+    #[test]
+    fn test_orders_walks_priority_order_without_draining() {
+        use crate::price_level::order_queue::UpdateDecision;
+
+        let queue = OrderQueue::new();
+        for id in 1..=4 {
+            queue
+                .try_push(Arc::new(create_test_order(id, 1000u128, 10)))
+                .expect("admit");
+        }
+        // Re-sequence 2 to the tail and remove 3.
+        let amended = queue.update_entry(Id::from_u64(2), |_| {
+            Ok(UpdateDecision::ReplaceAtTail(Arc::new(create_test_order(
+                2, 1000u128, 20,
+            ))))
+        });
+        assert!(matches!(amended, Some(Ok(_))));
+        assert!(queue.remove(Id::from_u64(3)).is_some());
+
+        let walked: Vec<Id> = queue.orders().map(|o| o.id()).collect();
+        assert_eq!(
+            walked,
+            vec![Id::from_u64(1), Id::from_u64(4), Id::from_u64(2)]
+        );
+        // The walk matches the snapshot order and leaves the queue intact.
+        let snapshot: Vec<Id> = queue.snapshot_by_seq().iter().map(|o| o.id()).collect();
+        assert_eq!(walked, snapshot);
+        assert_eq!(queue.len(), 3);
+        assert!(queue.debug_map_index_consistent());
+    }
+
+    #[test]
+    fn test_orders_skips_tombstones() {
+        let queue = OrderQueue::new();
+        queue.set_tombstone_mode(true);
+        for id in 1..=3 {
+            queue
+                .try_push(Arc::new(create_test_order(id, 1000u128, 10)))
+                .expect("admit");
+        }
+        assert!(queue.remove(Id::from_u64(1)).is_some());
+        assert_eq!(queue.tombstone_count(), 1);
+
+        let walked: Vec<Id> = queue.orders().map(|o| o.id()).collect();
+        assert_eq!(walked, vec![Id::from_u64(2), Id::from_u64(3)]);
+        assert_eq!(queue.tombstone_count(), 1);
+    }
+
+    #[test]
+    fn test_with_shards_walks_and_pops_in_sequence_order() {
+        let queue = OrderQueue::with_shards(4);
+        assert_eq!(queue.shard_count(), 4);
+        assert_eq!(OrderQueue::<()>::with_shards(0).shard_count(), 1);
+        for id in 1..=32 {
+            queue
+                .try_push(Arc::new(create_test_order(id, 1000u128, 10)))
+                .expect("admit");
+        }
+        assert!(queue.remove(Id::from_u64(7)).is_some());
+        assert!(queue.debug_map_index_consistent());
+
+        let expected: Vec<Id> = (1..=32).filter(|&id| id != 7).map(Id::from_u64).collect();
+        let walked: Vec<Id> = queue.orders().map(|o| o.id()).collect();
+        assert_eq!(walked, expected);
+        let popped: Vec<Id> = std::iter::from_fn(|| queue.pop()).map(|o| o.id()).collect();
+        assert_eq!(popped, expected);
+        assert!(queue.is_empty() && queue.debug_map_index_consistent());
+    }
+
+    #[test]
+    fn test_with_shards_match_front_round_robins_shards() {
+        use crate::price_level::order_queue::{FIRST_SEQ, FrontAction, FrontOutcome, FrontScan};
+        use std::collections::HashSet;
+
+        let queue = OrderQueue::with_shards(4);
+        for id in 1..=64 {
+            queue
+                .try_push(Arc::new(create_test_order(id, 1000u128, 10)))
+                .expect("admit");
+        }
+        let shard_of = |id: u64| queue.shard_of(Id::from_u64(id));
+        let populated: HashSet<usize> = (1..=64).map(shard_of).collect();
+        assert!(populated.len() > 1, "ids spread over several shards");
+
+        let mut set_aside = HashSet::new();
+        let mut matched: Vec<u64> = Vec::new();
+        while let FrontOutcome::Matched { result } =
+            queue.match_front(FrontScan::All, &mut set_aside, |seq, _order| {
+                (FrontAction::Remove, seq)
+            })
+        {
+            matched.push(result);
+        }
+        assert_eq!(matched.len(), 64);
+        assert!(queue.is_empty() && queue.debug_map_index_consistent());
+
+        // Sequences were minted from FIRST_SEQ for ids 1..=64. Within a
+        // shard the matches follow time priority; across shards they take
+        // turns.
+        for shard in &populated {
+            let mine: Vec<u64> = matched
+                .iter()
+                .copied()
+                .filter(|seq| shard_of(seq - FIRST_SEQ + 1) == *shard)
+                .collect();
+            assert!(
+                mine.windows(2).all(|w| w[0] < w[1]),
+                "shard {shard}: {mine:?}"
+            );
+        }
+        let first_round: HashSet<usize> = matched[..populated.len()]
+            .iter()
+            .map(|seq| shard_of(seq - FIRST_SEQ + 1))
+            .collect();
+        assert_eq!(
+            first_round, populated,
+            "every shard is served once per round"
+        );
+    }
+
+    #[test]
+    fn test_with_shards_match_front_is_exact_in_tombstone_mode() {
+        use crate::price_level::order_queue::{FrontAction, FrontOutcome, FrontScan};
+        use std::collections::HashSet;
+
+        let queue = OrderQueue::with_shards(4);
+        queue.set_tombstone_mode(true);
+        for id in 1..=32 {
+            queue
+                .try_push(Arc::new(create_test_order(id, 1000u128, 10)))
+                .expect("admit");
+        }
+        assert!(queue.remove(Id::from_u64(3)).is_some());
+
+        let mut set_aside = HashSet::new();
+        let mut matched: Vec<Id> = Vec::new();
+        while let FrontOutcome::Matched { result } =
+            queue.match_front(FrontScan::All, &mut set_aside, |_seq, order| {
+                (FrontAction::Remove, order.id())
+            })
+        {
+            matched.push(result);
+        }
+        let expected: Vec<Id> = (1..=32).filter(|&id| id != 3).map(Id::from_u64).collect();
+        assert_eq!(matched, expected);
+        assert_eq!(queue.tombstone_count(), 0);
+    }
+
+    #[test]
+    fn test_pending_stops_rest_outside_the_front_scan() {
+        use crate::price_level::order_queue::{
+            FrontAction, FrontOutcome, FrontScan, UpdateDecision,
+        };
+        use std::collections::HashSet;
+
+        let stop = |id: u64| OrderType::<()>::StopLimit {
+            id: Id::from_u64(id),
+            price: Price::new(1000),
+            quantity: Quantity::new(10),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            trigger_price: Price::new(1100),
+            extra_fields: (),
+        };
+        let queue = OrderQueue::new();
+        for id in 1..=64 {
+            queue.try_push(Arc::new(stop(id))).expect("admit");
+        }
+        queue
+            .try_push(Arc::new(create_test_order(65, 1000u128, 10)))
+            .expect("admit");
+        assert!(queue.debug_map_index_consistent());
+
+        // The front scan reaches the live order at once: no stop is parked.
+        let mut set_aside = HashSet::new();
+        let front = queue.match_front(FrontScan::All, &mut set_aside, |_seq, order| {
+            (FrontAction::SetAside, order.id())
+        });
+        assert!(matches!(
+            front,
+            FrontOutcome::Matched { result } if result == Id::from_u64(65)
+        ));
+        assert_eq!(set_aside.len(), 1);
+        assert!(matches!(
+            queue.match_front(FrontScan::All, &mut set_aside, |_seq, _order| (
+                FrontAction::SetAside,
+                ()
+            )),
+            FrontOutcome::Empty
+        ));
+
+        // Activation moves a stop into the time-priority index, at the tail.
+        let activated = queue.update_entry(Id::from_u64(1), |live| {
+            Ok(UpdateDecision::ReplaceAtTail(Arc::new(
+                live.activate().expect("a stop"),
+            )))
+        });
+        assert!(matches!(activated, Some(Ok(_))));
+        assert_eq!(queue.pending_stops().count(), 63);
+        assert_eq!(
+            queue.orders().last().map(|order| order.id()),
+            Some(Id::from_u64(1))
+        );
+        assert!(queue.remove(Id::from_u64(2)).is_some());
+        assert_eq!(queue.len(), 64);
+        assert!(queue.debug_map_index_consistent());
+
+        let popped: Vec<Id> = std::iter::from_fn(|| queue.pop()).map(|o| o.id()).collect();
+        assert_eq!(popped, vec![Id::from_u64(65), Id::from_u64(1)]);
+    }
 }
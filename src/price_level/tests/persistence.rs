@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{ChecksumAlgorithm, PriceLevel, PriceLevelSnapshotPackage};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "pricelevel-persistence-{}-{}",
+            name,
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn level_with_orders(count: u64) -> PriceLevel {
+        let level = PriceLevel::new(100);
+        for id in 1..=count {
+            level
+                .add_order(OrderType::Standard {
+                    id: Id::from_u64(id),
+                    price: Price::new(100),
+                    quantity: Quantity::new(10 * id),
+                    side: Side::Buy,
+                    user_id: Hash32::zero(),
+                    timestamp: TimestampMs::new(1616823000000 + id),
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                })
+                .unwrap();
+        }
+        level
+    }
+
+    fn file_names(dir: &PathBuf) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_save_then_load_restores_orders_and_statistics() {
+        let dir = temp_dir("roundtrip");
+        let path = dir.join("level.json");
+        let level = level_with_orders(3);
+        level.save_to_path(&path).unwrap();
+
+        let restored: PriceLevel = PriceLevel::load_from_path(&path).unwrap();
+        assert_eq!(restored.price(), 100);
+        assert_eq!(restored.order_count(), 3);
+        assert_eq!(restored.visible_quantity(), 60);
+        assert_eq!(restored.stats().orders_added(), 3);
+        assert_eq!(file_names(&dir), vec!["level.json".to_string()]);
+    }
+
+    #[test]
+    fn test_save_replaces_previous_file_without_leaving_temp_files() {
+        let dir = temp_dir("replace");
+        let path = dir.join("level.json");
+        level_with_orders(1).save_to_path(&path).unwrap();
+        level_with_orders(4).save_to_path(&path).unwrap();
+
+        let restored: PriceLevel = PriceLevel::load_from_path(&path).unwrap();
+        assert_eq!(restored.order_count(), 4);
+        assert_eq!(file_names(&dir), vec!["level.json".to_string()]);
+    }
+
+    #[test]
+    fn test_save_with_algorithm_round_trips() {
+        let dir = temp_dir("algorithm");
+        let path = dir.join("level.json");
+        level_with_orders(2)
+            .save_to_path_with(&path, ChecksumAlgorithm::Crc32c)
+            .unwrap();
+
+        let json = fs::read_to_string(&path).unwrap();
+        let package = PriceLevelSnapshotPackage::<()>::from_json(&json).unwrap();
+        assert_eq!(package.checksum_algorithm(), ChecksumAlgorithm::Crc32c);
+        let restored: PriceLevel = PriceLevel::load_from_path(&path).unwrap();
+        assert_eq!(restored.order_count(), 2);
+    }
+
+    #[test]
+    fn test_load_rejects_tampered_file() {
+        let dir = temp_dir("tampered");
+        let path = dir.join("level.json");
+        level_with_orders(2).save_to_path(&path).unwrap();
+        let json = fs::read_to_string(&path).unwrap();
+        let tampered = json.replace("\"quantity\":20", "\"quantity\":25");
+        assert_ne!(tampered, json);
+        fs::write(&path, tampered).unwrap();
+
+        let result: Result<PriceLevel, _> = PriceLevel::load_from_path(&path);
+        assert!(matches!(
+            result,
+            Err(PriceLevelError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_load_missing_file_is_deserialization_error() {
+        let dir = temp_dir("missing");
+        let result: Result<PriceLevel, _> = PriceLevel::load_from_path(dir.join("absent.json"));
+        assert!(matches!(
+            result,
+            Err(PriceLevelError::DeserializationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_failed_save_leaves_existing_file_intact() {
+        let dir = temp_dir("failed");
+        let path = dir.join("level.json");
+        level_with_orders(1).save_to_path(&path).unwrap();
+
+        // A directory cannot be renamed over by a file: the save fails after
+        // writing its temp file, which must be cleaned up.
+        let blocked = dir.join("blocked");
+        fs::create_dir(&blocked).unwrap();
+        fs::write(blocked.join("keep"), b"x").unwrap();
+        let result = level_with_orders(2).save_to_path(&blocked);
+        assert!(matches!(
+            result,
+            Err(PriceLevelError::SerializationError { .. })
+        ));
+        assert_eq!(
+            file_names(&dir),
+            vec!["blocked".to_string(), "level.json".to_string()]
+        );
+        let restored: PriceLevel = PriceLevel::load_from_path(&path).unwrap();
+        assert_eq!(restored.order_count(), 1);
+    }
+
+    #[test]
+    fn test_save_to_path_without_file_name_is_rejected() {
+        let result = level_with_orders(1).save_to_path("/");
+        assert!(matches!(
+            result,
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+    }
+}
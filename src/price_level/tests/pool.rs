@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::level::PriceLevel;
+    use crate::price_level::{DEFAULT_ORDER_POOL_CAPACITY, OrderPool};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+
+    fn create_standard_order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn match_taker(level: &PriceLevel, taker_id: u64, quantity: u64) -> crate::MatchResult {
+        level.match_order(
+            quantity,
+            Id::from_u64(taker_id),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    #[test]
+    fn test_acquire_reuses_a_released_allocation() {
+        let pool = OrderPool::new(4);
+        let first = pool.acquire(create_standard_order(1, 10));
+        let address = Arc::as_ptr(&first);
+        assert!(pool.release(first));
+        assert_eq!(pool.retained_orders(), 1);
+
+        let second = pool.acquire(create_standard_order(2, 20));
+        assert_eq!(Arc::as_ptr(&second), address);
+        assert_eq!(second.id(), Id::from_u64(2));
+        assert_eq!(second.visible_quantity(), Quantity::new(20));
+        assert_eq!((pool.hits(), pool.misses()), (1, 1));
+    }
+
+    #[test]
+    fn test_release_drops_shared_allocations_and_overflow() {
+        let pool = OrderPool::new(1);
+        let shared = pool.acquire(create_standard_order(1, 10));
+        let _held = Arc::clone(&shared);
+        assert!(!pool.release(shared), "a shared allocation is not pooled");
+
+        assert!(pool.release(Arc::new(create_standard_order(2, 10))));
+        assert!(!pool.release(Arc::new(create_standard_order(3, 10))));
+        assert_eq!(pool.retained_orders(), 1);
+    }
+
+    #[test]
+    fn test_default_pool_capacity() {
+        let pool = OrderPool::<()>::default();
+        for id in 0..=DEFAULT_ORDER_POOL_CAPACITY as u64 {
+            pool.release(Arc::new(create_standard_order(id, 1)));
+        }
+        assert_eq!(pool.retained_orders(), DEFAULT_ORDER_POOL_CAPACITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "order pool capacity must be positive")]
+    fn test_zero_capacity_panics() {
+        let _ = OrderPool::<()>::new(0);
+    }
+
+    #[test]
+    fn test_pooled_level_recycles_consumed_and_resized_orders() {
+        let pool = Arc::new(OrderPool::new(16));
+        let level = PriceLevel::with_order_pool(10000, Arc::clone(&pool));
+        assert!(level.order_pool().is_some());
+
+        level.add_order(create_standard_order(1, 10)).unwrap();
+        level.add_order(create_standard_order(2, 10)).unwrap();
+        assert_eq!(pool.misses(), 2);
+
+        // Order 1 is consumed, order 2 is partially filled: both evicted
+        // allocations come back.
+        let result = match_taker(&level, 100, 14);
+        assert_eq!(result.trades().len(), 2);
+        assert_eq!(pool.retained_orders(), 1, "one spare fed the residual");
+
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(2),
+                new_quantity: Quantity::new(3),
+            })
+            .unwrap();
+        level.add_order(create_standard_order(3, 5)).unwrap();
+        assert!(pool.hits() >= 2);
+        assert_eq!(level.visible_quantity(), 8);
+        assert_eq!(level.order_count(), 2);
+    }
+
+    #[test]
+    fn test_recycled_result_buffers_feed_the_next_match() {
+        let pool = Arc::new(OrderPool::new(4));
+        let level = PriceLevel::with_order_pool(10000, Arc::clone(&pool));
+        for id in 1..=8 {
+            level.add_order(create_standard_order(id, 10)).unwrap();
+        }
+
+        let result = match_taker(&level, 100, 40);
+        assert_eq!(result.trades().len(), 4);
+        assert!(pool.recycle(result));
+        assert_eq!(pool.retained_buffers(), 1);
+
+        let result = match_taker(&level, 101, 40);
+        assert_eq!(pool.retained_buffers(), 0);
+        assert_eq!(result.trades().len(), 4);
+        assert_eq!(result.filled_order_ids().len(), 4);
+        assert!(result.is_complete());
+    }
+
+    #[test]
+    fn test_pooled_level_matches_like_an_unpooled_one() {
+        let pooled = PriceLevel::with_order_pool(10000, Arc::new(OrderPool::new(2)));
+        let plain = PriceLevel::new(10000);
+        for level in [&pooled, &plain] {
+            for id in 1..=6 {
+                level.add_order(create_standard_order(id, id * 3)).unwrap();
+            }
+        }
+        for (taker, quantity) in [(100, 5), (101, 17), (102, 2)] {
+            let pooled_result = match_taker(&pooled, taker, quantity);
+            let plain_result = match_taker(&plain, taker, quantity);
+            assert_eq!(
+                pooled_result.trades().as_slice().len(),
+                plain_result.trades().as_slice().len()
+            );
+            assert_eq!(
+                pooled_result.remaining_quantity(),
+                plain_result.remaining_quantity()
+            );
+            if let Some(pool) = pooled.order_pool() {
+                pool.recycle(pooled_result);
+            }
+        }
+        assert_eq!(
+            pooled.snapshot_by_insertion_seq(),
+            plain.snapshot_by_insertion_seq()
+        );
+    }
+}
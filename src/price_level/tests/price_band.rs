@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::MatchOutcome;
+    use crate::orders::Id;
+    use crate::price_level::tests::fixtures::{sell, take, try_take};
+    use crate::price_level::{BandAction, BandWidth, PriceBand, PriceLevel};
+    use crate::utils::TimestampMs;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn resting(price: u128) -> PriceLevel {
+        let level = PriceLevel::new(price);
+        level.add_order(sell(1, price, 10)).unwrap();
+        level
+    }
+
+    #[test]
+    fn test_bounds_follow_width_and_saturate() {
+        let band = PriceBand::new(10_050, BandWidth::BasisPoints(100), BandAction::Reject);
+        assert_eq!(band.bounds(), (9_950, 10_150));
+        band.set_width(BandWidth::Absolute(20));
+        assert_eq!(band.bounds(), (10_030, 10_070));
+        band.set_reference(5);
+        assert_eq!(band.bounds(), (0, 25));
+        band.set_reference(u128::MAX);
+        band.set_width(BandWidth::BasisPoints(u32::MAX));
+        assert_eq!(band.bounds(), (0, u128::MAX));
+        assert_eq!(band.action(), BandAction::Reject);
+    }
+
+    #[test]
+    fn test_rejecting_band_refuses_takers_outside_it() {
+        let level = resting(106);
+        let band = Arc::new(PriceBand::new(
+            100,
+            BandWidth::BasisPoints(500),
+            BandAction::Reject,
+        ));
+        level.set_price_band(Arc::clone(&band));
+        assert_eq!(take(&level, 2).outcome(), MatchOutcome::Rejected);
+        assert!(matches!(
+            try_take(&level, 2),
+            Err(PriceLevelError::PriceOutsideBand {
+                price: 106,
+                lower: 95,
+                upper: 105,
+            })
+        ));
+        assert!(matches!(
+            level.propose_match(2, Id::from_u64(99), TimestampMs::new(1616823001000)),
+            Err(PriceLevelError::PriceOutsideBand { .. })
+        ));
+        assert_eq!(level.visible_quantity(), 10);
+
+        // Moving the reference brings the level back inside.
+        band.set_reference(102);
+        assert_eq!(take(&level, 2).outcome(), MatchOutcome::Filled);
+        level.clear_price_band();
+        band.set_reference(1_000);
+        assert_eq!(take(&level, 2).outcome(), MatchOutcome::Filled);
+        assert!(level.price_band().is_none());
+    }
+
+    #[test]
+    fn test_flagging_band_counts_breaches() {
+        let level = resting(106);
+        level.set_price_band(Arc::new(PriceBand::new(
+            100,
+            BandWidth::Absolute(5),
+            BandAction::Flag,
+        )));
+        assert_eq!(take(&level, 2).outcome(), MatchOutcome::Filled);
+        take(&level, 0);
+        assert_eq!(level.band_breaches(), 1);
+        level.price_band().unwrap().set_action(BandAction::Reject);
+        assert_eq!(take(&level, 2).outcome(), MatchOutcome::Rejected);
+        assert_eq!(level.band_breaches(), 1);
+    }
+
+    #[test]
+    fn test_readers_never_see_a_torn_update() {
+        let band = Arc::new(PriceBand::new(
+            0,
+            BandWidth::Absolute(0),
+            BandAction::Reject,
+        ));
+        let writer = {
+            let band = Arc::clone(&band);
+            thread::spawn(move || {
+                for step in 1..=2_000_u128 {
+                    // Reference and width always move together across the
+                    // 64-bit halves.
+                    let value = step << 64 | step;
+                    band.set_reference(value);
+                    band.set_width(BandWidth::Absolute(value));
+                }
+            })
+        };
+        while !writer.is_finished() {
+            let reference = band.reference();
+            assert_eq!(reference >> 64, reference & u128::from(u64::MAX));
+        }
+        writer.join().unwrap();
+        assert_eq!(band.reference(), 2_000 << 64 | 2_000);
+    }
+}
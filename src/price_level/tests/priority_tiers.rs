@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{MatchingPolicy, PriceLevel, PriceLevelConfig, TierTable};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+
+    const BROKER: Hash32 = Hash32::zero();
+
+    fn market_maker() -> Hash32 {
+        Hash32::new([7; 32])
+    }
+
+    fn id(id: u64) -> Id {
+        Id::from_u64(id)
+    }
+
+    fn standard(id: u64, quantity: u64, user_id: Hash32) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id,
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn hidden(id: u64, quantity: u64, user_id: Hash32) -> OrderType<()> {
+        OrderType::Hidden {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id,
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn tiered() -> PriceLevelConfig {
+        PriceLevelConfig::new()
+            .with_priority_tiers(Arc::new(TierTable::new(1).with_tier(market_maker(), 0)))
+    }
+
+    fn take(level: &PriceLevel, quantity: u64, tif: TimeInForce) -> MatchResult<()> {
+        level.match_order(
+            quantity,
+            Id::from_u64(99),
+            tif,
+            TakerKind::Standard,
+            TimestampMs::new(1616823001000),
+            &SequentialIdGenerator::new(0),
+        )
+    }
+
+    fn fills(result: &MatchResult<()>) -> Vec<(Id, u64)> {
+        result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|trade| (trade.maker_order_id(), trade.quantity().as_u64()))
+            .collect()
+    }
+
+    #[test]
+    fn test_higher_tier_trades_first_in_time_priority() {
+        let level = PriceLevel::with_config(100, tiered());
+        assert!(level.priority_tiers().is_some());
+        level.add_order(standard(1, 5, BROKER)).unwrap();
+        level.add_order(standard(2, 5, market_maker())).unwrap();
+        level.add_order(standard(3, 5, BROKER)).unwrap();
+        level.add_order(standard(4, 5, market_maker())).unwrap();
+
+        let plan = level
+            .propose_match(17, Id::from_u64(99), TimestampMs::new(1616823001000))
+            .unwrap();
+        let planned: Vec<_> = plan
+            .fills()
+            .iter()
+            .map(|fill| (fill.order_id(), fill.quantity()))
+            .collect();
+
+        let result = take(&level, 17, TimeInForce::Ioc);
+        assert_eq!(
+            fills(&result),
+            [(id(2), 5), (id(4), 5), (id(1), 5), (id(3), 2)]
+        );
+        assert_eq!(planned, fills(&result));
+        assert_eq!(level.visible_quantity(), 3);
+    }
+
+    #[test]
+    fn test_hidden_orders_trail_their_own_tier() {
+        let level = PriceLevel::with_config(100, tiered());
+        level.add_order(standard(1, 5, BROKER)).unwrap();
+        level.add_order(hidden(2, 5, market_maker())).unwrap();
+        level.add_order(standard(3, 5, market_maker())).unwrap();
+
+        let result = take(&level, 12, TimeInForce::Ioc);
+        assert_eq!(fills(&result), [(id(3), 5), (id(2), 5), (id(1), 2)]);
+    }
+
+    #[test]
+    fn test_queue_position_follows_the_tiers() {
+        let level = PriceLevel::with_config(100, tiered());
+        level.add_order(standard(1, 5, BROKER)).unwrap();
+        level.add_order(hidden(2, 5, market_maker())).unwrap();
+        level.add_order(standard(3, 4, market_maker())).unwrap();
+
+        assert_eq!(
+            level.queue_positions(&[id(3), id(2), id(1)]),
+            [Some((0, 0)), Some((1, 4)), Some((2, 4))]
+        );
+        assert_eq!(fills(&take(&level, 1, TimeInForce::Ioc)), [(id(3), 1)]);
+
+        // Strict FIFO ignores the tiers, and so does the standing.
+        level.set_matching_policy(MatchingPolicy::StrictFifo);
+        assert_eq!(level.queue_position(id(1)), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_fill_or_kill_sees_every_tier() {
+        let level = PriceLevel::with_config(100, tiered());
+        level.add_order(standard(1, 5, BROKER)).unwrap();
+        level.add_order(standard(2, 5, market_maker())).unwrap();
+        assert_eq!(level.matchable_quantity(20, Id::from_u64(99)), 10);
+
+        let killed = take(&level, 11, TimeInForce::Fok);
+        assert!(killed.trades().as_slice().is_empty());
+
+        let filled = take(&level, 10, TimeInForce::Fok);
+        assert_eq!(fills(&filled), [(id(2), 5), (id(1), 5)]);
+        assert_eq!(level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_strict_fifo_ignores_tiers() {
+        let level = PriceLevel::with_config(
+            100,
+            tiered().with_matching_policy(MatchingPolicy::StrictFifo),
+        );
+        level.add_order(standard(1, 5, BROKER)).unwrap();
+        level.add_order(standard(2, 5, market_maker())).unwrap();
+
+        let result = take(&level, 7, TimeInForce::Ioc);
+        assert_eq!(fills(&result), [(id(1), 5), (id(2), 2)]);
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod tests {
+    use crate::UuidGenerator;
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::level::PriceLevel;
+    use crate::price_level::{PriceLevelStatistics, prometheus_text};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use uuid::Uuid;
+
+    fn create_standard_order(id: u64, price: u128, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn level_with(price: u128, quantities: &[u64]) -> PriceLevel {
+        let level = PriceLevel::new(price);
+        for (id, &quantity) in quantities.iter().enumerate() {
+            level
+                .add_order(create_standard_order(id as u64, price, quantity))
+                .expect("add_order should succeed");
+        }
+        level
+    }
+
+    /// Value of the single sample line starting with `series`.
+    fn sample(text: &str, series: &str) -> u64 {
+        let lines: Vec<&str> = text
+            .lines()
+            .filter(|line| line.starts_with(series) && line[series.len()..].starts_with(' '))
+            .collect();
+        assert_eq!(lines.len(), 1, "expected one `{series}` sample in:\n{text}");
+        lines[0][series.len() + 1..]
+            .parse()
+            .expect("integer sample")
+    }
+
+    #[test]
+    fn test_statistics_to_prometheus() {
+        let stats = PriceLevelStatistics::new();
+        stats.record_order_added();
+        stats.record_order_added();
+        stats
+            .record_execution(10, 100, 1_716_000_000_000, 1_716_000_000_250)
+            .unwrap();
+
+        let text = stats.to_prometheus(&[("venue", "x")]);
+        assert!(text.contains("# TYPE pricelevel_orders_added_total counter\n"));
+        assert!(text.contains("# TYPE pricelevel_statistics_degraded gauge\n"));
+        assert_eq!(
+            sample(&text, "pricelevel_orders_added_total{venue=\"x\"}"),
+            2
+        );
+        assert_eq!(
+            sample(&text, "pricelevel_quantity_executed_total{venue=\"x\"}"),
+            10
+        );
+        assert_eq!(
+            sample(&text, "pricelevel_value_executed_total{venue=\"x\"}"),
+            1_000
+        );
+        assert_eq!(
+            sample(
+                &text,
+                "pricelevel_waiting_time_milliseconds_total{venue=\"x\"}"
+            ),
+            250
+        );
+        assert_eq!(
+            sample(
+                &text,
+                "pricelevel_last_execution_timestamp_milliseconds{venue=\"x\"}"
+            ),
+            1_716_000_000_250
+        );
+        assert_eq!(
+            sample(&text, "pricelevel_statistics_degraded{venue=\"x\"}"),
+            0
+        );
+
+        // Without labels the samples carry no braces.
+        let bare = stats.to_prometheus(&[]);
+        assert_eq!(sample(&bare, "pricelevel_orders_executed_total"), 1);
+    }
+
+    #[test]
+    fn test_labels_are_sanitized_and_escaped() {
+        let stats = PriceLevelStatistics::new();
+        let text = stats.to_prometheus(&[("9 bad-name", "a\"b\\c\nd")]);
+        assert!(
+            text.contains("pricelevel_orders_added_total{_9_bad_name=\"a\\\"b\\\\c\\nd\"} 0\n"),
+            "{text}"
+        );
+        // Every non-comment line stays a single sample line.
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            assert!(line.starts_with("pricelevel_"), "{line}");
+        }
+    }
+
+    #[test]
+    fn test_level_to_prometheus_includes_depth_and_price() {
+        let level = level_with(10_000, &[5, 7]);
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        let _ = level.match_order(
+            3,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823000100),
+            &generator,
+        );
+
+        // A caller-supplied `price` label gives way to the level's price.
+        let text = level.to_prometheus(&[("symbol", "BTC"), ("price", "1")]);
+        let labels = "{symbol=\"BTC\",price=\"10000\"}";
+        assert_eq!(
+            sample(&text, &format!("pricelevel_visible_quantity{labels}")),
+            9
+        );
+        assert_eq!(sample(&text, &format!("pricelevel_order_count{labels}")), 2);
+        assert_eq!(
+            sample(
+                &text,
+                &format!("pricelevel_quantity_executed_total{labels}")
+            ),
+            3
+        );
+        assert!(!text.contains("price=\"1\""));
+    }
+
+    #[test]
+    fn test_prometheus_text_aggregates_a_book() {
+        let levels = [level_with(100, &[1, 2]), level_with(101, &[4])];
+        let text = prometheus_text(levels.iter(), &[("side", "ask")]);
+
+        // Each family is declared exactly once.
+        assert_eq!(
+            text.matches("# TYPE pricelevel_visible_quantity gauge")
+                .count(),
+            1
+        );
+        assert_eq!(
+            sample(
+                &text,
+                "pricelevel_visible_quantity{side=\"ask\",price=\"100\"}"
+            ),
+            3
+        );
+        assert_eq!(
+            sample(
+                &text,
+                "pricelevel_visible_quantity{side=\"ask\",price=\"101\"}"
+            ),
+            4
+        );
+        assert_eq!(sample(&text, "pricelevel_book_levels{side=\"ask\"}"), 2);
+        assert_eq!(
+            sample(&text, "pricelevel_book_visible_quantity{side=\"ask\"}"),
+            7
+        );
+        assert_eq!(
+            sample(&text, "pricelevel_book_order_count{side=\"ask\"}"),
+            3
+        );
+        assert_eq!(
+            sample(&text, "pricelevel_book_orders_executed_total{side=\"ask\"}"),
+            0
+        );
+
+        let empty = prometheus_text(std::iter::empty::<&PriceLevel>(), &[]);
+        assert_eq!(sample(&empty, "pricelevel_book_levels"), 0);
+    }
+}
@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{PriceLevel, QueueAudit};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn order(id: u64, quantity: u64, timestamp: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(timestamp),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn queue(audit: &QueueAudit) -> Vec<Id> {
+        audit
+            .positions
+            .iter()
+            .map(|position| position.order_id)
+            .collect()
+    }
+
+    fn ids(ids: &[u64]) -> Vec<Id> {
+        ids.iter().copied().map(Id::from_u64).collect()
+    }
+
+    #[test]
+    fn test_queue_audit_lists_positions_front_to_back() {
+        let level = PriceLevel::new(10000);
+        level.add_order(order(1, 10, 300)).unwrap();
+        level.add_order(order(2, 20, 100)).unwrap();
+        level.add_order(order(3, 30, 200)).unwrap();
+
+        let audit = level.queue_audit();
+        assert_eq!(audit.price, Price::new(10000));
+        assert_eq!(audit.mutation_sequence, level.mutation_sequence());
+        // Insertion order, not timestamp order, decides priority.
+        assert_eq!(queue(&audit), ids(&[1, 2, 3]));
+        for (index, position) in audit.positions.iter().enumerate() {
+            assert_eq!(position.position, index);
+        }
+        assert!(
+            audit
+                .positions
+                .windows(2)
+                .all(|pair| pair[0].arrival_sequence < pair[1].arrival_sequence)
+        );
+        let second = audit.position_of(Id::from_u64(2)).unwrap();
+        assert_eq!(second.timestamp, TimestampMs::new(100));
+        assert_eq!(second.visible_quantity, 20);
+        assert!(audit.position_of(Id::from_u64(9)).is_none());
+    }
+
+    #[test]
+    fn test_size_up_moves_the_order_to_the_back() {
+        let level = PriceLevel::new(10000);
+        level.add_order(order(1, 10, 100)).unwrap();
+        level.add_order(order(2, 10, 200)).unwrap();
+        let before = level.queue_audit();
+
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(1),
+                new_quantity: Quantity::new(15),
+            })
+            .unwrap();
+        let after = level.queue_audit();
+        assert_eq!(queue(&after), ids(&[2, 1]));
+        let moved = after.position_of(Id::from_u64(1)).unwrap();
+        assert!(
+            moved.arrival_sequence
+                > before
+                    .position_of(Id::from_u64(2))
+                    .unwrap()
+                    .arrival_sequence
+        );
+    }
+
+    #[test]
+    fn test_fills_follow_the_audited_queue() {
+        let level = PriceLevel::new(10000);
+        for id in 1..=4 {
+            level.add_order(order(id, 5, 100 * id)).unwrap();
+        }
+        let audit = level.queue_audit();
+        let result = level.match_order(
+            12,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1_000),
+            &SequentialIdGenerator::new(0),
+        );
+        let makers: Vec<usize> = result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|trade| audit.position_of(trade.maker_order_id()).unwrap().position)
+            .collect();
+        assert_eq!(makers, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_queue_position_counts_displayed_quantity_ahead() {
+        let level = PriceLevel::new(10000);
+        level.add_order(order(1, 10, 100)).unwrap();
+        level.add_order(order(2, 20, 200)).unwrap();
+        level.add_order(order(3, 30, 300)).unwrap();
+
+        assert_eq!(level.queue_position(Id::from_u64(1)), Some((0, 0)));
+        assert_eq!(level.queue_position(Id::from_u64(3)), Some((2, 30)));
+        assert_eq!(level.queue_position(Id::from_u64(9)), None);
+
+        level.match_order(
+            15,
+            Id::from_u64(99),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1_000),
+            &SequentialIdGenerator::new(0),
+        );
+        assert_eq!(
+            level.queue_positions(&ids(&[3, 9, 2, 3])),
+            vec![Some((1, 15)), None, Some((0, 0)), Some((1, 15))]
+        );
+        let audit = level.queue_audit();
+        let third = audit.position_of(Id::from_u64(3)).unwrap();
+        assert_eq!(
+            level.queue_position(Id::from_u64(3)).unwrap().0,
+            third.position
+        );
+    }
+
+    #[test]
+    fn test_queue_audit_json_round_trip() {
+        let level = PriceLevel::new(10000);
+        level.add_order(order(1, 10, 100)).unwrap();
+        let audit = level.queue_audit();
+        let restored = QueueAudit::from_json(&audit.to_json().unwrap()).unwrap();
+        assert_eq!(restored, audit);
+        assert!(QueueAudit::from_json("not json").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{
+        PriceLevel, PriceLevelConfig, RateLimitedOperation, RateLimiter, TokenBucketRateLimiter,
+    };
+    use crate::utils::{ManualClock, Price, Quantity, TimestampMs};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Extra fields carrying the participant's account.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Account(u64);
+
+    fn order(id: u64, account: u64) -> OrderType<Account> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10_000),
+            quantity: Quantity::new(10),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: Account(account),
+        }
+    }
+
+    fn level(clock: &Arc<ManualClock>) -> PriceLevel<Account> {
+        PriceLevel::with_config(
+            10_000,
+            PriceLevelConfig::new().with_clock(Arc::clone(clock) as _),
+        )
+    }
+
+    fn is_rate_limited<R>(result: Result<R, PriceLevelError>, id: u64) -> bool {
+        matches!(result, Err(PriceLevelError::RateLimited { order_id }) if order_id == Id::from_u64(id))
+    }
+
+    #[test]
+    fn test_token_bucket_refills_over_time() {
+        let limiter = TokenBucketRateLimiter::new(2, 4, |account: &Account| Some(account.0));
+        let at = TimestampMs::new;
+        assert!(limiter.try_acquire(&Account(1), RateLimitedOperation::Add, at(0)));
+        assert!(limiter.try_acquire(&Account(1), RateLimitedOperation::Add, at(0)));
+        assert!(!limiter.try_acquire(&Account(1), RateLimitedOperation::Add, at(100)));
+        assert_eq!(limiter.available(&1, at(249)), 0);
+        assert_eq!(limiter.available(&1, at(250)), 1);
+        assert_eq!(limiter.available(&1, at(10_000)), 2);
+        assert_eq!(limiter.available(&2, at(0)), 2);
+
+        limiter.reset();
+        assert_eq!(limiter.available(&1, at(0)), 2);
+    }
+
+    #[test]
+    fn test_untagged_messages_are_never_throttled() {
+        let limiter = TokenBucketRateLimiter::new(1, 0, |account: &Account| {
+            (account.0 != 0).then_some(account.0)
+        });
+        for _ in 0..10 {
+            assert!(limiter.try_acquire(
+                &Account(0),
+                RateLimitedOperation::Add,
+                TimestampMs::new(0)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_level_throttles_adds_updates_and_cancels_per_participant() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(0)));
+        let level = level(&clock);
+        level.set_rate_limiter(Arc::new(TokenBucketRateLimiter::new(
+            2,
+            1,
+            |account: &Account| Some(account.0),
+        )));
+        assert!(level.has_rate_limiter());
+
+        level.add_order(order(1, 7)).unwrap();
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(1),
+                new_quantity: Quantity::new(5),
+            })
+            .unwrap();
+        assert!(is_rate_limited(level.add_order(order(2, 7)), 2));
+        assert!(is_rate_limited(
+            level.update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            }),
+            1
+        ));
+        assert_eq!(level.order_count(), 1);
+
+        // Another participant has its own bucket.
+        level.add_order(order(3, 8)).unwrap();
+
+        clock.advance(Duration::from_secs(1));
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        assert_eq!(level.order_count(), 1);
+
+        level.clear_rate_limiter();
+        assert!(!level.has_rate_limiter());
+        level.add_order(order(2, 7)).unwrap();
+    }
+
+    #[test]
+    fn test_limiter_sees_each_operation() {
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<(u64, RateLimitedOperation)>>);
+        impl RateLimiter<Account> for Recorder {
+            fn try_acquire(
+                &self,
+                extra_fields: &Account,
+                operation: RateLimitedOperation,
+                _now: TimestampMs,
+            ) -> bool {
+                self.0.lock().unwrap().push((extra_fields.0, operation));
+                true
+            }
+        }
+
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(0)));
+        let level = level(&clock);
+        let recorder = Arc::new(Recorder::default());
+        level.set_rate_limiter(Arc::clone(&recorder) as _);
+
+        level.add_order(order(1, 7)).unwrap();
+        level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(1),
+                new_quantity: Quantity::new(5),
+            })
+            .unwrap();
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+        // Nothing rests under this id, so there is no participant to charge.
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                (7, RateLimitedOperation::Add),
+                (7, RateLimitedOperation::Update),
+                (7, RateLimitedOperation::Cancel),
+            ]
+        );
+    }
+}
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{
+        PriceLevel, PriceLevelSnapshotPackage, PrunePolicy, RedbSnapshotStore, SnapshotKey,
+        SnapshotStore, SymbolKey,
+    };
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::fs;
+
+    fn add(level: &PriceLevel, id: u64) {
+        level
+            .add_order(OrderType::Standard {
+                id: Id::from_u64(id),
+                price: Price::new(level.price()),
+                quantity: Quantity::new(10),
+                side: Side::Buy,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1616823000000 + id),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+    }
+
+    fn stored(store: &RedbSnapshotStore) -> Vec<(String, u128, u64)> {
+        SnapshotStore::<()>::keys(store)
+            .unwrap()
+            .into_iter()
+            .map(|key| (key.symbol.to_string(), key.price, key.sequence))
+            .collect()
+    }
+
+    #[test]
+    fn test_put_level_keys_by_sequence_and_latest_restores_newest() {
+        let store = RedbSnapshotStore::in_memory().unwrap();
+        let symbol = SymbolKey::from("BTC-USD");
+        let level = PriceLevel::new(100);
+        add(&level, 1);
+        let first = store.put_level(&symbol, &level).unwrap();
+        add(&level, 2);
+        let second = store.put_level(&symbol, &level).unwrap();
+        assert!(second.sequence > first.sequence);
+        assert_eq!(second.price, 100);
+
+        let (key, package) = SnapshotStore::<()>::latest(&store, &symbol, 100)
+            .unwrap()
+            .unwrap();
+        assert_eq!(key, second);
+        assert_eq!(package.snapshot().order_count(), 2);
+
+        let restored: PriceLevel = store.restore_latest(&symbol, 100).unwrap().unwrap();
+        assert_eq!(restored.order_count(), 2);
+        let older: PriceLevelSnapshotPackage = store.get(&first).unwrap().unwrap();
+        assert_eq!(older.snapshot().order_count(), 1);
+    }
+
+    #[test]
+    fn test_missing_level_has_no_latest() {
+        let store = RedbSnapshotStore::in_memory().unwrap();
+        let level: PriceLevel = PriceLevel::new(100);
+        store
+            .put_level(&SymbolKey::from("BTC-USD"), &level)
+            .unwrap();
+
+        let other: Option<PriceLevel> = store
+            .restore_latest(&SymbolKey::from("BTC-USD"), 101)
+            .unwrap();
+        assert!(other.is_none());
+        let other: Option<PriceLevel> = store.restore_latest(&SymbolKey::Id(7), 100).unwrap();
+        assert!(other.is_none());
+        let missing: Option<PriceLevelSnapshotPackage> =
+            store.get(&SnapshotKey::new("ETH-USD", 100, 0)).unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[test]
+    fn test_keys_order_ids_before_names_then_price_and_sequence() {
+        let store = RedbSnapshotStore::in_memory().unwrap();
+        let package = PriceLevel::<()>::new(5).snapshot_package().unwrap();
+        for key in [
+            SnapshotKey::new("ETH-USD", 5, 1),
+            SnapshotKey::new(3u32, 9, 4),
+            SnapshotKey::new("ETH-USD", 4, 2),
+            SnapshotKey::new("BTC-USD", 5, 3),
+            SnapshotKey::new("ETH-USD", 5, 0),
+        ] {
+            store.put(&key, &package).unwrap();
+        }
+        assert_eq!(
+            stored(&store),
+            vec![
+                ("3".to_string(), 9, 4),
+                ("BTC-USD".to_string(), 5, 3),
+                ("ETH-USD".to_string(), 4, 2),
+                ("ETH-USD".to_string(), 5, 0),
+                ("ETH-USD".to_string(), 5, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prune_keep_latest_is_per_level() {
+        let store = RedbSnapshotStore::in_memory().unwrap();
+        let package = PriceLevel::<()>::new(5).snapshot_package().unwrap();
+        for sequence in 0..4 {
+            store
+                .put(&SnapshotKey::new("BTC-USD", 5, sequence), &package)
+                .unwrap();
+            store
+                .put(&SnapshotKey::new("BTC-USD", 6, sequence), &package)
+                .unwrap();
+        }
+        store
+            .put(&SnapshotKey::new("ETH-USD", 5, 0), &package)
+            .unwrap();
+
+        let removed = SnapshotStore::<()>::prune(&store, PrunePolicy::KeepLatest(2)).unwrap();
+        assert_eq!(removed, 4);
+        assert_eq!(
+            stored(&store),
+            vec![
+                ("BTC-USD".to_string(), 5, 2),
+                ("BTC-USD".to_string(), 5, 3),
+                ("BTC-USD".to_string(), 6, 2),
+                ("BTC-USD".to_string(), 6, 3),
+                ("ETH-USD".to_string(), 5, 0),
+            ]
+        );
+
+        assert_eq!(
+            SnapshotStore::<()>::prune(&store, PrunePolicy::KeepLatest(0)).unwrap(),
+            5
+        );
+        assert!(stored(&store).is_empty());
+    }
+
+    #[test]
+    fn test_prune_below_sequence() {
+        let store = RedbSnapshotStore::in_memory().unwrap();
+        let package = PriceLevel::<()>::new(5).snapshot_package().unwrap();
+        for sequence in [1, 5, 9] {
+            store
+                .put(&SnapshotKey::new("BTC-USD", 5, sequence), &package)
+                .unwrap();
+        }
+        let removed = SnapshotStore::<()>::prune(&store, PrunePolicy::BelowSequence(5)).unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(
+            stored(&store),
+            vec![("BTC-USD".to_string(), 5, 5), ("BTC-USD".to_string(), 5, 9)]
+        );
+    }
+
+    #[test]
+    fn test_file_store_survives_reopen() {
+        let dir =
+            std::env::temp_dir().join(format!("pricelevel-redb-store-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("snapshots.redb");
+        let symbol = SymbolKey::from("BTC-USD");
+        {
+            let store = RedbSnapshotStore::open(&path).unwrap();
+            let level = PriceLevel::new(100);
+            add(&level, 1);
+            store.put_level(&symbol, &level).unwrap();
+        }
+
+        let store = RedbSnapshotStore::open(&path).unwrap();
+        let restored: PriceLevel = store.restore_latest(&symbol, 100).unwrap().unwrap();
+        assert_eq!(restored.order_count(), 1);
+    }
+
+    #[test]
+    fn test_restore_rejects_package_with_bad_checksum() {
+        let store = RedbSnapshotStore::in_memory().unwrap();
+        let level = PriceLevel::new(100);
+        add(&level, 1);
+        let package = level.snapshot_package().unwrap();
+        let original = package.to_json().unwrap();
+        let json = original.replace("\"quantity\":10", "\"quantity\":11");
+        assert_ne!(json, original);
+        let tampered = PriceLevelSnapshotPackage::<()>::from_json(&json).unwrap();
+        store
+            .put(&SnapshotKey::new("BTC-USD", 100, 1), &tampered)
+            .unwrap();
+
+        let result: Result<Option<PriceLevel>, _> =
+            store.restore_latest(&SymbolKey::from("BTC-USD"), 100);
+        assert!(matches!(
+            result,
+            Err(PriceLevelError::ChecksumMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, OrderType, OrderUpdate, Side, TimeInForce};
+    use crate::price_level::{DeterministicReplay, PriceLevel, PriceLevelSnapshotPackage};
+    use crate::utils::{Clock, OrderIdGenerator, Price, Quantity};
+
+    /// Replay a fixed input stream, returning the serialized trades and the
+    /// serialized snapshot package it ends with.
+    fn replay(seed: u64) -> (Vec<String>, String) {
+        let replay = DeterministicReplay::new(seed);
+        let level: PriceLevel = PriceLevel::with_config(100, replay.config());
+        level.set_clock_stamping(true);
+        level.set_trade_stamping(true);
+
+        let mut resting = Vec::new();
+        for quantity in [5, 8, 13] {
+            let id = replay.order_ids().next_id();
+            level
+                .add_order(OrderType::IcebergOrder {
+                    id,
+                    price: Price::new(100),
+                    visible_quantity: Quantity::new(quantity),
+                    hidden_quantity: Quantity::new(quantity),
+                    side: Side::Sell,
+                    user_id: Hash32::zero(),
+                    timestamp: replay.next_timestamp(),
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                })
+                .unwrap();
+            resting.push(id);
+        }
+
+        let mut trades = Vec::new();
+        for quantity in [7, 11] {
+            let result = level.match_order(
+                quantity,
+                replay.order_ids().next_id(),
+                TimeInForce::Ioc,
+                TakerKind::Standard,
+                replay.next_timestamp(),
+                replay.trade_ids(),
+            );
+            trades.push(serde_json::to_string(result.trades()).unwrap());
+        }
+        replay.next_timestamp();
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: resting[2],
+            })
+            .unwrap();
+        assert_eq!(level.stats().clock().now(), replay.clock().now());
+
+        let package = PriceLevelSnapshotPackage::new(level.snapshot()).unwrap();
+        (trades, package.to_json().unwrap())
+    }
+
+    #[test]
+    fn test_replays_of_one_stream_are_byte_identical() {
+        let first = replay(11);
+        assert_eq!(first, replay(11));
+        assert_ne!(first.0, replay(12).0);
+    }
+
+    #[test]
+    fn test_order_and_trade_ids_never_collide() {
+        let replay = DeterministicReplay::new(3);
+        let order_id = replay.order_ids().next_id();
+        let (trade_id, _) = crate::ExecutionIdGenerator::next_execution(replay.trade_ids());
+        assert_ne!(order_id, trade_id);
+        assert_eq!(DeterministicReplay::new(3).order_ids().next_id(), order_id);
+    }
+}
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{PriceLevel, PriceLevelConfig};
+    use crate::utils::{ManualClock, Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    fn order(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn take(level: &PriceLevel, taker_id: u64, quantity: u64, tif: TimeInForce) -> u64 {
+        level
+            .match_order(
+                quantity,
+                Id::from_u64(taker_id),
+                tif,
+                TakerKind::Standard,
+                TimestampMs::new(1616823001000),
+                &SequentialIdGenerator::new(0),
+            )
+            .executed_quantity()
+            .unwrap()
+            .as_u64()
+    }
+
+    fn level_with_clock(clock: Arc<ManualClock>) -> PriceLevel {
+        let level = PriceLevel::with_config(10000, PriceLevelConfig::new().with_clock(clock));
+        level.add_order(order(1, 10)).unwrap();
+        level.add_order(order(2, 10)).unwrap();
+        level
+    }
+
+    #[test]
+    fn test_reserved_quantity_is_withheld_until_committed() {
+        let level = level_with_clock(Arc::new(ManualClock::new(TimestampMs::new(0))));
+        let guard = level.reserve(15).unwrap();
+        assert_eq!(guard.quantity(), 15);
+        assert_eq!(level.reserved_quantity(), 15);
+
+        // Another taker only reaches the unreserved depth.
+        assert_eq!(take(&level, 100, 20, TimeInForce::Ioc), 5);
+        assert_eq!(take(&level, 101, 1, TimeInForce::Fok), 0);
+        assert_eq!(level.visible_quantity(), 15);
+
+        let result = guard
+            .commit(
+                Id::from_u64(102),
+                TimestampMs::new(1616823002000),
+                &SequentialIdGenerator::new(10),
+            )
+            .unwrap();
+        assert!(result.is_complete());
+        assert_eq!(result.executed_quantity().unwrap().as_u64(), 15);
+        assert_eq!(level.reserved_quantity(), 0);
+        assert_eq!(level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_rollback_and_drop_release_the_reservation() {
+        let level = level_with_clock(Arc::new(ManualClock::new(TimestampMs::new(0))));
+        assert!(matches!(
+            level.reserve(0),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert!(matches!(
+            level.reserve(21),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+
+        let first = level.reserve(12).unwrap();
+        let second = level.reserve(8).unwrap();
+        assert_ne!(first.id(), second.id());
+        assert!(level.reserve(1).is_err(), "all visible depth is reserved");
+
+        first.rollback();
+        assert_eq!(level.reserved_quantity(), 8);
+        drop(second);
+        assert_eq!(level.reserved_quantity(), 0);
+        assert_eq!(take(&level, 100, 20, TimeInForce::Fok), 20);
+    }
+
+    #[test]
+    fn test_expired_reservation_is_released_and_cannot_commit() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(1_000)));
+        let level = level_with_clock(Arc::clone(&clock));
+        let guard = level.reserve_with_timeout(20, 50).unwrap();
+        assert_eq!(guard.expires_at(), TimestampMs::new(1_050));
+        assert!(!guard.is_expired());
+        assert_eq!(take(&level, 100, 5, TimeInForce::Ioc), 0);
+
+        clock.advance(Duration::from_millis(50));
+        assert!(guard.is_expired());
+        // The next sweep releases the expired earmark and trades through it.
+        assert_eq!(take(&level, 101, 5, TimeInForce::Ioc), 5);
+        assert_eq!(level.reserved_quantity(), 0);
+
+        let err = guard
+            .commit(
+                Id::from_u64(102),
+                TimestampMs::new(1616823002000),
+                &SequentialIdGenerator::new(0),
+            )
+            .unwrap_err();
+        assert!(matches!(err, PriceLevelError::InvalidOperation { .. }));
+        assert_eq!(level.visible_quantity(), 15);
+    }
+
+    #[test]
+    fn test_concurrent_takers_never_consume_the_reservation() {
+        let level = Arc::new(PriceLevel::new(10000));
+        for id in 0..40 {
+            level.add_order(order(id, 5)).unwrap();
+        }
+        let guard = level.reserve(50).unwrap();
+
+        let handles: Vec<_> = (0..4_u64)
+            .map(|thread_id| {
+                let level = Arc::clone(&level);
+                thread::spawn(move || {
+                    (0..20)
+                        .map(|i| take(&level, 1_000 + thread_id * 100 + i, 3, TimeInForce::Ioc))
+                        .sum::<u64>()
+                })
+            })
+            .collect();
+        let taken: u64 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+        assert_eq!(taken, 150);
+        assert_eq!(level.visible_quantity(), 50);
+
+        let result = guard
+            .commit(
+                Id::from_u64(9_999),
+                TimestampMs::new(1616823002000),
+                &SequentialIdGenerator::new(0),
+            )
+            .unwrap();
+        assert_eq!(result.executed_quantity().unwrap().as_u64(), 50);
+        assert_eq!(level.order_count(), 0);
+    }
+}
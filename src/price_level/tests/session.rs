@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome};
+    use crate::price_level::{
+        MultiBook, PriceLevel, PriceLevelConfig, SessionTag, SymbolKey, TokenBucketRateLimiter,
+    };
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::sync::Arc;
+
+    fn tagged(id: u64, price: u128, session: Option<u64>) -> OrderType<SessionTag> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(10),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: SessionTag {
+                session_id: session,
+            },
+        }
+    }
+
+    fn ids(orders: &[Arc<OrderType<SessionTag>>]) -> Vec<Id> {
+        orders.iter().map(|order| order.id()).collect()
+    }
+
+    #[test]
+    fn test_cancel_session_removes_only_its_orders() {
+        let level: PriceLevel<SessionTag> = PriceLevel::new(100);
+        level.add_order(tagged(1, 100, Some(7))).unwrap();
+        level.add_order(tagged(2, 100, Some(8))).unwrap();
+        level.add_order(tagged(3, 100, Some(7))).unwrap();
+        level.add_order(tagged(4, 100, None)).unwrap();
+
+        let cancelled = level.cancel_session(7).unwrap();
+        assert_eq!(ids(&cancelled), vec![Id::from_u64(1), Id::from_u64(3)]);
+        assert_eq!(
+            ids(&level.snapshot_by_insertion_seq()),
+            vec![Id::from_u64(2), Id::from_u64(4)]
+        );
+        assert_eq!(level.visible_quantity(), 20);
+        assert_eq!(level.order_count(), 2);
+        assert_eq!(level.stats().orders_removed(), 2);
+
+        assert!(level.cancel_session(7).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cancel_session_bypasses_rate_limiter() {
+        let level: PriceLevel<SessionTag> = PriceLevel::new(100);
+        level.add_order(tagged(1, 100, Some(7))).unwrap();
+        level.add_order(tagged(2, 100, Some(7))).unwrap();
+        level.set_rate_limiter(Arc::new(TokenBucketRateLimiter::new(
+            1,
+            0,
+            |tag: &SessionTag| tag.session_id,
+        )));
+        // The session's single token goes on one cancel...
+        assert!(matches!(
+            level
+                .update_order(OrderUpdate::Cancel {
+                    order_id: Id::from_u64(1),
+                })
+                .unwrap(),
+            UpdateOutcome::Cancelled(..)
+        ));
+        assert!(matches!(
+            level.update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(2),
+            }),
+            Err(PriceLevelError::RateLimited { .. })
+        ));
+        // ...yet the disconnect still clears the rest.
+        assert_eq!(
+            ids(&level.cancel_session(7).unwrap()),
+            vec![Id::from_u64(2)]
+        );
+        assert_eq!(level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_multi_book_cancel_session_sweeps_every_symbol() {
+        let book: MultiBook<SessionTag> = MultiBook::new();
+        book.register("ETH-USD", PriceLevelConfig::new()).unwrap();
+        book.register(3_u32, PriceLevelConfig::new()).unwrap();
+        book.add_order("ETH-USD", tagged(1, 101, Some(7))).unwrap();
+        book.add_order("ETH-USD", tagged(2, 100, Some(7))).unwrap();
+        book.add_order("ETH-USD", tagged(3, 100, Some(8))).unwrap();
+        book.add_order(3_u32, tagged(4, 50, Some(7))).unwrap();
+
+        let cancellation = book.cancel_session(7);
+        assert!(cancellation.is_complete());
+        let cancelled: Vec<(SymbolKey, Id)> = cancellation
+            .cancelled
+            .into_iter()
+            .map(|(symbol, order)| (symbol, order.id()))
+            .collect();
+        assert_eq!(
+            cancelled,
+            vec![
+                (SymbolKey::Id(3), Id::from_u64(4)),
+                (SymbolKey::from("ETH-USD"), Id::from_u64(2)),
+                (SymbolKey::from("ETH-USD"), Id::from_u64(1)),
+            ]
+        );
+        assert_eq!(book.statistics("ETH-USD").unwrap().order_count, 1);
+        assert_eq!(book.statistics(3_u32).unwrap().order_count, 0);
+    }
+
+    #[test]
+    fn test_multi_book_cancel_session_goes_past_a_failed_level() {
+        let book: MultiBook<SessionTag> = MultiBook::new();
+        book.register("ETH-USD", PriceLevelConfig::new()).unwrap();
+        book.add_order("ETH-USD", tagged(1, 99, Some(7))).unwrap();
+        book.add_order("ETH-USD", tagged(2, 100, Some(7))).unwrap();
+        book.add_order("ETH-USD", tagged(3, 101, Some(7))).unwrap();
+        book.level("ETH-USD", 100).unwrap().test_poison_guard();
+
+        let cancellation = book.cancel_session(7);
+        assert!(!cancellation.is_complete());
+        let cancelled: Vec<Id> = cancellation
+            .cancelled
+            .iter()
+            .map(|(_, order)| order.id())
+            .collect();
+        assert_eq!(cancelled, vec![Id::from_u64(1), Id::from_u64(3)]);
+        assert_eq!(cancellation.failed.len(), 1);
+        let (symbol, price, err) = &cancellation.failed[0];
+        assert_eq!(*symbol, SymbolKey::from("ETH-USD"));
+        assert_eq!(*price, 100);
+        assert!(matches!(err, PriceLevelError::InvalidOperation { .. }));
+    }
+}
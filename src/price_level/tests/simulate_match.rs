@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::{MatchResult, TakerKind};
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{MatchingPolicy, PriceLevel};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+
+    fn standard(id: u64, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            quantity: Quantity::new(quantity),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn iceberg(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(100),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn resting() -> PriceLevel {
+        let level = PriceLevel::new(100);
+        level.add_order(iceberg(1, 4, 8)).unwrap();
+        level.add_order(standard(2, 6)).unwrap();
+        level.add_order(standard(3, 10)).unwrap();
+        level
+    }
+
+    fn run(level: &PriceLevel, quantity: u64, tif: TimeInForce, simulate: bool) -> MatchResult {
+        let timestamp = TimestampMs::new(1616823001000);
+        let ids = SequentialIdGenerator::new(0);
+        if simulate {
+            level.simulate_match(
+                quantity,
+                Id::from_u64(99),
+                tif,
+                TakerKind::Standard,
+                timestamp,
+                &ids,
+            )
+        } else {
+            level.match_order(
+                quantity,
+                Id::from_u64(99),
+                tif,
+                TakerKind::Standard,
+                timestamp,
+                &ids,
+            )
+        }
+    }
+
+    fn summary(result: &MatchResult) -> (Vec<(Id, u64)>, u64, Vec<Id>) {
+        (
+            result
+                .trades()
+                .as_slice()
+                .iter()
+                .map(|trade| (trade.maker_order_id(), trade.quantity().as_u64()))
+                .collect(),
+            result.remaining_quantity().as_u64(),
+            result.filled_order_ids().to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_simulation_matches_the_real_sweep_without_mutating() {
+        for policy in [
+            MatchingPolicy::Fifo,
+            MatchingPolicy::ProRata,
+            MatchingPolicy::SizeTimePriority,
+        ] {
+            let level = resting();
+            level.set_matching_policy(policy);
+            let sequence = level.mutation_sequence();
+            let orders = level.snapshot_by_insertion_seq();
+
+            let simulated = run(&level, 15, TimeInForce::Ioc, true);
+            assert_eq!(level.mutation_sequence(), sequence);
+            assert_eq!(level.snapshot_by_insertion_seq(), orders);
+            assert_eq!(level.visible_quantity(), 20);
+            assert_eq!(level.hidden_quantity(), 8);
+            assert_eq!(level.stats().orders_executed(), 0);
+
+            let executed = run(&level, 15, TimeInForce::Ioc, false);
+            assert_eq!(summary(&simulated), summary(&executed), "{policy}");
+            assert_eq!(
+                simulated.status_transitions(),
+                executed.status_transitions()
+            );
+            assert_eq!(simulated.refreshes(), executed.refreshes());
+        }
+    }
+
+    #[test]
+    fn test_simulation_holds_back_reserved_depth() {
+        let level = resting();
+        let guard = level.reserve(12).unwrap();
+
+        let simulated = run(&level, 20, TimeInForce::Ioc, true);
+        assert_eq!(simulated.executed_quantity().unwrap(), Quantity::new(16));
+        assert_eq!(simulated.remaining_quantity(), Quantity::new(4));
+        assert_eq!(level.reserved_quantity(), 12);
+        assert!(run(&level, 20, TimeInForce::Fok, true).was_killed());
+
+        let executed = run(&level, 20, TimeInForce::Ioc, false);
+        assert_eq!(summary(&simulated), summary(&executed));
+        drop(guard);
+    }
+
+    #[test]
+    fn test_simulated_fill_or_kill_is_killed_alike() {
+        let level = resting();
+        let simulated = run(&level, 40, TimeInForce::Fok, true);
+        assert!(simulated.trades().as_slice().is_empty());
+        assert_eq!(
+            simulated.outcome(),
+            run(&level, 40, TimeInForce::Fok, false).outcome()
+        );
+        assert_eq!(level.order_count(), 3);
+
+        let simulated = run(&level, 28, TimeInForce::Fok, true);
+        assert_eq!(simulated.remaining_quantity(), Quantity::new(0));
+        assert_eq!(level.order_count(), 3);
+    }
+}
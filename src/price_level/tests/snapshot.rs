@@ -47,7 +47,7 @@ mod tests {
         package.validate().expect("Package validation failed");
 
         let json = package.to_json().expect("Failed to serialize package");
-        let restored_package =
+        let restored_package: PriceLevelSnapshotPackage =
             PriceLevelSnapshotPackage::from_json(&json).expect("Failed to deserialize package");
 
         restored_package
@@ -92,8 +92,9 @@ mod tests {
 
         let tampered_json = serde_json::to_string(&value).expect("JSON serialization failed");
 
-        let tampered_package = PriceLevelSnapshotPackage::from_json(&tampered_json)
-            .expect("Deserialization should still succeed");
+        let tampered_package: PriceLevelSnapshotPackage =
+            PriceLevelSnapshotPackage::from_json(&tampered_json)
+                .expect("Deserialization should still succeed");
 
         let err = tampered_package
             .validate()
@@ -141,7 +142,7 @@ mod tests {
         assert_eq!(package.version(), SNAPSHOT_FORMAT_VERSION);
 
         let json = package.to_json().expect("Failed to serialize package");
-        let restored_package =
+        let restored_package: PriceLevelSnapshotPackage =
             PriceLevelSnapshotPackage::from_json(&json).expect("Failed to deserialize package");
         let restored = restored_package
             .into_snapshot()
@@ -174,7 +175,7 @@ mod tests {
         }
         let v1_json = serde_json::to_string(&value).expect("JSON serialization failed");
 
-        let v1_package = PriceLevelSnapshotPackage::from_json(&v1_json)
+        let v1_package: PriceLevelSnapshotPackage = PriceLevelSnapshotPackage::from_json(&v1_json)
             .expect("Deserialization should still succeed");
         assert_eq!(v1_package.version(), 1);
 
@@ -192,7 +193,7 @@ mod tests {
         }
 
         // `into_snapshot` (used by the restore path) must reject it too.
-        let v1_package_again = PriceLevelSnapshotPackage::from_json(&v1_json)
+        let v1_package_again = PriceLevelSnapshotPackage::<()>::from_json(&v1_json)
             .expect("Deserialization should still succeed");
         let err = v1_package_again
             .into_snapshot()
@@ -202,8 +203,9 @@ mod tests {
 
     #[test]
     fn test_snapshot_v3_roundtrips_degraded_and_non_degraded() {
-        // Issue #129: new packages are v3 and round-trip BOTH a non-degraded
-        // (8-field statistics) and a degraded (9-field statistics) payload.
+        // Issue #129: new packages round-trip BOTH a non-degraded (8-field
+        // statistics) and a degraded (9-field statistics) payload. Both shapes
+        // were introduced by v3 and are still written under the current version.
         use crate::price_level::PriceLevelStatistics;
 
         // Non-degraded.
@@ -220,9 +222,8 @@ mod tests {
         .expect("snapshot");
         let package = PriceLevelSnapshotPackage::new(snap).expect("package");
         assert_eq!(package.version(), SNAPSHOT_FORMAT_VERSION);
-        assert_eq!(package.version(), 3);
         let json = package.to_json().expect("to_json");
-        let restored = PriceLevelSnapshotPackage::from_json(&json)
+        let restored = PriceLevelSnapshotPackage::<()>::from_json(&json)
             .expect("from_json")
             .into_snapshot()
             .expect("v3 non-degraded must validate + restore");
@@ -239,13 +240,13 @@ mod tests {
         )
         .expect("snapshot");
         let package = PriceLevelSnapshotPackage::new(snap).expect("package");
-        assert_eq!(package.version(), 3);
+        assert_eq!(package.version(), SNAPSHOT_FORMAT_VERSION);
         let json = package.to_json().expect("to_json");
         assert!(
             json.contains("stats_degraded"),
             "a degraded v3 payload carries the 9th field"
         );
-        let restored = PriceLevelSnapshotPackage::from_json(&json)
+        let restored = PriceLevelSnapshotPackage::<()>::from_json(&json)
             .expect("from_json")
             .into_snapshot()
             .expect("v3 degraded must validate + restore");
@@ -255,6 +256,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_snapshot_mutation_sequence_roundtrip_and_legacy_shape() {
+        // v4 writes `mutation_sequence` only when set: a sequenced snapshot
+        // round-trips it, and an unsequenced one keeps the v3 byte shape.
+        let snap = PriceLevelSnapshot::with_orders(Price::new(10), create_sample_orders())
+            .expect("snapshot")
+            .with_mutation_sequence(42);
+        let package = PriceLevelSnapshotPackage::new(snap).expect("package");
+        assert_eq!(package.version(), SNAPSHOT_FORMAT_VERSION);
+        let json = package.to_json().expect("to_json");
+        assert!(json.contains("\"mutation_sequence\":42"));
+        let restored = PriceLevelSnapshotPackage::<()>::from_json(&json)
+            .expect("from_json")
+            .into_snapshot()
+            .expect("v4 sequenced must validate + restore");
+        assert_eq!(restored.mutation_sequence(), 42);
+
+        let plain = PriceLevelSnapshot::with_orders(Price::new(10), create_sample_orders())
+            .expect("snapshot");
+        assert_eq!(plain.mutation_sequence(), 0);
+        let json = serde_json::to_string(&plain).expect("serialize");
+        assert!(!json.contains("mutation_sequence"));
+
+        // A v3-labelled package (no sequence field) still validates.
+        let package = PriceLevelSnapshotPackage::new(plain).expect("package");
+        let mut value: Value =
+            serde_json::from_str(&package.to_json().expect("to_json")).expect("parse");
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("version".to_string(), Value::Number(3u32.into()));
+        }
+        let v3_json = serde_json::to_string(&value).expect("reserialize");
+        let restored = PriceLevelSnapshotPackage::<()>::from_json(&v3_json)
+            .expect("from_json")
+            .into_snapshot()
+            .expect("legacy v3 package must validate + restore");
+        assert_eq!(restored.mutation_sequence(), 0);
+    }
+
     #[test]
     fn test_snapshot_v2_legacy_package_restores() {
         // Issue #129: a legacy v2 package (8-field statistics, checksum over the
@@ -273,7 +312,8 @@ mod tests {
         }
         let v2_json = serde_json::to_string(&value).expect("reserialize");
 
-        let v2_package = PriceLevelSnapshotPackage::from_json(&v2_json).expect("from_json");
+        let v2_package: PriceLevelSnapshotPackage =
+            PriceLevelSnapshotPackage::from_json(&v2_json).expect("from_json");
         assert_eq!(v2_package.version(), 2);
         let restored = v2_package
             .into_snapshot()
@@ -281,9 +321,50 @@ mod tests {
         assert!(!restored.statistics().stats_degraded());
     }
 
+    #[test]
+    fn test_checksum_crc32_skips_hidden_orders() {
+        let order = |id: u64, quantity: u64, hidden: bool| {
+            Arc::new(if hidden {
+                OrderType::Hidden {
+                    id: Id::from_u64(id),
+                    price: Price::new(100),
+                    quantity: Quantity::new(quantity),
+                    side: Side::Sell,
+                    user_id: Hash32::zero(),
+                    timestamp: TimestampMs::new(id),
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                }
+            } else {
+                OrderType::Standard {
+                    id: Id::from_u64(id),
+                    price: Price::new(100),
+                    quantity: Quantity::new(quantity),
+                    side: Side::Sell,
+                    user_id: Hash32::zero(),
+                    timestamp: TimestampMs::new(id),
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                }
+            })
+        };
+        let snapshot = PriceLevelSnapshot::from_raw_parts(
+            Price::new(100),
+            Quantity::new(12),
+            Quantity::new(9),
+            3,
+            vec![order(1, 5, false), order(2, 9, true), order(3, 7, false)],
+        );
+
+        // "100" "5" "100" "7"
+        assert_eq!(snapshot.checksum_crc32(10), 3_801_804_843);
+        assert_eq!(snapshot.checksum_crc32(1), 3_293_403_032);
+        assert_eq!(snapshot.checksum_crc32(0), 0);
+    }
+
     #[test]
     fn test_new() {
-        let snapshot = PriceLevelSnapshot::new(Price::new(1000));
+        let snapshot: PriceLevelSnapshot = PriceLevelSnapshot::new(Price::new(1000));
         assert_eq!(snapshot.price().as_u128(), 1000);
         assert_eq!(snapshot.visible_quantity().as_u64(), 0);
         assert_eq!(snapshot.hidden_quantity().as_u64(), 0);
@@ -293,7 +374,7 @@ mod tests {
 
     #[test]
     fn test_default() {
-        let snapshot = PriceLevelSnapshot::default();
+        let snapshot: PriceLevelSnapshot = PriceLevelSnapshot::default();
         assert_eq!(snapshot.price().as_u128(), 0);
         assert_eq!(snapshot.visible_quantity().as_u64(), 0);
         assert_eq!(snapshot.hidden_quantity().as_u64(), 0);
@@ -303,7 +384,7 @@ mod tests {
 
     #[test]
     fn test_total_quantity() {
-        let snapshot = PriceLevelSnapshot::from_raw_parts(
+        let snapshot: PriceLevelSnapshot = PriceLevelSnapshot::from_raw_parts(
             Price::new(1000),
             Quantity::new(50),
             Quantity::new(150),
@@ -363,7 +444,7 @@ mod tests {
 
     #[test]
     fn test_display() {
-        let snapshot = PriceLevelSnapshot::from_raw_parts(
+        let snapshot: PriceLevelSnapshot = PriceLevelSnapshot::from_raw_parts(
             Price::new(1000),
             Quantity::new(50),
             Quantity::new(150),
@@ -382,7 +463,7 @@ mod tests {
     fn test_from_str() {
         let input =
             "PriceLevelSnapshot:price=1000;visible_quantity=50;hidden_quantity=150;order_count=2";
-        let snapshot = PriceLevelSnapshot::from_str(input).unwrap();
+        let snapshot: PriceLevelSnapshot = PriceLevelSnapshot::from_str(input).unwrap();
 
         assert_eq!(snapshot.price().as_u128(), 1000);
         assert_eq!(snapshot.visible_quantity().as_u64(), 50);
@@ -394,27 +475,27 @@ mod tests {
     #[test]
     fn test_from_str_invalid_format() {
         let input = "InvalidFormat";
-        let result = PriceLevelSnapshot::from_str(input);
+        let result = PriceLevelSnapshot::<()>::from_str(input);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_from_str_missing_field() {
         let input = "PriceLevelSnapshot:price=1000;visible_quantity=50;hidden_quantity=150";
-        let result = PriceLevelSnapshot::from_str(input);
+        let result = PriceLevelSnapshot::<()>::from_str(input);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_from_str_invalid_field_value() {
         let input = "PriceLevelSnapshot:price=invalid;visible_quantity=50;hidden_quantity=150;order_count=2";
-        let result = PriceLevelSnapshot::from_str(input);
+        let result = PriceLevelSnapshot::<()>::from_str(input);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_roundtrip_display_fromstr() {
-        let original = PriceLevelSnapshot::from_raw_parts(
+        let original: PriceLevelSnapshot = PriceLevelSnapshot::from_raw_parts(
             Price::new(1000),
             Quantity::new(50),
             Quantity::new(150),
@@ -423,7 +504,7 @@ mod tests {
         );
 
         let string_representation = original.to_string();
-        let parsed = PriceLevelSnapshot::from_str(&string_representation).unwrap();
+        let parsed = PriceLevelSnapshot::<()>::from_str(&string_representation).unwrap();
 
         assert_eq!(parsed.price(), original.price());
         assert_eq!(parsed.visible_quantity(), original.visible_quantity());
@@ -436,7 +517,7 @@ mod tests {
     #[test]
     fn test_snapshot_serialization_fields() {
         // Create a snapshot with specific field values
-        let snapshot = PriceLevelSnapshot::from_raw_parts(
+        let snapshot: PriceLevelSnapshot = PriceLevelSnapshot::from_raw_parts(
             Price::new(10000),
             Quantity::new(200),
             Quantity::new(300),
@@ -587,6 +668,120 @@ mod tests {
             panic!("Expected IcebergOrder");
         }
     }
+    #[test]
+    fn test_snapshot_package_checksum_algorithms_roundtrip() {
+        use crate::price_level::ChecksumAlgorithm;
+
+        for (algorithm, digest_len) in [
+            (ChecksumAlgorithm::Crc32c, 8),
+            (ChecksumAlgorithm::XxHash64, 16),
+            (ChecksumAlgorithm::Sha256, 64),
+        ] {
+            let snapshot = PriceLevelSnapshot::with_orders(Price::new(42), create_sample_orders())
+                .expect("Failed to create snapshot with orders");
+            let package = PriceLevelSnapshotPackage::with_checksum_algorithm(snapshot, algorithm)
+                .expect("Failed to create package");
+            assert_eq!(package.checksum_algorithm(), algorithm);
+            assert_eq!(package.checksum().len(), digest_len);
+
+            let json = package.to_json().expect("Failed to serialize package");
+            let value: Value = serde_json::from_str(&json).expect("JSON parsing failed");
+            assert_eq!(
+                value["checksum_algorithm"],
+                Value::String(algorithm.to_string().to_lowercase())
+            );
+
+            let restored: PriceLevelSnapshotPackage =
+                PriceLevelSnapshotPackage::from_json(&json).expect("Failed to deserialize package");
+            assert_eq!(restored.checksum_algorithm(), algorithm);
+            let snapshot = restored.into_snapshot().expect("Checksum should validate");
+            assert_eq!(snapshot.order_count(), 2);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_package_checksum_algorithms_detect_corruption() {
+        use crate::price_level::ChecksumAlgorithm;
+
+        for algorithm in [
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::XxHash64,
+            ChecksumAlgorithm::Sha256,
+        ] {
+            let snapshot = PriceLevelSnapshot::with_orders(Price::new(42), create_sample_orders())
+                .expect("Failed to create snapshot with orders");
+            let json = PriceLevelSnapshotPackage::with_checksum_algorithm(snapshot, algorithm)
+                .and_then(|package| package.to_json())
+                .expect("Failed to build package JSON");
+
+            let mut value: Value = serde_json::from_str(&json).expect("JSON parsing failed");
+            value["snapshot"]["price"] = Value::from(43);
+            let corrupted: PriceLevelSnapshotPackage =
+                PriceLevelSnapshotPackage::from_json(&value.to_string())
+                    .expect("Deserialization should still succeed");
+
+            let err = corrupted
+                .validate()
+                .expect_err("Corrupted payload must fail validation");
+            assert!(matches!(err, PriceLevelError::ChecksumMismatch { .. }));
+        }
+    }
+
+    #[test]
+    fn test_snapshot_package_without_checksum_algorithm_defaults_to_sha256() {
+        use crate::price_level::ChecksumAlgorithm;
+
+        // A package written before the header field existed carries a SHA-256
+        // checksum and no `checksum_algorithm` key.
+        let snapshot = PriceLevelSnapshot::with_orders(Price::new(42), create_sample_orders())
+            .expect("Failed to create snapshot with orders");
+        let json = PriceLevelSnapshotPackage::new(snapshot)
+            .and_then(|package| package.to_json())
+            .expect("Failed to build package JSON");
+        let mut value: Value = serde_json::from_str(&json).expect("JSON parsing failed");
+        value
+            .as_object_mut()
+            .expect("package is a JSON object")
+            .remove("checksum_algorithm");
+
+        let legacy: PriceLevelSnapshotPackage =
+            PriceLevelSnapshotPackage::from_json(&value.to_string())
+                .expect("Legacy package should deserialize");
+        assert_eq!(legacy.checksum_algorithm(), ChecksumAlgorithm::Sha256);
+        legacy
+            .validate()
+            .expect("Legacy SHA-256 package should validate");
+    }
+
+    #[test]
+    fn test_checksum_algorithm_display_and_from_str() {
+        use crate::price_level::ChecksumAlgorithm;
+
+        for algorithm in [
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::XxHash64,
+            ChecksumAlgorithm::Sha256,
+        ] {
+            let parsed = ChecksumAlgorithm::from_str(&algorithm.to_string())
+                .expect("Display output should parse");
+            assert_eq!(parsed, algorithm);
+        }
+        assert_eq!(
+            ChecksumAlgorithm::from_str("sha-256").unwrap(),
+            ChecksumAlgorithm::Sha256
+        );
+        assert_eq!(
+            ChecksumAlgorithm::from_str("xxh64").unwrap(),
+            ChecksumAlgorithm::XxHash64
+        );
+        assert!(matches!(
+            ChecksumAlgorithm::from_str("md5"),
+            Err(PriceLevelError::ParseError { .. })
+        ));
+        assert_eq!(ChecksumAlgorithm::default(), ChecksumAlgorithm::Sha256);
+        assert!(ChecksumAlgorithm::Sha256.is_cryptographic());
+        assert!(!ChecksumAlgorithm::Crc32c.is_cryptographic());
+    }
 }
 
 #[cfg(test)]
@@ -766,7 +961,7 @@ mod pricelevel_snapshot_serialization_tests {
             "PriceLevelSnapshot:price=1000;visible_quantity=15;hidden_quantity=15;order_count=3";
 
         // Parse from string
-        let snapshot =
+        let snapshot: PriceLevelSnapshot =
             PriceLevelSnapshot::from_str(input).expect("Failed to parse PriceLevelSnapshot");
 
         // Verify basic fields
@@ -783,29 +978,29 @@ mod pricelevel_snapshot_serialization_tests {
     fn test_snapshot_string_format_invalid_inputs() {
         // Test missing price field
         let input = "PriceLevelSnapshot:visible_quantity=15;hidden_quantity=15;order_count=3";
-        let result = PriceLevelSnapshot::from_str(input);
+        let result = PriceLevelSnapshot::<()>::from_str(input);
         assert!(result.is_err());
 
         // Test invalid prefix
         let input = "InvalidPrefix:price=1000;visible_quantity=15;hidden_quantity=15;order_count=3";
-        let result = PriceLevelSnapshot::from_str(input);
+        let result = PriceLevelSnapshot::<()>::from_str(input);
         assert!(result.is_err());
 
         // Test invalid field value
         let input =
             "PriceLevelSnapshot:price=invalid;visible_quantity=15;hidden_quantity=15;order_count=3";
-        let result = PriceLevelSnapshot::from_str(input);
+        let result = PriceLevelSnapshot::<()>::from_str(input);
         assert!(result.is_err());
 
         // Test missing field separator
         let input =
             "PriceLevelSnapshot:price=1000visible_quantity=15;hidden_quantity=15;order_count=3";
-        let result = PriceLevelSnapshot::from_str(input);
+        let result = PriceLevelSnapshot::<()>::from_str(input);
         assert!(result.is_err());
 
         // Test with unknown field
         let input = "PriceLevelSnapshot:price=1000;visible_quantity=15;hidden_quantity=15;order_count=3;unknown_field=value";
-        let result = PriceLevelSnapshot::from_str(input);
+        let result = PriceLevelSnapshot::<()>::from_str(input);
         // This should still succeed as FromStr implementation doesn't validate for unknown fields
         assert!(result.is_ok());
     }
@@ -813,7 +1008,7 @@ mod pricelevel_snapshot_serialization_tests {
     #[test]
     fn test_snapshot_string_format_roundtrip() {
         // Create a snapshot with only basic fields (no orders)
-        let original = PriceLevelSnapshot::from_raw_parts(
+        let original: PriceLevelSnapshot = PriceLevelSnapshot::from_raw_parts(
             Price::new(1000),
             Quantity::new(15),
             Quantity::new(15),
@@ -825,7 +1020,7 @@ mod pricelevel_snapshot_serialization_tests {
         let string_representation = original.to_string();
 
         // Parse back to snapshot
-        let parsed = PriceLevelSnapshot::from_str(&string_representation)
+        let parsed = PriceLevelSnapshot::<()>::from_str(&string_representation)
             .expect("Failed to parse PriceLevelSnapshot");
 
         // Verify all fields match
@@ -838,7 +1033,7 @@ mod pricelevel_snapshot_serialization_tests {
     #[test]
     fn test_snapshot_edge_cases() {
         // Test with zero values
-        let snapshot = PriceLevelSnapshot::new(Price::new(0));
+        let snapshot: PriceLevelSnapshot = PriceLevelSnapshot::new(Price::new(0));
 
         let json = serde_json::to_string(&snapshot).expect("Failed to serialize");
         let deserialized: PriceLevelSnapshot =
@@ -850,7 +1045,7 @@ mod pricelevel_snapshot_serialization_tests {
         assert_eq!(deserialized.order_count(), 0);
 
         // Test with maximum values
-        let snapshot = PriceLevelSnapshot::from_raw_parts(
+        let snapshot = PriceLevelSnapshot::<()>::from_raw_parts(
             Price::new(u128::MAX),
             Quantity::new(u64::MAX),
             Quantity::new(u64::MAX),
@@ -903,7 +1098,7 @@ mod pricelevel_snapshot_serialization_tests {
     #[test]
     fn test_snapshot_empty_orders() {
         // Test with an empty orders array
-        let snapshot = PriceLevelSnapshot::from_raw_parts(
+        let snapshot: PriceLevelSnapshot = PriceLevelSnapshot::from_raw_parts(
             Price::new(1000),
             Quantity::new(15),
             Quantity::new(15),
@@ -1083,3 +1278,187 @@ mod pricelevel_snapshot_serialization_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod snapshot_validation_tests {
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{
+        ChecksumAlgorithm, PriceLevel, PriceLevelSnapshot, PriceLevelSnapshotPackage,
+        SnapshotFinding, SnapshotValidationReport,
+    };
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use serde_json::Value;
+    use std::sync::Arc;
+
+    fn order(id: u64, price: u128, side: Side) -> Arc<OrderType<()>> {
+        Arc::new(OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(10),
+            side,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        })
+    }
+
+    fn package(orders: Vec<Arc<OrderType<()>>>) -> PriceLevelSnapshotPackage {
+        let snapshot = PriceLevelSnapshot::with_orders(Price::new(1000), orders)
+            .expect("Failed to create snapshot with orders");
+        PriceLevelSnapshotPackage::new(snapshot).expect("Failed to create package")
+    }
+
+    /// Rewrites top-level package fields and snapshot fields in the JSON form,
+    /// optionally re-sealing the checksum over the edited snapshot.
+    fn tamper(
+        package: &PriceLevelSnapshotPackage,
+        package_fields: &[(&str, Value)],
+        snapshot_fields: &[(&str, Value)],
+        reseal: bool,
+    ) -> PriceLevelSnapshotPackage {
+        let json = package.to_json().expect("Failed to serialize package");
+        let mut value: Value = serde_json::from_str(&json).expect("JSON parsing failed");
+        for (field, field_value) in package_fields {
+            value[*field] = field_value.clone();
+        }
+        for (field, field_value) in snapshot_fields {
+            value["snapshot"][*field] = field_value.clone();
+        }
+        let tampered = PriceLevelSnapshotPackage::from_json(&value.to_string())
+            .expect("Deserialization should still succeed");
+        if !reseal {
+            return tampered;
+        }
+        let payload = serde_json::to_vec(tampered.snapshot()).expect("Failed to encode snapshot");
+        value["checksum"] = Value::String(ChecksumAlgorithm::Sha256.digest_hex(&payload));
+        PriceLevelSnapshotPackage::from_json(&value.to_string())
+            .expect("Deserialization should still succeed")
+    }
+
+    #[test]
+    fn test_clean_package_reports_no_findings() {
+        let package = package(vec![order(1, 1000, Side::Buy), order(2, 1000, Side::Buy)]);
+
+        let report = package.validation_report();
+
+        assert!(report.is_clean());
+        assert!(report.is_restorable());
+        assert_eq!(report.order_count(), 2);
+        assert_eq!(report.version(), package.version());
+        assert_eq!(report.checksum_algorithm(), ChecksumAlgorithm::Sha256);
+    }
+
+    #[test]
+    fn test_report_collects_every_order_finding() {
+        let package = package(vec![
+            order(1, 1000, Side::Buy),
+            order(1, 1000, Side::Buy),
+            order(2, 999, Side::Buy),
+            order(3, 1000, Side::Sell),
+        ]);
+
+        let report = package.validation_report();
+
+        assert_eq!(
+            report.findings(),
+            &[
+                SnapshotFinding::DuplicateOrderId {
+                    order_id: Id::from_u64(1)
+                },
+                SnapshotFinding::PriceMismatch {
+                    order_id: Id::from_u64(2),
+                    price: Price::new(999)
+                },
+                SnapshotFinding::SideMismatch {
+                    order_id: Id::from_u64(3),
+                    side: Side::Sell,
+                    level_side: Side::Buy
+                },
+            ]
+        );
+        assert!(!report.is_restorable());
+        assert!(PriceLevel::from_snapshot_package(package).is_err());
+    }
+
+    #[test]
+    fn test_report_flags_version_and_checksum_together() {
+        let package = package(vec![order(1, 1000, Side::Buy)]);
+        let tampered = tamper(
+            &package,
+            &[("version", Value::from(1u32))],
+            &[("visible_quantity", Value::from(99u64))],
+            false,
+        );
+
+        let report = tampered.validation_report();
+
+        let findings = report.findings();
+        assert_eq!(
+            findings[0],
+            SnapshotFinding::UnsupportedVersion { version: 1 }
+        );
+        assert!(matches!(
+            findings[1],
+            SnapshotFinding::ChecksumMismatch { .. }
+        ));
+        assert_eq!(
+            findings[2],
+            SnapshotFinding::VisibleQuantityMismatch {
+                recorded: Quantity::new(99),
+                actual: Quantity::new(10)
+            }
+        );
+        assert!(!report.is_restorable());
+    }
+
+    #[test]
+    fn test_aggregate_mismatch_alone_does_not_block_restore() {
+        let package = package(vec![order(1, 1000, Side::Buy), order(2, 1000, Side::Buy)]);
+        let resealed = tamper(
+            &package,
+            &[],
+            &[
+                ("order_count", Value::from(5u64)),
+                ("hidden_quantity", Value::from(7u64)),
+            ],
+            true,
+        );
+
+        let report = resealed.validation_report();
+
+        assert_eq!(
+            report.findings(),
+            &[
+                SnapshotFinding::OrderCountMismatch {
+                    recorded: 5,
+                    actual: 2
+                },
+                SnapshotFinding::HiddenQuantityMismatch {
+                    recorded: Quantity::new(7),
+                    actual: Quantity::ZERO
+                },
+            ]
+        );
+        assert!(!report.is_clean());
+        assert!(report.is_restorable());
+        let level = PriceLevel::from_snapshot_package(resealed).expect("restore should succeed");
+        assert_eq!(level.order_count(), 2);
+    }
+
+    #[test]
+    fn test_report_serializes_for_tooling() {
+        let package = package(vec![order(1, 1000, Side::Buy), order(1, 1000, Side::Buy)]);
+        let report = package.validation_report();
+
+        let json = serde_json::to_string(&report).expect("Failed to serialize report");
+        assert!(json.contains("\"kind\":\"duplicate_order_id\""));
+        let decoded: SnapshotValidationReport =
+            serde_json::from_str(&json).expect("Failed to deserialize report");
+        assert_eq!(decoded, report);
+
+        let text = report.to_string();
+        assert!(text.contains("1 finding(s)"));
+        assert!(text.contains("duplicate order id"));
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{BookSnapshot, PriceLevel, SnapshotSchedule, SnapshotScheduler};
+    use crate::utils::{ManualClock, Price, Quantity, TimestampMs};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    const EPOCH: u64 = 1_700_000_000_000;
+
+    fn add(level: &PriceLevel, id: u64) {
+        level
+            .add_order(OrderType::Standard {
+                id: Id::from_u64(id),
+                price: Price::new(100),
+                quantity: Quantity::new(10),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(EPOCH),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+    }
+
+    fn clock() -> Arc<ManualClock> {
+        Arc::new(ManualClock::new(TimestampMs::new(EPOCH)))
+    }
+
+    #[test]
+    fn test_interval_snapshots_only_changed_levels() {
+        let clock = clock();
+        let level = PriceLevel::new(100);
+        let (scheduler, receiver) = SnapshotScheduler::with_channel(
+            SnapshotSchedule::new().with_interval(Duration::from_secs(1)),
+            8,
+        );
+        let mut scheduler = scheduler.with_clock(clock.clone());
+
+        // The first poll always publishes.
+        assert!(scheduler.poll(&level));
+        add(&level, 1);
+        assert!(!scheduler.poll(&level));
+        clock.advance(Duration::from_secs(1));
+        assert!(scheduler.poll(&level));
+        // Nothing changed since: an idle level publishes nothing.
+        clock.advance(Duration::from_secs(5));
+        assert!(!scheduler.poll(&level));
+
+        let snapshots: Vec<_> = receiver.try_iter().collect();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[1].order_count(), 1);
+        assert_eq!(scheduler.delivered(), 2);
+        assert_eq!(
+            scheduler.last_snapshot_at(),
+            Some(TimestampMs::new(EPOCH + 1_000))
+        );
+    }
+
+    #[test]
+    fn test_mutation_trigger_coalesces_and_respects_min_interval() {
+        let clock = clock();
+        let level = PriceLevel::new(100);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let mut scheduler = SnapshotScheduler::with_callback(
+            SnapshotSchedule::new()
+                .with_mutations(3)
+                .with_min_interval(Duration::from_millis(100)),
+            move |snapshot| sink.lock().unwrap().push(snapshot),
+        )
+        .with_clock(clock.clone());
+
+        assert!(scheduler.poll(&level));
+        for id in 1..=2 {
+            add(&level, id);
+        }
+        assert!(!scheduler.poll(&level));
+        // A burst well past the trigger is rate-capped, then published once.
+        for id in 3..=10 {
+            add(&level, id);
+        }
+        assert!(!scheduler.poll(&level));
+        clock.advance(Duration::from_millis(100));
+        assert!(scheduler.poll(&level));
+        assert!(!scheduler.poll(&level));
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[1].order_count(), 10);
+    }
+
+    #[test]
+    fn test_full_channel_skips_until_drained() {
+        let level = PriceLevel::new(100);
+        let (scheduler, receiver) =
+            SnapshotScheduler::with_channel(SnapshotSchedule::new().with_mutations(1), 1);
+        let mut scheduler = scheduler.with_clock(clock());
+
+        assert!(scheduler.poll(&level));
+        add(&level, 1);
+        assert!(!scheduler.poll(&level));
+        assert_eq!(scheduler.skipped(), 1);
+
+        assert_eq!(receiver.try_recv().unwrap().order_count(), 0);
+        add(&level, 2);
+        assert!(scheduler.poll(&level));
+        assert_eq!(receiver.try_recv().unwrap().order_count(), 2);
+    }
+
+    #[test]
+    fn test_reset_forces_a_fresh_image() {
+        let level = PriceLevel::new(100);
+        let (scheduler, receiver) = SnapshotScheduler::with_channel(SnapshotSchedule::new(), 4);
+        let mut scheduler = scheduler.with_clock(clock());
+        assert!(scheduler.poll(&level));
+        add(&level, 1);
+        assert!(!scheduler.poll(&level));
+        scheduler.reset();
+        assert!(scheduler.poll(&level));
+        assert_eq!(receiver.len(), 2);
+    }
+
+    #[test]
+    fn test_book_scheduler_counts_the_book_sequence() {
+        let clock = clock();
+        let levels = [PriceLevel::new(100), PriceLevel::new(101)];
+        let (scheduler, receiver) = SnapshotScheduler::<BookSnapshot<()>>::with_channel(
+            SnapshotSchedule::new().with_mutations(5),
+            4,
+        );
+        let mut scheduler = scheduler.with_clock(clock.clone());
+
+        assert!(scheduler.poll_book("BTC-USD", 10, &levels));
+        assert!(!scheduler.poll_book("BTC-USD", 14, &levels));
+        clock.advance(Duration::from_millis(7));
+        assert!(scheduler.poll_book("BTC-USD", 15, &levels));
+
+        let book = receiver.try_iter().last().unwrap();
+        assert_eq!(book.symbol(), "BTC-USD");
+        assert_eq!(book.sequence(), 15);
+        assert_eq!(book.timestamp(), TimestampMs::new(EPOCH + 7));
+        assert_eq!(book.levels().len(), 2);
+    }
+}
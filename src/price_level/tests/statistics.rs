@@ -1,10 +1,11 @@
 #[cfg(test)]
 mod tests {
     use crate::price_level::PriceLevelStatistics;
+    use crate::utils::{ManualClock, TimestampMs};
     use std::str::FromStr;
     use std::sync::Arc;
     use std::thread;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     #[test]
     fn test_new() {
@@ -141,6 +142,48 @@ mod tests {
         assert!(time_since > 0);
     }
 
+    #[test]
+    fn test_manual_clock_drives_elapsed_time() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(1_716_000_000_000)));
+        let stats = PriceLevelStatistics::with_clock(clock.clone());
+        assert_eq!(stats.first_arrival_time(), 1_716_000_000_000);
+
+        assert!(
+            stats
+                .record_execution(10, 100, 0, 1_716_000_000_000)
+                .is_ok()
+        );
+        assert_eq!(stats.time_since_last_execution(), Some(0));
+
+        clock.advance(Duration::from_millis(1_500));
+        assert_eq!(stats.time_since_last_execution(), Some(1_500));
+        assert_eq!(stats.stats_window(Duration::from_secs(5)).executions, 1);
+
+        clock.advance(Duration::from_secs(120));
+        assert_eq!(stats.stats_window(Duration::from_secs(5)).executions, 0);
+
+        stats.reset();
+        assert_eq!(stats.first_arrival_time(), 1_716_000_121_500);
+    }
+
+    #[test]
+    fn test_clock_behind_last_execution_reports_none() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(1_000)));
+        let stats = PriceLevelStatistics::with_clock(clock);
+        assert!(stats.record_execution(1, 1, 0, 2_000).is_ok());
+        assert_eq!(stats.time_since_last_execution(), None);
+    }
+
+    #[test]
+    fn test_clone_reads_the_system_clock() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(1_000)));
+        let stats = PriceLevelStatistics::with_clock(clock);
+        assert!(stats.record_execution(1, 1, 0, 1_000).is_ok());
+
+        let copy = stats.clone();
+        assert!(copy.time_since_last_execution().unwrap() > 1_000);
+    }
+
     #[test]
     fn test_reset() {
         let stats = PriceLevelStatistics::new();
@@ -752,4 +795,479 @@ mod tests {
         assert_eq!(stats.quantity_executed(), N);
         assert_eq!(stats.value_executed(), N * 100);
     }
+
+    #[test]
+    fn test_stats_window_totals_and_rates() {
+        let stats = PriceLevelStatistics::new();
+        let base = 1_716_000_000_000;
+        stats.record_execution(10, 100, 0, base).unwrap();
+        stats.record_execution(20, 100, 0, base + 1_500).unwrap();
+        stats.record_execution(30, 200, 0, base + 2_100).unwrap();
+
+        let now = base + 2_500;
+        let last_second = stats.stats_window_at(Duration::from_millis(1), now);
+        assert_eq!(last_second.window_ms, 1_000);
+        assert_eq!(last_second.executions, 1);
+        assert_eq!(last_second.quantity, 30);
+        assert_eq!(last_second.value, 6_000);
+
+        let three = stats.stats_window_at(Duration::from_secs(3), now);
+        assert_eq!(three.window_ms, 3_000);
+        assert_eq!(three.executions, 3);
+        assert_eq!(three.quantity, 60);
+        assert_eq!(three.value, 9_000);
+        assert!((three.executions_per_second() - 1.0).abs() < f64::EPSILON);
+        assert!((three.quantity_per_second() - 20.0).abs() < f64::EPSILON);
+        assert!((three.value_per_second() - 3_000.0).abs() < f64::EPSILON);
+        assert_eq!(three.average_price(), Some(150.0));
+
+        // Two seconds later the first two executions have left a 2s window.
+        let later = stats.stats_window_at(Duration::from_secs(2), base + 3_900);
+        assert_eq!(later.executions, 1);
+
+        // Requests longer than the ring are capped at one minute.
+        let capped = stats.stats_window_at(Duration::from_secs(3_600), now);
+        assert_eq!(capped.window_ms, 60_000);
+        assert_eq!(capped.executions, 3);
+
+        // The lifetime aggregates are unaffected by the window.
+        assert_eq!(stats.orders_executed(), 3);
+    }
+
+    #[test]
+    fn test_stats_window_expires_and_recycles_buckets() {
+        let stats = PriceLevelStatistics::new();
+        let base = 1_716_000_000_000;
+        stats.record_execution(5, 10, 0, base).unwrap();
+        // One full ring later the same slot is recycled for the new bucket.
+        stats.record_execution(7, 10, 0, base + 60_000).unwrap();
+        let window = stats.stats_window_at(Duration::from_secs(60), base + 60_000);
+        assert_eq!(window.executions, 1);
+        assert_eq!(window.quantity, 7);
+
+        // An execution older than the bucket now in its slot is dropped from
+        // the window but still counted in the lifetime aggregates.
+        stats.record_execution(9, 10, 0, base + 10).unwrap();
+        let window = stats.stats_window_at(Duration::from_secs(60), base + 60_000);
+        assert_eq!(window.executions, 1);
+        assert_eq!(stats.orders_executed(), 3);
+
+        let empty = stats.stats_window_at(Duration::from_secs(5), base + 120_000);
+        assert_eq!(empty.executions, 0);
+        assert_eq!(empty.executions_per_second(), 0.0);
+        assert_eq!(empty.average_price(), None);
+    }
+
+    #[test]
+    fn test_stats_window_wall_clock_and_reset() {
+        let stats = PriceLevelStatistics::new();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        stats.record_execution(4, 25, 0, now).unwrap();
+        assert_eq!(stats.stats_window(Duration::from_secs(60)).executions, 1);
+        assert!(stats.executions_per_second(Duration::from_secs(60)) > 0.0);
+
+        // The window is runtime-only: a clone starts empty.
+        let cloned = stats.clone();
+        assert_eq!(cloned.orders_executed(), 1);
+        assert_eq!(cloned.stats_window(Duration::from_secs(60)).executions, 0);
+
+        stats.reset();
+        assert_eq!(stats.stats_window(Duration::from_secs(60)).executions, 0);
+    }
+
+    #[test]
+    fn test_reset_is_safe_while_recording() {
+        const THREADS: u64 = 4;
+        const PER_THREAD: u64 = 5_000;
+        let stats = Arc::new(PriceLevelStatistics::new());
+
+        thread::scope(|scope| {
+            for _ in 0..THREADS {
+                let stats = Arc::clone(&stats);
+                scope.spawn(move || {
+                    for i in 0..PER_THREAD {
+                        stats
+                            .record_execution(2, 3, 0, 1_716_000_000_000 + i)
+                            .unwrap();
+                    }
+                });
+            }
+            let stats = Arc::clone(&stats);
+            scope.spawn(move || {
+                for _ in 0..200 {
+                    stats.reset();
+                    // Every consistent view has the per-execution ratios.
+                    let view = stats.clone();
+                    assert_eq!(view.quantity_executed(), view.orders_executed() as u64 * 2);
+                    assert_eq!(view.value_executed(), view.quantity_executed() * 3);
+                    thread::yield_now();
+                }
+            });
+        });
+
+        let view = stats.clone();
+        assert!(view.orders_executed() as u64 <= THREADS * PER_THREAD);
+        assert_eq!(view.quantity_executed(), view.orders_executed() as u64 * 2);
+        assert_eq!(view.value_executed(), view.quantity_executed() * 3);
+        assert!(!view.stats_degraded());
+    }
+
+    #[test]
+    fn test_quantity_rate_alert_fires_once_per_breach() {
+        use crate::price_level::{AlertCondition, StatisticsAlert};
+        use std::sync::Mutex;
+
+        let stats = PriceLevelStatistics::new();
+        let raised: Arc<Mutex<Vec<StatisticsAlert>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&raised);
+        stats.add_alert(
+            AlertCondition::QuantityPerSecondAbove {
+                window: Duration::from_secs(1),
+                threshold: 100.0,
+            },
+            move |alert| sink.lock().unwrap().push(*alert),
+        );
+        assert_eq!(stats.alert_count(), 1);
+
+        let base = 1_700_000_000_000;
+        stats.record_execution(60, 10, 0, base).unwrap();
+        assert!(raised.lock().unwrap().is_empty());
+
+        // 120 in the same second crosses 100/s: one alert.
+        stats.record_execution(60, 10, 0, base + 10).unwrap();
+        // Still breached: no second alert.
+        stats.record_execution(60, 10, 0, base + 20).unwrap();
+        {
+            let alerts = raised.lock().unwrap();
+            assert_eq!(alerts.len(), 1);
+            assert_eq!(alerts[0].observed, 120.0);
+            assert_eq!(alerts[0].timestamp_ms, base + 10);
+        }
+
+        // A quiet second re-arms the rule; the next breach alerts again.
+        stats.record_execution(1, 10, 0, base + 5_000).unwrap();
+        stats.record_execution(200, 10, 0, base + 5_010).unwrap();
+        assert_eq!(raised.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_average_waiting_time_alert() {
+        use crate::price_level::AlertCondition;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let stats = PriceLevelStatistics::new();
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&fired);
+        stats.add_alert(
+            AlertCondition::AverageWaitingTimeAbove {
+                threshold_ms: 500.0,
+            },
+            move |alert| {
+                assert!(alert.observed > alert.condition.threshold());
+                counter.fetch_add(1, Ordering::Relaxed);
+            },
+        );
+
+        let now = 1_700_000_000_000;
+        stats.record_execution(1, 10, now - 100, now).unwrap();
+        assert_eq!(fired.load(Ordering::Relaxed), 0);
+        // Average becomes (100 + 2000) / 2 = 1050 ms.
+        stats.record_execution(1, 10, now - 2_000, now).unwrap();
+        assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+        // A rejected execution evaluates nothing.
+        assert!(stats.record_execution(1, 10, now + 1, now).is_err());
+        assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+        // Reset re-arms; clearing removes the rule.
+        stats.reset();
+        stats.record_execution(1, 10, now - 2_000, now).unwrap();
+        assert_eq!(fired.load(Ordering::Relaxed), 2);
+        stats.clear_alerts();
+        assert_eq!(stats.alert_count(), 0);
+    }
+
+    #[test]
+    fn test_alert_callback_may_read_statistics_and_register_alerts() {
+        use crate::price_level::AlertCondition;
+        use std::sync::Mutex;
+
+        let stats = Arc::new(PriceLevelStatistics::new());
+        let observed_count = Arc::new(Mutex::new(None));
+        let sink = Arc::clone(&observed_count);
+        let inner = Arc::clone(&stats);
+        stats.add_alert(
+            AlertCondition::ExecutionsPerSecondAbove {
+                window: Duration::from_secs(1),
+                threshold: 1.0,
+            },
+            move |_| {
+                // A consistent multi-field read and a registration must not
+                // deadlock against the recording that raised the alert.
+                *sink.lock().unwrap() = Some(PriceLevelStatistics::clone(&inner).orders_executed());
+                inner.add_alert(
+                    AlertCondition::AverageWaitingTimeAbove { threshold_ms: 1.0 },
+                    |_| {},
+                );
+            },
+        );
+
+        let now = 1_700_000_000_000;
+        stats.record_execution(1, 10, 0, now).unwrap();
+        stats.record_execution(1, 10, 0, now + 1).unwrap();
+        assert_eq!(*observed_count.lock().unwrap(), Some(2));
+        assert_eq!(stats.alert_count(), 2);
+
+        // Alerts are runtime configuration and are not cloned.
+        assert_eq!(stats.as_ref().clone().alert_count(), 0);
+    }
+
+    #[test]
+    fn test_level_alert_from_match() {
+        use crate::UuidGenerator;
+        use crate::execution::TakerKind;
+        use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+        use crate::price_level::{AlertCondition, PriceLevel};
+        use crate::utils::{Price, Quantity, TimestampMs};
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let level = PriceLevel::new(100);
+        level
+            .add_order(OrderType::Standard {
+                id: Id::from_u64(1),
+                price: Price::new(100),
+                quantity: Quantity::new(500),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1_700_000_000_000),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+        let tripped = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&tripped);
+        level.stats().add_alert(
+            AlertCondition::QuantityPerSecondAbove {
+                window: Duration::from_secs(1),
+                threshold: 250.0,
+            },
+            move |_| flag.store(true, Ordering::Relaxed),
+        );
+
+        let generator = UuidGenerator::new(uuid::Uuid::new_v4());
+        let _ = level.match_order(
+            300,
+            Id::from_u64(999),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1_700_000_000_500),
+            &generator,
+        );
+        assert!(tripped.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_alert_callback_may_freeze_the_level() {
+        use crate::UuidGenerator;
+        use crate::execution::TakerKind;
+        use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+        use crate::price_level::{AlertCondition, PriceLevel};
+        use crate::utils::{Price, Quantity, TimestampMs};
+
+        let level = Arc::new(PriceLevel::new(100));
+        level
+            .add_order(OrderType::Standard {
+                id: Id::from_u64(1),
+                price: Price::new(100),
+                quantity: Quantity::new(500),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1_700_000_000_000),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+        // The callback is raised inside the sweep; freezing takes the guard the
+        // sweep holds, so it only returns if the callback runs after release.
+        let weak = Arc::downgrade(&level);
+        level.stats().add_alert(
+            AlertCondition::QuantityPerSecondAbove {
+                window: Duration::from_secs(1),
+                threshold: 250.0,
+            },
+            move |_| {
+                if let Some(level) = weak.upgrade() {
+                    level.freeze();
+                }
+            },
+        );
+
+        let generator = UuidGenerator::new(uuid::Uuid::new_v4());
+        let result = level.match_order(
+            300,
+            Id::from_u64(999),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1_700_000_000_500),
+            &generator,
+        );
+        assert!(result.is_complete());
+        assert!(level.is_frozen());
+
+        let result = level.match_order(
+            100,
+            Id::from_u64(1_000),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1_700_000_000_600),
+            &generator,
+        );
+        assert!(result.trades().is_empty());
+        assert_eq!(level.visible_quantity(), 200);
+    }
+
+    #[test]
+    fn test_order_size_analytics() {
+        let stats = PriceLevelStatistics::new();
+        assert_eq!(stats.average_order_size(), None);
+        assert_eq!(stats.median_order_size(), None);
+
+        for quantity in [2, 4, 6, 1_000] {
+            stats.record_order_added_with_quantity(quantity);
+        }
+        stats.record_order_added();
+
+        assert_eq!(stats.orders_added(), 5);
+        assert_eq!(stats.average_order_size(), Some(253.0));
+        assert_eq!(stats.median_order_size(), Some(4));
+
+        for _ in 0..3 {
+            stats.record_order_added_with_quantity(1_000);
+        }
+        // The middle order sits in the bucket holding 1_000, whose upper bound
+        // is capped at the largest size seen.
+        assert_eq!(stats.median_order_size(), Some(1_000));
+
+        stats.record_order_added_with_quantity(1_003);
+        stats.record_order_added_with_quantity(1_003);
+        let median = stats.median_order_size().unwrap();
+        assert!((1_000..=1_000 + 1_000 / 8).contains(&median));
+    }
+
+    #[test]
+    fn test_add_to_cancel_ratio_and_imbalance() {
+        let stats = PriceLevelStatistics::new();
+        assert_eq!(stats.add_to_cancel_ratio(), None);
+        assert_eq!(stats.order_flow_imbalance(), None);
+
+        stats.record_order_added_with_quantity(60);
+        stats.record_order_added_with_quantity(40);
+        stats.record_order_added_with_quantity(100);
+        assert_eq!(stats.add_to_cancel_ratio(), None);
+        assert_eq!(stats.order_flow_imbalance(), Some(1.0));
+
+        stats.record_order_removed_with_quantity(40);
+        stats
+            .record_execution(60, 100, 0, 1_700_000_000_000)
+            .unwrap();
+        assert_eq!(stats.add_to_cancel_ratio(), Some(3.0));
+        assert_eq!(stats.order_flow_imbalance(), Some(100.0 / 300.0));
+
+        stats.record_order_removed_with_quantity(100);
+        assert_eq!(stats.order_flow_imbalance(), Some(0.0));
+
+        stats.reset();
+        assert_eq!(stats.order_flow_imbalance(), None);
+        assert_eq!(stats.average_order_size(), None);
+        assert_eq!(stats.median_order_size(), None);
+    }
+
+    #[test]
+    fn test_order_flow_overflow_marks_degraded() {
+        let stats = PriceLevelStatistics::new();
+        stats.record_order_added_with_quantity(u64::MAX);
+        assert!(!stats.stats_degraded());
+
+        // The second add would wrap `quantity_added`: it is dropped, not
+        // clamped, and the statistics say so.
+        stats.record_order_added_with_quantity(1);
+        assert!(stats.stats_degraded());
+        assert_eq!(stats.average_order_size(), Some(u64::MAX as f64));
+        assert_eq!(stats.median_order_size(), Some(u64::MAX));
+
+        stats.reset();
+        stats.record_order_removed_with_quantity(u64::MAX);
+        stats.record_order_removed_with_quantity(1);
+        assert!(stats.stats_degraded());
+    }
+
+    #[test]
+    fn test_analytics_snapshot() {
+        let clock = Arc::new(ManualClock::new(TimestampMs::new(1_700_000_010_000)));
+        let stats = PriceLevelStatistics::with_clock(clock.clone());
+        stats.record_order_added_with_quantity(10);
+        stats.record_order_added_with_quantity(30);
+        stats.record_order_removed_with_quantity(10);
+        stats
+            .record_execution(10, 100, 0, 1_700_000_000_000)
+            .unwrap();
+        stats
+            .record_execution(30, 200, 0, 1_700_000_009_500)
+            .unwrap();
+
+        let analytics = stats.analytics(Duration::from_secs(5));
+        assert_eq!(analytics.window_ms, 5_000);
+        assert_eq!(analytics.vwap, Some(200.0));
+        assert_eq!(stats.vwap(Duration::from_secs(60)), Some(175.0));
+        assert_eq!(analytics.average_order_size, Some(20.0));
+        assert_eq!(analytics.median_order_size, Some(10));
+        assert_eq!(analytics.add_to_cancel_ratio, Some(2.0));
+        assert_eq!(analytics.order_flow_imbalance, Some(-10.0 / 90.0));
+
+        let json = serde_json::to_string(&analytics).unwrap();
+        let decoded: crate::price_level::StatisticsAnalytics = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, analytics);
+
+        // Runtime-only: a clone keeps the counters but not the flow.
+        let restored = stats.clone();
+        assert_eq!(restored.add_to_cancel_ratio(), Some(2.0));
+        assert_eq!(restored.order_flow_imbalance(), None);
+    }
+
+    #[test]
+    fn test_level_feeds_order_flow() {
+        use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+        use crate::price_level::PriceLevel;
+        use crate::utils::{Price, Quantity};
+
+        let level: PriceLevel = PriceLevel::new(100);
+        for (id, quantity) in [(1, 30), (2, 50)] {
+            level
+                .add_order(OrderType::IcebergOrder {
+                    id: Id::from_u64(id),
+                    price: Price::new(100),
+                    visible_quantity: Quantity::new(10),
+                    hidden_quantity: Quantity::new(quantity - 10),
+                    side: Side::Buy,
+                    user_id: Hash32::zero(),
+                    timestamp: TimestampMs::new(1_700_000_000_000),
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                })
+                .unwrap();
+        }
+        level
+            .update_order(OrderUpdate::Cancel {
+                order_id: Id::from_u64(1),
+            })
+            .unwrap();
+
+        let stats = level.stats();
+        assert_eq!(stats.average_order_size(), Some(40.0));
+        assert_eq!(stats.add_to_cancel_ratio(), Some(2.0));
+        assert_eq!(stats.order_flow_imbalance(), Some(50.0 / 110.0));
+    }
 }
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use crate::price_level::{
+        PriceLevel, PriceLevelStatistics, STATISTICS_CSV_SCHEMA_VERSION, StatisticsCsvWriter,
+    };
+    use crate::utils::TimestampMs;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_csv_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "pricelevel-stats-csv-{}-{}",
+            name,
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("stats.csv")
+    }
+
+    #[test]
+    fn test_writes_versioned_header_and_rows() {
+        let path = temp_csv_path("header");
+        let stats = PriceLevelStatistics::new();
+        stats.record_order_added();
+        stats.record_order_added();
+        stats.record_order_removed();
+
+        let mut writer = StatisticsCsvWriter::new(&path);
+        writer
+            .append(TimestampMs::new(1_000), 10_000, &stats)
+            .unwrap();
+        writer
+            .append(TimestampMs::new(2_000), 10_000, &stats)
+            .unwrap();
+        writer.flush().unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(
+            lines[0],
+            format!("# pricelevel-statistics schema_version={STATISTICS_CSV_SCHEMA_VERSION}")
+        );
+        assert!(lines[1].starts_with("timestamp_ms,price,orders_added"));
+        assert_eq!(lines.len(), 4);
+        assert!(lines[2].starts_with("1000,10000,2,1,0,0,0,0,"));
+        assert!(lines[3].starts_with("2000,10000,2,1,"));
+        assert!(lines[3].ends_with(",false"));
+    }
+
+    #[test]
+    fn test_reopen_appends_without_duplicating_header() {
+        let path = temp_csv_path("reopen");
+        let level = PriceLevel::new(500);
+
+        let mut writer = StatisticsCsvWriter::new(&path);
+        writer.append_level(&level, TimestampMs::new(1)).unwrap();
+        drop(writer);
+
+        let mut writer = StatisticsCsvWriter::new(&path);
+        writer.append_level(&level, TimestampMs::new(2)).unwrap();
+        drop(writer);
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.matches("schema_version=").count(), 1);
+        assert_eq!(contents.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_rotation_keeps_bounded_history() {
+        let path = temp_csv_path("rotate");
+        let stats = PriceLevelStatistics::new();
+
+        // A threshold just above header + one row forces a rotation per row.
+        let mut writer = StatisticsCsvWriter::new(&path).with_rotation(150, 2);
+        for ts in 0..5 {
+            writer.append(TimestampMs::new(ts), 1, &stats).unwrap();
+        }
+        writer.flush().unwrap();
+
+        let rotated_1 = PathBuf::from(format!("{}.1", path.display()));
+        let rotated_2 = PathBuf::from(format!("{}.2", path.display()));
+        let rotated_3 = PathBuf::from(format!("{}.3", path.display()));
+        assert!(rotated_1.exists());
+        assert!(rotated_2.exists());
+        assert!(!rotated_3.exists());
+
+        let active = fs::read_to_string(&path).unwrap();
+        assert!(active.lines().nth(2).unwrap().starts_with("4,"));
+        let previous = fs::read_to_string(&rotated_1).unwrap();
+        assert!(previous.lines().nth(2).unwrap().starts_with("3,"));
+        assert!(previous.starts_with("# pricelevel-statistics"));
+    }
+
+    #[test]
+    fn test_foreign_schema_is_rotated_aside() {
+        let path = temp_csv_path("schema");
+        fs::write(&path, "# pricelevel-statistics schema_version=0\nold\n").unwrap();
+
+        let mut writer = StatisticsCsvWriter::new(&path).with_rotation(1 << 20, 1);
+        writer
+            .append(TimestampMs::new(7), 1, &PriceLevelStatistics::new())
+            .unwrap();
+        writer.flush().unwrap();
+
+        let rotated = fs::read_to_string(format!("{}.1", path.display())).unwrap();
+        assert!(rotated.contains("schema_version=0"));
+        let active = fs::read_to_string(&path).unwrap();
+        assert!(active.starts_with(&StatisticsCsvWriter::schema_header()));
+        assert!(active.lines().nth(2).unwrap().starts_with("7,1,"));
+    }
+}
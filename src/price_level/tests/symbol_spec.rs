@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome};
+    use crate::price_level::{PriceLevel, PriceLevelConfig, SymbolSpec};
+    use crate::utils::{Price, Quantity, TimestampMs};
+
+    fn spec() -> SymbolSpec {
+        SymbolSpec::new(5, 10).with_min_qty(20).with_max_qty(1_000)
+    }
+
+    fn level(price: u128) -> PriceLevel {
+        PriceLevel::with_config(price, PriceLevelConfig::new().with_symbol_spec(spec()))
+    }
+
+    fn standard(id: u64, price: u128, quantity: u64) -> OrderType<()> {
+        OrderType::Standard {
+            id: Id::from_u64(id),
+            price: Price::new(price),
+            quantity: Quantity::new(quantity),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn iceberg(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10_000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Buy,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    fn is_invalid_lot(result: Result<impl Sized, PriceLevelError>, rejected: u64) -> bool {
+        matches!(
+            result,
+            Err(PriceLevelError::InvalidLot { quantity, lot_size: 10, min_qty: 20, max_qty: 1_000 })
+                if quantity == rejected
+        )
+    }
+
+    #[test]
+    fn test_spec_defaults_and_getters() {
+        let spec = SymbolSpec::new(5, 10);
+        assert_eq!(spec.tick_size(), 5);
+        assert_eq!(spec.lot_size(), 10);
+        assert_eq!(spec.min_qty(), 10);
+        assert_eq!(spec.max_qty(), u64::MAX);
+        assert!(spec.check_quantity(Quantity::new(0)).is_err());
+        assert!(spec.check_quantity(Quantity::new(10)).is_ok());
+        assert_eq!(PriceLevelConfig::new().symbol_spec(), None);
+        assert_eq!(
+            PriceLevelConfig::new().with_symbol_spec(spec).symbol_spec(),
+            Some(spec)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "tick size must be positive")]
+    fn test_zero_tick_panics() {
+        let _ = SymbolSpec::new(0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "max quantity 5 is below min quantity 10")]
+    fn test_max_below_min_panics() {
+        let _ = SymbolSpec::new(1, 10).with_max_qty(5);
+    }
+
+    #[test]
+    fn test_check_quantity_bounds() {
+        let spec = spec();
+        assert!(is_invalid_lot(spec.check_quantity(Quantity::new(25)), 25));
+        assert!(is_invalid_lot(spec.check_quantity(Quantity::new(10)), 10));
+        assert!(is_invalid_lot(
+            spec.check_quantity(Quantity::new(1_010)),
+            1_010
+        ));
+        assert!(spec.check_quantity(Quantity::new(20)).is_ok());
+        assert!(spec.check_quantity(Quantity::new(1_000)).is_ok());
+    }
+
+    #[test]
+    fn test_add_order_rejects_off_tick_price() {
+        let level = level(10_003);
+        assert!(matches!(
+            level.add_order(standard(1, 10_003, 20)),
+            Err(PriceLevelError::InvalidTick {
+                price: 10_003,
+                tick_size: 5
+            })
+        ));
+        assert_eq!(level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_add_order_rejects_off_lot_quantity() {
+        let level = level(10_000);
+        assert!(is_invalid_lot(level.add_order(standard(1, 10_000, 25)), 25));
+        assert!(is_invalid_lot(level.add_order(standard(2, 10_000, 10)), 10));
+        assert!(is_invalid_lot(
+            level.add_order(standard(3, 10_000, 2_000)),
+            2_000
+        ));
+        assert_eq!(level.order_count(), 0);
+        assert_eq!(level.visible_quantity(), 0);
+
+        assert!(level.add_order(standard(4, 10_000, 30)).is_ok());
+        assert_eq!(level.visible_quantity(), 30);
+    }
+
+    #[test]
+    fn test_add_order_checks_each_tranche() {
+        let level = level(10_000);
+        assert!(is_invalid_lot(level.add_order(iceberg(1, 15, 15)), 15));
+        assert!(is_invalid_lot(level.add_order(iceberg(2, 10, 995)), 995));
+        assert!(level.add_order(iceberg(3, 10, 10)).is_ok());
+        assert_eq!(level.order_count(), 1);
+    }
+
+    #[test]
+    fn test_level_without_spec_accepts_any_increment() {
+        let level: PriceLevel = PriceLevel::new(10_003);
+        assert_eq!(level.symbol_spec(), None);
+        assert!(level.add_order(standard(1, 10_003, 7)).is_ok());
+        assert_eq!(self::level(10_000).symbol_spec(), Some(spec()));
+    }
+
+    #[test]
+    fn test_update_quantity_rejects_off_lot_resize() {
+        let level = level(10_000);
+        level.add_order(standard(1, 10_000, 50)).unwrap();
+        let result = level.update_order(OrderUpdate::UpdateQuantity {
+            order_id: Id::from_u64(1),
+            new_quantity: Quantity::new(35),
+        });
+        assert!(is_invalid_lot(result, 35));
+        assert_eq!(level.visible_quantity(), 50);
+
+        let result = level.update_order(OrderUpdate::UpdateQuantity {
+            order_id: Id::from_u64(1),
+            new_quantity: Quantity::new(10),
+        });
+        assert!(is_invalid_lot(result, 10));
+
+        let result = level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: Id::from_u64(1),
+                new_quantity: Quantity::new(40),
+            })
+            .unwrap();
+        assert!(matches!(result, UpdateOutcome::UpdatedInPlace(_)));
+        assert_eq!(level.visible_quantity(), 40);
+    }
+
+    #[test]
+    fn test_reprice_rejected_before_removal() {
+        let level = level(10_000);
+        level.add_order(standard(1, 10_000, 50)).unwrap();
+        assert!(matches!(
+            level.update_order(OrderUpdate::UpdatePrice {
+                order_id: Id::from_u64(1),
+                new_price: Price::new(10_002),
+            }),
+            Err(PriceLevelError::InvalidTick { price: 10_002, .. })
+        ));
+        assert!(is_invalid_lot(
+            level.update_order(OrderUpdate::Replace {
+                order_id: Id::from_u64(1),
+                price: Price::new(10_005),
+                quantity: Quantity::new(33),
+                side: Side::Buy,
+            }),
+            33
+        ));
+        assert_eq!(level.order_count(), 1);
+
+        let outcome = level
+            .update_order(OrderUpdate::UpdatePriceAndQuantity {
+                order_id: Id::from_u64(1),
+                new_price: Price::new(10_005),
+                new_quantity: Quantity::new(30),
+            })
+            .unwrap();
+        assert!(matches!(outcome, UpdateOutcome::RemovedForReprice(_)));
+        assert_eq!(level.order_count(), 0);
+    }
+}
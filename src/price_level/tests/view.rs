@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use crate::execution::TakerKind;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{PriceLevel, PriceLevelView};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use std::sync::Arc;
+    use std::thread;
+
+    fn iceberg_order(id: u64, visible: u64, hidden: u64) -> OrderType<()> {
+        OrderType::IcebergOrder {
+            id: Id::from_u64(id),
+            price: Price::new(10000),
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1616823000000 + id),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        }
+    }
+
+    #[test]
+    fn test_view_tracks_the_level() {
+        let level = Arc::new(PriceLevel::new(10000));
+        let view = PriceLevelView::from(Arc::clone(&level));
+        assert_eq!(view.price(), 10000);
+        assert!(view.is_empty());
+        let sequence = view.mutation_sequence();
+
+        level.add_order(iceberg_order(1, 10, 40)).unwrap();
+        level.add_order(iceberg_order(2, 5, 0)).unwrap();
+        assert!(view.mutation_sequence() > sequence);
+        assert_eq!(view.visible_quantity(), 15);
+        assert_eq!(view.hidden_quantity(), 40);
+        assert_eq!(view.total_quantity().unwrap(), 55);
+        assert_eq!(view.order_count(), 2);
+
+        level.match_order(
+            12,
+            Id::from_u64(9),
+            TimeInForce::Ioc,
+            TakerKind::Standard,
+            TimestampMs::new(1616823000100),
+            &SequentialIdGenerator::new(0),
+        );
+        let stats = view.statistics();
+        assert_eq!(stats.orders_added(), 2);
+        assert_eq!(stats.quantity_executed(), 12);
+    }
+
+    #[test]
+    fn test_view_statistics_are_detached() {
+        let level = Arc::new(PriceLevel::new(10000));
+        level.add_order(iceberg_order(1, 10, 0)).unwrap();
+        let view = PriceLevelView::new(Arc::clone(&level));
+
+        let stats = view.statistics();
+        stats.reset();
+        stats.record_order_added();
+        stats.record_order_added();
+        assert_eq!(level.stats().orders_added(), 1);
+        assert_eq!(view.statistics().orders_added(), 1);
+    }
+
+    #[test]
+    fn test_views_are_shared_across_reader_threads() {
+        let level = Arc::new(PriceLevel::new(10000));
+        let view = PriceLevelView::new(Arc::clone(&level));
+        let readers: Vec<_> = (0..4)
+            .map(|_| {
+                let view = view.clone();
+                thread::spawn(move || {
+                    let mut last = 0;
+                    for _ in 0..1_000 {
+                        let count = view.order_count();
+                        assert!(count >= last, "the level only grows here");
+                        last = count;
+                    }
+                })
+            })
+            .collect();
+        for id in 0..100 {
+            level.add_order(iceberg_order(id, 1, 0)).unwrap();
+        }
+        for reader in readers {
+            reader.join().unwrap();
+        }
+        assert_eq!(view.order_count(), 100);
+    }
+}
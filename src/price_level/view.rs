@@ -0,0 +1,131 @@
+use crate::errors::PriceLevelError;
+use crate::price_level::level::PriceLevel;
+use crate::price_level::statistics::PriceLevelStatistics;
+use std::sync::Arc;
+
+/// A cheap, cloneable, read-only handle to a shared [`PriceLevel`].
+///
+/// A view reads the level's price, its advisory quantity and order-count
+/// counters and its statistics, and nothing else: it has no method that adds,
+/// updates, matches or reconfigures orders, so it can be handed to reader
+/// threads (dashboards, risk checks, metrics exporters) without handing them
+/// the mutation API. Cloning a view clones an `Arc`.
+///
+/// The counters are the same advisory, eventually-consistent reads
+/// [`PriceLevel::visible_quantity`] documents; for a mutually-consistent
+/// picture of the queue, take a [`PriceLevel::snapshot`] on the owning side.
+///
+/// ```rust
+/// use pricelevel::{
+///     Hash32, Id, OrderType, Price, PriceLevel, PriceLevelView, Quantity, Side, TimeInForce,
+///     TimestampMs,
+/// };
+/// use std::sync::Arc;
+///
+/// let level = Arc::new(PriceLevel::new(10_000));
+/// let view = PriceLevelView::new(Arc::clone(&level));
+///
+/// level
+///     .add_order(OrderType::Standard {
+///         id: Id::from_u64(1),
+///         price: Price::new(10_000),
+///         quantity: Quantity::new(25),
+///         side: Side::Buy,
+///         user_id: Hash32::zero(),
+///         timestamp: TimestampMs::new(1_000),
+///         time_in_force: TimeInForce::Gtc,
+///         extra_fields: (),
+///     })
+///     .unwrap();
+///
+/// let reader = view.clone();
+/// std::thread::spawn(move || {
+///     assert_eq!(reader.price(), 10_000);
+///     assert_eq!(reader.visible_quantity(), 25);
+///     assert_eq!(reader.statistics().orders_added(), 1);
+/// })
+/// .join()
+/// .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct PriceLevelView<T = ()> {
+    level: Arc<PriceLevel<T>>,
+}
+
+impl<T> Clone for PriceLevelView<T> {
+    fn clone(&self) -> Self {
+        Self {
+            level: Arc::clone(&self.level),
+        }
+    }
+}
+
+impl<T: Clone> From<Arc<PriceLevel<T>>> for PriceLevelView<T> {
+    fn from(level: Arc<PriceLevel<T>>) -> Self {
+        Self::new(level)
+    }
+}
+
+impl<T: Clone> PriceLevelView<T> {
+    /// Creates a view of `level`.
+    #[must_use]
+    pub fn new(level: Arc<PriceLevel<T>>) -> Self {
+        Self { level }
+    }
+
+    /// The level's price.
+    #[must_use]
+    pub fn price(&self) -> u128 {
+        self.level.price()
+    }
+
+    /// The displayed quantity, per [`PriceLevel::visible_quantity`].
+    #[must_use]
+    pub fn visible_quantity(&self) -> u64 {
+        self.level.visible_quantity()
+    }
+
+    /// The hidden quantity, per [`PriceLevel::hidden_quantity`].
+    #[must_use]
+    pub fn hidden_quantity(&self) -> u64 {
+        self.level.hidden_quantity()
+    }
+
+    /// The visible plus hidden quantity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the sum overflows.
+    pub fn total_quantity(&self) -> Result<u64, PriceLevelError> {
+        self.level.total_quantity()
+    }
+
+    /// The number of resting orders, per [`PriceLevel::order_count`].
+    #[must_use]
+    pub fn order_count(&self) -> usize {
+        self.level.order_count()
+    }
+
+    /// Returns `true` if no order rests at the level.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.level.order_count() == 0
+    }
+
+    /// The level's mutation sequence, per [`PriceLevel::mutation_sequence`];
+    /// a reader can poll it to tell whether the level changed.
+    #[must_use]
+    pub fn mutation_sequence(&self) -> u64 {
+        self.level.mutation_sequence()
+    }
+
+    /// A consistent, detached copy of the level's statistics.
+    ///
+    /// The copy is taken under the statistics' seqlock, so its fields
+    /// describe one state the level held; recording into it, or resetting
+    /// it, does not touch the level.
+    #[must_use]
+    pub fn statistics(&self) -> PriceLevelStatistics {
+        (*self.level.stats()).clone()
+    }
+}
@@ -0,0 +1,462 @@
+//! Python bindings for the core price level operations.
+//!
+//! Behind the `python` feature, this module exposes
+//! [`PriceLevel`](crate::price_level::PriceLevel),
+//! [`OrderType`](crate::orders::OrderType) and
+//! [`MatchResult`](crate::execution::MatchResult) to Python as PyO3 classes of the same
+//! names, so research notebooks drive the exact matching logic production
+//! runs. Built with maturin (`maturin develop --features python`), the crate
+//! imports as `pricelevel`:
+//!
+//! ```python
+//! from pricelevel import OrderType, PriceLevel
+//!
+//! level = PriceLevel(10_000)
+//! level.add_order(OrderType(1, 10_000, 30, "SELL", timestamp_ms=1_000))
+//! level.add_order(OrderType(2, 10_000, 10, "SELL", hidden_quantity=40))
+//!
+//! result = level.match_order(35, taker_id=9, time_in_force="IOC")
+//! assert result.executed_quantity == 35
+//! assert result.outcome == "filled"
+//!
+//! level.cancel_order(2)
+//! package = level.snapshot()  # checksummed snapshot package as JSON
+//! ```
+//!
+//! Order ids are [`Id::Sequential`] values passed as Python ints, prices and
+//! quantities are ints, and sides, times in force, taker kinds and outcomes
+//! are strings: `"BUY"` / `"SELL"`, `"GTC"` / `"IOC"` / `"FOK"` / `"DAY"` /
+//! `"GTD-<expiry_ms>"`, `"standard"` / `"post_only"` / `"market_to_limit"`,
+//! and `"filled"` / `"partially_filled"` / `"not_filled"` / `"killed"` /
+//! `"rejected"`. A [`PriceLevelError`](crate::errors::PriceLevelError) is
+//! raised as the Python `pricelevel.PriceLevelError` exception with its
+//! stable [`code`](crate::errors::PriceLevelError::code) and message as
+//! arguments.
+
+use crate::errors;
+use crate::execution::{self, MatchOutcome, TakerKind};
+use crate::orders::{self, Hash32, Id, OrderUpdate, Side, TimeInForce, UpdateOutcome};
+use crate::price_level;
+use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+
+mod tests;
+
+create_exception!(
+    pricelevel,
+    PriceLevelError,
+    PyException,
+    "A price level error, raised with its stable code and message as arguments."
+);
+
+/// A price level and the execution id generator its matches draw from,
+/// exposed to Python as `pricelevel.PriceLevel`.
+#[pyclass(name = "PriceLevel", module = "pricelevel", frozen)]
+#[derive(Debug)]
+pub struct PyPriceLevel {
+    level: price_level::PriceLevel,
+    executions: SequentialIdGenerator,
+}
+
+/// An order, exposed to Python as `pricelevel.OrderType`.
+///
+/// Built from Python as a standard limit order, or as an iceberg order when
+/// `hidden_quantity` is non-zero; any variant read back from a level keeps
+/// its own type.
+#[pyclass(name = "OrderType", module = "pricelevel", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyOrderType {
+    order: orders::OrderType<()>,
+}
+
+/// The result of a match, exposed to Python as `pricelevel.MatchResult`.
+#[pyclass(name = "MatchResult", module = "pricelevel", frozen)]
+#[derive(Debug)]
+pub struct PyMatchResult {
+    result: execution::MatchResult<()>,
+}
+
+#[pymethods]
+impl PyPriceLevel {
+    /// Creates an empty level at `price` whose trades take execution ids from
+    /// a [`SequentialIdGenerator`] for `execution_epoch`.
+    #[new]
+    #[pyo3(signature = (price, execution_epoch = 0))]
+    pub fn new(price: u128, execution_epoch: u64) -> Self {
+        Self {
+            level: price_level::PriceLevel::new(price),
+            executions: SequentialIdGenerator::new(execution_epoch),
+        }
+    }
+
+    /// The level's price.
+    #[getter]
+    pub fn price(&self) -> u128 {
+        self.level.price()
+    }
+
+    /// The displayed quantity resting at the level.
+    #[getter]
+    pub fn visible_quantity(&self) -> u64 {
+        self.level.visible_quantity()
+    }
+
+    /// The hidden quantity resting at the level.
+    #[getter]
+    pub fn hidden_quantity(&self) -> u64 {
+        self.level.hidden_quantity()
+    }
+
+    /// The number of resting orders.
+    #[getter]
+    pub fn order_count(&self) -> usize {
+        self.level.order_count()
+    }
+
+    /// The resting orders in queue order.
+    pub fn orders(&self) -> Vec<PyOrderType> {
+        self.level
+            .snapshot_orders()
+            .into_iter()
+            .map(|order| PyOrderType { order: *order })
+            .collect()
+    }
+
+    /// Adds `order` to the back of the queue and returns the order as it
+    /// rests.
+    ///
+    /// # Errors
+    ///
+    /// Raises `PriceLevelError` with the code of the
+    /// [`add_order`](crate::price_level::PriceLevel::add_order) error.
+    pub fn add_order(&self, order: PyRef<'_, PyOrderType>) -> PyResult<PyOrderType> {
+        let order = self.level.add_order(order.order).map_err(to_py_err)?;
+        Ok(PyOrderType { order: *order })
+    }
+
+    /// Cancels the resting order `order_id`, raising `PriceLevelError` with
+    /// the code of [`errors::PriceLevelError::OrderNotFound`] if it is not at
+    /// the level.
+    ///
+    /// # Errors
+    ///
+    /// Raises `PriceLevelError` if the order is missing or the update fails.
+    pub fn cancel_order(&self, order_id: u64) -> PyResult<()> {
+        let order_id = Id::sequential(order_id);
+        match self
+            .level
+            .update_order(OrderUpdate::Cancel { order_id })
+            .map_err(to_py_err)?
+        {
+            UpdateOutcome::NotFound => Err(to_py_err(errors::PriceLevelError::OrderNotFound {
+                order_id,
+            })),
+            _ => Ok(()),
+        }
+    }
+
+    /// Matches an incoming order of `quantity` against the level.
+    ///
+    /// # Errors
+    ///
+    /// Raises `PriceLevelError` for an unparsable time in force and
+    /// `ValueError` for an unknown taker kind.
+    #[pyo3(signature = (
+        quantity,
+        taker_id,
+        time_in_force = "IOC",
+        kind = "standard",
+        timestamp_ms = 0,
+    ))]
+    pub fn match_order(
+        &self,
+        quantity: u64,
+        taker_id: u64,
+        time_in_force: &str,
+        kind: &str,
+        timestamp_ms: u64,
+    ) -> PyResult<PyMatchResult> {
+        let time_in_force = time_in_force.parse::<TimeInForce>().map_err(to_py_err)?;
+        let result = self.level.match_order(
+            quantity,
+            Id::sequential(taker_id),
+            time_in_force,
+            taker_kind(kind)?,
+            TimestampMs::new(timestamp_ms),
+            &self.executions,
+        );
+        Ok(PyMatchResult { result })
+    }
+
+    /// The level's checksummed snapshot package as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Raises `PriceLevelError` if the snapshot cannot be serialized.
+    pub fn snapshot(&self) -> PyResult<String> {
+        self.level.snapshot_to_json().map_err(to_py_err)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PriceLevel(price={}, visible_quantity={}, hidden_quantity={}, order_count={})",
+            self.level.price(),
+            self.level.visible_quantity(),
+            self.level.hidden_quantity(),
+            self.level.order_count()
+        )
+    }
+}
+
+#[pymethods]
+impl PyOrderType {
+    /// Creates a standard limit order, or an iceberg order showing `quantity`
+    /// at a time when `hidden_quantity` is non-zero. `user_id` is a 64-digit
+    /// hex string, all zeros when omitted.
+    ///
+    /// # Errors
+    ///
+    /// Raises `PriceLevelError` for an unparsable side, time in force or
+    /// user id.
+    #[new]
+    #[pyo3(signature = (
+        id,
+        price,
+        quantity,
+        side,
+        time_in_force = "GTC",
+        timestamp_ms = 0,
+        hidden_quantity = 0,
+        user_id = None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: u64,
+        price: u128,
+        quantity: u64,
+        side: &str,
+        time_in_force: &str,
+        timestamp_ms: u64,
+        hidden_quantity: u64,
+        user_id: Option<&str>,
+    ) -> PyResult<Self> {
+        let id = Id::sequential(id);
+        let price = Price::new(price);
+        let side = side.parse::<Side>().map_err(to_py_err)?;
+        let time_in_force = time_in_force.parse::<TimeInForce>().map_err(to_py_err)?;
+        let user_id = match user_id {
+            Some(hex) => Hash32::from_hex(hex).map_err(to_py_err)?,
+            None => Hash32::default(),
+        };
+        let timestamp = TimestampMs::new(timestamp_ms);
+        let order = if hidden_quantity == 0 {
+            orders::OrderType::Standard {
+                id,
+                price,
+                quantity: Quantity::new(quantity),
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: (),
+            }
+        } else {
+            orders::OrderType::IcebergOrder {
+                id,
+                price,
+                visible_quantity: Quantity::new(quantity),
+                hidden_quantity: Quantity::new(hidden_quantity),
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: (),
+            }
+        };
+        Ok(Self { order })
+    }
+
+    /// Parses an order from its display form, as produced by `str(order)`.
+    ///
+    /// # Errors
+    ///
+    /// Raises `PriceLevelError` if `text` is not a valid order.
+    #[staticmethod]
+    pub fn parse(text: &str) -> PyResult<Self> {
+        let order = text.parse::<orders::OrderType<()>>().map_err(to_py_err)?;
+        Ok(Self { order })
+    }
+
+    /// The order id, or `None` if it is not a sequential id.
+    #[getter]
+    pub fn id(&self) -> Option<u64> {
+        self.order.id().as_u64()
+    }
+
+    /// The limit price.
+    #[getter]
+    pub fn price(&self) -> u128 {
+        self.order.price().as_u128()
+    }
+
+    /// The displayed quantity.
+    #[getter]
+    pub fn visible_quantity(&self) -> u64 {
+        self.order.visible_quantity().as_u64()
+    }
+
+    /// The hidden quantity.
+    #[getter]
+    pub fn hidden_quantity(&self) -> u64 {
+        self.order.hidden_quantity().as_u64()
+    }
+
+    /// `"BUY"` or `"SELL"`.
+    #[getter]
+    pub fn side(&self) -> String {
+        self.order.side().to_string()
+    }
+
+    /// The time in force in its display form, such as `"GTC"`.
+    #[getter]
+    pub fn time_in_force(&self) -> String {
+        self.order.time_in_force().to_string()
+    }
+
+    /// The order timestamp in Unix milliseconds.
+    #[getter]
+    pub fn timestamp_ms(&self) -> u64 {
+        self.order.timestamp().as_u64()
+    }
+
+    fn __str__(&self) -> String {
+        self.order.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("OrderType({})", self.order)
+    }
+}
+
+#[pymethods]
+impl PyMatchResult {
+    /// The taker order id, or `None` if it is not a sequential id.
+    #[getter]
+    pub fn order_id(&self) -> Option<u64> {
+        self.result.order_id().as_u64()
+    }
+
+    /// The quantity executed against the level.
+    ///
+    /// # Errors
+    ///
+    /// Raises `PriceLevelError` if the executed quantity overflows.
+    #[getter]
+    pub fn executed_quantity(&self) -> PyResult<u64> {
+        self.result
+            .executed_quantity()
+            .map(Quantity::as_u64)
+            .map_err(to_py_err)
+    }
+
+    /// The taker quantity left unfilled.
+    #[getter]
+    pub fn remaining_quantity(&self) -> u64 {
+        self.result.remaining_quantity().as_u64()
+    }
+
+    /// Whether the taker was filled in full.
+    #[getter]
+    pub fn is_complete(&self) -> bool {
+        self.result.is_complete()
+    }
+
+    /// The match outcome, such as `"filled"` or `"killed"`.
+    #[getter]
+    pub fn outcome(&self) -> &'static str {
+        outcome_name(self.result.outcome())
+    }
+
+    /// The trades as `(execution_sequence, maker_order_id, price, quantity)`
+    /// tuples, with `None` for a maker id that is not sequential.
+    #[getter]
+    pub fn trades(&self) -> Vec<(Option<u64>, Option<u64>, u128, u64)> {
+        self.result
+            .trades()
+            .as_slice()
+            .iter()
+            .map(|trade| {
+                (
+                    trade.execution_sequence(),
+                    trade.maker_order_id().as_u64(),
+                    trade.price().as_u128(),
+                    trade.quantity().as_u64(),
+                )
+            })
+            .collect()
+    }
+
+    /// The ids of the makers the match filled completely.
+    #[getter]
+    pub fn filled_order_ids(&self) -> Vec<Option<u64>> {
+        self.result
+            .filled_order_ids()
+            .iter()
+            .map(Id::as_u64)
+            .collect()
+    }
+
+    /// The full result, fees and status transitions included, as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Raises `PriceLevelError` if the result cannot be serialized.
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.result)
+            .map_err(|error| to_py_err(errors::PriceLevelError::serialization(error)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MatchResult(outcome={:?}, remaining_quantity={}, trades={})",
+            outcome_name(self.result.outcome()),
+            self.result.remaining_quantity(),
+            self.result.trades().len()
+        )
+    }
+}
+
+/// The `pricelevel` Python module.
+#[pymodule]
+fn pricelevel(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyPriceLevel>()?;
+    module.add_class::<PyOrderType>()?;
+    module.add_class::<PyMatchResult>()?;
+    module.add("PriceLevelError", module.py().get_type::<PriceLevelError>())?;
+    Ok(())
+}
+
+fn to_py_err(error: errors::PriceLevelError) -> PyErr {
+    PriceLevelError::new_err((error.code(), error.to_string()))
+}
+
+fn taker_kind(kind: &str) -> PyResult<TakerKind> {
+    match kind {
+        "standard" => Ok(TakerKind::Standard),
+        "post_only" => Ok(TakerKind::PostOnly),
+        "market_to_limit" => Ok(TakerKind::MarketToLimit),
+        _ => Err(PyValueError::new_err(format!("unknown taker kind: {kind}"))),
+    }
+}
+
+fn outcome_name(outcome: MatchOutcome) -> &'static str {
+    match outcome {
+        MatchOutcome::Filled => "filled",
+        MatchOutcome::PartiallyFilled => "partially_filled",
+        MatchOutcome::NotFilled => "not_filled",
+        MatchOutcome::Killed => "killed",
+        MatchOutcome::Rejected => "rejected",
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use crate::price_level::PriceLevelSnapshotPackage;
+    use crate::python::*;
+
+    fn add(level: &PyPriceLevel, order: PyOrderType) -> PyResult<PyOrderType> {
+        Python::initialize();
+        Python::attach(|py| level.add_order(Bound::new(py, order)?.borrow()))
+    }
+
+    fn error_code(error: PyErr) -> u16 {
+        Python::attach(|py| {
+            assert!(error.is_instance_of::<PriceLevelError>(py));
+            error
+                .value(py)
+                .getattr("args")
+                .and_then(|args| args.get_item(0))
+                .and_then(|code| code.extract())
+                .expect("the first argument is the error code")
+        })
+    }
+
+    #[test]
+    fn test_add_match_and_cancel_from_python() {
+        let level = PyPriceLevel::new(10_000, 3);
+        let resting = add(
+            &level,
+            PyOrderType::new(1, 10_000, 30, "SELL", "GTC", 1_000, 0, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(resting.id(), Some(1));
+        add(
+            &level,
+            PyOrderType::new(2, 10_000, 10, "sell", "GTC", 1_001, 40, None).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(level.visible_quantity(), 40);
+        assert_eq!(level.hidden_quantity(), 40);
+        assert_eq!(level.order_count(), 2);
+
+        let result = level.match_order(35, 9, "IOC", "standard", 5_000).unwrap();
+        assert_eq!(result.executed_quantity().unwrap(), 35);
+        assert_eq!(result.remaining_quantity(), 0);
+        assert!(result.is_complete());
+        assert_eq!(result.outcome(), "filled");
+        assert_eq!(result.filled_order_ids(), vec![Some(1)]);
+        let trades = result.trades();
+        assert_eq!(trades.len(), 2);
+        assert_eq!((trades[0].0, trades[0].1), (Some(1), Some(1)));
+        assert_eq!((trades[0].2, trades[0].3), (10_000, 30));
+        assert_eq!((trades[1].0, trades[1].1), (Some(2), Some(2)));
+        assert_eq!(trades[1].3, 5);
+
+        level.cancel_order(2).unwrap();
+        assert_eq!(level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_errors_are_raised_with_their_code() {
+        let level = PyPriceLevel::new(10_000, 0);
+        add(
+            &level,
+            PyOrderType::new(1, 10_000, 5, "BUY", "GTC", 0, 0, None).unwrap(),
+        )
+        .unwrap();
+
+        let duplicate = PyOrderType::new(1, 10_000, 5, "BUY", "GTC", 0, 0, None).unwrap();
+        assert_eq!(error_code(add(&level, duplicate).unwrap_err()), 2001);
+        let mispriced = PyOrderType::new(2, 10_001, 5, "BUY", "GTC", 0, 0, None).unwrap();
+        assert_eq!(error_code(add(&level, mispriced).unwrap_err()), 2002);
+        assert_eq!(error_code(level.cancel_order(7).unwrap_err()), 2004);
+        assert_eq!(
+            error_code(PyOrderType::new(3, 10_000, 5, "LONG", "GTC", 0, 0, None).unwrap_err()),
+            1001
+        );
+        Python::attach(|py| {
+            let error = level.match_order(1, 9, "IOC", "sweep", 0).unwrap_err();
+            assert!(error.is_instance_of::<pyo3::exceptions::PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_fill_or_kill_and_post_only_outcomes() {
+        let level = PyPriceLevel::new(10_000, 0);
+        add(
+            &level,
+            PyOrderType::new(1, 10_000, 5, "SELL", "GTC", 0, 0, None).unwrap(),
+        )
+        .unwrap();
+
+        let killed = level.match_order(10, 8, "FOK", "standard", 0).unwrap();
+        assert_eq!(killed.outcome(), "killed");
+        assert_eq!(killed.remaining_quantity(), 10);
+        let rejected = level.match_order(1, 9, "GTC", "post_only", 0).unwrap();
+        assert_eq!(rejected.outcome(), "rejected");
+        assert_eq!(level.visible_quantity(), 5);
+    }
+
+    #[test]
+    fn test_orders_snapshot_and_display_round_trip() {
+        let level = PyPriceLevel::new(10_000, 0);
+        let order = PyOrderType::new(1, 10_000, 5, "BUY", "GTD-9000", 100, 0, None).unwrap();
+        add(&level, order).unwrap();
+
+        let orders = level.orders();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].time_in_force(), "GTD-9000");
+        let parsed = PyOrderType::parse(&orders[0].__str__()).unwrap();
+        assert_eq!(parsed.id(), Some(1));
+        assert_eq!(parsed.side(), "BUY");
+        assert_eq!(parsed.timestamp_ms(), 100);
+
+        let package = PriceLevelSnapshotPackage::<()>::from_json(&level.snapshot().unwrap())
+            .expect("snapshot JSON decodes");
+        let snapshot = package.into_snapshot().expect("checksum validates");
+        assert_eq!(snapshot.orders().len(), 1);
+    }
+}
@@ -0,0 +1,580 @@
+//! Deterministic workload simulation against a price level.
+//!
+//! The crate documentation reports a multi-threaded high-frequency trading
+//! simulation; this module is its reproducible, single-threaded counterpart.
+//! A [`Workload`] describes a seeded mix of maker, taker and cancel
+//! operations with their size distributions. [`Workload::generate`] expands
+//! it into a script of [`SimOp`]s that is identical for every run with the
+//! same seed, and [`Workload::run`] replays the script against a
+//! [`PriceLevel`], returning a [`SimReport`] with the throughput and the
+//! final state.
+//!
+//! A run also checks the level once the script is done: every quantity
+//! admitted is either executed, cancelled or still resting, the order count
+//! matches the admissions less the fills and cancels, and with the `testing`
+//! feature `PriceLevel::check_invariants` holds. A violation is an error,
+//! so the same workload doubles as a regression test:
+//!
+//! ```rust
+//! use pricelevel::sim::{OperationMix, SizeDistribution, Workload};
+//!
+//! let workload = Workload::new(42)
+//!     .with_operations(2_000)
+//!     .with_mix(OperationMix::new(5, 3, 2))
+//!     .with_taker_sizes(SizeDistribution::Geometric { mean: 40 });
+//!
+//! let first = workload.run().unwrap();
+//! let second = workload.run().unwrap();
+//! assert_eq!(first.executed_quantity, second.executed_quantity);
+//! assert_eq!(first.order_count, second.order_count);
+//! ```
+
+use crate::errors::PriceLevelError;
+use crate::execution::TakerKind;
+use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome};
+use crate::price_level::PriceLevel;
+use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+use std::time::{Duration, Instant};
+
+mod tests;
+
+/// Timestamp of the first simulated operation; each later one is a
+/// millisecond after the previous.
+const SIM_EPOCH_MS: u64 = 1_700_000_000_000;
+
+/// Taker ids are drawn from this offset up, clear of the maker ids.
+const TAKER_ID_OFFSET: u64 = 1 << 62;
+
+/// Distribution of the quantities a workload draws.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeDistribution {
+    /// Always the same quantity.
+    Fixed(u64),
+    /// Uniform over `[min, max]`.
+    Uniform {
+        /// Smallest quantity drawn.
+        min: u64,
+        /// Largest quantity drawn.
+        max: u64,
+    },
+    /// Geometric with the given mean: mostly small quantities with a long
+    /// tail of large ones.
+    Geometric {
+        /// Mean quantity drawn.
+        mean: u64,
+    },
+}
+
+impl SizeDistribution {
+    pub(crate) fn validate(&self) {
+        match *self {
+            Self::Fixed(quantity) => assert!(quantity >= 1, "fixed size must be at least 1"),
+            Self::Uniform { min, max } => assert!(
+                min >= 1 && min <= max,
+                "uniform sizes must satisfy 1 <= min <= max, got [{min}, {max}]"
+            ),
+            Self::Geometric { mean } => assert!(mean >= 1, "geometric mean must be at least 1"),
+        }
+    }
+
+    pub(crate) fn sample(&self, rng: &mut SimRng) -> u64 {
+        match *self {
+            Self::Fixed(quantity) => quantity,
+            Self::Uniform { min, max } => min + rng.below(max - min + 1),
+            Self::Geometric { mean } => {
+                // Inverse transform of an exponential with the given mean,
+                // rounded up so every draw is at least 1.
+                let draw = (-(mean as f64) * (1.0 - rng.unit()).ln()).ceil();
+                (draw as u64).max(1)
+            }
+        }
+    }
+}
+
+/// Relative weights of the operations a workload generates.
+///
+/// A cancel is drawn only while the script has an order it may still name;
+/// before that it becomes a maker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OperationMix {
+    maker: u32,
+    taker: u32,
+    cancel: u32,
+}
+
+impl OperationMix {
+    /// A mix with the given weights for makers, takers and cancels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every weight is zero or their sum overflows `u32`.
+    #[must_use]
+    pub fn new(maker: u32, taker: u32, cancel: u32) -> Self {
+        let total = maker
+            .checked_add(taker)
+            .and_then(|sum| sum.checked_add(cancel))
+            .expect("operation mix weights overflow u32");
+        assert!(total > 0, "operation mix needs a non-zero weight");
+        Self {
+            maker,
+            taker,
+            cancel,
+        }
+    }
+
+    /// Weight of makers.
+    #[must_use]
+    pub fn maker(&self) -> u32 {
+        self.maker
+    }
+
+    /// Weight of takers.
+    #[must_use]
+    pub fn taker(&self) -> u32 {
+        self.taker
+    }
+
+    /// Weight of cancels.
+    #[must_use]
+    pub fn cancel(&self) -> u32 {
+        self.cancel
+    }
+
+    fn total(&self) -> u64 {
+        u64::from(self.maker) + u64::from(self.taker) + u64::from(self.cancel)
+    }
+}
+
+impl Default for OperationMix {
+    /// Equal weights, as in the threaded simulation.
+    fn default() -> Self {
+        Self::new(1, 1, 1)
+    }
+}
+
+/// One operation of a simulation script.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimOp {
+    /// Admit a resting sell order.
+    Add(OrderType<()>),
+    /// Match an immediate-or-cancel taker against the level.
+    Match {
+        /// The taker's id.
+        taker_id: Id,
+        /// The taker's quantity.
+        quantity: u64,
+        /// When the taker arrives.
+        timestamp: TimestampMs,
+    },
+    /// Cancel an order the script added earlier. It may have been filled
+    /// since, in which case the cancel finds nothing.
+    Cancel(Id),
+}
+
+/// A seeded, reproducible workload against one price level.
+///
+/// Every maker rests a sell order, standard or iceberg, at the workload's
+/// price; every taker sweeps it immediate-or-cancel; every cancel names one
+/// of the script's earlier orders, each at most once. The level starts with
+/// [`Self::with_initial_orders`] makers before the mix begins.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workload {
+    seed: u64,
+    price: u128,
+    initial_orders: usize,
+    operations: usize,
+    mix: OperationMix,
+    maker_sizes: SizeDistribution,
+    taker_sizes: SizeDistribution,
+    iceberg_percent: u8,
+}
+
+impl Workload {
+    /// A workload seeded with `seed`: 10 000 operations in equal parts at
+    /// price 10 000 after 1 000 initial orders, makers of 1 to 100 with one
+    /// in five an iceberg, and takers of 1 to 150.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            price: 10_000,
+            initial_orders: 1_000,
+            operations: 10_000,
+            mix: OperationMix::default(),
+            maker_sizes: SizeDistribution::Uniform { min: 1, max: 100 },
+            taker_sizes: SizeDistribution::Uniform { min: 1, max: 150 },
+            iceberg_percent: 20,
+        }
+    }
+
+    /// Sets the price of the level the workload drives.
+    #[must_use]
+    pub fn with_price(mut self, price: u128) -> Self {
+        self.price = price;
+        self
+    }
+
+    /// Sets how many makers rest before the mix begins.
+    #[must_use]
+    pub fn with_initial_orders(mut self, initial_orders: usize) -> Self {
+        self.initial_orders = initial_orders;
+        self
+    }
+
+    /// Sets how many operations the mix generates after the initial orders.
+    #[must_use]
+    pub fn with_operations(mut self, operations: usize) -> Self {
+        self.operations = operations;
+        self
+    }
+
+    /// Sets the weights of makers, takers and cancels.
+    #[must_use]
+    pub fn with_mix(mut self, mix: OperationMix) -> Self {
+        self.mix = mix;
+        self
+    }
+
+    /// Sets the distribution of maker quantities. An iceberg draws its
+    /// visible and hidden quantities from it separately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distribution can draw zero.
+    #[must_use]
+    pub fn with_maker_sizes(mut self, sizes: SizeDistribution) -> Self {
+        sizes.validate();
+        self.maker_sizes = sizes;
+        self
+    }
+
+    /// Sets the distribution of taker quantities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distribution can draw zero.
+    #[must_use]
+    pub fn with_taker_sizes(mut self, sizes: SizeDistribution) -> Self {
+        sizes.validate();
+        self.taker_sizes = sizes;
+        self
+    }
+
+    /// Sets the percentage of makers that are icebergs rather than standard
+    /// orders.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percent` is above 100.
+    #[must_use]
+    pub fn with_iceberg_percent(mut self, percent: u8) -> Self {
+        assert!(percent <= 100, "iceberg percent must be at most 100");
+        self.iceberg_percent = percent;
+        self
+    }
+
+    /// Get the seed.
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get the price of the level the workload drives.
+    #[must_use]
+    pub fn price(&self) -> u128 {
+        self.price
+    }
+
+    /// Expand the workload into its script: the initial makers, then the
+    /// mix. The same workload always yields the same script.
+    #[must_use]
+    pub fn generate(&self) -> Vec<SimOp> {
+        let mut rng = SimRng::new(self.seed);
+        let mut script = Vec::with_capacity(self.initial_orders + self.operations);
+        // Orders the script may still cancel.
+        let mut cancellable: Vec<Id> = Vec::new();
+        let (mut next_maker, mut next_taker) = (0u64, 0u64);
+
+        for index in 0..self.initial_orders + self.operations {
+            let timestamp = TimestampMs::new(SIM_EPOCH_MS + index as u64);
+            let roll = if index < self.initial_orders {
+                0
+            } else {
+                rng.below(self.mix.total())
+            };
+            let maker = u64::from(self.mix.maker);
+            let taker = maker + u64::from(self.mix.taker);
+
+            if roll >= maker && roll < taker {
+                next_taker += 1;
+                script.push(SimOp::Match {
+                    taker_id: Id::from_u64(TAKER_ID_OFFSET + next_taker),
+                    quantity: self.taker_sizes.sample(&mut rng),
+                    timestamp,
+                });
+            } else if roll >= taker && !cancellable.is_empty() {
+                let position = rng.below(cancellable.len() as u64) as usize;
+                script.push(SimOp::Cancel(cancellable.swap_remove(position)));
+            } else {
+                next_maker += 1;
+                let id = Id::from_u64(next_maker);
+                cancellable.push(id);
+                script.push(SimOp::Add(self.maker_order(id, timestamp, &mut rng)));
+            }
+        }
+        script
+    }
+
+    /// Run the workload against a fresh level at [`Self::price`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::run_on`].
+    pub fn run(&self) -> Result<SimReport, PriceLevelError> {
+        self.run_on(&PriceLevel::new(self.price))
+    }
+
+    /// Replay the workload's script against `level`, timing the replay, and
+    /// check the final-state invariants.
+    ///
+    /// Script generation is not timed. A maker the level rejects is counted
+    /// in [`SimReport::rejected`] and the run goes on.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if `level` is not empty
+    /// when the run starts, if a match or cancel reports a quantity that
+    /// overflows, or if a final-state invariant does not hold.
+    pub fn run_on(&self, level: &PriceLevel<()>) -> Result<SimReport, PriceLevelError> {
+        if level.order_count() != 0 {
+            return Err(PriceLevelError::InvalidOperation {
+                message: "simulation needs an empty price level".to_string(),
+            });
+        }
+        let script = self.generate();
+        let trade_ids = SequentialIdGenerator::new(self.seed);
+        let mut report = SimReport {
+            seed: self.seed,
+            operations: script.len(),
+            ..SimReport::default()
+        };
+
+        let started = Instant::now();
+        for op in &script {
+            match op {
+                SimOp::Add(order) => match level.add_order(*order) {
+                    Ok(_) => {
+                        report.orders_added += 1;
+                        report.quantity_added =
+                            checked_add(report.quantity_added, resting_quantity(order)?)?;
+                    }
+                    Err(_) => report.rejected += 1,
+                },
+                SimOp::Match {
+                    taker_id,
+                    quantity,
+                    timestamp,
+                } => {
+                    let result = level.match_order(
+                        *quantity,
+                        *taker_id,
+                        TimeInForce::Ioc,
+                        TakerKind::Standard,
+                        *timestamp,
+                        &trade_ids,
+                    );
+                    report.matches += 1;
+                    report.trades += result.trades().len();
+                    report.orders_filled += result.filled_order_ids().len();
+                    report.executed_quantity = checked_add(
+                        report.executed_quantity,
+                        result.executed_quantity()?.as_u64(),
+                    )?;
+                }
+                SimOp::Cancel(order_id) => {
+                    match level.update_order(OrderUpdate::Cancel {
+                        order_id: *order_id,
+                    })? {
+                        UpdateOutcome::Cancelled(order, _) => {
+                            report.cancels += 1;
+                            report.quantity_cancelled =
+                                checked_add(report.quantity_cancelled, resting_quantity(&order)?)?;
+                        }
+                        _ => report.cancels_missed += 1,
+                    }
+                }
+            }
+        }
+        report.elapsed = started.elapsed();
+
+        report.visible_quantity = level.visible_quantity();
+        report.hidden_quantity = level.hidden_quantity();
+        report.order_count = level.order_count();
+        report.check()?;
+        #[cfg(feature = "testing")]
+        level.check_invariants()?;
+        Ok(report)
+    }
+
+    fn maker_order(&self, id: Id, timestamp: TimestampMs, rng: &mut SimRng) -> OrderType<()> {
+        let (price, side, user_id) = (Price::new(self.price), Side::Sell, Hash32::zero());
+        let time_in_force = TimeInForce::Gtc;
+        let visible = Quantity::new(self.maker_sizes.sample(rng));
+        if rng.below(100) < u64::from(self.iceberg_percent) {
+            OrderType::IcebergOrder {
+                id,
+                price,
+                visible_quantity: visible,
+                hidden_quantity: Quantity::new(self.maker_sizes.sample(rng)),
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: (),
+            }
+        } else {
+            OrderType::Standard {
+                id,
+                price,
+                quantity: visible,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: (),
+            }
+        }
+    }
+}
+
+/// Outcome of a [`Workload`] run.
+///
+/// Everything but [`Self::elapsed`] is fixed by the workload, so two runs of
+/// the same workload differ only in their timing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimReport {
+    /// Seed of the workload.
+    pub seed: u64,
+    /// Operations replayed, the initial makers included.
+    pub operations: usize,
+    /// Makers the level admitted.
+    pub orders_added: usize,
+    /// Makers the level rejected.
+    pub rejected: usize,
+    /// Takers matched.
+    pub matches: usize,
+    /// Trades the takers produced.
+    pub trades: usize,
+    /// Makers the takers filled completely.
+    pub orders_filled: usize,
+    /// Cancels that removed an order.
+    pub cancels: usize,
+    /// Cancels whose order had already been filled.
+    pub cancels_missed: usize,
+    /// Visible plus hidden quantity admitted.
+    pub quantity_added: u64,
+    /// Quantity the takers executed.
+    pub executed_quantity: u64,
+    /// Quantity the cancels removed.
+    pub quantity_cancelled: u64,
+    /// Visible quantity resting at the end.
+    pub visible_quantity: u64,
+    /// Hidden quantity resting at the end.
+    pub hidden_quantity: u64,
+    /// Orders resting at the end.
+    pub order_count: usize,
+    /// Wall-clock time of the replay.
+    pub elapsed: Duration,
+}
+
+impl SimReport {
+    /// Operations replayed per second, or `0.0` for a run too short to time.
+    #[must_use]
+    pub fn throughput(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.operations as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Check the report's quantities and order count balance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] naming the first
+    /// invariant that does not hold.
+    pub fn check(&self) -> Result<(), PriceLevelError> {
+        fn violated(message: String) -> PriceLevelError {
+            PriceLevelError::InvalidOperation {
+                message: format!("simulation invariant violated: {message}"),
+            }
+        }
+
+        let resting = u128::from(self.visible_quantity) + u128::from(self.hidden_quantity);
+        let accounted =
+            u128::from(self.executed_quantity) + u128::from(self.quantity_cancelled) + resting;
+        if accounted != u128::from(self.quantity_added) {
+            return Err(violated(format!(
+                "added {} != executed {} + cancelled {} + resting {resting}",
+                self.quantity_added, self.executed_quantity, self.quantity_cancelled
+            )));
+        }
+        let removed = self.orders_filled + self.cancels;
+        if self.orders_added.checked_sub(removed) != Some(self.order_count) {
+            return Err(violated(format!(
+                "order count {} != added {} - filled {} - cancelled {}",
+                self.order_count, self.orders_added, self.orders_filled, self.cancels
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn checked_add(total: u64, quantity: u64) -> Result<u64, PriceLevelError> {
+    total
+        .checked_add(quantity)
+        .ok_or_else(|| PriceLevelError::InvalidOperation {
+            message: "simulation quantity overflows u64".to_string(),
+        })
+}
+
+fn resting_quantity(order: &OrderType<()>) -> Result<u64, PriceLevelError> {
+    checked_add(
+        order.visible_quantity().as_u64(),
+        order.hidden_quantity().as_u64(),
+    )
+}
+
+/// SplitMix64, the generator of
+/// [`RandomizedReplenish`](crate::RandomizedReplenish), owned by one script so
+/// it needs no atomics. The [`stress`](crate::stress) threads own one each.
+#[derive(Debug)]
+pub(crate) struct SimRng {
+    state: u64,
+}
+
+impl SimRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, bound)`; `bound` must be non-zero.
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(bound)) >> 64) as u64
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,201 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::PriceLevel;
+    use crate::sim::{OperationMix, SimOp, SimReport, SizeDistribution, Workload};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::time::Duration;
+
+    fn small(seed: u64) -> Workload {
+        Workload::new(seed)
+            .with_initial_orders(50)
+            .with_operations(500)
+    }
+
+    fn untimed(report: SimReport) -> SimReport {
+        SimReport {
+            elapsed: Duration::ZERO,
+            ..report
+        }
+    }
+
+    #[test]
+    fn test_same_seed_generates_same_script() {
+        assert_eq!(small(7).generate(), small(7).generate());
+        assert_ne!(small(7).generate(), small(8).generate());
+    }
+
+    #[test]
+    fn test_same_seed_reports_same_outcome() {
+        let first = small(7).run().unwrap();
+        let second = small(7).run().unwrap();
+        assert_eq!(untimed(first), untimed(second));
+    }
+
+    #[test]
+    fn test_script_starts_with_initial_makers() {
+        let script = small(1).generate();
+        assert_eq!(script.len(), 550);
+        assert!(script[..50].iter().all(|op| matches!(op, SimOp::Add(_))));
+    }
+
+    #[test]
+    fn test_cancels_name_each_earlier_order_once() {
+        let script = Workload::new(3)
+            .with_initial_orders(0)
+            .with_operations(2_000)
+            .with_mix(OperationMix::new(1, 0, 1))
+            .generate();
+        let mut added = std::collections::HashSet::new();
+        let mut cancelled = std::collections::HashSet::new();
+        for op in &script {
+            match op {
+                SimOp::Add(order) => assert!(added.insert(order.id())),
+                SimOp::Cancel(id) => {
+                    assert!(added.contains(id));
+                    assert!(cancelled.insert(*id));
+                }
+                SimOp::Match { .. } => panic!("mix has no takers"),
+            }
+        }
+        assert!(!cancelled.is_empty());
+    }
+
+    #[test]
+    fn test_maker_only_mix_rests_everything() {
+        let report = Workload::new(5)
+            .with_initial_orders(10)
+            .with_operations(90)
+            .with_mix(OperationMix::new(1, 0, 0))
+            .with_maker_sizes(SizeDistribution::Fixed(4))
+            .with_iceberg_percent(0)
+            .run()
+            .unwrap();
+        assert_eq!(report.orders_added, 100);
+        assert_eq!(report.order_count, 100);
+        assert_eq!(report.quantity_added, 400);
+        assert_eq!(report.visible_quantity, 400);
+        assert_eq!(report.executed_quantity, 0);
+    }
+
+    #[test]
+    fn test_mixed_run_balances() {
+        let report = Workload::new(11)
+            .with_operations(5_000)
+            .with_mix(OperationMix::new(4, 4, 2))
+            .with_taker_sizes(SizeDistribution::Geometric { mean: 60 })
+            .run()
+            .unwrap();
+        assert!(report.trades > 0);
+        assert!(report.cancels > 0);
+        assert_eq!(
+            u128::from(report.quantity_added),
+            u128::from(report.executed_quantity)
+                + u128::from(report.quantity_cancelled)
+                + u128::from(report.visible_quantity)
+                + u128::from(report.hidden_quantity)
+        );
+        assert_eq!(report.rejected, 0);
+        assert!(report.check().is_ok());
+    }
+
+    #[test]
+    fn test_run_on_reports_the_level_state() {
+        let level = PriceLevel::new(10_000);
+        let report = small(9).run_on(&level).unwrap();
+        assert_eq!(report.order_count, level.order_count());
+        assert_eq!(report.visible_quantity, level.visible_quantity());
+        assert_eq!(report.hidden_quantity, level.hidden_quantity());
+        assert_eq!(level.stats().quantity_executed(), report.executed_quantity);
+    }
+
+    #[test]
+    fn test_run_on_rejects_non_empty_level() {
+        let level = PriceLevel::new(10_000);
+        level
+            .add_order(OrderType::Standard {
+                id: Id::from_u64(1),
+                price: Price::new(10_000),
+                quantity: Quantity::new(1),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+        assert!(matches!(
+            small(1).run_on(&level),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_size_distributions_stay_in_range() {
+        let script = Workload::new(13)
+            .with_initial_orders(0)
+            .with_operations(1_000)
+            .with_mix(OperationMix::new(1, 1, 0))
+            .with_maker_sizes(SizeDistribution::Uniform { min: 5, max: 9 })
+            .with_taker_sizes(SizeDistribution::Geometric { mean: 3 })
+            .with_iceberg_percent(0)
+            .generate();
+        for op in &script {
+            match op {
+                SimOp::Add(order) => {
+                    assert!((5..=9).contains(&order.visible_quantity().as_u64()));
+                }
+                SimOp::Match { quantity, .. } => assert!(*quantity >= 1),
+                SimOp::Cancel(_) => panic!("mix has no cancels"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_report_check_catches_imbalance() {
+        let report = SimReport {
+            orders_added: 2,
+            quantity_added: 10,
+            executed_quantity: 4,
+            visible_quantity: 5,
+            order_count: 2,
+            ..SimReport::default()
+        };
+        assert!(report.check().is_err());
+        assert!(
+            SimReport {
+                visible_quantity: 6,
+                ..report
+            }
+            .check()
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_throughput_of_untimed_report_is_zero() {
+        let report = SimReport {
+            operations: 10,
+            ..SimReport::default()
+        };
+        assert_eq!(report.throughput(), 0.0);
+        let timed = SimReport {
+            elapsed: Duration::from_millis(500),
+            ..report
+        };
+        assert!((timed.throughput() - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero weight")]
+    fn test_empty_mix_panics() {
+        let _ = OperationMix::new(0, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "1 <= min <= max")]
+    fn test_zero_size_panics() {
+        let _ = Workload::new(1).with_maker_sizes(SizeDistribution::Uniform { min: 0, max: 3 });
+    }
+}
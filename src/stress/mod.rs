@@ -0,0 +1,552 @@
+//! Concurrent stress testing of a price level.
+//!
+//! The crate documentation reports a multi-threaded high-frequency trading
+//! simulation; this module packages it for reuse. A [`StressConfig`] names
+//! how many maker, taker and canceller threads hammer one level, and for how
+//! many operations or how long. [`run`] starts them all from a common
+//! barrier, joins them, and returns a [`StressReport`] with the operations
+//! per second and the level's final state.
+//!
+//! Once every thread has joined, the run checks the level the way a
+//! [`Workload`](crate::sim::Workload) run does: every quantity admitted is
+//! either executed, cancelled or still resting, the order count matches the
+//! admissions less the fills and cancels, and with the `testing` feature
+//! `PriceLevel::check_invariants` holds. A violation is an error, so a
+//! downstream crate can validate its integration under contention with a
+//! single call:
+//!
+//! ```rust
+//! use pricelevel::stress::{self, StressConfig};
+//!
+//! let config = StressConfig::new(7)
+//!     .with_threads(2, 2, 1)
+//!     .with_initial_orders(100)
+//!     .with_operations_per_thread(500);
+//!
+//! let report = stress::run(&config).unwrap();
+//! assert_eq!(report.operations, 2_500);
+//! assert!(report.throughput() > 0.0);
+//! ```
+
+use crate::errors::PriceLevelError;
+use crate::execution::TakerKind;
+use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce, UpdateOutcome};
+use crate::price_level::PriceLevel;
+use crate::sim::{SimRng, SizeDistribution};
+use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+use std::sync::Barrier;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod tests;
+
+/// Timestamp stamped on every order and match of a run.
+const STRESS_TIMESTAMP: u64 = 1_700_000_000_000;
+
+/// Maker ids are `(slot << MAKER_ID_SHIFT) + n`: slot `0` holds the initial
+/// orders and slot `i` the orders of maker thread `i - 1`.
+const MAKER_ID_SHIFT: u32 = 40;
+
+/// Taker ids are drawn from this offset up, clear of the maker ids.
+const TAKER_ID_OFFSET: u64 = 1 << 62;
+
+/// A concurrent stress run against one price level.
+///
+/// Maker threads rest sell orders, standard or iceberg, at the configured
+/// price; taker threads sweep it immediate-or-cancel; canceller threads
+/// cancel orders the makers have already sent, picked at random, so they
+/// race the takers for the same orders. Each thread draws from its own
+/// generator derived from the seed, but the interleaving is up to the
+/// scheduler, so two runs of the same configuration need not agree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StressConfig {
+    seed: u64,
+    price: u128,
+    makers: usize,
+    takers: usize,
+    cancellers: usize,
+    initial_orders: usize,
+    operations_per_thread: usize,
+    duration: Option<Duration>,
+    maker_sizes: SizeDistribution,
+    taker_sizes: SizeDistribution,
+    iceberg_percent: u8,
+}
+
+impl StressConfig {
+    /// A run seeded with `seed`: ten threads of each role at price 10 000,
+    /// each sending 10 000 operations after 1 000 initial orders, makers of
+    /// 1 to 100 with one in five an iceberg, and takers of 1 to 150.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            price: 10_000,
+            makers: 10,
+            takers: 10,
+            cancellers: 10,
+            initial_orders: 1_000,
+            operations_per_thread: 10_000,
+            duration: None,
+            maker_sizes: SizeDistribution::Uniform { min: 1, max: 100 },
+            taker_sizes: SizeDistribution::Uniform { min: 1, max: 150 },
+            iceberg_percent: 20,
+        }
+    }
+
+    /// Sets the price of the level the run drives.
+    #[must_use]
+    pub fn with_price(mut self, price: u128) -> Self {
+        self.price = price;
+        self
+    }
+
+    /// Sets how many maker, taker and canceller threads run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if every count is zero.
+    #[must_use]
+    pub fn with_threads(mut self, makers: usize, takers: usize, cancellers: usize) -> Self {
+        assert!(
+            makers + takers + cancellers > 0,
+            "stress run needs at least one thread"
+        );
+        self.makers = makers;
+        self.takers = takers;
+        self.cancellers = cancellers;
+        self
+    }
+
+    /// Sets how many makers rest before the threads start.
+    #[must_use]
+    pub fn with_initial_orders(mut self, initial_orders: usize) -> Self {
+        self.initial_orders = initial_orders;
+        self
+    }
+
+    /// Sets how many operations each thread sends at most.
+    #[must_use]
+    pub fn with_operations_per_thread(mut self, operations: usize) -> Self {
+        self.operations_per_thread = operations;
+        self
+    }
+
+    /// Stops each thread after `duration`, even if it has operations left.
+    /// Pair it with [`usize::MAX`] operations per thread for a run bounded
+    /// by time alone.
+    #[must_use]
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Sets the distribution of maker quantities. An iceberg draws its
+    /// visible and hidden quantities from it separately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distribution can draw zero.
+    #[must_use]
+    pub fn with_maker_sizes(mut self, sizes: SizeDistribution) -> Self {
+        sizes.validate();
+        self.maker_sizes = sizes;
+        self
+    }
+
+    /// Sets the distribution of taker quantities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distribution can draw zero.
+    #[must_use]
+    pub fn with_taker_sizes(mut self, sizes: SizeDistribution) -> Self {
+        sizes.validate();
+        self.taker_sizes = sizes;
+        self
+    }
+
+    /// Sets the percentage of makers that are icebergs rather than standard
+    /// orders.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percent` is above 100.
+    #[must_use]
+    pub fn with_iceberg_percent(mut self, percent: u8) -> Self {
+        assert!(percent <= 100, "iceberg percent must be at most 100");
+        self.iceberg_percent = percent;
+        self
+    }
+
+    /// Get the seed.
+    #[must_use]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Get the price of the level the run drives.
+    #[must_use]
+    pub fn price(&self) -> u128 {
+        self.price
+    }
+
+    /// Get the number of threads the run starts.
+    #[must_use]
+    pub fn threads(&self) -> usize {
+        self.makers + self.takers + self.cancellers
+    }
+
+    fn maker_order(&self, id: Id, rng: &mut SimRng) -> OrderType<()> {
+        let (price, side, user_id) = (Price::new(self.price), Side::Sell, Hash32::zero());
+        let (timestamp, time_in_force) = (TimestampMs::new(STRESS_TIMESTAMP), TimeInForce::Gtc);
+        let visible = Quantity::new(self.maker_sizes.sample(rng));
+        if rng.below(100) < u64::from(self.iceberg_percent) {
+            OrderType::IcebergOrder {
+                id,
+                price,
+                visible_quantity: visible,
+                hidden_quantity: Quantity::new(self.maker_sizes.sample(rng)),
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: (),
+            }
+        } else {
+            OrderType::Standard {
+                id,
+                price,
+                quantity: visible,
+                side,
+                user_id,
+                timestamp,
+                time_in_force,
+                extra_fields: (),
+            }
+        }
+    }
+}
+
+/// Outcome of a stress run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StressReport {
+    /// Threads the run started.
+    pub threads: usize,
+    /// Operations the threads sent; the initial orders are not included.
+    pub operations: usize,
+    /// Makers the level admitted, the initial orders included.
+    pub orders_added: usize,
+    /// Makers and cancels the level refused with an error.
+    pub rejected: usize,
+    /// Takers matched.
+    pub matches: usize,
+    /// Trades the takers produced.
+    pub trades: usize,
+    /// Makers the takers filled completely.
+    pub orders_filled: usize,
+    /// Cancels that removed an order.
+    pub cancels: usize,
+    /// Cancels whose order was no longer resting.
+    pub cancels_missed: usize,
+    /// Visible plus hidden quantity admitted.
+    pub quantity_added: u64,
+    /// Quantity the takers executed.
+    pub executed_quantity: u64,
+    /// Quantity the cancels removed.
+    pub quantity_cancelled: u64,
+    /// Visible quantity resting at the end.
+    pub visible_quantity: u64,
+    /// Hidden quantity resting at the end.
+    pub hidden_quantity: u64,
+    /// Orders resting at the end.
+    pub order_count: usize,
+    /// Wall-clock time from releasing the threads until the last joined.
+    pub elapsed: Duration,
+}
+
+impl StressReport {
+    /// Operations sent per second across all threads, or `0.0` for a run
+    /// too short to time.
+    #[must_use]
+    pub fn throughput(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.operations as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// Check the report's quantities and order count balance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] naming the first
+    /// invariant that does not hold.
+    pub fn check(&self) -> Result<(), PriceLevelError> {
+        fn violated(message: String) -> PriceLevelError {
+            PriceLevelError::InvalidOperation {
+                message: format!("stress invariant violated: {message}"),
+            }
+        }
+
+        let resting = u128::from(self.visible_quantity) + u128::from(self.hidden_quantity);
+        let accounted =
+            u128::from(self.executed_quantity) + u128::from(self.quantity_cancelled) + resting;
+        if accounted != u128::from(self.quantity_added) {
+            return Err(violated(format!(
+                "added {} != executed {} + cancelled {} + resting {resting}",
+                self.quantity_added, self.executed_quantity, self.quantity_cancelled
+            )));
+        }
+        let removed = self.orders_filled + self.cancels;
+        if self.orders_added.checked_sub(removed) != Some(self.order_count) {
+            return Err(violated(format!(
+                "order count {} != added {} - filled {} - cancelled {}",
+                self.order_count, self.orders_added, self.orders_filled, self.cancels
+            )));
+        }
+        Ok(())
+    }
+
+    fn absorb(&mut self, tally: StressReport) -> Result<(), PriceLevelError> {
+        self.operations += tally.operations;
+        self.orders_added += tally.orders_added;
+        self.rejected += tally.rejected;
+        self.matches += tally.matches;
+        self.trades += tally.trades;
+        self.orders_filled += tally.orders_filled;
+        self.cancels += tally.cancels;
+        self.cancels_missed += tally.cancels_missed;
+        self.quantity_added = checked_add(self.quantity_added, tally.quantity_added)?;
+        self.executed_quantity = checked_add(self.executed_quantity, tally.executed_quantity)?;
+        self.quantity_cancelled = checked_add(self.quantity_cancelled, tally.quantity_cancelled)?;
+        Ok(())
+    }
+}
+
+/// Run `config` against a fresh level at [`StressConfig::price`].
+///
+/// # Errors
+///
+/// See [`run_on`].
+pub fn run(config: &StressConfig) -> Result<StressReport, PriceLevelError> {
+    run_on(&PriceLevel::new(config.price), config)
+}
+
+/// Run `config` against `level`, seeding it with the initial orders, and
+/// check the final-state invariants once every thread has joined.
+///
+/// Seeding is not timed. A maker or cancel the level refuses, say by a
+/// [`LevelLimits`](crate::LevelLimits) or a
+/// [`RateLimiter`](crate::RateLimiter) registered with it, is counted in
+/// [`StressReport::rejected`] and the thread goes on.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::InvalidOperation`] if `level` is not empty
+/// when the run starts, if a match or cancel reports a quantity that
+/// overflows, or if a final-state invariant does not hold.
+///
+/// # Panics
+///
+/// Propagates the panic of any thread of the run.
+pub fn run_on(
+    level: &PriceLevel<()>,
+    config: &StressConfig,
+) -> Result<StressReport, PriceLevelError> {
+    if level.order_count() != 0 {
+        return Err(PriceLevelError::InvalidOperation {
+            message: "stress run needs an empty price level".to_string(),
+        });
+    }
+    let mut report = StressReport {
+        threads: config.threads(),
+        ..StressReport::default()
+    };
+
+    // Orders each maker slot has sent so far, published for the cancellers.
+    let sent: Vec<AtomicU64> = (0..=config.makers).map(|_| AtomicU64::new(0)).collect();
+    let mut rng = SimRng::new(config.seed);
+    for n in 0..config.initial_orders as u64 {
+        let order = config.maker_order(maker_id(0, n), &mut rng);
+        report.absorb(add(level, order)?)?;
+        sent[0].store(n + 1, Ordering::Release);
+    }
+    // Seeding is not an operation of the run.
+    report.operations = 0;
+
+    let trade_ids = SequentialIdGenerator::new(config.seed);
+    let barrier = Barrier::new(report.threads + 1);
+    let (elapsed, tallies) = thread::scope(|scope| {
+        let mut handles = Vec::with_capacity(report.threads);
+        for thread in 0..report.threads {
+            let (barrier, sent, trade_ids) = (&barrier, &sent, &trade_ids);
+            handles.push(scope.spawn(move || {
+                let mut rng = SimRng::new(
+                    config
+                        .seed
+                        .wrapping_add((thread as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15)),
+                );
+                barrier.wait();
+                let deadline = config.duration.map(|duration| Instant::now() + duration);
+                let role = Role::of(thread, config);
+                let mut tally = StressReport::default();
+                for n in 0..config.operations_per_thread {
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        break;
+                    }
+                    let step = match role {
+                        Role::Maker(slot) => {
+                            let order = config.maker_order(maker_id(slot, n as u64), &mut rng);
+                            let step = add(level, order);
+                            sent[slot].store(n as u64 + 1, Ordering::Release);
+                            step
+                        }
+                        Role::Taker(taker) => {
+                            let taker_id = Id::from_u64(
+                                TAKER_ID_OFFSET + ((taker as u64) << MAKER_ID_SHIFT) + n as u64,
+                            );
+                            let quantity = config.taker_sizes.sample(&mut rng);
+                            take(level, trade_ids, taker_id, quantity)
+                        }
+                        Role::Canceller => {
+                            let slot = rng.below(sent.len() as u64) as usize;
+                            match sent[slot].load(Ordering::Acquire) {
+                                0 => Ok(StressReport {
+                                    operations: 1,
+                                    cancels_missed: 1,
+                                    ..StressReport::default()
+                                }),
+                                sent => cancel(level, maker_id(slot, rng.below(sent))),
+                            }
+                        }
+                    };
+                    tally.absorb(step?)?;
+                }
+                Ok::<_, PriceLevelError>(tally)
+            }));
+        }
+        let started = Instant::now();
+        barrier.wait();
+        let tallies: Vec<_> = handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+            })
+            .collect();
+        (started.elapsed(), tallies)
+    });
+    report.elapsed = elapsed;
+    for tally in tallies {
+        report.absorb(tally?)?;
+    }
+
+    report.visible_quantity = level.visible_quantity();
+    report.hidden_quantity = level.hidden_quantity();
+    report.order_count = level.order_count();
+    report.check()?;
+    #[cfg(feature = "testing")]
+    level.check_invariants()?;
+    Ok(report)
+}
+
+/// What one thread of a run does.
+#[derive(Debug, Clone, Copy)]
+enum Role {
+    /// Rest orders under the given maker slot.
+    Maker(usize),
+    /// Match takers, numbered among the taker threads.
+    Taker(usize),
+    /// Cancel orders the makers sent.
+    Canceller,
+}
+
+impl Role {
+    fn of(thread: usize, config: &StressConfig) -> Self {
+        if thread < config.makers {
+            Self::Maker(thread + 1)
+        } else if thread < config.makers + config.takers {
+            Self::Taker(thread - config.makers)
+        } else {
+            Self::Canceller
+        }
+    }
+}
+
+fn maker_id(slot: usize, n: u64) -> Id {
+    Id::from_u64(((slot as u64) << MAKER_ID_SHIFT) + n)
+}
+
+fn add(level: &PriceLevel<()>, order: OrderType<()>) -> Result<StressReport, PriceLevelError> {
+    let mut tally = StressReport {
+        operations: 1,
+        ..StressReport::default()
+    };
+    match level.add_order(order) {
+        Ok(_) => {
+            tally.orders_added = 1;
+            tally.quantity_added = checked_add(
+                order.visible_quantity().as_u64(),
+                order.hidden_quantity().as_u64(),
+            )?;
+        }
+        Err(_) => tally.rejected = 1,
+    }
+    Ok(tally)
+}
+
+fn take(
+    level: &PriceLevel<()>,
+    trade_ids: &SequentialIdGenerator,
+    taker_id: Id,
+    quantity: u64,
+) -> Result<StressReport, PriceLevelError> {
+    let result = level.match_order(
+        quantity,
+        taker_id,
+        TimeInForce::Ioc,
+        TakerKind::Standard,
+        TimestampMs::new(STRESS_TIMESTAMP),
+        trade_ids,
+    );
+    Ok(StressReport {
+        operations: 1,
+        matches: 1,
+        trades: result.trades().len(),
+        orders_filled: result.filled_order_ids().len(),
+        executed_quantity: result.executed_quantity()?.as_u64(),
+        ..StressReport::default()
+    })
+}
+
+fn cancel(level: &PriceLevel<()>, order_id: Id) -> Result<StressReport, PriceLevelError> {
+    let mut tally = StressReport {
+        operations: 1,
+        ..StressReport::default()
+    };
+    match level.update_order(OrderUpdate::Cancel { order_id }) {
+        Ok(UpdateOutcome::Cancelled(order, _)) => {
+            tally.cancels = 1;
+            tally.quantity_cancelled = checked_add(
+                order.visible_quantity().as_u64(),
+                order.hidden_quantity().as_u64(),
+            )?;
+        }
+        Ok(_) => tally.cancels_missed = 1,
+        Err(_) => tally.rejected = 1,
+    }
+    Ok(tally)
+}
+
+fn checked_add(total: u64, quantity: u64) -> Result<u64, PriceLevelError> {
+    total
+        .checked_add(quantity)
+        .ok_or_else(|| PriceLevelError::InvalidOperation {
+            message: "stress quantity overflows u64".to_string(),
+        })
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::{LevelLimits, PriceLevel, PriceLevelConfig};
+    use crate::sim::SizeDistribution;
+    use crate::stress::{self, StressConfig, StressReport};
+    use crate::utils::{Price, Quantity, TimestampMs};
+    use std::time::Duration;
+
+    fn small(seed: u64) -> StressConfig {
+        StressConfig::new(seed)
+            .with_threads(2, 2, 2)
+            .with_initial_orders(200)
+            .with_operations_per_thread(1_000)
+    }
+
+    #[test]
+    fn test_mixed_run_balances() {
+        let report = stress::run(&small(3)).unwrap();
+        assert_eq!(report.threads, 6);
+        assert_eq!(report.operations, 6_000);
+        assert_eq!(report.matches, 2_000);
+        assert_eq!(report.cancels + report.cancels_missed, 2_000);
+        assert_eq!(report.orders_added, 2_200);
+        assert_eq!(report.rejected, 0);
+        assert!(report.trades > 0);
+        assert!(report.check().is_ok());
+    }
+
+    #[test]
+    fn test_maker_only_run_rests_everything() {
+        let report = stress::run(
+            &StressConfig::new(5)
+                .with_threads(4, 0, 0)
+                .with_initial_orders(10)
+                .with_operations_per_thread(100)
+                .with_maker_sizes(SizeDistribution::Fixed(3))
+                .with_iceberg_percent(0),
+        )
+        .unwrap();
+        assert_eq!(report.orders_added, 410);
+        assert_eq!(report.order_count, 410);
+        assert_eq!(report.quantity_added, 1_230);
+        assert_eq!(report.visible_quantity, 1_230);
+    }
+
+    #[test]
+    fn test_run_on_reports_the_level_state() {
+        let level = PriceLevel::new(10_000);
+        let report = stress::run_on(&level, &small(9)).unwrap();
+        assert_eq!(report.order_count, level.order_count());
+        assert_eq!(report.visible_quantity, level.visible_quantity());
+        assert_eq!(report.hidden_quantity, level.hidden_quantity());
+        assert_eq!(level.stats().quantity_executed(), report.executed_quantity);
+    }
+
+    #[test]
+    fn test_run_on_counts_refused_makers() {
+        let limits = LevelLimits::new().with_max_orders(50);
+        let level = PriceLevel::with_config(10_000, PriceLevelConfig::new().with_limits(limits));
+        let report = stress::run_on(
+            &level,
+            &StressConfig::new(1)
+                .with_threads(3, 0, 1)
+                .with_initial_orders(0)
+                .with_operations_per_thread(100),
+        )
+        .unwrap();
+        assert!(report.rejected > 0);
+        assert!(report.order_count <= 50);
+    }
+
+    #[test]
+    fn test_run_on_rejects_non_empty_level() {
+        let level = PriceLevel::new(10_000);
+        level
+            .add_order(OrderType::Standard {
+                id: Id::from_u64(1),
+                price: Price::new(10_000),
+                quantity: Quantity::new(1),
+                side: Side::Sell,
+                user_id: Hash32::zero(),
+                timestamp: TimestampMs::new(1),
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+            })
+            .unwrap();
+        assert!(matches!(
+            stress::run_on(&level, &small(1)),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_duration_stops_the_threads() {
+        let report = stress::run(
+            &small(2)
+                .with_operations_per_thread(usize::MAX)
+                .with_duration(Duration::from_millis(50)),
+        )
+        .unwrap();
+        assert!(report.operations > 0);
+        assert!(report.elapsed >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_report_check_catches_imbalance() {
+        let report = StressReport {
+            orders_added: 2,
+            quantity_added: 10,
+            executed_quantity: 4,
+            visible_quantity: 5,
+            order_count: 2,
+            ..StressReport::default()
+        };
+        assert!(report.check().is_err());
+        assert!(
+            StressReport {
+                visible_quantity: 6,
+                ..report.clone()
+            }
+            .check()
+            .is_ok()
+        );
+        assert!(
+            StressReport {
+                visible_quantity: 6,
+                order_count: 1,
+                ..report
+            }
+            .check()
+            .is_err()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one thread")]
+    fn test_no_threads_panics() {
+        let _ = StressConfig::new(1).with_threads(0, 0, 0);
+    }
+}
@@ -0,0 +1,224 @@
+//! Stress-test support for price levels (feature `testing`).
+//!
+//! [`PriceLevel::check_invariants`] verifies a level's bookkeeping against
+//! its queue. This module drives a level from several threads at once and
+//! checks those invariants once every thread has joined:
+//!
+//! - [`LevelOp`] — one operation a stress script applies to a level.
+//! - [`run_concurrently`] — runs one script per thread from a common start
+//!   and checks the level afterwards.
+//! - [`op_strategy`] / [`scripts_strategy`] — `proptest` strategies over
+//!   operations and per-thread scripts, for property tests in downstream
+//!   crates.
+//!
+//! ```rust
+//! use pricelevel::PriceLevel;
+//! use pricelevel::testing::{run_concurrently, scripts_strategy};
+//! use proptest::strategy::{Strategy, ValueTree};
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::deterministic();
+//! let scripts = scripts_strategy(10_000, 4, 32)
+//!     .new_tree(&mut runner)
+//!     .unwrap()
+//!     .current();
+//! let level = PriceLevel::new(10_000);
+//! run_concurrently(&level, &scripts).unwrap();
+//! ```
+
+use crate::errors::PriceLevelError;
+use crate::execution::TakerKind;
+use crate::orders::{Hash32, Id, OrderType, OrderUpdate, Side, TimeInForce};
+use crate::price_level::PriceLevel;
+use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+use proptest::prelude::*;
+use std::sync::Barrier;
+
+mod tests;
+
+/// Timestamp stamped on every order and match a script makes.
+const SCRIPT_TIMESTAMP: u64 = 1_700_000_000_000;
+
+/// One operation of a stress script.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelOp<T = ()> {
+    /// Admit an order.
+    Add(OrderType<T>),
+    /// Match an immediate-or-cancel taker of `quantity` against the level.
+    Match {
+        /// The taker's id.
+        taker_id: Id,
+        /// The taker's quantity.
+        quantity: u64,
+    },
+    /// Resize a resting order.
+    Resize {
+        /// The order to resize.
+        order_id: Id,
+        /// Its new visible quantity.
+        quantity: u64,
+    },
+    /// Cancel a resting order.
+    Cancel(Id),
+}
+
+impl<T: Clone> LevelOp<T> {
+    /// Apply the operation to `level`. Trades are numbered by `trade_ids`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the underlying call: an admission or update the
+    /// level rejects. Under concurrency such rejections are expected (a
+    /// duplicate id, an order another thread already removed), so
+    /// [`run_concurrently`] ignores them.
+    pub fn apply(
+        &self,
+        level: &PriceLevel<T>,
+        trade_ids: &SequentialIdGenerator,
+    ) -> Result<(), PriceLevelError> {
+        match self {
+            Self::Add(order) => level.add_order(order.clone()).map(|_| ()),
+            Self::Match { taker_id, quantity } => {
+                level.match_order(
+                    *quantity,
+                    *taker_id,
+                    TimeInForce::Ioc,
+                    TakerKind::Standard,
+                    TimestampMs::new(SCRIPT_TIMESTAMP),
+                    trade_ids,
+                );
+                Ok(())
+            }
+            Self::Resize { order_id, quantity } => level
+                .update_order(OrderUpdate::UpdateQuantity {
+                    order_id: *order_id,
+                    new_quantity: Quantity::new(*quantity),
+                })
+                .map(|_| ()),
+            Self::Cancel(order_id) => level
+                .update_order(OrderUpdate::Cancel {
+                    order_id: *order_id,
+                })
+                .map(|_| ()),
+        }
+    }
+}
+
+/// Run each script on its own thread against `level`, starting them together,
+/// then check [`PriceLevel::check_invariants`] once every thread has joined.
+///
+/// Operations the level rejects are skipped: racing scripts routinely name
+/// orders another thread has already filled or cancelled.
+///
+/// # Errors
+///
+/// Returns the first invariant violation found after the run.
+///
+/// # Panics
+///
+/// Panics if a script thread panics.
+pub fn run_concurrently<T>(
+    level: &PriceLevel<T>,
+    scripts: &[Vec<LevelOp<T>>],
+) -> Result<(), PriceLevelError>
+where
+    T: Clone + Send + Sync,
+{
+    let start = Barrier::new(scripts.len());
+    let trade_ids = SequentialIdGenerator::new(0);
+    std::thread::scope(|scope| {
+        for script in scripts {
+            let (start, trade_ids) = (&start, &trade_ids);
+            scope.spawn(move || {
+                start.wait();
+                for op in script {
+                    let _ = op.apply(level, trade_ids);
+                }
+            });
+        }
+    });
+    level.check_invariants()
+}
+
+/// Strategy over one operation against a level at `price`: standard,
+/// iceberg and reserve sell orders, takers, resizes and cancels.
+///
+/// Order ids are drawn from a small pool so resizes and cancels often name a
+/// resting order and racing admissions sometimes collide.
+pub fn op_strategy(price: u128) -> impl Strategy<Value = LevelOp> {
+    let id = || (1u64..=32).prop_map(Id::from_u64);
+    let add =
+        (id(), 1u64..=100, 0u64..=100, 0u8..3).prop_map(move |(id, visible, hidden, kind)| {
+            LevelOp::Add(resting_order(id, price, visible, hidden, kind))
+        });
+    prop_oneof![
+        4 => add,
+        3 => (1_000u64..=1_032, 1u64..=250).prop_map(|(taker, quantity)| LevelOp::Match {
+            taker_id: Id::from_u64(taker),
+            quantity,
+        }),
+        1 => (id(), 1u64..=100).prop_map(|(order_id, quantity)| LevelOp::Resize {
+            order_id,
+            quantity,
+        }),
+        2 => id().prop_map(LevelOp::Cancel),
+    ]
+}
+
+/// Strategy over `threads` scripts of up to `len` operations each against a
+/// level at `price`.
+pub fn scripts_strategy(
+    price: u128,
+    threads: usize,
+    len: usize,
+) -> impl Strategy<Value = Vec<Vec<LevelOp>>> {
+    proptest::collection::vec(
+        proptest::collection::vec(op_strategy(price), 0..=len),
+        threads,
+    )
+}
+
+/// A resting sell order: standard for `kind` 0 or no hidden quantity,
+/// otherwise iceberg (1) or auto-replenishing reserve (2).
+fn resting_order(id: Id, price: u128, visible: u64, hidden: u64, kind: u8) -> OrderType<()> {
+    let (price, side, user_id) = (Price::new(price), Side::Sell, Hash32::zero());
+    let timestamp = TimestampMs::new(SCRIPT_TIMESTAMP);
+    let time_in_force = TimeInForce::Gtc;
+    match (kind, hidden) {
+        (0, _) | (_, 0) => OrderType::Standard {
+            id,
+            price,
+            quantity: Quantity::new(visible),
+            side,
+            user_id,
+            timestamp,
+            time_in_force,
+            extra_fields: (),
+        },
+        (1, _) => OrderType::IcebergOrder {
+            id,
+            price,
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side,
+            user_id,
+            timestamp,
+            time_in_force,
+            extra_fields: (),
+        },
+        _ => OrderType::ReserveOrder {
+            id,
+            price,
+            visible_quantity: Quantity::new(visible),
+            hidden_quantity: Quantity::new(hidden),
+            side,
+            user_id,
+            timestamp,
+            time_in_force,
+            replenish_threshold: Quantity::new(0),
+            replenish_amount: None,
+            auto_replenish: true,
+            extra_fields: (),
+        },
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use crate::orders::{Hash32, Id, OrderType, Side, TimeInForce};
+    use crate::price_level::PriceLevel;
+    use crate::testing::{LevelOp, run_concurrently, scripts_strategy};
+    use crate::utils::{Price, Quantity, SequentialIdGenerator, TimestampMs};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_apply_reports_rejections() {
+        let level = PriceLevel::new(10_000);
+        let trade_ids = SequentialIdGenerator::new(0);
+        let add = LevelOp::Add(OrderType::Standard {
+            id: Id::from_u64(1),
+            price: Price::new(10_000),
+            quantity: Quantity::new(10),
+            side: Side::Sell,
+            user_id: Hash32::zero(),
+            timestamp: TimestampMs::new(1),
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+        });
+
+        assert!(add.apply(&level, &trade_ids).is_ok());
+        assert!(add.apply(&level, &trade_ids).is_err(), "duplicate id");
+        assert!(
+            LevelOp::Cancel(Id::from_u64(99))
+                .apply(&level, &trade_ids)
+                .is_ok(),
+            "an unknown cancel is not an error"
+        );
+        LevelOp::Match {
+            taker_id: Id::from_u64(100),
+            quantity: 4,
+        }
+        .apply(&level, &trade_ids)
+        .unwrap();
+        assert_eq!(level.visible_quantity(), 6);
+        assert!(level.check_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_run_concurrently_with_no_scripts_checks_the_level() {
+        let level = PriceLevel::<()>::new(10_000);
+        assert!(run_concurrently(&level, &[]).is_ok());
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig { cases: 64, ..ProptestConfig::default() })]
+
+        /// Racing admissions, takers, resizes and cancels leave the level's
+        /// bookkeeping consistent with its queue.
+        #[test]
+        fn prop_concurrent_scripts_keep_invariants(scripts in scripts_strategy(10_000, 4, 48)) {
+            let level = PriceLevel::new(10_000);
+            let checked = run_concurrently(&level, &scripts);
+            prop_assert!(checked.is_ok(), "{:?}", checked);
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use crate::utils::TimestampMs;
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+
+/// A source of millisecond timestamps.
+///
+/// Reads that the engine makes on its own behalf — the
+/// [statistics'](crate::PriceLevel::stats) time since the last execution,
+/// windowed rates and reset stamp, and the clock stamp of a trade (see
+/// [`PriceLevel::set_clock_stamping`](crate::PriceLevel::set_clock_stamping))
+/// — go through the level's clock, set with
+/// [`PriceLevelConfig::with_clock`](crate::PriceLevelConfig::with_clock).
+/// Timestamps the caller passes in, such as the taker timestamp of
+/// [`PriceLevel::match_order`](crate::PriceLevel::match_order), are used as
+/// given.
+///
+/// A clock is read from matching threads, so it must not block or call back
+/// into the level.
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Returns the current time.
+    fn now(&self) -> TimestampMs;
+}
+
+/// The wall clock, in milliseconds since the Unix epoch, never running
+/// backwards.
+///
+/// A system clock stepped back (by NTP, say) would make elapsed-time metrics
+/// negative; this clock instead holds at the latest reading it returned until
+/// the wall clock passes it again. Reads `0` if the system time is before the
+/// Unix epoch.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct SystemClock {
+    latest: AtomicU64,
+}
+
+#[cfg(feature = "std")]
+impl SystemClock {
+    /// Creates a wall clock.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> TimestampMs {
+        let wall = TimestampMs::now().as_u64();
+        let previous = self.latest.fetch_max(wall, Ordering::Relaxed);
+        TimestampMs::new(previous.max(wall))
+    }
+}
+
+/// A clock that only moves when told to, for tests and simulations.
+///
+/// Shared behind an `Arc`, one handle drives the time a level observes:
+///
+/// ```
+/// use pricelevel::{Clock, ManualClock, TimestampMs};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::new(TimestampMs::new(1_000));
+/// clock.advance(Duration::from_millis(250));
+/// assert_eq!(clock.now(), TimestampMs::new(1_250));
+/// ```
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: AtomicU64,
+}
+
+impl ManualClock {
+    /// Creates a clock reading `start`.
+    #[must_use]
+    pub fn new(start: TimestampMs) -> Self {
+        Self {
+            now: AtomicU64::new(start.as_u64()),
+        }
+    }
+
+    /// Sets the clock to `now`. Moving it backwards is allowed, to test how
+    /// readers cope with a clock step.
+    pub fn set(&self, now: TimestampMs) {
+        self.now.store(now.as_u64(), Ordering::Relaxed);
+    }
+
+    /// Moves the clock forward by `by`, saturating at `u64::MAX` milliseconds.
+    pub fn advance(&self, by: Duration) {
+        let by = u64::try_from(by.as_millis()).unwrap_or(u64::MAX);
+        // `fetch_update` with an always-`Some` closure cannot fail.
+        let _ = self
+            .now
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |now| {
+                Some(now.saturating_add(by))
+            });
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> TimestampMs {
+        TimestampMs::new(self.now.load(Ordering::Relaxed))
+    }
+}
+
+/// A logical clock for deterministic replay: time moves only when the
+/// replay driver [ticks](Self::tick) it, by a fixed step per input event.
+///
+/// Reading never advances it, so two replays of the same input read the
+/// same times however often the engine consults the clock in between. Share
+/// one across the levels of a replay with an [`Arc`](alloc::sync::Arc); see
+/// [`DeterministicReplay`](crate::DeterministicReplay).
+///
+/// ```
+/// use pricelevel::{Clock, LogicalClock, TimestampMs};
+///
+/// let clock = LogicalClock::new(TimestampMs::new(1_000), 1);
+/// assert_eq!(clock.now(), TimestampMs::new(1_000));
+/// assert_eq!(clock.tick(), TimestampMs::new(1_001));
+/// assert_eq!(clock.now(), TimestampMs::new(1_001));
+/// ```
+#[derive(Debug)]
+pub struct LogicalClock {
+    now: AtomicU64,
+    step: u64,
+}
+
+impl LogicalClock {
+    /// Creates a clock reading `start` and ticking forward `step`
+    /// milliseconds at a time.
+    #[must_use]
+    pub fn new(start: TimestampMs, step: u64) -> Self {
+        Self {
+            now: AtomicU64::new(start.as_u64()),
+            step,
+        }
+    }
+
+    /// The milliseconds each [`Self::tick`] moves the clock.
+    #[must_use]
+    pub fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// Moves the clock forward one step, saturating at `u64::MAX`
+    /// milliseconds, and returns the new time.
+    pub fn tick(&self) -> TimestampMs {
+        let step = self.step;
+        // `fetch_update` with an always-`Some` closure cannot fail.
+        let previous = self
+            .now
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |now| {
+                Some(now.saturating_add(step))
+            })
+            .unwrap_or_else(|now| now);
+        TimestampMs::new(previous.saturating_add(step))
+    }
+}
+
+impl Clock for LogicalClock {
+    fn now(&self) -> TimestampMs {
+        TimestampMs::new(self.now.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_clock_tracks_the_wall_clock() {
+        let clock = SystemClock::new();
+        let before = TimestampMs::now();
+        let reading = clock.now();
+        assert!(reading >= before);
+        assert!(clock.now() >= reading);
+    }
+
+    #[test]
+    fn test_system_clock_never_runs_backwards() {
+        let clock = SystemClock::new();
+        let ahead = TimestampMs::now().as_u64() + 60_000;
+        clock.latest.store(ahead, Ordering::Relaxed);
+        assert_eq!(clock.now(), TimestampMs::new(ahead));
+    }
+
+    #[test]
+    fn test_manual_clock_moves_only_when_told() {
+        let clock = ManualClock::new(TimestampMs::new(5));
+        assert_eq!(clock.now(), TimestampMs::new(5));
+
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(clock.now(), TimestampMs::new(2_005));
+
+        clock.set(TimestampMs::new(1));
+        assert_eq!(clock.now(), TimestampMs::new(1));
+
+        clock.set(TimestampMs::new(u64::MAX - 1));
+        clock.advance(Duration::from_millis(10));
+        assert_eq!(clock.now(), TimestampMs::new(u64::MAX));
+    }
+
+    #[test]
+    fn test_logical_clock_moves_only_on_ticks() {
+        let clock = LogicalClock::new(TimestampMs::new(10), 5);
+        assert_eq!(clock.now(), clock.now());
+        assert_eq!(clock.tick(), TimestampMs::new(15));
+        assert_eq!(clock.tick(), TimestampMs::new(20));
+        assert_eq!(clock.now(), TimestampMs::new(20));
+
+        let saturating = LogicalClock::new(TimestampMs::new(u64::MAX - 1), 5);
+        assert_eq!(saturating.tick(), TimestampMs::new(u64::MAX));
+    }
+}
@@ -0,0 +1,185 @@
+use crate::utils::{Id, UuidGenerator};
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Mints the trade ids [`PriceLevel::match_order`](crate::PriceLevel::match_order)
+/// stamps onto each fill.
+///
+/// [`UuidGenerator`] mints name-based UUIDs that carry no order.
+/// [`SequentialIdGenerator`] also numbers every execution, so a consumer can
+/// order fills deterministically and detect gaps (see
+/// [`Trade::execution_sequence`](crate::Trade::execution_sequence)).
+pub trait ExecutionIdGenerator {
+    /// Returns the id of the next execution and, for a sequenced generator,
+    /// its sequence number.
+    fn next_execution(&self) -> (Id, Option<u64>);
+}
+
+// Passing `&Arc<G>` or `&&G` to `match_order` keeps compiling, as it did
+// through deref coercion when the parameter was a concrete `&UuidGenerator`.
+impl<G: ExecutionIdGenerator + ?Sized> ExecutionIdGenerator for Arc<G> {
+    fn next_execution(&self) -> (Id, Option<u64>) {
+        (**self).next_execution()
+    }
+}
+
+impl<G: ExecutionIdGenerator + ?Sized> ExecutionIdGenerator for &G {
+    fn next_execution(&self) -> (Id, Option<u64>) {
+        (**self).next_execution()
+    }
+}
+
+impl ExecutionIdGenerator for UuidGenerator {
+    fn next_execution(&self) -> (Id, Option<u64>) {
+        (Id::from_uuid(self.next()), None)
+    }
+}
+
+/// An [`ExecutionIdGenerator`] that numbers executions 1, 2, 3, … within an
+/// epoch.
+///
+/// The sequence is an atomic counter, so concurrent matches on different
+/// levels sharing one generator still receive distinct, gap-free numbers.
+/// The `epoch` tells generator incarnations apart — typically bumped on each
+/// process restart — so a consumer never confuses a restarted sequence with
+/// a replay. Each trade id is a UUID holding the epoch in its upper 64 bits
+/// and the sequence in its lower 64 bits, so ids sort in execution order.
+///
+/// ## Example
+///
+/// ```
+/// use pricelevel::{ExecutionIdGenerator, SequentialIdGenerator};
+///
+/// let generator = SequentialIdGenerator::new(7);
+/// let (first, first_seq) = generator.next_execution();
+/// let (second, second_seq) = generator.next_execution();
+///
+/// assert_eq!(first_seq, Some(1));
+/// assert_eq!(second_seq, Some(2));
+/// assert!(first.as_bytes() < second.as_bytes());
+/// ```
+// Serde's atomic impls need `std`.
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SequentialIdGenerator {
+    epoch: u64,
+    sequence: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    /// Creates a generator for `epoch` whose first execution is numbered 1.
+    #[must_use]
+    pub fn new(epoch: u64) -> Self {
+        Self::resume(epoch, 0)
+    }
+
+    /// Creates a generator for `epoch` that continues after `last_sequence`,
+    /// e.g. when recovering from a persisted trade log.
+    #[must_use]
+    pub fn resume(epoch: u64, last_sequence: u64) -> Self {
+        Self {
+            epoch,
+            sequence: AtomicU64::new(last_sequence),
+        }
+    }
+
+    /// Returns the generator's epoch.
+    #[must_use]
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Returns the sequence number of the last minted execution, or 0 if
+    /// none has been minted since `new` (or `resume`'s `last_sequence`).
+    #[must_use]
+    pub fn last_sequence(&self) -> u64 {
+        self.sequence.load(Ordering::Acquire)
+    }
+
+    /// Returns the trade id this generator mints for `sequence`.
+    #[must_use]
+    pub fn id_for(&self, sequence: u64) -> Id {
+        Id::from_uuid(Uuid::from_u64_pair(self.epoch, sequence))
+    }
+}
+
+impl ExecutionIdGenerator for SequentialIdGenerator {
+    fn next_execution(&self) -> (Id, Option<u64>) {
+        // Only uniqueness and order ride on this counter; no other memory is
+        // published through it.
+        let sequence = self.sequence.fetch_add(1, Ordering::AcqRel).wrapping_add(1);
+        (self.id_for(sequence), Some(sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::thread;
+
+    #[test]
+    fn test_sequential_generator_numbers_from_one() {
+        let generator = SequentialIdGenerator::new(3);
+
+        assert_eq!(generator.last_sequence(), 0);
+        let (id, sequence) = generator.next_execution();
+        assert_eq!(sequence, Some(1));
+        assert_eq!(id, generator.id_for(1));
+        assert_eq!(generator.last_sequence(), 1);
+        assert_eq!(generator.epoch(), 3);
+    }
+
+    #[test]
+    fn test_sequential_ids_sort_by_epoch_then_sequence() {
+        let old = SequentialIdGenerator::resume(1, 500);
+        let new = SequentialIdGenerator::new(2);
+
+        let (a, _) = old.next_execution();
+        let (b, _) = old.next_execution();
+        let (c, _) = new.next_execution();
+
+        assert!(a.as_bytes() < b.as_bytes());
+        assert!(b.as_bytes() < c.as_bytes());
+    }
+
+    #[test]
+    fn test_resume_continues_after_last_sequence() {
+        let generator = SequentialIdGenerator::resume(1, 41);
+
+        assert_eq!(generator.next_execution().1, Some(42));
+    }
+
+    #[test]
+    fn test_uuid_generator_is_unsequenced() {
+        let generator = UuidGenerator::new(Uuid::nil());
+
+        let (id, sequence) = generator.next_execution();
+        assert!(id.is_uuid());
+        assert_eq!(sequence, None);
+    }
+
+    #[test]
+    fn test_concurrent_sequences_are_gap_free() {
+        let generator = Arc::new(SequentialIdGenerator::new(0));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || {
+                    (0..250)
+                        .map(|_| generator.next_execution().1.unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let seen: HashSet<u64> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+        assert_eq!(seen, (1..=1000).collect::<HashSet<u64>>());
+    }
+}
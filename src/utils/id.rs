@@ -1,7 +1,9 @@
 use crate::errors::PriceLevelError;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::str::FromStr;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::fmt;
-use std::str::FromStr;
 use ulid::Ulid;
 use uuid::Uuid;
 
@@ -73,6 +75,7 @@ impl<'de> Deserialize<'de> for Id {
     }
 }
 
+#[cfg(feature = "std")]
 impl Default for Id {
     fn default() -> Self {
         Self::new()
@@ -81,18 +84,21 @@ impl Default for Id {
 
 impl Id {
     /// Create a new random id (defaults to ULID for better sortability).
+    #[cfg(feature = "std")]
     #[must_use]
     pub fn new() -> Self {
         Self::Ulid(Ulid::new())
     }
 
     /// Create a new UUID-based id.
+    #[cfg(feature = "std")]
     #[must_use]
     pub fn new_uuid() -> Self {
         Self::Uuid(Uuid::new_v4())
     }
 
     /// Create a new ULID-based id.
+    #[cfg(feature = "std")]
     #[must_use]
     pub fn new_ulid() -> Self {
         Self::Ulid(Ulid::new())
@@ -4,12 +4,29 @@
    Date: 28/3/25
 ******************************************************************************/
 
+mod clock;
+mod execution_id;
 mod id;
+#[cfg(feature = "std")]
 mod logger;
+mod order_id;
+mod scaled;
 mod uuid;
 mod value;
 
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+pub use clock::{Clock, LogicalClock, ManualClock};
+pub use execution_id::{ExecutionIdGenerator, SequentialIdGenerator};
 pub use id::Id;
+#[cfg(feature = "std")]
 pub use logger::setup_logger;
+#[cfg(feature = "std")]
+pub use order_id::{
+    MAX_SNOWFLAKE_NODE, SNOWFLAKE_NODE_BITS, SNOWFLAKE_SEQUENCE_BITS, SnowflakeGenerator,
+    UuidV7Generator,
+};
+pub use order_id::{OrderIdGenerator, SequentialOrderIdGenerator};
+pub use scaled::ScaledPrice;
 pub use uuid::UuidGenerator;
 pub use value::{Price, Quantity, TimestampMs};
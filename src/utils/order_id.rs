@@ -0,0 +1,389 @@
+#[cfg(feature = "std")]
+use crate::errors::PriceLevelError;
+use crate::utils::{Id, UuidGenerator};
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(feature = "std")]
+use uuid::Uuid;
+
+/// Number of bits a [`SnowflakeGenerator`] id spends on the node id.
+#[cfg(feature = "std")]
+pub const SNOWFLAKE_NODE_BITS: u32 = 10;
+
+/// Number of bits a [`SnowflakeGenerator`] id spends on the per-millisecond
+/// sequence.
+#[cfg(feature = "std")]
+pub const SNOWFLAKE_SEQUENCE_BITS: u32 = 12;
+
+/// Largest node id a [`SnowflakeGenerator`] accepts.
+#[cfg(feature = "std")]
+pub const MAX_SNOWFLAKE_NODE: u16 = (1 << SNOWFLAKE_NODE_BITS) - 1;
+
+/// Mints order ids for callers embedding the crate.
+///
+/// Every generator returns an [`Id`] in one of the formats the crate already
+/// parses and serializes, so a minted id round-trips through the string,
+/// serde and snapshot paths unchanged. [`SnowflakeGenerator`] and
+/// [`SequentialOrderIdGenerator`] mint [`Id::Sequential`] ids;
+/// [`UuidV7Generator`] mints time-ordered [`Id::Uuid`] ids.
+pub trait OrderIdGenerator {
+    /// Returns a fresh order id, distinct from every id this generator has
+    /// minted before.
+    fn next_id(&self) -> Id;
+}
+
+impl<G: OrderIdGenerator + ?Sized> OrderIdGenerator for Arc<G> {
+    fn next_id(&self) -> Id {
+        (**self).next_id()
+    }
+}
+
+impl<G: OrderIdGenerator + ?Sized> OrderIdGenerator for &G {
+    fn next_id(&self) -> Id {
+        (**self).next_id()
+    }
+}
+
+impl OrderIdGenerator for UuidGenerator {
+    fn next_id(&self) -> Id {
+        Id::from_uuid(self.next())
+    }
+}
+
+/// An [`OrderIdGenerator`] numbering orders 1, 2, 3, … as [`Id::Sequential`].
+///
+/// Suited to a single process owning a market, the way CEX venues number
+/// orders per book. The counter is atomic, so threads sharing one generator
+/// still receive distinct, gap-free ids.
+///
+/// ## Example
+///
+/// ```
+/// use pricelevel::{Id, OrderIdGenerator, SequentialOrderIdGenerator};
+///
+/// let generator = SequentialOrderIdGenerator::new();
+/// assert_eq!(generator.next_id(), Id::sequential(1));
+/// assert_eq!(generator.next_id(), Id::sequential(2));
+/// ```
+#[derive(Debug, Default)]
+pub struct SequentialOrderIdGenerator {
+    last: AtomicU64,
+}
+
+impl SequentialOrderIdGenerator {
+    /// Creates a generator whose first id is 1.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::resume(0)
+    }
+
+    /// Creates a generator that continues after `last_id`, e.g. when
+    /// recovering from a persisted order log.
+    #[must_use]
+    pub fn resume(last_id: u64) -> Self {
+        Self {
+            last: AtomicU64::new(last_id),
+        }
+    }
+
+    /// Returns the last id minted, or 0 if none has been minted since `new`
+    /// (or `resume`'s `last_id`).
+    #[must_use]
+    pub fn last_id(&self) -> u64 {
+        self.last.load(Ordering::Acquire)
+    }
+}
+
+impl OrderIdGenerator for SequentialOrderIdGenerator {
+    fn next_id(&self) -> Id {
+        Id::sequential(self.last.fetch_add(1, Ordering::AcqRel).wrapping_add(1))
+    }
+}
+
+/// An [`OrderIdGenerator`] minting Snowflake-style [`Id::Sequential`] ids.
+///
+/// Each id packs, from the most significant bit down, the milliseconds since
+/// the generator's epoch (41 bits, about 69 years), the node id
+/// ([`SNOWFLAKE_NODE_BITS`]) and a per-millisecond sequence
+/// ([`SNOWFLAKE_SEQUENCE_BITS`]). Nodes with distinct ids never collide, and
+/// one node's ids strictly increase, so they sort in minting order.
+///
+/// A node that mints more than 4096 ids in one millisecond borrows the next
+/// millisecond rather than waiting for the clock; the id's time bits then run
+/// briefly ahead of the wall clock, and catch up once the burst ends.
+///
+/// ## Example
+///
+/// ```
+/// use pricelevel::{OrderIdGenerator, SnowflakeGenerator};
+///
+/// let generator = SnowflakeGenerator::new(7).unwrap();
+/// let first = generator.next_id();
+/// let second = generator.next_id();
+///
+/// assert_eq!(SnowflakeGenerator::node_of(first), Some(7));
+/// assert!(first.as_bytes() < second.as_bytes());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct SnowflakeGenerator {
+    node: u16,
+    epoch_ms: u64,
+    /// The time and sequence bits of the last id minted,
+    /// `elapsed_ms << SNOWFLAKE_SEQUENCE_BITS | sequence`.
+    last: AtomicU64,
+}
+
+#[cfg(feature = "std")]
+impl SnowflakeGenerator {
+    /// Epoch [`Self::new`] counts from: 2025-01-01T00:00:00Z, in Unix
+    /// milliseconds.
+    pub const DEFAULT_EPOCH_MS: u64 = 1_735_689_600_000;
+
+    /// Creates a generator for `node` counting from
+    /// [`Self::DEFAULT_EPOCH_MS`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidFieldValue`] if `node` exceeds
+    /// [`MAX_SNOWFLAKE_NODE`].
+    pub fn new(node: u16) -> Result<Self, PriceLevelError> {
+        Self::with_epoch(node, Self::DEFAULT_EPOCH_MS)
+    }
+
+    /// Creates a generator for `node` counting from `epoch_ms` Unix
+    /// milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidFieldValue`] if `node` exceeds
+    /// [`MAX_SNOWFLAKE_NODE`].
+    pub fn with_epoch(node: u16, epoch_ms: u64) -> Result<Self, PriceLevelError> {
+        if node > MAX_SNOWFLAKE_NODE {
+            return Err(PriceLevelError::InvalidFieldValue {
+                field: "node".to_string(),
+                value: node.to_string(),
+            });
+        }
+        Ok(Self {
+            node,
+            epoch_ms,
+            last: AtomicU64::new(0),
+        })
+    }
+
+    /// Returns the generator's node id.
+    #[must_use]
+    pub fn node(&self) -> u16 {
+        self.node
+    }
+
+    /// Returns the epoch the generator counts from, in Unix milliseconds.
+    #[must_use]
+    pub fn epoch_ms(&self) -> u64 {
+        self.epoch_ms
+    }
+
+    /// Returns the node id packed into a Snowflake `id`, or `None` if `id` is
+    /// not [`Id::Sequential`].
+    #[must_use]
+    pub fn node_of(id: Id) -> Option<u16> {
+        match id {
+            Id::Sequential(value) => {
+                Some(((value >> SNOWFLAKE_SEQUENCE_BITS) & u64::from(MAX_SNOWFLAKE_NODE)) as u16)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the id this generator mints for `elapsed_ms` since its epoch
+    /// and `sequence` within that millisecond.
+    #[must_use]
+    pub fn id_for(&self, elapsed_ms: u64, sequence: u16) -> Id {
+        let sequence_mask = (1 << SNOWFLAKE_SEQUENCE_BITS) - 1;
+        Id::sequential(
+            (elapsed_ms << (SNOWFLAKE_NODE_BITS + SNOWFLAKE_SEQUENCE_BITS))
+                | (u64::from(self.node) << SNOWFLAKE_SEQUENCE_BITS)
+                | (u64::from(sequence) & sequence_mask),
+        )
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        now_ms.saturating_sub(self.epoch_ms)
+    }
+}
+
+#[cfg(feature = "std")]
+impl OrderIdGenerator for SnowflakeGenerator {
+    fn next_id(&self) -> Id {
+        let floor = self.elapsed_ms() << SNOWFLAKE_SEQUENCE_BITS;
+        // The next slot is the later of the current millisecond's first
+        // sequence and the slot after the last id; a full millisecond rolls
+        // its sequence over into the next one.
+        let previous = self
+            .last
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |last| {
+                Some(floor.max(last.wrapping_add(1)))
+            })
+            .unwrap_or_else(|last| last);
+        let slot = floor.max(previous.wrapping_add(1));
+        self.id_for(
+            slot >> SNOWFLAKE_SEQUENCE_BITS,
+            (slot & ((1 << SNOWFLAKE_SEQUENCE_BITS) - 1)) as u16,
+        )
+    }
+}
+
+/// An [`OrderIdGenerator`] minting UUIDv7 [`Id::Uuid`] ids.
+///
+/// A UUIDv7 leads with its Unix-millisecond timestamp followed by a counter
+/// and random bits, so ids from independent processes do not collide and ids
+/// from one process sort in minting order.
+///
+/// ## Example
+///
+/// ```
+/// use pricelevel::{OrderIdGenerator, UuidV7Generator};
+///
+/// let generator = UuidV7Generator::new();
+/// let first = generator.next_id();
+/// let second = generator.next_id();
+///
+/// assert!(first.is_uuid());
+/// assert!(first.as_bytes() < second.as_bytes());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UuidV7Generator;
+
+#[cfg(feature = "std")]
+impl UuidV7Generator {
+    /// Creates a UUIDv7 generator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "std")]
+impl OrderIdGenerator for UuidV7Generator {
+    fn next_id(&self) -> Id {
+        Id::from_uuid(Uuid::now_v7())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::thread;
+
+    fn mint_concurrently<G: OrderIdGenerator + Send + Sync + 'static>(generator: G) -> HashSet<Id> {
+        let generator = Arc::new(generator);
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = Arc::clone(&generator);
+                thread::spawn(move || (0..2500).map(|_| generator.next_id()).collect::<Vec<_>>())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_sequential_numbers_from_one_and_resumes() {
+        let generator = SequentialOrderIdGenerator::new();
+        assert_eq!(generator.next_id(), Id::sequential(1));
+        assert_eq!(generator.last_id(), 1);
+
+        let resumed = SequentialOrderIdGenerator::resume(41);
+        assert_eq!(resumed.next_id(), Id::sequential(42));
+    }
+
+    #[test]
+    fn test_sequential_concurrent_ids_are_gap_free() {
+        let seen = mint_concurrently(SequentialOrderIdGenerator::new());
+        assert_eq!(
+            seen,
+            (1..=10_000).map(Id::sequential).collect::<HashSet<_>>()
+        );
+    }
+
+    #[test]
+    fn test_snowflake_rejects_an_out_of_range_node() {
+        assert!(SnowflakeGenerator::new(MAX_SNOWFLAKE_NODE).is_ok());
+        assert!(matches!(
+            SnowflakeGenerator::new(MAX_SNOWFLAKE_NODE + 1),
+            Err(PriceLevelError::InvalidFieldValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_snowflake_layout() {
+        let generator = SnowflakeGenerator::with_epoch(5, 0).unwrap();
+        assert_eq!(
+            generator.id_for(3, 2),
+            Id::sequential((3 << 22) | (5 << 12) | 2)
+        );
+        assert_eq!(SnowflakeGenerator::node_of(generator.id_for(3, 2)), Some(5));
+        assert_eq!(SnowflakeGenerator::node_of(Id::new_uuid()), None);
+    }
+
+    #[test]
+    fn test_snowflake_ids_increase_and_carry_the_node() {
+        let generator = SnowflakeGenerator::new(9).unwrap();
+        let ids: Vec<Id> = (0..10_000).map(|_| generator.next_id()).collect();
+
+        assert!(
+            ids.windows(2)
+                .all(|pair| pair[0].as_bytes() < pair[1].as_bytes())
+        );
+        assert!(
+            ids.iter()
+                .all(|id| SnowflakeGenerator::node_of(*id) == Some(9))
+        );
+    }
+
+    #[test]
+    fn test_snowflake_nodes_never_collide() {
+        let a = SnowflakeGenerator::new(1).unwrap();
+        let b = SnowflakeGenerator::new(2).unwrap();
+        let ids: HashSet<Id> = (0..1000).flat_map(|_| [a.next_id(), b.next_id()]).collect();
+        assert_eq!(ids.len(), 2000);
+    }
+
+    #[test]
+    fn test_snowflake_concurrent_ids_are_distinct() {
+        let seen = mint_concurrently(SnowflakeGenerator::new(3).unwrap());
+        assert_eq!(seen.len(), 10_000);
+    }
+
+    #[test]
+    fn test_uuid_v7_ids_are_ordered_and_round_trip() {
+        let generator = UuidV7Generator::new();
+        let first = generator.next_id();
+        let second = generator.next_id();
+
+        match first {
+            Id::Uuid(uuid) => assert_eq!(uuid.get_version(), Some(uuid::Version::SortRand)),
+            other => panic!("expected a UUID, got {other:?}"),
+        }
+        assert!(first.as_bytes() < second.as_bytes());
+        assert_eq!(first.to_string().parse::<Id>().unwrap(), first);
+    }
+
+    #[test]
+    fn test_uuid_v7_concurrent_ids_are_distinct() {
+        let seen = mint_concurrently(UuidV7Generator::new());
+        assert_eq!(seen.len(), 10_000);
+    }
+}
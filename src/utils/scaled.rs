@@ -0,0 +1,322 @@
+use crate::errors::PriceLevelError;
+use crate::utils::Price;
+use alloc::format;
+use alloc::string::ToString;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+/// A decimal price: `mantissa × 10^exponent`.
+///
+/// [`Price`] is a bare integer, so a venue quoting fractional prices has to
+/// pick a fixed number of decimals and scale every price by it. A
+/// `ScaledPrice` carries that scale with the value and converts to and from
+/// the integer [`Price`] at an explicit exponent, failing rather than
+/// silently rounding when the value does not fit.
+///
+/// It displays and parses as a plain decimal (`"123.45"` is mantissa `12345`,
+/// exponent `-2`) and keeps the number of decimals it was written with, so
+/// `"1.50"` round-trips as `"1.50"`. Equality, ordering and hashing compare
+/// values: `1.50` equals `1.5`.
+///
+/// ```rust
+/// use pricelevel::{Price, ScaledPrice};
+///
+/// let quoted: ScaledPrice = "101.25".parse().unwrap();
+/// assert_eq!(quoted.mantissa(), 10_125);
+/// assert_eq!(quoted.exponent(), -2);
+///
+/// // A venue with four decimals stores it as 1_012_500.
+/// let price = quoted.to_price(-4).unwrap();
+/// assert_eq!(price, Price::new(1_012_500));
+/// assert_eq!(ScaledPrice::from_price(price, -4).to_string(), "101.2500");
+/// assert_eq!(ScaledPrice::from_price(price, -4), quoted);
+///
+/// // One decimal cannot hold it.
+/// assert!(quoted.to_price(-1).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ScaledPrice {
+    mantissa: u128,
+    exponent: i8,
+}
+
+impl ScaledPrice {
+    /// Zero, with no decimals.
+    pub const ZERO: Self = Self::new(0, 0);
+
+    /// Creates the price `mantissa × 10^exponent`.
+    #[must_use]
+    pub const fn new(mantissa: u128, exponent: i8) -> Self {
+        Self { mantissa, exponent }
+    }
+
+    /// Creates a decimal price from an integer [`Price`] stored at
+    /// `exponent`: the price's raw value is the mantissa.
+    #[must_use]
+    pub const fn from_price(price: Price, exponent: i8) -> Self {
+        Self::new(price.as_u128(), exponent)
+    }
+
+    /// Returns the integer digits.
+    #[must_use]
+    pub const fn mantissa(self) -> u128 {
+        self.mantissa
+    }
+
+    /// Returns the power of ten the mantissa is scaled by.
+    #[must_use]
+    pub const fn exponent(self) -> i8 {
+        self.exponent
+    }
+
+    /// Converts to an integer [`Price`] stored at `exponent`, as for a venue
+    /// with `-exponent` decimals.
+    ///
+    /// # Errors
+    ///
+    /// As [`Self::rescale`].
+    pub fn to_price(self, exponent: i8) -> Result<Price, PriceLevelError> {
+        self.rescale(exponent)
+            .map(|scaled| Price::new(scaled.mantissa))
+    }
+
+    /// Returns the same value written with `exponent`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if the value has more
+    /// decimals than `exponent` keeps, or if its mantissa at `exponent`
+    /// overflows `u128`.
+    pub fn rescale(self, exponent: i8) -> Result<Self, PriceLevelError> {
+        let diff = (i16::from(self.exponent) - i16::from(exponent)).unsigned_abs();
+        let mantissa = match exponent.cmp(&self.exponent) {
+            Ordering::Equal => self.mantissa,
+            Ordering::Less => pow10(diff)
+                .and_then(|factor| self.mantissa.checked_mul(factor))
+                .ok_or_else(|| PriceLevelError::InvalidOperation {
+                    message: format!("price {self} overflows at exponent {exponent}"),
+                })?,
+            Ordering::Greater => match pow10(diff) {
+                Some(factor) if self.mantissa.is_multiple_of(factor) => self.mantissa / factor,
+                None if self.mantissa == 0 => 0,
+                _ => {
+                    return Err(PriceLevelError::InvalidOperation {
+                        message: format!("price {self} loses precision at exponent {exponent}"),
+                    });
+                }
+            },
+        };
+        Ok(Self::new(mantissa, exponent))
+    }
+
+    /// Returns the same value with trailing zeros dropped from the mantissa,
+    /// so equal values have equal parts. Zero normalizes to exponent `0`.
+    #[must_use]
+    pub fn normalize(self) -> Self {
+        if self.mantissa == 0 {
+            return Self::ZERO;
+        }
+        let mut normal = self;
+        while normal.exponent < i8::MAX && normal.mantissa.is_multiple_of(10) {
+            normal.mantissa /= 10;
+            normal.exponent += 1;
+        }
+        normal
+    }
+
+    /// Converts to `f64` with potential precision loss.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn to_f64_lossy(self) -> f64 {
+        self.mantissa as f64 * 10f64.powi(i32::from(self.exponent))
+    }
+}
+
+/// `10^exponent`, or `None` past `u128`.
+fn pow10(exponent: u16) -> Option<u128> {
+    10u128.checked_pow(u32::from(exponent))
+}
+
+impl PartialEq for ScaledPrice {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScaledPrice {}
+
+impl PartialOrd for ScaledPrice {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScaledPrice {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.mantissa == 0 || other.mantissa == 0 || self.exponent == other.exponent {
+            return self.mantissa.cmp(&other.mantissa);
+        }
+        // Scale the coarser side up to the finer exponent; if that
+        // overflows, it is larger than any mantissa the finer side can hold.
+        let coarser_cmp = |coarse: &Self, fine: &Self| {
+            let diff = (i16::from(coarse.exponent) - i16::from(fine.exponent)).unsigned_abs();
+            pow10(diff)
+                .and_then(|factor| coarse.mantissa.checked_mul(factor))
+                .map_or(Ordering::Greater, |scaled| scaled.cmp(&fine.mantissa))
+        };
+        if self.exponent > other.exponent {
+            coarser_cmp(self, other)
+        } else {
+            coarser_cmp(other, self).reverse()
+        }
+    }
+}
+
+impl Hash for ScaledPrice {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normal = self.normalize();
+        normal.mantissa.hash(state);
+        normal.exponent.hash(state);
+    }
+}
+
+impl fmt::Display for ScaledPrice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.exponent >= 0 {
+            if self.mantissa == 0 {
+                return write!(f, "0");
+            }
+            return write!(
+                f,
+                "{}{}",
+                self.mantissa,
+                "0".repeat(usize::from(self.exponent.unsigned_abs()))
+            );
+        }
+        let decimals = usize::from(self.exponent.unsigned_abs());
+        let digits = format!("{:0>width$}", self.mantissa, width = decimals + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - decimals);
+        write!(f, "{integer}.{fraction}")
+    }
+}
+
+impl FromStr for ScaledPrice {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || PriceLevelError::InvalidFieldValue {
+            field: "price".to_string(),
+            value: s.to_string(),
+        };
+        let (integer, fraction) = match s.split_once('.') {
+            Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+            Some(_) => return Err(invalid()),
+            None => (s, ""),
+        };
+        let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+        if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+            return Err(invalid());
+        }
+        let exponent = i8::try_from(-(fraction.len() as i64)).map_err(|_| invalid())?;
+        let mantissa = format!("{integer}{fraction}")
+            .parse::<u128>()
+            .map_err(|_| invalid())?;
+        Ok(Self::new(mantissa, exponent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScaledPrice;
+    use crate::errors::PriceLevelError;
+    use crate::utils::Price;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    #[test]
+    fn display_parse_roundtrip() {
+        for text in ["0", "7", "1200", "0.05", "1.50", "101.25", "0.000001"] {
+            let price = ScaledPrice::from_str(text).unwrap();
+            assert_eq!(price.to_string(), text);
+        }
+        assert_eq!(ScaledPrice::new(12, 2).to_string(), "1200");
+        assert_eq!(ScaledPrice::new(5, -3).to_string(), "0.005");
+        assert_eq!(ScaledPrice::new(0, -2).to_string(), "0.00");
+    }
+
+    #[test]
+    fn parse_rejects_malformed() {
+        for text in ["", ".5", "1.", "-1", "+1", "1.2.3", "1e3", "abc", " 1"] {
+            assert!(
+                matches!(
+                    ScaledPrice::from_str(text),
+                    Err(PriceLevelError::InvalidFieldValue { .. })
+                ),
+                "accepted {text:?}"
+            );
+        }
+        let too_many_decimals = format!("0.{}", "1".repeat(129));
+        assert!(ScaledPrice::from_str(&too_many_decimals).is_err());
+        let too_large = "9".repeat(40);
+        assert!(ScaledPrice::from_str(&too_large).is_err());
+    }
+
+    #[test]
+    fn value_equality_and_ordering() {
+        let a = ScaledPrice::from_str("1.50").unwrap();
+        let b = ScaledPrice::from_str("1.5").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(HashSet::from([a, b]).len(), 1);
+        assert!(ScaledPrice::from_str("1.49").unwrap() < b);
+        assert!(ScaledPrice::from_str("2").unwrap() > a);
+        assert_eq!(ScaledPrice::new(0, -5), ScaledPrice::ZERO);
+        // Scaling the coarser side up would overflow: it is the larger.
+        assert!(ScaledPrice::new(1, 100) > ScaledPrice::new(u128::MAX, -10));
+        assert!(ScaledPrice::new(u128::MAX, -10) < ScaledPrice::new(1, 100));
+    }
+
+    #[test]
+    fn rescale_and_price_conversion() {
+        let quoted = ScaledPrice::from_str("101.25").unwrap();
+        assert_eq!(quoted.to_price(-2).unwrap(), Price::new(10_125));
+        assert_eq!(quoted.to_price(-4).unwrap(), Price::new(1_012_500));
+        assert!(matches!(
+            quoted.to_price(-1),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert!(ScaledPrice::new(u128::MAX, 0).rescale(-1).is_err());
+        assert_eq!(
+            ScaledPrice::new(1_000, -2).rescale(1).unwrap(),
+            ScaledPrice::new(1, 1)
+        );
+        assert_eq!(ScaledPrice::ZERO.rescale(100).unwrap().mantissa(), 0);
+        let price = Price::new(1_012_500);
+        assert_eq!(ScaledPrice::from_price(price, -4), quoted);
+    }
+
+    #[test]
+    fn normalize_drops_trailing_zeros() {
+        let normal = ScaledPrice::new(150_000, -5).normalize();
+        assert_eq!((normal.mantissa(), normal.exponent()), (15, -1));
+        let normal = ScaledPrice::new(0, -4).normalize();
+        assert_eq!((normal.mantissa(), normal.exponent()), (0, 0));
+    }
+
+    #[test]
+    fn to_f64_lossy() {
+        let value = ScaledPrice::new(10_125, -2).to_f64_lossy();
+        assert!((value - 101.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let price = ScaledPrice::new(10_125, -2);
+        let json = serde_json::to_string(&price).unwrap();
+        let parsed: ScaledPrice = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.exponent(), -2);
+        assert_eq!(parsed, price);
+    }
+}
@@ -1,7 +1,13 @@
 use crate::errors::PriceLevelError;
+#[cfg(feature = "std")]
+use alloc::format;
+use alloc::string::ToString;
+use core::fmt;
+use core::str::FromStr;
+use core::time::Duration;
 use serde::{Deserialize, Serialize};
-use std::fmt;
-use std::str::FromStr;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Domain value type representing a price.
 #[derive(
@@ -41,6 +47,7 @@ impl Price {
     /// `u128`. The range check is explicit because an `f64`-to-`u128` `as` cast
     /// saturates rather than failing, which would silently clamp out-of-range
     /// input to `u128::MAX`.
+    #[cfg(feature = "std")]
     pub fn from_f64(value: f64) -> Result<Self, PriceLevelError> {
         if !value.is_finite() || value < 0.0 {
             return Err(PriceLevelError::InvalidOperation {
@@ -129,6 +136,7 @@ impl Quantity {
     /// `u64`. The range check is explicit because an `f64`-to-`u64` `as` cast
     /// saturates rather than failing, which would silently clamp out-of-range
     /// input to `u64::MAX`.
+    #[cfg(feature = "std")]
     pub fn from_f64(value: f64) -> Result<Self, PriceLevelError> {
         if !value.is_finite() || value < 0.0 {
             return Err(PriceLevelError::InvalidOperation {
@@ -214,6 +222,43 @@ impl TimestampMs {
     pub const fn as_u64(self) -> u64 {
         self.0
     }
+
+    /// Returns the wall-clock time in milliseconds since the Unix epoch, or
+    /// [`Self::ZERO`] if the system time is before the epoch.
+    ///
+    /// Not monotonic; read a [`Clock`](crate::Clock) where that matters.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn now() -> Self {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(Self::ZERO, |elapsed| {
+                Self(u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX))
+            })
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`, or `None` if
+    /// `earlier` is later than `self`.
+    #[must_use]
+    pub fn checked_duration_since(self, earlier: Self) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration::from_millis)
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`, or zero if
+    /// `earlier` is later than `self`.
+    #[must_use]
+    pub fn saturating_duration_since(self, earlier: Self) -> Duration {
+        self.checked_duration_since(earlier).unwrap_or_default()
+    }
+
+    /// Returns `self` moved forward by `duration`, or `None` on overflow.
+    #[must_use]
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        u64::try_from(duration.as_millis())
+            .ok()
+            .and_then(|millis| self.0.checked_add(millis))
+            .map(Self)
+    }
 }
 
 impl fmt::Display for TimestampMs {
@@ -235,10 +280,49 @@ impl FromStr for TimestampMs {
     }
 }
 
+// Lossless conversions to and from the raw integers, for an order book that
+// keys its levels and locations by raw price.
+impl From<u128> for Price {
+    fn from(value: u128) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Price> for u128 {
+    fn from(price: Price) -> Self {
+        price.0
+    }
+}
+
+impl From<u64> for Quantity {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Quantity> for u64 {
+    fn from(quantity: Quantity) -> Self {
+        quantity.0
+    }
+}
+
+impl From<u64> for TimestampMs {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<TimestampMs> for u64 {
+    fn from(timestamp: TimestampMs) -> Self {
+        timestamp.0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Price, Quantity, TimestampMs};
     use std::str::FromStr;
+    use std::time::Duration;
 
     #[test]
     fn price_roundtrip() {
@@ -248,6 +332,16 @@ mod tests {
         assert_eq!(parsed.unwrap_or_default(), value);
     }
 
+    #[test]
+    fn raw_conversions_roundtrip() {
+        assert_eq!(Price::from(1_000_u128), Price::new(1_000));
+        assert_eq!(u128::from(Price::new(1_000)), 1_000);
+        assert_eq!(Quantity::from(42_u64), Quantity::new(42));
+        assert_eq!(u64::from(Quantity::new(42)), 42);
+        assert_eq!(TimestampMs::from(7_u64), TimestampMs::new(7));
+        assert_eq!(u64::from(TimestampMs::new(7)), 7);
+    }
+
     #[test]
     fn quantity_roundtrip() {
         let value = Quantity::new(42);
@@ -264,6 +358,24 @@ mod tests {
         assert_eq!(parsed.unwrap_or_default(), value);
     }
 
+    #[test]
+    fn timestamp_durations() {
+        let earlier = TimestampMs::new(1_000);
+        let later = TimestampMs::new(1_250);
+        assert_eq!(
+            later.checked_duration_since(earlier),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(earlier.checked_duration_since(later), None);
+        assert_eq!(earlier.saturating_duration_since(later), Duration::ZERO);
+        assert_eq!(earlier.checked_add(Duration::from_millis(250)), Some(later));
+        assert_eq!(
+            TimestampMs::new(u64::MAX).checked_add(Duration::from_millis(1)),
+            None
+        );
+        assert!(TimestampMs::now() > TimestampMs::ZERO);
+    }
+
     #[test]
     fn from_f64_rejects_negative() {
         assert!(Price::from_f64(-1.0).is_err());
@@ -0,0 +1,187 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+******************************************************************************/
+
+//! Allocation counts of the matching hot path.
+//!
+//! This is a dedicated integration-test target (`[[test]] name =
+//! "allocations"`) because it installs a counting global allocator, which
+//! must not sit under the benchmarks or the other tests. The allocator counts
+//! per thread, so the tests of this target, run in parallel, only see their
+//! own allocations. Each test warms a level and then asserts how many
+//! allocations one more operation makes.
+
+use pricelevel::{
+    Hash32, Id, MatchingPolicy, OrderType, Price, PriceLevel, Quantity, Side, TakerKind,
+    TimeInForce, TimestampMs, UuidGenerator,
+};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::hint::black_box;
+use uuid::Uuid;
+
+/// System allocator that counts the allocations of the calling thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+fn count_one() {
+    // `try_with`: an allocation during thread teardown goes uncounted.
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_one();
+        // SAFETY: forwarded unchanged to the system allocator.
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: `ptr` was returned by `System.alloc` with this layout.
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_one();
+        // SAFETY: forwarded unchanged to the system allocator.
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Allocations `f` makes on this thread.
+fn count_allocations<R>(f: impl FnOnce() -> R) -> u64 {
+    let before = ALLOCATIONS.with(Cell::get);
+    black_box(f());
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+fn generator() -> UuidGenerator {
+    let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    UuidGenerator::new(namespace)
+}
+
+fn maker(id: u64, quantity: u64, side: Side) -> OrderType<()> {
+    OrderType::Standard {
+        id: Id::from_u64(id),
+        price: Price::new(10000),
+        quantity: Quantity::new(quantity),
+        side,
+        user_id: Hash32::zero(),
+        timestamp: TimestampMs::new(1616823000000 + id),
+        time_in_force: TimeInForce::Gtc,
+        extra_fields: (),
+    }
+}
+
+fn take(price_level: &PriceLevel, quantity: u64, tif: TimeInForce, generator: &UuidGenerator) {
+    let result = price_level.match_order(
+        quantity,
+        Id::from_u64(999_999),
+        tif,
+        TakerKind::Standard,
+        TimestampMs::new(1_716_000_000_000),
+        generator,
+    );
+    #[cfg(feature = "pool")]
+    if let Some(pool) = price_level.order_pool() {
+        pool.recycle(result);
+        return;
+    }
+    black_box(result);
+}
+
+/// Makers of the scratch-buffer level, each filled by one lot per match.
+const SCRATCH_MAKERS: u64 = 64;
+
+fn count_scratch_matches(policy: MatchingPolicy) -> (u64, u64) {
+    let generator = generator();
+    let price_level = PriceLevel::with_matching_policy(10000, policy);
+    for i in 0..SCRATCH_MAKERS {
+        price_level
+            .add_order(maker(i, 1_000_000, Side::Buy))
+            .unwrap();
+    }
+    let match_once = || take(&price_level, SCRATCH_MAKERS, TimeInForce::Fok, &generator);
+    let cold = count_allocations(match_once);
+    let warm = count_allocations(match_once);
+    (cold, warm)
+}
+
+#[test]
+fn test_warm_match_reuses_the_scratch_buffers() {
+    for policy in [MatchingPolicy::ProRata, MatchingPolicy::Fifo] {
+        let (cold, warm) = count_scratch_matches(policy);
+        assert!(
+            warm < cold,
+            "{policy}: a warm match made {warm} allocations, a cold one {cold}"
+        );
+    }
+}
+
+/// Allocations of a taker sweeping `makers` one-lot makers, once the level
+/// has matched before.
+fn count_small_match(makers: u64) -> u64 {
+    let generator = generator();
+    let price_level = PriceLevel::new(10000);
+    for round in 0..2 {
+        for i in 0..makers {
+            price_level
+                .add_order(maker(round * makers + i, 1, Side::Sell))
+                .unwrap();
+        }
+    }
+    take(&price_level, makers, TimeInForce::Ioc, &generator);
+    count_allocations(|| take(&price_level, makers, TimeInForce::Ioc, &generator))
+}
+
+#[test]
+fn test_single_fill_keeps_its_trade_inline() {
+    let counts: Vec<u64> = (1..=3).map(count_small_match).collect();
+    // Each further maker costs the same, except the second, whose trade
+    // spills the list to the heap: the first one stayed inline.
+    let second = counts[1] - counts[0];
+    let third = counts[2] - counts[1];
+    assert!(second > third, "allocations per sweep: {counts:?}");
+}
+
+/// Makers rested by each churn cycle.
+#[cfg(feature = "pool")]
+const CHURN_ORDERS: u64 = 64;
+
+/// Rests `CHURN_ORDERS` makers, partially fills each one, then sweeps the
+/// rest.
+#[cfg(feature = "pool")]
+fn churn(price_level: &PriceLevel, generator: &UuidGenerator) {
+    for i in 0..CHURN_ORDERS {
+        price_level.add_order(maker(i, 10, Side::Sell)).unwrap();
+    }
+    take(price_level, CHURN_ORDERS * 5, TimeInForce::Ioc, generator);
+    take(price_level, CHURN_ORDERS * 5, TimeInForce::Ioc, generator);
+}
+
+#[cfg(feature = "pool")]
+#[test]
+fn test_warm_pool_absorbs_the_churn() {
+    use pricelevel::OrderPool;
+    use std::sync::Arc;
+
+    let generator = generator();
+    let unpooled = PriceLevel::new(10000);
+    let pooled = PriceLevel::with_order_pool(10000, Arc::new(OrderPool::default()));
+    let [unpooled, pooled] = [&unpooled, &pooled].map(|price_level| {
+        churn(price_level, &generator);
+        count_allocations(|| churn(price_level, &generator))
+    });
+    // At least every admitted order is drawn from the warm pool.
+    assert!(
+        pooled + CHURN_ORDERS <= unpooled,
+        "a churn cycle made {pooled} allocations pooled, {unpooled} unpooled"
+    );
+}
@@ -49,7 +49,7 @@ fn build_level(makers: &[Maker]) -> PriceLevel {
 /// Sum of trade quantities in a result, checked for overflow.
 fn traded_quantity(result: &MatchResult) -> Result<u64, TestCaseError> {
     let mut total: u64 = 0;
-    for trade in result.trades().as_vec() {
+    for trade in result.trades().as_slice() {
         total = total
             .checked_add(trade.quantity().as_u64())
             .ok_or_else(|| TestCaseError::fail("traded quantity overflow"))?;
@@ -143,7 +143,7 @@ proptest! {
             &generator,
         );
 
-        for trade in result.trades().as_vec() {
+        for trade in result.trades().as_slice() {
             prop_assert!(
                 trade.quantity().as_u64() > 0,
                 "a trade must never carry zero quantity"
@@ -195,7 +195,7 @@ proptest! {
         // First-touch order: the maker ids in the order each is first seen.
         let mut seen: HashSet<u64> = HashSet::new();
         let mut first_touch: Vec<u64> = Vec::new();
-        for trade in result.trades().as_vec() {
+        for trade in result.trades().as_slice() {
             let id = trade
                 .maker_order_id()
                 .as_u64()
@@ -318,7 +318,7 @@ proptest! {
             &generator,
         );
 
-        for trade in result.trades().as_vec() {
+        for trade in result.trades().as_slice() {
             prop_assert_ne!(
                 trade.maker_order_id(),
                 trade.taker_order_id(),
@@ -552,7 +552,7 @@ proptest! {
         let json = level
             .snapshot_to_json()
             .map_err(|e| TestCaseError::fail(format!("snapshot_to_json: {e}")))?;
-        let restored = PriceLevel::from_snapshot_json(&json)
+        let restored: PriceLevel = PriceLevel::from_snapshot_json(&json)
             .map_err(|e| TestCaseError::fail(format!("from_snapshot_json: {e}")))?;
 
         prop_assert_eq!(restored.price(), level.price());
@@ -576,7 +576,7 @@ proptest! {
         // the JSON payload and assert ChecksumMismatch.
         let tampered = tamper_checksum(&json)
             .ok_or_else(|| TestCaseError::fail("could not locate checksum to tamper"))?;
-        match PriceLevel::from_snapshot_json(&tampered) {
+        match PriceLevel::<()>::from_snapshot_json(&tampered) {
             Err(PriceLevelError::ChecksumMismatch { .. }) => {}
             other => {
                 return Err(TestCaseError::fail(format!(
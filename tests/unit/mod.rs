@@ -53,7 +53,10 @@ fn partial_fill_keeps_price_time_priority_across_calls() {
         &trade_ids,
     );
     assert_eq!(first.trades().len(), 1);
-    assert_eq!(first.trades().as_vec()[0].maker_order_id(), Id::from_u64(1));
+    assert_eq!(
+        first.trades().as_slice()[0].maker_order_id(),
+        Id::from_u64(1)
+    );
 
     // Second aggressor must hit A's remainder (40) first, then B (10).
     let second = level.match_order(
@@ -66,16 +69,16 @@ fn partial_fill_keeps_price_time_priority_across_calls() {
     );
     assert_eq!(second.trades().len(), 2);
     assert_eq!(
-        second.trades().as_vec()[0].maker_order_id(),
+        second.trades().as_slice()[0].maker_order_id(),
         Id::from_u64(1),
         "A's residual must be consumed before the later-arriving B"
     );
-    assert_eq!(second.trades().as_vec()[0].quantity(), Quantity::new(40));
+    assert_eq!(second.trades().as_slice()[0].quantity(), Quantity::new(40));
     assert_eq!(
-        second.trades().as_vec()[1].maker_order_id(),
+        second.trades().as_slice()[1].maker_order_id(),
         Id::from_u64(2)
     );
-    assert_eq!(second.trades().as_vec()[1].quantity(), Quantity::new(10));
+    assert_eq!(second.trades().as_slice()[1].quantity(), Quantity::new(10));
 
     // Conservation: started 200, consumed 110, 90 remains on B.
     assert_eq!(level.visible_quantity(), 90);